@@ -0,0 +1,254 @@
+//! Describing the stage to assistive technologies, such as screen readers.
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, Object as Avm1Object, TObject as _,
+    Value as Avm1Value,
+};
+use crate::avm2::{
+    Activation as Avm2Activation, Object as Avm2Object, TObject as _, Value as Avm2Value,
+};
+use crate::backend::ui::{AccessibilityNode, AccessibilityRole};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use ruffle_render::matrix::Matrix;
+
+/// The `accessibilityProperties` (AVM2) or `_accProps` (AVM1) set on a display object.
+#[derive(Default)]
+struct AccessibilityProperties {
+    name: String,
+    description: String,
+    shortcut: String,
+    silent: bool,
+    force_simple: bool,
+    no_auto_labeling: bool,
+}
+
+/// Describes every visible object on the stage again, in rendering order, if any of them
+/// changed since the last time or `force` is set.
+///
+/// Reading `_accProps` and `accessibilityProperties` can run ActionScript getters, so this
+/// doesn't happen on every frame. Changes to them are only noticed once the movie calls
+/// `Accessibility.updateProperties`, or something else about the objects changes.
+///
+/// Objects that are neither text nor buttons, and that the movie didn't name, are left out;
+/// their children take their place.
+pub fn updated_accessibility_tree(
+    context: &mut UpdateContext<'_>,
+    force: bool,
+) -> Option<Vec<AccessibilityNode>> {
+    let stage = context.stage;
+    if !force && !stage.accessibility_invalidated() {
+        return None;
+    }
+
+    // Anything the getters change is described next time.
+    clear_invalidated(context, stage.into());
+
+    let to_viewport = stage.view_matrix();
+    let mut nodes = vec![];
+    describe_children(context, stage.into(), to_viewport, &mut nodes);
+    Some(nodes)
+}
+
+/// The objects that are described in place of their parent, if it's left out.
+fn children<'gc>(parent: DisplayObject<'gc>) -> Vec<DisplayObject<'gc>> {
+    match parent {
+        DisplayObject::Avm2Button(button) => button
+            .get_state_child(swf::ButtonState::UP)
+            .into_iter()
+            .collect(),
+        _ => parent
+            .as_container()
+            .map(|container| container.iter_render_list().collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn clear_invalidated<'gc>(context: &mut UpdateContext<'gc>, object: DisplayObject<'gc>) {
+    object.clear_accessibility_invalidated(context.gc());
+    for child in children(object) {
+        clear_invalidated(context, child);
+    }
+}
+
+fn describe_children<'gc>(
+    context: &mut UpdateContext<'gc>,
+    parent: DisplayObject<'gc>,
+    to_viewport: Matrix,
+    nodes: &mut Vec<AccessibilityNode>,
+) {
+    for child in children(parent) {
+        describe(context, child, to_viewport, nodes);
+    }
+}
+
+fn describe<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+    to_viewport: Matrix,
+    nodes: &mut Vec<AccessibilityNode>,
+) {
+    if !object.visible() {
+        return;
+    }
+
+    let properties = accessibility_properties(context, object);
+    if properties.silent {
+        return;
+    }
+
+    let role = match object {
+        DisplayObject::Text(_) => AccessibilityRole::Text,
+        DisplayObject::EditText(_) => AccessibilityRole::TextField,
+        DisplayObject::Avm1Button(_) | DisplayObject::Avm2Button(_) => AccessibilityRole::Button,
+        DisplayObject::MovieClip(clip) if clip.is_button_mode(context) => AccessibilityRole::Button,
+        _ if !properties.name.is_empty() => AccessibilityRole::Graphic,
+        _ => {
+            if !properties.force_simple {
+                describe_children(context, object, to_viewport, nodes);
+            }
+            return;
+        }
+    };
+
+    let name = if !properties.name.is_empty() {
+        properties.name
+    } else if role != AccessibilityRole::Button || !properties.no_auto_labeling {
+        label(context, object)
+    } else {
+        String::new()
+    };
+
+    let mut children = vec![];
+    if role == AccessibilityRole::Graphic && !properties.force_simple {
+        describe_children(context, object, to_viewport, &mut children);
+    }
+
+    if name.is_empty() && children.is_empty() {
+        return;
+    }
+
+    nodes.push(AccessibilityNode {
+        role,
+        name,
+        description: properties.description,
+        shortcut: properties.shortcut,
+        bounds: to_viewport * object.world_bounds(),
+        children,
+    });
+}
+
+/// The text shown by an object and its children.
+fn label<'gc>(context: &mut UpdateContext<'gc>, object: DisplayObject<'gc>) -> String {
+    match object {
+        DisplayObject::Text(text) => text.text(context).trim().to_string(),
+        DisplayObject::EditText(text) if text.is_password() => String::new(),
+        DisplayObject::EditText(text) => text.text().to_utf8_lossy().trim().to_string(),
+        DisplayObject::Avm2Button(button) => button
+            .get_state_child(swf::ButtonState::UP)
+            .map(|child| label(context, child))
+            .unwrap_or_default(),
+        _ => {
+            let Some(container) = object.as_container() else {
+                return String::new();
+            };
+            let children: Vec<_> = container.iter_render_list().collect();
+            children
+                .into_iter()
+                .filter(|child| child.visible())
+                .map(|child| label(context, child))
+                .filter(|label| !label.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
+
+fn accessibility_properties<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+) -> AccessibilityProperties {
+    if let Avm1Value::Object(avm1_object) = object.object() {
+        let mut activation = Avm1Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[Accessibility]"),
+            object,
+        );
+        if let Ok(Avm1Value::Object(props)) = avm1_object.get("_accProps", &mut activation) {
+            return AccessibilityProperties {
+                name: avm1_string(&mut activation, props, "name"),
+                description: avm1_string(&mut activation, props, "description"),
+                shortcut: avm1_string(&mut activation, props, "shortcut"),
+                silent: avm1_bool(&mut activation, props, "silent"),
+                force_simple: avm1_bool(&mut activation, props, "forceSimple"),
+                no_auto_labeling: avm1_bool(&mut activation, props, "noAutoLabeling"),
+            };
+        }
+    } else if let Some(avm2_object) = object.object2().as_object() {
+        let mut activation = Avm2Activation::from_nothing(context);
+        if let Ok(props) =
+            avm2_object.get_public_property("accessibilityProperties", &mut activation)
+        {
+            if let Some(props) = props.as_object() {
+                return AccessibilityProperties {
+                    name: avm2_string(&mut activation, props, "name"),
+                    description: avm2_string(&mut activation, props, "description"),
+                    shortcut: avm2_string(&mut activation, props, "shortcut"),
+                    silent: avm2_bool(&mut activation, props, "silent"),
+                    force_simple: avm2_bool(&mut activation, props, "forceSimple"),
+                    no_auto_labeling: avm2_bool(&mut activation, props, "noAutoLabeling"),
+                };
+            }
+        }
+    }
+
+    Default::default()
+}
+
+fn avm1_string<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc>,
+    props: Avm1Object<'gc>,
+    name: &'static str,
+) -> String {
+    match props.get(name, activation) {
+        Ok(Avm1Value::Undefined | Avm1Value::Null) | Err(_) => String::new(),
+        Ok(value) => value
+            .coerce_to_string(activation)
+            .map(|string| string.to_utf8_lossy().into_owned())
+            .unwrap_or_default(),
+    }
+}
+
+fn avm1_bool<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc>,
+    props: Avm1Object<'gc>,
+    name: &'static str,
+) -> bool {
+    props
+        .get(name, activation)
+        .is_ok_and(|value| value.as_bool(activation.swf_version()))
+}
+
+fn avm2_string<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    props: Avm2Object<'gc>,
+    name: &'static str,
+) -> String {
+    match props.get_public_property(name, activation) {
+        Ok(Avm2Value::Undefined | Avm2Value::Null) | Err(_) => String::new(),
+        Ok(value) => value
+            .coerce_to_string(activation)
+            .map(|string| string.to_utf8_lossy().into_owned())
+            .unwrap_or_default(),
+    }
+}
+
+fn avm2_bool<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    props: Avm2Object<'gc>,
+    name: &'static str,
+) -> bool {
+    props
+        .get_public_property(name, activation)
+        .is_ok_and(|value| value.coerce_to_boolean())
+}
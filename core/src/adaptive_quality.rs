@@ -0,0 +1,159 @@
+//! Lowering the rendering quality while the player can't keep up with the movie's frame rate.
+
+use ruffle_render::quality::StageQuality;
+
+/// How much each new frame counts towards the average load.
+const LOAD_SMOOTHING: f64 = 0.1;
+
+/// The average load above which the quality is lowered.
+const OVERLOADED: f64 = 1.0;
+
+/// The average load below which the quality is restored.
+const UNDERLOADED: f64 = 0.5;
+
+/// How many frames to wait after a change before lowering the quality again.
+const FRAMES_BEFORE_LOWERING: u32 = 30;
+
+/// How many frames to wait after a change before restoring the quality.
+/// This is longer than lowering it, so that the quality doesn't keep flipping back and forth.
+const FRAMES_BEFORE_RESTORING: u32 = 180;
+
+/// The most levels below the movie's quality that the player renders at.
+/// That's enough to go from the highest quality down to `Low`.
+const MAX_LEVELS_LOWERED: u8 = 4;
+
+/// Decides which quality to render at, based on how long frames take.
+///
+/// This only affects the renderer: movies still see the quality they set.
+pub struct AdaptiveQuality {
+    enabled: bool,
+
+    /// The average time taken to run and render a frame, as a share of the time available.
+    load: f64,
+
+    /// How many quality levels below the movie's quality the player renders at.
+    levels_lowered: u8,
+
+    /// How many frames were recorded since the quality last changed.
+    frames_since_change: u32,
+
+    /// The quality that was last given to the renderer, if it was chosen here.
+    applied: Option<(StageQuality, u8)>,
+}
+
+impl AdaptiveQuality {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            load: 0.0,
+            levels_lowered: 0,
+            frames_since_change: 0,
+            applied: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled != self.enabled {
+            *self = Self::new(enabled);
+        }
+    }
+
+    /// Records how long a frame took to run and render, out of the time available for it.
+    pub fn record_frame(&mut self, elapsed: f64, frame_time: f64) {
+        if !self.enabled || frame_time <= 0.0 {
+            return;
+        }
+
+        self.load += (elapsed / frame_time - self.load) * LOAD_SMOOTHING;
+        self.frames_since_change = self.frames_since_change.saturating_add(1);
+
+        if self.load > OVERLOADED
+            && self.frames_since_change >= FRAMES_BEFORE_LOWERING
+            && self.levels_lowered < MAX_LEVELS_LOWERED
+        {
+            self.levels_lowered += 1;
+            self.frames_since_change = 0;
+        } else if self.load < UNDERLOADED
+            && self.frames_since_change >= FRAMES_BEFORE_RESTORING
+            && self.levels_lowered > 0
+        {
+            self.levels_lowered -= 1;
+            self.frames_since_change = 0;
+        }
+    }
+
+    /// The quality to render at when the movie asks for `stage_quality`.
+    pub fn quality(&self, stage_quality: StageQuality) -> StageQuality {
+        (0..self.levels_lowered).fold(stage_quality, |quality, _| lower(quality))
+    }
+
+    /// Returns the quality that the renderer should switch to, if it changed since the last call.
+    pub fn quality_change(&mut self, stage_quality: StageQuality) -> Option<StageQuality> {
+        let state = (stage_quality, self.levels_lowered);
+        if self.applied == Some(state) {
+            return None;
+        }
+
+        // Until the quality is first lowered, the renderer already uses the movie's quality.
+        if self.applied.is_none() && self.levels_lowered == 0 {
+            return None;
+        }
+        self.applied = Some(state);
+        Some(self.quality(stage_quality))
+    }
+}
+
+/// The next lower quality, by anti-aliasing.
+fn lower(quality: StageQuality) -> StageQuality {
+    match quality {
+        StageQuality::Low | StageQuality::Medium => StageQuality::Low,
+        StageQuality::High => StageQuality::Medium,
+        StageQuality::Best => StageQuality::High,
+        StageQuality::High8x8 | StageQuality::High8x8Linear => StageQuality::Best,
+        StageQuality::High16x16 | StageQuality::High16x16Linear => StageQuality::High8x8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowers_quality_when_overloaded() {
+        let mut adaptive = AdaptiveQuality::new(true);
+        for _ in 0..100 {
+            adaptive.record_frame(50.0, 25.0);
+        }
+        assert_eq!(adaptive.quality(StageQuality::High), StageQuality::Low);
+        assert_eq!(
+            adaptive.quality_change(StageQuality::High),
+            Some(StageQuality::Low)
+        );
+        assert_eq!(adaptive.quality_change(StageQuality::High), None);
+    }
+
+    #[test]
+    fn restores_quality_with_headroom() {
+        let mut adaptive = AdaptiveQuality::new(true);
+        for _ in 0..40 {
+            adaptive.record_frame(50.0, 25.0);
+        }
+        assert_eq!(
+            adaptive.quality_change(StageQuality::High),
+            Some(StageQuality::Medium)
+        );
+
+        for _ in 0..500 {
+            adaptive.record_frame(1.0, 25.0);
+        }
+        assert_eq!(adaptive.quality(StageQuality::High), StageQuality::High);
+        assert_eq!(
+            adaptive.quality_change(StageQuality::High),
+            Some(StageQuality::High)
+        );
+    }
+}
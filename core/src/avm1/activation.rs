@@ -27,7 +27,6 @@ use std::fmt;
 use swf::avm1::read::Reader;
 use swf::avm1::types::*;
 use url::form_urlencoded;
-use web_time::Instant;
 
 use super::object_reference::MovieClipReference;
 
@@ -484,7 +483,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 Action::CastOp => self.action_cast_op(),
                 Action::CharToAscii => self.action_char_to_ascii(),
                 Action::CloneSprite => self.action_clone_sprite(),
-                Action::ConstantPool(action) => self.action_constant_pool(action),
+                Action::ConstantPool(action) => self.action_constant_pool(action, data),
                 Action::Decrement => self.action_decrement(),
                 Action::DefineFunction(action) => self.action_define_function(action.into(), data),
                 Action::DefineFunction2(action) => self.action_define_function(action, data),
@@ -867,18 +866,46 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn action_constant_pool(
         &mut self,
         action: ConstantPool,
+        data: &SwfSlice,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let encoding = self.encoding();
-        let constants = action
-            .strings
-            .iter()
-            .map(|s| self.strings().intern_wstr(s.decode(encoding)).into())
-            .collect();
+        // The strings are read straight out of the movie, so where the first one starts
+        // tells this constant pool apart from any other. Strings from anywhere else can't
+        // be told apart, so they aren't cached.
+        let block = data.data();
+        let offset = action.strings.first().and_then(|s| {
+            let start = (s.as_bytes().as_ptr() as usize).checked_sub(block.as_ptr() as usize)?;
+            (start < block.len()).then_some(data.start + start)
+        });
+        let cached = offset.and_then(|offset| {
+            self.context
+                .avm1
+                .decoded_constant_pool(&data.movie, offset, encoding)
+        });
+
+        let constants = match cached {
+            Some(constants) => constants,
+            None => {
+                let constants = action
+                    .strings
+                    .iter()
+                    .map(|s| self.strings().intern_wstr(s.decode(encoding)).into())
+                    .collect();
+                let constants = Gc::new(self.context.gc_context, constants);
+                if let Some(offset) = offset {
+                    self.context.avm1.set_decoded_constant_pool(
+                        &data.movie,
+                        offset,
+                        encoding,
+                        constants,
+                    );
+                }
+                constants
+            }
+        };
 
-        self.context
-            .avm1
-            .set_constant_pool(Gc::new(self.context.gc_context, constants));
-        self.set_constant_pool(self.context.avm1.constant_pool());
+        self.context.avm1.set_constant_pool(constants);
+        self.set_constant_pool(constants);
 
         Ok(FrameControl::Continue)
     }
@@ -1176,9 +1203,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             *self.context.time_offset += 1;
         }
 
-        let time = Instant::now()
-            .duration_since(self.context.start_time)
-            .as_millis() as u32;
+        let time = self.context.timer_millis();
         let result = time.wrapping_add(*self.context.time_offset);
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
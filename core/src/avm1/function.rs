@@ -400,6 +400,17 @@ impl<'gc> Executable<'gc> {
         // The caller is the previous callee.
         let arguments_caller = activation.callee;
 
+        let profile_name =
+            activation
+                .context
+                .profiler
+                .is_active()
+                .then(|| match (af.name, &name) {
+                    (Some(name), _) => name.to_utf8_lossy().into_owned(),
+                    (None, ExecutionName::Dynamic(name)) => name.to_utf8_lossy().into_owned(),
+                    (None, ExecutionName::Static(name)) => name.to_string(),
+                });
+
         let name = if cfg!(feature = "avm_debug") {
             Cow::Owned(af.debug_string_for_call(name, args))
         } else {
@@ -453,7 +464,15 @@ impl<'gc> Executable<'gc> {
             }
         }
 
-        Ok(frame.run_actions(af.data.clone())?.value())
+        let Some(profile_name) = profile_name else {
+            return Ok(frame.run_actions(af.data.clone())?.value());
+        };
+        let allocated = frame.context.gc_context.metrics().total_gc_allocation();
+        frame.context.profiler.enter(|| profile_name, allocated);
+        let result = frame.run_actions(af.data.clone());
+        let allocated = frame.context.gc_context.metrics().total_gc_allocation();
+        frame.context.profiler.exit(allocated);
+        Ok(result?.value())
     }
 }
 
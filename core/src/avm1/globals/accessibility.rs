@@ -5,6 +5,7 @@ use crate::avm1::error::Error;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, Value};
 use crate::avm1_stub;
+use crate::display_object::TDisplayObject;
 use crate::string::StringContext;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
@@ -18,8 +19,7 @@ pub fn is_active<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "Accessibility", "isActive");
-    Ok(Value::Bool(false))
+    Ok(activation.context.ui.is_accessibility_active().into())
 }
 
 pub fn send_event<'gc>(
@@ -36,7 +36,9 @@ pub fn update_properties<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "Accessibility", "updateProperties");
+    // `_accProps` is read again after the current frame.
+    let stage = activation.context.stage;
+    stage.invalidate_accessibility(activation.gc());
     Ok(Value::Undefined)
 }
 
@@ -191,8 +191,13 @@ fn get_pixel<'gc>(
                 let x = x_val.coerce_to_u32(activation)?;
                 let y = y_val.coerce_to_u32(activation)?;
                 // AVM1 returns a signed int, so we need to convert it.
-                let col =
-                    operations::get_pixel(bitmap_data, activation.context.renderer, x, y) as i32;
+                let col = operations::get_pixel(
+                    bitmap_data,
+                    activation.context.renderer,
+                    activation.context.bitmap_readbacks,
+                    x,
+                    y,
+                ) as i32;
                 return Ok(col.into());
             }
         }
@@ -212,8 +217,13 @@ fn get_pixel32<'gc>(
                 let x = x_val.coerce_to_u32(activation)?;
                 let y = y_val.coerce_to_u32(activation)?;
                 // AVM1 returns a signed int, so we need to convert it.
-                let col =
-                    operations::get_pixel32(bitmap_data, activation.context.renderer, x, y) as i32;
+                let col = operations::get_pixel32(
+                    bitmap_data,
+                    activation.context.renderer,
+                    activation.context.bitmap_readbacks,
+                    x,
+                    y,
+                ) as i32;
                 return Ok(col.into());
             }
         }
@@ -641,6 +651,10 @@ fn apply_filter<'gc>(
                 .unwrap_or(&Value::Undefined)
                 .coerce_to_object(activation);
             let filter = bitmap_filter::avm1_to_filter(filter_object, activation.context);
+            let map_bitmap = match filter_object.native() {
+                NativeObject::DisplacementMapFilter(filter) => filter.map_bitmap_data(),
+                _ => None,
+            };
 
             if let Some(filter) = filter {
                 operations::apply_filter(
@@ -651,6 +665,7 @@ fn apply_filter<'gc>(
                     (src_width, src_height),
                     (dest_x, dest_y),
                     filter,
+                    map_bitmap,
                 );
                 return Ok(0.into());
             }
@@ -9,7 +9,7 @@ use crate::avm1::{
     ActivationIdentifier, ExecutionReason, NativeObject, Object, ScriptObject, Value,
 };
 use crate::context::UpdateContext;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::local_connection::{LocalConnectionHandle, LocalConnections};
 use crate::string::{AvmString, StringContext};
 use flash_lso::types::Value as AmfValue;
@@ -17,14 +17,18 @@ use gc_arena::{Collect, Gc};
 use std::cell::RefCell;
 
 #[derive(Debug, Collect)]
-#[collect(require_static)]
-struct LocalConnectionData {
+#[collect(no_drop)]
+struct LocalConnectionData<'gc> {
     handle: RefCell<Option<LocalConnectionHandle>>,
+
+    /// The clip that created this connection.
+    /// Callbacks run in its context, as the root clip may not even be an AVM1 movie.
+    base_clip: DisplayObject<'gc>,
 }
 
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(no_drop)]
-pub struct LocalConnection<'gc>(Gc<'gc, LocalConnectionData>);
+pub struct LocalConnection<'gc>(Gc<'gc, LocalConnectionData<'gc>>);
 
 impl<'gc> LocalConnection<'gc> {
     pub fn cast(value: Value<'gc>) -> Option<Self> {
@@ -40,6 +44,11 @@ impl<'gc> LocalConnection<'gc> {
         self.0.handle.borrow().is_some()
     }
 
+    /// The domain of the movie this connection belongs to.
+    pub fn domain(&self) -> String {
+        LocalConnections::get_domain(self.0.base_clip.movie().url()).into_owned()
+    }
+
     pub fn connect(
         &self,
         activation: &mut Activation<'_, 'gc>,
@@ -50,11 +59,11 @@ impl<'gc> LocalConnection<'gc> {
             return false;
         }
 
-        let connection_handle = activation.context.local_connections.connect(
-            &LocalConnections::get_domain(activation.context.swf.url()),
-            this,
-            &name,
-        );
+        let connection_handle =
+            activation
+                .context
+                .local_connections
+                .connect(&self.domain(), this, &name);
         let result = connection_handle.is_some();
         *self.0.handle.borrow_mut() = connection_handle;
         result
@@ -66,19 +75,24 @@ impl<'gc> LocalConnection<'gc> {
         }
     }
 
+    /// Returns the clip that owns the given connection object, in whose context
+    /// callbacks should run.
+    fn owner_clip(this: Object<'gc>) -> Option<DisplayObject<'gc>> {
+        Self::cast(this.into()).map(|local_connection| local_connection.0.base_clip)
+    }
+
     pub fn send_status(
         context: &mut UpdateContext<'gc>,
         this: Object<'gc>,
         status: &'static str,
     ) -> Result<(), Error<'gc>> {
-        let Some(root_clip) = context.stage.root_clip() else {
-            tracing::warn!("Ignored LocalConnection callback as there's no root movie");
+        let Some(base_clip) = Self::owner_clip(this) else {
             return Ok(());
         };
         let mut activation = Activation::from_nothing(
             context,
             ActivationIdentifier::root("[LocalConnection onStatus]"),
-            root_clip,
+            base_clip,
         );
         let constructor = activation.context.avm1.prototypes().object_constructor;
         let event = constructor
@@ -100,14 +114,13 @@ impl<'gc> LocalConnection<'gc> {
         method_name: AvmString<'gc>,
         amf_arguments: Vec<AmfValue>,
     ) -> Result<(), Error<'gc>> {
-        let Some(root_clip) = context.stage.root_clip() else {
-            tracing::warn!("Ignored LocalConnection callback as there's no root movie");
+        let Some(base_clip) = Self::owner_clip(this) else {
             return Ok(());
         };
         let mut activation = Activation::from_nothing(
             context,
             ActivationIdentifier::root("[LocalConnection call]"),
-            root_clip,
+            base_clip,
         );
         let mut args = Vec::with_capacity(amf_arguments.len());
         for arg in amf_arguments {
@@ -128,6 +141,33 @@ impl<'gc> LocalConnection<'gc> {
         )?;
         Ok(())
     }
+
+    /// Asks the user-defined `allowDomain` handler whether a movie from
+    /// `domain` may call methods on this connection.
+    ///
+    /// Without a handler, calls from other domains are rejected.
+    pub fn allows_domain(
+        context: &mut UpdateContext<'gc>,
+        this: Object<'gc>,
+        domain: &str,
+    ) -> Result<bool, Error<'gc>> {
+        let Some(base_clip) = Self::owner_clip(this) else {
+            return Ok(false);
+        };
+        let mut activation = Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[LocalConnection allowDomain]"),
+            base_clip,
+        );
+        let domain = AvmString::new_utf8(activation.gc(), domain);
+        let allowed = this.call_method(
+            "allowDomain".into(),
+            &[domain.into()],
+            &mut activation,
+            ExecutionReason::Special,
+        )?;
+        Ok(allowed.as_bool(activation.swf_version()))
+    }
 }
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
@@ -206,8 +246,9 @@ pub fn send<'gc>(
         amf_arguments.push(serialize(activation, *arg));
     }
 
+    let movie = activation.base_clip().movie();
     activation.context.local_connections.send(
-        &LocalConnections::get_domain(activation.context.swf.url()),
+        &LocalConnections::get_domain(movie.url()),
         this,
         *connection_name,
         *method_name,
@@ -238,6 +279,7 @@ pub fn constructor<'gc>(
             activation.gc(),
             LocalConnectionData {
                 handle: RefCell::new(None),
+                base_clip: activation.base_clip(),
             },
         ))),
     );
@@ -11,6 +11,7 @@ use crate::context::UpdateContext;
 use crate::net_connection::{NetConnectionHandle, NetConnections, ResponderCallback};
 use crate::string::{AvmString, StringContext};
 use flash_lso::packet::Header;
+use flash_lso::types::AMFVersion;
 use flash_lso::types::ObjectId;
 use flash_lso::types::Value as AMFValue;
 use gc_arena::{Collect, Gc};
@@ -134,6 +135,46 @@ impl<'gc> NetConnection<'gc> {
         )?;
         Ok(())
     }
+
+    /// Calls a method on a NetConnection on behalf of the server, such as `onStatus` with an
+    /// info object, or a client method invoked over RTMP.
+    pub fn call_remote_method(
+        context: &mut UpdateContext<'gc>,
+        this: Object<'gc>,
+        method_name: &str,
+        arguments: &[Rc<flash_lso::types::Value>],
+    ) -> Result<(), Error<'gc>> {
+        let Some(root_clip) = context.stage.root_clip() else {
+            tracing::warn!("Ignored NetConnection call as there's no root movie");
+            return Ok(());
+        };
+        let mut activation = Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[NetConnection call]"),
+            root_clip,
+        );
+        let reader = flash_lso::read::Reader::default();
+        let mut reference_cache = BTreeMap::default();
+        let arguments: Vec<_> = arguments
+            .iter()
+            .map(|argument| {
+                deserialize_value(
+                    &mut activation,
+                    argument,
+                    &reader.amf0_decoder,
+                    &mut reference_cache,
+                )
+            })
+            .collect();
+        let method_name = AvmString::new_utf8(activation.gc(), method_name);
+        this.call_method(
+            method_name,
+            &arguments,
+            &mut activation,
+            ExecutionReason::Special,
+        )?;
+        Ok(())
+    }
 }
 
 pub fn constructor<'gc>(
@@ -321,13 +362,31 @@ fn connect<'gc>(
         || url.starts_with(WStr::from_units(b"https://"))
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
-        NetConnections::connect_to_flash_remoting(activation.context, this, url.to_string());
+        NetConnections::connect_to_flash_remoting(
+            activation.context,
+            this,
+            url.to_string(),
+            AMFVersion::AMF0,
+        );
+    } else if url.starts_with(WStr::from_units(b"rtmp")) {
+        // This includes the `rtmps`, `rtmpt` and `rtmpe` variants, which fail to connect.
+        let mut arguments = Vec::new();
+        for arg in &args[1..] {
+            arguments.push(Rc::new(serialize(activation, *arg)));
+        }
+        NetConnections::connect_to_rtmp(
+            activation.context,
+            this,
+            url.to_string(),
+            AMFVersion::AMF0,
+            arguments,
+        );
     } else {
         avm1_stub!(
             activation,
             "NetConnection",
             "connect",
-            "with non-null, non-http command"
+            "with non-null, non-http, non-rtmp command"
         );
     }
 
@@ -0,0 +1,131 @@
+//! `PrintJob` class
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
+use crate::display_object::DisplayObject;
+use crate::printing;
+use crate::string::StringContext;
+use swf::{Rectangle, Twips};
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "start" => method(start; DONT_ENUM | DONT_DELETE);
+    "addPage" => method(add_page; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+};
+
+fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !printing::start(activation.context) {
+        return Ok(false.into());
+    }
+
+    // Like in Flash, the paper is only described once the job was started.
+    this.set("paperWidth", printing::PAPER_WIDTH.into(), activation)?;
+    this.set("paperHeight", printing::PAPER_HEIGHT.into(), activation)?;
+    this.set("pageWidth", printing::PAGE_WIDTH.into(), activation)?;
+    this.set("pageHeight", printing::PAGE_HEIGHT.into(), activation)?;
+    this.set("orientation", "portrait".into(), activation)?;
+    Ok(true.into())
+}
+
+fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let target = args.get(0).copied().unwrap_or(Value::Undefined);
+    let Some(target) = resolve_target(activation, target)? else {
+        return Ok(false.into());
+    };
+
+    let area = match args.get(1) {
+        Some(Value::Object(area)) => Some(object_to_print_area(activation, *area)?),
+        _ => None,
+    };
+
+    let print_as_bitmap = match args.get(2) {
+        Some(Value::Object(options)) => options
+            .get("printAsBitmap", activation)?
+            .as_bool(activation.swf_version()),
+        _ => false,
+    };
+
+    // `frameNum` is ignored, and the current frame of the target is printed.
+    Ok(printing::add_page(activation.context, target, area, print_as_bitmap).into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    printing::send(activation.context);
+    Ok(Value::Undefined)
+}
+
+/// Finds the clip to print, given either as a level number or as a clip.
+fn resolve_target<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Value<'gc>,
+) -> Result<Option<DisplayObject<'gc>>, Error<'gc>> {
+    if let Value::Number(level) = target {
+        return Ok(activation.get_level(level as i32));
+    }
+    let start = activation.target_clip_or_root();
+    activation.resolve_target_display_object(start, target, false)
+}
+
+/// Reads the `xMin`, `xMax`, `yMin` and `yMax` properties of a print area.
+fn object_to_print_area<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Rectangle<Twips>, Error<'gc>> {
+    const NAMES: &[&str] = &["xMin", "xMax", "yMin", "yMax"];
+    let mut values = [0.0; 4];
+    for (&name, value) in NAMES.iter().zip(&mut values) {
+        *value = object.get(name, activation)?.coerce_to_f64(activation)?;
+    }
+    let [x_min, x_max, y_min, y_max] = values;
+    Ok(Rectangle {
+        x_min: Twips::from_pixels(x_min),
+        x_max: Twips::from_pixels(x_max),
+        y_min: Twips::from_pixels(y_min),
+        y_max: Twips::from_pixels(y_max),
+    })
+}
+
+pub fn create_proto<'gc>(
+    context: &mut StringContext<'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut StringContext<'gc>,
+    print_job_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        print_job_proto,
+    )
+}
@@ -4,10 +4,12 @@ use crate::avm1::{
     property_decl::Declaration, ArrayObject, ExecutionReason, NativeObject, ScriptObject,
 };
 use crate::avm1::{Activation, Error, Value};
-use crate::avm1_stub;
 use crate::backend::navigator::Request;
-use crate::html::{transform_dashes_to_camel_case, CssStream, TextFormat};
-use crate::string::{AvmString, StringContext};
+use crate::html::{
+    parse_css_color, parse_css_font_family, transform_dashes_to_camel_case, CssStream, StyleSheet,
+    TextDisplay, TextFormat,
+};
+use crate::string::{AvmString, StringContext, WStr};
 use gc_arena::Gc;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
@@ -63,16 +65,24 @@ fn set_style<'gc>(
     let object = args.get(1).unwrap_or(&Value::Undefined);
 
     css.set(name, shallow_copy(activation, *object)?, activation)?;
-    styles.set(
-        name,
-        this.call_method(
-            "transform".into(),
-            &[shallow_copy(activation, *object)?],
-            activation,
-            ExecutionReason::Special,
-        )?,
+    let format = this.call_method(
+        "transform".into(),
+        &[shallow_copy(activation, *object)?],
         activation,
+        ExecutionReason::Special,
     )?;
+    styles.set(name, format, activation)?;
+
+    if let NativeObject::StyleSheet(style_sheet) = this.native() {
+        let format = match format {
+            Value::Object(format) => match format.native() {
+                NativeObject::TextFormat(format) => Some(format.borrow().clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        style_sheet.set_style(&name, format);
+    }
 
     Ok(Value::Undefined)
 }
@@ -133,14 +143,121 @@ fn load<'gc>(
     Ok(true.into())
 }
 
+/// Gets a property of a style object as a string, if it's set.
+fn style_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: Object<'gc>,
+    name: &'static str,
+) -> Result<Option<AvmString<'gc>>, Error<'gc>> {
+    match style.get(name, activation)? {
+        Value::Undefined | Value::Null => Ok(None),
+        value => value.coerce_to_string(activation).map(Some),
+    }
+}
+
+/// Parses the number at the start of a CSS value, ignoring any unit after it.
+fn parse_css_number(value: &WStr) -> Option<f64> {
+    let value = value.trim();
+    let end = value
+        .iter()
+        .enumerate()
+        .position(|(i, c)| {
+            !(u8::try_from(c).is_ok_and(|c| c.is_ascii_digit() || c == b'.')
+                || (i == 0 && (c == u16::from(b'-') || c == u16::from(b'+'))))
+        })
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
 fn transform<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "TextField.StyleSheet", "transform");
+    let Some(Value::Object(style)) = args.get(0) else {
+        return Ok(Value::Null);
+    };
+    let style = *style;
 
-    let text_format = TextFormat::default();
+    let mut text_format = TextFormat::default();
+    if let Some(color) = style_property(activation, style, "color")? {
+        text_format.color = Some(swf::Color::from_rgb(parse_css_color(&color), 0));
+    }
+    if let Some(display) = style_property(activation, style, "display")? {
+        text_format.display = if &display == b"block" {
+            Some(TextDisplay::Block)
+        } else if &display == b"inline" {
+            Some(TextDisplay::Inline)
+        } else if &display == b"none" {
+            Some(TextDisplay::None)
+        } else {
+            None
+        };
+    }
+    if let Some(font_family) = style_property(activation, style, "fontFamily")? {
+        text_format.font = Some(parse_css_font_family(&font_family));
+    }
+    if let Some(font_size) = style_property(activation, style, "fontSize")? {
+        text_format.size = parse_css_number(&font_size)
+            .map(f64::trunc)
+            .filter(|size| *size > 0.0);
+    }
+    if let Some(font_style) = style_property(activation, style, "fontStyle")? {
+        if &font_style == b"italic" {
+            text_format.italic = Some(true);
+        } else if &font_style == b"normal" {
+            text_format.italic = Some(false);
+        }
+    }
+    if let Some(font_weight) = style_property(activation, style, "fontWeight")? {
+        if &font_weight == b"bold" {
+            text_format.bold = Some(true);
+        } else if &font_weight == b"normal" {
+            text_format.bold = Some(false);
+        }
+    }
+    if let Some(kerning) = style_property(activation, style, "kerning")? {
+        if &kerning == b"true" {
+            text_format.kerning = Some(true);
+        } else if &kerning == b"false" {
+            text_format.kerning = Some(false);
+        }
+    }
+    if let Some(leading) = style_property(activation, style, "leading")? {
+        text_format.leading = parse_css_number(&leading).map(f64::trunc);
+    }
+    if let Some(letter_spacing) = style_property(activation, style, "letterSpacing")? {
+        text_format.letter_spacing = parse_css_number(&letter_spacing);
+    }
+    if let Some(margin_left) = style_property(activation, style, "marginLeft")? {
+        text_format.left_margin = parse_css_number(&margin_left);
+    }
+    if let Some(margin_right) = style_property(activation, style, "marginRight")? {
+        text_format.right_margin = parse_css_number(&margin_right);
+    }
+    if let Some(text_align) = style_property(activation, style, "textAlign")? {
+        text_format.align = if &text_align == b"left" {
+            Some(swf::TextAlign::Left)
+        } else if &text_align == b"center" {
+            Some(swf::TextAlign::Center)
+        } else if &text_align == b"right" {
+            Some(swf::TextAlign::Right)
+        } else if &text_align == b"justify" {
+            Some(swf::TextAlign::Justify)
+        } else {
+            None
+        };
+    }
+    if let Some(text_decoration) = style_property(activation, style, "textDecoration")? {
+        if &text_decoration == b"underline" {
+            text_format.underline = Some(true);
+        } else if &text_decoration == b"none" {
+            text_format.underline = Some(false);
+        }
+    }
+    if let Some(text_indent) = style_property(activation, style, "textIndent")? {
+        text_format.indent = parse_css_number(&text_indent).map(f64::trunc);
+    }
 
     let proto = activation.context.avm1.prototypes().text_format;
     let object = ScriptObject::new(activation.context.gc_context, Some(proto));
@@ -198,14 +315,21 @@ fn clear<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     this.set("_styles", ArrayObject::empty(activation).into(), activation)?;
     this.set("_css", ArrayObject::empty(activation).into(), activation)?;
+    if let NativeObject::StyleSheet(style_sheet) = this.native() {
+        style_sheet.clear();
+    }
     Ok(Value::Undefined)
 }
 
 pub fn constructor<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    this.set_native(
+        activation.gc(),
+        NativeObject::StyleSheet(StyleSheet::new(activation.gc())),
+    );
     Ok(this.into())
 }
 
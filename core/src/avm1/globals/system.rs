@@ -6,6 +6,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::runtime::Avm1;
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm1_stub;
+use crate::config::CapabilitiesConfig;
 use crate::context::UpdateContext;
 use crate::string::StringContext;
 use bitflags::bitflags;
@@ -269,6 +270,8 @@ pub struct SystemProperties {
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
     pub idc_level: String,
+    /// The strings reported in place of the real ones, shared with AVM2
+    pub overrides: CapabilitiesConfig,
 }
 
 impl Default for SystemProperties {
@@ -297,9 +300,13 @@ impl SystemProperties {
             os: OperatingSystem::Linux,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
+            overrides: Default::default(),
         }
     }
     pub fn get_version_string(&self, avm: &mut Avm1) -> String {
+        if let Some(version) = &self.overrides.version {
+            return version.clone();
+        }
         format!(
             "{} {},0,0,0",
             self.manufacturer.get_platform_name(),
@@ -331,7 +338,31 @@ impl SystemProperties {
         percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC).to_string()
     }
 
+    pub fn get_os_string(&self) -> String {
+        self.overrides
+            .os
+            .clone()
+            .unwrap_or_else(|| self.os.to_string())
+    }
+
+    pub fn get_player_type_string(&self) -> String {
+        self.overrides
+            .player_type
+            .clone()
+            .unwrap_or_else(|| self.player_type.to_string())
+    }
+
+    pub fn get_language_string(&self, player_version: u8) -> &str {
+        self.overrides
+            .language
+            .as_deref()
+            .unwrap_or_else(|| self.language.get_language_code(player_version))
+    }
+
     pub fn get_server_string(&self, context: &UpdateContext) -> String {
+        if let Some(server_string) = &self.overrides.server_string {
+            return server_string.clone();
+        }
         let viewport_dimensions = context.renderer.viewport_dimensions();
         url::form_urlencoded::Serializer::new(String::new())
             .append_pair("A", self.encode_capability(SystemCapabilities::AUDIO))
@@ -387,14 +418,10 @@ impl SystemProperties {
             )
             .append_pair("COL", &self.screen_color.to_string())
             .append_pair("AR", &self.pixel_aspect_ratio.to_string())
-            .append_pair("OS", &self.encode_string(&self.os.to_string()))
-            .append_pair(
-                "L",
-                self.language
-                    .get_language_code(context.avm1.player_version()),
-            )
+            .append_pair("OS", &self.encode_string(&self.get_os_string()))
+            .append_pair("L", self.get_language_string(context.avm1.player_version()))
             .append_pair("IME", self.encode_capability(SystemCapabilities::IME))
-            .append_pair("PT", &self.player_type.to_string())
+            .append_pair("PT", &self.get_player_type_string())
             .append_pair(
                 "AVD",
                 self.encode_not_capability(SystemCapabilities::AV_HARDWARE),
@@ -104,7 +104,7 @@ pub fn get_player_type<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
-        activation.context.system.player_type.to_string(),
+        activation.context.system.get_player_type_string(),
     )
     .into())
 }
@@ -131,8 +131,7 @@ pub fn get_language<'gc>(
         activation
             .context
             .system
-            .language
-            .get_language_code(activation.context.avm1.player_version()),
+            .get_language_string(activation.context.avm1.player_version()),
     )
     .into())
 }
@@ -198,7 +197,7 @@ pub fn get_os_name<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
-        activation.context.system.os.to_string(),
+        activation.context.system.get_os_string(),
     )
     .into())
 }
@@ -39,9 +39,19 @@ fn allow_insecure_domain<'gc>(
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "loadPolicyFile");
+    let url = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+
+    // The policy file is fetched once a load or a socket connection needs it.
+    match activation.context.navigator.resolve_url(&url) {
+        Ok(url) => activation.context.policy_files.request(url),
+        Err(e) => tracing::warn!("Ignoring invalid policy file URL {url}: {e}"),
+    }
     Ok(Value::Undefined)
 }
 
@@ -5,7 +5,7 @@ use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{globals, ArrayObject, Object, ScriptObject, TObject, Value};
 use crate::display_object::{
-    AutoSizeMode, EditText, TDisplayObject, TInteractiveObject, TextSelection,
+    AutoSizeMode, EditText, EditTextStyleSheet, TDisplayObject, TInteractiveObject, TextSelection,
 };
 use crate::html::TextFormat;
 use crate::string::{AvmString, StringContext, WStr};
@@ -83,6 +83,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "restrict" => property(tf_getter!(restrict), tf_setter!(set_restrict));
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll));
     "selectable" => property(tf_getter!(selectable), tf_setter!(set_selectable));
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet); VERSION_7);
     "text" => property(tf_getter!(text), tf_setter!(set_text));
     "textColor" => property(tf_getter!(text_color), tf_setter!(set_text_color));
     "textHeight" => property(tf_getter!(text_height));
@@ -384,6 +385,31 @@ pub fn set_html_text<'gc>(
     Ok(())
 }
 
+pub fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let EditTextStyleSheet::Avm1(style_sheet) = this.style_sheet() {
+        return Ok(style_sheet.into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let style_sheet = match value {
+        Value::Object(object) if matches!(object.native(), NativeObject::StyleSheet(_)) => {
+            EditTextStyleSheet::Avm1(object)
+        }
+        _ => EditTextStyleSheet::None,
+    };
+    this.set_style_sheet(activation.context, style_sheet);
+    Ok(())
+}
+
 pub fn background<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
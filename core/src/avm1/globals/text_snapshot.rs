@@ -0,0 +1,268 @@
+//! TextSnapshot object
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::NativeObject;
+use crate::avm1::object_reference::MovieClipReference;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{
+    Activation, ArrayObject, Attribute, Error, Object, ScriptObject, TObject, Value,
+};
+use crate::display_object::DisplayObject;
+use crate::string::{AvmString, StringContext};
+use crate::text_snapshot::TextSnapshot;
+use gc_arena::Collect;
+use swf::{Color, Point, Twips};
+
+#[derive(Copy, Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct TextSnapshotObject<'gc> {
+    clip: Option<MovieClipReference<'gc>>,
+}
+
+impl<'gc> TextSnapshotObject<'gc> {
+    fn clip(&self, activation: &mut Activation<'_, 'gc>) -> Option<DisplayObject<'gc>> {
+        let (_, _, clip) = self.clip?.resolve_reference(activation)?;
+        Some(clip)
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "findText" => method(find_text; DONT_ENUM | DONT_DELETE);
+    "getCount" => method(get_count; DONT_ENUM | DONT_DELETE);
+    "getSelected" => method(get_selected; DONT_ENUM | DONT_DELETE);
+    "getSelectedText" => method(get_selected_text; DONT_ENUM | DONT_DELETE);
+    "getText" => method(get_text; DONT_ENUM | DONT_DELETE);
+    "getTextRunInfo" => method(get_text_run_info; DONT_ENUM | DONT_DELETE);
+    "hitTestTextNearPos" => method(hit_test_text_near_pos; DONT_ENUM | DONT_DELETE);
+    "setSelectColor" => method(set_select_color; DONT_ENUM | DONT_DELETE);
+    "setSelected" => method(set_selected; DONT_ENUM | DONT_DELETE);
+};
+
+/// Describes the static text of the movie clip this snapshot was taken from.
+fn snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<TextSnapshot<'gc>> {
+    let NativeObject::TextSnapshot(snapshot) = this.native() else {
+        return None;
+    };
+    let clip = snapshot.clip(activation)?;
+    Some(TextSnapshot::new(activation.context, clip))
+}
+
+fn arg<'a, 'gc>(args: &'a [Value<'gc>], index: usize) -> &'a Value<'gc> {
+    args.get(index).unwrap_or(&Value::Undefined)
+}
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let clip = match args {
+        [Value::MovieClip(clip), ..] => Some(*clip),
+        [Value::Object(clip), ..] => clip
+            .as_stage_object()
+            .and_then(|clip| MovieClipReference::try_from_stage_object(activation, clip)),
+        _ => None,
+    };
+    this.set_native(
+        activation.gc(),
+        NativeObject::TextSnapshot(TextSnapshotObject { clip }),
+    );
+    Ok(this.into())
+}
+
+fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?.max(0) as usize;
+    let text = arg(args, 1).coerce_to_string(activation)?;
+    let case_sensitive = arg(args, 2).as_bool(activation.swf_version());
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    Ok(snapshot
+        .find(begin, &text.to_utf8_lossy(), case_sensitive)
+        .map_or(-1, |index| index as i32)
+        .into())
+}
+
+fn get_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    Ok(snapshot.char_count().into())
+}
+
+fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    Ok(snapshot.is_selected(snapshot.range(begin, end)).into())
+}
+
+fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let include_line_endings = arg(args, 0).as_bool(activation.swf_version());
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let text = snapshot.selected_text(include_line_endings);
+    Ok(AvmString::new_utf8(activation.gc(), text).into())
+}
+
+fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+    let include_line_endings = arg(args, 2).as_bool(activation.swf_version());
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let text = snapshot.text(snapshot.range(begin, end), include_line_endings);
+    Ok(AvmString::new_utf8(activation.gc(), text).into())
+}
+
+fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let range = snapshot.range(begin, end);
+    let object_proto = activation.context.avm1.prototypes().object;
+    let mut infos: Vec<Value<'gc>> = Vec::new();
+    for c in snapshot.chars() {
+        if !range.contains(&c.index) {
+            continue;
+        }
+
+        let info = ScriptObject::new(activation.gc(), Some(object_proto));
+        let font = AvmString::new_utf8(activation.gc(), &c.glyph.font_name);
+        let properties: [(&'static str, Value<'gc>); 19] = [
+            ("indexInRun", c.index.into()),
+            ("selected", c.selected.into()),
+            ("font", font.into()),
+            ("color", c.glyph.color.to_rgb().into()),
+            ("height", c.glyph.height.to_pixels().into()),
+            ("matrix_a", c.matrix.a.into()),
+            ("matrix_b", c.matrix.b.into()),
+            ("matrix_c", c.matrix.c.into()),
+            ("matrix_d", c.matrix.d.into()),
+            ("matrix_tx", c.matrix.tx.to_pixels().into()),
+            ("matrix_ty", c.matrix.ty.to_pixels().into()),
+            ("corner0x", c.corners[0].x.to_pixels().into()),
+            ("corner0y", c.corners[0].y.to_pixels().into()),
+            ("corner1x", c.corners[1].x.to_pixels().into()),
+            ("corner1y", c.corners[1].y.to_pixels().into()),
+            ("corner2x", c.corners[2].x.to_pixels().into()),
+            ("corner2y", c.corners[2].y.to_pixels().into()),
+            ("corner3x", c.corners[3].x.to_pixels().into()),
+            ("corner3y", c.corners[3].y.to_pixels().into()),
+        ];
+        for (name, value) in properties {
+            info.define_value(activation.gc(), name, value, Attribute::empty());
+        }
+        infos.push(info.into());
+    }
+
+    let array_proto = activation.context.avm1.prototypes().array;
+    Ok(ArrayObject::new(activation.gc(), array_proto, infos).into())
+}
+
+fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = arg(args, 0).coerce_to_f64(activation)?;
+    let y = arg(args, 1).coerce_to_f64(activation)?;
+    let max_distance = match args.get(2) {
+        Some(value) => value.coerce_to_f64(activation)?,
+        None => 0.0,
+    };
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let point = Point::new(Twips::from_pixels(x), Twips::from_pixels(y));
+    Ok(snapshot
+        .hit_test(point, Twips::from_pixels(max_distance.max(0.0)))
+        .map_or(-1.0, |index| index as f64)
+        .into())
+}
+
+fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = match args.get(0) {
+        Some(value) => value.coerce_to_u32(activation)?,
+        None => 0xFFFF00,
+    };
+
+    if let Some(snapshot) = snapshot(activation, this) {
+        snapshot.set_select_color(activation.gc(), Color::from_rgb(color, 255));
+    }
+    Ok(Value::Undefined)
+}
+
+fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+    let select = arg(args, 2).as_bool(activation.swf_version());
+
+    if let Some(snapshot) = snapshot(activation, this) {
+        snapshot.set_selected(activation.gc(), snapshot.range(begin, end), select);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn create_constructor<'gc>(
+    context: &mut StringContext<'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let text_snapshot_proto = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, text_snapshot_proto, fn_proto);
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        text_snapshot_proto.into(),
+    )
+}
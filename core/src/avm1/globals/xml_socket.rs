@@ -115,36 +115,47 @@ pub fn connect<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if XmlSocket::cast(this.into()).is_some() {
-        let host = args
-            .get(0)
-            .copied()
-            .unwrap_or_else(|| {
+        // A null host means the host that the movie was loaded from.
+        let host = match args.get(0) {
+            None | Some(Value::Undefined | Value::Null) => {
                 let movie = activation.base_clip().movie();
 
                 if let Ok(url) = url::Url::parse(movie.url()) {
                     if url.scheme() == "file" {
                         "localhost".into()
                     } else if let Some(domain) = url.domain() {
-                        AvmString::new_utf8(activation.context.gc_context, domain).into()
+                        AvmString::new_utf8(activation.context.gc_context, domain)
                     } else {
                         // no domain?
                         "localhost".into()
                     }
                 } else {
-                    Value::Undefined
+                    Value::Undefined.coerce_to_string(activation)?
                 }
-            })
-            .coerce_to_string(activation)?;
+            }
+            Some(host) => host.coerce_to_string(activation)?,
+        };
         let port = args
             .get(1)
             .unwrap_or(&Value::Undefined)
             .coerce_to_u16(activation)?;
 
         let UpdateContext {
-            sockets, navigator, ..
+            sockets,
+            navigator,
+            policy_files,
+            swf,
+            ..
         } = activation.context;
 
-        sockets.connect_avm1(*navigator, this, host.to_utf8_lossy().into_owned(), port);
+        sockets.connect_avm1(
+            *navigator,
+            policy_files,
+            swf,
+            this,
+            host.to_utf8_lossy().into_owned(),
+            port,
+        );
 
         // NOTE: At this point we do not know if the connection will succeed
         //       because connecting is an asynchronous process, so we just return true.
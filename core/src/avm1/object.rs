@@ -16,6 +16,7 @@ use crate::avm1::globals::local_connection::LocalConnection;
 use crate::avm1::globals::netconnection::NetConnection;
 use crate::avm1::globals::shared_object::SharedObject;
 use crate::avm1::globals::sound::Sound;
+use crate::avm1::globals::text_snapshot::TextSnapshotObject;
 use crate::avm1::globals::transform::TransformObject;
 use crate::avm1::globals::xml::Xml;
 use crate::avm1::globals::xml_socket::XmlSocket;
@@ -25,7 +26,7 @@ use crate::avm1::{Activation, Attribute, Error, ScriptObject, StageObject, Value
 use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::display_object::DisplayObject;
 use crate::display_object::TDisplayObject;
-use crate::html::TextFormat;
+use crate::html::{StyleSheet, TextFormat};
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::xml::XmlNode;
@@ -59,7 +60,9 @@ pub enum NativeObject<'gc> {
     GradientGlowFilter(GradientFilter<'gc>),
     ColorTransform(GcCell<'gc, ColorTransformObject>),
     Transform(TransformObject<'gc>),
+    TextSnapshot(TextSnapshotObject<'gc>),
     TextFormat(Gc<'gc, RefCell<TextFormat>>),
+    StyleSheet(StyleSheet<'gc>),
     NetStream(NetStream<'gc>),
     BitmapData(BitmapDataWrapper<'gc>),
     Xml(Xml<'gc>),
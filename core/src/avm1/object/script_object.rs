@@ -5,7 +5,7 @@ use crate::avm1::object::NativeObject;
 use crate::avm1::property::{Attribute, Property};
 use crate::avm1::property_map::{Entry, PropertyMap};
 use crate::avm1::{Object, ObjectPtr, TObject, Value};
-use crate::string::AvmString;
+use crate::string::{AvmString, WStr};
 use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
 
@@ -57,6 +57,19 @@ pub struct ScriptObjectData<'gc> {
     properties: PropertyMap<'gc, Property<'gc>>,
     interfaces: Vec<Object<'gc>>,
     watchers: PropertyMap<'gc, Watcher<'gc>>,
+
+    /// The value of `__proto__` as last looked up, along with whether the lookup was case
+    /// sensitive. Walking the prototype chain reads it at every step.
+    cached_proto: Option<(bool, Value<'gc>)>,
+}
+
+impl ScriptObjectData<'_> {
+    /// Forgets the cached `__proto__` if `name` may refer to it.
+    fn invalidate_proto(&mut self, name: &WStr) {
+        if name.eq_ignore_case(WStr::from_units(b"__proto__")) {
+            self.cached_proto = None;
+        }
+    }
 }
 
 impl fmt::Debug for ScriptObject<'_> {
@@ -76,6 +89,7 @@ impl<'gc> ScriptObject<'gc> {
                 properties: PropertyMap::new(),
                 interfaces: vec![],
                 watchers: PropertyMap::new(),
+                cached_proto: None,
             },
         ));
         if let Some(proto) = proto {
@@ -116,12 +130,9 @@ impl<'gc> ScriptObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
         // TODO: Call watchers.
-        match self
-            .0
-            .write(activation.context.gc_context)
-            .properties
-            .entry(name, activation.is_case_sensitive())
-        {
+        let mut write = self.0.write(activation.context.gc_context);
+        write.invalidate_proto(&name);
+        match write.properties.entry(name, activation.is_case_sensitive()) {
             Entry::Occupied(mut entry) => entry.get_mut().set_data(value),
             Entry::Vacant(entry) => entry.insert(Property::new_stored(value, Attribute::empty())),
         }
@@ -173,20 +184,19 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
         this: Object<'gc>,
     ) -> Result<(), Error<'gc>> {
-        let setter = match self
-            .0
-            .write(activation.context.gc_context)
-            .properties
-            .entry(name, activation.is_case_sensitive())
-        {
-            Entry::Occupied(mut entry) => {
-                let entry = entry.get_mut();
-                entry.set_data(value);
-                entry.setter()
-            }
-            Entry::Vacant(entry) => {
-                entry.insert(Property::new_stored(value, Attribute::empty()));
-                None
+        let setter = {
+            let mut write = self.0.write(activation.context.gc_context);
+            write.invalidate_proto(&name);
+            match write.properties.entry(name, activation.is_case_sensitive()) {
+                Entry::Occupied(mut entry) => {
+                    let entry = entry.get_mut();
+                    entry.set_data(value);
+                    entry.setter()
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Property::new_stored(value, Attribute::empty()));
+                    None
+                }
             }
         };
 
@@ -262,11 +272,10 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
     ///
     /// Returns false if the property cannot be deleted.
     fn delete(&self, activation: &mut Activation<'_, 'gc>, name: AvmString<'gc>) -> bool {
-        if let Entry::Occupied(mut entry) = self
-            .0
-            .write(activation.context.gc_context)
-            .properties
-            .entry(name, activation.is_case_sensitive())
+        let mut write = self.0.write(activation.context.gc_context);
+        write.invalidate_proto(&name);
+        if let Entry::Occupied(mut entry) =
+            write.properties.entry(name, activation.is_case_sensitive())
         {
             if entry.get().can_delete() {
                 entry.remove_entry();
@@ -284,7 +293,9 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         setter: Option<Object<'gc>>,
         attributes: Attribute,
     ) {
-        match self.0.write(gc_context).properties.entry(name, false) {
+        let mut write = self.0.write(gc_context);
+        write.invalidate_proto(&name);
+        match write.properties.entry(name, false) {
             Entry::Occupied(mut entry) => entry.get_mut().set_virtual(getter, setter),
             Entry::Vacant(entry) => entry.insert(Property::new_virtual(getter, setter, attributes)),
         }
@@ -298,12 +309,9 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         setter: Option<Object<'gc>>,
         attributes: Attribute,
     ) {
-        match self
-            .0
-            .write(activation.context.gc_context)
-            .properties
-            .entry(name, activation.is_case_sensitive())
-        {
+        let mut write = self.0.write(activation.context.gc_context);
+        write.invalidate_proto(&name);
+        match write.properties.entry(name, activation.is_case_sensitive()) {
             Entry::Occupied(mut entry) => entry.get_mut().set_virtual(getter, setter),
             Entry::Vacant(entry) => entry.insert(Property::new_virtual(getter, setter, attributes)),
         }
@@ -367,11 +375,12 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         value: Value<'gc>,
         attributes: Attribute,
     ) {
-        self.0.write(gc_context).properties.insert(
-            name.into(),
-            Property::new_stored(value, attributes),
-            true,
-        );
+        let name = name.into();
+        let mut write = self.0.write(gc_context);
+        write.invalidate_proto(&name);
+        write
+            .properties
+            .insert(name, Property::new_stored(value, attributes), true);
     }
 
     fn set_attributes(
@@ -399,7 +408,16 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
     }
 
     fn proto(&self, activation: &mut Activation<'_, 'gc>) -> Value<'gc> {
-        self.get_data("__proto__".into(), activation)
+        let case_sensitive = activation.is_case_sensitive();
+        if let Some((cached_case_sensitive, proto)) = self.0.read().cached_proto {
+            if cached_case_sensitive == case_sensitive {
+                return proto;
+            }
+        }
+
+        let proto = self.get_data("__proto__".into(), activation);
+        self.0.write(activation.context.gc_context).cached_proto = Some((case_sensitive, proto));
+        proto
     }
 
     /// Checks if the object has a given named property.
@@ -725,6 +743,38 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_replace_proto() {
+        with_object(0, |activation, object| {
+            let proto = ScriptObject::new(activation.context.gc_context, None);
+            proto.define_value(
+                activation.context.gc_context,
+                "inherited",
+                "Inherited!".into(),
+                Attribute::empty(),
+            );
+
+            assert_eq!(
+                object.get("inherited", activation).unwrap(),
+                Value::Undefined
+            );
+            object
+                .set("__proto__", Value::Object(proto.into()), activation)
+                .unwrap();
+            assert_eq!(
+                object.get("inherited", activation).unwrap(),
+                "Inherited!".into()
+            );
+            object
+                .set("__proto__", Value::Undefined, activation)
+                .unwrap();
+            assert_eq!(
+                object.get("inherited", activation).unwrap(),
+                Value::Undefined
+            );
+        })
+    }
+
     #[test]
     fn test_get_keys() {
         with_object(0, |activation, object| {
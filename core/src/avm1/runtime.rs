@@ -10,13 +10,50 @@ use crate::context::UpdateContext;
 use crate::frame_lifecycle::FramePhase;
 use crate::prelude::*;
 use crate::string::{AvmString, StringContext};
-use crate::tag_utils::SwfSlice;
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::{avm1, avm_debug};
+use fnv::FnvHashMap;
 use gc_arena::{Collect, Gc, Mutation};
 use std::borrow::Cow;
+use std::sync::{Arc, Weak};
 use swf::avm1::read::Reader;
 use tracing::instrument;
 
+/// How many decoded constant pools are kept before checking for ones of unloaded movies.
+const MIN_DECODED_CONSTANT_POOLS_LIMIT: usize = 256;
+
+/// Where a constant pool is defined, and the encoding its strings were decoded with.
+#[derive(Clone, Collect, Copy, Eq, Hash, PartialEq)]
+#[collect(require_static)]
+struct ConstantPoolKey {
+    movie: usize,
+    offset: usize,
+    encoding: usize,
+}
+
+impl ConstantPoolKey {
+    fn new(movie: &Arc<SwfMovie>, offset: usize, encoding: &'static swf::Encoding) -> Self {
+        Self {
+            movie: Arc::as_ptr(movie) as usize,
+            offset,
+            encoding: encoding as *const swf::Encoding as usize,
+        }
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct DecodedConstantPool<'gc> {
+    /// The movie defining the constant pool.
+    ///
+    /// This keeps the allocation of the movie alive, so that no other movie can take its
+    /// address and be mistaken for it.
+    #[collect(require_static)]
+    movie: Weak<SwfMovie>,
+
+    constants: Gc<'gc, Vec<Value<'gc>>>,
+}
+
 #[derive(Collect)]
 #[collect(no_drop)]
 pub struct Avm1<'gc> {
@@ -27,6 +64,16 @@ pub struct Avm1<'gc> {
     /// don't close over the constant pool they were defined with.
     constant_pool: Gc<'gc, Vec<Value<'gc>>>,
 
+    /// Constant pools that were already decoded, keyed by where they are defined.
+    ///
+    /// Frame scripts and event handlers define their constant pool again every time they
+    /// run, and decoding and interning every string each time adds up.
+    decoded_constant_pools: FnvHashMap<ConstantPoolKey, DecodedConstantPool<'gc>>,
+
+    /// How many decoded constant pools there may be before the ones of unloaded movies
+    /// are dropped.
+    decoded_constant_pools_limit: usize,
+
     /// The global scope (pre-allocated so that it can be reused by fresh `Activation`s).
     global_scope: Gc<'gc, Scope<'gc>>,
 
@@ -97,6 +144,8 @@ impl<'gc> Avm1<'gc> {
         Self {
             player_version,
             constant_pool: Gc::new(gc_context, vec![]),
+            decoded_constant_pools: FnvHashMap::default(),
+            decoded_constant_pools_limit: MIN_DECODED_CONSTANT_POOLS_LIMIT,
             global_scope: Gc::new(gc_context, Scope::from_global_object(globals)),
             prototypes,
             broadcaster_functions,
@@ -381,6 +430,44 @@ impl<'gc> Avm1<'gc> {
         self.constant_pool = constant_pool;
     }
 
+    /// Returns the constant pool defined at `offset` in `movie`, if it was already decoded
+    /// with `encoding`.
+    pub fn decoded_constant_pool(
+        &self,
+        movie: &Arc<SwfMovie>,
+        offset: usize,
+        encoding: &'static swf::Encoding,
+    ) -> Option<Gc<'gc, Vec<Value<'gc>>>> {
+        let key = ConstantPoolKey::new(movie, offset, encoding);
+        self.decoded_constant_pools
+            .get(&key)
+            .map(|decoded| decoded.constants)
+    }
+
+    /// Remembers the constant pool defined at `offset` in `movie`, as decoded with `encoding`.
+    pub fn set_decoded_constant_pool(
+        &mut self,
+        movie: &Arc<SwfMovie>,
+        offset: usize,
+        encoding: &'static swf::Encoding,
+        constants: Gc<'gc, Vec<Value<'gc>>>,
+    ) {
+        if self.decoded_constant_pools.len() >= self.decoded_constant_pools_limit {
+            self.decoded_constant_pools
+                .retain(|_, decoded| decoded.movie.strong_count() > 0);
+            self.decoded_constant_pools_limit =
+                (self.decoded_constant_pools.len() * 2).max(MIN_DECODED_CONSTANT_POOLS_LIMIT);
+        }
+
+        self.decoded_constant_pools.insert(
+            ConstantPoolKey::new(movie, offset, encoding),
+            DecodedConstantPool {
+                movie: Arc::downgrade(movie),
+                constants,
+            },
+        );
+    }
+
     /// DisplayObject property map.
     pub fn display_properties(&self) -> &stage_object::DisplayPropertyMap<'gc> {
         &self.display_properties
@@ -10,6 +10,7 @@ use crate::avm2::globals::{
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::scope::ScopeChain;
 use crate::avm2::script::{Script, TranslationUnit};
+use crate::backend::jit::JitBackend;
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
 use crate::string::{AvmString, StringContext};
@@ -39,6 +40,7 @@ mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+pub mod debugger;
 mod domain;
 mod dynamic_map;
 mod e4x;
@@ -48,6 +50,8 @@ mod filters;
 mod flv;
 mod function;
 pub mod globals;
+mod inline_cache;
+mod jit;
 mod metadata;
 mod method;
 mod multiname;
@@ -76,6 +80,7 @@ pub use crate::avm2::activation::Activation;
 pub use crate::avm2::array::ArrayStorage;
 pub use crate::avm2::call_stack::{CallNode, CallStack};
 pub use crate::avm2::class::Class;
+pub use crate::avm2::debugger::Debugger;
 #[allow(unused)] // For debug_ui
 pub use crate::avm2::domain::{Domain, DomainPtr};
 pub use crate::avm2::error::Error;
@@ -85,7 +90,7 @@ pub use crate::avm2::multiname::{CommonMultinames, Multiname};
 pub use crate::avm2::namespace::{CommonNamespaces, Namespace};
 pub use crate::avm2::object::{
     ArrayObject, BitmapDataObject, ClassObject, EventObject, Object, SoundChannelObject,
-    StageObject, TObject,
+    SoundObject, StageObject, TObject,
 };
 pub use crate::avm2::qname::QName;
 pub use crate::avm2::value::Value;
@@ -186,6 +191,19 @@ pub struct Avm2<'gc> {
     pub debug_output: bool,
 
     pub optimizer_enabled: bool,
+
+    /// The state of the attached debugger, if any.
+    #[collect(require_static)]
+    pub debugger: Debugger,
+
+    /// The backend that hot methods are compiled with, if any.
+    #[collect(require_static)]
+    pub jit: Option<Box<dyn JitBackend>>,
+
+    /// Whether a `paste` event is being dispatched.
+    ///
+    /// Flash Player only lets content read the clipboard while handling one.
+    pub pasting: bool,
 }
 
 impl<'gc> Avm2<'gc> {
@@ -236,6 +254,12 @@ impl<'gc> Avm2<'gc> {
             debug_output: false,
 
             optimizer_enabled: true,
+
+            debugger: Default::default(),
+
+            jit: None,
+
+            pasting: false,
         }
     }
 
@@ -791,6 +815,10 @@ impl<'gc> Avm2<'gc> {
     pub fn set_optimizer_enabled(&mut self, value: bool) {
         self.optimizer_enabled = value;
     }
+
+    pub fn set_jit(&mut self, jit: Option<Box<dyn JitBackend>>) {
+        self.jit = jit;
+    }
 }
 
 /// If the provided `DisplayObjectWeak` should have frames run, returns
@@ -1,12 +1,15 @@
 //! Activation frames
 
+use crate::avm2::api_version::ApiVersion;
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::debugger::{self, DebugInfo};
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
     make_error_1065, make_error_1127, make_error_1506, make_null_or_undefined_error, type_error,
 };
+use crate::avm2::inline_cache::PropertySite;
 use crate::avm2::method::{BytecodeMethod, Method, ResolvedParamConfig};
 use crate::avm2::object::{
     ArrayObject, ByteArrayObject, ClassObject, FunctionObject, NamespaceObject, ScriptObject,
@@ -134,6 +137,15 @@ pub struct Activation<'a, 'gc: 'a> {
     /// The index where the scope frame starts.
     scope_depth: usize,
 
+    /// What the debugging instructions of the method have said so far.
+    debug_info: DebugInfo<'gc>,
+
+    /// The namespace of unqualified E4X names, as set by `default xml namespace = ...`.
+    ///
+    /// Like in avmplus, this is inherited from the calling method, unless the
+    /// method sets its own.
+    default_xml_namespace: Option<Namespace<'gc>>,
+
     pub context: &'a mut UpdateContext<'gc>,
 }
 
@@ -173,6 +185,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             activation_class: None,
             stack_depth: context.avm2.stack.len(),
             scope_depth: context.avm2.scope_stack.len(),
+            debug_info: DebugInfo::default(),
+            default_xml_namespace: None,
             context,
         }
     }
@@ -201,6 +215,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             activation_class: None,
             stack_depth: context.avm2.stack.len(),
             scope_depth: context.avm2.scope_stack.len(),
+            debug_info: DebugInfo::default(),
+            default_xml_namespace: None,
             context,
         }
     }
@@ -262,6 +278,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             activation_class,
             stack_depth: context.avm2.stack.len(),
             scope_depth: context.avm2.scope_stack.len(),
+            debug_info: DebugInfo::default(),
+            default_xml_namespace: None,
             context,
         };
 
@@ -434,6 +452,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.activation_class = activation_class;
         self.stack_depth = self.context.avm2.stack.len();
         self.scope_depth = self.context.avm2.scope_stack.len();
+        self.debug_info = DebugInfo::default();
 
         // Everything is now setup for the verifier to run
         if method.verified_info.borrow().is_none() {
@@ -538,6 +557,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             activation_class: None,
             stack_depth: context.avm2.stack.len(),
             scope_depth: context.avm2.scope_stack.len(),
+            debug_info: DebugInfo::default(),
+            default_xml_namespace: None,
             context,
         }
     }
@@ -567,6 +588,16 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         *self.local_registers.get_unchecked_mut(id) = value.into();
     }
 
+    /// The namespace of unqualified E4X names, if one was set with
+    /// `default xml namespace = ...`.
+    pub fn default_xml_namespace(&self) -> Option<Namespace<'gc>> {
+        self.default_xml_namespace
+    }
+
+    pub fn set_default_xml_namespace(&mut self, namespace: Option<Namespace<'gc>>) {
+        self.default_xml_namespace = namespace;
+    }
+
     /// Retrieve the outer scope of this activation
     pub fn outer(&self) -> ScopeChain<'gc> {
         self.outer
@@ -836,18 +867,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     num_args,
                     push_return_value,
                 } => self.op_call_method(*index, *num_args, *push_return_value),
-                Op::CallProperty {
-                    multiname,
-                    num_args,
-                } => self.op_call_property(*multiname, *num_args),
+                Op::CallProperty { site, num_args } => self.op_call_property(*site, *num_args),
                 Op::CallPropLex {
                     multiname,
                     num_args,
                 } => self.op_call_prop_lex(*multiname, *num_args),
-                Op::CallPropVoid {
-                    multiname,
-                    num_args,
-                } => self.op_call_prop_void(*multiname, *num_args),
+                Op::CallPropVoid { site, num_args } => self.op_call_prop_void(*site, *num_args),
                 Op::CallStatic { index, num_args } => {
                     self.op_call_static(method, *index, *num_args)
                 }
@@ -862,8 +887,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 Op::ReturnValue => self.op_return_value(method),
                 Op::ReturnValueNoCoerce => self.op_return_value_no_coerce(),
                 Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { multiname } => self.op_get_property(*multiname),
-                Op::SetProperty { multiname } => self.op_set_property(*multiname),
+                Op::GetProperty { site } => self.op_get_property(*site),
+                Op::SetProperty { site } => self.op_set_property(*site),
                 Op::InitProperty { multiname } => self.op_init_property(*multiname),
                 Op::DeleteProperty { multiname } => self.op_delete_property(*multiname),
                 Op::GetSuper { multiname } => self.op_get_super(*multiname),
@@ -982,6 +1007,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 Op::BkptLine { line_num } => self.op_bkpt_line(*line_num),
                 Op::Timestamp => self.op_timestamp(),
                 Op::TypeOf => self.op_type_of(),
+                Op::Dxns { string } => self.op_dxns(*string),
+                Op::DxnsLate => self.op_dxns_late(),
                 Op::EscXAttr => self.op_esc_xattr(),
                 Op::EscXElem => self.op_esc_elem(),
                 Op::LookupSwitch(ref lookup_switch) => {
@@ -1156,16 +1183,17 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn op_call_property(
         &mut self,
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.pop_stack_args(arg_count);
-        let multiname = multiname.fill_with_runtime_params(self)?;
+        let multiname = site.multiname().fill_with_runtime_params(self)?;
         let receiver = self
             .pop_stack()
             .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+        let property = PropertySite::get_trait(site, self.gc(), receiver.vtable(), &multiname);
 
-        let value = receiver.call_property(&multiname, &args, self)?;
+        let value = receiver.call_property_with_trait(&multiname, property, &args, self)?;
 
         self.push_stack(value);
 
@@ -1192,16 +1220,17 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn op_call_prop_void(
         &mut self,
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.pop_stack_args(arg_count);
-        let multiname = multiname.fill_with_runtime_params(self)?;
+        let multiname = site.multiname().fill_with_runtime_params(self)?;
         let receiver = self
             .pop_stack()
             .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+        let property = PropertySite::get_trait(site, self.gc(), receiver.vtable(), &multiname);
 
-        receiver.call_property(&multiname, &args, self)?;
+        receiver.call_property_with_trait(&multiname, property, &args, self)?;
 
         Ok(FrameControl::Continue)
     }
@@ -1291,13 +1320,16 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn op_get_property(
         &mut self,
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let multiname = site.multiname();
+
         // default path for static names
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            let value = object.get_property(&multiname, self)?;
+            let property = PropertySite::get_trait(site, self.gc(), object.vtable(), &multiname);
+            let value = object.get_property_with_trait(&multiname, property, self)?;
             self.push_stack(value);
             return Ok(FrameControl::Continue);
         }
@@ -1351,15 +1383,17 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn op_set_property(
         &mut self,
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let multiname = site.multiname();
         let value = self.pop_stack();
 
         // default path for static names
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            object.set_property(&multiname, value, self)?;
+            let property = PropertySite::get_trait(site, self.gc(), object.vtable(), &multiname);
+            object.set_property_with_trait(&multiname, property, value, self)?;
             return Ok(FrameControl::Continue);
         }
 
@@ -2707,37 +2741,38 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_type_of(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack();
 
+        let common = self.context.strings.common();
         let type_name = match value {
-            Value::Undefined => "undefined",
-            Value::Null => "object",
-            Value::Bool(_) => "boolean",
-            Value::Number(_) | Value::Integer(_) => "number",
+            Value::Undefined => common.str_undefined,
+            Value::Null => common.str_object,
+            Value::Bool(_) => common.str_boolean,
+            Value::Number(_) | Value::Integer(_) => common.str_number,
             Value::Object(o) => {
-                let classes = self.avm2().class_defs();
+                let classes = self.context.avm2.class_defs();
 
                 match o {
                     Object::FunctionObject(_) => {
                         if o.instance_class() == classes.function {
-                            "function"
+                            common.str_function
                         } else {
                             // Subclasses always have a typeof = "object"
-                            "object"
+                            common.str_object
                         }
                     }
                     Object::XmlObject(_) | Object::XmlListObject(_) => {
                         if o.instance_class() == classes.xml_list
                             || o.instance_class() == classes.xml
                         {
-                            "xml"
+                            common.str_xml
                         } else {
                             // Subclasses always have a typeof = "object"
-                            "object"
+                            common.str_object
                         }
                     }
-                    _ => "object",
+                    _ => common.str_object,
                 }
             }
-            Value::String(_) => "string",
+            Value::String(_) => common.str_string,
         };
 
         self.push_raw(Value::String(type_name.into()));
@@ -2745,6 +2780,23 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    /// Implements `Op::Dxns`
+    fn op_dxns(&mut self, uri: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let namespace = Namespace::package(uri, ApiVersion::AllVersions, self.strings());
+        self.default_xml_namespace = Some(namespace);
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Implements `Op::DxnsLate`
+    fn op_dxns_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let uri = self.pop_stack().coerce_to_string(self)?;
+        let namespace = Namespace::package(uri, ApiVersion::AllVersions, self.strings());
+        self.default_xml_namespace = Some(namespace);
+
+        Ok(FrameControl::Continue)
+    }
+
     /// Implements `Op::EscXAttr`
     fn op_esc_xattr(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let s = self.pop_stack().coerce_to_string(self)?;
@@ -2763,7 +2815,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             Value::Object(Object::XmlObject(x)) => x.as_xml_string(self),
             Value::Object(Object::XmlListObject(x)) => x.as_xml_string(self),
             // contrary to the avmplus documentation, this escapes the value on the top of the stack using EscapeElementValue from ECMA-357 *NOT* EscapeAttributeValue.
-            x => AvmString::new(self.gc(), escape_element_value(x.coerce_to_string(self)?)),
+            x => AvmString::new(self.gc(), escape_element_value(&x.coerce_to_string(self)?)),
         };
 
         self.push_raw(r);
@@ -2855,7 +2907,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let Ok(address) = usize::try_from(address) else {
             return Err(make_error_1506(self));
         };
-        if address > dm.len() - 2 {
+        if dm.len() < 2 || address > dm.len() - 2 {
             return Err(make_error_1506(self));
         }
         dm.write_at_nongrowing(&val.to_le_bytes(), address)
@@ -2877,7 +2929,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let Ok(address) = usize::try_from(address) else {
             return Err(make_error_1506(self));
         };
-        if address > dm.len() - 4 {
+        if dm.len() < 4 || address > dm.len() - 4 {
             return Err(make_error_1506(self));
         }
         dm.write_at_nongrowing(&val.to_le_bytes(), address)
@@ -2899,7 +2951,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let Ok(address) = usize::try_from(address) else {
             return Err(make_error_1506(self));
         };
-        if address > dm.len() - 4 {
+        if dm.len() < 4 || address > dm.len() - 4 {
             return Err(make_error_1506(self));
         }
         dm.write_at_nongrowing(&val.to_le_bytes(), address)
@@ -2921,7 +2973,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let Ok(address) = usize::try_from(address) else {
             return Err(make_error_1506(self));
         };
-        if address > dm.len() - 8 {
+        if dm.len() < 8 || address > dm.len() - 8 {
             return Err(make_error_1506(self));
         }
         dm.write_at_nongrowing(&val.to_le_bytes(), address)
@@ -2955,7 +3007,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let dm = self.domain_memory();
         let dm = dm.as_bytearray().expect("Bytearray storage should exist");
 
-        if address > dm.len() - 2 {
+        if dm.len() < 2 || address > dm.len() - 2 {
             return Err(make_error_1506(self));
         }
 
@@ -2972,7 +3024,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let dm = self.domain_memory();
         let dm = dm.as_bytearray().expect("Bytearray storage should exist");
 
-        if address > dm.len() - 4 {
+        if dm.len() < 4 || address > dm.len() - 4 {
             return Err(make_error_1506(self));
         }
 
@@ -2988,7 +3040,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let dm = self.domain_memory();
         let dm = dm.as_bytearray().expect("Bytearray storage should exist");
 
-        if address > dm.len() - 4 {
+        if dm.len() < 4 || address > dm.len() - 4 {
             return Err(make_error_1506(self));
         }
 
@@ -3005,7 +3057,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let dm = self.domain_memory();
         let dm = dm.as_bytearray().expect("Bytearray storage should exist");
 
-        if address > dm.len() - 8 {
+        if dm.len() < 8 || address > dm.len() - 8 {
             return Err(make_error_1506(self));
         }
 
@@ -3054,6 +3106,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         register_name: AvmAtom<'gc>,
         register: u8,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        self.debug_local(is_local_register, register_name, register);
+
         if is_local_register {
             if (register as usize) < self.local_registers.0.len() {
                 let value = self.local_register(register as u32);
@@ -3075,41 +3129,89 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     #[cfg(not(feature = "avm_debug"))]
     fn op_debug(
         &mut self,
-        _is_local_register: bool,
-        _register_name: AvmAtom<'gc>,
-        _register: u8,
+        is_local_register: bool,
+        register_name: AvmAtom<'gc>,
+        register: u8,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        self.debug_local(is_local_register, register_name, register);
+
         Ok(FrameControl::Continue)
     }
 
+    /// Keeps the name of a local register for an attached debugger.
+    fn debug_local(&mut self, is_local_register: bool, register_name: AvmAtom<'gc>, register: u8) {
+        if is_local_register && self.context.avm2.debugger.is_attached() {
+            self.debug_info.locals.push((register, register_name));
+        }
+    }
+
     #[cfg(feature = "avm_debug")]
     fn op_debug_file(&mut self, file_name: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "File: {file_name}");
+        self.debug_info.file = Some(file_name);
 
         Ok(FrameControl::Continue)
     }
 
     #[cfg(not(feature = "avm_debug"))]
-    fn op_debug_file(&mut self, _file_name: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_debug_file(&mut self, file_name: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
+        self.debug_info.file = Some(file_name);
+
         Ok(FrameControl::Continue)
     }
 
     fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
 
+        let first_line = self.debug_info.line == 0;
+        self.debug_info.line = line_num;
+        if self.context.avm2.debugger.is_active() {
+            debugger::on_line(self, line_num, first_line);
+        }
+
         Ok(FrameControl::Continue)
     }
 
     fn op_bkpt(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.avm2.debugger.is_attached() {
+            let line = self.debug_info.line;
+            debugger::pause(self, "bkpt", line);
+        }
+
         Ok(FrameControl::Continue)
     }
 
-    fn op_bkpt_line(&mut self, _line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_bkpt_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.avm2.debugger.is_attached() {
+            debugger::pause(self, "bkpt", line_num);
+        }
+
         Ok(FrameControl::Continue)
     }
 
+    /// The source file of the method, if its debugging instructions named one.
+    pub fn debug_file(&self) -> Option<AvmAtom<'gc>> {
+        self.debug_info.file
+    }
+
+    /// The named local variables of the method and their values, if its debugging
+    /// instructions named any while a debugger was attached.
+    pub fn debug_locals(&self) -> Vec<(String, Value<'gc>)> {
+        self.debug_info
+            .locals
+            .iter()
+            .filter(|(register, _)| (*register as usize) < self.local_registers.0.len())
+            .map(|(register, name)| {
+                (
+                    name.to_string(),
+                    self.local_registers.get_unchecked(*register as u32),
+                )
+            })
+            .collect()
+    }
+
     fn op_timestamp(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
         Ok(FrameControl::Continue)
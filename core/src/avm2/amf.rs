@@ -12,11 +12,14 @@ use crate::avm2::{Activation, Error, Object, Value};
 use crate::avm2_stub_method;
 use crate::string::AvmString;
 use enumset::EnumSet;
-use flash_lso::types::{AMFVersion, Element, Lso, ObjectId};
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso, ObjectId, Reference};
 use flash_lso::types::{Attribute, ClassDefinition, Value as AmfValue};
 use fnv::FnvHashMap;
 
-pub type ObjectTable<'gc> = FnvHashMap<Object<'gc>, Rc<AmfValue>>;
+/// The objects serialized so far, along with their index in the AMF3 object reference table
+/// and, once they're finished, what they were serialized to.
+pub type ObjectTable<'gc> = FnvHashMap<Object<'gc>, (ObjectId, Option<Rc<AmfValue>>)>;
 
 /// Serialize a Value to an AmfValue
 pub fn serialize_value<'gc>(
@@ -47,115 +50,205 @@ pub fn serialize_value<'gc>(
             // TODO: Find a more general rule for which object types should be skipped,
             // and which turn into undefined.
             if o.as_executable().is_some() {
-                None
+                return None;
             } else if o.as_display_object().is_some() {
-                Some(AmfValue::Undefined)
-            } else if o.as_array_storage().is_some() {
-                let mut values = Vec::new();
-                // Don't serialize properties from the vtable (we don't want a 'length' field)
-                recursive_serialize(activation, o, &mut values, None, amf_version, object_table)
-                    .unwrap();
-                let len = o.as_array_storage().unwrap().length() as u32;
-
-                if amf_version == AMFVersion::AMF3 {
-                    let mut dense = vec![];
-                    let mut sparse = vec![];
-                    // ActionScript `Array`s can have non-number properties, and these properties
-                    // are confirmed and tested to also be serialized, so do not limit the values
-                    // iterated over by the length of the internal array data.
-                    for (i, elem) in values.into_iter().enumerate() {
-                        if elem.name == i.to_string() {
-                            dense.push(elem.value.clone());
-                        } else {
-                            sparse.push(elem);
-                        }
+                return Some(AmfValue::Undefined);
+            }
+
+            if let Some((id, value)) = object_table.get(&o) {
+                return Some(match (amf_version, value) {
+                    (AMFVersion::AMF3, _) => AmfValue::Amf3ObjectReference(*id),
+                    (AMFVersion::AMF0, Some(value)) => (**value).clone(),
+                    (AMFVersion::AMF0, None) => {
+                        avm2_stub_method!(
+                            activation,
+                            "flash.utils.ByteArray",
+                            "writeObject",
+                            "with cyclic objects in AMF0"
+                        );
+                        AmfValue::Undefined
                     }
+                });
+            }
 
-                    Some(AmfValue::ECMAArray(ObjectId::INVALID, dense, sparse, len))
-                } else {
-                    // TODO: is this right?
-                    Some(AmfValue::ECMAArray(ObjectId::INVALID, vec![], values, len))
+            // Every complex value takes up a slot in the reference table, in the order they
+            // are first written, so the id has to be taken before serializing any children.
+            let id = ObjectId(object_table.len() as i64);
+            object_table.insert(o, (id, None));
+            let value = serialize_object(activation, o, id, amf_version, object_table);
+            match &value {
+                // AMF0 has no references to finished objects, so they're written out again.
+                Some(value) if amf_version == AMFVersion::AMF0 => {
+                    object_table.insert(o, (id, Some(Rc::new(value.clone()))));
                 }
-            } else if let Some(vec) = o.as_vector_storage() {
-                let val_type = vec.value_type();
-                if val_type == Some(activation.avm2().class_defs().int) {
-                    let int_vec: Vec<_> = vec.iter().map(|v| v.as_i32()).collect();
-                    Some(AmfValue::VectorInt(int_vec, vec.is_fixed()))
-                } else if val_type == Some(activation.avm2().class_defs().uint) {
-                    let uint_vec: Vec<_> = vec.iter().map(|v| v.as_u32()).collect();
-                    Some(AmfValue::VectorUInt(uint_vec, vec.is_fixed()))
-                } else if val_type == Some(activation.avm2().class_defs().number) {
-                    let num_vec: Vec<_> = vec.iter().map(|v| v.as_f64()).collect();
-                    Some(AmfValue::VectorDouble(num_vec, vec.is_fixed()))
-                } else {
-                    let obj_vec: Vec<_> = vec
-                        .iter()
-                        .map(|v| {
-                            serialize_value(activation, v, amf_version, object_table)
-                                .unwrap_or(AmfValue::Undefined)
-                        })
-                        .collect();
-
-                    let val_type = val_type.unwrap_or(activation.avm2().class_defs().object);
-
-                    let name = class_to_alias(activation, val_type);
-                    Some(AmfValue::VectorObject(
-                        ObjectId::INVALID,
-                        obj_vec,
-                        name,
-                        vec.is_fixed(),
-                    ))
+                Some(_) => {}
+                None => {
+                    object_table.remove(&o);
                 }
-            } else if let Some(date) = o.as_date_object() {
-                date.date_time()
-                    .map(|date_time| AmfValue::Date(date_time.timestamp_millis() as f64, None))
-            } else if let Some(xml) = o.as_xml_object() {
-                // `is_string` is `true` for the AS3 XML class
-                Some(AmfValue::XML(
-                    xml.node().xml_to_xml_string(activation).to_string(),
-                    true,
-                ))
-            } else if let Some(bytearray) = o.as_bytearray() {
-                Some(AmfValue::ByteArray(bytearray.bytes().to_vec()))
-            } else {
-                let class = o.instance_class();
-                let name = class_to_alias(activation, class);
+            }
+            value
+        }
+    }
+}
 
-                let mut attributes = EnumSet::empty();
-                if !class.is_sealed() {
-                    attributes.insert(Attribute::Dynamic);
-                }
+/// Serialize an Object that hasn't been seen before to an AmfValue
+fn serialize_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    o: Object<'gc>,
+    id: ObjectId,
+    amf_version: AMFVersion,
+    object_table: &mut ObjectTable<'gc>,
+) -> Option<AmfValue> {
+    if amf_version == AMFVersion::AMF0
+        && (o.as_vector_storage().is_some()
+            || o.as_dictionary_object().is_some()
+            || o.as_bytearray().is_some())
+    {
+        // AMF0 has no markers for these, so they're written in AMF3 instead. The switch to
+        // AMF3 starts a reference table of its own.
+        let mut object_table = ObjectTable::default();
+        let value = serialize_value(activation, o.into(), AMFVersion::AMF3, &mut object_table)?;
+        return Some(AmfValue::AMF3(Rc::new(value)));
+    }
 
-                let mut object_body = Vec::new();
-                let mut static_properties = Vec::new();
-                recursive_serialize(
-                    activation,
-                    o,
-                    &mut object_body,
-                    Some(&mut static_properties),
-                    amf_version,
-                    object_table,
-                )
-                .unwrap();
-                Some(AmfValue::Object(
-                    ObjectId::INVALID,
-                    object_body,
-                    if amf_version == AMFVersion::AMF3 {
-                        Some(ClassDefinition {
-                            name,
-                            attributes,
-                            // FIXME - implement this
-                            static_properties,
-                        })
-                    } else {
-                        None
-                    },
-                ))
+    if o.as_array_storage().is_some() {
+        let mut values = Vec::new();
+        // Don't serialize properties from the vtable (we don't want a 'length' field)
+        recursive_serialize(activation, o, &mut values, None, amf_version, object_table).unwrap();
+        let len = o.as_array_storage().unwrap().length() as u32;
+
+        if amf_version == AMFVersion::AMF3 {
+            let mut dense = vec![];
+            let mut sparse = vec![];
+            // ActionScript `Array`s can have non-number properties, and these properties
+            // are confirmed and tested to also be serialized, so do not limit the values
+            // iterated over by the length of the internal array data.
+            for (i, elem) in values.into_iter().enumerate() {
+                if elem.name == i.to_string() {
+                    dense.push(elem.value.clone());
+                } else {
+                    sparse.push(elem);
+                }
             }
+
+            Some(AmfValue::ECMAArray(id, dense, sparse, len))
+        } else {
+            // TODO: is this right?
+            Some(AmfValue::ECMAArray(id, vec![], values, len))
+        }
+    } else if let Some(vec) = o.as_vector_storage() {
+        let val_type = vec.value_type();
+        if val_type == Some(activation.avm2().class_defs().int) {
+            let int_vec: Vec<_> = vec.iter().map(|v| v.as_i32()).collect();
+            Some(AmfValue::VectorInt(int_vec, vec.is_fixed()))
+        } else if val_type == Some(activation.avm2().class_defs().uint) {
+            let uint_vec: Vec<_> = vec.iter().map(|v| v.as_u32()).collect();
+            Some(AmfValue::VectorUInt(uint_vec, vec.is_fixed()))
+        } else if val_type == Some(activation.avm2().class_defs().number) {
+            let num_vec: Vec<_> = vec.iter().map(|v| v.as_f64()).collect();
+            Some(AmfValue::VectorDouble(num_vec, vec.is_fixed()))
+        } else {
+            let obj_vec: Vec<_> = vec
+                .iter()
+                .map(|v| {
+                    serialize_value(activation, v, amf_version, object_table)
+                        .unwrap_or(AmfValue::Undefined)
+                })
+                .collect();
+
+            let val_type = val_type.unwrap_or(activation.avm2().class_defs().object);
+
+            let name = class_to_alias(activation, val_type);
+            Some(AmfValue::VectorObject(id, obj_vec, name, vec.is_fixed()))
+        }
+    } else if let Some(dictionary) = o.as_dictionary_object() {
+        let mut entries = Vec::new();
+        let mut last_index = dictionary.get_next_enumerant(0, activation).ok()?;
+        while let Some(index) = last_index.filter(|index| *index != 0) {
+            let key = dictionary.get_enumerant_name(index, activation).ok()?;
+            let value = dictionary.get_enumerant_value(index, activation).ok()?;
+            let key = serialize_value(activation, key, amf_version, object_table)
+                .unwrap_or(AmfValue::Undefined);
+            let value = serialize_value(activation, value, amf_version, object_table)
+                .unwrap_or(AmfValue::Undefined);
+            entries.push((Rc::new(key), Rc::new(value)));
+            last_index = dictionary.get_next_enumerant(index, activation).ok()?;
+        }
+
+        // FIXME - Dictionaries don't remember whether they have weak keys
+        Some(AmfValue::Dictionary(id, entries, false))
+    } else if let Some(date) = o.as_date_object() {
+        date.date_time()
+            .map(|date_time| AmfValue::Date(date_time.timestamp_millis() as f64, None))
+    } else if let Some(xml) = o.as_xml_object() {
+        // `is_string` is `true` for the AS3 XML class
+        Some(AmfValue::XML(
+            xml.node().xml_to_xml_string(activation).to_string(),
+            true,
+        ))
+    } else if let Some(bytearray) = o.as_bytearray() {
+        Some(AmfValue::ByteArray(bytearray.bytes().to_vec()))
+    } else {
+        let class = o.instance_class();
+        let name = class_to_alias(activation, class);
+
+        if is_externalizable(class) {
+            // Flash calls `writeExternal` with the output stream itself, which
+            // flash_lso has no way for us to hook into. The public properties are
+            // written instead, as if the class was an ordinary sealed class.
+            avm2_stub_method!(
+                activation,
+                "flash.utils.ByteArray",
+                "writeObject",
+                "with IExternalizable"
+            );
+        }
+
+        let mut attributes = EnumSet::empty();
+        if !class.is_sealed() {
+            attributes.insert(Attribute::Dynamic);
         }
+
+        let mut object_body = Vec::new();
+        let mut static_properties = Vec::new();
+        recursive_serialize(
+            activation,
+            o,
+            &mut object_body,
+            Some(&mut static_properties),
+            amf_version,
+            object_table,
+        )
+        .unwrap();
+        Some(AmfValue::Object(
+            id,
+            object_body,
+            if amf_version == AMFVersion::AMF3 {
+                Some(ClassDefinition {
+                    name,
+                    attributes,
+                    // FIXME - implement this
+                    static_properties,
+                })
+            } else {
+                None
+            },
+        ))
     }
 }
 
+fn is_externalizable(class: Class<'_>) -> bool {
+    class.all_interfaces().iter().any(|interface| {
+        let name = interface.name();
+        let local_name = name.local_name();
+        &*local_name == b"IExternalizable"
+            && name
+                .namespace()
+                .as_uri_opt()
+                .is_some_and(|uri| &*uri == b"flash.utils")
+    })
+}
+
 fn alias_to_class<'gc>(
     activation: &mut Activation<'_, 'gc>,
     alias: AvmString<'gc>,
@@ -241,35 +334,37 @@ fn get_or_create_element<'gc>(
     object_table: &mut ObjectTable<'gc>,
     amf_version: AMFVersion,
 ) -> Option<Element> {
-    if let Some(obj) = val.as_object() {
-        let rc_val = match object_table.get(&obj) {
-            Some(rc_val) => {
-                // Even though we'll clone the same 'Rc<AmfValue>' for each occurrence
-                // of 'Object', flash_lso doesn't serialize this correctly yet.
-                avm2_stub_method!(
-                    activation,
-                    "flash.utils.ByteArray",
-                    "writeObject",
-                    "with same Object used multiple times"
-                );
-                Some(rc_val.clone())
-            }
-            None => {
-                if let Some(value) = serialize_value(activation, val, amf_version, object_table) {
-                    let rc_val = Rc::new(value);
-                    // We cannot use Entry, since we need to pass in 'object_table' to 'serialize_value'
-                    object_table.insert(obj, rc_val.clone());
-                    Some(rc_val)
-                } else {
-                    None
-                }
-            }
-        };
-        return rc_val.map(|val| Element::new(name, val));
-    } else if let Some(value) = serialize_value(activation, val, amf_version, object_table) {
-        return Some(Element::new(name, Rc::new(value)));
+    serialize_value(activation, val, amf_version, object_table)
+        .map(|value| Element::new(name, Rc::new(value)))
+}
+
+/// The objects deserialized so far, so that references to them can be resolved.
+#[derive(Default)]
+pub struct ObjectMap<'gc, 'a> {
+    amf3: BTreeMap<ObjectId, Object<'gc>>,
+    amf0: BTreeMap<Reference, Object<'gc>>,
+
+    /// The decoder that read the values, which knows what AMF0 references refer to.
+    amf0_decoder: Option<&'a AMF0Decoder>,
+}
+
+impl<'gc, 'a> ObjectMap<'gc, 'a> {
+    pub fn with_amf0_decoder(decoder: &'a AMF0Decoder) -> Self {
+        Self {
+            amf0_decoder: Some(decoder),
+            ..Default::default()
+        }
+    }
+
+    fn insert(&mut self, id: ObjectId, val: &AmfValue, object: Object<'gc>) {
+        self.amf3.insert(id, object);
+        if let Some(reference) = self
+            .amf0_decoder
+            .and_then(|decoder| decoder.as_reference(val))
+        {
+            self.amf0.insert(reference, object);
+        }
     }
-    None
 }
 
 /// Deserialize a AmfValue to a Value
@@ -277,14 +372,22 @@ pub fn deserialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     val: &AmfValue,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let mut x = BTreeMap::new();
-    deserialize_value_impl(activation, val, &mut x)
+    deserialize_value_impl(activation, val, &mut ObjectMap::default())
+}
+
+/// Deserialize a AmfValue read by an AMF0 decoder to a Value
+pub fn deserialize_amf0_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    val: &AmfValue,
+    decoder: &AMF0Decoder,
+) -> Result<Value<'gc>, Error<'gc>> {
+    deserialize_value_impl(activation, val, &mut ObjectMap::with_amf0_decoder(decoder))
 }
 
 pub fn deserialize_value_impl<'gc>(
     activation: &mut Activation<'_, 'gc>,
     val: &AmfValue,
-    object_map: &mut BTreeMap<ObjectId, Object<'gc>>,
+    object_map: &mut ObjectMap<'gc, '_>,
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(match val {
         AmfValue::Null => Value::Null,
@@ -301,7 +404,7 @@ pub fn deserialize_value_impl<'gc>(
         AmfValue::ECMAArray(id, values, elements, _) => {
             let empty_storage = ArrayStorage::new(0);
             let array = ArrayObject::from_storage(activation, empty_storage)?;
-            object_map.insert(*id, array);
+            object_map.insert(*id, val, array);
 
             // First let's create an array out of `values` (dense portion), then we add the elements onto it.
             let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
@@ -326,7 +429,7 @@ pub fn deserialize_value_impl<'gc>(
         AmfValue::StrictArray(id, values) => {
             let empty_storage = ArrayStorage::new(0);
             let array = ArrayObject::from_storage(activation, empty_storage)?;
-            object_map.insert(*id, array);
+            object_map.insert(*id, val, array);
 
             let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
             for value in values {
@@ -348,7 +451,7 @@ pub fn deserialize_value_impl<'gc>(
                 activation.avm2().classes().object
             };
             let obj = target_class.construct(activation, &[])?;
-            object_map.insert(*id, obj);
+            object_map.insert(*id, val, obj);
 
             for entry in elements {
                 let name = entry.name();
@@ -430,7 +533,7 @@ pub fn deserialize_value_impl<'gc>(
                 activation,
             );
             let obj = VectorObject::from_vector(empty_storage, activation)?;
-            object_map.insert(*id, obj);
+            object_map.insert(*id, val, obj);
 
             let new_values = vec
                 .iter()
@@ -460,7 +563,7 @@ pub fn deserialize_value_impl<'gc>(
                 .classes()
                 .dictionary
                 .construct(activation, &[(*has_weak_keys).into()])?;
-            object_map.insert(*id, obj);
+            object_map.insert(*id, val, obj);
             let dict_obj = obj
                 .as_dictionary_object()
                 .expect("Failed to get dictionary from constructed object");
@@ -482,17 +585,20 @@ pub fn deserialize_value_impl<'gc>(
             tracing::error!("Deserialization not yet implemented for Custom: {:?}", val);
             Value::Undefined
         }
-        AmfValue::Reference(_) => {
-            tracing::error!(
-                "Deserialization not yet implemented for Reference: {:?}",
-                val
-            );
-            Value::Undefined
+        AmfValue::Reference(r) => {
+            if let Some(o) = object_map.amf0.get(r) {
+                (*o).into()
+            } else {
+                tracing::error!(
+                    "AMF0 deserializer got a reference {r:?} to an object we've not seen yet"
+                );
+                Value::Undefined
+            }
         }
         AmfValue::AMF3(val) => deserialize_value_impl(activation, val, object_map)?,
         AmfValue::Unsupported => Value::Undefined,
         AmfValue::Amf3ObjectReference(r) => {
-            if let Some(o) = object_map.get(r) {
+            if let Some(o) = object_map.amf3.get(r) {
                 (*o).into()
             } else {
                 tracing::error!("AMF3 deserializer got an object reference {r:?} to an object we've not seen yet");
@@ -506,6 +612,7 @@ pub fn deserialize_value_impl<'gc>(
 pub fn deserialize_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
     lso: &Lso,
+    decoder: &AMF0Decoder,
 ) -> Result<Object<'gc>, Error<'gc>> {
     let obj = activation
         .avm2()
@@ -513,10 +620,12 @@ pub fn deserialize_lso<'gc>(
         .object
         .construct(activation, &[])?;
 
+    // References can point to objects in other properties.
+    let mut object_map = ObjectMap::with_amf0_decoder(decoder);
     for child in &lso.body {
         obj.set_public_property(
             AvmString::new_utf8(activation.context.gc_context, &child.name),
-            deserialize_value(activation, child.value())?,
+            deserialize_value_impl(activation, child.value(), &mut object_map)?,
             activation,
         )?;
     }
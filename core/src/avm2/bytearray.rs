@@ -239,9 +239,18 @@ impl ByteArrayStorage {
                 encoder.read_to_end(&mut buffer).err().map(|e| e.into())
             }
             #[cfg(feature = "lzma")]
-            CompressionAlgorithm::Lzma => lzma_rs::lzma_compress(&mut &*self.bytes, &mut buffer)
-                .err()
-                .map(|e| e.into()),
+            CompressionAlgorithm::Lzma => {
+                // Flash always writes the uncompressed size into the header, and content
+                // reading the header itself relies on it.
+                let options = lzma_rs::compress::Options {
+                    unpacked_size: lzma_rs::compress::UnpackedSize::WriteToHeader(Some(
+                        self.bytes.len() as u64,
+                    )),
+                };
+                lzma_rs::lzma_compress_with_options(&mut &*self.bytes, &mut buffer, &options)
+                    .err()
+                    .map(|e| e.into())
+            }
             #[cfg(not(feature = "lzma"))]
             CompressionAlgorithm::Lzma => Some("Ruffle was not compiled with LZMA support".into()),
         };
@@ -257,6 +266,11 @@ impl ByteArrayStorage {
     pub fn decompress(&mut self, algorithm: CompressionAlgorithm) -> Option<Vec<u8>> {
         let mut buffer = Vec::new();
         let error: Option<Box<dyn std::error::Error>> = match algorithm {
+            // Flash has no way to provide a preset dictionary, so streams that need one
+            // can't be decompressed.
+            CompressionAlgorithm::Zlib if self.bytes.get(1).is_some_and(|flg| flg & 0x20 != 0) => {
+                Some("zlib stream requires a preset dictionary".into())
+            }
             CompressionAlgorithm::Zlib => {
                 let mut decoder = ZlibDecoder::new(&*self.bytes);
                 decoder.read_to_end(&mut buffer).err().map(|e| e.into())
@@ -265,10 +279,19 @@ impl ByteArrayStorage {
                 let mut decoder = DeflateDecoder::new(&*self.bytes);
                 decoder.read_to_end(&mut buffer).err().map(|e| e.into())
             }
+            // The header is 5 bytes of properties followed by the uncompressed size as a
+            // 64-bit integer. Flash decompresses into a buffer of that size, so it rejects
+            // sizes that don't fit in a ByteArray, including the "unknown size" marker.
             #[cfg(feature = "lzma")]
-            CompressionAlgorithm::Lzma => lzma_rs::lzma_decompress(&mut &*self.bytes, &mut buffer)
-                .err()
-                .map(|e| e.into()),
+            CompressionAlgorithm::Lzma => match self.bytes.get(5..13) {
+                Some(size) if u64::from_le_bytes(size.try_into().unwrap()) <= u32::MAX as u64 => {
+                    lzma_rs::lzma_decompress(&mut &*self.bytes, &mut buffer)
+                        .err()
+                        .map(|e| e.into())
+                }
+                Some(_) => Some("LZMA uncompressed size is too large".into()),
+                None => Some("LZMA header is truncated".into()),
+            },
             #[cfg(not(feature = "lzma"))]
             CompressionAlgorithm::Lzma => Some("Ruffle was not compiled with LZMA support".into()),
         };
@@ -497,3 +520,43 @@ impl Default for ByteArrayStorage {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zlib_preset_dictionary_is_rejected() {
+        let mut bytes = ByteArrayStorage::from_vec(b"hello".to_vec());
+        let mut compressed = ByteArrayStorage::from_vec(bytes.compress(CompressionAlgorithm::Zlib));
+        assert_eq!(
+            compressed.decompress(CompressionAlgorithm::Zlib).as_deref(),
+            Some(&b"hello"[..])
+        );
+
+        // Set FDICT, keeping the header check bits valid.
+        let mut with_dictionary = vec![0x78, 0xBB, 0x00, 0x00, 0x00, 0x01];
+        with_dictionary.extend_from_slice(&compressed.bytes()[2..]);
+        let mut with_dictionary = ByteArrayStorage::from_vec(with_dictionary);
+        assert_eq!(with_dictionary.decompress(CompressionAlgorithm::Zlib), None);
+    }
+
+    #[cfg(feature = "lzma")]
+    #[test]
+    fn lzma_header_has_uncompressed_size() {
+        let mut bytes = ByteArrayStorage::from_vec(vec![7; 300]);
+        let compressed = bytes.compress(CompressionAlgorithm::Lzma);
+        assert_eq!(&compressed[5..13], &300u64.to_le_bytes());
+
+        let mut compressed = ByteArrayStorage::from_vec(compressed);
+        assert_eq!(
+            compressed.decompress(CompressionAlgorithm::Lzma),
+            Some(vec![7; 300])
+        );
+
+        let mut unknown_size = compressed.bytes().to_vec();
+        unknown_size[5..13].copy_from_slice(&[0xFF; 8]);
+        let mut unknown_size = ByteArrayStorage::from_vec(unknown_size);
+        assert_eq!(unknown_size.decompress(CompressionAlgorithm::Lzma), None);
+    }
+}
@@ -0,0 +1,421 @@
+//! A debugger for AVM2 code, which external tools attach to through a `DebuggerBackend`.
+//!
+//! Breakpoints and stepping rely on the `debugfile` and `debugline` instructions, so they
+//! only work in movies compiled with debugging information (such as with `-debug=true`).
+//! While paused, the player waits on the backend from within the interpreter, so nothing
+//! else happens until the debugger resumes the movie. That's also why debuggers can't attach
+//! on the web: waiting would freeze the page, and no messages (such as from a `MessagePort`)
+//! could arrive in the meantime.
+//!
+//! # Protocol
+//!
+//! Every message is a JSON object on a line of its own, whose `type` says what it is.
+//!
+//! The debugger may send these at any time:
+//! - `{"type": "setBreakpoint", "file": "Main.as", "line": 12}` pauses before line 12 of
+//!   any source file whose path ends with `Main.as`.
+//! - `{"type": "clearBreakpoint", "file": "Main.as", "line": 12}`
+//! - `{"type": "setFunctionBreakpoint", "name": "Main/update"}` pauses once a function of
+//!   that name (as it's shown in stack traces) starts running.
+//! - `{"type": "clearFunctionBreakpoint", "name": "Main/update"}`
+//! - `{"type": "pause"}` pauses before the next line that runs.
+//!
+//! These are only answered while paused:
+//! - `{"type": "continue"}`, `{"type": "stepIn"}`, `{"type": "stepOver"}` and
+//!   `{"type": "stepOut"}` resume the movie.
+//! - `{"type": "stack"}` is answered with `{"type": "stack", "frames": ["Main/update()"]}`,
+//!   starting with the innermost call.
+//! - `{"type": "locals"}` is answered with
+//!   `{"type": "locals", "variables": [{"name": "i", "value": "3"}]}`, for the innermost call.
+//!
+//! The player sends `{"type": "paused", "reason": ..., "file": ..., "line": ..., "function": ...}`
+//! when it pauses, where the reason is `breakpoint`, `step`, `pause` or `bkpt` (for the `bkpt`
+//! instruction), and `{"type": "resumed"}` when it resumes. Commands that can't be carried out
+//! are answered with `{"type": "error", "message": ...}`.
+
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation, Value};
+use crate::context::UpdateContext;
+use crate::string::{AvmAtom, WString};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use web_time::Instant;
+
+/// What the debugging instructions of a call have said so far.
+#[derive(Default)]
+pub struct DebugInfo<'gc> {
+    /// The source file, from `debugfile`.
+    pub file: Option<AvmAtom<'gc>>,
+
+    /// The line that is running, from `debugline`, or 0 before the first one.
+    pub line: u32,
+
+    /// The names of local registers, from `debug`. These are only kept while a debugger
+    /// is attached.
+    pub locals: Vec<(u8, AvmAtom<'gc>)>,
+}
+
+/// Where the movie should pause next, besides its breakpoints.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Step {
+    #[default]
+    None,
+
+    /// Before the next line that runs, as the debugger asked to pause.
+    Pause,
+
+    /// Before the next line that runs.
+    In,
+
+    /// Before the next line that runs with at most the given number of calls on the stack.
+    Over(usize),
+
+    /// Before the next line that runs with fewer than the given number of calls on the stack.
+    Out(usize),
+}
+
+/// A command sent by the debugger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Command {
+    SetBreakpoint { file: String, line: u32 },
+    ClearBreakpoint { file: String, line: u32 },
+    SetFunctionBreakpoint(String),
+    ClearFunctionBreakpoint(String),
+    Pause,
+    Continue,
+    StepIn,
+    StepOver,
+    StepOut,
+    Stack,
+    Locals,
+}
+
+impl Command {
+    fn parse(message: &str) -> Result<Self, String> {
+        let message: serde_json::Value =
+            serde_json::from_str(message).map_err(|e| format!("Invalid message: {e}"))?;
+        let string = |key: &str| {
+            message[key]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("Missing {key}"))
+        };
+        let line = || {
+            message["line"]
+                .as_u64()
+                .and_then(|line| u32::try_from(line).ok())
+                .ok_or_else(|| "Missing line".to_string())
+        };
+
+        Ok(match message["type"].as_str() {
+            Some("setBreakpoint") => Command::SetBreakpoint {
+                file: normalize_path(string("file")?),
+                line: line()?,
+            },
+            Some("clearBreakpoint") => Command::ClearBreakpoint {
+                file: normalize_path(string("file")?),
+                line: line()?,
+            },
+            Some("setFunctionBreakpoint") => Command::SetFunctionBreakpoint(string("name")?),
+            Some("clearFunctionBreakpoint") => Command::ClearFunctionBreakpoint(string("name")?),
+            Some("pause") => Command::Pause,
+            Some("continue") => Command::Continue,
+            Some("stepIn") => Command::StepIn,
+            Some("stepOver") => Command::StepOver,
+            Some("stepOut") => Command::StepOut,
+            Some("stack") => Command::Stack,
+            Some("locals") => Command::Locals,
+            Some(other) => return Err(format!("Unknown command {other}")),
+            None => return Err("Missing type".to_string()),
+        })
+    }
+}
+
+/// The breakpoints and stepping state of an attached debugger.
+#[derive(Default)]
+pub struct Debugger {
+    attached: bool,
+
+    /// The source files with breakpoints (by the end of their path), by line.
+    breakpoints: HashMap<u32, HashSet<String>>,
+
+    function_breakpoints: HashSet<String>,
+
+    step: Step,
+}
+
+impl Debugger {
+    /// Returns whether a debugger is attached.
+    pub fn is_attached(&self) -> bool {
+        self.attached
+    }
+
+    /// Returns whether lines need to be checked for breakpoints and steps.
+    pub fn is_active(&self) -> bool {
+        self.attached
+            && (!self.breakpoints.is_empty()
+                || !self.function_breakpoints.is_empty()
+                || self.step != Step::None)
+    }
+
+    /// Handles the commands that the debugger sent while the movie was running.
+    pub fn poll(context: &mut UpdateContext<'_>) {
+        if !context.debugger.is_attached() {
+            if context.avm2.debugger.attached {
+                context.avm2.debugger = Debugger::default();
+            }
+            return;
+        }
+
+        context.avm2.debugger.attached = true;
+        while let Some(message) = context.debugger.try_receive() {
+            let result = Command::parse(&message)
+                .and_then(|command| context.avm2.debugger.configure(command));
+            if let Err(message) = result {
+                send_error(context, message);
+            }
+        }
+    }
+
+    /// Carries out a command that doesn't need the movie to be paused.
+    fn configure(&mut self, command: Command) -> Result<(), String> {
+        match command {
+            Command::SetBreakpoint { file, line } => {
+                self.breakpoints.entry(line).or_default().insert(file);
+            }
+            Command::ClearBreakpoint { file, line } => {
+                if let Some(files) = self.breakpoints.get_mut(&line) {
+                    files.remove(&file);
+                    if files.is_empty() {
+                        self.breakpoints.remove(&line);
+                    }
+                }
+            }
+            Command::SetFunctionBreakpoint(name) => {
+                self.function_breakpoints.insert(name);
+            }
+            Command::ClearFunctionBreakpoint(name) => {
+                self.function_breakpoints.remove(&name);
+            }
+            Command::Pause => self.step = Step::Pause,
+            _ => return Err("The movie isn't paused".to_string()),
+        }
+        Ok(())
+    }
+
+    /// Returns whether there is a breakpoint on the given line of the given source file.
+    fn has_breakpoint(&self, file: &str, line: u32) -> bool {
+        self.breakpoints.get(&line).is_some_and(|files| {
+            files.iter().any(|breakpoint| {
+                file == breakpoint
+                    || file
+                        .strip_suffix(breakpoint.as_str())
+                        .is_some_and(|directory| directory.ends_with('/'))
+            })
+        })
+    }
+}
+
+/// Pauses before the given line, if the debugger wants to.
+///
+/// `first_line` is whether this is the first line of the current call.
+pub fn on_line(activation: &mut Activation<'_, '_>, line: u32, first_line: bool) {
+    let debugger = &activation.context.avm2.debugger;
+    let depth = activation.context.avm2.call_stack().borrow().len();
+    let reason = match debugger.step {
+        Step::Pause => Some("pause"),
+        Step::In => Some("step"),
+        Step::Over(step_depth) if depth <= step_depth => Some("step"),
+        Step::Out(step_depth) if depth < step_depth => Some("step"),
+        _ => None,
+    };
+    let reason = reason.or_else(|| {
+        let file = activation.debug_file().map(normalize_path);
+        let on_breakpoint = file
+            .as_deref()
+            .is_some_and(|file| debugger.has_breakpoint(file, line));
+        let in_function = first_line
+            && !debugger.function_breakpoints.is_empty()
+            && debugger.function_breakpoints.contains(
+                current_function(activation)
+                    .trim_end_matches("()")
+                    .trim_end(),
+            );
+        (on_breakpoint || in_function).then_some("breakpoint")
+    });
+
+    if let Some(reason) = reason {
+        pause(activation, reason, line);
+    }
+}
+
+/// Pauses the movie until the debugger resumes it.
+pub fn pause(activation: &mut Activation<'_, '_>, reason: &str, line: u32) {
+    let depth = activation.context.avm2.call_stack().borrow().len();
+    let paused = json!({
+        "type": "paused",
+        "reason": reason,
+        "file": activation.debug_file().map(normalize_path),
+        "line": line,
+        "function": current_function(activation),
+    });
+    activation.context.debugger.send(paused.to_string());
+
+    loop {
+        let Some(message) = activation.context.debugger.receive() else {
+            // The debugger detached, so there's nothing left to pause for.
+            activation.context.avm2.debugger = Debugger::default();
+            break;
+        };
+        let command = match Command::parse(&message) {
+            Ok(command) => command,
+            Err(message) => {
+                send_error(activation.context, message);
+                continue;
+            }
+        };
+
+        let step = match command {
+            Command::Continue => Step::None,
+            Command::StepIn => Step::In,
+            Command::StepOver => Step::Over(depth),
+            Command::StepOut => Step::Out(depth),
+            Command::Stack => {
+                let frames: Vec<_> = activation
+                    .context
+                    .avm2
+                    .call_stack()
+                    .borrow()
+                    .calls()
+                    .map(|call| {
+                        let mut frame = WString::new();
+                        call.display(&mut frame);
+                        frame.to_string()
+                    })
+                    .collect();
+                let stack = json!({ "type": "stack", "frames": frames });
+                activation.context.debugger.send(stack.to_string());
+                continue;
+            }
+            Command::Locals => {
+                let variables: Vec<_> = activation
+                    .debug_locals()
+                    .into_iter()
+                    .map(|(name, value)| {
+                        json!({ "name": name, "value": describe(activation, value) })
+                    })
+                    .collect();
+                let locals = json!({ "type": "locals", "variables": variables });
+                activation.context.debugger.send(locals.to_string());
+                continue;
+            }
+            command => {
+                if let Err(message) = activation.context.avm2.debugger.configure(command) {
+                    send_error(activation.context, message);
+                }
+                continue;
+            }
+        };
+
+        activation.context.avm2.debugger.step = step;
+        let resumed = json!({ "type": "resumed" });
+        activation.context.debugger.send(resumed.to_string());
+        break;
+    }
+
+    // The time spent paused doesn't count towards the script timeout.
+    activation.context.update_start = Instant::now();
+}
+
+fn send_error(context: &mut UpdateContext<'_>, message: String) {
+    let error = json!({ "type": "error", "message": message });
+    context.debugger.send(error.to_string());
+}
+
+/// The name of the innermost call, as it's shown in stack traces.
+fn current_function(activation: &Activation<'_, '_>) -> String {
+    let mut name = WString::new();
+    if let Some(call) = activation.context.avm2.call_stack().borrow().calls().next() {
+        call.display(&mut name);
+    }
+    name.to_string()
+}
+
+/// Describes a value without running any code, unlike converting it to a string.
+fn describe<'gc>(activation: &mut Activation<'_, 'gc>, value: Value<'gc>) -> String {
+    match value {
+        Value::String(string) => format!("{:?}", string.to_string()),
+        Value::Object(object) => {
+            format!("[object {}]", object.instance_class().name().local_name())
+        }
+        // Converting primitives never runs any code.
+        value => value
+            .coerce_to_string(activation)
+            .map(|string| string.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Compilers separate the directories of source files in different ways (Flex uses
+/// `C:\src;com\example;Main.as`), so they're all turned into slashes.
+fn normalize_path(path: impl ToString) -> String {
+    path.to_string().replace([';', '\\'], "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(
+            Command::parse(
+                r#"{"type": "setBreakpoint", "file": "com\\example\\Main.as", "line": 12}"#
+            ),
+            Ok(Command::SetBreakpoint {
+                file: "com/example/Main.as".to_string(),
+                line: 12
+            })
+        );
+        assert_eq!(
+            Command::parse(r#"{"type": "stepOver"}"#),
+            Ok(Command::StepOver)
+        );
+        assert!(Command::parse(r#"{"type": "setBreakpoint", "file": "Main.as"}"#).is_err());
+        assert!(Command::parse(r#"{"type": "explode"}"#).is_err());
+        assert!(Command::parse("not json").is_err());
+    }
+
+    #[test]
+    fn matches_breakpoints_by_the_end_of_the_path() {
+        let mut debugger = Debugger::default();
+        debugger
+            .configure(Command::SetBreakpoint {
+                file: "example/Main.as".to_string(),
+                line: 12,
+            })
+            .unwrap();
+
+        assert!(debugger.has_breakpoint("C:/src/com/example/Main.as", 12));
+        assert!(debugger.has_breakpoint("example/Main.as", 12));
+        assert!(!debugger.has_breakpoint("C:/src/com/example/Main.as", 13));
+        assert!(!debugger.has_breakpoint("C:/src/com/otherexample/Main.as", 12));
+
+        debugger
+            .configure(Command::ClearBreakpoint {
+                file: "example/Main.as".to_string(),
+                line: 12,
+            })
+            .unwrap();
+        assert!(!debugger.has_breakpoint("C:/src/com/example/Main.as", 12));
+        assert!(debugger.breakpoints.is_empty());
+    }
+
+    #[test]
+    fn stepping_needs_a_pause() {
+        let mut debugger = Debugger::default();
+        assert!(debugger.configure(Command::StepIn).is_err());
+        assert!(debugger.configure(Command::Pause).is_ok());
+        assert_eq!(debugger.step, Step::Pause);
+    }
+}
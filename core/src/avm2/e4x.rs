@@ -1118,7 +1118,16 @@ impl<'gc> E4XNode<'gc> {
             ResolveResult::Unknown(ns) => {
                 return Err(make_unknown_ns_error(activation, ns, name));
             }
-            ResolveResult::Unbound => None,
+            // Unprefixed elements without an `xmlns` declaration in scope
+            // belong to the default xml namespace.
+            ResolveResult::Unbound => activation
+                .default_xml_namespace()
+                .and_then(|ns| ns.as_uri_opt())
+                .filter(|uri| !uri.is_empty())
+                .map(|uri| E4XNamespace {
+                    prefix: Some(activation.strings().empty()),
+                    uri,
+                }),
         };
 
         let data = E4XNodeData {
@@ -1330,6 +1339,7 @@ impl<'gc> E4XNode<'gc> {
 
         name.namespace_set()
             .iter()
+            .filter(|ns| ns.is_namespace())
             .any(|ns| ns.as_uri_opt().expect("NS set cannot contain Any") == self_ns)
     }
 
@@ -1458,9 +1468,9 @@ pub fn escape_attribute_value(s: AvmString) -> WString {
 }
 
 // Implementation of `EscapeElementValue` from ECMA-357 (10.2.1.1)
-pub fn escape_element_value(s: AvmString) -> WString {
+pub fn escape_element_value(s: &WStr) -> WString {
     let mut r = WString::with_capacity(s.len(), s.is_wide());
-    for c in &s {
+    for c in s {
         let escape: &[u8] = match u8::try_from(c) {
             Ok(b'<') => b"&lt;",
             Ok(b'>') => b"&gt;",
@@ -1493,8 +1503,12 @@ fn to_xml_string_inner<'gc>(
 
     let (children, attributes) = match &*node_kind {
         E4XNodeKind::Text(text) => {
-            // FIXME: Spec says to trim XMLWhitespace characters here
-            buf.push_str(&escape_element_value(*text));
+            if pretty.is_some() {
+                let trimmed = text.trim_matches(|c| matches!(c, 0x09 | 0x0A | 0x0D | 0x20));
+                buf.push_str(&escape_element_value(trimmed));
+            } else {
+                buf.push_str(&escape_element_value(text));
+            }
             return;
         }
         E4XNodeKind::ProcessingInstruction(value) => {
@@ -1655,19 +1669,23 @@ fn to_xml_string_inner<'gc>(
     buf.push_char('>');
 }
 
-// Implementation of `ToXMLString` from ECMA-357 (10.2.1)
-pub fn to_xml_string<'gc>(
-    xml: E4XOrXml<'gc>,
-    activation: &mut Activation<'_, 'gc>,
-) -> AvmString<'gc> {
-    let pretty_printing = activation
+/// Returns the current value of `XML.prettyPrinting`.
+pub fn is_pretty_printing(activation: &mut Activation<'_, '_>) -> bool {
+    activation
         .avm2()
         .classes()
         .xml
         .get_public_property("prettyPrinting", activation)
         .expect("prettyPrinting should be set")
-        .coerce_to_boolean();
-    let pretty = if pretty_printing {
+        .coerce_to_boolean()
+}
+
+// Implementation of `ToXMLString` from ECMA-357 (10.2.1)
+pub fn to_xml_string<'gc>(
+    xml: E4XOrXml<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> AvmString<'gc> {
+    let pretty = if is_pretty_printing(activation) {
         let pretty_indent = activation
             .avm2()
             .classes()
@@ -1708,7 +1726,10 @@ pub fn string_to_multiname<'gc>(
     } else if &*name == b"*" {
         Multiname::any()
     } else {
-        Multiname::new(activation.avm2().namespaces.public_all(), name)
+        let namespace = activation
+            .default_xml_namespace()
+            .unwrap_or_else(|| activation.avm2().namespaces.public_all());
+        Multiname::new(namespace, name)
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::jit;
 use crate::avm2::method::{Method, ParamConfig};
 use crate::avm2::object::{ClassObject, Object};
 use crate::avm2::scope::ScopeChain;
@@ -152,6 +153,7 @@ pub fn exec<'gc>(
         Method::Native(bm) => {
             let caller_domain = activation.caller_domain();
             let caller_movie = activation.caller_movie();
+            let default_xml_namespace = activation.default_xml_namespace();
             let mut activation = Activation::from_builtin(
                 activation.context,
                 bound_superclass,
@@ -160,6 +162,7 @@ pub fn exec<'gc>(
                 caller_domain,
                 caller_movie,
             );
+            activation.set_default_xml_namespace(default_xml_namespace);
 
             if arguments.len() > bm.signature.len() && !bm.is_variadic {
                 return Err(format!(
@@ -196,13 +199,14 @@ pub fn exec<'gc>(
                 span
             };
 
+            profile_enter(&mut activation, &method, bound_class);
             activation
                 .context
                 .avm2
                 .push_call(activation.context.gc_context, method, bound_class);
             (bm.method)(&mut activation, receiver, &arguments)
         }
-        Method::Bytecode(bm) => {
+        Method::Bytecode(bm) => 'bytecode: {
             if bm.is_unchecked() {
                 let max_args = bm.signature().len();
                 if arguments.len() > max_args && !bm.is_variadic() {
@@ -210,9 +214,24 @@ pub fn exec<'gc>(
                 }
             }
 
+            if let Some(compiled) = jit::compiled_method(bm, activation) {
+                if let Some(args) = compiled.coerce_arguments(arguments, activation) {
+                    let args = args?;
+                    profile_enter(activation, &method, bound_class);
+                    activation.context.avm2.push_call(
+                        activation.context.gc_context,
+                        method,
+                        bound_class,
+                    );
+                    break 'bytecode compiled.run(&args, activation);
+                }
+            }
+
             // This used to be a one step called Activation::from_method,
             // but avoiding moving an Activation around helps perf
+            let default_xml_namespace = activation.default_xml_namespace();
             let mut activation = Activation::from_nothing(activation.context);
+            activation.set_default_xml_namespace(default_xml_namespace);
             activation.init_from_method(
                 bm,
                 scope,
@@ -239,6 +258,7 @@ pub fn exec<'gc>(
                 span
             };
 
+            profile_enter(&mut activation, &method, bound_class);
             activation
                 .context
                 .avm2
@@ -250,9 +270,40 @@ pub fn exec<'gc>(
         .context
         .avm2
         .pop_call(activation.context.gc_context);
+    if activation.context.profiler.is_active() {
+        let allocated = activation
+            .context
+            .gc_context
+            .metrics()
+            .total_gc_allocation();
+        activation.context.profiler.exit(allocated);
+    }
     ret
 }
 
+/// Records that a method was entered, if a profile is being taken.
+fn profile_enter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &Method<'gc>,
+    bound_class: Option<Class<'gc>>,
+) {
+    if activation.context.profiler.is_active() {
+        let allocated = activation
+            .context
+            .gc_context
+            .metrics()
+            .total_gc_allocation();
+        activation.context.profiler.enter(
+            || {
+                let mut name = WString::new();
+                display_function(&mut name, method, bound_class);
+                name.to_utf8_lossy().into_owned()
+            },
+            allocated,
+        );
+    }
+}
+
 impl fmt::Debug for BoundMethod<'_> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.method {
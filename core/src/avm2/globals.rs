@@ -103,10 +103,15 @@ pub struct SystemClasses<'gc> {
     pub date: ClassObject<'gc>,
     pub qname: ClassObject<'gc>,
     pub mouseevent: ClassObject<'gc>,
+    pub touchevent: ClassObject<'gc>,
+    pub gestureevent: ClassObject<'gc>,
+    pub transformgestureevent: ClassObject<'gc>,
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
+    pub locationchangeevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
@@ -131,6 +136,7 @@ pub struct SystemClasses<'gc> {
     pub error: ClassObject<'gc>,
     pub uncaughterrorevents: ClassObject<'gc>,
     pub statictext: ClassObject<'gc>,
+    pub textsnapshot: ClassObject<'gc>,
     pub textlinemetrics: ClassObject<'gc>,
     pub stage3d: ClassObject<'gc>,
     pub context3d: ClassObject<'gc>,
@@ -261,10 +267,15 @@ impl<'gc> SystemClasses<'gc> {
             date: object,
             qname: object,
             mouseevent: object,
+            touchevent: object,
+            gestureevent: object,
+            transformgestureevent: object,
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
+            dataevent: object,
             errorevent: object,
+            locationchangeevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
             transform: object,
@@ -289,6 +300,7 @@ impl<'gc> SystemClasses<'gc> {
             error: object,
             uncaughterrorevents: object,
             statictext: object,
+            textsnapshot: object,
             textlinemetrics: object,
             stage3d: object,
             context3d: object,
@@ -905,13 +917,22 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "DataEvent", dataevent),
             ("flash.events", "ErrorEvent", errorevent),
+            ("flash.events", "LocationChangeEvent", locationchangeevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
             ("flash.events", "HTTPStatusEvent", httpstatusevent),
             ("flash.events", "SecurityErrorEvent", securityerrorevent),
             ("flash.events", "IOErrorEvent", ioerrorevent),
             ("flash.events", "MouseEvent", mouseevent),
+            ("flash.events", "TouchEvent", touchevent),
+            ("flash.events", "GestureEvent", gestureevent),
+            (
+                "flash.events",
+                "TransformGestureEvent",
+                transformgestureevent
+            ),
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.events", "NetStatusEvent", netstatusevent),
@@ -940,6 +961,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.text", "TextFormat", textformat),
             ("flash.text", "TextField", textfield),
             ("flash.text", "TextLineMetrics", textlinemetrics),
+            ("flash.text", "TextSnapshot", textsnapshot),
             ("flash.text", "TextRun", textrun),
             ("flash.text.engine", "TextLine", textline),
             ("flash.filters", "BevelFilter", bevelfilter),
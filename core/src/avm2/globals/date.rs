@@ -5,7 +5,7 @@ pub use crate::avm2::object::date_allocator;
 use crate::avm2::object::{DateObject, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::locale::{get_current_date_time, get_timezone};
+use crate::locale::get_timezone;
 use crate::string::{utils as string_utils, AvmString, WStr};
 use chrono::{DateTime, Datelike, Duration, FixedOffset, LocalResult, TimeZone, Timelike, Utc};
 use num_traits::ToPrimitive;
@@ -234,7 +234,7 @@ pub fn init<'gc>(
             }
         }
     } else {
-        this.set_date_time(Some(get_current_date_time()))
+        this.set_date_time(Some(activation.context.current_date_time()))
     }
 
     Ok(Value::Undefined)
@@ -887,6 +887,9 @@ pub fn to_utc_string<'gc>(
 }
 
 /// Implements the `toLocaleString` method.
+///
+/// Like Flash Player, this always uses English names, whatever the locale of the user.
+/// `flash.globalization.DateTimeFormatter` is what formats dates for a locale.
 pub fn to_locale_string<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
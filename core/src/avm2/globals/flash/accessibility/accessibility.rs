@@ -0,0 +1,25 @@
+//! `flash.accessibility.Accessibility` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Object};
+use crate::display_object::TDisplayObject;
+
+pub fn get_active<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.is_accessibility_active().into())
+}
+
+pub fn update_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // `accessibilityProperties` is read again after the current frame.
+    let stage = activation.context.stage;
+    stage.invalidate_accessibility(activation.gc());
+    Ok(Value::Undefined)
+}
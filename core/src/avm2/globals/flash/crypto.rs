@@ -24,9 +24,12 @@ pub fn generate_random_bytes<'gc>(
     let mut ba_write = ba.as_bytearray_mut().unwrap();
     ba_write.set_length(length as usize);
 
-    let mut rng = OsRng {};
-
-    rng.fill_bytes(ba_write.bytes_mut());
+    // Replays need these bytes to be reproducible, so they come from the seeded generator.
+    if activation.context.replay_clock.is_some() {
+        activation.context.rng.fill_bytes(ba_write.bytes_mut());
+    } else {
+        OsRng.fill_bytes(ba_write.bytes_mut());
+    }
 
     Ok(ba.into())
 }
@@ -1,8 +1,8 @@
 package flash.desktop {
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_method;
-    import flash.system.System;
 
+    // TODO: Every instance uses the system clipboard, but clipboards created
+    // with `new Clipboard()` in AIR should only hold data for drag and drop.
     public class Clipboard {
         private static var _generalClipboard = new Clipboard();
 
@@ -15,36 +15,24 @@ package flash.desktop {
         }
 
         public function get formats(): Array {
-            stub_getter("flash.desktop.Clipboard", "formats");
-            return new Array();
+            var formats = new Array();
+            for each (var format in [ClipboardFormats.TEXT_FORMAT, ClipboardFormats.BITMAP_FORMAT]) {
+                if (hasFormat(format)) {
+                    formats.push(format);
+                }
+            }
+            return formats;
         }
 
-        public function clear(): void {
-            stub_method("flash.desktop.Clipboard", "clear");
-        }
+        public native function clear(): void;
 
-        public function clearData(format: String): void {
-            stub_method("flash.desktop.Clipboard", "clearData");
-        }
+        public native function clearData(format: String): void;
 
-        public function getData(format: String, transferMode: String = ClipboardTransferMode.ORIGINAL_PREFERRED): Object {
-            stub_method("flash.desktop.Clipboard", "getData");
-            return null;
-        }
+        public native function getData(format: String, transferMode: String = ClipboardTransferMode.ORIGINAL_PREFERRED): Object;
 
-        public function hasFormat(format: String): Boolean {
-            stub_method("flash.desktop.Clipboard", "hasFormat");
-            return false;
-        }
+        public native function hasFormat(format: String): Boolean;
 
-        public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setData");
-            if (format == ClipboardFormats.TEXT_FORMAT) {
-                System.setClipboard(data);
-                return true;
-            }
-            return false;
-        }
+        public native function setData(format: String, data: Object, serializable: Boolean = true): Boolean;
 
         public function setDataHandler(format: String, handler: Function, serializable: Boolean = true): Boolean {
             stub_method("flash.desktop.Clipboard", "setDataHandler");
@@ -0,0 +1,166 @@
+//! `flash.desktop.Clipboard` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::security_error;
+use crate::avm2::object::{BitmapDataObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::ClipboardImage;
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, Color};
+use crate::player::PlayerRuntime;
+use crate::string::AvmString;
+use gc_arena::GcCell;
+
+/// The formats that can be exchanged with the system clipboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ClipboardFormat {
+    Text,
+    Bitmap,
+}
+
+impl ClipboardFormat {
+    fn from_name(name: AvmString) -> Option<Self> {
+        if &name == b"air:text" {
+            Some(Self::Text)
+        } else if &name == b"air:bitmap" {
+            Some(Self::Bitmap)
+        } else {
+            None
+        }
+    }
+}
+
+/// Implements `Clipboard.clear`
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.ui.set_clipboard_content(String::new());
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.clearData`
+pub fn clear_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = args.get_string(activation, 0)?;
+    match ClipboardFormat::from_name(format) {
+        Some(ClipboardFormat::Text) => activation.context.ui.clear_clipboard_content(),
+        Some(ClipboardFormat::Bitmap) => activation.context.ui.clear_clipboard_image(),
+        None => {}
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.hasFormat`
+pub fn has_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = args.get_string(activation, 0)?;
+    Ok(is_available(activation, ClipboardFormat::from_name(format)).into())
+}
+
+/// Whether the clipboard has content of the given format.
+fn is_available(activation: &mut Activation<'_, '_>, format: Option<ClipboardFormat>) -> bool {
+    match format {
+        Some(ClipboardFormat::Text) => !activation.context.ui.clipboard_content().is_empty(),
+        Some(ClipboardFormat::Bitmap) => activation.context.ui.clipboard_image().is_some(),
+        None => false,
+    }
+}
+
+/// Implements `Clipboard.getData`
+pub fn get_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // AIR applications may read the clipboard at any time.
+    if activation.avm2().player_runtime == PlayerRuntime::FlashPlayer && !activation.avm2().pasting
+    {
+        return Err(Error::AvmError(security_error(
+            activation,
+            "Error #2179: The Clipboard.generalClipboard object may only be read while processing a flash.events.Event.PASTE event.",
+            2179,
+        )?));
+    }
+
+    let format = args.get_string(activation, 0)?;
+    match ClipboardFormat::from_name(format) {
+        Some(ClipboardFormat::Text) => {
+            let content = activation.context.ui.clipboard_content();
+            if content.is_empty() {
+                return Ok(Value::Null);
+            }
+            Ok(AvmString::new_utf8(activation.context.gc_context, content).into())
+        }
+        Some(ClipboardFormat::Bitmap) => {
+            let Some(image) = activation.context.ui.clipboard_image() else {
+                return Ok(Value::Null);
+            };
+            let pixels = image
+                .rgba
+                .chunks_exact(4)
+                .map(|p| Color::argb(p[3], p[0], p[1], p[2]).to_premultiplied_alpha(true))
+                .collect();
+            let bitmap_data = BitmapData::new_with_pixels(image.width, image.height, true, pixels);
+            let class = activation.avm2().classes().bitmapdata;
+            Ok(BitmapDataObject::from_bitmap_data_internal(
+                activation,
+                BitmapDataWrapper::new(GcCell::new(activation.context.gc_context, bitmap_data)),
+                class,
+            )?
+            .into())
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `Clipboard.setData`
+pub fn set_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = args.get_string(activation, 0)?;
+    match ClipboardFormat::from_name(format) {
+        Some(ClipboardFormat::Text) => {
+            let content = args.get_string(activation, 1)?.to_string();
+            activation.context.ui.set_clipboard_content(content);
+            Ok(true.into())
+        }
+        Some(ClipboardFormat::Bitmap) => {
+            let Some(bitmap_data) = args[1].as_object().and_then(|o| o.as_bitmap_data()) else {
+                return Ok(false.into());
+            };
+            bitmap_data.check_valid(activation)?;
+
+            let bitmap_data = bitmap_data.sync(activation.context.renderer);
+            let bitmap_data = bitmap_data.read();
+            let rgba = bitmap_data
+                .pixels()
+                .iter()
+                .flat_map(|p| {
+                    let p = p.to_un_multiplied_alpha();
+                    [p.red(), p.green(), p.blue(), p.alpha()]
+                })
+                .collect();
+            let image = ClipboardImage {
+                width: bitmap_data.width(),
+                height: bitmap_data.height(),
+                rgba,
+            };
+            drop(bitmap_data);
+
+            activation.context.ui.set_clipboard_image(image);
+            Ok(true.into())
+        }
+        None => Ok(false.into()),
+    }
+}
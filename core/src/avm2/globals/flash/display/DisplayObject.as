@@ -9,6 +9,7 @@ package flash.display {
     import flash.display.DisplayObjectContainer;
     import flash.display.LoaderInfo;
     import flash.display.Stage;
+    import flash.geom.Matrix;
     import flash.geom.Point;
     import flash.geom.Vector3D;
     import flash.events.EventDispatcher;
@@ -122,6 +123,9 @@ package flash.display {
         public native function get cacheAsBitmap():Boolean;
         public native function set cacheAsBitmap(value:Boolean):void;
 
+        public native function get cacheAsBitmapMatrix():Matrix;
+        public native function set cacheAsBitmapMatrix(value:Matrix):void;
+
         public native function hitTestPoint(x:Number, y:Number, shapeFlag:Boolean = false):Boolean;
 
         public native function hitTestObject(obj:DisplayObject):Boolean;
@@ -0,0 +1,10 @@
+package flash.display
+{
+  [API("661")]
+  public final class FocusDirection
+  {
+    public static const BOTTOM:String = "bottom";
+    public static const NONE:String = "none";
+    public static const TOP:String = "top";
+  }
+}
@@ -6,6 +6,7 @@ package flash.display
   import flash.events.Event;
   import flash.events.EventDispatcher;
   import flash.desktop.NativeApplication;
+  import flash.errors.IllegalOperationError;
   import __ruffle__.stub_method;
   import __ruffle__.stub_getter;
   import __ruffle__.stub_setter;
@@ -18,9 +19,10 @@ package flash.display
     public const systemMinSize:Point = new Point(1, 1);
     public var minSize:Point = systemMinSize;
     public var maxSize:Point = systemMaxSize;
-    public var title:String;
     public var alwaysInFront:Boolean = true;
-    public var visible:Boolean = true;
+
+    private var _title:String;
+    private var _visible:Boolean = true;
 
     private var _bounds:Rectangle;
     private var _maximizable:Boolean;
@@ -32,18 +34,32 @@ package flash.display
     private var _closed:Boolean = false;
     private var _stage:Stage;
 
+    // Whether this window was opened by the movie, rather than being the player itself.
+    private var _opened:Boolean = false;
+
     public function NativeWindow(initOptions:NativeWindowInitOptions, _stage:Stage = null)
     {
-      stub_constructor("flash.display.NativeWindow");
-      NativeApplication.nativeApplication.openedWindows.push(this);
       if (_stage)
       {
+        stub_constructor("flash.display.NativeWindow");
         this._stage = _stage;
         _stage.addEventListener(Event.RESIZE, function(e:Event):void
         {
           dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZE, false, false, _bounds, _bounds = new Rectangle(x, y, width, height)));
         });
       }
+      else
+      {
+        // Throws if too many windows are already open.
+        this._stage = openWindow(initOptions.transparent);
+        this._stage._nativeWindow = this;
+        this._opened = true;
+        // Windows opened by the movie stay hidden until they are activated or made visible.
+        this._visible = false;
+        this._bounds = new Rectangle(0, 0, 550, 400);
+        syncWindow();
+      }
+      NativeApplication.nativeApplication.openedWindows.push(this);
 
       _maximizable = initOptions.maximizable;
       _minimizable = initOptions.minimizable;
@@ -53,62 +69,151 @@ package flash.display
       _type = initOptions.type;
     }
 
+    public function get title():String
+    {
+      return _title;
+    }
+
+    public function set title(value:String):void
+    {
+      checkClosed();
+      _title = value;
+      syncWindow();
+    }
+
+    public function get visible():Boolean
+    {
+      return _visible;
+    }
+
+    public function set visible(value:Boolean):void
+    {
+      checkClosed();
+      _visible = value;
+      syncWindow();
+    }
+
     public function get width():Number
     {
+      if (_opened)
+      {
+        return _bounds.width;
+      }
       stub_getter("flash.display.NativeWindow", "width");
       return _stage.stageWidth;
     }
 
     public function set width(value:Number):void
     {
+      if (_opened)
+      {
+        bounds = new Rectangle(_bounds.x, _bounds.y, value, _bounds.height);
+        return;
+      }
       stub_setter("flash.display.NativeWindow", "width");
       _stage.stageWidth = value;
     }
 
     public function get height():Number
     {
+      if (_opened)
+      {
+        return _bounds.height;
+      }
       stub_getter("flash.display.NativeWindow", "height");
       return _stage.stageHeight;
     }
 
     public function set height(value:Number):void
     {
+      if (_opened)
+      {
+        bounds = new Rectangle(_bounds.x, _bounds.y, _bounds.width, value);
+        return;
+      }
       stub_setter("flash.display.NativeWindow", "height");
       _stage.stageHeight = value;
     }
 
     public function get x():Number
     {
+      if (_opened)
+      {
+        return _bounds.x;
+      }
       stub_getter("flash.display.NativeWindow", "x");
       return _stage.x;
     }
 
     public function set x(value:Number):void
     {
+      if (_opened)
+      {
+        bounds = new Rectangle(value, _bounds.y, _bounds.width, _bounds.height);
+        return;
+      }
       stub_setter("flash.display.NativeWindow", "x");
     }
 
     public function get y():Number
     {
+      if (_opened)
+      {
+        return _bounds.y;
+      }
       stub_getter("flash.display.NativeWindow", "y");
       return _stage.y;
     }
 
     public function set y(value:Number):void
     {
+      if (_opened)
+      {
+        bounds = new Rectangle(_bounds.x, value, _bounds.width, _bounds.height);
+        return;
+      }
       stub_setter("flash.display.NativeWindow", "y");
     }
 
     public function get bounds():Rectangle
     {
+      if (_opened)
+      {
+        return _bounds.clone();
+      }
       stub_getter("flash.display.NativeWindow", "bounds");
       return _bounds;
     }
 
     public function set bounds(value:Rectangle):void
     {
-      stub_setter("flash.display.NativeWindow", "bounds");
-      _bounds = value;
+      if (!_opened)
+      {
+        stub_setter("flash.display.NativeWindow", "bounds");
+        _bounds = value;
+        return;
+      }
+
+      checkClosed();
+      var before:Rectangle = _bounds;
+      var after:Rectangle = new Rectangle(
+        value.x,
+        value.y,
+        Math.min(Math.max(value.width, minSize.x), maxSize.x),
+        Math.min(Math.max(value.height, minSize.y), maxSize.y)
+      );
+      _bounds = after;
+      syncWindow();
+
+      if (before.x != after.x || before.y != after.y)
+      {
+        dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.MOVE, false, false, before, after));
+      }
+      if (before.width != after.width || before.height != after.height)
+      {
+        dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZE, false, false, before, after));
+        _stage.dispatchEvent(new Event(Event.RESIZE));
+      }
     }
 
     public function get maximizable():Boolean
@@ -155,6 +260,15 @@ package flash.display
     // Activates this window.
     public function activate():void
     {
+      if (_opened)
+      {
+        checkClosed();
+        _visible = true;
+        syncWindow();
+        // This dispatches `activate`, and `deactivate` on any other active window.
+        activateWindow();
+        return;
+      }
       stub_method("flash.display.NativeWindow", "activate");
       dispatchEvent(new Event(Event.ACTIVATE));
     }
@@ -162,10 +276,23 @@ package flash.display
     // Closes this window.
     public function close():void
     {
-      stub_method("flash.display.NativeWindow", "close");
+      if (_closed)
+      {
+        return;
+      }
+      if (!_opened)
+      {
+        stub_method("flash.display.NativeWindow", "close");
+      }
       if (dispatchEvent(new Event(Event.CLOSING, false, true)))
       {
         _closed = true;
+        if (_opened)
+        {
+          closeWindow();
+          var windows:Array = NativeApplication.nativeApplication.openedWindows;
+          windows.splice(windows.indexOf(this), 1);
+        }
         dispatchEvent(new Event(Event.CLOSE));
         dispatchEvent(new Event(Event.DEACTIVATE));
       }
@@ -255,6 +382,10 @@ package flash.display
 
     public function get active():Boolean
     {
+      if (_opened)
+      {
+        return isWindowActive();
+      }
       stub_getter("flash.display.NativeWindow", "active");
       return true;
     }
@@ -308,5 +439,27 @@ package flash.display
       stub_getter("flash.display.NativeWindow", "supportsTransparency");
       return false;
     }
+
+    private function checkClosed():void
+    {
+      if (_closed)
+      {
+        throw new IllegalOperationError("Error #3200: Cannot perform operation on closed window.", 3200);
+      }
+    }
+
+    private function syncWindow():void
+    {
+      if (_opened && !_closed)
+      {
+        updateWindow(_title == null ? "" : _title, _bounds.x, _bounds.y, _bounds.width, _bounds.height, _visible);
+      }
+    }
+
+    private native function openWindow(transparent:Boolean):Stage;
+    private native function updateWindow(title:String, x:Number, y:Number, width:Number, height:Number, visible:Boolean):void;
+    private native function activateWindow():void;
+    private native function isWindowActive():Boolean;
+    private native function closeWindow():void;
   }
 }
@@ -13,8 +13,8 @@ package flash.display {
     [Ruffle(Abstract)]
     public class Stage extends DisplayObjectContainer {
         private var _colorCorrection:String = ColorCorrection.DEFAULT;
-        private var _mouseLock:Boolean = false;
-        private var _nativeWindow:NativeWindow;
+        // Set by `NativeWindow` for the stages of windows opened by the movie.
+        internal var _nativeWindow:NativeWindow;
         private var _fullScreenSourceRect:Rectangle;
 
         override public function set accessibilityProperties(value:AccessibilityProperties):void {
@@ -276,16 +276,10 @@ package flash.display {
         }
 
         [API("678")]
-        public function get mouseLock():Boolean {
-            stub_getter("flash.display.Stage", "mouseLock");
-            return this._mouseLock;
-        }
+        public native function get mouseLock():Boolean;
 
         [API("678")]
-        public function set mouseLock(value:Boolean):void {
-            stub_setter("flash.display.Stage", "mouseLock");
-            this._mouseLock = value;
-        }
+        public native function set mouseLock(value:Boolean):void;
 
         [API("668")]
         public static function get supportsOrientationChange():Boolean {
@@ -332,6 +326,9 @@ package flash.display {
             stub_method("flash.display.Stage", "setAspectRatio");
         }
 
+        [API("661")]
+        public native function assignFocus(objectToFocus:InteractiveObject, direction:String):void;
+
         [API("661")]
         public function get nativeWindow():NativeWindow {
             stub_getter("flash.display.Stage", "nativeWindow");
@@ -364,6 +364,7 @@ pub fn get_vector<'gc>(
         let pixels = operations::get_vector(
             bitmap_data,
             activation.context.renderer,
+            activation.context.bitmap_readbacks,
             x,
             y,
             width,
@@ -389,7 +390,13 @@ pub fn get_pixel<'gc>(
         bitmap_data.check_valid(activation)?;
         let x = args.get_u32(activation, 0)?;
         let y = args.get_u32(activation, 1)?;
-        let col = operations::get_pixel(bitmap_data, activation.context.renderer, x, y);
+        let col = operations::get_pixel(
+            bitmap_data,
+            activation.context.renderer,
+            activation.context.bitmap_readbacks,
+            x,
+            y,
+        );
         return Ok(col.into());
     }
 
@@ -406,7 +413,13 @@ pub fn get_pixel32<'gc>(
         bitmap_data.check_valid(activation)?;
         let x = args.get_u32(activation, 0)?;
         let y = args.get_u32(activation, 1)?;
-        let pixel = operations::get_pixel32(bitmap_data, activation.context.renderer, x, y);
+        let pixel = operations::get_pixel32(
+            bitmap_data,
+            activation.context.renderer,
+            activation.context.bitmap_readbacks,
+            x,
+            y,
+        );
         return Ok(pixel.into());
     }
 
@@ -1120,8 +1133,16 @@ pub fn apply_filter<'gc>(
             })?;
         let source_rect = args.get_object(activation, 1, "sourceRect")?;
         let mut source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
-        let filter = args.get_object(activation, 3, "filter")?;
-        let filter = Filter::from_avm2_object(activation, filter)?;
+        let filter_object = args.get_object(activation, 3, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter_object)?;
+        let map_bitmap = if matches!(filter, Filter::DisplacementMapFilter(_)) {
+            filter_object
+                .get_public_property("mapBitmap", activation)?
+                .as_object()
+                .and_then(|map_bitmap| map_bitmap.as_bitmap_data())
+        } else {
+            None
+        };
 
         if matches!(filter, Filter::ShaderFilter(_)) {
             let source_bitmap_rect = Rectangle {
@@ -1179,6 +1200,7 @@ pub fn apply_filter<'gc>(
             source_size,
             dest_point,
             filter,
+            map_bitmap,
         );
     }
     Ok(Value::Undefined)
@@ -1219,6 +1241,7 @@ pub fn palette_map<'gc>(
             .get_object(activation, 0, "sourceBitmapData")?
             .as_bitmap_data()
             .unwrap();
+        source_bitmap.check_valid(activation)?;
 
         let source_rect = args.get_object(activation, 1, "sourceRect")?;
         let source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
@@ -1236,7 +1259,7 @@ pub fn palette_map<'gc>(
                 .get_public_property("x", activation)?
                 .coerce_to_i32(activation)?,
             dest_point
-                .get_public_property("x", activation)?
+                .get_public_property("y", activation)?
                 .coerce_to_i32(activation)?,
         );
 
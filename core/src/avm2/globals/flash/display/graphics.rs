@@ -80,7 +80,7 @@ pub fn begin_bitmap_fill<'gc>(
         );
 
         if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-            let id = draw.add_bitmap(bitmap);
+            let id = draw.add_bitmap(bitmap, activation.context.renderer.device_id());
             draw.set_fill_style(Some(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -1069,6 +1069,15 @@ impl TriangleCulling {
 
 type Triangle = (Point<Twips>, Point<Twips>, Point<Twips>);
 
+/// The texture coordinates of a vertex given to `drawTriangles`, where `t` is the
+/// reciprocal of its distance from the viewer.
+#[derive(Debug, Clone, Copy)]
+struct Uvt {
+    u: f64,
+    v: f64,
+    t: f64,
+}
+
 fn draw_triangles_internal<'gc>(
     activation: &mut Activation<'_, 'gc>,
     drawing: &mut Drawing,
@@ -1086,126 +1095,126 @@ fn draw_triangles_internal<'gc>(
         "winding behavior"
     );
 
-    if uvt_data.is_some() {
-        avm2_stub_method!(
-            activation,
-            "flash.display.Graphics",
-            "drawTriangles",
-            "with uvt data"
-        );
-    }
-
     let vertices = vertices
         .as_vector_storage()
         .expect("vertices is not a Vector");
 
-    if let Some(indices) = indices {
-        if vertices.length() % 2 != 0 {
-            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
-        }
+    let vertices_are_valid = if indices.is_some() {
+        vertices.length() % 2 == 0
+    } else {
+        vertices.length() % 6 == 0
+    };
+    if !vertices_are_valid {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+
+    let num_vertices = vertices.length() / 2;
+    let mut points = Vec::with_capacity(num_vertices);
+    for i in 0..num_vertices {
+        let x = vertices
+            .get(2 * i, activation)?
+            .coerce_to_number(activation)?;
+        let y = vertices
+            .get(2 * i + 1, activation)?
+            .coerce_to_number(activation)?;
+        points.push(Point::new(Twips::from_pixels(x), Twips::from_pixels(y)));
+    }
 
+    let indices = if let Some(indices) = indices {
         let indices = indices
             .as_vector_storage()
             .expect("indices is not a Vector");
+        let mut values = Vec::with_capacity(indices.length());
+        for index in indices.iter() {
+            values.push(index.coerce_to_u32(activation)? as usize);
+        }
+        values
+    } else {
+        (0..num_vertices).collect()
+    };
 
-        fn read_point<'gc>(
-            vertices: &VectorStorage<'gc>,
-            index: usize,
-            activation: &mut Activation<'_, 'gc>,
-        ) -> Result<Point<Twips>, Error<'gc>> {
-            let x = {
-                let x = vertices
-                    .get(2 * index, activation)?
-                    .coerce_to_number(activation)?;
-                Twips::from_pixels(x)
-            };
-            let y = {
-                let y = vertices
-                    .get(2 * index + 1, activation)?
-                    .coerce_to_number(activation)?;
-                Twips::from_pixels(y)
-            };
+    let uvts = if let Some(uvt_data) = uvt_data {
+        read_uvt_data(activation, uvt_data, num_vertices)?
+    } else {
+        None
+    };
 
-            Ok(Point::new(x, y))
-        }
+    // Texture coordinates only mean something for bitmap fills, which are then
+    // mapped onto each triangle separately.
+    let bitmap_fill = drawing
+        .current_fill()
+        .and_then(|(style, rule)| match style {
+            FillStyle::Bitmap { id, .. } => drawing.bitmap_size(id).map(|size| (style, rule, size)),
+            _ => None,
+        });
+
+    for triangle in indices.chunks_exact(3) {
+        let (Some(&a), Some(&b), Some(&c)) = (
+            points.get(triangle[0]),
+            points.get(triangle[1]),
+            points.get(triangle[2]),
+        ) else {
+            break;
+        };
 
-        fn next_triangle<'gc>(
-            vertices: &VectorStorage<'gc>,
-            indices: &mut impl Iterator<Item = Value<'gc>>,
-            activation: &mut Activation<'_, 'gc>,
-        ) -> Option<Triangle> {
-            match (indices.next(), indices.next(), indices.next()) {
-                (Some(i0), Some(i1), Some(i2)) => {
-                    let i0 = i0.coerce_to_u32(activation).ok()? as usize;
-                    let i1 = i1.coerce_to_u32(activation).ok()? as usize;
-                    let i2 = i2.coerce_to_u32(activation).ok()? as usize;
-
-                    let p0 = read_point(vertices, i0, activation).ok()?;
-                    let p1 = read_point(vertices, i1, activation).ok()?;
-                    let p2 = read_point(vertices, i2, activation).ok()?;
-
-                    Some((p0, p1, p2))
-                }
-                _ => None,
+        match (&bitmap_fill, &uvts) {
+            (Some((style, _, bitmap_size)), Some(uvts)) => {
+                let uvts = [uvts[triangle[0]], uvts[triangle[1]], uvts[triangle[2]]];
+                draw_textured_triangle((a, b, c), uvts, style, *bitmap_size, drawing, culling);
             }
+            _ => draw_triangle_internal((a, b, c), drawing, culling),
         }
+    }
 
-        let indices = &mut indices.iter();
-
-        while let Some(triangle) = next_triangle(&vertices, indices, activation) {
-            draw_triangle_internal(triangle, drawing, culling);
-        }
-    } else {
-        if vertices.length() % 6 != 0 {
-            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
-        }
+    // Go back to the fill that the triangles were textured with.
+    if let (Some((style, rule, _)), Some(_)) = (bitmap_fill, uvts) {
+        drawing.new_fill(Some(style), Some(rule));
+    }
 
-        let mut vertices = vertices.iter();
-
-        fn read_point<'gc>(
-            vertices: &mut impl Iterator<Item = Value<'gc>>,
-            activation: &mut Activation<'_, 'gc>,
-        ) -> Result<Option<Point<Twips>>, Error<'gc>> {
-            let x = {
-                let x = vertices.next();
-                let x = match x {
-                    Some(x) => x.coerce_to_number(activation)?,
-                    None => return Ok(None),
-                };
-                Twips::from_pixels(x)
-            };
-            let y = {
-                let y = vertices.next();
-                let y = match y {
-                    Some(y) => y.coerce_to_number(activation)?,
-                    None => return Ok(None),
-                };
-                Twips::from_pixels(y)
-            };
+    Ok(())
+}
 
-            Ok(Some(Point::new(x, y)))
-        }
+/// Reads the texture coordinates given to `drawTriangles`, which have either two
+/// (`u`, `v`) or three (`u`, `v`, `t`) values for each vertex.
+///
+/// A missing `t` is the same as all vertices being at the same depth.
+fn read_uvt_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    uvt_data: &Object<'gc>,
+    num_vertices: usize,
+) -> Result<Option<Vec<Uvt>>, Error<'gc>> {
+    let uvt_data = uvt_data
+        .as_vector_storage()
+        .expect("uvtData is not a Vector");
 
-        fn next_triangle<'gc>(
-            vertices: &mut impl Iterator<Item = Value<'gc>>,
-            activation: &mut Activation<'_, 'gc>,
-        ) -> Result<Option<Triangle>, Error<'gc>> {
-            match (
-                read_point(vertices, activation)?,
-                read_point(vertices, activation)?,
-                read_point(vertices, activation)?,
-            ) {
-                (Some(p0), Some(p1), Some(p2)) => Ok(Some((p0, p1, p2))),
-                _ => Ok(None),
-            }
-        }
+    let stride = if uvt_data.length() == num_vertices * 2 {
+        2
+    } else if uvt_data.length() == num_vertices * 3 {
+        3
+    } else {
+        // Coordinates that don't match up with the vertices are ignored.
+        return Ok(None);
+    };
 
-        while let Some(triangle) = next_triangle(&mut vertices, activation)? {
-            draw_triangle_internal(triangle, drawing, culling);
-        }
+    let mut uvts = Vec::with_capacity(num_vertices);
+    for i in 0..num_vertices {
+        let u = uvt_data
+            .get(stride * i, activation)?
+            .coerce_to_number(activation)?;
+        let v = uvt_data
+            .get(stride * i + 1, activation)?
+            .coerce_to_number(activation)?;
+        let t = if stride == 3 {
+            uvt_data
+                .get(stride * i + 2, activation)?
+                .coerce_to_number(activation)?
+        } else {
+            1.0
+        };
+        uvts.push(Uvt { u, v, t });
     }
 
-    Ok(())
+    Ok(Some(uvts))
 }
 
 #[inline]
@@ -1221,6 +1230,135 @@ fn draw_triangle_internal((a, b, c): Triangle, drawing: &mut Drawing, culling: T
     drawing.draw_command(DrawCommand::LineTo(a));
 }
 
+/// Draws a triangle with the given bitmap fill, mapped onto it with the texture
+/// coordinates of its vertices.
+fn draw_textured_triangle(
+    (a, b, c): Triangle,
+    uvts: [Uvt; 3],
+    style: &FillStyle,
+    (width, height): (u16, u16),
+    drawing: &mut Drawing,
+    culling: TriangleCulling,
+) {
+    let FillStyle::Bitmap {
+        id,
+        is_smoothed,
+        is_repeating,
+        ..
+    } = *style
+    else {
+        return;
+    };
+
+    if culling.cull((a, b, c)) {
+        return;
+    }
+
+    // Bitmaps can only be mapped affinely onto a triangle, so triangles whose vertices are
+    // at different depths are split up, each piece getting its perspective-correct coordinates.
+    let (min_t, max_t) = uvts
+        .iter()
+        .fold((f64::INFINITY, 0.0f64), |(min, max), uvt| {
+            (min.min(uvt.t), max.max(uvt.t))
+        });
+    let subdivisions = if min_t > 0.0 && min_t.is_finite() {
+        ((max_t / min_t - 1.0) * 16.0).ceil().clamp(1.0, 8.0) as usize
+    } else {
+        1
+    };
+
+    let corners = [a, b, c].map(|point| (point.x.get() as f64, point.y.get() as f64));
+    let vertex_at = |i: usize, j: usize| {
+        let weights = [
+            (subdivisions - i - j) as f64 / subdivisions as f64,
+            i as f64 / subdivisions as f64,
+            j as f64 / subdivisions as f64,
+        ];
+        let mut position = (0.0, 0.0);
+        let (mut u, mut v, mut total) = (0.0, 0.0, 0.0);
+        for ((weight, corner), uvt) in weights.iter().zip(corners).zip(uvts) {
+            position.0 += weight * corner.0;
+            position.1 += weight * corner.1;
+            let weight = if subdivisions > 1 {
+                weight * uvt.t
+            } else {
+                *weight
+            };
+            u += weight * uvt.u;
+            v += weight * uvt.v;
+            total += weight;
+        }
+        let texel = (u / total * width as f64, v / total * height as f64);
+        (position, texel)
+    };
+
+    for i in 0..subdivisions {
+        for j in 0..subdivisions - i {
+            let mut pieces = vec![[vertex_at(i, j), vertex_at(i + 1, j), vertex_at(i, j + 1)]];
+            if i + j + 1 < subdivisions {
+                pieces.push([
+                    vertex_at(i + 1, j),
+                    vertex_at(i + 1, j + 1),
+                    vertex_at(i, j + 1),
+                ]);
+            }
+
+            for piece in pieces {
+                drawing.new_fill(
+                    Some(FillStyle::Bitmap {
+                        id,
+                        matrix: texture_matrix(piece),
+                        is_smoothed,
+                        is_repeating,
+                    }),
+                    Some(FillRule::NonZero),
+                );
+
+                let [a, b, c] = piece.map(|((x, y), _)| {
+                    Point::new(Twips::new(x.round() as i32), Twips::new(y.round() as i32))
+                });
+                drawing.draw_command(DrawCommand::MoveTo(a));
+                drawing.draw_command(DrawCommand::LineTo(b));
+                drawing.draw_command(DrawCommand::LineTo(c));
+                drawing.draw_command(DrawCommand::LineTo(a));
+            }
+        }
+    }
+}
+
+/// The bitmap fill matrix that maps the given texels onto the given positions, in twips.
+fn texture_matrix(vertices: [((f64, f64), (f64, f64)); 3]) -> Matrix {
+    // Keep the matrix within what a `Fixed16` can hold.
+    const MAX_SCALE: f64 = 32767.0;
+
+    let [((x0, y0), (u0, v0)), ((x1, y1), (u1, v1)), ((x2, y2), (u2, v2))] = vertices;
+    let (du1, dv1, du2, dv2) = (u1 - u0, v1 - v0, u2 - u0, v2 - v0);
+    let (dx1, dy1, dx2, dy2) = (x1 - x0, y1 - y0, x2 - x0, y2 - y0);
+
+    let determinant = du1 * dv2 - du2 * dv1;
+    let (a, b, c, d) = if determinant.abs() > f64::EPSILON {
+        (
+            (dx1 * dv2 - dx2 * dv1) / determinant,
+            (dy1 * dv2 - dy2 * dv1) / determinant,
+            (dx2 * du1 - dx1 * du2) / determinant,
+            (dy2 * du1 - dy1 * du2) / determinant,
+        )
+    } else {
+        // The texels are all on a line, so stretch the first one across the triangle.
+        (MAX_SCALE, 0.0, 0.0, MAX_SCALE)
+    };
+    let [a, b, c, d] = [a, b, c, d].map(|n| n.clamp(-MAX_SCALE, MAX_SCALE));
+
+    Matrix {
+        a: Fixed16::from_f64(a),
+        b: Fixed16::from_f64(b),
+        c: Fixed16::from_f64(c),
+        d: Fixed16::from_f64(d),
+        tx: Twips::new((x0 - a * u0 - c * v0).round() as i32),
+        ty: Twips::new((y0 - b * u0 - d * v0).round() as i32),
+    }
+}
+
 /// Implements `Graphics.drawGraphicsData`
 pub fn draw_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -1279,7 +1417,7 @@ pub fn line_bitmap_style<'gc>(
         );
 
         if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-            let id = draw.add_bitmap(bitmap);
+            let id = draw.add_bitmap(bitmap, activation.context.renderer.device_id());
             draw.set_line_fill_style(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -1734,7 +1872,7 @@ fn handle_bitmap_fill<'gc>(
         Fixed16::from_f64(bitmap.height as f64),
     );
 
-    let id = drawing.add_bitmap(bitmap);
+    let id = drawing.add_bitmap(bitmap, activation.context.renderer.device_id());
 
     let style = FillStyle::Bitmap {
         id,
@@ -60,11 +60,24 @@ pub fn load<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let namespaces = activation.avm2().namespaces;
-
     let url_request = args.get_object(activation, 0, "request")?;
     let context = args.try_get_object(activation, 1);
 
+    let request = request_from_url_request(activation, url_request)?;
+    load_request(activation, this, request, context)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Start loading the content of `request` into a `Loader`.
+pub fn load_request<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    request: Request,
+    context: Option<Object<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let namespaces = activation.avm2().namespaces;
+
     let loader_info = this
         .get_property(
             &Multiname::new(namespaces.flash_display_internal, "_contentLoaderInfo"),
@@ -108,8 +121,6 @@ pub fn load<'gc>(
             activation.context.gc_context,
         );
 
-    let request = request_from_url_request(activation, url_request)?;
-
     let url = request.url().to_string();
     let future = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone(),
@@ -126,7 +137,7 @@ pub fn load<'gc>(
     );
     activation.context.navigator.spawn_future(future);
 
-    Ok(Value::Undefined)
+    Ok(())
 }
 
 pub fn request_from_url_request<'gc>(
@@ -0,0 +1,85 @@
+//! `flash.display.NativeWindow` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2037;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use crate::native_window::NativeWindows;
+
+/// Opens the window backing a `NativeWindow` created by the movie, and returns its stage.
+pub fn open_window<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let transparent = args.get_bool(0);
+
+    match NativeWindows::open(activation.context, this, transparent) {
+        Ok(stage) => Ok(stage.object2()),
+        Err(e) => {
+            // AIR has no limit of its own, so this fails like any other window that cannot be
+            // created, and the reason only goes to the log.
+            tracing::warn!("Unable to open NativeWindow: {e}");
+            Err(make_error_2037(activation))
+        }
+    }
+}
+
+/// Sends the title, bounds and visibility of a `NativeWindow` to its surface.
+pub fn update_window<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let title = args.get_string(activation, 0)?.to_string();
+    let x = args.get_f64(activation, 1)?;
+    let y = args.get_f64(activation, 2)?;
+    let width = args.get_f64(activation, 3)?;
+    let height = args.get_f64(activation, 4)?;
+    let visible = args.get_bool(5);
+
+    NativeWindows::update(activation.context, this, |descriptor| {
+        descriptor.title = title;
+        descriptor.x = x as i32;
+        descriptor.y = y as i32;
+        descriptor.width = width.max(0.0) as u32;
+        descriptor.height = height.max(0.0) as u32;
+        descriptor.visible = visible;
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Gives focus to the surface of a `NativeWindow`, dispatching `activate` if needed.
+pub fn activate_window<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    NativeWindows::activate(activation.context, this);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NativeWindow.active` for windows opened by the movie.
+pub fn is_window_active<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.native_windows.is_active(this).into())
+}
+
+/// Destroys the surface of a closed `NativeWindow`, and frees its render resources.
+pub fn close_window<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    NativeWindows::close(activation.context, this);
+
+    Ok(Value::Undefined)
+}
@@ -2,6 +2,7 @@ use ruffle_render::{
     backend::{PixelBenderOutput, PixelBenderTarget},
     bitmap::PixelRegion,
     pixel_bender::{
+        software::{self, OutOfRangeMode, SoftwareImage, SoftwareShader},
         ImageInputTexture, PixelBenderParam, PixelBenderParamQualifier, PixelBenderShaderArgument,
         PixelBenderShaderHandle, PixelBenderType, OUT_COORD_NAME,
     },
@@ -13,6 +14,7 @@ use crate::{
         TObject, Value,
     },
     avm2_stub_method,
+    bitmap::bitmap_data::Color,
     pixel_bender::PixelBenderTypeExt,
 };
 
@@ -205,6 +207,17 @@ pub fn start<'gc>(
 
     let output_height = this.get_public_property("height", activation)?.as_u32();
 
+    if shader_handle.0.is::<SoftwareShader>() {
+        return run_on_cpu(
+            activation,
+            shader,
+            &shader_handle,
+            &arguments,
+            target,
+            (output_width, output_height),
+        );
+    }
+
     let pixel_bender_target = if let Some(bitmap) = target.as_bitmap_data() {
         let target_bitmap = bitmap.sync(activation.context.renderer);
         // Perform both a GPU->CPU and CPU->GPU sync before writing to it.
@@ -245,21 +258,122 @@ pub fn start<'gc>(
                 PixelRegion::for_whole_size(width, height),
             );
         }
-        PixelBenderOutput::Bytes(pixels) => {
-            if let Some(mut bytearray) = target.as_bytearray_mut() {
-                bytearray.write_at(&pixels, 0).unwrap();
-            } else if let Some(mut vector) =
-                target.as_vector_storage_mut(activation.context.gc_context)
-            {
-                let new_storage: Vec<_> = bytemuck::cast_slice::<u8, f32>(&pixels)
-                    .iter()
-                    .map(|p| Value::from(*p as f64))
-                    .collect();
-                vector.replace_storage(new_storage);
-            } else {
-                panic!("Unexpected target object {target:?}");
+        PixelBenderOutput::Bytes(pixels) => write_bytes(activation, target, &pixels),
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn write_bytes<'gc>(activation: &mut Activation<'_, 'gc>, target: Object<'gc>, pixels: &[u8]) {
+    if let Some(mut bytearray) = target.as_bytearray_mut() {
+        bytearray.write_at(pixels, 0).unwrap();
+    } else if let Some(mut vector) = target.as_vector_storage_mut(activation.context.gc_context) {
+        let new_storage: Vec<_> = pixels
+            .chunks_exact(4)
+            .map(|p| Value::from(f32::from_le_bytes([p[0], p[1], p[2], p[3]]) as f64))
+            .collect();
+        vector.replace_storage(new_storage);
+    } else {
+        panic!("Unexpected target object {target:?}");
+    }
+}
+
+/// Runs a shader with the software evaluator, for renderers that can't run it themselves.
+///
+/// Bitmaps are read and written through their CPU-side pixels.
+fn run_on_cpu<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shader: Object<'gc>,
+    shader_handle: &PixelBenderShaderHandle,
+    arguments: &[PixelBenderShaderArgument],
+    target: Object<'gc>,
+    (output_width, output_height): (u32, u32),
+) -> Result<Value<'gc>, Error<'gc>> {
+    let shader_data = shader
+        .get_public_property("data", activation)?
+        .as_object()
+        .expect("Missing ShaderData object");
+
+    let mut images = Vec::new();
+    for argument in arguments {
+        let PixelBenderShaderArgument::ImageInput {
+            index,
+            name,
+            texture: Some(texture),
+            ..
+        } = argument
+        else {
+            continue;
+        };
+        let image = match texture {
+            ImageInputTexture::Bitmap(_) => {
+                let input = shader_data
+                    .get_public_property(
+                        AvmString::new_utf8(activation.context.gc_context, name),
+                        activation,
+                    )?
+                    .as_object()
+                    .and_then(|input| input.get_public_property("input", activation).ok())
+                    .and_then(|input| input.as_object())
+                    .and_then(|input| input.as_bitmap_data());
+                input.map(|bitmap| {
+                    let bitmap = bitmap.sync(activation.context.renderer);
+                    let bitmap = bitmap.read();
+                    SoftwareImage::from_rgba8(
+                        bitmap.width(),
+                        bitmap.height(),
+                        &bitmap.pixels_rgba(),
+                    )
+                })
             }
+            texture => SoftwareImage::from_input(texture),
+        };
+        let index = *index as usize;
+        if index >= images.len() {
+            images.resize(index + 1, None);
+        }
+        images[index] = image;
+    }
+
+    let shader = shader_handle.0.parsed_shader();
+    if let Some(bitmap) = target.as_bitmap_data() {
+        let target_bitmap = bitmap.sync(activation.context.renderer);
+        let mut target_bitmap_data = target_bitmap.write(activation.context.gc_context);
+        let width = target_bitmap_data.width();
+        let height = target_bitmap_data.height();
+        let output = software::run_shader(
+            shader,
+            arguments,
+            &images,
+            width,
+            height,
+            OutOfRangeMode::Clamp,
+        );
+
+        let transparency = target_bitmap_data.transparency();
+        let rgba = output.to_rgba8();
+        for (pixel, rgba) in target_bitmap_data
+            .raw_pixels_mut()
+            .iter_mut()
+            .zip(rgba.chunks_exact(4))
+        {
+            let alpha = if transparency { rgba[3] } else { 255 };
+            *pixel = Color::argb(alpha, rgba[0], rgba[1], rgba[2]);
         }
+        target_bitmap_data.set_cpu_dirty(
+            activation.context.gc_context,
+            PixelRegion::for_whole_size(width, height),
+        );
+    } else {
+        let output = software::run_shader(
+            shader,
+            arguments,
+            &images,
+            output_width,
+            output_height,
+            OutOfRangeMode::Clamp,
+        );
+        write_bytes(activation, target, &output.to_float_bytes());
     }
 
     Ok(Value::Undefined)
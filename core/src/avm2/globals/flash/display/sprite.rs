@@ -8,6 +8,7 @@ use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2::{ClassObject, Error};
 use crate::display_object::{MovieClip, SoundTransform, TDisplayObject};
+use crate::native_window::NativeWindows;
 use swf::{Rectangle, Twips};
 
 pub fn sprite_allocator<'gc>(
@@ -203,7 +204,11 @@ pub fn start_drag<'gc>(
 
         let drag_object = crate::player::DragObject {
             display_object,
-            last_mouse_position: *activation.context.mouse_position,
+            last_mouse_position: NativeWindows::mouse_position_on(
+                activation.context,
+                display_object,
+            )
+            .1,
             lock_center,
             constraint,
         };
@@ -11,6 +11,7 @@ use crate::avm2_stub_getter;
 use crate::display_object::{
     StageDisplayState, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
 };
+use crate::focus_tracker::FocusDirection;
 use crate::string::{AvmString, WString};
 use swf::Color;
 
@@ -201,6 +202,28 @@ pub fn set_focus<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `assignFocus`
+pub fn assign_focus<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let direction = args.get_string_non_null(activation, 1, "direction")?;
+    let Some(direction) = FocusDirection::from_name(&direction.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "direction"));
+    };
+
+    let object = args
+        .try_get_object(activation, 0)
+        .and_then(|obj| obj.as_display_object())
+        .and_then(|dobj| dobj.as_interactive());
+
+    let focus = activation.context.focus_tracker;
+    focus.assign(object, direction, activation.context);
+
+    Ok(Value::Undefined)
+}
+
 /// Implement `frameRate`'s getter
 pub fn get_frame_rate<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -250,6 +273,27 @@ pub fn set_show_default_context_menu<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.stage.mouse_lock().into())
+}
+
+pub fn set_mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mouse_lock = args.get_bool(0);
+    activation
+        .context
+        .stage
+        .set_mouse_lock(activation.context, mouse_lock);
+    Ok(Value::Undefined)
+}
+
 /// Implement `scaleMode`'s getter
 pub fn get_scale_mode<'gc>(
     activation: &mut Activation<'_, 'gc>,
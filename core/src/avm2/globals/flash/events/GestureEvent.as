@@ -47,13 +47,13 @@ package flash.events
         // Creates a copy of the GestureEvent object and sets the value of each property to match that of the original.
         override public function clone():Event
         {
-            return new GestureEvent(this.type, this.bubbles, this.cancelable, this.phase, this.localX, this.localY, this.ctrlKey, this.altKey, this.shiftKey, this.commandKey, this.controlKey);
+            return new GestureEvent(this.type, this.bubbles, this.cancelable, this.phase, this.localX, this.localY, this.ctrlKey, this.altKey, this.shiftKey, this.controlKey);
         }
 
         // Returns a string that contains all the properties of the GestureEvent object.
         override public function toString():String
         {
-            return this.formatToString("GestureEvent","type","bubbles","cancelable","eventPhase","phase","localX","localY","ctrlKey","altKey","shiftKey","commandKey","controlKey","stageX","stageY");
+            return this.formatToString("GestureEvent","type","bubbles","cancelable","eventPhase","phase","localX","localY","ctrlKey","altKey","shiftKey","controlKey","stageX","stageY");
         }
 
         // The horizontal coordinate at which the event occurred in global Stage coordinates.
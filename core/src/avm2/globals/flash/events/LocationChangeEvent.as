@@ -0,0 +1,22 @@
+package flash.events {
+    [API("669")]
+    public class LocationChangeEvent extends Event {
+        public static const LOCATION_CHANGE:String = "locationChange";
+        public static const LOCATION_CHANGING:String = "locationChanging";
+
+        public var location:String;
+
+        public function LocationChangeEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false, location:String = null) {
+            super(type, bubbles, cancelable);
+            this.location = location;
+        }
+
+        override public function clone():Event {
+            return new LocationChangeEvent(this.type, this.bubbles, this.cancelable, this.location);
+        }
+
+        override public function toString():String {
+            return this.formatToString("LocationChangeEvent", "type", "bubbles", "cancelable", "eventPhase", "location");
+        }
+    }
+}
@@ -68,8 +68,8 @@ package flash.events
             this.buttonDown = buttonDown;
             this.delta = delta;
 
-            this.movementX = 0.0; // unimplemented
-            this.movementY = 0.0; // unimplemented
+            this.movementX = 0.0;
+            this.movementY = 0.0;
         }
 
         override public function clone() : Event
@@ -1,36 +1,410 @@
 package flash.filesystem {
-    import __ruffle__.stub_constructor;
     import __ruffle__.stub_getter;
     import __ruffle__.stub_method;
 
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
     import flash.net.FileReference;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
 
+    // Ruffle gives AIR applications a sandboxed virtual filesystem, stored next to the
+    // movie's shared objects. Paths are kept as normalized URLs, such as
+    // `app-storage:/saves/slot1.dat`, and native paths are mapped into the `file:` root.
     [API("661")]
     public class File extends FileReference {
+        public static const lineEnding:String = "\n";
+        public static const separator:String = "/";
+        public static const systemCharset:String = "utf-8";
+
+        private static const ENTRY_MISSING:int = 0;
+        private static const ENTRY_FILE:int = 1;
+        private static const ENTRY_DIRECTORY:int = 2;
+
         private static var _applicationDirectory:File = null;
 
+        // The normalized URL of this file, or null if it doesn't point anywhere yet.
+        private var _url:String = null;
+
+        private var _data:ByteArray = null;
+
         public function File(path:String = null) {
-            stub_constructor("flash.filesystem.File");
+            if (path !== null) {
+                this._url = File.normalize(path);
+            }
+        }
+
+        internal static native function getEntryType(url:String):int;
+        internal static native function readEntry(url:String):ByteArray;
+        internal static native function writeEntry(url:String, data:ByteArray):Boolean;
+        internal static native function createDirectoryEntry(url:String):Boolean;
+        internal static native function deleteEntry(url:String):Boolean;
+        internal static native function listEntries(url:String):Array;
+
+        // Turns a native path or a URL into the `root:/path/to/file` form used internally.
+        private static function normalize(path:String):String {
+            var root:String;
+            var rest:String;
+
+            var match:Array = /^([a-zA-Z][a-zA-Z0-9+.\-]+):\/*(.*)$/.exec(path);
+            if (match) {
+                root = match[1].toLowerCase();
+                rest = match[2];
+            } else if (/^[a-zA-Z]:[\/\\]/.test(path) || path.charAt(0) == "/" || path.charAt(0) == "\\") {
+                root = "file";
+                rest = path;
+            } else {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var segments:Array = [];
+            for each (var segment:String in rest.split("\\").join("/").split("/")) {
+                if (segment == "" || segment == ".") {
+                    continue;
+                }
+                if (segment == "..") {
+                    segments.pop();
+                } else {
+                    segments.push(segment);
+                }
+            }
+
+            return root + ":/" + segments.join("/");
+        }
+
+        internal function get path():String {
+            if (this._url === null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            return this._url;
+        }
+
+        internal function checkWritable():void {
+            if (this.path.indexOf("app:/") == 0) {
+                throw new SecurityError("Error #3001: File or directory access denied.", 3001);
+            }
         }
 
         public static function get applicationDirectory():File {
             if (_applicationDirectory === null) {
-                _applicationDirectory = new File();
+                _applicationDirectory = new File("app:/");
             }
 
             return _applicationDirectory;
         }
 
-        public function resolvePath(path:String):File {
-            stub_method("flash.filesystem.File", "resolvePath");
+        public static function get applicationStorageDirectory():File {
+            return new File("app-storage:/");
+        }
+
+        public static function get userDirectory():File {
+            return new File("file:///home");
+        }
+
+        public static function get documentsDirectory():File {
+            return new File("file:///home/Documents");
+        }
+
+        public static function get desktopDirectory():File {
+            return new File("file:///home/Desktop");
+        }
+
+        public static function get cacheDirectory():File {
+            return new File("file:///tmp");
+        }
+
+        public static function getRootDirectories():Array {
+            return [new File("file:///")];
+        }
+
+        public function get url():String {
+            if (this._url === null) {
+                return null;
+            }
+            if (this._url.indexOf("file:/") == 0) {
+                return "file://" + this._url.substr(5);
+            }
+            return this._url;
+        }
 
-            return new File();
+        public function set url(value:String):void {
+            this._url = File.normalize(value);
+        }
+
+        public function get nativePath():String {
+            if (this._url === null) {
+                return null;
+            }
+            if (this._url.indexOf("file:/") == 0) {
+                return this._url.substr(5);
+            }
+            return this._url;
+        }
+
+        public function set nativePath(value:String):void {
+            this._url = File.normalize(value);
+        }
+
+        override public function get name():String {
+            var path:String = this.path;
+            return path.substr(path.lastIndexOf("/") + 1);
+        }
+
+        override public function get extension():String {
+            var name:String = this.name;
+            var dot:int = name.lastIndexOf(".");
+            return dot == -1 ? null : name.substr(dot + 1);
+        }
+
+        override public function get type():String {
+            var extension:String = this.extension;
+            return extension === null ? null : "." + extension;
+        }
+
+        override public function get size():Number {
+            var data:ByteArray = File.readEntry(this.path);
+            if (data === null) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            return data.length;
+        }
+
+        override public function get data():ByteArray {
+            return this._data;
+        }
+
+        override public function get creationDate():Date {
+            stub_getter("flash.filesystem.File", "creationDate");
+            return new Date();
+        }
+
+        override public function get modificationDate():Date {
+            stub_getter("flash.filesystem.File", "modificationDate");
+            return new Date();
         }
 
         public function get exists():Boolean {
-            stub_getter("flash.filesystem.File", "exists");
+            return File.getEntryType(this.path) != ENTRY_MISSING;
+        }
+
+        public function get isDirectory():Boolean {
+            return File.getEntryType(this.path) == ENTRY_DIRECTORY;
+        }
+
+        public function get isHidden():Boolean {
+            return this.name.charAt(0) == ".";
+        }
+
+        public function get isPackage():Boolean {
+            return false;
+        }
 
+        public function get isSymbolicLink():Boolean {
             return false;
         }
+
+        public function get parent():File {
+            var path:String = this.path;
+            var slash:int = path.lastIndexOf("/");
+            if (slash == path.length - 1) {
+                // This is a root directory.
+                return null;
+            }
+            return new File(path.substr(0, slash + 1));
+        }
+
+        public function resolvePath(path:String):File {
+            if (/^[a-zA-Z][a-zA-Z0-9+.\-]+:/.test(path) || path.charAt(0) == "/") {
+                return new File(path);
+            }
+            return new File(this.path + "/" + path);
+        }
+
+        public function getRelativePath(ref:FileReference, useDotDot:Boolean = false):String {
+            var other:File = ref as File;
+            if (other === null) {
+                return null;
+            }
+
+            var from:Array = this.path.split("/");
+            var to:Array = other.path.split("/");
+            if (from[0] != to[0]) {
+                return null;
+            }
+
+            var common:int = 0;
+            while (common < from.length && common < to.length && from[common] == to[common]) {
+                common++;
+            }
+            if (common < from.length && !useDotDot) {
+                return null;
+            }
+
+            var result:Array = [];
+            for (var i:int = common; i < from.length; i++) {
+                if (from[i] != "") {
+                    result.push("..");
+                }
+            }
+            return result.concat(to.slice(common)).join("/");
+        }
+
+        public function clone():File {
+            var file:File = new File();
+            file._url = this._url;
+            return file;
+        }
+
+        public function canonicalize():void {
+            // Paths are always kept in their canonical form.
+        }
+
+        override public function load():void {
+            var data:ByteArray = File.readEntry(this.path);
+            if (data === null) {
+                var self:File = this;
+                setTimeout(function():void {
+                    self.dispatchEvent(new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, "Error #2038: File I/O Error.", 2038));
+                }, 0);
+                return;
+            }
+
+            this._data = data;
+            this.dispatchEvent(new Event(Event.OPEN));
+            this.dispatchEvent(new ProgressEvent(ProgressEvent.PROGRESS, false, false, data.length, data.length));
+            this.dispatchEvent(new Event(Event.COMPLETE));
+        }
+
+        public function createDirectory():void {
+            this.checkWritable();
+            if (!File.createDirectoryEntry(this.path)) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+        }
+
+        public function getDirectoryListing():Array {
+            if (!this.isDirectory) {
+                throw new IOError("Error #3007: The specified directory does not exist.", 3007);
+            }
+
+            var listing:Array = [];
+            for each (var entry:String in File.listEntries(this.path)) {
+                listing.push(new File(entry));
+            }
+            return listing;
+        }
+
+        public function deleteFile():void {
+            this.checkWritable();
+            if (File.getEntryType(this.path) != ENTRY_FILE) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            File.deleteEntry(this.path);
+        }
+
+        public function deleteFileAsync():void {
+            this.runAsync(this.deleteFile);
+        }
+
+        public function deleteDirectory(deleteDirectoryContents:Boolean = false):void {
+            this.checkWritable();
+            if (!this.isDirectory) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            if (!deleteDirectoryContents && File.listEntries(this.path).length != 0) {
+                throw new IOError("Error #3011: Cannot delete directory because it is not empty.", 3011);
+            }
+            File.deleteEntry(this.path);
+        }
+
+        public function deleteDirectoryAsync(deleteDirectoryContents:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():void {
+                self.deleteDirectory(deleteDirectoryContents);
+            });
+        }
+
+        public function copyTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            var target:File = this.prepareTarget(newLocation, overwrite);
+            this.copyEntry(this.path, target.path);
+        }
+
+        public function copyToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():void {
+                self.copyTo(newLocation, overwrite);
+            });
+        }
+
+        public function moveTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            this.checkWritable();
+            var target:File = this.prepareTarget(newLocation, overwrite);
+            this.copyEntry(this.path, target.path);
+            File.deleteEntry(this.path);
+        }
+
+        public function moveToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():void {
+                self.moveTo(newLocation, overwrite);
+            });
+        }
+
+        private function prepareTarget(newLocation:FileReference, overwrite:Boolean):File {
+            var target:File = newLocation as File;
+            if (target === null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            target.checkWritable();
+            if (!this.exists) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            if (target.exists) {
+                if (!overwrite) {
+                    throw new IOError("Error #3000: Illegal path name.", 3000);
+                }
+                File.deleteEntry(target.path);
+            }
+            return target;
+        }
+
+        private function copyEntry(from:String, to:String):void {
+            if (File.getEntryType(from) == ENTRY_DIRECTORY) {
+                File.createDirectoryEntry(to);
+                for each (var child:String in File.listEntries(from)) {
+                    this.copyEntry(child, to + child.substr(from.length));
+                }
+            } else if (!File.writeEntry(to, File.readEntry(from))) {
+                throw new IOError("Error #3013: File or directory is in use.", 3013);
+            }
+        }
+
+        // Runs a synchronous operation on the next frame, reporting its outcome with events.
+        private function runAsync(operation:Function):void {
+            var self:File = this;
+            setTimeout(function():void {
+                try {
+                    operation();
+                } catch (e:Error) {
+                    self.dispatchEvent(new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID));
+                    return;
+                }
+                self.dispatchEvent(new Event(Event.COMPLETE));
+            }, 0);
+        }
+
+        public function browseForDirectory(title:String):void {
+            stub_method("flash.filesystem.File", "browseForDirectory");
+        }
+
+        public function browseForOpen(title:String, typeFilter:Array = null):void {
+            stub_method("flash.filesystem.File", "browseForOpen");
+        }
+
+        public function browseForSave(title:String):void {
+            stub_method("flash.filesystem.File", "browseForSave");
+        }
     }
 }
@@ -0,0 +1,350 @@
+package flash.filesystem {
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+    import flash.utils.setTimeout;
+
+    // The whole file is kept in memory while the stream is open. Changes are saved
+    // back to the virtual filesystem at the end of the frame and when the stream is closed.
+    [API("661")]
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        private var _file:File = null;
+        private var _mode:String = null;
+        private var _async:Boolean = false;
+        private var _buffer:ByteArray = new ByteArray();
+        private var _dirty:Boolean = false;
+        private var _flushScheduled:Boolean = false;
+
+        public function FileStream() {
+        }
+
+        public function open(file:File, fileMode:String):void {
+            this._async = false;
+            this.openFile(file, fileMode);
+        }
+
+        public function openAsync(file:File, fileMode:String):void {
+            this._async = true;
+
+            var self:FileStream = this;
+            try {
+                this.openFile(file, fileMode);
+            } catch (e:Error) {
+                setTimeout(function():void {
+                    self.dispatchEvent(new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID));
+                }, 0);
+                return;
+            }
+
+            if (fileMode == FileMode.READ || fileMode == FileMode.UPDATE) {
+                var length:uint = this._buffer.length;
+                setTimeout(function():void {
+                    self.dispatchEvent(new ProgressEvent(ProgressEvent.PROGRESS, false, false, length, length));
+                    self.dispatchEvent(new Event(Event.COMPLETE));
+                }, 0);
+            }
+        }
+
+        private function openFile(file:File, fileMode:String):void {
+            if (this._file !== null) {
+                this.close();
+            }
+
+            if (fileMode != FileMode.READ) {
+                file.checkWritable();
+            }
+
+            var existing:ByteArray = File.readEntry(file.path);
+            var buffer:ByteArray = new ByteArray();
+
+            switch (fileMode) {
+                case FileMode.READ:
+                    if (existing === null) {
+                        throw new IOError("Error #3003: File or directory does not exist.", 3003);
+                    }
+                    buffer = existing;
+                    break;
+                case FileMode.WRITE:
+                    break;
+                case FileMode.APPEND:
+                case FileMode.UPDATE:
+                    if (existing !== null) {
+                        buffer = existing;
+                    }
+                    break;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter fileMode must be one of the accepted values.", 2008);
+            }
+
+            buffer.endian = this._buffer.endian;
+            buffer.objectEncoding = this._buffer.objectEncoding;
+            buffer.position = fileMode == FileMode.APPEND ? buffer.length : 0;
+
+            this._file = file;
+            this._mode = fileMode;
+            this._buffer = buffer;
+            this._dirty = false;
+
+            // Opening a file for writing creates it.
+            if (fileMode != FileMode.READ && existing === null) {
+                this.save();
+            }
+        }
+
+        public function close():void {
+            if (this._file === null) {
+                return;
+            }
+
+            this.save();
+            this._file = null;
+            this._mode = null;
+
+            if (this._async) {
+                var self:FileStream = this;
+                setTimeout(function():void {
+                    self.dispatchEvent(new Event(Event.CLOSE));
+                }, 0);
+            }
+        }
+
+        public function truncate():void {
+            this.checkWritable();
+            this._buffer.length = this._buffer.position;
+            this.changed();
+        }
+
+        private function checkOpen():void {
+            if (this._file === null) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+        }
+
+        private function checkReadable():void {
+            this.checkOpen();
+            if (this._mode != FileMode.READ && this._mode != FileMode.UPDATE) {
+                throw new IOError("Error #2002: Operation attempted on invalid socket.", 2002);
+            }
+        }
+
+        private function checkWritable():void {
+            this.checkOpen();
+            if (this._mode == FileMode.READ) {
+                throw new IOError("Error #2002: Operation attempted on invalid socket.", 2002);
+            }
+            if (this._mode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+        }
+
+        private function changed():void {
+            this._dirty = true;
+            if (this._flushScheduled) {
+                return;
+            }
+
+            this._flushScheduled = true;
+            var self:FileStream = this;
+            setTimeout(function():void {
+                self._flushScheduled = false;
+                if (self._dirty) {
+                    self.save();
+                }
+            }, 0);
+        }
+
+        private function save():void {
+            if (this._file === null) {
+                return;
+            }
+
+            if (!File.writeEntry(this._file.path, this._buffer)) {
+                throw new IOError("Error #3013: File or directory is in use.", 3013);
+            }
+            this._dirty = false;
+        }
+
+        public function get bytesAvailable():uint {
+            return this._file === null ? 0 : this._buffer.bytesAvailable;
+        }
+
+        public function get position():Number {
+            return this._buffer.position;
+        }
+
+        public function set position(value:Number):void {
+            this._buffer.position = value;
+        }
+
+        public function get readAhead():Number {
+            return Number.POSITIVE_INFINITY;
+        }
+
+        public function set readAhead(value:Number):void {
+            // The whole file is always available.
+        }
+
+        public function get endian():String {
+            return this._buffer.endian;
+        }
+
+        public function set endian(value:String):void {
+            this._buffer.endian = value;
+        }
+
+        public function get objectEncoding():uint {
+            return this._buffer.objectEncoding;
+        }
+
+        public function set objectEncoding(value:uint):void {
+            this._buffer.objectEncoding = value;
+        }
+
+        public function readBoolean():Boolean {
+            this.checkReadable();
+            return this._buffer.readBoolean();
+        }
+
+        public function readByte():int {
+            this.checkReadable();
+            return this._buffer.readByte();
+        }
+
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkReadable();
+            this._buffer.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble():Number {
+            this.checkReadable();
+            return this._buffer.readDouble();
+        }
+
+        public function readFloat():Number {
+            this.checkReadable();
+            return this._buffer.readFloat();
+        }
+
+        public function readInt():int {
+            this.checkReadable();
+            return this._buffer.readInt();
+        }
+
+        public function readMultiByte(length:uint, charSet:String):String {
+            this.checkReadable();
+            return this._buffer.readMultiByte(length, charSet);
+        }
+
+        public function readObject():* {
+            this.checkReadable();
+            return this._buffer.readObject();
+        }
+
+        public function readShort():int {
+            this.checkReadable();
+            return this._buffer.readShort();
+        }
+
+        public function readUnsignedByte():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedByte();
+        }
+
+        public function readUnsignedInt():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedInt();
+        }
+
+        public function readUnsignedShort():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedShort();
+        }
+
+        public function readUTF():String {
+            this.checkReadable();
+            return this._buffer.readUTF();
+        }
+
+        public function readUTFBytes(length:uint):String {
+            this.checkReadable();
+            return this._buffer.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            this.checkWritable();
+            this._buffer.writeBoolean(value);
+            this.changed();
+        }
+
+        public function writeByte(value:int):void {
+            this.checkWritable();
+            this._buffer.writeByte(value);
+            this.changed();
+        }
+
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkWritable();
+            this._buffer.writeBytes(bytes, offset, length);
+            this.changed();
+        }
+
+        public function writeDouble(value:Number):void {
+            this.checkWritable();
+            this._buffer.writeDouble(value);
+            this.changed();
+        }
+
+        public function writeFloat(value:Number):void {
+            this.checkWritable();
+            this._buffer.writeFloat(value);
+            this.changed();
+        }
+
+        public function writeInt(value:int):void {
+            this.checkWritable();
+            this._buffer.writeInt(value);
+            this.changed();
+        }
+
+        public function writeMultiByte(value:String, charSet:String):void {
+            this.checkWritable();
+            this._buffer.writeMultiByte(value, charSet);
+            this.changed();
+        }
+
+        public function writeObject(object:*):void {
+            this.checkWritable();
+            this._buffer.writeObject(object);
+            this.changed();
+        }
+
+        public function writeShort(value:int):void {
+            this.checkWritable();
+            this._buffer.writeShort(value);
+            this.changed();
+        }
+
+        public function writeUnsignedInt(value:uint):void {
+            this.checkWritable();
+            this._buffer.writeUnsignedInt(value);
+            this.changed();
+        }
+
+        public function writeUTF(value:String):void {
+            this.checkWritable();
+            this._buffer.writeUTF(value);
+            this.changed();
+        }
+
+        public function writeUTFBytes(value:String):void {
+            this.checkWritable();
+            this._buffer.writeUTFBytes(value);
+            this.changed();
+        }
+    }
+}
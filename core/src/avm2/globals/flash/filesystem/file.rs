@@ -0,0 +1,267 @@
+//! `flash.filesystem.File` native function definitions
+//!
+//! AIR applications are given a sandboxed virtual filesystem, which is kept in the
+//! player's storage backend next to the movie's shared objects. Every entry is
+//! identified by its normalized `File.url` (e.g. `app-storage:/saves/slot1.dat`);
+//! the list of existing entries is kept in a separate index, as storage backends
+//! can't enumerate their keys.
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::ByteArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayObject, ArrayStorage, Error, Object, Value};
+use crate::string::AvmString;
+
+/// The kind of entry a `File` points to, as returned by `File.getEntryType`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EntryType {
+    Missing = 0,
+    File = 1,
+    Directory = 2,
+}
+
+/// The storage key prefix of the virtual filesystem belonging to the current movie.
+///
+/// Like shared objects, the filesystem is keyed based on the SWF URL.
+fn storage_prefix(activation: &mut Activation<'_, '_>) -> Option<String> {
+    let movie_url = url::Url::parse(activation.context.swf.url()).ok()?;
+
+    let mut movie_path = movie_url.path();
+    movie_path = movie_path.strip_prefix('/').unwrap_or(movie_path);
+    movie_path = movie_path.strip_suffix('/').unwrap_or(movie_path);
+
+    let movie_host = if movie_url.scheme() == "file" {
+        if let [_, b':', b'/', ..] = movie_path.as_bytes() {
+            movie_path = &movie_path[3..];
+        }
+        "localhost"
+    } else {
+        movie_url.host_str().unwrap_or_default()
+    };
+
+    Some(format!("{movie_host}/{movie_path}/#air"))
+}
+
+/// The storage key that the contents of the file at `url` are kept under.
+///
+/// Returns `None` for roots and for paths that could escape the storage directory.
+fn entry_key(prefix: &str, url: &str) -> Option<String> {
+    let (root, path) = url.split_once(":/")?;
+    if path.is_empty() || path.split('/').any(|s| s.is_empty() || s.starts_with('.')) {
+        return None;
+    }
+    Some(format!("{prefix}/{root}/{path}"))
+}
+
+/// The entries that currently exist. Directories end with a `/`.
+fn read_index(activation: &mut Activation<'_, '_>, prefix: &str) -> Vec<String> {
+    activation
+        .context
+        .storage
+        .get(&format!("{prefix}.index"))
+        .map(|index| {
+            String::from_utf8_lossy(&index)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn write_index(activation: &mut Activation<'_, '_>, prefix: &str, index: &[String]) -> bool {
+    activation
+        .context
+        .storage
+        .put(&format!("{prefix}.index"), index.join("\n").as_bytes())
+}
+
+fn entry_type(index: &[String], url: &str) -> EntryType {
+    if url.ends_with(":/") {
+        return EntryType::Directory;
+    }
+
+    let dir = format!("{url}/");
+    if index.iter().any(|entry| entry == url) {
+        EntryType::File
+    } else if index.iter().any(|entry| entry.starts_with(&dir)) {
+        EntryType::Directory
+    } else {
+        EntryType::Missing
+    }
+}
+
+/// Implements `File.getEntryType`
+pub fn get_entry_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    let Some(prefix) = storage_prefix(activation) else {
+        return Ok((EntryType::Missing as i32).into());
+    };
+    let index = read_index(activation, &prefix);
+
+    Ok((entry_type(&index, &url) as i32).into())
+}
+
+/// Implements `File.readEntry`
+pub fn read_entry<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    let Some(prefix) = storage_prefix(activation) else {
+        return Ok(Value::Null);
+    };
+    let index = read_index(activation, &prefix);
+    if entry_type(&index, &url) != EntryType::File {
+        return Ok(Value::Null);
+    }
+
+    let data = entry_key(&prefix, &url)
+        .and_then(|key| activation.context.storage.get(&key))
+        .unwrap_or_default();
+    let storage = ByteArrayStorage::from_vec(data);
+    Ok(ByteArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements `File.writeEntry`
+pub fn write_entry<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+    let data = args.get_object(activation, 1, "data")?;
+    let data = data
+        .as_bytearray()
+        .map(|bytearray| bytearray.bytes().to_vec())
+        .unwrap_or_default();
+
+    let Some(prefix) = storage_prefix(activation) else {
+        return Ok(false.into());
+    };
+    let Some(key) = entry_key(&prefix, &url) else {
+        return Ok(false.into());
+    };
+
+    let mut index = read_index(activation, &prefix);
+    if entry_type(&index, &url) == EntryType::Directory {
+        return Ok(false.into());
+    }
+    if !activation.context.storage.put(&key, &data) {
+        return Ok(false.into());
+    }
+
+    if !index.contains(&url) {
+        index.push(url);
+        return Ok(write_index(activation, &prefix, &index).into());
+    }
+    Ok(true.into())
+}
+
+/// Implements `File.createDirectoryEntry`
+pub fn create_directory_entry<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    let Some(prefix) = storage_prefix(activation) else {
+        return Ok(false.into());
+    };
+    if entry_key(&prefix, &url).is_none() {
+        // Roots always exist.
+        return Ok(url.ends_with(":/").into());
+    }
+
+    let mut index = read_index(activation, &prefix);
+    match entry_type(&index, &url) {
+        EntryType::Directory => Ok(true.into()),
+        EntryType::File => Ok(false.into()),
+        EntryType::Missing => {
+            index.push(format!("{url}/"));
+            Ok(write_index(activation, &prefix, &index).into())
+        }
+    }
+}
+
+/// Implements `File.deleteEntry`
+///
+/// Directories are deleted along with everything inside of them.
+pub fn delete_entry<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    let Some(prefix) = storage_prefix(activation) else {
+        return Ok(false.into());
+    };
+    if entry_key(&prefix, &url).is_none() {
+        return Ok(false.into());
+    }
+
+    let mut index = read_index(activation, &prefix);
+    if entry_type(&index, &url) == EntryType::Missing {
+        return Ok(false.into());
+    }
+
+    let dir = format!("{url}/");
+    let (removed, kept): (Vec<_>, Vec<_>) = index
+        .drain(..)
+        .partition(|entry| *entry == url || entry.starts_with(&dir));
+    for entry in removed {
+        if let Some(key) = entry_key(&prefix, &entry) {
+            activation.context.storage.remove_key(&key);
+        }
+    }
+
+    Ok(write_index(activation, &prefix, &kept).into())
+}
+
+/// Implements `File.listEntries`
+///
+/// Returns the URLs of the direct children of the directory at `url`.
+pub fn list_entries<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    let mut children: Vec<String> = Vec::new();
+    if let Some(prefix) = storage_prefix(activation) {
+        let dir = if url.ends_with('/') {
+            url
+        } else {
+            format!("{url}/")
+        };
+
+        for entry in read_index(activation, &prefix) {
+            let Some(rest) = entry.strip_prefix(&dir) else {
+                continue;
+            };
+            let Some(name) = rest.split('/').next().filter(|name| !name.is_empty()) else {
+                continue;
+            };
+            let child = format!("{dir}{name}");
+            if !children.contains(&child) {
+                children.push(child);
+            }
+        }
+    }
+
+    let storage = ArrayStorage::from_iter(
+        children
+            .into_iter()
+            .map(|child| AvmString::new_utf8(activation.context.gc_context, child).into()),
+    );
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
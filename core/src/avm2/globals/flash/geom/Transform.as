@@ -4,14 +4,10 @@ package flash.geom {
 	import flash.geom.PerspectiveProjection;
 	import __ruffle__.stub_getter;
 	import __ruffle__.stub_method;
-	import __ruffle__.stub_setter;
 
 	public class Transform {
 		internal var _displayObject:DisplayObject;
 
-		private var _matrix3D:Matrix3D = null;
-		private var _perspectiveProjection:PerspectiveProjection = null;
-
 		function Transform(object:DisplayObject) {
 			this.init(object);
 		}
@@ -30,24 +26,68 @@ package flash.geom {
 		public native function get concatenatedMatrix():Matrix;
 		public native function get pixelBounds():Rectangle;
 
+		private native function is3D():Boolean;
+		private native function getPerspectiveProjectionValues():Array;
+		private native function setPerspectiveProjectionValues(fieldOfView:Number, centerX:Number, centerY:Number):void;
+
 		public function get matrix3D():Matrix3D {
-			stub_getter("flash.geom.Transform", "matrix3D");
-			return this._matrix3D;
+			if (!this.is3D()) {
+				return null;
+			}
+
+			var object:DisplayObject = this._displayObject;
+			var toRadians:Number = Math.PI / 180;
+			var components:Vector.<Vector3D> = new <Vector3D>[
+				new Vector3D(object.x, object.y, object.z),
+				new Vector3D(object.rotationX * toRadians, object.rotationY * toRadians, object.rotationZ * toRadians),
+				new Vector3D(object.scaleX, object.scaleY, object.scaleZ)
+			];
+			var matrix:Matrix3D = new Matrix3D();
+			matrix.recompose(components);
+			return matrix;
 		}
 
 		public function set matrix3D(m:Matrix3D):void {
-			stub_setter("flash.geom.Transform", "matrix3D");
-			this._matrix3D = m;
+			var object:DisplayObject = this._displayObject;
+			if (m == null) {
+				object.z = 0;
+				object.rotationX = 0;
+				object.rotationY = 0;
+				object.scaleZ = 1;
+				return;
+			}
+
+			var toDegrees:Number = 180 / Math.PI;
+			var components:Vector.<Vector3D> = m.decompose();
+			object.x = components[0].x;
+			object.y = components[0].y;
+			object.z = components[0].z;
+			object.rotationX = components[1].x * toDegrees;
+			object.rotationY = components[1].y * toDegrees;
+			object.rotationZ = components[1].z * toDegrees;
+			object.scaleX = components[2].x;
+			object.scaleY = components[2].y;
+			object.scaleZ = components[2].z;
 		}
 
 		public function get perspectiveProjection():PerspectiveProjection {
-			stub_getter("flash.geom.Transform", "perspectiveProjection");
-			return this._perspectiveProjection;
+			var values:Array = this.getPerspectiveProjectionValues();
+			if (values == null) {
+				return null;
+			}
+
+			var projection:PerspectiveProjection = new PerspectiveProjection();
+			projection.fieldOfView = values[0];
+			projection.projectionCenter = new Point(values[1], values[2]);
+			return projection;
 		}
 
 		public function set perspectiveProjection(val: PerspectiveProjection):void {
-			stub_setter("flash.geom.Transform", "perspectiveProjection");
-			this._perspectiveProjection = val;
+			if (val == null) {
+				this.setPerspectiveProjectionValues(NaN, 0, 0);
+			} else {
+				this.setPerspectiveProjectionValues(val.fieldOfView, val.projectionCenter.x, val.projectionCenter.y);
+			}
 		}
 
 		public function getRelativeMatrix3D(relativeTo:DisplayObject):Matrix3D {
@@ -1,7 +1,7 @@
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Multiname;
-use crate::avm2::{Activation, Error, Object, TObject, Value};
-use crate::display_object::TDisplayObject;
+use crate::avm2::{Activation, ArrayObject, ArrayStorage, Error, Object, TObject, Value};
+use crate::display_object::{PerspectiveProjection, TDisplayObject, Transform3D};
 use crate::prelude::{DisplayObject, Matrix, Twips};
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
@@ -70,7 +70,12 @@ pub fn get_matrix<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let dobj = get_display_object(this, activation)?;
+    // Objects in 3D space only have a `matrix3D`.
+    if dobj.transform_3d() != Transform3D::default() {
+        return Ok(Value::Null);
+    }
+    let matrix = *dobj.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -85,6 +90,8 @@ pub fn set_matrix<'gc>(
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
     let dobj = get_display_object(this, activation)?;
     dobj.set_matrix(activation.context.gc_context, matrix);
+    // Setting a 2D matrix takes the object out of 3D space.
+    dobj.set_transform_3d(activation.context.gc_context, Transform3D::default());
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
         // we only want to inform ancestors to avoid unnecessary invalidations for tx/ty
@@ -93,6 +100,59 @@ pub fn set_matrix<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Whether the display object has been placed in 3D space, in which case it has
+/// a `matrix3D` instead of a `matrix`.
+pub fn is_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    Ok((dobj.transform_3d() != Transform3D::default()).into())
+}
+
+/// The field of view and projection center of the perspective of the display object,
+/// as an array, or `null` if it doesn't have its own.
+pub fn get_perspective_projection_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = match dobj.perspective_projection() {
+        Some(projection) => projection,
+        // The root always has a perspective, which looks at the center of the stage.
+        None if dobj.is_root() => {
+            PerspectiveProjection::for_stage(activation.context.stage.stage_size())
+        }
+        None => return Ok(Value::Null),
+    };
+    let storage = ArrayStorage::from_args(&[
+        projection.field_of_view.into(),
+        projection.projection_center.0.into(),
+        projection.projection_center.1.into(),
+    ]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Gives the display object its own perspective, or removes it if the field of view is `NaN`.
+pub fn set_perspective_projection_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let field_of_view = args.get_f64(activation, 0)?;
+    let center_x = args.get_f64(activation, 1)?;
+    let center_y = args.get_f64(activation, 2)?;
+    let projection = (!field_of_view.is_nan()).then_some(PerspectiveProjection {
+        field_of_view,
+        projection_center: (center_x, center_y),
+    });
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
+    Ok(Value::Undefined)
+}
+
 pub fn get_concatenated_matrix<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -0,0 +1,118 @@
+//! `flash.globalization` namespace
+
+use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Error, Value};
+use crate::locale::{self, Locale, LocaleMatch, NumberFormat};
+use crate::string::AvmString;
+
+pub mod collator;
+pub mod currency_formatter;
+pub mod date_time_formatter;
+pub mod number_formatter;
+
+/// Finds the locale to use for a `requestedLocaleIDName`.
+///
+/// `LocaleID.DEFAULT` refers to the language of the user.
+fn find_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    requested: AvmString<'gc>,
+) -> (&'static Locale, LocaleMatch) {
+    let system = activation.context.ui.language().to_string();
+    locale::find_locale(&requested.to_utf8_lossy(), &system)
+}
+
+/// Creates a `Vector.<String>`.
+fn string_vector<'gc, S: AsRef<str>>(
+    activation: &mut Activation<'_, 'gc>,
+    strings: impl IntoIterator<Item = S>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let storage = VectorStorage::from_values(
+        strings
+            .into_iter()
+            .map(|s| AvmString::new_utf8(activation.context.gc_context, s.as_ref()).into())
+            .collect(),
+        false,
+        Some(activation.avm2().class_defs().string),
+    );
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Implements `getAvailableLocaleIDNames`, which every formatter has.
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    string_vector(activation, locale::locale_names())
+}
+
+/// The properties that `NumberFormatter` and `CurrencyFormatter` share.
+struct NumberSettings {
+    fractional_digits: u32,
+    trailing_zeros: bool,
+    leading_zero: bool,
+    use_grouping: bool,
+    grouping_pattern: String,
+    grouping_separator: String,
+    decimal_separator: String,
+    negative_symbol: String,
+    digits_type: u32,
+}
+
+impl NumberSettings {
+    fn read<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        formatter: Object<'gc>,
+    ) -> Result<Self, Error<'gc>> {
+        let mut get = |name: &'static str| formatter.get_public_property(name, activation);
+        let fractional_digits = get("fractionalDigits")?;
+        let trailing_zeros = get("trailingZeros")?;
+        let leading_zero = get("leadingZero")?;
+        let use_grouping = get("useGrouping")?;
+        let grouping_pattern = get("groupingPattern")?;
+        let grouping_separator = get("groupingSeparator")?;
+        let decimal_separator = get("decimalSeparator")?;
+        let negative_symbol = get("negativeSymbol")?;
+        let digits_type = get("digitsType")?;
+
+        Ok(Self {
+            fractional_digits: fractional_digits.coerce_to_i32(activation)?.max(0) as u32,
+            trailing_zeros: trailing_zeros.coerce_to_boolean(),
+            leading_zero: leading_zero.coerce_to_boolean(),
+            use_grouping: use_grouping.coerce_to_boolean(),
+            grouping_pattern: grouping_pattern
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned(),
+            grouping_separator: grouping_separator
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned(),
+            decimal_separator: decimal_separator
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned(),
+            negative_symbol: negative_symbol
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned(),
+            digits_type: digits_type.coerce_to_u32(activation)?,
+        })
+    }
+
+    fn format(&self, negative_number_format: u32) -> NumberFormat<'_> {
+        NumberFormat {
+            fractional_digits: self.fractional_digits,
+            trailing_zeros: self.trailing_zeros,
+            leading_zero: self.leading_zero,
+            use_grouping: self.use_grouping,
+            grouping_pattern: &self.grouping_pattern,
+            grouping_separator: &self.grouping_separator,
+            decimal_separator: &self.decimal_separator,
+            negative_symbol: &self.negative_symbol,
+            negative_number_format,
+            digits_type: self.digits_type,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+package flash.globalization {
+    import flash.globalization.CollatorMode;
+    import flash.globalization.LastOperationStatus;
+
+    public final class Collator {
+        private var _actualLocaleIDName:String;
+        private var _ignoreCase:Boolean = false;
+        private var _ignoreCharacterWidth:Boolean = false;
+        private var _ignoreDiacritics:Boolean = false;
+        private var _ignoreKanaType:Boolean = false;
+        private var _ignoreSymbols:Boolean = false;
+        private var _lastOperationStatus:String;
+        private var _localeIDName:String;
+        private var _numericComparison:Boolean = false;
+
+        private static function throwNonNull(name: String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
+
+        private static native function localeInfo(requestedLocaleIDName:String):Array;
+        private native function compareStrings(string1:String, string2:String):int;
+
+        public function Collator(requestedLocaleIDName:String, initialMode:String = "sorting") {
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
+            if (initialMode == null) throwNonNull("initialMode");
+            if (initialMode != CollatorMode.SORTING && initialMode != CollatorMode.MATCHING) {
+                throw new ArgumentError("Error #2008: Parameter initialMode must be one of the accepted values.", 2008);
+            }
+            this._localeIDName = requestedLocaleIDName;
+
+            var info:Array = localeInfo(requestedLocaleIDName);
+            this._actualLocaleIDName = info[0];
+            this._lastOperationStatus = info[1];
+
+            if (initialMode == CollatorMode.MATCHING) {
+                this._ignoreCase = true;
+                this._ignoreCharacterWidth = true;
+                this._ignoreDiacritics = true;
+                this._ignoreKanaType = true;
+            }
+        }
+
+        public function get actualLocaleIDName():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._actualLocaleIDName;
+        }
+
+        public function get ignoreCase():Boolean {
+            return this._ignoreCase;
+        }
+        public function set ignoreCase(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreCase = value;
+        }
+
+        public function get ignoreCharacterWidth():Boolean {
+            return this._ignoreCharacterWidth;
+        }
+        public function set ignoreCharacterWidth(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreCharacterWidth = value;
+        }
+
+        public function get ignoreDiacritics():Boolean {
+            return this._ignoreDiacritics;
+        }
+        public function set ignoreDiacritics(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreDiacritics = value;
+        }
+
+        public function get ignoreKanaType():Boolean {
+            return this._ignoreKanaType;
+        }
+        public function set ignoreKanaType(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreKanaType = value;
+        }
+
+        public function get ignoreSymbols():Boolean {
+            return this._ignoreSymbols;
+        }
+        public function set ignoreSymbols(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._ignoreSymbols = value;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get numericComparison():Boolean {
+            return this._numericComparison;
+        }
+        public function set numericComparison(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._numericComparison = value;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._localeIDName;
+        }
+
+        public function compare(string1:String, string2:String):int {
+            if (string1 == null) throwNonNull("string1");
+            if (string2 == null) throwNonNull("string2");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return compareStrings(string1, string2);
+        }
+
+        public function equals(string1:String, string2:String):Boolean {
+            return this.compare(string1, string2) == 0;
+        }
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+    }
+}
@@ -1,18 +1,200 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
+    import flash.globalization.CurrencyParseResult;
+    import flash.globalization.LastOperationStatus;
+    import flash.globalization.NationalDigitsType;
 
     public final class CurrencyFormatter {
+        private var _actualLocaleIDName:String;
+        private var _currencyISOCode:String;
+        private var _currencySymbol:String;
+        private var _decimalSeparator:String;
+        private var _digitsType:uint = NationalDigitsType.EUROPEAN;
+        private var _fractionalDigits:int;
+        private var _groupingPattern:String;
+        private var _groupingSeparator:String;
+        private var _lastOperationStatus:String;
+        private var _leadingZero:Boolean = true;
+        private var _localeIDName:String;
+        private var _negativeCurrencyFormat:uint;
+        private var _negativeSymbol:String = "-";
+        private var _positiveCurrencyFormat:uint;
+        private var _trailingZeros:Boolean = true;
+        private var _useGrouping:Boolean = true;
 
-        public var fractionalDigits: int = 0;
+        private static function throwNonNull(name: String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
+
+        private static function throwNotAccepted(name: String) {
+            throw new ArgumentError("Error #2008: Parameter " + name + " must be one of the accepted values.", 2008);
+        }
+
+        private static native function localeInfo(requestedLocaleIDName:String):Array;
+        private native function formatValue(value:Number, symbol:String):String;
+        private native function parseValue(inputString:String):Array;
+
+        public function CurrencyFormatter(requestedLocaleIDName:String) {
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
+            this._localeIDName = requestedLocaleIDName;
+
+            var info:Array = localeInfo(requestedLocaleIDName);
+            this._actualLocaleIDName = info[0];
+            this._lastOperationStatus = info[1];
+            this._decimalSeparator = info[2];
+            this._groupingSeparator = info[3];
+            this._groupingPattern = info[4];
+            this._currencyISOCode = info[5];
+            this._currencySymbol = info[6];
+            this._fractionalDigits = info[7];
+            this._positiveCurrencyFormat = info[8];
+            this._negativeCurrencyFormat = info[9];
+        }
+
+        public function get actualLocaleIDName():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._actualLocaleIDName;
+        }
+
+        public function get currencyISOCode():String {
+            return this._currencyISOCode;
+        }
+
+        public function get currencySymbol():String {
+            return this._currencySymbol;
+        }
+
+        public function get decimalSeparator():String {
+            return this._decimalSeparator;
+        }
+        public function set decimalSeparator(value:String):void {
+            if (value == null) throwNonNull("decimalSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._decimalSeparator = value;
+        }
+
+        public function get digitsType():uint {
+            return this._digitsType;
+        }
+        public function set digitsType(value:uint):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._digitsType = value;
+        }
+
+        public function get fractionalDigits():int {
+            return this._fractionalDigits;
+        }
+        public function set fractionalDigits(value:int):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._fractionalDigits = value;
+        }
+
+        public function get groupingPattern():String {
+            return this._groupingPattern;
+        }
+        public function set groupingPattern(value:String):void {
+            if (value == null) throwNonNull("groupingPattern");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._groupingPattern = value;
+        }
+
+        public function get groupingSeparator():String {
+            return this._groupingSeparator;
+        }
+        public function set groupingSeparator(value:String):void {
+            if (value == null) throwNonNull("groupingSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._groupingSeparator = value;
+        }
 
-        public function CurrencyFormatter(locale: String) {
-            stub_constructor("flash.globalization.CurrencyFormatter");
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get leadingZero():Boolean {
+            return this._leadingZero;
+        }
+        public function set leadingZero(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._leadingZero = value;
+        }
+
+        public function get negativeCurrencyFormat():uint {
+            return this._negativeCurrencyFormat;
+        }
+        public function set negativeCurrencyFormat(value:uint):void {
+            if (value > 15) throwNotAccepted("negativeCurrencyFormat");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._negativeCurrencyFormat = value;
+        }
+
+        public function get negativeSymbol():String {
+            return this._negativeSymbol;
+        }
+        public function set negativeSymbol(value:String):void {
+            if (value == null) throwNonNull("negativeSymbol");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._negativeSymbol = value;
+        }
+
+        public function get positiveCurrencyFormat():uint {
+            return this._positiveCurrencyFormat;
+        }
+        public function set positiveCurrencyFormat(value:uint):void {
+            if (value > 3) throwNotAccepted("positiveCurrencyFormat");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._positiveCurrencyFormat = value;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._localeIDName;
+        }
+
+        public function get trailingZeros():Boolean {
+            return this._trailingZeros;
+        }
+        public function set trailingZeros(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._trailingZeros = value;
+        }
+
+        public function get useGrouping():Boolean {
+            return this._useGrouping;
+        }
+        public function set useGrouping(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._useGrouping = value;
         }
 
         public function format(value:Number, withCurrencySymbol:Boolean = false):String {
-            stub_method("flash.globalization.CurrencyFormatter", "format");
-            return value.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatValue(value, withCurrencySymbol ? this._currencySymbol : this._currencyISOCode);
+        }
+
+        public function formattingWithCurrencySymbolIsSafe(requestedISOCode:String):Boolean {
+            if (requestedISOCode == null) throwNonNull("requestedISOCode");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return requestedISOCode == this._currencyISOCode;
+        }
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+
+        public function parse(inputString:String):CurrencyParseResult {
+            if (inputString == null) throwNonNull("inputString");
+            var result:Array = parseValue(inputString);
+            if (result == null) {
+                this._lastOperationStatus = LastOperationStatus.PARSE_ERROR;
+                return new CurrencyParseResult();
+            }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return new CurrencyParseResult(result[0], result[1]);
+        }
+
+        public function setCurrency(currencyISOCode:String, currencySymbol:String):void {
+            if (currencyISOCode == null) throwNonNull("currencyISOCode");
+            if (currencySymbol == null) throwNonNull("currencySymbol");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._currencyISOCode = currencyISOCode;
+            this._currencySymbol = currencySymbol;
         }
     }
 }
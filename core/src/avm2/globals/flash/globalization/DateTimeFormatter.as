@@ -1,12 +1,15 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
+    import flash.globalization.DateTimeNameContext;
+    import flash.globalization.DateTimeNameStyle;
+    import flash.globalization.DateTimeStyle;
     import flash.globalization.LastOperationStatus;
 
     public final class DateTimeFormatter {
+        private var _actualLocaleIDName:String;
         private var _dateStyle:String;
         private var _dateTimePattern:String;
+        private var _firstWeekday:int;
+        private var _lastOperationStatus:String;
         private var _localeIDName:String;
         private var _timeStyle:String;
 
@@ -14,22 +17,54 @@ package flash.globalization {
             throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
         }
 
+        private static function throwNotAccepted(name: String) {
+            throw new ArgumentError("Error #2008: Parameter " + name + " must be one of the accepted values.", 2008);
+        }
+
+        private static native function localeInfo(requestedLocaleIDName:String):Array;
+        private static native function stylePattern(localeIDName:String, dateStyle:String, timeStyle:String):String;
+        private static native function formatTime(localeIDName:String, pattern:String, time:Number, utc:Boolean):String;
+        private static native function monthNames(localeIDName:String, nameStyle:String, context:String):Vector.<String>;
+        private static native function weekdayNames(localeIDName:String, nameStyle:String):Vector.<String>;
+
+        private static function checkStyle(style:String, name:String) {
+            if (style == null) throwNonNull(name);
+            if (style != DateTimeStyle.LONG && style != DateTimeStyle.MEDIUM &&
+                style != DateTimeStyle.SHORT && style != DateTimeStyle.NONE) {
+                throwNotAccepted(name);
+            }
+        }
+
+        private static function checkNameArguments(nameStyle:String, context:String) {
+            if (nameStyle == null) throwNonNull("nameStyle");
+            if (context == null) throwNonNull("context");
+            if (nameStyle != DateTimeNameStyle.FULL && nameStyle != DateTimeNameStyle.LONG_ABBREVIATION &&
+                nameStyle != DateTimeNameStyle.SHORT_ABBREVIATION) {
+                throwNotAccepted("nameStyle");
+            }
+            if (context != DateTimeNameContext.FORMAT && context != DateTimeNameContext.STANDALONE) {
+                throwNotAccepted("context");
+            }
+        }
+
         public function DateTimeFormatter(requestedLocaleIDName:String, dateStyle:String = "long", timeStyle:String = "long") {
-            stub_constructor("flash.globalization.DateTimeFormatter");
             if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
             this._localeIDName = requestedLocaleIDName;
-            this._dateTimePattern = "EEEE, MMMM d, yyyy h:mm:ss a";
+
+            var info:Array = localeInfo(requestedLocaleIDName);
+            this._actualLocaleIDName = info[0];
+            this._firstWeekday = info[2];
             this.setDateTimeStyles(dateStyle, timeStyle);
+            this._lastOperationStatus = info[1];
         }
 
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._actualLocaleIDName;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get requestedLocaleIDName():String {
@@ -37,65 +72,66 @@ package flash.globalization {
         }
 
         public function format(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "format");
             if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatTime(this._actualLocaleIDName, this._dateTimePattern, dateTime.time, false);
         }
 
         public function formatUTC(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "formatUTC");
             if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toUTCString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatTime(this._actualLocaleIDName, this._dateTimePattern, dateTime.time, true);
         }
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
         public function getDateStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateStyle;
         }
 
         public function getDateTimePattern():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateTimePattern;
         }
 
         public function getFirstWeekday():int {
-            stub_method("flash.globalization.DateTimeFormatter", "getFirstWeekday");
-            return 0;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._firstWeekday;
         }
 
         public function getMonthNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getMonthNames");
-            if (nameStyle == null) throwNonNull("nameStyle");
-            if (context == null) throwNonNull("context");
-            return new <String>["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+            checkNameArguments(nameStyle, context);
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return monthNames(this._actualLocaleIDName, nameStyle, context);
         }
 
         public function getTimeStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._timeStyle;
         }
 
         public function getWeekdayNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getWeekdayNames");
-            if (nameStyle == null) throwNonNull("nameStyle");
-            if (context == null) throwNonNull("context");
-            return new <String>["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+            checkNameArguments(nameStyle, context);
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return weekdayNames(this._actualLocaleIDName, nameStyle);
         }
 
         public function setDateTimePattern(pattern:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimePattern");
             if (pattern == null) throwNonNull("pattern");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._dateTimePattern = pattern;
+            this._dateStyle = DateTimeStyle.CUSTOM;
+            this._timeStyle = DateTimeStyle.CUSTOM;
         }
 
         public function setDateTimeStyles(dateStyle:String, timeStyle:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimeStyles");
-            if (dateStyle == null) throwNonNull("dateStyle");
-            if (timeStyle == null) throwNonNull("timeStyle");
+            checkStyle(dateStyle, "dateStyle");
+            checkStyle(timeStyle, "timeStyle");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._dateStyle = dateStyle;
             this._timeStyle = timeStyle;
+            this._dateTimePattern = stylePattern(this._actualLocaleIDName, dateStyle, timeStyle);
         }
     }
-}
\ No newline at end of file
+}
@@ -1,61 +1,125 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
 
     public final class LocaleID {
         public static const DEFAULT:String = "i-default";
 
+        private static const RIGHT_TO_LEFT_LANGUAGES:Array = ["ar", "dv", "fa", "he", "iw", "ji", "ps", "sd", "ug", "ur", "yi"];
+        private static const RIGHT_TO_LEFT_SCRIPTS:Array = ["Arab", "Hebr", "Syrc", "Thaa"];
+
         private var _name:String;
+        private var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
+        private var _language:String = "";
+        private var _script:String = "";
+        private var _region:String = "";
+        private var _variant:String = "";
+        private var _keywords:Object = {};
+
+        private static function throwNonNull(name: String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
 
         public function LocaleID(name:String) {
-            stub_constructor("flash.globalization.LocaleID");
+            if (name == null) throwNonNull("name");
             this._name = name;
+
+            var keywordStart:int = name.indexOf("@");
+            var tag:String = keywordStart >= 0 ? name.substring(0, keywordStart) : name;
+            if (keywordStart >= 0) {
+                for each (var pair:String in name.substring(keywordStart + 1).split(";")) {
+                    var separator:int = pair.indexOf("=");
+                    if (separator > 0) {
+                        this._keywords[pair.substring(0, separator)] = pair.substring(separator + 1);
+                    }
+                }
+            }
+
+            var subtags:Array = tag.split("_").join("-").split("-");
+            this._language = subtags.shift().toLowerCase();
+            if (subtags.length > 0 && /^[A-Za-z]{4}$/.test(subtags[0])) {
+                var script:String = subtags.shift();
+                this._script = script.charAt(0).toUpperCase() + script.substring(1).toLowerCase();
+            }
+            if (subtags.length > 0 && /^([A-Za-z]{2}|[0-9]{3})$/.test(subtags[0])) {
+                this._region = subtags.shift().toUpperCase();
+            }
+            this._variant = subtags.join("-").toUpperCase();
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.LocaleID", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get name():String {
             return this._name;
         }
 
+        private static function normalize(name:String):String {
+            return name.split("_").join("-").toLowerCase();
+        }
+
+        private static function languageOf(name:String):String {
+            return normalize(name).split("@")[0].split("-")[0];
+        }
+
         public static function determinePreferredLocales(want:Vector.<String>, have:Vector.<String>, keyword:String = "userinterface"):Vector.<String> {
-            stub_method("flash.globalization.LocaleID", "determinePreferredLocales");
-            return null;
+            if (want == null) throwNonNull("want");
+            if (have == null) throwNonNull("have");
+
+            var result:Vector.<String> = new <String>[];
+            var wanted:String;
+            var candidate:String;
+            for each (wanted in want) {
+                for each (candidate in have) {
+                    if (normalize(candidate) == normalize(wanted) && result.indexOf(candidate) < 0) {
+                        result.push(candidate);
+                    }
+                }
+            }
+            for each (wanted in want) {
+                for each (candidate in have) {
+                    if (languageOf(candidate) == languageOf(wanted) && result.indexOf(candidate) < 0) {
+                        result.push(candidate);
+                    }
+                }
+            }
+            return result;
         }
 
         public function getKeysAndValues():Object {
-            stub_method("flash.globalization.LocaleID", "getKeysAndValues");
-            return new Object();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            var result:Object = {};
+            for (var key:String in this._keywords) {
+                result[key] = this._keywords[key];
+            }
+            return result;
         }
 
         public function getLanguage():String {
-            stub_method("flash.globalization.LocaleID", "getLanguage");
-            return this._name;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._language;
         }
 
         public function getRegion():String {
-            stub_method("flash.globalization.LocaleID", "getRegion");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._region;
         }
 
         public function getScript():String {
-            stub_method("flash.globalization.LocaleID", "getScript");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._script;
         }
 
         public function getVariant():String {
-            stub_method("flash.globalization.LocaleID", "getVariant");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._variant;
         }
 
         public function isRightToLeft():Boolean {
-            stub_method("flash.globalization.LocaleID", "isRightToLeft");
-            return false;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return RIGHT_TO_LEFT_LANGUAGES.indexOf(this._language) >= 0 ||
+                RIGHT_TO_LEFT_SCRIPTS.indexOf(this._script) >= 0;
         }
     }
 }
@@ -1,38 +1,55 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
     import flash.globalization.NationalDigitsType;
     import flash.globalization.NumberParseResult;
 
     public final class NumberFormatter {
-        private var _decimalSeparator = ".";
-        private var _digitsType = NationalDigitsType.EUROPEAN;
-        private var _fractionalDigits = 2;
-        private var _groupingPattern = "3;*";
-        private var _groupingSeparator = ",";
-        private var _leadingZero = true;
+        private var _actualLocaleIDName:String;
+        private var _decimalSeparator:String;
+        private var _digitsType:uint = NationalDigitsType.EUROPEAN;
+        private var _fractionalDigits:int = 2;
+        private var _groupingPattern:String;
+        private var _groupingSeparator:String;
+        private var _lastOperationStatus:String;
+        private var _leadingZero:Boolean = true;
         private var _localeIDName:String;
-        private var _negativeNumberFormat = 1;
-        private var _negativeSymbol = "-";
-        private var _trailingZeros = true;
-        private var _useGrouping = true;
+        private var _negativeNumberFormat:uint;
+        private var _negativeSymbol:String = "-";
+        private var _trailingZeros:Boolean = true;
+        private var _useGrouping:Boolean = true;
+
+        private static function throwNonNull(name: String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
+
+        private static native function localeInfo(requestedLocaleIDName:String):Array;
+        private native function formatValue(value:Number):String;
+        private native function parseValue(parseString:String, whole:Boolean):Array;
 
         public function NumberFormatter(requestedLocaleIDName:String) {
-            stub_constructor("flash.globalization.NumberFormatter");
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
             this._localeIDName = requestedLocaleIDName;
+
+            var info:Array = localeInfo(requestedLocaleIDName);
+            this._actualLocaleIDName = info[0];
+            this._lastOperationStatus = info[1];
+            this._decimalSeparator = info[2];
+            this._groupingSeparator = info[3];
+            this._groupingPattern = info[4];
+            this._negativeNumberFormat = info[5];
         }
 
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.NumberFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._actualLocaleIDName;
         }
 
         public function get decimalSeparator():String {
             return this._decimalSeparator;
         }
         public function set decimalSeparator(value:String):void {
+            if (value == null) throwNonNull("decimalSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._decimalSeparator = value;
         }
 
@@ -40,6 +57,7 @@ package flash.globalization {
             return this._digitsType;
         }
         public function set digitsType(value:uint):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._digitsType = value;
         }
 
@@ -47,6 +65,7 @@ package flash.globalization {
             return this._fractionalDigits;
         }
         public function set fractionalDigits(value:int):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._fractionalDigits = value;
         }
 
@@ -54,6 +73,8 @@ package flash.globalization {
             return this._groupingPattern;
         }
         public function set groupingPattern(value:String):void {
+            if (value == null) throwNonNull("groupingPattern");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._groupingPattern = value;
         }
 
@@ -61,18 +82,20 @@ package flash.globalization {
             return this._groupingSeparator;
         }
         public function set groupingSeparator(value:String):void {
+            if (value == null) throwNonNull("groupingSeparator");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._groupingSeparator = value;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.NumberFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get leadingZero():Boolean {
             return this._leadingZero;
         }
         public function set leadingZero(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._leadingZero = value;
         }
 
@@ -80,15 +103,19 @@ package flash.globalization {
             return this._negativeNumberFormat;
         }
         public function set negativeNumberFormat(value:uint):void {
-            if (value >= 0 && value <= 4) {
-                this._negativeNumberFormat = value;
+            if (value > 4) {
+                throw new ArgumentError("Error #2008: Parameter negativeNumberFormat must be one of the accepted values.", 2008);
             }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            this._negativeNumberFormat = value;
         }
 
         public function get negativeSymbol():String {
             return this._negativeSymbol;
         }
         public function set negativeSymbol(value:String):void {
+            if (value == null) throwNonNull("negativeSymbol");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._negativeSymbol = value;
         }
 
@@ -100,6 +127,7 @@ package flash.globalization {
             return this._trailingZeros;
         }
         public function set trailingZeros(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._trailingZeros = value;
         }
 
@@ -107,37 +135,47 @@ package flash.globalization {
             return this._useGrouping;
         }
         public function set useGrouping(value:Boolean):void {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             this._useGrouping = value;
         }
 
         public function formatInt(value:int):String {
-            stub_method("flash.globalization.NumberFormatter", "formatInt");
-            return value.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatValue(value);
         }
 
         public function formatNumber(value:Number):String {
-            stub_method("flash.globalization.NumberFormatter", "formatNumber");
-            return value.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatValue(value);
         }
 
         public function formatUint(value:uint):String {
-            stub_method("flash.globalization.NumberFormatter", "formatUint");
-            return value.toString();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return formatValue(value);
         }
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.NumberFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
         public function parse(parseString:String):NumberParseResult {
-            stub_method("flash.globalization.NumberFormatter", "parse");
-            return new NumberParseResult();
+            if (parseString == null) throwNonNull("parseString");
+            var result:Array = parseValue(parseString, false);
+            if (result == null) {
+                this._lastOperationStatus = LastOperationStatus.PARSE_ERROR;
+                return new NumberParseResult();
+            }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return new NumberParseResult(result[0], result[1], result[2]);
         }
 
         public function parseNumber(parseString:String):Number {
-            stub_method("flash.globalization.NumberFormatter", "parseNumber");
-            return Number(parseString);
+            if (parseString == null) throwNonNull("parseString");
+            var result:Array = parseValue(parseString, true);
+            if (result == null) {
+                this._lastOperationStatus = LastOperationStatus.PARSE_ERROR;
+                return NaN;
+            }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return result[0];
         }
     }
 }
@@ -0,0 +1,61 @@
+//! `flash.globalization.Collator` native methods
+
+use super::find_locale;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Value};
+use crate::locale::{self, CollationOptions};
+use crate::string::AvmString;
+use std::cmp::Ordering;
+
+pub use super::get_available_locale_id_names;
+
+/// Implements the private `Collator.localeInfo`, which returns the actual locale ID and the
+/// status of the lookup.
+pub fn locale_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let (locale, locale_match) = find_locale(activation, requested);
+
+    let mc = activation.context.gc_context;
+    let storage = ArrayStorage::from_args(&[
+        AvmString::new_utf8(mc, locale.name).into(),
+        AvmString::new_utf8(mc, locale_match.status()).into(),
+    ]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements the private `Collator.compareStrings`, which backs `compare` and `equals`.
+pub fn compare_strings<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let a = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let b = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+
+    let mut get = |name: &'static str| {
+        Ok::<_, Error<'gc>>(
+            this.get_public_property(name, activation)?
+                .coerce_to_boolean(),
+        )
+    };
+    let options = CollationOptions {
+        ignore_case: get("ignoreCase")?,
+        ignore_character_width: get("ignoreCharacterWidth")?,
+        ignore_diacritics: get("ignoreDiacritics")?,
+        ignore_kana_type: get("ignoreKanaType")?,
+        ignore_symbols: get("ignoreSymbols")?,
+        numeric_comparison: get("numericComparison")?,
+    };
+
+    Ok(match locale::compare(&a, &b, options) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+    .into())
+}
@@ -0,0 +1,90 @@
+//! `flash.globalization.CurrencyFormatter` native methods
+
+use super::{find_locale, NumberSettings};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Value};
+use crate::string::AvmString;
+
+pub use super::get_available_locale_id_names;
+
+/// Implements the private `CurrencyFormatter.localeInfo`, which returns the actual locale
+/// ID, the status of the lookup and the defaults of the locale.
+pub fn locale_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let (locale, locale_match) = find_locale(activation, requested);
+
+    let mc = activation.context.gc_context;
+    let storage = ArrayStorage::from_args(&[
+        AvmString::new_utf8(mc, locale.name).into(),
+        AvmString::new_utf8(mc, locale_match.status()).into(),
+        AvmString::new_utf8(mc, locale.decimal_separator).into(),
+        AvmString::new_utf8(mc, locale.grouping_separator).into(),
+        AvmString::new_utf8(mc, locale.grouping_pattern).into(),
+        AvmString::new_utf8(mc, locale.currency_iso_code).into(),
+        AvmString::new_utf8(mc, locale.currency_symbol).into(),
+        locale.currency_fractional_digits.into(),
+        locale.positive_currency_format.into(),
+        locale.negative_currency_format.into(),
+    ]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements the private `CurrencyFormatter.formatValue`, which backs `format`.
+pub fn format_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let symbol = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let settings = NumberSettings::read(activation, this)?;
+    let positive_format = this
+        .get_public_property("positiveCurrencyFormat", activation)?
+        .coerce_to_u32(activation)?;
+    let negative_format = this
+        .get_public_property("negativeCurrencyFormat", activation)?
+        .coerce_to_u32(activation)?;
+
+    let formatted =
+        settings
+            .format(1)
+            .format_currency(value, &symbol, positive_format, negative_format);
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements the private `CurrencyFormatter.parseValue`, which returns the amount and the
+/// currency symbol or ISO code it was written with, or null if it isn't a currency amount.
+pub fn parse_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let string = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let settings = NumberSettings::read(activation, this)?;
+    let iso_code = this
+        .get_public_property("currencyISOCode", activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    let symbol = this
+        .get_public_property("currencySymbol", activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+
+    let Some((value, found)) = settings
+        .format(1)
+        .parse_currency(&string, &[iso_code.as_str(), symbol.as_str()])
+    else {
+        return Ok(Value::Null);
+    };
+
+    let found = AvmString::new_utf8(activation.context.gc_context, found);
+    let storage = ArrayStorage::from_args(&[value.into(), found.into()]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
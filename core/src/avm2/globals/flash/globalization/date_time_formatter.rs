@@ -0,0 +1,102 @@
+//! `flash.globalization.DateTimeFormatter` native methods
+
+use super::{find_locale, string_vector};
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Value};
+use crate::locale::{self, get_timezone};
+use crate::string::AvmString;
+use chrono::{FixedOffset, TimeZone, Utc};
+
+pub use super::get_available_locale_id_names;
+
+/// Implements the private `DateTimeFormatter.localeInfo`, which returns the actual locale
+/// ID, the status of the lookup and the first day of the week.
+pub fn locale_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let (locale, locale_match) = find_locale(activation, requested);
+
+    let mc = activation.context.gc_context;
+    let storage = ArrayStorage::from_args(&[
+        AvmString::new_utf8(mc, locale.name).into(),
+        AvmString::new_utf8(mc, locale_match.status()).into(),
+        locale.first_weekday.into(),
+    ]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements the private `DateTimeFormatter.stylePattern`, which returns the pattern a
+/// locale uses for a date and time style.
+pub fn style_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale_id = args.get_string(activation, 0)?;
+    let date_style = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let time_style = args.get_string(activation, 2)?.to_utf8_lossy().into_owned();
+    let (locale, _) = find_locale(activation, locale_id);
+
+    let pattern = locale::style_pattern(locale, &date_style, &time_style);
+    Ok(AvmString::new_utf8(activation.context.gc_context, pattern).into())
+}
+
+/// Implements the private `DateTimeFormatter.formatTime`, which formats a time in
+/// milliseconds since the epoch, in either local time or UTC.
+pub fn format_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale_id = args.get_string(activation, 0)?;
+    let pattern = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let time = args.get_f64(activation, 2)?;
+    let utc = args.get_bool(3);
+    let (locale, _) = find_locale(activation, locale_id);
+
+    let offset = if utc {
+        FixedOffset::east_opt(0).expect("UTC is a valid offset")
+    } else {
+        get_timezone()
+    };
+    let Some(date) = (time.is_finite())
+        .then(|| Utc.timestamp_millis_opt(time as i64).single())
+        .flatten()
+    else {
+        return Ok("Invalid Date".into());
+    };
+
+    let formatted = locale::format_date(locale, &pattern, date.with_timezone(&offset));
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements the private `DateTimeFormatter.monthNames`
+pub fn month_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale_id = args.get_string(activation, 0)?;
+    let style = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let context = args.get_string(activation, 2)?.to_utf8_lossy().into_owned();
+    let (locale, _) = find_locale(activation, locale_id);
+
+    string_vector(activation, locale::month_names(locale, &style, &context))
+}
+
+/// Implements the private `DateTimeFormatter.weekdayNames`
+pub fn weekday_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale_id = args.get_string(activation, 0)?;
+    let style = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let (locale, _) = find_locale(activation, locale_id);
+
+    string_vector(activation, locale::weekday_names(locale, &style))
+}
@@ -0,0 +1,77 @@
+//! `flash.globalization.NumberFormatter` native methods
+
+use super::{find_locale, NumberSettings};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Value};
+use crate::string::AvmString;
+
+pub use super::get_available_locale_id_names;
+
+/// Implements the private `NumberFormatter.localeInfo`, which returns the actual locale ID,
+/// the status of the lookup and the defaults of the locale.
+pub fn locale_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let (locale, locale_match) = find_locale(activation, requested);
+
+    let mc = activation.context.gc_context;
+    let storage = ArrayStorage::from_args(&[
+        AvmString::new_utf8(mc, locale.name).into(),
+        AvmString::new_utf8(mc, locale_match.status()).into(),
+        AvmString::new_utf8(mc, locale.decimal_separator).into(),
+        AvmString::new_utf8(mc, locale.grouping_separator).into(),
+        AvmString::new_utf8(mc, locale.grouping_pattern).into(),
+        locale.negative_number_format.into(),
+    ]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements the private `NumberFormatter.formatValue`, which backs `formatInt`,
+/// `formatUint` and `formatNumber`.
+pub fn format_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let settings = NumberSettings::read(activation, this)?;
+    let negative_number_format = this
+        .get_public_property("negativeNumberFormat", activation)?
+        .coerce_to_u32(activation)?;
+
+    let formatted = settings.format(negative_number_format).format_number(value);
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements the private `NumberFormatter.parseValue`, which returns the value and where
+/// it was found, or null if there was no number.
+///
+/// If `whole` is true, the string may only contain whitespace besides the number.
+pub fn parse_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let string = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let whole = args.get_bool(1);
+    let settings = NumberSettings::read(activation, this)?;
+    let format = settings.format(0);
+
+    let parsed = if whole {
+        format
+            .parse_number(&string)
+            .map(|value| (value, 0, string.chars().count()))
+    } else {
+        format.parse(&string)
+    };
+    let Some((value, start, end)) = parsed else {
+        return Ok(Value::Null);
+    };
+
+    let storage = ArrayStorage::from_args(&[value.into(), start.into(), end.into()]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
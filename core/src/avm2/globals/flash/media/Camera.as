@@ -1,10 +1,32 @@
 package flash.media {
+    import flash.display.Shape;
+    import flash.events.Event;
     import flash.events.EventDispatcher;
+    import flash.events.StatusEvent;
     import flash.utils.ByteArray;
     import flash.geom.Rectangle;
     import flash.display.BitmapData;
 
     public final class Camera extends EventDispatcher {
+        // Cameras are shared by every `getCamera` call with the same index.
+        private static var _cameras:Array = [];
+
+        // Dispatches `enterFrame`, which the capture status is checked on.
+        private static var _ticker:Shape;
+
+        private var _index:int;
+        private var _width:int = 160;
+        private var _height:int = 120;
+        private var _fps:Number = 15;
+        private var _capturing:Boolean = false;
+
+        // The number of `Video`s on the stage that this camera is attached to.
+        private var _attachments:int = 0;
+
+        // Whether the user last allowed ("active") or denied access, to dispatch a `StatusEvent`
+        // when that changes. Stopping and restarting the capture doesn't ask the user again.
+        private var _status:String = "closed";
+
         [API("682")]
         public function copyToByteArray(rect:Rectangle, destination:ByteArray) {
             __ruffle__.stub_method("flash.media.Camera", "copyToByteArray");
@@ -17,13 +39,69 @@ package flash.media {
 
         [API("682")]
         public function drawToBitmapData(destination:BitmapData) {
-            __ruffle__.stub_method("flash.media.Camera", "drawToBitmapData");
+            if (destination == null) {
+                throw new TypeError("Error #2007: Parameter destination must be non-null.", 2007);
+            }
+            drawFrame(this._index, destination);
         }
 
+        private static native function drawFrame(index:int, destination:BitmapData):void;
+
         public static function getCamera(name: String = null):Camera {
-            __ruffle__.stub_method("flash.media.Camera", "getCamera");
-            return null;
-        }
+            // The name of a camera is its index, as a string.
+            var index:int = name == null ? 0 : int(name);
+            if (index < 0 || index >= Camera.names.length) {
+                return null;
+            }
+            if (_cameras[index] == null) {
+                var camera:Camera = new Camera();
+                camera._index = index;
+                _cameras[index] = camera;
+            }
+            return _cameras[index];
+        }
+
+        // Called by `Video` when it starts showing what this camera sees.
+        internal function attach():void {
+            this._attachments++;
+            if (!this._capturing) {
+                if (_ticker == null) {
+                    _ticker = new Shape();
+                }
+                this._capturing = true;
+                _ticker.addEventListener(Event.ENTER_FRAME, this.checkStatus);
+                open(this._index, this._width, this._height, this._fps);
+            }
+        }
+
+        // Called by `Video` when it stops showing what this camera sees. The camera stops
+        // capturing once nothing shows it.
+        internal function detach():void {
+            this._attachments--;
+            if (this._attachments <= 0 && this._capturing) {
+                this._attachments = 0;
+                this._capturing = false;
+                _ticker.removeEventListener(Event.ENTER_FRAME, this.checkStatus);
+                close(this._index);
+            }
+        }
+
+        private function checkStatus(event:Event):void {
+            var status:String = captureStatus(this._index);
+            if ((status != "active" && status != "denied") || status == this._status) {
+                return;
+            }
+            this._status = status;
+            if (status == "active") {
+                this.dispatchEvent(new StatusEvent(StatusEvent.STATUS, false, false, "Camera.Unmuted", "status"));
+            } else if (status == "denied") {
+                this.dispatchEvent(new StatusEvent(StatusEvent.STATUS, false, false, "Camera.Muted", "status"));
+            }
+        }
+
+        private static native function open(index:int, width:int, height:int, fps:Number):void;
+        private static native function close(index:int):void;
+        private static native function captureStatus(index:int):String;
 
         public function setKeyFrameInterval(keyFrameInterval:int) {
             __ruffle__.stub_method("flash.media.Camera", "setKeyFrameInterval");
@@ -34,7 +112,15 @@ package flash.media {
         }
 
         public function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true) {
-            __ruffle__.stub_method("flash.media.Camera", "setMode");
+            if (!favorArea) {
+                __ruffle__.stub_method("flash.media.Camera", "setMode", "with favorArea false");
+            }
+            this._width = width;
+            this._height = height;
+            this._fps = fps;
+            if (this._capturing) {
+                open(this._index, this._width, this._height, this._fps);
+            }
         }
 
         public function setMotionLevel(motionLevel:int, timeout:int = 2000) {
@@ -61,23 +147,19 @@ package flash.media {
         }
 
         public function get fps(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "fps");
-            return 0;
+            return this._fps;
         }
 
         public function get height(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "height");
-            return 0;
+            return this._height;
         }
 
         public function get index(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "index");
-            return 0;
+            return this._index;
         }
 
         public static function get isSupported(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "isSupported");
-            return false;
+            return Camera.names.length > 0;
         }
 
         public function get keyFrameInterval(): int {
@@ -101,28 +183,24 @@ package flash.media {
         }
 
         public function get muted(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "muted");
-            return true;
+            return isMuted(this._index);
         }
 
+        private static native function isMuted(index:int):Boolean;
+
         public function get name(): String {
-            __ruffle__.stub_getter("flash.media.Camera", "name");
-            return "";
+            return Camera.names[this._index];
         }
 
-        public static function get names(): Array {
-            __ruffle__.stub_getter("flash.media.Camera", "names");
-            return [];
-        }
-        
+        public static native function get names(): Array;
+
         public function get quality(): int {
             __ruffle__.stub_getter("flash.media.Camera", "quality");
             return 0;
         }
         
         public function get width(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "width");
-            return 0;
+            return this._width;
         }
     }
 
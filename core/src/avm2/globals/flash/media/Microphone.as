@@ -1,8 +1,45 @@
 package flash.media {
+    import flash.display.Shape;
+    import flash.events.ActivityEvent;
+    import flash.events.Event;
     import flash.events.EventDispatcher;
+    import flash.events.SampleDataEvent;
+    import flash.events.StatusEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.getTimer;
+
+    namespace ruffle = "__ruffle__";
 
     public final class Microphone extends EventDispatcher {
-        
+        // Microphones are shared by every `getMicrophone` call with the same index.
+        private static var _microphones:Array = [];
+
+        // Dispatches `enterFrame`, which captured samples are processed on.
+        private static var _ticker:Shape;
+
+        private var _index:int;
+        private var _rate:int = 8;
+        private var _gain:Number = 50;
+        private var _silenceLevel:Number = 10;
+        private var _silenceTimeout:int = 2000;
+        private var _activityLevel:Number = -1;
+        private var _capturing:Boolean = false;
+
+        // The number of `NetStream`s that this microphone is attached to.
+        private var _attachments:int = 0;
+
+        // Whether the user last allowed ("active") or denied access, to dispatch a `StatusEvent`
+        // when that changes. Stopping and restarting the capture doesn't ask the user again.
+        private var _status:String = "closed";
+
+        // Whether the activity level is above the silence level, and when it last was.
+        private var _active:Boolean = false;
+        private var _lastActivity:int = 0;
+
+        // The number of samples dispatched so far.
+        private var _position:Number = 0;
+
+
         [API("672")]
         public static function getEnhancedMicrophone(index:int = -1):Microphone {
             __ruffle__.stub_method("flash.media.Microphone", "getEnhancedMicrophone");
@@ -10,16 +47,125 @@ package flash.media {
         }
 
         public static function getMicrophone(index:int = -1):Microphone {
-            __ruffle__.stub_method("flash.media.Microphone", "getMicrophone");
-            return new Microphone();
-        }
+            if (index < 0) {
+                index = 0;
+            }
+            if (index >= Microphone.names.length) {
+                return null;
+            }
+            if (_microphones[index] == null) {
+                var microphone:Microphone = new Microphone();
+                microphone._index = index;
+                _microphones[index] = microphone;
+            }
+            return _microphones[index];
+        }
+
+        // Called by `NetStream.attachAudio`.
+        ruffle function attach():void {
+            this._attachments++;
+            this.updateCapture();
+        }
+
+        ruffle function detach():void {
+            this._attachments--;
+            this.updateCapture();
+        }
+
+        override public function addEventListener(type:String, listener:Function, useCapture:Boolean = false, priority:int = 0, useWeakReference:Boolean = false):void {
+            super.addEventListener(type, listener, useCapture, priority, useWeakReference);
+            this.updateCapture();
+        }
+
+        override public function removeEventListener(type:String, listener:Function, useCapture:Boolean = false):void {
+            super.removeEventListener(type, listener, useCapture);
+            this.updateCapture();
+        }
+
+        // Like Flash, the microphone only captures while it's attached to a `NetStream`,
+        // or while something listens for its samples.
+        private function updateCapture():void {
+            var wanted:Boolean = this._attachments > 0 || this.hasEventListener(SampleDataEvent.SAMPLE_DATA);
+            if (wanted && !this._capturing) {
+                if (_ticker == null) {
+                    _ticker = new Shape();
+                }
+                this._capturing = true;
+                _ticker.addEventListener(Event.ENTER_FRAME, this.processSamples);
+                open(this._index, sampleRate(this._rate));
+            } else if (!wanted && this._capturing) {
+                this._capturing = false;
+                _ticker.removeEventListener(Event.ENTER_FRAME, this.processSamples);
+                close(this._index);
+                this._activityLevel = -1;
+                this._active = false;
+            }
+        }
+
+        // Converts a rate in kHz, as Flash names them, to a sample rate in Hz.
+        private static function sampleRate(rate:int):int {
+            switch (rate) {
+                case 5: return 5512;
+                case 8: return 8000;
+                case 11: return 11025;
+                case 16: return 16000;
+                case 22: return 22050;
+                default: return 44100;
+            }
+        }
+
+        private function processSamples(event:Event):void {
+            var status:String = captureStatus(this._index);
+            if ((status == "active" || status == "denied") && status != this._status) {
+                this._status = status;
+                if (status == "active") {
+                    this.dispatchEvent(new StatusEvent(StatusEvent.STATUS, false, false, "Microphone.Unmuted", "status"));
+                } else if (status == "denied") {
+                    this.dispatchEvent(new StatusEvent(StatusEvent.STATUS, false, false, "Microphone.Muted", "status"));
+                }
+            }
+            if (status != "active") {
+                this._activityLevel = -1;
+                return;
+            }
+            var data:ByteArray = new ByteArray();
+            this._activityLevel = takeSamples(this._index, data, this._gain);
+
+            var now:int = getTimer();
+            if (this._activityLevel > this._silenceLevel) {
+                this._lastActivity = now;
+                if (!this._active) {
+                    this._active = true;
+                    this.dispatchEvent(new ActivityEvent(ActivityEvent.ACTIVITY, false, false, true));
+                }
+            } else if (this._active && now - this._lastActivity >= this._silenceTimeout) {
+                this._active = false;
+                this.dispatchEvent(new ActivityEvent(ActivityEvent.ACTIVITY, false, false, false));
+            }
+
+            // Like Flash, no audio is sent while the microphone is silent.
+            if (data.length > 0 && (this._active || this._silenceLevel <= 0)) {
+                data.position = 0;
+                this.dispatchEvent(new SampleDataEvent(SampleDataEvent.SAMPLE_DATA, false, false, this._position, data));
+                this._position += data.length / 4;
+            }
+        }
+
+        private static native function open(index:int, sampleRate:int):void;
+        private static native function close(index:int):void;
+        private static native function isMuted(index:int):Boolean;
+        private static native function captureStatus(index:int):String;
+        private static native function takeSamples(index:int, data:ByteArray, gain:Number):Number;
 
         public function setLoopBack(isLooped:Boolean=true) {
             __ruffle__.stub_method("flash.media.Microphone", "setLoopBack");
         }
 
         public function setSilenceLevel(silenceLevel:Number, timeout:int = -1) {
-            __ruffle__.stub_method("flash.media.Microphone", "setSilenceLevel");
+            this._silenceLevel = Math.max(0, Math.min(100, silenceLevel));
+            if (timeout >= 0) {
+                this._silenceTimeout = timeout;
+            }
         }
 
         public function setUseEchoSuppression(isEchoSuppressed:Boolean) {
@@ -27,8 +173,7 @@ package flash.media {
         }
 
         public function get activityLevel():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "activityLevel");
-            return 0.0;
+            return this._activityLevel;
         }
 
         public function get codec():String {
@@ -79,38 +224,30 @@ package flash.media {
         }
 
         public function get gain():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "gain");
-            return 0.0;
+            return this._gain;
         }
 
         public function set gain(gain:Number) {
-            __ruffle__.stub_setter("flash.media.Microphone", "gain");
+            this._gain = Math.max(0, Math.min(100, gain));
         }
 
         public function get index():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "index");
-            return 0;
+            return this._index;
         }
 
         public static function get isSupported():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "isSupported");
-            return false;
+            return Microphone.names.length > 0;
         }
 
         public function get muted():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "muted");
-            return true;
+            return isMuted(this._index);
         }
 
-        public function get name():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "name");
-            return "";
+        public function get name():String {
+            return Microphone.names[this._index];
         }
 
-        public static function get names():Array {
-            __ruffle__.stub_getter("flash.media.Microphone", "names");
-            return [];
-        }
+        public static native function get names():Array;
 
         public function get noiseSuppressionLevel():int {
             __ruffle__.stub_getter("flash.media.Microphone", "noiseSuppressionLevel");
@@ -122,22 +259,26 @@ package flash.media {
         }
 
         public function get rate():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "rate");
-            return 0;
+            return this._rate;
         }
 
-        public function set rate(level:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "rate");
+        public function set rate(rate:int) {
+            // Unsupported rates fall back to the default of 8 kHz.
+            if (rate != 5 && rate != 11 && rate != 16 && rate != 22 && rate != 44) {
+                rate = 8;
+            }
+            this._rate = rate;
+            if (this._capturing) {
+                open(this._index, sampleRate(rate));
+            }
         }
 
-        public function get silenceLevel():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceLevel");
-            return 0;
+        public function get silenceLevel():Number {
+            return this._silenceLevel;
         }
 
         public function get silenceTimeout():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceTimeout");
-            return 0;
+            return this._silenceTimeout;
         }
 
         public function get soundTransform():flash.media.SoundTransform {
@@ -0,0 +1,108 @@
+package flash.media {
+    import __ruffle__.stub_method;
+
+    import flash.display.BitmapData;
+    import flash.display.Stage;
+    import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
+
+    // The web page is displayed by the frontend, on top of the movie.
+    [API("669")]
+    public final class StageWebView extends EventDispatcher {
+        private var _stage:Stage = null;
+        private var _viewPort:Rectangle = null;
+        private var _disposed:Boolean = false;
+
+        public function StageWebView(useNative:Boolean = false, mediaPlaybackRequiresUserAction:Boolean = true) {
+            this.createView();
+        }
+
+        private native function createView():void;
+        private native function setViewPort(visible:Boolean, x:Number, y:Number, width:Number, height:Number):void;
+        private native function loadURLInternal(url:String):void;
+        private native function loadStringInternal(text:String, mimeType:String):void;
+        private native function navigate(navigation:String):void;
+        private native function disposeView():void;
+
+        public static native function get isSupported():Boolean;
+
+        public function get stage():Stage {
+            return this._stage;
+        }
+
+        public function set stage(value:Stage):void {
+            this._stage = value;
+            this.syncViewPort();
+        }
+
+        public function get viewPort():Rectangle {
+            return this._viewPort === null ? null : this._viewPort.clone();
+        }
+
+        public function set viewPort(value:Rectangle):void {
+            if (value === null || value.width < 0 || value.height < 0) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            this._viewPort = value.clone();
+            this.syncViewPort();
+        }
+
+        public native function get location():String;
+        public native function get title():String;
+        public native function get isHistoryBackEnabled():Boolean;
+        public native function get isHistoryForwardEnabled():Boolean;
+
+        private function syncViewPort():void {
+            if (this._disposed) {
+                return;
+            }
+            if (this._stage === null || this._viewPort === null) {
+                this.setViewPort(false, 0, 0, 0, 0);
+            } else {
+                this.setViewPort(true, this._viewPort.x, this._viewPort.y, this._viewPort.width, this._viewPort.height);
+            }
+        }
+
+        public function loadURL(url:String):void {
+            this.loadURLInternal(url);
+        }
+
+        public function loadString(text:String, mimeType:String = "text/html"):void {
+            this.loadStringInternal(text, mimeType);
+        }
+
+        public function historyBack():void {
+            this.navigate("back");
+        }
+
+        public function historyForward():void {
+            this.navigate("forward");
+        }
+
+        public function reload():void {
+            this.navigate("reload");
+        }
+
+        public function stop():void {
+            this.navigate("stop");
+        }
+
+        public function assignFocus(direction:String = "none"):void {
+            stub_method("flash.media.StageWebView", "assignFocus");
+        }
+
+        public function drawViewPortToBitmapData(bitmap:BitmapData):void {
+            // The page is rendered by the frontend, so its pixels aren't available to the movie.
+            stub_method("flash.media.StageWebView", "drawViewPortToBitmapData");
+        }
+
+        public function dispose():void {
+            if (this._disposed) {
+                return;
+            }
+            this._disposed = true;
+            this._stage = null;
+            this.disposeView();
+        }
+    }
+}
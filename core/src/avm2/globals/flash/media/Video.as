@@ -3,6 +3,7 @@ package flash.media
     import __ruffle__.stub_method;
 
     import flash.display.DisplayObject
+    import flash.events.Event
     import flash.net.NetStream
     
     [Ruffle(InstanceAllocator)]
@@ -13,6 +14,10 @@ package flash.media
         private var _videoWidth: int;
         private var _videoHeight: int;
 
+        // The attached camera, and whether this video is on the stage and keeping it capturing.
+        private var _camera: Camera;
+        private var _cameraAttached: Boolean;
+
         public function Video(width: int = 320, height: int = 240) {
             if (width < 0 || height < 0) {
                 throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
@@ -20,6 +25,8 @@ package flash.media
             this._videoWidth = width;
             this._videoHeight = height;
             this.init(width, height);
+            this.addEventListener(Event.ADDED_TO_STAGE, this.updateCameraAttachment);
+            this.addEventListener(Event.REMOVED_FROM_STAGE, this.updateCameraAttachment);
         }
 
         private native function init(width: int, height: int);
@@ -48,7 +55,49 @@ package flash.media
             return this._videoHeight;
         }
 
-        public native function attachNetStream(netStream: NetStream);
+        public function attachNetStream(netStream: NetStream):void {
+            if (this._camera != null) {
+                this.attachCamera(null);
+            }
+            attachNetStreamInternal(netStream);
+        }
+
+        private native function attachNetStreamInternal(netStream: NetStream);
+
+        public function attachCamera(camera: Camera):void {
+            if (this._cameraAttached) {
+                this._camera.detach();
+                this._cameraAttached = false;
+            }
+            this._camera = camera;
+            // Like Flash, attaching a camera starts it capturing, even before the video is
+            // added to the stage.
+            if (camera != null) {
+                camera.attach();
+                this._cameraAttached = true;
+            }
+            attachCameraIndex(camera != null ? camera.index : -1);
+        }
+
+        // Stops the camera while this video is off the stage, so that removed videos don't keep
+        // it capturing.
+        private function updateCameraAttachment(event: Event):void {
+            if (event.target != this || this._camera == null) {
+                return;
+            }
+            var attached:Boolean = event.type == Event.ADDED_TO_STAGE;
+            if (attached == this._cameraAttached) {
+                return;
+            }
+            if (attached) {
+                this._camera.attach();
+            } else {
+                this._camera.detach();
+            }
+            this._cameraAttached = attached;
+        }
+
+        private native function attachCameraIndex(index: int);
 
         public function clear():void {
             stub_method("flash.media.Video", "clear");
@@ -0,0 +1,134 @@
+//! `flash.media.Camera` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::backend::camera::CaptureStatus;
+use crate::bitmap::bitmap_data::Color;
+use crate::string::AvmString;
+use ruffle_render::bitmap::PixelRegion;
+
+/// Implements `Camera.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.camera.names();
+    let storage = names
+        .into_iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name))
+        .collect::<ArrayStorage<'gc>>();
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements the private `Camera.open`, which starts capturing in the given mode.
+pub fn open<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let width = args.get_i32(activation, 1)?.max(1) as u32;
+    let height = args.get_i32(activation, 2)?.max(1) as u32;
+    let fps = args.get_f64(activation, 3)?;
+
+    if let Ok(index) = usize::try_from(index) {
+        activation.context.camera.open(index, width, height, fps);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `Camera.close`, which stops capturing.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+
+    if let Ok(index) = usize::try_from(index) {
+        activation.context.camera.close(index);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `Camera.isMuted`
+pub fn is_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let status = usize::try_from(index)
+        .map(|index| activation.context.camera.status(index))
+        .unwrap_or(CaptureStatus::Closed);
+
+    Ok((status != CaptureStatus::Active).into())
+}
+
+/// Implements the private `Camera.captureStatus`, which is one of `"closed"`, `"pending"`,
+/// `"active"` and `"denied"`.
+pub fn capture_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let status = usize::try_from(index)
+        .map(|index| activation.context.camera.status(index))
+        .unwrap_or(CaptureStatus::Closed);
+
+    let status = match status {
+        CaptureStatus::Closed => "closed",
+        CaptureStatus::Pending => "pending",
+        CaptureStatus::Active => "active",
+        CaptureStatus::Denied => "denied",
+    };
+    Ok(AvmString::new_utf8(activation.context.gc_context, status).into())
+}
+
+/// Implements the private `Camera.drawFrame`, which backs `Camera.drawToBitmapData`.
+pub fn draw_frame<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let Some(target) = args
+        .get_object(activation, 1, "destination")?
+        .as_bitmap_data()
+    else {
+        return Ok(Value::Undefined);
+    };
+    target.check_valid(activation)?;
+
+    let Ok(index) = usize::try_from(index) else {
+        return Ok(Value::Undefined);
+    };
+    let Some((_, frame)) = activation.context.camera.latest_frame(index) else {
+        return Ok(Value::Undefined);
+    };
+
+    let width = frame.width.min(target.width());
+    let height = frame.height.min(target.height());
+    let target = target.sync(activation.context.renderer);
+    let mut write = target.write(activation.context.gc_context);
+    for y in 0..height {
+        for x in 0..width {
+            let i = ((y * frame.width + x) * 4) as usize;
+            let rgba = &frame.rgba[i..i + 4];
+            write.set_pixel32_raw(x, y, Color::argb(0xFF, rgba[0], rgba[1], rgba[2]));
+        }
+    }
+    write.set_cpu_dirty(
+        activation.context.gc_context,
+        PixelRegion::for_whole_size(width, height),
+    );
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,129 @@
+//! `flash.media.Microphone` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::type_error;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::backend::microphone::CaptureStatus;
+use crate::string::AvmString;
+
+/// Implements `Microphone.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.microphone.names();
+    let storage = names
+        .into_iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name))
+        .collect::<ArrayStorage<'gc>>();
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements the private `Microphone.open`, which starts capturing at the given sample rate.
+pub fn open<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let sample_rate = args.get_u32(activation, 1)?;
+
+    if let Ok(index) = usize::try_from(index) {
+        activation.context.microphone.open(index, sample_rate);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `Microphone.close`, which stops capturing.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+
+    if let Ok(index) = usize::try_from(index) {
+        activation.context.microphone.close(index);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `Microphone.isMuted`
+pub fn is_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let status = usize::try_from(index)
+        .map(|index| activation.context.microphone.status(index))
+        .unwrap_or(CaptureStatus::Closed);
+
+    Ok((status != CaptureStatus::Active).into())
+}
+
+/// Implements the private `Microphone.captureStatus`, which is one of `"closed"`, `"pending"`,
+/// `"active"` and `"denied"`.
+pub fn capture_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let status = usize::try_from(index)
+        .map(|index| activation.context.microphone.status(index))
+        .unwrap_or(CaptureStatus::Closed);
+
+    let status = match status {
+        CaptureStatus::Closed => "closed",
+        CaptureStatus::Pending => "pending",
+        CaptureStatus::Active => "active",
+        CaptureStatus::Denied => "denied",
+    };
+    Ok(AvmString::new_utf8(activation.context.gc_context, status).into())
+}
+
+/// Implements the private `Microphone.takeSamples`.
+///
+/// Appends the samples captured since the last call to the given ByteArray as floats, after
+/// applying the gain, and returns their activity level from 0 to 100.
+pub fn take_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let data = args.get_object(activation, 1, "data")?;
+    // Flash's gain goes from 0 to 100, with 50 leaving the signal unchanged.
+    let gain = (args.get_f64(activation, 2)? / 50.0) as f32;
+
+    let Ok(index) = usize::try_from(index) else {
+        return Ok(0.into());
+    };
+    let Some(mut data) = data.as_bytearray_mut() else {
+        return Err(Error::AvmError(type_error(
+            activation,
+            &format!(
+                "Error #1034: Type Coercion failed: cannot convert {}@00000000000 to flash.utils.ByteArray.",
+                data.instance_of_class_name(activation.context.gc_context),
+            ),
+            1034,
+        )?));
+    };
+    let samples = activation.context.microphone.take_samples(index);
+
+    let mut peak: f32 = 0.0;
+    for sample in samples {
+        let sample = (sample * gain).clamp(-1.0, 1.0);
+        peak = peak.max(sample.abs());
+        data.write_float(sample).map_err(|e| e.to_avm(activation))?;
+    }
+
+    Ok((f64::from(peak) * 100.0).round().into())
+}
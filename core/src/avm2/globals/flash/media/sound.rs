@@ -174,6 +174,30 @@ pub fn play<'gc>(
 
         let sound_channel = SoundChannelObject::empty(activation)?;
 
+        // A `Sound` without audio of its own plays whatever its `sampleData` listeners generate.
+        if sound_object.sound_handle().is_none()
+            && this
+                .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+                .coerce_to_boolean()
+        {
+            let Some(instance) = activation.context.start_dynamic_sound(sound_object) else {
+                return Ok(Value::Null);
+            };
+            if let Some(sound_transform) = sound_transform {
+                activation
+                    .context
+                    .set_local_sound_transform(instance, sound_transform);
+            }
+            sound_channel
+                .as_sound_channel()
+                .unwrap()
+                .set_sound_instance(activation, instance);
+            activation
+                .context
+                .attach_avm2_sound_channel(instance, sound_channel);
+            return Ok(sound_channel.into());
+        }
+
         let queued_play = QueuedPlay {
             position,
             sound_info,
@@ -194,23 +218,57 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
-
-    let bytearray = args.try_get_object(activation, 0);
+    let target = args.get_object(activation, 0, "target")?;
     let length = args.get_f64(activation, 1)?;
+    let start_position = args.get_f64(activation, 2)?;
+
+    let Some(sound) = this.as_sound_object() else {
+        return Ok(0.into());
+    };
+    let Some(sound_handle) = sound.sound_handle() else {
+        return Ok(0.into());
+    };
+
+    // A negative start position continues from where the last call left off.
+    let start_sample_frame = if start_position < 0.0 {
+        sound.extract_position()
+    } else {
+        start_position as u32
+    };
 
-    if let Some(bytearray) = bytearray {
-        if let Some(mut bytearray) = bytearray.as_bytearray_mut() {
+    // Don't allocate more than the whole sound, no matter how much was asked for.
+    let total_frames = activation
+        .context
+        .audio
+        .get_sound_duration(sound_handle)
+        .map_or(0.0, |duration| (duration * 44.1).ceil());
+    let mut frames = vec![[0.0; 2]; length.clamp(0.0, total_frames) as usize];
+
+    let num_frames = activation
+        .context
+        .audio
+        .extract_sound(sound_handle, start_sample_frame, &mut frames)
+        .unwrap_or_else(|e| {
+            tracing::error!("Sound.extract: Failed to decode sound: {e}");
+            0
+        });
+    sound.set_extract_position(start_sample_frame.saturating_add(num_frames as u32));
+
+    if let Some(mut bytearray) = target.as_bytearray_mut() {
+        for [left, right] in &frames[..num_frames] {
             bytearray
-                .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
+                .write_float(*left)
+                .map_err(|e| e.to_avm(activation))?;
+            bytearray
+                .write_float(*right)
                 .map_err(|e| e.to_avm(activation))?;
         }
     }
 
-    Ok(Value::Undefined)
+    Ok(num_frames.into())
 }
 
 /// `Sound.close`
@@ -0,0 +1,152 @@
+//! `flash.media.StageWebView` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::{WebViewNavigation, WebViewViewport};
+use crate::string::AvmString;
+use crate::web_view::WebViews;
+
+/// Implements `StageWebView.isSupported`.
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.supports_web_views().into())
+}
+
+/// Creates the overlay backing a `StageWebView`.
+pub fn create_view<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    WebViews::create(activation.context, this);
+
+    Ok(Value::Undefined)
+}
+
+/// Moves the overlay of a `StageWebView`, or hides it when it isn't on a stage.
+pub fn set_view_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let visible = args.get_bool(0);
+    let x = args.get_f64(activation, 1)?;
+    let y = args.get_f64(activation, 2)?;
+    let width = args.get_f64(activation, 3)?;
+    let height = args.get_f64(activation, 4)?;
+
+    let viewport = visible.then(|| WebViewViewport {
+        x: x as i32,
+        y: y as i32,
+        width: width.max(0.0) as u32,
+        height: height.max(0.0) as u32,
+    });
+    WebViews::set_viewport(activation.context, this, viewport);
+
+    Ok(Value::Undefined)
+}
+
+pub fn load_url_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    WebViews::load_url(activation.context, this, &url);
+
+    Ok(Value::Undefined)
+}
+
+pub fn load_string_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string(activation, 0)?.to_string();
+    let mime_type = args.get_string(activation, 1)?.to_string();
+
+    WebViews::load_string(activation.context, this, &text, &mime_type);
+
+    Ok(Value::Undefined)
+}
+
+pub fn navigate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let navigation = args.get_string(activation, 0)?;
+    let navigation = if &*navigation == b"back" {
+        WebViewNavigation::Back
+    } else if &*navigation == b"forward" {
+        WebViewNavigation::Forward
+    } else if &*navigation == b"reload" {
+        WebViewNavigation::Reload
+    } else {
+        WebViewNavigation::Stop
+    };
+
+    WebViews::navigate(activation.context, this, navigation);
+
+    Ok(Value::Undefined)
+}
+
+/// Destroys the overlay of a disposed `StageWebView`.
+pub fn dispose_view<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    WebViews::dispose(activation.context, this);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_location<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let location = activation
+        .context
+        .web_views
+        .location(this)
+        .map(str::to_string);
+    Ok(location.map_or(Value::Null, |location| {
+        AvmString::new_utf8(activation.gc(), location).into()
+    }))
+}
+
+pub fn get_title<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let title = activation.context.web_views.title(this).map(str::to_string);
+    Ok(title.map_or(Value::Null, |title| {
+        AvmString::new_utf8(activation.gc(), title).into()
+    }))
+}
+
+pub fn get_is_history_back_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.web_views.can_go_back(this).into())
+}
+
+pub fn get_is_history_forward_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.web_views.can_go_forward(this).into())
+}
@@ -54,7 +54,7 @@ pub fn init<'gc>(
     Ok(Value::Undefined)
 }
 
-pub fn attach_net_stream<'gc>(
+pub fn attach_net_stream_internal<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
@@ -75,3 +75,17 @@ pub fn attach_net_stream<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn attach_camera_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        let index = usize::try_from(args.get_i32(activation, 0)?).ok();
+
+        video.attach_camera(activation.context, index);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -13,6 +13,7 @@ pub mod net_connection;
 pub mod net_stream;
 pub mod object_encoding;
 pub mod responder;
+pub mod secure_socket;
 pub mod shared_object;
 pub mod socket;
 pub mod url_loader;
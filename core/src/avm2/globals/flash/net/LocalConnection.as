@@ -27,12 +27,8 @@ package flash.net {
         public native function get client():Object;
         public native function set client(client:Object):void;
 
-        public function allowDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowDomain");
-        }
+        public native function allowDomain(... domains): void;
 
-        public function allowInsecureDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowInsecureDomain");
-        }
+        public native function allowInsecureDomain(... domains): void;
     }
 }
@@ -8,11 +8,13 @@ package flash.net {
 
         public static var defaultObjectEncoding:uint = 3;
 
-        public var objectEncoding:uint = NetConnection.defaultObjectEncoding;
         public var client:Object = this;
         public var maxPeerConnections:uint = 8;
         public var proxyType:String = "none";
 
+        public function NetConnection() {
+            this.objectEncoding = NetConnection.defaultObjectEncoding;
+        }
 
         public native function connect(command:String, ... arguments):void;
 
@@ -32,6 +34,9 @@ package flash.net {
         public native function get uri():String;
         public native function get usingTLS():Boolean;
 
+        public native function get objectEncoding():uint;
+        public native function set objectEncoding(value:uint):void;
+
         public function get unconnectedPeerStreams():Array {
             if (this.connected) {
                 // [NA] Arguably this isn't a stub as it can't ever be anything else in our current implementation...
@@ -12,29 +12,42 @@ package flash.net {
     import __ruffle__.stub_getter;
     import __ruffle__.stub_setter;
 
+    namespace ruffle = "__ruffle__";
+
     [Ruffle(InstanceAllocator)]
     public class NetStream extends EventDispatcher {
         public static const CONNECT_TO_FMS: String = "connectToFMS";
         public static const DIRECT_CONNECTIONS: String = "directConnections";
 
-        public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
+        // The microphone attached with `attachAudio`, which captures while it's attached.
+        private var _microphone:Microphone;
 
+        public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
+            this.init(connection);
         }
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        private native function init(connection:NetConnection):void;
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytes(bytes:ByteArray):void;
+
+        public native function appendBytesAction(action:String):void;
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
         }
 
         public function attachAudio(mic:Microphone) {
-            stub_method("flash.net.NetStream", "attachAudio");
+            if (mic == this._microphone) {
+                return;
+            }
+            if (this._microphone != null) {
+                this._microphone.ruffle::detach();
+            }
+            this._microphone = mic;
+            if (mic != null) {
+                mic.ruffle::attach();
+                stub_method("flash.net.NetStream", "attachAudio", "with sending audio to the server");
+            }
         }
 
         public function attachCamera(cam:Camera, ms:int = -1) {
@@ -42,6 +55,7 @@ package flash.net {
         }
 
         public function close() {
+            this.attachAudio(null);
             stub_method("flash.net.NetStream", "close");
         }
 
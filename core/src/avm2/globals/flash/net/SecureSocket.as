@@ -0,0 +1,50 @@
+package flash.net {
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.security.CertificateStatus;
+    import flash.security.X509Certificate;
+    import flash.utils.ByteArray;
+
+    import __ruffle__.stub_getter;
+    import __ruffle__.stub_method;
+
+    [API("668")] // AIR 2.0
+    public class SecureSocket extends Socket {
+        private var _serverCertificateStatus:String = CertificateStatus.UNKNOWN;
+
+        public function SecureSocket() {
+            super();
+
+            // The backend refuses certificates that aren't valid for the host,
+            // so any connection that was made was made with a trusted one.
+            this.addEventListener(Event.CONNECT, this.onConnect, false, int.MAX_VALUE);
+            this.addEventListener(IOErrorEvent.IO_ERROR, this.onIoError, false, int.MAX_VALUE);
+        }
+
+        public static native function get isSupported():Boolean;
+
+        override public native function connect(host:String, port:int):void;
+
+        private function onConnect(event:Event):void {
+            this._serverCertificateStatus = CertificateStatus.TRUSTED;
+        }
+
+        private function onIoError(event:IOErrorEvent):void {
+            this._serverCertificateStatus = CertificateStatus.INVALID;
+        }
+
+        public function get serverCertificateStatus():String {
+            return this._serverCertificateStatus;
+        }
+
+        [API("674")]
+        public function get serverCertificate():X509Certificate {
+            stub_getter("flash.net.SecureSocket", "serverCertificate");
+            return null;
+        }
+
+        public function addBinaryChainBuildingCertificate(certificate:ByteArray, trusted:Boolean):void {
+            stub_method("flash.net.SecureSocket", "addBinaryChainBuildingCertificate");
+        }
+    }
+}
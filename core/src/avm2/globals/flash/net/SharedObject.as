@@ -10,10 +10,7 @@ package flash.net {
            // Unreachable; the allocator always throws
         }
 
-        // NOTE: We currently always use AMF3 serialization.
-        // If you implement the `defaultObjectEncoding` or `objectEncoding`,
-        // you will need to adjust the serialization and deserialization code
-        // to work with AMF0.
+        public static var defaultObjectEncoding:uint = 3;
 
         public static native function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
 
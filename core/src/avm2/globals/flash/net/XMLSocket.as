@@ -45,25 +45,38 @@ package flash.net
 
         private function socketDataListener(evt:ProgressEvent):void
         {
-            // FIXME: There is probably a better way to do this.
-            for (var i:uint = 0; i < evt.bytesLoaded; i++)
-            {
-                var byte:int = this.socket.readByte();
+            // Messages end with a null byte, and may be split across packets,
+            // so anything after the last one is kept for the next packet.
+            var searchFrom:uint = this.tempBuf.length;
+            this.socket.readBytes(this.tempBuf, this.tempBuf.length);
 
-                if (byte == 0)
+            var start:uint = 0;
+            for (var i:uint = searchFrom; i < this.tempBuf.length; i++)
+            {
+                if (this.tempBuf[i] == 0)
                 {
-                    var length:uint = this.tempBuf.position;
-                    this.tempBuf.position = 0;
-
-                    var data:String = this.tempBuf.readUTFBytes(length);
-                    this.tempBuf.clear();
+                    this.tempBuf.position = start;
+                    var data:String = this.tempBuf.readUTFBytes(i - start);
+                    start = i + 1;
 
                     this.dispatchEvent(new DataEvent(DataEvent.DATA, false, false, data));
+
+                    // The listener may have closed the socket.
+                    if (!this.socket.connected)
+                    {
+                        return;
+                    }
                 }
-                else
+            }
+
+            if (start > 0)
+            {
+                var rest:ByteArray = new ByteArray();
+                if (start < this.tempBuf.length)
                 {
-                    this.tempBuf.writeByte(byte);
+                    rest.writeBytes(this.tempBuf, start);
                 }
+                this.tempBuf = rest;
             }
         }
 
@@ -87,7 +100,7 @@ package flash.net
             return this.socket.timeout;
         }
 
-        public function set timeout(value:int)
+        public function set timeout(value:int):void
         {
             this.socket.timeout = value;
         }
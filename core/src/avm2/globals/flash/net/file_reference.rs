@@ -2,6 +2,7 @@ use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::{argument_error, error, make_error_2037, make_error_2097};
 pub use crate::avm2::object::file_reference_allocator;
 use crate::avm2::object::{ByteArrayObject, DateObject, FileReference};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Avm2, Error, EventObject, Object, TObject, Value};
 use crate::backend::ui::FileFilter;
 use crate::string::AvmString;
@@ -278,3 +279,38 @@ pub fn save<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_file_reference().unwrap();
+    let request = args.get_object(activation, 0, "request")?;
+    let field_name = args.get_string(activation, 1)?;
+
+    let (data, file_name) = match *this.file_reference() {
+        FileReference::None => return Err(make_error_2037(activation)),
+        FileReference::FileDialogResult(ref dialog_result) => (
+            dialog_result.contents().to_vec(),
+            dialog_result.file_name().unwrap_or_default(),
+        ),
+    };
+
+    // FIXME - The variables in `URLRequest.data` should be sent along as extra form fields.
+    let url = request
+        .get_public_property("url", activation)?
+        .coerce_to_string(activation)?;
+
+    let future = activation.context.load_manager.upload_file_avm2(
+        activation.context.player.clone(),
+        this,
+        url.to_string(),
+        data,
+        file_name,
+        field_name.to_string(),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
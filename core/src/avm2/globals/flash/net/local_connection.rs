@@ -15,7 +15,7 @@ pub fn get_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let movie = &activation.context.swf;
+    let movie = activation.caller_movie_or_root();
     let domain = LocalConnections::get_domain(movie.url());
 
     Ok(Value::String(AvmString::new_utf8(
@@ -58,8 +58,9 @@ pub fn send<'gc>(
     }
 
     if let Some(local_connection) = this.as_local_connection_object() {
+        let movie = activation.caller_movie_or_root();
         activation.context.local_connections.send(
-            &LocalConnections::get_domain(activation.context.swf.url()),
+            &LocalConnections::get_domain(movie.url()),
             (activation.domain(), local_connection),
             connection_name,
             method_name,
@@ -149,3 +150,29 @@ pub fn set_client<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implements `LocalConnection.allowDomain`
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(local_connection) = this.as_local_connection_object() {
+        for domain in args {
+            let domain = domain.coerce_to_string(activation)?;
+            local_connection.allow_domain(domain.to_string());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.allowInsecureDomain`
+pub fn allow_insecure_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // We don't distinguish between HTTP and HTTPS senders.
+    allow_domain(activation, this, args)
+}
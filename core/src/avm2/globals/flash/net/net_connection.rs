@@ -1,5 +1,6 @@
 use crate::avm2::amf::serialize_value;
-use crate::avm2::error::make_error_2126;
+use crate::avm2::bytearray::ObjectEncoding;
+use crate::avm2::error::{make_error_2008, make_error_2126};
 pub use crate::avm2::object::net_connection_allocator;
 use crate::avm2::object::TObject;
 use crate::avm2::parameters::ParametersExt;
@@ -32,17 +33,44 @@ pub fn connect<'gc>(
     }
 
     let url = args.get_string(activation, 0)?;
+    let version = match connection.object_encoding() {
+        ObjectEncoding::Amf0 => AMFVersion::AMF0,
+        ObjectEncoding::Amf3 => AMFVersion::AMF3,
+    };
     if url.starts_with(WStr::from_units(b"http://"))
         || url.starts_with(WStr::from_units(b"https://"))
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
-        NetConnections::connect_to_flash_remoting(activation.context, connection, url.to_string());
+        NetConnections::connect_to_flash_remoting(
+            activation.context,
+            connection,
+            url.to_string(),
+            version,
+        );
+    } else if url.starts_with(WStr::from_units(b"rtmp")) {
+        // This includes the `rtmps`, `rtmpt` and `rtmpe` variants, which fail to connect.
+        let mut arguments = Vec::new();
+        let mut object_table = FnvHashMap::default();
+        for arg in &args[1..] {
+            if let Some(value) =
+                serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
+            {
+                arguments.push(Rc::new(value));
+            }
+        }
+        NetConnections::connect_to_rtmp(
+            activation.context,
+            connection,
+            url.to_string(),
+            version,
+            arguments,
+        );
     } else {
         avm2_stub_method!(
             activation,
             "flash.net.NetConnection",
             "connect",
-            "with non-null, non-http command"
+            "with non-null, non-http, non-rtmp command"
         );
     }
 
@@ -238,6 +266,37 @@ pub fn get_using_tls<'gc>(
     Err(make_error_2126(activation))
 }
 
+pub fn get_object_encoding<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = this
+        .as_net_connection()
+        .expect("Must be NetConnection object");
+
+    Ok((connection.object_encoding() as u8).into())
+}
+
+pub fn set_object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = this
+        .as_net_connection()
+        .expect("Must be NetConnection object");
+
+    let new_encoding = args.get_u32(activation, 0)?;
+    match new_encoding {
+        0 => connection.set_object_encoding(ObjectEncoding::Amf0),
+        3 => connection.set_object_encoding(ObjectEncoding::Amf3),
+        _ => return Err(make_error_2008(activation, "objectEncoding")),
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn call<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -255,9 +314,22 @@ pub fn call<'gc>(
 
     let mut object_table = FnvHashMap::default();
     for arg in &args[2..] {
-        if let Some(value) = serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
-        {
-            arguments.push(Rc::new(value));
+        match connection.object_encoding() {
+            ObjectEncoding::Amf0 => {
+                if let Some(value) =
+                    serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
+                {
+                    arguments.push(Rc::new(value));
+                }
+            }
+            ObjectEncoding::Amf3 => {
+                // Each argument is switched to AMF3 separately, with its own reference tables.
+                if let Some(value) =
+                    serialize_value(activation, *arg, AMFVersion::AMF3, &mut Default::default())
+                {
+                    arguments.push(Rc::new(AMFValue::AMF3(Rc::new(value))));
+                }
+            }
         }
     }
 
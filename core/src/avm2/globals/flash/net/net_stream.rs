@@ -1,9 +1,26 @@
-use crate::avm2::error::{make_error_2004, Error2004Type};
+use crate::avm2::error::{make_error_2004, make_error_2008, Error2004Type};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let connection = args
+            .try_get_object(activation, 0)
+            .and_then(|connection| connection.as_net_connection())
+            .and_then(|connection| connection.handle());
+        ns.set_connection(activation.context.gc_context, connection);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -34,10 +51,15 @@ pub fn play<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(ns) = this.as_netstream() {
+        // `play(null)` enables Data Generation Mode, where data comes from `appendBytes`.
+        if matches!(args.get(0), Some(Value::Null)) {
+            ns.play_appended_bytes(activation.context);
+            return Ok(Value::Undefined);
+        }
+
         let name = args
             .get(0)
             .cloned()
-            .filter(|v| !matches!(v, Value::Null))
             .map(|v| v.coerce_to_string(activation))
             .transpose()?;
 
@@ -47,6 +69,48 @@ pub fn play<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let bytes = args.get_object(activation, 0, "bytes")?;
+        let data = bytes
+            .as_bytearray()
+            .expect("Parameter must be a ByteArray")
+            .bytes()
+            .to_vec();
+
+        ns.append_bytes(activation.context, data);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let action = args.get_string(activation, 0)?;
+        let action = if &action == b"resetBegin" {
+            AppendBytesAction::ResetBegin
+        } else if &action == b"resetSeek" {
+            AppendBytesAction::ResetSeek
+        } else if &action == b"endSequence" {
+            AppendBytesAction::EndSequence
+        } else {
+            return Err(make_error_2008(activation, "action"));
+        };
+
+        ns.append_bytes_action(activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn pause<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -0,0 +1,24 @@
+//! `flash.net.SecureSocket` native methods
+
+use crate::avm2::globals::flash::net::socket::connect_socket;
+use crate::avm2::{Activation, Error, Object, Value};
+
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .navigator
+        .supports_secure_sockets()
+        .into())
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    connect_socket(activation, this, args, true)
+}
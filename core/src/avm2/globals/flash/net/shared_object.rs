@@ -1,10 +1,12 @@
 //! `flash.net.SharedObject` builtin/prototype
 
-use crate::avm2::error::error;
+use crate::avm2::bytearray::ObjectEncoding;
+use crate::avm2::error::{error, make_error_2008};
 use crate::avm2::object::TObject;
 pub use crate::avm2::object::{shared_object_allocator, SharedObjectObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, Value};
-use crate::{avm2_stub_getter, avm2_stub_method, avm2_stub_setter};
+use crate::avm2_stub_method;
 use flash_lso::types::{AMFVersion, Lso};
 use std::borrow::Cow;
 
@@ -12,14 +14,20 @@ fn new_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
     name: &str,
     data: Object<'gc>,
+    object_encoding: ObjectEncoding,
 ) -> Result<Lso, Error<'gc>> {
+    let amf_version = match object_encoding {
+        ObjectEncoding::Amf0 => AMFVersion::AMF0,
+        ObjectEncoding::Amf3 => AMFVersion::AMF3,
+    };
+
     let mut elements = Vec::new();
     crate::avm2::amf::recursive_serialize(
         activation,
         data,
         &mut elements,
         None,
-        AMFVersion::AMF3,
+        amf_version,
         &mut Default::default(),
     )?;
     Ok(Lso::new(
@@ -28,7 +36,7 @@ fn new_lso<'gc>(
             .last()
             .map(|e| e.to_string())
             .unwrap_or_else(|| "<unknown>".to_string()),
-        AMFVersion::AMF3,
+        amf_version,
     ))
 }
 
@@ -150,8 +158,10 @@ pub fn get_local<'gc>(
 
     // Load the data object from storage if it existed prior
     if let Some(saved) = activation.context.storage.get(&full_name) {
-        if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
-            data = crate::avm2::amf::deserialize_lso(activation, &lso)?.into();
+        let mut reader = flash_lso::read::Reader::default();
+        if let Ok(lso) = reader.parse(&saved) {
+            data =
+                crate::avm2::amf::deserialize_lso(activation, &lso, &reader.amf0_decoder)?.into();
         }
     }
 
@@ -168,6 +178,15 @@ pub fn get_local<'gc>(
 
     let created_shared_object =
         SharedObjectObject::from_data_and_name(activation, data, full_name.clone());
+    let default_encoding = activation
+        .avm2()
+        .classes()
+        .sharedobject
+        .get_public_property("defaultObjectEncoding", activation)?
+        .coerce_to_u32(activation)?;
+    if default_encoding == 0 {
+        created_shared_object.set_object_encoding(ObjectEncoding::Amf0);
+    }
 
     activation
         .context
@@ -197,7 +216,7 @@ pub fn flush<'gc>(
     let data = shared_object.data();
     let name = shared_object.name();
 
-    let mut lso = new_lso(activation, name, data)?;
+    let mut lso = new_lso(activation, name, data, shared_object.object_encoding())?;
     // Flash does not write empty LSOs to disk
     if lso.body.is_empty() {
         Ok("flushed".into())
@@ -226,7 +245,7 @@ pub fn get_size<'gc>(
     let data = shared_object.data();
     let name = shared_object.name();
 
-    let mut lso = new_lso(activation, name, data)?;
+    let mut lso = new_lso(activation, name, data, shared_object.object_encoding())?;
     // Flash returns 0 for empty LSOs, but the actual number of bytes (including the header) otherwise
     if lso.body.is_empty() {
         Ok(0.into())
@@ -263,19 +282,27 @@ pub fn clear<'gc>(
 }
 
 pub fn get_object_encoding<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.net.SharedObject", "objectEncoding");
-    Ok(0.into())
+    let shared_object = this.as_shared_object().unwrap();
+
+    Ok((shared_object.object_encoding() as u8).into())
 }
 
 pub fn set_object_encoding<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.net.SharedObject", "objectEncoding");
+    let shared_object = this.as_shared_object().unwrap();
+
+    match args.get_u32(activation, 0)? {
+        0 => shared_object.set_object_encoding(ObjectEncoding::Amf0),
+        3 => shared_object.set_object_encoding(ObjectEncoding::Amf3),
+        _ => return Err(make_error_2008(activation, "objectEncoding")),
+    }
+
     Ok(Value::Undefined)
 }
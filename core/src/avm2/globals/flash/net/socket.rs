@@ -29,6 +29,16 @@ pub fn connect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    connect_socket(activation, this, args, false)
+}
+
+/// Connects a `Socket`, or a `SecureSocket` if `secure` is set.
+pub fn connect_socket<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    secure: bool,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let socket = match this.as_socket() {
         Some(socket) => socket,
@@ -42,10 +52,22 @@ pub fn connect<'gc>(
         .map_err(|_| invalid_port_number(activation))?;
 
     let UpdateContext {
-        sockets, navigator, ..
+        sockets,
+        navigator,
+        policy_files,
+        swf,
+        ..
     } = activation.context;
 
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    sockets.connect_avm2(
+        *navigator,
+        policy_files,
+        swf,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+        secure,
+    );
 
     Ok(Value::Undefined)
 }
@@ -372,7 +394,7 @@ pub fn read_object<'gc>(
                     .map_err(|_| "Error: Invalid object")?;
                 (
                     extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
+                    crate::avm2::amf::deserialize_amf0_value(activation, &amf, &decoder)?,
                 )
             }
             ObjectEncoding::Amf3 => {
@@ -1,6 +1,88 @@
 package flash.printing {
+    import flash.display.Sprite;
+    import flash.errors.IllegalOperationError;
     import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
 
     public class PrintJob extends EventDispatcher {
+        // The size of the paper and its printable area, in points.
+        // These are only known once the job was started.
+        private var _paperWidth:int = 0;
+        private var _paperHeight:int = 0;
+        private var _pageWidth:int = 0;
+        private var _pageHeight:int = 0;
+
+        private var _started:Boolean = false;
+
+        public function PrintJob() {
+        }
+
+        public static native function get isSupported():Boolean;
+
+        public function get paperWidth():int {
+            return this._paperWidth;
+        }
+
+        public function get paperHeight():int {
+            return this._paperHeight;
+        }
+
+        public function get pageWidth():int {
+            return this._pageWidth;
+        }
+
+        public function get pageHeight():int {
+            return this._pageHeight;
+        }
+
+        // Ruffle always prints on portrait paper.
+        public function get orientation():String {
+            return PrintJobOrientation.PORTRAIT;
+        }
+
+        public function start():Boolean {
+            if (this._started) {
+                throw new IllegalOperationError("Error #2041: Only one PrintJob may be in use at a time.", 2041);
+            }
+            this._started = this._start();
+            if (this._started) {
+                this._paperWidth = this._getPaperWidth();
+                this._paperHeight = this._getPaperHeight();
+                this._pageWidth = this._getPageWidth();
+                this._pageHeight = this._getPageHeight();
+            }
+            return this._started;
+        }
+
+        // `frameNum` is ignored, and the current frame of the sprite is printed.
+        public function addPage(sprite:Sprite, printArea:Rectangle = null, options:PrintJobOptions = null, frameNum:int = 0):void {
+            var printAsBitmap:Boolean = options != null && options.printAsBitmap;
+            if (!this._started || !this._addPage(sprite, printArea, printAsBitmap)) {
+                throw new Error("Error #2057: The page could not be added to the print job.", 2057);
+            }
+        }
+
+        public function send():void {
+            if (this._started) {
+                this._started = false;
+                this._send();
+            }
+        }
+
+        public function terminate():void {
+            if (this._started) {
+                this._started = false;
+                this._terminate();
+            }
+        }
+
+        private native function _start():Boolean;
+        private native function _getPaperWidth():int;
+        private native function _getPaperHeight():int;
+        private native function _getPageWidth():int;
+        private native function _getPageHeight():int;
+        private native function _addPage(sprite:Sprite, printArea:Rectangle, printAsBitmap:Boolean):Boolean;
+        private native function _send():void;
+        private native function _terminate():void;
     }
-}
\ No newline at end of file
+}
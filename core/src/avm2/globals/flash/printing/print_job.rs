@@ -0,0 +1,104 @@
+//! `flash.printing.PrintJob` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::printing;
+
+/// Implements `PrintJob.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.can_print().into())
+}
+
+/// Implements `PrintJob.start`
+pub fn _start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(printing::start(activation.context).into())
+}
+
+/// Implements `PrintJob.paperWidth`
+pub fn _get_paper_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(printing::PAPER_WIDTH.into())
+}
+
+/// Implements `PrintJob.paperHeight`
+pub fn _get_paper_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(printing::PAPER_HEIGHT.into())
+}
+
+/// Implements `PrintJob.pageWidth`
+pub fn _get_page_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(printing::PAGE_WIDTH.into())
+}
+
+/// Implements `PrintJob.pageHeight`
+pub fn _get_page_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(printing::PAGE_HEIGHT.into())
+}
+
+/// Implements `PrintJob.addPage`
+pub fn _add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(sprite) = args
+        .try_get_object(activation, 0)
+        .and_then(|sprite| sprite.as_display_object())
+    else {
+        return Ok(false.into());
+    };
+    let area = match args.try_get_object(activation, 1) {
+        Some(area) => Some(object_to_rectangle(activation, area)?),
+        None => None,
+    };
+    let print_as_bitmap = args.get_bool(2);
+
+    Ok(printing::add_page(activation.context, sprite, area, print_as_bitmap).into())
+}
+
+/// Implements `PrintJob.send`
+pub fn _send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    printing::send(activation.context);
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.terminate`
+pub fn _terminate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    printing::abort(activation.context);
+    Ok(Value::Undefined)
+}
@@ -3,6 +3,7 @@
 
 pub mod application_domain;
 pub mod capabilities;
+pub mod ime;
 pub mod security;
 pub mod system;
 
@@ -4,6 +4,8 @@ package flash.system {
         public native static function get os(): String;
         public native static function get playerType(): String;
         public native static function get version(): String;
+        public native static function get language(): String;
+        public native static function get serverString(): String;
         public native static function get screenResolutionX():Number;
         public native static function get screenResolutionY():Number;
         public native static function get pixelAspectRatio():Number;
@@ -12,10 +14,6 @@ package flash.system {
             stub_getter("flash.system.Capabilities", "manufacturer");
             return "Adobe Windows"
         }
-        public static function get language(): String {
-            stub_getter("flash.system.Capabilities", "language");
-            return "en"
-        }
         public static function get isDebugger(): Boolean {
             return false
         }
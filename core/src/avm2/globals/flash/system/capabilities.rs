@@ -9,6 +9,9 @@ pub fn get_os<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(os) = &activation.context.system.overrides.os {
+        return Ok(AvmString::new_utf8(activation.gc(), os).into());
+    }
     let os = match activation.avm2().player_runtime {
         // For most normal Flash Player usage, the OS should not matter,
         // so let's pretend it's Windows for the broadest possible compatibility.
@@ -32,6 +35,9 @@ pub fn get_version<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(version) = &activation.context.system.overrides.version {
+        return Ok(AvmString::new_utf8(activation.gc(), version).into());
+    }
     let os = match activation.avm2().player_runtime {
         PlayerRuntime::FlashPlayer => "WIN",
         PlayerRuntime::AIR => {
@@ -57,6 +63,9 @@ pub fn get_player_type<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(player_type) = &activation.context.system.overrides.player_type {
+        return Ok(AvmString::new_utf8(activation.gc(), player_type).into());
+    }
     // TODO: When should "External" be returned?
     let player_type = if cfg!(target_family = "wasm") {
         "PlugIn"
@@ -70,6 +79,33 @@ pub fn get_player_type<'gc>(
     Ok(AvmString::new_utf8(activation.context.gc_context, player_type).into())
 }
 
+/// Implements `flash.system.Capabilities.language`
+pub fn get_language<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let player_version = activation.avm2().player_version;
+    let language = activation
+        .context
+        .system
+        .get_language_string(player_version);
+    Ok(AvmString::new_utf8(activation.context.gc_context, language).into())
+}
+
+/// Implements `flash.system.Capabilities.serverString`
+pub fn get_server_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let server_string = activation
+        .context
+        .system
+        .get_server_string(activation.context);
+    Ok(AvmString::new_utf8(activation.context.gc_context, server_string).into())
+}
+
 /// Implements `flash.system.Capabilities.screenResolutionX`
 pub fn get_screen_resolution_x<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -0,0 +1,53 @@
+//! `flash.system.IME` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `flash.system.IME.enabled`'s getter
+pub fn get_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.focus_tracker.ime_enabled().into())
+}
+
+/// Implements `flash.system.IME.enabled`'s setter
+pub fn set_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let enabled = args.get_bool(0);
+    let tracker = activation.context.focus_tracker;
+    tracker.set_ime_enabled(enabled, activation.context);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.IME.compositionAbandoned`
+pub fn composition_abandoned<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text) = activation.context.focus_tracker.get_as_edit_text() {
+        text.abandon_ime_composition(activation.context);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.IME.setCompositionString`
+pub fn set_composition_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let composition = args.get_string(activation, 0)?;
+    if let Some(text) = activation.context.focus_tracker.get_as_edit_text() {
+        text.set_ime_preedit(&composition.to_utf8_lossy(), None, activation.context);
+    }
+    Ok(Value::Undefined)
+}
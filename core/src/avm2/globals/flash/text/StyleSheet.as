@@ -1,8 +1,10 @@
 package flash.text {
     import flash.events.EventDispatcher;
 
+    [Ruffle(InstanceAllocator)]
     public dynamic class StyleSheet extends EventDispatcher {
-        // Shallow copies of the original style objects. Not used by Ruffle itself, just for getStyle()
+        // Shallow copies of the original style objects, for getStyle().
+        // Text fields use the transformed styles, which are kept on the Rust side.
         private var _styles: Object = {};
 
         public function StyleSheet() {}
@@ -17,6 +19,7 @@ package flash.text {
         
         public function clear():void {
             _styles = {};
+            innerClear();
         }
         
         public function getStyle(styleName:String):Object {
@@ -37,7 +40,13 @@ package flash.text {
         
         public function setStyle(styleName:String, styleObject:Object):void {
             _styles[styleName.toLowerCase()] = _createShallowCopy(styleObject);
-            transform(_createShallowCopy(styleObject)); // TODO: Store this in a way that Rust can access it, when we implement `TextField.stylesheet`
+            var copy:Object = _createShallowCopy(styleObject);
+            var format:TextFormat = transform(copy);
+            if (format && !copy.display) {
+                // A new TextFormat is a block by default, but styles are only blocks if they say so.
+                format.display = null;
+            }
+            innerSetStyle(styleName, format);
         }
         
         public function transform(formatObject:Object):TextFormat {
@@ -131,5 +140,7 @@ package flash.text {
         private native function innerParseCss(css: String): Object;
         private native function innerParseColor(color: String): Number;
         private native function innerParseFontFamily(fontFamily: String): String;
+        private native function innerSetStyle(styleName: String, format: TextFormat): void;
+        private native function innerClear(): void;
     }
 }
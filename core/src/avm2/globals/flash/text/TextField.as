@@ -8,7 +8,6 @@ package flash.text {
 
     [Ruffle(InstanceAllocator)]
     public class TextField extends InteractiveObject {
-        internal var _styleSheet:StyleSheet;
         internal var _useRichTextClipboard:Boolean;
 
         public native function get alwaysShowSelection():Boolean;
@@ -73,13 +72,8 @@ package flash.text {
         public native function get selectable():Boolean;
         public native function set selectable(value:Boolean):void;
 
-        public function get styleSheet():StyleSheet {
-            return this._styleSheet;
-        }
-        public function set styleSheet(value:StyleSheet):void {
-            this._styleSheet = value;
-            stub_setter("flash.text.TextField", "styleSheet");
-        }
+        public native function get styleSheet():StyleSheet;
+        public native function set styleSheet(value:StyleSheet):void;
 
         public native function get text():String;
         public native function set text(value:String):void;
@@ -152,10 +146,7 @@ package flash.text {
 
         public native function getFirstCharInParagraph(charIndex:int):int;
 
-        public function getImageReference(id:String):DisplayObject {
-            stub_method("flash.text.TextField", "getImageReference");
-            return null;
-        }
+        public native function getImageReference(id:String):DisplayObject;
 
         public native function getLineIndexAtPoint(x:Number, y:Number):int;
 
@@ -28,5 +28,14 @@ package flash.text.engine {
         public function set elementFormat(value:ElementFormat):void {
             this._elementFormat = value;
         }
+
+        // Adds the text of this element to `texts`, and the format it's drawn with to `formats`.
+        internal function collectRuns(texts:Array, formats:Array, parentFormat:ElementFormat):void {
+            var text:String = this.text;
+            if (text) {
+                texts.push(text);
+                formats.push(this._elementFormat ? this._elementFormat : parentFormat);
+            }
+        }
     }
 }
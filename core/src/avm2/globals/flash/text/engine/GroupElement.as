@@ -70,6 +70,13 @@ package flash.text.engine {
             return newTextElement;
         }
 
+        override internal function collectRuns(texts:Array, formats:Array, parentFormat:ElementFormat):void {
+            var format:ElementFormat = this.elementFormat ? this.elementFormat : parentFormat;
+            for (var i = 0; i < this._elements.length; i ++) {
+                this._elements[i].collectRuns(texts, formats, format);
+            }
+        }
+
         // FIXME: This is wrong, FP doesn't do an override of `get text` in GroupElement
         override public function get text():String {
             var resultingText:String = "";
@@ -16,6 +16,7 @@ package flash.text.engine {
 
         internal var _textLineCreationResult:String = null;
         internal var _firstLine:TextLine = null;
+        internal var _lastLine:TextLine = null;
 
 
         public function TextBlock(content:ContentElement = null,
@@ -132,7 +133,47 @@ package flash.text.engine {
             this._content = value;
         }
 
-        public native function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine;
+        public function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine {
+            if (previousLine && previousLine._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (this._content == null || this._content.text == null) {
+                // FP returns a null TextLine when there's no text to lay out
+                return null;
+            }
+
+            var beginIndex:int = 0;
+            if (previousLine) {
+                beginIndex = previousLine._textBlockBeginIndex + previousLine._rawTextLength;
+            }
+
+            var texts:Array = [];
+            var formats:Array = [];
+            this._content.collectRuns(texts, formats, null);
+
+            var line:TextLine = this.innerCreateTextLine(texts, formats, beginIndex, width);
+            if (line == null) {
+                // Some SWFs rely on eventually getting `null` from createTextLine.
+                this._textLineCreationResult = TextLineCreationResult.COMPLETE;
+                return null;
+            }
+
+            line._textBlock = this;
+            line._specifiedWidth = width;
+            line._textBlockBeginIndex = beginIndex;
+            line._previousLine = previousLine;
+            if (previousLine) {
+                previousLine._nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            this._lastLine = line;
+
+            this._textLineCreationResult = TextLineCreationResult.SUCCESS;
+            return line;
+        }
+
+        private native function innerCreateTextLine(texts:Array, formats:Array, beginIndex:int, width:Number):TextLine;
 
         public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine {
             if (textLine == null) {
@@ -160,16 +201,40 @@ package flash.text.engine {
         }
 
         public function get lastLine():TextLine {
-            return this._firstLine;
+            return this._lastLine;
         }
-        
+
         public function releaseLines(start:TextLine, end:TextLine):void {
-            if (start != end || end != this._firstLine) {
-                stub_method("flash.text.engine.TextBlock", "releaseLines", "with start != end or multiple lines");
-                return;
+            if (start == null || end == null || start._textBlock != this || end._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before:TextLine = start._previousLine;
+            var after:TextLine = end._nextLine;
+
+            var line:TextLine = start;
+            while (line != null) {
+                var next:TextLine = line._nextLine;
+                line._validity = TextLineValidity.INVALID;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                if (line == end) {
+                    break;
+                }
+                line = next;
+            }
+
+            if (before) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
             }
-            this._firstLine._validity = "invalid";
-            this._firstLine._textBlock = null;
         }
     }
 }
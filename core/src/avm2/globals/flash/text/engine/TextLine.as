@@ -1,6 +1,5 @@
 package flash.text.engine {
     import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
     import __ruffle__.stub_method;
 
     import flash.display.DisplayObjectContainer;
@@ -18,6 +17,9 @@ package flash.text.engine {
         internal var _textBlock:TextBlock = null;
         internal var _rawTextLength:int = 0;
         internal var _validity:String = "valid";
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _previousLine:TextLine = null;
+        internal var _nextLine:TextLine = null;
 
         public static const MAX_LINE_WIDTH:int = 1000000;
 
@@ -28,8 +30,7 @@ package flash.text.engine {
         }
 
         public function get textBlockBeginIndex():int {
-            stub_getter("flash.text.engine.TextLine", "textBlockBeginIndex");
-            return 0;
+            return this._textBlockBeginIndex;
         }
 
         public function get specifiedWidth():Number {
@@ -40,31 +41,26 @@ package flash.text.engine {
             return this._textBlock;
         }
 
-        public function get ascent():Number {
-            stub_getter("flash.text.engine.TextLine", "ascent");
-            return 12.0;
-        }
-
-        public function get descent():Number {
-            stub_getter("flash.text.engine.TextLine", "descent");
-            return 3.0;
-        }
+        public native function get ascent():Number;
+        public native function get descent():Number;
 
         public function get unjustifiedTextWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "unjustifiedTextWidth");
-            return this._specifiedWidth;
+            // Ruffle doesn't justify lines.
+            return this.textWidth;
         }
 
         public native function get textWidth():Number;
         public native function get textHeight():Number;
 
         public function get validity():String {
-            stub_getter("flash.text.engine.TextLine", "validity");
             return this._validity;
         }
 
         public function set validity(value:String):void {
-            stub_setter("flash.text.engine.TextLine", "validity");
+            if (value != TextLineValidity.VALID && value != TextLineValidity.INVALID &&
+                value != TextLineValidity.POSSIBLY_INVALID && value != TextLineValidity.STATIC) {
+                throw new ArgumentError("Error #2008: Parameter validity must be one of the accepted values.", 2008);
+            }
             this._validity = value;
         }
 
@@ -79,11 +75,11 @@ package flash.text.engine {
         }
 
         public function get nextLine():TextLine {
-            return null;
+            return this._nextLine;
         }
 
         public function get previousLine():TextLine {
-            return null;
+            return this._previousLine;
         }
 
         public function getBaselinePosition(baseline:String):Number {
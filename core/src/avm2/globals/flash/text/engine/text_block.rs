@@ -5,188 +5,206 @@ use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
-use crate::avm2_stub_method;
-use crate::display_object::{EditText, TDisplayObject};
+use crate::context::UpdateContext;
+use crate::display_object::{AutoSizeMode, EditText, TDisplayObject};
 use crate::html::TextFormat;
-use crate::string::WStr;
+use crate::string::{WStr, WString};
+use std::ops::Range;
+
+/// A piece of the content of a `TextBlock` that is drawn with a single `ElementFormat`.
+struct TextRun {
+    range: Range<usize>,
+    format: TextFormat,
+    is_device_font: bool,
+}
 
-pub fn create_text_line<'gc>(
+/// Lays out the line of a `TextBlock` that starts at the given index of its text.
+///
+/// The content of the block is given as runs of text, along with the `ElementFormat`
+/// that each of them is drawn with. Returns `null` once all of the text has been used.
+pub fn inner_create_text_line<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    this: Object<'gc>,
+    _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let namespaces = activation.avm2().namespaces;
-    avm2_stub_method!(activation, "flash.text.TextBlock", "createTextLine");
-
-    let previous_text_line = args.try_get_object(activation, 0);
-    let width = args.get_f64(activation, 1)?;
 
-    let content = this.get_public_property("content", activation)?;
+    let texts = array_values(args.get_object(activation, 0, "texts")?);
+    let formats = array_values(args.get_object(activation, 1, "formats")?);
+    let begin_index = args.get_u32(activation, 2)? as usize;
+    let width = args.get_f64(activation, 3)?;
+
+    let mut text = WString::new();
+    let mut runs = Vec::with_capacity(texts.len());
+    for (run_text, element_format) in texts.into_iter().zip(formats) {
+        let run_text = run_text.coerce_to_string(activation)?;
+        let start = text.len();
+        text.push_str(&run_text);
+
+        let (format, is_device_font) = element_text_format(activation, element_format.as_object())?;
+        runs.push(TextRun {
+            range: start..text.len(),
+            format,
+            is_device_font,
+        });
+    }
 
-    let content = if matches!(content, Value::Null) {
+    if begin_index >= text.len() {
         return Ok(Value::Null);
-    } else {
-        content.as_object().unwrap()
-    };
-
-    let text = match previous_text_line {
-        Some(_) => {
-            // Some SWFs rely on eventually getting `null` from createLineText.
-            // TODO: Support multiple lines
-            this.set_property(
-                &Multiname::new(
-                    namespaces.flash_text_engine_internal,
-                    "_textLineCreationResult",
-                ),
-                "complete".into(),
-                activation,
-            )?;
-            return Ok(Value::Null);
-        }
-        // Get the content element's text property.
-        // TODO: GraphicElement?
-        None => {
-            let txt = content
-                .get_public_property("text", activation)
-                .unwrap_or_else(|_| activation.strings().empty().into());
-
-            if matches!(txt, Value::Null) {
-                // FP returns a null TextLine when `o` is null- note that
-                // `o` is already coerced to a String because of the AS bindings.
-                return Ok(Value::Null);
-            } else {
-                txt.coerce_to_string(activation)
-                    .expect("Guaranteed by AS bindings")
-            }
-        }
-    };
+    }
 
-    let class = activation.avm2().classes().textline;
     let movie = activation.caller_movie_or_root();
 
     // FIXME: TextLine should be its own DisplayObject
     let display_object: EditText =
         EditText::new_tlf(activation.context, movie, 0.0, 0.0, width, 15.0);
 
-    display_object.set_text(text.as_wstr(), activation.context);
-
-    // FIXME: This needs to use `intrinsic_bounds` to measure the width
-    // of the provided text, and set the width of the EditText to that.
-    // Some games depend on this (e.g. Realm Grinder).
-
-    let element_format = content
-        .get_public_property("elementFormat", activation)?
-        .as_object();
+    // Lay out the rest of the text to find out how much of it fits on this line,
+    // then only keep that part.
+    display_object.set_word_wrap(true, activation.context);
+    set_runs(
+        activation.context,
+        display_object,
+        &text,
+        &runs,
+        begin_index..text.len(),
+    );
+    let line_length = display_object.line_length(0).unwrap_or_default().max(1);
+    let end_index = (begin_index + line_length).min(text.len());
+
+    display_object.set_word_wrap(false, activation.context);
+    if end_index < text.len() {
+        set_runs(
+            activation.context,
+            display_object,
+            &text,
+            &runs,
+            begin_index..end_index,
+        );
+    }
 
-    apply_format(activation, display_object, text.as_wstr(), element_format)?;
+    // The line is as large as its text.
+    display_object.set_autosize(AutoSizeMode::Left, activation.context);
 
+    let class = activation.avm2().classes().textline;
     let instance = initialize_for_allocator(activation, display_object.into(), class)?;
     class.call_init(instance.into(), &[], activation)?;
 
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_textBlock"),
-        this.into(),
-        activation,
-    )?;
-
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_specifiedWidth"),
-        args.get_value(1),
-        activation,
-    )?;
-
     instance.set_property(
         &Multiname::new(namespaces.flash_text_engine_internal, "_rawTextLength"),
-        text.len().into(),
-        activation,
-    )?;
-
-    this.set_property(
-        &Multiname::new(
-            namespaces.flash_text_engine_internal,
-            "_textLineCreationResult",
-        ),
-        "success".into(),
-        activation,
-    )?;
-
-    this.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_firstLine"),
-        instance.into(),
+        (end_index - begin_index).into(),
         activation,
     )?;
 
     Ok(instance.into())
 }
 
-fn apply_format<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+fn array_values<'gc>(array: Object<'gc>) -> Vec<Value<'gc>> {
+    array
+        .as_array_storage()
+        .map(|array| {
+            array
+                .iter()
+                .map(|value| value.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Sets the text of a line to the given range of the text of its block.
+fn set_runs<'gc>(
+    context: &mut UpdateContext<'gc>,
     display_object: EditText<'gc>,
     text: &WStr,
+    runs: &[TextRun],
+    range: Range<usize>,
+) {
+    display_object.set_text(&text[range.clone()], context);
+
+    // Fonts can't be mixed within a field, so the first run decides.
+    let first_run = runs.iter().find(|run| run.range.contains(&range.start));
+    display_object.set_is_device_font(context, first_run.map_or(true, |run| run.is_device_font));
+
+    for run in runs {
+        let start = run.range.start.max(range.start);
+        let end = run.range.end.min(range.end);
+        if start < end {
+            display_object.set_text_format(
+                start - range.start,
+                end - range.start,
+                run.format.clone(),
+                context,
+            );
+        }
+    }
+
+    if let Some(first_run) = first_run {
+        display_object.set_new_text_format(first_run.format.clone(), context);
+    }
+}
+
+/// Converts an `ElementFormat` to the format of the text drawn with it, along with
+/// whether it uses device fonts.
+fn element_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
     element_format: Option<Object<'gc>>,
-) -> Result<(), Error<'gc>> {
-    if let Some(element_format) = element_format {
-        // TODO: Support more ElementFormat properties
-        let color = element_format
-            .get_public_property("color", activation)?
-            .coerce_to_u32(activation)?;
-        let size = element_format
-            .get_public_property("fontSize", activation)?
-            .coerce_to_number(activation)?;
-
-        let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
-            element_format.get_public_property("fontDescription", activation)?
-        {
-            (
-                Some(
-                    font_description
-                        .get_public_property("fontName", activation)?
-                        .coerce_to_string(activation)?
-                        .as_wstr()
-                        .into(),
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontWeight", activation)?
-                        .coerce_to_string(activation)?
-                        == b"bold",
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontPosture", activation)?
-                        .coerce_to_string(activation)?
-                        == b"italic",
-                ),
+) -> Result<(TextFormat, bool), Error<'gc>> {
+    let Some(element_format) = element_format else {
+        return Ok((TextFormat::default(), true));
+    };
+
+    // TODO: Support more ElementFormat properties
+    let color = element_format
+        .get_public_property("color", activation)?
+        .coerce_to_u32(activation)?;
+    let size = element_format
+        .get_public_property("fontSize", activation)?
+        .coerce_to_number(activation)?;
+    let tracking = element_format
+        .get_public_property("trackingRight", activation)?
+        .coerce_to_number(activation)?;
+
+    let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
+        element_format.get_public_property("fontDescription", activation)?
+    {
+        (
+            Some(
+                font_description
+                    .get_public_property("fontName", activation)?
+                    .coerce_to_string(activation)?
+                    .as_wstr()
+                    .into(),
+            ),
+            Some(
+                &font_description
+                    .get_public_property("fontWeight", activation)?
+                    .coerce_to_string(activation)?
+                    == b"bold",
+            ),
+            Some(
                 &font_description
-                    .get_public_property("fontLookup", activation)?
+                    .get_public_property("fontPosture", activation)?
                     .coerce_to_string(activation)?
-                    == b"device",
-            )
-        } else {
-            (None, None, None, true)
-        };
-
-        let format = TextFormat {
-            color: Some(swf::Color::from_rgb(color, 0xFF)),
-            size: Some(size),
-            font,
-            bold,
-            italic,
-            ..TextFormat::default()
-        };
-
-        display_object.set_is_device_font(activation.context, is_device_font);
-        display_object.set_text_format(0, text.len(), format.clone(), activation.context);
-        display_object.set_new_text_format(format, activation.context);
+                    == b"italic",
+            ),
+            &font_description
+                .get_public_property("fontLookup", activation)?
+                .coerce_to_string(activation)?
+                == b"device",
+        )
     } else {
-        display_object.set_is_device_font(activation.context, true);
-    }
-
-    display_object.set_word_wrap(true, activation.context);
-
-    let measured_text = display_object.measure_text(activation.context);
+        (None, None, None, true)
+    };
 
-    display_object.set_height(activation.context, measured_text.1.to_pixels());
+    let format = TextFormat {
+        color: Some(swf::Color::from_rgb(color, 0xFF)),
+        size: Some(size),
+        font,
+        bold,
+        italic,
+        letter_spacing: Some(tracking),
+        ..TextFormat::default()
+    };
 
-    Ok(())
+    Ok((format, is_device_font))
 }
@@ -27,3 +27,31 @@ pub fn get_text_height<'gc>(
     let measured_text = edit_text.measure_text(activation.context);
     Ok(measured_text.1.to_pixels().into())
 }
+
+pub fn get_ascent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let display_object = this.as_display_object().unwrap();
+    let edit_text = display_object.as_edit_text().unwrap();
+
+    let ascent = edit_text
+        .line_metrics(0)
+        .map_or(0.0, |metrics| metrics.ascent.to_pixels());
+    Ok(ascent.into())
+}
+
+pub fn get_descent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let display_object = this.as_display_object().unwrap();
+    let edit_text = display_object.as_edit_text().unwrap();
+
+    let descent = edit_text
+        .line_metrics(0)
+        .map_or(0.0, |metrics| metrics.descent.to_pixels());
+    Ok(descent.into())
+}
@@ -1,8 +1,11 @@
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
-use crate::html::{transform_dashes_to_camel_case, CssStream};
+use crate::html::{
+    parse_css_color, parse_css_font_family, transform_dashes_to_camel_case, CssStream,
+};
 use crate::string::AvmString;
-use ruffle_wstr::{WStr, WString};
+
+pub use crate::avm2::object::style_sheet_allocator;
 
 pub fn inner_parse_css<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -48,15 +51,7 @@ pub fn inner_parse_color<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let input = args.get_string(activation, 0)?;
 
-    if let Some(stripped) = input.strip_prefix(WStr::from_units(b"#")) {
-        if stripped.len() <= 6 {
-            if let Ok(number) = u32::from_str_radix(&stripped.to_string(), 16) {
-                return Ok(number.into());
-            }
-        }
-    }
-
-    Ok(0.into())
+    Ok(parse_css_color(&input).into())
 }
 
 pub fn inner_parse_font_family<'gc>(
@@ -65,44 +60,37 @@ pub fn inner_parse_font_family<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let input = args.get_string(activation, 0)?;
-    let mut result = WString::new();
 
-    let mut pos = 0;
-    while pos < input.len() {
-        // Skip whitespace
-        while input.get(pos) == Some(' ' as u16) {
-            pos += 1;
-        }
-
-        // Find the whole value
-        let start = pos;
-        while input.get(pos) != Some(',' as u16) && pos < input.len() {
-            pos += 1;
-        }
-
-        let mut value = &input[start..pos];
+    Ok(Value::String(AvmString::new(
+        activation.gc(),
+        parse_css_font_family(&input),
+    )))
+}
 
-        if pos < input.len() {
-            pos += 1; // move past the comma
-        }
+pub fn inner_set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet() {
+        let selector = args.get_string(activation, 0)?;
+        let format = args
+            .try_get_object(activation, 1)
+            .and_then(|format| format.as_text_format().map(|format| format.clone()));
+        style_sheet.style_sheet().set_style(&selector, format);
+    }
 
-        // Transform some names
-        if value == b"mono" {
-            value = WStr::from_units(b"_typewriter");
-        } else if value == b"sans-serif" {
-            value = WStr::from_units(b"_sans");
-        } else if value == b"serif" {
-            value = WStr::from_units(b"_serif");
-        }
+    Ok(Value::Undefined)
+}
 
-        // Add it to the result (without any extra space)
-        if !value.is_empty() {
-            if !result.is_empty() {
-                result.push_char(',');
-            }
-            result.push_str(value);
-        }
+pub fn inner_clear<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet() {
+        style_sheet.style_sheet().clear();
     }
 
-    Ok(Value::String(AvmString::new(activation.gc(), result)))
+    Ok(Value::Undefined)
 }
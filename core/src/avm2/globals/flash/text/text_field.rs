@@ -7,7 +7,9 @@ use crate::avm2::object::{ClassObject, Object, TObject, TextFormatObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{ArrayObject, ArrayStorage, Error};
-use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
+use crate::display_object::{
+    AutoSizeMode, EditText, EditTextStyleSheet, TDisplayObject, TextSelection,
+};
 use crate::html::TextFormat;
 use crate::string::AvmString;
 use crate::{avm2_stub_getter, avm2_stub_method, avm2_stub_setter};
@@ -503,6 +505,44 @@ pub fn set_selectable<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        if let EditTextStyleSheet::Avm2(style_sheet) = this.style_sheet() {
+            return Ok(Object::from(style_sheet).into());
+        }
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = args
+            .try_get_object(activation, 0)
+            .and_then(|style_sheet| style_sheet.as_style_sheet())
+            .map_or(EditTextStyleSheet::None, EditTextStyleSheet::Avm2);
+
+        this.set_style_sheet(activation.context, style_sheet);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_text<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -1227,6 +1267,24 @@ pub fn get_line_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_image_reference<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let id = args.get_string(activation, 0)?;
+    Ok(this
+        .image_reference(&id)
+        .map_or(Value::Null, |image| image.object2()))
+}
+
 pub fn get_line_offset<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -0,0 +1,195 @@
+//! `flash.text.TextSnapshot` builtin/prototype
+
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayObject, ArrayStorage, Error, Multiname, Object, Value};
+use crate::string::AvmString;
+use crate::text_snapshot::TextSnapshot;
+use swf::{Color, Point, Twips};
+
+/// Describes the static text of the container this snapshot was taken from.
+///
+/// The snapshot is taken anew on every call, so that it reflects
+/// the current children of the container.
+fn snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<TextSnapshot<'gc>, Error<'gc>> {
+    let namespaces = activation.avm2().namespaces;
+    let container = this
+        .get_property(
+            &Multiname::new(namespaces.flash_text_internal, "_container"),
+            activation,
+        )?
+        .as_object()
+        .and_then(|container| container.as_display_object());
+
+    Ok(container
+        .map(|container| TextSnapshot::new(activation.context, container))
+        .unwrap_or_default())
+}
+
+/// Implements `TextSnapshot.charCount`'s getter
+pub fn get_char_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(snapshot(activation, this)?.char_count().into())
+}
+
+/// Implements `TextSnapshot.findText`
+pub fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?.max(0) as usize;
+    let text = args.get_string(activation, 1)?;
+    let case_sensitive = args.get_bool(2);
+
+    let snapshot = snapshot(activation, this)?;
+    Ok(snapshot
+        .find(begin, &text.to_utf8_lossy(), case_sensitive)
+        .map_or(-1, |index| index as i32)
+        .into())
+}
+
+/// Implements `TextSnapshot.getSelected`
+pub fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+
+    let snapshot = snapshot(activation, this)?;
+    Ok(snapshot.is_selected(snapshot.range(begin, end)).into())
+}
+
+/// Implements `TextSnapshot.getSelectedText`
+pub fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let include_line_endings = args.get_bool(0);
+
+    let text = snapshot(activation, this)?.selected_text(include_line_endings);
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getText`
+pub fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+    let include_line_endings = args.get_bool(2);
+
+    let snapshot = snapshot(activation, this)?;
+    let text = snapshot.text(snapshot.range(begin, end), include_line_endings);
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getTextRunInfo`
+pub fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+
+    let snapshot = snapshot(activation, this)?;
+    let range = snapshot.range(begin, end);
+    let object_class = activation.avm2().classes().object;
+    let mut infos = Vec::new();
+    for c in snapshot.chars() {
+        if !range.contains(&c.index) {
+            continue;
+        }
+
+        let info = object_class.construct(activation, &[])?;
+        let font = AvmString::new_utf8(activation.context.gc_context, &c.glyph.font_name);
+        let properties: [(&'static str, Value<'gc>); 19] = [
+            ("indexInRun", c.index.into()),
+            ("selected", c.selected.into()),
+            ("font", font.into()),
+            ("color", c.glyph.color.to_rgb().into()),
+            ("height", c.glyph.height.to_pixels().into()),
+            ("matrix_a", c.matrix.a.into()),
+            ("matrix_b", c.matrix.b.into()),
+            ("matrix_c", c.matrix.c.into()),
+            ("matrix_d", c.matrix.d.into()),
+            ("matrix_tx", c.matrix.tx.to_pixels().into()),
+            ("matrix_ty", c.matrix.ty.to_pixels().into()),
+            ("corner0x", c.corners[0].x.to_pixels().into()),
+            ("corner0y", c.corners[0].y.to_pixels().into()),
+            ("corner1x", c.corners[1].x.to_pixels().into()),
+            ("corner1y", c.corners[1].y.to_pixels().into()),
+            ("corner2x", c.corners[2].x.to_pixels().into()),
+            ("corner2y", c.corners[2].y.to_pixels().into()),
+            ("corner3x", c.corners[3].x.to_pixels().into()),
+            ("corner3y", c.corners[3].y.to_pixels().into()),
+        ];
+        for (name, value) in properties {
+            info.set_public_property(name, value, activation)?;
+        }
+        infos.push(Value::from(info));
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&infos))?.into())
+}
+
+/// Implements `TextSnapshot.hitTestTextNearPos`
+pub fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let x = args.get_f64(activation, 0)?;
+    let y = args.get_f64(activation, 1)?;
+    let max_distance = args.get_f64(activation, 2)?;
+
+    let point = Point::new(Twips::from_pixels(x), Twips::from_pixels(y));
+    let snapshot = snapshot(activation, this)?;
+    Ok(snapshot
+        .hit_test(point, Twips::from_pixels(max_distance.max(0.0)))
+        .map_or(-1.0, |index| index as f64)
+        .into())
+}
+
+/// Implements `TextSnapshot.setSelectColor`
+pub fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = Color::from_rgb(args.get_u32(activation, 0)?, 255);
+
+    snapshot(activation, this)?.set_select_color(activation.context.gc_context, color);
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.setSelected`
+pub fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+    let select = args.get_bool(2);
+
+    let snapshot = snapshot(activation, this)?;
+    snapshot.set_selected(
+        activation.context.gc_context,
+        snapshot.range(begin, end),
+        select,
+    );
+    Ok(Value::Undefined)
+}
@@ -3,3 +3,4 @@
 pub mod context_menu;
 pub mod keyboard;
 pub mod mouse;
+pub mod multitouch;
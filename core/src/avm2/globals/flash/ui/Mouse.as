@@ -1,11 +1,17 @@
 package flash.ui {
 
+    import flash.display.BitmapData;
     import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
 
     public final class Mouse {
         public static native function hide(): void;
         public static native function show(): void;
+
+        [API("667")]
+        public static native function get cursor():String;
+        [API("667")]
+        public static native function set cursor(value:String):void;
+
         public static function get supportsCursor():Boolean {
             stub_getter("flash.ui.Mouse", "supportsCursor");
             return true;
@@ -15,12 +21,23 @@ package flash.ui {
             return true;
         }
 
+        private static native function registerCursorFrames(name:String, frames:Vector.<BitmapData>, hotSpotX:Number, hotSpotY:Number, frameRate:Number):void;
+
+        [API("667")]
         public static function registerCursor(name:String, cursor:MouseCursorData):void {
-            stub_method("flash.ui.Mouse", "registerCursor");
+            if (name == null) {
+                throw new TypeError("Error #2007: Parameter name must be non-null.", 2007);
+            }
+            if (cursor == null) {
+                throw new TypeError("Error #2007: Parameter cursor must be non-null.", 2007);
+            }
+            if (cursor.data == null || cursor.data.length == 0) {
+                throw new ArgumentError("Error #2008: Parameter cursor must be one of the accepted values.", 2008);
+            }
+            registerCursorFrames(name, cursor.data, cursor.hotSpot.x, cursor.hotSpot.y, cursor.frameRate);
         }
 
-        public static function unregisterCursor(name:String):void {
-            stub_method("flash.ui.Mouse", "unregisterCursor");
-        }
+        [API("667")]
+        public static native function unregisterCursor(name:String):void;
     }
 }
@@ -1,6 +1,31 @@
 package flash.ui {
+    import flash.events.GestureEvent;
+    import flash.events.TransformGestureEvent;
+
     public final class Multitouch {
-        // FIXME - implement a setter and gett
-        public static var inputMode:String = MultitouchInputMode.GESTURE;
+        public static native function get inputMode():String;
+        public static native function set inputMode(value:String):void;
+
+        public static native function get maxTouchPoints():int;
+
+        public static function get supportsTouchEvents():Boolean {
+            return maxTouchPoints > 0;
+        }
+
+        public static function get supportsGestureEvents():Boolean {
+            return maxTouchPoints > 1;
+        }
+
+        public static function get supportedGestures():Vector.<String> {
+            if (!supportsGestureEvents) {
+                return null;
+            }
+            return new <String>[
+                TransformGestureEvent.GESTURE_PAN,
+                TransformGestureEvent.GESTURE_ROTATE,
+                TransformGestureEvent.GESTURE_ZOOM,
+                GestureEvent.GESTURE_TWO_FINGER_TAP
+            ];
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,13 @@
 //! `flash.ui.Mouse` builtin
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::ui::CursorImage;
+use crate::string::AvmString;
 
 pub fn hide<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -22,3 +26,98 @@ pub fn show<'gc>(
     activation.context.ui.set_mouse_visible(true);
     Ok(Value::Undefined)
 }
+
+pub fn get_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let cursor = activation.context.mouse_data.cursors.selected();
+    Ok(AvmString::new_utf8(activation.gc(), cursor).into())
+}
+
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let cursor = args.get_string_non_null(activation, 0, "cursor")?;
+    if !activation
+        .context
+        .mouse_data
+        .cursors
+        .select(&cursor.to_utf8_lossy())
+    {
+        return Err(make_error_2008(activation, "cursor"));
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn register_cursor_frames<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    let frames = args.get_object(activation, 1, "frames")?;
+    let hotspot_x = args.get_f64(activation, 2)?;
+    let hotspot_y = args.get_f64(activation, 3)?;
+    let frame_rate = args.get_f64(activation, 4)?;
+
+    let mut images = Vec::new();
+    let frames = frames
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    for frame in frames {
+        let Some(bitmap_data) = frame.as_object().and_then(|o| o.as_bitmap_data()) else {
+            return Err(make_error_2008(activation, "cursor"));
+        };
+        bitmap_data.check_valid(activation)?;
+
+        let bitmap_data = bitmap_data.sync(activation.context.renderer);
+        let bitmap_data = bitmap_data.read();
+        let rgba = bitmap_data
+            .pixels()
+            .iter()
+            .flat_map(|p| {
+                let p = p.to_un_multiplied_alpha();
+                [p.red(), p.green(), p.blue(), p.alpha()]
+            })
+            .collect();
+        let (width, height) = (bitmap_data.width(), bitmap_data.height());
+        images.push(CursorImage {
+            width,
+            height,
+            hotspot_x: (hotspot_x.max(0.0) as u32).min(width.saturating_sub(1)),
+            hotspot_y: (hotspot_y.max(0.0) as u32).min(height.saturating_sub(1)),
+            rgba,
+        });
+    }
+
+    let frame_rate = if frame_rate.is_finite() {
+        frame_rate
+    } else {
+        0.0
+    };
+    activation
+        .context
+        .mouse_data
+        .cursors
+        .register(activation.context.ui, name, images, frame_rate);
+    Ok(Value::Undefined)
+}
+
+pub fn unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?;
+    activation
+        .context
+        .mouse_data
+        .cursors
+        .unregister(activation.context.ui, &name.to_utf8_lossy());
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,46 @@
+//! `flash.ui.Multitouch` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::multitouch::MultitouchInputMode;
+
+pub fn get_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input_mode = activation.context.mouse_data.touches.input_mode;
+    Ok(input_mode.name().into())
+}
+
+pub fn set_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input_mode = args.get_string_non_null(activation, 0, "inputMode")?;
+    let Some(input_mode) = MultitouchInputMode::from_name(&input_mode.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "inputMode"));
+    };
+    activation.context.mouse_data.touches.input_mode = input_mode;
+    Ok(Value::Undefined)
+}
+
+pub fn get_max_touch_points<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Not every frontend knows about the touch screen before it's used,
+    // so also count the fingers we've seen.
+    let max_touch_points = activation
+        .context
+        .ui
+        .max_touch_points()
+        .max(activation.context.mouse_data.touches.max_points_seen);
+    Ok(max_touch_points.into())
+}
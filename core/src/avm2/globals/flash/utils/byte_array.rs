@@ -678,13 +678,7 @@ pub fn compress<'gc>(
             .coerce_to_string(activation)?;
         let algorithm = match algorithm.parse() {
             Ok(algorithm) => algorithm,
-            Err(_) => {
-                return Err(Error::AvmError(crate::avm2::error::io_error(
-                    activation,
-                    "Error #2058: There was an error decompressing the data.",
-                    2058,
-                )?))
-            }
+            Err(_) => return Err(make_error_2008(activation, "algorithm")),
         };
         let buffer = bytearray.compress(algorithm);
         bytearray.clear();
@@ -709,13 +703,7 @@ pub fn uncompress<'gc>(
             .coerce_to_string(activation)?;
         let algorithm = match algorithm.parse() {
             Ok(algorithm) => algorithm,
-            Err(_) => {
-                return Err(Error::AvmError(crate::avm2::error::io_error(
-                    activation,
-                    "Error #2058: There was an error decompressing the data.",
-                    2058,
-                )?))
-            }
+            Err(_) => return Err(make_error_2008(activation, "algorithm")),
         };
         let buffer = match bytearray.decompress(algorithm) {
             Some(buffer) => buffer,
@@ -755,7 +743,7 @@ pub fn read_object<'gc>(
                     .map_err(|_| "Error: Invalid object")?;
                 (
                     extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
+                    crate::avm2::amf::deserialize_amf0_value(activation, &amf, &decoder)?,
                 )
             }
             ObjectEncoding::Amf3 => {
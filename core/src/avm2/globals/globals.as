@@ -60,6 +60,7 @@ include "flash/display/BlendMode.as"
 include "flash/display/CapsStyle.as"
 include "flash/display/ColorCorrection.as"
 include "flash/display/ColorCorrectionSupport.as"
+include "flash/display/FocusDirection.as"
 include "flash/display/FrameLabel.as"
 include "flash/display/GradientType.as"
 include "flash/display/IGraphicsStroke.as"
@@ -177,6 +178,7 @@ include "flash/events/HTTPStatusEvent.as"
 include "flash/events/IOErrorEvent.as"
 include "flash/events/InvokeEvent.as"
 include "flash/events/KeyboardEvent.as"
+include "flash/events/LocationChangeEvent.as"
 include "flash/events/NativeWindowBoundsEvent.as"
 include "flash/events/NativeWindowDisplayStateEvent.as"
 include "flash/events/NetDataEvent.as"
@@ -231,6 +233,7 @@ include "flash/geom/Transform.as"
 include "flash/geom/Utils3D.as"
 include "flash/geom/Vector3D.as"
 
+include "flash/globalization/Collator.as"
 include "flash/globalization/CollatorMode.as"
 include "flash/globalization/CurrencyParseResult.as"
 include "flash/globalization/CurrencyFormatter.as"
@@ -265,6 +268,7 @@ include "flash/media/SoundMixer.as"
 include "flash/media/SoundTransform.as"
 include "flash/media/StageVideoAvailability.as"
 include "flash/media/StageVideoAvailabilityReason.as"
+include "flash/media/StageWebView.as"
 include "flash/media/Video.as"
 include "flash/media/VideoCodec.as"
 include "flash/media/VideoStatus.as"
@@ -296,6 +300,7 @@ include "flash/net/Responder.as"
 include "flash/net/SharedObject.as"
 include "flash/net/SharedObjectFlushStatus.as"
 include "flash/net/Socket.as"
+include "flash/net/SecureSocket.as" // SecureSocket extends Socket
 include "flash/net/URLLoader.as"
 include "flash/net/URLLoaderDataFormat.as"
 include "flash/net/URLRequest.as"
@@ -306,6 +311,8 @@ include "flash/net/URLVariables.as"
 include "flash/net/XMLSocket.as"
 
 include "flash/filesystem/File.as" // File extends FileReference
+include "flash/filesystem/FileMode.as"
+include "flash/filesystem/FileStream.as"
 
 include "flash/net/drm/AuthenticationMethod.as"
 include "flash/net/drm/LoadVoucherSetting.as"
@@ -90,7 +90,11 @@ pub fn q_name_constructor<'gc>(
 
         if &*local != b"*" {
             this.set_local_name(activation.context.gc_context, local);
-            Some(activation.avm2().find_public_namespace())
+            Some(
+                activation
+                    .default_xml_namespace()
+                    .unwrap_or_else(|| activation.avm2().find_public_namespace()),
+            )
         } else {
             None
         }
@@ -245,6 +245,7 @@ fn last_index_of<'gc>(
 
 /// Implements String.localeCompare
 /// NOTE: Despite the declaration of this function in the documentation, FP does not support multiple strings in comparison
+/// NOTE: FP compares code units here regardless of the locale; `flash.globalization.Collator` is the locale-aware comparison
 fn locale_compare<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
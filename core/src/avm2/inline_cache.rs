@@ -0,0 +1,218 @@
+//! Inline caches for property lookups.
+
+use crate::avm2::multiname::Multiname;
+use crate::avm2::property::Property;
+use crate::avm2::vtable::VTable;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, Mutation};
+use std::fmt;
+
+/// How many vtables a single site remembers lookups for.
+///
+/// Sites that see more classes than this forget the one they saw first.
+const MAX_ENTRIES: usize = 4;
+
+/// A `getproperty`, `setproperty`, `callproperty` or `callpropvoid` instruction, along with
+/// the traits its multiname resolved to on the vtables of the objects it was used on.
+///
+/// Resolving a multiname against a vtable means hashing the name and searching every
+/// namespace in its namespace set. Most sites only ever see objects of one or two classes,
+/// so remembering what each vtable resolved to skips all of that the next time. An entry is
+/// only used as long as the vtable hasn't changed since it was made.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct PropertySite<'gc> {
+    multiname: Gc<'gc, Multiname<'gc>>,
+
+    entries: RefLock<Vec<Entry<'gc>>>,
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+struct Entry<'gc> {
+    vtable: VTable<'gc>,
+
+    /// The generation of `vtable` at the time of the lookup.
+    generation: u32,
+
+    property: Option<Property>,
+}
+
+impl<'gc> PropertySite<'gc> {
+    pub fn new(mc: &Mutation<'gc>, multiname: Gc<'gc, Multiname<'gc>>) -> Gc<'gc, Self> {
+        Gc::new(
+            mc,
+            Self {
+                multiname,
+                entries: RefLock::new(Vec::new()),
+            },
+        )
+    }
+
+    pub fn multiname(&self) -> Gc<'gc, Multiname<'gc>> {
+        self.multiname
+    }
+
+    /// Looks up the trait that `multiname` refers to on `vtable`.
+    ///
+    /// `multiname` is the multiname of this site, with any runtime parts filled in. Lookups
+    /// are only remembered if there were none, as the name could differ every time otherwise.
+    pub fn get_trait(
+        this: Gc<'gc, Self>,
+        mc: &Mutation<'gc>,
+        vtable: VTable<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Property> {
+        if this.multiname.has_lazy_component() {
+            return vtable.get_trait(multiname);
+        }
+
+        let generation = vtable.generation();
+        if let Some(entry) = this
+            .entries
+            .borrow()
+            .iter()
+            .find(|entry| entry.vtable == vtable)
+        {
+            if entry.generation == generation {
+                return entry.property;
+            }
+        }
+
+        let property = vtable.get_trait(multiname);
+        let mut entries = unlock!(Gc::write(mc, this), PropertySite, entries).borrow_mut();
+        entries.retain(|entry| entry.vtable != vtable);
+        if entries.len() >= MAX_ENTRIES {
+            entries.remove(0);
+        }
+        entries.push(Entry {
+            vtable,
+            generation,
+            property,
+        });
+        property
+    }
+}
+
+impl fmt::Debug for PropertySite<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PropertySite")
+            .field("multiname", &self.multiname)
+            .field("entries", &self.entries.borrow().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PropertySite, MAX_ENTRIES};
+    use crate::avm2::api_version::ApiVersion;
+    use crate::avm2::multiname::Multiname;
+    use crate::avm2::namespace::Namespace;
+    use crate::avm2::property::Property;
+    use crate::avm2::qname::QName;
+    use crate::avm2::vtable::VTable;
+    use crate::string::{AvmStringInterner, StringContext};
+    use gc_arena::{rootless_arena, Gc};
+
+    fn with_context(f: impl for<'gc> FnOnce(&mut StringContext<'gc>)) {
+        rootless_arena(|mc| {
+            let interner = Box::leak(Box::new(AvmStringInterner::new(mc)));
+            f(&mut StringContext::from_parts(mc, interner));
+        });
+    }
+
+    fn public_name<'gc>(context: &mut StringContext<'gc>, name: &'static str) -> QName<'gc> {
+        let public = Namespace::package("", ApiVersion::AllVersions, context);
+        QName::new(public, name)
+    }
+
+    fn new_site<'gc>(
+        context: &mut StringContext<'gc>,
+        name: QName<'gc>,
+    ) -> Gc<'gc, PropertySite<'gc>> {
+        let multiname = Gc::new(context.gc(), Multiname::from(name));
+        PropertySite::new(context.gc(), multiname)
+    }
+
+    fn lookup<'gc>(
+        context: &mut StringContext<'gc>,
+        site: Gc<'gc, PropertySite<'gc>>,
+        vtable: VTable<'gc>,
+    ) -> Option<Property> {
+        let multiname = site.multiname();
+        PropertySite::get_trait(site, context.gc(), vtable, &multiname)
+    }
+
+    fn is_cached<'gc>(site: Gc<'gc, PropertySite<'gc>>, vtable: VTable<'gc>) -> bool {
+        site.entries
+            .borrow()
+            .iter()
+            .any(|entry| entry.vtable == vtable)
+    }
+
+    #[test]
+    fn test_monomorphic_site() {
+        with_context(|context| {
+            let name = public_name(context, "value");
+            let vtable = VTable::newcatch(context.gc(), &name);
+            let site = new_site(context, name);
+
+            for _ in 0..3 {
+                let property = lookup(context, site, vtable);
+                assert!(matches!(property, Some(Property::Slot { slot_id: 0 })));
+            }
+            assert_eq!(site.entries.borrow().len(), 1);
+        })
+    }
+
+    #[test]
+    fn test_polymorphic_site() {
+        with_context(|context| {
+            let name = public_name(context, "value");
+            let vtables: Vec<_> = (0..MAX_ENTRIES + 2)
+                .map(|_| VTable::newcatch(context.gc(), &name))
+                .collect();
+            let site = new_site(context, name);
+
+            for vtable in &vtables {
+                let property = lookup(context, site, *vtable);
+                assert!(matches!(property, Some(Property::Slot { slot_id: 0 })));
+            }
+            assert_eq!(site.entries.borrow().len(), MAX_ENTRIES);
+            assert!(!is_cached(site, vtables[0]));
+            assert!(!is_cached(site, vtables[1]));
+            assert!(vtables[2..].iter().all(|vtable| is_cached(site, *vtable)));
+
+            // A forgotten vtable is looked up again, and pushes out the oldest one left.
+            let property = lookup(context, site, vtables[0]);
+            assert!(matches!(property, Some(Property::Slot { slot_id: 0 })));
+            assert_eq!(site.entries.borrow().len(), MAX_ENTRIES);
+            assert!(is_cached(site, vtables[0]));
+            assert!(!is_cached(site, vtables[2]));
+        })
+    }
+
+    #[test]
+    fn test_vtable_generation_invalidates_site() {
+        with_context(|context| {
+            let existing = public_name(context, "existing");
+            let added = public_name(context, "added");
+            let vtable = VTable::newcatch(context.gc(), &existing);
+            let site = new_site(context, added);
+
+            // Misses are remembered as well.
+            assert!(lookup(context, site, vtable).is_none());
+            assert!(is_cached(site, vtable));
+            let generation = vtable.generation();
+
+            vtable.copy_property_for_interface(context.gc(), existing, added);
+            assert_ne!(vtable.generation(), generation);
+
+            let property = lookup(context, site, vtable);
+            assert!(matches!(property, Some(Property::Slot { slot_id: 0 })));
+            assert_eq!(site.entries.borrow().len(), 1);
+        })
+    }
+}
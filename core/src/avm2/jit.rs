@@ -0,0 +1,1262 @@
+//! Tiering hot bytecode methods up to compiled code.
+//!
+//! See `crate::backend::jit` for the IR that methods are lowered to.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::SystemClassDefs;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::op::Op;
+use crate::avm2::{Error, Value};
+use crate::backend::jit::{
+    BinaryOp, Block, BlockId, CompiledFunction, Exit, Function, Instruction, Interrupted, Slot,
+    Type, UnaryOp, Var,
+};
+use gc_arena::Gc;
+use std::cell::{Cell, OnceCell};
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+use swf::avm2::types::MethodFlags as AbcMethodFlags;
+
+/// How many times a method is interpreted before it is compiled.
+const TIER_UP_CALLS: u32 = 1000;
+
+/// The type that a parameter or return value is coerced to, out of the types that compiled
+/// code supports.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Coercion {
+    Int,
+    Uint,
+    Number,
+    Boolean,
+}
+
+impl Coercion {
+    fn of_class<'gc>(defs: &SystemClassDefs<'gc>, class: Class<'gc>) -> Option<Self> {
+        if class == defs.int {
+            Some(Coercion::Int)
+        } else if class == defs.uint {
+            Some(Coercion::Uint)
+        } else if class == defs.number {
+            Some(Coercion::Number)
+        } else if class == defs.boolean {
+            Some(Coercion::Boolean)
+        } else {
+            None
+        }
+    }
+
+    /// The type of the variables that hold values of this type. `uint` values are held as
+    /// `Number`s, as they don't all fit in an `int`.
+    fn ty(self) -> Type {
+        match self {
+            Coercion::Int => Type::Int,
+            Coercion::Uint | Coercion::Number => Type::Number,
+            Coercion::Boolean => Type::Boolean,
+        }
+    }
+}
+
+/// What `returnvalue` does with the returned value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Returns {
+    /// The method has no return type, and returns the value unchanged.
+    Any,
+
+    /// The method returns `void`, so the value is discarded.
+    Void,
+
+    Coerce(Coercion),
+}
+
+/// The tier-up state of a bytecode method.
+#[derive(Default)]
+pub struct JitState {
+    calls: Cell<u32>,
+
+    /// The compiled method, once compiling it has been attempted.
+    compiled: OnceCell<Option<Rc<CompiledMethod>>>,
+}
+
+/// A bytecode method compiled by the JIT backend.
+pub struct CompiledMethod {
+    function: Box<dyn CompiledFunction>,
+    params: Vec<Coercion>,
+    returns: Returns,
+}
+
+impl CompiledMethod {
+    /// Coerces the arguments of a call to the types the compiled method takes, or returns
+    /// `None` if the call has to be interpreted instead.
+    ///
+    /// Only calls that pass every parameter are compiled; the interpreter takes care of default
+    /// values and argument count errors.
+    pub fn coerce_arguments<'gc>(
+        &self,
+        arguments: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Option<Result<Vec<Slot>, Error<'gc>>> {
+        if arguments.len() != self.params.len() {
+            return None;
+        }
+
+        let coerce = |activation: &mut Activation<'_, 'gc>| {
+            let mut args = Vec::with_capacity(arguments.len());
+            for (value, coercion) in arguments.iter().zip(&self.params) {
+                args.push(match coercion {
+                    Coercion::Int => Slot::Int(value.coerce_to_i32(activation)?),
+                    Coercion::Uint => Slot::Number(value.coerce_to_u32(activation)?.into()),
+                    Coercion::Number => Slot::Number(value.coerce_to_number(activation)?),
+                    Coercion::Boolean => Slot::Boolean(value.coerce_to_boolean()),
+                });
+            }
+            Ok(args)
+        };
+        Some(coerce(activation))
+    }
+
+    /// Runs the compiled method with arguments from `coerce_arguments`.
+    pub fn run<'gc>(
+        &self,
+        args: &[Slot],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let deadline = activation.context.update_start + activation.context.max_execution_duration;
+        match self.function.call(args, deadline) {
+            Ok(None) => Ok(Value::Undefined),
+            Ok(Some(Slot::Int(value))) => Ok(value.into()),
+            Ok(Some(Slot::Number(value))) if self.returns == Returns::Coerce(Coercion::Uint) => {
+                Ok((value as u32).into())
+            }
+            Ok(Some(Slot::Number(value))) => Ok(value.into()),
+            Ok(Some(Slot::Boolean(value))) => Ok(value.into()),
+            Err(Interrupted) => Err(
+                "A script in this movie has taken too long to execute and has been terminated."
+                    .into(),
+            ),
+        }
+    }
+}
+
+/// Counts a call to a method, and returns its compiled code if it has been called often
+/// enough to be compiled.
+pub fn compiled_method<'gc>(
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Option<Rc<CompiledMethod>> {
+    if activation.context.avm2.jit.is_none() || activation.context.avm2.debugger.is_attached() {
+        return None;
+    }
+
+    let state = &method.jit;
+    if let Some(compiled) = state.compiled.get() {
+        return compiled.clone();
+    }
+
+    let calls = state.calls.get().saturating_add(1);
+    state.calls.set(calls);
+    if calls < TIER_UP_CALLS || method.verified_info.borrow().is_none() {
+        return None;
+    }
+
+    let compiled = compile(method, activation).map(Rc::new);
+    if compiled.is_none() {
+        tracing::debug!(
+            "Not compiling AVM2 method {}, it uses unsupported features",
+            method.method_name()
+        );
+    }
+    state.compiled.get_or_init(|| compiled).clone()
+}
+
+fn compile<'gc>(
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Option<CompiledMethod> {
+    if method.is_variadic()
+        || method
+            .method()
+            .flags
+            .intersects(AbcMethodFlags::NEED_ACTIVATION | AbcMethodFlags::SET_DXNS)
+    {
+        return None;
+    }
+
+    let body = method.body()?;
+    let verified_info = method.verified_info.borrow();
+    let verified_info = verified_info.as_ref()?;
+    if !verified_info.exceptions.is_empty() {
+        return None;
+    }
+
+    let defs = activation.avm2().class_defs().clone();
+    let params = verified_info
+        .param_config
+        .iter()
+        .map(|param| Coercion::of_class(&defs, param.param_type?))
+        .collect::<Option<Vec<_>>>()?;
+    let returns = match verified_info.return_type {
+        None => Returns::Any,
+        Some(class) if class == defs.void => Returns::Void,
+        Some(class) => Returns::Coerce(Coercion::of_class(&defs, class)?),
+    };
+
+    let function = lower(
+        &verified_info.parsed_code,
+        body.num_locals,
+        &params,
+        returns,
+        |class| Coercion::of_class(&defs, class),
+    )?;
+    let function = activation.context.avm2.jit.as_mut()?.compile(&function)?;
+    Some(CompiledMethod {
+        function,
+        params,
+        returns,
+    })
+}
+
+/// Lowers the verified code of a method to the IR, or returns `None` if it uses anything
+/// compiled code doesn't support.
+///
+/// `coercion_of` returns the coercion that `coerce` to a class applies, if it is supported.
+pub fn lower<'gc>(
+    code: &[Op<'gc>],
+    num_locals: u32,
+    params: &[Coercion],
+    returns: Returns,
+    coercion_of: impl Fn(Class<'gc>) -> Option<Coercion>,
+) -> Option<Function> {
+    let num_registers = num_locals as usize;
+    if num_registers < params.len() + 1 {
+        return None;
+    }
+
+    let leaders = find_leaders(code)?;
+    let mut lowering = Lowering {
+        code,
+        block_of: leaders
+            .iter()
+            .enumerate()
+            .map(|(block, &ip)| (ip, block))
+            .collect(),
+        leaders,
+        params,
+        returns,
+        coercion_of,
+        storage: vec![None; num_registers],
+    };
+    for (i, param) in params.iter().enumerate() {
+        lowering.storage[i + 1] = Some(param.ty());
+    }
+
+    // Locals hold a single type each, which is widened from `int` to `Number` whenever a local
+    // turns out to hold both. The widened type changes what reading the local pushes, so the
+    // method is lowered again from the start.
+    loop {
+        match lowering.lower_pass()? {
+            Pass::Done(function) => return Some(function),
+            Pass::Restart => {}
+        }
+    }
+}
+
+/// Finds the instructions that start a block: the first one, jump targets, and those that
+/// follow a jump or return.
+fn find_leaders(code: &[Op<'_>]) -> Option<Vec<usize>> {
+    let mut leaders = BTreeSet::from([0]);
+    for (ip, op) in code.iter().enumerate() {
+        if let Some(offset) = jump_offset(op) {
+            leaders.insert(jump_target(code, ip, offset)?);
+        } else if !op.is_block_terminating() {
+            continue;
+        }
+        if ip + 1 < code.len() {
+            leaders.insert(ip + 1);
+        }
+    }
+    Some(leaders.into_iter().collect())
+}
+
+fn jump_offset(op: &Op<'_>) -> Option<i32> {
+    match op {
+        Op::Jump { offset }
+        | Op::IfTrue { offset }
+        | Op::IfFalse { offset }
+        | Op::IfEq { offset }
+        | Op::IfNe { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfLt { offset }
+        | Op::IfLe { offset }
+        | Op::IfGt { offset }
+        | Op::IfGe { offset }
+        | Op::IfNlt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNge { offset } => Some(*offset),
+        _ => None,
+    }
+}
+
+/// The instruction that a jump at `ip` goes to. Offsets are relative to the next instruction.
+fn jump_target(code: &[Op<'_>], ip: usize, offset: i32) -> Option<usize> {
+    let target = (ip as i64) + 1 + i64::from(offset);
+    usize::try_from(target).ok().filter(|&t| t < code.len())
+}
+
+enum Pass {
+    Done(Function),
+    Restart,
+}
+
+/// The state of the operand stack and locals at the start of a block.
+#[derive(Clone)]
+struct EntryState {
+    stack: Vec<Type>,
+
+    /// Whether each local has been assigned on every path to the block. Reading any other
+    /// local is not supported, as it could still be `undefined`.
+    assigned: Vec<bool>,
+}
+
+struct Lowering<'a, 'gc, F> {
+    code: &'a [Op<'gc>],
+    leaders: Vec<usize>,
+    block_of: HashMap<usize, usize>,
+    params: &'a [Coercion],
+    returns: Returns,
+    coercion_of: F,
+
+    /// The type of each local, once it has been assigned.
+    storage: Vec<Option<Type>>,
+}
+
+impl<'gc, F: Fn(Class<'gc>) -> Option<Coercion>> Lowering<'_, 'gc, F> {
+    fn lower_pass(&mut self) -> Option<Pass> {
+        let num_blocks = self.leaders.len();
+        let mut vars = Vars {
+            types: self.params.iter().map(|param| param.ty()).collect(),
+            stack: HashMap::new(),
+            locals: vec![None; self.storage.len()],
+        };
+        for i in 0..self.params.len() {
+            vars.locals[i + 1] = Some(i as Var);
+        }
+
+        let mut assigned = vec![false; self.storage.len()];
+        assigned[1..=self.params.len()].fill(true);
+        let mut entries: Vec<Option<EntryState>> = vec![None; num_blocks];
+        entries[0] = Some(EntryState {
+            stack: vec![],
+            assigned,
+        });
+
+        let mut lowered: Vec<Option<(Vec<Instruction>, BytecodeExit)>> = vec![None; num_blocks];
+        let mut return_type = None;
+        let mut worklist = vec![0];
+        while let Some(block) = worklist.pop() {
+            let entry = entries[block].clone()?;
+            let mut builder = Builder {
+                vars: &mut vars,
+                stack: entry.stack,
+                assigned: entry.assigned,
+                instructions: vec![],
+            };
+            let exit = match self.lower_block(block, &mut builder)? {
+                Lowered::Exit(exit) => exit,
+                Lowered::Restart => return Some(Pass::Restart),
+            };
+
+            if let BytecodeExit::Return(value) = &exit {
+                let ty = value.map(|var| builder.vars.types[var as usize]);
+                if *return_type.get_or_insert(ty) != ty {
+                    return None;
+                }
+            }
+
+            for successor in exit.successors() {
+                let state = EntryState {
+                    stack: builder.stack.clone(),
+                    assigned: builder.assigned.clone(),
+                };
+                match &mut entries[successor] {
+                    None => {
+                        entries[successor] = Some(state);
+                        worklist.push(successor);
+                    }
+                    Some(existing) => {
+                        if existing.stack != state.stack {
+                            return None;
+                        }
+                        let mut changed = false;
+                        for (old, new) in existing.assigned.iter_mut().zip(&state.assigned) {
+                            if *old && !*new {
+                                *old = false;
+                                changed = true;
+                            }
+                        }
+                        if changed {
+                            worklist.push(successor);
+                        }
+                    }
+                }
+            }
+
+            lowered[block] = Some((builder.instructions, exit));
+        }
+
+        // Unreachable blocks are left out, and the rest keep their order so that loop
+        // back-edges still go backwards.
+        let ids: Vec<Option<BlockId>> = lowered
+            .iter()
+            .scan(0, |next, block| {
+                Some(block.as_ref().map(|_| {
+                    *next += 1;
+                    *next - 1
+                }))
+            })
+            .collect();
+        let id = |block: usize| ids[block];
+        let blocks = lowered
+            .into_iter()
+            .flatten()
+            .map(|(instructions, exit)| {
+                let exit = match exit {
+                    BytecodeExit::Jump(target) => Exit::Jump(id(target)?),
+                    BytecodeExit::Branch {
+                        cond,
+                        if_true,
+                        if_false,
+                    } => Exit::Branch {
+                        cond,
+                        if_true: id(if_true)?,
+                        if_false: id(if_false)?,
+                    },
+                    BytecodeExit::Return(value) => Exit::Return(value),
+                };
+                Some(Block { instructions, exit })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Pass::Done(Function {
+            vars: vars.types,
+            num_params: self.params.len(),
+            blocks,
+            return_type: return_type.flatten(),
+        }))
+    }
+
+    fn lower_block(&mut self, block: usize, b: &mut Builder<'_>) -> Option<Lowered> {
+        let start = self.leaders[block];
+        let end = self
+            .leaders
+            .get(block + 1)
+            .copied()
+            .unwrap_or(self.code.len());
+
+        for ip in start..end {
+            let op = &self.code[ip];
+            let next = ip + 1;
+            match op {
+                Op::Nop
+                | Op::CoerceA
+                | Op::Debug { .. }
+                | Op::DebugFile { .. }
+                | Op::DebugLine { .. }
+                | Op::Bkpt
+                | Op::BkptLine { .. }
+                | Op::Timestamp => {}
+
+                Op::PushByte { value } => b.push_const(Slot::Int((*value).into())),
+                Op::PushShort { value } => b.push_const(Slot::Int((*value).into())),
+                Op::PushInt { value } => b.push_const(Slot::Int(*value)),
+                Op::PushUint { value } => b.push_const(Slot::Number((*value).into())),
+                Op::PushDouble { value } => b.push_const(Slot::Number(*value)),
+                Op::PushNaN => b.push_const(Slot::Number(f64::NAN)),
+                Op::PushTrue => b.push_const(Slot::Boolean(true)),
+                Op::PushFalse => b.push_const(Slot::Boolean(false)),
+
+                Op::Pop => {
+                    b.pop()?;
+                }
+                Op::Dup => {
+                    let ty = *b.stack.last()?;
+                    let src = b.vars.stack_var(b.stack.len() - 1, ty);
+                    let dst = b.push(ty);
+                    b.emit(Instruction::Move { dst, src });
+                }
+                Op::Swap => {
+                    let (top, top_ty) = b.pop()?;
+                    let (below, below_ty) = b.pop()?;
+                    let temp = b.vars.new_var(below_ty);
+                    b.emit(Instruction::Move {
+                        dst: temp,
+                        src: below,
+                    });
+                    let dst = b.push(top_ty);
+                    b.emit(Instruction::Move { dst, src: top });
+                    let dst = b.push(below_ty);
+                    b.emit(Instruction::Move { dst, src: temp });
+                }
+
+                Op::GetLocal { index } => {
+                    let (local, ty) = self.read_local(b, *index)?;
+                    let dst = b.push(ty);
+                    b.emit(Instruction::Move { dst, src: local });
+                }
+                Op::SetLocal { index } => {
+                    let value = b.pop()?;
+                    if self.write_local(b, *index, value)? {
+                        return Some(Lowered::Restart);
+                    }
+                }
+                Op::Kill { index } => {
+                    *b.assigned.get_mut(self.register(*index)?)? = false;
+                }
+                Op::IncLocal { index } | Op::DecLocal { index } => {
+                    let (local, ty) = self.read_local(b, *index)?;
+                    let value = b.convert(local, ty, Type::Number);
+                    let one = b.constant(Slot::Number(1.0));
+                    let result = b.vars.new_var(Type::Number);
+                    let op = if matches!(op, Op::IncLocal { .. }) {
+                        BinaryOp::Add
+                    } else {
+                        BinaryOp::Subtract
+                    };
+                    b.emit(Instruction::Binary {
+                        op,
+                        dst: result,
+                        lhs: value,
+                        rhs: one,
+                    });
+                    if self.write_local(b, *index, (result, Type::Number))? {
+                        return Some(Lowered::Restart);
+                    }
+                }
+                Op::IncLocalI { index } | Op::DecLocalI { index } => {
+                    let (local, ty) = self.read_local(b, *index)?;
+                    let value = b.convert(local, ty, Type::Int);
+                    let one = b.constant(Slot::Int(1));
+                    let result = b.vars.new_var(Type::Int);
+                    let op = if matches!(op, Op::IncLocalI { .. }) {
+                        BinaryOp::AddI
+                    } else {
+                        BinaryOp::SubtractI
+                    };
+                    b.emit(Instruction::Binary {
+                        op,
+                        dst: result,
+                        lhs: value,
+                        rhs: one,
+                    });
+                    if self.write_local(b, *index, (result, Type::Int))? {
+                        return Some(Lowered::Restart);
+                    }
+                }
+
+                Op::CoerceB => b.coerce(Coercion::Boolean)?,
+                Op::CoerceD => b.coerce(Coercion::Number)?,
+                Op::CoerceI => b.coerce(Coercion::Int)?,
+                Op::CoerceU => b.coerce(Coercion::Uint)?,
+                Op::Coerce { class } => b.coerce((self.coercion_of)(*class)?)?,
+
+                Op::Add => b.binary(BinaryOp::Add, Type::Number, Type::Number)?,
+                Op::Subtract => b.binary(BinaryOp::Subtract, Type::Number, Type::Number)?,
+                Op::Multiply => b.binary(BinaryOp::Multiply, Type::Number, Type::Number)?,
+                Op::Divide => b.binary(BinaryOp::Divide, Type::Number, Type::Number)?,
+                Op::Modulo => b.binary(BinaryOp::Modulo, Type::Number, Type::Number)?,
+                Op::AddI => b.binary(BinaryOp::AddI, Type::Int, Type::Int)?,
+                Op::SubtractI => b.binary(BinaryOp::SubtractI, Type::Int, Type::Int)?,
+                Op::MultiplyI => b.binary(BinaryOp::MultiplyI, Type::Int, Type::Int)?,
+                Op::BitAnd => b.binary(BinaryOp::BitAnd, Type::Int, Type::Int)?,
+                Op::BitOr => b.binary(BinaryOp::BitOr, Type::Int, Type::Int)?,
+                Op::BitXor => b.binary(BinaryOp::BitXor, Type::Int, Type::Int)?,
+                Op::LShift => b.binary(BinaryOp::LShift, Type::Int, Type::Int)?,
+                Op::RShift => b.binary(BinaryOp::RShift, Type::Int, Type::Int)?,
+                Op::URShift => b.binary(BinaryOp::URShift, Type::Int, Type::Number)?,
+
+                Op::Increment | Op::Decrement => {
+                    b.push_const(Slot::Number(1.0));
+                    let op = if matches!(op, Op::Increment) {
+                        BinaryOp::Add
+                    } else {
+                        BinaryOp::Subtract
+                    };
+                    b.binary(op, Type::Number, Type::Number)?;
+                }
+                Op::IncrementI | Op::DecrementI => {
+                    b.push_const(Slot::Int(1));
+                    let op = if matches!(op, Op::IncrementI) {
+                        BinaryOp::AddI
+                    } else {
+                        BinaryOp::SubtractI
+                    };
+                    b.binary(op, Type::Int, Type::Int)?;
+                }
+                Op::Negate => b.unary(UnaryOp::Negate, Type::Number, Type::Number)?,
+                Op::NegateI => b.unary(UnaryOp::NegateI, Type::Int, Type::Int)?,
+                Op::BitNot => b.unary(UnaryOp::BitNot, Type::Int, Type::Int)?,
+                Op::Not => b.unary(UnaryOp::Not, Type::Boolean, Type::Boolean)?,
+
+                Op::Equals
+                | Op::StrictEquals
+                | Op::LessThan
+                | Op::LessEquals
+                | Op::GreaterThan
+                | Op::GreaterEquals => {
+                    let comparison = match op {
+                        Op::Equals => Comparison::Equal,
+                        Op::StrictEquals => Comparison::StrictEqual,
+                        Op::LessThan => Comparison::LessThan,
+                        Op::LessEquals => Comparison::LessEqual,
+                        Op::GreaterThan => Comparison::GreaterThan,
+                        _ => Comparison::GreaterEqual,
+                    };
+                    let result = b.compare(comparison)?;
+                    let dst = b.push(Type::Boolean);
+                    b.emit(Instruction::Move { dst, src: result });
+                }
+
+                Op::Jump { offset } => {
+                    let target = self.block(jump_target(self.code, ip, *offset)?)?;
+                    return Some(Lowered::Exit(BytecodeExit::Jump(target)));
+                }
+                Op::IfTrue { offset } | Op::IfFalse { offset } => {
+                    let (value, ty) = b.pop()?;
+                    let cond = b.convert(value, ty, Type::Boolean);
+                    let target = self.block(jump_target(self.code, ip, *offset)?)?;
+                    let next = self.block(next)?;
+                    let (if_true, if_false) = if matches!(op, Op::IfTrue { .. }) {
+                        (target, next)
+                    } else {
+                        (next, target)
+                    };
+                    return Some(Lowered::Exit(BytecodeExit::Branch {
+                        cond,
+                        if_true,
+                        if_false,
+                    }));
+                }
+                Op::IfEq { offset }
+                | Op::IfNe { offset }
+                | Op::IfStrictEq { offset }
+                | Op::IfStrictNe { offset }
+                | Op::IfLt { offset }
+                | Op::IfLe { offset }
+                | Op::IfGt { offset }
+                | Op::IfGe { offset }
+                | Op::IfNlt { offset }
+                | Op::IfNle { offset }
+                | Op::IfNgt { offset }
+                | Op::IfNge { offset } => {
+                    // The negated branches are taken when the comparison is false, which
+                    // includes comparisons with `NaN`.
+                    let (comparison, negated) = match op {
+                        Op::IfEq { .. } => (Comparison::Equal, false),
+                        Op::IfNe { .. } => (Comparison::Equal, true),
+                        Op::IfStrictEq { .. } => (Comparison::StrictEqual, false),
+                        Op::IfStrictNe { .. } => (Comparison::StrictEqual, true),
+                        Op::IfLt { .. } => (Comparison::LessThan, false),
+                        Op::IfLe { .. } => (Comparison::LessEqual, false),
+                        Op::IfGt { .. } => (Comparison::GreaterThan, false),
+                        Op::IfGe { .. } => (Comparison::GreaterEqual, false),
+                        Op::IfNlt { .. } => (Comparison::LessThan, true),
+                        Op::IfNle { .. } => (Comparison::LessEqual, true),
+                        Op::IfNgt { .. } => (Comparison::GreaterThan, true),
+                        _ => (Comparison::GreaterEqual, true),
+                    };
+                    let cond = b.compare(comparison)?;
+                    let target = self.block(jump_target(self.code, ip, *offset)?)?;
+                    let next = self.block(next)?;
+                    let (if_true, if_false) = if negated {
+                        (next, target)
+                    } else {
+                        (target, next)
+                    };
+                    return Some(Lowered::Exit(BytecodeExit::Branch {
+                        cond,
+                        if_true,
+                        if_false,
+                    }));
+                }
+
+                Op::ReturnValue => {
+                    let (value, ty) = b.pop()?;
+                    let value = match self.returns {
+                        Returns::Any => Some(value),
+                        Returns::Void => None,
+                        Returns::Coerce(coercion) => {
+                            b.stack.push(ty);
+                            b.coerce(coercion)?;
+                            Some(b.pop()?.0)
+                        }
+                    };
+                    return Some(Lowered::Exit(BytecodeExit::Return(value)));
+                }
+                Op::ReturnValueNoCoerce => {
+                    let (value, _) = b.pop()?;
+                    return Some(Lowered::Exit(BytecodeExit::Return(Some(value))));
+                }
+                Op::ReturnVoid => return Some(Lowered::Exit(BytecodeExit::Return(None))),
+
+                _ => return None,
+            }
+        }
+
+        // Falling through into the next block. The verifier doesn't let code fall off the end
+        // of a method.
+        Some(Lowered::Exit(BytecodeExit::Jump(self.block(end)?)))
+    }
+
+    fn block(&self, ip: usize) -> Option<usize> {
+        self.block_of.get(&ip).copied()
+    }
+
+    /// The register of a local, which can't be `this`.
+    fn register(&self, index: u32) -> Option<usize> {
+        let register = index as usize;
+        (register != 0 && register < self.storage.len()).then_some(register)
+    }
+
+    fn read_local(&self, b: &mut Builder<'_>, index: u32) -> Option<(Var, Type)> {
+        let register = self.register(index)?;
+        if !b.assigned[register] {
+            return None;
+        }
+        let ty = self.storage[register]?;
+        Some((b.vars.local_var(register, ty), ty))
+    }
+
+    /// Stores a value in a local, and returns whether the type of the local had to be widened.
+    fn write_local(
+        &mut self,
+        b: &mut Builder<'_>,
+        index: u32,
+        (value, ty): (Var, Type),
+    ) -> Option<bool> {
+        let register = self.register(index)?;
+        let is_param = register <= self.params.len();
+        let storage = match (self.storage[register], ty) {
+            (None, ty) => ty,
+            (Some(storage), ty) if storage == ty => storage,
+            (Some(Type::Number), Type::Int) => Type::Number,
+            (Some(Type::Int), Type::Number) if !is_param => {
+                self.storage[register] = Some(Type::Number);
+                return Some(true);
+            }
+            _ => return None,
+        };
+        self.storage[register] = Some(storage);
+
+        let local = b.vars.local_var(register, storage);
+        let value = b.convert(value, ty, storage);
+        b.emit(Instruction::Move {
+            dst: local,
+            src: value,
+        });
+        b.assigned[register] = true;
+        Some(false)
+    }
+}
+
+enum Lowered {
+    Exit(BytecodeExit),
+    Restart,
+}
+
+/// The exit of a block, with successors given as bytecode block indices.
+#[derive(Clone)]
+enum BytecodeExit {
+    Jump(usize),
+    Branch {
+        cond: Var,
+        if_true: usize,
+        if_false: usize,
+    },
+    Return(Option<Var>),
+}
+
+impl BytecodeExit {
+    fn successors(&self) -> Vec<usize> {
+        match self {
+            BytecodeExit::Jump(target) => vec![*target],
+            BytecodeExit::Branch {
+                if_true, if_false, ..
+            } => vec![*if_true, *if_false],
+            BytecodeExit::Return(_) => vec![],
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Comparison {
+    Equal,
+    StrictEqual,
+    LessThan,
+    LessEqual,
+    GreaterThan,
+    GreaterEqual,
+}
+
+/// The variables of a function being lowered.
+struct Vars {
+    types: Vec<Type>,
+
+    /// The variable holding each depth of the operand stack, by the type of the value.
+    stack: HashMap<(usize, Type), Var>,
+
+    locals: Vec<Option<Var>>,
+}
+
+impl Vars {
+    fn new_var(&mut self, ty: Type) -> Var {
+        self.types.push(ty);
+        (self.types.len() - 1) as Var
+    }
+
+    fn stack_var(&mut self, depth: usize, ty: Type) -> Var {
+        if let Some(var) = self.stack.get(&(depth, ty)) {
+            return *var;
+        }
+        let var = self.new_var(ty);
+        self.stack.insert((depth, ty), var);
+        var
+    }
+
+    fn local_var(&mut self, register: usize, ty: Type) -> Var {
+        if let Some(var) = self.locals[register] {
+            return var;
+        }
+        let var = self.new_var(ty);
+        self.locals[register] = Some(var);
+        var
+    }
+}
+
+/// Lowers the instructions of a block.
+struct Builder<'a> {
+    vars: &'a mut Vars,
+    stack: Vec<Type>,
+    assigned: Vec<bool>,
+    instructions: Vec<Instruction>,
+}
+
+impl Builder<'_> {
+    fn emit(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+    }
+
+    fn push(&mut self, ty: Type) -> Var {
+        let var = self.vars.stack_var(self.stack.len(), ty);
+        self.stack.push(ty);
+        var
+    }
+
+    fn pop(&mut self) -> Option<(Var, Type)> {
+        let ty = self.stack.pop()?;
+        Some((self.vars.stack_var(self.stack.len(), ty), ty))
+    }
+
+    fn constant(&mut self, value: Slot) -> Var {
+        let dst = self.vars.new_var(value.ty());
+        self.emit(Instruction::Const { dst, value });
+        dst
+    }
+
+    fn push_const(&mut self, value: Slot) {
+        let dst = self.push(value.ty());
+        self.emit(Instruction::Const { dst, value });
+    }
+
+    fn convert(&mut self, src: Var, from: Type, to: Type) -> Var {
+        if from == to {
+            return src;
+        }
+        let dst = self.vars.new_var(to);
+        self.emit(Instruction::Convert { dst, src });
+        dst
+    }
+
+    fn coerce(&mut self, coercion: Coercion) -> Option<()> {
+        let (value, ty) = self.pop()?;
+        let (src, ty) = if coercion == Coercion::Uint {
+            let value = self.convert(value, ty, Type::Int);
+            let uint = self.vars.new_var(Type::Number);
+            self.emit(Instruction::Unary {
+                op: UnaryOp::ToUint,
+                dst: uint,
+                src: value,
+            });
+            (uint, Type::Number)
+        } else {
+            (self.convert(value, ty, coercion.ty()), coercion.ty())
+        };
+        let dst = self.push(ty);
+        self.emit(Instruction::Move { dst, src });
+        Some(())
+    }
+
+    fn unary(&mut self, op: UnaryOp, operand: Type, result: Type) -> Option<()> {
+        let (value, ty) = self.pop()?;
+        let src = self.convert(value, ty, operand);
+        let dst = self.push(result);
+        self.emit(Instruction::Unary { op, dst, src });
+        Some(())
+    }
+
+    fn binary(&mut self, op: BinaryOp, operands: Type, result: Type) -> Option<()> {
+        let (rhs, rhs_ty) = self.pop()?;
+        let (lhs, lhs_ty) = self.pop()?;
+        let rhs = self.convert(rhs, rhs_ty, operands);
+        let lhs = self.convert(lhs, lhs_ty, operands);
+        let dst = self.push(result);
+        self.emit(Instruction::Binary { op, dst, lhs, rhs });
+        Some(())
+    }
+
+    /// Pops two values and compares them, returning a `Boolean` variable with the result.
+    fn compare(&mut self, comparison: Comparison) -> Option<Var> {
+        let (rhs, rhs_ty) = self.pop()?;
+        let (lhs, lhs_ty) = self.pop()?;
+
+        let (op, (lhs, lhs_ty), (rhs, rhs_ty)) = match comparison {
+            Comparison::Equal | Comparison::StrictEqual => {
+                (BinaryOp::Equal, (lhs, lhs_ty), (rhs, rhs_ty))
+            }
+            Comparison::LessThan => (BinaryOp::LessThan, (lhs, lhs_ty), (rhs, rhs_ty)),
+            Comparison::LessEqual => (BinaryOp::LessEqual, (lhs, lhs_ty), (rhs, rhs_ty)),
+            Comparison::GreaterThan => (BinaryOp::LessThan, (rhs, rhs_ty), (lhs, lhs_ty)),
+            Comparison::GreaterEqual => (BinaryOp::LessEqual, (rhs, rhs_ty), (lhs, lhs_ty)),
+        };
+
+        let lhs_bool = lhs_ty == Type::Boolean;
+        let rhs_bool = rhs_ty == Type::Boolean;
+        let operands = if lhs_bool && rhs_bool {
+            Type::Int
+        } else if lhs_bool != rhs_bool {
+            // `true === 1` is false, while `true == 1` compares numbers.
+            if matches!(comparison, Comparison::StrictEqual) {
+                return Some(self.constant(Slot::Boolean(false)));
+            }
+            Type::Number
+        } else if lhs_ty == Type::Int && rhs_ty == Type::Int {
+            Type::Int
+        } else {
+            Type::Number
+        };
+
+        let lhs = self.convert(lhs, lhs_ty, operands);
+        let rhs = self.convert(rhs, rhs_ty, operands);
+        let dst = self.vars.new_var(Type::Boolean);
+        self.emit(Instruction::Binary { op, dst, lhs, rhs });
+        Some(dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecma_conversions::f64_to_wrapping_i32;
+
+    /// Runs lowered code the way a backend would.
+    fn run(function: &Function, args: &[Slot]) -> Option<Slot> {
+        let mut vars: Vec<Slot> = function
+            .vars
+            .iter()
+            .map(|ty| match ty {
+                Type::Int => Slot::Int(0),
+                Type::Number => Slot::Number(0.0),
+                Type::Boolean => Slot::Boolean(false),
+            })
+            .collect();
+        vars[..args.len()].copy_from_slice(args);
+
+        let int = |slot: Slot| match slot {
+            Slot::Int(value) => value,
+            _ => panic!("Expected an int, got {slot:?}"),
+        };
+        let number = |slot: Slot| match slot {
+            Slot::Number(value) => value,
+            _ => panic!("Expected a Number, got {slot:?}"),
+        };
+
+        let mut block = 0;
+        loop {
+            let current = &function.blocks[block as usize];
+            for instruction in &current.instructions {
+                match *instruction {
+                    Instruction::Const { dst, value } => vars[dst as usize] = value,
+                    Instruction::Move { dst, src } => vars[dst as usize] = vars[src as usize],
+                    Instruction::Convert { dst, src } => {
+                        let value = match vars[src as usize] {
+                            Slot::Int(value) => f64::from(value),
+                            Slot::Number(value) => value,
+                            Slot::Boolean(value) => f64::from(u8::from(value)),
+                        };
+                        vars[dst as usize] = match function.vars[dst as usize] {
+                            Type::Int => Slot::Int(f64_to_wrapping_i32(value)),
+                            Type::Number => Slot::Number(value),
+                            Type::Boolean => Slot::Boolean(value != 0.0 && !value.is_nan()),
+                        };
+                    }
+                    Instruction::Unary { op, dst, src } => {
+                        let src = vars[src as usize];
+                        vars[dst as usize] = match op {
+                            UnaryOp::Negate => Slot::Number(-number(src)),
+                            UnaryOp::NegateI => Slot::Int(int(src).wrapping_neg()),
+                            UnaryOp::BitNot => Slot::Int(!int(src)),
+                            UnaryOp::Not => Slot::Boolean(src == Slot::Boolean(false)),
+                            UnaryOp::ToUint => Slot::Number((int(src) as u32).into()),
+                        };
+                    }
+                    Instruction::Binary { op, dst, lhs, rhs } => {
+                        let (lhs, rhs) = (vars[lhs as usize], vars[rhs as usize]);
+                        vars[dst as usize] = match op {
+                            BinaryOp::Add => Slot::Number(number(lhs) + number(rhs)),
+                            BinaryOp::Subtract => Slot::Number(number(lhs) - number(rhs)),
+                            BinaryOp::Multiply => Slot::Number(number(lhs) * number(rhs)),
+                            BinaryOp::Divide => Slot::Number(number(lhs) / number(rhs)),
+                            BinaryOp::Modulo => Slot::Number(number(lhs) % number(rhs)),
+                            BinaryOp::AddI => Slot::Int(int(lhs).wrapping_add(int(rhs))),
+                            BinaryOp::SubtractI => Slot::Int(int(lhs).wrapping_sub(int(rhs))),
+                            BinaryOp::MultiplyI => Slot::Int(int(lhs).wrapping_mul(int(rhs))),
+                            BinaryOp::BitAnd => Slot::Int(int(lhs) & int(rhs)),
+                            BinaryOp::BitOr => Slot::Int(int(lhs) | int(rhs)),
+                            BinaryOp::BitXor => Slot::Int(int(lhs) ^ int(rhs)),
+                            BinaryOp::LShift => Slot::Int(int(lhs) << (int(rhs) & 0x1F)),
+                            BinaryOp::RShift => Slot::Int(int(lhs) >> (int(rhs) & 0x1F)),
+                            BinaryOp::URShift => {
+                                Slot::Number(((int(lhs) as u32) >> (int(rhs) & 0x1F)).into())
+                            }
+                            BinaryOp::Equal => Slot::Boolean(match (lhs, rhs) {
+                                (Slot::Int(lhs), Slot::Int(rhs)) => lhs == rhs,
+                                _ => number(lhs) == number(rhs),
+                            }),
+                            BinaryOp::LessThan => Slot::Boolean(match (lhs, rhs) {
+                                (Slot::Int(lhs), Slot::Int(rhs)) => lhs < rhs,
+                                _ => number(lhs) < number(rhs),
+                            }),
+                            BinaryOp::LessEqual => Slot::Boolean(match (lhs, rhs) {
+                                (Slot::Int(lhs), Slot::Int(rhs)) => lhs <= rhs,
+                                _ => number(lhs) <= number(rhs),
+                            }),
+                        };
+                    }
+                }
+            }
+            block = match current.exit {
+                Exit::Jump(target) => target,
+                Exit::Branch {
+                    cond,
+                    if_true,
+                    if_false,
+                } => {
+                    if vars[cond as usize] == Slot::Boolean(true) {
+                        if_true
+                    } else {
+                        if_false
+                    }
+                }
+                Exit::Return(value) => return value.map(|var| vars[var as usize]),
+            };
+        }
+    }
+
+    fn lower_ops(
+        code: &[Op<'static>],
+        num_locals: u32,
+        params: &[Coercion],
+        returns: Returns,
+    ) -> Option<Function> {
+        lower(code, num_locals, params, returns, |_| None)
+    }
+
+    /// `function sum(n:int):Number { var total:Number = 0; for (var i:int = 0; i < n; i++) {
+    /// total += i; } return total; }`
+    fn sum_loop() -> Vec<Op<'static>> {
+        vec![
+            Op::PushByte { value: 0 },
+            Op::CoerceD,
+            Op::SetLocal { index: 2 },
+            Op::PushByte { value: 0 },
+            Op::SetLocal { index: 3 },
+            Op::Jump { offset: 6 },
+            // Loop body
+            Op::GetLocal { index: 2 },
+            Op::GetLocal { index: 3 },
+            Op::Add,
+            Op::CoerceD,
+            Op::SetLocal { index: 2 },
+            Op::IncLocalI { index: 3 },
+            // Loop condition
+            Op::GetLocal { index: 3 },
+            Op::GetLocal { index: 1 },
+            Op::IfLt { offset: -9 },
+            Op::GetLocal { index: 2 },
+            Op::ReturnValue,
+        ]
+    }
+
+    #[test]
+    fn lowers_loops() {
+        let function = lower_ops(
+            &sum_loop(),
+            4,
+            &[Coercion::Int],
+            Returns::Coerce(Coercion::Number),
+        )
+        .expect("The loop should be supported");
+
+        assert_eq!(function.params(), [Type::Int]);
+        assert_eq!(function.return_type, Some(Type::Number));
+        assert_eq!(run(&function, &[Slot::Int(0)]), Some(Slot::Number(0.0)));
+        assert_eq!(
+            run(&function, &[Slot::Int(100)]),
+            Some(Slot::Number(4950.0))
+        );
+
+        // The condition jumps back to the body, which is the only back-edge.
+        let back_edges = function
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(id, block)| match block.exit {
+                Exit::Jump(target) => target as usize <= *id,
+                Exit::Branch {
+                    if_true, if_false, ..
+                } => if_true as usize <= *id || if_false as usize <= *id,
+                Exit::Return(_) => false,
+            })
+            .count();
+        assert_eq!(back_edges, 1);
+    }
+
+    #[test]
+    fn widens_locals_to_number() {
+        // `var x = 1; x = x / 2; return x;`, which stores an `int` and then a `Number`.
+        let code = [
+            Op::PushByte { value: 1 },
+            Op::SetLocal { index: 1 },
+            Op::GetLocal { index: 1 },
+            Op::PushByte { value: 2 },
+            Op::Divide,
+            Op::SetLocal { index: 1 },
+            Op::GetLocal { index: 1 },
+            Op::ReturnValue,
+        ];
+        let function = lower_ops(&code, 2, &[], Returns::Any).unwrap();
+        assert_eq!(run(&function, &[]), Some(Slot::Number(0.5)));
+    }
+
+    #[test]
+    fn follows_flash_coercions() {
+        // `return (a + b) | 0;` with `a:Number` and `b:Boolean`, as `uint`.
+        let code = [
+            Op::GetLocal { index: 1 },
+            Op::GetLocal { index: 2 },
+            Op::Add,
+            Op::PushByte { value: 0 },
+            Op::BitOr,
+            Op::ReturnValue,
+        ];
+        let function = lower_ops(
+            &code,
+            3,
+            &[Coercion::Number, Coercion::Boolean],
+            Returns::Coerce(Coercion::Uint),
+        )
+        .unwrap();
+        let call = |a: f64, b: bool| run(&function, &[Slot::Number(a), Slot::Boolean(b)]);
+        assert_eq!(call(1.5, true), Some(Slot::Number(2.0)));
+        assert_eq!(call(-1.0, false), Some(Slot::Number(4294967295.0)));
+        assert_eq!(call(f64::NAN, true), Some(Slot::Number(0.0)));
+        assert_eq!(call(4294967298.0, false), Some(Slot::Number(2.0)));
+    }
+
+    #[test]
+    fn negated_branches_are_taken_for_nan() {
+        // `if (!(a < b)) return 1; return 2;`
+        let code = [
+            Op::GetLocal { index: 1 },
+            Op::GetLocal { index: 2 },
+            Op::IfNlt { offset: 2 },
+            Op::PushByte { value: 2 },
+            Op::ReturnValue,
+            Op::PushByte { value: 1 },
+            Op::ReturnValue,
+        ];
+        let function = lower_ops(
+            &code,
+            3,
+            &[Coercion::Number, Coercion::Number],
+            Returns::Coerce(Coercion::Int),
+        )
+        .unwrap();
+        let call = |a: f64, b: f64| run(&function, &[Slot::Number(a), Slot::Number(b)]);
+        assert_eq!(call(1.0, 2.0), Some(Slot::Int(2)));
+        assert_eq!(call(2.0, 1.0), Some(Slot::Int(1)));
+        assert_eq!(call(f64::NAN, 1.0), Some(Slot::Int(1)));
+    }
+
+    #[test]
+    fn keeps_values_on_the_stack_across_blocks() {
+        // `return c ? 10 : 20;`
+        let code = [
+            Op::GetLocal { index: 1 },
+            Op::IfFalse { offset: 2 },
+            Op::PushByte { value: 10 },
+            Op::Jump { offset: 1 },
+            Op::PushByte { value: 20 },
+            Op::ReturnValue,
+        ];
+        let function = lower_ops(
+            &code,
+            2,
+            &[Coercion::Boolean],
+            Returns::Coerce(Coercion::Int),
+        )
+        .unwrap();
+        assert_eq!(run(&function, &[Slot::Boolean(true)]), Some(Slot::Int(10)));
+        assert_eq!(run(&function, &[Slot::Boolean(false)]), Some(Slot::Int(20)));
+    }
+
+    #[test]
+    fn rejects_unsupported_methods() {
+        // Reading `this`.
+        let code = [Op::GetLocal { index: 0 }, Op::ReturnValue];
+        assert_eq!(lower_ops(&code, 1, &[], Returns::Any), None);
+
+        // Reading a local that may still be `undefined`.
+        let code = [
+            Op::GetLocal { index: 1 },
+            Op::IfFalse { offset: 2 },
+            Op::PushByte { value: 1 },
+            Op::SetLocal { index: 2 },
+            Op::GetLocal { index: 2 },
+            Op::ReturnValue,
+        ];
+        assert_eq!(
+            lower_ops(&code, 3, &[Coercion::Boolean], Returns::Any),
+            None
+        );
+
+        // Reading a local after it was killed.
+        let code = [
+            Op::PushByte { value: 1 },
+            Op::SetLocal { index: 1 },
+            Op::Kill { index: 1 },
+            Op::GetLocal { index: 1 },
+            Op::ReturnValue,
+        ];
+        assert_eq!(lower_ops(&code, 2, &[], Returns::Any), None);
+
+        // Storing a `Boolean` in an `int` local.
+        let code = [Op::PushTrue, Op::SetLocal { index: 1 }, Op::ReturnVoid];
+        assert_eq!(lower_ops(&code, 2, &[Coercion::Int], Returns::Void), None);
+
+        // Anything that isn't arithmetic.
+        let code = [Op::PushUndefined, Op::ReturnValue];
+        assert_eq!(lower_ops(&code, 1, &[], Returns::Any), None);
+    }
+}
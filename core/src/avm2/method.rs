@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::jit::JitState;
 use crate::avm2::object::{ClassObject, Object};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
@@ -151,6 +152,10 @@ pub struct BytecodeMethod<'gc> {
     /// A free-standing function corresponds to the `Function` trait type, and
     /// is instantiated with the `newfunction` opcode.
     pub is_function: bool,
+
+    /// Whether this method has been compiled, or how often it has been called so far.
+    #[collect(require_static)]
+    pub jit: JitState,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -189,6 +194,7 @@ impl<'gc> BytecodeMethod<'gc> {
             return_type,
             is_function,
             activation_class: Lock::new(None),
+            jit: JitState::default(),
         })
     }
 
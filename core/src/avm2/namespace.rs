@@ -348,6 +348,7 @@ pub struct CommonNamespaces<'gc> {
     pub(super) flash_utils_internal: Namespace<'gc>,
     pub(super) flash_geom_internal: Namespace<'gc>,
     pub(super) flash_events_internal: Namespace<'gc>,
+    pub(super) flash_text_internal: Namespace<'gc>,
     pub(super) flash_text_engine_internal: Namespace<'gc>,
     pub(super) flash_net_internal: Namespace<'gc>,
 
@@ -379,6 +380,7 @@ impl<'gc> CommonNamespaces<'gc> {
             flash_utils_internal: Namespace::internal("flash.utils", context),
             flash_geom_internal: Namespace::internal("flash.geom", context),
             flash_events_internal: Namespace::internal("flash.events", context),
+            flash_text_internal: Namespace::internal("flash.text", context),
             flash_text_engine_internal: Namespace::internal("flash.text.engine", context),
             flash_net_internal: Namespace::internal("flash.net", context),
 
@@ -61,6 +61,7 @@ mod sound_object;
 mod soundchannel_object;
 mod stage3d_object;
 mod stage_object;
+mod style_sheet_object;
 mod textformat_object;
 mod texture_object;
 mod vector_object;
@@ -140,6 +141,9 @@ pub use crate::avm2::object::stage3d_object::{
     stage_3d_allocator, Stage3DObject, Stage3DObjectWeak,
 };
 pub use crate::avm2::object::stage_object::{StageObject, StageObjectWeak};
+pub use crate::avm2::object::style_sheet_object::{
+    style_sheet_allocator, StyleSheetObject, StyleSheetObjectWeak,
+};
 pub use crate::avm2::object::textformat_object::{
     textformat_allocator, TextFormatObject, TextFormatObjectWeak,
 };
@@ -201,6 +205,7 @@ use crate::font::Font;
         FontObject(FontObject<'gc>),
         LocalConnectionObject(LocalConnectionObject<'gc>),
         SharedObjectObject(SharedObjectObject<'gc>),
+        StyleSheetObject(StyleSheetObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -236,14 +241,27 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This corresponds directly to the AVM2 operation `getproperty`, with the
     /// exception that it does not special-case object lookups on dictionary
     /// structured objects.
-    #[allow(unused_mut)] //Not unused.
     #[no_dynamic]
     fn get_property(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().get_trait(multiname);
+        self.get_property_with_trait(multiname, property, activation)
+    }
+
+    /// Same as get_property, but with the trait that `multiname` refers to on the vtable
+    /// of this object already looked up, such as by an inline cache.
+    #[allow(unused_mut)] //Not unused.
+    #[no_dynamic]
+    fn get_property_with_trait(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().get_trait(multiname) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 Ok(self.base().get_slot(slot_id))
             }
@@ -344,7 +362,21 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        match self.vtable().get_trait(multiname) {
+        let property = self.vtable().get_trait(multiname);
+        self.set_property_with_trait(multiname, property, value, activation)
+    }
+
+    /// Same as set_property, but with the trait that `multiname` refers to on the vtable
+    /// of this object already looked up, such as by an inline cache.
+    #[no_dynamic]
+    fn set_property_with_trait(
+        &self,
+        multiname: &Multiname<'gc>,
+        property: Option<Property>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        match property {
             Some(Property::Slot { slot_id }) => {
                 let value = self
                     .vtable()
@@ -483,15 +515,29 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This method should not be overridden.
     ///
     /// This corresponds directly to the `callproperty` operation in AVM2.
-    #[allow(unused_mut)]
     #[no_dynamic]
     fn call_property(
+        self,
+        multiname: &Multiname<'gc>,
+        arguments: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().get_trait(multiname);
+        self.call_property_with_trait(multiname, property, arguments, activation)
+    }
+
+    /// Same as call_property, but with the trait that `multiname` refers to on the vtable
+    /// of this object already looked up, such as by an inline cache.
+    #[allow(unused_mut)]
+    #[no_dynamic]
+    fn call_property_with_trait(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().get_trait(multiname) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 let obj = self.base().get_slot(slot_id);
 
@@ -1304,6 +1350,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    fn as_style_sheet(&self) -> Option<StyleSheetObject<'gc>> {
+        None
+    }
+
     fn as_local_connection_object(&self) -> Option<LocalConnectionObject<'gc>> {
         None
     }
@@ -1367,6 +1417,7 @@ impl<'gc> Object<'gc> {
             Self::FontObject(o) => WeakObject::FontObject(FontObjectWeak(Gc::downgrade(o.0))),
             Self::LocalConnectionObject(o) => WeakObject::LocalConnectionObject(LocalConnectionObjectWeak(Gc::downgrade(o.0))),
             Self::SharedObjectObject(o) => WeakObject::SharedObjectObject(SharedObjectObjectWeak(Gc::downgrade(o.0))),
+            Self::StyleSheetObject(o) => WeakObject::StyleSheetObject(StyleSheetObjectWeak(Gc::downgrade(o.0))),
         }
     }
 }
@@ -1429,6 +1480,7 @@ pub enum WeakObject<'gc> {
     FontObject(FontObjectWeak<'gc>),
     LocalConnectionObject(LocalConnectionObjectWeak<'gc>),
     SharedObjectObject(SharedObjectObjectWeak<'gc>),
+    StyleSheetObject(StyleSheetObjectWeak<'gc>),
 }
 
 impl<'gc> WeakObject<'gc> {
@@ -1474,6 +1526,7 @@ impl<'gc> WeakObject<'gc> {
             Self::FontObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
             Self::LocalConnectionObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
             Self::SharedObjectObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
+            Self::StyleSheetObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
         }
     }
 
@@ -1519,6 +1572,7 @@ impl<'gc> WeakObject<'gc> {
             Self::FontObject(o) => FontObject(o.0.upgrade(mc)?).into(),
             Self::LocalConnectionObject(o) => LocalConnectionObject(o.0.upgrade(mc)?).into(),
             Self::SharedObjectObject(o) => SharedObjectObject(o.0.upgrade(mc)?).into(),
+            Self::StyleSheetObject(o) => StyleSheetObject(o.0.upgrade(mc)?).into(),
         })
     }
 }
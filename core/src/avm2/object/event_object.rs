@@ -10,11 +10,14 @@ use crate::context::UpdateContext;
 use crate::display_object::TDisplayObject;
 use crate::display_object::{DisplayObject, InteractiveObject, TInteractiveObject};
 use crate::events::{KeyCode, MouseButton};
+use crate::focus_tracker::FocusDirection;
+use crate::multitouch::{Gesture, GestureKind};
 use crate::string::AvmString;
 use gc_arena::barrier::unlock;
 use gc_arena::{lock::RefLock, Collect, Gc, GcWeak, Mutation};
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
+use swf::{Point, Twips};
 
 /// A class instance allocator that allocates Event objects.
 pub fn event_allocator<'gc>(
@@ -115,7 +118,7 @@ impl<'gc> EventObject<'gc> {
         let event_type: AvmString<'gc> = event_type.into();
 
         let mouse_event_cls = activation.avm2().classes().mouseevent;
-        mouse_event_cls
+        let event = mouse_event_cls
             .construct(
                 activation,
                 &[
@@ -148,7 +151,12 @@ impl<'gc> EventObject<'gc> {
                     delta.into(),
                 ],
             )
-            .unwrap() // we don't expect to break here
+            .unwrap(); // we don't expect to break here
+
+        let (movement_x, movement_y) = activation.context.mouse_data.movement;
+        let _ = event.set_public_property("movementX", movement_x.into(), activation);
+        let _ = event.set_public_property("movementY", movement_y.into(), activation);
+        event
     }
 
     pub fn mouse_event_down(
@@ -214,6 +222,149 @@ impl<'gc> EventObject<'gc> {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn touch_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        target: DisplayObject<'gc>,
+        related_object: Option<InteractiveObject<'gc>>,
+        touch_point_id: i32,
+        is_primary: bool,
+        position: Point<Twips>,
+        bubbles: bool,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let local = target.global_to_local(position).unwrap_or(position);
+
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let touch_event_cls = activation.avm2().classes().touchevent;
+        touch_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    bubbles.into(),
+                    // cancelable
+                    false.into(),
+                    // touchPointID
+                    touch_point_id.into(),
+                    // isPrimaryTouchPoint
+                    is_primary.into(),
+                    // localX
+                    local.x.to_pixels().into(),
+                    // localY
+                    local.y.to_pixels().into(),
+                    // sizeX
+                    f64::NAN.into(),
+                    // sizeY
+                    f64::NAN.into(),
+                    // pressure
+                    f64::NAN.into(),
+                    // relatedObject
+                    related_object
+                        .map(|o| o.as_displayobject().object2())
+                        .unwrap_or(Value::Null),
+                    // ctrlKey
+                    activation
+                        .context
+                        .input
+                        .is_key_down(KeyCode::CONTROL)
+                        .into(),
+                    // altKey
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    // shiftKey
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    pub fn gesture_event(
+        activation: &mut Activation<'_, 'gc>,
+        target: DisplayObject<'gc>,
+        gesture: &Gesture,
+    ) -> Object<'gc> {
+        let local = target
+            .global_to_local(gesture.position)
+            .unwrap_or(gesture.position);
+        let ctrl_key = activation.context.input.is_key_down(KeyCode::CONTROL);
+        let alt_key = activation.context.input.is_key_down(KeyCode::ALT);
+        let shift_key = activation.context.input.is_key_down(KeyCode::SHIFT);
+
+        let event_type: AvmString<'gc> = gesture.kind.event_type().into();
+        let phase: AvmString<'gc> = gesture.phase.name().into();
+
+        if gesture.kind == GestureKind::TwoFingerTap {
+            let gesture_event_cls = activation.avm2().classes().gestureevent;
+            return gesture_event_cls
+                .construct(
+                    activation,
+                    &[
+                        event_type.into(),
+                        // bubbles
+                        true.into(),
+                        // cancelable
+                        false.into(),
+                        phase.into(),
+                        // localX
+                        local.x.to_pixels().into(),
+                        // localY
+                        local.y.to_pixels().into(),
+                        // ctrlKey
+                        ctrl_key.into(),
+                        // altKey
+                        alt_key.into(),
+                        // shiftKey
+                        shift_key.into(),
+                        // controlKey
+                        ctrl_key.into(),
+                    ],
+                )
+                .unwrap(); // we don't expect to break here
+        }
+
+        let transform_gesture_event_cls = activation.avm2().classes().transformgestureevent;
+        transform_gesture_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    false.into(),
+                    phase.into(),
+                    // localX
+                    local.x.to_pixels().into(),
+                    // localY
+                    local.y.to_pixels().into(),
+                    // scaleX
+                    gesture.scale.into(),
+                    // scaleY
+                    gesture.scale.into(),
+                    // rotation
+                    gesture.rotation.into(),
+                    // offsetX
+                    gesture.offset.0.into(),
+                    // offsetY
+                    gesture.offset.1.into(),
+                    // ctrlKey
+                    ctrl_key.into(),
+                    // altKey
+                    alt_key.into(),
+                    // shiftKey
+                    shift_key.into(),
+                    // controlKey
+                    ctrl_key.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
     pub fn text_event<S>(
         activation: &mut Activation<'_, 'gc>,
         event_type: S,
@@ -319,6 +470,7 @@ impl<'gc> EventObject<'gc> {
         cancelable: bool,
         related_object: Option<InteractiveObject<'gc>>,
         key_code: u32,
+        direction: FocusDirection,
     ) -> Object<'gc>
     where
         S: Into<AvmString<'gc>>,
@@ -339,7 +491,7 @@ impl<'gc> EventObject<'gc> {
                         .unwrap_or(Value::Null),
                     shift_key.into(),
                     key_code.into(),
-                    "none".into(), // TODO implement direction
+                    direction.name().into(),
                 ],
             )
             .unwrap()
@@ -26,6 +26,7 @@ pub fn local_connection_allocator<'gc>(
             base,
             connection_handle: RefCell::new(None),
             client: Lock::new(None),
+            allowed_domains: RefCell::new(Vec::new()),
         },
     ));
 
@@ -60,6 +61,9 @@ pub struct LocalConnectionObjectData<'gc> {
     connection_handle: RefCell<Option<LocalConnectionHandle>>,
 
     client: Lock<Option<Object<'gc>>>,
+
+    /// Domains that were allowed to call methods on this connection, through `allowDomain`.
+    allowed_domains: RefCell<Vec<String>>,
 }
 
 const _: () = assert!(std::mem::offset_of!(LocalConnectionObjectData, base) == 0);
@@ -80,13 +84,27 @@ impl<'gc> LocalConnectionObject<'gc> {
         unlock!(Gc::write(mc, self.0), LocalConnectionObjectData, client).set(Some(client));
     }
 
+    pub fn allow_domain(&self, domain: String) {
+        self.0.allowed_domains.borrow_mut().push(domain);
+    }
+
+    /// Checks whether a movie from `domain` may call methods on this connection.
+    pub fn allows_domain(&self, domain: &str) -> bool {
+        self.0
+            .allowed_domains
+            .borrow()
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(domain))
+    }
+
     pub fn connect(&self, activation: &mut Activation<'_, 'gc>, name: AvmString<'gc>) -> bool {
         if self.is_connected() {
             return false;
         }
 
+        let movie = activation.caller_movie_or_root();
         let connection_handle = activation.context.local_connections.connect(
-            &LocalConnections::get_domain(activation.context.swf.url()),
+            &LocalConnections::get_domain(movie.url()),
             (activation.domain(), *self),
             &name,
         );
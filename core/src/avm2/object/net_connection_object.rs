@@ -1,14 +1,19 @@
 //! Object representation for NetConnection
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ObjectEncoding;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
-use crate::avm2::Error;
+use crate::avm2::{Avm2, Error, Value};
+use crate::context::UpdateContext;
 use crate::net_connection::NetConnectionHandle;
+use crate::string::AvmString;
+use flash_lso::types::Value as AMFValue;
 use gc_arena::{Collect, Gc, GcWeak};
 use std::cell::Cell;
 use std::fmt;
 use std::fmt::Debug;
+use std::rc::Rc;
 
 pub fn net_connection_allocator<'gc>(
     class: ClassObject<'gc>,
@@ -20,6 +25,7 @@ pub fn net_connection_allocator<'gc>(
         NetConnectionObjectData {
             base,
             handle: Cell::new(None),
+            object_encoding: Cell::new(ObjectEncoding::Amf3),
         },
     ))
     .into();
@@ -42,6 +48,9 @@ pub struct NetConnectionObjectData<'gc> {
     base: ScriptObjectData<'gc>,
 
     handle: Cell<Option<NetConnectionHandle>>,
+
+    /// The AMF encoding used for arguments passed to `call`.
+    object_encoding: Cell<ObjectEncoding>,
 }
 
 const _: () = assert!(std::mem::offset_of!(NetConnectionObjectData, base) == 0);
@@ -67,7 +76,44 @@ impl<'gc> TObject<'gc> for NetConnectionObject<'gc> {
     }
 }
 
-impl NetConnectionObject<'_> {
+impl<'gc> NetConnectionObject<'gc> {
+    /// Calls a method that the server invoked on this connection.
+    ///
+    /// These are looked up on the `client` object, except for `onStatus`, which becomes a
+    /// `netStatus` event.
+    pub fn call_remote_method(
+        &self,
+        context: &mut UpdateContext<'gc>,
+        name: &str,
+        arguments: &[Rc<AMFValue>],
+    ) -> Result<(), Error<'gc>> {
+        let mut activation = Activation::from_nothing(context);
+        let mut values = Vec::with_capacity(arguments.len());
+        for argument in arguments {
+            values.push(crate::avm2::amf::deserialize_value(
+                &mut activation,
+                argument,
+            )?);
+        }
+
+        if name == "onStatus" {
+            let info = values.first().copied().unwrap_or(Value::Null);
+            let event = activation.avm2().classes().netstatusevent.construct(
+                &mut activation,
+                &["netStatus".into(), false.into(), false.into(), info],
+            )?;
+            Avm2::dispatch_event(activation.context, event, (*self).into());
+        } else {
+            let client = self
+                .get_public_property("client", &mut activation)?
+                .coerce_to_object_or_typeerror(&mut activation, None)?;
+            let name = AvmString::new_utf8(activation.gc(), name);
+            client.call_public_property(name, &values, &mut activation)?;
+        }
+
+        Ok(())
+    }
+
     pub fn handle(&self) -> Option<NetConnectionHandle> {
         self.0.handle.get()
     }
@@ -75,6 +121,14 @@ impl NetConnectionObject<'_> {
     pub fn set_handle(&self, handle: Option<NetConnectionHandle>) -> Option<NetConnectionHandle> {
         self.0.handle.replace(handle)
     }
+
+    pub fn object_encoding(&self) -> ObjectEncoding {
+        self.0.object_encoding.get()
+    }
+
+    pub fn set_object_encoding(&self, object_encoding: ObjectEncoding) {
+        self.0.object_encoding.set(object_encoding)
+    }
 }
 
 impl Debug for NetConnectionObject<'_> {
@@ -1,12 +1,14 @@
 //! Object representation for SharedObjects
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ObjectEncoding;
 use crate::avm2::error::argument_error;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::Error;
 use gc_arena::barrier::unlock;
 use gc_arena::{lock::Lock, Collect, Gc, GcWeak};
+use std::cell::Cell;
 use std::fmt::Debug;
 
 /// SharedObjects cannot be constructed by AS.
@@ -43,6 +45,9 @@ pub struct SharedObjectObjectData<'gc> {
 
     /// The name of this SharedObject.
     name: String,
+
+    /// The AMF encoding used when writing the data to disk.
+    object_encoding: Cell<ObjectEncoding>,
 }
 
 const _: () = assert!(std::mem::offset_of!(SharedObjectObjectData, base) == 0);
@@ -65,6 +70,7 @@ impl<'gc> SharedObjectObject<'gc> {
                 base,
                 data: Lock::new(data),
                 name,
+                object_encoding: Cell::new(ObjectEncoding::Amf3),
             },
         ))
     }
@@ -93,6 +99,14 @@ impl<'gc> SharedObjectObject<'gc> {
     pub fn name(&self) -> &String {
         &self.0.name
     }
+
+    pub fn object_encoding(&self) -> ObjectEncoding {
+        self.0.object_encoding.get()
+    }
+
+    pub fn set_object_encoding(&self, object_encoding: ObjectEncoding) {
+        self.0.object_encoding.set(object_encoding)
+    }
 }
 
 impl<'gc> TObject<'gc> for SharedObjectObject<'gc> {
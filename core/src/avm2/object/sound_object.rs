@@ -1,6 +1,7 @@
 //! Object representation for sounds
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::Endian;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::Avm2;
@@ -17,6 +18,7 @@ use gc_arena::{
     Collect, Gc, GcWeak, Mutation,
 };
 use id3::{Tag, TagLike};
+use std::cell::Cell;
 use std::io::Cursor;
 use swf::SoundInfo;
 
@@ -37,6 +39,7 @@ pub fn sound_allocator<'gc>(
                 queued_plays: Vec::new(),
             }),
             id3: Lock::new(None),
+            extract_position: Cell::new(0),
         },
     ))
     .into())
@@ -70,6 +73,9 @@ pub struct SoundObjectData<'gc> {
 
     /// ID3Info Object
     id3: Lock<Option<Object<'gc>>>,
+
+    /// The sample frame that `Sound.extract` continues from when no start position is given.
+    extract_position: Cell<u32>,
 }
 
 const _: () = assert!(std::mem::offset_of!(SoundObjectData, base) == 0);
@@ -100,6 +106,12 @@ pub struct QueuedPlay<'gc> {
 }
 
 impl<'gc> SoundObject<'gc> {
+    /// The most sample frames that a single `sampleData` event may provide.
+    pub const SAMPLE_DATA_MAX_FRAMES: usize = 8192;
+
+    /// Providing fewer sample frames than this in a `sampleData` event ends the sound.
+    pub const SAMPLE_DATA_MIN_FRAMES: usize = 2048;
+
     pub fn sound_handle(self) -> Option<SoundHandle> {
         let sound_data = self.0.sound_data.borrow();
         match &*sound_data {
@@ -108,6 +120,14 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    pub fn extract_position(self) -> u32 {
+        self.0.extract_position.get()
+    }
+
+    pub fn set_extract_position(self, position: u32) {
+        self.0.extract_position.set(position);
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
     pub fn play(
         self,
@@ -162,6 +182,55 @@ impl<'gc> SoundObject<'gc> {
         Ok(())
     }
 
+    /// Dispatches a `sampleData` event to ask ActionScript for more generated audio.
+    ///
+    /// `position` is the number of sample frames generated so far. Returns the 44.1KHz
+    /// stereo sample frames that the listeners wrote into the event's `data`.
+    pub fn request_sample_data(
+        self,
+        activation: &mut Activation<'_, 'gc>,
+        position: u32,
+    ) -> Result<Vec<[f32; 2]>, Error<'gc>> {
+        let data = activation
+            .avm2()
+            .classes()
+            .bytearray
+            .construct(activation, &[])?;
+        let event = activation.avm2().classes().sampledataevent.construct(
+            activation,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                position.into(),
+                data.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(activation.context, event, self.into());
+
+        let data = data
+            .as_bytearray()
+            .expect("SampleDataEvent data should be a ByteArray");
+        let endian = data.endian();
+        let frames = data
+            .bytes()
+            .chunks_exact(8)
+            .take(Self::SAMPLE_DATA_MAX_FRAMES)
+            .map(|frame| {
+                let (left, right) = frame.split_at(4);
+                let read_f32 = |bytes: &[u8]| {
+                    let bytes = bytes.try_into().expect("Chunk is 4 bytes long");
+                    match endian {
+                        Endian::Big => f32::from_be_bytes(bytes),
+                        Endian::Little => f32::from_le_bytes(bytes),
+                    }
+                };
+                [read_f32(left), read_f32(right)]
+            })
+            .collect();
+        Ok(frames)
+    }
+
     pub fn id3(self) -> Option<Object<'gc>> {
         self.0.id3.get()
     }
@@ -1,6 +1,8 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::api_version::ApiVersion;
-use crate::avm2::e4x::{string_to_multiname, E4XNamespace, E4XNode, E4XNodeKind};
+use crate::avm2::e4x::{
+    is_pretty_printing, string_to_multiname, E4XNamespace, E4XNode, E4XNodeKind,
+};
 use crate::avm2::error::make_error_1089;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{Object, ObjectPtr, TObject};
@@ -16,6 +18,7 @@ use ruffle_wstr::WString;
 use std::cell::{Cell, Ref, RefMut};
 use std::fmt::{self, Debug};
 
+use super::xml_object::handle_input_multiname;
 use super::{ClassObject, XmlObject};
 
 /// A class instance allocator that allocates XMLList objects.
@@ -146,10 +149,11 @@ impl<'gc> XmlListObject<'gc> {
     }
 
     pub fn as_xml_string(&self, activation: &mut Activation<'_, 'gc>) -> AvmString<'gc> {
+        let pretty_printing = is_pretty_printing(activation);
         let children = self.children();
         let mut out = WString::new();
         for (i, child) in children.iter().enumerate() {
-            if i != 0 {
+            if i != 0 && pretty_printing {
                 out.push_char('\n');
             }
             out.push_str(child.node().xml_to_xml_string(activation).as_wstr())
@@ -496,9 +500,10 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
         multiname: &Multiname<'gc>,
     ) -> Option<XmlListObject<'gc>> {
+        let multiname = handle_input_multiname(multiname.clone(), activation);
         let mut descendants = Vec::new();
         for child in self.0.children.borrow().iter() {
-            child.node().descendants(multiname, &mut descendants);
+            child.node().descendants(&multiname, &mut descendants);
         }
 
         // NOTE: The way avmplus implemented this means we do not need to set target_dirty flag.
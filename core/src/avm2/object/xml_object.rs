@@ -311,8 +311,9 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
         multiname: &Multiname<'gc>,
     ) -> Option<XmlListObject<'gc>> {
+        let multiname = handle_input_multiname(multiname.clone(), activation);
         let mut descendants = Vec::new();
-        self.0.node.get().descendants(multiname, &mut descendants);
+        self.0.node.get().descendants(&multiname, &mut descendants);
 
         let list = XmlListObject::new_with_children(activation, descendants, None, None);
         // NOTE: avmplus does not set a target property/object here, but if there was at least one child
@@ -556,10 +557,17 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
                 // 12.b.ii. Else
                 // 12.b.ii.1. Let name be a new QName created as if by calling the constructor new QName(n)
 
+                let uri = name.explicit_namespace().or_else(|| {
+                    activation
+                        .default_xml_namespace()
+                        .and_then(|ns| ns.as_uri_opt())
+                        .filter(|uri| !uri.is_empty())
+                });
+
                 // 12.b.iii. Create a new XML object y with y.[[Name]] = name, y.[[Class]] = "element" and y.[[Parent]] = x
                 let node = E4XNode::element(
                     activation.gc(),
-                    name.explicit_namespace().map(E4XNamespace::new_uri),
+                    uri.map(E4XNamespace::new_uri),
                     name.local_name().unwrap(),
                     Some(self_node),
                 );
@@ -708,7 +716,7 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
     }
 }
 
-fn handle_input_multiname<'gc>(
+pub(super) fn handle_input_multiname<'gc>(
     name: Multiname<'gc>,
     activation: &mut Activation<'_, 'gc>,
 ) -> Multiname<'gc> {
@@ -728,11 +736,23 @@ fn handle_input_multiname<'gc>(
         {
             // Copy the namespaces from the previous name,
             // but make sure to definitely include the public namespace.
+            // If a default xml namespace is set, it takes the place of the
+            // public namespace for element names.
             if !new_name.is_any_namespace() {
                 let mut ns = Vec::new();
                 ns.extend(name.namespace_set());
-                if !name.contains_public_namespace() {
-                    ns.push(activation.avm2().namespaces.public_all());
+                let default_ns = activation
+                    .default_xml_namespace()
+                    .filter(|_| !new_name.is_attribute());
+                match default_ns {
+                    Some(default_ns) => {
+                        ns.retain(|ns| !ns.is_public());
+                        ns.push(default_ns);
+                    }
+                    None if !name.contains_public_namespace() => {
+                        ns.push(activation.avm2().namespaces.public_all());
+                    }
+                    None => {}
                 }
                 new_name.set_ns(NamespaceSet::new(ns, activation.gc()));
             }
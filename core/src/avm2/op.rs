@@ -1,4 +1,5 @@
 use crate::avm2::class::Class;
+use crate::avm2::inline_cache::PropertySite;
 use crate::avm2::multiname::Multiname;
 use crate::avm2::script::Script;
 use crate::string::AvmAtom;
@@ -35,7 +36,7 @@ pub enum Op<'gc> {
         push_return_value: bool,
     },
     CallProperty {
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
 
         num_args: u32,
     },
@@ -45,7 +46,7 @@ pub enum Op<'gc> {
         num_args: u32,
     },
     CallPropVoid {
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
 
         num_args: u32,
     },
@@ -149,7 +150,7 @@ pub enum Op<'gc> {
         index: u32,
     },
     GetProperty {
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
     },
     GetScopeObject {
         index: u8,
@@ -315,7 +316,7 @@ pub enum Op<'gc> {
         index: u32,
     },
     SetProperty {
-        multiname: Gc<'gc, Multiname<'gc>>,
+        site: Gc<'gc, PropertySite<'gc>>,
     },
     SetSlot {
         // note: 0-indexed, as opposed to FP.
@@ -1,9 +1,9 @@
 use crate::avm2::error::verify_error;
-use crate::avm2::method::{BytecodeMethod, ResolvedParamConfig};
+use crate::avm2::method::{BytecodeMethod, Method, ResolvedParamConfig};
 use crate::avm2::multiname::Multiname;
 use crate::avm2::object::TObject;
 use crate::avm2::op::Op;
-use crate::avm2::property::Property;
+use crate::avm2::property::{Property, PropertyClass};
 use crate::avm2::verify::{Exception, JumpSource};
 use crate::avm2::vtable::VTable;
 use crate::avm2::{Activation, Class, Error};
@@ -499,6 +499,33 @@ pub fn optimize<'gc>(
         namespace: activation.avm2().class_defs().namespace,
     };
 
+    let is_primitive = |class: Class<'gc>| {
+        class == types.int
+            || class == types.uint
+            || class == types.number
+            || class == types.boolean
+            || class == types.void
+    };
+
+    // Whether coercing this value to `class` is guaranteed to leave it unchanged.
+    let is_noop_coercion = |value: OptValue<'gc>, class: Class<'gc>| {
+        if value.is_null() {
+            // Coercing null to a non-primitive is a noop.
+            return !is_primitive(class);
+        }
+
+        match value.class {
+            Some(value_class) if value_class == class => true,
+            // Non-primitive coercions of a subclass instance return it as-is.
+            Some(value_class) => {
+                !is_primitive(class)
+                    && value_class != types.void
+                    && value_class.has_class_in_chain(class)
+            }
+            None => false,
+        }
+    };
+
     let method_body = method
         .body()
         .expect("Cannot verify non-native method without body!");
@@ -545,10 +572,31 @@ pub fn optimize<'gc>(
     // we're absolutely sure about- invalidate the local register's
     // known type if any other register-modifying opcodes mention them
     // anywhere else in the function.
-    for op in &*code {
+    for (i, op) in code.iter().enumerate() {
         match op {
-            Op::SetLocal { index }
-            | Op::Kill { index }
+            Op::SetLocal { index } => {
+                // Typed locals are always set to values coerced to their type by
+                // compilers, which preserves the type of the local.
+                let local_class = initial_local_types.at(*index as usize).class;
+                let coerced_class = if i > 0 && !jump_targets.contains_key(&(i as i32)) {
+                    match code[i - 1] {
+                        Op::Coerce { class } => Some(class),
+                        Op::CoerceB => Some(types.boolean),
+                        Op::CoerceD => Some(types.number),
+                        Op::CoerceI => Some(types.int),
+                        Op::CoerceS => Some(types.string),
+                        Op::CoerceU => Some(types.uint),
+                        _ => None,
+                    }
+                } else {
+                    None
+                };
+
+                if *index == 0 || local_class.is_none() || local_class != coerced_class {
+                    initial_local_types.set_any(*index as usize);
+                }
+            }
+            Op::Kill { index }
             | Op::IncLocal { index }
             | Op::IncLocalI { index }
             | Op::DecLocal { index }
@@ -750,7 +798,7 @@ pub fn optimize<'gc>(
                 }
                 Op::CoerceS => {
                     let stack_value = stack.pop(activation)?;
-                    if stack_value.is_null() {
+                    if stack_value.is_null() || stack_value.class == Some(types.string) {
                         *op = Op::Nop;
                     }
                     stack.push_class(activation, types.string)?;
@@ -1038,13 +1086,7 @@ pub fn optimize<'gc>(
                     let mut new_value = OptValue::any();
 
                     if let Some(class) = type_c_class.class.and_then(|c| c.i_class()) {
-                        let class_is_primitive = class == types.int
-                            || class == types.uint
-                            || class == types.number
-                            || class == types.boolean
-                            || class == types.void;
-
-                        if !class_is_primitive {
+                        if !is_primitive(class) {
                             // If the type on the stack was a c_class with a non-primitive
                             // i_class, we can use the type
                             new_value = OptValue::of_type(class);
@@ -1056,11 +1098,7 @@ pub fn optimize<'gc>(
                 Op::AsType { class } => {
                     let stack_value = stack.pop(activation)?;
 
-                    let class_is_primitive = *class == types.int
-                        || *class == types.uint
-                        || *class == types.number
-                        || *class == types.boolean
-                        || *class == types.void;
+                    let class_is_primitive = is_primitive(*class);
 
                     let mut new_value = OptValue::any();
                     if !class_is_primitive {
@@ -1071,7 +1109,11 @@ pub fn optimize<'gc>(
                         if *class == stack_class {
                             // If type check is guaranteed, preserve original type
                             // TODO: there are more cases when this can succeed,
-                            // like inheritance and numbers (`x: Number = 1; x as int;`)
+                            // like numbers (`x: Number = 1; x as int;`)
+                            new_value = stack_value;
+                        } else if !class_is_primitive && is_noop_coercion(stack_value, *class) {
+                            // Instances of subclasses always pass the type check
+                            *op = Op::Nop;
                             new_value = stack_value;
                         }
                     }
@@ -1086,23 +1128,24 @@ pub fn optimize<'gc>(
                     let stack_value = stack.pop(activation)?;
                     let mut new_value = OptValue::of_type(*class);
 
-                    if stack_value.is_null() {
-                        // Coercing null to a non-primitive or void is a noop.
-                        if *class != types.int
-                            && *class != types.uint
-                            && *class != types.number
-                            && *class != types.boolean
-                            && *class != types.void
-                        {
-                            *op = Op::Nop;
+                    if is_noop_coercion(stack_value, *class) {
+                        *op = Op::Nop;
+                        if stack_value.is_null() {
                             new_value.null_state = NullState::IsNull;
-                        }
-                    } else if let Some(stack_class) = stack_value.class {
-                        // TODO: this could check for inheritance
-                        if *class == stack_class {
-                            *op = Op::Nop;
+                        } else if stack_value.class == Some(*class) {
                             new_value.null_state = stack_value.null_state;
+                        } else {
+                            // Keep the more precise type of the subclass
+                            new_value = stack_value;
                         }
+                    } else if *class == types.int {
+                        *op = Op::CoerceI;
+                    } else if *class == types.uint {
+                        *op = Op::CoerceU;
+                    } else if *class == types.number {
+                        *op = Op::CoerceD;
+                    } else if *class == types.boolean {
+                        *op = Op::CoerceB;
                     }
 
                     stack.push(activation, new_value)?;
@@ -1229,9 +1272,25 @@ pub fn optimize<'gc>(
                         stack.push_any(activation)?;
                     }
                 }
-                Op::FindDef { .. } => {
-                    // Avoid handling for now
-                    stack.push_any(activation)?;
+                Op::FindDef { multiname } => {
+                    let multiname = *multiname;
+                    let mut stack_push_done = false;
+
+                    // The defining script of a non-lazy name can be looked up ahead of time.
+                    if let Ok(Some((_, script))) =
+                        activation.domain().get_defining_script(&multiname)
+                    {
+                        *op = Op::GetScriptGlobals { script };
+
+                        if script.traits_loaded() {
+                            stack_push_done = true;
+                            stack.push_class_not_null(activation, script.global_class())?;
+                        }
+                    }
+
+                    if !stack_push_done {
+                        stack.push_any(activation)?;
+                    }
                 }
                 Op::In => {
                     stack.pop(activation)?;
@@ -1297,7 +1356,8 @@ pub fn optimize<'gc>(
                     stack.pop(activation)?;
                     stack.pop(activation)?;
                 }
-                Op::GetProperty { multiname } => {
+                Op::GetProperty { site } => {
+                    let multiname = &site.multiname();
                     let mut stack_push_done = false;
                     stack.pop_for_multiname(activation, *multiname)?;
                     let stack_value = stack.pop(activation)?;
@@ -1335,6 +1395,13 @@ pub fn optimize<'gc>(
                                         index: disp_id,
                                         push_return_value: true,
                                     };
+
+                                    if let Some(class) =
+                                        method_return_type(activation, vtable, disp_id)
+                                    {
+                                        stack_push_done = true;
+                                        stack.push_class(activation, class)?;
+                                    }
                                 }
                                 _ => {}
                             }
@@ -1365,10 +1432,8 @@ pub fn optimize<'gc>(
 
                                     if let Ok(slot_class) = resolved_value_class {
                                         if let Some(slot_class) = slot_class {
-                                            if let Some(set_value_class) = set_value.class {
-                                                if set_value_class == slot_class {
-                                                    *op = Op::SetSlotNoCoerce { index: slot_id };
-                                                }
+                                            if is_noop_coercion(set_value, slot_class) {
+                                                *op = Op::SetSlotNoCoerce { index: slot_id };
                                             }
                                         } else {
                                             // Slot type was Any, no coercion will be done anyways
@@ -1391,7 +1456,8 @@ pub fn optimize<'gc>(
                     }
                     // `stack_pop_multiname` handled lazy
                 }
-                Op::SetProperty { multiname } => {
+                Op::SetProperty { site } => {
+                    let multiname = &site.multiname();
                     let set_value = stack.pop(activation)?;
 
                     stack.pop_for_multiname(activation, *multiname)?;
@@ -1409,10 +1475,8 @@ pub fn optimize<'gc>(
 
                                     if let Ok(slot_class) = resolved_value_class {
                                         if let Some(slot_class) = slot_class {
-                                            if let Some(set_value_class) = set_value.class {
-                                                if set_value_class == slot_class {
-                                                    *op = Op::SetSlotNoCoerce { index: slot_id };
-                                                }
+                                            if is_noop_coercion(set_value, slot_class) {
+                                                *op = Op::SetSlotNoCoerce { index: slot_id };
                                             }
                                         } else {
                                             // Slot type was Any, no coercion will be done anyways
@@ -1533,10 +1597,8 @@ pub fn optimize<'gc>(
                     // Avoid checking return value for now
                     stack.push_any(activation)?;
                 }
-                Op::CallProperty {
-                    multiname,
-                    num_args,
-                } => {
+                Op::CallProperty { site, num_args } => {
+                    let multiname = &site.multiname();
                     let mut stack_push_done = false;
 
                     // Arguments
@@ -1556,6 +1618,13 @@ pub fn optimize<'gc>(
                                         index: disp_id,
                                         push_return_value: true,
                                     };
+
+                                    if let Some(class) =
+                                        method_return_type(activation, vtable, disp_id)
+                                    {
+                                        stack_push_done = true;
+                                        stack.push_class(activation, class)?;
+                                    }
                                 }
                                 Some(Property::Slot { slot_id })
                                 | Some(Property::ConstSlot { slot_id }) => {
@@ -1610,10 +1679,8 @@ pub fn optimize<'gc>(
                         stack.push_any(activation)?;
                     }
                 }
-                Op::CallPropVoid {
-                    multiname,
-                    num_args,
-                } => {
+                Op::CallPropVoid { site, num_args } => {
+                    let multiname = &site.multiname();
                     // Arguments
                     stack.popn(activation, *num_args)?;
 
@@ -1876,22 +1943,8 @@ pub fn optimize<'gc>(
                     let stack_value = stack.pop(activation)?;
 
                     if let Some(return_type) = return_type {
-                        let return_type_is_primitive = return_type == types.int
-                            || return_type == types.uint
-                            || return_type == types.number
-                            || return_type == types.boolean
-                            || return_type == types.void;
-
-                        if let Some(stack_value_class) = stack_value.class {
-                            if stack_value_class == return_type {
-                                *op = Op::ReturnValueNoCoerce;
-                            }
-                        }
-
-                        if !return_type_is_primitive {
-                            if stack_value.is_null() {
-                                *op = Op::ReturnValueNoCoerce;
-                            }
+                        if is_noop_coercion(stack_value, return_type) {
+                            *op = Op::ReturnValueNoCoerce;
                         }
                     } else {
                         // Return type was Any, no coercion will be done anyways
@@ -1976,6 +2029,23 @@ pub fn optimize<'gc>(
     Ok(())
 }
 
+/// Returns the declared return type of the method with the given dispatch id.
+///
+/// Only bytecode methods are considered, as their return values are always
+/// coerced to the declared type.
+fn method_return_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    vtable: VTable<'gc>,
+    disp_id: u32,
+) -> Option<Class<'gc>> {
+    let Some(Method::Bytecode(method)) = vtable.get_method(disp_id) else {
+        return None;
+    };
+
+    let mut return_class = PropertyClass::name(method.return_type, Some(method.translation_unit()));
+    return_class.get_class(activation).ok().flatten()
+}
+
 #[allow(clippy::too_many_arguments)]
 fn check_target<'gc>(
     activation: &mut Activation<'_, 'gc>,
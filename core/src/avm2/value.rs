@@ -783,17 +783,19 @@ impl<'gc> Value<'gc> {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<AvmString<'gc>, Error<'gc>> {
         Ok(match self {
-            Value::Undefined => "undefined".into(),
-            Value::Null => "null".into(),
-            Value::Bool(true) => "true".into(),
-            Value::Bool(false) => "false".into(),
-            Value::Number(n) if n.is_nan() => "NaN".into(),
-            Value::Number(n) if *n == 0.0 => "0".into(),
+            Value::Undefined => activation.strings().common().str_undefined.into(),
+            Value::Null => activation.strings().common().str_null.into(),
+            Value::Bool(true) => activation.strings().common().str_true.into(),
+            Value::Bool(false) => activation.strings().common().str_false.into(),
+            Value::Number(n) if n.is_nan() => activation.strings().common().str_nan.into(),
+            Value::Number(n) if *n == 0.0 => activation.strings().ascii_char(b'0'),
             Value::Number(n) if *n < 0.0 => AvmString::new_utf8(
                 activation.context.gc_context,
                 format!("-{}", Value::Number(-n).coerce_to_string(activation)?),
             ),
-            Value::Number(n) if n.is_infinite() => "Infinity".into(),
+            Value::Number(n) if n.is_infinite() => {
+                activation.strings().common().str_infinity.into()
+            }
             Value::Number(n) => {
                 let digits = n.log10().floor();
 
@@ -3,6 +3,7 @@ use crate::avm2::error::{
     make_error_1014, make_error_1021, make_error_1025, make_error_1032, make_error_1054,
     make_error_1107, verify_error,
 };
+use crate::avm2::inline_cache::PropertySite;
 use crate::avm2::method::{BytecodeMethod, ParamConfig, ResolvedParamConfig};
 use crate::avm2::multiname::Multiname;
 use crate::avm2::op::Op;
@@ -902,7 +903,7 @@ fn resolve_op<'gc>(
             let multiname = pool_multiname(activation, translation_unit, index)?;
 
             Op::CallProperty {
-                multiname,
+                site: PropertySite::new(activation.gc(), multiname),
                 num_args,
             }
         }
@@ -918,7 +919,7 @@ fn resolve_op<'gc>(
             let multiname = pool_multiname(activation, translation_unit, index)?;
 
             Op::CallPropVoid {
-                multiname,
+                site: PropertySite::new(activation.gc(), multiname),
                 num_args,
             }
         }
@@ -944,12 +945,16 @@ fn resolve_op<'gc>(
         AbcOp::GetProperty { index } => {
             let multiname = pool_multiname(activation, translation_unit, index)?;
 
-            Op::GetProperty { multiname }
+            Op::GetProperty {
+                site: PropertySite::new(activation.gc(), multiname),
+            }
         }
         AbcOp::SetProperty { index } => {
             let multiname = pool_multiname(activation, translation_unit, index)?;
 
-            Op::SetProperty { multiname }
+            Op::SetProperty {
+                site: PropertySite::new(activation.gc(), multiname),
+            }
         }
         AbcOp::InitProperty { index } => {
             let multiname = pool_multiname(activation, translation_unit, index)?;
@@ -40,6 +40,10 @@ pub struct VTableData<'gc> {
     method_table: Vec<ClassBoundMethod<'gc>>,
 
     default_slots: Vec<Option<Value<'gc>>>,
+
+    /// Incremented whenever `resolved_traits` changes, so that inline caches can tell
+    /// whether what they remember about this vtable is still true.
+    generation: u32,
 }
 
 impl PartialEq for VTable<'_> {
@@ -73,6 +77,7 @@ impl<'gc> VTable<'gc> {
                 slot_classes: vec![],
                 method_table: vec![],
                 default_slots: vec![],
+                generation: 0,
             },
         ))
     }
@@ -94,6 +99,7 @@ impl<'gc> VTable<'gc> {
                 method_table: vec![],
                 default_slots: vec![None],
                 slot_classes: vec![PropertyClass::Any],
+                generation: 0,
             },
         ));
 
@@ -125,6 +131,10 @@ impl<'gc> VTable<'gc> {
             .map(|c| c.get_name(context))
     }
 
+    pub fn generation(self) -> u32 {
+        self.0.read().generation
+    }
+
     pub fn get_trait(self, name: &Multiname<'gc>) -> Option<Property> {
         if name.is_attribute() {
             return None;
@@ -270,6 +280,7 @@ impl<'gc> VTable<'gc> {
         let mut write = self.0.write(mc);
         let write = write.deref_mut();
 
+        write.generation = write.generation.wrapping_add(1);
         write.scope = scope;
 
         write.protected_namespace = defining_class_def.protected_namespace();
@@ -547,6 +558,7 @@ impl<'gc> VTable<'gc> {
 
         if let Some(prop) = prop {
             write.resolved_traits.insert(interface_name, prop);
+            write.generation = write.generation.wrapping_add(1);
         }
     }
 
@@ -1,5 +1,10 @@
 pub mod audio;
+pub mod camera;
+pub mod debugger;
+pub mod jit;
+pub mod local_connection;
 pub mod log;
+pub mod microphone;
 pub mod navigator;
 pub mod storage;
 pub mod ui;
@@ -1,6 +1,9 @@
 use crate::{
     avm1::{NativeObject, Object as Avm1Object, TObject as _},
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{
+        Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, SoundChannelObject,
+        SoundObject as Avm2SoundObject,
+    },
     buffer::Substream,
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
@@ -130,6 +133,21 @@ pub trait AudioBackend: Downcast {
         stream_info: &SoundStreamInfo,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts a sound whose audio is generated while it plays, such as by AVM2's `SampleDataEvent`.
+    ///
+    /// Audio is queued onto the sound with `append_dynamic_samples`.
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError>;
+
+    /// Queues 44.1KHz stereo sample frames onto a sound started by `start_dynamic_sound`.
+    ///
+    /// Passing no frames marks the sound as finished. It will stop once the queued audio has played.
+    fn append_dynamic_samples(&mut self, instance: SoundInstanceHandle, frames: &[[f32; 2]]);
+
+    /// Returns the number of sample frames queued on a dynamic sound that haven't been played yet.
+    ///
+    /// Returns `None` if the sound is no longer playing, or if the backend doesn't play audio.
+    fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<usize>;
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -178,6 +196,12 @@ pub trait AudioBackend: Downcast {
         None
     }
 
+    /// Inform the audio backend whether sounds should be output with as little latency
+    /// as possible, such as for instruments controlled by the user.
+    ///
+    /// Backends may use smaller buffers while this is enabled, at the cost of efficiency.
+    fn set_low_latency(&mut self, _low_latency: bool) {}
+
     /// Returns the master volume of the audio backend.
     fn volume(&self) -> f32;
 
@@ -187,6 +211,19 @@ pub trait AudioBackend: Downcast {
     /// Returns the last whole window of output samples.
     fn get_sample_history(&self) -> [[f32; 2]; 1024];
 
+    /// Copies decoded audio from a sound into `output`, as 44.1KHz stereo sample frames.
+    ///
+    /// This is used by `Sound.extract`. Returns the number of sample frames written,
+    /// which is fewer than `output.len()` when the end of the sound is reached.
+    fn extract_sound(
+        &mut self,
+        _sound: SoundHandle,
+        _start_sample_frame: u32,
+        _output: &mut [[f32; 2]],
+    ) -> Result<usize, DecodeError> {
+        Ok(0)
+    }
+
     /// Determine if a sound is still playing.
     fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool {
         self.get_sound_position(instance).is_some()
@@ -282,6 +319,16 @@ impl AudioBackend for NullAudioBackend {
         Ok(SoundInstanceHandle::null())
     }
 
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::null())
+    }
+
+    fn append_dynamic_samples(&mut self, _instance: SoundInstanceHandle, _frames: &[[f32; 2]]) {}
+
+    fn dynamic_sound_buffered_frames(&self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
     fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
 
     fn stop_all_sounds(&mut self) {}
@@ -363,6 +410,11 @@ impl<'gc> AudioManager<'gc> {
     /// The threshold in seconds where an audio stream is considered too out-of-sync and will be stopped.
     pub const STREAM_RESTART_THRESHOLD: f64 = 1.0;
 
+    /// The number of generated sample frames to keep queued for sounds driven by `sampleData` events.
+    ///
+    /// More audio is requested whenever less than this is waiting to be played.
+    pub const DYNAMIC_SOUND_BUFFER_FRAMES: usize = 4096;
+
     /// The minimum audio syncing threshold in seconds.
     ///
     /// The player will adjust animation speed to stay within this many seconds of the audio track.
@@ -458,6 +510,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data_source: None,
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -467,6 +521,87 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Starts a sound whose audio is generated by an AVM2 `Sound` through `sampleData` events.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        source: Avm2SoundObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound().ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                sample_data_source: Some(source),
+                sample_data_position: 0,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Asks the `Sound`s behind dynamic sounds for more audio, if they're running low.
+    ///
+    /// Should be called regularly, more often than once per frame if possible.
+    pub fn update_dynamic_sounds(context: &mut UpdateContext<'gc>) {
+        // Dispatching `sampleData` needs the whole context, so find the sounds to refill first.
+        let requests: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter_map(|sound| {
+                let source = sound.sample_data_source?;
+                let buffered = context
+                    .audio
+                    .dynamic_sound_buffered_frames(sound.instance)?;
+                (buffered < Self::DYNAMIC_SOUND_BUFFER_FRAMES).then_some((
+                    sound.instance,
+                    source,
+                    sound.sample_data_position,
+                ))
+            })
+            .collect();
+
+        for (instance, source, position) in requests {
+            let mut activation = Avm2Activation::from_nothing(context);
+            let frames = match source.request_sample_data(&mut activation, position) {
+                Ok(frames) => frames,
+                Err(e) => {
+                    tracing::error!("Error when generating sound through sampleData: {e:?}");
+                    Vec::new()
+                }
+            };
+
+            if !frames.is_empty() {
+                context.audio.append_dynamic_samples(instance, &frames);
+            }
+            let is_finished = frames.len() < Avm2SoundObject::SAMPLE_DATA_MIN_FRAMES;
+            if is_finished {
+                context.audio.append_dynamic_samples(instance, &[]);
+            }
+
+            if let Some(sound) = context
+                .audio_manager
+                .sounds
+                .iter_mut()
+                .find(|sound| sound.instance == instance)
+            {
+                sound.sample_data_position += frames.len() as u32;
+                if is_finished {
+                    sound.sample_data_source = None;
+                }
+            }
+        }
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -583,6 +718,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                sample_data_source: None,
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -609,6 +746,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data_source: None,
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -618,37 +757,65 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Returns the difference in seconds between a stream sound's time and the time of the
+    /// movie clip playing it, along with that clip.
+    ///
+    /// `offset_ms` is added to the clip's time. Returns `None` if this isn't a stream sound.
+    fn stream_skew(
+        audio: &dyn AudioBackend,
+        instance: &SoundInstance<'gc>,
+        offset_ms: f64,
+    ) -> Option<(MovieClip<'gc>, f64)> {
+        let start_frame = instance.stream_start_frame?;
+        let clip = instance
+            .display_object
+            .and_then(|clip| clip.as_movie_clip())?;
+        let stream_pos = audio.get_sound_position(instance.instance)?;
+        let frame_rate = clip.movie().frame_rate().to_f64();
+
+        // Calculate the difference in time between the owning movie clip and its audio track.
+        let timeline_pos = f64::from(clip.current_frame().saturating_sub(start_frame)) / frame_rate
+            + offset_ms / 1000.0;
+
+        Some((clip, stream_pos / 1000.0 - timeline_pos))
+    }
+
+    /// Returns the difference in seconds between the time of the stream sound playing in
+    /// the given clip and the clip's own time, or `None` if it has no stream sound playing.
+    ///
+    /// This is the skew that `audio_skew_time` corrects once it goes past `stream_sync_threshold`.
+    pub fn stream_skew_time(&self, audio: &dyn AudioBackend, clip: MovieClip<'gc>) -> Option<f64> {
+        self.sounds.iter().find_map(|instance| {
+            let (owner, skew) = Self::stream_skew(audio, instance, 0.0)?;
+            DisplayObject::ptr_eq(owner.into(), clip.into()).then_some(skew)
+        })
+    }
+
+    /// The skew in seconds that is tolerated between a timeline and its stream sound.
+    ///
+    /// This is based on the audio backend's frequency in updating sound positions.
+    pub fn stream_sync_threshold(audio: &dyn AudioBackend) -> f64 {
+        audio
+            .position_resolution()
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or(Self::STREAM_DEFAULT_SYNC_THRESHOLD)
+    }
+
     /// Returns the difference in seconds between the primary audio stream's time and the player's time.
     pub fn audio_skew_time(&mut self, audio: &mut dyn AudioBackend, offset_ms: f64) -> f64 {
         // Consider the first playing "stream" sound to be the primary audio track.
         // Needs research: It's not clear how Flash handles the case of multiple stream sounds.
+        // If the difference is beyond some threshold, inform the player to adjust playback speed.
         let (i, skew) = self
             .sounds
             .iter()
             .enumerate()
             .find_map(|(i, instance)| {
-                let start_frame = instance.stream_start_frame?;
-                let clip = instance
-                    .display_object
-                    .and_then(|clip| clip.as_movie_clip())?;
-                let stream_pos = audio.get_sound_position(instance.instance)?;
-                let frame_rate = clip.movie().frame_rate().to_f64();
-
-                // Calculate the difference in time between the owning movie clip and its audio track.
-                // If the difference is beyond some threshold, inform the player to adjust playback speed.
-                let timeline_pos = f64::from(clip.current_frame().saturating_sub(start_frame))
-                    / frame_rate
-                    + offset_ms / 1000.0;
-
-                Some((i, stream_pos / 1000.0 - timeline_pos))
+                Self::stream_skew(&*audio, instance, offset_ms).map(|(_, skew)| (i, skew))
             })
             .unwrap_or_default();
 
-        // Calculate the syncing threshold based on the audio backend's frequency in updating sound position.
-        let sync_threshold = audio
-            .position_resolution()
-            .map(|duration| duration.as_secs_f64())
-            .unwrap_or(Self::STREAM_DEFAULT_SYNC_THRESHOLD);
+        let sync_threshold = Self::stream_sync_threshold(&*audio);
 
         if skew.abs() >= Self::STREAM_RESTART_THRESHOLD {
             // Way out of sync, let's stop the entire stream.
@@ -741,7 +908,7 @@ impl<'gc> AudioManager<'gc> {
 
     /// Update the sound transforms for all sounds.
     /// This should be called whenever a sound transform changes on a display object.
-    fn update_sound_transforms(&mut self, audio: &mut dyn AudioBackend) {
+    pub fn update_sound_transforms(&mut self, audio: &mut dyn AudioBackend) {
         // This updates the sound transform for all sounds, even though the transform has
         // only changed on a single display object. There are only a small amount
         // of sounds playing at any time, so this shouldn't be a big deal.
@@ -824,6 +991,14 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The AVM2 `Sound` that generates this sound's audio through `sampleData` events, if any.
+    ///
+    /// This is cleared once the `Sound` stops providing audio.
+    sample_data_source: Option<Avm2SoundObject<'gc>>,
+
+    /// The number of sample frames that `sample_data_source` has generated so far.
+    sample_data_position: u32,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
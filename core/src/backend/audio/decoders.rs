@@ -491,6 +491,13 @@ impl Read for SubstreamTagReader {
     }
 }
 
+/// Makes a decoder for a `Substream` with a decoder of the platform, such as the browser's
+/// WebCodecs, or returns `None` to use Ruffle's own decoder.
+///
+/// The substream is shared, as with `make_substream_decoder`.
+pub type ExternalSubstreamDecoder =
+    Box<dyn Fn(&SoundStreamInfo, &Substream) -> Option<Box<dyn Decoder + Send>> + Send + Sync>;
+
 /// Create a new decoder that reads data from a shared `Substream` instance.
 ///
 /// This works similarly to `make_stream_decoder` but using the new buffer
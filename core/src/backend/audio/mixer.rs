@@ -1,9 +1,12 @@
-use super::decoders::{self, AdpcmDecoder, Decoder, PcmDecoder, SeekableDecoder};
+use super::decoders::{
+    self, AdpcmDecoder, Decoder, ExternalSubstreamDecoder, PcmDecoder, SeekableDecoder,
+};
 use super::{SoundHandle, SoundInstanceHandle, SoundStreamInfo, SoundTransform};
 use crate::backend::audio::{DecodeError, RegisterError};
 use crate::buffer::Substream;
 use crate::tag_utils::SwfSlice;
 use slotmap::SlotMap;
+use std::collections::VecDeque;
 use std::io::Cursor;
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
@@ -70,6 +73,9 @@ pub struct AudioMixer {
 
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
+
+    /// Decodes substreams in place of Ruffle's own decoders, where the platform can.
+    external_substream_decoder: Option<ExternalSubstreamDecoder>,
 }
 
 /// An audio stream.
@@ -137,6 +143,102 @@ impl<D: Decoder> dasp::signal::Signal for DecoderStream<D> {
     }
 }
 
+/// How many sample frames a dynamic sound takes from its shared buffer at a time.
+///
+/// The audio thread locks the buffer once per batch, rather than once per sample frame.
+const DYNAMIC_SOUND_BATCH_FRAMES: usize = 512;
+
+/// The sample frames queued for a dynamic sound, shared between the player and the audio thread.
+#[derive(Default)]
+struct DynamicSoundBuffer {
+    /// The generated sample frames that haven't been played yet.
+    frames: VecDeque<[i16; 2]>,
+
+    /// Whether the player has stopped generating audio for this sound.
+    ///
+    /// The sound ends once all of `frames` has been played.
+    is_finished: bool,
+}
+
+/// A stream of 44.1KHz stereo audio generated on demand by the player, such as through
+/// AVM2's `SampleDataEvent`.
+///
+/// If the buffer runs dry before the sound is finished, silence is played until more
+/// audio arrives. The silence doesn't count towards the stream position.
+struct DynamicSoundStream {
+    buffer: Arc<Mutex<DynamicSoundBuffer>>,
+
+    /// The sample frames taken from `buffer` that haven't been played yet.
+    batch: VecDeque<[i16; 2]>,
+
+    /// The number of sample frames of silence to play before checking `buffer` again, after
+    /// it ran dry.
+    silence: usize,
+
+    position: u32,
+    is_exhausted: bool,
+}
+
+impl DynamicSoundStream {
+    fn new(buffer: Arc<Mutex<DynamicSoundBuffer>>) -> Self {
+        Self {
+            buffer,
+            batch: VecDeque::with_capacity(DYNAMIC_SOUND_BATCH_FRAMES),
+            silence: 0,
+            position: 0,
+            is_exhausted: false,
+        }
+    }
+
+    /// Takes the next batch of sample frames from the shared buffer.
+    fn refill(&mut self) {
+        let mut buffer = self.buffer.lock().expect("Cannot be called reentrant");
+        let count = buffer.frames.len().min(DYNAMIC_SOUND_BATCH_FRAMES);
+        self.batch.extend(buffer.frames.drain(..count));
+        if self.batch.is_empty() {
+            self.is_exhausted = buffer.is_finished;
+            self.silence = DYNAMIC_SOUND_BATCH_FRAMES;
+        }
+    }
+}
+
+impl Stream for DynamicSoundStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        44100
+    }
+}
+
+impl dasp::signal::Signal for DynamicSoundStream {
+    type Frame = [i16; 2];
+
+    fn next(&mut self) -> [i16; 2] {
+        if self.batch.is_empty() {
+            if self.silence > 0 {
+                self.silence -= 1;
+                return Default::default();
+            }
+            self.refill();
+        }
+        if let Some(frame) = self.batch.pop_front() {
+            self.position += 1;
+            frame
+        } else {
+            Default::default()
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
 /// Contains the data and metadata for a sound in an SWF file.
 ///
 /// A sound is defined by the `DefineSound` SWF tags and contains the audio data for the sound.
@@ -159,6 +261,24 @@ struct Sound {
     /// `skip_sample_frames` indicates how many sample frames to skip to bypass the delay.
     /// This is `0` unless `format.compression` is `AudioCompression::Mp3`.
     skip_sample_frames: u16,
+
+    /// Where the last `Sound.extract` of this sound left off.
+    extract_cursor: Option<ExtractCursor>,
+}
+
+/// A decoder partway through a sound, used by `Sound.extract`.
+///
+/// Movies usually extract a sound in consecutive chunks, so the decoder is kept where the last
+/// chunk ended, rather than decoding the sound from the start for every chunk.
+struct ExtractCursor {
+    /// The sound, resampled to 44.1KHz.
+    stream: Box<dyn Stream>,
+
+    /// The sample frame of the sound that `stream` produces next.
+    position: usize,
+
+    /// The number of sample frames in the sound.
+    num_sample_frames: usize,
 }
 
 /// An actively playing instance of a sound.
@@ -195,6 +315,9 @@ struct SoundInstance {
     /// (respectively) of this sound over the buffer currently being
     /// mixed. Used to compute `peak`, and is reset after every time.
     range: ([f32; 2], [f32; 2]),
+
+    /// The queue that feeds `stream`, if this is a dynamic sound.
+    dynamic_buffer: Option<Arc<Mutex<DynamicSoundBuffer>>>,
 }
 
 impl SoundInstance {
@@ -208,6 +331,7 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            dynamic_buffer: None,
         }
     }
 
@@ -223,6 +347,7 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            dynamic_buffer: None,
         }
     }
 
@@ -245,9 +370,16 @@ impl AudioMixer {
             num_output_channels,
             output_sample_rate,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
+            external_substream_decoder: None,
         }
     }
 
+    /// Sets the decoder that substreams, such as the audio of a `NetStream`, are decoded with
+    /// before falling back to Ruffle's own decoders.
+    pub fn set_external_substream_decoder(&mut self, decoder: ExternalSubstreamDecoder) {
+        self.external_substream_decoder = Some(decoder);
+    }
+
     /// Creates a proxy that may be sent to a different thread.
     pub fn proxy(&self) -> AudioMixerProxy {
         AudioMixerProxy {
@@ -332,7 +464,12 @@ impl AudioMixer {
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
+    fn make_resampler(&self, stream: impl Stream) -> impl Stream {
+        Self::make_resampler_to(stream, self.output_sample_rate)
+    }
+
+    /// Transforms a `Stream` into a new `Stream` with the given sample rate.
+    fn make_resampler_to(mut stream: impl Stream, output_sample_rate: u32) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
@@ -342,10 +479,30 @@ impl AudioMixer {
             stream,
             interpolator,
             sample_rate,
-            self.output_sample_rate.into(),
+            output_sample_rate.into(),
         ))
     }
 
+    /// Starts decoding a sound from its first sample frame, for `Sound.extract`.
+    fn start_extracting(sound: &Sound) -> Result<ExtractCursor, DecodeError> {
+        let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+        let decoder = decoders::make_decoder(&sound.format, data)?;
+        let mut stream = Self::make_resampler_to(DecoderStream::new(decoder), 44100);
+
+        // `num_sample_frames` and `skip_sample_frames` are both counted at the source sample rate.
+        let source_sample_rate = u64::from(sound.format.sample_rate.max(1));
+        let to_output_frames = |frames: u64| (frames * 44100).div_ceil(source_sample_rate) as usize;
+
+        for _ in 0..to_output_frames(sound.skip_sample_frames.into()) {
+            stream.next();
+        }
+        Ok(ExtractCursor {
+            stream: Box::new(stream),
+            position: 0,
+            num_sample_frames: to_output_frames(sound.num_sample_frames.into()),
+        })
+    }
+
     /// Creates a `Stream` for an "event" that decodes and resamples the audio stream to the
     /// output format.
     ///
@@ -416,7 +573,14 @@ impl AudioMixer {
         data_stream: Substream,
     ) -> Result<Box<dyn Stream>, DecodeError> {
         // Instantiate a decoder for the compression that the sound data uses.
-        let clip_stream_decoder = decoders::make_substream_decoder(stream_info, data_stream)?;
+        let external_decoder = self
+            .external_substream_decoder
+            .as_ref()
+            .and_then(|make_decoder| make_decoder(stream_info, &data_stream));
+        let clip_stream_decoder = match external_decoder {
+            Some(decoder) => decoder,
+            None => decoders::make_substream_decoder(stream_info, data_stream)?,
+        };
 
         // Convert the `Decoder` to a `Stream`, and resample it to the output sample rate.
         let stream = DecoderStream::new(clip_stream_decoder);
@@ -522,6 +686,7 @@ impl AudioMixer {
             data: Arc::from(data),
             num_sample_frames: swf_sound.num_samples,
             skip_sample_frames,
+            extract_cursor: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -542,6 +707,7 @@ impl AudioMixer {
             data,
             num_sample_frames: metadata.num_sample_frames,
             skip_sample_frames: 0,
+            extract_cursor: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -551,6 +717,45 @@ impl AudioMixer {
         Err(decoders::Error::UnhandledCompression(AudioCompression::Mp3))
     }
 
+    /// Copies decoded audio from a registered sound into `output`, as 44.1KHz stereo sample frames.
+    ///
+    /// Only the requested sample frames are decoded. Continuing from where the last call ended is
+    /// cheap, but going back decodes the sound from the start again.
+    /// Returns the number of sample frames written, which is fewer than `output.len()` when the
+    /// end of the sound is reached.
+    pub fn extract_sound(
+        &mut self,
+        sound_handle: SoundHandle,
+        start_sample_frame: u32,
+        output: &mut [[f32; 2]],
+    ) -> Result<usize, DecodeError> {
+        let Some(sound) = self.sounds.get_mut(sound_handle) else {
+            return Ok(0);
+        };
+        let start = start_sample_frame as usize;
+        let cursor = match sound.extract_cursor.take() {
+            Some(cursor) if cursor.position <= start => cursor,
+            // Decoders can only go forward.
+            _ => Self::start_extracting(sound)?,
+        };
+        let cursor = sound.extract_cursor.insert(cursor);
+        if start >= cursor.num_sample_frames {
+            return Ok(0);
+        }
+
+        while cursor.position < start {
+            cursor.stream.next();
+            cursor.position += 1;
+        }
+        let count = output.len().min(cursor.num_sample_frames - start);
+        for out in &mut output[..count] {
+            let frame = cursor.stream.next();
+            *out = [f32::from(frame[0]) / 32768.0, f32::from(frame[1]) / 32768.0];
+        }
+        cursor.position += count;
+        Ok(count)
+    }
+
     /// Starts a timeline audio stream.
     pub fn start_stream(
         &mut self,
@@ -622,6 +827,69 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a sound whose audio is generated by the player while it plays.
+    ///
+    /// Audio is queued onto the sound with `AudioMixer::append_dynamic_samples`.
+    pub fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        let buffer = Arc::new(Mutex::new(DynamicSoundBuffer::default()));
+        let stream = DynamicSoundStream::new(Arc::clone(&buffer));
+        let mut instance = SoundInstance::new_stream(Box::new(self.make_resampler(stream)));
+        instance.dynamic_buffer = Some(buffer);
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(instance);
+        Ok(handle)
+    }
+
+    /// Queues 44.1KHz stereo sample frames onto a sound started by `AudioMixer::start_dynamic_sound`.
+    ///
+    /// Passing no frames marks the sound as finished. It will stop once the queued audio has played.
+    pub fn append_dynamic_samples(&mut self, instance: SoundInstanceHandle, frames: &[[f32; 2]]) {
+        use dasp::Sample;
+
+        let sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let Some(buffer) = sound_instances
+            .get(instance)
+            .and_then(|instance| instance.dynamic_buffer.as_ref())
+        else {
+            return;
+        };
+
+        let mut buffer = buffer.lock().expect("Cannot be called reentrant");
+        if frames.is_empty() {
+            buffer.is_finished = true;
+        } else {
+            buffer.frames.extend(
+                frames
+                    .iter()
+                    .map(|[left, right]| [left.to_sample::<i16>(), right.to_sample::<i16>()]),
+            );
+        }
+    }
+
+    /// Returns the number of 44.1KHz sample frames queued on a dynamic sound that haven't been
+    /// played yet.
+    ///
+    /// This leaves out the batch of up to `DYNAMIC_SOUND_BATCH_FRAMES` that the audio thread is
+    /// playing, so the sound may be refilled slightly early, but never late.
+    ///
+    /// Returns `None` if the sound is no longer playing, or isn't a dynamic sound.
+    pub fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        let sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let buffer = sound_instances.get(instance)?.dynamic_buffer.as_ref()?;
+        let buffer = buffer.lock().expect("Cannot be called reentrant");
+        Some(buffer.frames.len())
+    }
+
     /// Stops a playing sound instance.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
@@ -1078,6 +1346,16 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.register_mp3(data)
         }
 
+        #[inline]
+        fn extract_sound(
+            &mut self,
+            sound: SoundHandle,
+            start_sample_frame: u32,
+            output: &mut [[f32; 2]],
+        ) -> Result<usize, DecodeError> {
+            self.$mixer.extract_sound(sound, start_sample_frame, output)
+        }
+
         #[inline]
         fn start_stream(
             &mut self,
@@ -1105,6 +1383,21 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.start_substream(stream_data, stream_info)
         }
 
+        #[inline]
+        fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_dynamic_sound()
+        }
+
+        #[inline]
+        fn append_dynamic_samples(&mut self, instance: SoundInstanceHandle, frames: &[[f32; 2]]) {
+            self.$mixer.append_dynamic_samples(instance, frames)
+        }
+
+        #[inline]
+        fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<usize> {
+            self.$mixer.dynamic_sound_buffered_frames(instance)
+        }
+
         #[inline]
         fn stop_sound(&mut self, sound: SoundInstanceHandle) {
             self.$mixer.stop_sound(sound)
@@ -0,0 +1,74 @@
+/// A single frame captured from a camera.
+#[derive(Clone, Debug)]
+pub struct CameraFrame {
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels of the frame, in RGBA order. Cameras are opaque, so every alpha is 255.
+    pub rgba: Vec<u8>,
+}
+
+/// Whether a movie may currently receive data from a camera or microphone.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaptureStatus {
+    /// The device is not capturing.
+    Closed,
+
+    /// Capturing was requested, and is waiting on the user (or the system) to allow it.
+    Pending,
+
+    /// The device is capturing.
+    Active,
+
+    /// The user (or the system) refused access to the device.
+    Denied,
+}
+
+pub trait CameraBackend {
+    /// The names of the cameras available to movies, in the order `Camera.getCamera` indexes them.
+    fn names(&self) -> Vec<String>;
+
+    /// Starts capturing from the camera with the given index, or changes the capture mode of a
+    /// camera that is already capturing.
+    ///
+    /// The requested size and frame rate are only a preference; the frames that are actually
+    /// captured may differ.
+    fn open(&mut self, index: usize, width: u32, height: u32, fps: f64);
+
+    /// Stops capturing from the camera with the given index.
+    fn close(&mut self, index: usize);
+
+    fn status(&self, index: usize) -> CaptureStatus;
+
+    /// The most recent frame captured by the camera with the given index, along with a number
+    /// that changes whenever a new frame is captured.
+    fn latest_frame(&mut self, index: usize) -> Option<(u64, &CameraFrame)>;
+}
+
+/// A camera backend for platforms without cameras, or where movies may not use them.
+#[derive(Default)]
+pub struct NullCameraBackend;
+
+impl NullCameraBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CameraBackend for NullCameraBackend {
+    fn names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn open(&mut self, _index: usize, _width: u32, _height: u32, _fps: f64) {}
+
+    fn close(&mut self, _index: usize) {}
+
+    fn status(&self, _index: usize) -> CaptureStatus {
+        CaptureStatus::Denied
+    }
+
+    fn latest_frame(&mut self, _index: usize) -> Option<(u64, &CameraFrame)> {
+        None
+    }
+}
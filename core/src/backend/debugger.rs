@@ -0,0 +1,48 @@
+//! Connecting an external debugger, such as an IDE, to the player.
+
+/// Carries the messages between the player and an external debugger.
+///
+/// Messages are lines of JSON, as described in `crate::avm2::debugger`. The backend only
+/// moves them around; the player makes sense of them.
+pub trait DebuggerBackend {
+    /// Returns whether a debugger is currently attached.
+    fn is_attached(&self) -> bool;
+
+    /// Sends a message to the attached debugger. The message is dropped if there is none.
+    fn send(&mut self, message: String);
+
+    /// Takes the next message from the debugger, if one has arrived.
+    fn try_receive(&mut self) -> Option<String>;
+
+    /// Waits for the next message from the debugger.
+    ///
+    /// This is only called while the movie is paused, and blocks the player until a message
+    /// arrives. Returns `None` once the debugger has detached.
+    fn receive(&mut self) -> Option<String>;
+}
+
+/// A debugger backend for platforms where debuggers can't attach.
+#[derive(Default)]
+pub struct NullDebuggerBackend;
+
+impl NullDebuggerBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl DebuggerBackend for NullDebuggerBackend {
+    fn is_attached(&self) -> bool {
+        false
+    }
+
+    fn send(&mut self, _message: String) {}
+
+    fn try_receive(&mut self) -> Option<String> {
+        None
+    }
+
+    fn receive(&mut self) -> Option<String> {
+        None
+    }
+}
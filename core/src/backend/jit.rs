@@ -0,0 +1,210 @@
+//! Compiling hot AVM2 methods to native code.
+//!
+//! Methods that are called often, and only do arithmetic on `int`, `uint`, `Number` and
+//! `Boolean` values, are lowered to the small typed IR in this module and handed to the
+//! `JitBackend`. Everything else keeps running in the interpreter.
+//!
+//! The IR is in register form: every value lives in a typed variable, and the AVM2 operand
+//! stack has already been assigned to variables. Conversions between types follow Flash's
+//! coercion rules:
+//!
+//! * `Number` to `int` is ECMA-262 `ToInt32`: `NaN` and infinities become `0`, and other
+//!   values are truncated and wrapped modulo 2^32.
+//! * `int` to `Number` is exact.
+//! * `Boolean` to `int` or `Number` is `1` or `0`.
+//! * `int` or `Number` to `Boolean` is whether the value is neither zero nor `NaN`.
+
+use crate::ecma_conversions::f64_to_wrapping_i32;
+use web_time::Instant;
+
+/// The type of a variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Type {
+    /// A signed 32-bit integer.
+    Int,
+
+    /// A 64-bit float.
+    Number,
+
+    /// A boolean, stored as `0` or `1` wherever backends need an integer.
+    Boolean,
+}
+
+/// A value passed to or returned from compiled code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Slot {
+    Int(i32),
+    Number(f64),
+    Boolean(bool),
+}
+
+impl Slot {
+    pub fn ty(self) -> Type {
+        match self {
+            Slot::Int(_) => Type::Int,
+            Slot::Number(_) => Type::Number,
+            Slot::Boolean(_) => Type::Boolean,
+        }
+    }
+}
+
+/// The index of a variable in `Function::vars`.
+pub type Var = u32;
+
+/// The index of a block in `Function::blocks`.
+pub type BlockId = u32;
+
+/// A method lowered for compilation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    /// The type of every variable. The first `num_params` variables hold the arguments, and
+    /// all other variables start out as zero.
+    pub vars: Vec<Type>,
+
+    pub num_params: usize,
+
+    /// The blocks of the function. Execution starts at the first one.
+    ///
+    /// A jump or branch to a block at or before the block it leaves is a loop back-edge.
+    /// Backends must poll the deadline on back-edges, as otherwise an infinite loop in a movie
+    /// would never return control to the player.
+    pub blocks: Vec<Block>,
+
+    /// The type of the returned value, or `None` if the function returns nothing.
+    pub return_type: Option<Type>,
+}
+
+impl Function {
+    pub fn params(&self) -> &[Type] {
+        &self.vars[..self.num_params]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    pub instructions: Vec<Instruction>,
+    pub exit: Exit,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction {
+    /// Stores a constant, which has the type of `dst`.
+    Const {
+        dst: Var,
+        value: Slot,
+    },
+
+    /// Copies a variable to another of the same type.
+    Move {
+        dst: Var,
+        src: Var,
+    },
+
+    /// Converts a variable to the type of `dst`, which differs from the type of `src`.
+    Convert {
+        dst: Var,
+        src: Var,
+    },
+
+    Unary {
+        op: UnaryOp,
+        dst: Var,
+        src: Var,
+    },
+
+    Binary {
+        op: BinaryOp,
+        dst: Var,
+        lhs: Var,
+        rhs: Var,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOp {
+    /// `Number` to `Number`.
+    Negate,
+
+    /// `int` to `int`, wrapping.
+    NegateI,
+
+    /// `int` to `int`.
+    BitNot,
+
+    /// `Boolean` to `Boolean`.
+    Not,
+
+    /// `int` to `Number`, reading the bits of the `int` as unsigned.
+    ToUint,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinaryOp {
+    /// `Number` operands and result.
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+
+    /// The remainder of a truncating division, with the sign of the dividend, like `fmod`.
+    Modulo,
+
+    /// `int` operands and result, wrapping.
+    AddI,
+    SubtractI,
+    MultiplyI,
+    BitAnd,
+    BitOr,
+    BitXor,
+
+    /// Shifts use the low five bits of the right-hand side.
+    LShift,
+    RShift,
+
+    /// `int` operands read as unsigned, and a `Number` result.
+    URShift,
+
+    /// Operands of the same type, either `int` or `Number`, and a `Boolean` result.
+    ///
+    /// Comparisons involving `NaN` are false.
+    Equal,
+    LessThan,
+    LessEqual,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Exit {
+    Jump(BlockId),
+
+    /// Continues at `if_true` if the `Boolean` variable `cond` is true, otherwise at `if_false`.
+    Branch {
+        cond: Var,
+        if_true: BlockId,
+        if_false: BlockId,
+    },
+
+    /// Returns the variable, which has the function's return type.
+    Return(Option<Var>),
+}
+
+/// Converts a `Number` to an `int` with `ToInt32`, for backends that don't do it inline.
+pub fn number_to_int(n: f64) -> i32 {
+    f64_to_wrapping_i32(n)
+}
+
+/// The deadline passed to compiled code ran out before it returned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Interrupted;
+
+pub trait CompiledFunction {
+    /// Runs the function with arguments of its parameter types.
+    ///
+    /// Returns `Interrupted` if the function was still running at the deadline.
+    fn call(&self, args: &[Slot], deadline: Instant) -> Result<Option<Slot>, Interrupted>;
+}
+
+pub trait JitBackend {
+    /// Compiles a function, or returns `None` if this backend can't compile it. The method
+    /// then keeps running in the interpreter.
+    fn compile(&mut self, function: &Function) -> Option<Box<dyn CompiledFunction>>;
+}
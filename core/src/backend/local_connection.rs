@@ -0,0 +1,202 @@
+//! Connecting the `LocalConnection`s of separate players.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A message sent with `LocalConnection.send()` to a connection of another player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalConnectionMessage {
+    /// The full name of the receiving connection, including its domain prefix.
+    pub connection_name: String,
+
+    /// The method to call and its arguments, as an AMF0 packet.
+    pub data: Vec<u8>,
+}
+
+/// Lets the `LocalConnection`s of this player talk to those of other players, such as
+/// other movies on the same page.
+///
+/// Connections between movies of the same player are handled by the player itself,
+/// and never reach the backend.
+pub trait LocalConnectionBackend {
+    /// Claims a connection name for this player.
+    ///
+    /// Returns `false` if another player is already listening on it.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Releases a connection name claimed with `connect`.
+    fn close(&mut self, name: &str);
+
+    /// Returns whether another player is listening on the given connection name.
+    fn is_connected(&self, name: &str) -> bool;
+
+    /// Sends a message to the player listening on its connection name.
+    fn send(&mut self, message: LocalConnectionMessage);
+
+    /// Takes the messages that other players sent to the connections of this player.
+    fn receive(&mut self) -> Vec<LocalConnectionMessage>;
+}
+
+#[derive(Default)]
+struct Bus {
+    next_id: usize,
+
+    /// The player listening on each connection name.
+    listeners: HashMap<String, usize>,
+
+    /// The messages waiting to be received, by player.
+    inboxes: HashMap<usize, Vec<LocalConnectionMessage>>,
+}
+
+/// Connects the players of a process with each other.
+///
+/// Every player that should be able to reach the others gets its own backend from `join`.
+#[derive(Clone, Default)]
+pub struct LocalConnectionBus(Arc<Mutex<Bus>>);
+
+impl LocalConnectionBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Bus> {
+        self.0.lock().expect("LocalConnectionBus is not reentrant")
+    }
+
+    /// Creates the backend of a new player on this bus.
+    pub fn join(&self) -> InProcessLocalConnectionBackend {
+        let mut bus = self.lock();
+        let id = bus.next_id;
+        bus.next_id += 1;
+        bus.inboxes.insert(id, Vec::new());
+        InProcessLocalConnectionBackend {
+            bus: self.clone(),
+            id,
+        }
+    }
+
+    /// Returns the connection names that players on this bus are listening on.
+    pub fn connection_names(&self) -> Vec<String> {
+        let bus = self.lock();
+        bus.listeners.keys().cloned().collect()
+    }
+
+    /// Queues a message for the player listening on its connection name.
+    ///
+    /// Returns `false` if no player on this bus is listening on it.
+    pub fn deliver(&self, message: LocalConnectionMessage) -> bool {
+        let mut bus = self.lock();
+        let Some(&id) = bus.listeners.get(&message.connection_name) else {
+            return false;
+        };
+        if let Some(inbox) = bus.inboxes.get_mut(&id) {
+            inbox.push(message);
+        }
+        true
+    }
+}
+
+/// A player on a `LocalConnectionBus`.
+///
+/// Its connections are closed once it's dropped.
+pub struct InProcessLocalConnectionBackend {
+    bus: LocalConnectionBus,
+    id: usize,
+}
+
+impl LocalConnectionBackend for InProcessLocalConnectionBackend {
+    fn connect(&mut self, name: &str) -> bool {
+        let mut bus = self.bus.lock();
+        if bus.listeners.contains_key(name) {
+            return false;
+        }
+        bus.listeners.insert(name.to_owned(), self.id);
+        true
+    }
+
+    fn close(&mut self, name: &str) {
+        let mut bus = self.bus.lock();
+        if bus.listeners.get(name) == Some(&self.id) {
+            bus.listeners.remove(name);
+        }
+    }
+
+    fn is_connected(&self, name: &str) -> bool {
+        let bus = self.bus.lock();
+        bus.listeners.get(name).is_some_and(|id| *id != self.id)
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) {
+        self.bus.deliver(message);
+    }
+
+    fn receive(&mut self) -> Vec<LocalConnectionMessage> {
+        let mut bus = self.bus.lock();
+        bus.inboxes
+            .get_mut(&self.id)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for InProcessLocalConnectionBackend {
+    fn drop(&mut self) {
+        let mut bus = self.bus.lock();
+        bus.listeners.retain(|_, id| *id != self.id);
+        bus.inboxes.remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(connection_name: &str) -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            connection_name: connection_name.to_string(),
+            data: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn names_are_claimed_once() {
+        let bus = LocalConnectionBus::new();
+        let mut first = bus.join();
+        let mut second = bus.join();
+
+        assert!(first.connect("localhost:chat"));
+        assert!(!second.connect("localhost:chat"));
+        assert!(second.is_connected("localhost:chat"));
+        assert!(!first.is_connected("localhost:chat"));
+
+        first.close("localhost:chat");
+        assert!(second.connect("localhost:chat"));
+    }
+
+    #[test]
+    fn messages_reach_the_listener() {
+        let bus = LocalConnectionBus::new();
+        let mut sender = bus.join();
+        let mut receiver = bus.join();
+        assert!(receiver.connect("_game"));
+
+        sender.send(message("_game"));
+        sender.send(message("_nobody"));
+        assert_eq!(sender.receive(), vec![]);
+        assert_eq!(receiver.receive(), vec![message("_game")]);
+        assert_eq!(receiver.receive(), vec![]);
+    }
+
+    #[test]
+    fn dropped_players_release_their_names() {
+        let bus = LocalConnectionBus::new();
+        let mut first = bus.join();
+        assert!(first.connect("_game"));
+        drop(first);
+
+        let mut second = bus.join();
+        assert!(!second.is_connected("_game"));
+        assert!(second.connect("_game"));
+        assert_eq!(bus.connection_names(), vec!["_game".to_string()]);
+    }
+}
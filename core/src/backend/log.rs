@@ -2,6 +2,20 @@ pub trait LogBackend {
     fn avm_trace(&self, message: &str);
 }
 
+/// A destination for the output of `trace()`, such as a console shown to the user.
+///
+/// Any number of these may be attached to a player. They receive the same messages as the
+/// `LogBackend`, which is meant for logging rather than for consuming the output.
+pub trait TraceOutput {
+    fn trace(&self, message: &str);
+}
+
+impl<F: Fn(&str)> TraceOutput for F {
+    fn trace(&self, message: &str) {
+        self(message)
+    }
+}
+
 /// Logging backend that just reroutes traces to the log crate
 pub struct NullLogBackend {}
 
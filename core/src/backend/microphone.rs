@@ -0,0 +1,117 @@
+pub use crate::backend::camera::CaptureStatus;
+
+pub trait MicrophoneBackend {
+    /// The names of the microphones available to movies, in the order
+    /// `Microphone.getMicrophone` indexes them.
+    fn names(&self) -> Vec<String>;
+
+    /// Starts capturing from the microphone with the given index, or changes the sample rate of
+    /// a microphone that is already capturing.
+    fn open(&mut self, index: usize, sample_rate: u32);
+
+    /// Stops capturing from the microphone with the given index.
+    fn close(&mut self, index: usize);
+
+    fn status(&self, index: usize) -> CaptureStatus;
+
+    /// Takes the samples captured by the microphone with the given index since the last call.
+    ///
+    /// Samples are mono, in the range `-1.0..=1.0`, at the sample rate the microphone was opened
+    /// with.
+    fn take_samples(&mut self, index: usize) -> Vec<f32>;
+}
+
+/// A microphone backend for platforms without microphones, or where movies may not use them.
+#[derive(Default)]
+pub struct NullMicrophoneBackend;
+
+impl NullMicrophoneBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MicrophoneBackend for NullMicrophoneBackend {
+    fn names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn open(&mut self, _index: usize, _sample_rate: u32) {}
+
+    fn close(&mut self, _index: usize) {}
+
+    fn status(&self, _index: usize) -> CaptureStatus {
+        CaptureStatus::Denied
+    }
+
+    fn take_samples(&mut self, _index: usize) -> Vec<f32> {
+        vec![]
+    }
+}
+
+/// Converts a stream of mono samples from one sample rate to another, by linear interpolation.
+pub struct Resampler {
+    from_rate: u32,
+    to_rate: u32,
+
+    /// The read position, relative to `previous`.
+    position: f64,
+
+    /// The last sample of the previous buffer, which the next one is interpolated from.
+    previous: Option<f32>,
+}
+
+impl Resampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            from_rate,
+            to_rate,
+            position: 0.0,
+            previous: None,
+        }
+    }
+
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        if self.from_rate == self.to_rate || self.from_rate == 0 || self.to_rate == 0 {
+            return input.to_vec();
+        }
+
+        let samples: Vec<f32> = self.previous.iter().chain(input).copied().collect();
+        let step = f64::from(self.from_rate) / f64::from(self.to_rate);
+        let mut out = Vec::with_capacity((samples.len() as f64 / step) as usize + 1);
+        while (self.position as usize) + 1 < samples.len() {
+            let i = self.position as usize;
+            let t = (self.position - i as f64) as f32;
+            out.push(samples[i] * (1.0 - t) + samples[i + 1] * t);
+            self.position += step;
+        }
+
+        if let Some(last) = samples.last() {
+            self.previous = Some(*last);
+            self.position -= (samples.len() - 1) as f64;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resampler_keeps_matching_rates() {
+        let mut resampler = Resampler::new(8000, 8000);
+        assert_eq!(resampler.process(&[0.5, -0.5]), [0.5, -0.5]);
+    }
+
+    #[test]
+    fn resampler_continues_across_buffers() {
+        let mut resampler = Resampler::new(16000, 8000);
+        let samples: Vec<f32> = (0..9).map(|i| i as f32).collect();
+        assert_eq!(resampler.process(&samples), [0.0, 2.0, 4.0, 6.0]);
+
+        // The last sample of the previous buffer is the first one of this one.
+        let samples: Vec<f32> = (9..13).map(|i| i as f32).collect();
+        assert_eq!(resampler.process(&samples), [8.0, 10.0]);
+    }
+}
@@ -301,6 +301,29 @@ pub trait NavigatorBackend: Downcast {
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     );
+
+    /// Whether sockets secured with TLS can be opened with [NavigatorBackend::connect_secure_socket].
+    fn supports_secure_sockets(&self) -> bool {
+        false
+    }
+
+    /// Handle a connection request for a socket secured with TLS, such as a `SecureSocket`.
+    ///
+    /// This works like [NavigatorBackend::connect_socket], except that the data goes through
+    /// a TLS session with the server, whose certificate has to be valid for `host`.
+    fn connect_secure_socket(
+        &mut self,
+        _host: String,
+        _port: u16,
+        _timeout: Duration,
+        handle: SocketHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        sender
+            .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
+            .expect("working channel send");
+    }
 }
 impl_downcast!(NavigatorBackend);
 
@@ -5,6 +5,7 @@ use downcast_rs::Downcast;
 use fluent_templates::loader::langid;
 pub use fluent_templates::LanguageIdentifier;
 use std::borrow::Cow;
+use swf::{Rectangle, Twips};
 use url::Url;
 
 pub type FullscreenError = Cow<'static, str>;
@@ -14,6 +15,10 @@ pub enum FontDefinition<'a> {
     /// A singular DefineFont tag extracted from a swf.
     SwfTag(swf::Font<'a>, &'static swf::Encoding),
 
+    /// A DefineFont tag extracted from a swf, along with the DefineFontInfo tag that
+    /// names it and its glyphs.
+    SwfFont1Tag(swf::FontV1, swf::FontInfo<'a>, &'static swf::Encoding),
+
     /// A font contained in an external file, such as a ttf.
     FontFile {
         name: String,
@@ -60,6 +65,115 @@ impl_downcast!(FileDialogResult);
 /// Future representing a file selection in process
 pub type DialogResultFuture = OwnedFuture<Box<dyn FileDialogResult>, DialogLoaderError>;
 
+/// Identifies an additional `NativeWindow` opened by AIR content.
+///
+/// The primary window is the player itself, and never has an ID.
+pub type NativeWindowId = u32;
+
+/// An image on the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels in RGBA order, with straight (not premultiplied) alpha.
+    pub rgba: Vec<u8>,
+}
+
+/// A page of a print job, rendered as an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintPage {
+    pub width: u32,
+    pub height: u32,
+
+    /// The number of pixels per point (1/72 inch). The image is placed at the top left
+    /// of the printable area of the paper.
+    pub scale: u32,
+
+    /// The pixels in RGBA order. Pages are always opaque.
+    pub rgba: Vec<u8>,
+}
+
+/// Identifies a cursor registered by the movie with `Mouse.registerCursor`.
+pub type CustomCursorId = u32;
+
+/// A frame of a cursor registered by the movie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorImage {
+    pub width: u32,
+    pub height: u32,
+
+    /// The point of the image that is placed at the mouse position.
+    pub hotspot_x: u32,
+    pub hotspot_y: u32,
+
+    /// The pixels in RGBA order, with straight (not premultiplied) alpha.
+    pub rgba: Vec<u8>,
+}
+
+/// The state of an additional `NativeWindow`, as it should be presented by the UI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeWindowDescriptor {
+    pub title: String,
+    /// The position of the window, relative to the page-provided window container.
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub visible: bool,
+    pub transparent: bool,
+}
+
+/// Identifies a `StageWebView` created by AIR content.
+pub type WebViewId = u32;
+
+/// Where a `StageWebView` is displayed, in pixels relative to the top left of the movie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebViewViewport {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A history navigation requested by the movie on a `StageWebView`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebViewNavigation {
+    Back,
+    Forward,
+    Reload,
+    Stop,
+}
+
+/// Something that happened in a `StageWebView`, as reported to the player by the UI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebViewEvent {
+    /// The web view navigated to a new page.
+    LocationChanged {
+        location: String,
+        can_go_back: bool,
+        can_go_forward: bool,
+    },
+
+    /// The current page finished loading.
+    Complete { title: Option<String> },
+
+    /// The current page couldn't be loaded.
+    Error(String),
+}
+
+/// Which part of loading the root movie a progress report is about.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RootMovieLoadPhase {
+    /// The movie is being downloaded. Bytes are counted as they are received, out of the
+    /// length reported by the server, if any.
+    Download,
+
+    /// The movie was downloaded and is being preloaded. Bytes are counted the same way as
+    /// `MovieClip.getBytesLoaded()` and `MovieClip.getBytesTotal()` count them.
+    Preload,
+}
+
 pub trait UiBackend: Downcast {
     fn mouse_visible(&self) -> bool;
 
@@ -68,6 +182,23 @@ pub trait UiBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Prepares the frames of a cursor registered by the movie, so that they can
+    /// later be displayed with `MouseCursor::Custom`.
+    fn register_custom_cursor(&mut self, _id: CustomCursorId, _frames: Vec<CursorImage>) {}
+
+    /// Forgets a cursor previously given to `register_custom_cursor`.
+    fn unregister_custom_cursor(&mut self, _id: CustomCursorId) {}
+
+    /// Hides the mouse and keeps it from moving, so that only its movement is reported
+    /// through `PlayerEvent::MouseMotion`. This is only requested in fullscreen.
+    fn set_mouse_lock(&mut self, _locked: bool) {}
+
+    /// The number of fingers the device's touch screen can track at once,
+    /// or 0 if it's unknown whether there is one.
+    fn max_touch_points(&self) -> u32 {
+        0
+    }
+
     /// Get the clipboard content
     fn clipboard_content(&mut self) -> String;
 
@@ -79,6 +210,24 @@ pub trait UiBackend: Downcast {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Get the image on the clipboard, if there is one.
+    fn clipboard_image(&mut self) -> Option<ClipboardImage> {
+        None
+    }
+
+    /// Sets the clipboard to the given image.
+    fn set_clipboard_image(&mut self, _image: ClipboardImage) {}
+
+    /// Removes the text from the clipboard, keeping any image on it.
+    fn clear_clipboard_content(&mut self) {
+        if self.clipboard_image().is_none() {
+            self.set_clipboard_content(String::new());
+        }
+    }
+
+    /// Removes the image from the clipboard, keeping any text on it.
+    fn clear_clipboard_image(&mut self) {}
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a message about an error during root movie download.
@@ -86,6 +235,22 @@ pub trait UiBackend: Downcast {
     /// by providing a direct .swf link instead.
     fn display_root_movie_download_failed_message(&self, _invalid_swf: bool);
 
+    /// Reports how much of the root movie has loaded so far.
+    /// `bytes_total` is `None` when the length of the download isn't known.
+    fn root_movie_load_progress(
+        &self,
+        _phase: RootMovieLoadPhase,
+        _bytes_loaded: u64,
+        _bytes_total: Option<u64>,
+    ) {
+    }
+
+    /// Called once the root movie has been completely loaded.
+    fn root_movie_loaded(&self) {}
+
+    /// Called when the root movie could not be loaded, with a description of the error.
+    fn root_movie_load_failed(&self, _message: &str) {}
+
     // Unused, but kept in case we need it later.
     fn message(&self, message: &str);
 
@@ -93,6 +258,17 @@ pub trait UiBackend: Downcast {
 
     fn close_virtual_keyboard(&self);
 
+    /// Allows or disallows the input method editor, which is used to compose text
+    /// in languages such as Chinese or Japanese.
+    ///
+    /// It's allowed while an editable text field has focus, unless the movie has
+    /// turned it off with `flash.system.IME.enabled`.
+    fn set_ime_allowed(&mut self, _allowed: bool) {}
+
+    /// Tells the input method editor where the text cursor is, so that it can show
+    /// its candidates next to it. The area is in viewport coordinates.
+    fn set_ime_cursor_area(&mut self, _area: Rectangle<Twips>) {}
+
     fn language(&self) -> LanguageIdentifier;
 
     fn display_unsupported_video(&self, url: Url);
@@ -128,6 +304,84 @@ pub trait UiBackend: Downcast {
 
     /// Mark that any previously open dialog has been closed
     fn close_file_dialog(&mut self);
+
+    /// Creates the surface for an additional `NativeWindow`.
+    ///
+    /// Returns `false` if additional windows cannot be displayed, in which case
+    /// the window still exists for the movie but is never presented.
+    fn open_native_window(
+        &mut self,
+        _id: NativeWindowId,
+        _descriptor: &NativeWindowDescriptor,
+    ) -> bool {
+        false
+    }
+
+    /// Updates the bounds, title or visibility of an additional `NativeWindow`.
+    fn update_native_window(&mut self, _id: NativeWindowId, _descriptor: &NativeWindowDescriptor) {}
+
+    /// Gives keyboard focus to an additional `NativeWindow`.
+    fn focus_native_window(&mut self, _id: NativeWindowId) {}
+
+    /// Displays a newly rendered frame of an additional `NativeWindow`.
+    ///
+    /// `rgba` contains premultiplied RGBA pixels, with rows `row_stride` bytes apart.
+    fn present_native_window(
+        &mut self,
+        _id: NativeWindowId,
+        _width: u32,
+        _height: u32,
+        _rgba: &[u8],
+        _row_stride: u32,
+    ) {
+    }
+
+    /// Destroys the surface of an additional `NativeWindow`.
+    fn close_native_window(&mut self, _id: NativeWindowId) {}
+
+    /// Whether `StageWebView`s can be displayed.
+    /// Movies see this as `StageWebView.isSupported`.
+    fn supports_web_views(&self) -> bool {
+        false
+    }
+
+    /// Creates the overlay for a `StageWebView`. It stays hidden until it's given a viewport.
+    fn create_web_view(&mut self, _id: WebViewId) {}
+
+    /// Shows a `StageWebView` over the given part of the movie, or hides it if `None`.
+    fn set_web_view_viewport(&mut self, _id: WebViewId, _viewport: Option<WebViewViewport>) {}
+
+    /// Loads the page at the given URL into a `StageWebView`.
+    fn load_web_view_url(&mut self, _id: WebViewId, _url: &str) {}
+
+    /// Displays the given text, such as an HTML document, in a `StageWebView`.
+    fn load_web_view_string(&mut self, _id: WebViewId, _text: &str, _mime_type: &str) {}
+
+    /// Goes through the history of a `StageWebView`, or reloads or stops its current page.
+    fn navigate_web_view(&mut self, _id: WebViewId, _navigation: WebViewNavigation) {}
+
+    /// Destroys the overlay of a `StageWebView`.
+    fn dispose_web_view(&mut self, _id: WebViewId) {}
+
+    /// Whether assistive technologies, such as screen readers, are following the movie.
+    /// Movies see this as `Accessibility.active`.
+    fn is_accessibility_active(&self) -> bool {
+        false
+    }
+
+    /// Replaces the description of the stage given to assistive technologies.
+    /// Only called while `is_accessibility_active` is true, and only when the tree changed.
+    fn update_accessibility_tree(&mut self, _nodes: &[AccessibilityNode]) {}
+
+    /// Whether pages can be printed. Movies see this as `PrintJob.isSupported`,
+    /// and can't start a print job without it.
+    fn can_print(&self) -> bool {
+        false
+    }
+
+    /// Prints the pages of a finished print job, usually through the print dialog
+    /// of the system or browser.
+    fn print(&mut self, _pages: Vec<PrintPage>) {}
 }
 impl_downcast!(UiBackend);
 
@@ -150,6 +404,47 @@ pub enum MouseCursor {
     /// The grabby-dragging hand icon.
     /// Equivalent to AS3 `MouseCursor.HAND`.
     Grab,
+
+    /// A frame of a cursor registered by the movie with `Mouse.registerCursor`.
+    /// The player advances the frames of animated cursors.
+    Custom { id: CustomCursorId, frame: u32 },
+}
+
+/// What kind of object an `AccessibilityNode` describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessibilityRole {
+    /// Static text.
+    Text,
+
+    /// A dynamic or input text field.
+    TextField,
+
+    /// A button, or a movie clip acting as one.
+    Button,
+
+    /// A named group of other objects.
+    Graphic,
+}
+
+/// A visible object on the stage, as described to assistive technologies.
+/// Communicated from the core to the UI backend via `UiBackend::update_accessibility_tree`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    pub role: AccessibilityRole,
+
+    /// The text read out for this object.
+    pub name: String,
+
+    /// A longer description of this object, if the movie gave one.
+    pub description: String,
+
+    /// The keyboard shortcut for this object, if the movie gave one.
+    pub shortcut: String,
+
+    /// The area covered by this object, in pixels of the viewport.
+    pub bounds: Rectangle<Twips>,
+
+    pub children: Vec<AccessibilityNode>,
 }
 
 /// UiBackend that does nothing.
@@ -0,0 +1,147 @@
+//! Bidirectional text support, for text in right-to-left scripts such as Arabic and Hebrew.
+//!
+//! Text is stored in logical order, and reordered here into the order that it's drawn in,
+//! following the Unicode Bidirectional Algorithm (UAX #9).
+
+use crate::string::{utils as string_utils, WStr};
+use std::ops::Range;
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo};
+
+/// A piece of text that is drawn in a single direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidiRun {
+    /// The range of the text in this run.
+    pub range: Range<usize>,
+
+    /// Whether this run is drawn from right to left.
+    pub is_rtl: bool,
+}
+
+/// Whether the given character may be drawn differently depending on its surroundings,
+/// or could change the direction of the text around it.
+fn is_complex(c: char) -> bool {
+    // Hebrew, Arabic, Syriac, Thaana, N'Ko, Indic scripts, Thai, Lao, Tibetan, Myanmar,
+    // Khmer and Mongolian, along with the directional formatting characters.
+    matches!(c, '\u{0590}'..='\u{1DFF}' | '\u{200E}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Whether the given text should be shaped with a font's layout tables, rather than drawn
+/// one character at a time.
+pub fn needs_shaping(text: &WStr) -> bool {
+    text.chars().any(|c| c.is_ok_and(is_complex))
+}
+
+/// Whether the given character is written from right to left.
+pub fn is_rtl_char(c: char) -> bool {
+    matches!(bidi_class(c), BidiClass::R | BidiClass::AL)
+}
+
+/// Splits a line of text into the runs that it's drawn as, from left to right.
+///
+/// The direction of each paragraph in the text comes from its first strong character.
+pub fn visual_runs(text: &WStr) -> Vec<BidiRun> {
+    if !text
+        .chars()
+        .any(|c| c.is_ok_and(|c| is_rtl_char(c) || is_complex(c)))
+    {
+        return vec![BidiRun {
+            range: 0..text.len(),
+            is_rtl: false,
+        }];
+    }
+
+    // The algorithm works on UTF-8, so remember where each character came from.
+    let mut string = String::with_capacity(text.len());
+    let mut positions = Vec::with_capacity(text.len());
+    for (pos, c) in text.char_indices() {
+        positions.push((string.len(), pos));
+        string.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+    positions.push((string.len(), text.len()));
+    let to_pos = |offset: usize| match positions.binary_search_by_key(&offset, |(o, _)| *o) {
+        Ok(index) => positions[index].1,
+        Err(index) => positions[index.min(positions.len() - 1)].1,
+    };
+
+    let bidi_info = BidiInfo::new(&string, None);
+    let mut runs = Vec::new();
+    for paragraph in &bidi_info.paragraphs {
+        let (levels, level_runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+        runs.extend(level_runs.into_iter().map(|run| BidiRun {
+            is_rtl: levels[run.start].is_rtl(),
+            range: to_pos(run.start)..to_pos(run.end),
+        }));
+    }
+    runs
+}
+
+/// Whether the character at the given position (or the one before it, at the end of the text)
+/// is written from right to left, which swaps the meaning of the arrow keys.
+pub fn is_rtl_at(text: &WStr, pos: usize) -> bool {
+    let after = text.slice(pos..).and_then(|after| after.chars().next());
+    let before = text
+        .slice(string_utils::prev_char_boundary(text, pos)..pos)
+        .and_then(|before| before.chars().next());
+    after
+        .or(before)
+        .and_then(Result::ok)
+        .is_some_and(is_rtl_char)
+}
+
+/// The mirrored form of a character, as drawn in right-to-left text.
+///
+/// Only needed when the text isn't shaped, as shaping mirrors characters itself.
+pub fn mirror_char(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '<' => '>',
+        '>' => '<',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '«' => '»',
+        '»' => '«',
+        '‹' => '›',
+        '›' => '‹',
+        '≤' => '≥',
+        '≥' => '≤',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(text: &str) -> Vec<(Range<usize>, bool)> {
+        let text: Vec<u16> = text.encode_utf16().collect();
+        visual_runs(WStr::from_units(&text[..]))
+            .into_iter()
+            .map(|run| (run.range, run.is_rtl))
+            .collect()
+    }
+
+    #[test]
+    fn ltr_text_is_one_run() {
+        assert_eq!(runs("hello world"), vec![(0..11, false)]);
+    }
+
+    #[test]
+    fn rtl_text_is_reordered() {
+        // "abc אבג" in a left-to-right paragraph.
+        assert_eq!(runs("abc אבג"), vec![(0..4, false), (4..7, true)]);
+        // "אבג abc" in a right-to-left paragraph.
+        assert_eq!(runs("אבג abc"), vec![(4..7, false), (0..4, true)]);
+    }
+
+    #[test]
+    fn rtl_at_position() {
+        let text: Vec<u16> = "ab אב".encode_utf16().collect();
+        let text = WStr::from_units(&text[..]);
+        assert!(!is_rtl_at(text, 0));
+        assert!(is_rtl_at(text, 3));
+        assert!(is_rtl_at(text, 5));
+    }
+}
@@ -1,10 +1,12 @@
 use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
+use crate::bitmap::readback::ReadbackTracker;
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
-use ruffle_render::backend::RenderBackend;
+use ruffle_render::backend::{DeviceId, RenderBackend};
 use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, PixelRegion, SyncHandle};
 use ruffle_wstr::WStr;
+use std::cell::Cell;
 use std::fmt::Debug;
 use std::ops::Range;
 use swf::{Rectangle, Twips};
@@ -211,9 +213,10 @@ pub struct BitmapData<'gc> {
     /// The bitmap handle for this data.
     ///
     /// This is lazily initialized; a value of `None` indicates that
-    /// initialization has not yet happened.
+    /// initialization has not yet happened. The handle is registered again
+    /// from the CPU-side pixels if the render device changes.
     #[collect(require_static)]
-    bitmap_handle: Option<BitmapHandle>,
+    bitmap_handle: Option<(DeviceId, BitmapHandle)>,
 
     /// The AVM2 side of this `BitmapData`.
     ///
@@ -227,6 +230,10 @@ pub struct BitmapData<'gc> {
     #[collect(require_static)]
     dirty_state: DirtyState,
 
+    /// How often this bitmap has been read from, used to throttle GPU -> CPU readbacks.
+    #[collect(require_static)]
+    readback: Cell<ReadbackTracker>,
+
     /// Holds an egui texture handle, used for rendering this Bitmap in the debug ui.
     /// This is automatically set to `None` when the texture is updated (either from
     /// marking the CPU side dirty, or from performing a GPU -> CPU sync).
@@ -248,11 +255,14 @@ enum DirtyState {
 
 mod wrapper {
     use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
+    use crate::bitmap::readback::{BitmapReadbackMode, BitmapReadbacks};
     use crate::context::RenderContext;
     use crate::display_object::DisplayObjectWeak;
     use gc_arena::{Collect, GcCell, Mutation};
     use ruffle_render::backend::RenderBackend;
-    use ruffle_render::bitmap::{BitmapHandle, PixelRegion, PixelSnapping};
+    use ruffle_render::bitmap::{
+        BitmapHandle, PixelRegion, PixelSnapping, RgbaBufRead, SyncHandle,
+    };
     use ruffle_render::commands::CommandHandler;
     use std::cell::Ref;
 
@@ -312,6 +322,7 @@ mod wrapper {
                     avm2_object: None,
                     display_objects: vec![],
                     dirty_state: DirtyState::Clean,
+                    readback: Default::default(),
                     #[cfg(feature = "egui")]
                     egui_texture: Default::default(),
                 },
@@ -336,6 +347,7 @@ mod wrapper {
                 display_objects: vec![],
                 // We have no GPU texture, so there's no need to mark as dirty
                 dirty_state: DirtyState::Clean,
+                readback: Default::default(),
                 #[cfg(feature = "egui")]
                 egui_texture: Default::default(),
             }
@@ -350,6 +362,10 @@ mod wrapper {
             // `RefCell` instead of a `GcCell`.
             let mut write = unsafe { self.0.borrow_mut() };
             match std::mem::replace(&mut write.dirty_state, DirtyState::Clean) {
+                DirtyState::GpuModified(_, _) if write.lost_gpu_pixels(renderer) => {
+                    tracing::warn!("Discarding BitmapData changes lost with the render device");
+                    write.readback.get_mut().mark_synced();
+                }
                 DirtyState::GpuModified(sync_handle, bounds) => {
                     renderer
                         .resolve_sync_handle(
@@ -360,6 +376,7 @@ mod wrapper {
                         )
                         .expect("Failed to sync BitmapData");
                     write.dirty_state = DirtyState::Clean;
+                    write.readback.get_mut().mark_synced();
                     #[cfg(feature = "egui")]
                     write.egui_texture.borrow_mut().take();
                 }
@@ -397,6 +414,7 @@ mod wrapper {
             let dirty_rect = match write.dirty_state {
                 DirtyState::GpuModified(_, rect) => {
                     write.dirty_state = DirtyState::Clean;
+                    write.readback.get_mut().mark_synced();
                     Some(rect)
                 }
                 DirtyState::CpuModified(_) | DirtyState::Clean => None,
@@ -425,6 +443,88 @@ mod wrapper {
             self.0.read()
         }
 
+        /// Provides read access to the BitmapData pixels, like `read_area`,
+        /// but following the player's `BitmapReadbackMode`.
+        ///
+        /// In `BitmapReadbackMode::Throttled`, frequently read bitmaps may return
+        /// pixels from before the most recent GPU write (see `readback::MAX_STALE_FRAMES`),
+        /// and any other pending readbacks are resolved together with this one.
+        pub fn read_area_throttled(
+            &self,
+            read_area: PixelRegion,
+            renderer: &mut dyn RenderBackend,
+            readbacks: &mut BitmapReadbacks<'gc>,
+        ) -> Ref<'_, BitmapData<'gc>> {
+            if readbacks.mode() == BitmapReadbackMode::Strict {
+                return self.read_area(read_area, renderer);
+            }
+
+            let frame = readbacks.frame();
+            let read = self.0.read();
+            let mut tracker = read.readback.get();
+            let hot = tracker.record_read(frame);
+            let needs_update = matches!(
+                read.dirty_state,
+                DirtyState::GpuModified(_, area) if area.intersects(read_area)
+            );
+            let serve_stale = needs_update && hot && tracker.may_serve_stale(frame);
+            read.readback.set(tracker);
+            drop(read);
+
+            if needs_update {
+                readbacks.queue(*self);
+                if !serve_stale {
+                    readbacks.flush(renderer);
+                }
+            }
+            self.0.read()
+        }
+
+        /// Waits for the pending GPU -> CPU syncs of all of the given bitmaps,
+        /// allowing the renderer to resolve them as a single batch.
+        ///
+        /// `bitmaps` must not contain the same bitmap more than once.
+        pub fn sync_all(bitmaps: &[Self], renderer: &mut dyn RenderBackend) {
+            // SAFETY: See `sync` - we don't update any gc pointers here.
+            let mut writes: Vec<_> = bitmaps
+                .iter()
+                .map(|bitmap| unsafe { bitmap.0.borrow_mut() })
+                .collect();
+
+            let mut batch: Vec<(Box<dyn SyncHandle>, RgbaBufRead<'_>)> = Vec::new();
+            for write in writes.iter_mut() {
+                if !matches!(write.dirty_state, DirtyState::GpuModified(_, _)) {
+                    continue;
+                }
+                if write.lost_gpu_pixels(renderer) {
+                    tracing::warn!("Discarding BitmapData changes lost with the render device");
+                    write.dirty_state = DirtyState::Clean;
+                    write.readback.get_mut().mark_synced();
+                    continue;
+                }
+                let DirtyState::GpuModified(sync_handle, bounds) =
+                    std::mem::replace(&mut write.dirty_state, DirtyState::Clean)
+                else {
+                    unreachable!()
+                };
+                write.readback.get_mut().mark_synced();
+                #[cfg(feature = "egui")]
+                write.egui_texture.borrow_mut().take();
+                batch.push((
+                    sync_handle,
+                    Box::new(move |buffer, buffer_width| {
+                        copy_pixels_to_bitmapdata(write, buffer, buffer_width, bounds)
+                    }),
+                ));
+            }
+
+            if !batch.is_empty() {
+                renderer
+                    .resolve_sync_handles(batch)
+                    .expect("Failed to sync BitmapData");
+            }
+        }
+
         // These methods do not require a sync to complete, as they do not depend on the
         // CPU-side pixels. They are implemented directly on `BitmapDataWrapper`, allowing
         // callers to avoid calling sync()
@@ -575,6 +675,7 @@ impl<'gc> BitmapData<'gc> {
             avm2_object: None,
             display_objects: vec![],
             dirty_state: DirtyState::Clean,
+            readback: Default::default(),
             #[cfg(feature = "egui")]
             egui_texture: Default::default(),
         }
@@ -595,6 +696,7 @@ impl<'gc> BitmapData<'gc> {
             avm2_object: None,
             disposed: false,
             dirty_state: DirtyState::Clean,
+            readback: Default::default(),
             display_objects: vec![],
             #[cfg(feature = "egui")]
             egui_texture: Default::default(),
@@ -616,7 +718,15 @@ impl<'gc> BitmapData<'gc> {
     }
 
     pub fn bitmap_handle(&mut self, renderer: &mut dyn RenderBackend) -> Option<BitmapHandle> {
-        if self.bitmap_handle.is_none() {
+        let device_id = renderer.device_id();
+        let registered_on = self.bitmap_handle.as_ref().map(|(id, _)| *id);
+        if registered_on != Some(device_id) {
+            if registered_on.is_some() {
+                // The old texture went away with its device, along with any changes
+                // that were only made on the GPU. All pixels are uploaded below.
+                self.dirty_state = DirtyState::Clean;
+                self.readback.get_mut().mark_synced();
+            }
             let bitmap = Bitmap::new(
                 self.width(),
                 self.height(),
@@ -627,10 +737,22 @@ impl<'gc> BitmapData<'gc> {
             if let Err(e) = &bitmap_handle {
                 tracing::warn!("Failed to register raw bitmap for BitmapData: {:?}", e);
             }
-            self.bitmap_handle = bitmap_handle.ok();
+            self.bitmap_handle = bitmap_handle.ok().map(|handle| (device_id, handle));
         }
 
-        self.bitmap_handle.clone()
+        self.bitmap_handle
+            .as_ref()
+            .map(|(_, handle)| handle.clone())
+    }
+
+    /// Whether changes made on the GPU can no longer be read back, because
+    /// the device they were made on was lost.
+    fn lost_gpu_pixels(&self, renderer: &dyn RenderBackend) -> bool {
+        renderer.is_device_lost()
+            || self
+                .bitmap_handle
+                .as_ref()
+                .is_some_and(|(device_id, _)| *device_id != renderer.device_id())
     }
 
     pub fn transparency(&self) -> bool {
@@ -739,7 +861,9 @@ impl<'gc> BitmapData<'gc> {
     // Updates the data stored with our `BitmapHandle` if this `BitmapData`
     // is dirty
     pub fn update_dirty_texture(&mut self, renderer: &mut dyn RenderBackend) {
-        let handle = self.bitmap_handle(renderer).unwrap();
+        let Some(handle) = self.bitmap_handle(renderer) else {
+            return;
+        };
         match &self.dirty_state {
             DirtyState::CpuModified(region) => {
                 if let Err(e) = renderer.update_texture(
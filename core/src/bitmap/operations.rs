@@ -1,4 +1,4 @@
-use crate::avm2::bytearray::{ByteArrayError, ByteArrayStorage};
+use crate::avm2::bytearray::{ByteArrayError, ByteArrayStorage, Endian};
 use crate::avm2::error::make_error_2006;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{Activation, Error, Value as Avm2Value};
@@ -6,6 +6,7 @@ use crate::bitmap::bitmap_data::{
     BitmapData, BitmapDataDrawError, BitmapDataWrapper, ChannelOptions, Color, IBitmapDrawable,
     LehmerRng, ThresholdOperation,
 };
+use crate::bitmap::readback::BitmapReadbacks;
 use crate::bitmap::turbulence::Turbulence;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::TDisplayObject;
@@ -13,7 +14,9 @@ use gc_arena::Mutation;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::bitmap::{PixelRegion, PixelSnapping};
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
-use ruffle_render::filters::Filter;
+use ruffle_render::filters::{
+    gradient_filter_colors, DisplacementMapFilter, DisplacementMapFilterMode, Filter,
+};
 use ruffle_render::matrix::Matrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -88,16 +91,17 @@ pub fn set_pixel32<'gc>(
     write.set_cpu_dirty(mc, PixelRegion::for_pixel(x, y));
 }
 
-pub fn get_pixel32(
-    target: BitmapDataWrapper,
+pub fn get_pixel32<'gc>(
+    target: BitmapDataWrapper<'gc>,
     renderer: &mut dyn RenderBackend,
+    readbacks: &mut BitmapReadbacks<'gc>,
     x: u32,
     y: u32,
 ) -> u32 {
     if x >= target.width() || y >= target.height() {
         return 0;
     }
-    let read = target.read_area(PixelRegion::for_pixel(x, y), renderer);
+    let read = target.read_area_throttled(PixelRegion::for_pixel(x, y), renderer, readbacks);
     read.get_pixel32_raw(x, y).to_un_multiplied_alpha().into()
 }
 
@@ -125,16 +129,17 @@ pub fn set_pixel<'gc>(
     write.set_cpu_dirty(mc, PixelRegion::for_pixel(x, y));
 }
 
-pub fn get_pixel(
-    target: BitmapDataWrapper,
+pub fn get_pixel<'gc>(
+    target: BitmapDataWrapper<'gc>,
     renderer: &mut dyn RenderBackend,
+    readbacks: &mut BitmapReadbacks<'gc>,
     x: u32,
     y: u32,
 ) -> u32 {
     if x >= target.width() || y >= target.height() {
         return 0;
     }
-    let read = target.read_area(PixelRegion::for_pixel(x, y), renderer);
+    let read = target.read_area_throttled(PixelRegion::for_pixel(x, y), renderer, readbacks);
     read.get_pixel32_raw(x, y)
         .to_un_multiplied_alpha()
         .with_alpha(0x0)
@@ -562,8 +567,10 @@ pub fn threshold<'gc>(
         Some(source_bitmap.read_area(source_region, renderer))
     };
 
+    let transparency = target.transparency();
     let target = target.sync(renderer);
     let mut write = target.write(mc);
+    let colour = Color::from(colour).to_premultiplied_alpha(transparency);
 
     // Check each pixel
     for y in 0..dest_region.height() {
@@ -575,32 +582,26 @@ pub fn threshold<'gc>(
 
             // Extract source colour
             let source_color = if let Some(source) = &source {
-                source
-                    .get_pixel32_raw(src_x, src_y)
-                    .to_un_multiplied_alpha()
+                source.get_pixel32_raw(src_x, src_y)
             } else {
-                write.get_pixel32_raw(src_x, src_y).to_un_multiplied_alpha()
+                write.get_pixel32_raw(src_x, src_y)
             };
 
             // If the test, as defined by the operation pass then set to input colour
-            if operation.matches(u32::from(source_color) & mask, masked_threshold) {
+            let test_color = u32::from(source_color.to_un_multiplied_alpha());
+            if operation.matches(test_color & mask, masked_threshold) {
                 modified_count += 1;
-                write.set_pixel32_raw(dest_x, dest_y, Color::from(colour));
-            } else {
+                write.set_pixel32_raw(dest_x, dest_y, colour);
+            } else if copy_source {
                 // If the test fails, but copy_source is true then take the colour from the source
-                if copy_source {
-                    let new_color = if let Some(source) = &source {
-                        source
-                            .get_pixel32_raw(dest_x, dest_y)
-                            .to_un_multiplied_alpha()
-                    } else {
-                        write
-                            .get_pixel32_raw(dest_x, dest_y)
-                            .to_un_multiplied_alpha()
-                    };
-
-                    write.set_pixel32_raw(dest_x, dest_y, new_color);
-                }
+                let new_color = if transparency {
+                    source_color
+                } else {
+                    source_color
+                        .to_un_multiplied_alpha()
+                        .to_premultiplied_alpha(false)
+                };
+                write.set_pixel32_raw(dest_x, dest_y, new_color);
             }
             if let Some(dirty_area) = &mut dirty_area {
                 dirty_area.encompass(dest_x, dest_y);
@@ -703,6 +704,7 @@ pub fn palette_map<'gc>(
         Some(source_bitmap.read_area(source_region, renderer))
     };
 
+    let transparency = target.transparency();
     let target = target.sync(renderer);
     let mut write = target.write(mc);
 
@@ -727,7 +729,7 @@ pub fn palette_map<'gc>(
             let a = channel_arrays.3[source_color.alpha() as usize];
 
             let sum = u32::wrapping_add(u32::wrapping_add(r, g), u32::wrapping_add(b, a));
-            let mix_color = Color::from(sum).to_premultiplied_alpha(true);
+            let mix_color = Color::from(sum).to_premultiplied_alpha(transparency);
 
             write.set_pixel32_raw(dest_x, dest_y, mix_color);
         }
@@ -1200,6 +1202,11 @@ pub fn copy_pixels_with_alpha_source<'gc>(
     write.set_cpu_dirty(context.gc_context, dirty_region);
 }
 
+/// Applies a filter to an area of `source`, writing the result into `target`.
+///
+/// `map_bitmap` is the map of a `DisplacementMapFilter`, which is needed to apply one
+/// without the help of the renderer.
+#[allow(clippy::too_many_arguments)]
 pub fn apply_filter<'gc>(
     context: &mut UpdateContext<'gc>,
     target: BitmapDataWrapper<'gc>,
@@ -1208,6 +1215,7 @@ pub fn apply_filter<'gc>(
     source_size: (u32, u32),
     dest_point: (u32, u32),
     filter: Filter,
+    map_bitmap: Option<BitmapDataWrapper<'gc>>,
 ) {
     // Prevent creating 0x0 textures.
     // FIXME: this is not correct.
@@ -1234,16 +1242,57 @@ pub fn apply_filter<'gc>(
             return;
         }
 
-        // Until we support these filters, treat this like a copy
-        copy_on_cpu(
-            context.gc_context,
-            context.renderer,
-            source,
-            target,
-            source_region,
-            dest_region,
-            false,
-        );
+        match (filter, map_bitmap) {
+            (Filter::ConvolutionFilter(filter), _) => convolve_on_cpu(
+                context.gc_context,
+                context.renderer,
+                source,
+                target,
+                source_region,
+                dest_region,
+                &filter,
+            ),
+            (Filter::DisplacementMapFilter(filter), Some(map_bitmap)) => displace_on_cpu(
+                context.gc_context,
+                context.renderer,
+                source,
+                target,
+                map_bitmap,
+                source_region,
+                dest_region,
+                &filter,
+            ),
+            (Filter::GradientGlowFilter(filter), _) => gradient_filter_on_cpu(
+                context.gc_context,
+                context.renderer,
+                source,
+                target,
+                source_region,
+                dest_region,
+                &filter,
+                false,
+            ),
+            (Filter::GradientBevelFilter(filter), _) => gradient_filter_on_cpu(
+                context.gc_context,
+                context.renderer,
+                source,
+                target,
+                source_region,
+                dest_region,
+                &filter,
+                true,
+            ),
+            // Until we support these filters, treat this like a copy
+            _ => copy_on_cpu(
+                context.gc_context,
+                context.renderer,
+                source,
+                target,
+                source_region,
+                dest_region,
+                false,
+            ),
+        }
         return;
     }
 
@@ -1269,6 +1318,342 @@ pub fn apply_filter<'gc>(
     }
 }
 
+/// A copy of all of the pixels of a bitmap, so that it can be read while being written to.
+fn read_pixels<'gc>(
+    bitmap: BitmapDataWrapper<'gc>,
+    renderer: &mut dyn RenderBackend,
+) -> Vec<Color> {
+    let region = PixelRegion::for_whole_size(bitmap.width(), bitmap.height());
+    bitmap.read_area(region, renderer).pixels().to_vec()
+}
+
+/// Applies a `ConvolutionFilter` without the help of the renderer.
+fn convolve_on_cpu<'gc>(
+    context: &Mutation<'gc>,
+    renderer: &mut dyn RenderBackend,
+    source: BitmapDataWrapper<'gc>,
+    dest: BitmapDataWrapper<'gc>,
+    source_region: PixelRegion,
+    dest_region: PixelRegion,
+    filter: &swf::ConvolutionFilter,
+) {
+    let pixels = read_pixels(source, renderer);
+    let width = source.width() as i32;
+    let height = source.height() as i32;
+    let columns = filter.num_matrix_cols as i32;
+    let rows = filter.num_matrix_rows as i32;
+    let divisor = if filter.divisor == 0.0 {
+        1.0
+    } else {
+        filter.divisor
+    };
+    let channels = |color: Color| {
+        [
+            color.red() as f32,
+            color.green() as f32,
+            color.blue() as f32,
+            color.alpha() as f32,
+        ]
+    };
+    let default_color = channels(Color::argb(
+        filter.default_color.a,
+        filter.default_color.r,
+        filter.default_color.g,
+        filter.default_color.b,
+    ));
+    let pixel = |x: i32, y: i32| {
+        let color = pixels[(y * width + x) as usize].to_un_multiplied_alpha();
+        channels(color)
+    };
+
+    let dest = dest.sync(renderer);
+    let mut write = dest.write(context);
+    let transparency = write.transparency();
+    for y in 0..dest_region.height() {
+        for x in 0..dest_region.width() {
+            let source_x = (source_region.x_min + x) as i32;
+            let source_y = (source_region.y_min + y) as i32;
+
+            let mut total = [0.0; 4];
+            for matrix_y in 0..rows {
+                for matrix_x in 0..columns {
+                    let weight = filter
+                        .matrix
+                        .get((matrix_y * columns + matrix_x) as usize)
+                        .copied()
+                        .unwrap_or_default();
+                    if weight == 0.0 {
+                        continue;
+                    }
+                    let sample_x = source_x + matrix_x - columns / 2;
+                    let sample_y = source_y + matrix_y - rows / 2;
+                    let color = if (0..width).contains(&sample_x) && (0..height).contains(&sample_y)
+                    {
+                        pixel(sample_x, sample_y)
+                    } else if filter.is_clamped() {
+                        pixel(sample_x.clamp(0, width - 1), sample_y.clamp(0, height - 1))
+                    } else {
+                        default_color
+                    };
+                    for (total, channel) in total.iter_mut().zip(color) {
+                        *total += weight * channel;
+                    }
+                }
+            }
+
+            let channel = |total: f32| (total / divisor + filter.bias).clamp(0.0, 255.0) as u8;
+            let alpha = if filter.is_preserve_alpha() {
+                pixels[(source_y * width + source_x) as usize].alpha()
+            } else {
+                channel(total[3])
+            };
+            let color = Color::argb(
+                alpha,
+                channel(total[0]),
+                channel(total[1]),
+                channel(total[2]),
+            )
+            .to_premultiplied_alpha(transparency);
+            write.set_pixel32_raw(dest_region.x_min + x, dest_region.y_min + y, color);
+        }
+    }
+
+    write.set_cpu_dirty(context, dest_region);
+}
+
+/// Applies a `DisplacementMapFilter` without the help of the renderer.
+#[allow(clippy::too_many_arguments)]
+fn displace_on_cpu<'gc>(
+    context: &Mutation<'gc>,
+    renderer: &mut dyn RenderBackend,
+    source: BitmapDataWrapper<'gc>,
+    dest: BitmapDataWrapper<'gc>,
+    map_bitmap: BitmapDataWrapper<'gc>,
+    source_region: PixelRegion,
+    dest_region: PixelRegion,
+    filter: &DisplacementMapFilter,
+) {
+    let pixels = read_pixels(source, renderer);
+    let map_pixels = read_pixels(map_bitmap, renderer);
+    let width = source.width() as i32;
+    let height = source.height() as i32;
+    let map_width = map_bitmap.width() as i32;
+    let map_height = map_bitmap.height() as i32;
+
+    // Components without a channel, and pixels outside of the map, don't move anything.
+    let component = |color: Color, component: u8| match component {
+        1 => color.red(),
+        2 => color.green(),
+        4 => color.blue(),
+        8 => color.alpha(),
+        _ => 128,
+    };
+    let color = Color::argb(
+        filter.color.a,
+        filter.color.r,
+        filter.color.g,
+        filter.color.b,
+    )
+    .to_premultiplied_alpha(true);
+
+    let dest = dest.sync(renderer);
+    let mut write = dest.write(context);
+    for y in 0..dest_region.height() {
+        for x in 0..dest_region.width() {
+            let source_x = (source_region.x_min + x) as i32;
+            let source_y = (source_region.y_min + y) as i32;
+
+            let map_x = source_x - filter.map_point.0;
+            let map_y = source_y - filter.map_point.1;
+            let (offset_x, offset_y) = if (0..map_width).contains(&map_x)
+                && (0..map_height).contains(&map_y)
+            {
+                let map_color = map_pixels[(map_y * map_width + map_x) as usize];
+                let offset =
+                    |value: u8, scale: f32| ((value as f32 - 128.0) * scale / 256.0).floor() as i32;
+                (
+                    offset(component(map_color, filter.component_x), filter.scale_x),
+                    offset(component(map_color, filter.component_y), filter.scale_y),
+                )
+            } else {
+                (0, 0)
+            };
+
+            let mut displaced_x = source_x + offset_x;
+            let mut displaced_y = source_y + offset_y;
+            let in_bounds = (0..width).contains(&displaced_x) && (0..height).contains(&displaced_y);
+            let result = if in_bounds {
+                pixels[(displaced_y * width + displaced_x) as usize]
+            } else {
+                match filter.mode {
+                    DisplacementMapFilterMode::Wrap => {
+                        displaced_x = displaced_x.rem_euclid(width);
+                        displaced_y = displaced_y.rem_euclid(height);
+                        pixels[(displaced_y * width + displaced_x) as usize]
+                    }
+                    DisplacementMapFilterMode::Clamp => {
+                        displaced_x = displaced_x.clamp(0, width - 1);
+                        displaced_y = displaced_y.clamp(0, height - 1);
+                        pixels[(displaced_y * width + displaced_x) as usize]
+                    }
+                    DisplacementMapFilterMode::Ignore => {
+                        pixels[(source_y * width + source_x) as usize]
+                    }
+                    DisplacementMapFilterMode::Color => color,
+                }
+            };
+            write.set_pixel32_raw(dest_region.x_min + x, dest_region.y_min + y, result);
+        }
+    }
+
+    write.set_cpu_dirty(context, dest_region);
+}
+
+/// Applies a `GradientGlowFilter` or `GradientBevelFilter` without the help of the renderer.
+#[allow(clippy::too_many_arguments)]
+fn gradient_filter_on_cpu<'gc>(
+    context: &Mutation<'gc>,
+    renderer: &mut dyn RenderBackend,
+    source: BitmapDataWrapper<'gc>,
+    dest: BitmapDataWrapper<'gc>,
+    source_region: PixelRegion,
+    dest_region: PixelRegion,
+    filter: &swf::GradientFilter,
+    is_bevel: bool,
+) {
+    let pixels = read_pixels(source, renderer);
+    let width = source.width() as usize;
+    let height = source.height() as usize;
+    let mut blurred: Vec<f32> = pixels
+        .iter()
+        .map(|color| color.alpha() as f32 / 255.0)
+        .collect();
+    blur_on_cpu(&mut blurred, width, height, &filter.inner_blur_filter());
+    let blurred_at = |x: i32, y: i32| {
+        if (0..width as i32).contains(&x) && (0..height as i32).contains(&y) {
+            blurred[y as usize * width + x as usize]
+        } else {
+            0.0
+        }
+    };
+
+    let gradient = gradient_filter_colors(&filter.colors).map(|color| {
+        let alpha = color.a as f32 / 255.0;
+        [
+            color.r as f32 / 255.0 * alpha,
+            color.g as f32 / 255.0 * alpha,
+            color.b as f32 / 255.0 * alpha,
+            alpha,
+        ]
+    });
+    let gradient_color =
+        |position: f32| gradient[(position.clamp(0.0, 1.0) * 255.0).round() as usize];
+
+    // Glows are cast away from the light, like a drop shadow.
+    let distance = filter.distance.to_f32();
+    let angle = filter.angle.to_f32();
+    let direction = if is_bevel { 1.0 } else { -1.0 };
+    let offset_x = (angle.cos() * distance * direction).round() as i32;
+    let offset_y = (angle.sin() * distance * direction).round() as i32;
+    let strength = filter.strength.to_f32();
+    let inner = filter.is_inner() || filter.is_on_top();
+    let outer = !filter.is_inner() || filter.is_on_top();
+
+    let dest = dest.sync(renderer);
+    let mut write = dest.write(context);
+    for y in 0..dest_region.height() {
+        for x in 0..dest_region.width() {
+            let source_x = (source_region.x_min + x) as i32;
+            let source_y = (source_region.y_min + y) as i32;
+            let blur_left = blurred_at(source_x + offset_x, source_y + offset_y);
+            let blur_right = blurred_at(source_x - offset_x, source_y - offset_y);
+
+            let glow = if is_bevel {
+                // The highlight is at the start of the gradient, and the shadow at the end.
+                gradient_color(0.5 - (blur_left - blur_right) * strength * 0.5)
+            } else if inner && !outer {
+                gradient_color((1.0 - blur_left) * strength)
+            } else {
+                gradient_color(blur_left * strength)
+            };
+
+            let color = pixels[source_y as usize * width + source_x as usize];
+            let source_color = [
+                color.red() as f32 / 255.0,
+                color.green() as f32 / 255.0,
+                color.blue() as f32 / 255.0,
+                color.alpha() as f32 / 255.0,
+            ];
+            let result: [f32; 4] = std::array::from_fn(|i| {
+                if inner && outer {
+                    if filter.is_knockout() {
+                        glow[i]
+                    } else {
+                        source_color[i] - source_color[i] * glow[3] + glow[i]
+                    }
+                } else if inner {
+                    if filter.is_knockout() {
+                        glow[i] * source_color[3]
+                    } else {
+                        glow[i] * source_color[3] + source_color[i] * (1.0 - glow[3])
+                    }
+                } else if filter.is_knockout() {
+                    glow[i] - glow[i] * source_color[3]
+                } else {
+                    source_color[i] + glow[i] - glow[i] * source_color[3]
+                }
+            });
+            let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            let color = Color::argb(
+                channel(result[3]),
+                channel(result[0]),
+                channel(result[1]),
+                channel(result[2]),
+            );
+            write.set_pixel32_raw(dest_region.x_min + x, dest_region.y_min + y, color);
+        }
+    }
+
+    write.set_cpu_dirty(context, dest_region);
+}
+
+/// Blurs a single channel of a bitmap in the same way as a `BlurFilter`,
+/// with a box blur in each direction for every pass.
+fn blur_on_cpu(values: &mut [f32], width: usize, height: usize, filter: &swf::BlurFilter) {
+    let blur_x = (filter.blur_x.to_f32().round() as usize).max(1);
+    let blur_y = (filter.blur_y.to_f32().round() as usize).max(1);
+    let mut line = Vec::with_capacity(width.max(height));
+    for _ in 0..filter.num_passes() {
+        for y in 0..height {
+            line.clear();
+            line.extend_from_slice(&values[y * width..(y + 1) * width]);
+            box_blur_line(&line, blur_x, |x, value| values[y * width + x] = value);
+        }
+        for x in 0..width {
+            line.clear();
+            line.extend((0..height).map(|y| values[y * width + x]));
+            box_blur_line(&line, blur_y, |y, value| values[y * width + x] = value);
+        }
+    }
+}
+
+/// Averages each value of a line with the values around it, in a window of the given size.
+/// Values outside of the line count as zero.
+fn box_blur_line(line: &[f32], size: usize, mut set: impl FnMut(usize, f32)) {
+    let before = size / 2;
+    let after = size - before;
+    let mut sum: f32 = line.iter().take(after).sum();
+    for index in 0..line.len() {
+        set(index, sum / size as f32);
+        if let Some(value) = line.get(index + after) {
+            sum += value;
+        }
+        if let Some(value) = index.checked_sub(before).map(|i| line[i]) {
+            sum -= value;
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn copy_on_cpu<'gc>(
     context: &Mutation<'gc>,
@@ -1615,8 +2000,9 @@ pub fn draw<'gc>(
 }
 
 pub fn get_vector<'gc>(
-    target: BitmapDataWrapper,
+    target: BitmapDataWrapper<'gc>,
     renderer: &mut dyn RenderBackend,
+    readbacks: &mut BitmapReadbacks<'gc>,
     x: i32,
     y: i32,
     width: i32,
@@ -1627,7 +2013,7 @@ pub fn get_vector<'gc>(
 
     let mut result = Vec::with_capacity((region.width() * region.height()) as usize);
 
-    let read = target.read_area(region, renderer);
+    let read = target.read_area_throttled(region, renderer, readbacks);
 
     for y in region.y_min..region.y_max {
         for x in region.x_min..region.x_max {
@@ -1682,7 +2068,7 @@ pub fn set_vector<'gc>(
 
 pub fn get_pixels_as_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    target: BitmapDataWrapper,
+    target: BitmapDataWrapper<'gc>,
     x: i32,
     y: i32,
     width: i32,
@@ -1692,15 +2078,41 @@ pub fn get_pixels_as_byte_array<'gc>(
     let mut region = PixelRegion::for_region_i32(x, y, width, height);
     region.clamp(target.width(), target.height());
 
-    let read = target.read_area(region, activation.context.renderer);
+    // Grow the destination once and write straight into its buffer,
+    // rather than growing it by four bytes for every pixel.
+    let start = result.position();
+    let end = match start
+        .checked_add(region.width() as usize * region.height() as usize * 4)
+        .filter(|end| *end <= u32::MAX as usize)
+    {
+        Some(end) => end,
+        None => return Err(ByteArrayError::IndexOutOfBounds.to_avm(activation)),
+    };
+    if result.len() < end {
+        result.set_length(end);
+    }
+    let endian = result.endian();
+
+    let read = target.read_area_throttled(
+        region,
+        activation.context.renderer,
+        activation.context.bitmap_readbacks,
+    );
+    let mut chunks = result.bytes_mut()[start..end].chunks_exact_mut(4);
     for y in region.y_min..region.y_max {
         for x in region.x_min..region.x_max {
-            let color = read.get_pixel32_raw(x, y);
-            result
-                .write_unsigned_int(color.to_un_multiplied_alpha().into())
-                .map_err(|e| e.to_avm(activation))?;
+            let color = u32::from(read.get_pixel32_raw(x, y).to_un_multiplied_alpha());
+            let bytes = match endian {
+                Endian::Big => color.to_be_bytes(),
+                Endian::Little => color.to_le_bytes(),
+            };
+            chunks
+                .next()
+                .expect("Destination should fit the whole region")
+                .copy_from_slice(&bytes);
         }
     }
+    result.set_position(end);
 
     Ok(())
 }
@@ -1918,7 +2330,8 @@ pub fn pixel_dissolve<'gc>(
     let feistel_block_size: u32 = get_feistel_block_size(final_pixel_sequence_length);
     let permutation_length = 1 << feistel_block_size;
     // Raw permutation index.
-    let mut raw_perm_index = (random_seed % (permutation_length as i32)) as u32;
+    // Negative seeds wrap around, rather than starting before the sequence.
+    let mut raw_perm_index = random_seed.rem_euclid(permutation_length as i32) as u32;
 
     for _ in 0..num_pixels {
         // Feistel permutation index.
@@ -0,0 +1,222 @@
+//! Throttling of GPU -> CPU readbacks for `BitmapData` pixel queries.
+//!
+//! Reading pixels from a `BitmapData` that was last drawn to on the GPU normally
+//! requires waiting for the GPU to finish and copying the pixels back, which
+//! stalls the render pipeline. Content that samples a GPU-resident bitmap every
+//! frame (minimaps, color pickers) pays that cost on every single call.
+//!
+//! In `BitmapReadbackMode::Throttled`, we track how often each bitmap is read.
+//! Bitmaps that are read frequently keep serving their existing CPU pixels as a
+//! "shadow copy" for a short staleness window after a GPU write, and the shadow
+//! is refreshed at the end of the frame. Bitmaps without a shadow still need an
+//! immediate readback, but all readbacks that are pending at that point are
+//! resolved together, so that the renderer can perform them as a single batch.
+
+use crate::bitmap::bitmap_data::BitmapDataWrapper;
+use gc_arena::Collect;
+use ruffle_render::backend::RenderBackend;
+
+/// A bitmap that is read more than this many times within a window of
+/// `HOT_WINDOW_FRAMES` frames is considered to be read frequently.
+pub const HOT_READ_THRESHOLD: u32 = 8;
+
+/// The length (in frames) of the window used to count reads.
+pub const HOT_WINDOW_FRAMES: u32 = 4;
+
+/// The number of frames that a frequently-read bitmap may serve stale pixels for,
+/// counted from the first read after a GPU write.
+///
+/// A value of `1` means that reads may observe the contents from before a GPU write
+/// until the end of the frame following the one that performed the write.
+/// In practice the shadow is refreshed at the end of every frame, so reads
+/// made in a later frame always see up-to-date pixels.
+pub const MAX_STALE_FRAMES: u32 = 1;
+
+/// Controls how `BitmapData` pixel reads wait for pending GPU work.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitmapReadbackMode {
+    /// Every read of GPU-modified pixels blocks until the GPU has finished
+    /// and the pixels have been copied back.
+    ///
+    /// This matches the behavior of Flash Player exactly.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "strict"))]
+    Strict,
+
+    /// Frequently read bitmaps may serve pixels that are up to `MAX_STALE_FRAMES`
+    /// frames old, and other readbacks are batched.
+    ///
+    /// This changes the observable timing of pixel reads (e.g. a `getPixel` made
+    /// straight after a `draw` may see the previous contents), so it should only
+    /// be enabled for content that is known to tolerate it.
+    #[cfg_attr(feature = "serde", serde(rename = "throttled"))]
+    Throttled,
+}
+
+/// Per-bitmap statistics used to decide whether a bitmap keeps a shadow copy.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReadbackTracker {
+    /// The frame at which the current counting window started.
+    window_start: u32,
+
+    /// The number of reads made since `window_start`.
+    reads_in_window: u32,
+
+    /// Whether this bitmap is currently considered to be read frequently.
+    hot: bool,
+
+    /// The frame in which stale pixels were first served after a GPU write.
+    stale_since: Option<u32>,
+}
+
+impl ReadbackTracker {
+    /// Records a pixel read made during `frame`, and returns whether this
+    /// bitmap is read frequently enough to keep a shadow copy.
+    pub fn record_read(&mut self, frame: u32) -> bool {
+        let elapsed = frame.wrapping_sub(self.window_start);
+        if elapsed >= HOT_WINDOW_FRAMES {
+            // Only the window that just ended counts; if there was a gap
+            // after it, the bitmap is no longer being read frequently.
+            self.hot = elapsed < 2 * HOT_WINDOW_FRAMES && self.reads_in_window > HOT_READ_THRESHOLD;
+            self.window_start = frame;
+            self.reads_in_window = 0;
+        }
+
+        self.reads_in_window = self.reads_in_window.saturating_add(1);
+        if self.reads_in_window > HOT_READ_THRESHOLD {
+            self.hot = true;
+        }
+        self.hot
+    }
+
+    /// Whether the CPU pixels may still be served during `frame`,
+    /// even though the GPU holds newer contents.
+    pub fn may_serve_stale(&mut self, frame: u32) -> bool {
+        let since = *self.stale_since.get_or_insert(frame);
+        frame.wrapping_sub(since) <= MAX_STALE_FRAMES
+    }
+
+    /// Marks the CPU pixels as up to date with the GPU.
+    pub fn mark_synced(&mut self) {
+        self.stale_since = None;
+    }
+}
+
+/// Player-wide readback state, shared by all `BitmapData`s.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct BitmapReadbacks<'gc> {
+    #[collect(require_static)]
+    mode: BitmapReadbackMode,
+
+    /// The number of frames that have been run, used as a clock by `ReadbackTracker`.
+    frame: u32,
+
+    /// Bitmaps with a pending GPU -> CPU sync that will be resolved together,
+    /// either when a read cannot be served from a shadow copy or at the end of the frame.
+    ///
+    /// This never contains the same bitmap twice.
+    pending: Vec<BitmapDataWrapper<'gc>>,
+}
+
+impl<'gc> BitmapReadbacks<'gc> {
+    pub fn new(mode: BitmapReadbackMode) -> Self {
+        Self {
+            mode,
+            frame: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn mode(&self) -> BitmapReadbackMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: BitmapReadbackMode) {
+        self.mode = mode;
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Queues a bitmap to be synced with the next batch of readbacks.
+    pub fn queue(&mut self, bitmap: BitmapDataWrapper<'gc>) {
+        if !self.pending.iter().any(|pending| pending.ptr_eq(bitmap)) {
+            self.pending.push(bitmap);
+        }
+    }
+
+    /// Resolves all queued readbacks as a single batch.
+    pub fn flush(&mut self, renderer: &mut dyn RenderBackend) {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            BitmapDataWrapper::sync_all(&pending, renderer);
+        }
+    }
+
+    /// Refreshes all shadow copies and advances the frame clock.
+    /// This should be called once at the end of every frame.
+    pub fn end_frame(&mut self, renderer: &mut dyn RenderBackend) {
+        self.flush(renderer);
+        self.frame = self.frame.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn becomes_hot_after_threshold() {
+        let mut tracker = ReadbackTracker::default();
+        for _ in 0..HOT_READ_THRESHOLD {
+            assert!(!tracker.record_read(0));
+        }
+        assert!(tracker.record_read(0));
+    }
+
+    #[test]
+    fn reads_spread_over_window_count() {
+        let mut tracker = ReadbackTracker::default();
+        let mut hot = false;
+        for frame in 0..HOT_WINDOW_FRAMES {
+            for _ in 0..3 {
+                hot = tracker.record_read(frame);
+            }
+        }
+        assert!(hot);
+    }
+
+    #[test]
+    fn stays_hot_across_windows() {
+        let mut tracker = ReadbackTracker::default();
+        for _ in 0..=HOT_READ_THRESHOLD {
+            tracker.record_read(0);
+        }
+        // The first read of the next window inherits the previous window's result.
+        assert!(tracker.record_read(HOT_WINDOW_FRAMES));
+    }
+
+    #[test]
+    fn cools_down_after_gap() {
+        let mut tracker = ReadbackTracker::default();
+        for _ in 0..=HOT_READ_THRESHOLD {
+            tracker.record_read(0);
+        }
+        assert!(!tracker.record_read(HOT_WINDOW_FRAMES * 3));
+    }
+
+    #[test]
+    fn stale_window() {
+        let mut tracker = ReadbackTracker::default();
+        assert!(tracker.may_serve_stale(10));
+        assert!(tracker.may_serve_stale(10 + MAX_STALE_FRAMES));
+        assert!(!tracker.may_serve_stale(11 + MAX_STALE_FRAMES));
+
+        tracker.mark_synced();
+        assert!(tracker.may_serve_stale(20));
+    }
+}
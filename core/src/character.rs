@@ -7,6 +7,7 @@ use crate::display_object::{
 };
 use crate::font::Font;
 use gc_arena::{Collect, GcCell};
+use ruffle_render::backend::DeviceId;
 use ruffle_render::bitmap::{BitmapHandle, BitmapSize};
 use swf::DefineBitsLossless;
 
@@ -19,9 +20,10 @@ pub enum Character<'gc> {
     Bitmap {
         #[collect(require_static)]
         compressed: CompressedBitmap,
-        /// A lazily constructed GPU handle, used when performing fills with this bitmap
+        /// A lazily constructed GPU handle, used when performing fills with this bitmap,
+        /// along with the device it lives on
         #[collect(require_static)]
-        handle: RefCell<Option<BitmapHandle>>,
+        handle: RefCell<Option<(DeviceId, BitmapHandle)>>,
         /// The bitmap class set by `SymbolClass` - this is used when we instantaite
         /// a `Bitmap` displayobject.
         avm2_bitmapdata_class: GcCell<'gc, BitmapClass<'gc>>,
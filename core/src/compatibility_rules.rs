@@ -1,6 +1,7 @@
 use url::Url;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UrlRewriteRule {
     pub host: String,
     pub replacement: String,
@@ -15,10 +16,51 @@ impl UrlRewriteRule {
     }
 }
 
-#[derive(Debug, Clone)]
+/// A named group of fixes, built in or loaded from a ruleset document.
+///
+/// The movie overrides only apply to movies loaded from one of `swf_hosts`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct RuleSet {
     name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
     swf_domain_rewrite_rules: Vec<UrlRewriteRule>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    swf_hosts: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    player_version: Option<u8>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    frame_rate: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    spoofed_domain: Option<String>,
+}
+
+/// A document of rule sets, such as one downloaded to fix specific games.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct RuleSetDocument {
+    pub rule_sets: Vec<RuleSet>,
+}
+
+/// What the rules change about the player of a specific movie.
+///
+/// These have to be known before the player is created, so it's up to the frontend
+/// to apply them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MovieOverrides {
+    pub player_version: Option<u8>,
+    pub frame_rate: Option<f64>,
+
+    /// The URL that the movie is told it was loaded from, with the spoofed domain.
+    pub spoofed_url: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +97,7 @@ impl CompatibilityRules {
                 "*.konggames.com",
                 "chat.kongregate.com",
             )],
+            ..Default::default()
         };
 
         Self {
@@ -62,6 +105,67 @@ impl CompatibilityRules {
         }
     }
 
+    /// The names of all enabled rule sets.
+    pub fn rule_set_names(&self) -> impl Iterator<Item = &str> {
+        self.rule_sets.iter().map(|rule_set| rule_set.name.as_str())
+    }
+
+    /// Disables every rule set whose name the given predicate returns `false` for.
+    pub fn retain_rule_sets(&mut self, mut predicate: impl FnMut(&str) -> bool) {
+        self.rule_sets.retain(|rule_set| predicate(&rule_set.name));
+    }
+
+    /// Adds the rule sets of a document, replacing any enabled rule set of the same name.
+    pub fn add_rule_sets(&mut self, document: RuleSetDocument) {
+        for rule_set in document.rule_sets {
+            self.rule_sets
+                .retain(|existing| existing.name != rule_set.name);
+            self.rule_sets.push(rule_set);
+        }
+    }
+
+    /// Returns how the player of the movie at `swf_url` should be set up.
+    ///
+    /// When several rule sets apply, the last one to override a value wins.
+    pub fn movie_overrides(&self, swf_url: &str) -> MovieOverrides {
+        let mut overrides = MovieOverrides::default();
+        let Ok(url) = Url::parse(swf_url) else {
+            return overrides;
+        };
+        let Some(host) = url.host_str() else {
+            return overrides;
+        };
+
+        for rule_set in &self.rule_sets {
+            if !rule_set
+                .swf_hosts
+                .iter()
+                .any(|expected| domain_matches(expected, host))
+            {
+                continue;
+            }
+            tracing::info!(
+                "Overriding player settings due to compatibility ruleset '{}'",
+                rule_set.name
+            );
+            if let Some(version) = rule_set.player_version {
+                overrides.player_version = Some(version);
+            }
+            if let Some(frame_rate) = rule_set.frame_rate {
+                overrides.frame_rate = Some(frame_rate);
+            }
+            if let Some(domain) = &rule_set.spoofed_domain {
+                let mut spoofed_url = url.clone();
+                match spoofed_url.set_host(Some(domain)) {
+                    Ok(()) => overrides.spoofed_url = Some(spoofed_url.to_string()),
+                    Err(e) => tracing::warn!("Couldn't spoof swf domain {domain}: {e}"),
+                }
+            }
+        }
+
+        overrides
+    }
+
     pub fn rewrite_swf_url(&self, original_url: String) -> String {
         let mut url = match Url::parse(&original_url) {
             Ok(url) => url,
@@ -123,7 +227,9 @@ pub fn domain_matches(expected: &str, actual: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::compatibility_rules::domain_matches;
+    use crate::compatibility_rules::{
+        domain_matches, CompatibilityRules, MovieOverrides, RuleSet, RuleSetDocument,
+    };
 
     #[test]
     fn test_domain_matches() {
@@ -138,4 +244,41 @@ mod tests {
         assert!(!domain_matches("bar.example.com", "foo.example.com"));
         assert!(!domain_matches("bar.foo.example.com", "foo.example.com"));
     }
+
+    #[test]
+    fn test_movie_overrides() {
+        let mut rules = CompatibilityRules::empty();
+        rules.add_rule_sets(RuleSetDocument {
+            rule_sets: vec![
+                RuleSet {
+                    name: "old_game".to_string(),
+                    swf_hosts: vec!["*.games.example.com".to_string()],
+                    player_version: Some(9),
+                    frame_rate: Some(24.0),
+                    ..Default::default()
+                },
+                RuleSet {
+                    name: "sitelocked_game".to_string(),
+                    swf_hosts: vec!["cdn.games.example.com".to_string()],
+                    player_version: Some(10),
+                    spoofed_domain: Some("www.example.org".to_string()),
+                    ..Default::default()
+                },
+            ],
+        });
+
+        assert_eq!(
+            rules.movie_overrides("https://cdn.games.example.com/game.swf?level=1"),
+            MovieOverrides {
+                player_version: Some(10),
+                frame_rate: Some(24.0),
+                spoofed_url: Some("https://www.example.org/game.swf?level=1".to_string()),
+            }
+        );
+        assert_eq!(
+            rules.movie_overrides("https://example.com/game.swf"),
+            MovieOverrides::default()
+        );
+        assert_eq!(rules.rule_set_names().count(), 2);
+    }
 }
@@ -42,6 +42,46 @@ impl FromStr for Letterbox {
     }
 }
 
+/// How the player treats cross-domain policy files, which servers use to let movies
+/// from other domains load their data (`crossdomain.xml`) or connect to their sockets.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename = "policy_file_mode")
+)]
+pub enum PolicyFileMode {
+    /// Loads and socket connections that no policy file permits fail with a security error,
+    /// as they do in Flash Player.
+    #[cfg_attr(feature = "serde", serde(rename = "enforce"))]
+    Enforce,
+
+    /// Policy files are checked, but a warning is logged instead of blocking
+    /// anything that they don't permit.
+    #[cfg_attr(feature = "serde", serde(rename = "warn"))]
+    Warn,
+
+    /// Policy files aren't checked at all.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "ignore"))]
+    Ignore,
+}
+
+impl FromStr for PolicyFileMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mode = match s {
+            "enforce" => PolicyFileMode::Enforce,
+            "warn" => PolicyFileMode::Warn,
+            "ignore" => PolicyFileMode::Ignore,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(mode)
+    }
+}
+
 /// The networking API access mode of the Ruffle player.
 /// This setting is only used on web.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -72,3 +112,27 @@ pub enum NetworkingAccessMode {
     #[cfg_attr(feature = "serde", serde(rename = "none"))]
     None,
 }
+
+/// Overrides for what the movie is told about the player, through `System.capabilities`
+/// in AVM1 and `flash.system.Capabilities` in AVM2.
+///
+/// Some content only runs on specific versions of Flash Player, or on specific platforms.
+/// Every string that is `None` is reported as usual.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CapabilitiesConfig {
+    /// The version string, such as `WIN 32,0,0,0`.
+    pub version: Option<String>,
+
+    /// The operating system, such as `Windows 8`.
+    pub os: Option<String>,
+
+    /// The type of player, such as `PlugIn` or `StandAlone`.
+    pub player_type: Option<String>,
+
+    /// The language code, such as `en`.
+    pub language: Option<String>,
+
+    /// The URL-encoded string of all capabilities.
+    pub server_string: Option<String>,
+}
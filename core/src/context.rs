@@ -12,14 +12,18 @@ use crate::avm2::api_version::ApiVersion;
 use crate::avm2::object::LoaderInfoObject;
 use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::TObject as _;
-use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
+use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject, SoundObject as Avm2SoundObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
-    log::LogBackend,
+    camera::CameraBackend,
+    debugger::DebuggerBackend,
+    log::{LogBackend, TraceOutput},
+    microphone::MicrophoneBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
     ui::UiBackend,
 };
+use crate::bitmap::readback::BitmapReadbacks;
 use crate::context_menu::ContextMenuState;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
@@ -29,10 +33,16 @@ use crate::input::InputManager;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::local_connection::LocalConnections;
+use crate::locale::get_current_date_time;
+use crate::native_window::NativeWindows;
 use crate::net_connection::NetConnections;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
+use crate::policy_file::PolicyFiles;
 use crate::prelude::*;
+use crate::printing::PrintJob;
+use crate::profiler::Profiler;
+use crate::replay::ReplayClock;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, StringContext};
@@ -40,6 +50,8 @@ use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
+use crate::web_view::WebViews;
+use chrono::{DateTime, Utc};
 use core::fmt;
 use gc_arena::{Collect, Mutation};
 use rand::rngs::SmallRng;
@@ -72,6 +84,9 @@ pub struct UpdateContext<'gc> {
     /// A collection of stubs encountered during this movie.
     pub stub_tracker: &'gc mut StubCollection,
 
+    /// Records the time spent in ActionScript functions while a profile is being taken.
+    pub profiler: &'gc mut Profiler,
+
     /// The library containing character definitions for this SWF.
     /// Used to instantiate a `DisplayObject` of a given ID.
     pub library: &'gc mut Library<'gc>,
@@ -107,11 +122,25 @@ pub struct UpdateContext<'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'gc mut dyn StorageBackend,
 
+    /// The camera backend, used by `flash.media.Camera` to capture video.
+    pub camera: &'gc mut dyn CameraBackend,
+
+    /// The microphone backend, used by `flash.media.Microphone` to capture audio.
+    pub microphone: &'gc mut dyn MicrophoneBackend,
+
+    /// The debugger backend, used to talk to an attached debugger.
+    pub debugger: &'gc mut dyn DebuggerBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
     pub log: &'gc mut dyn LogBackend,
 
+    /// The destinations of `trace()` output, besides the log.
+    ///
+    /// **DO NOT** use this field directly, use the `avm_trace` method instead.
+    pub trace_outputs: &'gc [Box<dyn TraceOutput>],
+
     /// The video backend, used for video decoding
     pub video: &'gc mut dyn VideoBackend,
 
@@ -181,6 +210,9 @@ pub struct UpdateContext<'gc> {
     /// The instant at which the current update started.
     pub update_start: Instant,
 
+    /// The time as seen by the movie, if a replay is being recorded or played back.
+    pub replay_clock: Option<ReplayClock>,
+
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
     pub max_execution_duration: Duration,
@@ -225,6 +257,21 @@ pub struct UpdateContext<'gc> {
     /// Currently, this is just used for handling `Loader.loadBytes`
     #[allow(clippy::type_complexity)]
     pub post_frame_callbacks: &'gc mut Vec<PostFrameCallback<'gc>>,
+
+    /// Pending GPU -> CPU readbacks of `BitmapData` pixels.
+    pub bitmap_readbacks: &'gc mut BitmapReadbacks<'gc>,
+
+    /// Additional `NativeWindow`s opened by AIR content.
+    pub native_windows: &'gc mut NativeWindows<'gc>,
+
+    /// `StageWebView`s created by AIR content.
+    pub web_views: &'gc mut WebViews<'gc>,
+
+    /// The print job started by `PrintJob.start()`, if it wasn't sent yet.
+    pub print_job: &'gc mut Option<PrintJob>,
+
+    /// The cross-domain policy files that were fetched for the movie.
+    pub policy_files: &'gc PolicyFiles,
 }
 
 /// Convenience methods for controlling audio.
@@ -264,6 +311,13 @@ impl<'gc> UpdateContext<'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(
+        &mut self,
+        source: Avm2SoundObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        self.audio_manager.start_dynamic_sound(self.audio, source)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -448,7 +502,27 @@ impl<'gc> UpdateContext<'gc> {
     }
 
     pub fn avm_trace(&self, message: &str) {
-        self.log.avm_trace(&message.replace('\r', "\n"));
+        let message = message.replace('\r', "\n");
+        self.log.avm_trace(&message);
+        for output in self.trace_outputs {
+            output.trace(&message);
+        }
+    }
+
+    /// The number of milliseconds since the movie started, as reported by `getTimer`.
+    pub fn timer_millis(&self) -> u32 {
+        match self.replay_clock {
+            Some(clock) => clock.elapsed_millis(),
+            None => Instant::now().duration_since(self.start_time).as_millis() as u32,
+        }
+    }
+
+    /// The current date and time, as reported by `Date`.
+    pub fn current_date_time(&self) -> DateTime<Utc> {
+        match self.replay_clock {
+            Some(clock) => clock.date_time(),
+            None => get_current_date_time(),
+        }
     }
 }
 
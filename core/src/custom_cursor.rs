@@ -0,0 +1,126 @@
+//! Cursors chosen by movies through `Mouse.cursor`, and registered with `Mouse.registerCursor`.
+
+use crate::backend::ui::{CursorImage, CustomCursorId, MouseCursor, UiBackend};
+use std::collections::HashMap;
+
+/// A cursor that was registered by a movie under some name.
+struct RegisteredCursor {
+    id: CustomCursorId,
+    frame_count: u32,
+
+    /// The number of frames shown per second, for animated cursors.
+    frame_rate: f64,
+}
+
+/// The cursors of a player, as controlled by `flash.ui.Mouse`.
+pub struct CustomCursors {
+    registered: HashMap<String, RegisteredCursor>,
+    next_id: CustomCursorId,
+
+    /// The value of `Mouse.cursor`.
+    selected: String,
+
+    /// How long the selected cursor has been animating for, in milliseconds.
+    animation_time: f64,
+}
+
+impl Default for CustomCursors {
+    fn default() -> Self {
+        Self {
+            registered: HashMap::new(),
+            next_id: 0,
+            selected: "auto".to_string(),
+            animation_time: 0.0,
+        }
+    }
+}
+
+impl CustomCursors {
+    /// Maps the names of the built-in cursors of `flash.ui.MouseCursor`.
+    fn builtin(name: &str) -> Option<Option<MouseCursor>> {
+        match name {
+            "auto" => Some(None),
+            "arrow" => Some(Some(MouseCursor::Arrow)),
+            "button" => Some(Some(MouseCursor::Hand)),
+            "hand" => Some(Some(MouseCursor::Grab)),
+            "ibeam" => Some(Some(MouseCursor::IBeam)),
+            _ => None,
+        }
+    }
+
+    /// Registers the frames of a cursor under the given name, replacing any cursor
+    /// that was previously registered with it.
+    pub fn register(
+        &mut self,
+        ui: &mut dyn UiBackend,
+        name: String,
+        frames: Vec<CursorImage>,
+        frame_rate: f64,
+    ) {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+
+        let frame_count = frames.len() as u32;
+        ui.register_custom_cursor(id, frames);
+        let cursor = RegisteredCursor {
+            id,
+            frame_count,
+            frame_rate,
+        };
+        if let Some(previous) = self.registered.insert(name, cursor) {
+            ui.unregister_custom_cursor(previous.id);
+        }
+    }
+
+    /// Removes a registered cursor. If it was selected, the cursor goes back to `auto`.
+    pub fn unregister(&mut self, ui: &mut dyn UiBackend, name: &str) {
+        if let Some(cursor) = self.registered.remove(name) {
+            ui.unregister_custom_cursor(cursor.id);
+            if self.selected == name {
+                self.selected = "auto".to_string();
+            }
+        }
+    }
+
+    /// The value of `Mouse.cursor`.
+    pub fn selected(&self) -> &str {
+        &self.selected
+    }
+
+    /// Selects a cursor by the name of a built-in or registered cursor.
+    /// Returns `false` if there's no cursor with that name.
+    pub fn select(&mut self, name: &str) -> bool {
+        if Self::builtin(name).is_none() && !self.registered.contains_key(name) {
+            return false;
+        }
+        if self.selected != name {
+            self.selected = name.to_string();
+            self.animation_time = 0.0;
+        }
+        true
+    }
+
+    /// Advances the animation of the selected cursor.
+    pub fn tick(&mut self, dt: f64) {
+        self.animation_time += dt;
+    }
+
+    /// The cursor to display instead of the one chosen by what's under the mouse,
+    /// or `None` if the movie left it on `auto`.
+    pub fn forced_cursor(&self) -> Option<MouseCursor> {
+        if let Some(builtin) = Self::builtin(&self.selected) {
+            return builtin;
+        }
+
+        let cursor = self.registered.get(&self.selected)?;
+        let elapsed_frames = if cursor.frame_rate > 0.0 {
+            (self.animation_time * cursor.frame_rate / 1000.0) as u64
+        } else {
+            0
+        };
+        Some(MouseCursor::Custom {
+            id: cursor.id,
+            frame: (elapsed_frames % cursor.frame_count.max(1) as u64) as u32,
+        })
+    }
+}
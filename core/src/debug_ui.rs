@@ -2,6 +2,7 @@ mod avm1;
 mod avm2;
 mod display_object;
 mod domain;
+mod gc;
 mod handle;
 mod movie;
 
@@ -10,11 +11,13 @@ use crate::debug_ui::avm1::Avm1ObjectWindow;
 use crate::debug_ui::avm2::Avm2ObjectWindow;
 use crate::debug_ui::display_object::{DisplayObjectSearchWindow, DisplayObjectWindow};
 use crate::debug_ui::domain::DomainListWindow;
+use crate::debug_ui::gc::GarbageCollectorWindow;
 use crate::debug_ui::handle::{
     AVM1ObjectHandle, AVM2ObjectHandle, DisplayObjectHandle, DomainHandle,
 };
 use crate::debug_ui::movie::{MovieListWindow, MovieWindow};
 use crate::display_object::TDisplayObject;
+use crate::gc_scheduler::GcStats;
 use crate::tag_utils::SwfMovie;
 use gc_arena::DynamicRootSet;
 use hashbrown::HashMap;
@@ -35,6 +38,7 @@ pub struct DebugUi {
     movie_list: Option<MovieListWindow>,
     domain_list: Option<DomainListWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
+    garbage_collector: Option<GarbageCollectorWindow>,
 }
 
 #[derive(Debug)]
@@ -50,6 +54,7 @@ pub enum Message {
     ShowDomains,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
+    ShowGarbageCollector,
 }
 
 impl DebugUi {
@@ -58,6 +63,7 @@ impl DebugUi {
         egui_ctx: &egui::Context,
         context: &mut UpdateContext,
         movie_offset: f64,
+        gc_stats: GcStats,
     ) {
         let mut messages = std::mem::take(&mut self.queued_messages);
 
@@ -97,6 +103,12 @@ impl DebugUi {
             }
         }
 
+        if let Some(mut garbage_collector) = self.garbage_collector.take() {
+            if garbage_collector.show(egui_ctx, gc_stats) {
+                self.garbage_collector = Some(garbage_collector);
+            }
+        }
+
         for message in messages {
             match message {
                 Message::TrackDisplayObject(object) => {
@@ -132,6 +144,9 @@ impl DebugUi {
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
+                Message::ShowGarbageCollector => {
+                    self.garbage_collector = Some(Default::default());
+                }
             }
         }
     }
@@ -0,0 +1,54 @@
+use crate::gc_scheduler::GcStats;
+use egui::{Grid, Window};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+pub struct GarbageCollectorWindow {}
+
+impl GarbageCollectorWindow {
+    pub fn show(&mut self, egui_ctx: &egui::Context, stats: GcStats) -> bool {
+        let mut keep_open = true;
+
+        Window::new("Garbage Collector")
+            .open(&mut keep_open)
+            .show(egui_ctx, |ui| {
+                Grid::new("garbage_collector_stats")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Heap Size");
+                        ui.label(format!("{:.1} MiB", stats.heap_size as f64 / 1048576.0));
+                        ui.end_row();
+
+                        ui.label("Collections");
+                        ui.label(stats.collections.to_string());
+                        ui.end_row();
+
+                        ui.label("Deferred");
+                        ui.label(stats.deferred.to_string());
+                        ui.end_row();
+
+                        ui.label("Last Pause");
+                        ui.label(format_pause(stats.last_pause));
+                        ui.end_row();
+
+                        ui.label("Longest Pause");
+                        ui.label(format_pause(stats.longest_pause));
+                        ui.end_row();
+
+                        ui.label("Average Pause");
+                        let average = stats
+                            .total_pause
+                            .checked_div(stats.collections.try_into().unwrap_or(u32::MAX))
+                            .unwrap_or_default();
+                        ui.label(format_pause(average));
+                        ui.end_row();
+                    });
+            });
+
+        keep_open
+    }
+}
+
+fn format_pause(pause: Duration) -> String {
+    format!("{:.2} ms", pause.as_secs_f64() * 1000.0)
+}
@@ -9,6 +9,7 @@ use crate::avm2::{
 use crate::context::{RenderContext, UpdateContext};
 use crate::drawing::Drawing;
 use crate::loader::LoadManager;
+use crate::native_window::NativeWindows;
 use crate::prelude::*;
 use crate::string::{AvmString, WString};
 use crate::tag_utils::SwfMovie;
@@ -35,8 +36,10 @@ mod interactive;
 mod loader_display;
 mod morph_shape;
 mod movie_clip;
+mod scaling_grid;
 mod stage;
 mod text;
+mod transform_3d;
 mod video;
 
 use crate::avm1::Activation;
@@ -50,19 +53,20 @@ pub use avm2_button::Avm2Button;
 pub use bitmap::{Bitmap, BitmapClass};
 #[allow(unused)]
 pub use edit_text::LayoutDebugBoxesFlag;
-pub use edit_text::{AutoSizeMode, EditText, TextSelection};
+pub use edit_text::{AutoSizeMode, EditText, EditTextStyleSheet, TextSelection};
 pub use graphic::Graphic;
 pub use interactive::{Avm2MousePick, InteractiveObject, TInteractiveObject};
 pub use loader_display::LoaderDisplay;
 pub use morph_shape::MorphShape;
 pub use movie_clip::{MovieClip, MovieClipWeak, Scene};
-use ruffle_render::backend::{BitmapCacheEntry, RenderBackend};
+use ruffle_render::backend::{BitmapCacheEntry, DeviceId, RenderBackend};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
-pub use text::Text;
+pub use text::{Text, TextGlyph};
+pub use transform_3d::{PerspectiveProjection, Transform3D};
 pub use video::Video;
 
 use self::loader_display::LoaderDisplayWeak;
@@ -106,6 +110,9 @@ pub struct BitmapCache {
     /// The current contents of the cache, if any. Values are post-filters.
     bitmap: Option<BitmapInfo>,
 
+    /// The render device that `bitmap` was created on
+    device_id: DeviceId,
+
     /// Whether we warned that this bitmap was too large to be cached
     warned_for_oversize: bool,
 }
@@ -119,7 +126,13 @@ impl BitmapCache {
         self.matrix_a = f32::NAN;
     }
 
-    fn is_dirty(&self, other: &Matrix, source_width: u16, source_height: u16) -> bool {
+    fn is_dirty(
+        &self,
+        other: &Matrix,
+        source_width: u16,
+        source_height: u16,
+        device_id: DeviceId,
+    ) -> bool {
         self.matrix_a != other.a
             || self.matrix_b != other.b
             || self.matrix_c != other.c
@@ -127,6 +140,7 @@ impl BitmapCache {
             || self.source_width != source_width
             || self.source_height != source_height
             || self.bitmap.is_none()
+            || self.device_id != device_id
     }
 
     /// Clears any dirtiness and ensure there's an appropriately sized texture allocated
@@ -150,10 +164,14 @@ impl BitmapCache {
         self.source_height = source_height;
         self.draw_offset = draw_offset;
         if let Some(current) = &mut self.bitmap {
-            if current.width == actual_width && current.height == actual_height {
+            if current.width == actual_width
+                && current.height == actual_height
+                && self.device_id == renderer.device_id()
+            {
                 return; // No need to resize it
             }
         }
+        self.device_id = renderer.device_id();
         let acceptable_size = if swf_version > 9 {
             let total = actual_width as u32 * actual_height as u32;
             actual_width < 8191 && actual_height < 8191 && total < 16777215
@@ -268,6 +286,21 @@ pub struct DisplayObjectBase<'gc> {
     /// None means not cached, Some means cached.
     #[collect(require_static)]
     cache: Option<BitmapCache>,
+
+    /// The matrix that the bitmap cache is drawn with (`DisplayObject.cacheAsBitmapMatrix`),
+    /// instead of the concatenated matrix of this object.
+    #[collect(require_static)]
+    cache_as_bitmap_matrix: Option<Matrix>,
+
+    /// The position, rotation and scale of this display object along the z axis,
+    /// if it has been placed in 3D space.
+    #[collect(require_static)]
+    transform_3d: Option<Box<Transform3D>>,
+
+    /// The perspective that the descendants of this display object are seen with,
+    /// if it differs from that of its ancestors.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
 }
 
 impl Default for DisplayObjectBase<'_> {
@@ -297,6 +330,9 @@ impl Default for DisplayObjectBase<'_> {
             next_scroll_rect: Default::default(),
             scaling_grid: Default::default(),
             cache: None,
+            cache_as_bitmap_matrix: None,
+            transform_3d: None,
+            perspective_projection: None,
         }
     }
 }
@@ -341,6 +377,30 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.set_scale_rotation_cached(false);
     }
 
+    pub fn transform_3d(&self) -> Option<&Transform3D> {
+        self.transform_3d.as_deref()
+    }
+
+    fn set_transform_3d(&mut self, transform_3d: Transform3D) -> bool {
+        let changed = self.transform_3d() != Some(&transform_3d);
+        self.set_transformed_by_script(true);
+        // Flat objects are drawn like any other.
+        self.transform_3d =
+            (transform_3d != Transform3D::default()).then(|| Box::new(transform_3d));
+        self.recheck_cache_as_bitmap();
+        changed
+    }
+
+    pub fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.perspective_projection
+    }
+
+    fn set_perspective_projection(&mut self, value: Option<PerspectiveProjection>) -> bool {
+        let changed = self.perspective_projection != value;
+        self.perspective_projection = value;
+        changed
+    }
+
     pub fn color_transform(&self) -> &ColorTransform {
         &self.transform.color_transform
     }
@@ -719,6 +779,16 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.cache.as_mut()
     }
 
+    fn cache_as_bitmap_matrix(&self) -> Option<Matrix> {
+        self.cache_as_bitmap_matrix
+    }
+
+    fn set_cache_as_bitmap_matrix(&mut self, matrix: Option<Matrix>) -> bool {
+        let changed = self.cache_as_bitmap_matrix != matrix;
+        self.cache_as_bitmap_matrix = matrix;
+        changed
+    }
+
     /// Invalidates a cached bitmap, if it exists.
     /// This may only be called once per frame - the first call will return true, regardless of
     /// if there was a cache.
@@ -739,8 +809,35 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.remove(DisplayObjectFlags::CACHE_INVALIDATED);
     }
 
+    /// Marks this object as changed since the stage was last described to assistive technologies.
+    /// Returns false if it already was, in which case its ancestors already know too.
+    fn invalidate_accessibility(&mut self) -> bool {
+        if self
+            .flags
+            .contains(DisplayObjectFlags::ACCESSIBILITY_INVALIDATED)
+        {
+            return false;
+        }
+        self.flags
+            .insert(DisplayObjectFlags::ACCESSIBILITY_INVALIDATED);
+        true
+    }
+
+    fn accessibility_invalidated(&self) -> bool {
+        self.flags
+            .contains(DisplayObjectFlags::ACCESSIBILITY_INVALIDATED)
+    }
+
+    fn clear_accessibility_invalidated(&mut self) {
+        self.flags
+            .remove(DisplayObjectFlags::ACCESSIBILITY_INVALIDATED);
+    }
+
     fn recheck_cache_as_bitmap(&mut self) {
-        let should_cache = self.is_bitmap_cached_preference() || !self.filters.is_empty();
+        // Objects in 3D space are drawn flat into their cache, which is then projected.
+        let should_cache = self.is_bitmap_cached_preference()
+            || !self.filters.is_empty()
+            || self.transform_3d.is_some();
         if should_cache && self.cache.is_none() {
             self.cache = Some(Default::default());
         } else if !should_cache && self.cache.is_some() {
@@ -812,7 +909,12 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
-    context.transform_stack.push(this.base().transform());
+    let parent_matrix = context.transform_stack.transform().matrix;
+    let projection = transform_3d::projection(this);
+    context.transform_stack.push(&Transform {
+        matrix: this.placement_matrix(),
+        color_transform: *this.base().color_transform(),
+    });
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
@@ -820,9 +922,20 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         None
     };
 
-    let cache_info = if context.use_bitmap_cache && this.is_bitmap_cached() {
+    let cache_info = if context.use_bitmap_cache && this.base().cache.is_some() {
         let mut cache_info: Option<DrawCacheInfo> = None;
-        let base_transform = context.transform_stack.transform();
+        let mut base_transform = context.transform_stack.transform();
+        // With a `cacheAsBitmapMatrix`, the cache is drawn once with that matrix and then
+        // transformed along with the object, instead of being redrawn as it changes.
+        if let Some(matrix) = this.cache_as_bitmap_matrix() {
+            base_transform.matrix = Matrix {
+                tx: base_transform.matrix.tx,
+                ty: base_transform.matrix.ty,
+                ..matrix
+            };
+        } else if projection.is_some() {
+            base_transform.matrix = parent_matrix * *this.base().matrix();
+        }
         let bounds: Rectangle<Twips> = this.render_bounds_with_transform(
             &base_transform.matrix,
             false, // we want to do the filter growth for this object ourselves, to know the offsets
@@ -858,7 +971,8 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                     y_max: filter_rect.y_max.to_pixels().ceil() as i32,
                 };
                 let draw_offset = Point::new(filter_rect.x_min, filter_rect.y_min);
-                if cache.is_dirty(&base_transform.matrix, width, height) {
+                let device_id = context.renderer.device_id();
+                if cache.is_dirty(&base_transform.matrix, width, height, device_id) {
                     cache.update(
                         context.renderer,
                         base_transform.matrix,
@@ -935,7 +1049,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                 use_bitmap_cache: true,
                 stage: context.stage,
             };
-            this.render_self(&mut offscreen_context);
+            scaling_grid::render_with_scaling_grid(this, &mut offscreen_context);
             offscreen_context.cache_draws.push(BitmapCacheEntry {
                 handle: cache_info.handle.clone(),
                 commands: offscreen_context.commands,
@@ -945,15 +1059,52 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         }
 
         // When rendering it back, ensure we're only keeping the translation - scale/rotation is within the image already
+        let cache_as_bitmap_matrix = this.cache_as_bitmap_matrix();
         apply_standard_mask_and_scroll(this, context, |context| {
+            if let Some(projection) = projection {
+                // The image was drawn flat, and is now seen in perspective.
+                let image_to_local = cache_info
+                    .base_transform
+                    .matrix
+                    .inverse()
+                    .unwrap_or_default()
+                    * Matrix::translate(
+                        cache_info.base_transform.matrix.tx + offset_x,
+                        cache_info.base_transform.matrix.ty + offset_y,
+                    );
+                context.commands.render_bitmap_projected(
+                    cache_info.handle,
+                    parent_matrix * projection * image_to_local,
+                    cache_info.base_transform.color_transform,
+                    true,
+                );
+                return;
+            }
+            let current_matrix = context.transform_stack.transform().matrix;
+            let matrix = match cache_as_bitmap_matrix {
+                // The image was drawn with another matrix, which needs to be undone.
+                Some(_) => {
+                    current_matrix
+                        * cache_info
+                            .base_transform
+                            .matrix
+                            .inverse()
+                            .unwrap_or_default()
+                        * Matrix::translate(
+                            cache_info.base_transform.matrix.tx + offset_x,
+                            cache_info.base_transform.matrix.ty + offset_y,
+                        )
+                }
+                None => Matrix {
+                    tx: current_matrix.tx + offset_x,
+                    ty: current_matrix.ty + offset_y,
+                    ..Default::default()
+                },
+            };
             context.commands.render_bitmap(
                 cache_info.handle,
                 Transform {
-                    matrix: Matrix {
-                        tx: context.transform_stack.transform().matrix.tx + offset_x,
-                        ty: context.transform_stack.transform().matrix.ty + offset_y,
-                        ..Default::default()
-                    },
+                    matrix,
                     color_transform: cache_info.base_transform.color_transform,
                 },
                 true,
@@ -973,21 +1124,24 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                 .commands
                 .draw_rect(background, Matrix::create_box_from_rectangle(&bounds));
         }
-        apply_standard_mask_and_scroll(this, context, |context| this.render_self(context));
+        apply_standard_mask_and_scroll(this, context, |context| {
+            scaling_grid::render_with_scaling_grid(this, context)
+        });
     }
 
     if let Some(original_commands) = original_commands {
         let sub_commands = std::mem::replace(&mut context.commands, original_commands);
         // If there's nothing to draw, throw away the blend entirely.
         if !sub_commands.is_empty() {
-            let render_blend_mode = if let ExtendedBlendMode::Shader = blend_mode {
+            let render_blend_mode = match (blend_mode, this.blend_shader()) {
+                (ExtendedBlendMode::Shader, Some(shader)) => RenderBlendMode::Shader(shader),
                 // Note - Flash appears to let you set `dobj.blendMode = BlendMode.SHADER` without
                 // having `dobj.blendShader` result, but the resulting rendered displayobject
-                // seems to be corrupted. For now, let's panic, and see if any swfs actually
-                // rely on this behavior.
-                RenderBlendMode::Shader(this.blend_shader().expect("Missing blend shader"))
-            } else {
-                RenderBlendMode::Builtin(blend_mode.try_into().unwrap())
+                // seems to be corrupted. We just draw it without blending.
+                (ExtendedBlendMode::Shader, None) => {
+                    RenderBlendMode::Builtin(swf::BlendMode::Normal)
+                }
+                (blend_mode, _) => RenderBlendMode::Builtin(blend_mode.try_into().unwrap()),
             };
             context.commands.blend(sub_commands, render_blend_mode);
         }
@@ -1140,7 +1294,7 @@ pub trait TDisplayObject<'gc>:
 
     /// The local bounding box of this object including children, in its parent's coordinate system.
     fn local_bounds(&self) -> Rectangle<Twips> {
-        self.bounds_with_transform(self.base().matrix())
+        self.bounds_with_transform(&self.placement_matrix())
     }
 
     /// The world bounding box of this object including children, relative to the stage.
@@ -1180,7 +1334,7 @@ pub trait TDisplayObject<'gc>:
 
         if let Some(ctr) = self.as_container() {
             for child in ctr.iter_render_list() {
-                let matrix = *matrix * *child.base().matrix();
+                let matrix = *matrix * child.placement_matrix();
                 bounds = bounds.union(&child.bounds_with_transform(&matrix));
             }
         }
@@ -1202,7 +1356,7 @@ pub trait TDisplayObject<'gc>:
 
         if let Some(ctr) = self.as_container() {
             for child in ctr.iter_render_list() {
-                let matrix = *matrix * *child.base().matrix();
+                let matrix = *matrix * child.placement_matrix();
                 bounds =
                     bounds.union(&child.render_bounds_with_transform(&matrix, true, view_matrix));
             }
@@ -1226,6 +1380,12 @@ pub trait TDisplayObject<'gc>:
         self.base_mut(gc_context).set_place_frame(frame)
     }
 
+    /// The matrix that places this object in its parent's coordinate system.
+    /// For an object in 3D space, this is the 2D matrix that best matches its projection.
+    fn placement_matrix(&self) -> Matrix {
+        transform_3d::projected_matrix((*self).into()).unwrap_or(*self.base().matrix())
+    }
+
     /// Sets the matrix of this object.
     /// This does NOT invalidate the cache, as it's often used with other operations.
     /// It is the callers responsibility to do so.
@@ -1244,7 +1404,7 @@ pub trait TDisplayObject<'gc>:
     /// Should only be used to implement 'Transform.concatenatedMatrix'
     fn local_to_global_matrix_without_own_scroll_rect(&self) -> Matrix {
         let mut node = self.parent();
-        let mut matrix = *self.base().matrix();
+        let mut matrix = self.placement_matrix();
         while let Some(display_object) = node {
             // We want to transform to Stage-local coordinates,
             // so do *not* apply the Stage's matrix
@@ -1254,7 +1414,7 @@ pub trait TDisplayObject<'gc>:
             if let Some(rect) = display_object.scroll_rect() {
                 matrix = Matrix::translate(-rect.x_min, -rect.y_min) * matrix;
             }
-            matrix = *display_object.base().matrix() * matrix;
+            matrix = display_object.placement_matrix() * matrix;
             node = display_object.parent();
         }
         matrix
@@ -1290,12 +1450,11 @@ pub trait TDisplayObject<'gc>:
     /// If the object has zero scale, then the stage `TWIPS_TO_PIXELS` matrix will be used.
     /// This matches Flash's behavior for `mouseX`/`mouseY` on an object with zero scale.
     fn local_mouse_position(&self, context: &UpdateContext<'gc>) -> Point<Twips> {
-        let stage = context.stage;
+        let (stage, global_twips) = NativeWindows::mouse_position_on(context, (*self).into());
         let pixel_ratio = stage.view_matrix().a;
         let virtual_to_device = Matrix::scale(pixel_ratio, pixel_ratio);
 
         // Get mouse pos in global device pixels
-        let global_twips = *context.mouse_position;
         let global_device_twips = virtual_to_device * global_twips;
         let global_device_pixels = Matrix::TWIPS_TO_PIXELS * global_device_twips;
 
@@ -1416,6 +1575,42 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The position, rotation and scale of this display object along the z axis.
+    /// Returned by the `z`, `rotationX`, `rotationY` and `scaleZ` ActionScript properties.
+    fn transform_3d(&self) -> Transform3D {
+        self.base().transform_3d().cloned().unwrap_or_default()
+    }
+
+    /// Places this display object in 3D space, or back onto the plane if the transform is flat.
+    /// Set by the `z`, `rotationX`, `rotationY` and `scaleZ` ActionScript properties.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_transform_3d(&self, gc_context: &Mutation<'gc>, transform_3d: Transform3D) {
+        if self.base_mut(gc_context).set_transform_3d(transform_3d) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The perspective that the descendants of this display object are seen with,
+    /// if it overrides that of its ancestors.
+    /// Returned by the `transform.perspectiveProjection` ActionScript property.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection()
+    }
+
+    /// Sets the perspective that the descendants of this display object are seen with.
+    /// This invalidates the cacheAsBitmap of this object and its ancestors automatically.
+    fn set_perspective_projection(
+        &self,
+        gc_context: &Mutation<'gc>,
+        value: Option<PerspectiveProjection>,
+    ) {
+        if self.base_mut(gc_context).set_perspective_projection(value) {
+            self.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -1719,6 +1914,7 @@ pub trait TDisplayObject<'gc>:
 
     fn set_scaling_grid(&self, gc_context: &Mutation<'gc>, rect: Rectangle<Twips>) {
         self.base_mut(gc_context).scaling_grid = rect;
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     /// Whether this object has been removed. Only applies to AVM1.
@@ -1800,7 +1996,14 @@ pub trait TDisplayObject<'gc>:
 
     fn set_blend_shader(&self, gc_context: &Mutation<'gc>, value: Option<PixelBenderShaderHandle>) {
         self.base_mut(gc_context).set_blend_shader(value);
-        self.set_blend_mode(gc_context, ExtendedBlendMode::Shader);
+        if self.blend_mode() == ExtendedBlendMode::Shader {
+            // The blend mode doesn't change, but how it's drawn does.
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        } else {
+            self.set_blend_mode(gc_context, ExtendedBlendMode::Shader);
+        }
     }
 
     /// The opaque background color of this display object.
@@ -1871,8 +2074,10 @@ pub trait TDisplayObject<'gc>:
     }
 
     /// Whether this display object is using a bitmap cache, whether by preference or necessity.
+    /// Objects in 3D space are always drawn through one, but that isn't reported to scripts.
     fn is_bitmap_cached(&self) -> bool {
-        self.base().cache.is_some()
+        let base = self.base();
+        base.is_bitmap_cached_preference() || !base.filters.is_empty()
     }
 
     /// Explicitly sets the preference of this display object to be cached into a bitmap rendering.
@@ -1882,6 +2087,19 @@ pub trait TDisplayObject<'gc>:
             .set_bitmap_cached_preference(value)
     }
 
+    /// The matrix that the bitmap cache of this display object is drawn with, if it
+    /// shouldn't follow the transform of the object (`DisplayObject.cacheAsBitmapMatrix`).
+    fn cache_as_bitmap_matrix(&self) -> Option<Matrix> {
+        self.base().cache_as_bitmap_matrix()
+    }
+
+    /// Sets the matrix that the bitmap cache of this display object is drawn with.
+    fn set_cache_as_bitmap_matrix(&self, gc_context: &Mutation<'gc>, matrix: Option<Matrix>) {
+        if self.base_mut(gc_context).set_cache_as_bitmap_matrix(matrix) {
+            self.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
     /// Whether this display object has a scroll rectangle applied.
     fn has_scroll_rect(&self) -> bool {
         self.base().has_scroll_rect()
@@ -2209,7 +2427,7 @@ pub trait TDisplayObject<'gc>:
                 if let Some(mut morph_shape) = self.as_morph_shape() {
                     morph_shape.set_ratio(context.gc_context, ratio);
                 } else if let Some(video) = self.as_video() {
-                    video.seek(context, ratio.into());
+                    video.seek_to_ratio(context, ratio.into());
                 }
             }
             if let Some(is_bitmap_cached) = place_object.is_bitmap_cached {
@@ -2427,6 +2645,7 @@ pub trait TDisplayObject<'gc>:
 
         let ancestor = ancestor.unwrap_or_else(|| self.into());
         DisplayObject::ptr_eq(ancestor, context.stage.into())
+            || context.native_windows.is_window_stage(ancestor)
     }
 
     /// Assigns a default instance name `instanceN` to this object.
@@ -2473,15 +2692,37 @@ pub trait TDisplayObject<'gc>:
 
     /// Inform this object and its ancestors that it has visually changed and must be redrawn.
     /// If this object or any ancestor is marked as cacheAsBitmap, it will invalidate that cache.
+    /// Anything that has to be redrawn may also be described differently to assistive
+    /// technologies, so this also invalidates the accessibility tree.
     fn invalidate_cached_bitmap(&self, mc: &Mutation<'gc>) {
-        if self.base_mut(mc).invalidate_cached_bitmap() {
-            // Don't inform ancestors if we've already done so this frame
+        let mut base = self.base_mut(mc);
+        let accessibility_invalidated = base.invalidate_accessibility();
+        let cache_invalidated = base.invalidate_cached_bitmap();
+        drop(base);
+        // Don't inform ancestors if we've already done so this frame
+        if cache_invalidated || accessibility_invalidated {
             if let Some(parent) = self.parent() {
                 parent.invalidate_cached_bitmap(mc);
             }
         }
     }
 
+    /// Whether this object or any of its descendants changed since the stage was last
+    /// described to assistive technologies.
+    fn accessibility_invalidated(&self) -> bool {
+        self.base().accessibility_invalidated()
+    }
+
+    /// Forces the stage to be described to assistive technologies again, such as when
+    /// `Accessibility.updateProperties` is called.
+    fn invalidate_accessibility(&self, mc: &Mutation<'gc>) {
+        self.base_mut(mc).invalidate_accessibility();
+    }
+
+    fn clear_accessibility_invalidated(&self, mc: &Mutation<'gc>) {
+        self.base_mut(mc).clear_accessibility_invalidated();
+    }
+
     /// Retrieve a named property from the AVM1 object.
     ///
     /// This is required as some boolean properties in AVM1 can in fact hold any value.
@@ -2606,6 +2847,10 @@ bitflags! {
 
         /// If this AVM1 object is pending removal (will be removed on the next frame).
         const AVM1_PENDING_REMOVAL     = 1 << 13;
+
+        /// If this object or any of its descendants changed since the stage was last
+        /// described to assistive technologies.
+        const ACCESSIBILITY_INVALIDATED = 1 << 14;
     }
 }
 
@@ -4,26 +4,33 @@ use crate::avm1::Avm1;
 use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier};
 use crate::avm1::{
-    Object as Avm1Object, StageObject as Avm1StageObject, TObject as Avm1TObject,
-    Value as Avm1Value,
+    NativeObject as Avm1NativeObject, Object as Avm1Object, StageObject as Avm1StageObject,
+    TObject as Avm1TObject, Value as Avm1Value,
 };
+use crate::avm2::globals::flash::display::loader::load_request;
+use crate::avm2::object::StyleSheetObject;
 use crate::avm2::Avm2;
 use crate::avm2::{
-    Activation as Avm2Activation, EventObject as Avm2EventObject, Object as Avm2Object,
-    StageObject as Avm2StageObject, TObject as _,
+    Activation as Avm2Activation, Error as Avm2Error, EventObject as Avm2EventObject,
+    Object as Avm2Object, QName as Avm2QName, StageObject as Avm2StageObject, TObject as _,
 };
+use crate::backend::navigator::Request;
 use crate::backend::ui::MouseCursor;
+use crate::bidi;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
-use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
-use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
+use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, MovieClip};
+use crate::events::{ClipEvent, ClipEventResult, ImeEvent, TextControlCode};
 use crate::font::{FontType, Glyph, TextRenderSettings};
 use crate::html;
 use crate::html::{
-    FormatSpans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, Position, TextFormat,
+    FormatSpans, HtmlImage, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, Position,
+    StyleSheet, TextFormat,
 };
+use crate::loader::MovieLoaderVMData;
+use crate::native_window::NativeWindows;
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -53,6 +60,41 @@ pub enum AutoSizeMode {
     Right,
 }
 
+/// An image embedded in the HTML text of an `EditText`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct EditTextImage<'gc> {
+    /// The source the image was created from.
+    #[collect(require_static)]
+    src: WString,
+
+    /// The display object showing the image, if it could be created.
+    object: Option<DisplayObject<'gc>>,
+}
+
+/// The style sheet of an `EditText`, along with the object it was set from.
+#[derive(Copy, Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum EditTextStyleSheet<'gc> {
+    None,
+    Avm1(Avm1Object<'gc>),
+    Avm2(StyleSheetObject<'gc>),
+}
+
+impl<'gc> EditTextStyleSheet<'gc> {
+    /// The styles to apply to HTML text, if any.
+    pub fn style_sheet(self) -> Option<StyleSheet<'gc>> {
+        match self {
+            Self::None => None,
+            Self::Avm1(object) => match object.native() {
+                Avm1NativeObject::StyleSheet(style_sheet) => Some(style_sheet),
+                _ => None,
+            },
+            Self::Avm2(object) => Some(object.style_sheet()),
+        }
+    }
+}
+
 /// A dynamic text field.
 /// The text in this text field can be changed dynamically.
 /// It may be selectable or editable by the user, depending on the text field properties.
@@ -94,6 +136,19 @@ pub struct EditTextData<'gc> {
     #[collect(require_static)]
     text_spans: FormatSpans,
 
+    /// The style sheet that HTML text is styled with.
+    style_sheet: EditTextStyleSheet<'gc>,
+
+    /// The HTML that was given to this field while it had a style sheet.
+    ///
+    /// Flash returns it as-is from `htmlText`, and it is parsed again whenever the
+    /// styles change.
+    #[collect(require_static)]
+    original_html_text: Option<WString>,
+
+    /// The generation of the style sheet that the text was last styled with.
+    style_sheet_generation: u32,
+
     /// The color of the background fill. Only applied when has_border and has_background.
     #[collect(require_static)]
     background_color: Color,
@@ -117,6 +172,9 @@ pub struct EditTextData<'gc> {
     /// The calculated layout.
     layout: Layout<'gc>,
 
+    /// The images embedded with `<img>` tags, matching the images of `text_spans`.
+    images: Vec<EditTextImage<'gc>>,
+
     /// The current intrinsic bounds of the text field.
     #[collect(require_static)]
     bounds: Rectangle<Twips>,
@@ -175,6 +233,11 @@ pub struct EditTextData<'gc> {
     /// Information related to the last click event inside this text field.
     #[collect(require_static)]
     last_click: Option<ClickEventData>,
+
+    /// The text being composed with an input method editor, which is shown
+    /// inline until it's committed.
+    #[collect(require_static)]
+    ime_preedit: Option<ImePreedit>,
 }
 
 impl EditTextData<'_> {
@@ -228,6 +291,7 @@ impl<'gc> EditText<'gc> {
             FormatSpans::from_html(
                 &text,
                 default_format,
+                None,
                 swf_tag.is_multiline(),
                 false,
                 swf_movie.version(),
@@ -295,6 +359,9 @@ impl<'gc> EditText<'gc> {
             EditTextData {
                 base: InteractiveObjectBase::default(),
                 text_spans,
+                style_sheet: EditTextStyleSheet::None,
+                original_html_text: None,
+                style_sheet_generation: 0,
                 static_data: Gc::new(
                     context.gc_context,
                     EditTextStatic {
@@ -310,6 +377,7 @@ impl<'gc> EditText<'gc> {
                 border_color: Color::BLACK,
                 object: None,
                 layout,
+                images: Vec::new(),
                 bounds: swf_tag.bounds().clone(),
                 autosize,
                 requested_width: swf_tag.bounds().width(),
@@ -325,6 +393,7 @@ impl<'gc> EditText<'gc> {
                 is_tlf: false,
                 restrict: EditTextRestrict::allow_all(),
                 last_click: None,
+                ime_preedit: None,
                 layout_debug_boxes_flags: LayoutDebugBoxesFlag::empty(),
             },
         ));
@@ -394,6 +463,8 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.original_html_text = None;
+        edit_text.ime_preedit = None;
         drop(edit_text);
 
         self.relayout(context);
@@ -401,7 +472,11 @@ impl<'gc> EditText<'gc> {
 
     pub fn html_text(self) -> WString {
         if self.is_html() {
-            self.0.read().text_spans.to_html()
+            let read = self.0.read();
+            if let Some(html) = &read.original_html_text {
+                return html.clone();
+            }
+            read.text_spans.to_html()
         } else {
             // Non-HTML text fields always return plain text.
             self.text()
@@ -412,13 +487,22 @@ impl<'gc> EditText<'gc> {
         if self.is_html() {
             let mut write = self.0.write(context.gc_context);
             let default_format = write.text_spans.default_format().clone();
+            let style_sheet = write.style_sheet.style_sheet();
             write.text_spans = FormatSpans::from_html(
                 text,
                 default_format,
+                style_sheet,
                 write.flags.contains(EditTextFlag::MULTILINE),
                 write.flags.contains(EditTextFlag::CONDENSE_WHITE),
                 write.static_data.swf.version(),
             );
+            if let Some(style_sheet) = style_sheet {
+                write.original_html_text = Some(text.into());
+                write.style_sheet_generation = style_sheet.generation();
+            } else {
+                write.original_html_text = None;
+            }
+            write.ime_preedit = None;
             drop(write);
 
             self.relayout(context);
@@ -427,6 +511,44 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    pub fn style_sheet(self) -> EditTextStyleSheet<'gc> {
+        self.0.read().style_sheet
+    }
+
+    pub fn set_style_sheet(
+        self,
+        context: &mut UpdateContext<'gc>,
+        style_sheet: EditTextStyleSheet<'gc>,
+    ) {
+        let mut write = self.0.write(context.gc());
+        write.style_sheet = style_sheet;
+        let html = write.original_html_text.take();
+        drop(write);
+
+        // Restyle the current text with the new styles.
+        if self.is_html() {
+            let html = html.unwrap_or_else(|| self.html_text());
+            self.set_html_text(&html, context);
+        }
+    }
+
+    /// Restyles the text if its style sheet was changed since the text was set.
+    fn update_style_sheet(self, context: &mut UpdateContext<'gc>) {
+        let read = self.0.read();
+        let Some(style_sheet) = read.style_sheet.style_sheet() else {
+            return;
+        };
+        if style_sheet.generation() == read.style_sheet_generation {
+            return;
+        }
+        let Some(html) = read.original_html_text.clone() else {
+            return;
+        };
+        drop(read);
+
+        self.set_html_text(&html, context);
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -768,6 +890,9 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     pub fn relayout(self, context: &mut UpdateContext<'gc>) {
+        self.sync_images(context);
+        self.update_image_sizes(context.gc_context);
+
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.flags.contains(EditTextFlag::WORD_WRAP);
@@ -799,6 +924,7 @@ impl<'gc> EditText<'gc> {
         );
 
         edit_text.layout = new_layout;
+        Self::position_images(&edit_text, context.gc_context);
         // reset scroll
         edit_text.hscroll = 0.0;
         edit_text.scroll = 1;
@@ -840,6 +966,216 @@ impl<'gc> EditText<'gc> {
         self.invalidate_cached_bitmap(context.gc_context);
     }
 
+    /// Create display objects for the images embedded in the text, unless
+    /// they are already up to date.
+    fn sync_images(self, context: &mut UpdateContext<'gc>) {
+        let edit_text = self.0.read();
+        let html_images = edit_text.text_spans.images();
+        let sources = edit_text.images.iter().map(|image| &*image.src);
+        if html_images.iter().map(|image| image.src()).eq(sources) {
+            return;
+        }
+        let html_images = html_images.to_vec();
+        drop(edit_text);
+
+        let old_images = std::mem::take(&mut self.0.write(context.gc_context).images);
+        for object in old_images.into_iter().filter_map(|image| image.object) {
+            if !object.movie().is_action_script_3() {
+                object.avm1_unload(context);
+            }
+            object.set_parent(context, None);
+        }
+
+        let images = html_images
+            .iter()
+            .map(|image| EditTextImage {
+                src: image.src().into(),
+                object: self.create_image(context, image),
+            })
+            .collect();
+        self.0.write(context.gc_context).images = images;
+    }
+
+    /// Create the display object showing an image.
+    ///
+    /// Images whose source is the linkage name of a library symbol are
+    /// instantiated from it, the other ones are loaded from their URL.
+    fn create_image(
+        self,
+        context: &mut UpdateContext<'gc>,
+        image: &HtmlImage,
+    ) -> Option<DisplayObject<'gc>> {
+        let movie = self.movie();
+        let src = AvmString::new(context.gc_context, image.src());
+
+        if movie.is_action_script_3() {
+            let object = match Self::create_avm2_image(context, movie, src) {
+                Ok(object) => object?,
+                Err(e) => {
+                    tracing::error!("Error when creating image {}: {:?}", src, e);
+                    return None;
+                }
+            };
+            object.set_parent(context, Some(self.into()));
+            return Some(object);
+        }
+
+        let library = context.library.library_for_movie_mut(movie.clone());
+        let is_symbol = library.character_by_export_name(src).is_some();
+        let object = if is_symbol {
+            library
+                .instantiate_by_export_name(src, context.gc_context)
+                .ok()?
+        } else {
+            MovieClip::new(movie, context.gc_context).into()
+        };
+
+        object.set_parent(context, Some(self.into()));
+        if let Some(id) = image.id() {
+            object.set_name(context.gc_context, AvmString::new(context.gc_context, id));
+        }
+        object.post_instantiation(context, None, Instantiator::Avm1, false);
+
+        if !is_symbol {
+            let future = context.load_manager.load_movie_into_clip(
+                context.player.clone(),
+                object,
+                Request::get(src.to_utf8_lossy().into_owned()),
+                None,
+                MovieLoaderVMData::Avm1 { broadcaster: None },
+            );
+            context.navigator.spawn_future(future);
+        }
+
+        Some(object)
+    }
+
+    /// Create the AVM2 display object of an image: either an instance of
+    /// the class linked to `src`, or a `Loader` loading it.
+    fn create_avm2_image(
+        context: &mut UpdateContext<'gc>,
+        movie: Arc<SwfMovie>,
+        src: AvmString<'gc>,
+    ) -> Result<Option<DisplayObject<'gc>>, Avm2Error<'gc>> {
+        let domain = context.library.library_for_movie_mut(movie).avm2_domain();
+        let mut activation = Avm2Activation::from_domain(context, domain);
+        let api_version = activation.avm2().root_api_version;
+        let name = Avm2QName::from_qualified_name(src, api_version, activation.context);
+
+        let class = domain
+            .get_defined_value(&mut activation, name)
+            .ok()
+            .and_then(|value| value.as_object())
+            .and_then(|object| object.as_class_object());
+        if let Some(class) = class {
+            let bitmapdata = activation.avm2().class_defs().bitmapdata;
+            let object = if class
+                .inner_class_definition()
+                .has_class_in_chain(bitmapdata)
+            {
+                let bitmap_data = class.construct(&mut activation, &[0.into(), 0.into()])?;
+                let bitmap = activation.avm2().classes().bitmap;
+                bitmap.construct(&mut activation, &[bitmap_data.into()])?
+            } else {
+                class.construct(&mut activation, &[])?
+            };
+            return Ok(object.as_display_object());
+        }
+
+        let loader_class = activation.avm2().classes().loader;
+        let loader = loader_class.construct(&mut activation, &[])?;
+        let request = Request::get(src.to_utf8_lossy().into_owned());
+        load_request(&mut activation, loader, request, None)?;
+        Ok(loader.as_display_object())
+    }
+
+    /// Update the content sizes of the images embedded in the text.
+    ///
+    /// Returns `true` if any of them has changed, and the text needs to be
+    /// laid out again.
+    fn update_image_sizes(self, gc_context: &Mutation<'gc>) -> bool {
+        let mut edit_text = self.0.write(gc_context);
+        let edit_text = &mut *edit_text;
+
+        let mut changed = false;
+        for (index, image) in edit_text.images.iter().enumerate() {
+            let Some(bounds) = image.object.map(|object| object.bounds()) else {
+                continue;
+            };
+            if bounds.is_valid() {
+                changed |= edit_text.text_spans.set_image_content_size(
+                    index,
+                    bounds.width().to_pixels(),
+                    bounds.height().to_pixels(),
+                );
+            }
+        }
+        changed
+    }
+
+    /// Move the images embedded in the text to their laid out bounds,
+    /// scaling them to their requested size.
+    fn position_images(edit_text: &EditTextData<'gc>, gc_context: &Mutation<'gc>) {
+        let html_images = edit_text.text_spans.images();
+        for layout_image in edit_text.layout.images() {
+            let index = layout_image.index();
+            let (Some(html_image), Some(object)) = (
+                html_images.get(index),
+                edit_text.images.get(index).and_then(|image| image.object),
+            ) else {
+                continue;
+            };
+
+            let (width, height) = html_image.size();
+            let (content_width, content_height) =
+                html_image.content_size().unwrap_or((width, height));
+            let scale = |size: f64, content_size: f64| {
+                if content_size > 0.0 {
+                    (size / content_size) as f32
+                } else {
+                    1.0
+                }
+            };
+
+            let bounds = layout_image.bounds();
+            object.set_matrix(
+                gc_context,
+                Matrix::translate(bounds.offset_x(), bounds.offset_y())
+                    * Matrix::scale(scale(width, content_width), scale(height, content_height)),
+            );
+        }
+    }
+
+    /// Keep the images embedded in the text up to date with their content.
+    fn update_images(self, context: &mut UpdateContext<'gc>) {
+        self.sync_images(context);
+        if self.update_image_sizes(context.gc_context) {
+            self.relayout(context);
+        }
+    }
+
+    /// The display objects of the images embedded in the text.
+    fn image_objects(self) -> Vec<DisplayObject<'gc>> {
+        let edit_text = self.0.read();
+        edit_text
+            .images
+            .iter()
+            .filter_map(|image| image.object)
+            .collect()
+    }
+
+    /// The display object of the embedded image with the given `id`, if any.
+    pub fn image_reference(self, id: &WStr) -> Option<DisplayObject<'gc>> {
+        let edit_text = self.0.read();
+        edit_text
+            .text_spans
+            .images()
+            .iter()
+            .zip(&edit_text.images)
+            .find(|(html_image, _)| html_image.id() == Some(id))
+            .and_then(|(_, image)| image.object)
+    }
+
     /// Measure the width and height of the `EditText`'s current text load.
     ///
     /// The returned tuple should be interpreted as width, then height.
@@ -1031,14 +1367,14 @@ impl<'gc> EditText<'gc> {
             line.leading()
         };
 
-        let x_start = line
-            .char_x_bounds(local_start)
-            .map(|b| b.0)
-            .unwrap_or_else(|| line_bounds.offset_x());
-        let x_end = line
-            .char_x_bounds(local_end - 1)
-            .map(|b| b.1)
-            .unwrap_or_else(|| line_bounds.extent_x());
+        // Right-to-left text is drawn backwards, so the selected characters
+        // don't necessarily start at the first one.
+        let (x_start, x_end) = (local_start..local_end)
+            .filter_map(|position| line.char_x_bounds(position))
+            .reduce(|(start, end), (char_start, char_end)| {
+                (start.min(char_start), end.max(char_end))
+            })
+            .unwrap_or_else(|| (line_bounds.offset_x(), line_bounds.extent_x()));
 
         let width = x_end - x_start;
         let height = line_bounds.height() + leading;
@@ -1127,38 +1463,67 @@ impl<'gc> EditText<'gc> {
             let descent = font.get_descent_for_height(params.height());
             let baseline_adjustment = baseline - params.height();
             let caret_height = baseline + descent;
+            let bold_offset = font.synthetic_bold_offset(params);
             let mut caret_x = Twips::ZERO;
+            let preedit = edit_text
+                .ime_preedit
+                .as_ref()
+                .map(|preedit| preedit.start..preedit.end);
+            let mut preedit_x: Option<(Twips, Twips)> = None;
             font.evaluate(
                 text,
                 self.text_transform(color, baseline_adjustment),
                 params,
-                |pos, transform, glyph: &Glyph, advance, x| {
+                |pos, transform, glyph: &Glyph, advance, x, is_rtl| {
                     if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
                         // If it's highlighted, override the color.
-                        if matches!(visible_selection, Some(visible_selection) if visible_selection.contains(start + pos)) {
+                        let mut glyph_transform = if matches!(visible_selection, Some(visible_selection) if visible_selection.contains(start + pos)) {
                             // Set text color to white
-                            context.transform_stack.push(&Transform {
+                            Transform {
                                 matrix: transform.matrix,
                                 color_transform: ColorTransform::IDENTITY,
-                            });
+                            }
                         } else {
-                            context.transform_stack.push(transform);
-                        }
+                            transform.clone()
+                        };
 
                         // Render glyph.
+                        context.transform_stack.push(&glyph_transform);
                         context
                             .commands
-                            .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                            .render_shape(glyph_shape_handle.clone(), context.transform_stack.transform());
                         context.transform_stack.pop();
+
+                        // Synthesized bold draws the glyph a second time, slightly offset.
+                        if let Some(bold_offset) = bold_offset {
+                            glyph_transform.matrix.tx += bold_offset;
+                            context.transform_stack.push(&glyph_transform);
+                            context
+                                .commands
+                                .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                            context.transform_stack.pop();
+                        }
+                    }
+
+                    // The text being composed is underlined.
+                    if matches!(&preedit, Some(preedit) if preedit.contains(&(start + pos))) {
+                        let (_, end_x) = preedit_x.get_or_insert((x, x));
+                        *end_x = x + advance;
                     }
 
                     // Update caret position
                     if let Some(caret) = caret {
+                        // Right-to-left glyphs start on their right.
+                        let (glyph_start, glyph_end) = if is_rtl {
+                            (x + advance, x)
+                        } else {
+                            (x, x + advance)
+                        };
                         if pos == caret {
-                            caret_x = x;
+                            caret_x = glyph_start;
                         } else if caret > 0 && pos == caret - 1 {
                             // The caret may be rendered at the end, after all glyphs.
-                            caret_x = x + advance;
+                            caret_x = glyph_end;
                         }
                     }
                 },
@@ -1167,6 +1532,10 @@ impl<'gc> EditText<'gc> {
             if caret.is_some() {
                 self.render_caret(context, caret_x, caret_height, color);
             }
+
+            if let Some((start_x, end_x)) = preedit_x {
+                self.render_ime_underline(context, start_x, end_x, caret_height, color);
+            }
         }
 
         if let Some(drawing) = lbox.as_renderable_drawing() {
@@ -1196,6 +1565,21 @@ impl<'gc> EditText<'gc> {
         context.commands.draw_line(color, caret);
     }
 
+    fn render_ime_underline(
+        self,
+        context: &mut RenderContext<'_, 'gc>,
+        start_x: Twips,
+        end_x: Twips,
+        y: Twips,
+        color: Color,
+    ) {
+        let mut underline = context.transform_stack.transform().matrix
+            * Matrix::create_box((end_x - start_x).to_pixels() as f32, 1.0, start_x, y);
+        let pixel_snapping = EditTextPixelSnapping::new(context.stage.quality());
+        pixel_snapping.apply(&mut underline);
+        context.commands.draw_line(color, underline);
+    }
+
     /// Attempts to bind this text field to a property of a display object.
     /// If we find a parent display object matching the given path, we register oursevles and a property name with it.
     /// `set_text` will be called by the stage object whenever the property changes.
@@ -1418,6 +1802,11 @@ impl<'gc> EditText<'gc> {
         self.0.write(context.gc_context).max_chars = value;
     }
 
+    /// Where the pointer is on the stage this text field is on.
+    fn mouse_position(self, context: &UpdateContext<'gc>) -> Point<Twips> {
+        NativeWindows::mouse_position_on(context, self.into()).1
+    }
+
     pub fn screen_position_to_index(self, position: Point<Twips>) -> Option<usize> {
         let text = self.0.read();
         let position = self.global_to_local(position)?;
@@ -1454,15 +1843,19 @@ impl<'gc> EditText<'gc> {
                 layout_box.as_renderable_text(text.text_spans.text())
             {
                 let mut result = 0;
+                let mut closest_x = None;
                 let baseline_adjustment =
                     font.get_baseline_for_height(params.height()) - params.height();
                 font.evaluate(
                     text,
                     self.text_transform(color, baseline_adjustment),
                     params,
-                    |pos, _transform, _glyph: &Glyph, advance, x| {
-                        if local_position.x >= x {
-                            if local_position.x > x + (advance / 2) {
+                    |pos, _transform, _glyph: &Glyph, advance, x, is_rtl| {
+                        // Find the rightmost glyph that starts before the position.
+                        if local_position.x >= x && closest_x.map_or(true, |closest| x >= closest) {
+                            closest_x = Some(x);
+                            // Right-to-left glyphs start on their right.
+                            if (local_position.x > x + (advance / 2)) != is_rtl {
                                 result = string_utils::next_char_boundary(text, pos);
                             } else {
                                 result = pos;
@@ -1542,6 +1935,7 @@ impl<'gc> EditText<'gc> {
             return;
         };
 
+        let control_code = self.logical_control_code(control_code, selection.to);
         let mut changed = false;
         let is_selectable = self.is_selectable();
         match control_code {
@@ -1713,6 +2107,29 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// The arrow keys move through right-to-left text backwards, so their control codes
+    /// are swapped when the caret is in such text.
+    fn logical_control_code(
+        self,
+        control_code: TextControlCode,
+        current_pos: usize,
+    ) -> TextControlCode {
+        if !bidi::is_rtl_at(&self.text(), current_pos) {
+            return control_code;
+        }
+        match control_code {
+            TextControlCode::MoveLeft => TextControlCode::MoveRight,
+            TextControlCode::MoveRight => TextControlCode::MoveLeft,
+            TextControlCode::MoveLeftWord => TextControlCode::MoveRightWord,
+            TextControlCode::MoveRightWord => TextControlCode::MoveLeftWord,
+            TextControlCode::SelectLeft => TextControlCode::SelectRight,
+            TextControlCode::SelectRight => TextControlCode::SelectLeft,
+            TextControlCode::SelectLeftWord => TextControlCode::SelectRightWord,
+            TextControlCode::SelectRightWord => TextControlCode::SelectLeftWord,
+            _ => control_code,
+        }
+    }
+
     /// Find the new position in the text for the given control code.
     ///
     /// * For selection codes it will represent the "to" part of the selection.
@@ -1843,17 +2260,23 @@ impl<'gc> EditText<'gc> {
             return;
         };
 
+        self.insert_user_text(selection, &WString::from_char(character), context);
+    }
+
+    /// Replaces the selection with text entered by the user, unless a `textInput`
+    /// handler cancels it.
+    fn insert_user_text(
+        self,
+        selection: TextSelection,
+        text: &WStr,
+        context: &mut UpdateContext<'gc>,
+    ) {
         if let Avm2Value::Object(target) = self.object2() {
-            let character_string = AvmString::new_utf8(context.gc_context, character.to_string());
+            let text_string = AvmString::new(context.gc_context, text);
 
             let mut activation = Avm2Activation::from_nothing(context);
-            let text_evt = Avm2EventObject::text_event(
-                &mut activation,
-                "textInput",
-                character_string,
-                true,
-                true,
-            );
+            let text_evt =
+                Avm2EventObject::text_event(&mut activation, "textInput", text_string, true, true);
             Avm2::dispatch_event(activation.context, text_evt, target);
 
             if text_evt.as_event().unwrap().is_cancelled() {
@@ -1861,13 +2284,8 @@ impl<'gc> EditText<'gc> {
             }
         }
 
-        self.replace_text(
-            selection.start(),
-            selection.end(),
-            &WString::from_char(character),
-            context,
-        );
-        let new_pos = selection.start() + character.len_utf8();
+        self.replace_text(selection.start(), selection.end(), text, context);
+        let new_pos = selection.start() + text.len();
         self.set_selection(
             Some(TextSelection::for_position(new_pos)),
             context.gc_context,
@@ -1882,6 +2300,107 @@ impl<'gc> EditText<'gc> {
         self.on_changed(&mut activation);
     }
 
+    /// Handles text composed with the system's input method editor.
+    pub fn ime_input(self, event: ImeEvent, context: &mut UpdateContext<'gc>) {
+        if self.0.read().flags.contains(EditTextFlag::READ_ONLY) {
+            return;
+        }
+
+        match event {
+            ImeEvent::Preedit(text, cursor) => self.set_ime_preedit(&text, cursor, context),
+            ImeEvent::Commit(text) => self.ime_commit(&text, context),
+        }
+    }
+
+    /// Shows the text being composed in place of the selection, or of the previous composition.
+    ///
+    /// The composition isn't user input yet, so it's neither restricted nor reported to scripts.
+    /// The text and selection it replaced are kept, to be restored when it's abandoned.
+    pub fn set_ime_preedit(
+        self,
+        text: &str,
+        cursor: Option<(usize, usize)>,
+        context: &mut UpdateContext<'gc>,
+    ) {
+        self.abandon_ime_composition(context);
+        let Some(selection) = self.selection() else {
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        let start = selection.start();
+        let preedit = WString::from_utf8(text);
+        let replaced_spans = self.0.read().text_spans.clone();
+        self.replace_text(start, selection.end(), &preedit, context);
+        self.0.write(context.gc_context).ime_preedit = Some(ImePreedit {
+            start,
+            end: start + preedit.len(),
+            replaced_spans,
+            replaced_selection: selection,
+        });
+
+        // The cursor is given in bytes, but text is indexed in UTF-16 code units.
+        let to_index = |offset: usize| {
+            let prefix = text.get(..offset).unwrap_or(text);
+            start + WString::from_utf8(prefix).len()
+        };
+        let selection = match cursor {
+            Some((from, to)) => TextSelection::for_range(to_index(from), to_index(to)),
+            None => TextSelection::for_position(start + preedit.len()),
+        };
+        self.set_selection(Some(selection), context.gc_context);
+    }
+
+    /// Removes the text being composed, if any, and restores the text and selection it replaced.
+    pub fn abandon_ime_composition(self, context: &mut UpdateContext<'gc>) {
+        let mut edit_text = self.0.write(context.gc_context);
+        let Some(preedit) = edit_text.ime_preedit.take() else {
+            return;
+        };
+        edit_text.text_spans = preedit.replaced_spans;
+        drop(edit_text);
+
+        self.relayout(context);
+        self.set_selection(Some(preedit.replaced_selection), context.gc_context);
+    }
+
+    /// Inserts the composed text as if it was typed, all at once.
+    fn ime_commit(self, text: &str, context: &mut UpdateContext<'gc>) {
+        self.abandon_ime_composition(context);
+        let Some(selection) = self.selection() else {
+            return;
+        };
+
+        let is_multiline = self.is_multiline();
+        let mut allowed = WString::new();
+        {
+            let edit_text = self.0.read();
+            text.chars()
+                .filter(|&c| !c.is_control() || (is_multiline && c == Self::INPUT_NEWLINE))
+                .filter_map(|c| edit_text.restrict.to_allowed(c))
+                .take(self.available_chars())
+                .for_each(|c| allowed.push_char(c));
+        }
+        if allowed.is_empty() {
+            return;
+        }
+
+        self.insert_user_text(selection, &allowed, context);
+    }
+
+    /// The bounds of the text cursor in viewport coordinates, used to position the
+    /// candidates of the input method editor.
+    pub fn ime_cursor_area(self, context: &mut UpdateContext<'gc>) -> Option<Rectangle<Twips>> {
+        let position = self.selection()?.start();
+        // There's no character after the caret at the end of the text.
+        let bounds = self
+            .char_bounds(position)
+            .or_else(|| self.char_bounds(position.checked_sub(1)?))?;
+        Some(context.stage.view_matrix() * self.local_to_global_matrix() * bounds)
+    }
+
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc>) {
         if let Avm1Value::Object(object) = self.object() {
             activation.context.avm1.broadcaster_functions().initialize(
@@ -2264,10 +2783,29 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             self.construct_as_avm2_object(context, (*self).into());
             self.on_construction_complete(context);
         }
+        self.update_style_sheet(context);
+        self.update_images(context);
+
+        for object in self.image_objects() {
+            object.construct_frame(context);
+        }
+    }
+
+    fn enter_frame(&self, context: &mut UpdateContext<'gc>) {
+        for object in self.image_objects() {
+            object.enter_frame(context);
+        }
+    }
+
+    fn run_frame_avm1(&self, context: &mut UpdateContext<'gc>) {
+        self.update_style_sheet(context);
+        self.update_images(context);
     }
 
-    fn run_frame_avm1(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame_scripts(self, context: &mut UpdateContext<'gc>) {
+        for object in self.image_objects() {
+            object.run_frame_scripts(context);
+        }
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -2445,6 +2983,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         self.render_text(context, &edit_text);
 
+        for object in edit_text.images.iter().filter_map(|image| image.object) {
+            object.render(context);
+        }
+
         self.render_debug_boxes(
             context,
             edit_text.layout_debug_boxes_flags,
@@ -2474,6 +3016,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             node.set_maskee(context.gc_context, None, true);
         }
 
+        for object in self.image_objects() {
+            object.avm1_unload(context);
+        }
+
         // Unbind any display objects bound to this text.
         if let Some(stage_object) = self.0.write(context.gc_context).bound_stage_object.take() {
             stage_object.clear_text_field_binding(context.gc_context, *self);
@@ -2675,7 +3221,7 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
             // We can't hold self as any link may end up modifying this object, so pull the info out
             let mut link_to_open = None;
 
-            if let Some(position) = self.screen_position_to_index(*context.mouse_position) {
+            if let Some(position) = self.screen_position_to_index(self.mouse_position(context)) {
                 self.handle_click(index, position, context);
 
                 if let Some((span_index, _)) =
@@ -2712,7 +3258,8 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         if let ClipEvent::MouseMove = event {
             // If a mouse has moved and this EditTest is pressed, we need to update the selection.
             if InteractiveObject::option_ptr_eq(context.mouse_data.pressed, self.as_interactive()) {
-                if let Some(position) = self.screen_position_to_index(*context.mouse_position) {
+                if let Some(position) = self.screen_position_to_index(self.mouse_position(context))
+                {
                     self.handle_drag(position, context);
                 }
             }
@@ -2774,7 +3321,7 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     }
 
     fn mouse_cursor(self, context: &mut UpdateContext<'gc>) -> MouseCursor {
-        if self.is_link_at(*context.mouse_position) {
+        if self.is_link_at(self.mouse_position(context)) {
             MouseCursor::Hand
         } else if self.is_selectable() {
             MouseCursor::IBeam
@@ -2789,6 +3336,10 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         focused: bool,
         _other: Option<InteractiveObject<'gc>>,
     ) {
+        if !focused {
+            self.abandon_ime_composition(context);
+        }
+
         let is_avm1 = !self.movie().is_action_script_3();
         if !focused && is_avm1 {
             self.set_selection(None, context.gc_context);
@@ -2859,6 +3410,19 @@ struct EditTextStatic {
     initial_text: Option<WString>,
 }
 
+/// Where the text being composed with an input method editor is, in the text.
+#[derive(Clone, Debug)]
+struct ImePreedit {
+    start: usize,
+    end: usize,
+
+    /// The text before the composition was shown in it.
+    replaced_spans: FormatSpans,
+
+    /// The selection that the composition replaced.
+    replaced_selection: TextSelection,
+}
+
 #[derive(Clone, Debug)]
 struct ClickEventData {
     /// The position in text resolved from click coordinates.
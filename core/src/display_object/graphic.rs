@@ -11,9 +11,9 @@ use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
-use ruffle_render::backend::ShapeHandle;
+use ruffle_render::backend::{DeviceId, ShapeHandle};
 use ruffle_render::commands::CommandHandler;
-use std::cell::{Ref, RefMut};
+use std::cell::{Ref, RefCell, RefMut};
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -50,11 +50,12 @@ impl<'gc> Graphic<'gc> {
         let static_data = GraphicStatic {
             id: swf_shape.id,
             bounds: swf_shape.shape_bounds.clone(),
-            render_handle: Some(
+            render_handle: RefCell::new(Some((
+                context.renderer.device_id(),
                 context
                     .renderer
                     .register_shape((&swf_shape).into(), &MovieLibrarySource { library }),
-            ),
+            ))),
             shape: swf_shape,
             movie,
         };
@@ -75,7 +76,7 @@ impl<'gc> Graphic<'gc> {
         let static_data = GraphicStatic {
             id: 0,
             bounds: Default::default(),
-            render_handle: None,
+            render_handle: RefCell::new(None),
             shape: swf::Shape {
                 version: 32,
                 id: 0,
@@ -102,6 +103,27 @@ impl<'gc> Graphic<'gc> {
         ))
     }
 
+    /// The handle of this graphic's shape, registered again if the renderer's device changed.
+    fn render_handle(&self, context: &mut RenderContext<'_, '_>) -> Option<ShapeHandle> {
+        let static_data = self.0.read().static_data;
+        let device_id = context.renderer.device_id();
+        let mut render_handle = static_data.render_handle.borrow_mut();
+        match &*render_handle {
+            Some((registered_on, handle)) if *registered_on == device_id => Some(handle.clone()),
+            Some(_) => {
+                let library = context
+                    .library
+                    .library_for_movie(static_data.movie.clone())?;
+                let handle = context
+                    .renderer
+                    .register_shape((&static_data.shape).into(), &MovieLibrarySource { library });
+                *render_handle = Some((device_id, handle.clone()));
+                Some(handle)
+            }
+            None => None,
+        }
+    }
+
     pub fn drawing_mut(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, Drawing> {
         RefMut::map(self.0.write(gc_context), |w| {
             &mut **w.drawing.get_or_insert_with(Default::default)
@@ -187,7 +209,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
         if let Some(drawing) = &self.0.read().drawing {
             drawing.render(context);
-        } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
+        } else if let Some(render_handle) = self.render_handle(context) {
             context
                 .commands
                 .render_shape(render_handle, context.transform_stack.transform())
@@ -269,7 +291,8 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 struct GraphicStatic {
     id: CharacterId,
     shape: swf::Shape,
-    render_handle: Option<ShapeHandle>,
+    /// The handle of the shape, along with the device it lives on.
+    render_handle: RefCell<Option<(DeviceId, ShapeHandle)>>,
     bounds: Rectangle<Twips>,
     movie: Arc<SwfMovie>,
 }
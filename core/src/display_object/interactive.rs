@@ -17,6 +17,7 @@ use crate::display_object::{
     DisplayObject, DisplayObjectBase, TDisplayObject, TDisplayObjectContainer,
 };
 use crate::events::{ClipEvent, ClipEventResult, MouseButton};
+use crate::focus_tracker::FocusDirection;
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
 use ruffle_macros::enum_trait_object;
@@ -585,6 +586,7 @@ pub trait TInteractiveObject<'gc>:
         context: &mut UpdateContext<'gc>,
         focused: bool,
         other: Option<InteractiveObject<'gc>>,
+        direction: FocusDirection,
     ) {
         let self_do = self.as_displayobject();
         if let Avm1Value::Object(object) = self_do.object() {
@@ -600,7 +602,8 @@ pub trait TInteractiveObject<'gc>:
         } else if let Avm2Value::Object(object) = self_do.object2() {
             let mut activation = Avm2Activation::from_nothing(context);
             let event_name = if focused { "focusIn" } else { "focusOut" };
-            let event = EventObject::focus_event(&mut activation, event_name, false, other, 0);
+            let event =
+                EventObject::focus_event(&mut activation, event_name, false, other, 0, direction);
             Avm2::dispatch_event(activation.context, event, object);
         }
     }
@@ -8,7 +8,7 @@ use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use core::fmt;
 use gc_arena::{Collect, Gc, GcCell, Mutation};
-use ruffle_render::backend::ShapeHandle;
+use ruffle_render::backend::{DeviceId, ShapeHandle};
 use ruffle_render::commands::CommandHandler;
 use std::cell::{Ref, RefCell, RefMut};
 use std::sync::Arc;
@@ -186,7 +186,8 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
 
 /// A precalculated intermediate frame for a morph shape.
 struct Frame {
-    shape_handle: Option<ShapeHandle>,
+    /// The handle of the shape, along with the device it lives on.
+    shape_handle: Option<(DeviceId, ShapeHandle)>,
     shape: swf::Shape,
     bounds: Rectangle<Twips>,
 }
@@ -234,15 +235,17 @@ impl MorphShapeStatic {
         ratio: u16,
     ) -> ShapeHandle {
         let mut frame = self.get_frame(ratio);
-        if let Some(handle) = frame.shape_handle.clone() {
-            handle
-        } else {
-            let library = library.library_for_movie(self.movie.clone()).unwrap();
-            let handle = context
-                .renderer
-                .register_shape((&frame.shape).into(), &MovieLibrarySource { library });
-            frame.shape_handle = Some(handle.clone());
-            handle
+        let device_id = context.renderer.device_id();
+        match &frame.shape_handle {
+            Some((registered_on, handle)) if *registered_on == device_id => handle.clone(),
+            _ => {
+                let library = library.library_for_movie(self.movie.clone()).unwrap();
+                let handle = context
+                    .renderer
+                    .register_shape((&frame.shape).into(), &MovieLibrarySource { library });
+                frame.shape_handle = Some((device_id, handle.clone()));
+                handle
+            }
         }
     }
 
@@ -666,6 +666,14 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_font_2(context, reader),
+                TagCode::DefineFontInfo => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_info(context, reader, 1),
+                TagCode::DefineFontInfo2 => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_info(context, reader, 2),
                 TagCode::DefineFont3 => self
                     .0
                     .write(context.gc_context)
@@ -3556,12 +3564,16 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let id = reader.read_u16()?;
         let rect = reader.read_rectangle()?;
         let library = context.library.library_for_movie_mut(self.movie());
-        if let Some(character) = library.character_by_id(id) {
-            if let Character::MovieClip(clip) = character {
-                clip.set_scaling_grid(context.gc_context, rect);
-            } else {
-                tracing::warn!("DefineScalingGrid for invalid ID {}", id);
+        match library.character_by_id(id) {
+            Some(Character::MovieClip(clip)) => clip.set_scaling_grid(context.gc_context, rect),
+            Some(Character::Avm1Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect)
+            }
+            Some(Character::Avm2Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect)
             }
+            Some(_) => tracing::warn!("DefineScalingGrid for invalid ID {}", id),
+            None => {}
         }
         Ok(())
     }
@@ -3893,31 +3905,12 @@ impl<'gc, 'a> MovieClipData<'gc> {
         reader: &mut SwfStream<'a>,
     ) -> Result<(), Error> {
         let font = reader.read_define_font_1()?;
-        let glyphs = font
-            .glyphs
-            .into_iter()
-            .map(|g| swf::Glyph {
-                shape_records: g,
-                code: 0,
-                advance: 0,
-                bounds: None,
-            })
-            .collect::<Vec<_>>();
-
         let font_id = font.id;
-        let font = swf::Font {
-            id: font.id,
-            version: 0,
-            name: "".into(),
-            glyphs,
-            language: swf::Language::Unknown,
-            layout: None,
-            flags: swf::FontFlag::empty(),
-        };
-        let font_object = Font::from_swf_tag(
+        let font_object = Font::from_font1_tag(
             context.gc_context,
             context.renderer,
             font,
+            None,
             reader.encoding(),
             FontType::Embedded,
         );
@@ -3928,6 +3921,27 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    /// Names a font defined by an earlier `DefineFont` tag, along with the characters
+    /// that its glyphs draw.
+    #[inline]
+    fn define_font_info(
+        &mut self,
+        context: &mut UpdateContext<'gc>,
+        reader: &mut SwfStream<'a>,
+        version: u8,
+    ) -> Result<(), Error> {
+        let font_info = reader.read_define_font_info(version)?;
+        let library = context.library.library_for_movie_mut(self.movie());
+        match library.character_by_id(font_info.id) {
+            Some(Character::Font(font)) => {
+                let font = font.with_font_info(context.gc_context, &font_info, reader.encoding());
+                library.replace_font(font_info.id, font);
+            }
+            _ => tracing::warn!("DefineFontInfo: Character {} is not a font", font_info.id),
+        }
+        Ok(())
+    }
+
     #[inline]
     fn define_font_2(
         &mut self,
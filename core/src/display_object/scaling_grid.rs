@@ -0,0 +1,205 @@
+//! 9-slice scaling of display objects (`DisplayObject.scale9Grid` and `DefineScalingGrid`).
+//!
+//! An object with a scaling grid is split into nine slices by the lines of the grid.
+//! When the object is scaled, its corners keep their size, its edges are only stretched
+//! along their length, and only its center is scaled as a whole.
+
+use crate::context::RenderContext;
+use crate::prelude::*;
+use ruffle_render::transform::Transform;
+
+/// One of the slices that an object with a scaling grid is drawn in.
+#[derive(Clone, Debug, PartialEq)]
+struct Slice {
+    /// The transform from the slice of the object to where it's drawn, in the object's
+    /// own coordinates.
+    matrix: Matrix,
+
+    /// The area that the slice is drawn in, in the object's own coordinates.
+    clip: Rectangle<Twips>,
+}
+
+/// Draws the contents of the given object, slicing it along its scaling grid if it has one.
+pub fn render_with_scaling_grid<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+) {
+    let grid = this.scaling_grid();
+    let matrix = *this.base().matrix();
+    let scale_x = f32::hypot(matrix.a, matrix.b) as f64;
+    let scale_y = f32::hypot(matrix.c, matrix.d) as f64;
+    let slices = if grid.is_valid() {
+        slices(&grid, &this.bounds(), scale_x, scale_y)
+    } else {
+        None
+    };
+    let Some(slices) = slices else {
+        this.render_self(context);
+        return;
+    };
+
+    for slice in slices {
+        let clip_matrix = context.transform_stack.transform().matrix
+            * Matrix::create_box_from_rectangle(&slice.clip);
+        context.commands.push_mask();
+        // The color doesn't matter, as this is a mask.
+        context.commands.draw_rect(Color::WHITE, clip_matrix);
+        context.commands.activate_mask();
+
+        context.transform_stack.push(&Transform {
+            matrix: slice.matrix,
+            color_transform: Default::default(),
+        });
+        this.render_self(context);
+        context.transform_stack.pop();
+
+        context.commands.deactivate_mask();
+        context.commands.draw_rect(Color::WHITE, clip_matrix);
+        context.commands.pop_mask();
+    }
+}
+
+/// Splits an object with the given bounds along its scaling grid, or returns `None`
+/// if it doesn't need to be.
+///
+/// The object is scaled by the given amounts, which the corners and edges of the grid
+/// make up for. If the object is too small to fit them, they're shrunk to fit.
+fn slices(
+    grid: &Rectangle<Twips>,
+    bounds: &Rectangle<Twips>,
+    scale_x: f64,
+    scale_y: f64,
+) -> Option<Vec<Slice>> {
+    if (scale_x - 1.0).abs() < 1e-6 && (scale_y - 1.0).abs() < 1e-6 {
+        return None;
+    }
+    if scale_x <= 0.0 || scale_y <= 0.0 {
+        return None;
+    }
+
+    // The grid only has an effect if it's within the bounds of the object.
+    let grid_is_inside = bounds.is_valid()
+        && bounds.x_min < grid.x_min
+        && grid.x_max < bounds.x_max
+        && bounds.y_min < grid.y_min
+        && grid.y_max < bounds.y_max;
+    if !grid_is_inside {
+        return None;
+    }
+
+    let columns = split(
+        [bounds.x_min, grid.x_min, grid.x_max, bounds.x_max],
+        scale_x,
+    );
+    let rows = split(
+        [bounds.y_min, grid.y_min, grid.y_max, bounds.y_max],
+        scale_y,
+    );
+
+    let mut slices = Vec::with_capacity(9);
+    for (source_y, dest_y) in &rows {
+        for (source_x, dest_x) in &columns {
+            if dest_x.1 <= dest_x.0 || dest_y.1 <= dest_y.0 {
+                continue;
+            }
+
+            let a = (dest_x.1 - dest_x.0) / (source_x.1 - source_x.0);
+            let d = (dest_y.1 - dest_y.0) / (source_y.1 - source_y.0);
+            slices.push(Slice {
+                matrix: Matrix {
+                    a: a as f32,
+                    b: 0.0,
+                    c: 0.0,
+                    d: d as f32,
+                    tx: Twips::new((dest_x.0 - source_x.0 * a).round() as i32),
+                    ty: Twips::new((dest_y.0 - source_y.0 * d).round() as i32),
+                },
+                clip: Rectangle {
+                    x_min: Twips::new(dest_x.0.round() as i32),
+                    x_max: Twips::new(dest_x.1.round() as i32),
+                    y_min: Twips::new(dest_y.0.round() as i32),
+                    y_max: Twips::new(dest_y.1.round() as i32),
+                },
+            });
+        }
+    }
+    Some(slices)
+}
+
+/// Splits one axis of an object into its three slices, returning where each of them
+/// comes from and where it's drawn, in twips.
+fn split(lines: [Twips; 4], scale: f64) -> [((f64, f64), (f64, f64)); 3] {
+    let [start, first, second, end] = lines.map(|line| line.get() as f64);
+
+    // The borders keep their size once the object is scaled, unless there isn't enough room.
+    let borders = (first - start) + (end - second);
+    let shrink = ((end - start) * scale / borders).min(1.0);
+    let dest_first = start + (first - start) * shrink / scale;
+    let dest_second = end - (end - second) * shrink / scale;
+
+    [
+        ((start, first), (start, dest_first)),
+        ((first, second), (dest_first, dest_second)),
+        ((second, end), (dest_second, end)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x_min: f64, y_min: f64, x_max: f64, y_max: f64) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::from_pixels(x_min),
+            y_min: Twips::from_pixels(y_min),
+            x_max: Twips::from_pixels(x_max),
+            y_max: Twips::from_pixels(y_max),
+        }
+    }
+
+    #[test]
+    fn unscaled_objects_are_not_sliced() {
+        let grid = rect(10.0, 10.0, 90.0, 90.0);
+        let bounds = rect(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(slices(&grid, &bounds, 1.0, 1.0), None);
+    }
+
+    #[test]
+    fn grid_outside_bounds_is_ignored() {
+        let grid = rect(-10.0, 10.0, 90.0, 90.0);
+        let bounds = rect(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(slices(&grid, &bounds, 2.0, 2.0), None);
+    }
+
+    #[test]
+    fn corners_keep_their_size() {
+        let grid = rect(10.0, 10.0, 90.0, 90.0);
+        let bounds = rect(0.0, 0.0, 100.0, 100.0);
+        let slices = slices(&grid, &bounds, 2.0, 4.0).unwrap();
+        assert_eq!(slices.len(), 9);
+
+        // Once scaled, the top left corner is still 10 by 10 pixels.
+        let corner = &slices[0];
+        assert_eq!(corner.clip, rect(0.0, 0.0, 5.0, 2.5));
+        assert_eq!(corner.matrix.a, 0.5);
+        assert_eq!(corner.matrix.d, 0.25);
+
+        // The center makes up for it.
+        let center = &slices[4];
+        assert_eq!(center.clip, rect(5.0, 2.5, 95.0, 97.5));
+        assert_eq!(
+            center.matrix * Point::new(grid.x_min, grid.y_min),
+            Point::new(Twips::from_pixels(5.0), Twips::from_pixels(2.5))
+        );
+    }
+
+    #[test]
+    fn small_objects_shrink_their_borders() {
+        let grid = rect(10.0, 10.0, 90.0, 90.0);
+        let bounds = rect(0.0, 0.0, 100.0, 100.0);
+        // Scaled to 10 pixels, which is half of the 20 pixels of borders.
+        let slices = slices(&grid, &bounds, 0.1, 0.1).unwrap();
+        assert_eq!(slices.len(), 4);
+        assert_eq!(slices[0].clip, rect(0.0, 0.0, 50.0, 50.0));
+    }
+}
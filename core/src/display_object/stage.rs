@@ -129,6 +129,9 @@ pub struct StageData<'gc> {
     /// Whether to show default context menu items
     show_menu: bool,
 
+    /// Whether the mouse is locked, so that it only reports how far it moved.
+    mouse_lock: bool,
+
     /// The AVM2 view of this stage object.
     avm2_object: Option<Avm2Object<'gc>>,
 
@@ -182,6 +185,7 @@ impl<'gc> Stage<'gc> {
                 view_bounds: Default::default(),
                 window_mode: Default::default(),
                 show_menu: true,
+                mouse_lock: false,
                 stage_focus_rect: true,
                 avm2_object: None,
                 loader_info: None,
@@ -211,7 +215,6 @@ impl<'gc> Stage<'gc> {
             .unwrap_or(Matrix::ZERO)
     }
 
-    #[allow(dead_code)]
     pub fn view_matrix(self) -> Matrix {
         self.0.read().viewport_matrix
     }
@@ -397,6 +400,9 @@ impl<'gc> Stage<'gc> {
 
         if result.is_ok() {
             self.0.write(context.gc_context).display_state = display_state;
+            if !self.is_fullscreen() {
+                self.set_mouse_lock(context, false);
+            }
             self.fire_fullscreen_event(context);
         }
     }
@@ -462,6 +468,21 @@ impl<'gc> Stage<'gc> {
         write.show_menu = show_menu;
     }
 
+    /// Whether the mouse is locked, as set by `Stage.mouseLock`.
+    pub fn mouse_lock(self) -> bool {
+        self.0.read().mouse_lock
+    }
+
+    /// Locks or unlocks the mouse. The mouse can only be locked in fullscreen,
+    /// and is unlocked when leaving it.
+    pub fn set_mouse_lock(self, context: &mut UpdateContext<'gc>, mouse_lock: bool) {
+        let mouse_lock = mouse_lock && self.is_fullscreen();
+        if mouse_lock != self.mouse_lock() {
+            self.0.write(context.gc_context).mouse_lock = mouse_lock;
+            context.ui.set_mouse_lock(mouse_lock);
+        }
+    }
+
     /// Determine if we should letterbox the stage content.
     fn should_letterbox(self) -> bool {
         // Only enable letterbox in the default `ShowAll` scale mode.
@@ -12,6 +12,7 @@ use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefMut};
+use std::ops::Range;
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -34,6 +35,33 @@ pub struct TextData<'gc> {
     #[collect(require_static)]
     render_settings: TextRenderSettings,
     avm2_object: Option<Avm2Object<'gc>>,
+
+    /// Glyphs selected with `TextSnapshot.setSelected`, indexed by glyph.
+    selected_glyphs: Vec<bool>,
+
+    /// The color used to highlight selected glyphs.
+    #[collect(require_static)]
+    select_color: Color,
+}
+
+/// A single character drawn by a static text.
+#[derive(Clone, Debug)]
+pub struct TextGlyph {
+    /// The character this glyph draws.
+    pub character: char,
+
+    /// Whether this glyph starts a new line.
+    pub new_line: bool,
+
+    /// The bounds of this glyph in the local space of the text.
+    pub bounds: Rectangle<Twips>,
+
+    /// The matrix positioning this glyph in the local space of the text.
+    pub matrix: Matrix,
+
+    pub font_name: String,
+    pub color: Color,
+    pub height: Twips,
 }
 
 impl<'gc> Text<'gc> {
@@ -58,6 +86,8 @@ impl<'gc> Text<'gc> {
                 ),
                 render_settings: Default::default(),
                 avm2_object: None,
+                selected_glyphs: Vec::new(),
+                select_color: Color::YELLOW,
             },
         ))
     }
@@ -66,6 +96,103 @@ impl<'gc> Text<'gc> {
         self.0.write(gc_context).render_settings = settings;
         self.invalidate_cached_bitmap(gc_context);
     }
+
+    /// The characters drawn by this text, with a line break wherever a block starts a new line.
+    pub fn text(self, context: &UpdateContext<'gc>) -> String {
+        let mut text = String::new();
+        for glyph in self.glyphs(context) {
+            if glyph.new_line {
+                text.push('\n');
+            }
+            text.push(glyph.character);
+        }
+        text
+    }
+
+    /// The glyphs drawn by this text, in the order they are rendered.
+    pub fn glyphs(self, context: &UpdateContext<'gc>) -> Vec<TextGlyph> {
+        let tf = self.0.read();
+        let Some(library) = context.library.library_for_movie(self.movie()) else {
+            return Vec::new();
+        };
+
+        let text_transform = tf.static_data.text_transform;
+        let mut glyphs = Vec::new();
+        let mut color = Color::BLACK;
+        let mut font_id = 0;
+        let mut height = Twips::ZERO;
+        let mut x = Twips::ZERO;
+        let mut y = Twips::ZERO;
+        let mut line_y = None;
+        for block in &tf.static_data.text_blocks {
+            color = block.color.unwrap_or(color);
+            font_id = block.font_id.unwrap_or(font_id);
+            height = block.height.unwrap_or(height);
+            if let Some(x_offset) = block.x_offset {
+                x = x_offset;
+            }
+            let mut new_line = false;
+            if let Some(y_offset) = block.y_offset {
+                new_line = line_y.is_some_and(|y| y != y_offset);
+                line_y = Some(y_offset);
+                y = y_offset;
+            }
+
+            let Some(font) = library.get_font(font_id) else {
+                continue;
+            };
+            let ascent = font.get_baseline_for_height(height);
+            let descent = font.get_descent_for_height(height);
+            for c in &block.glyphs {
+                if let Some(glyph) = font.get_glyph(c.index as usize) {
+                    let advance = Twips::new(c.advance);
+                    let bounds = Rectangle {
+                        x_min: x,
+                        x_max: x + advance,
+                        y_min: y - ascent,
+                        y_max: y + descent,
+                    };
+                    glyphs.push(TextGlyph {
+                        character: glyph.character(),
+                        new_line,
+                        bounds: text_transform * bounds,
+                        matrix: text_transform * Matrix::translate(x, y),
+                        font_name: font.descriptor().name().to_owned(),
+                        color,
+                        height,
+                    });
+                    new_line = false;
+                    x += advance;
+                }
+            }
+        }
+        glyphs
+    }
+
+    /// Whether the glyph at the given index was selected with `TextSnapshot.setSelected`.
+    pub fn is_glyph_selected(self, index: usize) -> bool {
+        self.0
+            .read()
+            .selected_glyphs
+            .get(index)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    pub fn set_glyphs_selected(self, mc: &Mutation<'gc>, range: Range<usize>, selected: bool) {
+        let mut tf = self.0.write(mc);
+        if tf.selected_glyphs.len() < range.end {
+            tf.selected_glyphs.resize(range.end, false);
+        }
+        tf.selected_glyphs[range].fill(selected);
+        drop(tf);
+        self.invalidate_cached_bitmap(mc);
+    }
+
+    pub fn set_select_color(self, mc: &Mutation<'gc>, color: Color) {
+        self.0.write(mc).select_color = color;
+        self.invalidate_cached_bitmap(mc);
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Text<'gc> {
@@ -126,6 +253,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         let mut font_id = 0;
         let mut height = Twips::ZERO;
         let mut transform: Transform = Default::default();
+        let mut glyph_index = 0;
         for block in &tf.static_data.text_blocks {
             if let Some(x) = block.x_offset {
                 transform.matrix.tx = x;
@@ -148,6 +276,20 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 transform.color_transform.set_mult_color(&color);
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        if tf.selected_glyphs.get(glyph_index).copied() == Some(true) {
+                            let ascent = font.get_baseline_for_height(height);
+                            let descent = font.get_descent_for_height(height);
+                            let selection_box = context.transform_stack.transform().matrix
+                                * Matrix::create_box(
+                                    Twips::new(c.advance).to_pixels() as f32,
+                                    (ascent + descent).to_pixels() as f32,
+                                    transform.matrix.tx,
+                                    transform.matrix.ty - ascent,
+                                );
+                            context.commands.draw_rect(tf.select_color, selection_box);
+                        }
+                        glyph_index += 1;
+
                         if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
                             context.transform_stack.push(&transform);
                             context.commands.render_shape(
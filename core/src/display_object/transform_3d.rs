@@ -0,0 +1,289 @@
+//! The Flash Player 10 "2.5D" transforms of display objects.
+//!
+//! Display objects can be moved along the z axis and rotated around the x and y axes,
+//! after which they're drawn in perspective. Such an object is drawn flat into its bitmap
+//! cache, which is then projected into its parent. Anything that only works with 2D
+//! matrices, such as hit tests and bounds, uses the matrix that best matches the projection.
+
+use crate::prelude::*;
+use crate::types::{Degrees, Percent};
+use ruffle_render::projective::ProjectiveMatrix;
+
+/// The parts of the transform of a display object that place it in 3D space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transform3D {
+    /// The position along the z axis, in pixels. Larger values are further away.
+    pub z: f64,
+
+    /// The rotation around the x axis.
+    pub rotation_x: Degrees,
+
+    /// The rotation around the y axis.
+    pub rotation_y: Degrees,
+
+    /// The scale along the z axis.
+    pub scale_z: f64,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from(0.0),
+            rotation_y: Degrees::from(0.0),
+            scale_z: 1.0,
+        }
+    }
+}
+
+/// The perspective that display objects in 3D space are seen with (`PerspectiveProjection`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The angle that the viewer sees, from 0 to 180 degrees.
+    pub field_of_view: f64,
+
+    /// The point that the viewer looks at, in pixels of the owning display object.
+    pub projection_center: (f64, f64),
+}
+
+impl PerspectiveProjection {
+    /// The default perspective of the stage, which looks at its center.
+    pub fn for_stage(stage_size: (u32, u32)) -> Self {
+        Self {
+            field_of_view: 55.0,
+            projection_center: (stage_size.0 as f64 / 2.0, stage_size.1 as f64 / 2.0),
+        }
+    }
+
+    /// The distance between the viewer and the plane at z = 0, in pixels, which depends
+    /// on the width of the stage.
+    pub fn focal_length(&self, stage_width: f64) -> f64 {
+        let half_angle = (self.field_of_view.clamp(0.01, 179.99) / 2.0).to_radians();
+        stage_width / 2.0 / half_angle.tan()
+    }
+}
+
+/// How the given display object is projected into its parent, if it's been placed in 3D space.
+pub fn projection(object: DisplayObject<'_>) -> Option<ProjectiveMatrix> {
+    let transform_3d = object.base().transform_3d().cloned()?;
+    let stage_size = stage_size(object);
+
+    // The nearest ancestor with its own perspective decides how this object is seen.
+    let mut owner = object.parent();
+    let (perspective, owner_matrix) = loop {
+        match owner {
+            Some(ancestor) if ancestor.as_stage().is_none() => {
+                if let Some(perspective) = ancestor.base().perspective_projection() {
+                    break (perspective, flat_local_to_global_matrix(ancestor));
+                }
+                owner = ancestor.parent();
+            }
+            _ => {
+                break (
+                    PerspectiveProjection::for_stage(stage_size),
+                    Matrix::IDENTITY,
+                )
+            }
+        }
+    };
+    let parent_to_global = object
+        .parent()
+        .map(flat_local_to_global_matrix)
+        .unwrap_or(Matrix::IDENTITY);
+    let center = parent_to_global.inverse()?
+        * (owner_matrix
+            * Point::new(
+                Twips::from_pixels(perspective.projection_center.0),
+                Twips::from_pixels(perspective.projection_center.1),
+            ));
+
+    // Skew can't be kept in 3D space, but flips are.
+    let matrix = *object.base().matrix();
+    let (a, b, c, d) = (
+        f64::from(matrix.a),
+        f64::from(matrix.b),
+        f64::from(matrix.c),
+        f64::from(matrix.d),
+    );
+    let scale_x = a.hypot(b);
+    let scale_y = if scale_x > 0.0 {
+        (a * d - b * c) / scale_x
+    } else {
+        c.hypot(d)
+    };
+    Some(project(
+        &matrix,
+        Degrees::from_radians(b.atan2(a)),
+        (Percent::from_unit(scale_x), Percent::from_unit(scale_y)),
+        &transform_3d,
+        center,
+        perspective.focal_length(stage_size.0 as f64),
+    ))
+}
+
+/// The 2D matrix that best matches how the given display object is projected into its
+/// parent, if it's been placed in 3D space.
+pub fn projected_matrix(object: DisplayObject<'_>) -> Option<Matrix> {
+    let projection = projection(object)?;
+    let bounds = object.bounds();
+    let middle = if bounds.is_valid() {
+        Point::new(
+            Twips::new((bounds.x_min.get() + bounds.x_max.get()) / 2),
+            Twips::new((bounds.y_min.get() + bounds.y_max.get()) / 2),
+        )
+    } else {
+        Point::ZERO
+    };
+    Some(projection.linearize_at(middle))
+}
+
+/// The matrix from the coordinates of the given display object to the stage's, as if it and
+/// its ancestors were all flat.
+///
+/// The bounds that `projected_matrix` depends on include the projections of children, so
+/// using the projections of ancestors here would never end.
+fn flat_local_to_global_matrix(object: DisplayObject<'_>) -> Matrix {
+    let mut node = Some(object);
+    let mut matrix = Matrix::IDENTITY;
+    while let Some(display_object) = node {
+        if display_object.as_stage().is_some() {
+            break;
+        }
+        if let Some(rect) = display_object.scroll_rect() {
+            matrix = Matrix::translate(-rect.x_min, -rect.y_min) * matrix;
+        }
+        matrix = *display_object.base().matrix() * matrix;
+        node = display_object.parent();
+    }
+    matrix
+}
+
+/// The size of the stage that the given display object is on.
+fn stage_size(object: DisplayObject<'_>) -> (u32, u32) {
+    let mut node = Some(object);
+    while let Some(display_object) = node {
+        if let Some(stage) = display_object.as_stage() {
+            return stage.stage_size();
+        }
+        node = display_object.parent();
+    }
+    // Objects that aren't on a stage are seen as if they were on one the size of their movie.
+    let movie = object.movie();
+    (
+        movie.width().to_pixels() as u32,
+        movie.height().to_pixels() as u32,
+    )
+}
+
+/// Projects an object with the given transform from its own coordinates into its parent's.
+fn project(
+    matrix: &Matrix,
+    rotation: Degrees,
+    (scale_x, scale_y): (Percent, Percent),
+    transform_3d: &Transform3D,
+    center: Point<Twips>,
+    focal_length: f64,
+) -> ProjectiveMatrix {
+    // Scale, then rotate around x, y and z, then move into place.
+    let (sin_x, cos_x) = transform_3d.rotation_x.into_radians().sin_cos();
+    let (sin_y, cos_y) = transform_3d.rotation_y.into_radians().sin_cos();
+    let (sin_z, cos_z) = rotation.into_radians().sin_cos();
+    let rotate = |[x, y, z]: [f64; 3]| {
+        let (y, z) = (y * cos_x - z * sin_x, y * sin_x + z * cos_x);
+        let (x, z) = (x * cos_y + z * sin_y, -x * sin_y + z * cos_y);
+        [x * cos_z - y * sin_z, x * sin_z + y * cos_z, z]
+    };
+    let focal_length = focal_length * Twips::TWIPS_PER_PIXEL as f64;
+    let center = (center.x.get() as f64, center.y.get() as f64);
+
+    // Where the x and y axes and the origin of the object end up in 3D space.
+    let x_axis = rotate([scale_x.unit(), 0.0, 0.0]);
+    let y_axis = rotate([0.0, scale_y.unit(), 0.0]);
+    let origin = [
+        matrix.tx.get() as f64,
+        matrix.ty.get() as f64,
+        transform_3d.z * Twips::TWIPS_PER_PIXEL as f64,
+    ];
+
+    // A point at depth z is moved towards the center by `focal_length / (focal_length + z)`,
+    // which is a division by `1 + z / focal_length`.
+    let project_axis = |[x, y, z]: [f64; 3]| {
+        let w = z / focal_length;
+        (x + center.0 * w, y + center.1 * w, w)
+    };
+    let (a, b, p) = project_axis(x_axis);
+    let (c, d, q) = project_axis(y_axis);
+    let (tx, ty, r) = project_axis(origin);
+    ProjectiveMatrix {
+        a,
+        b,
+        c,
+        d,
+        tx,
+        ty,
+        p,
+        q,
+        r: 1.0 + r,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_simple(transform_3d: Transform3D) -> ProjectiveMatrix {
+        project(
+            &Matrix::translate(Twips::from_pixels(100.0), Twips::from_pixels(100.0)),
+            Degrees::from(0.0),
+            (Percent::from_unit(1.0), Percent::from_unit(1.0)),
+            &transform_3d,
+            Point::new(Twips::from_pixels(100.0), Twips::from_pixels(100.0)),
+            500.0,
+        )
+    }
+
+    fn pixels(x: f64, y: f64) -> Point<Twips> {
+        Point::new(Twips::from_pixels(x), Twips::from_pixels(y))
+    }
+
+    fn project_pixels(projection: &ProjectiveMatrix, x: f64, y: f64) -> (f64, f64) {
+        let point = projection
+            .transform_point(pixels(x, y))
+            .expect("In front of the viewer");
+        (point.x.to_pixels(), point.y.to_pixels())
+    }
+
+    #[test]
+    fn flat_objects_are_unchanged() {
+        let projection = project_simple(Transform3D::default());
+        assert_eq!(project_pixels(&projection, 0.0, 0.0), (100.0, 100.0));
+        assert_eq!(project_pixels(&projection, 50.0, 20.0), (150.0, 120.0));
+    }
+
+    #[test]
+    fn distant_objects_are_smaller() {
+        let projection = project_simple(Transform3D {
+            z: 500.0,
+            ..Default::default()
+        });
+        // Twice as far away as the plane at z = 0, so half as large around the center.
+        assert_eq!(project_pixels(&projection, 0.0, 0.0), (100.0, 100.0));
+        assert_eq!(project_pixels(&projection, 50.0, 50.0), (125.0, 125.0));
+    }
+
+    #[test]
+    fn rotated_objects_are_seen_in_perspective() {
+        let projection = project_simple(Transform3D {
+            rotation_y: Degrees::from(60.0),
+            ..Default::default()
+        });
+        // The right side turns towards the viewer, and the left side away from them.
+        let right = project_pixels(&projection, 50.0, 50.0);
+        let left = project_pixels(&projection, -50.0, 50.0);
+        assert!(right.0 - 100.0 > 25.0 && right.0 - 100.0 < 30.0);
+        assert!(100.0 - left.0 > 20.0 && 100.0 - left.0 < 25.0);
+        // A skew would keep both sides the same height.
+        assert!(right.1 - 100.0 > 54.0);
+        assert!(left.1 - 100.0 < 47.0);
+    }
+}
@@ -13,7 +13,7 @@ use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::{AvmObject, Instantiator};
 use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
-use ruffle_render::bitmap::{BitmapInfo, PixelSnapping};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapInfo, PixelRegion, PixelSnapping};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::quality::StageQuality;
 use ruffle_video::error::Error;
@@ -25,6 +25,12 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use swf::{DefineVideoStream, VideoCodec, VideoFrame};
 
+/// The number of frames that timeline video may drift from its stream sound
+/// before video frames are dropped or held to re-converge.
+///
+/// This is raised to the audio backend's position resolution if that is coarser.
+const SYNC_THRESHOLD_FRAMES: f64 = 1.0;
+
 /// A Video display object is a high-level interface to a video player.
 ///
 /// Video data may be embedded within a variety of container formats, including
@@ -77,6 +83,13 @@ pub struct VideoData<'gc> {
     /// NOTE: This is only used for SWF-source video streams.
     #[collect(require_static)]
     decoded_frame: Option<(u32, BitmapInfo)>,
+
+    /// The frame of the parent timeline on which a `PlaceObject` ratio was last
+    /// applied to this video, along with that ratio.
+    ///
+    /// This tells how fast the timeline goes through the video, which
+    /// isn't necessarily one video frame per timeline frame.
+    placed_ratio: Option<(u16, u32)>,
 }
 
 /// An optionally-instantiated video stream.
@@ -101,7 +114,7 @@ pub enum VideoSource<'gc> {
     /// particular character. If you need to mutate the video source, consider
     /// reallocating a new source for your specific video instead.
     ///
-    /// This warning does not apply to `NetStream` or `Unconnected` videos,
+    /// This warning does not apply to `NetStream`, `Camera` or `Unconnected` videos,
     /// which are never aliased.
     Swf {
         /// The video stream definition.
@@ -119,6 +132,16 @@ pub enum VideoSource<'gc> {
         /// The stream the video is downloaded from.
         stream: NetStream<'gc>,
     },
+    /// An attached camera.
+    Camera {
+        /// The index of the camera in the camera backend.
+        index: usize,
+
+        /// The last camera frame uploaded to the renderer, along with the
+        /// backend's frame number for it.
+        #[collect(require_static)]
+        frame: Option<(u64, BitmapInfo)>,
+    },
     Unconnected,
 }
 
@@ -149,6 +172,7 @@ impl<'gc> Video<'gc> {
                 movie,
                 size,
                 decoded_frame: None,
+                placed_ratio: None,
             },
         ))
     }
@@ -173,6 +197,7 @@ impl<'gc> Video<'gc> {
                 movie,
                 size: (width, height),
                 decoded_frame: None,
+                placed_ratio: None,
             },
         ))
     }
@@ -192,6 +217,71 @@ impl<'gc> Video<'gc> {
         video.keyframes = BTreeSet::new();
     }
 
+    /// Convert this Video into a camera sourced video, or disconnect it if no
+    /// camera is given.
+    ///
+    /// Existing video state related to the old video stream will be dropped.
+    pub fn attach_camera(self, context: &mut UpdateContext<'gc>, index: Option<usize>) {
+        let mut video = self.0.write(context.gc_context);
+
+        let source = match index {
+            Some(index) => VideoSource::Camera { index, frame: None },
+            None => VideoSource::Unconnected,
+        };
+        video.source = GcCell::new(context.gc_context, source);
+        video.stream = VideoStream::Uninstantiated(0);
+        video.keyframes = BTreeSet::new();
+    }
+
+    /// Upload the latest frame of the attached camera, if it changed since
+    /// the last upload.
+    fn update_camera_frame(self, context: &mut UpdateContext<'gc>) {
+        let source = self.0.read().source;
+        let mut source = source.write(context.gc_context);
+        let VideoSource::Camera { index, frame } = &mut *source else {
+            return;
+        };
+
+        let Some((number, camera_frame)) = context.camera.latest_frame(*index) else {
+            return;
+        };
+        if matches!(frame, Some((last_number, _)) if *last_number == number) {
+            return;
+        }
+
+        let bitmap = Bitmap::new(
+            camera_frame.width,
+            camera_frame.height,
+            BitmapFormat::Rgba,
+            camera_frame.rgba.clone(),
+        );
+        let result = match frame {
+            Some((_, info))
+                if u32::from(info.width) == camera_frame.width
+                    && u32::from(info.height) == camera_frame.height =>
+            {
+                let region = PixelRegion::for_whole_size(camera_frame.width, camera_frame.height);
+                context
+                    .renderer
+                    .update_texture(&info.handle, bitmap, region)
+                    .map(|_| info.clone())
+            }
+            _ => context
+                .renderer
+                .register_bitmap(bitmap)
+                .map(|handle| BitmapInfo {
+                    handle,
+                    width: camera_frame.width as u16,
+                    height: camera_frame.height as u16,
+                }),
+        };
+
+        match result {
+            Ok(info) => *frame = Some((number, info)),
+            Err(e) => tracing::error!("Got error when uploading camera frame: {}", e),
+        }
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -216,6 +306,7 @@ impl<'gc> Video<'gc> {
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
             VideoSource::NetStream { .. } => {}
+            VideoSource::Camera { .. } => {}
             VideoSource::Unconnected { .. } => {}
         }
     }
@@ -244,6 +335,7 @@ impl<'gc> Video<'gc> {
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.num_frames as usize,
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -296,6 +388,88 @@ impl<'gc> Video<'gc> {
         }
     }
 
+    /// Seek to the frame selected by a `PlaceObject` ratio.
+    ///
+    /// If the timeline this video is placed on is playing a stream sound, the
+    /// audio track is used as the clock: once the video drifts too far from it,
+    /// frames are either dropped (decoding from the preceding keyframe, as with
+    /// any other seek) or held until the audio catches up. The audio itself is
+    /// never adjusted; keeping the timeline close to it is up to
+    /// `AudioManager::audio_skew_time`, so this only corrects the drift that's
+    /// left over.
+    pub fn seek_to_ratio(self, context: &mut UpdateContext<'gc>, ratio: u32) {
+        let Some(drift) = self.audio_drift(context, ratio) else {
+            return self.seek(context, ratio);
+        };
+
+        let last_frame = self.0.read().decoded_frame.as_ref().map(|(lf, _)| *lf);
+        match sync_action(ratio, last_frame, drift) {
+            SyncAction::Show(frame_id) => {
+                if frame_id != ratio {
+                    tracing::debug!(
+                        "Video is {:.2} frames out of sync with its audio, showing frame {frame_id} instead of {ratio}",
+                        drift.frames
+                    );
+                }
+                self.seek(context, frame_id);
+            }
+            SyncAction::Hold => {}
+        }
+    }
+
+    /// Returns how far this video is from the stream sound playing on its
+    /// timeline (or the nearest ancestor timeline that has one).
+    ///
+    /// Also records `ratio` as the latest one placed on the timeline.
+    fn audio_drift(self, context: &mut UpdateContext<'gc>, ratio: u32) -> Option<AudioDrift> {
+        let timeline = self.parent()?.as_movie_clip()?;
+        let timeline_frame = timeline.current_frame();
+        let placed_ratio = self
+            .0
+            .write(context.gc_context)
+            .placed_ratio
+            .replace((timeline_frame, ratio));
+
+        let num_frames = match &*self.0.read().source.read() {
+            VideoSource::Swf { streamdef, .. } => u32::from(streamdef.num_frames),
+            _ => return None,
+        };
+
+        let mut ancestor = Some(DisplayObject::from(timeline));
+        let skew = std::iter::from_fn(|| {
+            let object = ancestor?;
+            ancestor = object.parent();
+            Some(object)
+        })
+        .find_map(|object| {
+            context
+                .audio_manager
+                .stream_skew_time(&*context.audio, object.as_movie_clip()?)
+        })?;
+
+        // Work out how many video frames the timeline goes through per second, from the
+        // ratio it placed last time. After a goto backwards, wait for the next placement.
+        let (placed_frame, placed_ratio) = placed_ratio?;
+        let timeline_frames = timeline_frame
+            .checked_sub(placed_frame)
+            .filter(|f| *f > 0)?;
+        let video_frames = ratio.checked_sub(placed_ratio)?;
+        let frame_rate = timeline.movie().frame_rate().to_f64();
+        let video_frame_rate = f64::from(video_frames) / f64::from(timeline_frames) * frame_rate;
+
+        let threshold = context
+            .audio
+            .position_resolution()
+            .map(|resolution| resolution.as_secs_f64() * video_frame_rate)
+            .unwrap_or_default()
+            .max(SYNC_THRESHOLD_FRAMES);
+        Some(AudioDrift {
+            frames: -skew * video_frame_rate,
+            threshold,
+            num_frames,
+        })
+    }
+
     /// Decode a single frame of video.
     ///
     /// This function makes no attempt to ensure that the proposed seek is
@@ -332,6 +506,7 @@ impl<'gc> Video<'gc> {
                 }
             },
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -343,11 +518,56 @@ impl<'gc> Video<'gc> {
                 self.invalidate_cached_bitmap(context.gc_context);
                 *context.needs_render = true;
             }
+            // Keep showing the current frame until the decoder catches up.
+            Err(Error::FramePending) => {}
             Err(e) => tracing::error!("Got error when seeking to video frame {}: {}", frame_id, e),
         }
     }
 }
 
+/// How far a timeline video is from the stream sound it's synchronized to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct AudioDrift {
+    /// How many video frames the video is ahead of its audio track (or behind it, if negative).
+    frames: f64,
+
+    /// The drift that is tolerated before correcting it, in video frames.
+    threshold: f64,
+
+    /// The number of frames in the video, which a correction never goes past.
+    num_frames: u32,
+}
+
+/// How a timeline video responds to the frame requested by a `PlaceObject` ratio.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SyncAction {
+    /// Show the given frame, which is either the requested one or the one
+    /// that the audio track has reached.
+    Show(u32),
+
+    /// Keep showing the current frame until the audio track catches up.
+    Hold,
+}
+
+/// Decides which frame to show for `ratio`, given how far the video is from its audio track.
+fn sync_action(ratio: u32, last_frame: Option<u32>, drift: AudioDrift) -> SyncAction {
+    if drift.frames.abs() <= drift.threshold {
+        return SyncAction::Show(ratio);
+    }
+
+    // Seeking past the end would wrap around to the start of the video.
+    let final_frame = drift.num_frames.saturating_sub(1);
+    let audio_frame = (f64::from(ratio) - drift.frames)
+        .round()
+        .clamp(0.0, f64::from(final_frame)) as u32;
+    match last_frame {
+        // Going back to an earlier frame would mean decoding from a keyframe again,
+        // and the audio will reach the current frame soon enough.
+        Some(last_frame) if drift.frames > 0.0 && audio_frame <= last_frame => SyncAction::Hold,
+        _ => SyncAction::Show(audio_frame),
+    }
+}
+
 impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     fn base(&self) -> Ref<DisplayObjectBase<'gc>> {
         Ref::map(self.0.read(), |r| &r.base)
@@ -435,6 +655,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 }
             }
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -470,6 +691,10 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         }
     }
 
+    fn enter_frame(&self, context: &mut UpdateContext<'gc>) {
+        self.update_camera_frame(context);
+    }
+
     fn construct_frame(&self, context: &mut UpdateContext<'gc>) {
         if self.movie().is_action_script_3() && matches!(self.object2(), Avm2Value::Null) {
             let video_constr = context.avm2.classes().video;
@@ -496,6 +721,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         match &*self.0.read().source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
             VideoSource::NetStream { .. } => 0,
+            VideoSource::Camera { .. } => 0,
             VideoSource::Unconnected { .. } => 0,
         }
     }
@@ -541,6 +767,18 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 stream.last_decoded_bitmap(),
                 None,
             ),
+            VideoSource::Camera {
+                frame: Some((_, bitmap)),
+                ..
+            } => (
+                false,
+                None,
+                read.movie.version(),
+                Some(bitmap.clone()),
+                None,
+            ),
+            // No frame has been captured yet.
+            VideoSource::Camera { frame: None, .. } => return context.transform_stack.pop(),
             VideoSource::Unconnected { .. } => return context.transform_stack.pop(),
         };
 
@@ -599,3 +837,38 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
             .unwrap_or(Avm2Value::Null)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drift(frames: f64) -> AudioDrift {
+        AudioDrift {
+            frames,
+            threshold: 1.0,
+            num_frames: 100,
+        }
+    }
+
+    #[test]
+    fn in_sync_shows_requested_frame() {
+        assert_eq!(sync_action(10, Some(9), drift(0.5)), SyncAction::Show(10));
+        assert_eq!(sync_action(10, Some(9), drift(-1.0)), SyncAction::Show(10));
+    }
+
+    #[test]
+    fn behind_audio_drops_frames() {
+        assert_eq!(sync_action(10, Some(9), drift(-5.0)), SyncAction::Show(15));
+    }
+
+    #[test]
+    fn behind_audio_stops_at_final_frame() {
+        assert_eq!(sync_action(97, Some(96), drift(-5.0)), SyncAction::Show(99));
+    }
+
+    #[test]
+    fn ahead_of_audio_holds_frame() {
+        assert_eq!(sync_action(10, Some(9), drift(3.0)), SyncAction::Hold);
+        assert_eq!(sync_action(10, None, drift(3.0)), SyncAction::Show(7));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::context::RenderContext;
-use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::backend::{DeviceId, RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::shape_utils::{
@@ -10,12 +10,14 @@ use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
 
 #[derive(Clone, Debug)]
 pub struct Drawing {
-    render_handle: RefCell<Option<ShapeHandle>>,
+    /// The handle of the last registered shape, along with the device it lives on.
+    render_handle: RefCell<Option<(DeviceId, Option<ShapeHandle>)>>,
     shape_bounds: Rectangle<Twips>,
     edge_bounds: Rectangle<Twips>,
     dirty: Cell<bool>,
     paths: Vec<DrawingPath>,
-    bitmaps: Vec<BitmapInfo>,
+    /// Bitmaps used by fills, along with the device they were registered on.
+    bitmaps: Vec<(DeviceId, BitmapInfo)>,
     current_fill: Option<DrawingFill>,
     current_line: Option<DrawingLine>,
     pending_lines: Vec<DrawingLine>,
@@ -244,15 +246,31 @@ impl Drawing {
         self.dirty.set(true);
     }
 
-    pub fn add_bitmap(&mut self, bitmap: BitmapInfo) -> u16 {
+    pub fn add_bitmap(&mut self, bitmap: BitmapInfo, device_id: DeviceId) -> u16 {
         let id = self.bitmaps.len() as u16;
-        self.bitmaps.push(bitmap);
+        self.bitmaps.push((device_id, bitmap));
         id
     }
 
+    /// The style and rule of the fill that's currently being drawn, if any.
+    pub fn current_fill(&self) -> Option<(FillStyle, FillRule)> {
+        self.current_fill
+            .as_ref()
+            .map(|fill| (fill.style.clone(), fill.rule))
+    }
+
+    /// The size in pixels of a bitmap used by the fills of this drawing.
+    pub fn bitmap_size(&self, id: u16) -> Option<(u16, u16)> {
+        self.bitmaps
+            .get(id as usize)
+            .map(|(_, bitmap)| (bitmap.width, bitmap.height))
+    }
+
     /// Obtain a `ShapeHandle` that represents this `Drawing`, or `None` if it is empty.
     pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
-        if self.dirty.get() {
+        let device_id = renderer.device_id();
+        let registered_on = self.render_handle.borrow().as_ref().map(|(id, _)| *id);
+        if self.dirty.get() || registered_on != Some(device_id) {
             let mut paths = Vec::with_capacity(self.paths.len());
 
             for path in &self.paths {
@@ -325,10 +343,14 @@ impl Drawing {
             };
 
             self.dirty.set(false);
-            self.render_handle.replace(handle.clone());
+            self.render_handle
+                .replace(Some((device_id, handle.clone())));
             handle
         } else {
-            self.render_handle.borrow().to_owned()
+            self.render_handle
+                .borrow()
+                .as_ref()
+                .and_then(|(_, handle)| handle.clone())
         }
     }
 
@@ -434,13 +456,17 @@ impl Drawing {
 
 impl BitmapSource for Drawing {
     fn bitmap_size(&self, id: u16) -> Option<BitmapSize> {
-        self.bitmaps.get(id as usize).map(|bm| BitmapSize {
+        self.bitmaps.get(id as usize).map(|(_, bm)| BitmapSize {
             width: bm.width,
             height: bm.height,
         })
     }
-    fn bitmap_handle(&self, id: u16, _backend: &mut dyn RenderBackend) -> Option<BitmapHandle> {
-        self.bitmaps.get(id as usize).map(|bm| bm.handle.clone())
+    fn bitmap_handle(&self, id: u16, backend: &mut dyn RenderBackend) -> Option<BitmapHandle> {
+        // A bitmap registered on a lost device can't be drawn; the fill is skipped instead.
+        self.bitmaps
+            .get(id as usize)
+            .filter(|(device_id, _)| *device_id == backend.device_id())
+            .map(|(_, bm)| bm.handle.clone())
     }
 }
 
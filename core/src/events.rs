@@ -1,7 +1,7 @@
 use crate::display_object::InteractiveObject;
 use swf::ClipEventFlag;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PlayerEvent {
     KeyDown {
         key_code: KeyCode,
@@ -27,6 +27,13 @@ pub enum PlayerEvent {
         index: Option<usize>,
     },
     MouseLeave,
+
+    /// The mouse moved by the given number of pixels while it was locked
+    /// with `Stage.mouseLock`, so its position didn't change.
+    MouseMotion {
+        dx: f64,
+        dy: f64,
+    },
     MouseWheel {
         delta: MouseWheelDelta,
     },
@@ -46,6 +53,47 @@ pub enum PlayerEvent {
     FocusLost,
 }
 
+/// An event from the system's input method editor (IME), which is used to
+/// compose text in languages such as Chinese, Japanese or Korean.
+///
+/// These aren't `PlayerEvent`s, because they carry strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// The text being composed has changed.
+    ///
+    /// The range is the cursor or selection within the composed text, as byte
+    /// offsets, if the IME shows one.
+    Preedit(String, Option<(usize, usize)>),
+
+    /// The composition is done, and the text should be inserted.
+    Commit(String),
+}
+
+/// A finger touching the player, as reported by a touch screen.
+///
+/// The primary touch point should also be reported as mouse events by the
+/// frontend; these are only used for `TouchEvent`s and gestures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchEvent {
+    /// Identifies the finger for as long as it touches the screen.
+    pub id: u64,
+    pub phase: TouchPhase,
+
+    /// The position of the finger, in the same coordinates as mouse events.
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    Begin,
+    Move,
+    End,
+
+    /// The system took the touch away, for example to scroll the page.
+    Cancel,
+}
+
 /// The distance scrolled by the mouse wheel.
 #[derive(Debug, Clone, Copy)]
 pub enum MouseWheelDelta {
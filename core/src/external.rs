@@ -1,3 +1,4 @@
+use crate::avm1::NativeObject as Avm1NativeObject;
 use crate::avm1::TObject as _;
 use crate::avm1::Value as Avm1Value;
 use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier};
@@ -6,8 +7,9 @@ use crate::avm1::{
     ScriptObject as Avm1ScriptObject,
 };
 use crate::avm2::activation::Activation as Avm2Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::Error as Avm2Error;
-use crate::avm2::object::TObject as _;
+use crate::avm2::object::{ByteArrayObject, TObject as _};
 use crate::avm2::Value as Avm2Value;
 use crate::avm2::{ArrayObject as Avm2ArrayObject, Object as Avm2Object};
 use crate::context::UpdateContext;
@@ -16,6 +18,10 @@ use gc_arena::Collect;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
+mod xml;
+
+pub use xml::{Invoke, InvokeError};
+
 /// An intermediate format of representing shared data between ActionScript and elsewhere.
 ///
 /// Regardless of the capabilities of both sides, all data will be translated to this potentially
@@ -29,6 +35,9 @@ pub enum Value {
     String(String),
     Object(BTreeMap<String, Value>),
     List(Vec<Value>),
+    /// Milliseconds since the Unix epoch.
+    Date(f64),
+    ByteArray(Vec<u8>),
 }
 
 impl From<AvmString<'_>> for Value {
@@ -134,7 +143,9 @@ impl Value {
             Avm1Value::String(value) => Value::String(value.to_string()),
             Avm1Value::MovieClip(_) => Value::Null,
             Avm1Value::Object(object) => {
-                if object.as_array_object().is_some() {
+                if let Avm1NativeObject::Date(date) = object.native() {
+                    Value::Date(date.get().time())
+                } else if object.as_array_object().is_some() {
                     let length = object.length(activation)?;
                     let values: Result<Vec<_>, Avm1Error<'gc>> = (0..length)
                         .map(|i| {
@@ -189,6 +200,19 @@ impl Value {
                     .map(|value| value.to_owned().into_avm1(activation)),
             )
             .into(),
+            Value::Date(time) => {
+                let date_constructor = activation.context.avm1.prototypes().date_constructor;
+                date_constructor
+                    .construct(activation, &[time.into()])
+                    .unwrap_or(Avm1Value::Undefined)
+            }
+            // AVM1 has no ByteArray, so the bytes become an array of numbers.
+            Value::ByteArray(bytes) => Avm1ArrayObject::new(
+                activation.context.gc_context,
+                activation.context.avm1.prototypes().array,
+                bytes.into_iter().map(|byte| Avm1Value::Number(byte.into())),
+            )
+            .into(),
         }
     }
 
@@ -214,6 +238,12 @@ impl Value {
                         })
                         .collect::<Result<Vec<Value>, Avm2Error>>()?;
                     Value::List(values)
+                } else if let Some(date) = obj.as_date_object() {
+                    date.date_time()
+                        .map(|date_time| Value::Date(date_time.timestamp_millis() as f64))
+                        .unwrap_or(Value::Date(f64::NAN))
+                } else if let Some(bytearray) = obj.as_bytearray() {
+                    Value::ByteArray(bytearray.bytes().to_vec())
                 } else if matches!(obj, Avm2Object::ScriptObject(_)) {
                     let mut values = BTreeMap::new();
 
@@ -273,6 +303,17 @@ impl Value {
 
                 Avm2Value::Object(Avm2ArrayObject::from_storage(activation, storage).unwrap())
             }
+            Value::Date(time) => activation
+                .avm2()
+                .classes()
+                .date
+                .construct(activation, &[time.into()])
+                .map(Avm2Value::Object)
+                .unwrap_or(Avm2Value::Undefined),
+            Value::ByteArray(bytes) => {
+                let storage = ByteArrayStorage::from_vec(bytes);
+                Avm2Value::Object(ByteArrayObject::from_storage(activation, storage).unwrap())
+            }
         }
     }
 }
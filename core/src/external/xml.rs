@@ -0,0 +1,301 @@
+//! The XML encoding that Flash Player uses for `ExternalInterface` calls between the
+//! host and the movie, as seen by `CallFunction` and `__flash__toXML`.
+
+use super::Value;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+#[derive(Debug, thiserror::Error)]
+pub enum InvokeError {
+    #[error("Invalid XML: {0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("Unexpected element <{0}>")]
+    UnexpectedElement(String),
+
+    #[error("Missing attribute '{0}'")]
+    MissingAttribute(&'static str),
+
+    #[error("Invalid number '{0}'")]
+    InvalidNumber(String),
+
+    #[error("Unexpected end of document")]
+    UnexpectedEof,
+}
+
+/// A method call in the form of `<invoke name="..."><arguments>...</arguments></invoke>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invoke {
+    pub name: String,
+    pub arguments: Vec<Value>,
+}
+
+impl Invoke {
+    pub fn from_xml(xml: &str) -> Result<Self, InvokeError> {
+        let mut parser = Parser::new(xml);
+        let start = parser.start()?;
+        if start.name().as_ref() != b"invoke" {
+            return Err(unexpected(&start));
+        }
+        let name = attribute(&start, "name")?;
+
+        let mut arguments = Vec::new();
+        while let Some(start) = parser.start_or_end()? {
+            if start.name().as_ref() != b"arguments" {
+                return Err(unexpected(&start));
+            }
+            while let Some(start) = parser.start_or_end()? {
+                arguments.push(parser.value(&start)?);
+            }
+        }
+
+        Ok(Self { name, arguments })
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = format!(
+            "<invoke name=\"{}\" returntype=\"xml\"><arguments>",
+            escape(&self.name)
+        );
+        for argument in &self.arguments {
+            write_value(&mut xml, argument);
+        }
+        xml.push_str("</arguments></invoke>");
+        xml
+    }
+}
+
+impl Value {
+    /// Parses a single value, such as the result of a `CallFunction`.
+    pub fn from_xml(xml: &str) -> Result<Self, InvokeError> {
+        let mut parser = Parser::new(xml);
+        let start = parser.start()?;
+        parser.value(&start)
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        write_value(&mut xml, self);
+        xml
+    }
+}
+
+fn write_value(xml: &mut String, value: &Value) {
+    match value {
+        Value::Undefined => xml.push_str("<undefined/>"),
+        Value::Null => xml.push_str("<null/>"),
+        Value::Bool(true) => xml.push_str("<true/>"),
+        Value::Bool(false) => xml.push_str("<false/>"),
+        Value::Number(value) => {
+            let _ = write!(xml, "<number>{value}</number>");
+        }
+        Value::String(value) => {
+            let _ = write!(xml, "<string>{}</string>", escape(value));
+        }
+        Value::Date(time) => {
+            let _ = write!(xml, "<date>{time}</date>");
+        }
+        Value::Object(values) => {
+            xml.push_str("<object>");
+            for (key, value) in values {
+                let _ = write!(xml, "<property id=\"{}\">", escape(key));
+                write_value(xml, value);
+                xml.push_str("</property>");
+            }
+            xml.push_str("</object>");
+        }
+        Value::List(values) => write_array(xml, values.iter()),
+        // Flash has no encoding of its own for bytes, they travel as an array of numbers.
+        Value::ByteArray(bytes) => {
+            write_array(xml, bytes.iter().map(|byte| Value::Number((*byte).into())))
+        }
+    }
+}
+
+fn write_array<V: Borrow<Value>>(xml: &mut String, values: impl Iterator<Item = V>) {
+    xml.push_str("<array>");
+    for (index, value) in values.enumerate() {
+        let _ = write!(xml, "<property id=\"{index}\">");
+        write_value(xml, value.borrow());
+        xml.push_str("</property>");
+    }
+    xml.push_str("</array>");
+}
+
+fn unexpected(start: &BytesStart) -> InvokeError {
+    InvokeError::UnexpectedElement(String::from_utf8_lossy(start.name().as_ref()).into_owned())
+}
+
+fn attribute(start: &BytesStart, name: &'static str) -> Result<String, InvokeError> {
+    let attribute = start
+        .try_get_attribute(name)
+        .map_err(quick_xml::Error::from)?
+        .ok_or(InvokeError::MissingAttribute(name))?;
+    Ok(attribute.unescape_value()?.into_owned())
+}
+
+struct Parser<'a> {
+    reader: Reader<&'a [u8]>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(xml: &'a str) -> Self {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().expand_empty_elements = true;
+        Self { reader }
+    }
+
+    /// Returns the next opening tag, or `None` at the closing tag of the current element.
+    fn start_or_end(&mut self) -> Result<Option<BytesStart<'a>>, InvokeError> {
+        loop {
+            match self.reader.read_event()? {
+                Event::Start(start) => return Ok(Some(start)),
+                Event::End(_) => return Ok(None),
+                Event::Eof => return Err(InvokeError::UnexpectedEof),
+                _ => {}
+            }
+        }
+    }
+
+    fn start(&mut self) -> Result<BytesStart<'a>, InvokeError> {
+        self.start_or_end()?.ok_or(InvokeError::UnexpectedEof)
+    }
+
+    /// Reads the text up to the closing tag of the current element.
+    fn text(&mut self) -> Result<String, InvokeError> {
+        let mut text = String::new();
+        loop {
+            match self.reader.read_event()? {
+                Event::Text(value) => text.push_str(&value.unescape()?),
+                Event::CData(value) => text.push_str(&String::from_utf8_lossy(&value)),
+                Event::End(_) => return Ok(text),
+                Event::Eof => return Err(InvokeError::UnexpectedEof),
+                Event::Start(start) => return Err(unexpected(&start)),
+                _ => {}
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, InvokeError> {
+        let text = self.text()?;
+        text.trim()
+            .parse()
+            .map_err(|_| InvokeError::InvalidNumber(text))
+    }
+
+    /// Reads `<property id="...">` children up to the closing tag of the current element.
+    fn properties(&mut self) -> Result<Vec<(String, Value)>, InvokeError> {
+        let mut properties = Vec::new();
+        while let Some(start) = self.start_or_end()? {
+            if start.name().as_ref() != b"property" {
+                return Err(unexpected(&start));
+            }
+            let id = attribute(&start, "id")?;
+            let value_start = self.start()?;
+            properties.push((id, self.value(&value_start)?));
+            if let Some(start) = self.start_or_end()? {
+                return Err(unexpected(&start));
+            }
+        }
+        Ok(properties)
+    }
+
+    /// Reads the value opened by `start`, including its closing tag.
+    fn value(&mut self, start: &BytesStart) -> Result<Value, InvokeError> {
+        let value = match start.name().as_ref() {
+            b"undefined" => Value::Undefined,
+            b"null" => Value::Null,
+            b"true" => Value::Bool(true),
+            b"false" => Value::Bool(false),
+            b"number" => return Ok(Value::Number(self.number()?)),
+            b"string" => return Ok(Value::String(self.text()?)),
+            b"date" => return Ok(Value::Date(self.number()?)),
+            b"object" => {
+                let values: BTreeMap<_, _> = self.properties()?.into_iter().collect();
+                return Ok(Value::Object(values));
+            }
+            b"array" => {
+                let mut properties = self.properties()?;
+                properties.sort_by_key(|(id, _)| id.parse::<usize>().unwrap_or(usize::MAX));
+                let values = properties.into_iter().map(|(_, value)| value).collect();
+                return Ok(Value::List(values));
+            }
+            _ => return Err(unexpected(start)),
+        };
+        if let Some(start) = self.start_or_end()? {
+            return Err(unexpected(&start));
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_round_trip() {
+        let value = Value::List(vec![
+            Value::Undefined,
+            Value::Null,
+            Value::Bool(true),
+            Value::Number(-1.5),
+            Value::String("<a & \"b\">".to_string()),
+            Value::Date(1234567890123.0),
+            Value::Object(BTreeMap::from([
+                ("nested".to_string(), Value::List(vec![Value::Bool(false)])),
+                ("empty".to_string(), Value::Object(BTreeMap::new())),
+            ])),
+        ]);
+        assert_eq!(Value::from_xml(&value.to_xml()).unwrap(), value);
+    }
+
+    #[test]
+    fn bytes_are_encoded_as_numbers() {
+        let value = Value::ByteArray(vec![1, 255]);
+        assert_eq!(
+            value.to_xml(),
+            "<array><property id=\"0\"><number>1</number></property>\
+             <property id=\"1\"><number>255</number></property></array>"
+        );
+    }
+
+    #[test]
+    fn invoke_from_flash() {
+        let xml = r#"<invoke name="jsFunc" returntype="xml">
+            <arguments>
+                <string>hello</string>
+                <number>42</number>
+                <array>
+                    <property id="1"><true/></property>
+                    <property id="0"><null/></property>
+                </array>
+            </arguments>
+        </invoke>"#;
+        assert_eq!(
+            Invoke::from_xml(xml).unwrap(),
+            Invoke {
+                name: "jsFunc".to_string(),
+                arguments: vec![
+                    Value::String("hello".to_string()),
+                    Value::Number(42.0),
+                    Value::List(vec![Value::Null, Value::Bool(true)]),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn invoke_round_trip() {
+        let invoke = Invoke {
+            name: "a<b".to_string(),
+            arguments: vec![Value::String("".to_string()), Value::Number(0.0)],
+        };
+        assert_eq!(Invoke::from_xml(&invoke.to_xml()).unwrap(), invoke);
+        assert!(Invoke::from_xml("<invoke><arguments/></invoke>").is_err());
+    }
+}
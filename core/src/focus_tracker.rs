@@ -13,7 +13,7 @@ use either::Either;
 use gc_arena::barrier::unlock;
 use gc_arena::lock::Lock;
 use gc_arena::{Collect, Gc, Mutation};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::slice::Iter;
 use swf::{Color, Rectangle, Twips};
 
@@ -22,6 +22,10 @@ use swf::{Color, Rectangle, Twips};
 pub struct FocusTrackerData<'gc> {
     focus: Lock<Option<InteractiveObject<'gc>>>,
     highlight: RefCell<Highlight>,
+
+    /// Whether the movie allows the input method editor, set with `flash.system.IME.enabled`.
+    #[collect(require_static)]
+    ime_enabled: Cell<bool>,
 }
 
 #[derive(Copy, Clone)]
@@ -64,6 +68,7 @@ impl<'gc> FocusTracker<'gc> {
             FocusTrackerData {
                 focus: Lock::new(None),
                 highlight: RefCell::new(Highlight::Inactive),
+                ime_enabled: Cell::new(true),
             },
         ))
     }
@@ -86,15 +91,37 @@ impl<'gc> FocusTracker<'gc> {
             .and_then(|o| o.as_edit_text())
     }
 
+    pub fn ime_enabled(&self) -> bool {
+        self.0.ime_enabled.get()
+    }
+
+    pub fn set_ime_enabled(&self, enabled: bool, context: &mut UpdateContext<'gc>) {
+        self.0.ime_enabled.set(enabled);
+        self.update_ime(context);
+    }
+
     /// Set the focus programmatically.
     pub fn set(&self, new: Option<InteractiveObject<'gc>>, context: &mut UpdateContext<'gc>) {
-        self.set_internal(new, context, false);
+        self.set_internal(new, context, false, FocusDirection::None);
+        self.update_edittext_selection(context);
+    }
+
+    /// Set the focus programmatically, specifying the direction from which it arrives.
+    ///
+    /// This is used by `Stage.assignFocus`; the direction is reported in `focusIn`.
+    pub fn assign(
+        &self,
+        new: Option<InteractiveObject<'gc>>,
+        direction: FocusDirection,
+        context: &mut UpdateContext<'gc>,
+    ) {
+        self.set_internal(new, context, false, direction);
         self.update_edittext_selection(context);
     }
 
     /// Reset the focus programmatically.
     pub fn reset_focus(&self, context: &mut UpdateContext<'gc>) {
-        self.set_internal(None, context, true);
+        self.set_internal(None, context, true, FocusDirection::None);
     }
 
     /// Set the focus and acknowledge that this change was caused by a pointer device.
@@ -110,6 +137,7 @@ impl<'gc> FocusTracker<'gc> {
         if InteractiveObject::option_ptr_eq(old, new) {
             // Re-open the keyboard when the user clicked an already focused text field.
             self.update_virtual_keyboard(context);
+            self.update_ime(context);
             return;
         }
 
@@ -122,7 +150,7 @@ impl<'gc> FocusTracker<'gc> {
         // but `None` will be used when setting the focus.
         let new = new.filter(|new| new.is_focusable_by_mouse(context));
 
-        self.set_internal(new, context, false);
+        self.set_internal(new, context, false, FocusDirection::None);
     }
 
     /// Set the focus and acknowledge that this change was caused by a key.
@@ -130,6 +158,7 @@ impl<'gc> FocusTracker<'gc> {
         &self,
         new: Option<InteractiveObject<'gc>>,
         key_code: KeyCode,
+        direction: FocusDirection,
         context: &mut UpdateContext<'gc>,
     ) {
         let old = self.0.focus.get();
@@ -137,7 +166,7 @@ impl<'gc> FocusTracker<'gc> {
             return;
         }
 
-        self.set_internal(new, context, true);
+        self.set_internal(new, context, true, direction);
         self.update_edittext_selection(context);
     }
 
@@ -146,6 +175,7 @@ impl<'gc> FocusTracker<'gc> {
         new: Option<InteractiveObject<'gc>>,
         context: &mut UpdateContext<'gc>,
         run_actions: bool,
+        direction: FocusDirection,
     ) {
         Self::roll_over(context, new);
 
@@ -169,12 +199,12 @@ impl<'gc> FocusTracker<'gc> {
             if let Some(old) = old {
                 old.set_has_focus(context.gc(), false);
                 old.on_focus_changed(context, false, new);
-                old.call_focus_handler(context, false, new);
+                old.call_focus_handler(context, false, new, FocusDirection::None);
             }
             if let Some(new) = new {
                 new.set_has_focus(context.gc(), true);
                 new.on_focus_changed(context, true, old);
-                new.call_focus_handler(context, true, old);
+                new.call_focus_handler(context, true, old, direction);
             }
 
             tracing::info!("Focus is now on {:?}", new);
@@ -198,6 +228,7 @@ impl<'gc> FocusTracker<'gc> {
         }
 
         self.update_virtual_keyboard(context);
+        self.update_ime(context);
     }
 
     fn update_virtual_keyboard(&self, context: &mut UpdateContext<'gc>) {
@@ -212,6 +243,21 @@ impl<'gc> FocusTracker<'gc> {
         }
     }
 
+    /// Allows the input method editor while an editable text field has focus,
+    /// and moves its candidates next to the text cursor.
+    pub fn update_ime(&self, context: &mut UpdateContext<'gc>) {
+        let text_field = self
+            .get_as_edit_text()
+            .filter(|text_field| text_field.is_editable());
+        context
+            .ui
+            .set_ime_allowed(self.ime_enabled() && text_field.is_some());
+
+        if let Some(area) = text_field.and_then(|text_field| text_field.ime_cursor_area(context)) {
+            context.ui.set_ime_cursor_area(area);
+        }
+    }
+
     /// Update selection on the newly focused text field.
     ///
     /// This applies even if the focused element hasn't changed.
@@ -249,8 +295,14 @@ impl<'gc> FocusTracker<'gc> {
 
         let mut activation = Activation::from_nothing(context);
         let key_code = key_code.map(|k| k.value()).unwrap_or_default();
-        let event =
-            EventObject::focus_event(&mut activation, event_type, true, related_object, key_code);
+        let event = EventObject::focus_event(
+            &mut activation,
+            event_type,
+            true,
+            related_object,
+            key_code,
+            FocusDirection::None,
+        );
         Avm2::dispatch_event(activation.context, event, target);
 
         let canceled = event.as_event().unwrap().is_cancelled();
@@ -309,7 +361,13 @@ impl<'gc> FocusTracker<'gc> {
         };
 
         if next.is_some() {
-            self.set_by_key(next.copied(), KeyCode::TAB, context);
+            // Tabbing forward enters an object from its top, tabbing backwards from its bottom.
+            let direction = if reverse {
+                FocusDirection::Bottom
+            } else {
+                FocusDirection::Top
+            };
+            self.set_by_key(next.copied(), KeyCode::TAB, direction, context);
             self.update_highlight(context);
         }
     }
@@ -322,7 +380,7 @@ impl<'gc> FocusTracker<'gc> {
         let tab_order = TabOrder::fill(context);
         let ordering = NavigationOrdering::new(focus, direction);
         if let Some(next) = tab_order.first(ordering) {
-            self.set_by_key(Some(next), direction.key(), context);
+            self.set_by_key(Some(next), direction.key(), FocusDirection::None, context);
         }
     }
 
@@ -555,6 +613,39 @@ impl NavigationDirection {
     }
 }
 
+/// The direction from which the focus arrives at an object,
+/// as reported by `FocusEvent.direction` and `flash.display.FocusDirection`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FocusDirection {
+    /// The focus arrived at the beginning of the object's reading order.
+    Top,
+
+    /// The focus arrived at the end of the object's reading order.
+    Bottom,
+
+    /// The focus did not arrive from a specific direction.
+    None,
+}
+
+impl FocusDirection {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "top" => Self::Top,
+            "bottom" => Self::Bottom,
+            "none" => Self::None,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::None => "none",
+        }
+    }
+}
+
 /// Ordering used for keyboard navigation.
 struct NavigationOrdering {
     /// Bounds of the object we are navigating from.
@@ -1,10 +1,11 @@
+use crate::bidi;
 use crate::drawing::Drawing;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::string::WStr;
 use gc_arena::{Collect, Gc, Mutation};
 use ruffle_render::backend::null::NullBitmapSource;
-use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::backend::{DeviceId, RenderBackend, ShapeHandle};
 use ruffle_render::shape_utils::{DrawCommand, FillRule};
 use ruffle_render::transform::Transform;
 use std::borrow::Cow;
@@ -33,8 +34,15 @@ pub enum DefaultFont {
 
     /// `_明朝`, a Japanese Mincho font
     JapaneseMincho,
+
+    /// Fonts used for the characters that the requested device font has no
+    /// glyphs for, such as CJK characters or emoji.
+    Fallback,
 }
 
+/// The size of the EM square that the glyphs of `DefineFont` and `DefineFont2` tags are drawn in.
+const FONT1_EM_SQUARE: i32 = 1024;
+
 fn round_to_pixel(t: Twips) -> Twips {
     Twips::from_pixels(t.to_pixels().round())
 }
@@ -187,41 +195,97 @@ impl FontFace {
     pub fn get_glyph(&self, character: char) -> Option<&Glyph> {
         let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
             .expect("Font was already checked to be valid");
-        if let Some(glyph_id) = face.glyph_index(character) {
-            return self.glyphs[glyph_id.0 as usize]
-                .get_or_init(|| {
-                    let mut drawing = Drawing::new();
-                    // TTF uses NonZero
-                    drawing.new_fill(
-                        Some(FillStyle::Color(Color::WHITE)),
-                        Some(FillRule::NonZero),
-                    );
-                    if face
-                        .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
-                        .is_some()
-                    {
-                        let advance = face.glyph_hor_advance(glyph_id).map_or_else(
-                            || drawing.self_bounds().width(),
-                            |a| Twips::new(a as i32),
-                        );
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::Drawing(drawing),
-                            advance,
-                        })
-                    } else {
-                        let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
-                        // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::None,
-                            advance,
-                        })
-                    }
-                })
-                .as_ref();
+        let glyph_id = face.glyph_index(character)?;
+        self.get_glyph_by_id(&face, glyph_id, character)
+    }
+
+    fn get_glyph_by_id(
+        &self,
+        face: &ttf_parser::Face<'_>,
+        glyph_id: ttf_parser::GlyphId,
+        character: char,
+    ) -> Option<&Glyph> {
+        self.glyphs
+            .get(glyph_id.0 as usize)?
+            .get_or_init(|| {
+                let mut drawing = Drawing::new();
+                // TTF uses NonZero
+                drawing.new_fill(
+                    Some(FillStyle::Color(Color::WHITE)),
+                    Some(FillRule::NonZero),
+                );
+                if face
+                    .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
+                    .is_some()
+                {
+                    let advance = face
+                        .glyph_hor_advance(glyph_id)
+                        .map_or_else(|| drawing.self_bounds().width(), |a| Twips::new(a as i32));
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        shape: GlyphShape::Drawing(drawing),
+                        advance,
+                        character,
+                    })
+                } else {
+                    let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
+                    // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        shape: GlyphShape::None,
+                        advance,
+                        character,
+                    })
+                }
+            })
+            .as_ref()
+    }
+
+    /// Shapes a run of text that is drawn in a single direction, using the layout tables
+    /// of the font. This joins the letters of scripts such as Arabic, and places the marks
+    /// of scripts such as Devanagari.
+    ///
+    /// The glyphs are returned from left to right, each with the position in `text`
+    /// of the character it was made from.
+    fn shape(&self, text: &WStr, is_rtl: bool) -> Vec<ShapedGlyph<'_>> {
+        let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
+            .expect("Font was already checked to be valid");
+        let Some(shaper) = rustybuzz::Face::from_slice(&self.bytes, self.font_index) else {
+            return vec![];
+        };
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        for (pos, c) in text.char_indices() {
+            buffer.add(c.unwrap_or(char::REPLACEMENT_CHARACTER), pos as u32);
         }
-        None
+        buffer.set_direction(if is_rtl {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+        buffer.guess_segment_properties();
+
+        let output = rustybuzz::shape(&shaper, &[], buffer);
+        output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .filter_map(|(info, position)| {
+                let pos = info.cluster as usize;
+                let character = text
+                    .slice(pos..)
+                    .and_then(|rest| rest.chars().next())
+                    .and_then(Result::ok)
+                    .unwrap_or(char::REPLACEMENT_CHARACTER);
+                let glyph_id = ttf_parser::GlyphId(u16::try_from(info.glyph_id).ok()?);
+                Some(ShapedGlyph {
+                    pos,
+                    glyph: self.get_glyph_by_id(&face, glyph_id, character)?,
+                    advance: Twips::new(position.x_advance),
+                    offset: (Twips::new(position.x_offset), Twips::new(position.y_offset)),
+                })
+            })
+            .collect()
     }
 
     pub fn has_kerning_info(&self) -> bool {
@@ -250,6 +314,20 @@ impl FontFace {
     }
 }
 
+/// A glyph that was picked by shaping a run of text.
+struct ShapedGlyph<'a> {
+    /// The position of the character that the glyph was made from.
+    pos: usize,
+
+    glyph: &'a Glyph,
+
+    /// How far the next glyph is moved, in font units.
+    advance: Twips,
+
+    /// How far this glyph is moved from where it would otherwise be, in font units.
+    offset: (Twips, Twips),
+}
+
 #[derive(Debug)]
 pub enum GlyphSource {
     Memory {
@@ -331,9 +409,17 @@ pub enum FontType {
     Device,
 }
 
+/// The styles synthesized for a device font that lacks a bold or italic variant.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Collect)]
+#[collect(require_static)]
+pub struct FontSynthesis {
+    bold: bool,
+    italic: bool,
+}
+
 #[derive(Debug, Clone, Collect, Copy)]
 #[collect(no_drop)]
-pub struct Font<'gc>(Gc<'gc, FontData>);
+pub struct Font<'gc>(Gc<'gc, FontData>, FontSynthesis);
 
 #[derive(Debug, Collect)]
 #[collect(require_static)]
@@ -364,6 +450,13 @@ struct FontData {
 }
 
 impl<'gc> Font<'gc> {
+    /// The horizontal shear applied to glyphs with a synthesized italic style.
+    const SYNTHETIC_ITALIC_SHEAR: f32 = 0.2;
+
+    fn new(gc_context: &Mutation<'gc>, data: FontData) -> Self {
+        Self(Gc::new(gc_context, data), FontSynthesis::default())
+    }
+
     pub fn from_font_file(
         gc_context: &Mutation<'gc>,
         descriptor: FontDescriptor,
@@ -373,7 +466,7 @@ impl<'gc> Font<'gc> {
     ) -> Result<Font<'gc>, ttf_parser::FaceParsingError> {
         let face = FontFace::new(bytes, font_index)?;
 
-        Ok(Font(Gc::new(
+        Ok(Font::new(
             gc_context,
             FontData {
                 scale: face.scale,
@@ -384,7 +477,7 @@ impl<'gc> Font<'gc> {
                 descriptor,
                 font_type,
             },
-        )))
+        ))
     }
 
     pub fn from_swf_tag(
@@ -414,6 +507,7 @@ impl<'gc> Font<'gc> {
                 let glyph = Glyph {
                     shape_handle: None.into(),
                     advance: Twips::new(swf_glyph.advance.into()),
+                    character: char::from_u32(code.into()).unwrap_or(char::REPLACEMENT_CHARACTER),
                     shape: GlyphShape::Swf(RefCell::new(SwfGlyphOrShape::Glyph(swf_glyph))),
                 };
 
@@ -436,7 +530,7 @@ impl<'gc> Font<'gc> {
             fnv::FnvHashMap::default()
         };
 
-        Font(Gc::new(
+        Font::new(
             gc_context,
             FontData {
                 glyphs: if glyphs.is_empty() {
@@ -458,7 +552,7 @@ impl<'gc> Font<'gc> {
                 descriptor,
                 font_type,
             },
-        ))
+        )
     }
 
     pub fn from_font4_tag(
@@ -478,7 +572,7 @@ impl<'gc> Font<'gc> {
                 FontType::EmbeddedCFF,
             )
         } else {
-            Ok(Font(Gc::new(
+            Ok(Font::new(
                 gc_context,
                 FontData {
                     scale: 1.0,
@@ -489,10 +583,161 @@ impl<'gc> Font<'gc> {
                     descriptor,
                     font_type: FontType::EmbeddedCFF,
                 },
-            )))
+            ))
         }
     }
 
+    /// Creates a font from a `DefineFont` tag, which only contains the shapes of its glyphs.
+    ///
+    /// The characters that the glyphs draw, along with the name of the font, come from a
+    /// `DefineFontInfo` tag. Without one, the font can only be used by `DefineText` tags.
+    /// The font has no layout either, so it's made up from the shapes of the glyphs.
+    pub fn from_font1_tag(
+        gc_context: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        tag: swf::FontV1,
+        info: Option<&swf::FontInfo<'_>>,
+        encoding: &'static swf::Encoding,
+        font_type: FontType,
+    ) -> Font<'gc> {
+        let mut ascent = 0;
+        let mut descent = 0;
+        let glyphs = tag
+            .glyphs
+            .into_iter()
+            .enumerate()
+            .map(|(index, shape_records)| {
+                let bounds = ruffle_render::shape_utils::calculate_shape_bounds(&shape_records);
+                let advance = if bounds.x_min > bounds.x_max {
+                    // An empty glyph, such as a space.
+                    FONT1_EM_SQUARE / 4
+                } else {
+                    ascent = ascent.max(-bounds.y_min.get());
+                    descent = descent.max(bounds.y_max.get());
+                    bounds.x_max.get() + FONT1_EM_SQUARE / 16
+                };
+                swf::Glyph {
+                    shape_records,
+                    code: info
+                        .and_then(|info| info.code_table.get(index).copied())
+                        .unwrap_or_default(),
+                    advance: advance.clamp(0, i16::MAX.into()) as i16,
+                    bounds: None,
+                }
+            })
+            .collect();
+
+        let (name, flags) = match info {
+            Some(info) => {
+                let mut flags = swf::FontFlag::empty();
+                flags.set(
+                    swf::FontFlag::IS_BOLD,
+                    info.flags.contains(swf::FontInfoFlag::IS_BOLD),
+                );
+                flags.set(
+                    swf::FontFlag::IS_ITALIC,
+                    info.flags.contains(swf::FontInfoFlag::IS_ITALIC),
+                );
+                (info.name, flags)
+            }
+            None => (swf::SwfStr::from_bytes(b""), swf::FontFlag::empty()),
+        };
+
+        let font = swf::Font {
+            version: 1,
+            id: tag.id,
+            name,
+            language: info.map_or(swf::Language::Unknown, |info| info.language),
+            layout: Some(swf::FontLayout {
+                ascent: ascent.clamp(0, u16::MAX.into()) as u16,
+                descent: descent.clamp(0, u16::MAX.into()) as u16,
+                leading: 0,
+                kerning: vec![],
+            }),
+            glyphs,
+            flags,
+        };
+        Font::from_swf_tag(gc_context, renderer, font, encoding, font_type)
+    }
+
+    /// Returns this font, with the names from a `DefineFontInfo` tag given to it and to
+    /// its glyphs. Used for fonts defined by `DefineFont` tags.
+    pub fn with_font_info(
+        &self,
+        gc_context: &Mutation<'gc>,
+        info: &swf::FontInfo<'_>,
+        encoding: &'static swf::Encoding,
+    ) -> Font<'gc> {
+        let glyphs = match &self.0.glyphs {
+            GlyphSource::Memory {
+                glyphs,
+                kerning_pairs,
+                ..
+            } => {
+                let mut code_point_to_glyph = fnv::FnvHashMap::default();
+                let glyphs = glyphs
+                    .iter()
+                    .zip(&info.code_table)
+                    .enumerate()
+                    .map(|(index, (glyph, &code))| {
+                        code_point_to_glyph.insert(code, index);
+                        Glyph {
+                            character: char::from_u32(code.into())
+                                .unwrap_or(char::REPLACEMENT_CHARACTER),
+                            ..glyph.clone()
+                        }
+                    })
+                    .collect();
+                GlyphSource::Memory {
+                    glyphs,
+                    code_point_to_glyph,
+                    kerning_pairs: kerning_pairs.clone(),
+                }
+            }
+            _ => GlyphSource::Empty,
+        };
+
+        Font::new(
+            gc_context,
+            FontData {
+                glyphs,
+                scale: self.0.scale,
+                ascent: self.0.ascent,
+                descent: self.0.descent,
+                leading: self.0.leading,
+                descriptor: FontDescriptor::from_parts(
+                    &info.name.to_string_lossy(encoding),
+                    info.flags.contains(swf::FontInfoFlag::IS_BOLD),
+                    info.flags.contains(swf::FontInfoFlag::IS_ITALIC),
+                ),
+                font_type: self.0.font_type,
+            },
+        )
+    }
+
+    pub fn ptr_eq(a: Font<'gc>, b: Font<'gc>) -> bool {
+        Gc::ptr_eq(a.0, b.0) && a.1 == b.1
+    }
+
+    /// Returns this font, synthesizing the requested bold and italic styles
+    /// when it doesn't provide them itself.
+    pub fn with_synthesized_style(self, is_bold: bool, is_italic: bool) -> Self {
+        let descriptor = self.descriptor();
+        let synthesis = FontSynthesis {
+            bold: is_bold && !descriptor.bold(),
+            italic: is_italic && !descriptor.italic(),
+        };
+        Self(self.0, synthesis)
+    }
+
+    /// How far to the right glyphs are drawn a second time to embolden
+    /// them, if this font has a synthesized bold style.
+    pub fn synthetic_bold_offset(&self, params: EvalParameters) -> Option<Twips> {
+        self.1
+            .bold
+            .then(|| Twips::new((params.height.get() / 24).max(1)))
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(&self) -> bool {
@@ -568,6 +813,10 @@ impl<'gc> Font<'gc> {
     /// of transforms and glyphs which will be consumed by the `glyph_func`
     /// closure. This corresponds to the series of drawing operations necessary
     /// to render the text on a single horizontal line.
+    ///
+    /// Glyphs are produced from left to right, so text in right-to-left scripts
+    /// is produced backwards. Along with its position in the text, each glyph
+    /// comes with whether it's drawn from right to left.
     pub fn evaluate<FGlyph>(
         &self,
         text: &WStr, // TODO: take an `IntoIterator<Item=char>`, to not depend on string representation?
@@ -575,45 +824,104 @@ impl<'gc> Font<'gc> {
         params: EvalParameters,
         mut glyph_func: FGlyph,
     ) where
-        FGlyph: FnMut(usize, &Transform, &Glyph, Twips, Twips),
+        FGlyph: FnMut(usize, &Transform, &Glyph, Twips, Twips, bool),
     {
         transform.matrix.ty += params.height;
         let scale = params.height.get() as f32 / self.scale();
 
         transform.matrix.a = scale;
         transform.matrix.d = scale;
-        let mut char_indices = text.char_indices().peekable();
+        if self.1.italic {
+            // Slant the glyphs to the right, around their baseline.
+            transform.matrix.c = -scale * Self::SYNTHETIC_ITALIC_SHEAR;
+        }
         let has_kerning_info = self.has_kerning_info();
         let mut x = Twips::ZERO;
-        while let Some((pos, c)) = char_indices.next() {
-            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
-            if let Some(glyph) = self.get_glyph_for_char(c) {
-                let mut advance = glyph.advance();
-                if has_kerning_info && params.kerning {
-                    let next_char = char_indices.peek().cloned().unwrap_or((0, Ok('\0'))).1;
-                    let next_char = next_char.unwrap_or(char::REPLACEMENT_CHARACTER);
-                    advance += self.get_kerning_offset(c, next_char);
+        for run in bidi::visual_runs(text) {
+            let run_text = &text[run.range.clone()];
+            let is_rtl = run.is_rtl;
+
+            if let GlyphSource::FontFace(face) = &self.0.glyphs {
+                if is_rtl || bidi::needs_shaping(run_text) {
+                    for shaped in face.shape(run_text, is_rtl) {
+                        let twips_advance = self.scaled_advance(shaped.advance, scale, params);
+                        let mut glyph_transform = transform.clone();
+                        glyph_transform.matrix.tx +=
+                            Twips::new((shaped.offset.0.get() as f32 * scale) as i32);
+                        glyph_transform.matrix.ty -=
+                            Twips::new((shaped.offset.1.get() as f32 * scale) as i32);
+
+                        glyph_func(
+                            run.range.start + shaped.pos,
+                            &glyph_transform,
+                            shaped.glyph,
+                            twips_advance,
+                            x,
+                            is_rtl,
+                        );
+
+                        // Step horizontally.
+                        transform.matrix.tx += twips_advance;
+                        x += twips_advance;
+                    }
+                    continue;
                 }
-                let twips_advance = if self.font_type() == FontType::Device {
-                    let unspaced_advance =
-                        round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32));
-                    let spaced_advance =
-                        unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
-                    if spaced_advance > Twips::ZERO {
-                        spaced_advance
-                    } else {
-                        unspaced_advance
+            }
+
+            let mut chars: Vec<(usize, char)> = run_text
+                .char_indices()
+                .map(|(pos, c)| (pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)))
+                .collect();
+            if is_rtl {
+                chars.reverse();
+                for (_, c) in &mut chars {
+                    *c = bidi::mirror_char(*c);
+                }
+            }
+
+            let mut chars = chars.into_iter().peekable();
+            while let Some((pos, c)) = chars.next() {
+                if let Some(glyph) = self.get_glyph_for_char(c) {
+                    let mut advance = glyph.advance();
+                    if has_kerning_info && params.kerning {
+                        let next_char = chars.peek().map_or('\0', |(_, c)| *c);
+                        advance += self.get_kerning_offset(c, next_char);
                     }
-                } else {
-                    Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
-                };
+                    let twips_advance = self.scaled_advance(advance, scale, params);
+
+                    glyph_func(
+                        run.range.start + pos,
+                        &transform,
+                        glyph,
+                        twips_advance,
+                        x,
+                        is_rtl,
+                    );
 
-                glyph_func(pos, &transform, glyph, twips_advance, x);
+                    // Step horizontally.
+                    transform.matrix.tx += twips_advance;
+                    x += twips_advance;
+                }
+            }
+        }
+    }
 
-                // Step horizontally.
-                transform.matrix.tx += twips_advance;
-                x += twips_advance;
+    /// How far to move along the line after drawing a glyph, given its advance in font units.
+    fn scaled_advance(&self, advance: Twips, scale: f32, params: EvalParameters) -> Twips {
+        if self.font_type() == FontType::Device {
+            // Synthesized bold glyphs are wider by the offset they're drawn again at.
+            let bold_offset = self.synthetic_bold_offset(params).unwrap_or_default();
+            let unspaced_advance =
+                round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32)) + bold_offset;
+            let spaced_advance =
+                unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
+            if spaced_advance > Twips::ZERO {
+                spaced_advance
+            } else {
+                unspaced_advance
             }
+        } else {
+            Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
         }
     }
 
@@ -625,7 +933,7 @@ impl<'gc> Font<'gc> {
             text,
             Default::default(),
             params,
-            |_pos, _transform, _glyph, advance, x| {
+            |_pos, _transform, _glyph, advance, x, _is_rtl| {
                 width = width.max(x + advance);
             },
         );
@@ -781,21 +1089,30 @@ impl GlyphShape {
 
 #[derive(Debug, Clone)]
 pub struct Glyph {
-    // Handle to registered shape.
+    // Handle to registered shape, along with the device it lives on.
     // If None, it'll be loaded lazily on first render of this glyph.
     // It's a double option; the outer one is "have we registered", the inner one is option because it may not exist
-    shape_handle: RefCell<Option<Option<ShapeHandle>>>,
+    shape_handle: RefCell<Option<(DeviceId, Option<ShapeHandle>)>>,
 
     shape: GlyphShape,
     advance: Twips,
+
+    /// The character that this glyph draws.
+    character: char,
 }
 
 impl Glyph {
     pub fn shape_handle(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
-        self.shape_handle
-            .borrow_mut()
-            .get_or_insert_with(|| self.shape.register(renderer))
-            .clone()
+        let device_id = renderer.device_id();
+        let mut shape_handle = self.shape_handle.borrow_mut();
+        match &*shape_handle {
+            Some((registered_on, handle)) if *registered_on == device_id => handle.clone(),
+            _ => {
+                let handle = self.shape.register(renderer);
+                *shape_handle = Some((device_id, handle.clone()));
+                handle
+            }
+        }
     }
 
     pub fn hit_test(&self, point: Point<Twips>, local_matrix: &Matrix) -> bool {
@@ -805,6 +1122,10 @@ impl Glyph {
     pub fn advance(&self) -> Twips {
         self.advance
     }
+
+    pub fn character(&self) -> char {
+        self.character
+    }
 }
 
 /// Structure which identifies a particular font by name and properties.
@@ -1264,4 +1585,66 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn font1_tag_with_font_info() {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new(ViewportDimensions {
+                width: 0,
+                height: 0,
+                scale_factor: 1.0,
+            });
+            let square = vec![
+                swf::ShapeRecord::StyleChange(Box::new(swf::StyleChangeData {
+                    move_to: Some(swf::Point::new(Twips::new(0), Twips::new(-500))),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                })),
+                swf::ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::new(400), Twips::new(0)),
+                },
+                swf::ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::new(0), Twips::new(600)),
+                },
+                swf::ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::new(-400), Twips::new(0)),
+                },
+                swf::ShapeRecord::StraightEdge {
+                    delta: swf::PointDelta::new(Twips::new(0), Twips::new(-600)),
+                },
+            ];
+            let tag = swf::FontV1 {
+                id: 1,
+                glyphs: vec![square, vec![]],
+            };
+            let font =
+                Font::from_font1_tag(mc, &mut renderer, tag, None, swf::UTF_8, FontType::Embedded);
+            assert!(font.get_glyph(0).is_some());
+            assert!(font.get_glyph_for_char('A').is_none());
+            assert_eq!(
+                font.get_baseline_for_height(Twips::new(1024)),
+                Twips::new(500)
+            );
+
+            let font_info = swf::FontInfo {
+                id: 1,
+                version: 1,
+                name: swf::SwfStr::from_utf8_str("Square"),
+                flags: swf::FontInfoFlag::IS_BOLD,
+                language: swf::Language::Unknown,
+                code_table: vec![u16::from(b'A'), u16::from(b' ')],
+            };
+            let font = font.with_font_info(mc, &font_info, swf::UTF_8);
+            assert_eq!(font.descriptor().name(), "Square");
+            assert!(font.descriptor().bold());
+            assert!(!font.descriptor().italic());
+
+            let glyph = font.get_glyph_for_char('A').expect("Glyph should be named");
+            assert_eq!(glyph.advance(), Twips::new(400 + 64));
+            let space = font.get_glyph_for_char(' ').expect("Glyph should be named");
+            assert_eq!(space.advance(), Twips::new(256));
+        })
+    }
 }
@@ -14,7 +14,9 @@
 use crate::avm2::Avm2;
 use crate::avm2_stub_method_context;
 use crate::context::UpdateContext;
-use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::display_object::{
+    DisplayObject, MovieClip, Stage, TDisplayObject, TDisplayObjectContainer,
+};
 use tracing::instrument;
 
 /// Which phase of the frame we're currently in.
@@ -80,6 +82,14 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_>) {
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.enter_frame(context);
     });
+    // The stages of additional AIR windows only advance their children, before
+    // `Stage::enter_frame` broadcasts `enterFrame` to everything on every stage.
+    // `frameConstructed` and `exitFrame` are broadcast the same way.
+    for window_stage in native_window_stages(context) {
+        for child in window_stage.iter_render_list() {
+            child.enter_frame(context);
+        }
+    }
     stage.enter_frame(context);
 
     *context.frame_phase = FramePhase::Construct;
@@ -87,6 +97,9 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_>) {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
+    for window_stage in native_window_stages(context) {
+        window_stage.construct_frame(context);
+    }
     stage.frame_constructed(context);
 
     *context.frame_phase = FramePhase::FrameScripts;
@@ -94,6 +107,9 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_>) {
         orphan.run_frame_scripts(context);
     });
     stage.run_frame_scripts(context);
+    for window_stage in native_window_stages(context) {
+        window_stage.run_frame_scripts(context);
+    }
 
     *context.frame_phase = FramePhase::Exit;
     stage.exit_frame(context);
@@ -108,6 +124,13 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_>) {
     *context.frame_phase = FramePhase::Idle;
 }
 
+/// The stages of any additional AIR `NativeWindow`s.
+///
+/// This is collected up front, as running frames may open or close windows.
+fn native_window_stages<'gc>(context: &UpdateContext<'gc>) -> Vec<Stage<'gc>> {
+    context.native_windows.stages().collect()
+}
+
 /// Like `run_all_phases_avm2`, but specialized for the "nested frame" triggered
 /// by a goto. This is different enough to not be worth combining into a single
 /// method with extra parameters.
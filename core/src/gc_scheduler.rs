@@ -0,0 +1,225 @@
+//! Deciding when the garbage collector runs, so that its pauses stay short.
+//!
+//! gc-arena collects incrementally: every allocation adds to a debt, and `collect_debt` does
+//! an amount of marking and sweeping proportional to it. By default the player pays the debt
+//! after every update. With a budget, the debt is paid in slices instead, each one as large as
+//! can be paid within the budget: the debt is left to grow, across frames and ticks, until
+//! waiting for one more update would make it take longer than the budget to pay. A pause only
+//! goes over the budget when a single update allocated more than the budget's worth.
+
+use std::time::Duration;
+
+/// How much the newest pause counts towards the estimate of how fast debt is paid.
+const ESTIMATE_WEIGHT: f64 = 0.2;
+
+/// How many of the latest updates are looked at to guess how much the next one allocates.
+const GROWTH_WINDOW: usize = 8;
+
+#[derive(Default)]
+pub struct GcScheduler {
+    budget: Option<Duration>,
+
+    /// How long paying one unit of debt has taken, as a moving average.
+    secs_per_debt: Option<f64>,
+
+    /// The debt seen by the last call to `should_collect`, to estimate how much the next
+    /// update will add.
+    last_debt: f64,
+
+    /// How much debt each of the latest updates added, oldest first.
+    recent_growth: [f64; GROWTH_WINDOW],
+
+    stats: GcStats,
+}
+
+/// How the garbage collector has been doing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GcStats {
+    /// How many times the collector ran.
+    pub collections: u64,
+
+    /// How many times paying the debt was left to a later update.
+    pub deferred: u64,
+
+    pub last_pause: Duration,
+
+    pub longest_pause: Duration,
+
+    pub total_pause: Duration,
+
+    /// The size of the GC heap after the last collection, in bytes.
+    pub heap_size: usize,
+}
+
+impl GcScheduler {
+    pub fn budget(&self) -> Option<Duration> {
+        self.budget
+    }
+
+    /// Sets how long a single collection may pause the player for, or `None` to always collect
+    /// right away.
+    pub fn set_budget(&mut self, budget: Option<Duration>) {
+        self.budget = budget;
+    }
+
+    pub fn stats(&self) -> GcStats {
+        self.stats
+    }
+
+    /// The most debt that is expected to be paid within the budget, once it is known how fast
+    /// the collector runs.
+    pub fn debt_cap(&self) -> Option<f64> {
+        Some(self.budget?.as_secs_f64() / self.secs_per_debt?)
+    }
+
+    /// Decides whether `debt` should be paid now.
+    pub fn should_collect(&mut self, debt: f64) -> bool {
+        let growth = (debt - self.last_debt).max(0.0);
+        self.last_debt = debt;
+        self.recent_growth.rotate_left(1);
+        self.recent_growth[GROWTH_WINDOW - 1] = growth;
+        if debt <= 0.0 {
+            return false;
+        }
+        let Some(cap) = self.debt_cap() else {
+            return true;
+        };
+
+        // Assume the next update allocates no more than the latest ones did, so that frames
+        // with a few uneven updates do not overshoot the cap.
+        let growth = self.recent_growth.iter().copied().fold(0.0, f64::max);
+        if debt + growth > cap {
+            return true;
+        }
+
+        self.stats.deferred += 1;
+        false
+    }
+
+    /// Records that `debt` was paid, which took `pause`.
+    pub fn record(&mut self, debt: f64, pause: Duration, heap_size: usize) {
+        self.last_debt = 0.0;
+        if debt > 0.0 {
+            let sample = pause.as_secs_f64() / debt;
+            self.secs_per_debt = Some(match self.secs_per_debt {
+                Some(average) => average + (sample - average) * ESTIMATE_WEIGHT,
+                None => sample,
+            });
+        }
+
+        self.stats.collections += 1;
+        self.stats.last_pause = pause;
+        self.stats.longest_pause = self.stats.longest_pause.max(pause);
+        self.stats.total_pause += pause;
+        self.stats.heap_size = heap_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How long the simulated collector takes to pay one unit of debt.
+    const SECS_PER_DEBT: f64 = 1e-6;
+
+    /// A simulated movie, whose pauses are as long as the collector would take.
+    #[derive(Default)]
+    struct Simulation {
+        scheduler: GcScheduler,
+        debt: f64,
+        pauses: Vec<Duration>,
+    }
+
+    impl Simulation {
+        fn with_budget(budget: Duration) -> Self {
+            let mut simulation = Self::default();
+            simulation.scheduler.set_budget(Some(budget));
+            // Let the scheduler learn how fast the collector is.
+            simulation.update(1000.0);
+            simulation
+        }
+
+        /// Runs an update that allocates `allocation` units of debt.
+        fn update(&mut self, allocation: f64) {
+            self.debt += allocation;
+            if self.scheduler.should_collect(self.debt) {
+                let pause = Duration::from_secs_f64(self.debt * SECS_PER_DEBT);
+                self.scheduler.record(self.debt, pause, 0);
+                self.pauses.push(pause);
+                self.debt = 0.0;
+            }
+        }
+    }
+
+    #[test]
+    fn collects_every_update_without_budget() {
+        let mut simulation = Simulation::default();
+        assert!(!simulation.scheduler.should_collect(0.0));
+        for _ in 0..100 {
+            simulation.update(700.0);
+        }
+        assert_eq!(simulation.pauses.len(), 100);
+        assert_eq!(simulation.debt, 0.0);
+        assert_eq!(simulation.scheduler.stats().deferred, 0);
+    }
+
+    #[test]
+    fn learns_debt_cap_from_pauses() {
+        let simulation = Simulation::with_budget(Duration::from_millis(5));
+        let cap = simulation.scheduler.debt_cap().unwrap();
+        assert!((cap - 5000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pauses_stay_within_budget() {
+        let budget = Duration::from_millis(5);
+        let mut simulation = Simulation::with_budget(budget);
+
+        // Frames of a few uneven updates each, where the frame script allocates the most.
+        for _ in 0..200 {
+            for allocation in [1200.0, 50.0, 50.0, 400.0, 0.0, 0.0] {
+                simulation.update(allocation);
+            }
+        }
+
+        for pause in &simulation.pauses {
+            assert!(
+                *pause <= budget,
+                "paused for {pause:?}, budget is {budget:?}"
+            );
+        }
+        let stats = simulation.scheduler.stats();
+        assert!(stats.longest_pause <= budget);
+        assert!(stats.deferred > 0);
+        // Slices are close to the budget, rather than paying a little after every update.
+        assert!(stats.total_pause.as_secs_f64() / stats.collections as f64 > 0.002);
+    }
+
+    #[test]
+    fn carries_debt_over_to_later_frames() {
+        let mut simulation = Simulation::with_budget(Duration::from_millis(5));
+        let collections = simulation.scheduler.stats().collections;
+
+        // Frames that allocate little leave the debt for a later frame.
+        for _ in 0..20 {
+            simulation.update(100.0);
+        }
+        assert_eq!(simulation.scheduler.stats().collections, collections);
+        assert_eq!(simulation.debt, 2000.0);
+
+        // Until waiting any longer would go over the budget.
+        for _ in 0..30 {
+            simulation.update(100.0);
+        }
+        assert_eq!(simulation.scheduler.stats().collections, collections + 1);
+        assert!(simulation.debt < 5000.0);
+    }
+
+    #[test]
+    fn pays_large_allocation_right_away() {
+        let mut simulation = Simulation::with_budget(Duration::from_millis(5));
+        simulation.update(20_000.0);
+        assert_eq!(simulation.debt, 0.0);
+        assert!(simulation.scheduler.stats().longest_pause > Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,610 @@
+//! Deterministic playback of a movie, hashing every rendered frame.
+//!
+//! This lets archives detect when a new version of Ruffle changes how a movie renders,
+//! by comparing reports made by each version. Every frame gets two hashes:
+//!
+//! * An exact hash of the render commands that make up the frame. These are produced before
+//!   any render backend is involved, so this hash does not depend on the renderer or the GPU,
+//!   and is reproducible across runs on the same build. Shapes and bitmaps are identified by the
+//!   order in which they are first drawn rather than by their contents, so changes to shape
+//!   tessellation or to the pixels of a bitmap only show up in the perceptual hash.
+//! * A perceptual hash of the rendered pixels, which tolerates small differences such as
+//!   anti-aliasing. This needs a renderer that supports offscreen rendering, and may vary
+//!   slightly between renderers, so it should only be compared between reports made with
+//!   the same one.
+//!
+//! Playback uses a fixed timestep and a seeded random number generator, and recorded input
+//! is delivered before the frame it was recorded in. Content that reads the wall clock through
+//! `Date` is not deterministic.
+
+use crate::events::PlayerEvent;
+use crate::Player;
+use ruffle_render::backend::{ShapeHandle, ViewportDimensions};
+use ruffle_render::bitmap::{BitmapHandle, PixelRegion};
+use ruffle_render::commands::{Command, CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::pixel_bender::PixelBenderShaderHandle;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use swf::{Color, ColorTransform, Twips};
+use web_time::Instant;
+
+/// The version of the `HashReport` format, and of the hashes in it.
+///
+/// This must be increased whenever a change would make reports from different
+/// versions of Ruffle incomparable, such as a change to how commands are hashed.
+pub const HASH_REPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone)]
+pub struct HashSessionOptions {
+    /// The number of frames to run and hash.
+    pub frames: u32,
+
+    /// The resolution that frames are rendered at, regardless of the size of the player.
+    pub width: u32,
+    pub height: u32,
+
+    /// The seed for the random number generator used by the movie.
+    pub seed: u64,
+
+    /// Whether to compute perceptual hashes of the rendered pixels.
+    pub perceptual: bool,
+
+    /// Input events to deliver, along with the (zero-based) frame that each is delivered before.
+    pub inputs: Vec<(u32, PlayerEvent)>,
+}
+
+/// The hashes of a single frame.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct FrameHash {
+    /// The zero-based index of this frame in the session.
+    pub frame: u32,
+
+    /// The exact hash of the render commands, as 16 hexadecimal digits.
+    pub exact: String,
+
+    /// The perceptual hash of the rendered pixels, as 16 hexadecimal digits,
+    /// if it was requested and the renderer supports it.
+    pub perceptual: Option<String>,
+
+    /// How long the movie took to run this frame, in milliseconds.
+    pub run_ms: f64,
+
+    /// How long rendering and hashing this frame took, in milliseconds.
+    pub render_ms: f64,
+}
+
+/// The result of a hash session, meant to be compared against reports made by other versions.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct HashReport {
+    /// See `HASH_REPORT_VERSION`. Reports with different versions cannot be compared.
+    pub version: u32,
+    pub ruffle_version: &'static str,
+
+    /// The name of the renderer that perceptual hashes were made with.
+    pub renderer: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub seed: u64,
+    pub frame_rate: f64,
+    pub frames: Vec<FrameHash>,
+
+    /// How long the whole session took, in milliseconds.
+    pub total_ms: f64,
+}
+
+/// Plays a movie deterministically, hashing every frame. See the module documentation.
+///
+/// While a session is running, the player must not be ticked or rendered by anything else.
+pub struct HashSession {
+    options: HashSessionOptions,
+    inputs: VecDeque<(u32, PlayerEvent)>,
+    next_frame: u32,
+    frame_time: f64,
+    ids: HandleIds,
+    target: Option<BitmapHandle>,
+    frames: Vec<FrameHash>,
+    start_time: Instant,
+
+    /// The state of the player from before the session, which is restored by `finish`.
+    was_playing: bool,
+    viewport: ViewportDimensions,
+}
+
+impl HashSession {
+    /// Prepares the player for a session, pausing its normal playback.
+    pub fn new(player: &mut Player, options: HashSessionOptions) -> Self {
+        let was_playing = player.is_playing();
+        let viewport = player.viewport_dimensions();
+
+        player.set_is_playing(false);
+        player.set_random_seed(options.seed);
+        player.set_viewport_dimensions(ViewportDimensions {
+            width: options.width,
+            height: options.height,
+            scale_factor: 1.0,
+        });
+
+        let frame_rate = player.frame_rate();
+        let frame_time = if frame_rate > 0.0 {
+            1000.0 / frame_rate
+        } else {
+            0.0
+        };
+
+        let mut inputs = options.inputs.clone();
+        inputs.sort_by_key(|(frame, _)| *frame);
+
+        Self {
+            inputs: inputs.into(),
+            options,
+            next_frame: 0,
+            frame_time,
+            ids: HandleIds::default(),
+            target: None,
+            frames: Vec::new(),
+            start_time: Instant::now(),
+            was_playing,
+            viewport,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.options.frames
+    }
+
+    /// Runs, renders and hashes the next frame.
+    pub fn step(&mut self, player: &mut Player) {
+        if self.is_finished() {
+            return;
+        }
+        let frame = self.next_frame;
+        self.next_frame += 1;
+
+        while let Some(&(_, event)) = self.inputs.front().filter(|(at, _)| *at <= frame) {
+            self.inputs.pop_front();
+            player.handle_event(event);
+        }
+
+        let timer = Instant::now();
+        player.run_fixed_frame(f64::from(frame) * self.frame_time);
+        let run_ms = timer.elapsed().as_secs_f64() * 1000.0;
+
+        let timer = Instant::now();
+        let (background_color, stage_commands) = player.render_commands();
+
+        // The frame is always drawn onto an opaque background, so that nothing
+        // from the previous frame shows through.
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            Color {
+                a: 255,
+                ..background_color
+            },
+            Matrix::create_box(
+                self.options.width as f32,
+                self.options.height as f32,
+                Twips::ZERO,
+                Twips::ZERO,
+            ),
+        );
+        commands.commands.extend(stage_commands.commands);
+
+        let mut hasher = Fnv1a::new();
+        self.ids.hash_commands(&mut hasher, &commands);
+        let exact = format!("{:016x}", hasher.finish());
+
+        let perceptual = if self.options.perceptual {
+            self.perceptual_hash(player, commands)
+                .map(|hash| format!("{hash:016x}"))
+        } else {
+            None
+        };
+
+        self.frames.push(FrameHash {
+            frame,
+            exact,
+            perceptual,
+            run_ms,
+            render_ms: timer.elapsed().as_secs_f64() * 1000.0,
+        });
+    }
+
+    fn perceptual_hash(&mut self, player: &mut Player, commands: CommandList) -> Option<u64> {
+        let renderer = player.renderer_mut();
+        if !renderer.is_offscreen_supported() {
+            return None;
+        }
+
+        let (width, height) = (self.options.width, self.options.height);
+        let target = match &self.target {
+            Some(target) => target.clone(),
+            None => {
+                let target = renderer.create_empty_texture(width, height).ok()?;
+                self.target = Some(target.clone());
+                target
+            }
+        };
+
+        // Always render at the same quality, whatever the movie asks for.
+        let sync_handle = renderer.render_offscreen(
+            target,
+            commands,
+            StageQuality::High,
+            PixelRegion::for_whole_size(width, height),
+        )?;
+
+        let mut hash = None;
+        let result = renderer.resolve_sync_handle(
+            sync_handle,
+            Box::new(|rgba, row_stride| {
+                hash = Some(difference_hash(width, height, rgba, row_stride as usize));
+            }),
+        );
+        if let Err(e) = result {
+            tracing::error!("Unable to read back frame for hashing: {e}");
+        }
+        hash
+    }
+
+    /// Restores the player to how it was before the session, and returns the report.
+    ///
+    /// The movie itself is not rewound.
+    pub fn finish(self, player: &mut Player) -> HashReport {
+        player.set_viewport_dimensions(self.viewport);
+        player.set_is_playing(self.was_playing);
+
+        HashReport {
+            version: HASH_REPORT_VERSION,
+            ruffle_version: env!("CARGO_PKG_VERSION"),
+            renderer: player.renderer().name(),
+            width: self.options.width,
+            height: self.options.height,
+            seed: self.options.seed,
+            frame_rate: player.frame_rate(),
+            frames: self.frames,
+            total_ms: self.start_time.elapsed().as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// A 64-bit FNV-1a hasher.
+///
+/// Unlike the standard library hashers, its output is guaranteed to never change.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        // Treat `-0.0` the same as `0.0`.
+        let value = if value == 0.0 { 0.0 } else { value };
+        self.write(&value.to_bits().to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        // Treat `-0.0` the same as `0.0`.
+        let value = if value == 0.0 { 0.0 } else { value };
+        self.write(&value.to_bits().to_le_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Stable identifiers for the handles drawn during a session, in the order they were first drawn.
+///
+/// The handles are kept alive for the whole session, so that their addresses cannot be reused.
+#[derive(Default)]
+struct HandleIds {
+    ids: HashMap<usize, u32>,
+    shapes: Vec<ShapeHandle>,
+    bitmaps: Vec<BitmapHandle>,
+    shaders: Vec<PixelBenderShaderHandle>,
+}
+
+impl HandleIds {
+    fn id(&mut self, address: usize) -> u32 {
+        let next = self.ids.len() as u32;
+        *self.ids.entry(address).or_insert(next)
+    }
+
+    fn shape(&mut self, shape: &ShapeHandle) -> u32 {
+        let len = self.ids.len();
+        let id = self.id(Arc::as_ptr(&shape.0) as *const () as usize);
+        if self.ids.len() != len {
+            self.shapes.push(shape.clone());
+        }
+        id
+    }
+
+    fn bitmap(&mut self, bitmap: &BitmapHandle) -> u32 {
+        let len = self.ids.len();
+        let id = self.id(Arc::as_ptr(&bitmap.0) as *const () as usize);
+        if self.ids.len() != len {
+            self.bitmaps.push(bitmap.clone());
+        }
+        id
+    }
+
+    fn shader(&mut self, shader: &PixelBenderShaderHandle) -> u32 {
+        let len = self.ids.len();
+        let id = self.id(Arc::as_ptr(&shader.0) as *const () as usize);
+        if self.ids.len() != len {
+            self.shaders.push(shader.clone());
+        }
+        id
+    }
+
+    fn hash_commands(&mut self, hasher: &mut Fnv1a, commands: &CommandList) {
+        hasher.write_u32(commands.commands.len() as u32);
+        for command in &commands.commands {
+            match command {
+                Command::RenderBitmap {
+                    bitmap,
+                    transform,
+                    smoothing,
+                    pixel_snapping,
+                } => {
+                    hasher.write_u8(0);
+                    hasher.write_u32(self.bitmap(bitmap));
+                    hash_transform(hasher, transform);
+                    hasher.write_u8(*smoothing as u8);
+                    hasher.write_u8(*pixel_snapping as u8);
+                }
+                Command::RenderBitmapProjected {
+                    bitmap,
+                    matrix,
+                    color_transform,
+                    smoothing,
+                } => {
+                    hasher.write_u8(11);
+                    hasher.write_u32(self.bitmap(bitmap));
+                    for value in [
+                        matrix.a, matrix.b, matrix.c, matrix.d, matrix.tx, matrix.ty, matrix.p,
+                        matrix.q, matrix.r,
+                    ] {
+                        hasher.write_f64(value);
+                    }
+                    hash_color_transform(hasher, color_transform);
+                    hasher.write_u8(*smoothing as u8);
+                }
+                Command::RenderStage3D { bitmap, transform } => {
+                    hasher.write_u8(1);
+                    hasher.write_u32(self.bitmap(bitmap));
+                    hash_transform(hasher, transform);
+                }
+                Command::RenderShape { shape, transform } => {
+                    hasher.write_u8(2);
+                    hasher.write_u32(self.shape(shape));
+                    hash_transform(hasher, transform);
+                }
+                Command::DrawRect { color, matrix } => {
+                    hasher.write_u8(3);
+                    hash_color(hasher, color);
+                    hash_matrix(hasher, matrix);
+                }
+                Command::DrawLine { color, matrix } => {
+                    hasher.write_u8(4);
+                    hash_color(hasher, color);
+                    hash_matrix(hasher, matrix);
+                }
+                Command::DrawLineRect { color, matrix } => {
+                    hasher.write_u8(5);
+                    hash_color(hasher, color);
+                    hash_matrix(hasher, matrix);
+                }
+                Command::PushMask => hasher.write_u8(6),
+                Command::ActivateMask => hasher.write_u8(7),
+                Command::DeactivateMask => hasher.write_u8(8),
+                Command::PopMask => hasher.write_u8(9),
+                Command::Blend(commands, blend_mode) => {
+                    hasher.write_u8(10);
+                    match blend_mode {
+                        RenderBlendMode::Builtin(mode) => {
+                            hasher.write_u8(0);
+                            hasher.write_u8(*mode as u8);
+                        }
+                        RenderBlendMode::Shader(shader) => {
+                            hasher.write_u8(1);
+                            hasher.write_u32(self.shader(shader));
+                        }
+                    }
+                    self.hash_commands(hasher, commands);
+                }
+            }
+        }
+    }
+}
+
+fn hash_color(hasher: &mut Fnv1a, color: &Color) {
+    hasher.write(&[color.r, color.g, color.b, color.a]);
+}
+
+fn hash_matrix(hasher: &mut Fnv1a, matrix: &Matrix) {
+    hasher.write_f32(matrix.a);
+    hasher.write_f32(matrix.b);
+    hasher.write_f32(matrix.c);
+    hasher.write_f32(matrix.d);
+    hasher.write_i32(matrix.tx.get());
+    hasher.write_i32(matrix.ty.get());
+}
+
+fn hash_transform(hasher: &mut Fnv1a, transform: &Transform) {
+    hash_matrix(hasher, &transform.matrix);
+    hash_color_transform(hasher, &transform.color_transform);
+}
+
+fn hash_color_transform(hasher: &mut Fnv1a, color: &ColorTransform) {
+    for multiply in [
+        color.r_multiply,
+        color.g_multiply,
+        color.b_multiply,
+        color.a_multiply,
+    ] {
+        hasher.write(&multiply.get().to_le_bytes());
+    }
+    for add in [color.r_add, color.g_add, color.b_add, color.a_add] {
+        hasher.write(&add.to_le_bytes());
+    }
+}
+
+/// The size of the grid of brightness samples that `difference_hash` compares.
+const DHASH_WIDTH: usize = 9;
+const DHASH_HEIGHT: usize = 8;
+
+/// Computes a 64-bit difference hash ("dHash") of an RGBA image.
+///
+/// The image is shrunk to 9x8 cells of average brightness, and each bit records whether a cell
+/// is brighter than the one to its right. Similar images have hashes with few differing bits.
+fn difference_hash(width: u32, height: u32, rgba: &[u8], row_stride: usize) -> u64 {
+    let (width, height) = (width as usize, height as usize);
+    let mut sums = [[0u64; DHASH_WIDTH]; DHASH_HEIGHT];
+    let mut counts = [[0u64; DHASH_WIDTH]; DHASH_HEIGHT];
+
+    for y in 0..height {
+        let Some(row) = rgba.get(y * row_stride..) else {
+            break;
+        };
+        let cell_y = y * DHASH_HEIGHT / height;
+        for (x, pixel) in row.chunks_exact(4).take(width).enumerate() {
+            let cell_x = x * DHASH_WIDTH / width;
+            // Rec. 601 luma, in integers so that the result is exact.
+            let luma =
+                299 * u64::from(pixel[0]) + 587 * u64::from(pixel[1]) + 114 * u64::from(pixel[2]);
+            sums[cell_y][cell_x] += luma;
+            counts[cell_y][cell_x] += 1;
+        }
+    }
+
+    let mut hash = 0u64;
+    for y in 0..DHASH_HEIGHT {
+        let average = |x: usize| sums[y][x] / counts[y][x].max(1);
+        for x in 0..DHASH_WIDTH - 1 {
+            hash <<= 1;
+            if average(x) > average(x + 1) {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(width: u32, height: u32, noise: u8) -> Vec<u8> {
+        let mut rgba = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let value = ((x * 255 / width) as u8).saturating_add(((x ^ y) % 2) as u8 * noise);
+                rgba.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+        rgba
+    }
+
+    #[test]
+    fn fnv1a_known_values() {
+        let mut hasher = Fnv1a::new();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"foobar");
+        assert_eq!(hasher.finish(), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn negative_zero_hashes_like_zero() {
+        let mut a = Fnv1a::new();
+        a.write_f32(0.0);
+        let mut b = Fnv1a::new();
+        b.write_f32(-0.0);
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn commands_hash_reproducibly() {
+        let hash = |color: Color| {
+            let mut commands = CommandList::new();
+            commands.push_mask();
+            commands.draw_rect(
+                color,
+                Matrix::create_box(10.0, 20.0, Twips::ZERO, Twips::ONE),
+            );
+            commands.activate_mask();
+            let mut hasher = Fnv1a::new();
+            HandleIds::default().hash_commands(&mut hasher, &commands);
+            hasher.finish()
+        };
+        assert_eq!(hash(Color::RED), hash(Color::RED));
+        assert_ne!(hash(Color::RED), hash(Color::BLUE));
+    }
+
+    #[test]
+    fn difference_hash_tolerates_noise() {
+        let clean = difference_hash(64, 48, &gradient(64, 48, 0), 64 * 4);
+        let noisy = difference_hash(64, 48, &gradient(64, 48, 3), 64 * 4);
+        assert_eq!(clean, noisy);
+
+        // Brightness falls from left to right in a reversed gradient.
+        let reversed: Vec<u8> = gradient(64, 48, 0)
+            .chunks_exact(4)
+            .flat_map(|pixel| [255 - pixel[0], 255 - pixel[1], 255 - pixel[2], 255])
+            .collect();
+        let reversed = difference_hash(64, 48, &reversed, 64 * 4);
+        assert_eq!(reversed, u64::MAX);
+        assert_eq!(clean, 0);
+    }
+
+    #[test]
+    fn difference_hash_respects_row_stride() {
+        let packed = gradient(10, 10, 0);
+        let mut padded = Vec::new();
+        for row in packed.chunks_exact(40) {
+            padded.extend_from_slice(row);
+            padded.extend_from_slice(&[0xAB; 24]);
+        }
+        assert_eq!(
+            difference_hash(10, 10, &packed, 40),
+            difference_hash(10, 10, &padded, 64)
+        );
+    }
+}
@@ -7,10 +7,12 @@ mod text_format;
 
 pub use dimensions::Position;
 pub use layout::{
-    lower_from_text_spans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics,
+    lower_from_text_spans, Layout, LayoutBox, LayoutContent, LayoutImage, LayoutLine, LayoutMetrics,
 };
-pub use stylesheet::{transform_dashes_to_camel_case, CssStream};
-pub use text_format::{FormatSpans, TextDisplay, TextFormat, TextSpan};
+pub use stylesheet::{
+    parse_css_color, parse_css_font_family, transform_dashes_to_camel_case, CssStream, StyleSheet,
+};
+pub use text_format::{FormatSpans, HtmlImage, ImageAlign, TextDisplay, TextFormat, TextSpan};
 
 mod stylesheet;
 #[cfg(test)]
@@ -4,7 +4,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font, FontType};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, HtmlImage, ImageAlign, TextFormat, TextSpan};
 use crate::string::{utils as string_utils, WStr};
 use crate::tag_utils::SwfMovie;
 use crate::DefaultFont;
@@ -13,7 +13,7 @@ use ruffle_render::shape_utils::DrawCommand;
 use std::cmp::{max, min, Ordering};
 use std::fmt::{Debug, Formatter};
 use std::mem;
-use std::ops::{Deref, Range};
+use std::ops::Range;
 use std::slice::Iter;
 use std::sync::Arc;
 use swf::{Point, Rectangle, Twips};
@@ -127,6 +127,9 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The images floated to the sides of the text field so far.
+    images: Vec<LayoutImage>,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
@@ -155,15 +158,111 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             has_line_break: false,
             current_line_span: Default::default(),
             max_bounds,
+            images: Vec::new(),
             is_word_wrap,
             font_type,
         }
     }
 
     fn lay_out_spans(&mut self, context: &mut UpdateContext<'gc>, fs: &'a FormatSpans) {
-        for (span_start, _end, span_text, span) in fs.iter_spans() {
-            self.lay_out_span(context, span_start, span_text, span);
+        let mut images = fs.images().iter().enumerate().peekable();
+
+        for (span_start, span_end, span_text, span) in fs.iter_spans() {
+            // Spans are split at image positions, so that every image is
+            // floated before the text following it is laid out.
+            let mut start = span_start;
+            loop {
+                while let Some((index, image)) =
+                    images.next_if(|(_, image)| image.position() <= start)
+                {
+                    self.lay_out_image(index, image);
+                }
+
+                let end = images
+                    .peek()
+                    .map_or(span_end, |(_, image)| image.position().min(span_end));
+                self.lay_out_span(
+                    context,
+                    start,
+                    &span_text[start - span_start..end - span_start],
+                    span,
+                );
+
+                if end >= span_end {
+                    break;
+                }
+                start = end;
+            }
+        }
+
+        // Images anchored at the end of the text.
+        for (index, image) in images {
+            self.lay_out_image(index, image);
+        }
+    }
+
+    /// Float an image to its side of the text field.
+    ///
+    /// The image starts at the current line if nothing has been laid out on
+    /// it yet, or at the next line otherwise. Images floated to the same side
+    /// are stacked below each other.
+    fn lay_out_image(&mut self, index: usize, image: &HtmlImage) {
+        let (width, height) = image.size();
+        let size = Size::from((Twips::from_pixels(width), Twips::from_pixels(height)));
+        let hspace = Twips::from_pixels(image.hspace());
+        let vspace = Twips::from_pixels(image.vspace());
+        let margin_size = Size::from((size.width() + hspace * 2, size.height() + vspace * 2));
+
+        let mut top = self.cursor.y();
+        if !self.is_start_of_line() {
+            top += self.max_ascent + self.max_descent + self.line_leading_adjustment();
+        }
+        let top = self
+            .images
+            .iter()
+            .filter(|other| other.align == image.align())
+            .map(|other| other.margin_bounds.extent_y())
+            .fold(top, max);
+        let left = match image.align() {
+            ImageAlign::Left => Twips::ZERO,
+            ImageAlign::Right => self.max_bounds - margin_size.width(),
+        };
+
+        let margin_bounds =
+            BoxBounds::from_position_and_size(Position::from((left, top)), margin_size);
+        let bounds = BoxBounds::from_position_and_size(
+            margin_bounds.origin() + Position::from((hspace, vspace)),
+            size,
+        );
+
+        Self::extend_bounds(&mut self.bounds, bounds);
+        Self::extend_bounds(&mut self.text_size_bounds, margin_bounds);
+        self.images.push(LayoutImage {
+            index,
+            align: image.align(),
+            bounds,
+            margin_bounds,
+        });
+    }
+
+    /// The horizontal space taken by floating images on the left and on the
+    /// right side of the current line.
+    fn image_offsets(&self) -> (Twips, Twips) {
+        let top = self.cursor.y();
+        let bottom = top + self.max_ascent + self.max_descent;
+
+        let mut left = Twips::ZERO;
+        let mut right = Twips::ZERO;
+        for image in &self.images {
+            let bounds = image.margin_bounds;
+            if bounds.offset_y() < bottom && top < bounds.extent_y() {
+                match image.align {
+                    ImageAlign::Left => left = left.max(bounds.extent_x()),
+                    ImageAlign::Right => right = right.max(self.max_bounds - bounds.offset_x()),
+                }
+            }
         }
+        (left, right)
     }
 
     fn lay_out_span(
@@ -173,102 +272,121 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span_text: &'a WStr,
         span: &TextSpan,
     ) {
-        if let Some(font) = self.resolve_font(context, span) {
-            self.font = Some(font);
-            self.newspan(span);
-
-            let params = EvalParameters::from_span(span);
+        let fonts = self.resolve_fonts(context, span);
+        for (start, end, font) in font_runs(span_text, &fonts) {
+            self.lay_out_font_run(
+                context,
+                font,
+                span_start + start,
+                &span_text[start..end],
+                span,
+            );
+        }
+    }
 
-            for text in span_text.split(&[b'\n', b'\r', b'\t'][..]) {
-                let slice_start = text.offset_in(span_text).unwrap();
-                let delimiter = if slice_start > 0 {
-                    span_text
-                        .get(slice_start - 1)
-                        .and_then(|c| u8::try_from(c).ok())
-                } else {
-                    None
-                };
+    /// Lay out a part of a span that is drawn with a single font.
+    fn lay_out_font_run(
+        &mut self,
+        context: &mut UpdateContext<'gc>,
+        font: Font<'gc>,
+        span_start: usize,
+        span_text: &'a WStr,
+        span: &TextSpan,
+    ) {
+        self.font = Some(font);
+        self.newspan(span);
 
-                match delimiter {
-                    Some(b'\n' | b'\r') => {
-                        self.newline(context, span_start + slice_start - 1, span, true)
-                    }
-                    Some(b'\t') => self.tab(),
-                    _ => {}
-                }
+        let params = EvalParameters::from_span(span);
 
-                let start = span_start + slice_start;
-
-                let mut last_breakpoint = 0;
-
-                if self.is_word_wrap {
-                    let (mut width, mut offset) = self.wrap_dimensions(span);
-
-                    while let Some(breakpoint) = font.wrap_line(
-                        &text[last_breakpoint..],
-                        params,
-                        width,
-                        offset,
-                        self.is_start_of_line(),
-                    ) {
-                        // This ensures that the space causing the line break
-                        // is included in the line it broke.
-                        let next_breakpoint =
-                            string_utils::next_char_boundary(text, last_breakpoint + breakpoint);
-
-                        // If text doesn't fit at the start of a line, it
-                        // won't fit on the next either, abort and put the
-                        // whole text on the line (will be cut-off). This
-                        // can happen for small text fields with single
-                        // characters.
-                        if breakpoint == 0 && self.is_start_of_line() {
-                            break;
-                        } else if breakpoint == 0 {
-                            self.newline(context, start + next_breakpoint, span, false);
+        for text in span_text.split(&[b'\n', b'\r', b'\t'][..]) {
+            let slice_start = text.offset_in(span_text).unwrap();
+            let delimiter = if slice_start > 0 {
+                span_text
+                    .get(slice_start - 1)
+                    .and_then(|c| u8::try_from(c).ok())
+            } else {
+                None
+            };
 
-                            let next_dim = self.wrap_dimensions(span);
+            match delimiter {
+                Some(b'\n' | b'\r') => {
+                    self.newline(context, span_start + slice_start - 1, span, true)
+                }
+                Some(b'\t') => self.tab(),
+                _ => {}
+            }
 
-                            width = next_dim.0;
-                            offset = next_dim.1;
+            let start = span_start + slice_start;
+
+            let mut last_breakpoint = 0;
+
+            if self.is_word_wrap {
+                let (mut width, mut offset) = self.wrap_dimensions(span);
+
+                while let Some(breakpoint) = font.wrap_line(
+                    &text[last_breakpoint..],
+                    params,
+                    width,
+                    offset,
+                    self.is_start_of_line(),
+                ) {
+                    // This ensures that the space causing the line break
+                    // is included in the line it broke.
+                    let next_breakpoint =
+                        string_utils::next_char_boundary(text, last_breakpoint + breakpoint);
+
+                    // If text doesn't fit at the start of a line, it
+                    // won't fit on the next either, abort and put the
+                    // whole text on the line (will be cut-off). This
+                    // can happen for small text fields with single
+                    // characters.
+                    if breakpoint == 0 && self.is_start_of_line() {
+                        break;
+                    } else if breakpoint == 0 {
+                        self.newline(context, start + next_breakpoint, span, false);
 
-                            if last_breakpoint >= text.len() {
-                                break;
-                            } else {
-                                continue;
-                            }
-                        }
+                        let next_dim = self.wrap_dimensions(span);
 
-                        self.append_text(
-                            &text[last_breakpoint..next_breakpoint],
-                            start + last_breakpoint,
-                            start + next_breakpoint,
-                            span,
-                        );
+                        width = next_dim.0;
+                        offset = next_dim.1;
 
-                        last_breakpoint = next_breakpoint;
                         if last_breakpoint >= text.len() {
                             break;
+                        } else {
+                            continue;
                         }
-
-                        self.newline(context, start + next_breakpoint, span, false);
-                        let next_dim = self.wrap_dimensions(span);
-
-                        width = next_dim.0;
-                        offset = next_dim.1;
                     }
-                }
-
-                let span_end = text.len();
 
-                if last_breakpoint < span_end {
                     self.append_text(
-                        &text[last_breakpoint..span_end],
+                        &text[last_breakpoint..next_breakpoint],
                         start + last_breakpoint,
-                        start + span_end,
+                        start + next_breakpoint,
                         span,
                     );
+
+                    last_breakpoint = next_breakpoint;
+                    if last_breakpoint >= text.len() {
+                        break;
+                    }
+
+                    self.newline(context, start + next_breakpoint, span, false);
+                    let next_dim = self.wrap_dimensions(span);
+
+                    width = next_dim.0;
+                    offset = next_dim.1;
                 }
             }
+
+            let span_end = text.len();
+
+            if last_breakpoint < span_end {
+                self.append_text(
+                    &text[last_breakpoint..span_end],
+                    start + last_breakpoint,
+                    start + span_end,
+                    span,
+                );
+            }
         }
     }
 
@@ -417,9 +535,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_size_bounds = line_size_bounds.unwrap_or_default();
 
+        let (left_images, right_images) = self.image_offsets();
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line) + left_images;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + right_images;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_size_bounds.width();
@@ -609,11 +729,16 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
-    fn resolve_font(
+    /// Resolve the chain of fonts used to draw a span.
+    ///
+    /// The first font is the one requested by the span. Device fonts are
+    /// followed by the fallback fonts used for the characters they lack, and
+    /// have their bold and italic styles synthesized when necessary.
+    fn resolve_fonts(
         &mut self,
         context: &mut UpdateContext<'gc>,
         span: &TextSpan,
-    ) -> Option<Font<'gc>> {
+    ) -> Vec<Font<'gc>> {
         let font_name = span.font.face.to_utf8_lossy();
 
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
@@ -630,7 +755,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
                 )
                 .filter(|f| f.has_glyphs())
             {
-                return Some(font);
+                return vec![font];
             }
             // TODO: If set to use embedded fonts and we couldn't find any matching font, show nothing
             // However - at time of writing, we don't support DefineFont4. If we matched this behaviour,
@@ -638,8 +763,36 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             // return None;
         }
 
+        let mut fonts = self.resolve_device_fonts(context, &font_name, span);
+        if !fonts.is_empty() {
+            for font in context.library.default_font(
+                DefaultFont::Fallback,
+                span.style.bold,
+                span.style.italic,
+                context.ui,
+                context.renderer,
+                context.gc_context,
+            ) {
+                if !fonts.iter().any(|other| Font::ptr_eq(*other, font)) {
+                    fonts.push(font);
+                }
+            }
+        }
+
+        fonts
+            .into_iter()
+            .map(|font| font.with_synthesized_style(span.style.bold, span.style.italic))
+            .collect()
+    }
+
+    fn resolve_device_fonts(
+        &mut self,
+        context: &mut UpdateContext<'gc>,
+        font_name: &str,
+        span: &TextSpan,
+    ) -> Vec<Font<'gc>> {
         // Check if the font name is one of the known default fonts.
-        if let Some(default_font) = match font_name.deref() {
+        if let Some(default_font) = match font_name {
             "_serif" => Some(DefaultFont::Serif),
             "_sans" => Some(DefaultFont::Sans),
             "_typewriter" => Some(DefaultFont::Typewriter),
@@ -648,39 +801,33 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             "_明朝" => Some(DefaultFont::JapaneseMincho),
             _ => None,
         } {
-            return context
-                .library
-                .default_font(
-                    default_font,
-                    span.style.bold,
-                    span.style.italic,
-                    context.ui,
-                    context.renderer,
-                    context.gc_context,
-                )
-                .first()
-                .copied();
+            return context.library.default_font(
+                default_font,
+                span.style.bold,
+                span.style.italic,
+                context.ui,
+                context.renderer,
+                context.gc_context,
+            );
         }
 
         if let Some(font) = context.library.get_or_load_device_font(
-            &font_name,
+            font_name,
             span.style.bold,
             span.style.italic,
             context.ui,
             context.renderer,
             context.gc_context,
         ) {
-            return Some(font);
+            return vec![font];
         }
 
-        // TODO: handle multiple fonts for a definition, each covering different sets of glyphs
-
         // At this point, the font name was neither one of the default
         // fonts nor matched any device font. We explicitly handle some of the
         // well-known aliases for the default fonts for better compatibility
         // with devices that don't have those fonts installed. As a last resort
         // we fall back to using sans (like Flash).
-        let default_font = match font_name.deref() {
+        let default_font = match font_name {
             "Times New Roman" => DefaultFont::Serif,
             "Arial" => DefaultFont::Sans,
             "Courier New" => DefaultFont::Typewriter,
@@ -692,18 +839,14 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
                 }
             }
         };
-        context
-            .library
-            .default_font(
-                default_font,
-                span.style.bold,
-                span.style.italic,
-                context.ui,
-                context.renderer,
-                context.gc_context,
-            )
-            .first()
-            .copied()
+        context.library.default_font(
+            default_font,
+            span.style.bold,
+            span.style.italic,
+            context.ui,
+            context.renderer,
+            context.gc_context,
+        )
     }
 
     /// Append text to the current line of the ongoing layout operation.
@@ -763,7 +906,12 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     /// should be appended after line fixup has completed, but before the text
     /// cursor is moved down.
     fn append_bullet(&mut self, context: &mut UpdateContext<'gc>, span: &TextSpan) {
-        if let Some(bullet_font) = self.resolve_font(context, span).or(self.font) {
+        if let Some(bullet_font) = self
+            .resolve_fonts(context, span)
+            .first()
+            .copied()
+            .or(self.font)
+        {
             let mut bullet_cursor = self.cursor;
 
             bullet_cursor.set_x(
@@ -837,8 +985,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
-        let offset = Self::left_alignment_offset(current_span, self.is_first_line);
+        let (left_images, right_images) = self.image_offsets();
+        let width = self.max_bounds
+            - Twips::from_pixels(self.current_line_span.right_margin)
+            - right_images;
+        let offset = Self::left_alignment_offset(current_span, self.is_first_line) + left_images;
 
         (width, offset + self.cursor.x())
     }
@@ -853,6 +1004,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             bounds: self.bounds.unwrap_or_default(),
             text_size: Size::from((text_size.width(), text_size.height())),
             lines: self.lines,
+            images: self.images,
         }
     }
 
@@ -908,6 +1060,47 @@ fn lower_from_text_spans_known_width<'gc>(
     layout_context.end_layout(context, fs)
 }
 
+/// Split text into runs of characters drawn with the same font of a chain.
+///
+/// Each character is drawn with the first font that has a glyph for it, or
+/// with the first font if none does. Whitespace stays in the current run.
+fn font_runs<'gc>(text: &WStr, fonts: &[Font<'gc>]) -> Vec<(usize, usize, Font<'gc>)> {
+    let Some(&first_font) = fonts.first() else {
+        return vec![];
+    };
+    if fonts.len() == 1 {
+        return vec![(0, text.len(), first_font)];
+    }
+
+    let mut runs: Vec<(usize, usize, Font<'gc>)> = vec![];
+    for (pos, c) in text.char_indices() {
+        let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+        let font = if c.is_whitespace() {
+            None
+        } else {
+            let font = fonts
+                .iter()
+                .find(|font| font.get_glyph_for_char(c).is_some())
+                .copied();
+            Some(font.unwrap_or(first_font))
+        };
+
+        match (runs.last_mut(), font) {
+            (None, font) => runs.push((0, text.len(), font.unwrap_or(first_font))),
+            (Some(run), Some(font)) if !Font::ptr_eq(run.2, font) => {
+                run.1 = pos;
+                runs.push((pos, text.len(), font));
+            }
+            _ => {}
+        }
+    }
+
+    if runs.is_empty() {
+        runs.push((0, text.len(), first_font));
+    }
+    runs
+}
+
 /// A `Layout` represents a fully laid-out text field.
 /// It consists of [`LayoutLine`]s.
 #[derive(Clone, Debug, Collect)]
@@ -920,6 +1113,9 @@ pub struct Layout<'gc> {
     text_size: Size<Twips>,
 
     lines: Vec<LayoutLine<'gc>>,
+
+    #[collect(require_static)]
+    images: Vec<LayoutImage>,
 }
 
 impl<'gc> Layout<'gc> {
@@ -937,6 +1133,11 @@ impl<'gc> Layout<'gc> {
         &self.lines
     }
 
+    /// Images floated in this layout, ordered by their position in text.
+    pub fn images(&self) -> &[LayoutImage] {
+        &self.images
+    }
+
     pub fn boxes_iter(&self) -> LayoutBoxIter<'_, 'gc> {
         LayoutBoxIter {
             lines_iter: self.lines.iter(),
@@ -998,6 +1199,31 @@ impl<'gc> Layout<'gc> {
     }
 }
 
+/// A `LayoutImage` represents an image floated to a side of a text field.
+#[derive(Clone, Debug)]
+pub struct LayoutImage {
+    /// The index of the image in [`FormatSpans::images`].
+    index: usize,
+
+    align: ImageAlign,
+
+    /// The area the image is drawn in.
+    bounds: BoxBounds<Twips>,
+
+    /// The area taken by the image, including the space around it.
+    margin_bounds: BoxBounds<Twips>,
+}
+
+impl LayoutImage {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn bounds(&self) -> BoxBounds<Twips> {
+        self.bounds
+    }
+}
+
 /// A `LayoutLine` represents a single line of text.
 /// It consists of [`LayoutBox`]es.
 #[derive(Clone, Debug, Collect)]
@@ -1174,7 +1400,7 @@ pub enum LayoutContent<'gc> {
         #[collect(require_static)]
         color: swf::Color,
 
-        /// List of end positions (relative to this box) for each character.
+        /// List of start and end positions (relative to this box) for each character.
         ///
         /// By having this here, we do not have to reevaluate the font
         /// each time we want to get the position of a character,
@@ -1184,10 +1410,13 @@ pub enum LayoutContent<'gc> {
         /// For instance, for the text "hello", this field may contain:
         ///
         /// ```text
-        /// [100, 200, 250, 300, 400]
+        /// [(0, 100), (100, 200), (200, 250), (250, 300), (300, 400)]
         /// ```
+        ///
+        /// Characters in right-to-left text are drawn backwards, so their
+        /// positions go down instead.
         #[collect(require_static)]
-        char_end_pos: Vec<Twips>,
+        char_x_bounds: Vec<(Twips, Twips)>,
     },
 
     /// A layout box containing a bullet.
@@ -1259,11 +1488,33 @@ impl<'gc> LayoutBox<'gc> {
         span: &TextSpan,
     ) -> Self {
         let params = EvalParameters::from_span(span);
-        let mut char_end_pos = Vec::with_capacity(end - start);
+        let mut char_x_bounds: Vec<Option<(Twips, Twips)>> = vec![None; text.len()];
 
-        font.evaluate(text, Default::default(), params, |_, _, _, advance, x| {
-            char_end_pos.push(x + advance);
-        });
+        font.evaluate(
+            text,
+            Default::default(),
+            params,
+            |pos, _, _, advance, x, _| {
+                if let Some(bounds) = char_x_bounds.get_mut(pos) {
+                    // Shaping may draw a character with several glyphs.
+                    let (start, end) = bounds.get_or_insert((x, x + advance));
+                    *start = (*start).min(x);
+                    *end = (*end).max(x + advance);
+                }
+            },
+        );
+
+        // Characters without glyphs of their own (such as the second half of a surrogate
+        // pair, or a letter that was combined with another) take no space.
+        let mut last_end = Twips::ZERO;
+        let char_x_bounds = char_x_bounds
+            .into_iter()
+            .map(|bounds| {
+                let bounds = bounds.unwrap_or((last_end, last_end));
+                last_end = bounds.1;
+                bounds
+            })
+            .collect();
 
         Self {
             bounds: Default::default(),
@@ -1274,7 +1525,7 @@ impl<'gc> LayoutBox<'gc> {
                 font,
                 params,
                 color: span.font.color,
-                char_end_pos,
+                char_x_bounds,
             },
         }
     }
@@ -1401,18 +1652,11 @@ impl<'gc> LayoutBox<'gc> {
     pub fn char_x_bounds(&self, position: usize) -> Option<(Twips, Twips)> {
         let relative_position = position.checked_sub(self.start())?;
 
-        let LayoutContent::Text { char_end_pos, .. } = &self.content else {
+        let LayoutContent::Text { char_x_bounds, .. } = &self.content else {
             return None;
         };
 
-        Some(if relative_position == 0 {
-            (Twips::ZERO, *char_end_pos.get(0)?)
-        } else {
-            (
-                *char_end_pos.get(relative_position - 1)?,
-                *char_end_pos.get(relative_position)?,
-            )
-        })
+        char_x_bounds.get(relative_position).copied()
     }
 }
 
@@ -1,6 +1,9 @@
+use crate::html::TextFormat;
 use fnv::FnvHashMap;
+use gc_arena::{Collect, Gc, Mutation};
 use ruffle_wstr::{WStr, WString};
 use std::borrow::Cow;
+use std::cell::RefCell;
 
 pub type CssProperties<'a> = FnvHashMap<&'a WStr, &'a WStr>;
 
@@ -281,11 +284,139 @@ pub fn transform_dashes_to_camel_case(input: &WStr) -> Cow<WStr> {
     Cow::Owned(result)
 }
 
+/// Parses a CSS color of the form `#RRGGBB`, as accepted by `StyleSheet.transform`.
+///
+/// Anything else is treated as black, like Flash does.
+pub fn parse_css_color(input: &WStr) -> u32 {
+    if let Some(stripped) = input.strip_prefix(WStr::from_units(b"#")) {
+        if stripped.len() <= 6 {
+            if let Ok(number) = u32::from_str_radix(&stripped.to_string(), 16) {
+                return number;
+            }
+        }
+    }
+
+    0
+}
+
+/// Parses a CSS `font-family` list into the comma separated form used by `TextFormat.font`,
+/// mapping the generic family names to Flash's device fonts.
+pub fn parse_css_font_family(input: &WStr) -> WString {
+    let mut result = WString::new();
+
+    let mut pos = 0;
+    while pos < input.len() {
+        // Skip whitespace
+        while input.get(pos) == Some(' ' as u16) {
+            pos += 1;
+        }
+
+        // Find the whole value
+        let start = pos;
+        while input.get(pos) != Some(',' as u16) && pos < input.len() {
+            pos += 1;
+        }
+
+        let mut value = &input[start..pos];
+
+        if pos < input.len() {
+            pos += 1; // move past the comma
+        }
+
+        // Transform some names
+        if value == b"mono" {
+            value = WStr::from_units(b"_typewriter");
+        } else if value == b"sans-serif" {
+            value = WStr::from_units(b"_sans");
+        } else if value == b"serif" {
+            value = WStr::from_units(b"_serif");
+        }
+
+        // Add it to the result (without any extra space)
+        if !value.is_empty() {
+            if !result.is_empty() {
+                result.push_char(',');
+            }
+            result.push_str(value);
+        }
+    }
+
+    result
+}
+
+/// The styles of a `StyleSheet` object, as used by the text fields it is attached to.
+///
+/// Each selector (a tag name, or a class name starting with `.`) is stored in lowercase,
+/// along with the `TextFormat` its style object was transformed into.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct StyleSheet<'gc>(Gc<'gc, RefCell<StyleSheetData>>);
+
+#[derive(Default, Collect)]
+#[collect(require_static)]
+struct StyleSheetData {
+    selectors: FnvHashMap<WString, TextFormat>,
+
+    /// Increased on every change, so that text fields know when to restyle their text.
+    generation: u32,
+}
+
+impl<'gc> StyleSheet<'gc> {
+    pub fn new(mc: &Mutation<'gc>) -> Self {
+        Self(Gc::new(mc, Default::default()))
+    }
+
+    pub fn ptr_eq(a: Self, b: Self) -> bool {
+        Gc::ptr_eq(a.0, b.0)
+    }
+
+    pub fn get_style(self, selector: &WStr) -> Option<TextFormat> {
+        self.0
+            .borrow()
+            .selectors
+            .get(&selector.to_ascii_lowercase())
+            .cloned()
+    }
+
+    /// Sets the style of a selector, or removes it if `format` is `None`.
+    pub fn set_style(self, selector: &WStr, format: Option<TextFormat>) {
+        let mut data = self.0.borrow_mut();
+        let selector = selector.to_ascii_lowercase();
+        match format {
+            Some(format) => {
+                data.selectors.insert(selector, format);
+            }
+            None => {
+                data.selectors.remove(&selector);
+            }
+        }
+        data.generation = data.generation.wrapping_add(1);
+    }
+
+    pub fn clear(self) {
+        let mut data = self.0.borrow_mut();
+        data.selectors.clear();
+        data.generation = data.generation.wrapping_add(1);
+    }
+
+    pub fn generation(self) -> u32 {
+        self.0.borrow().generation
+    }
+}
+
+impl std::fmt::Debug for StyleSheet<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StyleSheet")
+            .field("ptr", &Gc::as_ptr(self.0))
+            .finish()
+    }
+}
+
 // More exhaustive tests live inside avm2 stylesheet swf test
 // These are just some useful ones extracted out
 #[cfg(test)]
 mod tests {
-    use super::{CssError, CssStream};
+    use super::{parse_css_font_family, CssError, CssStream};
     use fnv::FnvHashMap;
     use ruffle_wstr::WStr;
 
@@ -475,4 +606,12 @@ mod tests {
         let mut stream = CssStream::new(WStr::from_units(b"a{:"));
         assert_eq!(stream.parse(), Err(CssError::PropertyValueMissing));
     }
+
+    #[test]
+    fn parse_font_family_generic_names() {
+        assert_eq!(
+            parse_css_font_family(WStr::from_units(b" Arial, sans-serif,,mono")).as_wstr(),
+            WStr::from_units(b"Arial,_sans,_typewriter")
+        );
+    }
 }
@@ -1,8 +1,10 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, ImageAlign, TextDisplay, TextFormat, TextSpan};
+use crate::html::StyleSheet;
 use crate::string::{WStr, WString};
+use gc_arena::rootless_arena;
 use swf::{Rectangle, Twips};
 
 #[test]
@@ -868,3 +870,221 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+fn field_format() -> TextFormat {
+    TextFormat {
+        font: Some(WString::from_utf8("Arial")),
+        size: Some(12.0),
+        bold: Some(false),
+        italic: Some(false),
+        align: Some(swf::TextAlign::Left),
+        ..Default::default()
+    }
+}
+
+/// The htmlText of a field reported by Flash Player in the `edittext_html_roundtrip` SWF tests.
+const FLASH_HELLO_WORLD_HTML: &str = concat!(
+    r#"<TEXTFORMAT LEFTMARGIN="5" LEADING="2"><P ALIGN="LEFT"><FONT FACE="Times New Roman" SIZE="12" COLOR="#000000" LETTERSPACING="0" KERNING="0">"#,
+    r#"hello <B>bolded</B> world</FONT></P></TEXTFORMAT>"#,
+    r#"<TEXTFORMAT LEADING="2"><P ALIGN="LEFT"><FONT FACE="Times New Roman" SIZE="12" COLOR="#000000" LETTERSPACING="0" KERNING="0">"#,
+    r#"next line</FONT></P></TEXTFORMAT>"#,
+);
+
+/// Replays the `setTextFormat` calls made by the `edittext_html_roundtrip` SWF tests, and checks
+/// the formats against the htmlText that Flash Player reported after each of them.
+#[test]
+fn formatspans_set_text_format_flash_sequence() {
+    let mut fs = FormatSpans::from_html(
+        &WString::from_utf8(FLASH_HELLO_WORLD_HTML),
+        field_format(),
+        None,
+        true,
+        false,
+        10,
+    );
+    assert_eq!(
+        WStr::from_units(b"hello bolded world\rnext line"),
+        &fs.text()[..28]
+    );
+    let len = fs.text().len();
+    assert_eq!(Some(true), fs.get_text_format(6, 12).bold);
+    assert_eq!(None, fs.get_text_format(0, 12).bold);
+
+    // setTextFormat(unbold_tf, 6, 12): "hello bolded world", without any <B>.
+    fs.set_text_format(
+        6,
+        12,
+        &TextFormat {
+            bold: Some(false),
+            ..Default::default()
+        },
+    );
+    assert_eq!(Some(false), fs.get_text_format(0, len).bold);
+
+    // setTextFormat(noto_tf, 4, 10): "hell<FONT FACE="Noto Sans">o bold</FONT>ed world".
+    let times = Some(WString::from_utf8("Times New Roman"));
+    let noto = Some(WString::from_utf8("Noto Sans"));
+    fs.set_text_format(
+        4,
+        10,
+        &TextFormat {
+            font: noto.clone(),
+            ..Default::default()
+        },
+    );
+    assert_eq!(times, fs.get_text_format(0, 4).font);
+    assert_eq!(noto, fs.get_text_format(4, 10).font);
+    assert_eq!(times, fs.get_text_format(10, len).font);
+    assert_eq!(None, fs.get_text_format(0, len).font);
+    assert_eq!(Some(12.0), fs.get_text_format(0, len).size);
+
+    // setTextFormat(center_tf, 0, text.length): both paragraphs are centered,
+    // and the first one keeps its left margin.
+    fs.set_text_format(
+        0,
+        len,
+        &TextFormat {
+            align: Some(swf::TextAlign::Center),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        Some(swf::TextAlign::Center),
+        fs.get_text_format(0, len).align
+    );
+    assert_eq!(Some(5.0), fs.get_text_format(0, 19).left_margin);
+    assert_eq!(None, fs.get_text_format(0, len).left_margin);
+
+    // setTextFormat(big_tf, 8, 15): the size changes across the font boundary,
+    // and the fonts are kept, in
+    // "hell<FONT FACE="Noto Sans">o bo<FONT SIZE="14">ld<FONT FACE="Times New Roman">ed wo</FONT></FONT></FONT>rld".
+    fs.set_text_format(
+        8,
+        15,
+        &TextFormat {
+            size: Some(14.0),
+            ..Default::default()
+        },
+    );
+    assert_eq!(Some(12.0), fs.get_text_format(0, 8).size);
+    assert_eq!(Some(14.0), fs.get_text_format(8, 15).size);
+    assert_eq!(Some(12.0), fs.get_text_format(15, len).size);
+    assert_eq!(noto, fs.get_text_format(4, 10).font);
+    assert_eq!(times, fs.get_text_format(10, 15).font);
+    assert_eq!(None, fs.get_text_format(8, 15).font);
+    assert_eq!(
+        Some(swf::TextAlign::Center),
+        fs.get_text_format(0, len).align
+    );
+}
+
+#[test]
+fn formatspans_html_round_trip() {
+    let mut fs = FormatSpans::from_text(WString::from_utf8("one two three"), field_format());
+    fs.set_text_format(
+        0,
+        3,
+        &TextFormat {
+            bold: Some(true),
+            ..Default::default()
+        },
+    );
+    fs.set_text_format(
+        2,
+        7,
+        &TextFormat {
+            italic: Some(true),
+            size: Some(20.0),
+            ..Default::default()
+        },
+    );
+    fs.set_text_format(
+        8,
+        13,
+        &TextFormat {
+            font: Some(WString::from_utf8("Times")),
+            ..Default::default()
+        },
+    );
+
+    let html = fs.to_html();
+    let parsed = FormatSpans::from_html(&html, field_format(), None, false, false, 10);
+    assert_eq!(fs.text(), parsed.text());
+    for i in 0..fs.text().len() {
+        let expected = fs.get_text_format(i, i + 1);
+        let actual = parsed.get_text_format(i, i + 1);
+        assert_eq!(expected.font, actual.font, "font at {i} in {html:?}");
+        assert_eq!(expected.size, actual.size, "size at {i} in {html:?}");
+        assert_eq!(expected.bold, actual.bold, "bold at {i} in {html:?}");
+        assert_eq!(expected.italic, actual.italic, "italic at {i} in {html:?}");
+    }
+}
+
+#[test]
+fn formatspans_html_style_sheet() {
+    rootless_arena(|mc| {
+        let style_sheet = StyleSheet::new(mc);
+        style_sheet.set_style(
+            WStr::from_units(b"heading"),
+            Some(TextFormat {
+                bold: Some(true),
+                display: Some(TextDisplay::Block),
+                ..Default::default()
+            }),
+        );
+        style_sheet.set_style(
+            WStr::from_units(b".Big"),
+            Some(TextFormat {
+                size: Some(20.0),
+                ..Default::default()
+            }),
+        );
+        style_sheet.set_style(
+            WStr::from_units(b".hidden"),
+            Some(TextFormat {
+                display: Some(TextDisplay::None),
+                ..Default::default()
+            }),
+        );
+
+        let html = WString::from_utf8(
+            "a<heading>b</heading><span class='big'>c</span><span class='hidden'>d</span>",
+        );
+        let parsed =
+            FormatSpans::from_html(&html, field_format(), Some(style_sheet), true, false, 10);
+        assert_eq!(WStr::from_units(b"a\rb\rc"), parsed.text());
+        assert_eq!(parsed.get_text_format(2, 3).bold, Some(true));
+        assert_eq!(parsed.get_text_format(4, 5).bold, Some(false));
+        assert_eq!(parsed.get_text_format(4, 5).size, Some(20.0));
+    });
+}
+
+#[test]
+fn formatspans_html_images() {
+    let html = WString::from_utf8(
+        "ab<img src='pic.png' id='pic' width='20' align='right'/>cd<img src='Symbol'>",
+    );
+    let mut parsed = FormatSpans::from_html(&html, field_format(), None, true, false, 10);
+    assert_eq!(WStr::from_units(b"abcd"), parsed.text());
+
+    let images = parsed.images();
+    assert_eq!(images.len(), 2);
+    assert_eq!(images[0].position(), 2);
+    assert_eq!(images[0].src(), WStr::from_units(b"pic.png"));
+    assert_eq!(images[0].id(), Some(WStr::from_units(b"pic")));
+    assert_eq!(images[0].align(), ImageAlign::Right);
+    assert_eq!(images[0].size(), (20.0, 0.0));
+    assert_eq!(images[0].hspace(), 8.0);
+    assert_eq!(images[1].position(), 4);
+    assert_eq!(images[1].id(), None);
+    assert_eq!(images[1].align(), ImageAlign::Left);
+
+    assert!(parsed.set_image_content_size(0, 40.0, 30.0));
+    assert!(!parsed.set_image_content_size(0, 40.0, 30.0));
+    assert_eq!(parsed.images()[0].size(), (20.0, 30.0));
+
+    parsed.replace_text(1, 3, WStr::from_units(b"xyz"));
+    assert_eq!(WStr::from_units(b"axyzd"), parsed.text());
+    assert_eq!(parsed.images()[0].position(), 1);
+    assert_eq!(parsed.images()[1].position(), 5);
+}
@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::StyleSheet;
 use crate::string::{Integer, SwfStrExt as _, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -205,6 +206,63 @@ impl TextFormat {
         }
     }
 
+    /// Split this format into its character-level properties and its
+    /// paragraph-level properties, in that order.
+    ///
+    /// Paragraph-level properties always apply to whole paragraphs, even if
+    /// they are set on a range that only covers part of one.
+    pub fn split_paragraph_properties(self) -> (Self, Self) {
+        let paragraph = TextFormat {
+            align: self.align,
+            left_margin: self.left_margin,
+            right_margin: self.right_margin,
+            indent: self.indent,
+            block_indent: self.block_indent,
+            leading: self.leading,
+            tab_stops: self.tab_stops,
+            bullet: self.bullet,
+            ..Default::default()
+        };
+        let character = TextFormat {
+            font: self.font,
+            size: self.size,
+            color: self.color,
+            bold: self.bold,
+            italic: self.italic,
+            underline: self.underline,
+            kerning: self.kerning,
+            letter_spacing: self.letter_spacing,
+            url: self.url,
+            target: self.target,
+            display: self.display,
+            ..Default::default()
+        };
+        (character, paragraph)
+    }
+
+    /// Whether this format does not define any property.
+    pub fn is_empty(&self) -> bool {
+        self.font.is_none()
+            && self.size.is_none()
+            && self.color.is_none()
+            && self.align.is_none()
+            && self.bold.is_none()
+            && self.italic.is_none()
+            && self.underline.is_none()
+            && self.left_margin.is_none()
+            && self.right_margin.is_none()
+            && self.indent.is_none()
+            && self.block_indent.is_none()
+            && self.kerning.is_none()
+            && self.leading.is_none()
+            && self.letter_spacing.is_none()
+            && self.tab_stops.is_none()
+            && self.bullet.is_none()
+            && self.url.is_none()
+            && self.target.is_none()
+            && self.display.is_none()
+    }
+
     /// Given two text formats, construct a new `TextFormat` where only
     /// matching properties between the two formats are defined.
     pub fn merge_matching_properties(self, rhs: TextFormat) -> Self {
@@ -571,6 +629,99 @@ impl TextSpan {
     }
 }
 
+/// The side of the text field an `<img>` is floated to.
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageAlign {
+    #[default]
+    Left,
+    Right,
+}
+
+/// An image embedded in HTML text with the `<img>` tag.
+///
+/// Images do not occupy any characters in the text; they are anchored at a
+/// text position and float to one side of the field, with text flowing
+/// around them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtmlImage {
+    /// The text position the image is anchored at.
+    position: usize,
+
+    /// The URL or the linkage identifier of the image.
+    src: WString,
+
+    /// The identifier used to retrieve the image from script.
+    id: Option<WString>,
+
+    /// The requested width of the image, in pixels.
+    width: Option<f64>,
+
+    /// The requested height of the image, in pixels.
+    height: Option<f64>,
+
+    /// The size of the loaded content, in pixels.
+    ///
+    /// It is used for any dimension not requested explicitly.
+    content_size: Option<(f64, f64)>,
+
+    align: ImageAlign,
+
+    /// The horizontal space around the image, in pixels.
+    hspace: f64,
+
+    /// The vertical space around the image, in pixels.
+    vspace: f64,
+
+    check_policy_file: bool,
+}
+
+impl HtmlImage {
+    /// The default space around an image, in pixels.
+    const DEFAULT_SPACE: f64 = 8.0;
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn src(&self) -> &WStr {
+        &self.src
+    }
+
+    pub fn id(&self) -> Option<&WStr> {
+        self.id.as_deref()
+    }
+
+    pub fn align(&self) -> ImageAlign {
+        self.align
+    }
+
+    pub fn hspace(&self) -> f64 {
+        self.hspace
+    }
+
+    pub fn vspace(&self) -> f64 {
+        self.vspace
+    }
+
+    pub fn check_policy_file(&self) -> bool {
+        self.check_policy_file
+    }
+
+    /// The size of the loaded content, in pixels.
+    pub fn content_size(&self) -> Option<(f64, f64)> {
+        self.content_size
+    }
+
+    /// The size the image is laid out with, in pixels.
+    pub fn size(&self) -> (f64, f64) {
+        let (content_width, content_height) = self.content_size.unwrap_or_default();
+        (
+            self.width.unwrap_or(content_width),
+            self.height.unwrap_or(content_height),
+        )
+    }
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug)]
 pub struct FormatSpans {
@@ -578,6 +729,7 @@ pub struct FormatSpans {
     displayed_text: WString,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+    images: Vec<HtmlImage>,
 }
 
 impl Default for FormatSpans {
@@ -593,6 +745,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: Vec::new(),
         }
     }
 
@@ -604,6 +757,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: Vec::new(),
         }
     }
 
@@ -614,6 +768,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::with_length_and_format(len, &format)],
             default_format: format,
+            images: Vec::new(),
         }
     }
 
@@ -626,6 +781,7 @@ impl FormatSpans {
     pub fn from_html(
         html: &WStr,
         default_format: TextFormat,
+        style_sheet: Option<StyleSheet<'_>>,
         is_multiline: bool,
         condense_white: bool,
         swf_version: u8,
@@ -637,6 +793,7 @@ impl FormatSpans {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
+        let mut images: Vec<HtmlImage> = Vec::new();
 
         // quick_xml::Reader requires a [u8] slice, but doesn't actually care about Unicode;
         // this means we can pass the raw buffer in the Latin1 case.
@@ -668,6 +825,9 @@ impl FormatSpans {
         let mut opened_buffer: Vec<u8> = Vec::new();
         let mut opened_starts: Vec<usize> = Vec::new();
 
+        // The `display` given to each opened tag by the style sheet, if any.
+        let mut opened_displays: Vec<Option<TextDisplay>> = Vec::new();
+
         // For the weird behaviors of <p>
         let mut p_open = false;
         let mut last_closed_font: Option<TextSpanFont> = None;
@@ -700,6 +860,22 @@ impl FormatSpans {
                         })
                     };
                     let mut format = format_stack.last().unwrap().clone();
+
+                    // Styles apply to their tag first, then to the class of the element.
+                    let mut display = None;
+                    if let Some(style_sheet) = style_sheet {
+                        let tag_style = style_sheet.get_style(&decode_to_wstr(tag_name));
+                        let class_style = attribute(b"class").and_then(|class| {
+                            let mut selector = WString::from_unit(b'.'.into());
+                            selector.push_str(&class);
+                            style_sheet.get_style(&selector)
+                        });
+                        for style in [tag_style, class_style].into_iter().flatten() {
+                            display = style.display.or(display);
+                            format = style.mix_with(format);
+                        }
+                    }
+
                     match tag_name {
                         b"br" => {
                             if is_multiline {
@@ -719,6 +895,40 @@ impl FormatSpans {
                             // Skip push to `format_stack`.
                             continue;
                         }
+                        b"img" => {
+                            if let Some(src) = attribute(b"src") {
+                                let dimension = |name| {
+                                    attribute(name).and_then(|v: WString| v.trim().parse().ok())
+                                };
+                                let align = match attribute(b"align") {
+                                    Some(align)
+                                        if align.eq_ignore_case(WStr::from_units(b"right")) =>
+                                    {
+                                        ImageAlign::Right
+                                    }
+                                    _ => ImageAlign::Left,
+                                };
+                                images.push(HtmlImage {
+                                    position: text.len(),
+                                    src,
+                                    id: attribute(b"id"),
+                                    width: dimension(b"width"),
+                                    height: dimension(b"height"),
+                                    content_size: None,
+                                    align,
+                                    hspace: dimension(b"hspace")
+                                        .unwrap_or(HtmlImage::DEFAULT_SPACE),
+                                    vspace: dimension(b"vspace")
+                                        .unwrap_or(HtmlImage::DEFAULT_SPACE),
+                                    check_policy_file: attribute(b"checkpolicyfile").is_some_and(
+                                        |v| v.eq_ignore_case(WStr::from_units(b"true")),
+                                    ),
+                                });
+                            }
+
+                            // Images do not add any text, skip push to `format_stack`.
+                            continue;
+                        }
                         b"p" => {
                             p_open = true;
                             if let Some(align) = attribute(b"align") {
@@ -871,7 +1081,24 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+
+                    let is_custom_block =
+                        display == Some(TextDisplay::Block) && !matches!(tag_name, b"p" | b"li");
+                    if is_custom_block
+                        && is_multiline
+                        && !text.is_empty()
+                        && text.iter().last() != Some(HTML_NEWLINE)
+                    {
+                        // Blocks start on a new line.
+                        text.push(HTML_NEWLINE);
+                        spans.push(TextSpan::with_length_and_format(
+                            1,
+                            format_stack.last().unwrap(),
+                        ));
+                    }
+
                     opened_starts.push(opened_buffer.len());
+                    opened_displays.push(display);
                     opened_buffer.extend(tag_name);
                     format_stack.push(format);
                 }
@@ -879,6 +1106,10 @@ impl FormatSpans {
                     let e = decode_to_wstr(&e.into_inner());
                     let e = process_html_entity(&e).unwrap_or(e);
                     let format = format_stack.last().unwrap().clone();
+                    if opened_displays.contains(&Some(TextDisplay::None)) {
+                        // Text inside of `display: none` elements is skipped entirely.
+                        break 'text;
+                    }
                     if swf_version <= 7 && e.trim().is_empty() {
                         // SWFs version 6,7 ignore whitespace-only text.
                         // But whitespace is preserved when there
@@ -896,20 +1127,21 @@ impl FormatSpans {
                 Ok(Event::End(e)) => {
                     let tag_name = &e.name().into_inner().to_ascii_lowercase()[..];
                     // Check for a mismatch.
-                    match opened_starts.last() {
+                    let display = match opened_starts.last() {
                         Some(start) => {
                             if tag_name != &opened_buffer[*start..] {
                                 continue;
                             } else {
                                 opened_buffer.truncate(*start);
                                 opened_starts.pop();
+                                opened_displays.pop().flatten()
                             }
                         }
                         None => continue,
-                    }
+                    };
 
                     match tag_name {
-                        b"br" | b"sbr" => {
+                        b"br" | b"sbr" | b"img" => {
                             // Skip pop from `format_stack`.
                             continue;
                         }
@@ -951,6 +1183,14 @@ impl FormatSpans {
                             let tf = format_stack.last().unwrap();
                             last_closed_font = Some(TextSpanFont::with_format(tf));
                         }
+                        _ if display == Some(TextDisplay::Block) && is_multiline => {
+                            // Blocks made by the style sheet end with a new line.
+                            text.push(HTML_NEWLINE);
+                            spans.push(TextSpan::with_length_and_format(
+                                1,
+                                format_stack.last().unwrap(),
+                            ));
+                        }
                         _ => {}
                     }
                     format_stack.pop();
@@ -969,6 +1209,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans,
             default_format,
+            images,
         };
         if condense_white && swf_version >= 8 {
             ret.condense_white_swf8();
@@ -1273,7 +1514,35 @@ impl FormatSpans {
 
     /// Change some portion of the text to have a particular set of text
     /// attributes.
+    ///
+    /// Character-level properties only apply to the range [from, to), which
+    /// may split existing spans. Paragraph-level properties (such as `align`)
+    /// apply to every paragraph that the range touches, in full.
+    ///
+    /// Spans are merged back together afterwards only if they end up with
+    /// identical formatting, so `get_text_format` reports any property that
+    /// differs within a range as `None`.
     pub fn set_text_format(&mut self, from: usize, to: usize, fmt: &TextFormat) {
+        if to <= from {
+            // This only has an effect on the null span of an empty field.
+            self.apply_text_format(from, to, fmt);
+            self.normalize();
+            return;
+        }
+
+        let (character, paragraph) = fmt.clone().split_paragraph_properties();
+        if !paragraph.is_empty() {
+            let (paragraph_from, paragraph_to) = self.paragraph_bounds(from, to);
+            self.apply_text_format(paragraph_from, paragraph_to, &paragraph);
+        }
+        if !character.is_empty() {
+            self.apply_text_format(from, to, &character);
+        }
+
+        self.normalize();
+    }
+
+    fn apply_text_format(&mut self, from: usize, to: usize, fmt: &TextFormat) {
         self.ensure_span_break_at(from);
         self.ensure_span_break_at(to);
 
@@ -1284,8 +1553,23 @@ impl FormatSpans {
                 span.set_text_format(fmt);
             }
         }
+    }
 
-        self.normalize();
+    /// Extend the non-empty range [from, to) to cover whole paragraphs,
+    /// including the newline that terminates the last one.
+    fn paragraph_bounds(&self, from: usize, to: usize) -> (usize, usize) {
+        let from = min(from, self.text.len());
+        let to = min(to, self.text.len());
+        let start = self.text[..from]
+            .rfind(swf_is_newline)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let last = to.saturating_sub(1).max(from);
+        let end = self.text[last.min(self.text.len())..]
+            .find(swf_is_newline)
+            .map(|i| last + i + 1)
+            .unwrap_or(self.text.len());
+        (start, end)
     }
 
     /// Replace the text in the range [from, to) with the contents of `with`.
@@ -1342,11 +1626,41 @@ impl FormatSpans {
             new_string.push_str(text);
         }
 
+        // Images anchored after the replaced text move along with it,
+        // while the ones anchored inside of it end up at its start.
+        let from = from.min(self.text.len());
+        let to = to.min(self.text.len());
+        for image in &mut self.images {
+            if image.position >= to {
+                image.position = image.position - (to - from) + with.len();
+            } else if image.position > from {
+                image.position = from;
+            }
+        }
+
         self.text = new_string;
 
         self.normalize();
     }
 
+    /// The images embedded with `<img>` tags, ordered by their position.
+    pub fn images(&self) -> &[HtmlImage] {
+        &self.images
+    }
+
+    /// Set the size of the content loaded for the image at `index`.
+    ///
+    /// Returns `true` if the size has changed.
+    pub fn set_image_content_size(&mut self, index: usize, width: f64, height: f64) -> bool {
+        match self.images.get_mut(index) {
+            Some(image) if image.content_size != Some((width, height)) => {
+                image.content_size = Some((width, height));
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Iterate over all text spans in the current list of format spans.
     ///
     /// The iterator returned by this function yields a tuple for each span,
@@ -1,6 +1,13 @@
 use crate::events::{GamepadButton, KeyCode, MouseButton, PlayerEvent, TextControlCode};
 use chrono::{DateTime, TimeDelta, Utc};
 use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LowLatencyInputError {
+    #[error("Low-latency input is not supported for ActionScript 3 movies, as they may rely on input events being dispatched in frame order. It must be forced to be used anyway.")]
+    FramePhaseOrdering,
+}
 
 struct ClickEventData {
     x: f64,
@@ -27,10 +34,16 @@ pub struct InputManager {
 
     /// A map from gamepad buttons to key codes.
     gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+
+    /// A map from the keys that are pressed to the keys that the movie sees instead.
+    key_remap: HashMap<KeyCode, KeyCode>,
 }
 
 impl InputManager {
-    pub fn new(gamepad_button_mapping: HashMap<GamepadButton, KeyCode>) -> Self {
+    pub fn new(
+        gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+        key_remap: HashMap<KeyCode, KeyCode>,
+    ) -> Self {
         Self {
             keys_down: HashSet::new(),
             keys_toggled: HashSet::new(),
@@ -39,6 +52,7 @@ impl InputManager {
             last_text_control: None,
             last_click: None,
             gamepad_button_mapping,
+            key_remap,
         }
     }
 
@@ -92,6 +106,20 @@ impl InputManager {
                     None
                 }
             }
+            // A remapped key has no character, as it stands for a different key.
+            // Text input is sent separately, so typing into text fields is unaffected.
+            PlayerEvent::KeyDown { key_code, .. } if self.key_remap.contains_key(&key_code) => {
+                Some(PlayerEvent::KeyDown {
+                    key_code: self.key_remap[&key_code],
+                    key_char: None,
+                })
+            }
+            PlayerEvent::KeyUp { key_code, .. } if self.key_remap.contains_key(&key_code) => {
+                Some(PlayerEvent::KeyUp {
+                    key_code: self.key_remap[&key_code],
+                    key_char: None,
+                })
+            }
             _ => Some(event),
         }
     }
@@ -18,21 +18,27 @@ extern crate downcast_rs;
 #[macro_use]
 extern crate num_derive;
 
+pub mod accessibility;
+mod adaptive_quality;
 #[macro_use]
 mod avm1;
 mod avm2;
+mod bidi;
 mod binary_data;
 pub mod bitmap;
 pub mod buffer;
 mod character;
 pub mod context;
 pub mod context_menu;
+mod custom_cursor;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
 pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
+pub mod gc_scheduler;
+pub mod hash_session;
 mod html;
 mod input;
 mod library;
@@ -40,18 +46,27 @@ pub mod limits;
 pub mod loader;
 mod local_connection;
 mod locale;
+mod multitouch;
+pub mod native_window;
 mod net_connection;
 pub mod pixel_bender;
 mod player;
+pub mod policy_file;
 mod prelude;
+pub mod printing;
+pub mod profiler;
+pub mod replay;
+pub mod rsl;
 pub mod sandbox;
 pub mod socket;
 mod streams;
 pub mod string;
 pub mod tag_utils;
+mod text_snapshot;
 pub mod timer;
 mod types;
 mod vminterface;
+mod web_view;
 mod xml;
 
 pub mod backend;
@@ -67,8 +82,9 @@ pub use context_menu::ContextMenuItem;
 pub use events::PlayerEvent;
 pub use font::DefaultFont;
 pub use indexmap;
+pub use input::LowLatencyInputError;
 pub use loader::LoadBehavior;
-pub use player::{Player, PlayerBuilder, PlayerRuntime, StaticCallstack};
+pub use player::{GotoFrameError, Player, PlayerBuilder, PlayerRuntime, StaticCallstack};
 pub use ruffle_render::backend::ViewportDimensions;
 pub use swf;
 pub use swf::Color;
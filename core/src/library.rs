@@ -154,6 +154,16 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Replaces a registered font with another version of it, such as once a `DefineFontInfo`
+    /// tag has named a font that was defined by a `DefineFont` tag.
+    pub fn replace_font(&mut self, id: CharacterId, font: Font<'gc>) {
+        if let Some(Character::Font(old_font)) = self.characters.get(&id) {
+            self.fonts.remove(*old_font);
+        }
+        self.fonts.register(font);
+        self.characters.insert(id, Character::Font(font));
+    }
+
     /// Registers an export name for a given character ID.
     /// This character will then be instantiable from AVM1.
     pub fn register_export(&mut self, id: CharacterId, export_name: AvmString<'gc>) {
@@ -311,6 +321,35 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Returns the number of bytes taken up by the decoded bitmaps that are registered with
+    /// the renderer.
+    pub fn cached_bitmap_bytes(&self) -> usize {
+        self.characters
+            .values()
+            .filter_map(|character| match character {
+                Character::Bitmap {
+                    compressed, handle, ..
+                } if handle.borrow().is_some() => Some(compressed.size()),
+                _ => None,
+            })
+            .map(|size| size.width as usize * size.height as usize * 4)
+            .sum()
+    }
+
+    /// Releases the renderer's copy of every bitmap character, returning the number of
+    /// bytes that were released.
+    ///
+    /// Bitmaps are decoded and registered again the next time they're needed.
+    pub fn evict_bitmap_handles(&self) -> usize {
+        let bytes = self.cached_bitmap_bytes();
+        for character in self.characters.values() {
+            if let Character::Bitmap { handle, .. } = character {
+                *handle.borrow_mut() = None;
+            }
+        }
+        bytes
+    }
+
     pub fn set_jpeg_tables(&mut self, data: &[u8]) {
         if self.jpeg_tables.is_some() {
             // SWF spec says there should only be one JPEGTables tag.
@@ -372,9 +411,12 @@ impl ruffle_render::bitmap::BitmapSource for MovieLibrarySource<'_, '_> {
         else {
             return None;
         };
+        let device_id = backend.device_id();
         let mut handle = handle.borrow_mut();
-        if let Some(handle) = &*handle {
-            return Some(handle.clone());
+        if let Some((registered_on, handle)) = &*handle {
+            if *registered_on == device_id {
+                return Some(handle.clone());
+            }
         }
         let decoded = match compressed.decode() {
             Ok(decoded) => decoded,
@@ -390,8 +432,8 @@ impl ruffle_render::bitmap::BitmapSource for MovieLibrarySource<'_, '_> {
                 return None;
             }
         };
-        // FIXME - do we ever want to release this handle, to avoid taking up GPU memory?
-        *handle = Some(new_handle.clone());
+        // This is released by `MovieLibrary::evict_bitmap_handles` when memory runs low.
+        *handle = Some((device_id, new_handle.clone()));
         Some(new_handle)
     }
 }
@@ -468,7 +510,28 @@ impl<'gc> Library<'gc> {
         self.movie_libraries.keys().collect()
     }
 
+    /// Returns the number of bytes taken up by the decoded bitmaps of every movie that are
+    /// registered with the renderer.
+    pub fn cached_bitmap_bytes(&self) -> usize {
+        self.movie_libraries
+            .values()
+            .map(|library| library.cached_bitmap_bytes())
+            .sum()
+    }
+
+    /// Releases the renderer's copy of the bitmaps of every movie, returning the number
+    /// of bytes that were released.
+    pub fn evict_bitmap_handles(&self) -> usize {
+        self.movie_libraries
+            .values()
+            .map(|library| library.evict_bitmap_handles())
+            .sum()
+    }
+
     /// Returns the default Font implementations behind the built in names (ie `_sans`)
+    ///
+    /// The fonts are returned in priority order, and together they form the chain
+    /// that characters missing from the first font fall back on.
     pub fn default_font(
         &mut self,
         name: DefaultFont,
@@ -484,26 +547,24 @@ impl<'gc> Library<'gc> {
             return cache.clone();
         }
 
-        let mut result = vec![];
+        let mut result: Vec<Font<'gc>> = vec![];
         // First try to find any exactly matching fonts.
         for name in self.default_font_names.entry(name).or_default().clone() {
             if let Some(font) = self
                 .get_or_load_exact_device_font(&name, is_bold, is_italic, ui, renderer, gc_context)
             {
                 result.push(font);
-                break; // TODO: Return multiple fonts when it's needed.
             }
         }
 
-        // Nothing found, try a compatible font.
-        if result.is_empty() {
-            for name in self.default_font_names.entry(name).or_default().clone() {
-                if let Some(font) =
-                    self.device_fonts
-                        .find(&name, FontType::Device, is_bold, is_italic)
-                {
+        // Then follow up with compatible fonts, which will have their style synthesized.
+        for name in self.default_font_names.entry(name).or_default().clone() {
+            if let Some(font) = self
+                .device_fonts
+                .find(&name, FontType::Device, is_bold, is_italic)
+            {
+                if !result.iter().any(|other| Font::ptr_eq(*other, font)) {
                     result.push(font);
-                    break; // TODO: Return multiple fonts when it's needed.
                 }
             }
         }
@@ -600,6 +661,21 @@ impl<'gc> Library<'gc> {
                 info!("Loaded new device font \"{name}\" (bold: {is_bold}, italic: {is_italic}) from swf tag");
                 self.device_fonts.register(font);
             }
+            FontDefinition::SwfFont1Tag(tag, font_info, encoding) => {
+                let font = Font::from_font1_tag(
+                    gc_context,
+                    renderer,
+                    tag,
+                    Some(&font_info),
+                    encoding,
+                    FontType::Device,
+                );
+                let name = font.descriptor().name().to_owned();
+                let is_bold = font.descriptor().bold();
+                let is_italic = font.descriptor().italic();
+                info!("Loaded new device font \"{name}\" (bold: {is_bold}, italic: {is_italic}) from swf tag");
+                self.device_fonts.register(font);
+            }
             FontDefinition::FontFile {
                 name,
                 is_bold,
@@ -684,6 +760,11 @@ impl<'gc> FontMap<'gc> {
             .or_insert(font);
     }
 
+    pub fn remove(&mut self, font: Font<'gc>) {
+        self.0
+            .retain(|_, registered| !Font::ptr_eq(*registered, font));
+    }
+
     pub fn get(
         &self,
         name: &str,
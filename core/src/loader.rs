@@ -15,7 +15,7 @@ use crate::avm2::{
     Object as Avm2Object,
 };
 use crate::backend::navigator::{ErrorResponse, OwnedFuture, Request, SuccessResponse};
-use crate::backend::ui::DialogResultFuture;
+use crate::backend::ui::{DialogResultFuture, RootMovieLoadPhase};
 use crate::bitmap::bitmap_data::Color;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
@@ -211,6 +211,11 @@ pub enum Error {
     #[error("Could not fetch: {0:?}")]
     FetchError(String),
 
+    /// The URL isn't on the load allow list of the player, or no cross-domain
+    /// policy file permits the movie to load it.
+    #[error("Loading from {0} is not allowed")]
+    NotAllowed(String),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
@@ -279,6 +284,7 @@ impl<'gc> LoadManager<'gc> {
             | Loader::SaveFileDialog { self_handle, .. }
             | Loader::DownloadFileDialog { self_handle, .. }
             | Loader::UploadFile { self_handle, .. }
+            | Loader::UploadFileAvm2 { self_handle, .. }
             | Loader::StyleSheet { self_handle, .. }
             | Loader::MovieUnloader { self_handle, .. } => *self_handle = Some(handle),
         }
@@ -354,7 +360,7 @@ impl<'gc> LoadManager<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request);
 
             match Loader::wait_for_full_response(fetch).await {
                 Ok((body, url, _status, _redirected)) => {
@@ -717,6 +723,28 @@ impl<'gc> LoadManager<'gc> {
         let loader = self.get_loader_mut(handle).unwrap();
         loader.file_upload_loader(player, url, data, file_name)
     }
+
+    /// Upload a file from an AVM2 `FileReference`
+    ///
+    /// Returns a future that will be resolved when the file upload has completed
+    #[must_use]
+    pub fn upload_file_avm2(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceObject<'gc>,
+        url: String,
+        data: Vec<u8>,
+        file_name: String,
+        field_name: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::UploadFileAvm2 {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_upload_avm2_loader(player, url, data, file_name, field_name)
+    }
 }
 
 impl Default for LoadManager<'_> {
@@ -921,6 +949,16 @@ pub enum Loader<'gc> {
         target_object: Object<'gc>,
     },
 
+    /// Loader that is uploading a file from an AVM2 scope.
+    UploadFileAvm2 {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<LoaderHandle>,
+
+        /// The target AVM2 object that is being uploaded.
+        target_object: FileReferenceObject<'gc>,
+    },
+
     /// Loader that is downloading a stylesheet
     StyleSheet {
         /// The handle to refer to this loader instance.
@@ -1033,22 +1071,32 @@ impl<'gc> Loader<'gc> {
 
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await.map_err(|error| {
-                player
-                    .lock()
-                    .unwrap()
-                    .ui()
-                    .display_root_movie_download_failed_message(false);
+            let mut response = fetch.await.map_err(|error| {
+                report_root_movie_load_failure(&player, false, &error.error);
                 error.error
             })?;
             let url = response.url().into_owned();
-            let body = response.body().await.inspect_err(|_error| {
-                player
-                    .lock()
-                    .unwrap()
-                    .ui()
-                    .display_root_movie_download_failed_message(true);
-            })?;
+
+            // Read the body in chunks, so that embedders can show how far along the download is.
+            let expected_length = response.expected_length().ok().flatten();
+            let mut body = Vec::new();
+            player.lock().unwrap().ui().root_movie_load_progress(
+                RootMovieLoadPhase::Download,
+                0,
+                expected_length,
+            );
+            while let Some(chunk) = response
+                .next_chunk()
+                .await
+                .inspect_err(|error| report_root_movie_load_failure(&player, true, error))?
+            {
+                body.extend_from_slice(&chunk);
+                player.lock().unwrap().ui().root_movie_load_progress(
+                    RootMovieLoadPhase::Download,
+                    body.len() as u64,
+                    expected_length,
+                );
+            }
 
             // The spoofed root movie URL takes precedence over the actual URL.
             let swf_url = player
@@ -1063,14 +1111,8 @@ impl<'gc> Loader<'gc> {
                 .map(|u| u.to_string())
                 .unwrap_or(swf_url);
 
-            let mut movie =
-                SwfMovie::from_data(&body, spoofed_or_swf_url, None).inspect_err(|_error| {
-                    player
-                        .lock()
-                        .unwrap()
-                        .ui()
-                        .display_root_movie_download_failed_message(true);
-                })?;
+            let mut movie = SwfMovie::from_data(&body, spoofed_or_swf_url, None)
+                .inspect_err(|error| report_root_movie_load_failure(&player, true, error))?;
             on_metadata(movie.header());
             movie.append_parameters(parameters);
             player.lock().unwrap().mutate_with_update_context(|uc| {
@@ -1106,7 +1148,7 @@ impl<'gc> Loader<'gc> {
             let request_url = request.url().to_string();
             let resolved_url = player.lock().unwrap().navigator().resolve_url(&request_url);
 
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request);
 
             let mut replacing_root_movie = false;
             player.lock().unwrap().update(|uc| -> Result<(), Error> {
@@ -1231,6 +1273,7 @@ impl<'gc> Loader<'gc> {
                             status_code,
                             redirected,
                             response.url,
+                            matches!(response.error, Error::NotAllowed(_)),
                         )
                     })?;
                 }
@@ -1296,7 +1339,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch_data(request);
 
             let response = fetch.await.map_err(|e| e.error)?;
             let response_encoding = response.text_encoding();
@@ -1380,7 +1423,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch_data(request);
             let response = Self::wait_for_full_response(fetch).await;
 
             // Fire the load handler.
@@ -1480,7 +1523,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch_data(request);
             let response = Self::wait_for_full_response(fetch).await;
 
             // Fire the load handler.
@@ -1552,7 +1595,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch_data(request);
             let response = Self::wait_for_full_response(fetch).await;
 
             player.lock().unwrap().update(|uc| {
@@ -1682,6 +1725,10 @@ impl<'gc> Loader<'gc> {
 
                         set_data(Vec::new(), &mut activation, target);
 
+                        if let Error::NotAllowed(url) = &response.error {
+                            return dispatch_security_error(&mut activation, target, url);
+                        }
+
                         let (status_code, redirected) =
                             if let Error::HttpNotOk(_, status_code, redirected, _) = response.error
                             {
@@ -1751,7 +1798,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request);
             let response = Self::wait_for_full_response(fetch).await;
 
             // Fire the load handler.
@@ -1818,7 +1865,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request);
             let response = Self::wait_for_full_response(fetch).await;
 
             player.lock().unwrap().update(|uc| {
@@ -1920,7 +1967,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request);
             match fetch.await {
                 Ok(mut response) => {
                     let expected_length = response.expected_length();
@@ -2352,6 +2399,7 @@ impl<'gc> Loader<'gc> {
                             status,
                             redirected,
                             url,
+                            false,
                         )?;
                     }
                 }
@@ -2601,6 +2649,10 @@ impl<'gc> Loader<'gc> {
     ///
     /// This is an associated function because we cannot borrow both the update
     /// context and one of it's loaders.
+    /// Reports a failed movie load to the movie.
+    ///
+    /// If the load was `blocked` by the load allow list, AVM2 movies get a `securityError`
+    /// event instead of an `ioError`.
     fn movie_loader_error(
         handle: LoaderHandle,
         uc: &mut UpdateContext<'gc>,
@@ -2608,6 +2660,7 @@ impl<'gc> Loader<'gc> {
         status: u16,
         redirected: bool,
         swf_url: String,
+        blocked: bool,
     ) -> Result<(), Error> {
         //TODO: Inspect the fetch error.
         //This requires cooperation from the backend to send abstract
@@ -2626,7 +2679,7 @@ impl<'gc> Loader<'gc> {
 
         // If the SWF can't be loaded, the MovieClip enters the error state
         if let Some(mut mc) = clip.as_movie_clip() {
-            Loader::load_error_swf(&mut mc, uc, swf_url);
+            Loader::load_error_swf(&mut mc, uc, swf_url.clone());
         }
 
         match vm_data {
@@ -2645,6 +2698,10 @@ impl<'gc> Loader<'gc> {
                     );
                 }
             }
+            MovieLoaderVMData::Avm2 { loader_info, .. } if blocked => {
+                let mut activation = Avm2Activation::from_nothing(uc);
+                dispatch_security_error(&mut activation, loader_info, &swf_url)?;
+            }
             MovieLoaderVMData::Avm2 { loader_info, .. } => {
                 let mut activation = Avm2Activation::from_nothing(uc);
 
@@ -3042,7 +3099,7 @@ impl<'gc> Loader<'gc> {
             // Download the data
             let req = Request::get(url.clone());
             // Doing this in two steps to prevent holding the player lock during fetch
-            let future = player.lock().unwrap().navigator().fetch(req);
+            let future = player.lock().unwrap().fetch(req);
             let download_res = Self::wait_for_full_response(future).await;
 
             // Fire the load handler.
@@ -3240,33 +3297,12 @@ impl<'gc> Loader<'gc> {
         Box::pin(async move {
             let total_size_bytes = data.len();
 
-            //FIXME: The code below won't work if the payload contains the boundary separator
-            if file_name.contains("------------BOUNDARY")
-                || data.windows(20).any(|b| b == b"------------BOUNDARY")
-            {
+            let Some(out_data) = multipart_form_data(&file_name, "Filedata", &data) else {
                 tracing::error!(
                     "File upload data contains boundary separator, request cannot be sent"
                 );
                 return Err(Error::Cancelled);
-            }
-
-            // Format the data into multipart/form-data
-            let mut out_data = Vec::new();
-            out_data.extend_from_slice(b"------------BOUNDARY\n");
-            out_data.extend_from_slice(b"Content-Disposition: form-data; name=\"Filename\"\n\n");
-            out_data.extend_from_slice(file_name.as_bytes());
-            out_data.extend_from_slice(b"\n------------BOUNDARY\n");
-            out_data.extend_from_slice(
-                b"Content-Disposition: form-data; name=\"Filedata\"; filename=\"",
-            );
-            out_data.extend_from_slice(file_name.as_bytes());
-            out_data.extend_from_slice(b"\"\n");
-            out_data.extend_from_slice(b"Content-Type: application/octet-stream\n\n");
-            out_data.extend_from_slice(&data);
-            out_data.extend_from_slice(b"\n------------BOUNDARY\n");
-            out_data.extend_from_slice(b"Content-Disposition: form-data; name=\"Upload\"\n\n");
-            out_data.extend_from_slice(b"Submit Query");
-            out_data.extend_from_slice(b"\n------------BOUNDARY\n");
+            };
 
             // Upload the data
             let req = Request::post(
@@ -3277,7 +3313,7 @@ impl<'gc> Loader<'gc> {
                 )),
             );
             // Doing this in two steps to prevent holding the player lock during fetch
-            let future = player.lock().unwrap().navigator().fetch(req);
+            let future = player.lock().unwrap().fetch_data(req);
             let result = future.await;
 
             // Fire the load handler.
@@ -3382,4 +3418,263 @@ impl<'gc> Loader<'gc> {
             })
         })
     }
+
+    /// Loader to handle a file upload task from an AVM2 `FileReference`
+    ///
+    /// Uploads the given `data` to the provided `url`, using `field_name` as the
+    /// name of the multipart/form-data field containing the file.
+    pub fn file_upload_avm2_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        url: String,
+        data: Vec<u8>,
+        file_name: String,
+        field_name: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::UploadFileAvm2 { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileUploadLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let total_size_bytes = data.len();
+
+            let Some(out_data) = multipart_form_data(&file_name, &field_name, &data) else {
+                tracing::error!(
+                    "File upload data contains boundary separator, request cannot be sent"
+                );
+                return Err(Error::Cancelled);
+            };
+
+            let req = Request::post(
+                url,
+                Some((
+                    out_data,
+                    "multipart/form-data; boundary=------------BOUNDARY".to_string(),
+                )),
+            );
+            // Doing this in two steps to prevent holding the player lock during fetch
+            let future = player.lock().unwrap().fetch_data(req);
+            let response = Self::wait_for_full_response(future).await;
+
+            player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                let loader = uc.load_manager.get_loader(handle);
+                let target = match loader {
+                    Some(&Loader::UploadFileAvm2 { target_object, .. }) => target_object,
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotFileUploadLoader),
+                };
+                let target: Avm2Object<'_> = target.into();
+
+                let mut activation = Avm2Activation::from_nothing(uc);
+
+                let (status, redirected, body) = match response {
+                    Ok((body, _, status, redirected)) => (status, redirected, Some(body)),
+                    Err(response) => {
+                        tracing::error!(
+                            "Error during FileReference upload to {:?}: {:?}",
+                            response.url,
+                            response.error
+                        );
+
+                        match response.error {
+                            Error::NotAllowed(url) => {
+                                return dispatch_security_error(&mut activation, target, &url);
+                            }
+                            // The server was reached, so the upload itself went through
+                            // before the error response was received.
+                            Error::HttpNotOk(_, status, redirected, _) => {
+                                (status, redirected, None)
+                            }
+                            _ => {
+                                dispatch_upload_io_error(&mut activation, target)?;
+                                return Ok(());
+                            }
+                        }
+                    }
+                };
+
+                let open_evt = Avm2EventObject::bare_default_event(activation.context, "open");
+                Avm2::dispatch_event(activation.context, open_evt, target);
+
+                // FIXME - we should fire "progress" events as the data is sent, not
+                // just at the end
+                let progress_evt = activation
+                    .avm2()
+                    .classes()
+                    .progressevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "progress".into(),
+                            false.into(),
+                            false.into(),
+                            total_size_bytes.into(),
+                            total_size_bytes.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                Avm2::dispatch_event(activation.context, progress_evt, target);
+
+                let http_status_evt = activation
+                    .avm2()
+                    .classes()
+                    .httpstatusevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "httpStatus".into(),
+                            false.into(),
+                            false.into(),
+                            status.into(),
+                            redirected.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                Avm2::dispatch_event(activation.context, http_status_evt, target);
+
+                let Some(body) = body else {
+                    return dispatch_upload_io_error(&mut activation, target);
+                };
+
+                let complete_evt =
+                    Avm2EventObject::bare_default_event(activation.context, "complete");
+                Avm2::dispatch_event(activation.context, complete_evt, target);
+
+                // The server's response is only reported if it actually sent one.
+                if !body.is_empty() {
+                    let body = AvmString::new_utf8(
+                        activation.context.gc_context,
+                        String::from_utf8_lossy(&body),
+                    );
+                    let data_evt = activation
+                        .avm2()
+                        .classes()
+                        .dataevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "uploadCompleteData".into(),
+                                false.into(),
+                                false.into(),
+                                body.into(),
+                            ],
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    Avm2::dispatch_event(activation.context, data_evt, target);
+                }
+
+                Ok(())
+            })
+        })
+    }
+}
+
+/// Formats an uploaded file into a multipart/form-data body, as sent by
+/// `FileReference.upload`.
+///
+/// Returns `None` if the file name or the data contain the boundary separator,
+/// as the body could not be parsed correctly in that case.
+fn multipart_form_data(file_name: &str, field_name: &str, data: &[u8]) -> Option<Vec<u8>> {
+    //FIXME: This won't work if the payload contains the boundary separator
+    if file_name.contains("------------BOUNDARY")
+        || field_name.contains("------------BOUNDARY")
+        || data.windows(20).any(|b| b == b"------------BOUNDARY")
+    {
+        return None;
+    }
+
+    let mut out_data = Vec::new();
+    out_data.extend_from_slice(b"------------BOUNDARY\n");
+    out_data.extend_from_slice(b"Content-Disposition: form-data; name=\"Filename\"\n\n");
+    out_data.extend_from_slice(file_name.as_bytes());
+    out_data.extend_from_slice(b"\n------------BOUNDARY\n");
+    out_data.extend_from_slice(b"Content-Disposition: form-data; name=\"");
+    out_data.extend_from_slice(field_name.as_bytes());
+    out_data.extend_from_slice(b"\"; filename=\"");
+    out_data.extend_from_slice(file_name.as_bytes());
+    out_data.extend_from_slice(b"\"\n");
+    out_data.extend_from_slice(b"Content-Type: application/octet-stream\n\n");
+    out_data.extend_from_slice(data);
+    out_data.extend_from_slice(b"\n------------BOUNDARY\n");
+    out_data.extend_from_slice(b"Content-Disposition: form-data; name=\"Upload\"\n\n");
+    out_data.extend_from_slice(b"Submit Query");
+    out_data.extend_from_slice(b"\n------------BOUNDARY\n");
+    Some(out_data)
+}
+
+/// Dispatches the `ioError` event that a failed `FileReference.upload` reports.
+fn dispatch_upload_io_error<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    target: Avm2Object<'gc>,
+) -> Result<(), Error> {
+    let io_error_evt = activation
+        .avm2()
+        .classes()
+        .ioerrorevent
+        .construct(
+            activation,
+            &[
+                "ioError".into(),
+                false.into(),
+                false.into(),
+                "Error #2038: File I/O Error.".into(),
+                2038.into(),
+            ],
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+    Avm2::dispatch_event(activation.context, io_error_evt, target);
+    Ok(())
+}
+
+/// Tells the user, and the embedder, that the root movie couldn't be loaded.
+fn report_root_movie_load_failure(
+    player: &Mutex<Player>,
+    invalid_swf: bool,
+    error: &dyn fmt::Display,
+) {
+    let player = player.lock().unwrap();
+    player
+        .ui()
+        .display_root_movie_download_failed_message(invalid_swf);
+    player.ui().root_movie_load_failed(&error.to_string());
+}
+
+/// Dispatches the `securityError` event that Flash reports blocked loads with.
+fn dispatch_security_error<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    target: Avm2Object<'gc>,
+    url: &str,
+) -> Result<(), Error> {
+    let text = AvmString::new_utf8(
+        activation.context.gc_context,
+        format!(
+            "Error #2048: Security sandbox violation: {} cannot load data from {url}.",
+            activation.context.swf.url()
+        ),
+    );
+    let security_error_evt = activation
+        .avm2()
+        .classes()
+        .securityerrorevent
+        .construct(
+            activation,
+            &[
+                "securityError".into(),
+                false.into(),
+                false.into(),
+                text.into(),
+                2048.into(),
+            ],
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Avm2::dispatch_event(activation.context, security_error_evt, target);
+    Ok(())
 }
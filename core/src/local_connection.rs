@@ -2,13 +2,16 @@ use crate::avm1::globals::local_connection::LocalConnection as Avm1LocalConnecti
 use crate::avm1::Object as Avm1Object;
 use crate::avm2::object::LocalConnectionObject;
 use crate::avm2::Domain as Avm2Domain;
+use crate::backend::local_connection::{LocalConnectionBackend, LocalConnectionMessage};
 use crate::context::UpdateContext;
 use crate::string::AvmString;
-use flash_lso::types::Value as AmfValue;
+use flash_lso::packet::{Message, Packet};
+use flash_lso::types::{AMFVersion, ObjectId, Value as AmfValue};
 use fnv::FnvHashMap;
 use gc_arena::Collect;
 use ruffle_wstr::{WStr, WString};
 use std::borrow::Cow;
+use std::rc::Rc;
 
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
@@ -62,6 +65,35 @@ impl<'gc> LocalConnectionKind<'gc> {
             }
         }
     }
+
+    /// Checks whether this connection accepts calls from a movie of another domain.
+    pub fn allows_domain(&self, context: &mut UpdateContext<'gc>, domain: &str) -> bool {
+        match self {
+            LocalConnectionKind::Avm2(_domain, object) => object.allows_domain(domain),
+            LocalConnectionKind::Avm1(object) => {
+                match Avm1LocalConnectionObject::allows_domain(context, *object, domain) {
+                    Ok(allowed) => allowed,
+                    Err(e) => {
+                        tracing::error!(
+                            "Unhandled AVM1 error during LocalConnection allowDomain: {e}"
+                        );
+                        false
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A connection that is listening under some name.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct Listener<'gc> {
+    kind: LocalConnectionKind<'gc>,
+
+    /// The domain of the movie that is listening.
+    #[collect(require_static)]
+    domain: String,
 }
 
 #[derive(Collect)]
@@ -76,6 +108,17 @@ pub struct QueuedMessage<'gc> {
 pub enum QueuedMessageKind<'gc> {
     Failure,
     Message {
+        #[collect(require_static)]
+        connection_name: WString,
+        /// The domain of the sending movie.
+        #[collect(require_static)]
+        sender_domain: String,
+        method_name: AvmString<'gc>,
+        #[collect(require_static)]
+        arguments: Vec<AmfValue>,
+    },
+    /// A message to a connection of another player, through the `LocalConnectionBackend`.
+    External {
         #[collect(require_static)]
         connection_name: WString,
         method_name: AvmString<'gc>,
@@ -92,20 +135,87 @@ impl<'gc> QueuedMessageKind<'gc> {
             }
             QueuedMessageKind::Message {
                 connection_name,
+                sender_domain,
                 method_name,
                 arguments,
             } => {
-                if let Some(receiver) = context.local_connections.find_listener(&connection_name) {
-                    source.send_status(context, "status");
-                    receiver.run_method(context, method_name, arguments);
-                } else {
+                let Some(receiver) = context.local_connections.find_listener(&connection_name)
+                else {
                     source.send_status(context, "error");
+                    return;
+                };
+                // Movies from other domains need to be explicitly allowed by the receiver.
+                if receiver.domain != sender_domain
+                    && !receiver.kind.allows_domain(context, &sender_domain)
+                {
+                    source.send_status(context, "error");
+                    return;
+                }
+                source.send_status(context, "status");
+                receiver.kind.run_method(context, method_name, arguments);
+            }
+            QueuedMessageKind::External {
+                connection_name,
+                method_name,
+                arguments,
+            } => {
+                let connection_name = connection_name.to_utf8_lossy().into_owned();
+                let Some(backend) = context
+                    .local_connections
+                    .backend
+                    .as_mut()
+                    .filter(|backend| backend.is_connected(&connection_name))
+                else {
+                    source.send_status(context, "error");
+                    return;
+                };
+                match encode_message(&method_name.to_string(), arguments) {
+                    Some(data) => {
+                        backend.send(LocalConnectionMessage {
+                            connection_name,
+                            data,
+                        });
+                        source.send_status(context, "status");
+                    }
+                    None => source.send_status(context, "error"),
                 }
             }
         }
     }
 }
 
+/// Encodes a method call for another player, as an AMF0 packet with a single message.
+fn encode_message(method_name: &str, arguments: Vec<AmfValue>) -> Option<Vec<u8>> {
+    let packet = Packet {
+        version: AMFVersion::AMF0,
+        headers: vec![],
+        messages: vec![Message {
+            target_uri: method_name.to_owned(),
+            response_uri: String::new(),
+            contents: Rc::new(AmfValue::StrictArray(
+                ObjectId::INVALID,
+                arguments.into_iter().map(Rc::new).collect(),
+            )),
+        }],
+    };
+    flash_lso::packet::write::write_to_bytes(&packet, true).ok()
+}
+
+/// Decodes a method call made by another player, returning the name of the method and
+/// its arguments.
+fn decode_message(data: &[u8]) -> Option<(String, Vec<AmfValue>)> {
+    let packet = flash_lso::packet::read::parse(data).ok()?;
+    let message = packet.messages.into_iter().next()?;
+    let AmfValue::StrictArray(_, arguments) = &*message.contents else {
+        return None;
+    };
+    let arguments = arguments
+        .iter()
+        .map(|argument| (**argument).clone())
+        .collect();
+    Some((message.target_uri, arguments))
+}
+
 /// An opaque handle to an actively listening LocalConnection.
 /// Owning this handle represents ownership of a LocalConnection;
 /// However, a LocalConnection must be manually closed, you can't just Drop this handle.
@@ -114,8 +224,11 @@ pub struct LocalConnectionHandle(WString);
 
 /// Manages the collection of local connections.
 pub struct LocalConnections<'gc> {
-    connections: FnvHashMap<WString, LocalConnectionKind<'gc>>,
+    connections: FnvHashMap<WString, Listener<'gc>>,
     messages: Vec<QueuedMessage<'gc>>,
+
+    /// Reaches the connections of other players, if there are any.
+    backend: Option<Box<dyn LocalConnectionBackend>>,
 }
 
 unsafe impl Collect for LocalConnections<'_> {
@@ -130,10 +243,11 @@ unsafe impl Collect for LocalConnections<'_> {
 }
 
 impl<'gc> LocalConnections<'gc> {
-    pub fn empty() -> Self {
+    pub fn new(backend: Option<Box<dyn LocalConnectionBackend>>) -> Self {
         Self {
             connections: Default::default(),
             messages: Default::default(),
+            backend,
         }
     }
 
@@ -154,15 +268,28 @@ impl<'gc> LocalConnections<'gc> {
         };
 
         if self.connections.contains_key(&key) {
-            None
-        } else {
-            self.connections.insert(key.to_owned(), connection.into());
-            Some(LocalConnectionHandle(key.to_owned()))
+            return None;
+        }
+        if let Some(backend) = &mut self.backend {
+            if !backend.connect(&key.to_utf8_lossy()) {
+                return None;
+            }
         }
+        self.connections.insert(
+            key.to_owned(),
+            Listener {
+                kind: connection.into(),
+                domain: domain.to_owned(),
+            },
+        );
+        Some(LocalConnectionHandle(key.to_owned()))
     }
 
     pub fn close(&mut self, handle: LocalConnectionHandle) {
         self.connections.remove(&handle.0);
+        if let Some(backend) = &mut self.backend {
+            backend.close(&handle.0.to_utf8_lossy());
+        }
     }
 
     pub fn send<C: Into<LocalConnectionKind<'gc>>>(
@@ -190,6 +317,17 @@ impl<'gc> LocalConnections<'gc> {
 
         let kind = if self.find_listener(&connection_name).is_some() {
             QueuedMessageKind::Message {
+                connection_name,
+                sender_domain: domain.to_owned(),
+                method_name,
+                arguments,
+            }
+        } else if self
+            .backend
+            .as_ref()
+            .is_some_and(|backend| backend.is_connected(&connection_name.to_utf8_lossy()))
+        {
+            QueuedMessageKind::External {
                 connection_name,
                 method_name,
                 arguments,
@@ -203,11 +341,33 @@ impl<'gc> LocalConnections<'gc> {
         });
     }
 
-    fn find_listener(&self, name: &WStr) -> Option<LocalConnectionKind<'gc>> {
+    fn find_listener(&self, name: &WStr) -> Option<Listener<'gc>> {
         self.connections.get(name).cloned()
     }
 
     pub fn update_connections(context: &mut UpdateContext<'gc>) {
+        let external_messages = context
+            .local_connections
+            .backend
+            .as_mut()
+            .map(|backend| backend.receive())
+            .unwrap_or_default();
+        for message in external_messages {
+            let connection_name = WString::from_utf8(&message.connection_name);
+            let Some(receiver) = context.local_connections.find_listener(&connection_name) else {
+                continue;
+            };
+            let Some((method_name, arguments)) = decode_message(&message.data) else {
+                tracing::warn!(
+                    "LocalConnection: Invalid message received on {}",
+                    message.connection_name
+                );
+                continue;
+            };
+            let method_name = AvmString::new_utf8(context.gc_context, method_name);
+            receiver.kind.run_method(context, method_name, arguments);
+        }
+
         if context.local_connections.messages.is_empty() {
             return;
         }
@@ -1,3 +1,14 @@
+//! Locale-dependent behaviour: the current time and timezone, and the conventions of the
+//! locales that `flash.globalization` supports.
+
+mod collation;
+mod data;
+mod format;
+
+pub use collation::{compare, CollationOptions};
+pub use data::{find_locale, locale_names, Locale, LocaleMatch};
+pub use format::{format_date, month_names, style_pattern, weekday_names, NumberFormat};
+
 use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
 
 // For tests, we emulate being in Nepal with a local time of 2001-02-03 at 04:05:06.
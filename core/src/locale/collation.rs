@@ -0,0 +1,302 @@
+//! Comparing strings the way a reader would sort them, for `flash.globalization.Collator`.
+//!
+//! Strings are compared in up to three passes. The first only looks at the base letters, so
+//! that "é" sorts next to "e" instead of after "z". The second pass breaks ties by accents and
+//! the third by case, so that strings are only equal when they are identical.
+
+use std::cmp::Ordering;
+
+/// What a comparison should ignore.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CollationOptions {
+    pub ignore_case: bool,
+    pub ignore_character_width: bool,
+    pub ignore_diacritics: bool,
+    pub ignore_kana_type: bool,
+    pub ignore_symbols: bool,
+
+    /// Compare runs of digits by their numeric value, so that "2" sorts before "10".
+    pub numeric_comparison: bool,
+}
+
+/// A character, split into the parts that are compared in each pass.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct CollationElement {
+    base: char,
+    diacritic: u8,
+    upper_case: bool,
+}
+
+pub fn compare(a: &str, b: &str, options: CollationOptions) -> Ordering {
+    let a = elements(a, options);
+    let b = elements(b, options);
+
+    let primary = if options.numeric_comparison {
+        compare_numeric(&a, &b)
+    } else {
+        a.iter().map(|e| e.base).cmp(b.iter().map(|e| e.base))
+    };
+    if primary != Ordering::Equal {
+        return primary;
+    }
+
+    if !options.ignore_diacritics {
+        let secondary = a
+            .iter()
+            .map(|e| e.diacritic)
+            .cmp(b.iter().map(|e| e.diacritic));
+        if secondary != Ordering::Equal {
+            return secondary;
+        }
+    }
+
+    if !options.ignore_case {
+        // Lower case sorts first.
+        let tertiary = a
+            .iter()
+            .map(|e| e.upper_case)
+            .cmp(b.iter().map(|e| e.upper_case));
+        if tertiary != Ordering::Equal {
+            return tertiary;
+        }
+    }
+
+    Ordering::Equal
+}
+
+fn elements(s: &str, options: CollationOptions) -> Vec<CollationElement> {
+    s.chars()
+        .filter(|c| !options.ignore_symbols || c.is_alphanumeric())
+        .map(|c| {
+            let mut c = c;
+            if options.ignore_character_width {
+                c = narrow(c);
+            }
+            if options.ignore_kana_type {
+                c = hiragana(c);
+            }
+            let (base, diacritic) = decompose(c);
+            let upper_case = base.is_uppercase();
+            let base = base.to_lowercase().next().unwrap_or(base);
+            CollationElement {
+                base,
+                diacritic,
+                upper_case,
+            }
+        })
+        .collect()
+}
+
+/// Compares the base letters, treating every run of ASCII digits as one number.
+fn compare_numeric(a: &[CollationElement], b: &[CollationElement]) -> Ordering {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i].base.is_ascii_digit() && b[j].base.is_ascii_digit() {
+            let a_end = a[i..]
+                .iter()
+                .position(|e| !e.base.is_ascii_digit())
+                .map_or(a.len(), |n| i + n);
+            let b_end = b[j..]
+                .iter()
+                .position(|e| !e.base.is_ascii_digit())
+                .map_or(b.len(), |n| j + n);
+            let a_digits = trim_leading_zeros(&a[i..a_end]);
+            let b_digits = trim_leading_zeros(&b[j..b_end]);
+            let ordering = a_digits.len().cmp(&b_digits.len()).then_with(|| {
+                a_digits
+                    .iter()
+                    .map(|e| e.base)
+                    .cmp(b_digits.iter().map(|e| e.base))
+            });
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            i = a_end;
+            j = b_end;
+        } else {
+            let ordering = a[i].base.cmp(&b[j].base);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+    (a.len() - i).cmp(&(b.len() - j))
+}
+
+fn trim_leading_zeros(digits: &[CollationElement]) -> &[CollationElement] {
+    let zeros = digits.iter().take_while(|e| e.base == '0').count();
+    &digits[zeros..]
+}
+
+/// Maps full-width ASCII forms to their ordinary counterparts.
+fn narrow(c: char) -> char {
+    match c {
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(c as u32 - 0xfee0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        _ => c,
+    }
+}
+
+/// Maps katakana to the equivalent hiragana.
+fn hiragana(c: char) -> char {
+    match c {
+        '\u{30a1}'..='\u{30f6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Splits a character into its base letter and an identifier for its diacritic, with 0
+/// meaning none.
+fn decompose(c: char) -> (char, u8) {
+    const GRAVE: u8 = 1;
+    const ACUTE: u8 = 2;
+    const CIRCUMFLEX: u8 = 3;
+    const TILDE: u8 = 4;
+    const DIAERESIS: u8 = 5;
+    const RING: u8 = 6;
+    const CEDILLA: u8 = 7;
+    const STROKE: u8 = 8;
+    const MACRON: u8 = 9;
+    const BREVE: u8 = 10;
+    const OGONEK: u8 = 11;
+    const DOT: u8 = 12;
+    const CARON: u8 = 13;
+    const DOUBLE_ACUTE: u8 = 14;
+
+    match c {
+        'À' => ('A', GRAVE),
+        'Á' => ('A', ACUTE),
+        'Â' => ('A', CIRCUMFLEX),
+        'Ã' => ('A', TILDE),
+        'Ä' => ('A', DIAERESIS),
+        'Å' => ('A', RING),
+        'Ç' => ('C', CEDILLA),
+        'È' => ('E', GRAVE),
+        'É' => ('E', ACUTE),
+        'Ê' => ('E', CIRCUMFLEX),
+        'Ë' => ('E', DIAERESIS),
+        'Ì' => ('I', GRAVE),
+        'Í' => ('I', ACUTE),
+        'Î' => ('I', CIRCUMFLEX),
+        'Ï' => ('I', DIAERESIS),
+        'Ñ' => ('N', TILDE),
+        'Ò' => ('O', GRAVE),
+        'Ó' => ('O', ACUTE),
+        'Ô' => ('O', CIRCUMFLEX),
+        'Õ' => ('O', TILDE),
+        'Ö' => ('O', DIAERESIS),
+        'Ø' => ('O', STROKE),
+        'Ù' => ('U', GRAVE),
+        'Ú' => ('U', ACUTE),
+        'Û' => ('U', CIRCUMFLEX),
+        'Ü' => ('U', DIAERESIS),
+        'Ý' => ('Y', ACUTE),
+        'à' => ('a', GRAVE),
+        'á' => ('a', ACUTE),
+        'â' => ('a', CIRCUMFLEX),
+        'ã' => ('a', TILDE),
+        'ä' => ('a', DIAERESIS),
+        'å' => ('a', RING),
+        'ç' => ('c', CEDILLA),
+        'è' => ('e', GRAVE),
+        'é' => ('e', ACUTE),
+        'ê' => ('e', CIRCUMFLEX),
+        'ë' => ('e', DIAERESIS),
+        'ì' => ('i', GRAVE),
+        'í' => ('i', ACUTE),
+        'î' => ('i', CIRCUMFLEX),
+        'ï' => ('i', DIAERESIS),
+        'ñ' => ('n', TILDE),
+        'ò' => ('o', GRAVE),
+        'ó' => ('o', ACUTE),
+        'ô' => ('o', CIRCUMFLEX),
+        'õ' => ('o', TILDE),
+        'ö' => ('o', DIAERESIS),
+        'ø' => ('o', STROKE),
+        'ù' => ('u', GRAVE),
+        'ú' => ('u', ACUTE),
+        'û' => ('u', CIRCUMFLEX),
+        'ü' => ('u', DIAERESIS),
+        'ý' => ('y', ACUTE),
+        'ÿ' => ('y', DIAERESIS),
+        'Ā' | 'Ē' | 'Ī' | 'Ō' | 'Ū' | 'ā' | 'ē' | 'ī' | 'ō' | 'ū' => {
+            (latin_extended_base(c), MACRON)
+        }
+        'Ă' | 'Ğ' | 'Ŭ' | 'ă' | 'ğ' | 'ŭ' => (latin_extended_base(c), BREVE),
+        'Ą' | 'Ę' | 'Į' | 'Ų' | 'ą' | 'ę' | 'į' | 'ų' => (latin_extended_base(c), OGONEK),
+        'Ć' | 'Ń' | 'Ś' | 'Ź' | 'ć' | 'ń' | 'ś' | 'ź' => (latin_extended_base(c), ACUTE),
+        'Ċ' | 'Ė' | 'Ġ' | 'İ' | 'Ż' | 'ċ' | 'ė' | 'ġ' | 'ż' => {
+            (latin_extended_base(c), DOT)
+        }
+        'Č' | 'Ď' | 'Ě' | 'Ň' | 'Ř' | 'Š' | 'Ť' | 'Ž' | 'č' | 'ď' | 'ě' | 'ň' | 'ř' | 'š' | 'ť'
+        | 'ž' => (latin_extended_base(c), CARON),
+        'Ş' | 'Ţ' | 'ş' | 'ţ' => (latin_extended_base(c), CEDILLA),
+        'Ů' | 'ů' => (latin_extended_base(c), RING),
+        'Ő' | 'Ű' | 'ő' | 'ű' => (latin_extended_base(c), DOUBLE_ACUTE),
+        'Đ' | 'Ł' | 'đ' | 'ł' => (latin_extended_base(c), STROKE),
+        'ё' => ('е', DIAERESIS),
+        'Ё' => ('Е', DIAERESIS),
+        _ => (c, 0),
+    }
+}
+
+fn latin_extended_base(c: char) -> char {
+    let base = match c.to_lowercase().next().unwrap_or(c) {
+        'ā' | 'ă' | 'ą' => 'a',
+        'ć' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'ē' | 'ę' | 'ė' | 'ě' => 'e',
+        'ğ' | 'ġ' => 'g',
+        'ī' | 'į' | 'i' => 'i',
+        'ł' => 'l',
+        'ń' | 'ň' => 'n',
+        'ō' | 'ő' => 'o',
+        'ř' => 'r',
+        'ś' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' => 't',
+        'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ź' | 'ż' | 'ž' => 'z',
+        other => other,
+    };
+    if c.is_uppercase() {
+        base.to_ascii_uppercase()
+    } else {
+        base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_by_base_letters_first() {
+        let sorting = CollationOptions::default();
+        assert_eq!(compare("résumé", "resume", sorting), Ordering::Greater);
+        assert_eq!(compare("résumé", "resumes", sorting), Ordering::Less);
+        assert_eq!(compare("apple", "Apple", sorting), Ordering::Less);
+        assert_eq!(compare("Apple", "banana", sorting), Ordering::Less);
+        assert_eq!(compare("file10", "file9", sorting), Ordering::Less);
+
+        let matching = CollationOptions {
+            ignore_case: true,
+            ignore_character_width: true,
+            ignore_diacritics: true,
+            ignore_kana_type: true,
+            ..Default::default()
+        };
+        assert_eq!(compare("RÉSUMÉ", "resume", matching), Ordering::Equal);
+        assert_eq!(compare("ＡＢＣ", "abc", matching), Ordering::Equal);
+        assert_eq!(compare("カタカナ", "かたかな", matching), Ordering::Equal);
+
+        let numeric = CollationOptions {
+            numeric_comparison: true,
+            ignore_symbols: true,
+            ..Default::default()
+        };
+        assert_eq!(compare("file10", "file9", numeric), Ordering::Greater);
+        assert_eq!(compare("file 010", "file-10", numeric), Ordering::Equal);
+    }
+}
@@ -0,0 +1,567 @@
+//! The conventions of the locales that `flash.globalization` knows about.
+
+/// How numbers, currencies and dates are written in a locale.
+pub struct Locale {
+    /// The locale ID, as a language tag.
+    pub name: &'static str,
+
+    pub decimal_separator: &'static str,
+    pub grouping_separator: &'static str,
+    pub grouping_pattern: &'static str,
+    pub negative_number_format: u32,
+
+    pub currency_symbol: &'static str,
+    pub currency_iso_code: &'static str,
+    pub currency_fractional_digits: u32,
+    pub positive_currency_format: u32,
+    pub negative_currency_format: u32,
+
+    /// The month names used on their own, as in a calendar heading.
+    pub month_names: [&'static str; 12],
+
+    /// The month names used within a date, if they differ from `month_names`.
+    pub format_month_names: Option<[&'static str; 12]>,
+
+    pub short_month_names: [&'static str; 12],
+
+    /// The weekday names, starting with Sunday.
+    pub weekday_names: [&'static str; 7],
+
+    pub short_weekday_names: [&'static str; 7],
+
+    pub am: &'static str,
+    pub pm: &'static str,
+
+    /// The first day of the week, with 0 being Sunday.
+    pub first_weekday: u32,
+
+    /// The date patterns for the long, medium and short styles.
+    pub date_patterns: [&'static str; 3],
+
+    /// The time patterns for the long, medium and short styles.
+    pub time_patterns: [&'static str; 3],
+}
+
+/// How well a requested locale ID could be satisfied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocaleMatch {
+    /// The requested locale is supported.
+    Exact,
+
+    /// Another locale for the same language is used instead.
+    Fallback,
+
+    /// The language isn't supported at all, so the default locale is used.
+    Default,
+}
+
+impl LocaleMatch {
+    /// The `flash.globalization.LastOperationStatus` that Flash reports for this match.
+    pub fn status(self) -> &'static str {
+        match self {
+            LocaleMatch::Exact => "noError",
+            LocaleMatch::Fallback => "usingFallbackWarning",
+            LocaleMatch::Default => "usingDefaultWarning",
+        }
+    }
+}
+
+/// Finds the locale to use for `requested`.
+///
+/// `system` is the language of the user, which is used for `LocaleID.DEFAULT`.
+pub fn find_locale(requested: &str, system: &str) -> (&'static Locale, LocaleMatch) {
+    let requested = if requested.is_empty() || requested.eq_ignore_ascii_case("i-default") {
+        system
+    } else {
+        requested
+    };
+    let requested = requested.replace('_', "-");
+    // Keywords (as in "de-DE@collation=phonebook") don't affect formatting.
+    let requested = requested.split('@').next().unwrap_or_default();
+
+    if let Some(locale) = LOCALES
+        .iter()
+        .find(|locale| locale.name.eq_ignore_ascii_case(requested))
+    {
+        return (locale, LocaleMatch::Exact);
+    }
+
+    let language = requested.split('-').next().unwrap_or_default();
+    if let Some(locale) = LOCALES.iter().find(|locale| {
+        let locale_language = locale.name.split('-').next().unwrap_or_default();
+        locale_language.eq_ignore_ascii_case(language)
+    }) {
+        return (locale, LocaleMatch::Fallback);
+    }
+
+    (&LOCALES[0], LocaleMatch::Default)
+}
+
+/// The IDs of all supported locales.
+pub fn locale_names() -> impl Iterator<Item = &'static str> {
+    LOCALES.iter().map(|locale| locale.name)
+}
+
+const NUMERIC_MONTH_NAMES: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+
+/// The supported locales. The first one is the default.
+static LOCALES: &[Locale] = &[
+    Locale {
+        name: "en-US",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "$",
+        currency_iso_code: "USD",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        format_month_names: None,
+        short_month_names: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekday_names: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        short_weekday_names: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        am: "AM",
+        pm: "PM",
+        first_weekday: 0,
+        date_patterns: ["EEEE, MMMM d, yyyy", "MMM d, yyyy", "M/d/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+    },
+    Locale {
+        name: "en-GB",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "£",
+        currency_iso_code: "GBP",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        format_month_names: None,
+        short_month_names: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekday_names: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        short_weekday_names: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        am: "am",
+        pm: "pm",
+        first_weekday: 1,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    Locale {
+        name: "de-DE",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "€",
+        currency_iso_code: "EUR",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        format_month_names: None,
+        short_month_names: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekday_names: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        short_weekday_names: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+        am: "AM",
+        pm: "PM",
+        first_weekday: 1,
+        date_patterns: ["EEEE, d. MMMM yyyy", "dd.MM.yyyy", "dd.MM.yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    Locale {
+        name: "fr-FR",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "€",
+        currency_iso_code: "EUR",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        format_month_names: None,
+        short_month_names: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekday_names: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        short_weekday_names: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+        am: "AM",
+        pm: "PM",
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    Locale {
+        name: "es-ES",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "€",
+        currency_iso_code: "EUR",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        format_month_names: None,
+        short_month_names: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        weekday_names: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        short_weekday_names: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+        am: "a. m.",
+        pm: "p. m.",
+        first_weekday: 1,
+        date_patterns: ["EEEE, d 'de' MMMM 'de' yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    Locale {
+        name: "it-IT",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "€",
+        currency_iso_code: "EUR",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        format_month_names: None,
+        short_month_names: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekday_names: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        short_weekday_names: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+        am: "AM",
+        pm: "PM",
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    Locale {
+        name: "pt-BR",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "R$",
+        currency_iso_code: "BRL",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        month_names: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        format_month_names: None,
+        short_month_names: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekday_names: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        short_weekday_names: ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"],
+        am: "AM",
+        pm: "PM",
+        first_weekday: 0,
+        date_patterns: [
+            "EEEE, d 'de' MMMM 'de' yyyy",
+            "d 'de' MMM 'de' yyyy",
+            "dd/MM/yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    Locale {
+        name: "ru-RU",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "₽",
+        currency_iso_code: "RUB",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "январь",
+            "февраль",
+            "март",
+            "апрель",
+            "май",
+            "июнь",
+            "июль",
+            "август",
+            "сентябрь",
+            "октябрь",
+            "ноябрь",
+            "декабрь",
+        ],
+        format_month_names: Some([
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ]),
+        short_month_names: [
+            "янв.",
+            "февр.",
+            "март",
+            "апр.",
+            "май",
+            "июнь",
+            "июль",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        weekday_names: [
+            "воскресенье",
+            "понедельник",
+            "вторник",
+            "среда",
+            "четверг",
+            "пятница",
+            "суббота",
+        ],
+        short_weekday_names: ["вс", "пн", "вт", "ср", "чт", "пт", "сб"],
+        am: "AM",
+        pm: "PM",
+        first_weekday: 1,
+        date_patterns: ["EEEE, d MMMM yyyy 'г'.", "d MMM yyyy 'г'.", "dd.MM.yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    Locale {
+        name: "ja-JP",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "¥",
+        currency_iso_code: "JPY",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: NUMERIC_MONTH_NAMES,
+        format_month_names: None,
+        short_month_names: NUMERIC_MONTH_NAMES,
+        weekday_names: [
+            "日曜日",
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+        ],
+        short_weekday_names: ["日", "月", "火", "水", "木", "金", "土"],
+        am: "午前",
+        pm: "午後",
+        first_weekday: 0,
+        date_patterns: ["yyyy'年'M'月'd'日' EEEE", "yyyy/MM/dd", "yyyy/MM/dd"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    Locale {
+        name: "zh-CN",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_symbol: "¥",
+        currency_iso_code: "CNY",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        format_month_names: None,
+        short_month_names: NUMERIC_MONTH_NAMES,
+        weekday_names: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        short_weekday_names: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+        am: "上午",
+        pm: "下午",
+        first_weekday: 1,
+        date_patterns: ["yyyy'年'M'月'd'日' EEEE", "yyyy'年'M'月'd'日'", "yyyy/M/d"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+];
@@ -0,0 +1,549 @@
+//! Writing and reading numbers and dates the way a locale does, for `flash.globalization`.
+
+use super::data::Locale;
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+
+/// The settings of a `NumberFormatter` or `CurrencyFormatter`.
+pub struct NumberFormat<'a> {
+    pub fractional_digits: u32,
+    pub trailing_zeros: bool,
+    pub leading_zero: bool,
+    pub use_grouping: bool,
+
+    /// The sizes of the digit groups, starting from the decimal separator, such as "3;*".
+    pub grouping_pattern: &'a str,
+
+    pub grouping_separator: &'a str,
+    pub decimal_separator: &'a str,
+    pub negative_symbol: &'a str,
+    pub negative_number_format: u32,
+
+    /// The code point of the zero digit to use, as in `NationalDigitsType`.
+    pub digits_type: u32,
+}
+
+/// The layouts of negative numbers, where `-` is the negative symbol and `n` the number.
+const NEGATIVE_NUMBER_FORMATS: [&str; 5] = ["(n)", "-n", "- n", "n-", "n -"];
+
+/// The layouts of positive currency amounts, where `¤` is the currency symbol.
+const POSITIVE_CURRENCY_FORMATS: [&str; 4] = ["¤n", "n¤", "¤ n", "n ¤"];
+
+/// The layouts of negative currency amounts.
+const NEGATIVE_CURRENCY_FORMATS: [&str; 16] = [
+    "(¤n)", "-¤n", "¤-n", "¤n-", "(n¤)", "-n¤", "n-¤", "n¤-", "-n ¤", "-¤ n", "n ¤-", "¤ n-",
+    "¤ -n", "n- ¤", "(¤ n)", "(n ¤)",
+];
+
+impl NumberFormat<'_> {
+    pub fn format_number(&self, value: f64) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+
+        let number = self.format_magnitude(value.abs());
+        if value < 0.0 {
+            let layout = NEGATIVE_NUMBER_FORMATS
+                .get(self.negative_number_format as usize)
+                .unwrap_or(&NEGATIVE_NUMBER_FORMATS[1]);
+            self.apply_layout(layout, &number, "")
+        } else {
+            number
+        }
+    }
+
+    /// Formats a currency amount.
+    ///
+    /// The formats are indices into the layouts Flash supports for `positiveCurrencyFormat`
+    /// and `negativeCurrencyFormat`.
+    pub fn format_currency(
+        &self,
+        value: f64,
+        symbol: &str,
+        positive_format: u32,
+        negative_format: u32,
+    ) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+
+        let number = self.format_magnitude(value.abs());
+        let layout = if value < 0.0 {
+            NEGATIVE_CURRENCY_FORMATS
+                .get(negative_format as usize)
+                .unwrap_or(&NEGATIVE_CURRENCY_FORMATS[1])
+        } else {
+            POSITIVE_CURRENCY_FORMATS
+                .get(positive_format as usize)
+                .unwrap_or(&POSITIVE_CURRENCY_FORMATS[0])
+        };
+        self.apply_layout(layout, &number, symbol)
+    }
+
+    fn apply_layout(&self, layout: &str, number: &str, symbol: &str) -> String {
+        let mut out = String::new();
+        for c in layout.chars() {
+            match c {
+                'n' => out.push_str(number),
+                '-' => out.push_str(self.negative_symbol),
+                '¤' => out.push_str(symbol),
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn format_magnitude(&self, value: f64) -> String {
+        if value.is_infinite() {
+            return "Infinity".to_string();
+        }
+
+        let rounded = format!("{:.*}", self.fractional_digits.min(20) as usize, value);
+        let (integer, fraction) = rounded.split_once('.').unwrap_or((rounded.as_str(), ""));
+        let fraction = if self.trailing_zeros {
+            fraction
+        } else {
+            fraction.trim_end_matches('0')
+        };
+        let integer = if integer == "0" && !self.leading_zero && !fraction.is_empty() {
+            ""
+        } else {
+            integer
+        };
+
+        let mut out = if self.use_grouping {
+            self.group(integer)
+        } else {
+            self.localize_digits(integer)
+        };
+        if !fraction.is_empty() {
+            out.push_str(self.decimal_separator);
+            out.push_str(&self.localize_digits(fraction));
+        }
+        out
+    }
+
+    /// Inserts grouping separators into a run of digits, as described by `grouping_pattern`.
+    fn group(&self, digits: &str) -> String {
+        let mut sizes = vec![];
+        let mut repeat = false;
+        for part in self.grouping_pattern.split(';') {
+            match part.trim() {
+                "*" => repeat = true,
+                size => match size.parse::<usize>() {
+                    Ok(size) if size > 0 => sizes.push(size),
+                    _ => break,
+                },
+            }
+        }
+
+        let mut groups = vec![];
+        let mut rest = digits;
+        for i in 0.. {
+            let size = match sizes.get(i) {
+                Some(size) => *size,
+                None if repeat && !sizes.is_empty() => sizes[sizes.len() - 1],
+                None => break,
+            };
+            if rest.len() <= size {
+                break;
+            }
+            let (head, tail) = rest.split_at(rest.len() - size);
+            groups.push(tail);
+            rest = head;
+        }
+        groups.push(rest);
+
+        groups
+            .iter()
+            .rev()
+            .map(|group| self.localize_digits(group))
+            .collect::<Vec<_>>()
+            .join(self.grouping_separator)
+    }
+
+    fn localize_digits(&self, digits: &str) -> String {
+        if self.digits_type == '0' as u32 {
+            return digits.to_string();
+        }
+        digits
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) => char::from_u32(self.digits_type + digit).unwrap_or(c),
+                None => c,
+            })
+            .collect()
+    }
+
+    /// Finds a number at the start of `s`, after any whitespace.
+    ///
+    /// Returns the value, and the character indices where the number starts and ends.
+    pub fn parse(&self, s: &str) -> Option<(f64, usize, usize)> {
+        let chars: Vec<char> = s.chars().collect();
+        let mut start = 0;
+        while start < chars.len() && chars[start].is_whitespace() {
+            start += 1;
+        }
+
+        let mut i = start;
+        let mut negative = false;
+        let mut parenthesized = false;
+        if chars.get(i) == Some(&'(') {
+            parenthesized = true;
+            i += 1;
+        } else if let Some(after) = self.match_at(&chars, i, self.negative_symbol) {
+            negative = true;
+            i = after;
+            while chars.get(i) == Some(&' ') {
+                i += 1;
+            }
+        }
+
+        let mut number = String::new();
+        let mut seen_decimal = false;
+        let mut last_digit_end = None;
+        while i < chars.len() {
+            if let Some(digit) = self.digit_value(chars[i]) {
+                number.push(char::from_digit(digit, 10).unwrap_or('0'));
+                i += 1;
+                last_digit_end = Some(i);
+            } else if let Some(after) = self.match_at(&chars, i, self.decimal_separator) {
+                if seen_decimal {
+                    break;
+                }
+                seen_decimal = true;
+                number.push('.');
+                i = after;
+            } else if let Some(after) = self.match_at(&chars, i, self.grouping_separator) {
+                // Grouping separators are only allowed between digits of the integer part.
+                if seen_decimal || last_digit_end != Some(i) {
+                    break;
+                }
+                match chars.get(after).and_then(|c| self.digit_value(*c)) {
+                    Some(_) => i = after,
+                    None => break,
+                }
+            } else {
+                break;
+            }
+        }
+        let mut end = last_digit_end?;
+        if seen_decimal && number.ends_with('.') {
+            number.pop();
+        }
+
+        if parenthesized {
+            if chars.get(end) != Some(&')') {
+                return None;
+            }
+            negative = true;
+            end += 1;
+        } else if !negative {
+            let mut after_space = end;
+            while chars.get(after_space) == Some(&' ') {
+                after_space += 1;
+            }
+            if let Some(after) = self.match_at(&chars, after_space, self.negative_symbol) {
+                negative = true;
+                end = after;
+            }
+        }
+
+        let value: f64 = number.parse().ok()?;
+        Some((if negative { -value } else { value }, start, end))
+    }
+
+    /// Parses `s` as a number, allowing only whitespace around it.
+    pub fn parse_number(&self, s: &str) -> Option<f64> {
+        let (value, _, end) = self.parse(s)?;
+        if s.chars().skip(end).all(char::is_whitespace) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Parses a currency amount, which may have one of `symbols` on either side.
+    ///
+    /// Returns the value and the symbol that was found, if any.
+    pub fn parse_currency<'s>(&self, s: &str, symbols: &[&'s str]) -> Option<(f64, &'s str)> {
+        let trimmed = s.trim();
+        for symbol in symbols.iter().filter(|symbol| !symbol.is_empty()) {
+            let rest = if let Some(rest) = trimmed.strip_prefix(symbol) {
+                Some(rest.to_string())
+            } else if let Some(rest) = trimmed.strip_suffix(symbol) {
+                Some(rest.to_string())
+            } else {
+                trimmed
+                    .find(symbol)
+                    .map(|i| format!("{}{}", &trimmed[..i], &trimmed[i + symbol.len()..]))
+            };
+            if let Some(value) = rest.and_then(|rest| self.parse_number(&rest)) {
+                return Some((value, symbol));
+            }
+        }
+        self.parse_number(trimmed).map(|value| (value, ""))
+    }
+
+    fn digit_value(&self, c: char) -> Option<u32> {
+        c.to_digit(10).or_else(|| {
+            let digit = (c as u32).checked_sub(self.digits_type)?;
+            (digit < 10).then_some(digit)
+        })
+    }
+
+    /// Returns the index after `pattern` if it occurs at `i`.
+    fn match_at(&self, chars: &[char], i: usize, pattern: &str) -> Option<usize> {
+        if pattern.is_empty() {
+            return None;
+        }
+        let mut j = i;
+        for c in pattern.chars() {
+            if chars.get(j) != Some(&c) {
+                return None;
+            }
+            j += 1;
+        }
+        Some(j)
+    }
+}
+
+/// The index of a `DateTimeStyle` in the pattern tables of a locale, or `None` if the date or
+/// time shouldn't be included.
+fn style_index(style: &str) -> Option<usize> {
+    match style {
+        "long" => Some(0),
+        "medium" => Some(1),
+        "short" => Some(2),
+        _ => None,
+    }
+}
+
+/// The pattern a locale uses for a combination of `DateTimeStyle`s.
+pub fn style_pattern(locale: &Locale, date_style: &str, time_style: &str) -> String {
+    let date = style_index(date_style).map(|i| locale.date_patterns[i]);
+    let time = style_index(time_style).map(|i| locale.time_patterns[i]);
+    match (date, time) {
+        (Some(date), Some(time)) => format!("{date} {time}"),
+        (Some(pattern), None) | (None, Some(pattern)) => pattern.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Month or weekday names, in one of the `DateTimeNameStyle`s.
+fn styled_names<const N: usize>(
+    full: &[&str; N],
+    abbreviated: &[&str; N],
+    style: &str,
+) -> Vec<String> {
+    match style {
+        "longAbbreviation" => abbreviated.iter().map(|name| name.to_string()).collect(),
+        "shortAbbreviation" => full
+            .iter()
+            .map(|name| name.chars().take(1).collect())
+            .collect(),
+        _ => full.iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+/// The month names of a locale, for `DateTimeFormatter.getMonthNames`.
+pub fn month_names(locale: &Locale, style: &str, context: &str) -> Vec<String> {
+    let full = match (context, &locale.format_month_names) {
+        ("format", Some(names)) => names,
+        _ => &locale.month_names,
+    };
+    styled_names(full, &locale.short_month_names, style)
+}
+
+/// The weekday names of a locale, starting with Sunday, for
+/// `DateTimeFormatter.getWeekdayNames`.
+pub fn weekday_names(locale: &Locale, style: &str) -> Vec<String> {
+    styled_names(&locale.weekday_names, &locale.short_weekday_names, style)
+}
+
+/// Formats a date with a Unicode date pattern, like `DateTimeFormatter.format` does.
+pub fn format_date(locale: &Locale, pattern: &str, date: DateTime<FixedOffset>) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' {
+            if chars.get(i + 1) == Some(&'\'') {
+                out.push('\'');
+                i += 2;
+                continue;
+            }
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' {
+                    if chars.get(i + 1) == Some(&'\'') {
+                        out.push('\'');
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                out.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        if !c.is_ascii_alphabetic() {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        let count = chars[i..].iter().take_while(|other| **other == c).count();
+        i += count;
+        format_field(&mut out, locale, c, count, &date);
+    }
+    out
+}
+
+fn format_field(
+    out: &mut String,
+    locale: &Locale,
+    field: char,
+    count: usize,
+    date: &DateTime<FixedOffset>,
+) {
+    let padded = |value: i64| format!("{value:0count$}");
+    let month = date.month0() as usize;
+    let weekday = date.weekday().num_days_from_sunday() as usize;
+    let hour = date.hour();
+
+    let text = match field {
+        'G' => if date.year() > 0 { "AD" } else { "BC" }.to_string(),
+        'y' if count == 2 => format!("{:02}", date.year().rem_euclid(100)),
+        'y' => padded(date.year().into()),
+        'M' | 'L' => match count {
+            1 | 2 => padded((month + 1) as i64),
+            3 => locale.short_month_names[month].to_string(),
+            _ => {
+                let names = match (&locale.format_month_names, field) {
+                    (Some(names), 'M') => names,
+                    _ => &locale.month_names,
+                };
+                names[month].to_string()
+            }
+        },
+        'd' => padded(date.day().into()),
+        'D' => padded(date.ordinal().into()),
+        'E' if count >= 4 => locale.weekday_names[weekday].to_string(),
+        'E' => locale.short_weekday_names[weekday].to_string(),
+        'Q' => padded((month / 3 + 1) as i64),
+        'w' => padded(date.iso_week().week().into()),
+        'a' => if hour < 12 { locale.am } else { locale.pm }.to_string(),
+        'h' => padded(match hour % 12 {
+            0 => 12,
+            hour => hour.into(),
+        }),
+        'H' => padded(hour.into()),
+        'K' => padded((hour % 12).into()),
+        'k' => padded(match hour {
+            0 => 24,
+            hour => hour.into(),
+        }),
+        'm' => padded(date.minute().into()),
+        's' => padded(date.second().into()),
+        'S' => {
+            let millis = format!("{:03}", date.timestamp_subsec_millis());
+            let mut digits: String = millis.chars().take(count).collect();
+            while digits.len() < count {
+                digits.push('0');
+            }
+            digits
+        }
+        'z' | 'Z' | 'v' => {
+            let offset = date.offset().local_minus_utc() / 60;
+            let sign = if offset < 0 { '-' } else { '+' };
+            let (hours, minutes) = (offset.abs() / 60, offset.abs() % 60);
+            if field == 'Z' {
+                format!("{sign}{hours:02}{minutes:02}")
+            } else {
+                format!("GMT{sign}{hours:02}:{minutes:02}")
+            }
+        }
+        field => field.to_string().repeat(count),
+    };
+    out.push_str(&text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::find_locale;
+    use chrono::TimeZone;
+
+    fn default_format(locale: &Locale) -> NumberFormat<'_> {
+        NumberFormat {
+            fractional_digits: 2,
+            trailing_zeros: true,
+            leading_zero: true,
+            use_grouping: true,
+            grouping_pattern: locale.grouping_pattern,
+            grouping_separator: locale.grouping_separator,
+            decimal_separator: locale.decimal_separator,
+            negative_symbol: "-",
+            negative_number_format: locale.negative_number_format,
+            digits_type: '0' as u32,
+        }
+    }
+
+    #[test]
+    fn formats_numbers() {
+        let (en_us, _) = find_locale("en-US", "en-US");
+        let (de_de, _) = find_locale("de-DE", "en-US");
+
+        let mut format = default_format(en_us);
+        assert_eq!(format.format_number(1234567.891), "1,234,567.89");
+        assert_eq!(format.format_number(-0.5), "-0.50");
+
+        format.trailing_zeros = false;
+        format.leading_zero = false;
+        format.negative_number_format = 0;
+        assert_eq!(format.format_number(-0.5), "(.5)");
+
+        format.grouping_pattern = "3;2;*";
+        assert_eq!(format.format_number(12345678.0), "1,23,45,678");
+
+        let format = default_format(de_de);
+        assert_eq!(format.format_number(1234.5), "1.234,50");
+        assert_eq!(
+            format.format_currency(-1234.5, "€", 3, 8),
+            "-1.234,50 €".to_string()
+        );
+        assert_eq!(format.parse_number(" 1.234,5 "), Some(1234.5));
+        assert_eq!(format.parse("-12 abc"), Some((-12.0, 0, 3)));
+        assert_eq!(format.parse_number("12 abc"), None);
+        assert_eq!(
+            format.parse_currency("12,50 €", &["EUR", "€"]),
+            Some((12.5, "€"))
+        );
+    }
+
+    #[test]
+    fn formats_dates() {
+        let (en_us, _) = find_locale("en-US", "en-US");
+        let (ru_ru, _) = find_locale("ru", "en-US");
+        let date = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2001, 2, 3, 16, 5, 6)
+            .unwrap();
+
+        let pattern = style_pattern(en_us, "long", "long");
+        assert_eq!(
+            format_date(en_us, &pattern, date),
+            "Saturday, February 3, 2001 4:05:06 PM"
+        );
+        assert_eq!(
+            format_date(en_us, "yy-MM-dd 'at' HH:mm ''Z''", date),
+            "01-02-03 at 16:05 '+0000'"
+        );
+        assert_eq!(format_date(ru_ru, "d MMMM", date), "3 февраля");
+        assert_eq!(month_names(ru_ru, "full", "standalone")[1], "февраль");
+    }
+}
@@ -0,0 +1,645 @@
+//! Touch points and the gestures made with them, as exposed through `flash.ui.Multitouch`.
+
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, InteractiveObject, TDisplayObject, TInteractiveObject};
+use crate::events::{TouchEvent, TouchPhase};
+use crate::player::run_pointer_pick;
+use gc_arena::Collect;
+use swf::{Point, Twips};
+use web_time::{Duration, Instant};
+
+/// How far the fingers of a two-finger tap may move, in pixels, before it's no longer a tap.
+const TAP_SLOP: f64 = 10.0;
+
+/// How long the fingers of a two-finger tap may stay down.
+const TAP_DURATION: Duration = Duration::from_millis(500);
+
+/// The value of `Multitouch.inputMode`, which decides which events touches are turned into.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MultitouchInputMode {
+    /// Only the primary touch point is used, as the mouse.
+    None,
+
+    /// Touch points are recognized as gestures, and dispatched as `GestureEvent`s.
+    #[default]
+    Gesture,
+
+    /// Every touch point is dispatched as `TouchEvent`s.
+    TouchPoint,
+}
+
+impl MultitouchInputMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::None),
+            "gesture" => Some(Self::Gesture),
+            "touchPoint" => Some(Self::TouchPoint),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gesture => "gesture",
+            Self::TouchPoint => "touchPoint",
+        }
+    }
+}
+
+/// A finger that's currently touching the stage.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct TouchPoint<'gc> {
+    /// The identifier given by the frontend.
+    pub id: u64,
+
+    /// The `touchPointID` given to the movie.
+    pub touch_point_id: i32,
+
+    /// Whether this touch point also acts as the mouse.
+    pub is_primary: bool,
+
+    /// The object that the touch point is over.
+    pub over: Option<InteractiveObject<'gc>>,
+
+    /// The object that the touch point began on, which receives a `touchTap`
+    /// if the touch point also ends over it.
+    pub began_on: Option<InteractiveObject<'gc>>,
+}
+
+/// The state of the touch points of a player.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct TouchData<'gc> {
+    #[collect(require_static)]
+    pub input_mode: MultitouchInputMode,
+
+    /// The fingers touching the stage, in the order they touched it.
+    pub points: Vec<TouchPoint<'gc>>,
+
+    /// The most touch points that have been down at the same time.
+    pub max_points_seen: u32,
+
+    next_touch_point_id: i32,
+
+    #[collect(require_static)]
+    pub gestures: GestureRecognizer,
+}
+
+impl<'gc> TouchData<'gc> {
+    /// Returns the next `touchPointID`.
+    pub fn next_touch_point_id(&mut self) -> i32 {
+        let id = self.next_touch_point_id;
+        self.next_touch_point_id = self.next_touch_point_id.wrapping_add(1);
+        id
+    }
+
+    pub fn get(&self, id: u64) -> Option<&TouchPoint<'gc>> {
+        self.points.iter().find(|point| point.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut TouchPoint<'gc>> {
+        self.points.iter_mut().find(|point| point.id == id)
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<TouchPoint<'gc>> {
+        let index = self.points.iter().position(|point| point.id == id)?;
+        Some(self.points.remove(index))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GestureKind {
+    Pan,
+    Rotate,
+    Zoom,
+    TwoFingerTap,
+}
+
+impl GestureKind {
+    /// The type of the event dispatched for this gesture.
+    pub fn event_type(self) -> &'static str {
+        match self {
+            Self::Pan => "gesturePan",
+            Self::Rotate => "gestureRotate",
+            Self::Zoom => "gestureZoom",
+            Self::TwoFingerTap => "gestureTwoFingerTap",
+        }
+    }
+}
+
+/// The values of `flash.events.GesturePhase`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GesturePhase {
+    Begin,
+    Update,
+    End,
+    All,
+}
+
+impl GesturePhase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Begin => "begin",
+            Self::Update => "update",
+            Self::End => "end",
+            Self::All => "all",
+        }
+    }
+}
+
+/// A step of a gesture, to be dispatched to the movie.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gesture {
+    pub kind: GestureKind,
+    pub phase: GesturePhase,
+
+    /// The point between the fingers, on the stage.
+    pub position: Point<Twips>,
+
+    /// How much the distance between the fingers changed since the last step.
+    pub scale: f64,
+
+    /// How much the fingers turned since the last step, in degrees.
+    pub rotation: f64,
+
+    /// How far the point between the fingers moved since the last step, in pixels.
+    pub offset: (f64, f64),
+}
+
+impl Gesture {
+    fn new(kind: GestureKind, phase: GesturePhase, position: Point<Twips>) -> Self {
+        Self {
+            kind,
+            phase,
+            position,
+            scale: 1.0,
+            rotation: 0.0,
+            offset: (0.0, 0.0),
+        }
+    }
+}
+
+/// Two fingers which are making a gesture.
+struct TwoFingerGesture {
+    ids: [u64; 2],
+    start: Instant,
+
+    /// How far the fingers have moved in total, in pixels.
+    travelled: f64,
+
+    /// The transform gestures that have begun, and will need to end.
+    begun: Vec<GestureKind>,
+}
+
+/// Turns touch points into pan, rotate, zoom and two-finger tap gestures.
+///
+/// This is shared by all frontends, which only need to report touch points.
+#[derive(Default)]
+pub struct GestureRecognizer {
+    positions: Vec<(u64, Point<Twips>)>,
+    gesture: Option<TwoFingerGesture>,
+}
+
+impl GestureRecognizer {
+    fn position(&self, id: u64) -> Option<Point<Twips>> {
+        self.positions
+            .iter()
+            .find(|(touch, _)| *touch == id)
+            .map(|(_, position)| *position)
+    }
+
+    pub fn touch_begin(&mut self, id: u64, position: Point<Twips>) {
+        self.positions.push((id, position));
+        if self.gesture.is_none() && self.positions.len() == 2 {
+            self.gesture = Some(TwoFingerGesture {
+                ids: [self.positions[0].0, id],
+                start: Instant::now(),
+                travelled: 0.0,
+                begun: vec![],
+            });
+        }
+    }
+
+    pub fn touch_move(&mut self, id: u64, position: Point<Twips>) -> Vec<Gesture> {
+        let Some(previous) = self.position(id) else {
+            return vec![];
+        };
+        let Some(gesture) = &self.gesture else {
+            self.set_position(id, position);
+            return vec![];
+        };
+        let [first, second] = gesture.ids;
+        if id != first && id != second {
+            self.set_position(id, position);
+            return vec![];
+        }
+
+        let other = self
+            .position(if id == first { second } else { first })
+            .unwrap_or(previous);
+        self.set_position(id, position);
+
+        let (old_distance, old_angle, old_middle) = Self::measure(previous, other);
+        let (new_distance, new_angle, new_middle) = Self::measure(position, other);
+
+        let gesture = self.gesture.as_mut().expect("Gesture was checked above");
+        let moved = position - previous;
+        gesture.travelled += moved.dx.to_pixels().hypot(moved.dy.to_pixels());
+        if gesture.begun.is_empty() && gesture.travelled < TAP_SLOP {
+            return vec![];
+        }
+
+        let position = Point::from_pixels(new_middle.0, new_middle.1);
+        let mut gestures = vec![];
+        let step = |kind: GestureKind, gesture: &mut TwoFingerGesture| {
+            let phase = if gesture.begun.contains(&kind) {
+                GesturePhase::Update
+            } else {
+                gesture.begun.push(kind);
+                GesturePhase::Begin
+            };
+            Gesture::new(kind, phase, position)
+        };
+
+        if old_distance > 0.0 && new_distance != old_distance {
+            gestures.push(Gesture {
+                scale: new_distance / old_distance,
+                ..step(GestureKind::Zoom, gesture)
+            });
+        }
+
+        let mut rotation = (new_angle - old_angle).to_degrees();
+        if rotation > 180.0 {
+            rotation -= 360.0;
+        } else if rotation < -180.0 {
+            rotation += 360.0;
+        }
+        if rotation != 0.0 {
+            gestures.push(Gesture {
+                rotation,
+                ..step(GestureKind::Rotate, gesture)
+            });
+        }
+
+        let offset = (new_middle.0 - old_middle.0, new_middle.1 - old_middle.1);
+        if offset != (0.0, 0.0) {
+            gestures.push(Gesture {
+                offset,
+                ..step(GestureKind::Pan, gesture)
+            });
+        }
+
+        gestures
+    }
+
+    /// A touch point was lifted from the stage.
+    pub fn touch_end(&mut self, id: u64) -> Vec<Gesture> {
+        self.finish(id, true)
+    }
+
+    /// A touch point was interrupted by the system, so it can't make a tap.
+    pub fn touch_cancel(&mut self, id: u64) -> Vec<Gesture> {
+        self.finish(id, false)
+    }
+
+    fn finish(&mut self, id: u64, can_tap: bool) -> Vec<Gesture> {
+        let mut gestures = vec![];
+        if let Some(gesture) = &self.gesture {
+            if gesture.ids.contains(&id) {
+                let [first, second] = gesture.ids;
+                let position = match (self.position(first), self.position(second)) {
+                    (Some(first), Some(second)) => {
+                        let (_, _, (x, y)) = Self::measure(first, second);
+                        Point::from_pixels(x, y)
+                    }
+                    (Some(position), None) | (None, Some(position)) => position,
+                    (None, None) => Point::ZERO,
+                };
+
+                if gesture.begun.is_empty() {
+                    if can_tap
+                        && gesture.travelled < TAP_SLOP
+                        && gesture.start.elapsed() < TAP_DURATION
+                    {
+                        gestures.push(Gesture::new(
+                            GestureKind::TwoFingerTap,
+                            GesturePhase::All,
+                            position,
+                        ));
+                    }
+                } else {
+                    gestures.extend(
+                        gesture
+                            .begun
+                            .iter()
+                            .map(|kind| Gesture::new(*kind, GesturePhase::End, position)),
+                    );
+                }
+                self.gesture = None;
+            }
+        }
+        self.positions.retain(|(touch, _)| *touch != id);
+        gestures
+    }
+
+    fn set_position(&mut self, id: u64, position: Point<Twips>) {
+        if let Some((_, current)) = self.positions.iter_mut().find(|(touch, _)| *touch == id) {
+            *current = position;
+        }
+    }
+
+    /// Returns the distance, angle and middle point between two fingers, in pixels.
+    fn measure(a: Point<Twips>, b: Point<Twips>) -> (f64, f64, (f64, f64)) {
+        let (ax, ay) = (a.x.to_pixels(), a.y.to_pixels());
+        let (bx, by) = (b.x.to_pixels(), b.y.to_pixels());
+        let (dx, dy) = (bx - ax, by - ay);
+        (
+            dx.hypot(dy),
+            dy.atan2(dx),
+            ((ax + bx) / 2.0, (ay + by) / 2.0),
+        )
+    }
+}
+
+/// Handles a finger touching the stage at the given position.
+///
+/// Returns `true` if the movie handled any of the dispatched events.
+pub fn handle_touch_event<'gc>(
+    context: &mut UpdateContext<'gc>,
+    event: TouchEvent,
+    position: Point<Twips>,
+) -> bool {
+    let mut handled = false;
+    let over = run_pointer_pick(context, position, true);
+    let input_mode = context.mouse_data.touches.input_mode;
+    let touches = &mut context.mouse_data.touches;
+
+    let gestures = match event.phase {
+        TouchPhase::Begin => {
+            if touches.get(event.id).is_some() {
+                return false;
+            }
+            let touch_point_id = touches.next_touch_point_id();
+            let is_primary = touches.points.is_empty();
+            touches.points.push(TouchPoint {
+                id: event.id,
+                touch_point_id,
+                is_primary,
+                over,
+                began_on: over,
+            });
+            touches.max_points_seen = touches.max_points_seen.max(touches.points.len() as u32);
+            touches.gestures.touch_begin(event.id, position);
+
+            if input_mode == MultitouchInputMode::TouchPoint {
+                let target = over.unwrap_or_else(|| context.stage.into());
+                handled |= dispatch_touch_event(
+                    context,
+                    "touchOver",
+                    target,
+                    None,
+                    touch_point_id,
+                    is_primary,
+                    position,
+                );
+                handled |= dispatch_touch_event(
+                    context,
+                    "touchBegin",
+                    target,
+                    None,
+                    touch_point_id,
+                    is_primary,
+                    position,
+                );
+            }
+            vec![]
+        }
+        TouchPhase::Move => {
+            let Some(point) = touches.get_mut(event.id) else {
+                return false;
+            };
+            let previous_over = point.over;
+            point.over = over;
+            let (touch_point_id, is_primary) = (point.touch_point_id, point.is_primary);
+            let gestures = touches.gestures.touch_move(event.id, position);
+
+            if input_mode == MultitouchInputMode::TouchPoint {
+                let target = over.unwrap_or_else(|| context.stage.into());
+                let previous_target = previous_over.unwrap_or_else(|| context.stage.into());
+                if !InteractiveObject::ptr_eq(target, previous_target) {
+                    handled |= dispatch_touch_event(
+                        context,
+                        "touchOut",
+                        previous_target,
+                        Some(target),
+                        touch_point_id,
+                        is_primary,
+                        position,
+                    );
+                    handled |= dispatch_touch_event(
+                        context,
+                        "touchOver",
+                        target,
+                        Some(previous_target),
+                        touch_point_id,
+                        is_primary,
+                        position,
+                    );
+                }
+                handled |= dispatch_touch_event(
+                    context,
+                    "touchMove",
+                    target,
+                    None,
+                    touch_point_id,
+                    is_primary,
+                    position,
+                );
+            }
+            gestures
+        }
+        TouchPhase::End | TouchPhase::Cancel => {
+            let Some(point) = touches.remove(event.id) else {
+                return false;
+            };
+            let gestures = if event.phase == TouchPhase::End {
+                touches.gestures.touch_end(event.id)
+            } else {
+                touches.gestures.touch_cancel(event.id)
+            };
+
+            if input_mode == MultitouchInputMode::TouchPoint {
+                let target = over.unwrap_or_else(|| context.stage.into());
+                handled |= dispatch_touch_event(
+                    context,
+                    "touchEnd",
+                    target,
+                    None,
+                    point.touch_point_id,
+                    point.is_primary,
+                    position,
+                );
+                let began_on = point.began_on.unwrap_or_else(|| context.stage.into());
+                if event.phase == TouchPhase::End && InteractiveObject::ptr_eq(target, began_on) {
+                    handled |= dispatch_touch_event(
+                        context,
+                        "touchTap",
+                        target,
+                        None,
+                        point.touch_point_id,
+                        point.is_primary,
+                        position,
+                    );
+                }
+                handled |= dispatch_touch_event(
+                    context,
+                    "touchOut",
+                    target,
+                    None,
+                    point.touch_point_id,
+                    point.is_primary,
+                    position,
+                );
+            }
+            gestures
+        }
+    };
+
+    if input_mode == MultitouchInputMode::Gesture {
+        for gesture in gestures {
+            let target = run_pointer_pick(context, gesture.position, true)
+                .unwrap_or_else(|| context.stage.into());
+            handled |= dispatch_to_avm2(context, target, |activation, target| {
+                Avm2EventObject::gesture_event(activation, target, &gesture)
+            });
+        }
+    }
+
+    handled
+}
+
+fn dispatch_touch_event<'gc>(
+    context: &mut UpdateContext<'gc>,
+    event_type: &'static str,
+    target: InteractiveObject<'gc>,
+    related_object: Option<InteractiveObject<'gc>>,
+    touch_point_id: i32,
+    is_primary: bool,
+    position: Point<Twips>,
+) -> bool {
+    dispatch_to_avm2(context, target, |activation, target| {
+        Avm2EventObject::touch_event(
+            activation,
+            event_type,
+            target,
+            related_object,
+            touch_point_id,
+            is_primary,
+            position,
+            true,
+        )
+    })
+}
+
+fn dispatch_to_avm2<'gc>(
+    context: &mut UpdateContext<'gc>,
+    target: InteractiveObject<'gc>,
+    make_event: impl FnOnce(&mut Avm2Activation<'_, 'gc>, DisplayObject<'gc>) -> Avm2Object<'gc>,
+) -> bool {
+    let display_object = target.as_displayobject();
+    if !display_object.movie().is_action_script_3() {
+        return false;
+    }
+    let Some(object) = display_object.object2().as_object() else {
+        return false;
+    };
+
+    let mut activation = Avm2Activation::from_nothing(context);
+    let event = make_event(&mut activation, display_object);
+    Avm2::dispatch_event(activation.context, event, object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Point<Twips> {
+        Point::from_pixels(x, y)
+    }
+
+    #[test]
+    fn two_finger_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.touch_begin(1, point(100.0, 100.0));
+        recognizer.touch_begin(2, point(200.0, 100.0));
+        assert!(recognizer.touch_move(2, point(202.0, 100.0)).is_empty());
+
+        let gestures = recognizer.touch_end(1);
+        assert_eq!(gestures.len(), 1);
+        assert_eq!(gestures[0].kind, GestureKind::TwoFingerTap);
+        assert_eq!(gestures[0].phase, GesturePhase::All);
+        assert_eq!(gestures[0].position, point(151.0, 100.0));
+        assert!(recognizer.touch_end(2).is_empty());
+    }
+
+    #[test]
+    fn single_finger_makes_no_gesture() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.touch_begin(1, point(100.0, 100.0));
+        assert!(recognizer.touch_move(1, point(300.0, 300.0)).is_empty());
+        assert!(recognizer.touch_end(1).is_empty());
+    }
+
+    #[test]
+    fn zoom() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.touch_begin(1, point(100.0, 100.0));
+        recognizer.touch_begin(2, point(200.0, 100.0));
+
+        let gestures = recognizer.touch_move(2, point(300.0, 100.0));
+        let zoom = gestures
+            .iter()
+            .find(|gesture| gesture.kind == GestureKind::Zoom)
+            .unwrap();
+        assert_eq!(zoom.phase, GesturePhase::Begin);
+        assert_eq!(zoom.scale, 2.0);
+        assert!(gestures
+            .iter()
+            .all(|gesture| gesture.kind != GestureKind::Rotate));
+
+        let gestures = recognizer.touch_move(2, point(400.0, 100.0));
+        let zoom = gestures
+            .iter()
+            .find(|gesture| gesture.kind == GestureKind::Zoom)
+            .unwrap();
+        assert_eq!(zoom.phase, GesturePhase::Update);
+        assert_eq!(zoom.scale, 1.5);
+
+        let gestures = recognizer.touch_cancel(1);
+        assert!(gestures
+            .iter()
+            .all(|gesture| gesture.phase == GesturePhase::End));
+        assert!(gestures
+            .iter()
+            .any(|gesture| gesture.kind == GestureKind::Zoom));
+    }
+
+    #[test]
+    fn rotate() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.touch_begin(1, point(100.0, 100.0));
+        recognizer.touch_begin(2, point(200.0, 100.0));
+
+        let gestures = recognizer.touch_move(2, point(100.0, 200.0));
+        let rotate = gestures
+            .iter()
+            .find(|gesture| gesture.kind == GestureKind::Rotate)
+            .unwrap();
+        assert!((rotate.rotation - 90.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,463 @@
+//! Additional `NativeWindow`s opened by AIR content.
+//!
+//! The primary window is the player itself. Every other window gets its own `Stage`,
+//! which is rendered offscreen through the player's renderer and then handed to the
+//! `UiBackend` to be displayed on a surface of its own (such as an extra canvas on web).
+//!
+//! Reading a frame back waits for the GPU to finish drawing it, so each frame is only read
+//! back on the following tick, and windows are shown slightly behind the player.
+
+use crate::avm2::object::TObject;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+};
+use crate::backend::ui::{NativeWindowDescriptor, NativeWindowId, UiBackend};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObject, Stage, TDisplayObject};
+use crate::library::Library;
+use crate::vminterface::Instantiator;
+use gc_arena::{Collect, Mutation};
+use ruffle_render::backend::RenderBackend;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, PixelRegion, SyncHandle};
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::TransformStack;
+use swf::{Color, Point, Twips};
+use thiserror::Error;
+
+/// The number of additional windows that may be open at once, unless configured otherwise.
+pub const DEFAULT_MAX_NATIVE_WINDOWS: usize = 8;
+
+/// The size given to a new window until the movie sets its bounds.
+const DEFAULT_WINDOW_SIZE: (u32, u32) = (550, 400);
+
+#[derive(Debug, Error)]
+pub enum NativeWindowError {
+    #[error("The maximum number of open windows ({0}) has been reached.")]
+    LimitReached(usize),
+}
+
+/// The offscreen texture that a window's stage is rendered into.
+struct RenderTarget {
+    handle: BitmapHandle,
+    width: u32,
+    height: u32,
+}
+
+/// A frame that was rendered, but not yet read back and presented.
+struct PendingFrame {
+    sync_handle: Box<dyn SyncHandle>,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct AuxWindow<'gc> {
+    #[collect(require_static)]
+    id: NativeWindowId,
+
+    /// The AVM2 `NativeWindow` object that owns this window.
+    object: Avm2Object<'gc>,
+
+    stage: Stage<'gc>,
+
+    #[collect(require_static)]
+    descriptor: NativeWindowDescriptor,
+
+    /// Whether the UI backend accepted this window, and so wants to receive its frames.
+    presented: bool,
+
+    /// Whether this window currently has focus.
+    active: bool,
+
+    /// Where the pointer last was over this window, in stage coordinates.
+    #[collect(require_static)]
+    mouse_position: Point<Twips>,
+
+    #[collect(require_static)]
+    target: Option<RenderTarget>,
+
+    #[collect(require_static)]
+    pending_frame: Option<PendingFrame>,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct NativeWindows<'gc> {
+    max_windows: usize,
+
+    next_id: NativeWindowId,
+
+    windows: Vec<AuxWindow<'gc>>,
+
+    /// The window the pointer is currently over, or `None` if it is over the primary window.
+    pointer_window: Option<NativeWindowId>,
+}
+
+impl<'gc> NativeWindows<'gc> {
+    pub fn new(max_windows: usize) -> Self {
+        Self {
+            max_windows,
+            next_id: 0,
+            windows: Vec::new(),
+            pointer_window: None,
+        }
+    }
+
+    pub fn max_windows(&self) -> usize {
+        self.max_windows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    pub fn contains(&self, id: NativeWindowId) -> bool {
+        self.windows.iter().any(|window| window.id == id)
+    }
+
+    /// The stages of all open additional windows.
+    pub fn stages(&self) -> impl Iterator<Item = Stage<'gc>> + '_ {
+        self.windows.iter().map(|window| window.stage)
+    }
+
+    /// Whether the given display object is the stage of an additional window.
+    pub fn is_window_stage(&self, object: DisplayObject<'gc>) -> bool {
+        self.windows
+            .iter()
+            .any(|window| DisplayObject::ptr_eq(window.stage.into(), object))
+    }
+
+    /// The stage that pointer input should be delivered to,
+    /// or `None` if the pointer is over the primary window.
+    pub fn pointer_stage(&self) -> Option<Stage<'gc>> {
+        let id = self.pointer_window?;
+        self.get(id).map(|window| window.stage)
+    }
+
+    pub fn set_pointer_window(&mut self, id: Option<NativeWindowId>) {
+        self.pointer_window = id;
+    }
+
+    /// Where the pointer is on the stage of the window it is over,
+    /// or `None` if it is over the primary window.
+    pub fn pointer_position(&self) -> Option<Point<Twips>> {
+        let id = self.pointer_window?;
+        self.get(id).map(|window| window.mouse_position)
+    }
+
+    /// Moves the pointer on the window it is over.
+    ///
+    /// Returns `false` if it is over the primary window, whose position is kept by the player.
+    pub fn set_pointer_position(&mut self, position: Point<Twips>) -> bool {
+        let Some(id) = self.pointer_window else {
+            return false;
+        };
+        match self.windows.iter_mut().find(|window| window.id == id) {
+            Some(window) => {
+                window.mouse_position = position;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The stage that the given object is on, and where the pointer last was on it.
+    ///
+    /// Each window keeps its own pointer position, so that objects on one window do not
+    /// follow the pointer while it moves over another.
+    pub fn mouse_position_on(
+        context: &UpdateContext<'gc>,
+        object: DisplayObject<'gc>,
+    ) -> (Stage<'gc>, Point<Twips>) {
+        if !context.native_windows.is_empty() {
+            let mut root = object;
+            while let Some(parent) = root.parent() {
+                root = parent;
+            }
+            if let Some(window) = context
+                .native_windows
+                .windows
+                .iter()
+                .find(|window| DisplayObject::ptr_eq(window.stage.into(), root))
+            {
+                return (window.stage, window.mouse_position);
+            }
+        }
+        (context.stage, *context.mouse_position)
+    }
+
+    /// Whether the window owned by the given object currently has focus.
+    pub fn is_active(&self, object: Avm2Object<'gc>) -> bool {
+        self.windows
+            .iter()
+            .any(|window| Avm2Object::ptr_eq(window.object, object) && window.active)
+    }
+
+    fn get(&self, id: NativeWindowId) -> Option<&AuxWindow<'gc>> {
+        self.windows.iter().find(|window| window.id == id)
+    }
+
+    fn find_object(&mut self, object: Avm2Object<'gc>) -> Option<&mut AuxWindow<'gc>> {
+        self.windows
+            .iter_mut()
+            .find(|window| Avm2Object::ptr_eq(window.object, object))
+    }
+
+    /// Opens a new window owned by the given `NativeWindow` object, and returns its stage.
+    ///
+    /// The window starts out invisible, as in AIR.
+    pub fn open(
+        context: &mut UpdateContext<'gc>,
+        object: Avm2Object<'gc>,
+        transparent: bool,
+    ) -> Result<Stage<'gc>, NativeWindowError> {
+        let windows = &mut *context.native_windows;
+        if windows.windows.len() >= windows.max_windows {
+            return Err(NativeWindowError::LimitReached(windows.max_windows));
+        }
+        let id = windows.next_id;
+        windows.next_id = windows.next_id.wrapping_add(1);
+
+        let (width, height) = DEFAULT_WINDOW_SIZE;
+        let stage = Stage::empty(context.gc_context, false, context.swf.clone());
+        stage.set_movie_size(context.gc_context, width, height);
+        stage.set_background_color(context.gc_context, context.stage.background_color());
+        stage.post_instantiation(context, None, Instantiator::Avm2, false);
+
+        let descriptor = NativeWindowDescriptor {
+            title: String::new(),
+            x: 0,
+            y: 0,
+            width,
+            height,
+            visible: false,
+            transparent,
+        };
+        let presented = context.ui.open_native_window(id, &descriptor);
+
+        context.native_windows.windows.push(AuxWindow {
+            id,
+            object,
+            stage,
+            descriptor,
+            presented,
+            active: false,
+            mouse_position: Point::ZERO,
+            target: None,
+            pending_frame: None,
+        });
+        Ok(stage)
+    }
+
+    /// Updates the title, bounds and visibility of the window owned by the given object.
+    pub fn update(
+        context: &mut UpdateContext<'gc>,
+        object: Avm2Object<'gc>,
+        update: impl FnOnce(&mut NativeWindowDescriptor),
+    ) {
+        let Some(window) = context.native_windows.find_object(object) else {
+            return;
+        };
+        let old = window.descriptor.clone();
+        update(&mut window.descriptor);
+        if window.descriptor == old {
+            return;
+        }
+
+        let id = window.id;
+        let stage = window.stage;
+        let descriptor = window.descriptor.clone();
+        stage.set_movie_size(context.gc_context, descriptor.width, descriptor.height);
+        context.ui.update_native_window(id, &descriptor);
+        *context.needs_render = true;
+    }
+
+    /// Gives focus to the window owned by the given object.
+    pub fn activate(context: &mut UpdateContext<'gc>, object: Avm2Object<'gc>) {
+        if let Some(window) = context.native_windows.find_object(object) {
+            let id = window.id;
+            context.ui.focus_native_window(id);
+            Self::set_active(context, id, true);
+        }
+    }
+
+    /// Marks a window as focused or unfocused, dispatching `activate` or `deactivate`
+    /// to its `NativeWindow` if that changed.
+    ///
+    /// Activating a window deactivates any other active window.
+    pub fn set_active(context: &mut UpdateContext<'gc>, id: NativeWindowId, active: bool) {
+        let mut events = Vec::new();
+        for window in context.native_windows.windows.iter_mut() {
+            let is_target = window.id == id;
+            if is_target && window.active != active {
+                window.active = active;
+                let name = if active { "activate" } else { "deactivate" };
+                events.push((window.object, name));
+            } else if !is_target && active && window.active {
+                window.active = false;
+                events.push((window.object, "deactivate"));
+            }
+        }
+
+        // Deactivation is dispatched before the matching activation.
+        events.sort_by_key(|(_, name)| *name == "activate");
+        for (object, name) in events {
+            let event = Avm2EventObject::bare_default_event(context, name);
+            Avm2::dispatch_event(context, event, object);
+        }
+    }
+
+    /// Asks the movie to close a window, as if the user had used its close button.
+    ///
+    /// The movie may cancel this by preventing the default action of the `closing` event.
+    pub fn request_close(context: &mut UpdateContext<'gc>, id: NativeWindowId) {
+        let Some(object) = context.native_windows.get(id).map(|window| window.object) else {
+            return;
+        };
+        let mut activation = Avm2Activation::from_nothing(context);
+        if let Err(e) = object.call_public_property("close", &[], &mut activation) {
+            tracing::error!("Unhandled error while closing NativeWindow: {e:?}");
+        }
+    }
+
+    /// Closes the window owned by the given object, freeing its render resources.
+    pub fn close(context: &mut UpdateContext<'gc>, object: Avm2Object<'gc>) {
+        let windows = &mut *context.native_windows;
+        let Some(index) = windows
+            .windows
+            .iter()
+            .position(|window| Avm2Object::ptr_eq(window.object, object))
+        else {
+            return;
+        };
+
+        // Dropping the window releases its offscreen texture, and any frame not yet read back.
+        let window = windows.windows.remove(index);
+        if windows.pointer_window == Some(window.id) {
+            windows.pointer_window = None;
+        }
+        if window.presented {
+            context.ui.close_native_window(window.id);
+        }
+    }
+
+    /// Reads back the frames rendered last time, and hands them to the UI backend.
+    ///
+    /// This is done on the next tick or render, whichever comes first, by which time the GPU
+    /// has usually finished drawing them.
+    pub fn present(&mut self, renderer: &mut dyn RenderBackend, ui: &mut dyn UiBackend) {
+        for window in self.windows.iter_mut() {
+            let Some(frame) = window.pending_frame.take() else {
+                continue;
+            };
+            let id = window.id;
+            let result = renderer.resolve_sync_handle(
+                frame.sync_handle,
+                Box::new(|rgba, row_stride| {
+                    ui.present_native_window(id, frame.width, frame.height, rgba, row_stride)
+                }),
+            );
+            if let Err(e) = result {
+                tracing::error!("Unable to read back NativeWindow surface: {e}");
+            }
+        }
+    }
+
+    /// Renders every visible window, to be presented on the next tick or render.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &mut self,
+        gc_context: &'gc Mutation<'gc>,
+        library: &Library<'gc>,
+        renderer: &mut dyn RenderBackend,
+        ui: &mut dyn UiBackend,
+        transform_stack: &mut TransformStack,
+        quality: StageQuality,
+    ) {
+        self.present(renderer, ui);
+
+        for window in self.windows.iter_mut() {
+            if !window.presented || !window.descriptor.visible {
+                continue;
+            }
+
+            let width = window.descriptor.width.max(1);
+            let height = window.descriptor.height.max(1);
+            let target = match window.target.take() {
+                Some(target) if target.width == width && target.height == height => target,
+                _ => {
+                    let bitmap = Bitmap::new(
+                        width,
+                        height,
+                        BitmapFormat::Rgba,
+                        vec![0; (width * height * 4) as usize],
+                    );
+                    match renderer.register_bitmap(bitmap) {
+                        Ok(handle) => RenderTarget {
+                            handle,
+                            width,
+                            height,
+                        },
+                        Err(e) => {
+                            tracing::error!("Unable to create NativeWindow surface: {e}");
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let mut cache_draws = vec![];
+            let mut render_context = RenderContext {
+                renderer: &mut *renderer,
+                commands: CommandList::new(),
+                cache_draws: &mut cache_draws,
+                gc_context,
+                library,
+                transform_stack: &mut *transform_stack,
+                is_offscreen: true,
+                use_bitmap_cache: false,
+                stage: window.stage,
+            };
+
+            if window.descriptor.transparent {
+                // Offscreen rendering draws on top of the previous frame, so start from scratch.
+                let clear = Bitmap::new(
+                    width,
+                    height,
+                    BitmapFormat::Rgba,
+                    vec![0; (width * height * 4) as usize],
+                );
+                if let Err(e) = render_context.renderer.update_texture(
+                    &target.handle,
+                    clear,
+                    PixelRegion::for_whole_size(width, height),
+                ) {
+                    tracing::error!("Unable to clear NativeWindow surface: {e}");
+                }
+            } else {
+                render_context.commands.draw_rect(
+                    window.stage.background_color().unwrap_or(Color::WHITE),
+                    Matrix::create_box(width as f32, height as f32, Twips::ZERO, Twips::ZERO),
+                );
+            }
+            window.stage.render(&mut render_context);
+            let commands = render_context.commands;
+
+            let sync_handle = renderer.render_offscreen(
+                target.handle.clone(),
+                commands,
+                quality,
+                PixelRegion::for_whole_size(width, height),
+            );
+            window.target = Some(target);
+            window.pending_frame = sync_handle.map(|sync_handle| PendingFrame {
+                sync_handle,
+                width,
+                height,
+            });
+        }
+    }
+}
@@ -7,15 +7,22 @@ use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventOb
 use crate::backend::navigator::{ErrorResponse, NavigatorBackend, OwnedFuture, Request};
 use crate::context::UpdateContext;
 use crate::loader::Error;
+use crate::socket::{SocketHandle, Sockets};
+use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::Player;
 use flash_lso::packet::{Header, Message, Packet};
-use flash_lso::types::{AMFVersion, Value as AmfValue};
+use flash_lso::types::{AMFVersion, Element, ObjectId, Value as AmfValue};
+use fnv::FnvHashMap;
 use gc_arena::{Collect, DynamicRoot, Rootable};
+use rtmp::{RtmpMessage, RtmpSession};
 use slotmap::{new_key_type, SlotMap};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::sync::{Mutex, Weak};
+use url::Url;
+
+mod rtmp;
 
 new_key_type! {
     pub struct NetConnectionHandle;
@@ -132,6 +139,8 @@ impl<'gc> NetConnections<'gc> {
         let connection = NetConnection {
             object: target,
             protocol: NetConnectionProtocol::Local,
+            creating_streams: FnvHashMap::default(),
+            streams: FnvHashMap::default(),
         };
         let handle = context.net_connections.connections.insert(connection);
 
@@ -168,15 +177,19 @@ impl<'gc> NetConnections<'gc> {
         context: &mut UpdateContext<'gc>,
         target: O,
         url: String,
+        version: AMFVersion,
     ) {
         let target = target.into();
         let connection = NetConnection {
             object: target,
             protocol: NetConnectionProtocol::FlashRemoting(FlashRemoting {
                 url,
+                version,
                 headers: vec![],
                 outgoing_queue: vec![],
             }),
+            creating_streams: FnvHashMap::default(),
+            streams: FnvHashMap::default(),
         };
         let handle = context.net_connections.connections.insert(connection);
 
@@ -187,11 +200,402 @@ impl<'gc> NetConnections<'gc> {
         // No open event here
     }
 
+    pub fn connect_to_rtmp<O: Into<NetConnectionObject<'gc>>>(
+        context: &mut UpdateContext<'gc>,
+        target: O,
+        url: String,
+        version: AMFVersion,
+        connect_arguments: Vec<Rc<AmfValue>>,
+    ) {
+        let target = target.into();
+        let parsed = Url::parse(&url).ok().filter(|parsed| {
+            // Tunneled (`rtmpt`), encrypted (`rtmpe`) and secure (`rtmps`) RTMP need transports
+            // that we don't have.
+            let is_supported = parsed.scheme() == "rtmp";
+            if !is_supported {
+                tracing::warn!(
+                    "Can't connect to {url}, as only plain rtmp:// connections are supported"
+                );
+            }
+            is_supported
+        });
+        let Some((host, port, app)) = parsed.and_then(|parsed| {
+            let host = parsed.host_str()?.to_string();
+            let port = parsed.port().unwrap_or(rtmp::DEFAULT_PORT);
+            let mut app = parsed.path().trim_start_matches('/').to_string();
+            if let Some(query) = parsed.query() {
+                app.push('?');
+                app.push_str(query);
+            }
+            Some((host, port, app))
+        }) else {
+            if let Some(existing_handle) = target.set_handle(None) {
+                NetConnections::close(context, existing_handle, false);
+            }
+            Self::dispatch_status(context, target, "NetConnection.Connect.Failed", "error");
+            return;
+        };
+
+        let flash_version = format!("WIN {},0,0,0", context.player_version);
+        let sockets = &mut context.sockets;
+        let navigator = &mut context.navigator;
+        let handle = context
+            .net_connections
+            .connections
+            .insert_with_key(|handle| NetConnection {
+                object: target,
+                protocol: NetConnectionProtocol::Rtmp(Rtmp {
+                    socket: sockets.connect_net_connection(*navigator, handle, host, port),
+                    url,
+                    app,
+                    flash_version,
+                    version,
+                    connect_arguments,
+                    session: RtmpSession::new(),
+                    connected: false,
+                    outgoing: vec![],
+                    next_transaction_id: 2, // 1 is used by `connect`
+                    responders: FnvHashMap::default(),
+                }),
+                creating_streams: FnvHashMap::default(),
+                streams: FnvHashMap::default(),
+            });
+
+        if let Some(existing_handle) = target.set_handle(Some(handle)) {
+            NetConnections::close(context, existing_handle, false);
+        }
+    }
+
+    pub fn on_socket_connected(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle) {
+        if let Some(NetConnection {
+            protocol: NetConnectionProtocol::Rtmp(rtmp),
+            ..
+        }) = context.net_connections.connections.get_mut(handle)
+        {
+            let hello = rtmp.session.start_handshake(&mut *context.rng);
+            context.sockets.send(rtmp.socket, hello);
+        }
+    }
+
+    pub fn on_socket_failed(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle) {
+        let Some(connection) = context.net_connections.connections.remove(handle) else {
+            return;
+        };
+        Self::dispatch_status(
+            context,
+            connection.object,
+            "NetConnection.Connect.Failed",
+            "error",
+        );
+    }
+
+    pub fn on_socket_data(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        data: &[u8],
+    ) {
+        let Some(NetConnection {
+            object,
+            protocol: NetConnectionProtocol::Rtmp(rtmp),
+        }) = context.net_connections.connections.get_mut(handle)
+        else {
+            return;
+        };
+        let object = *object;
+
+        let mut out = vec![];
+        let messages = rtmp.session.receive(data, &mut out);
+        if messages.contains(&RtmpMessage::HandshakeComplete) {
+            rtmp.write_connect(&mut out);
+        }
+        if !out.is_empty() {
+            context.sockets.send(rtmp.socket, out);
+        }
+
+        for message in messages {
+            match message {
+                RtmpMessage::HandshakeComplete => {}
+                RtmpMessage::Command {
+                    stream_id: 0,
+                    name,
+                    transaction_id,
+                    arguments,
+                } => {
+                    Self::on_rtmp_command(context, handle, object, &name, transaction_id, arguments)
+                }
+                RtmpMessage::Command {
+                    stream_id,
+                    name,
+                    arguments,
+                    ..
+                } => Self::on_rtmp_stream_command(context, handle, stream_id, &name, arguments),
+                RtmpMessage::Media {
+                    stream_id,
+                    kind,
+                    timestamp,
+                    data,
+                } => {
+                    let stream = context
+                        .net_connections
+                        .connections
+                        .get(handle)
+                        .and_then(|connection| connection.streams.get(&stream_id).copied());
+                    if let Some(stream) = stream {
+                        stream.append_bytes(context, rtmp::flv_tag(kind, timestamp, &data));
+                    }
+                }
+            }
+        }
+    }
+
+    fn on_rtmp_command(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        object: NetConnectionObject<'gc>,
+        name: &str,
+        transaction_id: f64,
+        arguments: Vec<Rc<AmfValue>>,
+    ) {
+        // The first argument of every command is a (usually null) command object.
+        let info = arguments
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| Rc::new(AmfValue::Null));
+
+        match name {
+            "_result" | "_error" if transaction_id == 1.0 => {
+                // The response to our `connect` call.
+                let is_success = name == "_result";
+                if let Some(NetConnection {
+                    protocol: NetConnectionProtocol::Rtmp(rtmp),
+                    ..
+                }) = context.net_connections.connections.get_mut(handle)
+                {
+                    rtmp.connected = is_success;
+                }
+                Self::call_remote_method(context, object, "onStatus", &[info]);
+                if !is_success {
+                    NetConnections::close(context, handle, false);
+                }
+            }
+            "_result" | "_error" => {
+                if let Some((stream, stream_name)) = context
+                    .net_connections
+                    .connections
+                    .get_mut(handle)
+                    .and_then(|connection| {
+                        connection.creating_streams.remove(&(transaction_id as u32))
+                    })
+                {
+                    Self::on_stream_created(context, handle, stream, stream_name, name, &info);
+                    return;
+                }
+
+                let responder = match context.net_connections.connections.get_mut(handle) {
+                    Some(NetConnection {
+                        protocol: NetConnectionProtocol::Rtmp(rtmp),
+                        ..
+                    }) => rtmp.responders.remove(&(transaction_id as u32)),
+                    _ => None,
+                };
+                if let Some(responder) = responder {
+                    let callback = if name == "_result" {
+                        ResponderCallback::Result
+                    } else {
+                        ResponderCallback::Status
+                    };
+                    responder.call(context, callback, info);
+                }
+            }
+            "onStatus" => Self::call_remote_method(context, object, "onStatus", &[info]),
+            "close" => NetConnections::close(context, handle, false),
+            _ => Self::call_remote_method(
+                context,
+                object,
+                name,
+                arguments.get(1..).unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Ask the server to play the media called `name` through a `NetStream`.
+    ///
+    /// This creates a stream on the server first, and the media sent on that stream is then
+    /// appended to the `NetStream` as an FLV.
+    pub fn play_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        name: String,
+    ) {
+        let Some(NetConnection {
+            protocol: NetConnectionProtocol::Rtmp(rtmp),
+            creating_streams,
+            streams,
+            ..
+        }) = context.net_connections.connections.get_mut(handle)
+        else {
+            return;
+        };
+
+        // A stream playing something else stops receiving it.
+        streams.retain(|_, playing| *playing != stream);
+
+        let transaction_id = rtmp.next_transaction_id;
+        rtmp.next_transaction_id += 1;
+        rtmp.session.write_command(
+            &mut rtmp.outgoing,
+            rtmp.version,
+            0,
+            "createStream",
+            transaction_id.into(),
+            &[Rc::new(AmfValue::Null)],
+        );
+        creating_streams.insert(transaction_id, (stream, name));
+
+        stream.append_bytes(context, rtmp::FLV_HEADER.to_vec());
+    }
+
+    /// Handle the response to the `createStream` call of a `NetStream`.
+    fn on_stream_created(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        stream_name: String,
+        response: &str,
+        info: &AmfValue,
+    ) {
+        let Some(NetConnection {
+            protocol: NetConnectionProtocol::Rtmp(rtmp),
+            streams,
+            ..
+        }) = context.net_connections.connections.get_mut(handle)
+        else {
+            return;
+        };
+
+        match (response, info) {
+            ("_result", AmfValue::Number(stream_id)) => {
+                let stream_id = *stream_id as u32;
+                rtmp.session.write_command(
+                    &mut rtmp.outgoing,
+                    rtmp.version,
+                    stream_id,
+                    "play",
+                    0.0,
+                    &[
+                        Rc::new(AmfValue::Null),
+                        Rc::new(AmfValue::String(stream_name)),
+                    ],
+                );
+                streams.insert(stream_id, stream);
+            }
+            _ => stream.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Play.Failed"), ("level", "error")],
+            ),
+        }
+    }
+
+    /// Handle a command that the server sent on the stream of a `NetStream`.
+    fn on_rtmp_stream_command(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream_id: u32,
+        name: &str,
+        arguments: Vec<Rc<AmfValue>>,
+    ) {
+        let Some(stream) = context
+            .net_connections
+            .connections
+            .get(handle)
+            .and_then(|connection| connection.streams.get(&stream_id).copied())
+        else {
+            return;
+        };
+
+        if name != "onStatus" {
+            tracing::debug!("Ignoring RTMP command {name} on stream {stream_id}");
+            return;
+        }
+
+        // The info object is the argument after the command object.
+        let mut values = vec![];
+        if let Some(AmfValue::Object(_, properties, _) | AmfValue::ECMAArray(_, _, properties, _)) =
+            arguments.get(1).map(|info| &**info)
+        {
+            for property in properties {
+                let value: &AmfValue = property.value();
+                if let AmfValue::String(value) = value {
+                    values.push((
+                        AvmString::new_utf8(context.gc_context, property.name()),
+                        AvmString::new_utf8(context.gc_context, value),
+                    ));
+                }
+            }
+        }
+        stream.trigger_status_event(context, values);
+    }
+
+    /// Calls a method that the server invoked on the connection.
+    ///
+    /// In AVM2 these are called on the `client` object of the `NetConnection`, except for
+    /// `onStatus`, which becomes a `netStatus` event.
+    fn call_remote_method(
+        context: &mut UpdateContext<'gc>,
+        object: NetConnectionObject<'gc>,
+        name: &str,
+        arguments: &[Rc<AmfValue>],
+    ) {
+        match object {
+            NetConnectionObject::Avm2(object) => {
+                if let Err(e) = object.call_remote_method(context, name, arguments) {
+                    tracing::error!("Unhandled error calling NetConnection method {name}: {e:?}");
+                }
+            }
+            NetConnectionObject::Avm1(object) => {
+                if let Err(e) =
+                    Avm1NetConnectionObject::call_remote_method(context, object, name, arguments)
+                {
+                    tracing::error!("Unhandled error calling NetConnection method {name}: {e}");
+                }
+            }
+        }
+    }
+
+    fn dispatch_status(
+        context: &mut UpdateContext<'gc>,
+        object: NetConnectionObject<'gc>,
+        code: &'static str,
+        level: &'static str,
+    ) {
+        match object {
+            NetConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context);
+                let event = Avm2EventObject::net_status_event(
+                    &mut activation,
+                    "netStatus",
+                    vec![("code", code), ("level", level)],
+                );
+                Avm2::dispatch_event(activation.context, event, object.into());
+            }
+            NetConnectionObject::Avm1(object) => {
+                if let Err(e) = Avm1NetConnectionObject::on_status_event(context, object, code) {
+                    tracing::error!("Unhandled error sending connection callback: {e}");
+                }
+            }
+        }
+    }
+
     pub fn close(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle, is_explicit: bool) {
         let Some(connection) = context.net_connections.connections.remove(handle) else {
             return;
         };
 
+        if let NetConnectionProtocol::Rtmp(rtmp) = &connection.protocol {
+            context.sockets.close(rtmp.socket);
+        }
+
         match connection.object {
             NetConnectionObject::Avm2(object) => {
                 let mut activation = Avm2Activation::from_nothing(context);
@@ -244,7 +648,12 @@ impl<'gc> NetConnections<'gc> {
 
     pub fn update_connections(context: &mut UpdateContext<'gc>) {
         for (handle, connection) in context.net_connections.connections.iter_mut() {
-            connection.update(handle, context.navigator, context.player.clone());
+            connection.update(
+                handle,
+                context.navigator,
+                context.sockets,
+                context.player.clone(),
+            );
         }
     }
 
@@ -293,6 +702,16 @@ impl<'gc> NetConnections<'gc> {
         }
     }
 
+    pub fn is_rtmp(&self, handle: NetConnectionHandle) -> bool {
+        matches!(
+            self.connections.get(handle),
+            Some(NetConnection {
+                protocol: NetConnectionProtocol::Rtmp(_),
+                ..
+            })
+        )
+    }
+
     pub fn is_connected(&self, handle: NetConnectionHandle) -> bool {
         self.connections
             .get(handle)
@@ -342,27 +761,36 @@ pub struct NetConnection<'gc> {
 
     #[collect(require_static)]
     protocol: NetConnectionProtocol,
+
+    /// The `NetStream`s waiting for the server to create their stream, by the transaction ID
+    /// of their `createStream` call, along with the name of what they want to play.
+    creating_streams: FnvHashMap<u32, (NetStream<'gc>, String)>,
+
+    /// The `NetStream`s playing media sent by the server, by their stream ID.
+    streams: FnvHashMap<u32, NetStream<'gc>>,
 }
 
 impl NetConnection<'_> {
     pub fn is_connected(&self) -> bool {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => true,
             NetConnectionProtocol::FlashRemoting(_) => false,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.connected,
         }
     }
 
     pub fn connected_proxy_type(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some("none"),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.connected.then_some("none"),
         }
     }
 
     pub fn far_id(&self) -> Option<&'static str> {
         match self.protocol {
             NetConnectionProtocol::Local => Some(""),
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
@@ -371,14 +799,14 @@ impl NetConnection<'_> {
             NetConnectionProtocol::Local => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
     pub fn near_id(&self) -> Option<&'static str> {
         match self.protocol {
             NetConnectionProtocol::Local => Some(""),
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
@@ -387,14 +815,15 @@ impl NetConnection<'_> {
             NetConnectionProtocol::Local => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
     pub fn protocol(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some("rtmp"),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.connected.then_some("rtmp"),
         }
     }
 
@@ -402,6 +831,7 @@ impl NetConnection<'_> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some("null".to_string()), // Yes, it's a string "null", not a real null.
             NetConnectionProtocol::FlashRemoting(remoting) => Some(remoting.url.to_string()),
+            NetConnectionProtocol::Rtmp(rtmp) => Some(rtmp.url.to_string()),
         }
     }
 
@@ -409,6 +839,7 @@ impl NetConnection<'_> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some(false),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.connected.then_some(false),
         }
     }
 
@@ -423,6 +854,7 @@ impl NetConnection<'_> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.send(command, responder_handle, message)
             }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.send(command, responder_handle, message),
         }
     }
 
@@ -430,6 +862,7 @@ impl NetConnection<'_> {
         &mut self,
         self_handle: NetConnectionHandle,
         navigator: &mut dyn NavigatorBackend,
+        sockets: &mut Sockets<'_>,
         player: Weak<Mutex<Player>>,
     ) {
         match &mut self.protocol {
@@ -439,6 +872,12 @@ impl NetConnection<'_> {
                     navigator.spawn_future(remoting.flush_queue(self_handle, player));
                 }
             }
+            NetConnectionProtocol::Rtmp(rtmp) => {
+                // Calls made before the handshake finished have to wait for `connect` to be sent.
+                if rtmp.session.is_established() && !rtmp.outgoing.is_empty() {
+                    sockets.send(rtmp.socket, std::mem::take(&mut rtmp.outgoing));
+                }
+            }
         }
     }
 
@@ -448,6 +887,7 @@ impl NetConnection<'_> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.set_header(header);
             }
+            NetConnectionProtocol::Rtmp(_) => {}
         }
     }
 }
@@ -459,11 +899,106 @@ pub enum NetConnectionProtocol {
 
     /// Flash Remoting protocol, caused by connecting to a `http://` address.
     FlashRemoting(FlashRemoting),
+
+    /// RTMP, caused by connecting to a `rtmp://` address.
+    Rtmp(Rtmp),
+}
+
+#[derive(Debug)]
+pub struct Rtmp {
+    url: String,
+
+    /// The application name, which is the path and query of the URL.
+    app: String,
+
+    /// The `flashVer` we report to the server, matching `Capabilities.version`.
+    flash_version: String,
+
+    /// The AMF version used for calls, as given by `objectEncoding` when connecting.
+    version: AMFVersion,
+
+    /// Extra arguments given to `NetConnection.connect`, sent along with the `connect` command.
+    connect_arguments: Vec<Rc<AmfValue>>,
+
+    socket: SocketHandle,
+    session: RtmpSession,
+
+    /// Whether the server accepted our `connect` command.
+    connected: bool,
+
+    /// Commands waiting to be written to the socket.
+    outgoing: Vec<u8>,
+
+    next_transaction_id: u32,
+    responders: FnvHashMap<u32, ResponderHandle>,
+}
+
+impl Rtmp {
+    pub fn send(
+        &mut self,
+        command: String,
+        responder_handle: Option<ResponderHandle>,
+        message: AmfValue,
+    ) {
+        // Calls without a responder don't expect an answer, and use a transaction ID of 0.
+        let transaction_id = if let Some(responder_handle) = responder_handle {
+            let transaction_id = self.next_transaction_id;
+            self.next_transaction_id += 1;
+            self.responders.insert(transaction_id, responder_handle);
+            transaction_id
+        } else {
+            0
+        };
+
+        let mut arguments = vec![Rc::new(AmfValue::Null)];
+        if let AmfValue::StrictArray(_, values) = message {
+            arguments.extend(values);
+        }
+        self.session.write_command(
+            &mut self.outgoing,
+            self.version,
+            0,
+            &command,
+            transaction_id.into(),
+            &arguments,
+        );
+    }
+
+    fn write_connect(&self, out: &mut Vec<u8>) {
+        let object_encoding = match self.version {
+            AMFVersion::AMF0 => 0.0,
+            AMFVersion::AMF3 => 3.0,
+        };
+        let property = |name: &str, value: AmfValue| Element::new(name, Rc::new(value));
+        let command_object = AmfValue::Object(
+            ObjectId::INVALID,
+            vec![
+                property("app", AmfValue::String(self.app.clone())),
+                property("flashVer", AmfValue::String(self.flash_version.clone())),
+                property("tcUrl", AmfValue::String(self.url.clone())),
+                property("fpad", AmfValue::Bool(false)),
+                property("capabilities", AmfValue::Number(239.0)),
+                property("audioCodecs", AmfValue::Number(3575.0)),
+                property("videoCodecs", AmfValue::Number(252.0)),
+                property("videoFunction", AmfValue::Number(1.0)),
+                property("objectEncoding", AmfValue::Number(object_encoding)),
+            ],
+            None,
+        );
+
+        let mut arguments = vec![Rc::new(command_object)];
+        arguments.extend(self.connect_arguments.iter().cloned());
+        self.session
+            .write_command(out, AMFVersion::AMF0, 0, "connect", 1.0, &arguments);
+    }
 }
 
 #[derive(Debug)]
 pub struct FlashRemoting {
     url: String,
+    /// The AMF version written in the packet envelope, which follows the `objectEncoding` of
+    /// the connection at the time it was opened.
+    version: AMFVersion,
     headers: Vec<Header>,
     outgoing_queue: Vec<(Message, Option<ResponderHandle>)>,
 }
@@ -505,7 +1040,7 @@ impl FlashRemoting {
         let queue = std::mem::take(&mut self.outgoing_queue);
         let (messages, responder_handles): (Vec<_>, Vec<_>) = queue.into_iter().unzip();
         let packet = Packet {
-            version: AMFVersion::AMF0,
+            version: self.version,
             headers: self.headers.clone(),
             messages,
         };
@@ -518,7 +1053,7 @@ impl FlashRemoting {
             let bytes = flash_lso::packet::write::write_to_bytes(&packet, true)
                 .expect("Must be able to serialize a packet");
             let request = Request::post(url, Some((bytes, "application/x-amf".to_string())));
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request);
             let response: Result<_, ErrorResponse> = async {
                 let response = fetch.await?;
                 let url = response.url().to_string();
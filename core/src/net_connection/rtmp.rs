@@ -0,0 +1,731 @@
+//! A client implementation of the RTMP protocol, as used by `NetConnection` to talk to
+//! media and chat servers.
+//!
+//! This module only deals with bytes: the caller is responsible for moving data to and from
+//! the socket, and for interpreting the messages that come out of it.
+
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use rand::RngCore;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The default port of RTMP servers.
+pub const DEFAULT_PORT: u16 = 1935;
+
+/// The protocol version we send and expect in the first byte of the handshake.
+const RTMP_VERSION: u8 = 3;
+
+/// The size of the C1/C2 and S1/S2 handshake packets.
+const HANDSHAKE_SIZE: usize = 1536;
+
+/// The chunk size both sides start with, until a `SetChunkSize` message is received.
+const DEFAULT_CHUNK_SIZE: usize = 128;
+
+/// The chunk size we use for outgoing messages.
+const OUTGOING_CHUNK_SIZE: usize = 4096;
+
+/// Timestamps at or above this value are written into the extended timestamp field.
+const EXTENDED_TIMESTAMP: u32 = 0xFFFFFF;
+
+/// Chunk stream used for protocol control messages.
+const CONTROL_CHUNK_STREAM: u8 = 2;
+
+/// Chunk stream used for command messages.
+const COMMAND_CHUNK_STREAM: u8 = 3;
+
+mod message_type {
+    pub const SET_CHUNK_SIZE: u8 = 1;
+    pub const ABORT: u8 = 2;
+    pub const ACKNOWLEDGEMENT: u8 = 3;
+    pub const USER_CONTROL: u8 = 4;
+    pub const WINDOW_ACK_SIZE: u8 = 5;
+    pub const SET_PEER_BANDWIDTH: u8 = 6;
+    pub const AUDIO: u8 = 8;
+    pub const VIDEO: u8 = 9;
+    pub const DATA_AMF3: u8 = 15;
+    pub const COMMAND_AMF3: u8 = 17;
+    pub const DATA_AMF0: u8 = 18;
+    pub const COMMAND_AMF0: u8 = 20;
+}
+
+mod user_control_event {
+    pub const PING_REQUEST: u16 = 6;
+    pub const PING_RESPONSE: u16 = 7;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum HandshakeState {
+    /// We haven't sent C0 and C1 yet.
+    Uninitialized,
+
+    /// We've sent C0 and C1, and are waiting for S0, S1 and S2.
+    AwaitingServerHandshake,
+
+    /// The handshake is over, everything from now on is chunked messages.
+    Established,
+}
+
+/// The kind of media carried by a [`RtmpMessage::Media`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MediaKind {
+    Audio,
+    Video,
+    Data,
+}
+
+/// A message that the owner of a [`RtmpSession`] needs to act upon.
+#[derive(Debug, PartialEq)]
+pub enum RtmpMessage {
+    /// The handshake has finished, and commands can now be sent.
+    HandshakeComplete,
+
+    /// A remote procedure call, or the response to one of ours.
+    Command {
+        stream_id: u32,
+        name: String,
+        transaction_id: f64,
+        arguments: Vec<Rc<AmfValue>>,
+    },
+
+    /// Audio, video or script data belonging to a `NetStream`.
+    Media {
+        stream_id: u32,
+        kind: MediaKind,
+        timestamp: u32,
+        data: Vec<u8>,
+    },
+}
+
+/// The state of a single incoming chunk stream.
+///
+/// Chunks only send the parts of their header that changed since the previous chunk on the
+/// same stream, so we need to remember the rest.
+#[derive(Debug, Default)]
+struct IncomingChunkStream {
+    timestamp: u32,
+    timestamp_delta: u32,
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+    has_extended_timestamp: bool,
+
+    /// The payload of the message currently being received.
+    payload: Vec<u8>,
+}
+
+/// The header fields read from the start of a chunk.
+struct ChunkHeader {
+    chunk_stream_id: u32,
+    format: u8,
+    timestamp: u32,
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+    has_extended_timestamp: bool,
+    header_length: usize,
+}
+
+#[derive(Debug)]
+pub struct RtmpSession {
+    handshake: HandshakeState,
+
+    /// Bytes received from the server that haven't formed a complete chunk yet.
+    read_buffer: Vec<u8>,
+
+    incoming_chunk_size: usize,
+    incoming: HashMap<u32, IncomingChunkStream>,
+
+    /// How many bytes we may receive before we need to send an acknowledgement.
+    window_ack_size: u32,
+    bytes_received: u32,
+    bytes_acknowledged: u32,
+}
+
+impl Default for RtmpSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RtmpSession {
+    pub fn new() -> Self {
+        Self {
+            handshake: HandshakeState::Uninitialized,
+            read_buffer: vec![],
+            incoming_chunk_size: DEFAULT_CHUNK_SIZE,
+            incoming: HashMap::new(),
+            window_ack_size: u32::MAX,
+            bytes_received: 0,
+            bytes_acknowledged: 0,
+        }
+    }
+
+    /// Begin the handshake, returning the C0 and C1 packets to send to the server.
+    pub fn start_handshake(&mut self, rng: &mut impl RngCore) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + HANDSHAKE_SIZE);
+        out.push(RTMP_VERSION);
+        // C1 is a timestamp, four zero bytes and random data. Servers don't validate the
+        // random data in the simple (non-digest) handshake that Flash Player also falls back to.
+        out.extend_from_slice(&[0; 8]);
+        let mut random = [0; HANDSHAKE_SIZE - 8];
+        rng.fill_bytes(&mut random);
+        out.extend_from_slice(&random);
+
+        self.handshake = HandshakeState::AwaitingServerHandshake;
+        out
+    }
+
+    pub fn is_established(&self) -> bool {
+        self.handshake == HandshakeState::Established
+    }
+
+    /// Feed bytes received from the server into the session.
+    ///
+    /// Any bytes that need to be sent back in response (handshake replies, acknowledgements,
+    /// pings) are appended to `out`.
+    pub fn receive(&mut self, data: &[u8], out: &mut Vec<u8>) -> Vec<RtmpMessage> {
+        let mut messages = vec![];
+        self.read_buffer.extend_from_slice(data);
+
+        if self.handshake == HandshakeState::AwaitingServerHandshake {
+            if self.read_buffer.len() < 1 + HANDSHAKE_SIZE * 2 {
+                return messages;
+            }
+            if self.read_buffer[0] != RTMP_VERSION {
+                tracing::warn!(
+                    "RTMP server replied with unexpected version {}",
+                    self.read_buffer[0]
+                );
+            }
+
+            // C2 is an echo of S1. We don't bother validating S2 against C1.
+            out.extend_from_slice(&self.read_buffer[1..1 + HANDSHAKE_SIZE]);
+            write_message(
+                out,
+                CONTROL_CHUNK_STREAM,
+                message_type::SET_CHUNK_SIZE,
+                0,
+                &(OUTGOING_CHUNK_SIZE as u32).to_be_bytes(),
+            );
+            self.read_buffer.drain(..1 + HANDSHAKE_SIZE * 2);
+            self.handshake = HandshakeState::Established;
+            messages.push(RtmpMessage::HandshakeComplete);
+        }
+
+        if self.handshake != HandshakeState::Established {
+            return messages;
+        }
+
+        self.bytes_received = self.bytes_received.wrapping_add(data.len() as u32);
+        if self.bytes_received.wrapping_sub(self.bytes_acknowledged) >= self.window_ack_size {
+            self.bytes_acknowledged = self.bytes_received;
+            write_message(
+                out,
+                CONTROL_CHUNK_STREAM,
+                message_type::ACKNOWLEDGEMENT,
+                0,
+                &self.bytes_received.to_be_bytes(),
+            );
+        }
+
+        let mut position = 0;
+        while let Some(consumed) = self.read_chunk(position, out, &mut messages) {
+            position += consumed;
+        }
+        self.read_buffer.drain(..position);
+
+        messages
+    }
+
+    /// Serialize a command message, such as `connect` or a `NetConnection.call`.
+    ///
+    /// AMF3 commands are still made of AMF0 values, but may contain AMF3 values introduced by
+    /// the AMF0 type switch marker.
+    pub fn write_command(
+        &self,
+        out: &mut Vec<u8>,
+        version: AMFVersion,
+        stream_id: u32,
+        name: &str,
+        transaction_id: f64,
+        arguments: &[Rc<AmfValue>],
+    ) {
+        let (type_id, mut payload) = match version {
+            AMFVersion::AMF0 => (message_type::COMMAND_AMF0, vec![]),
+            AMFVersion::AMF3 => (message_type::COMMAND_AMF3, vec![0]),
+        };
+        payload.extend(write_amf0_value(&AmfValue::String(name.to_string())));
+        payload.extend(write_amf0_value(&AmfValue::Number(transaction_id)));
+        for argument in arguments {
+            payload.extend(write_amf0_value(argument));
+        }
+        write_message(out, COMMAND_CHUNK_STREAM, type_id, stream_id, &payload);
+    }
+
+    /// Try to read a single chunk starting at `position` in the read buffer.
+    ///
+    /// Returns how many bytes were consumed, or `None` if the chunk isn't complete yet.
+    fn read_chunk(
+        &mut self,
+        position: usize,
+        out: &mut Vec<u8>,
+        messages: &mut Vec<RtmpMessage>,
+    ) -> Option<usize> {
+        let data = &self.read_buffer[position..];
+        let header = self.read_chunk_header(data)?;
+
+        let stream = self.incoming.entry(header.chunk_stream_id).or_default();
+        // Type 0 and 1 headers always begin a new message, even if the previous one was
+        // never completed.
+        let starts_message = header.format <= 1 || stream.payload.is_empty();
+        let (length, received) = if header.format <= 1 {
+            (header.length, 0)
+        } else {
+            (stream.length, stream.payload.len())
+        };
+        let chunk_length = length
+            .saturating_sub(received)
+            .min(self.incoming_chunk_size);
+        if data.len() < header.header_length + chunk_length {
+            return None;
+        }
+        if header.format <= 1 {
+            stream.payload.clear();
+        }
+
+        match header.format {
+            0 => {
+                stream.timestamp = header.timestamp;
+                stream.timestamp_delta = 0;
+                stream.length = header.length;
+                stream.type_id = header.type_id;
+                stream.stream_id = header.stream_id;
+            }
+            1 => {
+                stream.timestamp_delta = header.timestamp;
+                stream.length = header.length;
+                stream.type_id = header.type_id;
+            }
+            2 => stream.timestamp_delta = header.timestamp,
+            _ => {}
+        }
+        if header.format <= 2 {
+            stream.has_extended_timestamp = header.has_extended_timestamp;
+        }
+        if starts_message && header.format != 0 {
+            stream.timestamp = stream.timestamp.wrapping_add(stream.timestamp_delta);
+        }
+
+        let start = header.header_length;
+        stream
+            .payload
+            .extend_from_slice(&data[start..start + chunk_length]);
+
+        if stream.payload.len() >= stream.length {
+            let type_id = stream.type_id;
+            let stream_id = stream.stream_id;
+            let timestamp = stream.timestamp;
+            let payload = std::mem::take(&mut stream.payload);
+            self.handle_message(type_id, stream_id, timestamp, payload, out, messages);
+        }
+
+        Some(header.header_length + chunk_length)
+    }
+
+    fn read_chunk_header(&self, data: &[u8]) -> Option<ChunkHeader> {
+        let first = *data.first()?;
+        let format = first >> 6;
+        let (chunk_stream_id, mut position) = match first & 0x3F {
+            0 => (64 + u32::from(*data.get(1)?), 2),
+            1 => {
+                let bytes = data.get(1..3)?;
+                (64 + u32::from(bytes[0]) + u32::from(bytes[1]) * 256, 3)
+            }
+            id => (u32::from(id), 1),
+        };
+
+        let mut timestamp = 0;
+        let mut length = 0;
+        let mut type_id = 0;
+        let mut stream_id = 0;
+        if format <= 2 {
+            timestamp = read_u24(data.get(position..position + 3)?);
+            position += 3;
+        }
+        if format <= 1 {
+            length = read_u24(data.get(position..position + 3)?) as usize;
+            type_id = *data.get(position + 3)?;
+            position += 4;
+        }
+        if format == 0 {
+            let bytes = data.get(position..position + 4)?;
+            // The message stream ID is the only little-endian field in the protocol.
+            stream_id = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            position += 4;
+        }
+
+        let has_extended_timestamp = if format <= 2 {
+            timestamp == EXTENDED_TIMESTAMP
+        } else {
+            self.incoming
+                .get(&chunk_stream_id)
+                .is_some_and(|stream| stream.has_extended_timestamp)
+        };
+        if has_extended_timestamp {
+            let bytes = data.get(position..position + 4)?;
+            let extended = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            if format <= 2 {
+                timestamp = extended;
+            }
+            position += 4;
+        }
+
+        Some(ChunkHeader {
+            chunk_stream_id,
+            format,
+            timestamp,
+            length,
+            type_id,
+            stream_id,
+            has_extended_timestamp,
+            header_length: position,
+        })
+    }
+
+    fn handle_message(
+        &mut self,
+        type_id: u8,
+        stream_id: u32,
+        timestamp: u32,
+        payload: Vec<u8>,
+        out: &mut Vec<u8>,
+        messages: &mut Vec<RtmpMessage>,
+    ) {
+        match type_id {
+            message_type::SET_CHUNK_SIZE => {
+                if let Some(size) = read_u32(&payload) {
+                    self.incoming_chunk_size = (size & 0x7FFFFFFF).max(1) as usize;
+                }
+            }
+            message_type::ABORT => {
+                if let Some(id) = read_u32(&payload) {
+                    if let Some(stream) = self.incoming.get_mut(&id) {
+                        stream.payload.clear();
+                    }
+                }
+            }
+            message_type::ACKNOWLEDGEMENT => {}
+            message_type::USER_CONTROL => {
+                if payload.len() >= 6
+                    && u16::from_be_bytes([payload[0], payload[1]])
+                        == user_control_event::PING_REQUEST
+                {
+                    let mut response = user_control_event::PING_RESPONSE.to_be_bytes().to_vec();
+                    response.extend_from_slice(&payload[2..6]);
+                    write_message(
+                        out,
+                        CONTROL_CHUNK_STREAM,
+                        message_type::USER_CONTROL,
+                        0,
+                        &response,
+                    );
+                }
+            }
+            message_type::WINDOW_ACK_SIZE => {
+                if let Some(size) = read_u32(&payload) {
+                    self.window_ack_size = size;
+                }
+            }
+            message_type::SET_PEER_BANDWIDTH => {
+                if let Some(size) = read_u32(&payload) {
+                    write_message(
+                        out,
+                        CONTROL_CHUNK_STREAM,
+                        message_type::WINDOW_ACK_SIZE,
+                        0,
+                        &size.to_be_bytes(),
+                    );
+                }
+            }
+            message_type::AUDIO => messages.push(RtmpMessage::Media {
+                stream_id,
+                kind: MediaKind::Audio,
+                timestamp,
+                data: payload,
+            }),
+            message_type::VIDEO => messages.push(RtmpMessage::Media {
+                stream_id,
+                kind: MediaKind::Video,
+                timestamp,
+                data: payload,
+            }),
+            message_type::DATA_AMF0 => messages.push(RtmpMessage::Media {
+                stream_id,
+                kind: MediaKind::Data,
+                timestamp,
+                data: payload,
+            }),
+            message_type::DATA_AMF3 => messages.push(RtmpMessage::Media {
+                stream_id,
+                kind: MediaKind::Data,
+                timestamp,
+                // Like AMF3 commands, these are AMF0 values after a single format byte.
+                data: payload.get(1..).unwrap_or_default().to_vec(),
+            }),
+            message_type::COMMAND_AMF0 | message_type::COMMAND_AMF3 => {
+                // AMF3 commands are AMF0 values prefixed by a single format byte; any AMF3
+                // data within them is introduced by the AMF0 type switch marker.
+                let body = if type_id == message_type::COMMAND_AMF3 {
+                    payload.get(1..).unwrap_or_default()
+                } else {
+                    &payload[..]
+                };
+                let mut values = read_amf0_values(body).into_iter();
+                let name = match values.next().as_deref() {
+                    Some(AmfValue::String(name)) => name.clone(),
+                    _ => {
+                        tracing::warn!("Ignoring RTMP command without a name");
+                        return;
+                    }
+                };
+                let transaction_id = match values.next().as_deref() {
+                    Some(AmfValue::Number(id)) => *id,
+                    _ => 0.0,
+                };
+                messages.push(RtmpMessage::Command {
+                    stream_id,
+                    name,
+                    transaction_id,
+                    arguments: values.collect(),
+                });
+            }
+            _ => tracing::warn!("Ignoring unknown RTMP message type {type_id}"),
+        }
+    }
+}
+
+/// The header of the FLV file that the media of a `NetStream` is rebuilt into, followed by
+/// the size of the (nonexistent) tag before the first one.
+pub const FLV_HEADER: [u8; 13] = [b'F', b'L', b'V', 1, 0x05, 0, 0, 0, 9, 0, 0, 0, 0];
+
+/// Turn a media message into an FLV tag, followed by the size of that tag.
+///
+/// RTMP media messages carry exactly what the body of an FLV tag would, so only the tag
+/// header needs to be added.
+pub fn flv_tag(kind: MediaKind, timestamp: u32, data: &[u8]) -> Vec<u8> {
+    let tag_type = match kind {
+        MediaKind::Audio => message_type::AUDIO,
+        MediaKind::Video => message_type::VIDEO,
+        MediaKind::Data => message_type::DATA_AMF0,
+    };
+    let mut tag = Vec::with_capacity(11 + data.len() + 4);
+    tag.push(tag_type);
+    tag.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+    // The lower 24 bits of the timestamp come first, followed by the upper 8 bits.
+    tag.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+    tag.push((timestamp >> 24) as u8);
+    tag.extend_from_slice(&[0; 3]); // stream ID
+    tag.extend_from_slice(data);
+    tag.extend_from_slice(&(11 + data.len() as u32).to_be_bytes());
+    tag
+}
+
+/// Write a message as a series of chunks, using a full header for the first chunk.
+fn write_message(
+    out: &mut Vec<u8>,
+    chunk_stream_id: u8,
+    type_id: u8,
+    stream_id: u32,
+    payload: &[u8],
+) {
+    out.push(chunk_stream_id & 0x3F);
+    out.extend_from_slice(&[0; 3]); // timestamp
+    out.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+    out.push(type_id);
+    out.extend_from_slice(&stream_id.to_le_bytes());
+
+    for (i, chunk) in payload.chunks(OUTGOING_CHUNK_SIZE).enumerate() {
+        if i > 0 {
+            out.push(0xC0 | (chunk_stream_id & 0x3F));
+        }
+        out.extend_from_slice(chunk);
+    }
+}
+
+fn read_u24(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+}
+
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+    let bytes = bytes.get(..4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_amf0_values(mut data: &[u8]) -> Vec<Rc<AmfValue>> {
+    let mut values = vec![];
+    let mut decoder = AMF0Decoder::default();
+    while !data.is_empty() {
+        match decoder.parse_single_element(data) {
+            Ok((rest, value)) => {
+                values.push(Rc::new(value));
+                data = rest;
+            }
+            Err(_) => {
+                tracing::warn!("Failed to parse AMF0 value in RTMP command");
+                break;
+            }
+        }
+    }
+    values
+}
+
+fn write_amf0_value(value: &AmfValue) -> Vec<u8> {
+    let element = Element::new("", Rc::new(value.clone()));
+    let mut lso = Lso::new(vec![element], "", AMFVersion::AMF0);
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+    // Strip the LSO header, the empty element name and the trailing padding, leaving just the value.
+    let start = flash_lso::write::header_length(&lso.header) + 8;
+    bytes
+        .get(start..bytes.len().saturating_sub(1))
+        .map(|value| value.to_vec())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn established_session() -> RtmpSession {
+        let mut session = RtmpSession::new();
+        session.handshake = HandshakeState::Established;
+        session
+    }
+
+    #[test]
+    fn handshake_echoes_s1() {
+        let mut session = RtmpSession::new();
+        let hello = session.start_handshake(&mut rand::rngs::mock::StepRng::new(0, 1));
+        assert_eq!(hello.len(), 1 + HANDSHAKE_SIZE);
+        assert_eq!(hello[0], RTMP_VERSION);
+
+        let mut server = vec![RTMP_VERSION];
+        server.extend_from_slice(&[7; HANDSHAKE_SIZE]);
+        server.extend_from_slice(&[9; HANDSHAKE_SIZE]);
+
+        let mut out = vec![];
+        // The handshake should wait until S0, S1 and S2 have all arrived.
+        assert!(session.receive(&server[..100], &mut out).is_empty());
+        assert!(out.is_empty());
+
+        let messages = session.receive(&server[100..], &mut out);
+        assert_eq!(messages, vec![RtmpMessage::HandshakeComplete]);
+        assert_eq!(&out[..HANDSHAKE_SIZE], &server[1..1 + HANDSHAKE_SIZE]);
+        assert!(session.is_established());
+    }
+
+    #[test]
+    fn reassembles_chunked_messages() {
+        let payload: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+        let mut written = vec![];
+        write_message(&mut written, 4, message_type::VIDEO, 1, &payload);
+
+        // The server hasn't changed its chunk size, so split the message to match.
+        let mut data = written[..12].to_vec();
+        for (i, chunk) in payload.chunks(DEFAULT_CHUNK_SIZE).enumerate() {
+            if i > 0 {
+                data.push(0xC4);
+            }
+            data.extend_from_slice(chunk);
+        }
+
+        let mut session = established_session();
+        let mut out = vec![];
+        let mut messages = vec![];
+        for byte in data {
+            messages.extend(session.receive(&[byte], &mut out));
+        }
+
+        assert_eq!(
+            messages,
+            vec![RtmpMessage::Media {
+                stream_id: 1,
+                kind: MediaKind::Video,
+                timestamp: 0,
+                data: payload,
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_commands() {
+        let arguments = vec![
+            Rc::new(AmfValue::Null),
+            Rc::new(AmfValue::String("Hello".to_string())),
+            Rc::new(AmfValue::Number(12.5)),
+        ];
+        let mut data = vec![];
+        let session = established_session();
+        session.write_command(&mut data, AMFVersion::AMF0, 0, "test", 2.0, &arguments);
+
+        let mut session = established_session();
+        let mut out = vec![];
+        assert_eq!(
+            session.receive(&data, &mut out),
+            vec![RtmpMessage::Command {
+                stream_id: 0,
+                name: "test".to_string(),
+                transaction_id: 2.0,
+                arguments,
+            }]
+        );
+    }
+
+    #[test]
+    fn builds_flv_tags() {
+        let tag = flv_tag(MediaKind::Audio, 0x12345678, &[0xAF, 1, 2]);
+        assert_eq!(
+            tag,
+            vec![8, 0, 0, 3, 0x34, 0x56, 0x78, 0x12, 0, 0, 0, 0xAF, 1, 2, 0, 0, 0, 14]
+        );
+
+        let mut flv = FLV_HEADER.to_vec();
+        flv.extend_from_slice(&tag);
+        let mut reader = flv_rs::FlvReader::from_source(&flv);
+        assert!(flv_rs::Header::parse(&mut reader).is_ok());
+        assert!(matches!(
+            flv_rs::Tag::parse(&mut reader),
+            Ok(flv_rs::Tag {
+                timestamp: 0x12345678,
+                data: flv_rs::TagData::Audio(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn responds_to_ping() {
+        let mut data = vec![];
+        write_message(
+            &mut data,
+            CONTROL_CHUNK_STREAM,
+            message_type::USER_CONTROL,
+            0,
+            &[0, 6, 1, 2, 3, 4],
+        );
+
+        let mut session = established_session();
+        let mut out = vec![];
+        assert!(session.receive(&data, &mut out).is_empty());
+
+        let mut expected = vec![];
+        write_message(
+            &mut expected,
+            CONTROL_CHUNK_STREAM,
+            message_type::USER_CONTROL,
+            0,
+            &[0, 7, 1, 2, 3, 4],
+        );
+        assert_eq!(out, expected);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::accessibility;
+use crate::adaptive_quality::AdaptiveQuality;
 use crate::avm1::Attribute;
 use crate::avm1::Avm1;
 use crate::avm1::Object;
@@ -5,53 +7,79 @@ use crate::avm1::SystemProperties;
 use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{TObject, Value};
-use crate::avm2::{Activation as Avm2Activation, Avm2, CallStack, Object as Avm2Object};
-use crate::backend::ui::FontDefinition;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, CallStack, Debugger as Avm2Debugger,
+    EventObject as Avm2EventObject, Object as Avm2Object,
+};
+use crate::backend::ui::{
+    AccessibilityNode, FontDefinition, NativeWindowId, RootMovieLoadPhase, WebViewEvent, WebViewId,
+};
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
-    log::LogBackend,
-    navigator::{NavigatorBackend, Request},
+    camera::CameraBackend,
+    debugger::DebuggerBackend,
+    jit::JitBackend,
+    local_connection::LocalConnectionBackend,
+    log::{LogBackend, TraceOutput},
+    microphone::MicrophoneBackend,
+    navigator::{ErrorResponse, NavigatorBackend, OwnedFuture, Request, SuccessResponse},
     storage::StorageBackend,
     ui::{MouseCursor, UiBackend},
 };
+use crate::bitmap::readback::{BitmapReadbackMode, BitmapReadbacks};
 use crate::compatibility_rules::CompatibilityRules;
-use crate::config::Letterbox;
+use crate::config::{Letterbox, PolicyFileMode};
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::custom_cursor::CustomCursors;
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
     EditText, InteractiveObject, Stage, StageAlign, StageDisplayState, StageScaleMode,
     TInteractiveObject, WindowMode,
 };
 use crate::events::GamepadButton;
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
+use crate::events::{
+    ButtonKeyCode, ClipEvent, ClipEventResult, ImeEvent, KeyCode, MouseButton, PlayerEvent,
+    TextControlCode, TouchEvent,
+};
 use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsCommandProvider};
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::NavigationDirection;
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
-use crate::input::InputManager;
+use crate::gc_scheduler::{GcScheduler, GcStats};
+use crate::input::{InputManager, LowLatencyInputError};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
-use crate::loader::{LoadBehavior, LoadManager};
+use crate::loader::{Error as LoadError, LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
+use crate::multitouch::{self, TouchData};
+use crate::native_window::{NativeWindows, DEFAULT_MAX_NATIVE_WINDOWS};
 use crate::net_connection::NetConnections;
+use crate::policy_file::PolicyFiles;
 use crate::prelude::*;
+use crate::printing::PrintJob;
+use crate::profiler::{Profile, Profiler};
+use crate::replay::{Replay, ReplayMode, ReplaySession};
+use crate::rsl::{RslCache, RslMirror};
+use crate::sandbox::LoadAllowList;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::StringContext;
-use crate::string::{AvmString, AvmStringInterner};
+use crate::string::{AvmString, AvmStringInterner, WString};
 use crate::stub::StubCollection;
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
+use crate::web_view::WebViews;
 use crate::DefaultFont;
 use gc_arena::lock::GcRefLock;
 use gc_arena::{Collect, DynamicRootSet, Mutation, Rootable};
 use rand::{rngs::SmallRng, SeedableRng};
 use ruffle_render::backend::{null::NullRenderer, RenderBackend, ViewportDimensions};
+use ruffle_render::bitmap::Bitmap;
 use ruffle_render::commands::CommandList;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::TransformStack;
@@ -64,12 +92,22 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use tracing::instrument;
+use url::Url;
 use web_time::Instant;
 
 /// The newest known Flash Player version, serves as a default to
 /// `player_version`.
 pub const NEWEST_PLAYER_VERSION: u8 = 32;
 
+/// How long a tick may spend playing back a save state that is being loaded.
+///
+/// This keeps the page or window responsive while a long save state loads.
+const SAVE_STATE_TIME_PER_TICK: Duration = Duration::from_millis(50);
+
+/// How long an input micro-tick may spend handling events, before the rest wait for the next
+/// micro-tick or frame. See `Player::run_input_micro_tick`.
+const INPUT_MICRO_TICK_BUDGET: Duration = Duration::from_millis(4);
+
 #[cfg(feature = "default_font")]
 pub const FALLBACK_DEVICE_FONT_TAG: &[u8] = include_bytes!("../assets/noto-sans-definefont3.bin");
 
@@ -110,6 +148,18 @@ pub struct MouseData<'gc> {
     pub pressed: Option<InteractiveObject<'gc>>,
     pub right_pressed: Option<InteractiveObject<'gc>>,
     pub middle_pressed: Option<InteractiveObject<'gc>>,
+
+    /// The cursors chosen and registered by the movie through `flash.ui.Mouse`.
+    #[collect(require_static)]
+    pub cursors: CustomCursors,
+
+    /// The fingers touching the stage, and the gestures they're making.
+    pub touches: TouchData<'gc>,
+
+    /// How far the mouse moved with the last mouse event, in stage pixels.
+    /// This is reported as `MouseEvent.movementX` and `movementY`.
+    #[collect(require_static)]
+    pub movement: (f64, f64),
 }
 
 impl<'gc> MouseData<'gc> {
@@ -193,6 +243,15 @@ struct GcRootData<'gc> {
     dynamic_root: DynamicRootSet<'gc>,
 
     post_frame_callbacks: Vec<PostFrameCallback<'gc>>,
+
+    /// Pending GPU -> CPU readbacks of `BitmapData` pixels.
+    bitmap_readbacks: BitmapReadbacks<'gc>,
+
+    /// Additional `NativeWindow`s opened by AIR content.
+    native_windows: NativeWindows<'gc>,
+
+    /// `StageWebView`s created by AIR content.
+    web_views: WebViews<'gc>,
 }
 
 #[derive(Collect)]
@@ -233,6 +292,9 @@ impl<'gc> GcRootData<'gc> {
         &mut Vec<PostFrameCallback<'gc>>,
         &mut MouseData<'gc>,
         DynamicRootSet<'gc>,
+        &mut BitmapReadbacks<'gc>,
+        &mut NativeWindows<'gc>,
+        &mut WebViews<'gc>,
     ) {
         (
             self.stage,
@@ -257,6 +319,9 @@ impl<'gc> GcRootData<'gc> {
             &mut self.post_frame_callbacks,
             &mut self.mouse_data,
             self.dynamic_root,
+            &mut self.bitmap_readbacks,
+            &mut self.native_windows,
+            &mut self.web_views,
         )
     }
 }
@@ -264,10 +329,13 @@ impl<'gc> GcRootData<'gc> {
 type GcArena = gc_arena::Arena<Rootable![GcRoot<'_>]>;
 
 type Audio = Box<dyn AudioBackend>;
+type Camera = Box<dyn CameraBackend>;
+type Debugger = Box<dyn DebuggerBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Log = Box<dyn LogBackend>;
+type Microphone = Box<dyn MicrophoneBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
 
@@ -275,7 +343,21 @@ type Video = Box<dyn VideoBackend>;
 enum RunState {
     Playing,
     Suspended,
-    Stepping,
+    /// Running the given number of frames, then suspending.
+    Stepping(u32),
+}
+
+/// An error from `Player::goto_frame`.
+#[derive(Debug, thiserror::Error)]
+pub enum GotoFrameError {
+    #[error("No movie is loaded")]
+    NoMovie,
+
+    #[error("Unknown scene \"{0}\"")]
+    UnknownScene(String),
+
+    #[error("Frame {0} does not exist")]
+    UnknownFrame(u16),
 }
 
 pub struct Player {
@@ -305,7 +387,11 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    camera: Camera,
+    microphone: Microphone,
+    debugger: Debugger,
     log: Log,
+    trace_outputs: Vec<Box<dyn TraceOutput>>,
     ui: Ui,
     video: Video,
 
@@ -323,6 +409,10 @@ pub struct Player {
 
     stub_tracker: StubCollection,
 
+    profiler: Profiler,
+
+    gc_scheduler: GcScheduler,
+
     /// A time budget for executing frames.
     /// Gained by passage of time between host frames, spent by executing SWF frames.
     /// This is how we support custom SWF framerates
@@ -333,15 +423,47 @@ pub struct Player {
     /// Faked time passage for fooling hand-written busy-loop FPS limiters.
     time_offset: u32,
 
+    /// The replay being recorded or played back, if any. See `crate::replay`.
+    replay: Option<ReplaySession>,
+
     input: InputManager,
 
+    /// Whether input is handled in low-latency mode. See `set_low_latency_input`.
+    low_latency_input: bool,
+
+    /// Whether low-latency input stays enabled for ActionScript 3 movies.
+    force_low_latency_input: bool,
+
+    /// Whether the audio backend was last told to output sound with low latency,
+    /// or `None` if it hasn't been told yet.
+    low_latency_audio: Option<bool>,
+
+    /// Lowers the rendering quality while frames take too long. See `set_adaptive_quality`.
+    adaptive_quality: AdaptiveQuality,
+
+    /// How long the last call to `render` took, in milliseconds.
+    last_render_time: f64,
+
+    /// The stage as last described to assistive technologies.
+    accessibility_tree: Vec<AccessibilityNode>,
+
+    /// The view matrix of the stage when it was last described to assistive technologies,
+    /// or `None` if they weren't following the movie then.
+    accessibility_view_matrix: Option<Matrix>,
+
+    /// The print job started by the movie, if it wasn't sent yet.
+    print_job: Option<PrintJob>,
+
     mouse_in_stage: bool,
     mouse_position: Point<Twips>,
 
-    /// The current mouse cursor icon.
+    /// The mouse cursor icon chosen by what's under the mouse.
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
+    /// The mouse cursor icon that is displayed, which the movie may have forced with `Mouse.cursor`.
+    displayed_cursor: MouseCursor,
+
     system: SystemProperties,
 
     page_url: Option<String>,
@@ -380,6 +502,18 @@ pub struct Player {
     /// Any compatibility rules to apply for this movie.
     compatibility_rules: CompatibilityRules,
 
+    /// Where the movie may load from, if the embedder restricted it.
+    load_allow_list: Option<LoadAllowList>,
+
+    /// The cross-domain policy files that were fetched for the movie.
+    policy_files: PolicyFiles,
+
+    /// Where Adobe's signed RSLs are loaded from instead of Adobe's servers, if anywhere.
+    rsl_mirror: Option<RslMirror>,
+
+    /// The signed RSLs that have been loaded so far.
+    rsl_cache: RslCache,
+
     /// Debug UI windows
     #[cfg(feature = "egui")]
     debug_ui: Rc<RefCell<crate::debug_ui::DebugUi>>,
@@ -504,7 +638,13 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: f64) {
-        if self.is_playing() {
+        self.present_native_windows();
+
+        if self.replay.is_some() {
+            if self.is_playing() {
+                self.tick_replay(dt);
+            }
+        } else if self.is_playing() {
             self.frame_accumulator += dt;
             let frame_time = self.frame_time(1000.0);
 
@@ -517,6 +657,7 @@ impl Player {
                 let elapsed = timer.elapsed().as_millis() as f64;
 
                 self.add_frame_timing(elapsed);
+                self.update_adaptive_quality(elapsed);
 
                 self.frame_accumulator -= frame_time;
                 frame += 1;
@@ -528,10 +669,13 @@ impl Player {
                     self.frame_accumulator -= self.time_offset as f64;
                 }
 
-                // If we are stepping a single frame, immediately suspend ourselves.
-                if self.run_state == RunState::Stepping {
-                    self.set_run_state(RunState::Suspended);
-                    break;
+                // If we are stepping and ran the last frame, immediately suspend ourselves.
+                if let RunState::Stepping(remaining) = self.run_state {
+                    if remaining <= 1 {
+                        self.set_run_state(RunState::Suspended);
+                        break;
+                    }
+                    self.run_state = RunState::Stepping(remaining - 1);
                 }
             }
 
@@ -544,6 +688,10 @@ impl Player {
             // so timer callbacks won't get cancelled/delayed.
             self.time_offset = 0;
 
+            if frame > 0 {
+                self.update_accessibility_tree();
+            }
+
             // Sanity: If we had too many frames to tick, just reset the accumulator
             // to prevent running at turbo speed.
             if self.frame_accumulator >= frame_time {
@@ -562,12 +710,145 @@ impl Player {
             self.update_sockets();
             self.update_net_connections();
             self.update_timers(dt);
+            self.update_displayed_cursor(dt);
             self.update(|context| {
                 StreamManager::tick(context, dt);
+                AudioManager::update_dynamic_sounds(context);
             });
             self.audio.tick();
         }
+
+        self.update_low_latency_input();
+    }
+
+    /// Whether input is currently handled in low-latency mode.
+    pub fn low_latency_input(&self) -> bool {
+        self.low_latency_input
+    }
+
+    /// Enables or disables low-latency input, for content such as instruments and rhythm games.
+    ///
+    /// In low-latency mode, frontends deliver input between frames with `run_input_micro_tick`,
+    /// which passes sounds started by event handlers on to the audio backend right away. The
+    /// audio backend is additionally asked to output sound with as little latency as possible,
+    /// so that those sounds are heard right away too.
+    ///
+    /// This is refused for ActionScript 3 movies unless `force` is set, as they may rely on
+    /// input events being dispatched in frame order. If an ActionScript 3 movie is loaded later,
+    /// the mode is disabled again.
+    pub fn set_low_latency_input(
+        &mut self,
+        enabled: bool,
+        force: bool,
+    ) -> Result<(), LowLatencyInputError> {
+        if enabled && !force && self.swf.is_action_script_3() {
+            return Err(LowLatencyInputError::FramePhaseOrdering);
+        }
+
+        self.low_latency_input = enabled;
+        self.force_low_latency_input = force;
+        self.update_low_latency_input();
+        Ok(())
+    }
+
+    /// Disables low-latency input if the current movie does not support it,
+    /// and tells the audio backend if its latency requirements changed.
+    fn update_low_latency_input(&mut self) {
+        if self.low_latency_input && !self.force_low_latency_input && self.swf.is_action_script_3()
+        {
+            tracing::error!("{}", LowLatencyInputError::FramePhaseOrdering);
+            self.low_latency_input = false;
+        }
+
+        if self.low_latency_audio != Some(self.low_latency_input) {
+            self.low_latency_audio = Some(self.low_latency_input);
+            self.audio.set_low_latency(self.low_latency_input);
+        }
     }
+
+    /// Handles input that arrived between frames in an "input micro-tick", for low-latency
+    /// input mode.
+    ///
+    /// Only the movie's event handlers run, not its frame scripts. Changes they made to
+    /// playing sounds are passed on to the audio backend right away, rather than on the next
+    /// frame. The micro-tick is bounded: once it has run for `INPUT_MICRO_TICK_BUDGET`, the
+    /// remaining events are left in `events` for the next micro-tick or frame.
+    ///
+    /// Returns whether the movie handled any of the events.
+    pub fn run_input_micro_tick(&mut self, events: &mut VecDeque<PlayerEvent>) -> bool {
+        let start = Instant::now();
+        let mut handled = false;
+        while let Some(event) = events.pop_front() {
+            handled |= self.handle_event(event);
+            if start.elapsed() >= INPUT_MICRO_TICK_BUDGET {
+                break;
+            }
+        }
+
+        self.mutate_with_update_context(|context| {
+            context.audio_manager.update_sound_transforms(context.audio);
+        });
+        self.audio.tick();
+        handled
+    }
+
+    /// Whether the rendering quality is lowered while frames take too long.
+    pub fn adaptive_quality(&self) -> bool {
+        self.adaptive_quality.is_enabled()
+    }
+
+    /// Enables or disables adaptive quality.
+    ///
+    /// When enabled, the rendering quality is lowered a step at a time while running and
+    /// rendering frames consistently takes longer than the movie's frame rate allows, and
+    /// restored once there's time to spare again. Movies still see the quality they set.
+    pub fn set_adaptive_quality(&mut self, enabled: bool) {
+        let was_enabled = self.adaptive_quality.is_enabled();
+        self.adaptive_quality.set_enabled(enabled);
+        if was_enabled && !enabled {
+            let quality = self.enter_arena(|_, gc_root, _| gc_root.stage.quality());
+            self.renderer.set_quality(quality);
+        }
+    }
+
+    /// Lowers or restores the rendering quality, given how long the last frame took to run.
+    fn update_adaptive_quality(&mut self, run_frame_time: f64) {
+        if !self.adaptive_quality.is_enabled() {
+            return;
+        }
+
+        let frame_time = self.frame_time(1000.0);
+        self.adaptive_quality
+            .record_frame(run_frame_time + self.last_render_time, frame_time);
+
+        let stage_quality = self.enter_arena(|_, gc_root, _| gc_root.stage.quality());
+        if let Some(quality) = self.adaptive_quality.quality_change(stage_quality) {
+            self.renderer.set_quality(quality);
+        }
+    }
+
+    /// Describes the stage to assistive technologies again, if the UI reports any and the
+    /// description changed.
+    fn update_accessibility_tree(&mut self) {
+        if !self.ui.is_accessibility_active() {
+            self.accessibility_view_matrix = None;
+            return;
+        }
+
+        // Resizing the stage moves everything on it, without invalidating any object.
+        let view_matrix = self.enter_arena(|_, gc_root, _| gc_root.stage.view_matrix());
+        let force = self.accessibility_view_matrix != Some(view_matrix);
+        self.accessibility_view_matrix = Some(view_matrix);
+
+        let tree = self.mutate_with_update_context(|context| {
+            accessibility::updated_accessibility_tree(context, force)
+        });
+        if let Some(tree) = tree.filter(|tree| *tree != self.accessibility_tree) {
+            self.ui.update_accessibility_tree(&tree);
+            self.accessibility_tree = tree;
+        }
+    }
+
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
     }
@@ -595,7 +876,7 @@ impl Player {
 
     pub fn is_playing(&self) -> bool {
         match self.run_state {
-            RunState::Playing | RunState::Stepping => true,
+            RunState::Playing | RunState::Stepping(_) => true,
             RunState::Suspended => false,
         }
     }
@@ -749,6 +1030,9 @@ impl Player {
                         context.stage.set_quality(context, StageQuality::High)
                     }
                     ContextMenuCallback::TextControl { code, text } => {
+                        if *code == TextControlCode::Paste {
+                            Self::dispatch_paste_event(context);
+                        }
                         text.text_control_input(*code, context)
                     }
                     _ => {}
@@ -758,6 +1042,30 @@ impl Player {
         });
     }
 
+    /// Dispatches a `paste` event to the object with focus (or the stage),
+    /// whose handlers may then read the clipboard.
+    ///
+    /// Pasting into a text field with focus is the default behavior of this event,
+    /// and happens after it's dispatched.
+    fn dispatch_paste_event<'gc>(context: &mut UpdateContext<'gc>) {
+        let target = context
+            .focus_tracker
+            .get()
+            .map(|o| o.as_displayobject())
+            .unwrap_or_else(|| context.stage.into());
+        if !target.movie().is_action_script_3() {
+            return;
+        }
+        let Avm2Value::Object(target) = target.object2() else {
+            return;
+        };
+
+        let paste_evt = Avm2EventObject::bare_event(context, "paste", true, false);
+        context.avm2.pasting = true;
+        Avm2::dispatch_event(context, paste_evt, target);
+        context.avm2.pasting = false;
+    }
+
     fn run_context_menu_custom_callback<'gc>(
         item: Object<'gc>,
         callback: Object<'gc>,
@@ -870,7 +1178,7 @@ impl Player {
             RunState::Suspended => false,
             // Do not run audio when stepping frame-by-frame,
             // to avoid unpleasant short bursts of sound.
-            RunState::Stepping => false,
+            RunState::Stepping(_) => false,
         };
 
         if play_audio {
@@ -892,7 +1200,46 @@ impl Player {
     }
 
     pub fn suspend_after_next_frame(&mut self) {
-        self.set_run_state(RunState::Stepping);
+        self.suspend_after_frames(1);
+    }
+
+    /// Runs the given number of frames at the movie's frame rate, then suspends the player.
+    ///
+    /// Timers and video advance with the frames, but audio stays paused.
+    pub fn suspend_after_frames(&mut self, frames: u32) {
+        if frames == 0 {
+            self.set_is_playing(false);
+        } else {
+            self.set_run_state(RunState::Stepping(frames));
+        }
+    }
+
+    /// Moves the root movie to the given frame, keeping it playing or stopped as it was.
+    ///
+    /// `frame` is 1-based. When a `scene` is given, it's relative to the start of that scene,
+    /// like in `MovieClip.gotoAndPlay`.
+    pub fn goto_frame(&mut self, frame: u16, scene: Option<&str>) -> Result<(), GotoFrameError> {
+        self.mutate_with_update_context(|context| {
+            let mc = context
+                .stage
+                .root_clip()
+                .and_then(|root| root.as_movie_clip())
+                .ok_or(GotoFrameError::NoMovie)?;
+
+            let scene_start = match scene {
+                Some(scene) => mc
+                    .scene_label_to_number(&WString::from_utf8(scene))
+                    .ok_or_else(|| GotoFrameError::UnknownScene(scene.to_string()))?,
+                None => 1,
+            };
+            let target = frame
+                .checked_add(scene_start.saturating_sub(1))
+                .filter(|target| (1..=mc.total_frames()).contains(target))
+                .ok_or(GotoFrameError::UnknownFrame(frame))?;
+
+            mc.goto_frame(context, target, !mc.playing());
+            Ok(())
+        })
     }
 
     pub fn needs_render(&self) -> bool {
@@ -997,6 +1344,21 @@ impl Player {
     /// Handle an event sent into the player from the external windowing system
     /// or an HTML element.
     pub fn handle_event(&mut self, event: PlayerEvent) -> bool {
+        if let Some(replay) = &mut self.replay {
+            match replay.mode() {
+                ReplayMode::Recording => replay.record(event),
+                // The replay's input takes the place of the user's.
+                ReplayMode::Playing => return false,
+            }
+        }
+        self.dispatch_event(event)
+    }
+
+    fn dispatch_event(&mut self, event: PlayerEvent) -> bool {
+        if Self::is_pointer_event(event) {
+            self.set_pointer_window(None);
+        }
+
         match event {
             PlayerEvent::FocusGained | PlayerEvent::FocusLost => self.handle_focus_event(event),
             PlayerEvent::KeyDown { .. }
@@ -1005,6 +1367,7 @@ impl Player {
             | PlayerEvent::MouseUp { .. }
             | PlayerEvent::MouseDown { .. }
             | PlayerEvent::MouseLeave
+            | PlayerEvent::MouseMotion { .. }
             | PlayerEvent::MouseWheel { .. }
             | PlayerEvent::GamepadButtonDown { .. }
             | PlayerEvent::GamepadButtonUp { .. }
@@ -1013,6 +1376,107 @@ impl Player {
         }
     }
 
+    /// Handle text composed with the system's input method editor.
+    ///
+    /// The text goes to the focused text field, if any.
+    pub fn handle_ime_event(&mut self, event: ImeEvent) {
+        self.mutate_with_update_context(|context| {
+            if let Some(text) = context.focus_tracker.get_as_edit_text() {
+                text.ime_input(event, context);
+            }
+            let tracker = context.focus_tracker;
+            tracker.update_ime(context);
+            Self::run_actions(context);
+        });
+    }
+
+    /// Handle a finger touching the player.
+    ///
+    /// Depending on `Multitouch.inputMode`, this dispatches `TouchEvent`s or
+    /// gestures recognized from the touch points. Returns `true` if the movie
+    /// handled any of them.
+    pub fn handle_touch_event(&mut self, event: TouchEvent) -> bool {
+        self.set_pointer_window(None);
+        self.mutate_with_update_context(|context| {
+            let position =
+                context.stage.inverse_view_matrix() * Point::from_pixels(event.x, event.y);
+            let handled = multitouch::handle_touch_event(context, event, position);
+            Self::run_actions(context);
+            handled
+        })
+    }
+
+    /// Hands the last rendered frames of additional `NativeWindow`s to the UI backend.
+    fn present_native_windows(&mut self) {
+        self.enter_arena_mut(|_, gc_root, this| {
+            gc_root
+                .native_windows
+                .present(this.renderer.deref_mut(), this.ui.deref_mut());
+        });
+    }
+
+    /// Handle an event sent into the player from an additional `NativeWindow`
+    /// opened by AIR content.
+    ///
+    /// Pointer coordinates are relative to that window, and the event is delivered
+    /// to its stage. Returns `false` without handling the event if the window is not open.
+    pub fn handle_native_window_event(
+        &mut self,
+        window: NativeWindowId,
+        event: PlayerEvent,
+    ) -> bool {
+        if !Self::is_pointer_event(event) {
+            return self.handle_event(event);
+        }
+
+        let is_open = self.enter_arena(|_, gc_root, _| gc_root.native_windows.contains(window));
+        if !is_open {
+            return false;
+        }
+        self.set_pointer_window(Some(window));
+        self.handle_input_event(event)
+    }
+
+    /// Marks an additional `NativeWindow` as focused or unfocused by the user.
+    pub fn set_native_window_active(&mut self, window: NativeWindowId, active: bool) {
+        self.update(|context| {
+            NativeWindows::set_active(context, window, active);
+        });
+    }
+
+    /// Asks the movie to close an additional `NativeWindow`, as the window's
+    /// close button would. The movie may cancel this.
+    pub fn request_native_window_close(&mut self, window: NativeWindowId) {
+        self.update(|context| {
+            NativeWindows::request_close(context, window);
+        });
+    }
+
+    /// Handle something that happened in a `StageWebView` created by AIR content.
+    pub fn handle_web_view_event(&mut self, web_view: WebViewId, event: WebViewEvent) {
+        self.update(|context| {
+            WebViews::handle_event(context, web_view, event);
+        });
+    }
+
+    fn is_pointer_event(event: PlayerEvent) -> bool {
+        matches!(
+            event,
+            PlayerEvent::MouseMove { .. }
+                | PlayerEvent::MouseUp { .. }
+                | PlayerEvent::MouseDown { .. }
+                | PlayerEvent::MouseLeave
+                | PlayerEvent::MouseMotion { .. }
+                | PlayerEvent::MouseWheel { .. }
+        )
+    }
+
+    fn set_pointer_window(&mut self, window: Option<NativeWindowId>) {
+        self.enter_arena_mut(|_, gc_root, _| {
+            gc_root.native_windows.set_pointer_window(window);
+        });
+    }
+
     fn handle_focus_event(&mut self, event: PlayerEvent) -> bool {
         if let PlayerEvent::FocusLost = event {
             self.mutate_with_update_context(|context| {
@@ -1280,6 +1744,12 @@ impl Player {
 
             // KeyPress events take precedence over text input.
             if !key_press_handled {
+                if let PlayerEvent::TextControl {
+                    code: TextControlCode::Paste,
+                } = event
+                {
+                    Self::dispatch_paste_event(context);
+                }
                 if let Some(text) = context.focus_tracker.get_as_edit_text() {
                     if let PlayerEvent::TextInput { codepoint } = event {
                         text.text_input(codepoint, context);
@@ -1337,17 +1807,30 @@ impl Player {
         | PlayerEvent::MouseDown { x, y, .. }
         | PlayerEvent::MouseUp { x, y, .. } = event
         {
-            let inverse_view_matrix =
-                self.mutate_with_update_context(|context| context.stage.inverse_view_matrix());
-            let prev_mouse_position = self.mouse_position;
-            self.mouse_position = inverse_view_matrix * Point::from_pixels(x, y);
+            let (inverse_view_matrix, prev_mouse_position) =
+                self.mutate_with_update_context(|context| {
+                    (
+                        pointer_stage(context).inverse_view_matrix(),
+                        pointer_position(context),
+                    )
+                });
+            let mouse_position = inverse_view_matrix * Point::from_pixels(x, y);
+            // Each window keeps its own position, and the player's is that of the primary window.
+            let in_native_window = self.enter_arena_mut(|_, gc_root, _| {
+                gc_root.native_windows.set_pointer_position(mouse_position)
+            });
+            if !in_native_window {
+                self.mouse_position = mouse_position;
+            }
+            let movement = mouse_position - prev_mouse_position;
 
             // Update the dragged object here to keep it constantly in sync with the mouse position.
             self.mutate_with_update_context(|context| {
+                context.mouse_data.movement = (movement.dx.to_pixels(), movement.dy.to_pixels());
                 Self::update_drag(context);
             });
 
-            let is_mouse_moved = prev_mouse_position != self.mouse_position;
+            let is_mouse_moved = prev_mouse_position != mouse_position;
 
             // This fires button rollover/press events, which should run after the above mouseMove events.
             if self.update_mouse_state(
@@ -1359,6 +1842,23 @@ impl Player {
             }
         }
 
+        if let PlayerEvent::MouseMotion { dx, dy } = event {
+            // The mouse stays where it was locked, but `mouseMove` events report the movement.
+            let is_locked = self.mutate_with_update_context(|context| {
+                let stage = pointer_stage(context);
+                let inverse_view_matrix = stage.inverse_view_matrix();
+                let movement = inverse_view_matrix * Point::from_pixels(dx, dy)
+                    - inverse_view_matrix * Point::ZERO;
+                context.mouse_data.movement = (movement.dx.to_pixels(), movement.dy.to_pixels());
+                stage.mouse_lock()
+            });
+            if is_locked
+                && self.update_mouse_state(&changed_mouse_buttons, true, &mut player_event_handled)
+            {
+                self.needs_render = true;
+            }
+        }
+
         if let PlayerEvent::MouseWheel { delta } = event {
             self.mutate_with_update_context(|context| {
                 let target = if let Some(over_object) = context.mouse_data.hovered {
@@ -1370,7 +1870,7 @@ impl Player {
                         None
                     }
                 } else {
-                    context.stage.as_interactive()
+                    pointer_stage(context).as_interactive()
                 };
                 if let Some(target) = target {
                     let event = ClipEvent::MouseWheel { delta };
@@ -1428,7 +1928,10 @@ impl Player {
 
     /// Update dragged object, if any.
     pub fn update_drag(context: &mut UpdateContext<'_>) {
-        let mouse_position = *context.mouse_position;
+        let Some(dragged) = context.drag_object.as_ref().map(|drag| drag.display_object) else {
+            return;
+        };
+        let (_, mouse_position) = NativeWindows::mouse_position_on(context, dragged);
         if let Some(drag_object) = context.drag_object {
             let display_object = drag_object.display_object;
             if !display_object.movie().is_action_script_3() && display_object.avm1_removed() {
@@ -1510,7 +2013,7 @@ impl Player {
 
             if is_mouse_moved {
                 events.push((
-                    new_over_object.unwrap_or_else(|| context.stage.into()),
+                    new_over_object.unwrap_or_else(|| pointer_stage(context).into()),
                     ClipEvent::MouseMoveInside,
                 ));
             }
@@ -1791,12 +2294,9 @@ impl Player {
             needs_render
         });
 
-        // Update mouse cursor if it has changed.
-        if new_cursor != self.mouse_cursor {
-            self.mouse_cursor = new_cursor;
-            self.ui.set_mouse_cursor(new_cursor)
-        }
+        self.mouse_cursor = new_cursor;
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
+        self.update_displayed_cursor(0.0);
 
         needs_render
     }
@@ -1879,6 +2379,17 @@ impl Player {
                 let was_root_movie_loaded = root.loaded_bytes() as i32 == root.total_bytes();
                 did_finish = root.preload(context, limit);
 
+                if !was_root_movie_loaded {
+                    context.ui.root_movie_load_progress(
+                        RootMovieLoadPhase::Preload,
+                        root.loaded_bytes().into(),
+                        Some(root.total_bytes().max(0) as u64),
+                    );
+                    if root.loaded_bytes() as i32 == root.total_bytes() {
+                        context.ui.root_movie_loaded();
+                    }
+                }
+
                 if let Some(loader_info) = root.loader_info().filter(|_| !was_root_movie_loaded) {
                     let mut activation = Avm2Activation::from_nothing(context);
 
@@ -1937,6 +2448,8 @@ impl Player {
         }
 
         self.update(|context| {
+            Avm2Debugger::poll(context);
+
             // TODO: Is this order correct?
             run_all_phases_avm2(context);
             Avm1::run_frame(context);
@@ -1948,13 +2461,275 @@ impl Player {
             for cb in std::mem::take(context.post_frame_callbacks) {
                 (cb.callback)(context, cb.data);
             }
+
+            context.bitmap_readbacks.end_frame(context.renderer);
         });
 
         self.needs_render = true;
     }
 
+    /// Runs a single frame as if exactly one frame's worth of time had passed,
+    /// for deterministic playback.
+    ///
+    /// `elapsed` is the time (in milliseconds) since the movie started that this frame runs at,
+    /// which is what `getTimer` reports at the start of the frame.
+    pub fn run_fixed_frame(&mut self, elapsed: f64) {
+        if let Some(start_time) =
+            Instant::now().checked_sub(Duration::from_secs_f64(elapsed / 1000.0))
+        {
+            self.start_time = start_time;
+        }
+
+        let frame_time = self.frame_time(1000.0);
+        self.run_frame();
+        self.update_timers(frame_time);
+        self.update(|context| {
+            StreamManager::tick(context, frame_time);
+            AudioManager::update_dynamic_sounds(context);
+        });
+        self.audio.tick();
+    }
+
+    /// Reseeds the random number generator used by the movie, making its results reproducible.
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    /// Starts recording the movie's input into a replay, with the given random seed.
+    ///
+    /// This should be called before the first frame of the movie runs, so that the
+    /// replay can be played back from the start. See `crate::replay`.
+    pub fn start_replay_recording(&mut self, seed: u64) {
+        let start_date = get_current_date_time().timestamp_millis();
+        self.start_replay_session(ReplaySession::recording(seed, start_date));
+    }
+
+    /// Starts playing back a replay. Input from the user is ignored until it has finished.
+    ///
+    /// Like recording, this should be called before the first frame of the movie runs.
+    pub fn start_replay(&mut self, replay: Replay) {
+        self.start_replay_session(ReplaySession::playing(replay));
+    }
+
+    fn start_replay_session(&mut self, session: ReplaySession) {
+        self.set_random_seed(session.seed());
+        self.frame_accumulator = 0.0;
+        self.replay = Some(session);
+    }
+
+    /// Stops the replay being recorded or played back, and returns it,
+    /// or `None` if there was no replay.
+    pub fn stop_replay(&mut self) -> Option<Replay> {
+        self.replay.take().map(ReplaySession::into_replay)
+    }
+
+    /// Returns a save state of the movie as it is now, or `None` if no replay is being
+    /// recorded or played back.
+    ///
+    /// This is not a snapshot of the player. The save state is the replay of the movie up to
+    /// now, so one can only be taken while a replay is active. See `crate::replay`.
+    pub fn save_state(&self) -> Option<Replay> {
+        self.replay.as_ref().map(ReplaySession::snapshot)
+    }
+
+    /// Loads a save state made by `save_state`, by playing it back as fast as possible.
+    ///
+    /// Nothing is restored directly: every frame up to the save state runs again, so this
+    /// takes as long as running them does.
+    ///
+    /// Like replays, this should be called before the first frame of the movie runs. Once the
+    /// save state is caught up with, input from the user is recorded on top of it, so that
+    /// `save_state` can be used again.
+    pub fn load_state(&mut self, state: Replay) {
+        self.start_replay_session(ReplaySession::resuming(state));
+    }
+
+    /// Whether a replay is being recorded or played back.
+    pub fn replay_mode(&self) -> Option<ReplayMode> {
+        self.replay.as_ref().map(ReplaySession::mode)
+    }
+
+    /// Runs as many frames of the replay as are due, like `tick` does for normal playback.
+    /// A save state being loaded runs as many frames as fit in `SAVE_STATE_TIME_PER_TICK`.
+    ///
+    /// Every frame counts as exactly one frame's worth of time, however long it really took.
+    fn tick_replay(&mut self, dt: f64) {
+        self.frame_accumulator += dt;
+        let frame_time = self.frame_time(1000.0);
+
+        let max_frames_per_tick = self.max_frames_per_tick();
+        let mut frame = 0;
+        let tick_start = Instant::now();
+
+        loop {
+            let resuming = self.replay.as_ref().is_some_and(ReplaySession::is_resuming);
+            let is_due = if resuming {
+                self.frame_accumulator = 0.0;
+                tick_start.elapsed() < SAVE_STATE_TIME_PER_TICK
+            } else {
+                frame < max_frames_per_tick && self.frame_accumulator >= frame_time
+            };
+            if !is_due {
+                break;
+            }
+
+            if self.replay.as_ref().is_some_and(ReplaySession::is_finished) {
+                self.finish_replay();
+                break;
+            }
+
+            // Adaptive quality is left alone, as the movie can see the stage quality.
+            let timer = Instant::now();
+            self.run_replay_frame();
+            self.add_frame_timing(timer.elapsed().as_millis() as f64);
+
+            if !resuming {
+                self.frame_accumulator -= frame_time;
+            }
+            frame += 1;
+
+            if let RunState::Stepping(remaining) = self.run_state {
+                if remaining <= 1 {
+                    self.set_run_state(RunState::Suspended);
+                    break;
+                }
+                self.run_state = RunState::Stepping(remaining - 1);
+            }
+        }
+
+        if frame > 0 {
+            self.update_accessibility_tree();
+        }
+
+        if self.frame_accumulator >= frame_time {
+            self.frame_accumulator = 0.0;
+        }
+
+        self.update_displayed_cursor(dt);
+        self.audio.tick();
+    }
+
+    /// Hands control back to the user once a replay has been played back. A loaded save
+    /// state goes on recording instead, along with any input it ended with.
+    fn finish_replay(&mut self) {
+        let Some(session) = self.replay.as_mut().filter(|session| session.is_resuming()) else {
+            tracing::info!("Replay finished, handing control back to the user");
+            self.replay = None;
+            return;
+        };
+
+        tracing::info!("Save state loaded, handing control back to the user");
+        for event in session.resume_recording() {
+            self.dispatch_event(event);
+        }
+    }
+
+    /// Runs the next frame of the replay, after delivering the input recorded before it.
+    fn run_replay_frame(&mut self) {
+        let frame_time = self.frame_time(1000.0);
+        let inputs = self
+            .replay
+            .as_mut()
+            .map(ReplaySession::take_inputs)
+            .unwrap_or_default();
+        for event in inputs {
+            self.dispatch_event(event);
+        }
+
+        self.run_frame();
+        self.update_sockets();
+        self.update_net_connections();
+        self.update_timers(frame_time);
+        self.update(|context| {
+            StreamManager::tick(context, frame_time);
+            AudioManager::update_dynamic_sounds(context);
+        });
+
+        // The time faked for busy loops must not carry over into the next frame,
+        // as that would depend on how many frames ran in the same tick.
+        self.time_offset = 0;
+        if let Some(replay) = &mut self.replay {
+            replay.advance(frame_time);
+        }
+    }
+
+    /// Renders the stage into a list of commands, without submitting them to the renderer.
+    ///
+    /// Unlike `render`, this never draws from bitmap caches, so the commands
+    /// describe the whole frame. Returns the background color along with them.
+    pub fn render_commands(&mut self) -> (Color, CommandList) {
+        let invalidated = self.enter_arena(|_, gc_root, _| gc_root.stage.invalidated());
+
+        if invalidated {
+            self.update(|context| {
+                let stage = context.stage;
+                stage.broadcast_render(context);
+            });
+        }
+
+        self.enter_arena_mut(|gc_context, gc_root, this| {
+            let stage = gc_root.stage;
+
+            let mut cache_draws = vec![];
+            let mut render_context = RenderContext {
+                renderer: this.renderer.deref_mut(),
+                commands: CommandList::new(),
+                cache_draws: &mut cache_draws,
+                gc_context,
+                library: &gc_root.library,
+                transform_stack: &mut this.transform_stack,
+                is_offscreen: false,
+                use_bitmap_cache: false,
+                stage,
+            };
+
+            stage.render(&mut render_context);
+
+            let background_color =
+                if stage.window_mode() != WindowMode::Transparent || stage.is_fullscreen() {
+                    stage.background_color().unwrap_or(Color::WHITE)
+                } else {
+                    Color::from_rgba(0)
+                };
+            (background_color, render_context.commands)
+        })
+    }
+
+    /// Renders the stage at the size of the viewport, and reads it back as RGBA.
+    ///
+    /// The capture is always opaque: a transparent stage is drawn over its background color,
+    /// as if it were not transparent. Nothing is presented.
+    pub fn capture_frame(&mut self) -> Result<Bitmap, ruffle_render::error::Error> {
+        let (_, stage_commands) = self.render_commands();
+        let (background_color, quality) = self.mutate_with_update_context(|context| {
+            let stage = context.stage;
+            let background_color = stage.background_color().unwrap_or(Color::WHITE);
+            (background_color, stage.quality())
+        });
+
+        let ViewportDimensions { width, height, .. } = self.renderer.viewport_dimensions();
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            Color {
+                a: 255,
+                ..background_color
+            },
+            Matrix::create_box(width as f32, height as f32, Twips::ZERO, Twips::ZERO),
+        );
+        commands.commands.extend(stage_commands.commands);
+
+        self.renderer
+            .capture_frame(commands, quality, width, height)
+    }
+
     #[instrument(level = "debug", skip_all)]
     pub fn render(&mut self) {
+        if self.renderer.is_device_lost() {
+            // Nothing can be drawn until the renderer is replaced.
+            return;
+        }
+
+        let timer = Instant::now();
         let invalidated = self.enter_arena(|_, gc_root, _| gc_root.stage.invalidated());
 
         if invalidated {
@@ -2005,7 +2780,22 @@ impl Player {
         self.renderer
             .submit_frame(background_color, commands, cache_draws);
 
+        self.enter_arena_mut(|gc_context, gc_root, this| {
+            if !gc_root.native_windows.is_empty() {
+                let quality = gc_root.stage.quality();
+                gc_root.native_windows.render(
+                    gc_context,
+                    &gc_root.library,
+                    this.renderer.deref_mut(),
+                    this.ui.deref_mut(),
+                    &mut this.transform_stack,
+                    quality,
+                );
+            }
+        });
+
         self.needs_render = false;
+        self.last_render_time = timer.elapsed().as_secs_f64() * 1000.0;
     }
 
     /// The current frame of the main timeline, if available.
@@ -2026,6 +2816,107 @@ impl Player {
         &self.navigator
     }
 
+    /// Fetches something that the movie asked to load, unless the load allow list forbids it.
+    ///
+    /// Every load made on behalf of the movie should go through here, rather than straight
+    /// to the navigator.
+    pub fn fetch(&self, request: Request) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        if let Some(fetch) = self.fetch_rsl(&request) {
+            return fetch;
+        }
+        if let Some(allow_list) = &self.load_allow_list {
+            let root_url = Url::parse(self.swf.url()).ok();
+            let allowed = self
+                .navigator
+                .resolve_url(request.url())
+                .is_ok_and(|url| allow_list.allows(&url, root_url.as_ref()));
+            if !allowed {
+                let url = request.url().to_string();
+                tracing::warn!("Blocked loading {url}, as it's not on the load allow list");
+                return Box::pin(async move {
+                    Err(ErrorResponse {
+                        url: url.clone(),
+                        error: LoadError::NotAllowed(url),
+                    })
+                });
+            }
+        }
+        match self.navigator.resolve_url(request.url()) {
+            Ok(url) => self.rsl_cache.cache(&url, self.navigator.fetch(request)),
+            Err(_) => self.navigator.fetch(request),
+        }
+    }
+
+    /// Fetches data that the movie asked to load, once the cross-domain policy files
+    /// of its server permit it.
+    ///
+    /// Unlike SWFs and images, which may be loaded from anywhere, data from another
+    /// domain (such as with `URLLoader`) may only be loaded if its server allows it.
+    pub fn fetch_data(
+        &self,
+        request: Request,
+    ) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        if let Some(fetch) = self.fetch_rsl(&request) {
+            return fetch;
+        }
+        let url = self.navigator.resolve_url(request.url());
+        let fetch = self.fetch(request);
+
+        let Some(root_url) = self.policy_files.requester(&self.swf) else {
+            return fetch;
+        };
+        let Ok(url) = url else {
+            return fetch;
+        };
+        if url.origin() == root_url.origin() || !matches!(url.scheme(), "http" | "https") {
+            return fetch;
+        }
+
+        let check = self
+            .policy_files
+            .check_http(self.navigator.as_ref(), &root_url, &url);
+        let mode = self.policy_files.mode();
+        Box::pin(async move {
+            if !check.await {
+                if mode == PolicyFileMode::Enforce {
+                    tracing::warn!("Blocked loading {url}, as no policy file permits it");
+                    return Err(ErrorResponse {
+                        url: url.to_string(),
+                        error: LoadError::NotAllowed(url.to_string()),
+                    });
+                }
+                tracing::warn!("Loading {url}, although no policy file permits it");
+            }
+            fetch.await
+        })
+    }
+
+    /// Fetches one of Adobe's signed RSLs from the cache or from the RSL mirror,
+    /// if the given request is for one that is cached or there is an RSL mirror.
+    ///
+    /// The mirror was chosen by the embedder, so the mirrored request skips the load
+    /// allow list and policy file checks.
+    fn fetch_rsl(
+        &self,
+        request: &Request,
+    ) -> Option<OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse>> {
+        let url = self.navigator.resolve_url(request.url()).ok()?;
+        if let Some(response) = self.rsl_cache.get(&url) {
+            return Some(Box::pin(async move { Ok(response) }));
+        }
+
+        let mirror = self.rsl_mirror.as_ref()?;
+        let mirror_url = mirror.redirect(&url)?;
+        tracing::info!("Loading RSL {} from {mirror_url}", request.url());
+        let mut mirrored = Request::request(
+            request.method(),
+            mirror_url.to_string(),
+            request.body().clone(),
+        );
+        mirrored.set_headers(request.headers().clone());
+        Some(self.rsl_cache.cache(&url, self.navigator.fetch(mirrored)))
+    }
+
     pub fn navigator_mut(&mut self) -> &mut Navigator {
         &mut self.navigator
     }
@@ -2043,6 +2934,17 @@ impl Player {
         &mut self.renderer
     }
 
+    /// Replaces the renderer, such as after the previous one lost its device.
+    ///
+    /// Shapes, bitmaps and other resources are registered again with the new renderer
+    /// as they are drawn.
+    pub fn replace_renderer(&mut self, mut renderer: Renderer) {
+        renderer.set_viewport_dimensions(self.renderer.viewport_dimensions());
+        renderer.set_quality(self.quality());
+        self.renderer = renderer;
+        self.needs_render = true;
+    }
+
     pub fn storage(&self) -> &Storage {
         &self.storage
     }
@@ -2189,6 +3091,9 @@ impl Player {
                 post_frame_callbacks,
                 mouse_data,
                 dynamic_root,
+                bitmap_readbacks,
+                native_windows,
+                web_views,
             ) = gc_root.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -2214,7 +3119,11 @@ impl Player {
                 page_url: &mut this.page_url,
                 instance_counter: &mut this.instance_counter,
                 storage: this.storage.deref_mut(),
+                camera: this.camera.deref_mut(),
+                microphone: this.microphone.deref_mut(),
+                debugger: this.debugger.deref_mut(),
                 log: this.log.deref_mut(),
+                trace_outputs: &this.trace_outputs,
                 video: this.video.deref_mut(),
                 avm1_shared_objects,
                 avm2_shared_objects,
@@ -2227,6 +3136,7 @@ impl Player {
                 external_interface,
                 start_time: this.start_time,
                 update_start: Instant::now(),
+                replay_clock: this.replay.as_ref().map(ReplaySession::clock),
                 max_execution_duration: this.max_execution_duration,
                 focus_tracker: stage.focus_tracker(),
                 times_get_time_called: 0,
@@ -2237,12 +3147,18 @@ impl Player {
                 actions_since_timeout_check: &mut this.actions_since_timeout_check,
                 frame_phase: &mut this.frame_phase,
                 stub_tracker: &mut this.stub_tracker,
+                profiler: &mut this.profiler,
                 stream_manager,
                 sockets,
                 net_connections,
                 local_connections,
                 dynamic_root,
                 post_frame_callbacks,
+                bitmap_readbacks,
+                native_windows,
+                web_views,
+                print_job: &mut this.print_job,
+                policy_files: &this.policy_files,
             };
 
             let prev_frame_rate = *update_context.frame_rate;
@@ -2273,8 +3189,9 @@ impl Player {
         // This method should only be called once and it will panic if it tries to recursively render.
         let debug_ui = self.debug_ui.clone();
         let mut debug_ui = debug_ui.borrow_mut();
+        let gc_stats = self.gc_stats();
         self.mutate_with_update_context(|context| {
-            debug_ui.show(egui_ctx, context, movie_offset);
+            debug_ui.show(egui_ctx, context, movie_offset, gc_stats);
         });
     }
 
@@ -2310,11 +3227,42 @@ impl Player {
         self.update_mouse_state(&HashSet::new(), false, &mut false);
 
         // GC
-        self.gc_arena.borrow_mut().collect_debt();
+        self.collect_garbage();
 
         rval
     }
 
+    /// Pays off the allocation debt of the GC, unless the scheduler leaves it to a later update.
+    fn collect_garbage(&mut self) {
+        let mut gc_arena = self.gc_arena.borrow_mut();
+        let debt = gc_arena.metrics().allocation_debt();
+        if !self.gc_scheduler.should_collect(debt) {
+            return;
+        }
+
+        let start = Instant::now();
+        gc_arena.collect_debt();
+        let heap_size = gc_arena.metrics().total_gc_allocation();
+        self.gc_scheduler.record(debt, start.elapsed(), heap_size);
+    }
+
+    /// Sets how long a garbage collection may pause the player for.
+    ///
+    /// The allocation debt is then left to build up, across frames, until it is about as much
+    /// as can be paid within the budget. `None` collects after every update, which is the
+    /// default.
+    pub fn set_gc_budget(&mut self, budget: Option<Duration>) {
+        self.gc_scheduler.set_budget(budget);
+    }
+
+    pub fn gc_budget(&self) -> Option<Duration> {
+        self.gc_scheduler.budget()
+    }
+
+    pub fn gc_stats(&self) -> GcStats {
+        self.gc_scheduler.stats()
+    }
+
     pub fn flush_shared_objects(&mut self) {
         self.update(|context| {
             if let Some(mut avm1_activation) =
@@ -2342,6 +3290,20 @@ impl Player {
         });
     }
 
+    /// Displays the cursor forced by the movie with `Mouse.cursor`, or else the one chosen
+    /// by what's under the mouse, advancing animated cursors by `dt` milliseconds.
+    fn update_displayed_cursor(&mut self, dt: f64) {
+        let forced_cursor = self.mutate_with_update_context(|context| {
+            context.mouse_data.cursors.tick(dt);
+            context.mouse_data.cursors.forced_cursor()
+        });
+        let cursor = forced_cursor.unwrap_or(self.mouse_cursor);
+        if cursor != self.displayed_cursor {
+            self.displayed_cursor = cursor;
+            self.ui.set_mouse_cursor(cursor);
+        }
+    }
+
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: f64) {
@@ -2404,6 +3366,11 @@ impl Player {
         &self.log
     }
 
+    /// Sends the output of `trace()` to the given destination, as well as the log backend.
+    pub fn add_trace_output(&mut self, output: impl 'static + TraceOutput) {
+        self.trace_outputs.push(Box::new(output));
+    }
+
     pub fn max_execution_duration(&self) -> Duration {
         self.max_execution_duration
     }
@@ -2434,6 +3401,55 @@ impl Player {
             context.library.set_default_font(font, names);
         });
     }
+
+    /// Lays out every text field on the stage again.
+    ///
+    /// Backends that load device fonts in the background call this once a font
+    /// becomes available, so that text fields stop using their fallback fonts.
+    /// Frees whatever memory can be recreated later, such as when the system is running low
+    /// on memory.
+    ///
+    /// Bitmaps defined by movies are released from the renderer, and are decoded again
+    /// from the movie the next time they're drawn.
+    pub fn low_memory(&mut self) {
+        let bytes =
+            self.mutate_with_update_context(|context| context.library.evict_bitmap_handles());
+        tracing::info!("Released {bytes} bytes of bitmaps due to low memory");
+    }
+
+    /// Starts recording the time spent in ActionScript functions.
+    ///
+    /// Any profile already being taken is discarded.
+    pub fn start_profiling(&mut self) {
+        self.profiler.start();
+    }
+
+    /// Stops recording and returns what was recorded since `start_profiling`, or `None` if
+    /// no profile was being taken.
+    pub fn stop_profiling(&mut self) -> Option<Profile> {
+        self.mutate_with_update_context(|context| {
+            let allocated = context.gc_context.metrics().total_gc_allocation();
+            context.profiler.stop(allocated)
+        })
+    }
+
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.is_active()
+    }
+
+    pub fn relayout_text_fields(&mut self) {
+        self.mutate_with_update_context(|context| {
+            let mut pending: Vec<DisplayObject<'_>> = vec![context.stage.into()];
+            while let Some(object) = pending.pop() {
+                if let Some(edit_text) = object.as_edit_text() {
+                    edit_text.relayout(context);
+                }
+                if let Some(container) = object.as_container() {
+                    pending.extend(container.iter_render_list());
+                }
+            }
+        });
+    }
 }
 
 /// Player factory, which can be used to configure the aspects of a Ruffle player.
@@ -2442,7 +3458,11 @@ pub struct PlayerBuilder {
 
     // Backends
     audio: Option<Audio>,
+    camera: Option<Camera>,
     log: Option<Log>,
+    trace_outputs: Vec<Box<dyn TraceOutput>>,
+    microphone: Option<Microphone>,
+    debugger: Option<Debugger>,
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
     storage: Option<Storage>,
@@ -2466,6 +3486,7 @@ pub struct PlayerBuilder {
     spoofed_url: Option<String>,
     compatibility_rules: CompatibilityRules,
     gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+    key_remap: HashMap<KeyCode, KeyCode>,
     player_version: Option<u8>,
     player_runtime: PlayerRuntime,
     quality: StageQuality,
@@ -2473,9 +3494,21 @@ pub struct PlayerBuilder {
     frame_rate: Option<f64>,
     external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
+    local_connection_backend: Option<Box<dyn LocalConnectionBackend>>,
     #[cfg(feature = "known_stubs")]
     stub_report_output: Option<std::path::PathBuf>,
     avm2_optimizer_enabled: bool,
+    jit: Option<Box<dyn JitBackend>>,
+    gc_budget: Option<Duration>,
+    bitmap_readback_mode: BitmapReadbackMode,
+    max_native_windows: usize,
+    low_latency_input: bool,
+    force_low_latency_input: bool,
+    adaptive_quality: bool,
+    load_allow_list: Option<LoadAllowList>,
+    policy_file_mode: PolicyFileMode,
+    rsl_mirror: Option<RslMirror>,
+    capabilities: CapabilitiesConfig,
 }
 
 impl PlayerBuilder {
@@ -2489,7 +3522,11 @@ impl PlayerBuilder {
             movie: None,
 
             audio: None,
+            camera: None,
             log: None,
+            trace_outputs: vec![],
+            microphone: None,
+            debugger: None,
             navigator: None,
             renderer: None,
             storage: None,
@@ -2517,6 +3554,7 @@ impl PlayerBuilder {
             spoofed_url: None,
             compatibility_rules: CompatibilityRules::default(),
             gamepad_button_mapping: HashMap::new(),
+            key_remap: HashMap::new(),
             player_version: None,
             player_runtime: PlayerRuntime::default(),
             quality: StageQuality::High,
@@ -2524,9 +3562,21 @@ impl PlayerBuilder {
             frame_rate: None,
             external_interface_provider: None,
             fs_command_provider: Box::new(NullFsCommandProvider),
+            local_connection_backend: None,
             #[cfg(feature = "known_stubs")]
             stub_report_output: None,
             avm2_optimizer_enabled: true,
+            jit: None,
+            gc_budget: None,
+            bitmap_readback_mode: BitmapReadbackMode::default(),
+            max_native_windows: DEFAULT_MAX_NATIVE_WINDOWS,
+            low_latency_input: false,
+            force_low_latency_input: false,
+            adaptive_quality: false,
+            load_allow_list: None,
+            policy_file_mode: PolicyFileMode::default(),
+            rsl_mirror: None,
+            capabilities: CapabilitiesConfig::default(),
         }
     }
 
@@ -2558,6 +3608,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Adds a destination for the output of `trace()`, besides the logging backend.
+    #[inline]
+    pub fn with_trace_output(mut self, output: impl 'static + TraceOutput) -> Self {
+        self.trace_outputs.push(Box::new(output));
+        self
+    }
+
     /// Sets the navigator backend of the player.
     #[inline]
     pub fn with_navigator(mut self, navigator: impl 'static + NavigatorBackend) -> Self {
@@ -2579,6 +3636,33 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the camera backend of the player.
+    ///
+    /// Movies can't use any cameras unless one is set.
+    #[inline]
+    pub fn with_camera(mut self, camera: impl 'static + CameraBackend) -> Self {
+        self.camera = Some(Box::new(camera));
+        self
+    }
+
+    /// Sets the microphone backend of the player.
+    ///
+    /// Movies can't use any microphones unless one is set.
+    #[inline]
+    pub fn with_microphone(mut self, microphone: impl 'static + MicrophoneBackend) -> Self {
+        self.microphone = Some(Box::new(microphone));
+        self
+    }
+
+    /// Sets the debugger backend of the player.
+    ///
+    /// Debuggers can't attach to the movie unless one is set.
+    #[inline]
+    pub fn with_debugger(mut self, debugger: impl 'static + DebuggerBackend) -> Self {
+        self.debugger = Some(Box::new(debugger));
+        self
+    }
+
     /// Sets the storage backend of the player.
     #[inline]
     pub fn with_storage(mut self, storage: Box<dyn StorageBackend>) -> Self {
@@ -2717,11 +3801,27 @@ impl PlayerBuilder {
         self
     }
 
+    /// Lets the `LocalConnection`s of movies reach those of other players.
+    pub fn with_local_connection_backend(
+        mut self,
+        backend: Box<dyn LocalConnectionBackend>,
+    ) -> Self {
+        self.local_connection_backend = Some(backend);
+        self
+    }
+
     pub fn with_gamepad_button_mapping(mut self, mapping: HashMap<GamepadButton, KeyCode>) -> Self {
         self.gamepad_button_mapping = mapping;
         self
     }
 
+    /// Sets which keys act as other keys. Each pressed key in the map is seen by the movie
+    /// as the key it maps to.
+    pub fn with_key_remap(mut self, remap: HashMap<KeyCode, KeyCode>) -> Self {
+        self.key_remap = remap;
+        self
+    }
+
     #[cfg(feature = "known_stubs")]
     /// Sets the output path for the stub report. When set, the player
     /// will write the report to this path and exit the process.
@@ -2735,6 +3835,87 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the backend that hot AVM2 methods are compiled with.
+    ///
+    /// Without one, all ActionScript 3 code is interpreted.
+    pub fn with_jit(mut self, jit: impl 'static + JitBackend) -> Self {
+        self.jit = Some(Box::new(jit));
+        self
+    }
+
+    /// Sets how long a single garbage collection may pause the player for.
+    ///
+    /// The allocation debt is then paid in slices that are expected to fit in the budget.
+    #[inline]
+    pub fn with_gc_budget(mut self, budget: Option<Duration>) -> Self {
+        self.gc_budget = budget;
+        self
+    }
+
+    /// Configures how `BitmapData` pixel reads wait for pending GPU work.
+    ///
+    /// `BitmapReadbackMode::Throttled` changes observable timing, and so is
+    /// disabled by default.
+    pub fn with_bitmap_readback_mode(mut self, mode: BitmapReadbackMode) -> Self {
+        self.bitmap_readback_mode = mode;
+        self
+    }
+
+    /// Sets how many additional `NativeWindow`s AIR content may have open at once.
+    ///
+    /// Constructing a `NativeWindow` beyond this limit throws `Error #2037`, as a window that
+    /// could not be created would.
+    pub fn with_max_native_windows(mut self, max_windows: usize) -> Self {
+        self.max_native_windows = max_windows;
+        self
+    }
+
+    /// Sets whether input is handled in low-latency mode, for content such as instruments.
+    ///
+    /// This is disabled for ActionScript 3 movies, unless `force` is set.
+    /// See `Player::set_low_latency_input` for details.
+    pub fn with_low_latency_input(mut self, enabled: bool, force: bool) -> Self {
+        self.low_latency_input = enabled;
+        self.force_low_latency_input = force;
+        self
+    }
+
+    /// Sets whether the rendering quality is lowered while frames take too long.
+    /// See `Player::set_adaptive_quality` for details.
+    pub fn with_adaptive_quality(mut self, enabled: bool) -> Self {
+        self.adaptive_quality = enabled;
+        self
+    }
+
+    /// Restricts where the movie may load SWFs and data from, besides the origin of the
+    /// root movie. Blocked loads fail with a security error. See `LoadAllowList`.
+    pub fn with_load_allow_list(mut self, allow_list: Option<LoadAllowList>) -> Self {
+        self.load_allow_list = allow_list;
+        self
+    }
+
+    /// Sets whether cross-domain policy files are checked before loading data from, or
+    /// connecting to, other domains. See `PolicyFileMode`.
+    pub fn with_policy_file_mode(mut self, mode: PolicyFileMode) -> Self {
+        self.policy_file_mode = mode;
+        self
+    }
+
+    /// Sets where Adobe's signed runtime shared libraries, such as the Text Layout Framework
+    /// used by TLF text, are loaded from instead of Adobe's servers. See `RslMirror`.
+    pub fn with_rsl_mirror(mut self, mirror: Option<RslMirror>) -> Self {
+        self.rsl_mirror = mirror;
+        self
+    }
+
+    /// Sets the strings that are reported to the movie about the player, in place of the
+    /// real ones. See `CapabilitiesConfig`.
+    pub fn with_capabilities(mut self, capabilities: CapabilitiesConfig) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn create_gc_root<'gc>(
         gc_context: &'gc Mutation<'gc>,
         player_version: u8,
@@ -2743,6 +3924,9 @@ impl PlayerBuilder {
         fake_movie: Arc<SwfMovie>,
         external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
         fs_command_provider: Box<dyn FsCommandProvider>,
+        local_connection_backend: Option<Box<dyn LocalConnectionBackend>>,
+        bitmap_readback_mode: BitmapReadbackMode,
+        max_native_windows: usize,
     ) -> GcRoot<'gc> {
         let mut interner = AvmStringInterner::new(gc_context);
         let (avm1, avm2) = {
@@ -2775,6 +3959,9 @@ impl PlayerBuilder {
                 pressed: None,
                 right_pressed: None,
                 middle_pressed: None,
+                cursors: CustomCursors::default(),
+                touches: TouchData::default(),
+                movement: (0.0, 0.0),
             },
             avm1_shared_objects: HashMap::new(),
             avm2_shared_objects: HashMap::new(),
@@ -2784,9 +3971,12 @@ impl PlayerBuilder {
             stream_manager: StreamManager::new(),
             sockets: Sockets::empty(),
             net_connections: NetConnections::default(),
-            local_connections: LocalConnections::empty(),
+            local_connections: LocalConnections::new(local_connection_backend),
             dynamic_root: DynamicRootSet::new(gc_context),
             post_frame_callbacks: Vec::new(),
+            bitmap_readbacks: BitmapReadbacks::new(bitmap_readback_mode),
+            native_windows: NativeWindows::new(max_native_windows),
+            web_views: WebViews::default(),
         };
 
         GcRoot {
@@ -2818,6 +4008,15 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let camera = self
+            .camera
+            .unwrap_or_else(|| Box::new(camera::NullCameraBackend::new()));
+        let microphone = self
+            .microphone
+            .unwrap_or_else(|| Box::new(microphone::NullMicrophoneBackend::new()));
+        let debugger = self
+            .debugger
+            .unwrap_or_else(|| Box::new(debugger::NullDebuggerBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
@@ -2836,9 +4035,13 @@ impl PlayerBuilder {
                 // Backends
                 audio,
                 log,
+                trace_outputs: self.trace_outputs,
                 navigator,
                 renderer,
                 storage,
+                camera,
+                microphone,
+                debugger,
                 ui,
                 video,
 
@@ -2854,20 +4057,33 @@ impl PlayerBuilder {
                 recent_run_frame_timings: VecDeque::with_capacity(10),
                 start_time: Instant::now(),
                 time_offset: 0,
+                replay: None,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
                 actions_since_timeout_check: 0,
 
                 // Input
-                input: InputManager::new(self.gamepad_button_mapping),
+                input: InputManager::new(self.gamepad_button_mapping, self.key_remap),
+                low_latency_input: self.low_latency_input,
+                adaptive_quality: AdaptiveQuality::new(self.adaptive_quality),
+                last_render_time: 0.0,
+                accessibility_tree: Vec::new(),
+                accessibility_view_matrix: None,
+                print_job: None,
+                force_low_latency_input: self.force_low_latency_input,
+                low_latency_audio: None,
                 mouse_in_stage: true,
                 mouse_position: Point::ZERO,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
+                displayed_cursor: MouseCursor::Arrow,
 
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
-                system: SystemProperties::new(),
+                system: SystemProperties {
+                    overrides: self.capabilities.clone(),
+                    ..SystemProperties::new()
+                },
                 page_url: self.page_url.clone(),
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
@@ -2883,7 +4099,13 @@ impl PlayerBuilder {
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
                 compatibility_rules: self.compatibility_rules.clone(),
+                load_allow_list: self.load_allow_list.clone(),
+                policy_files: PolicyFiles::new(self.policy_file_mode),
+                rsl_mirror: self.rsl_mirror.clone(),
+                rsl_cache: RslCache::default(),
                 stub_tracker: StubCollection::new(),
+                profiler: Profiler::default(),
+                gc_scheduler: GcScheduler::default(),
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
 
@@ -2897,6 +4119,9 @@ impl PlayerBuilder {
                         fake_movie.clone(),
                         self.external_interface_provider,
                         self.fs_command_provider,
+                        self.local_connection_backend,
+                        self.bitmap_readback_mode,
+                        self.max_native_windows,
                     )
                 }))),
             })
@@ -2926,6 +4151,8 @@ impl PlayerBuilder {
                 .set_default_font(DefaultFont::JapaneseMincho, vec!["Noto Sans".to_string()]);
         }
 
+        player_lock.set_gc_budget(self.gc_budget);
+
         player_lock.mutate_with_update_context(|context| {
             if !self.avm2_optimizer_enabled {
                 tracing::warn!(
@@ -2936,6 +4163,7 @@ impl PlayerBuilder {
             context
                 .avm2
                 .set_optimizer_enabled(self.avm2_optimizer_enabled);
+            context.avm2.set_jit(self.jit);
             Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
 
             let stage = context.stage;
@@ -2967,6 +4195,7 @@ impl PlayerBuilder {
                 context.set_root_movie(movie);
             });
         }
+        player_lock.update_low_latency_input();
         drop(player_lock);
         player
     }
@@ -2998,16 +4227,44 @@ pub struct DragObject<'gc> {
     pub constraint: Rectangle<Twips>,
 }
 
+/// The stage that is currently under the pointer, which is either the main stage
+/// or the stage of an additional `NativeWindow`.
+fn pointer_stage<'gc>(context: &UpdateContext<'gc>) -> Stage<'gc> {
+    context
+        .native_windows
+        .pointer_stage()
+        .unwrap_or(context.stage)
+}
+
+/// Where the pointer is on the stage that is currently under it.
+fn pointer_position(context: &UpdateContext<'_>) -> Point<Twips> {
+    context
+        .native_windows
+        .pointer_position()
+        .unwrap_or(*context.mouse_position)
+}
+
 fn run_mouse_pick<'gc>(
     context: &mut UpdateContext<'gc>,
     require_button_mode: bool,
 ) -> Option<InteractiveObject<'gc>> {
-    context.stage.iter_render_list().rev().find_map(|level| {
+    let mouse_position = pointer_position(context);
+    run_pointer_pick(context, mouse_position, require_button_mode)
+}
+
+/// Finds the topmost interactive object at a point on the stage under the pointer.
+pub(crate) fn run_pointer_pick<'gc>(
+    context: &mut UpdateContext<'gc>,
+    point: Point<Twips>,
+    require_button_mode: bool,
+) -> Option<InteractiveObject<'gc>> {
+    let stage = pointer_stage(context);
+    stage.iter_render_list().rev().find_map(|level| {
         level.as_interactive().and_then(|l| {
             if l.as_displayobject().movie().is_action_script_3() {
                 let mut res = None;
                 if let Avm2MousePick::Hit(target) =
-                    l.mouse_pick_avm2(context, *context.mouse_position, require_button_mode)
+                    l.mouse_pick_avm2(context, point, require_button_mode)
                 {
                     // Flash Player appears to never target events at the root object
                     if !target.as_displayobject().is_root() {
@@ -3017,7 +4274,7 @@ fn run_mouse_pick<'gc>(
 
                 res
             } else {
-                l.mouse_pick_avm1(context, *context.mouse_position, require_button_mode)
+                l.mouse_pick_avm1(context, point, require_button_mode)
             }
         })
     })
@@ -0,0 +1,523 @@
+//! Cross-domain policy files, which servers use to let movies from other domains load
+//! their data or connect to their sockets. See
+//! https://www.adobe.com/devnet-docs/acrobatetk/tools/AppSec/CrossDomain_PolicyFile_Specification.pdf
+
+use crate::backend::navigator::{
+    ErrorResponse, NavigatorBackend, OwnedFuture, Request, SuccessResponse,
+};
+use crate::config::PolicyFileMode;
+use crate::sandbox::SandboxType;
+use crate::tag_utils::SwfMovie;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use url::Url;
+
+/// The port that socket policy files are requested from, unless the movie
+/// asked for another one with `loadPolicyFile`.
+pub const SOCKET_POLICY_PORT: u16 = 843;
+
+/// Which policy files on a server may grant access, as declared by its master policy file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetaPolicy {
+    /// No policy file grants access, not even the master one.
+    None,
+
+    /// Only the master policy file grants access.
+    MasterOnly,
+
+    /// Any policy file grants access to its own directory.
+    All,
+}
+
+impl MetaPolicy {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(MetaPolicy::None),
+            "master-only" => Some(MetaPolicy::MasterOnly),
+            // Ruffle doesn't check the content type of policy files.
+            "all" | "by-content-type" | "by-ftp-filename" => Some(MetaPolicy::All),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AllowAccessFrom {
+    /// A host, `*.` followed by a domain, or `*` for everyone.
+    domain: String,
+
+    /// The ports that socket connections may be made to. Empty when not specified.
+    to_ports: Vec<(u16, u16)>,
+
+    /// Whether movies that weren't loaded over HTTPS are denied by an HTTPS policy file.
+    secure: Option<bool>,
+}
+
+impl AllowAccessFrom {
+    fn matches_domain(&self, requester: &Url) -> bool {
+        if self.domain == "*" {
+            return true;
+        }
+
+        // Local movies have no domain, so only wildcard entries apply to them.
+        let Some(host) = requester.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        match self.domain.strip_prefix("*.") {
+            Some(domain) => {
+                host == domain
+                    || host
+                        .strip_suffix(domain)
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            }
+            None => host == self.domain,
+        }
+    }
+}
+
+/// A parsed `<cross-domain-policy>` document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    meta_policy: Option<MetaPolicy>,
+    allow_access_from: Vec<AllowAccessFrom>,
+}
+
+impl PolicyFile {
+    /// Parses a policy file, returning `None` if it isn't one.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        let mut policy = PolicyFile::default();
+        let mut depth = 0;
+        let mut found_root = false;
+
+        loop {
+            let (element, is_empty) = match reader.read_event() {
+                Ok(Event::Start(element)) => (element, false),
+                Ok(Event::Empty(element)) => (element, true),
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                    continue;
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::warn!("Couldn't parse policy file: {e}");
+                    return None;
+                }
+            };
+
+            let name = element.name().into_inner();
+            if depth == 0 {
+                if name != b"cross-domain-policy" {
+                    return None;
+                }
+                found_root = true;
+            } else if depth == 1 {
+                match name {
+                    b"site-control" => {
+                        if let Some(meta_policy) =
+                            attribute(&element, b"permitted-cross-domain-policies")
+                                .and_then(|value| MetaPolicy::parse(&value))
+                        {
+                            policy.meta_policy = Some(meta_policy);
+                        }
+                    }
+                    b"allow-access-from" => {
+                        if let Some(domain) = attribute(&element, b"domain") {
+                            policy.allow_access_from.push(AllowAccessFrom {
+                                domain: domain.trim().to_ascii_lowercase(),
+                                to_ports: attribute(&element, b"to-ports")
+                                    .map(|ports| parse_ports(&ports))
+                                    .unwrap_or_default(),
+                                secure: attribute(&element, b"secure")
+                                    .map(|secure| secure != "false"),
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if !is_empty {
+                depth += 1;
+            }
+        }
+
+        (found_root && depth == 0).then_some(policy)
+    }
+
+    /// Whether this policy file, served from `policy_url`, lets a movie from `requester` load data.
+    fn allows_http(&self, requester: &Url, policy_url: &Url) -> bool {
+        let secure_policy = policy_url.scheme() == "https";
+        self.allow_access_from.iter().any(|entry| {
+            entry.matches_domain(requester)
+                && !(secure_policy && entry.secure.unwrap_or(true) && requester.scheme() != "https")
+        })
+    }
+
+    /// Whether this socket policy file lets a movie from `requester` connect to `port`.
+    pub fn allows_socket(&self, requester: &Url, port: u16) -> bool {
+        self.meta_policy != Some(MetaPolicy::None)
+            && self.allow_access_from.iter().any(|entry| {
+                entry.matches_domain(requester)
+                    && entry
+                        .to_ports
+                        .iter()
+                        .any(|(start, end)| (*start..=*end).contains(&port))
+            })
+    }
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .with_checks(false)
+        .flatten()
+        .find(|attribute| attribute.key.into_inner() == name)
+        .map(|attribute| String::from_utf8_lossy(&attribute.value).into_owned())
+}
+
+/// Parses a `to-ports` attribute, such as `*` or `507,516-523`.
+fn parse_ports(ports: &str) -> Vec<(u16, u16)> {
+    ports
+        .split(',')
+        .filter_map(|range| {
+            let range = range.trim();
+            if range == "*" {
+                return Some((0, u16::MAX));
+            }
+            match range.split_once('-') {
+                Some((start, end)) => Some((start.trim().parse().ok()?, end.trim().parse().ok()?)),
+                None => range.parse().ok().map(|port| (port, port)),
+            }
+        })
+        .collect()
+}
+
+/// The master policy file of the server that `url` points to.
+fn master_policy_url(url: &Url) -> Url {
+    let mut master = url.clone();
+    master.set_path("/crossdomain.xml");
+    master.set_query(None);
+    master.set_fragment(None);
+    master
+}
+
+/// Whether the policy file at `policy_url` applies to `url`, which it does for
+/// everything in its directory.
+fn policy_covers(policy_url: &Url, url: &Url) -> bool {
+    let path = policy_url.path();
+    let directory = &path[..path.rfind('/').map_or(0, |slash| slash + 1)];
+    policy_url.origin() == url.origin() && url.path().starts_with(directory)
+}
+
+async fn fetch_policy_file(
+    url: &Url,
+    fetch: OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse>,
+) -> Option<PolicyFile> {
+    let response = fetch.await.ok()?;
+
+    // Policy files only apply to the server that actually served them.
+    let final_url = Url::parse(&response.url()).ok()?;
+    if final_url.origin() != url.origin() {
+        tracing::warn!("Ignoring policy file {url}, as it was redirected to {final_url}");
+        return None;
+    }
+
+    let body = response.body().await.ok()?;
+    let policy = PolicyFile::parse(&body);
+    if policy.is_none() {
+        tracing::warn!("Ignoring policy file {url}, as it's not a valid policy file");
+    }
+    policy
+}
+
+#[derive(Default)]
+struct PolicyFilesState {
+    /// Policy files that the movie asked for with `loadPolicyFile`.
+    requested: Vec<Url>,
+
+    /// Policy files that were already fetched, or `None` for those that couldn't be.
+    loaded: HashMap<String, Option<PolicyFile>>,
+}
+
+/// The policy files known to a player.
+///
+/// This is shared with the loads that are waiting for policy files to be fetched.
+#[derive(Clone, Default)]
+pub struct PolicyFiles {
+    mode: PolicyFileMode,
+    state: Rc<RefCell<PolicyFilesState>>,
+}
+
+impl PolicyFiles {
+    pub fn new(mode: PolicyFileMode) -> Self {
+        Self {
+            mode,
+            state: Default::default(),
+        }
+    }
+
+    pub fn mode(&self) -> PolicyFileMode {
+        self.mode
+    }
+
+    /// The URL that policy files are checked against for loads made by `movie`,
+    /// or `None` if they don't need to be checked.
+    pub fn requester(&self, movie: &SwfMovie) -> Option<Url> {
+        // AIR applications and trusted local movies aren't restricted.
+        if self.mode == PolicyFileMode::Ignore
+            || matches!(
+                movie.sandbox_type(),
+                SandboxType::Application | SandboxType::LocalTrusted
+            )
+        {
+            return None;
+        }
+        Url::parse(movie.url()).ok()
+    }
+
+    /// Registers a policy file that the movie asked for with `loadPolicyFile`.
+    ///
+    /// HTTP policy files grant access to their own directory, while `xmlsocket:` ones
+    /// replace port 843 as the place socket policy files are requested from.
+    pub fn request(&self, url: Url) {
+        let mut state = self.state.borrow_mut();
+        if !state.requested.contains(&url) {
+            state.requested.push(url);
+        }
+    }
+
+    /// Checks whether the policy files of the server that `url` points to let
+    /// a movie from `requester` load it, fetching them if needed.
+    pub fn check_http(
+        &self,
+        navigator: &dyn NavigatorBackend,
+        requester: &Url,
+        url: &Url,
+    ) -> Pin<Box<dyn Future<Output = bool>>> {
+        let master = master_policy_url(url);
+        let state = self.state.borrow();
+        let others: Vec<Url> = state
+            .requested
+            .iter()
+            .filter(|policy_url| {
+                matches!(policy_url.scheme(), "http" | "https")
+                    && **policy_url != master
+                    && policy_covers(policy_url, url)
+            })
+            .cloned()
+            .collect();
+        let fetches: Vec<_> = std::iter::once(master)
+            .chain(others)
+            .map(|policy_url| {
+                let fetch = (!state.loaded.contains_key(policy_url.as_str()))
+                    .then(|| navigator.fetch(Request::get(policy_url.to_string())));
+                (policy_url, fetch)
+            })
+            .collect();
+        drop(state);
+
+        let state = self.state.clone();
+        let requester = requester.clone();
+        Box::pin(async move {
+            let mut meta_policy = MetaPolicy::MasterOnly;
+            for (index, (policy_url, fetch)) in fetches.into_iter().enumerate() {
+                if let Some(fetch) = fetch {
+                    let policy = fetch_policy_file(&policy_url, fetch).await;
+                    state
+                        .borrow_mut()
+                        .loaded
+                        .insert(policy_url.to_string(), policy);
+                }
+
+                let loaded = &state.borrow().loaded;
+                let policy = loaded.get(policy_url.as_str()).and_then(Option::as_ref);
+                if index == 0 {
+                    meta_policy = policy
+                        .and_then(|policy| policy.meta_policy)
+                        .unwrap_or(MetaPolicy::MasterOnly);
+                    if meta_policy == MetaPolicy::None {
+                        return false;
+                    }
+                }
+                if policy.is_some_and(|policy| policy.allows_http(&requester, &policy_url)) {
+                    return true;
+                }
+                if meta_policy == MetaPolicy::MasterOnly {
+                    return false;
+                }
+            }
+            false
+        })
+    }
+
+    /// Where the socket policy file for connections to `host` is requested from.
+    pub fn socket_policy_port(&self, host: &str) -> u16 {
+        self.state
+            .borrow()
+            .requested
+            .iter()
+            .find(|url| url.scheme() == "xmlsocket" && url.host_str() == Some(host))
+            .and_then(Url::port)
+            .unwrap_or(SOCKET_POLICY_PORT)
+    }
+
+    /// The socket policy file served at `host:port`, if it was already requested.
+    pub fn socket_policy(&self, host: &str, port: u16) -> Option<Option<PolicyFile>> {
+        self.state
+            .borrow()
+            .loaded
+            .get(&format!("xmlsocket://{host}:{port}"))
+            .cloned()
+    }
+
+    /// Remembers the socket policy file served at `host:port`, or that there isn't one.
+    pub fn set_socket_policy(&self, host: &str, port: u16, policy: Option<PolicyFile>) {
+        self.state
+            .borrow_mut()
+            .loaded
+            .insert(format!("xmlsocket://{host}:{port}"), policy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    fn allows_http(policy: &str, requester: &str, policy_url: &str) -> bool {
+        PolicyFile::parse(policy.as_bytes())
+            .unwrap()
+            .allows_http(&url(requester), &url(policy_url))
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert_eq!(PolicyFile::parse(b"<html><body/></html>"), None);
+        assert_eq!(PolicyFile::parse(b"<cross-domain-policy>"), None);
+        assert_eq!(PolicyFile::parse(b"not xml at all"), None);
+    }
+
+    #[test]
+    fn allows_domains() {
+        let policy = r#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <allow-access-from domain="www.example.com" />
+                <allow-access-from domain="*.example.org" />
+            </cross-domain-policy>"#;
+        let policy_url = "http://data.example/crossdomain.xml";
+        assert!(allows_http(
+            policy,
+            "http://WWW.example.com/a.swf",
+            policy_url
+        ));
+        assert!(!allows_http(policy, "http://example.com/a.swf", policy_url));
+        assert!(allows_http(policy, "http://example.org/a.swf", policy_url));
+        assert!(allows_http(
+            policy,
+            "http://a.b.example.org/a.swf",
+            policy_url
+        ));
+        assert!(!allows_http(
+            policy,
+            "http://badexample.org/a.swf",
+            policy_url
+        ));
+        assert!(!allows_http(policy, "file:///a.swf", policy_url));
+    }
+
+    #[test]
+    fn allows_everyone() {
+        let policy =
+            r#"<cross-domain-policy><allow-access-from domain="*"/></cross-domain-policy>"#;
+        assert!(allows_http(
+            policy,
+            "file:///a.swf",
+            "http://data.example/crossdomain.xml"
+        ));
+    }
+
+    #[test]
+    fn secure_policies() {
+        let policy =
+            r#"<cross-domain-policy><allow-access-from domain="*"/></cross-domain-policy>"#;
+        let insecure = r#"<cross-domain-policy>
+                <allow-access-from domain="*" secure="false"/>
+            </cross-domain-policy>"#;
+        let policy_url = "https://data.example/crossdomain.xml";
+        assert!(!allows_http(
+            policy,
+            "http://movie.example/a.swf",
+            policy_url
+        ));
+        assert!(allows_http(
+            policy,
+            "https://movie.example/a.swf",
+            policy_url
+        ));
+        assert!(allows_http(
+            insecure,
+            "http://movie.example/a.swf",
+            policy_url
+        ));
+    }
+
+    #[test]
+    fn socket_ports() {
+        let policy = PolicyFile::parse(
+            br#"<cross-domain-policy>
+                <allow-access-from domain="movie.example" to-ports="507,516-523"/>
+                <allow-access-from domain="other.example"/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+        let requester = url("http://movie.example/a.swf");
+        assert!(policy.allows_socket(&requester, 507));
+        assert!(policy.allows_socket(&requester, 520));
+        assert!(!policy.allows_socket(&requester, 508));
+        assert!(!policy.allows_socket(&url("http://other.example/a.swf"), 507));
+    }
+
+    #[test]
+    fn meta_policies() {
+        let policy = PolicyFile::parse(
+            br#"<cross-domain-policy>
+                <site-control permitted-cross-domain-policies="by-content-type"/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+        assert_eq!(policy.meta_policy, Some(MetaPolicy::All));
+    }
+
+    #[test]
+    fn policy_directories() {
+        let policy_url = url("http://data.example/api/crossdomain.xml");
+        assert!(policy_covers(
+            &policy_url,
+            &url("http://data.example/api/v1/x")
+        ));
+        assert!(!policy_covers(
+            &policy_url,
+            &url("http://data.example/other/x")
+        ));
+        assert!(!policy_covers(
+            &policy_url,
+            &url("https://data.example/api/x")
+        ));
+        assert_eq!(
+            master_policy_url(&url("https://data.example:8443/a/b?c")).as_str(),
+            "https://data.example:8443/crossdomain.xml"
+        );
+    }
+}
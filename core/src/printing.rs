@@ -0,0 +1,349 @@
+//! Printing, as done by `PrintJob` in both AVMs.
+//!
+//! There is no print dialog to ask for a printer and paper up front, so every job is laid
+//! out on US Letter paper in portrait orientation. Each page is rendered to an image when
+//! it's added, as the movie is free to change the sprite afterwards, and the finished job
+//! is handed to the `UiBackend` to be printed. Backends that print documents rather than
+//! images can lay the pages out as a PDF with `to_pdf`.
+
+use crate::backend::ui::PrintPage;
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::transform::{Transform, TransformStack};
+use std::io::Write;
+use swf::{Color, Rectangle, Twips};
+
+/// The size of the paper, in points (1/72 inch).
+pub const PAPER_WIDTH: u32 = 612;
+pub const PAPER_HEIGHT: u32 = 792;
+
+/// The margin on every side of the paper that printers can't reach, in points.
+const PAPER_MARGIN: u32 = 18;
+
+/// The size of the printable area of the paper, in points.
+pub const PAGE_WIDTH: u32 = PAPER_WIDTH - 2 * PAPER_MARGIN;
+pub const PAGE_HEIGHT: u32 = PAPER_HEIGHT - 2 * PAPER_MARGIN;
+
+/// The number of pixels per point that pages are rendered at.
+///
+/// Vector pages are rendered at a higher resolution than the screen, so that they stay sharp
+/// on paper. Pages printed as bitmaps are rendered at screen resolution, like in Flash.
+const VECTOR_SCALE: u32 = 3;
+const BITMAP_SCALE: u32 = 1;
+
+/// The print job that was started and not yet sent. Like in Flash, only one job can be
+/// in progress at a time.
+#[derive(Default)]
+pub struct PrintJob {
+    pages: Vec<PrintPage>,
+}
+
+/// Starts a new print job, returning `false` if printing isn't possible or a job is
+/// already in progress.
+pub fn start(context: &mut UpdateContext<'_>) -> bool {
+    if context.print_job.is_some() || !context.ui.can_print() {
+        return false;
+    }
+    *context.print_job = Some(PrintJob::default());
+    true
+}
+
+/// Renders `object` as a new page of the current print job.
+///
+/// `area` is in the coordinates of `object`, and defaults to the size of the page starting
+/// at its registration point. Its top left corner is placed at the top left of the page,
+/// and anything that doesn't fit on the page is cut off.
+///
+/// Returns `false` if no job is in progress, or the page couldn't be rendered.
+pub fn add_page<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+    area: Option<Rectangle<Twips>>,
+    print_as_bitmap: bool,
+) -> bool {
+    if context.print_job.is_none() {
+        return false;
+    }
+
+    let area = area.unwrap_or_else(|| Rectangle {
+        x_min: Twips::ZERO,
+        y_min: Twips::ZERO,
+        x_max: Twips::from_pixels(PAGE_WIDTH.into()),
+        y_max: Twips::from_pixels(PAGE_HEIGHT.into()),
+    });
+    let scale = if print_as_bitmap {
+        BITMAP_SCALE
+    } else {
+        VECTOR_SCALE
+    };
+    let width = (area.width().to_pixels().ceil() as u32).min(PAGE_WIDTH) * scale;
+    let height = (area.height().to_pixels().ceil() as u32).min(PAGE_HEIGHT) * scale;
+    if width == 0 || height == 0 {
+        return false;
+    }
+
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix: Matrix::scale(scale as f32, scale as f32)
+            * Matrix::translate(-area.x_min, -area.y_min),
+        color_transform: Default::default(),
+    });
+
+    let mut commands = CommandList::new();
+    // Paper is white, and printers can't print transparency.
+    commands.draw_rect(
+        Color::WHITE,
+        Matrix::create_box(width as f32, height as f32, Twips::ZERO, Twips::ZERO),
+    );
+
+    let mut cache_draws = vec![];
+    let mut render_context = RenderContext {
+        renderer: context.renderer,
+        commands,
+        cache_draws: &mut cache_draws,
+        gc_context: context.gc_context,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        is_offscreen: true,
+        use_bitmap_cache: false,
+        stage: context.stage,
+    };
+    // Like `BitmapData.draw()`, this ignores the transform and mask of the object itself.
+    object.render_self(&mut render_context);
+    let commands = render_context.commands;
+
+    let quality = context.stage.quality();
+    match context
+        .renderer
+        .capture_frame(commands, quality, width, height)
+    {
+        Ok(bitmap) => {
+            if let Some(job) = context.print_job.as_mut() {
+                job.pages.push(PrintPage {
+                    width,
+                    height,
+                    scale,
+                    rgba: bitmap.to_rgba().data().to_vec(),
+                });
+            }
+            true
+        }
+        Err(e) => {
+            tracing::warn!("Unable to render page to print: {e}");
+            false
+        }
+    }
+}
+
+/// Finishes the current print job, and sends its pages to the printer.
+pub fn send(context: &mut UpdateContext<'_>) {
+    if let Some(job) = context.print_job.take() {
+        if !job.pages.is_empty() {
+            context.ui.print(job.pages);
+        }
+    }
+}
+
+/// Discards the current print job without printing anything.
+pub fn abort(context: &mut UpdateContext<'_>) {
+    *context.print_job = None;
+}
+
+/// Lays out the pages of a finished print job as a PDF document, with each page on its own
+/// sheet of paper.
+pub fn to_pdf(pages: &[PrintPage]) -> Vec<u8> {
+    let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = vec![];
+
+    // The catalog and the page tree come first, then three objects for every page.
+    let page_ids: Vec<_> = (0..pages.len()).map(|i| 3 + 3 * i).collect();
+    write_pdf_object(&mut pdf, &mut offsets, b"<< /Type /Catalog /Pages 2 0 R >>");
+    let kids: Vec<_> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+    write_pdf_object(
+        &mut pdf,
+        &mut offsets,
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .as_bytes(),
+    );
+
+    for (page, id) in pages.iter().zip(page_ids) {
+        write_pdf_object(
+            &mut pdf,
+            &mut offsets,
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAPER_WIDTH} {PAPER_HEIGHT}] \
+                 /Resources << /XObject << /Page {} 0 R >> >> /Contents {} 0 R >>",
+                id + 2,
+                id + 1
+            )
+            .as_bytes(),
+        );
+
+        // PDF measures from the bottom left corner of the paper.
+        let width = page.width / page.scale;
+        let height = page.height / page.scale;
+        let contents = format!(
+            "q {width} 0 0 {height} {PAPER_MARGIN} {} cm /Page Do Q",
+            PAPER_HEIGHT - PAPER_MARGIN - height
+        );
+        write_pdf_stream(&mut pdf, &mut offsets, "", contents.as_bytes());
+
+        // Pages are always opaque, so the alpha channel is left out.
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        for pixel in page.rgba.chunks_exact(4) {
+            encoder
+                .write_all(&pixel[..3])
+                .expect("Writing to memory can't fail");
+        }
+        let image = encoder.finish().expect("Writing to memory can't fail");
+        write_pdf_stream(
+            &mut pdf,
+            &mut offsets,
+            &format!(
+                " /Type /XObject /Subtype /Image /Width {} /Height {} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode",
+                page.width, page.height
+            ),
+            &image,
+        );
+    }
+
+    let xref_offset = pdf.len();
+    let size = offsets.len() + 1;
+    pdf.extend_from_slice(format!("xref\n0 {size}\n0000000000 65535 f \n").as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!("trailer\n<< /Size {size} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n")
+            .as_bytes(),
+    );
+    pdf
+}
+
+/// Appends an object to a PDF document, numbering it after the ones before it.
+fn write_pdf_object(pdf: &mut Vec<u8>, offsets: &mut Vec<usize>, body: &[u8]) {
+    offsets.push(pdf.len());
+    pdf.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+    pdf.extend_from_slice(body);
+    pdf.extend_from_slice(b"\nendobj\n");
+}
+
+fn write_pdf_stream(pdf: &mut Vec<u8>, offsets: &mut Vec<usize>, dictionary: &str, data: &[u8]) {
+    let mut body = format!("<< /Length {}{dictionary} >>\nstream\n", data.len()).into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\nendstream");
+    write_pdf_object(pdf, offsets, &body);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    fn page(width: u32, height: u32, scale: u32, color: [u8; 4]) -> PrintPage {
+        PrintPage {
+            width,
+            height,
+            scale,
+            rgba: color.repeat((width * height) as usize),
+        }
+    }
+
+    fn find(haystack: &[u8], needle: &[u8], from: usize) -> usize {
+        from + haystack[from..]
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .expect("Needle should be in the document")
+    }
+
+    /// The data of every stream in the document, in order.
+    fn streams(pdf: &[u8]) -> Vec<&[u8]> {
+        let mut streams = vec![];
+        let mut position = 0;
+        while let Some(start) = pdf[position..]
+            .windows(b"/Length ".len())
+            .position(|window| window == b"/Length ")
+        {
+            let length_start = position + start + b"/Length ".len();
+            let length_end = find(pdf, b" ", length_start);
+            let length: usize = std::str::from_utf8(&pdf[length_start..length_end])
+                .unwrap()
+                .parse()
+                .unwrap();
+            let data_start = find(pdf, b"stream\n", length_end) + b"stream\n".len();
+            streams.push(&pdf[data_start..data_start + length]);
+            position = data_start + length;
+        }
+        streams
+    }
+
+    #[test]
+    fn pdf_has_a_sheet_per_page() {
+        let pages = [
+            page(6, 3, 3, [255, 0, 0, 255]),
+            page(4, 2, 1, [0, 0, 255, 255]),
+        ];
+        let pdf = to_pdf(&pages);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        find(&pdf, b"/Kids [3 0 R 6 0 R] /Count 2", 0);
+        assert_eq!(
+            pdf.windows(b"/MediaBox [0 0 612 792]".len())
+                .filter(|window| window == b"/MediaBox [0 0 612 792]")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn pdf_cross_reference_table_points_at_objects() {
+        let pdf = to_pdf(&[page(3, 3, 3, [255, 255, 255, 255])]);
+        let startxref = find(&pdf, b"startxref\n", 0) + b"startxref\n".len();
+        let xref_offset: usize = std::str::from_utf8(&pdf[startxref..find(&pdf, b"\n", startxref)])
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(pdf[xref_offset..].starts_with(b"xref\n0 6\n0000000000 65535 f \n"));
+
+        let entries = xref_offset + b"xref\n0 6\n".len() + 20;
+        for id in 1..6 {
+            let entry = &pdf[entries + (id - 1) * 20..entries + id * 20];
+            let offset: usize = std::str::from_utf8(&entry[..10]).unwrap().parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{id} 0 obj\n").as_bytes()));
+        }
+    }
+
+    #[test]
+    fn pdf_places_pages_at_top_left_of_printable_area() {
+        let pdf = to_pdf(&[page(30, 60, 3, [255, 255, 255, 255])]);
+        // 10x20 points, with the top 18 points below the top of 792 point tall paper.
+        assert_eq!(streams(&pdf)[0], b"q 10 0 0 20 18 754 cm /Page Do Q");
+    }
+
+    #[test]
+    fn pdf_images_hold_page_pixels() {
+        let mut print_page = page(2, 2, 1, [255, 255, 255, 255]);
+        print_page.rgba[4..8].copy_from_slice(&[10, 20, 30, 255]);
+        let pdf = to_pdf(&[print_page]);
+        find(&pdf, b"/Width 2 /Height 2", 0);
+
+        let mut rgb = vec![];
+        ZlibDecoder::new(streams(&pdf)[1])
+            .read_to_end(&mut rgb)
+            .unwrap();
+        assert_eq!(
+            rgb,
+            [255, 255, 255, 10, 20, 30, 255, 255, 255, 255, 255, 255]
+        );
+    }
+}
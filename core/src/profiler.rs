@@ -0,0 +1,268 @@
+//! Profiling the time spent in ActionScript functions.
+//!
+//! The profiler is instrumented: every AVM1 and AVM2 function call records when it was
+//! entered and left, and how many bytes were allocated on the GC heap in the meantime.
+//! Recording is only done while a profile is being taken, see `Player::start_profiling`.
+//!
+//! A finished [`Profile`] can be exported to the [speedscope] file format, which can be
+//! viewed as a flame graph.
+//!
+//! [speedscope]: https://github.com/jlfwong/speedscope/wiki/Importing-from-custom-sources
+
+use fnv::FnvHashMap;
+use serde_json::json;
+use std::time::Duration;
+use web_time::Instant;
+
+/// Records function calls while a profile is being taken.
+#[derive(Default)]
+pub struct Profiler {
+    session: Option<Session>,
+}
+
+struct Session {
+    start: Instant,
+    profile: Profile,
+    frame_indices: FnvHashMap<String, usize>,
+    stack: Vec<OpenCall>,
+}
+
+struct OpenCall {
+    frame: usize,
+    entered: Instant,
+    allocated: usize,
+    /// The time spent in the functions this one called.
+    children: Duration,
+    /// Whether the function was already being called further down the stack.
+    recursive: bool,
+}
+
+impl Profiler {
+    /// Starts taking a new profile, discarding any profile in progress.
+    pub fn start(&mut self) {
+        self.session = Some(Session {
+            start: Instant::now(),
+            profile: Profile::default(),
+            frame_indices: FnvHashMap::default(),
+            stack: Vec::new(),
+        });
+    }
+
+    /// Stops taking the profile and returns it, or `None` if no profile was being taken.
+    ///
+    /// Functions that are still running are treated as if they returned now.
+    pub fn stop(&mut self, allocated: usize) -> Option<Profile> {
+        let mut session = self.session.take()?;
+        while !session.stack.is_empty() {
+            session.exit(allocated);
+        }
+        session.profile.duration = session.start.elapsed();
+        Some(session.profile)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Records that a function was entered.
+    ///
+    /// `allocated` is the current size of the GC heap. The name is only built if a profile is
+    /// being taken.
+    pub fn enter(&mut self, name: impl FnOnce() -> String, allocated: usize) {
+        if let Some(session) = &mut self.session {
+            session.enter(name(), allocated);
+        }
+    }
+
+    /// Records that the most recently entered function returned.
+    pub fn exit(&mut self, allocated: usize) {
+        if let Some(session) = &mut self.session {
+            session.exit(allocated);
+        }
+    }
+}
+
+impl Session {
+    fn enter(&mut self, name: String, allocated: usize) {
+        let frame = match self.frame_indices.get(&name) {
+            Some(frame) => *frame,
+            None => {
+                let frame = self.profile.functions.len();
+                self.frame_indices.insert(name.clone(), frame);
+                self.profile.functions.push(FunctionStats::new(name));
+                frame
+            }
+        };
+
+        let entered = Instant::now();
+        self.profile.events.push(Event {
+            open: true,
+            frame,
+            at: entered - self.start,
+        });
+        let recursive = self.stack.iter().any(|call| call.frame == frame);
+        self.stack.push(OpenCall {
+            frame,
+            entered,
+            allocated,
+            children: Duration::ZERO,
+            recursive,
+        });
+    }
+
+    fn exit(&mut self, allocated: usize) {
+        let Some(call) = self.stack.pop() else {
+            return;
+        };
+
+        let exited = Instant::now();
+        let total = exited - call.entered;
+        self.profile.events.push(Event {
+            open: false,
+            frame: call.frame,
+            at: exited - self.start,
+        });
+
+        let stats = &mut self.profile.functions[call.frame];
+        stats.calls += 1;
+        stats.self_time += total.saturating_sub(call.children);
+        // Time spent in a recursive call is already part of the outer call.
+        if !call.recursive {
+            stats.total_time += total;
+            stats.allocated_bytes += allocated.saturating_sub(call.allocated);
+        }
+
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children += total;
+        }
+    }
+}
+
+/// How much time was spent in a single function.
+#[derive(Clone, Debug)]
+pub struct FunctionStats {
+    pub name: String,
+
+    /// How many times the function was called.
+    pub calls: u32,
+
+    /// The time spent in the function itself, excluding the functions it called.
+    pub self_time: Duration,
+
+    /// The time spent in the function, including the functions it called.
+    pub total_time: Duration,
+
+    /// How many bytes were allocated on the GC heap while the function ran.
+    pub allocated_bytes: usize,
+}
+
+impl FunctionStats {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            calls: 0,
+            self_time: Duration::ZERO,
+            total_time: Duration::ZERO,
+            allocated_bytes: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Event {
+    open: bool,
+    frame: usize,
+    at: Duration,
+}
+
+/// The function calls recorded between starting and stopping the profiler.
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    functions: Vec<FunctionStats>,
+    events: Vec<Event>,
+    duration: Duration,
+}
+
+impl Profile {
+    /// Every function that was called, in the order they were first called.
+    pub fn functions(&self) -> &[FunctionStats] {
+        &self.functions
+    }
+
+    /// How long the profile was taken for.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Exports the profile as a speedscope file, which can be opened in a flame graph viewer.
+    pub fn to_speedscope_json(&self) -> String {
+        let frames: Vec<_> = self
+            .functions
+            .iter()
+            .map(|function| json!({ "name": function.name }))
+            .collect();
+        let events: Vec<_> = self
+            .events
+            .iter()
+            .map(|event| {
+                json!({
+                    "type": if event.open { "O" } else { "C" },
+                    "frame": event.frame,
+                    "at": event.at.as_secs_f64() * 1_000_000.0,
+                })
+            })
+            .collect();
+
+        json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "exporter": concat!("ruffle ", env!("CARGO_PKG_VERSION")),
+            "name": "Ruffle ActionScript profile",
+            "shared": { "frames": frames },
+            "profiles": [{
+                "type": "evented",
+                "name": "ActionScript",
+                "unit": "microseconds",
+                "startValue": 0,
+                "endValue": self.duration.as_secs_f64() * 1_000_000.0,
+                "events": events,
+            }],
+        })
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_nested_calls() {
+        let mut profiler = Profiler::default();
+        profiler.enter(|| "ignored".to_string(), 0);
+        profiler.exit(0);
+
+        profiler.start();
+        profiler.enter(|| "outer".to_string(), 0);
+        profiler.enter(|| "inner".to_string(), 10);
+        profiler.enter(|| "inner".to_string(), 20);
+        profiler.exit(30);
+        profiler.exit(40);
+        profiler.exit(50);
+        let profile = profiler.stop(50).unwrap();
+
+        let functions = profile.functions();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].name, "outer");
+        assert_eq!(functions[0].calls, 1);
+        assert_eq!(functions[0].allocated_bytes, 50);
+        assert_eq!(functions[1].name, "inner");
+        assert_eq!(functions[1].calls, 2);
+        assert_eq!(functions[1].allocated_bytes, 30);
+        assert!(functions[0].total_time >= functions[1].total_time);
+        assert!(!profiler.is_active());
+
+        let json: serde_json::Value = serde_json::from_str(&profile.to_speedscope_json()).unwrap();
+        assert_eq!(json["shared"]["frames"][1]["name"], "inner");
+        assert_eq!(json["profiles"][0]["events"].as_array().unwrap().len(), 6);
+    }
+}
@@ -0,0 +1,607 @@
+//! Recording a movie's input, and replaying it deterministically.
+//!
+//! While a replay session is active, the movie runs on a fixed timestep rather than the wall
+//! clock. Everything that could differ between runs is derived from the replay instead:
+//!
+//! * `getTimer` reports the time since the movie started as if every frame took exactly one
+//!   frame's worth of time, and `Date` adds that time to the date the recording started at.
+//! * `Math.random` and `flash.crypto.generateRandomBytes` draw from a generator seeded with
+//!   the replay's seed.
+//! * Input events are delivered before the frame they were recorded in. While a replay is
+//!   played back, input from the user is ignored.
+//!
+//! Recording and playback both start before the first frame of the movie, so a replay only
+//! reproduces a run of the movie it was recorded with. Content loaded at runtime, sockets and
+//! sounds are not part of the replay, and may still make playback diverge if they take a
+//! different number of frames to arrive.
+//!
+//! Replays also serve as save states. These are not snapshots: nothing of the player's state
+//! is serialized, as the AVM1 and AVM2 heaps can't be. A save state is only the replay of
+//! everything up to the point it was saved at, so loading one restarts the movie and plays
+//! every frame up to that point again, as fast as possible, before recording goes on. This
+//! takes longer the longer the movie had been running, and has the same limits as replays:
+//! the movie must be the same, and content loaded at runtime may make it diverge.
+
+use crate::events::{
+    GamepadButton, KeyCode, MouseButton, MouseWheelDelta, PlayerEvent, TextControlCode,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// The version of the `Replay` format.
+///
+/// This must be increased whenever a change would make older replays play back differently.
+pub const REPLAY_FORMAT_VERSION: u64 = 1;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Invalid replay: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error(
+        "Unsupported replay version {0}, expected {expected}",
+        expected = REPLAY_FORMAT_VERSION
+    )]
+    UnsupportedVersion(u64),
+
+    #[error("Missing or invalid \"{0}\" in replay")]
+    InvalidField(&'static str),
+
+    #[error("Invalid input event at index {0}")]
+    InvalidInput(usize),
+}
+
+/// A recorded run of a movie.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    /// The seed for the random number generator used by the movie.
+    pub seed: u64,
+
+    /// The date and time that the movie started at, in milliseconds since the Unix epoch.
+    pub start_date: i64,
+
+    /// The number of frames that were recorded.
+    pub frames: u32,
+
+    /// Input events, along with the (zero-based) frame that each is delivered before.
+    pub inputs: Vec<(u32, PlayerEvent)>,
+}
+
+impl Replay {
+    pub fn to_json(&self) -> String {
+        let inputs: Vec<_> = self
+            .inputs
+            .iter()
+            .map(|(frame, event)| {
+                let mut input = event_to_json(event);
+                input["frame"] = json!(frame);
+                input
+            })
+            .collect();
+
+        json!({
+            "version": REPLAY_FORMAT_VERSION,
+            "ruffleVersion": env!("CARGO_PKG_VERSION"),
+            "seed": self.seed,
+            "startDate": self.start_date,
+            "frames": self.frames,
+            "inputs": inputs,
+        })
+        .to_string()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ReplayError> {
+        let replay: Value = serde_json::from_str(json)?;
+        let version = replay["version"]
+            .as_u64()
+            .ok_or(ReplayError::InvalidField("version"))?;
+        if version != REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let inputs = replay["inputs"]
+            .as_array()
+            .ok_or(ReplayError::InvalidField("inputs"))?
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let frame = input["frame"].as_u64().and_then(|f| u32::try_from(f).ok());
+                frame
+                    .zip(event_from_json(input))
+                    .ok_or(ReplayError::InvalidInput(index))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            seed: replay["seed"]
+                .as_u64()
+                .ok_or(ReplayError::InvalidField("seed"))?,
+            start_date: replay["startDate"]
+                .as_i64()
+                .ok_or(ReplayError::InvalidField("startDate"))?,
+            frames: replay["frames"]
+                .as_u64()
+                .and_then(|frames| u32::try_from(frames).ok())
+                .ok_or(ReplayError::InvalidField("frames"))?,
+            inputs,
+        })
+    }
+}
+
+/// Whether a replay session is recording or playing back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    Recording,
+    Playing,
+}
+
+/// The time as seen by the movie during a replay session.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayClock {
+    start_date: i64,
+    elapsed: f64,
+}
+
+impl ReplayClock {
+    /// The time since the movie started, in milliseconds.
+    pub fn elapsed_millis(self) -> u32 {
+        self.elapsed as u32
+    }
+
+    pub fn date_time(self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.start_date + self.elapsed as i64)
+            .single()
+            .unwrap_or_default()
+    }
+}
+
+/// A replay being recorded or played back by a `Player`. See the module documentation.
+#[derive(Debug)]
+pub struct ReplaySession {
+    replay: Replay,
+    mode: ReplayMode,
+
+    /// The inputs that are yet to be delivered, when playing back.
+    pending: VecDeque<(u32, PlayerEvent)>,
+
+    /// The number of frames run so far.
+    frame: u32,
+
+    /// The time since the movie started, in milliseconds.
+    elapsed: f64,
+
+    /// Whether this is a save state being loaded, which goes on recording once played back.
+    resuming: bool,
+}
+
+impl ReplaySession {
+    pub fn recording(seed: u64, start_date: i64) -> Self {
+        Self {
+            replay: Replay {
+                seed,
+                start_date,
+                frames: 0,
+                inputs: Vec::new(),
+            },
+            mode: ReplayMode::Recording,
+            pending: VecDeque::new(),
+            frame: 0,
+            elapsed: 0.0,
+            resuming: false,
+        }
+    }
+
+    pub fn playing(replay: Replay) -> Self {
+        let mut inputs = replay.inputs.clone();
+        inputs.sort_by_key(|(frame, _)| *frame);
+
+        Self {
+            replay,
+            mode: ReplayMode::Playing,
+            pending: inputs.into(),
+            frame: 0,
+            elapsed: 0.0,
+            resuming: false,
+        }
+    }
+
+    /// Loads a save state made by `snapshot`.
+    pub fn resuming(replay: Replay) -> Self {
+        Self {
+            resuming: true,
+            ..Self::playing(replay)
+        }
+    }
+
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    pub fn clock(&self) -> ReplayClock {
+        ReplayClock {
+            start_date: self.replay.start_date,
+            elapsed: self.elapsed,
+        }
+    }
+
+    /// Whether every recorded frame has been played back.
+    pub fn is_finished(&self) -> bool {
+        self.mode == ReplayMode::Playing && self.frame >= self.replay.frames
+    }
+
+    /// Whether this is a save state that is still being played back.
+    pub fn is_resuming(&self) -> bool {
+        self.resuming && self.mode == ReplayMode::Playing
+    }
+
+    /// Switches a save state that was played back to recording what comes after it.
+    ///
+    /// Returns the input that was recorded after the last frame of the save state, which has
+    /// yet to be delivered.
+    pub fn resume_recording(&mut self) -> Vec<PlayerEvent> {
+        self.mode = ReplayMode::Recording;
+        self.pending.drain(..).map(|(_, event)| event).collect()
+    }
+
+    /// Returns a save state of the movie as it is now: the replay up to the current frame.
+    pub fn snapshot(&self) -> Replay {
+        let mut replay = self.replay.clone();
+        replay.frames = self.frame;
+        if self.mode == ReplayMode::Playing {
+            // Input recorded before the next frame is only delivered when it runs.
+            replay.inputs.retain(|(frame, _)| *frame < self.frame);
+        }
+        replay
+    }
+
+    /// Records an input event that arrived before the next frame, if recording.
+    pub fn record(&mut self, event: PlayerEvent) {
+        if self.mode == ReplayMode::Recording {
+            self.replay.inputs.push((self.frame, event));
+        }
+    }
+
+    /// Takes the recorded input events to deliver before the next frame, if playing back.
+    pub fn take_inputs(&mut self) -> Vec<PlayerEvent> {
+        let mut events = Vec::new();
+        while let Some(&(_, event)) = self.pending.front().filter(|(at, _)| *at <= self.frame) {
+            self.pending.pop_front();
+            events.push(event);
+        }
+        events
+    }
+
+    /// Moves on to the next frame, after one that took `frame_time` milliseconds.
+    pub fn advance(&mut self, frame_time: f64) {
+        self.frame += 1;
+        self.elapsed += frame_time;
+        if self.mode == ReplayMode::Recording {
+            self.replay.frames = self.frame;
+        }
+    }
+
+    pub fn into_replay(self) -> Replay {
+        self.replay
+    }
+}
+
+const TEXT_CONTROL_CODES: [TextControlCode; 25] = [
+    TextControlCode::MoveLeft,
+    TextControlCode::MoveLeftWord,
+    TextControlCode::MoveLeftLine,
+    TextControlCode::MoveLeftDocument,
+    TextControlCode::MoveRight,
+    TextControlCode::MoveRightWord,
+    TextControlCode::MoveRightLine,
+    TextControlCode::MoveRightDocument,
+    TextControlCode::SelectLeft,
+    TextControlCode::SelectLeftWord,
+    TextControlCode::SelectLeftLine,
+    TextControlCode::SelectLeftDocument,
+    TextControlCode::SelectRight,
+    TextControlCode::SelectRightWord,
+    TextControlCode::SelectRightLine,
+    TextControlCode::SelectRightDocument,
+    TextControlCode::SelectAll,
+    TextControlCode::Copy,
+    TextControlCode::Paste,
+    TextControlCode::Cut,
+    TextControlCode::Backspace,
+    TextControlCode::BackspaceWord,
+    TextControlCode::Enter,
+    TextControlCode::Delete,
+    TextControlCode::DeleteWord,
+];
+
+const GAMEPAD_BUTTONS: [GamepadButton; 14] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::North,
+    GamepadButton::West,
+    GamepadButton::LeftTrigger,
+    GamepadButton::LeftTrigger2,
+    GamepadButton::RightTrigger,
+    GamepadButton::RightTrigger2,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+/// Finds the value whose `Debug` name is `name`.
+fn from_name<T: Debug + Copy>(values: &[T], name: &str) -> Option<T> {
+    values
+        .iter()
+        .copied()
+        .find(|value| format!("{value:?}") == name)
+}
+
+fn button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Unknown => "unknown",
+        MouseButton::Left => "left",
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+    }
+}
+
+fn event_to_json(event: &PlayerEvent) -> Value {
+    match *event {
+        PlayerEvent::KeyDown { key_code, key_char } => {
+            json!({ "type": "keyDown", "keyCode": key_code.value(), "keyChar": key_char })
+        }
+        PlayerEvent::KeyUp { key_code, key_char } => {
+            json!({ "type": "keyUp", "keyCode": key_code.value(), "keyChar": key_char })
+        }
+        PlayerEvent::MouseMove { x, y } => json!({ "type": "mouseMove", "x": x, "y": y }),
+        PlayerEvent::MouseUp { x, y, button } => {
+            json!({ "type": "mouseUp", "x": x, "y": y, "button": button_name(button) })
+        }
+        PlayerEvent::MouseDown {
+            x,
+            y,
+            button,
+            index,
+        } => json!({
+            "type": "mouseDown",
+            "x": x,
+            "y": y,
+            "button": button_name(button),
+            "index": index,
+        }),
+        PlayerEvent::MouseLeave => json!({ "type": "mouseLeave" }),
+        PlayerEvent::MouseMotion { dx, dy } => json!({ "type": "mouseMotion", "dx": dx, "dy": dy }),
+        PlayerEvent::MouseWheel { delta } => match delta {
+            MouseWheelDelta::Lines(lines) => json!({ "type": "mouseWheel", "lines": lines }),
+            MouseWheelDelta::Pixels(pixels) => json!({ "type": "mouseWheel", "pixels": pixels }),
+        },
+        PlayerEvent::GamepadButtonDown { button } => {
+            json!({ "type": "gamepadButtonDown", "button": format!("{button:?}") })
+        }
+        PlayerEvent::GamepadButtonUp { button } => {
+            json!({ "type": "gamepadButtonUp", "button": format!("{button:?}") })
+        }
+        PlayerEvent::TextInput { codepoint } => json!({ "type": "textInput", "char": codepoint }),
+        PlayerEvent::TextControl { code } => {
+            json!({ "type": "textControl", "code": format!("{code:?}") })
+        }
+        PlayerEvent::FocusGained => json!({ "type": "focusGained" }),
+        PlayerEvent::FocusLost => json!({ "type": "focusLost" }),
+    }
+}
+
+fn event_from_json(input: &Value) -> Option<PlayerEvent> {
+    let number = |key: &str| input[key].as_f64();
+    let key_code = || {
+        input["keyCode"]
+            .as_u64()
+            .and_then(|code| u32::try_from(code).ok())
+            .map(KeyCode::from_code)
+    };
+    let key_char = || input["keyChar"].as_str().and_then(|c| c.chars().next());
+    let mouse_button = || {
+        Some(match input["button"].as_str()? {
+            "left" => MouseButton::Left,
+            "right" => MouseButton::Right,
+            "middle" => MouseButton::Middle,
+            _ => MouseButton::Unknown,
+        })
+    };
+    let gamepad_button = || from_name(&GAMEPAD_BUTTONS, input["button"].as_str()?);
+
+    Some(match input["type"].as_str()? {
+        "keyDown" => PlayerEvent::KeyDown {
+            key_code: key_code()?,
+            key_char: key_char(),
+        },
+        "keyUp" => PlayerEvent::KeyUp {
+            key_code: key_code()?,
+            key_char: key_char(),
+        },
+        "mouseMove" => PlayerEvent::MouseMove {
+            x: number("x")?,
+            y: number("y")?,
+        },
+        "mouseUp" => PlayerEvent::MouseUp {
+            x: number("x")?,
+            y: number("y")?,
+            button: mouse_button()?,
+        },
+        "mouseDown" => PlayerEvent::MouseDown {
+            x: number("x")?,
+            y: number("y")?,
+            button: mouse_button()?,
+            index: input["index"]
+                .as_u64()
+                .and_then(|index| usize::try_from(index).ok()),
+        },
+        "mouseLeave" => PlayerEvent::MouseLeave,
+        "mouseMotion" => PlayerEvent::MouseMotion {
+            dx: number("dx")?,
+            dy: number("dy")?,
+        },
+        "mouseWheel" => PlayerEvent::MouseWheel {
+            delta: match number("lines") {
+                Some(lines) => MouseWheelDelta::Lines(lines),
+                None => MouseWheelDelta::Pixels(number("pixels")?),
+            },
+        },
+        "gamepadButtonDown" => PlayerEvent::GamepadButtonDown {
+            button: gamepad_button()?,
+        },
+        "gamepadButtonUp" => PlayerEvent::GamepadButtonUp {
+            button: gamepad_button()?,
+        },
+        "textInput" => PlayerEvent::TextInput {
+            codepoint: input["char"].as_str()?.chars().next()?,
+        },
+        "textControl" => PlayerEvent::TextControl {
+            code: from_name(&TEXT_CONTROL_CODES, input["code"].as_str()?)?,
+        },
+        "focusGained" => PlayerEvent::FocusGained,
+        "focusLost" => PlayerEvent::FocusLost,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let replay = Replay {
+            seed: u64::MAX,
+            start_date: 1_700_000_000_000,
+            frames: 90,
+            inputs: vec![
+                (
+                    0,
+                    PlayerEvent::KeyDown {
+                        key_code: KeyCode::A,
+                        key_char: Some('a'),
+                    },
+                ),
+                (
+                    3,
+                    PlayerEvent::MouseDown {
+                        x: 10.5,
+                        y: 20.0,
+                        button: MouseButton::Right,
+                        index: Some(2),
+                    },
+                ),
+                (
+                    4,
+                    PlayerEvent::MouseWheel {
+                        delta: MouseWheelDelta::Pixels(-40.0),
+                    },
+                ),
+                (
+                    5,
+                    PlayerEvent::GamepadButtonDown {
+                        button: GamepadButton::DPadLeft,
+                    },
+                ),
+                (
+                    6,
+                    PlayerEvent::TextControl {
+                        code: TextControlCode::SelectRightWord,
+                    },
+                ),
+                (7, PlayerEvent::TextInput { codepoint: '€' }),
+                (8, PlayerEvent::FocusLost),
+            ],
+        };
+
+        assert_eq!(Replay::from_json(&replay.to_json()).unwrap(), replay);
+    }
+
+    #[test]
+    fn rejects_invalid_replays() {
+        assert!(matches!(
+            Replay::from_json(r#"{ "version": 999 }"#),
+            Err(ReplayError::UnsupportedVersion(999))
+        ));
+        assert!(matches!(
+            Replay::from_json(
+                r#"{
+                    "version": 1, "seed": 1, "startDate": 0, "frames": 1,
+                    "inputs": [{ "frame": 0, "type": "teleport" }]
+                }"#
+            ),
+            Err(ReplayError::InvalidInput(0))
+        ));
+    }
+
+    #[test]
+    fn plays_inputs_before_their_frame() {
+        let mut session = ReplaySession::playing(Replay {
+            seed: 0,
+            start_date: 0,
+            frames: 2,
+            inputs: vec![(1, PlayerEvent::FocusLost), (0, PlayerEvent::FocusGained)],
+        });
+
+        assert_eq!(session.take_inputs(), [PlayerEvent::FocusGained]);
+        session.advance(25.0);
+        assert_eq!(session.clock().elapsed_millis(), 25);
+        assert_eq!(session.take_inputs(), [PlayerEvent::FocusLost]);
+        session.advance(25.0);
+        assert!(session.is_finished());
+    }
+
+    #[test]
+    fn snapshots_only_delivered_inputs() {
+        let mut session = ReplaySession::playing(Replay {
+            seed: 0,
+            start_date: 0,
+            frames: 3,
+            inputs: vec![(0, PlayerEvent::FocusGained), (1, PlayerEvent::FocusLost)],
+        });
+
+        assert_eq!(session.take_inputs(), [PlayerEvent::FocusGained]);
+        session.advance(25.0);
+        let state = session.snapshot();
+        assert_eq!(state.frames, 1);
+        assert_eq!(state.inputs, [(0, PlayerEvent::FocusGained)]);
+    }
+
+    #[test]
+    fn resumes_recording_after_save_state() {
+        let mut recording = ReplaySession::recording(7, 0);
+        recording.record(PlayerEvent::FocusGained);
+        recording.advance(25.0);
+        // Input that arrives after the last frame is part of the save state too.
+        recording.record(PlayerEvent::FocusLost);
+        let state = recording.snapshot();
+        assert_eq!(state.frames, 1);
+
+        let mut session = ReplaySession::resuming(state);
+        assert!(session.is_resuming());
+        assert_eq!(session.take_inputs(), [PlayerEvent::FocusGained]);
+        session.advance(25.0);
+        assert!(session.is_finished());
+        assert_eq!(session.resume_recording(), [PlayerEvent::FocusLost]);
+        assert!(!session.is_resuming());
+        assert_eq!(session.mode(), ReplayMode::Recording);
+
+        session.advance(25.0);
+        let state = session.snapshot();
+        assert_eq!(state.seed, 7);
+        assert_eq!(state.frames, 2);
+        assert_eq!(
+            state.inputs,
+            [(0, PlayerEvent::FocusGained), (1, PlayerEvent::FocusLost)]
+        );
+    }
+}
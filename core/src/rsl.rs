@@ -0,0 +1,306 @@
+//! Adobe's signed runtime shared libraries (RSLs).
+//!
+//! Movies made with Flash CS5 and later that use TLF text don't embed the Text Layout
+//! Framework. Instead, their preloader downloads it as a signed `.swz` archive from
+//! Adobe's servers (falling back to a copy next to the movie), and only shows its
+//! content once it has been loaded. Flex movies load their framework the same way.
+//!
+//! Those servers can't be relied upon, and browsers refuse to read from them, which
+//! leaves such movies without any text. The embedder may provide a mirror of them instead,
+//! which loads of signed RSLs are then sent to. The archives themselves are unpacked
+//! by the `Loader` once the preloader hands them over with `loadBytes`.
+//!
+//! Flash Player kept signed RSLs in a cache shared by every movie, as they're signed by
+//! Adobe and can't differ between servers. Once one has been loaded, loading it again
+//! (from any of Adobe's servers) is answered from the `RslCache`.
+
+use crate::backend::navigator::{ErrorResponse, OwnedFuture, SuccessResponse};
+use crate::loader::Error;
+use encoding_rs::Encoding;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use url::Url;
+
+/// The hosts that Adobe served its signed RSLs from.
+const RSL_HOSTS: &[&str] = &[
+    "fpdownload.adobe.com",
+    "fpdownload.macromedia.com",
+    "fpdownload2.macromedia.com",
+];
+
+/// The path on those hosts that the RSLs are in.
+const RSL_PATH: &str = "/pub/swz/";
+
+/// A location that holds copies of Adobe's signed RSLs, laid out like `/pub/swz/` on
+/// Adobe's servers (such as `tlf/2.0.0.232/textLayout_2.0.0.232.swz`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RslMirror(Url);
+
+impl RslMirror {
+    /// Parses the URL of a mirror. Returns `None` if it isn't a valid URL.
+    pub fn new(base: &str) -> Option<Self> {
+        let mut base = match Url::parse(base) {
+            Ok(base) if !base.cannot_be_a_base() => base,
+            _ => {
+                tracing::warn!("Ignoring invalid RSL mirror {base}");
+                return None;
+            }
+        };
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        Some(Self(base))
+    }
+
+    /// The mirrored copy of the given URL, if it points to one of Adobe's signed RSLs.
+    pub fn redirect(&self, url: &Url) -> Option<Url> {
+        let path = signed_rsl_path(url)?;
+        self.0.join(path).ok()
+    }
+}
+
+/// The signed RSLs that have been loaded so far, by their path within `/pub/swz/`.
+#[derive(Clone, Default)]
+pub struct RslCache(Rc<RefCell<HashMap<String, Rc<[u8]>>>>);
+
+impl RslCache {
+    /// A response with the cached copy of the given URL, if it points to one of Adobe's
+    /// signed RSLs that has already been loaded.
+    pub fn get(&self, url: &Url) -> Option<Box<dyn SuccessResponse>> {
+        let path = signed_rsl_path(url)?;
+        let body = self.0.borrow().get(path)?.clone();
+        tracing::info!("Loading RSL {url} from the cache");
+        Some(Box::new(CachedResponse {
+            url: url.to_string(),
+            body: Some(body),
+        }))
+    }
+
+    /// Caches the body of the response to the given fetch once it has been read, if the
+    /// URL points to one of Adobe's signed RSLs.
+    pub fn cache(
+        &self,
+        url: &Url,
+        fetch: OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse>,
+    ) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        let Some(path) = signed_rsl_path(url) else {
+            return fetch;
+        };
+        let cache = self.clone();
+        let path = path.to_string();
+        Box::pin(async move {
+            let response = fetch.await?;
+            if response.status() != 200 {
+                return Ok(response);
+            }
+            let response: Box<dyn SuccessResponse> = Box::new(CachingResponse {
+                inner: response,
+                cache,
+                path,
+                received: Default::default(),
+            });
+            Ok(response)
+        })
+    }
+
+    fn insert(&self, path: String, body: &[u8]) {
+        self.0.borrow_mut().insert(path, body.into());
+    }
+}
+
+/// A signed RSL that is answered from the cache.
+struct CachedResponse {
+    url: String,
+    body: Option<Rc<[u8]>>,
+}
+
+impl SuccessResponse for CachedResponse {
+    fn url(&self) -> Cow<str> {
+        Cow::Borrowed(&self.url)
+    }
+
+    fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+        let body = self.body.map(|body| body.to_vec()).unwrap_or_default();
+        Box::pin(async move { Ok(body) })
+    }
+
+    fn text_encoding(&self) -> Option<&'static Encoding> {
+        None
+    }
+
+    fn status(&self) -> u16 {
+        200
+    }
+
+    fn redirected(&self) -> bool {
+        false
+    }
+
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        // The whole body is handed over at once.
+        let chunk = self.body.take().map(|body| body.to_vec());
+        Box::pin(async move { Ok(chunk) })
+    }
+
+    fn expected_length(&self) -> Result<Option<u64>, Error> {
+        Ok(self.body.as_ref().map(|body| body.len() as u64))
+    }
+}
+
+/// A signed RSL that is being downloaded, which is cached once all of it has been read.
+struct CachingResponse {
+    inner: Box<dyn SuccessResponse>,
+    cache: RslCache,
+    path: String,
+
+    /// What has been read with `next_chunk` so far.
+    received: Rc<RefCell<Vec<u8>>>,
+}
+
+impl SuccessResponse for CachingResponse {
+    fn url(&self) -> Cow<str> {
+        self.inner.url()
+    }
+
+    fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+        let Self {
+            inner, cache, path, ..
+        } = *self;
+        Box::pin(async move {
+            let body = inner.body().await?;
+            cache.insert(path, &body);
+            Ok(body)
+        })
+    }
+
+    fn text_encoding(&self) -> Option<&'static Encoding> {
+        self.inner.text_encoding()
+    }
+
+    fn status(&self) -> u16 {
+        self.inner.status()
+    }
+
+    fn redirected(&self) -> bool {
+        self.inner.redirected()
+    }
+
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let chunk = self.inner.next_chunk();
+        let cache = self.cache.clone();
+        let path = self.path.clone();
+        let received = self.received.clone();
+        Box::pin(async move {
+            let chunk = chunk.await?;
+            match &chunk {
+                Some(chunk) => received.borrow_mut().extend_from_slice(chunk),
+                None => cache.insert(path, &received.take()),
+            }
+            Ok(chunk)
+        })
+    }
+
+    fn expected_length(&self) -> Result<Option<u64>, Error> {
+        self.inner.expected_length()
+    }
+}
+
+/// The path of a signed RSL within `/pub/swz/`, if the given URL points to one.
+fn signed_rsl_path(url: &Url) -> Option<&str> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?;
+    if !RSL_HOSTS
+        .iter()
+        .any(|rsl_host| host.eq_ignore_ascii_case(rsl_host))
+    {
+        return None;
+    }
+    url.path()
+        .strip_prefix(RSL_PATH)
+        .filter(|path| path.ends_with(".swz"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redirect(mirror: &str, url: &str) -> Option<String> {
+        RslMirror::new(mirror)?
+            .redirect(&Url::parse(url).unwrap())
+            .map(|url| url.to_string())
+    }
+
+    #[test]
+    fn redirects_signed_rsls() {
+        assert_eq!(
+            redirect(
+                "https://mirror.example/rsl",
+                "http://fpdownload.adobe.com/pub/swz/tlf/1.0.0.595/textLayout_1.0.0.595.swz"
+            )
+            .as_deref(),
+            Some("https://mirror.example/rsl/tlf/1.0.0.595/textLayout_1.0.0.595.swz")
+        );
+        assert_eq!(
+            redirect(
+                "https://mirror.example/",
+                "https://FPDOWNLOAD2.macromedia.com/pub/swz/flex/4.0.0.14159/framework_4.0.0.14159.swz"
+            )
+            .as_deref(),
+            Some("https://mirror.example/flex/4.0.0.14159/framework_4.0.0.14159.swz")
+        );
+    }
+
+    #[test]
+    fn ignores_other_urls() {
+        let mirror = "https://mirror.example/rsl/";
+        assert!(redirect(
+            mirror,
+            "https://fpdownload.adobe.com/pub/swz/crossdomain.xml"
+        )
+        .is_none());
+        assert!(redirect(mirror, "https://fpdownload.adobe.com/other/textLayout.swz").is_none());
+        assert!(redirect(mirror, "https://example.com/pub/swz/textLayout.swz").is_none());
+        assert!(redirect(mirror, "file:///pub/swz/textLayout.swz").is_none());
+        assert!(redirect("not a url", "https://fpdownload.adobe.com/pub/swz/a.swz").is_none());
+    }
+
+    #[test]
+    fn caches_signed_rsls() {
+        use futures::executor::block_on;
+
+        let cache = RslCache::default();
+        let url = Url::parse(
+            "https://fpdownload.adobe.com/pub/swz/tlf/1.0.0.595/textLayout_1.0.0.595.swz",
+        )
+        .unwrap();
+        assert!(cache.get(&url).is_none());
+
+        let response: Box<dyn SuccessResponse> = Box::new(CachedResponse {
+            url: url.to_string(),
+            body: Some(b"swz".as_slice().into()),
+        });
+        let fetch = cache.cache(&url, Box::pin(async move { Ok(response) }));
+        let body = block_on(async {
+            let response = fetch
+                .await
+                .unwrap_or_else(|_| panic!("Fetch should succeed"));
+            response.body().await.unwrap()
+        });
+        assert_eq!(body, b"swz");
+
+        // The same RSL on another of Adobe's servers is the same file.
+        let other_url = Url::parse(
+            "http://fpdownload.macromedia.com/pub/swz/tlf/1.0.0.595/textLayout_1.0.0.595.swz",
+        )
+        .unwrap();
+        let cached = cache.get(&other_url).expect("RSL should be cached");
+        assert_eq!(block_on(cached.body()).unwrap(), b"swz");
+
+        let uncached_url = Url::parse("https://example.com/textLayout_1.0.0.595.swz").unwrap();
+        assert!(cache.get(&uncached_url).is_none());
+    }
+}
@@ -61,3 +61,119 @@ impl SandboxType {
         }
     }
 }
+
+/// The places that movies may load SWFs and data from, as configured by the embedder.
+///
+/// Each entry is either a host (`example.com`), a host along with all of its subdomains
+/// (`*.example.com`), or an origin (`https://example.com:8080`).
+/// Loads from the origin of the root movie are always allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadAllowList(Vec<AllowedOrigin>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AllowedOrigin {
+    Host(String),
+    HostAndSubdomains(String),
+    Origin(url::Origin),
+}
+
+impl LoadAllowList {
+    /// Parses the entries of an allow list. Invalid entries are skipped.
+    pub fn new(entries: &[String]) -> Self {
+        let entries = entries
+            .iter()
+            .filter_map(|entry| {
+                let entry = entry.trim();
+                if entry.contains("://") {
+                    match Url::parse(entry).map(|url| url.origin()) {
+                        Ok(origin) if origin.is_tuple() => Some(AllowedOrigin::Origin(origin)),
+                        _ => {
+                            tracing::warn!("Ignoring invalid load allow list entry {entry}");
+                            None
+                        }
+                    }
+                } else if let Some(host) = entry.strip_prefix("*.") {
+                    Some(AllowedOrigin::HostAndSubdomains(host.to_ascii_lowercase()))
+                } else if !entry.is_empty() {
+                    Some(AllowedOrigin::Host(entry.to_ascii_lowercase()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        Self(entries)
+    }
+
+    /// Whether a movie whose root movie came from `root_url` may load from `url`.
+    pub fn allows(&self, url: &Url, root_url: Option<&Url>) -> bool {
+        if let Some(root_url) = root_url {
+            // Local files don't have an origin to compare, but may load each other.
+            if root_url.origin() == url.origin()
+                || (root_url.scheme() == "file" && url.scheme() == "file")
+            {
+                return true;
+            }
+        }
+
+        let origin = url.origin();
+        let host = url.host_str().unwrap_or_default();
+        self.0.iter().any(|entry| match entry {
+            AllowedOrigin::Host(allowed) => host == allowed,
+            AllowedOrigin::HostAndSubdomains(allowed) => {
+                host == allowed
+                    || host
+                        .strip_suffix(allowed.as_str())
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            }
+            AllowedOrigin::Origin(allowed) => origin == *allowed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allows(entries: &[&str], url: &str) -> bool {
+        let entries: Vec<String> = entries.iter().map(|entry| entry.to_string()).collect();
+        let root_url = Url::parse("https://root.example/movie.swf").unwrap();
+        LoadAllowList::new(&entries).allows(&Url::parse(url).unwrap(), Some(&root_url))
+    }
+
+    #[test]
+    fn allows_root_origin() {
+        assert!(allows(&[], "https://root.example/data.xml"));
+        assert!(!allows(&[], "http://root.example/data.xml"));
+        assert!(!allows(&[], "https://other.example/data.xml"));
+    }
+
+    #[test]
+    fn allows_hosts() {
+        assert!(allows(&["Cdn.Example"], "http://cdn.example:8080/a.swf"));
+        assert!(!allows(&["cdn.example"], "https://www.cdn.example/a.swf"));
+    }
+
+    #[test]
+    fn allows_subdomains() {
+        assert!(allows(&["*.cdn.example"], "https://cdn.example/a.swf"));
+        assert!(allows(&["*.cdn.example"], "https://a.b.cdn.example/a.swf"));
+        assert!(!allows(&["*.cdn.example"], "https://evilcdn.example/a.swf"));
+    }
+
+    #[test]
+    fn allows_origins() {
+        assert!(allows(
+            &["https://cdn.example"],
+            "https://cdn.example/a.swf"
+        ));
+        assert!(!allows(
+            &["https://cdn.example"],
+            "http://cdn.example/a.swf"
+        ));
+        assert!(!allows(
+            &["https://cdn.example:8443"],
+            "https://cdn.example/a.swf"
+        ));
+        assert!(!allows(&["not a url://"], "https://cdn.example/a.swf"));
+    }
+}
@@ -5,16 +5,22 @@ use crate::{
     },
     avm2::{object::SocketObject, Activation as Avm2Activation, Avm2, EventObject},
     backend::navigator::NavigatorBackend,
+    config::PolicyFileMode,
     context::UpdateContext,
+    net_connection::{NetConnectionHandle, NetConnections},
+    policy_file::{PolicyFile, PolicyFiles},
     string::AvmString,
+    tag_utils::SwfMovie,
 };
 use async_channel::{unbounded, Receiver, Sender as AsyncSender, Sender};
 use gc_arena::Collect;
 use slotmap::{new_key_type, SlotMap};
 use std::{
     cell::{Cell, RefCell},
+    collections::HashMap,
     time::Duration,
 };
+use url::Url;
 
 new_key_type! {
     pub struct SocketHandle;
@@ -25,6 +31,10 @@ new_key_type! {
 enum SocketKind<'gc> {
     Avm2(SocketObject<'gc>),
     Avm1(Avm1Object<'gc>),
+    /// A socket owned by an RTMP `NetConnection`, which handles its own data.
+    NetConnection(#[collect(require_static)] NetConnectionHandle),
+    /// A request for the socket policy file that a `PendingConnection` waits for.
+    PolicyFile,
 }
 
 #[derive(Collect)]
@@ -50,6 +60,8 @@ pub enum ConnectionState {
     Connected,
     Failed,
     TimedOut,
+    /// No socket policy file permits the movie to connect to this address.
+    NotAllowed(String),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -59,10 +71,31 @@ pub enum SocketAction {
     Close(SocketHandle),
 }
 
+/// A socket connection that waits for the socket policy file of its server.
+struct PendingConnection {
+    /// The socket that is connected once the policy file permits it.
+    socket: SocketHandle,
+    receiver: Receiver<Vec<u8>>,
+    host: String,
+    port: u16,
+    timeout: Duration,
+    secure: bool,
+
+    /// The movie that the policy file has to permit.
+    requester: Url,
+    policy_port: u16,
+
+    /// What the policy file server sent so far.
+    response: Vec<u8>,
+}
+
 /// Manages the collection of Sockets.
 pub struct Sockets<'gc> {
     sockets: SlotMap<SocketHandle, Socket<'gc>>,
 
+    /// Connections waiting for a socket policy file, by the socket requesting it.
+    pending: HashMap<SocketHandle, PendingConnection>,
+
     receiver: Receiver<SocketAction>,
     sender: Sender<SocketAction>,
 }
@@ -81,31 +114,33 @@ impl<'gc> Sockets<'gc> {
 
         Self {
             sockets: SlotMap::with_key(),
+            pending: HashMap::new(),
             receiver,
             sender,
         }
     }
 
+    /// Connects an AVM2 `Socket`, or a `SecureSocket` if `secure` is set.
+    #[allow(clippy::too_many_arguments)]
     pub fn connect_avm2(
         &mut self,
         backend: &mut dyn NavigatorBackend,
+        policy_files: &PolicyFiles,
+        movie: &SwfMovie,
         target: SocketObject<'gc>,
         host: String,
         port: u16,
+        secure: bool,
     ) {
-        let (sender, receiver) = unbounded();
-
-        let socket = Socket::new(SocketKind::Avm2(target), sender);
-        let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
+        let handle = self.connect(
+            backend,
+            policy_files,
+            movie,
+            SocketKind::Avm2(target),
             sanitize_host(&host).to_string(),
             port,
             Duration::from_millis(target.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
+            secure,
         );
 
         if let Some(existing_handle) = target.set_handle(handle) {
@@ -118,35 +153,203 @@ impl<'gc> Sockets<'gc> {
     pub fn connect_avm1(
         &mut self,
         backend: &mut dyn NavigatorBackend,
+        policy_files: &PolicyFiles,
+        movie: &SwfMovie,
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
     ) {
-        let (sender, receiver) = unbounded();
-
         let xml_socket = match XmlSocket::cast(target.into()) {
             Some(xml_socket) => xml_socket,
             None => return,
         };
 
-        let socket = Socket::new(SocketKind::Avm1(target), sender);
+        let handle = self.connect(
+            backend,
+            policy_files,
+            movie,
+            SocketKind::Avm1(target),
+            sanitize_host(&host).to_string(),
+            port,
+            Duration::from_millis(xml_socket.timeout().into()),
+            false,
+        );
+
+        if let Some(existing_handle) = xml_socket.set_handle(handle) {
+            // NOTE: AS2 docs don't specify what happens when connect is called with open connection,
+            //       but we will close the existing connection anyway.
+            self.close(existing_handle)
+        }
+    }
+
+    /// Connects a socket made by the movie, once the socket policy file of its server
+    /// permits it.
+    #[allow(clippy::too_many_arguments)]
+    fn connect(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        policy_files: &PolicyFiles,
+        movie: &SwfMovie,
+        target: SocketKind<'gc>,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        secure: bool,
+    ) -> SocketHandle {
+        let (sender, receiver) = unbounded();
+
+        let socket = Socket::new(target, sender);
+        let handle = self.sockets.insert(socket);
+
+        let Some(requester) = policy_files.requester(movie) else {
+            self.open(backend, host, port, timeout, secure, handle, receiver);
+            return handle;
+        };
+
+        let policy_port = policy_files.socket_policy_port(&host);
+        let pending = PendingConnection {
+            socket: handle,
+            receiver,
+            host,
+            port,
+            timeout,
+            secure,
+            requester,
+            policy_port,
+            response: Vec::new(),
+        };
+        match policy_files.socket_policy(&pending.host, policy_port) {
+            Some(policy) => self.finish_pending(backend, policy_files, pending, policy.as_ref()),
+            None => {
+                let (sender, receiver) = unbounded();
+                let policy_socket = self
+                    .sockets
+                    .insert(Socket::new(SocketKind::PolicyFile, sender));
+                backend.connect_socket(
+                    pending.host.clone(),
+                    policy_port,
+                    timeout,
+                    policy_socket,
+                    receiver,
+                    self.sender.clone(),
+                );
+                self.pending.insert(policy_socket, pending);
+            }
+        }
+
+        handle
+    }
+
+    /// Handles the end of a socket policy file request, connecting the socket that
+    /// was waiting for it if the policy file permits it.
+    fn policy_file_received(context: &mut UpdateContext<'gc>, policy_socket: SocketHandle) {
+        let Some(pending) = context.sockets.pending.remove(&policy_socket) else {
+            return;
+        };
+        context.sockets.close(policy_socket);
+
+        let response = match pending.response.iter().position(|&b| b == 0) {
+            Some(end) => &pending.response[..end],
+            None => &pending.response[..],
+        };
+        let policy = PolicyFile::parse(response);
+        context
+            .policy_files
+            .set_socket_policy(&pending.host, pending.policy_port, policy.clone());
+        context.sockets.finish_pending(
+            *context.navigator,
+            context.policy_files,
+            pending,
+            policy.as_ref(),
+        );
+    }
+
+    fn finish_pending(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        policy_files: &PolicyFiles,
+        pending: PendingConnection,
+        policy: Option<&PolicyFile>,
+    ) {
+        // The socket may have been closed in the meantime.
+        if !self.sockets.contains_key(pending.socket) {
+            return;
+        }
+
+        let PendingConnection {
+            socket,
+            receiver,
+            host,
+            port,
+            timeout,
+            secure,
+            requester,
+            ..
+        } = pending;
+        if !policy.is_some_and(|policy| policy.allows_socket(&requester, port)) {
+            if policy_files.mode() == PolicyFileMode::Enforce {
+                tracing::warn!(
+                    "Blocked connecting to {host}:{port}, as no socket policy file permits it"
+                );
+                let address = format!("{host}:{port}");
+                let _ = self.sender.try_send(SocketAction::Connect(
+                    socket,
+                    ConnectionState::NotAllowed(address),
+                ));
+                return;
+            }
+            tracing::warn!(
+                "Connecting to {host}:{port}, although no socket policy file permits it"
+            );
+        }
+
+        self.open(backend, host, port, timeout, secure, socket, receiver);
+    }
+
+    /// Asks the backend to open the connection of a socket made by the movie.
+    #[allow(clippy::too_many_arguments)]
+    fn open(
+        &self,
+        backend: &mut dyn NavigatorBackend,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        secure: bool,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+    ) {
+        // NOTE: These calls will send SocketAction::Connect to sender with connection status.
+        let sender = self.sender.clone();
+        if secure {
+            backend.connect_secure_socket(host, port, timeout, handle, receiver, sender);
+        } else {
+            backend.connect_socket(host, port, timeout, handle, receiver, sender);
+        }
+    }
+
+    pub fn connect_net_connection(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        target: NetConnectionHandle,
+        host: String,
+        port: u16,
+    ) -> SocketHandle {
+        let (sender, receiver) = unbounded();
+
+        let socket = Socket::new(SocketKind::NetConnection(target), sender);
         let handle = self.sockets.insert(socket);
 
         // NOTE: This call will send SocketAction::Connect to sender with connection status.
         backend.connect_socket(
             sanitize_host(&host).to_string(),
             port,
-            Duration::from_millis(xml_socket.timeout().into()),
+            NET_CONNECTION_TIMEOUT,
             handle,
             receiver,
             self.sender.clone(),
         );
 
-        if let Some(existing_handle) = xml_socket.set_handle(handle) {
-            // NOTE: AS2 docs don't specify what happens when connect is called with open connection,
-            //       but we will close the existing connection anyway.
-            self.close(existing_handle)
-        }
+        handle
     }
 
     pub fn is_connected(&self, handle: SocketHandle) -> bool {
@@ -168,6 +371,7 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn close_all(&mut self) {
+        self.pending.clear();
         for (_, socket) in self.sockets.drain() {
             Self::close_internal(socket);
         }
@@ -199,6 +403,7 @@ impl<'gc> Sockets<'gc> {
                 target.read_buffer().clear();
                 target.write_buffer().clear();
             }
+            SocketKind::NetConnection(_) | SocketKind::PolicyFile => {}
         }
     }
 
@@ -242,6 +447,71 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::NetConnection(target) => {
+                            NetConnections::on_socket_connected(context, target);
+                        }
+                        SocketKind::PolicyFile => {
+                            context
+                                .sockets
+                                .send(handle, b"<policy-file-request/>\0".to_vec());
+                        }
+                    }
+                }
+                SocketAction::Connect(handle, ConnectionState::NotAllowed(address)) => {
+                    let target = match context.sockets.sockets.get(handle) {
+                        Some(socket) => socket.target,
+                        // Socket must have been closed before we could send event.
+                        None => continue,
+                    };
+
+                    match target {
+                        SocketKind::Avm2(target) => {
+                            let mut activation = Avm2Activation::from_nothing(context);
+
+                            let text = AvmString::new_utf8(
+                                activation.gc(),
+                                format!(
+                                    "Error #2048: Security sandbox violation: {} cannot load data from {address}.",
+                                    activation.context.swf.url()
+                                ),
+                            );
+                            let security_error_evt = activation
+                                .avm2()
+                                .classes()
+                                .securityerrorevent
+                                .construct(
+                                    &mut activation,
+                                    &[
+                                        "securityError".into(),
+                                        false.into(),
+                                        false.into(),
+                                        text.into(),
+                                        2048.into(),
+                                    ],
+                                )
+                                .expect("SecurityErrorEvent should be constructed");
+
+                            Avm2::dispatch_event(
+                                activation.context,
+                                security_error_evt,
+                                target.into(),
+                            );
+                        }
+                        SocketKind::Avm1(target) => {
+                            let mut activation = Avm1Activation::from_stub(
+                                context,
+                                ActivationIdentifier::root("[XMLSocket]"),
+                            );
+
+                            let _ = target.call_method(
+                                "onConnect".into(),
+                                &[false.into()],
+                                &mut activation,
+                                ExecutionReason::Special,
+                            );
+                        }
+                        // Neither of these wait for policy files.
+                        SocketKind::NetConnection(_) | SocketKind::PolicyFile => {}
                     }
                 }
                 SocketAction::Connect(
@@ -290,9 +560,14 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::NetConnection(target) => {
+                            context.sockets.sockets.remove(handle);
+                            NetConnections::on_socket_failed(context, target);
+                        }
+                        SocketKind::PolicyFile => Self::policy_file_received(context, handle),
                     }
                 }
-                SocketAction::Data(handle, mut data) => {
+                SocketAction::Data(handle, data) => {
                     let target = match context.sockets.sockets.get(handle) {
                         Some(socket) => socket.target,
                         // Socket must have been closed before we could send event.
@@ -335,52 +610,46 @@ impl<'gc> Sockets<'gc> {
                             let xml_socket =
                                 XmlSocket::cast(target.into()).expect("target should be XmlSocket");
 
-                            // Check if the current received packet includes a null byte.
-                            if let Some((index, _)) = data.iter().enumerate().find(|(_, &b)| b == 0)
-                            {
-                                // Received payload contains a null byte, so take data from sockets read buffer and append message data ontop.
-                                let mut buffer = xml_socket
-                                    .read_buffer()
-                                    .drain(..)
-                                    .chain(data.drain(..index))
-                                    .collect::<Vec<_>>();
-
-                                // Now we loop to check for more null bytes.
-                                loop {
-                                    // Remove null byte.
-                                    data.drain(..1);
-
-                                    // Create message from the buffer.
-                                    let message =
-                                        AvmString::new_utf8_bytes(activation.gc(), &buffer);
-
-                                    // Call the event handler.
-                                    let _ = target.call_method(
-                                        "onData".into(),
-                                        &[message.into()],
-                                        &mut activation,
-                                        ExecutionReason::Special,
-                                    );
-
-                                    // Check if we have another null byte in the same payload.
-                                    if let Some((index, _)) =
-                                        data.iter().enumerate().find(|(_, &b)| b == 0)
-                                    {
-                                        // Because data in XmlSocket::read_buffer() has already been consumed
-                                        // we do not need to access it again.
-                                        buffer = data.drain(..index).collect::<Vec<_>>();
-                                    } else {
-                                        // No more messages in the payload, so exit the loop.
-                                        break;
-                                    }
+                            // Messages end with a null byte, and may be split across packets,
+                            // so anything after the last one is kept for the next packet.
+                            let messages = {
+                                let mut buffer = xml_socket.read_buffer();
+                                buffer.extend(data);
+                                let mut messages = vec![];
+                                while let Some(index) = buffer.iter().position(|&b| b == 0) {
+                                    let mut message = buffer.drain(..=index).collect::<Vec<_>>();
+                                    message.pop();
+                                    messages.push(message);
                                 }
+                                messages
+                            };
 
-                                // Check if we have leftover bytes.
-                                if !data.is_empty() {
-                                    // We had leftover bytes, so append them to XmlSocket internal read buffer,
-                                    // to be used when the next packet arrives.
-                                    xml_socket.read_buffer().extend(data);
+                            for message in messages {
+                                // The handler of a previous message may have closed the socket.
+                                if !activation.context.sockets.sockets.contains_key(handle) {
+                                    break;
                                 }
+
+                                let message = AvmString::new_utf8_bytes(activation.gc(), &message);
+                                let _ = target.call_method(
+                                    "onData".into(),
+                                    &[message.into()],
+                                    &mut activation,
+                                    ExecutionReason::Special,
+                                );
+                            }
+                        }
+                        SocketKind::NetConnection(target) => {
+                            NetConnections::on_socket_data(context, target, &data);
+                        }
+                        SocketKind::PolicyFile => {
+                            let Some(pending) = context.sockets.pending.get_mut(&handle) else {
+                                continue;
+                            };
+                            pending.response.extend(data);
+                            // The policy file ends with a null byte.
+                            if pending.response.contains(&0) {
+                                Self::policy_file_received(context, handle);
                             }
                         }
                     }
@@ -426,6 +695,10 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::NetConnection(target) => {
+                            NetConnections::close(context, target, false);
+                        }
+                        SocketKind::PolicyFile => Self::policy_file_received(context, handle),
                     }
                 }
             }
@@ -433,6 +706,9 @@ impl<'gc> Sockets<'gc> {
     }
 }
 
+/// How long an RTMP `NetConnection` waits for its socket to connect.
+const NET_CONNECTION_TIMEOUT: Duration = Duration::from_secs(20);
+
 /// Flash treats a socket host as a cstring, and stops reading at a null byte.
 /// We need to account for this here.
 fn sanitize_host(host: &str) -> &str {
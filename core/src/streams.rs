@@ -18,6 +18,7 @@ use crate::buffer::{Buffer, Slice, Substream, SubstreamError};
 use crate::context::UpdateContext;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::loader::Error;
+use crate::net_connection::{NetConnectionHandle, NetConnections};
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
@@ -29,6 +30,7 @@ use flv_rs::{
 };
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::bitmap::BitmapInfo;
+use ruffle_video::error::Error as VideoError;
 use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
@@ -159,6 +161,20 @@ impl PartialEq for NetStream<'_> {
 
 impl Eq for NetStream<'_> {}
 
+/// An action passed to `NetStream.appendBytesAction`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// The next appended bytes start a new file, including its header.
+    ResetBegin,
+
+    /// The next appended bytes continue the current file from a new position,
+    /// starting at a tag boundary.
+    ResetSeek,
+
+    /// No more bytes will be appended.
+    EndSequence,
+}
+
 /// The current type of the data in the stream buffer.
 #[derive(Clone, Debug)]
 pub enum NetStreamType {
@@ -238,6 +254,11 @@ pub struct NetStreamData<'gc> {
     /// The URL of the requested FLV if one exists.
     url: Option<String>,
 
+    /// The `NetConnection` this stream was created with, which media is
+    /// streamed from when it's connected to a server.
+    #[collect(require_static)]
+    connection: Option<NetConnectionHandle>,
+
     /// The `Substream` associated with the currently playing audio track and
     /// the expected playback format of that audio.
     #[collect(require_static)]
@@ -272,6 +293,7 @@ impl<'gc> NetStream<'gc> {
                 avm_object,
                 avm2_client: None,
                 url: None,
+                connection: None,
                 audio_stream: None,
                 sound_instance: None,
                 attached_to: None,
@@ -293,6 +315,14 @@ impl<'gc> NetStream<'gc> {
         self.0.write(gc_context).avm_object = Some(avm_object);
     }
 
+    pub fn set_connection(
+        self,
+        gc_context: &Mutation<'gc>,
+        connection: Option<NetConnectionHandle>,
+    ) {
+        self.0.write(gc_context).connection = connection;
+    }
+
     /// Reset the `NetStream` buffer to accept new source data.
     ///
     /// This must be done once per source change and should ideally be done
@@ -545,9 +575,24 @@ impl<'gc> NetStream<'gc> {
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
-    /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
+    /// the given resource, or ask the server to stream it if the connection
+    /// of this stream is to an RTMP server. Otherwise, the stream will play
+    /// whatever data is available in the buffer.
     pub fn play(self, context: &mut UpdateContext<'gc>, name: Option<AvmString<'gc>>) {
+        let connection = self.0.read().connection;
+        if let (Some(name), Some(connection)) = (name, connection) {
+            if context.net_connections.is_rtmp(connection) {
+                // The server reports the start of playback itself.
+                self.reset_buffer(context);
+                let mut write = self.0.write(context.gc_context);
+                write.url = None;
+                write.playing = true;
+                drop(write);
+                NetConnections::play_stream(context, connection, self, name.to_string());
+                return;
+            }
+        }
+
         if let Some(name) = name {
             let request = if let Ok(stream_url) =
                 Url::parse(context.swf.url()).and_then(|url| url.join(name.to_string().as_str()))
@@ -575,6 +620,51 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Start playing media supplied by `appendBytes` rather than downloaded
+    /// from a URL, also known as Data Generation Mode.
+    pub fn play_appended_bytes(self, context: &mut UpdateContext<'gc>) {
+        self.reset_buffer(context);
+        self.0.write(context.gc_context).url = None;
+        self.play(context, None);
+    }
+
+    /// Append data to the buffer in Data Generation Mode.
+    ///
+    /// Unlike `load_buffer`, this does not report the buffer as full, as the
+    /// data is usually appended in small pieces as it arrives.
+    pub fn append_bytes(self, context: &mut UpdateContext<'gc>, mut data: Vec<u8>) {
+        self.0.write(context.gc_context).buffer.append(&mut data);
+        StreamManager::activate(context, self);
+    }
+
+    /// Handle an `appendBytesAction` call in Data Generation Mode.
+    pub fn append_bytes_action(self, context: &mut UpdateContext<'gc>, action: AppendBytesAction) {
+        match action {
+            AppendBytesAction::ResetBegin => self.reset_buffer(context),
+            AppendBytesAction::ResetSeek => {
+                // The container has already been identified, so the new data
+                // is parsed as tags without expecting another header. Any
+                // queued seek will then look for a keyframe in the new data.
+                let (stream_type, stream_time, queued_seek_time) = {
+                    let read = self.0.read();
+                    (
+                        read.stream_type.clone(),
+                        read.stream_time,
+                        read.queued_seek_time,
+                    )
+                };
+                self.reset_buffer(context);
+
+                let mut write = self.0.write(context.gc_context);
+                write.stream_type = stream_type;
+                write.stream_time = stream_time;
+                write.queued_seek_time = queued_seek_time;
+            }
+            AppendBytesAction::EndSequence => self.finish_buffer(context),
+        }
+        StreamManager::activate(context, self);
+    }
+
     /// Pause stream playback.
     pub fn pause(self, context: &mut UpdateContext<'gc>, notify: bool) {
         // NOTE: We do not deactivate the stream here as there may be other
@@ -978,6 +1068,8 @@ impl<'gc> NetStream<'gc> {
                             *context.needs_render = true;
                         }
                     }
+                    // The frame is picked up by `poll_video_frame` once it's decoded.
+                    Err(VideoError::FramePending) => {}
                     Err(e) => {
                         tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
                     }
@@ -1019,6 +1111,7 @@ impl<'gc> NetStream<'gc> {
                     Ok(bitmap_info) => {
                         write.last_decoded_bitmap = Some(bitmap_info);
                     }
+                    Err(VideoError::FramePending) => {}
                     Err(e) => {
                         tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
                     }
@@ -1143,9 +1236,14 @@ impl<'gc> NetStream<'gc> {
             self.execute_seek(context, offset);
         }
 
-        // Paused streams deactivate themselves after seek processing.
+        let is_decoding = self.poll_video_frame(context);
+
+        // Paused streams deactivate themselves after seek processing, once
+        // the frame they were left on has finished decoding.
         if !self.0.read().playing {
-            StreamManager::deactivate(context, self);
+            if !is_decoding {
+                StreamManager::deactivate(context, self);
+            }
             return;
         }
 
@@ -1275,6 +1373,39 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Show any video frame that finished decoding after it was submitted.
+    ///
+    /// Returns whether more frames are still being decoded.
+    fn poll_video_frame(self, context: &mut UpdateContext<'gc>) -> bool {
+        let mut write = self.0.write(context.gc_context);
+        let Some(NetStreamType::Flv {
+            video_stream: Some(video_handle),
+            ..
+        }) = write.stream_type
+        else {
+            return false;
+        };
+
+        match context
+            .video
+            .poll_video_stream_frame(video_handle, context.renderer)
+        {
+            Ok(Some(bitmap_info)) => {
+                write.last_decoded_bitmap = Some(bitmap_info);
+                if let Some(mc) = write.attached_to {
+                    mc.invalidate_cached_bitmap(context.gc_context);
+                    *context.needs_render = true;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!("Decoding video frame failed: {}", e);
+            }
+        }
+
+        context.video.is_video_stream_decoding(video_handle)
+    }
+
     pub fn last_decoded_bitmap(self) -> Option<BitmapInfo> {
         self.0.read().last_decoded_bitmap.clone()
     }
@@ -5,13 +5,16 @@ mod context;
 mod interner;
 mod repr;
 
+#[cfg(test)]
+mod test;
+
 use repr::AvmStringRepr;
 
 pub use ruffle_wstr::*;
 
 pub use avm_string::AvmString;
 pub use context::StringContext;
-pub use interner::{AvmAtom, AvmStringInterner};
+pub use interner::{AvmAtom, AvmStringInterner, CommonStrings};
 
 pub trait SwfStrExt {
     /// Converts a SWF-encoded string into a `WStr`.
@@ -4,7 +4,10 @@ use std::ops::Deref;
 use gc_arena::{Collect, Gc, Mutation};
 use ruffle_wstr::{wstr_impl_traits, WStr, WString};
 
-use crate::string::{AvmAtom, AvmStringRepr};
+use crate::string::{repr, AvmAtom, AvmStringRepr};
+
+/// Concatenations producing strings at least this long are done lazily, with a Rope.
+const MIN_ROPE_LEN: usize = 256;
 
 #[derive(Clone, Copy, Collect)]
 #[collect(no_drop)]
@@ -89,6 +92,37 @@ impl<'gc> AvmString<'gc> {
         }
     }
 
+    /// Whether this string is a concatenation whose contents haven't been needed yet.
+    pub fn is_rope(&self) -> bool {
+        match &self.source {
+            Source::Managed(s) => s.is_rope(),
+            Source::Static(_) => false,
+        }
+    }
+
+    // The following methods shadow the ones on `WStr`, so that they don't flatten ropes.
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.source {
+            Source::Managed(s) => s.len(),
+            Source::Static(s) => s.len(),
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn is_wide(&self) -> bool {
+        match self.source {
+            Source::Managed(s) => s.is_wide(),
+            Source::Static(s) => s.is_wide(),
+        }
+    }
+
     pub fn as_wstr(&self) -> &'gc WStr {
         match self.source {
             Source::Managed(s) => Gc::as_ref(s).as_wstr(),
@@ -112,10 +146,22 @@ impl<'gc> AvmString<'gc> {
             right
         } else if right.is_empty() {
             left
-        } else if let Some(repr) = left
-            .as_managed()
-            .and_then(|l| AvmStringRepr::try_append_inline(l, &right))
-        {
+        } else if let Some(repr) = left.as_managed().and_then(|l| {
+            if l.is_rope() && l.is_wide() == right.is_wide() && right.len() < MIN_ROPE_LEN {
+                // Flattening leaves room for further appends, so a string that a script
+                // keeps appending short parts to goes back to being appended in-place.
+                l.as_wstr();
+            }
+            AvmStringRepr::try_append_inline(l, &right)
+        }) {
+            Self {
+                source: Source::Managed(Gc::new(mc, repr)),
+            }
+        } else if left.len() + right.len() >= MIN_ROPE_LEN {
+            // Long strings are usually built by repeated concatenation, which would
+            // be quadratic if we copied them every time: defer copying until the
+            // contents are actually needed.
+            let repr = AvmStringRepr::new_rope(left, right);
             Self {
                 source: Source::Managed(Gc::new(mc, repr)),
             }
@@ -124,15 +170,7 @@ impl<'gc> AvmString<'gc> {
             // Overallocate a bit so that further appends can be in-place.
             // (Note that this means that all first-time appends will happen here and
             // overallocate, even if done only once)
-            // This growth logic should be equivalent to AVM's, except I capped the growth at 1MB instead of 4MB.
-            let new_size = left.len() + right.len();
-            let new_capacity = if new_size < 32 {
-                32
-            } else if new_size > 1024 * 1024 {
-                new_size + 1024 * 1024
-            } else {
-                new_size * 2
-            };
+            let new_capacity = repr::grown_capacity(left.len() + right.len());
 
             let mut out = WString::with_capacity(new_capacity, left.is_wide() || right.is_wide());
             out.push_str(&left);
@@ -2,7 +2,7 @@ use std::{borrow::Cow, ops::Range};
 
 use gc_arena::{Gc, Mutation};
 
-use super::{AvmAtom, AvmString, AvmStringInterner, AvmStringRepr, WStr, WString};
+use super::{AvmAtom, AvmString, AvmStringInterner, AvmStringRepr, CommonStrings, WStr, WString};
 
 /// Context for managing `AvmString`s: allocating them, interning them, etc...
 pub struct StringContext<'gc> {
@@ -70,6 +70,12 @@ impl<'gc> StringContext<'gc> {
         self.interner.get(self.gc(), s)
     }
 
+    /// Frequently used strings, which are always interned.
+    #[must_use]
+    pub fn common(&self) -> &CommonStrings<'gc> {
+        &self.interner.common
+    }
+
     #[must_use]
     pub fn empty(&self) -> AvmString<'gc> {
         self.interner.empty.into()
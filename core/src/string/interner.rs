@@ -54,6 +54,45 @@ pub struct AvmStringInterner<'gc> {
 
     pub(super) empty: Gc<'gc, AvmStringRepr<'gc>>,
     pub(super) chars: [Gc<'gc, AvmStringRepr<'gc>>; INTERNED_CHAR_LEN],
+    pub(super) common: CommonStrings<'gc>,
+}
+
+macro_rules! define_common_strings {
+    ($($field:ident: $str:literal,)*) => {
+        /// Frequently used strings, interned once and for all.
+        ///
+        /// Using these avoids both allocating the strings and hashing them
+        /// when they get interned again, e.g. when used as property names.
+        #[derive(Collect)]
+        #[collect(no_drop)]
+        pub struct CommonStrings<'gc> {
+            $(pub $field: AvmAtom<'gc>,)*
+        }
+
+        impl<'gc> CommonStrings<'gc> {
+            fn new(mut intern: impl FnMut(&'static [u8]) -> Gc<'gc, AvmStringRepr<'gc>>) -> Self {
+                Self {
+                    $($field: AvmAtom(intern($str)),)*
+                }
+            }
+        }
+    };
+}
+
+// Single ASCII characters are already interned, and mustn't be repeated here.
+define_common_strings! {
+    str_boolean: b"boolean",
+    str_false: b"false",
+    str_function: b"function",
+    str_infinity: b"Infinity",
+    str_nan: b"NaN",
+    str_null: b"null",
+    str_number: b"number",
+    str_object: b"object",
+    str_string: b"string",
+    str_true: b"true",
+    str_undefined: b"undefined",
+    str_xml: b"xml",
 }
 
 const INTERNED_CHAR_LEN: usize = 128;
@@ -84,6 +123,7 @@ impl<'gc> AvmStringInterner<'gc> {
                 let c = &INTERNED_CHARS[i];
                 intern_from_static(std::slice::from_ref(c))
             }),
+            common: CommonStrings::new(&mut intern_from_static),
             interned,
         }
     }
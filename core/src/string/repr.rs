@@ -1,9 +1,24 @@
 use std::cell::Cell;
 use std::ops::Deref;
 
-use gc_arena::{Collect, Gc};
+use gc_arena::{lock::Lock, Collect, Gc};
 use ruffle_wstr::{panic_on_invalid_length, ptr as wptr, wstr_impl_traits, WStr, WString};
 
+use crate::string::AvmString;
+
+/// The capacity to allocate for a string of `len` chars which is expected to grow.
+///
+/// This growth logic should be equivalent to AVM's, except I capped the growth at 1MB instead of 4MB.
+pub(super) fn grown_capacity(len: usize) -> usize {
+    if len < 32 {
+        32
+    } else if len > 1024 * 1024 {
+        len + 1024 * 1024
+    } else {
+        len * 2
+    }
+}
+
 /// Internal representation of `AvmAtom`s and (owned) `AvmString`s.
 ///
 /// Using this type directly is dangerous, as it can be used to violate
@@ -11,8 +26,9 @@ use ruffle_wstr::{panic_on_invalid_length, ptr as wptr, wstr_impl_traits, WStr,
 #[derive(Collect)]
 #[collect(unsafe_drop)]
 pub struct AvmStringRepr<'gc> {
+    // Only changes when a Rope string gets flattened.
     #[collect(require_static)]
-    ptr: *mut (),
+    ptr: Cell<*mut ()>,
 
     // Length and is_wide bit.
     #[collect(require_static)]
@@ -31,6 +47,11 @@ pub struct AvmStringRepr<'gc> {
 
     // If Some, the string is Dependent. The owner is assumed to be non-dynamic.
     owner: Option<Gc<'gc, Self>>,
+
+    // If Some, the string is a Rope: the concatenation of two strings, whose
+    // contents are only copied into an owned buffer when they are first accessed.
+    // Until then, `ptr` is dangling and the capacity is 0.
+    rope: Lock<Option<(AvmString<'gc>, AvmString<'gc>)>>,
 }
 
 impl<'gc> AvmStringRepr<'gc> {
@@ -38,11 +59,12 @@ impl<'gc> AvmStringRepr<'gc> {
         let (ptr, meta, cap) = s.into_raw_parts();
         let capacity = Cell::new(wptr::WStrMetadata::new32(cap, interned));
         Self {
-            ptr,
+            ptr: Cell::new(ptr),
             meta,
             capacity,
             chars_used: Cell::new(meta.len32()),
             owner: None,
+            rope: Lock::new(None),
         }
     }
 
@@ -51,6 +73,24 @@ impl<'gc> AvmStringRepr<'gc> {
         unsafe { Self::new_dependent_raw(None, s, interned) }
     }
 
+    pub fn new_rope(left: AvmString<'gc>, right: AvmString<'gc>) -> Self {
+        let len = left.len() + right.len();
+        if len > WStr::MAX_LEN {
+            panic_on_invalid_length(len);
+        }
+
+        // SAFETY: we checked the length above.
+        let meta = unsafe { wptr::WStrMetadata::new(len, left.is_wide() || right.is_wide()) };
+        Self {
+            ptr: Cell::new(std::ptr::NonNull::<u8>::dangling().as_ptr().cast()),
+            meta,
+            capacity: Cell::new(wptr::WStrMetadata::new32(0, false)),
+            chars_used: Cell::new(0),
+            owner: None,
+            rope: Lock::new(Some((left, right))),
+        }
+    }
+
     pub fn new_dependent(s: Gc<'gc, Self>, start: usize, end: usize) -> Self {
         let wstr = &s.as_ref()[start..end];
         let owner = Some(s.owner().unwrap_or(s));
@@ -67,17 +107,18 @@ impl<'gc> AvmStringRepr<'gc> {
     ) -> Self {
         Self {
             owner,
-            ptr: wstr as *const WStr as *mut (),
+            ptr: Cell::new(wstr as *const WStr as *mut ()),
             meta: wptr::WStrMetadata::of(wstr),
             chars_used: Cell::new(0),
             capacity: Cell::new(wptr::WStrMetadata::new32(0, interned)),
+            rope: Lock::new(None),
         }
     }
 
     pub fn try_append_inline(left: Gc<'gc, Self>, right: &WStr) -> Option<Self> {
         // note: we could also in-place append a byte string to a wide string
         // But it was skipped for now.
-        if left.is_wide() != right.is_wide() {
+        if left.is_rope() || left.is_wide() != right.is_wide() {
             return None;
         }
 
@@ -91,8 +132,8 @@ impl<'gc> AvmStringRepr<'gc> {
             note: it's possible that left == left_origin.
         */
         unsafe {
-            let left_origin_ptr = left_origin.ptr as *const u8;
-            let left_ptr = left.ptr as *const u8;
+            let left_origin_ptr = left_origin.ptr.get() as *const u8;
+            let left_ptr = left.ptr.get() as *const u8;
 
             /*
             Assume a="abc", b=a+"d", c=a.substr(1), we're running d=c+"e"
@@ -160,10 +201,70 @@ impl<'gc> AvmStringRepr<'gc> {
         self.owner
     }
 
+    #[inline]
+    pub fn is_rope(&self) -> bool {
+        self.rope.get().is_some()
+    }
+
+    /// The length of the string, without flattening it.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the string is wide, without flattening it.
+    #[inline]
+    pub fn is_wide(&self) -> bool {
+        self.meta.is_wide()
+    }
+
     #[inline]
     pub fn as_wstr(&self) -> &WStr {
-        // SAFETY: we own a `WString`.
-        unsafe { &*wptr::from_raw_parts(self.ptr, self.meta) }
+        if self.is_rope() {
+            self.flatten();
+        }
+
+        // SAFETY: we own a `WString`, or the string we depend on is kept alive by `owner`.
+        unsafe { &*wptr::from_raw_parts(self.ptr.get(), self.meta) }
+    }
+
+    /// Copies the contents of a Rope string into an owned buffer.
+    #[cold]
+    fn flatten(&self) {
+        let Some((left, right)) = self.rope.get() else {
+            return;
+        };
+
+        // Leave room for further appends, so that a string built in a loop which is
+        // also read in that loop can go back to being appended in-place.
+        let mut buf = WString::with_capacity(grown_capacity(self.len()), self.is_wide());
+        // Ropes can get very deep when built in loops, so avoid recursion.
+        let mut parts = vec![right, left];
+        while let Some(part) = parts.pop() {
+            match part.as_managed().and_then(|repr| repr.rope.get()) {
+                Some((left, right)) => {
+                    parts.push(right);
+                    parts.push(left);
+                }
+                None => buf.push_str(part.as_wstr()),
+            }
+        }
+
+        let (ptr, meta, cap) = buf.into_raw_parts();
+        debug_assert_eq!(meta.len(), self.len());
+        debug_assert_eq!(meta.is_wide(), self.is_wide());
+        self.ptr.set(ptr);
+        self.chars_used.set(self.meta.len32());
+        let interned = self.is_interned();
+        self.capacity.set(wptr::WStrMetadata::new32(cap, interned));
+
+        // SAFETY: we only remove pointers, which doesn't need a write barrier.
+        unsafe { self.rope.as_cell().set(None) };
     }
 
     pub fn is_interned(&self) -> bool {
@@ -186,9 +287,9 @@ impl Drop for AvmStringRepr<'_> {
         if cap > 0 {
             // SAFETY: we drop the `WString` we logically own.
             debug_assert!(self.owner.is_none());
-            let _ = unsafe { WString::from_raw_parts(self.ptr, self.meta, cap) };
+            let _ = unsafe { WString::from_raw_parts(self.ptr.get(), self.meta, cap) };
         } else {
-            // Nothing to do, this is a Static or a Dependant string.
+            // Nothing to do, this is a Static or a Dependant string, or an unflattened Rope.
             // It could also have been an empty owned WString, but
             // these don't need to be dropped either.
         }
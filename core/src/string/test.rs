@@ -0,0 +1,181 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+use gc_arena::rootless_arena;
+
+use super::{AvmString, AvmStringInterner, StringContext, WStr, WString};
+
+fn with_context(f: impl for<'gc> FnOnce(&mut StringContext<'gc>)) {
+    rootless_arena(|mc| {
+        let interner = Box::leak(Box::new(AvmStringInterner::new(mc)));
+        f(&mut StringContext::from_parts(mc, interner));
+    });
+}
+
+/// Concatenates `count` copies of `part`, like a script building a string in a loop.
+fn build<'gc>(context: &StringContext<'gc>, part: &str, count: usize) -> AvmString<'gc> {
+    let part = AvmString::new_utf8(context.gc(), part);
+    let mut s = context.empty();
+    for _ in 0..count {
+        s = AvmString::concat(context.gc(), s, part);
+    }
+    s
+}
+
+/// Builds a rope of `count` copies of `part`, from two halves that are too long to copy.
+fn rope<'gc>(context: &StringContext<'gc>, part: &str, count: usize) -> AvmString<'gc> {
+    let left = AvmString::new_utf8(context.gc(), part.repeat(count / 2));
+    let right = AvmString::new_utf8(context.gc(), part.repeat(count - count / 2));
+    let rope = AvmString::concat(context.gc(), left, right);
+    assert!(rope.is_rope());
+    rope
+}
+
+#[test]
+fn rope_flatten() {
+    with_context(|context| {
+        let left = AvmString::new_utf8(context.gc(), "a".repeat(200));
+        let right = AvmString::new_utf8(context.gc(), "b".repeat(200));
+        let rope = AvmString::concat(context.gc(), left, right);
+        assert!(rope.is_rope());
+        assert_eq!(rope.len(), 400);
+
+        let nested = AvmString::concat(context.gc(), rope, rope);
+        assert!(nested.is_rope());
+        assert!(rope.is_rope());
+
+        let expected = format!("{0}{1}{0}{1}", "a".repeat(200), "b".repeat(200));
+        assert_eq!(nested.to_utf8_lossy(), expected);
+        assert!(!nested.is_rope());
+    });
+}
+
+#[test]
+fn rope_flatten_wide() {
+    with_context(|context| {
+        let left = AvmString::new_utf8(context.gc(), "a".repeat(300));
+        let right = AvmString::new_utf8(context.gc(), "é€");
+        let rope = AvmString::concat(context.gc(), left, right);
+        assert!(rope.is_rope());
+        assert!(rope.is_wide());
+
+        let expected = format!("{}é€", "a".repeat(300));
+        assert_eq!(rope.to_utf8_lossy(), expected);
+    });
+}
+
+#[test]
+fn rope_append_after_flatten_is_inline() {
+    with_context(|context| {
+        let part = AvmString::new_utf8(context.gc(), "abcd");
+        let mut s = build(context, "abcd", 100);
+        for i in 0..100 {
+            // Reading the string flattens it, with room to spare for the next appends.
+            assert_eq!(s.len(), (100 + i) * 4);
+            assert_eq!(s.get(s.len() - 1), Some(u16::from(b'd')));
+
+            s = AvmString::concat(context.gc(), s, part);
+            assert!(!s.is_rope(), "append {i} built a rope");
+        }
+        assert_eq!(s.to_utf8_lossy(), "abcd".repeat(200));
+    });
+}
+
+#[test]
+fn rope_short_append_is_inline() {
+    with_context(|context| {
+        let part = AvmString::new_utf8(context.gc(), "abcd");
+        let mut s = rope(context, "abcd", 100);
+        for i in 0..100 {
+            s = AvmString::concat(context.gc(), s, part);
+            assert!(!s.is_rope(), "append {i} built a rope");
+        }
+        assert_eq!(s.to_utf8_lossy(), "abcd".repeat(200));
+
+        // Long parts are still appended lazily.
+        let long = AvmString::new_utf8(context.gc(), "e".repeat(300));
+        let s = AvmString::concat(context.gc(), rope(context, "abcd", 100), long);
+        assert!(s.is_rope());
+        assert_eq!(s.len(), 700);
+    });
+}
+
+#[test]
+fn rope_substring() {
+    with_context(|context| {
+        let s = rope(context, "0123456789", 50);
+        assert!(s.is_rope());
+
+        let sub = context.substring(s, 95..115);
+        assert_eq!(sub.to_utf8_lossy(), "56789012345678901234");
+        assert_eq!(context.substring(s, 3..4), context.ascii_char(b'3'));
+        assert!(AvmString::ptr_eq(
+            &context.substring(s, 3..4),
+            &context.ascii_char(b'3')
+        ));
+        assert_eq!(context.substring(s, 0..0).len(), 0);
+
+        let full = context.substring(s, 0..s.len());
+        assert_eq!(full.to_utf8_lossy(), "0123456789".repeat(50));
+    });
+}
+
+#[test]
+fn rope_eq_and_hash() {
+    with_context(|context| {
+        let rope = rope(context, "xyz", 200);
+        let flat = AvmString::new_utf8(context.gc(), "xyz".repeat(200));
+        let other = AvmString::new_utf8(context.gc(), "xyz".repeat(199) + "xyy");
+        assert!(rope.is_rope());
+
+        let hasher = RandomState::new();
+        assert_eq!(hasher.hash_one(rope), hasher.hash_one(flat));
+        assert_eq!(rope, flat);
+        assert_ne!(rope, other);
+
+        let wstr: &WStr = &WString::from_utf8(&"xyz".repeat(200));
+        assert_eq!(hasher.hash_one(rope), hasher.hash_one(wstr));
+    });
+}
+
+#[test]
+fn rope_intern() {
+    with_context(|context| {
+        let rope = rope(context, "key", 100);
+        let atom = context.intern(rope);
+        let flat = AvmString::new_utf8(context.gc(), "key".repeat(100));
+        assert_eq!(context.intern(flat), atom);
+        assert_eq!(AvmString::from(atom).to_utf8_lossy(), "key".repeat(100));
+    });
+}
+
+#[test]
+fn intern_common_strings() {
+    with_context(|context| {
+        let undefined = AvmString::new_utf8(context.gc(), "undefined");
+        assert_eq!(context.intern(undefined), context.common().str_undefined);
+        assert_eq!(
+            context.intern_wstr(WStr::from_units(b"null")),
+            context.common().str_null
+        );
+        assert_eq!(
+            context.intern_static(WStr::from_units(b"NaN")),
+            context.common().str_nan
+        );
+        assert_eq!(
+            context.get_interned(WStr::from_units(b"Infinity")),
+            Some(context.common().str_infinity)
+        );
+
+        let s = AvmString::new_utf8(context.gc(), "s");
+        assert!(AvmString::ptr_eq(
+            &context.intern(s).into(),
+            &context.ascii_char(b's')
+        ));
+        let empty = AvmString::new_utf8(context.gc(), "");
+        assert!(AvmString::ptr_eq(
+            &context.intern(empty).into(),
+            &context.empty()
+        ));
+    });
+}
@@ -0,0 +1,243 @@
+//! The static text of a display object container, as seen by `TextSnapshot`.
+
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{Text, TextGlyph};
+use gc_arena::Mutation;
+use ruffle_render::matrix::Matrix;
+use std::ops::Range;
+use swf::{Color, Point, Twips};
+
+/// A character of a snapshot, along with its position in the container.
+pub struct SnapshotChar<'a> {
+    /// The index of this character in the snapshot.
+    pub index: usize,
+
+    /// Whether this character is selected.
+    pub selected: bool,
+
+    /// Whether this character starts a new line.
+    pub new_line: bool,
+
+    pub glyph: &'a TextGlyph,
+
+    /// The matrix positioning this character in the local space of the container.
+    pub matrix: Matrix,
+
+    /// The corners of this character in the local space of the container,
+    /// starting from the bottom left and going counterclockwise.
+    pub corners: [Point<Twips>; 4],
+}
+
+/// The static texts directly contained by a display object container.
+///
+/// Characters are indexed in the order the texts are rendered, so that
+/// the first character of each text follows the last character of the previous one.
+#[derive(Default)]
+pub struct TextSnapshot<'gc> {
+    texts: Vec<(Text<'gc>, Vec<TextGlyph>)>,
+}
+
+impl<'gc> TextSnapshot<'gc> {
+    pub fn new(context: &UpdateContext<'gc>, container: DisplayObject<'gc>) -> Self {
+        let texts = container
+            .as_container()
+            .into_iter()
+            .flat_map(|container| container.iter_render_list())
+            .filter_map(|child| match child {
+                DisplayObject::Text(text) => Some((text, text.glyphs(context))),
+                _ => None,
+            })
+            .collect();
+        Self { texts }
+    }
+
+    pub fn char_count(&self) -> usize {
+        self.texts.iter().map(|(_, glyphs)| glyphs.len()).sum()
+    }
+
+    /// Clamps the given indices to the characters of this snapshot.
+    pub fn range(&self, begin: i32, end: i32) -> Range<usize> {
+        let count = self.char_count();
+        let begin = (begin.max(0) as usize).min(count);
+        let end = (end.max(0) as usize).min(count);
+        begin..end.max(begin)
+    }
+
+    /// All characters of this snapshot, in order.
+    pub fn chars(&self) -> Vec<SnapshotChar<'_>> {
+        let mut chars = Vec::with_capacity(self.char_count());
+        for (text_index, (text, glyphs)) in self.texts.iter().enumerate() {
+            let text_matrix = *text.base().matrix();
+            for (glyph_index, glyph) in glyphs.iter().enumerate() {
+                let bounds = &glyph.bounds;
+                let corners = [
+                    (bounds.x_min, bounds.y_max),
+                    (bounds.x_max, bounds.y_max),
+                    (bounds.x_max, bounds.y_min),
+                    (bounds.x_min, bounds.y_min),
+                ]
+                .map(|(x, y)| text_matrix * Point::new(x, y));
+                chars.push(SnapshotChar {
+                    index: chars.len(),
+                    selected: text.is_glyph_selected(glyph_index),
+                    new_line: glyph.new_line || (text_index > 0 && glyph_index == 0),
+                    glyph,
+                    matrix: text_matrix * glyph.matrix,
+                    corners,
+                });
+            }
+        }
+        chars
+    }
+
+    /// The characters in the given range, optionally with a line break before each new line.
+    pub fn text(&self, range: Range<usize>, include_line_endings: bool) -> String {
+        self.collect_text(
+            self.chars()
+                .into_iter()
+                .filter(|c| range.contains(&c.index))
+                .map(|c| (c.glyph.character, c.new_line)),
+            include_line_endings,
+        )
+    }
+
+    /// The selected characters, optionally with a line break before each new line.
+    pub fn selected_text(&self, include_line_endings: bool) -> String {
+        self.collect_text(
+            self.chars()
+                .into_iter()
+                .filter(|c| c.selected)
+                .map(|c| (c.glyph.character, c.new_line)),
+            include_line_endings,
+        )
+    }
+
+    fn collect_text(
+        &self,
+        chars: impl Iterator<Item = (char, bool)>,
+        include_line_endings: bool,
+    ) -> String {
+        let mut text = String::new();
+        for (character, new_line) in chars {
+            if include_line_endings && new_line && !text.is_empty() {
+                text.push('\n');
+            }
+            text.push(character);
+        }
+        text
+    }
+
+    /// Finds the index of the first occurrence of `needle` at or after `begin`.
+    pub fn find(&self, begin: usize, needle: &str, case_sensitive: bool) -> Option<usize> {
+        let haystack: Vec<char> = self.chars().iter().map(|c| c.glyph.character).collect();
+        let needle: Vec<char> = needle.chars().collect();
+        find_chars(&haystack, &needle, begin, case_sensitive)
+    }
+
+    /// Whether any character in the given range is selected.
+    pub fn is_selected(&self, range: Range<usize>) -> bool {
+        self.chars()
+            .iter()
+            .any(|c| range.contains(&c.index) && c.selected)
+    }
+
+    pub fn set_selected(&self, mc: &Mutation<'gc>, range: Range<usize>, selected: bool) {
+        let mut start = 0;
+        for (text, glyphs) in &self.texts {
+            let end = start + glyphs.len();
+            let begin = range.start.clamp(start, end);
+            let finish = range.end.clamp(start, end);
+            if begin < finish {
+                text.set_glyphs_selected(mc, (begin - start)..(finish - start), selected);
+            }
+            start = end;
+        }
+    }
+
+    pub fn set_select_color(&self, mc: &Mutation<'gc>, color: Color) {
+        for (text, _) in &self.texts {
+            text.set_select_color(mc, color);
+        }
+    }
+
+    /// Finds the character at the given point in the local space of the container.
+    ///
+    /// When no character contains the point, the closest character
+    /// no further than `max_distance` is returned.
+    pub fn hit_test(&self, point: Point<Twips>, max_distance: Twips) -> Option<usize> {
+        let mut closest = None;
+        for c in self.chars() {
+            let x_min = c.corners.iter().map(|p| p.x).min().unwrap_or_default();
+            let x_max = c.corners.iter().map(|p| p.x).max().unwrap_or_default();
+            let y_min = c.corners.iter().map(|p| p.y).min().unwrap_or_default();
+            let y_max = c.corners.iter().map(|p| p.y).max().unwrap_or_default();
+            let dx = (x_min - point.x).max(point.x - x_max).max(Twips::ZERO);
+            let dy = (y_min - point.y).max(point.y - y_max).max(Twips::ZERO);
+            let distance = (dx.get() as f64).hypot(dy.get() as f64);
+            if distance <= max_distance.get() as f64
+                && closest.map_or(true, |(_, closest)| distance < closest)
+            {
+                closest = Some((c.index, distance));
+            }
+        }
+        closest.map(|(index, _)| index)
+    }
+}
+
+/// Finds the first occurrence of `needle` in `haystack` at or after `begin`.
+///
+/// Case-insensitive comparisons are made character by character.
+fn find_chars(
+    haystack: &[char],
+    needle: &[char],
+    begin: usize,
+    case_sensitive: bool,
+) -> Option<usize> {
+    if needle.is_empty() || begin >= haystack.len() {
+        return None;
+    }
+
+    let eq = |a: &char, b: &char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+    haystack[begin..]
+        .windows(needle.len())
+        .position(|window| window.iter().zip(needle).all(|(a, b)| eq(a, b)))
+        .map(|position| begin + position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn find_case_sensitive() {
+        let haystack = chars("Hello hello");
+        assert_eq!(find_chars(&haystack, &chars("hello"), 0, true), Some(6));
+        assert_eq!(find_chars(&haystack, &chars("Hello"), 1, true), None);
+    }
+
+    #[test]
+    fn find_case_insensitive() {
+        let haystack = chars("Hello hello");
+        assert_eq!(find_chars(&haystack, &chars("HELLO"), 0, false), Some(0));
+        assert_eq!(find_chars(&haystack, &chars("HELLO"), 1, false), Some(6));
+    }
+
+    #[test]
+    fn find_out_of_range() {
+        let haystack = chars("abc");
+        assert_eq!(find_chars(&haystack, &chars(""), 0, true), None);
+        assert_eq!(find_chars(&haystack, &chars("abcd"), 0, true), None);
+        assert_eq!(find_chars(&haystack, &chars("c"), 3, true), None);
+    }
+}
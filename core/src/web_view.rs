@@ -0,0 +1,215 @@
+//! `StageWebView`s created by AIR content.
+//!
+//! The web views themselves are overlays owned by the `UiBackend`, which reports what
+//! happens in them back to the player. The player only keeps track of which `StageWebView`
+//! object each of them belongs to, along with the state that the movie can query.
+
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+};
+use crate::backend::ui::{WebViewEvent, WebViewId, WebViewNavigation, WebViewViewport};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use gc_arena::Collect;
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct WebView<'gc> {
+    #[collect(require_static)]
+    id: WebViewId,
+
+    /// The AVM2 `StageWebView` object that owns this web view.
+    object: Avm2Object<'gc>,
+
+    #[collect(require_static)]
+    location: String,
+
+    #[collect(require_static)]
+    title: Option<String>,
+
+    can_go_back: bool,
+
+    can_go_forward: bool,
+}
+
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct WebViews<'gc> {
+    next_id: WebViewId,
+
+    views: Vec<WebView<'gc>>,
+}
+
+impl<'gc> WebViews<'gc> {
+    fn find_object(&self, object: Avm2Object<'gc>) -> Option<&WebView<'gc>> {
+        self.views
+            .iter()
+            .find(|view| Avm2Object::ptr_eq(view.object, object))
+    }
+
+    /// Creates the web view owned by the given `StageWebView` object.
+    pub fn create(context: &mut UpdateContext<'gc>, object: Avm2Object<'gc>) {
+        let views = &mut *context.web_views;
+        if views.find_object(object).is_some() {
+            return;
+        }
+        let id = views.next_id;
+        views.next_id = views.next_id.wrapping_add(1);
+        views.views.push(WebView {
+            id,
+            object,
+            location: "about:blank".to_string(),
+            title: None,
+            can_go_back: false,
+            can_go_forward: false,
+        });
+        context.ui.create_web_view(id);
+    }
+
+    /// Destroys the web view owned by the given object.
+    pub fn dispose(context: &mut UpdateContext<'gc>, object: Avm2Object<'gc>) {
+        let views = &mut *context.web_views;
+        let Some(index) = views
+            .views
+            .iter()
+            .position(|view| Avm2Object::ptr_eq(view.object, object))
+        else {
+            return;
+        };
+        let view = views.views.remove(index);
+        context.ui.dispose_web_view(view.id);
+    }
+
+    pub fn set_viewport(
+        context: &mut UpdateContext<'gc>,
+        object: Avm2Object<'gc>,
+        viewport: Option<WebViewViewport>,
+    ) {
+        if let Some(view) = context.web_views.find_object(object) {
+            context.ui.set_web_view_viewport(view.id, viewport);
+        }
+    }
+
+    pub fn load_url(context: &mut UpdateContext<'gc>, object: Avm2Object<'gc>, url: &str) {
+        if let Some(view) = context.web_views.find_object(object) {
+            context.ui.load_web_view_url(view.id, url);
+        }
+    }
+
+    pub fn load_string(
+        context: &mut UpdateContext<'gc>,
+        object: Avm2Object<'gc>,
+        text: &str,
+        mime_type: &str,
+    ) {
+        if let Some(view) = context.web_views.find_object(object) {
+            context.ui.load_web_view_string(view.id, text, mime_type);
+        }
+    }
+
+    pub fn navigate(
+        context: &mut UpdateContext<'gc>,
+        object: Avm2Object<'gc>,
+        navigation: WebViewNavigation,
+    ) {
+        if let Some(view) = context.web_views.find_object(object) {
+            context.ui.navigate_web_view(view.id, navigation);
+        }
+    }
+
+    /// The URL of the page shown by the web view owned by the given object.
+    pub fn location(&self, object: Avm2Object<'gc>) -> Option<&str> {
+        self.find_object(object).map(|view| view.location.as_str())
+    }
+
+    /// The title of the page shown by the web view owned by the given object,
+    /// if it has finished loading.
+    pub fn title(&self, object: Avm2Object<'gc>) -> Option<&str> {
+        self.find_object(object)?.title.as_deref()
+    }
+
+    pub fn can_go_back(&self, object: Avm2Object<'gc>) -> bool {
+        self.find_object(object)
+            .is_some_and(|view| view.can_go_back)
+    }
+
+    pub fn can_go_forward(&self, object: Avm2Object<'gc>) -> bool {
+        self.find_object(object)
+            .is_some_and(|view| view.can_go_forward)
+    }
+
+    /// Records something that happened in a web view, and dispatches the matching
+    /// event to its `StageWebView`.
+    pub fn handle_event(context: &mut UpdateContext<'gc>, id: WebViewId, event: WebViewEvent) {
+        let Some(view) = context
+            .web_views
+            .views
+            .iter_mut()
+            .find(|view| view.id == id)
+        else {
+            return;
+        };
+        let object = view.object;
+        match &event {
+            WebViewEvent::LocationChanged {
+                location,
+                can_go_back,
+                can_go_forward,
+            } => {
+                view.location = location.clone();
+                view.title = None;
+                view.can_go_back = *can_go_back;
+                view.can_go_forward = *can_go_forward;
+            }
+            WebViewEvent::Complete { title } => view.title = title.clone(),
+            WebViewEvent::Error(_) => {}
+        }
+
+        let mut activation = Avm2Activation::from_nothing(context);
+        let result = match event {
+            WebViewEvent::LocationChanged { location, .. } => {
+                // The web view has already navigated by the time we hear about it,
+                // so cancelling `locationChanging` has no effect.
+                let location = AvmString::new_utf8(activation.gc(), location);
+                ["locationChanging", "locationChange"]
+                    .into_iter()
+                    .try_for_each(|name| {
+                        let event = activation.avm2().classes().locationchangeevent.construct(
+                            &mut activation,
+                            &[
+                                name.into(),
+                                false.into(),
+                                (name == "locationChanging").into(),
+                                location.into(),
+                            ],
+                        )?;
+                        Avm2::dispatch_event(activation.context, event, object);
+                        Ok(())
+                    })
+            }
+            WebViewEvent::Complete { .. } => {
+                let event = Avm2EventObject::bare_default_event(activation.context, "complete");
+                Avm2::dispatch_event(activation.context, event, object);
+                Ok(())
+            }
+            WebViewEvent::Error(message) => {
+                let message = AvmString::new_utf8(activation.gc(), message);
+                activation
+                    .avm2()
+                    .classes()
+                    .errorevent
+                    .construct(
+                        &mut activation,
+                        &["error".into(), false.into(), false.into(), message.into()],
+                    )
+                    .map(|event| {
+                        Avm2::dispatch_event(activation.context, event, object);
+                    })
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::error!("Unhandled error while dispatching StageWebView event: {e:?}");
+        }
+    }
+}
@@ -1,6 +1,8 @@
 file-picker-title-open-file = Open a single file
+file-picker-title-save-profile = Save profile
 file-picker-filter-supported = All Supported Files
 file-picker-filter-swf = SWF (*.swf)
 file-picker-filter-spl = FutureSplash Animator (*.spl)
 file-picker-filter-ruf = Ruffle Bundle (*.ruf)
 file-picker-filter-all = All Files
+file-picker-filter-speedscope = Speedscope Profile (*.speedscope.json)
@@ -40,6 +40,10 @@ debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-open-garbage-collector = Show Garbage Collector
+debug-menu-trace-console = Trace Console
+debug-menu-start-profiling = Start Profiling
+debug-menu-stop-profiling = Stop Profiling and Save...
 
 view-menu = View
 view-menu-fullscreen = Full Screen
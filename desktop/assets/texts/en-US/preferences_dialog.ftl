@@ -9,6 +9,9 @@ graphics-power = Power Preference
 graphics-power-low = Low (e.g. iGPU)
 graphics-power-high = High (e.g. GPU)
 
+adaptive-quality = Adaptive Quality
+adaptive-quality-tooltip = Lowers the rendering quality while the movie can't keep up with its frame rate, and restores it once it can.
+
 language = Language
 
 audio-output-device = Audio Output Device
@@ -0,0 +1,3 @@
+trace-console = Trace Console
+trace-console-clear = Clear
+trace-console-close = Close
@@ -1,13 +1,18 @@
+use crate::backends::DesktopUiBackend;
 use crate::custom_event::RuffleEvent;
 use crate::gui::{GuiController, MENU_HEIGHT};
+use crate::native_windows::NativeWindowInput;
 use crate::player::{LaunchOptions, PlayerController};
 use crate::preferences::GlobalPreferences;
 use crate::util::{
     get_screen_size, gilrs_button_to_gamepad_button, parse_url, plot_stats_in_tracy,
     winit_to_ruffle_key_code, winit_to_ruffle_text_control,
 };
+use crate::SWF_INFO;
 use anyhow::Error;
 use gilrs::{Event, EventType, Gilrs};
+use ruffle_core::backend::ui::RootMovieLoadPhase;
+use ruffle_core::events::{ImeEvent, TouchEvent, TouchPhase};
 use ruffle_core::swf::HeaderExt;
 use ruffle_core::PlayerEvent;
 use ruffle_render::backend::ViewportDimensions;
@@ -16,7 +21,9 @@ use std::time::Instant;
 use url::Url;
 use winit::application::ApplicationHandler;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
-use winit::event::{ElementState, KeyEvent, Modifiers, StartCause, WindowEvent};
+use winit::event::{
+    DeviceEvent, DeviceId, ElementState, Ime, KeyEvent, Modifiers, StartCause, WindowEvent,
+};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy};
 use winit::keyboard::{Key, NamedKey};
 use winit::window::{Fullscreen, Icon, WindowAttributes, WindowId};
@@ -27,6 +34,9 @@ struct MainWindow {
     player: PlayerController,
     minimized: bool,
     mouse_pos: PhysicalPosition<f64>,
+
+    /// The finger that's also used as the mouse, as winit doesn't turn touches into mouse events.
+    primary_touch: Option<u64>,
     modifiers: Modifiers,
     min_window_size: LogicalSize<u32>,
     max_window_size: PhysicalSize<u32>,
@@ -46,6 +56,9 @@ impl MainWindow {
             // Don't render when minimized to avoid potential swap chain errors in `wgpu`.
             if !self.minimized {
                 if let Some(mut player) = self.player.get() {
+                    if let Some(ui) = player.ui_mut().downcast_mut::<DesktopUiBackend>() {
+                        ui.create_custom_cursors(event_loop);
+                    }
                     // Even if the movie is paused, user interaction with debug tools can change the render output
                     player.render();
                     self.gui.render(Some(player));
@@ -96,6 +109,10 @@ impl MainWindow {
                 }
 
                 self.mouse_pos = position;
+                if self.player.is_mouse_locked() {
+                    // The movement is reported by `DeviceEvent::MouseMotion` instead.
+                    return;
+                }
                 let event = PlayerEvent::MouseMove {
                     x: position.x,
                     y: position.y - height_offset,
@@ -103,6 +120,56 @@ impl MainWindow {
                 self.player.handle_event(event);
                 self.check_redraw();
             }
+            WindowEvent::Touch(touch) => {
+                if self.gui.is_context_menu_visible() {
+                    return;
+                }
+
+                use ruffle_core::events::MouseButton as RuffleMouseButton;
+                use winit::event::TouchPhase as WinitTouchPhase;
+                let x = touch.location.x;
+                let y = touch.location.y - height_offset;
+                let phase = match touch.phase {
+                    WinitTouchPhase::Started => TouchPhase::Begin,
+                    WinitTouchPhase::Moved => TouchPhase::Move,
+                    WinitTouchPhase::Ended => TouchPhase::End,
+                    WinitTouchPhase::Cancelled => TouchPhase::Cancel,
+                };
+                self.player.handle_touch_event(TouchEvent {
+                    id: touch.id,
+                    phase,
+                    x,
+                    y,
+                });
+
+                if phase == TouchPhase::Begin && self.primary_touch.is_none() {
+                    self.primary_touch = Some(touch.id);
+                }
+                if self.primary_touch == Some(touch.id) {
+                    self.mouse_pos = PhysicalPosition::new(touch.location.x, touch.location.y);
+                    self.player.handle_event(PlayerEvent::MouseMove { x, y });
+                    match phase {
+                        TouchPhase::Begin => {
+                            self.player.handle_event(PlayerEvent::MouseDown {
+                                x,
+                                y,
+                                button: RuffleMouseButton::Left,
+                                index: None,
+                            });
+                        }
+                        TouchPhase::End | TouchPhase::Cancel => {
+                            self.primary_touch = None;
+                            self.player.handle_event(PlayerEvent::MouseUp {
+                                x,
+                                y,
+                                button: RuffleMouseButton::Left,
+                            });
+                        }
+                        TouchPhase::Move => {}
+                    }
+                }
+                self.check_redraw();
+            }
             WindowEvent::DroppedFile(file) => {
                 if let Ok(url) = parse_url(&file) {
                     self.gui.create_movie(
@@ -242,10 +309,63 @@ impl MainWindow {
                 };
                 self.check_redraw();
             }
+            WindowEvent::Ime(ime) => {
+                let event = match ime {
+                    Ime::Preedit(text, cursor) => ImeEvent::Preedit(text, cursor),
+                    Ime::Commit(text) => ImeEvent::Commit(text),
+                    Ime::Enabled | Ime::Disabled => return,
+                };
+                self.player.handle_ime_event(event);
+                self.check_redraw();
+            }
             _ => (),
         }
     }
 
+    /// Handles an event received by one of the movie's additional `NativeWindow`s.
+    fn native_window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        let input = self
+            .player
+            .native_windows()
+            .borrow_mut()
+            .handle_window_event(window_id, &event);
+
+        match input {
+            Some((id, input)) => {
+                if let Some(mut player) = self.player.get() {
+                    match input {
+                        NativeWindowInput::Pointer(event) => {
+                            player.handle_native_window_event(id, event);
+                        }
+                        NativeWindowInput::Focused(focused) => {
+                            player.set_native_window_active(id, focused);
+                        }
+                        NativeWindowInput::CloseRequested => {
+                            player.request_native_window_close(id);
+                        }
+                    }
+                }
+                self.check_redraw();
+            }
+            // Keyboard input goes to the player, whichever of its windows is focused.
+            None if matches!(
+                event,
+                WindowEvent::KeyboardInput { .. }
+                    | WindowEvent::ModifiersChanged(_)
+                    | WindowEvent::Ime(_)
+            ) =>
+            {
+                self.window_event(event_loop, event);
+            }
+            None => {}
+        }
+    }
+
     fn on_metadata(&mut self, swf_header: HeaderExt) {
         let height_offset = if self.gui.window().fullscreen().is_some() || self.no_gui {
             0.0
@@ -505,6 +625,7 @@ impl ApplicationHandler<RuffleEvent> for App {
                 loaded,
                 minimized: false,
                 mouse_pos: PhysicalPosition::new(0.0, 0.0),
+                primary_touch: None,
                 modifiers: Modifiers::default(),
                 time: Instant::now(),
                 next_frame_time: None,
@@ -553,6 +674,45 @@ impl ApplicationHandler<RuffleEvent> for App {
                 main_window.gui.open_dialog(descriptor);
             }
 
+            (
+                Some(main_window),
+                RuffleEvent::LoadProgress {
+                    phase,
+                    bytes_loaded,
+                    bytes_total,
+                },
+            ) => {
+                // Show how far along the movie is, if that's known.
+                if let Some(bytes_total) = bytes_total.filter(|total| *total > 0) {
+                    let action = match phase {
+                        RootMovieLoadPhase::Download => "Downloading",
+                        RootMovieLoadPhase::Preload => "Loading",
+                    };
+                    let percent = (bytes_loaded.min(bytes_total) * 100) / bytes_total;
+                    let name = SWF_INFO.with(|i| i.borrow().clone()).unwrap_or_default();
+                    main_window
+                        .gui
+                        .window()
+                        .set_title(&format!("Ruffle - {name} ({action} {percent}%)"));
+                }
+            }
+
+            (Some(main_window), RuffleEvent::Loaded) => {
+                let name = SWF_INFO.with(|i| i.borrow().clone()).unwrap_or_default();
+                main_window
+                    .gui
+                    .window()
+                    .set_title(&format!("Ruffle - {name}"));
+            }
+
+            (Some(main_window), RuffleEvent::NativeWindowsChanged) => {
+                main_window
+                    .player
+                    .native_windows()
+                    .borrow_mut()
+                    .create_pending(event_loop);
+            }
+
             (Some(main_window), RuffleEvent::CloseFile) => {
                 main_window.gui.window().set_title("Ruffle"); // Reset title since file has been closed.
                 main_window.gui.close_movie(&mut main_window.player);
@@ -578,6 +738,12 @@ impl ApplicationHandler<RuffleEvent> for App {
                 event_loop.exit();
             }
 
+            (Some(main_window), RuffleEvent::SetScaleMode(scale_mode)) => {
+                if let Some(mut player) = main_window.player.get() {
+                    player.set_scale_mode(scale_mode);
+                }
+            }
+
             _ => {}
         }
     }
@@ -585,11 +751,33 @@ impl ApplicationHandler<RuffleEvent> for App {
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
-        _window_id: WindowId,
+        window_id: WindowId,
         event: WindowEvent,
     ) {
         if let Some(main_window) = &mut self.main_window {
-            main_window.window_event(event_loop, event);
+            if window_id == main_window.gui.window().id() {
+                main_window.window_event(event_loop, event);
+            } else {
+                main_window.native_window_event(event_loop, window_id, event);
+            }
+        }
+    }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        if let (Some(main_window), DeviceEvent::MouseMotion { delta: (dx, dy) }) =
+            (&mut self.main_window, event)
+        {
+            if main_window.player.is_mouse_locked() {
+                main_window
+                    .player
+                    .handle_event(PlayerEvent::MouseMotion { dx, dy });
+                main_window.check_redraw();
+            }
         }
     }
 
@@ -1,10 +1,19 @@
+mod debugger;
 mod external_interface;
 mod fscommand;
+#[cfg(feature = "jit")]
+mod jit;
+mod microphone;
 mod navigator;
+mod print;
 mod ui;
 
+pub use debugger::DesktopDebuggerBackend;
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use fscommand::DesktopFSCommandProvider;
+#[cfg(feature = "jit")]
+pub use jit::CraneliftJitBackend;
+pub use microphone::DesktopMicrophoneBackend;
 pub use navigator::DesktopNavigatorInterface;
 pub use navigator::PathAllowList;
-pub use ui::DesktopUiBackend;
+pub use ui::{DesktopCursor, DesktopUiBackend};
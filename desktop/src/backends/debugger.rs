@@ -0,0 +1,103 @@
+use ruffle_core::backend::debugger::DebuggerBackend;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+enum Event {
+    Message(String),
+    Detached,
+}
+
+/// Lets a debugger attach over TCP, on the local machine only.
+///
+/// Messages are exchanged as lines of JSON. One debugger may be attached at a time;
+/// any other waits until it detaches.
+pub struct DesktopDebuggerBackend {
+    /// The connection of the attached debugger.
+    stream: Arc<Mutex<Option<TcpStream>>>,
+    events: Receiver<Event>,
+}
+
+impl DesktopDebuggerBackend {
+    pub fn new(port: u16) -> Result<Self, std::io::Error> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        tracing::info!("Waiting for debuggers on port {port}");
+
+        let stream = Arc::new(Mutex::new(None));
+        let (sender, events) = channel();
+        let listener_stream = stream.clone();
+        std::thread::Builder::new()
+            .name("Debugger listener".to_string())
+            .spawn(move || listen(listener, listener_stream, sender))?;
+
+        Ok(Self { stream, events })
+    }
+}
+
+/// Accepts debuggers one at a time, forwarding their messages to the player.
+fn listen(listener: TcpListener, stream: Arc<Mutex<Option<TcpStream>>>, sender: Sender<Event>) {
+    for connection in listener.incoming() {
+        let connection = match connection {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::warn!("Couldn't accept debugger: {e}");
+                continue;
+            }
+        };
+        let Ok(writer) = connection.try_clone() else {
+            continue;
+        };
+        tracing::info!("Debugger attached from {:?}", connection.peer_addr());
+        *stream.lock().expect("Stream is not poisoned") = Some(writer);
+
+        for line in BufReader::new(connection).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if !line.trim().is_empty() && sender.send(Event::Message(line)).is_err() {
+                // The player is gone.
+                return;
+            }
+        }
+
+        *stream.lock().expect("Stream is not poisoned") = None;
+        tracing::info!("Debugger detached");
+        if sender.send(Event::Detached).is_err() {
+            return;
+        }
+    }
+}
+
+impl DebuggerBackend for DesktopDebuggerBackend {
+    fn is_attached(&self) -> bool {
+        self.stream
+            .lock()
+            .expect("Stream is not poisoned")
+            .is_some()
+    }
+
+    fn send(&mut self, message: String) {
+        if let Some(stream) = self.stream.lock().expect("Stream is not poisoned").as_mut() {
+            if let Err(e) = writeln!(stream, "{message}") {
+                tracing::warn!("Couldn't send message to debugger: {e}");
+            }
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<String> {
+        while let Ok(event) = self.events.try_recv() {
+            if let Event::Message(message) = event {
+                return Some(message);
+            }
+        }
+        None
+    }
+
+    fn receive(&mut self) -> Option<String> {
+        match self.events.recv() {
+            Ok(Event::Message(message)) => Some(message),
+            Ok(Event::Detached) | Err(_) => None,
+        }
+    }
+}
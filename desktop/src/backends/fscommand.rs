@@ -1,31 +1,43 @@
 use crate::custom_event::RuffleEvent;
 
 use ruffle_core::external::FsCommandProvider;
+use ruffle_core::StageScaleMode;
+use std::collections::HashSet;
 use winit::event_loop::EventLoopProxy;
 
 pub struct DesktopFSCommandProvider {
     pub event_loop: EventLoopProxy<RuffleEvent>,
+
+    /// The (lowercase) commands that movies may use. If empty, all of them are allowed.
+    pub allowed: HashSet<String>,
 }
 
 impl FsCommandProvider for DesktopFSCommandProvider {
     fn on_fs_command(&self, command: &str, args: &str) -> bool {
-        match command {
-            "quit" => {
-                let _ = self.event_loop.send_event(RuffleEvent::ExitRequested);
-            }
-            "fullscreen" => {
-                match args {
-                    "true" => {
-                        let _ = self.event_loop.send_event(RuffleEvent::EnterFullScreen);
-                    }
-                    "false" => {
-                        let _ = self.event_loop.send_event(RuffleEvent::ExitFullScreen);
-                    }
-                    _ => {}
-                };
-            }
+        let command = command.to_ascii_lowercase();
+        if !self.allowed.is_empty() && !self.allowed.contains(&command) {
+            tracing::info!("FSCommand {command} is not in the allow-list, ignoring it");
+            return false;
+        }
+
+        let enabled = if args.eq_ignore_ascii_case("true") {
+            Some(true)
+        } else if args.eq_ignore_ascii_case("false") {
+            Some(false)
+        } else {
+            None
+        };
+
+        let event = match (command.as_str(), enabled) {
+            ("quit", _) => RuffleEvent::ExitRequested,
+            ("fullscreen", Some(true)) => RuffleEvent::EnterFullScreen,
+            ("fullscreen", Some(false)) => RuffleEvent::ExitFullScreen,
+            ("allowscale", Some(true)) => RuffleEvent::SetScaleMode(StageScaleMode::ShowAll),
+            ("allowscale", Some(false)) => RuffleEvent::SetScaleMode(StageScaleMode::NoScale),
+            ("fullscreen" | "allowscale", None) => return true,
             _ => return false,
         };
+        let _ = self.event_loop.send_event(event);
 
         true
     }
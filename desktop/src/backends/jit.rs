@@ -0,0 +1,642 @@
+use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
+use cranelift_codegen::ir::{types, AbiParam, Block, FuncRef, InstBuilder, MemFlags, Value};
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, FuncId, Linkage, Module};
+use ruffle_core::backend::jit::{
+    number_to_int, BinaryOp, CompiledFunction, Exit, Function, Instruction, Interrupted,
+    JitBackend, Slot, Type, UnaryOp,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// How many loop iterations compiled code runs between checks of the deadline.
+const ITERATIONS_PER_POLL: i64 = 10000;
+
+/// A value passed to or returned from compiled code, in its native type.
+#[derive(Clone, Copy)]
+#[repr(C)]
+union Raw {
+    int: i32,
+    number: f64,
+    boolean: u8,
+}
+
+/// `fn(args: *const Raw, ret: *mut Raw, deadline: *const Instant) -> interrupted`
+type Entry = unsafe extern "C" fn(*const Raw, *mut Raw, *const Instant) -> u8;
+
+extern "C" fn poll(deadline: *const Instant) -> u8 {
+    // SAFETY: Compiled code passes on the deadline that `CompiledCode::call` gave it.
+    let deadline = unsafe { &*deadline };
+    (Instant::now() >= *deadline).into()
+}
+
+extern "C" fn fmod(a: f64, b: f64) -> f64 {
+    a % b
+}
+
+extern "C" fn to_int(n: f64) -> i32 {
+    number_to_int(n)
+}
+
+/// The code of every function compiled by a backend, which is freed once the backend and all
+/// of its functions are gone.
+struct CodeMemory(Option<JITModule>);
+
+impl Drop for CodeMemory {
+    fn drop(&mut self) {
+        if let Some(module) = self.0.take() {
+            // SAFETY: Nothing can call into the module any more, as the backend and every
+            // compiled function held a reference to it.
+            unsafe { module.free_memory() };
+        }
+    }
+}
+
+/// Compiles AVM2 methods to native code with Cranelift.
+pub struct CraneliftJitBackend {
+    memory: Rc<RefCell<CodeMemory>>,
+    builder_context: FunctionBuilderContext,
+    poll: FuncId,
+    fmod: FuncId,
+    to_int: FuncId,
+}
+
+impl CraneliftJitBackend {
+    pub fn new() -> Result<Self, anyhow::Error> {
+        let mut flags = settings::builder();
+        flags.set("opt_level", "speed")?;
+        flags.set("use_colocated_libcalls", "false")?;
+        flags.set("is_pic", "false")?;
+        let isa = cranelift_native::builder()
+            .map_err(|e| anyhow::anyhow!("Unsupported host for the JIT: {e}"))?
+            .finish(settings::Flags::new(flags))?;
+
+        let mut builder = JITBuilder::with_isa(isa, default_libcall_names());
+        builder.symbol("ruffle_jit_poll", poll as *const u8);
+        builder.symbol("ruffle_jit_fmod", fmod as *const u8);
+        builder.symbol("ruffle_jit_to_int", to_int as *const u8);
+        let mut module = JITModule::new(builder);
+        let pointer = module.target_config().pointer_type();
+
+        let mut import = |name: &str, params: &[types::Type], ret: types::Type| {
+            let mut signature = module.make_signature();
+            signature
+                .params
+                .extend(params.iter().map(|&ty| AbiParam::new(ty)));
+            signature.returns.push(AbiParam::new(ret));
+            module
+                .declare_function(name, Linkage::Import, &signature)
+                .map_err(anyhow::Error::from)
+        };
+        let poll = import("ruffle_jit_poll", &[pointer], types::I8)?;
+        let fmod = import("ruffle_jit_fmod", &[types::F64, types::F64], types::F64)?;
+        let to_int = import("ruffle_jit_to_int", &[types::F64], types::I32)?;
+
+        Ok(Self {
+            memory: Rc::new(RefCell::new(CodeMemory(Some(module)))),
+            builder_context: FunctionBuilderContext::new(),
+            poll,
+            fmod,
+            to_int,
+        })
+    }
+
+    fn try_compile(&mut self, function: &Function) -> Result<Entry, anyhow::Error> {
+        let mut memory = self.memory.borrow_mut();
+        let module = memory.0.as_mut().expect("Module is only taken on drop");
+        let pointer = module.target_config().pointer_type();
+
+        let mut signature = module.make_signature();
+        signature.params.extend([AbiParam::new(pointer); 3]);
+        signature.returns.push(AbiParam::new(types::I8));
+        let id = module.declare_anonymous_function(&signature)?;
+
+        let mut context = module.make_context();
+        context.func.signature = signature;
+        let imports = Imports {
+            poll: module.declare_func_in_func(self.poll, &mut context.func),
+            fmod: module.declare_func_in_func(self.fmod, &mut context.func),
+            to_int: module.declare_func_in_func(self.to_int, &mut context.func),
+        };
+
+        let builder = FunctionBuilder::new(&mut context.func, &mut self.builder_context);
+        Translator::new(builder, function, imports).translate();
+
+        module.define_function(id, &mut context)?;
+        module.clear_context(&mut context);
+        module.finalize_definitions()?;
+
+        let code = module.get_finalized_function(id);
+        // SAFETY: The function was declared with the signature of `Entry`.
+        Ok(unsafe { std::mem::transmute::<*const u8, Entry>(code) })
+    }
+}
+
+impl JitBackend for CraneliftJitBackend {
+    fn compile(&mut self, function: &Function) -> Option<Box<dyn CompiledFunction>> {
+        match self.try_compile(function) {
+            Ok(entry) => Some(Box::new(CompiledCode {
+                entry,
+                return_type: function.return_type,
+                _memory: self.memory.clone(),
+            })),
+            Err(e) => {
+                tracing::warn!("Couldn't compile AVM2 method: {e}");
+                None
+            }
+        }
+    }
+}
+
+struct CompiledCode {
+    entry: Entry,
+    return_type: Option<Type>,
+
+    /// Keeps the code of `entry` alive.
+    _memory: Rc<RefCell<CodeMemory>>,
+}
+
+impl CompiledFunction for CompiledCode {
+    fn call(&self, args: &[Slot], deadline: Instant) -> Result<Option<Slot>, Interrupted> {
+        let args: Vec<Raw> = args
+            .iter()
+            .map(|arg| match *arg {
+                Slot::Int(int) => Raw { int },
+                Slot::Number(number) => Raw { number },
+                Slot::Boolean(boolean) => Raw {
+                    boolean: boolean.into(),
+                },
+            })
+            .collect();
+        let mut ret = Raw { number: 0.0 };
+
+        // SAFETY: `args` has a value of the right type for every parameter, as checked by the
+        // caller, and the code stays alive as long as `self` does.
+        let interrupted = unsafe { (self.entry)(args.as_ptr(), &mut ret, &deadline) };
+        if interrupted != 0 {
+            return Err(Interrupted);
+        }
+
+        // SAFETY: Compiled code writes a value of the return type.
+        Ok(self.return_type.map(|ty| unsafe {
+            match ty {
+                Type::Int => Slot::Int(ret.int),
+                Type::Number => Slot::Number(ret.number),
+                Type::Boolean => Slot::Boolean(ret.boolean != 0),
+            }
+        }))
+    }
+}
+
+struct Imports {
+    poll: FuncRef,
+    fmod: FuncRef,
+    to_int: FuncRef,
+}
+
+/// Translates a function from the IR to Cranelift IR.
+struct Translator<'a> {
+    builder: FunctionBuilder<'a>,
+    function: &'a Function,
+    imports: Imports,
+    blocks: Vec<Block>,
+    back_edges: Vec<(Block, Block)>,
+    fuel: Variable,
+    deadline: Value,
+    ret: Value,
+}
+
+impl<'a> Translator<'a> {
+    fn new(mut builder: FunctionBuilder<'a>, function: &'a Function, imports: Imports) -> Self {
+        let entry = builder.create_block();
+        builder.append_block_params_for_function_params(entry);
+        builder.switch_to_block(entry);
+        builder.seal_block(entry);
+        let args = builder.block_params(entry)[0];
+        let ret = builder.block_params(entry)[1];
+        let deadline = builder.block_params(entry)[2];
+
+        for (var, ty) in function.vars.iter().enumerate() {
+            let var = Variable::from_u32(var as u32);
+            let ty = native_type(*ty);
+            builder.declare_var(var, ty);
+            let value = if var.as_u32() < function.num_params as u32 {
+                let offset = var.as_u32() as i32 * std::mem::size_of::<Raw>() as i32;
+                builder.ins().load(ty, MemFlags::trusted(), args, offset)
+            } else if ty == types::F64 {
+                builder.ins().f64const(0.0)
+            } else {
+                builder.ins().iconst(ty, 0)
+            };
+            builder.def_var(var, value);
+        }
+
+        let fuel = Variable::from_u32(function.vars.len() as u32);
+        builder.declare_var(fuel, types::I64);
+        let initial_fuel = builder.ins().iconst(types::I64, ITERATIONS_PER_POLL);
+        builder.def_var(fuel, initial_fuel);
+
+        let blocks: Vec<_> = function
+            .blocks
+            .iter()
+            .map(|_| builder.create_block())
+            .collect();
+        builder.ins().jump(blocks[0], &[]);
+
+        Self {
+            builder,
+            function,
+            imports,
+            blocks,
+            back_edges: vec![],
+            fuel,
+            deadline,
+            ret,
+        }
+    }
+
+    fn translate(mut self) {
+        let interrupted = self.builder.create_block();
+        for (id, block) in self.function.blocks.iter().enumerate() {
+            self.builder.switch_to_block(self.blocks[id]);
+            for instruction in &block.instructions {
+                self.instruction(instruction);
+            }
+            match block.exit {
+                Exit::Jump(target) => {
+                    let target = self.edge(id, target as usize);
+                    self.builder.ins().jump(target, &[]);
+                }
+                Exit::Branch {
+                    cond,
+                    if_true,
+                    if_false,
+                } => {
+                    let cond = self.get(cond);
+                    let if_true = self.edge(id, if_true as usize);
+                    let if_false = self.edge(id, if_false as usize);
+                    self.builder.ins().brif(cond, if_true, &[], if_false, &[]);
+                }
+                Exit::Return(value) => {
+                    if let Some(value) = value {
+                        let value = self.get(value);
+                        self.builder
+                            .ins()
+                            .store(MemFlags::trusted(), value, self.ret, 0);
+                    }
+                    let status = self.builder.ins().iconst(types::I8, 0);
+                    self.builder.ins().return_(&[status]);
+                }
+            }
+        }
+
+        for (countdown, target) in std::mem::take(&mut self.back_edges) {
+            self.back_edge(countdown, target, interrupted);
+        }
+
+        self.builder.switch_to_block(interrupted);
+        let status = self.builder.ins().iconst(types::I8, 1);
+        self.builder.ins().return_(&[status]);
+
+        self.builder.seal_all_blocks();
+        self.builder.finalize();
+    }
+
+    /// The block to go to when leaving block `from` for block `to`. Back-edges go through a
+    /// block that polls the deadline every `ITERATIONS_PER_POLL` times it is taken, which is
+    /// filled in by `back_edge` once the current block is done.
+    fn edge(&mut self, from: usize, to: usize) -> Block {
+        let target = self.blocks[to];
+        if to > from {
+            return target;
+        }
+        let countdown = self.builder.create_block();
+        self.back_edges.push((countdown, target));
+        countdown
+    }
+
+    fn back_edge(&mut self, countdown: Block, target: Block, interrupted: Block) {
+        let poll = self.builder.create_block();
+
+        self.builder.switch_to_block(countdown);
+        let fuel = self.builder.use_var(self.fuel);
+        let fuel = self.builder.ins().iadd_imm(fuel, -1);
+        self.builder.def_var(self.fuel, fuel);
+        self.builder.ins().brif(fuel, target, &[], poll, &[]);
+
+        self.builder.switch_to_block(poll);
+        let initial_fuel = self.builder.ins().iconst(types::I64, ITERATIONS_PER_POLL);
+        self.builder.def_var(self.fuel, initial_fuel);
+        let call = self.builder.ins().call(self.imports.poll, &[self.deadline]);
+        let expired = self.builder.inst_results(call)[0];
+        self.builder
+            .ins()
+            .brif(expired, interrupted, &[], target, &[]);
+    }
+
+    fn get(&mut self, var: u32) -> Value {
+        self.builder.use_var(Variable::from_u32(var))
+    }
+
+    fn set(&mut self, var: u32, value: Value) {
+        self.builder.def_var(Variable::from_u32(var), value);
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) {
+        match *instruction {
+            Instruction::Const { dst, value } => {
+                let value = match value {
+                    Slot::Int(int) => self.builder.ins().iconst(types::I32, i64::from(int)),
+                    Slot::Number(number) => self.builder.ins().f64const(number),
+                    Slot::Boolean(boolean) => {
+                        self.builder.ins().iconst(types::I8, i64::from(boolean))
+                    }
+                };
+                self.set(dst, value);
+            }
+            Instruction::Move { dst, src } => {
+                let value = self.get(src);
+                self.set(dst, value);
+            }
+            Instruction::Convert { dst, src } => {
+                let value = self.get(src);
+                let from = self.function.vars[src as usize];
+                let to = self.function.vars[dst as usize];
+                let value = self.convert(value, from, to);
+                self.set(dst, value);
+            }
+            Instruction::Unary { op, dst, src } => {
+                let value = self.get(src);
+                let ins = self.builder.ins();
+                let value = match op {
+                    UnaryOp::Negate => ins.fneg(value),
+                    UnaryOp::NegateI => ins.ineg(value),
+                    UnaryOp::BitNot => ins.bnot(value),
+                    UnaryOp::Not => ins.bxor_imm(value, 1),
+                    UnaryOp::ToUint => ins.fcvt_from_uint(types::F64, value),
+                };
+                self.set(dst, value);
+            }
+            Instruction::Binary { op, dst, lhs, rhs } => {
+                let operands = self.function.vars[lhs as usize];
+                let lhs = self.get(lhs);
+                let rhs = self.get(rhs);
+                let ins = self.builder.ins();
+                let value = match op {
+                    BinaryOp::Add => ins.fadd(lhs, rhs),
+                    BinaryOp::Subtract => ins.fsub(lhs, rhs),
+                    BinaryOp::Multiply => ins.fmul(lhs, rhs),
+                    BinaryOp::Divide => ins.fdiv(lhs, rhs),
+                    BinaryOp::Modulo => {
+                        let call = ins.call(self.imports.fmod, &[lhs, rhs]);
+                        self.builder.inst_results(call)[0]
+                    }
+                    BinaryOp::AddI => ins.iadd(lhs, rhs),
+                    BinaryOp::SubtractI => ins.isub(lhs, rhs),
+                    BinaryOp::MultiplyI => ins.imul(lhs, rhs),
+                    BinaryOp::BitAnd => ins.band(lhs, rhs),
+                    BinaryOp::BitOr => ins.bor(lhs, rhs),
+                    BinaryOp::BitXor => ins.bxor(lhs, rhs),
+                    // Cranelift shifts by the amount modulo the width, which is the low five
+                    // bits for 32-bit integers.
+                    BinaryOp::LShift => ins.ishl(lhs, rhs),
+                    BinaryOp::RShift => ins.sshr(lhs, rhs),
+                    BinaryOp::URShift => {
+                        let shifted = ins.ushr(lhs, rhs);
+                        self.builder.ins().fcvt_from_uint(types::F64, shifted)
+                    }
+                    BinaryOp::Equal | BinaryOp::LessThan | BinaryOp::LessEqual => {
+                        if operands == Type::Number {
+                            let cc = match op {
+                                BinaryOp::Equal => FloatCC::Equal,
+                                BinaryOp::LessThan => FloatCC::LessThan,
+                                _ => FloatCC::LessThanOrEqual,
+                            };
+                            ins.fcmp(cc, lhs, rhs)
+                        } else {
+                            let cc = match op {
+                                BinaryOp::Equal => IntCC::Equal,
+                                BinaryOp::LessThan => IntCC::SignedLessThan,
+                                _ => IntCC::SignedLessThanOrEqual,
+                            };
+                            ins.icmp(cc, lhs, rhs)
+                        }
+                    }
+                };
+                self.set(dst, value);
+            }
+        }
+    }
+
+    fn convert(&mut self, value: Value, from: Type, to: Type) -> Value {
+        let ins = self.builder.ins();
+        match (from, to) {
+            (Type::Int, Type::Number) => ins.fcvt_from_sint(types::F64, value),
+            (Type::Boolean, Type::Int) => ins.uextend(types::I32, value),
+            (Type::Boolean, Type::Number) => ins.fcvt_from_uint(types::F64, value),
+            (Type::Int, Type::Boolean) => ins.icmp_imm(IntCC::NotEqual, value, 0),
+            (Type::Number, Type::Boolean) => {
+                let zero = ins.f64const(0.0);
+                self.builder
+                    .ins()
+                    .fcmp(FloatCC::OrderedNotEqual, value, zero)
+            }
+            (Type::Number, Type::Int) => self.number_to_int(value),
+            _ => value,
+        }
+    }
+
+    /// Converts with `ToInt32`, truncating inline when the value is in range and calling out
+    /// to Rust for everything else.
+    fn number_to_int(&mut self, value: Value) -> Value {
+        let in_range = self.builder.create_block();
+        let out_of_range = self.builder.create_block();
+        let done = self.builder.create_block();
+        let result = self.builder.append_block_param(done, types::I32);
+
+        let magnitude = self.builder.ins().fabs(value);
+        let limit = self.builder.ins().f64const(2147483648.0);
+        let fits = self.builder.ins().fcmp(FloatCC::LessThan, magnitude, limit);
+        self.builder
+            .ins()
+            .brif(fits, in_range, &[], out_of_range, &[]);
+
+        self.builder.switch_to_block(in_range);
+        let truncated = self.builder.ins().fcvt_to_sint_sat(types::I32, value);
+        self.builder.ins().jump(done, &[truncated]);
+
+        self.builder.switch_to_block(out_of_range);
+        let call = self.builder.ins().call(self.imports.to_int, &[value]);
+        let wrapped = self.builder.inst_results(call)[0];
+        self.builder.ins().jump(done, &[wrapped]);
+
+        self.builder.switch_to_block(done);
+        result
+    }
+}
+
+fn native_type(ty: Type) -> types::Type {
+    match ty {
+        Type::Int => types::I32,
+        Type::Number => types::F64,
+        Type::Boolean => types::I8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::backend::jit::Block as IrBlock;
+    use std::time::Duration;
+
+    fn compile(function: &Function) -> Box<dyn CompiledFunction> {
+        CraneliftJitBackend::new()
+            .expect("Host should be supported")
+            .compile(function)
+            .expect("Function should compile")
+    }
+
+    fn far_deadline() -> Instant {
+        Instant::now() + Duration::from_secs(60)
+    }
+
+    /// `var total:Number = 0; for (var i:int = 0; i < n; i++) { total += i % 7; } return total;`
+    fn sum_loop() -> Function {
+        Function {
+            // n, total, i, 1, 7, i as Number, i < n
+            vars: vec![
+                Type::Int,
+                Type::Number,
+                Type::Int,
+                Type::Int,
+                Type::Number,
+                Type::Number,
+                Type::Boolean,
+            ],
+            num_params: 1,
+            blocks: vec![
+                IrBlock {
+                    instructions: vec![
+                        Instruction::Const {
+                            dst: 3,
+                            value: Slot::Int(1),
+                        },
+                        Instruction::Const {
+                            dst: 4,
+                            value: Slot::Number(7.0),
+                        },
+                    ],
+                    exit: Exit::Jump(2),
+                },
+                IrBlock {
+                    instructions: vec![
+                        Instruction::Convert { dst: 5, src: 2 },
+                        Instruction::Binary {
+                            op: BinaryOp::Modulo,
+                            dst: 5,
+                            lhs: 5,
+                            rhs: 4,
+                        },
+                        Instruction::Binary {
+                            op: BinaryOp::Add,
+                            dst: 1,
+                            lhs: 1,
+                            rhs: 5,
+                        },
+                        Instruction::Binary {
+                            op: BinaryOp::AddI,
+                            dst: 2,
+                            lhs: 2,
+                            rhs: 3,
+                        },
+                    ],
+                    exit: Exit::Jump(2),
+                },
+                IrBlock {
+                    instructions: vec![Instruction::Binary {
+                        op: BinaryOp::LessThan,
+                        dst: 6,
+                        lhs: 2,
+                        rhs: 0,
+                    }],
+                    exit: Exit::Branch {
+                        cond: 6,
+                        if_true: 1,
+                        if_false: 3,
+                    },
+                },
+                IrBlock {
+                    instructions: vec![],
+                    exit: Exit::Return(Some(1)),
+                },
+            ],
+            return_type: Some(Type::Number),
+        }
+    }
+
+    #[test]
+    fn runs_loops() {
+        let function = compile(&sum_loop());
+        let call = |n| function.call(&[Slot::Int(n)], far_deadline());
+        assert_eq!(call(0), Ok(Some(Slot::Number(0.0))));
+        assert_eq!(call(-5), Ok(Some(Slot::Number(0.0))));
+        assert_eq!(call(10), Ok(Some(Slot::Number(24.0))));
+        assert_eq!(call(70000), Ok(Some(Slot::Number(210000.0))));
+    }
+
+    #[test]
+    fn converts_like_flash() {
+        // Number to int, int to Boolean, and the unsigned value of the int.
+        let function = Function {
+            vars: vec![Type::Number, Type::Int, Type::Number],
+            num_params: 1,
+            blocks: vec![IrBlock {
+                instructions: vec![
+                    Instruction::Convert { dst: 1, src: 0 },
+                    Instruction::Unary {
+                        op: UnaryOp::ToUint,
+                        dst: 2,
+                        src: 1,
+                    },
+                ],
+                exit: Exit::Return(Some(2)),
+            }],
+            return_type: Some(Type::Number),
+        };
+        let function = compile(&function);
+        let to_uint = |n| match function.call(&[Slot::Number(n)], far_deadline()) {
+            Ok(Some(Slot::Number(n))) => n,
+            result => panic!("Unexpected result {result:?}"),
+        };
+        assert_eq!(to_uint(1.9), 1.0);
+        assert_eq!(to_uint(-1.9), 4294967295.0);
+        assert_eq!(to_uint(4294967298.5), 2.0);
+        assert_eq!(to_uint(-2147483649.0), 2147483647.0);
+        assert_eq!(to_uint(1e20), 1661992960.0);
+        assert_eq!(to_uint(f64::NAN), 0.0);
+        assert_eq!(to_uint(f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn interrupts_infinite_loops() {
+        let function = Function {
+            vars: vec![],
+            num_params: 0,
+            blocks: vec![IrBlock {
+                instructions: vec![],
+                exit: Exit::Jump(0),
+            }],
+            return_type: None,
+        };
+        let function = compile(&function);
+        let start = Instant::now();
+        assert_eq!(
+            function.call(&[], start + Duration::from_millis(50)),
+            Err(Interrupted)
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,147 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SizedSample};
+use ruffle_core::backend::microphone::{CaptureStatus, MicrophoneBackend, Resampler};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// The most samples kept for a microphone that the movie isn't reading from.
+const MAX_BUFFERED_SAMPLES: usize = 96000;
+
+struct Capture {
+    // Capturing stops when the stream is dropped.
+    _stream: cpal::Stream,
+    samples: Arc<Mutex<Vec<f32>>>,
+    resampler: Resampler,
+}
+
+/// Captures microphones with cpal input streams.
+pub struct DesktopMicrophoneBackend {
+    /// The input devices, starting with the default one.
+    devices: Vec<cpal::Device>,
+    captures: HashMap<usize, Capture>,
+
+    /// The microphones that couldn't be opened.
+    failed: HashSet<usize>,
+}
+
+impl DesktopMicrophoneBackend {
+    pub fn new() -> Self {
+        let host = cpal::default_host();
+        let default = host.default_input_device();
+        let default_name = default.as_ref().and_then(|device| device.name().ok());
+
+        let mut devices: Vec<_> = default.into_iter().collect();
+        match host.input_devices() {
+            Ok(inputs) => devices.extend(
+                inputs
+                    .filter(|device| default_name.is_none() || device.name().ok() != default_name),
+            ),
+            Err(e) => tracing::warn!("Couldn't list microphones: {e}"),
+        }
+
+        Self {
+            devices,
+            captures: HashMap::new(),
+            failed: HashSet::new(),
+        }
+    }
+
+    fn start(&self, index: usize, sample_rate: u32) -> Result<Capture, anyhow::Error> {
+        let device = self
+            .devices
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!("No microphone {index}"))?;
+        let config = device.default_input_config()?;
+        let sample_format = config.sample_format();
+        let config = cpal::StreamConfig::from(config);
+        let samples = Arc::new(Mutex::new(Vec::new()));
+
+        let stream = match sample_format {
+            cpal::SampleFormat::F32 => build_stream::<f32>(device, &config, samples.clone()),
+            cpal::SampleFormat::I16 => build_stream::<i16>(device, &config, samples.clone()),
+            cpal::SampleFormat::U16 => build_stream::<u16>(device, &config, samples.clone()),
+            format => return Err(anyhow::anyhow!("Unsupported sample format {format:?}")),
+        }?;
+        stream.play()?;
+
+        Ok(Capture {
+            _stream: stream,
+            samples,
+            resampler: Resampler::new(config.sample_rate.0, sample_rate),
+        })
+    }
+}
+
+impl MicrophoneBackend for DesktopMicrophoneBackend {
+    fn names(&self) -> Vec<String> {
+        self.devices
+            .iter()
+            .map(|device| device.name().unwrap_or_default())
+            .collect()
+    }
+
+    fn open(&mut self, index: usize, sample_rate: u32) {
+        self.close(index);
+
+        match self.start(index, sample_rate) {
+            Ok(capture) => {
+                self.captures.insert(index, capture);
+            }
+            Err(e) => {
+                tracing::error!("Couldn't open microphone {index}: {e}");
+                self.failed.insert(index);
+            }
+        }
+    }
+
+    fn close(&mut self, index: usize) {
+        self.captures.remove(&index);
+        self.failed.remove(&index);
+    }
+
+    fn status(&self, index: usize) -> CaptureStatus {
+        if self.captures.contains_key(&index) {
+            CaptureStatus::Active
+        } else if self.failed.contains(&index) {
+            CaptureStatus::Denied
+        } else {
+            CaptureStatus::Closed
+        }
+    }
+
+    fn take_samples(&mut self, index: usize) -> Vec<f32> {
+        let Some(capture) = self.captures.get_mut(&index) else {
+            return vec![];
+        };
+        let samples = std::mem::take(&mut *capture.samples.lock().expect("Non-poisoned lock"));
+        capture.resampler.process(&samples)
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: Arc<Mutex<Vec<f32>>>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = usize::from(config.channels.max(1));
+    device.build_input_stream(
+        config,
+        move |data: &[T], _| {
+            let mut samples = samples.lock().expect("Non-poisoned lock");
+            // Mix every frame down to mono.
+            samples.extend(data.chunks_exact(channels).map(|frame| {
+                frame.iter().map(|s| s.to_sample::<f32>()).sum::<f32>() / channels as f32
+            }));
+            if samples.len() > MAX_BUFFERED_SAMPLES {
+                let excess = samples.len() - MAX_BUFFERED_SAMPLES;
+                samples.drain(..excess);
+            }
+        },
+        |err| tracing::error!("Microphone stream error: {err}"),
+        None,
+    )
+}
@@ -0,0 +1,254 @@
+//! Sending the pages of a `PrintJob` to a printer, through the print dialog of the system.
+
+use ruffle_core::backend::ui::PrintPage;
+
+/// Whether this platform has a print dialog that pages can be sent to.
+pub const CAN_PRINT: bool = cfg!(any(windows, target_os = "macos", target_os = "linux"));
+
+/// Shows the print dialog of the system, and prints the pages if the user confirms it.
+pub fn print(pages: Vec<PrintPage>) {
+    #[cfg(windows)]
+    if let Err(e) = windows::print(&pages) {
+        tracing::error!("Couldn't print: {e}");
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Err(e) = macos::print(&pages) {
+        tracing::error!("Couldn't print: {e}");
+    }
+
+    #[cfg(target_os = "linux")]
+    linux::print(&pages);
+
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+    let _ = pages;
+}
+
+#[cfg(windows)]
+mod windows {
+    use anyhow::{anyhow, Error};
+    use ruffle_core::backend::ui::PrintPage;
+    use ruffle_core::printing;
+    use std::ptr::null_mut;
+    use winapi::um::commdlg::{
+        PrintDlgW, PD_NOPAGENUMS, PD_NOSELECTION, PD_RETURNDC, PD_USEDEVMODECOPIESANDCOLLATE,
+        PRINTDLGW,
+    };
+    use winapi::um::winbase::GlobalFree;
+    use winapi::um::wingdi::{
+        AbortDoc, DeleteDC, EndDoc, EndPage, GetDeviceCaps, StartDocW, StartPage, StretchDIBits,
+        BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DOCINFOW, LOGPIXELSX, LOGPIXELSY,
+        PHYSICALOFFSETX, PHYSICALOFFSETY, SRCCOPY,
+    };
+
+    /// The distance from the edge of the paper to the printable area of the pages, in points.
+    const MARGIN: i32 = ((printing::PAPER_WIDTH - printing::PAGE_WIDTH) / 2) as i32;
+
+    pub fn print(pages: &[PrintPage]) -> Result<(), Error> {
+        let mut dialog = PRINTDLGW {
+            lStructSize: std::mem::size_of::<PRINTDLGW>() as u32,
+            Flags: PD_RETURNDC | PD_NOPAGENUMS | PD_NOSELECTION | PD_USEDEVMODECOPIESANDCOLLATE,
+            ..unsafe { std::mem::zeroed() }
+        };
+
+        // SAFETY: `dialog` is initialized as the dialog expects, and everything it returns is
+        // freed below.
+        unsafe {
+            let confirmed = PrintDlgW(&mut dialog) != 0;
+            if !dialog.hDevMode.is_null() {
+                GlobalFree(dialog.hDevMode);
+            }
+            if !dialog.hDevNames.is_null() {
+                GlobalFree(dialog.hDevNames);
+            }
+            if !confirmed || dialog.hDC.is_null() {
+                // The dialog was cancelled.
+                return Ok(());
+            }
+
+            let result = print_document(dialog.hDC, pages);
+            DeleteDC(dialog.hDC);
+            result
+        }
+    }
+
+    unsafe fn print_document(
+        dc: winapi::shared::windef::HDC,
+        pages: &[PrintPage],
+    ) -> Result<(), Error> {
+        let name: Vec<u16> = "Ruffle".encode_utf16().chain(Some(0)).collect();
+        let document = DOCINFOW {
+            cbSize: std::mem::size_of::<DOCINFOW>() as i32,
+            lpszDocName: name.as_ptr(),
+            lpszOutput: null_mut(),
+            lpszDatatype: null_mut(),
+            fwType: 0,
+        };
+        if StartDocW(dc, &document) <= 0 {
+            return Err(anyhow!("The printer refused the document"));
+        }
+
+        // The device context starts at the edge of what the printer can reach, not the paper.
+        let dpi_x = GetDeviceCaps(dc, LOGPIXELSX);
+        let dpi_y = GetDeviceCaps(dc, LOGPIXELSY);
+        let x = MARGIN * dpi_x / 72 - GetDeviceCaps(dc, PHYSICALOFFSETX);
+        let y = MARGIN * dpi_y / 72 - GetDeviceCaps(dc, PHYSICALOFFSETY);
+
+        for page in pages {
+            if StartPage(dc) <= 0 {
+                AbortDoc(dc);
+                return Err(anyhow!("The printer refused a page"));
+            }
+
+            let bgra: Vec<u8> = page
+                .rgba
+                .chunks_exact(4)
+                .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], 0])
+                .collect();
+            let info = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: page.width as i32,
+                    // Rows go from top to bottom.
+                    biHeight: -(page.height as i32),
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB,
+                    ..std::mem::zeroed()
+                },
+                bmiColors: std::mem::zeroed(),
+            };
+            let width = (page.width / page.scale) as i32 * dpi_x / 72;
+            let height = (page.height / page.scale) as i32 * dpi_y / 72;
+            StretchDIBits(
+                dc,
+                x,
+                y,
+                width,
+                height,
+                0,
+                0,
+                page.width as i32,
+                page.height as i32,
+                bgra.as_ptr().cast(),
+                &info,
+                DIB_RGB_COLORS,
+                SRCCOPY,
+            );
+
+            if EndPage(dc) <= 0 {
+                AbortDoc(dc);
+                return Err(anyhow!("The printer refused a page"));
+            }
+        }
+
+        EndDoc(dc);
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use anyhow::{anyhow, Error};
+    use objc2::rc::{Allocated, Retained};
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send_id};
+    use objc2_app_kit::{NSPrintInfo, NSPrintOperation};
+    use objc2_foundation::NSData;
+    use ruffle_core::backend::ui::PrintPage;
+    use ruffle_core::printing;
+
+    // `PDFDocument` comes from PDFKit, which isn't linked otherwise.
+    #[link(name = "PDFKit", kind = "framework")]
+    extern "C" {}
+
+    /// `kPDFPrintPageScaleNone`: the pages are printed at the size they were laid out at.
+    const PDF_PRINT_PAGE_SCALE_NONE: isize = 0;
+
+    pub fn print(pages: &[PrintPage]) -> Result<(), Error> {
+        let data = NSData::from_vec(printing::to_pdf(pages));
+
+        // SAFETY: These are the documented signatures of the `PDFDocument` methods.
+        unsafe {
+            let document: Allocated<AnyObject> = msg_send_id![class!(PDFDocument), alloc];
+            let document: Option<Retained<AnyObject>> =
+                msg_send_id![document, initWithData: &*data];
+            let document = document.ok_or_else(|| anyhow!("PDFKit couldn't read the pages"))?;
+
+            let print_info = NSPrintInfo::sharedPrintInfo();
+            let operation: Option<Retained<NSPrintOperation>> = msg_send_id![
+                &document,
+                printOperationForPrintInfo: &*print_info,
+                scalingMode: PDF_PRINT_PAGE_SCALE_NONE,
+                autoRotate: false
+            ];
+            let operation =
+                operation.ok_or_else(|| anyhow!("PDFKit couldn't create a print operation"))?;
+
+            // This shows the print panel, and blocks until it's closed.
+            operation.runOperation();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use anyhow::Error;
+    use ashpd::desktop::print::{Orientation, PageSetup, PrintProxy, Settings};
+    use ashpd::WindowIdentifier;
+    use ruffle_core::backend::ui::PrintPage;
+    use ruffle_core::printing;
+    use std::io::{Seek, Write};
+    use std::os::fd::AsFd;
+
+    /// The size of US Letter paper, in millimeters.
+    const PAPER_WIDTH_MM: f64 = printing::PAPER_WIDTH as f64 * 25.4 / 72.0;
+    const PAPER_HEIGHT_MM: f64 = printing::PAPER_HEIGHT as f64 * 25.4 / 72.0;
+
+    pub fn print(pages: &[PrintPage]) {
+        // The print dialog is shown by the desktop environment, through the print portal.
+        let pdf = printing::to_pdf(pages);
+        tokio::spawn(async move {
+            if let Err(e) = print_with_portal(pdf).await {
+                tracing::error!("Couldn't print: {e}");
+            }
+        });
+    }
+
+    async fn print_with_portal(pdf: Vec<u8>) -> Result<(), Error> {
+        let proxy = PrintProxy::new().await?;
+        let identifier = WindowIdentifier::default();
+        let page_setup = PageSetup::default()
+            .width(PAPER_WIDTH_MM)
+            .height(PAPER_HEIGHT_MM)
+            .orientation(Orientation::Portrait);
+        let prepared = proxy
+            .prepare_print(
+                &identifier,
+                "Ruffle",
+                Settings::default(),
+                page_setup,
+                None,
+                true,
+            )
+            .await?
+            .response()?;
+
+        // The portal reads the document from a file, which is deleted once it's closed.
+        let mut file = tempfile::tempfile()?;
+        file.write_all(&pdf)?;
+        file.rewind()?;
+        proxy
+            .print(
+                &identifier,
+                "Ruffle",
+                &file.as_fd(),
+                Some(prepared.token),
+                true,
+            )
+            .await?
+            .response()?;
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
+use crate::backends::print;
 use crate::cli::OpenUrlMode;
 use crate::custom_event::RuffleEvent;
 use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
-use crate::gui::{DialogDescriptor, FilePicker, LocalizableText};
+use crate::gui::{DialogDescriptor, FilePicker, LocalizableText, MENU_HEIGHT};
+use crate::native_windows::SharedNativeWindows;
 use crate::preferences::GlobalPreferences;
 use anyhow::Error;
 use chrono::{DateTime, Utc};
@@ -11,16 +13,21 @@ use rfd::{
     AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
 };
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    ClipboardImage, CursorImage, CustomCursorId, DialogLoaderError, DialogResultFuture,
+    FileDialogResult, FileFilter, FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor,
+    NativeWindowDescriptor, NativeWindowId, PrintPage, RootMovieLoadPhase, UiBackend,
 };
+use ruffle_core::swf::{Rectangle, Twips};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
-use tracing::error;
+use tracing::{error, warn};
 use url::Url;
-use winit::event_loop::EventLoopProxy;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::raw_window_handle::HasDisplayHandle;
-use winit::window::{Fullscreen, Window};
+use winit::window::{CursorGrabMode, CustomCursor, CustomCursorSource, Fullscreen, Window};
 
 pub struct DesktopFileDialogResult {
     handle: Option<FileHandle>,
@@ -120,11 +127,34 @@ pub struct DesktopUiBackend {
     window: Arc<Window>,
     event_loop: EventLoopProxy<RuffleEvent>,
     cursor_visible: bool,
+
+    /// Whether the movie locked the mouse with `Stage.mouseLock`.
+    mouse_locked: bool,
     clipboard: Clipboard,
+
+    /// Images go through arboard directly, as egui's clipboard only handles text.
+    image_clipboard: Option<arboard::Clipboard>,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
+
+    /// The frames of cursors registered by the movie.
+    custom_cursors: HashMap<CustomCursorId, Vec<CustomCursorFrame>>,
     font_database: Rc<fontdb::Database>,
     file_picker: FilePicker,
+    native_windows: SharedNativeWindows,
+}
+
+/// A frame of a custom cursor, which can only be created by winit
+/// once we have access to the event loop.
+enum CustomCursorFrame {
+    Pending(CustomCursorSource),
+    Created(CustomCursor),
+}
+
+/// The cursor that the player would like to show.
+pub enum DesktopCursor {
+    Icon(egui::CursorIcon),
+    Custom(CustomCursor),
 }
 
 impl DesktopUiBackend {
@@ -134,6 +164,7 @@ impl DesktopUiBackend {
         font_database: Rc<fontdb::Database>,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
+        native_windows: SharedNativeWindows,
     ) -> Result<Self, Error> {
         // The window handle is only relevant to linux/wayland
         // If it fails it'll fallback to x11 or wlr-data-control
@@ -144,28 +175,60 @@ impl DesktopUiBackend {
                 .ok()
                 .map(|handle| handle.as_raw()),
         );
+        let image_clipboard = arboard::Clipboard::new()
+            .inspect_err(|e| warn!("Couldn't open the clipboard for images: {e}"))
+            .ok();
         Ok(Self {
             window,
             event_loop,
             cursor_visible: true,
+            mouse_locked: false,
             clipboard,
+            image_clipboard,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
+            custom_cursors: HashMap::new(),
             font_database,
             file_picker,
+            native_windows,
         })
     }
 
-    pub fn cursor(&self) -> egui::CursorIcon {
-        if self.cursor_visible {
-            match self.preferred_cursor {
-                MouseCursor::Arrow => egui::CursorIcon::Default,
-                MouseCursor::Hand => egui::CursorIcon::PointingHand,
-                MouseCursor::IBeam => egui::CursorIcon::Text,
-                MouseCursor::Grab => egui::CursorIcon::Grab,
+    pub fn is_mouse_locked(&self) -> bool {
+        self.mouse_locked
+    }
+
+    pub fn cursor(&self) -> DesktopCursor {
+        if !self.cursor_visible || self.mouse_locked {
+            return DesktopCursor::Icon(egui::CursorIcon::None);
+        }
+        match self.preferred_cursor {
+            MouseCursor::Arrow => DesktopCursor::Icon(egui::CursorIcon::Default),
+            MouseCursor::Hand => DesktopCursor::Icon(egui::CursorIcon::PointingHand),
+            MouseCursor::IBeam => DesktopCursor::Icon(egui::CursorIcon::Text),
+            MouseCursor::Grab => DesktopCursor::Icon(egui::CursorIcon::Grab),
+            MouseCursor::Custom { id, frame } => {
+                match self
+                    .custom_cursors
+                    .get(&id)
+                    .and_then(|frames| frames.get(frame as usize))
+                {
+                    Some(CustomCursorFrame::Created(cursor)) => {
+                        DesktopCursor::Custom(cursor.clone())
+                    }
+                    _ => DesktopCursor::Icon(egui::CursorIcon::Default),
+                }
+            }
+        }
+    }
+
+    /// Creates the winit cursors for any custom cursor frames registered since the last call.
+    pub fn create_custom_cursors(&mut self, event_loop: &ActiveEventLoop) {
+        for frame in self.custom_cursors.values_mut().flatten() {
+            if let CustomCursorFrame::Pending(source) = frame {
+                *frame =
+                    CustomCursorFrame::Created(event_loop.create_custom_cursor(source.clone()));
             }
-        } else {
-            egui::CursorIcon::None
         }
     }
 }
@@ -183,6 +246,45 @@ impl UiBackend for DesktopUiBackend {
         self.preferred_cursor = cursor;
     }
 
+    fn set_mouse_lock(&mut self, locked: bool) {
+        let result = if locked {
+            // Not every platform can lock the cursor in place, but confining it is close enough,
+            // as only its movement is used.
+            self.window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Confined))
+        } else {
+            self.window.set_cursor_grab(CursorGrabMode::None)
+        };
+        if let Err(e) = result {
+            warn!("Couldn't change the mouse lock: {e}");
+        }
+        self.mouse_locked = locked;
+    }
+
+    fn register_custom_cursor(&mut self, id: CustomCursorId, frames: Vec<CursorImage>) {
+        let frames = frames
+            .into_iter()
+            .filter_map(|image| {
+                CustomCursor::from_rgba(
+                    image.rgba,
+                    image.width.try_into().ok()?,
+                    image.height.try_into().ok()?,
+                    image.hotspot_x.try_into().ok()?,
+                    image.hotspot_y.try_into().ok()?,
+                )
+                .inspect_err(|e| warn!("Couldn't create custom cursor: {e}"))
+                .ok()
+                .map(CustomCursorFrame::Pending)
+            })
+            .collect();
+        self.custom_cursors.insert(id, frames);
+    }
+
+    fn unregister_custom_cursor(&mut self, id: CustomCursorId) {
+        self.custom_cursors.remove(&id);
+    }
+
     fn clipboard_content(&mut self) -> String {
         self.clipboard.get().unwrap_or_default()
     }
@@ -191,6 +293,54 @@ impl UiBackend for DesktopUiBackend {
         self.clipboard.set(content);
     }
 
+    fn clipboard_image(&mut self) -> Option<ClipboardImage> {
+        let image = self.image_clipboard.as_mut()?.get_image().ok()?;
+        Some(ClipboardImage {
+            width: image.width as u32,
+            height: image.height as u32,
+            rgba: image.bytes.into_owned(),
+        })
+    }
+
+    fn set_clipboard_image(&mut self, image: ClipboardImage) {
+        let Some(clipboard) = &mut self.image_clipboard else {
+            return;
+        };
+        let image = arboard::ImageData {
+            width: image.width as usize,
+            height: image.height as usize,
+            bytes: Cow::Owned(image.rgba),
+        };
+        if let Err(e) = clipboard.set_image(image) {
+            error!("Couldn't set clipboard image: {e}");
+        }
+    }
+
+    fn clear_clipboard_content(&mut self) {
+        // Setting either format replaces everything on the system clipboard,
+        // so the image has to be put back.
+        let image = self.clipboard_image();
+        self.set_clipboard_content(String::new());
+        if let Some(image) = image {
+            self.set_clipboard_image(image);
+        }
+    }
+
+    fn clear_clipboard_image(&mut self) {
+        if self.clipboard_image().is_none() {
+            return;
+        }
+        let content = self.clipboard_content();
+        if let Some(clipboard) = &mut self.image_clipboard {
+            if let Err(e) = clipboard.clear() {
+                error!("Couldn't clear clipboard: {e}");
+            }
+        }
+        if !content.is_empty() {
+            self.set_clipboard_content(content);
+        }
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -213,6 +363,23 @@ impl UiBackend for DesktopUiBackend {
             )));
     }
 
+    fn root_movie_load_progress(
+        &self,
+        phase: RootMovieLoadPhase,
+        bytes_loaded: u64,
+        bytes_total: Option<u64>,
+    ) {
+        let _ = self.event_loop.send_event(RuffleEvent::LoadProgress {
+            phase,
+            bytes_loaded,
+            bytes_total,
+        });
+    }
+
+    fn root_movie_loaded(&self) {
+        let _ = self.event_loop.send_event(RuffleEvent::Loaded);
+    }
+
     fn message(&self, message: &str) {
         let _ = self
             .event_loop
@@ -322,6 +489,26 @@ impl UiBackend for DesktopUiBackend {
 
     fn close_virtual_keyboard(&self) {}
 
+    fn set_ime_allowed(&mut self, allowed: bool) {
+        self.window.set_ime_allowed(allowed);
+    }
+
+    fn set_ime_cursor_area(&mut self, area: Rectangle<Twips>) {
+        // The viewport is below the menu bar, unless it's hidden.
+        let height_offset = if self.window.fullscreen().is_some() || self.preferences.cli.no_gui {
+            0.0
+        } else {
+            MENU_HEIGHT as f64 * self.window.scale_factor()
+        };
+        self.window.set_ime_cursor_area(
+            PhysicalPosition::new(
+                area.x_min.to_pixels(),
+                area.y_min.to_pixels() + height_offset,
+            ),
+            PhysicalSize::new(area.width().to_pixels(), area.height().to_pixels()),
+        );
+    }
+
     fn language(&self) -> LanguageIdentifier {
         self.preferences.language().clone()
     }
@@ -373,4 +560,45 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn open_native_window(
+        &mut self,
+        id: NativeWindowId,
+        descriptor: &NativeWindowDescriptor,
+    ) -> bool {
+        self.native_windows.borrow_mut().open(id, descriptor)
+    }
+
+    fn update_native_window(&mut self, id: NativeWindowId, descriptor: &NativeWindowDescriptor) {
+        self.native_windows.borrow_mut().update(id, descriptor);
+    }
+
+    fn focus_native_window(&mut self, id: NativeWindowId) {
+        self.native_windows.borrow_mut().focus(id);
+    }
+
+    fn present_native_window(
+        &mut self,
+        id: NativeWindowId,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        row_stride: u32,
+    ) {
+        self.native_windows
+            .borrow_mut()
+            .present(id, width, height, rgba, row_stride);
+    }
+
+    fn close_native_window(&mut self, id: NativeWindowId) {
+        self.native_windows.borrow_mut().close(id);
+    }
+
+    fn can_print(&self) -> bool {
+        print::CAN_PRINT
+    }
+
+    fn print(&mut self, pages: Vec<PrintPage>) {
+        print::print(pages);
+    }
 }
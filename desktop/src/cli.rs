@@ -3,7 +3,8 @@ use crate::RUFFLE_VERSION;
 use anyhow::{anyhow, Error};
 use clap::{Parser, ValueEnum};
 use ruffle_core::backend::navigator::SocketMode;
-use ruffle_core::config::Letterbox;
+use ruffle_core::bitmap::readback::BitmapReadbackMode;
+use ruffle_core::config::{Letterbox, PolicyFileMode};
 use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
@@ -143,10 +144,62 @@ pub struct Opt {
     #[clap(long)]
     pub proxy: Option<Url>,
 
+    /// Load Adobe's signed runtime shared libraries, such as the Text Layout Framework
+    /// used by TLF text, from this URL instead of Adobe's servers.
+    ///
+    /// The mirror should be laid out like `https://fpdownload.adobe.com/pub/swz/`.
+    #[clap(long)]
+    pub rsl_mirror: Option<Url>,
+
+    /// Let a debugger attach to the movie on this TCP port, on the local machine only.
+    ///
+    /// Breakpoints and stepping need a movie compiled with debugging information.
+    #[clap(long)]
+    pub debugger_port: Option<u16>,
+
+    /// Record the movie's input into a replay, saved to this file when the movie is closed.
+    ///
+    /// Random numbers and the time are made reproducible while recording,
+    /// so that `--replay` can play the movie back exactly.
+    #[clap(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record_replay: Option<std::path::PathBuf>,
+
+    /// Play back a replay recorded with `--record-replay`, ignoring input until it ends.
+    #[clap(long, value_name = "FILE")]
+    pub replay: Option<std::path::PathBuf>,
+
+    /// Load a save state, and go on playing from there.
+    ///
+    /// Save states are replays made with `--record-replay`, not snapshots: the movie is played
+    /// from the start up to where it was saved, as fast as possible. Input is recorded again
+    /// once the save state has caught up, so with `--record-replay` the movie is saved again
+    /// on close.
+    #[clap(long, value_name = "FILE", conflicts_with = "replay")]
+    pub load_state: Option<std::path::PathBuf>,
+
+    /// Maximum number of milliseconds a single garbage collection may pause the movie for.
+    ///
+    /// The garbage collector's work is split into slices that are expected to fit in this budget.
+    #[clap(long, value_parser(parse_duration_millis))]
+    pub gc_budget: Option<Duration>,
+
+    /// How `BitmapData` pixel reads wait for the GPU.
+    ///
+    /// "throttled" lets movies that read the same bitmap every frame, such as color pickers,
+    /// see pixels up to a frame old instead of stalling on every read.
+    #[clap(long, default_value = "strict")]
+    pub bitmap_readback: BitmapReadbackMode,
+
     /// Add an endpoint (`[host]:[port]`) to the socket whitelist.
     #[clap(long = "socket-allow", number_of_values = 1, action = clap::ArgAction::Append)]
     pub socket_allow: Vec<String>,
 
+    /// Allow movies to use this standalone player fscommand (`fullscreen`, `quit` or `allowscale`).
+    ///
+    /// Can be given multiple times. If it is never given, all of them are allowed.
+    #[clap(long = "fscommand-allow", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub fscommand_allow: Vec<String>,
+
     /// Define how to deal with TCP Socket connections.
     #[clap(long = "tcp-connections")]
     pub tcp_connections: Option<SocketMode>,
@@ -187,6 +240,26 @@ pub struct Opt {
     #[clap(long)]
     pub player_runtime: Option<PlayerRuntime>,
 
+    /// Spoofs the version string reported by `Capabilities.version`, such as "WIN 10,0,0,0".
+    #[clap(long)]
+    pub spoof_version: Option<String>,
+
+    /// Spoofs the operating system reported by `Capabilities.os`, such as "Windows XP".
+    #[clap(long)]
+    pub spoof_os: Option<String>,
+
+    /// Spoofs the player type reported by `Capabilities.playerType`, such as "PlugIn".
+    #[clap(long)]
+    pub spoof_player_type: Option<String>,
+
+    /// Spoofs the language reported by `Capabilities.language`, such as "en".
+    #[clap(long)]
+    pub spoof_language: Option<String>,
+
+    /// Spoofs the string of all capabilities reported by `Capabilities.serverString`.
+    #[clap(long)]
+    pub spoof_server_string: Option<String>,
+
     /// Set and lock the player's frame rate, overriding the movie's frame rate.
     #[clap(long)]
     pub frame_rate: Option<f64>,
@@ -195,6 +268,11 @@ pub struct Opt {
     #[clap(long)]
     pub open_url_mode: Option<OpenUrlMode>,
 
+    /// Whether cross-domain policy files are checked before the movie reads data
+    /// from other domains or connects sockets to them.
+    #[clap(long)]
+    pub policy_file_mode: Option<PolicyFileMode>,
+
     /// How to handle non-interactive filesystem access.
     #[clap(long, default_value = "ask")]
     pub filesystem_access_mode: FilesystemAccessMode,
@@ -238,11 +316,29 @@ pub struct Opt {
     )]
     pub gamepad_button: Vec<(GamepadButton, KeyCode)>,
 
+    /// Makes a keyboard key act as another key, for games whose controls are
+    /// awkward on some keyboards. For example, the arrow keys can be played
+    /// with WASD using --key-remap w=up --key-remap a=left etc.
+    ///
+    /// The key names are the same as for --gamepad-button.
+    #[clap(
+        long,
+        value_parser(parse_key_remap),
+        verbatim_doc_comment,
+        value_name = "KEY NAME>=<KEY NAME"
+    )]
+    pub key_remap: Vec<(KeyCode, KeyCode)>,
+
     /// Disable AVM2 optimizer.
     /// Note that some early opcode conversions
     /// (like inlining constant pool entries) can't be disabled.
     #[clap(long)]
     pub no_avm2_optimizer: bool,
+
+    /// Disable compiling frequently called AVM2 methods to native code.
+    /// This only has an effect in builds with the `jit` feature.
+    #[clap(long)]
+    pub no_jit: bool,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
@@ -253,46 +349,61 @@ fn parse_duration_seconds(value: &str) -> Result<Duration, Error> {
     Ok(Duration::from_secs_f64(value.parse()?))
 }
 
+fn parse_duration_millis(value: &str) -> Result<Duration, Error> {
+    Ok(Duration::from_secs_f64(value.parse::<f64>()? / 1000.0))
+}
+
 fn parse_align(value: &str) -> Result<StageAlign, Error> {
     value
         .parse()
         .map_err(|_| anyhow::anyhow!("Invalid stage alignment"))
 }
 
+fn to_aliases<T: ValueEnum>(variants: &[T]) -> String {
+    let aliases: Vec<String> = variants
+        .iter()
+        .map(|variant| {
+            variant
+                .to_possible_value()
+                .expect("Must have a PossibleValue")
+                .get_name_and_aliases()
+                .next()
+                .expect("Must have one alias")
+                .to_owned()
+        })
+        .collect();
+    aliases.join(", ")
+}
+
+fn parse_key_name(name: &str) -> Result<KeyCode, Error> {
+    let key_code = NamedKeyCode::from_str(name, true).map_err(|err| {
+        anyhow!(
+            "Could not parse <key name>: {err}\n  The possible values are: {}",
+            to_aliases(NamedKeyCode::value_variants())
+        )
+    })?;
+    Ok(KeyCode::from_code(key_code as u32))
+}
+
 fn parse_gamepad_button(mapping: &str) -> Result<(GamepadButton, KeyCode), Error> {
     let pos = mapping.find('=').ok_or_else(|| {
         anyhow!("invalid <gamepad button>=<key name>: no `=` found in `{mapping}`")
     })?;
 
-    fn to_aliases<T: ValueEnum>(variants: &[T]) -> String {
-        let aliases: Vec<String> = variants
-            .iter()
-            .map(|variant| {
-                variant
-                    .to_possible_value()
-                    .expect("Must have a PossibleValue")
-                    .get_name_and_aliases()
-                    .next()
-                    .expect("Must have one alias")
-                    .to_owned()
-            })
-            .collect();
-        aliases.join(", ")
-    }
-
     let button = GamepadButton::from_str(&mapping[..pos], true).map_err(|err| {
         anyhow!(
             "Could not parse <gamepad button>: {err}\n  The possible values are: {}",
             to_aliases(GamepadButton::value_variants())
         )
     })?;
-    let key_code = NamedKeyCode::from_str(&mapping[pos + 1..], true).map_err(|err| {
-        anyhow!(
-            "Could not parse <key name>: {err}\n  The possible values are: {}",
-            to_aliases(NamedKeyCode::value_variants())
-        )
-    })?;
-    Ok((button, KeyCode::from_code(key_code as u32)))
+    Ok((button, parse_key_name(&mapping[pos + 1..])?))
+}
+
+fn parse_key_remap(mapping: &str) -> Result<(KeyCode, KeyCode), Error> {
+    let (from, to) = mapping
+        .split_once('=')
+        .ok_or_else(|| anyhow!("invalid <key name>=<key name>: no `=` found in `{mapping}`"))?;
+    Ok((parse_key_name(from)?, parse_key_name(to)?))
 }
 
 impl Opt {
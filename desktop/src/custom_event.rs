@@ -1,6 +1,8 @@
 //! Custom event type for desktop ruffle
 
 use crate::{gui::DialogDescriptor, player::LaunchOptions};
+use ruffle_core::backend::ui::RootMovieLoadPhase;
+use ruffle_core::StageScaleMode;
 
 /// User-defined events.
 pub enum RuffleEvent {
@@ -28,9 +30,25 @@ pub enum RuffleEvent {
     /// The user requested to exit Ruffle.
     ExitRequested,
 
+    /// The movie requested a different scale mode, through `fscommand("allowscale")`.
+    SetScaleMode(StageScaleMode),
+
     /// The user selected an item in the right-click context menu.
     ContextMenuItemClicked(usize),
 
     /// The movie wants to open a dialog.
     OpenDialog(DialogDescriptor),
+
+    /// More of the root movie has been downloaded or preloaded.
+    LoadProgress {
+        phase: RootMovieLoadPhase,
+        bytes_loaded: u64,
+        bytes_total: Option<u64>,
+    },
+
+    /// The root movie has been completely loaded.
+    Loaded,
+
+    /// The movie opened additional `NativeWindow`s, which need to be created.
+    NativeWindowsChanged,
 }
@@ -232,5 +232,8 @@ impl RuffleGui {
             .recreate_open_dialog(opt, Some(movie_url), self.event_loop.clone());
 
         player.set_volume(self.dialogs.volume_controls.get_volume());
+
+        self.dialogs.trace_console.clear();
+        player.add_trace_output(self.dialogs.trace_console.output());
     }
 }
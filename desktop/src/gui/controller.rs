@@ -1,4 +1,4 @@
-use crate::backends::DesktopUiBackend;
+use crate::backends::{DesktopCursor, DesktopUiBackend};
 use crate::custom_event::RuffleEvent;
 use crate::gui::movie::{MovieView, MovieViewRenderer};
 use crate::gui::theme::ThemeController;
@@ -43,6 +43,8 @@ pub struct GuiController {
     /// If this is set, we should not render the main menu.
     no_gui: bool,
     theme_controller: ThemeController,
+    /// Whether the window is showing a custom cursor of the movie, which egui doesn't know about.
+    showing_custom_cursor: bool,
 }
 
 impl GuiController {
@@ -148,6 +150,7 @@ impl GuiController {
             size,
             no_gui,
             theme_controller,
+            showing_custom_cursor: false,
         })
     }
 
@@ -305,17 +308,33 @@ impl GuiController {
             .repaint_delay;
 
         // If we're not in a UI, tell egui which cursor we prefer to use instead
+        let mut custom_cursor = None;
         if !self.egui_winit.egui_ctx().wants_pointer_input() {
             if let Some(player) = player.as_deref() {
-                full_output.platform_output.cursor_icon = player
+                match player
                     .ui()
                     .downcast_ref::<DesktopUiBackend>()
                     .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"))
-                    .cursor();
+                    .cursor()
+                {
+                    DesktopCursor::Icon(icon) => full_output.platform_output.cursor_icon = icon,
+                    DesktopCursor::Custom(cursor) => {
+                        full_output.platform_output.cursor_icon = egui::CursorIcon::Default;
+                        custom_cursor = Some(cursor);
+                    }
+                }
             }
         }
+        if custom_cursor.is_none() && self.showing_custom_cursor {
+            // egui thinks the default cursor is still shown, so put it back before egui changes it.
+            self.window.set_cursor(winit::window::CursorIcon::Default);
+        }
         self.egui_winit
             .handle_platform_output(&self.window, full_output.platform_output);
+        self.showing_custom_cursor = custom_cursor.is_some();
+        if let Some(cursor) = custom_cursor {
+            self.window.set_cursor(cursor);
+        }
 
         let clipped_primitives = self
             .egui_winit
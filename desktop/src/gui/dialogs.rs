@@ -6,6 +6,7 @@ pub mod network_access_dialog;
 mod open_dialog;
 mod open_url_dialog;
 mod preferences_dialog;
+mod trace_console;
 mod volume_controls;
 
 use crate::custom_event::RuffleEvent;
@@ -20,6 +21,7 @@ use open_url_dialog::OpenUrlDialog;
 use preferences_dialog::PreferencesDialog;
 use ruffle_core::Player;
 use std::{collections::VecDeque, sync::Weak};
+use trace_console::TraceConsole;
 use unic_langid::LanguageIdentifier;
 use url::Url;
 use volume_controls::VolumeControls;
@@ -53,6 +55,9 @@ pub struct Dialogs {
 
     is_about_visible: bool,
 
+    pub trace_console: TraceConsole,
+    is_trace_console_visible: bool,
+
     preferences: GlobalPreferences,
 }
 
@@ -96,6 +101,9 @@ impl Dialogs {
 
             is_about_visible: false,
 
+            trace_console: TraceConsole::default(),
+            is_trace_console_visible: false,
+
             event_loop,
             picker,
             preferences,
@@ -158,6 +166,14 @@ impl Dialogs {
         self.is_about_visible = true;
     }
 
+    pub fn toggle_trace_console(&mut self) {
+        self.is_trace_console_visible = !self.is_trace_console_visible;
+    }
+
+    pub fn is_trace_console_visible(&self) -> bool {
+        self.is_trace_console_visible
+    }
+
     pub fn open_dialog(&mut self, event: DialogDescriptor) {
         match event {
             DialogDescriptor::OpenUrl(url) => {
@@ -181,6 +197,7 @@ impl Dialogs {
         egui_ctx: &egui::Context,
         player: Option<&mut Player>,
     ) {
+        self.show_trace_console(locale, egui_ctx);
         self.show_open_dialog(locale, egui_ctx);
         self.show_preferences_dialog(locale, egui_ctx);
         self.show_bookmarks_dialog(locale, egui_ctx);
@@ -247,6 +264,13 @@ impl Dialogs {
         }
     }
 
+    fn show_trace_console(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        if self.is_trace_console_visible {
+            let keep_open = self.trace_console.show(locale, egui_ctx);
+            self.is_trace_console_visible = keep_open;
+        }
+    }
+
     fn show_about_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
         if self.is_about_visible {
             let keep_open = about_dialog::show_about_dialog(locale, egui_ctx);
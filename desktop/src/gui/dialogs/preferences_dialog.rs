@@ -31,6 +31,9 @@ pub struct PreferencesDialog {
     available_output_devices: Vec<String>,
     output_device_changed: bool,
 
+    adaptive_quality: bool,
+    adaptive_quality_changed: bool,
+
     enable_openh264: bool,
     enable_openh264_changed: bool,
     openh264_license_visible: bool,
@@ -88,6 +91,9 @@ impl PreferencesDialog {
             available_output_devices,
             output_device_changed: false,
 
+            adaptive_quality: preferences.adaptive_quality(),
+            adaptive_quality_changed: false,
+
             enable_openh264: preferences.openh264_enabled(),
             enable_openh264_changed: false,
             openh264_license_visible: false,
@@ -177,6 +183,7 @@ impl PreferencesDialog {
         self.graphics_backend != self.preferences.graphics_backends()
             || self.power_preference != self.preferences.graphics_power_preference()
             || self.output_device != self.preferences.output_device_name()
+            || self.adaptive_quality != self.preferences.adaptive_quality()
             || self.enable_openh264 != self.preferences.openh264_enabled()
             || self.log_filename_pattern != self.preferences.log_filename_pattern()
             || self.storage_backend != self.preferences.storage_backend()
@@ -262,6 +269,15 @@ impl PreferencesDialog {
             }
         }
         ui.end_row();
+
+        ui.label(text(locale, "adaptive-quality"));
+        let previous = self.adaptive_quality;
+        ui.add(Checkbox::without_text(&mut self.adaptive_quality))
+            .on_hover_text(text(locale, "adaptive-quality-tooltip"));
+        if self.adaptive_quality != previous {
+            self.adaptive_quality_changed = true;
+        }
+        ui.end_row();
     }
 
     fn show_language_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
@@ -545,6 +561,9 @@ impl PreferencesDialog {
                 preferences.set_output_device(self.output_device.clone());
                 // [NA] TODO: Inform the running player that the device changed
             }
+            if self.adaptive_quality_changed {
+                preferences.set_adaptive_quality(self.adaptive_quality);
+            }
             if self.enable_openh264_changed {
                 preferences.set_enable_openh264(self.enable_openh264);
             }
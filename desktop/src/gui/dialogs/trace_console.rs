@@ -0,0 +1,68 @@
+use crate::gui::text;
+use egui::{ScrollArea, TextStyle, TopBottomPanel};
+use ruffle_core::backend::log::TraceOutput;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use unic_langid::LanguageIdentifier;
+
+/// How many lines of output are kept, dropping the oldest ones first.
+const MAX_LINES: usize = 10_000;
+
+/// A panel docked to the bottom of the window, showing the `trace()` output of the movie.
+#[derive(Default)]
+pub struct TraceConsole {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl TraceConsole {
+    /// Creates a destination for the `trace()` output of a player, to be shown in this console.
+    pub fn output(&self) -> impl TraceOutput {
+        let lines = self.lines.clone();
+        move |message: &str| {
+            let mut lines = lines.lock().expect("Trace console lines");
+            for line in message.split('\n') {
+                if lines.len() == MAX_LINES {
+                    lines.pop_front();
+                }
+                lines.push_back(line.to_string());
+            }
+        }
+    }
+
+    pub fn clear(&self) {
+        self.lines.lock().expect("Trace console lines").clear();
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+
+        TopBottomPanel::bottom("trace_console")
+            .resizable(true)
+            .default_height(150.0)
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong(text(locale, "trace-console"));
+                    if ui.button(text(locale, "trace-console-clear")).clicked() {
+                        self.clear();
+                    }
+                    if ui.button(text(locale, "trace-console-close")).clicked() {
+                        keep_open = false;
+                    }
+                });
+                ui.separator();
+
+                let lines = self.lines.lock().expect("Trace console lines");
+                let row_height = ui.text_style_height(&TextStyle::Monospace);
+                ScrollArea::both()
+                    .auto_shrink(false)
+                    .stick_to_bottom(true)
+                    .show_rows(ui, row_height, lines.len(), |ui, rows| {
+                        for line in lines.range(rows) {
+                            ui.monospace(line);
+                        }
+                    });
+            });
+
+        keep_open
+    }
+}
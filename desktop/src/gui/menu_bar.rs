@@ -159,6 +159,38 @@ impl MenuBar {
                                 player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
                             }
                         }
+                        if Button::new(text(locale, "debug-menu-open-garbage-collector")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowGarbageCollector);
+                            }
+                        }
+                        if player.as_ref().is_some_and(|player| player.is_profiling()) {
+                            if Button::new(text(locale, "debug-menu-stop-profiling")).ui(ui).clicked() {
+                                ui.close_menu();
+                                if let Some(profile) = player.as_mut().and_then(|player| player.stop_profiling()) {
+                                    let json = profile.to_speedscope_json();
+                                    let picker = dialogs.file_picker();
+                                    tokio::spawn(async move {
+                                        if let Some(path) = picker.pick_profile_destination().await {
+                                            if let Err(e) = std::fs::write(&path, json) {
+                                                tracing::error!("Couldn't save profile to {}: {e}", path.display());
+                                            }
+                                        }
+                                    });
+                                }
+                            }
+                        } else if Button::new(text(locale, "debug-menu-start-profiling")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.start_profiling();
+                            }
+                        }
+                        let mut trace_console_visible = dialogs.is_trace_console_visible();
+                        if ui.checkbox(&mut trace_console_visible, text(locale, "debug-menu-trace-console")).clicked() {
+                            ui.close_menu();
+                            dialogs.toggle_trace_console();
+                        }
                     });
                 });
                 menu::menu_button(ui, text(locale, "help-menu"), |ui| {
@@ -56,6 +56,20 @@ impl FilePicker {
         }
     }
 
+    pub async fn pick_profile_destination(&self) -> Option<PathBuf> {
+        let locale = &self.data.preferences.language();
+        let dialog = AsyncFileDialog::new()
+            .add_filter(text(locale, "file-picker-filter-speedscope"), &["json"])
+            .set_file_name("profile.speedscope.json")
+            .set_title(text(locale, "file-picker-title-save-profile"));
+
+        if let Some(result) = self.show_dialog(dialog, |d| d.save_file()) {
+            result.await.map(|h| h.into())
+        } else {
+            None
+        }
+    }
+
     pub fn show_dialog<F, O>(&self, mut dialog: AsyncFileDialog, f: F) -> Option<O>
     where
         F: FnOnce(AsyncFileDialog) -> O,
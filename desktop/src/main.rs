@@ -13,6 +13,7 @@ mod custom_event;
 mod dbus;
 mod gui;
 mod log;
+mod native_windows;
 mod player;
 mod preferences;
 #[cfg(feature = "tracy")]
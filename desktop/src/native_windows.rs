@@ -0,0 +1,334 @@
+//! Additional OS windows for `NativeWindow`s opened by AIR content.
+//!
+//! The player asks for windows through the UI backend, but winit can only create
+//! windows from inside the event loop. Requests are queued here, and the windows are
+//! created once the event loop handles `RuffleEvent::NativeWindowsChanged`.
+
+use crate::custom_event::RuffleEvent;
+use ruffle_core::backend::ui::{NativeWindowDescriptor, NativeWindowId};
+use ruffle_core::events::{MouseButton as RuffleMouseButton, MouseWheelDelta};
+use ruffle_core::PlayerEvent;
+use ruffle_render_wgpu::descriptors::Descriptors;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
+use winit::window::{Window, WindowAttributes, WindowId};
+
+/// The native windows of the current player, shared between its UI backend and the event loop.
+pub type SharedNativeWindows = Rc<RefCell<NativeWindowHost>>;
+
+/// What the event loop should do with an event received by one of the native windows.
+pub enum NativeWindowInput {
+    /// Deliver this pointer event to the stage of the window.
+    Pointer(PlayerEvent),
+
+    /// The user focused or unfocused the window.
+    Focused(bool),
+
+    /// The user pressed the close button of the window.
+    CloseRequested,
+}
+
+struct DesktopNativeWindow {
+    window: Arc<Window>,
+
+    /// The surface frames are copied to, if the platform allows writing to it directly.
+    surface: Option<(wgpu::Surface<'static>, wgpu::SurfaceConfiguration)>,
+    mouse_pos: PhysicalPosition<f64>,
+}
+
+impl DesktopNativeWindow {
+    fn new(
+        event_loop: &ActiveEventLoop,
+        descriptors: &Descriptors,
+        descriptor: &NativeWindowDescriptor,
+    ) -> Option<Self> {
+        let attributes = WindowAttributes::default()
+            .with_title(descriptor.title.clone())
+            .with_inner_size(PhysicalSize::new(
+                descriptor.width.max(1),
+                descriptor.height.max(1),
+            ))
+            .with_position(PhysicalPosition::new(descriptor.x, descriptor.y))
+            .with_visible(descriptor.visible)
+            .with_transparent(descriptor.transparent)
+            // The movie controls the bounds of its windows.
+            .with_resizable(false);
+        let window = match event_loop.create_window(attributes) {
+            Ok(window) => Arc::new(window),
+            Err(e) => {
+                tracing::error!("Couldn't create a NativeWindow: {e}");
+                return None;
+            }
+        };
+
+        let surface = Self::create_surface(&window, descriptors, descriptor);
+        Some(Self {
+            window,
+            surface,
+            mouse_pos: PhysicalPosition::new(0.0, 0.0),
+        })
+    }
+
+    fn create_surface(
+        window: &Arc<Window>,
+        descriptors: &Descriptors,
+        descriptor: &NativeWindowDescriptor,
+    ) -> Option<(wgpu::Surface<'static>, wgpu::SurfaceConfiguration)> {
+        let surface = match descriptors.wgpu_instance.create_surface(window.clone()) {
+            Ok(surface) => surface,
+            Err(e) => {
+                tracing::error!("Couldn't create a surface for a NativeWindow: {e}");
+                return None;
+            }
+        };
+
+        // Frames are rendered by the player, so they only need to be copied into the surface.
+        let capabilities = surface.get_capabilities(&descriptors.adapter);
+        if !capabilities.usages.contains(wgpu::TextureUsages::COPY_DST) {
+            tracing::warn!("NativeWindows can't be displayed, as surfaces can't be copied to");
+            return None;
+        }
+        let format = capabilities.formats.iter().copied().find(|format| {
+            matches!(
+                format,
+                wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Bgra8Unorm
+            )
+        })?;
+        let alpha_mode = if descriptor.transparent
+            && capabilities
+                .alpha_modes
+                .contains(&wgpu::CompositeAlphaMode::PreMultiplied)
+        {
+            wgpu::CompositeAlphaMode::PreMultiplied
+        } else {
+            capabilities.alpha_modes[0]
+        };
+
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::COPY_DST,
+            format,
+            width: descriptor.width.max(1),
+            height: descriptor.height.max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            desired_maximum_frame_latency: 2,
+            alpha_mode,
+            view_formats: vec![],
+        };
+        surface.configure(&descriptors.device, &config);
+        Some((surface, config))
+    }
+
+    fn update(&self, descriptor: &NativeWindowDescriptor) {
+        self.window.set_title(&descriptor.title);
+        self.window
+            .set_outer_position(PhysicalPosition::new(descriptor.x, descriptor.y));
+        let _ = self.window.request_inner_size(PhysicalSize::new(
+            descriptor.width.max(1),
+            descriptor.height.max(1),
+        ));
+        self.window.set_visible(descriptor.visible);
+    }
+
+    fn present(
+        &mut self,
+        descriptors: &Descriptors,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        row_stride: u32,
+    ) {
+        let Some((surface, config)) = &mut self.surface else {
+            return;
+        };
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        if config.width != width || config.height != height {
+            config.width = width;
+            config.height = height;
+            surface.configure(&descriptors.device, config);
+        }
+
+        let frame = match surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                surface.configure(&descriptors.device, config);
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("Couldn't present a NativeWindow: {e}");
+                return;
+            }
+        };
+
+        let pixels = if config.format == wgpu::TextureFormat::Bgra8Unorm {
+            let mut pixels = rgba.to_vec();
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            Cow::Owned(pixels)
+        } else {
+            Cow::Borrowed(rgba)
+        };
+
+        descriptors.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &frame.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &pixels,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(row_stride),
+                rows_per_image: None,
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.window.pre_present_notify();
+        frame.present();
+    }
+}
+
+pub struct NativeWindowHost {
+    descriptors: Arc<Descriptors>,
+    event_loop: EventLoopProxy<RuffleEvent>,
+    windows: HashMap<NativeWindowId, DesktopNativeWindow>,
+
+    /// Windows opened by the movie which the event loop hasn't created yet.
+    pending: Vec<(NativeWindowId, NativeWindowDescriptor)>,
+}
+
+impl NativeWindowHost {
+    pub fn new(descriptors: Arc<Descriptors>, event_loop: EventLoopProxy<RuffleEvent>) -> Self {
+        Self {
+            descriptors,
+            event_loop,
+            windows: HashMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self, id: NativeWindowId, descriptor: &NativeWindowDescriptor) -> bool {
+        self.pending.push((id, descriptor.clone()));
+        self.event_loop
+            .send_event(RuffleEvent::NativeWindowsChanged)
+            .is_ok()
+    }
+
+    pub fn update(&mut self, id: NativeWindowId, descriptor: &NativeWindowDescriptor) {
+        if let Some(window) = self.windows.get(&id) {
+            window.update(descriptor);
+        } else if let Some((_, pending)) = self.pending.iter_mut().find(|(p, _)| *p == id) {
+            *pending = descriptor.clone();
+        }
+    }
+
+    pub fn focus(&mut self, id: NativeWindowId) {
+        if let Some(window) = self.windows.get(&id) {
+            window.window.focus_window();
+        }
+    }
+
+    pub fn present(
+        &mut self,
+        id: NativeWindowId,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        row_stride: u32,
+    ) {
+        if let Some(window) = self.windows.get_mut(&id) {
+            window.present(&self.descriptors, width, height, rgba, row_stride);
+        }
+    }
+
+    pub fn close(&mut self, id: NativeWindowId) {
+        self.windows.remove(&id);
+        self.pending.retain(|(pending, _)| *pending != id);
+    }
+
+    /// Closes every window, as the player that opened them is going away.
+    pub fn close_all(&mut self) {
+        self.windows.clear();
+        self.pending.clear();
+    }
+
+    /// Creates the windows that were opened since the last call.
+    pub fn create_pending(&mut self, event_loop: &ActiveEventLoop) {
+        for (id, descriptor) in std::mem::take(&mut self.pending) {
+            if let Some(window) =
+                DesktopNativeWindow::new(event_loop, &self.descriptors, &descriptor)
+            {
+                self.windows.insert(id, window);
+            }
+        }
+    }
+
+    /// Translates an event received by one of the native windows.
+    ///
+    /// Returns `None` if `window_id` isn't a native window, or if the event isn't relevant
+    /// to the player.
+    pub fn handle_window_event(
+        &mut self,
+        window_id: WindowId,
+        event: &WindowEvent,
+    ) -> Option<(NativeWindowId, NativeWindowInput)> {
+        let (&id, window) = self
+            .windows
+            .iter_mut()
+            .find(|(_, window)| window.window.id() == window_id)?;
+
+        let input = match event {
+            WindowEvent::CloseRequested => NativeWindowInput::CloseRequested,
+            WindowEvent::Focused(focused) => NativeWindowInput::Focused(*focused),
+            WindowEvent::CursorMoved { position, .. } => {
+                window.mouse_pos = *position;
+                NativeWindowInput::Pointer(PlayerEvent::MouseMove {
+                    x: position.x,
+                    y: position.y,
+                })
+            }
+            WindowEvent::CursorLeft { .. } => NativeWindowInput::Pointer(PlayerEvent::MouseLeave),
+            WindowEvent::MouseInput { button, state, .. } => {
+                let PhysicalPosition { x, y } = window.mouse_pos;
+                let button = match button {
+                    MouseButton::Left => RuffleMouseButton::Left,
+                    MouseButton::Right => RuffleMouseButton::Right,
+                    MouseButton::Middle => RuffleMouseButton::Middle,
+                    _ => RuffleMouseButton::Unknown,
+                };
+                NativeWindowInput::Pointer(match state {
+                    ElementState::Pressed => PlayerEvent::MouseDown {
+                        x,
+                        y,
+                        button,
+                        index: None,
+                    },
+                    ElementState::Released => PlayerEvent::MouseUp { x, y, button },
+                })
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(_, dy) => MouseWheelDelta::Lines((*dy).into()),
+                    MouseScrollDelta::PixelDelta(pos) => MouseWheelDelta::Pixels(pos.y),
+                };
+                NativeWindowInput::Pointer(PlayerEvent::MouseWheel { delta })
+            }
+            _ => return None,
+        };
+        Some((id, input))
+    }
+}
@@ -1,17 +1,23 @@
 use crate::backends::{
-    DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopNavigatorInterface,
-    DesktopUiBackend,
+    DesktopDebuggerBackend, DesktopExternalInterfaceProvider, DesktopFSCommandProvider,
+    DesktopMicrophoneBackend, DesktopNavigatorInterface, DesktopUiBackend,
 };
 use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
 use crate::custom_event::RuffleEvent;
 use crate::gui::{FilePicker, MovieView};
+use crate::native_windows::{NativeWindowHost, SharedNativeWindows};
 use crate::preferences::GlobalPreferences;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
+use ruffle_core::backend::local_connection::LocalConnectionBus;
 use ruffle_core::backend::navigator::SocketMode;
-use ruffle_core::config::Letterbox;
-use ruffle_core::events::{GamepadButton, KeyCode};
+use ruffle_core::bitmap::readback::BitmapReadbackMode;
+use ruffle_core::config::{CapabilitiesConfig, Letterbox};
+use ruffle_core::events::{GamepadButton, ImeEvent, KeyCode, TouchEvent};
+use ruffle_core::replay::Replay;
+use ruffle_core::rsl::RslMirror;
+use ruffle_core::sandbox::LoadAllowList;
 use ruffle_core::{DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent};
 use ruffle_frontend_utils::backends::audio::CpalAudioBackend;
 use ruffle_frontend_utils::backends::executor::{AsyncExecutor, PollRequester};
@@ -27,6 +33,7 @@ use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::clap::PowerPreference;
 use ruffle_render_wgpu::descriptors::Descriptors;
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::path::PathBuf;
@@ -43,14 +50,25 @@ use winit::window::Window;
 pub struct LaunchOptions {
     pub player: PlayerOptions,
     pub proxy: Option<Url>,
+    pub rsl_mirror: Option<Url>,
+    pub debugger_port: Option<u16>,
+    pub record_replay: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub load_state: Option<PathBuf>,
+    pub gc_budget: Option<Duration>,
+    pub bitmap_readback: BitmapReadbackMode,
     pub socket_allowed: HashSet<String>,
+    pub fscommands_allowed: HashSet<String>,
     pub tcp_connections: Option<SocketMode>,
     pub fullscreen: bool,
     pub save_directory: PathBuf,
     pub cache_directory: PathBuf,
     pub filesystem_access_mode: FilesystemAccessMode,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+    pub key_remap: HashMap<KeyCode, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    pub jit_enabled: bool,
+    pub capabilities: CapabilitiesConfig,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -93,14 +111,36 @@ impl From<&GlobalPreferences> for LaunchOptions {
                 },
             },
             proxy: value.cli.proxy.clone(),
+            rsl_mirror: value.cli.rsl_mirror.clone(),
+            debugger_port: value.cli.debugger_port,
+            record_replay: value.cli.record_replay.clone(),
+            replay: value.cli.replay.clone(),
+            load_state: value.cli.load_state.clone(),
+            gc_budget: value.cli.gc_budget,
+            bitmap_readback: value.cli.bitmap_readback,
             fullscreen: value.cli.fullscreen,
             save_directory: value.cli.save_directory.clone(),
             cache_directory: value.cli.cache_directory.clone(),
             filesystem_access_mode: value.cli.filesystem_access_mode,
             socket_allowed: HashSet::from_iter(value.cli.socket_allow.iter().cloned()),
+            fscommands_allowed: value
+                .cli
+                .fscommand_allow
+                .iter()
+                .map(|command| command.to_ascii_lowercase())
+                .collect(),
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
+            key_remap: HashMap::from_iter(value.cli.key_remap.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            jit_enabled: !value.cli.no_jit,
+            capabilities: CapabilitiesConfig {
+                version: value.cli.spoof_version.clone(),
+                os: value.cli.spoof_os.clone(),
+                player_type: value.cli.spoof_player_type.clone(),
+                language: value.cli.spoof_language.clone(),
+                server_string: value.cli.spoof_server_string.clone(),
+            },
         }
     }
 }
@@ -122,6 +162,9 @@ struct ActivePlayer {
     player: Arc<Mutex<Player>>,
     executor: Arc<AsyncExecutor<WinitWaker>>,
 
+    /// Where to save the replay being recorded, once this player is closed.
+    record_replay: Option<PathBuf>,
+
     #[cfg(target_os = "linux")]
     _gamemode_session: crate::dbus::GameModeSession,
 }
@@ -138,6 +181,8 @@ impl ActivePlayer {
         font_database: Rc<fontdb::Database>,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
+        local_connection_bus: &LocalConnectionBus,
+        native_windows: &SharedNativeWindows,
     ) -> Self {
         let mut builder = PlayerBuilder::new();
 
@@ -199,14 +244,25 @@ impl ActivePlayer {
                 Cow::Owned(LaunchOptions {
                     player,
                     proxy: opt.proxy.clone(),
+                    rsl_mirror: opt.rsl_mirror.clone(),
+                    debugger_port: opt.debugger_port,
+                    record_replay: opt.record_replay.clone(),
+                    replay: opt.replay.clone(),
+                    load_state: opt.load_state.clone(),
+                    gc_budget: opt.gc_budget,
+                    bitmap_readback: opt.bitmap_readback,
                     socket_allowed: opt.socket_allowed.clone(),
+                    fscommands_allowed: opt.fscommands_allowed.clone(),
                     tcp_connections: opt.tcp_connections,
                     fullscreen: opt.fullscreen,
                     save_directory: opt.save_directory.clone(),
                     cache_directory: opt.cache_directory.clone(),
                     filesystem_access_mode: opt.filesystem_access_mode,
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
+                    key_remap: opt.key_remap.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    jit_enabled: opt.jit_enabled,
+                    capabilities: opt.capabilities.clone(),
                 })
             }
         };
@@ -290,12 +346,34 @@ impl ActivePlayer {
             builder = builder.with_gamepad_button_mapping(opt.gamepad_button_mapping.clone());
         }
 
+        if !opt.key_remap.is_empty() {
+            builder = builder.with_key_remap(opt.key_remap.clone());
+        }
+
+        if let Some(port) = opt.debugger_port {
+            match DesktopDebuggerBackend::new(port) {
+                Ok(debugger) => builder = builder.with_debugger(debugger),
+                Err(e) => tracing::error!("Couldn't listen for debuggers on port {port}: {e}"),
+            }
+        }
+
+        #[cfg(feature = "jit")]
+        if opt.jit_enabled {
+            match crate::backends::CraneliftJitBackend::new() {
+                Ok(jit) => builder = builder.with_jit(jit),
+                Err(e) => tracing::error!("Couldn't create the AVM2 JIT: {e}"),
+            }
+        }
+
         builder = builder
             .with_navigator(navigator)
             .with_renderer(renderer)
             .with_storage(preferences.storage_backend().create_backend(&opt))
+            .with_microphone(DesktopMicrophoneBackend::new())
+            .with_local_connection_backend(Box::new(local_connection_bus.join()))
             .with_fs_commands(Box::new(DesktopFSCommandProvider {
                 event_loop: event_loop.clone(),
+                allowed: opt.fscommands_allowed.clone(),
             }))
             .with_ui(
                 DesktopUiBackend::new(
@@ -304,6 +382,7 @@ impl ActivePlayer {
                     font_database,
                     preferences,
                     file_picker,
+                    native_windows.clone(),
                 )
                 .expect("Couldn't create ui backend"),
             )
@@ -311,6 +390,19 @@ impl ActivePlayer {
             .with_letterbox(opt.player.letterbox.unwrap_or(Letterbox::On))
             .with_max_execution_duration(opt.player.max_execution_duration.unwrap_or(Duration::MAX))
             .with_quality(opt.player.quality.unwrap_or(StageQuality::High))
+            .with_adaptive_quality(preferences.adaptive_quality())
+            .with_load_allow_list(
+                preferences
+                    .load_allow_list()
+                    .as_deref()
+                    .map(LoadAllowList::new),
+            )
+            .with_policy_file_mode(preferences.policy_file_mode())
+            .with_rsl_mirror(
+                opt.rsl_mirror
+                    .as_ref()
+                    .and_then(|url| RslMirror::new(url.as_str())),
+            )
             .with_align(
                 opt.player.align.unwrap_or_default(),
                 opt.player.force_align.unwrap_or_default(),
@@ -326,7 +418,10 @@ impl ActivePlayer {
             .with_player_version(opt.player.player_version)
             .with_player_runtime(opt.player.player_runtime.unwrap_or_default())
             .with_frame_rate(opt.player.frame_rate)
-            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled);
+            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled)
+            .with_gc_budget(opt.gc_budget)
+            .with_bitmap_readback_mode(opt.bitmap_readback)
+            .with_capabilities(opt.capabilities.clone());
         let player = builder.build();
 
         window.set_title(&format!("Ruffle - {readable_name}"));
@@ -402,17 +497,69 @@ impl ActivePlayer {
                     "Arial Unicode MS".into(),    // Mac fallback
                 ],
             );
+            player_lock.set_default_font(
+                DefaultFont::Fallback,
+                vec![
+                    "Microsoft YaHei".into(),   // Windows
+                    "Segoe UI Emoji".into(),    // Windows
+                    "PingFang SC".into(),       // Mac
+                    "Apple Color Emoji".into(), // Mac
+                    "Noto Sans CJK JP".into(),  // Linux
+                    "Noto Color Emoji".into(),  // Linux
+                    "Arial Unicode MS".into(),  // Mac fallback
+                ],
+            );
+
+            let read_replay = |path: &PathBuf| {
+                std::fs::read_to_string(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| Ok(Replay::from_json(&json)?))
+            };
+            if let Some(path) = &opt.replay {
+                match read_replay(path) {
+                    Ok(replay) => player_lock.start_replay(replay),
+                    Err(e) => tracing::error!("Couldn't load replay from {}: {e}", path.display()),
+                }
+            } else if let Some(path) = &opt.load_state {
+                match read_replay(path) {
+                    Ok(state) => player_lock.load_state(state),
+                    Err(e) => {
+                        tracing::error!("Couldn't load save state from {}: {e}", path.display())
+                    }
+                }
+            } else if opt.record_replay.is_some() {
+                player_lock.start_replay_recording(rand::random());
+            }
         }
 
         Self {
             player,
             executor,
+            record_replay: opt.record_replay.clone(),
             #[cfg(target_os = "linux")]
             _gamemode_session: crate::dbus::GameModeSession::new(gamemode_enable),
         }
     }
 }
 
+impl Drop for ActivePlayer {
+    fn drop(&mut self) {
+        let Some(path) = &self.record_replay else {
+            return;
+        };
+        let replay = match self.player.lock() {
+            Ok(mut player) => player.stop_replay(),
+            Err(_) => None,
+        };
+        if let Some(replay) = replay {
+            match std::fs::write(path, replay.to_json()) {
+                Ok(()) => tracing::info!("Saved replay to {}", path.display()),
+                Err(e) => tracing::error!("Couldn't save replay to {}: {e}", path.display()),
+            }
+        }
+    }
+}
+
 /// Owner of a Ruffle Player (via ActivePlayer),
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
@@ -423,6 +570,13 @@ pub struct PlayerController {
     font_database: Rc<fontdb::Database>,
     preferences: GlobalPreferences,
     file_picker: FilePicker,
+
+    /// Connects the `LocalConnection`s of successive players, so that a movie can reach
+    /// a connection left open by the movie it replaced.
+    local_connection_bus: LocalConnectionBus,
+
+    /// Additional windows opened by AIR content.
+    native_windows: SharedNativeWindows,
 }
 
 impl PlayerController {
@@ -434,6 +588,10 @@ impl PlayerController {
         preferences: GlobalPreferences,
         file_picker: FilePicker,
     ) -> Self {
+        let native_windows = Rc::new(RefCell::new(NativeWindowHost::new(
+            descriptors.clone(),
+            event_loop.clone(),
+        )));
         Self {
             player: None,
             event_loop,
@@ -442,10 +600,13 @@ impl PlayerController {
             font_database: Rc::new(font_database),
             preferences,
             file_picker,
+            local_connection_bus: LocalConnectionBus::new(),
+            native_windows,
         }
     }
 
     pub fn create(&mut self, opt: &LaunchOptions, movie_url: &Url, movie_view: MovieView) {
+        self.native_windows.borrow_mut().close_all();
         self.player = Some(ActivePlayer::new(
             opt,
             self.event_loop.clone(),
@@ -456,11 +617,18 @@ impl PlayerController {
             self.font_database.clone(),
             self.preferences.clone(),
             self.file_picker.clone(),
+            &self.local_connection_bus,
+            &self.native_windows,
         ));
     }
 
     pub fn destroy(&mut self) {
         self.player = None;
+        self.native_windows.borrow_mut().close_all();
+    }
+
+    pub fn native_windows(&self) -> &SharedNativeWindows {
+        &self.native_windows
     }
 
     pub fn get(&self) -> Option<MutexGuard<Player>> {
@@ -486,6 +654,34 @@ impl PlayerController {
         false
     }
 
+    /// Whether the movie locked the mouse, so that its movement should be reported instead.
+    pub fn is_mouse_locked(&self) -> bool {
+        self.get().is_some_and(|player| {
+            player
+                .ui()
+                .downcast_ref::<DesktopUiBackend>()
+                .is_some_and(|ui| ui.is_mouse_locked())
+        })
+    }
+
+    pub fn handle_touch_event(&self, event: TouchEvent) -> bool {
+        if let Some(mut player) = self.get() {
+            if player.is_playing() {
+                return player.handle_touch_event(event);
+            }
+        }
+
+        false
+    }
+
+    pub fn handle_ime_event(&self, event: ImeEvent) {
+        if let Some(mut player) = self.get() {
+            if player.is_playing() {
+                player.handle_ime_event(event);
+            }
+        }
+    }
+
     pub fn poll(&self) {
         if let Some(player) = &self.player {
             player.executor.poll_all()
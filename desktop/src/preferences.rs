@@ -10,6 +10,7 @@ use crate::preferences::read::read_preferences;
 use crate::preferences::write::PreferencesWriter;
 use anyhow::{Context, Error};
 use ruffle_core::backend::ui::US_ENGLISH;
+use ruffle_core::config::PolicyFileMode;
 use ruffle_frontend_utils::bookmarks::{read_bookmarks, Bookmarks, BookmarksWriter};
 use ruffle_frontend_utils::parse::DocumentHolder;
 use ruffle_frontend_utils::recents::{read_recents, Recents, RecentsWriter};
@@ -160,6 +161,13 @@ impl GlobalPreferences {
         })
     }
 
+    pub fn adaptive_quality(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Preferences is not reentrant")
+            .adaptive_quality
+    }
+
     pub fn openh264_enabled(&self) -> bool {
         self.preferences
             .lock()
@@ -222,6 +230,23 @@ impl GlobalPreferences {
         })
     }
 
+    pub fn load_allow_list(&self) -> Option<Vec<String>> {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .load_allow_list
+            .clone()
+    }
+
+    pub fn policy_file_mode(&self) -> PolicyFileMode {
+        self.cli.policy_file_mode.unwrap_or_else(|| {
+            self.preferences
+                .lock()
+                .expect("Non-poisoned preferences")
+                .policy_file_mode
+        })
+    }
+
     pub fn recents<R>(&self, fun: impl FnOnce(&Recents) -> R) -> R {
         fun(&self.recents.lock().expect("Recents is not reentrant"))
     }
@@ -273,12 +298,15 @@ pub struct SavedGlobalPreferences {
     pub output_device: Option<String>,
     pub mute: bool,
     pub volume: f32,
+    pub adaptive_quality: bool,
     pub enable_openh264: bool,
     pub recent_limit: usize,
     pub log: LogPreferences,
     pub storage: StoragePreferences,
     pub theme_preference: ThemePreference,
     pub open_url_mode: OpenUrlMode,
+    pub load_allow_list: Option<Vec<String>>,
+    pub policy_file_mode: PolicyFileMode,
 }
 
 impl Default for SavedGlobalPreferences {
@@ -296,12 +324,15 @@ impl Default for SavedGlobalPreferences {
             output_device: None,
             mute: false,
             volume: 1.0,
+            adaptive_quality: false,
             enable_openh264: true,
             recent_limit: 10,
             log: Default::default(),
             storage: Default::default(),
             theme_preference: Default::default(),
             open_url_mode: Default::default(),
+            load_allow_list: None,
+            policy_file_mode: Default::default(),
         }
     }
 }
@@ -51,6 +51,10 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.mute = value;
     };
 
+    if let Some(value) = document.get_bool(&mut cx, "adaptive_quality") {
+        result.adaptive_quality = value;
+    };
+
     if let Some(value) = document.get_bool(&mut cx, "enable_openh264") {
         result.enable_openh264 = value;
     };
@@ -71,6 +75,28 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.open_url_mode = value;
     }
 
+    if let Some(item) = document.get("load_allow_list") {
+        cx.push_key("load_allow_list");
+        if let Some(array) = item.as_array() {
+            let mut allow_list = vec![];
+            for value in array.iter() {
+                if let Some(entry) = value.as_str() {
+                    allow_list.push(entry.to_owned());
+                } else {
+                    cx.unexpected_type("string", value.type_name());
+                }
+            }
+            result.load_allow_list = Some(allow_list);
+        } else {
+            cx.unexpected_type("array", item.type_name());
+        }
+        cx.pop_key();
+    }
+
+    if let Some(value) = document.parse_from_str(&mut cx, "policy_file_mode") {
+        result.policy_file_mode = value;
+    }
+
     document.get_table_like(&mut cx, "log", |cx, log| {
         if let Some(value) = log.parse_from_str(cx, "filename_pattern") {
             result.log.filename_pattern = value;
@@ -97,6 +123,7 @@ mod tests {
     use crate::log::FilenamePattern;
     use crate::preferences::{storage::StorageBackend, LogPreferences, StoragePreferences};
     use fluent_templates::loader::langid;
+    use ruffle_core::config::PolicyFileMode;
     use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 
     #[test]
@@ -345,6 +372,30 @@ mod tests {
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
     }
 
+    #[test]
+    fn adaptive_quality() {
+        let result = read_preferences("adaptive_quality = 1");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "integer",
+                path: "adaptive_quality".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("adaptive_quality = true");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                adaptive_quality: true,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
     #[test]
     fn enable_openh264() {
         let result = read_preferences("enable_openh264 = \"true\"");
@@ -707,4 +758,68 @@ mod tests {
             result.warnings
         );
     }
+
+    #[test]
+    fn load_allow_list() {
+        let result = read_preferences("load_allow_list = [\"example.com\", 1]");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                load_allow_list: Some(vec!["example.com".to_string()]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "string",
+                actual: "integer",
+                path: "load_allow_list".to_string(),
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("load_allow_list = \"example.com\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "array",
+                actual: "string",
+                path: "load_allow_list".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn policy_file_mode() {
+        let result = read_preferences("policy_file_mode = \"enforce\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                policy_file_mode: PolicyFileMode::Enforce,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("policy_file_mode = \"warn\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                policy_file_mode: PolicyFileMode::Warn,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("policy_file_mode = \"strict\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "strict".to_string(),
+                path: "policy_file_mode".to_string(),
+            }],
+            result.warnings
+        );
+    }
 }
@@ -3,9 +3,10 @@ use crate::gui::ThemePreference;
 use crate::log::FilenamePattern;
 use crate::preferences::storage::StorageBackend;
 use crate::preferences::{GlobalPreferencesWatchers, SavedGlobalPreferences};
+use ruffle_core::config::PolicyFileMode;
 use ruffle_frontend_utils::parse::DocumentHolder;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use toml_edit::value;
+use toml_edit::{value, Array};
 use unic_langid::LanguageIdentifier;
 
 pub struct PreferencesWriter<'a>(
@@ -68,6 +69,13 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_adaptive_quality(&mut self, enable: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["adaptive_quality"] = value(enable);
+            values.adaptive_quality = enable;
+        })
+    }
+
     pub fn set_enable_openh264(&mut self, enable: bool) {
         self.0.edit(|values, toml_document| {
             toml_document["enable_openh264"] = value(enable);
@@ -131,6 +139,33 @@ impl<'a> PreferencesWriter<'a> {
             values.open_url_mode = open_url_mode;
         });
     }
+
+    pub fn set_load_allow_list(&mut self, load_allow_list: Option<Vec<String>>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(load_allow_list) = &load_allow_list {
+                toml_document["load_allow_list"] = value(load_allow_list.iter().collect::<Array>());
+            } else {
+                toml_document.remove("load_allow_list");
+            }
+            values.load_allow_list = load_allow_list;
+        });
+    }
+
+    pub fn set_policy_file_mode(&mut self, policy_file_mode: PolicyFileMode) {
+        self.0.edit(|values, toml_document| {
+            let name = match policy_file_mode {
+                PolicyFileMode::Enforce => Some("enforce"),
+                PolicyFileMode::Warn => Some("warn"),
+                PolicyFileMode::Ignore => None,
+            };
+            if let Some(name) = name {
+                toml_document["policy_file_mode"] = value(name);
+            } else {
+                toml_document.remove("policy_file_mode");
+            }
+            values.policy_file_mode = policy_file_mode;
+        });
+    }
 }
 
 #[cfg(test)]
@@ -220,6 +255,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_adaptive_quality() {
+        test(
+            "",
+            |writer| writer.set_adaptive_quality(true),
+            "adaptive_quality = true\n",
+        );
+        test(
+            "adaptive_quality = true",
+            |writer| writer.set_adaptive_quality(false),
+            "adaptive_quality = false\n",
+        );
+    }
+
     #[test]
     fn set_enable_openh264() {
         test(
@@ -327,4 +376,37 @@ mod tests {
             "",
         );
     }
+
+    #[test]
+    fn set_load_allow_list() {
+        test(
+            "",
+            |writer| {
+                writer.set_load_allow_list(Some(vec![
+                    "example.com".to_string(),
+                    "*.example.org".to_string(),
+                ]))
+            },
+            "load_allow_list = [\"example.com\", \"*.example.org\"]\n",
+        );
+        test(
+            "load_allow_list = [\"example.com\"]\n",
+            |writer| writer.set_load_allow_list(None),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_policy_file_mode() {
+        test(
+            "",
+            |writer| writer.set_policy_file_mode(PolicyFileMode::Enforce),
+            "policy_file_mode = \"enforce\"\n",
+        );
+        test(
+            "policy_file_mode = \"warn\"\n",
+            |writer| writer.set_policy_file_mode(PolicyFileMode::Ignore),
+            "",
+        );
+    }
 }
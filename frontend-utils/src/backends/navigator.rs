@@ -20,10 +20,14 @@ use std::io;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::rc::Rc;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{self, ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
 use tracing::warn;
 use url::{ParseError, Url};
 
@@ -128,6 +132,233 @@ impl<F: FutureSpawner, I: NavigatorInterface> ExternalNavigatorBackend<F, I> {
             interface,
         }
     }
+
+    /// Opens a socket for the movie, with TLS if `secure` is set.
+    #[allow(clippy::too_many_arguments)]
+    fn open_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        secure: bool,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let addr = format!("{}:{}", host, port);
+        let is_allowed = self.socket_allowed.contains(&addr);
+        let socket_mode = self.socket_mode;
+        let interface = self.interface.clone();
+
+        let future = Box::pin(async move {
+            match (is_allowed, socket_mode) {
+                (false, SocketMode::Allow) | (true, _) => {} // the process is allowed to continue. just dont do anything.
+                (false, SocketMode::Deny) => {
+                    // Just fail the connection.
+                    let action = SocketAction::Connect(handle, ConnectionState::Failed);
+                    let _ = send_action(&sender, action).await;
+
+                    tracing::warn!(
+                        "SWF tried to open a socket, but opening a socket is not allowed"
+                    );
+
+                    return;
+                }
+                (false, SocketMode::Ask) => {
+                    let attempt_sandbox_connect = interface.confirm_socket(&host, port).await;
+
+                    if !attempt_sandbox_connect {
+                        // fail the connection.
+                        let action = SocketAction::Connect(handle, ConnectionState::Failed);
+                        let _ = send_action(&sender, action).await;
+                        return;
+                    }
+                }
+            }
+
+            let host2 = host.clone();
+
+            let timeout = async {
+                Timer::after(timeout).await;
+                Result::<TcpStream, io::Error>::Err(io::Error::new(ErrorKind::TimedOut, ""))
+            };
+
+            let stream = match TcpStream::connect((host, port)).or(timeout).await {
+                Err(e) if e.kind() == ErrorKind::TimedOut => {
+                    warn!("Connection to {}:{} timed out", host2, port);
+                    let action = SocketAction::Connect(handle, ConnectionState::TimedOut);
+                    let _ = send_action(&sender, action).await;
+                    return;
+                }
+                Ok(stream) => stream,
+                Err(err) => {
+                    warn!("Failed to connect to {}:{}, error: {}", host2, port, err);
+                    let action = SocketAction::Connect(handle, ConnectionState::Failed);
+                    let _ = send_action(&sender, action).await;
+                    return;
+                }
+            };
+
+            if !secure {
+                let action = SocketAction::Connect(handle, ConnectionState::Connected);
+                if send_action(&sender, action).await {
+                    relay_socket(stream, handle, receiver, sender).await;
+                }
+                return;
+            }
+
+            let stream = match start_tls(&host2, stream).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    warn!(
+                        "Failed to secure connection to {}:{}, error: {}",
+                        host2, port, err
+                    );
+                    let action = SocketAction::Connect(handle, ConnectionState::Failed);
+                    let _ = send_action(&sender, action).await;
+                    return;
+                }
+            };
+            let action = SocketAction::Connect(handle, ConnectionState::Connected);
+            if send_action(&sender, action).await {
+                relay_socket(stream, handle, receiver, sender).await;
+            }
+        });
+
+        tokio::spawn(future);
+    }
+}
+
+/// Tries to send the given action properly handling failures.
+///
+/// Returns `true` when the action has been sent properly,
+/// `false` when the channel is closed.
+async fn send_action(sender: &Sender<SocketAction>, action: SocketAction) -> bool {
+    sender
+        .send(action)
+        .await
+        .inspect_err(|err| tracing::warn!("Failed to send SocketAction: {}", err))
+        .is_ok()
+}
+
+/// Performs a TLS handshake with `host` over an established connection.
+///
+/// The certificate of the server has to be valid for `host`, and chain up to one of the
+/// root certificates trusted by browsers.
+async fn start_tls(host: &str, stream: TcpStream) -> io::Result<TlsStream<TcpStream>> {
+    static CONFIG: OnceLock<Option<Arc<ClientConfig>>> = OnceLock::new();
+
+    let config = CONFIG.get_or_init(|| {
+        let roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .inspect_err(|e| tracing::error!("Couldn't configure TLS: {e}"))
+            .ok()
+            .map(|builder| Arc::new(builder.with_root_certificates(roots).with_no_client_auth()))
+    });
+    let Some(config) = config else {
+        return Err(io::Error::new(ErrorKind::Unsupported, "TLS is unavailable"));
+    };
+
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+    TlsConnector::from(config.clone())
+        .connect(server_name, stream)
+        .await
+}
+
+/// Relays data between a connected socket and the movie, until either side closes it.
+async fn relay_socket<S: AsyncRead + AsyncWrite>(
+    stream: S,
+    handle: SocketHandle,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+) {
+    //NOTE: We clone the sender here as we cant share it between async tasks.
+    let sender2 = sender.clone();
+    let (mut reader, mut writer) = tokio::io::split(stream);
+
+    let read = async move {
+        loop {
+            let mut buffer = [0; 4096];
+
+            match reader.read(&mut buffer).await {
+                Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
+                Err(_) | Ok(0) => {
+                    let _ = send_action(&sender, SocketAction::Close(handle)).await;
+                    break;
+                }
+                Ok(read) => {
+                    let buffer = buffer.into_iter().take(read).collect::<Vec<_>>();
+
+                    let action = SocketAction::Data(handle, buffer);
+                    if !send_action(&sender, action).await {
+                        return;
+                    }
+                }
+            };
+        }
+    };
+
+    let write = async {
+        let mut pending_write = vec![];
+
+        loop {
+            let close_connection = loop {
+                match receiver.try_recv() {
+                    Ok(val) => {
+                        pending_write.extend(val);
+                    }
+                    Err(TryRecvError::Empty) => break false,
+                    Err(TryRecvError::Closed) => {
+                        //NOTE: Channel sender has been dropped.
+                        //      This means we have to close the connection,
+                        //      but not here, as we might have a pending write.
+                        break true;
+                    }
+                }
+            };
+
+            if !pending_write.is_empty() {
+                match writer.write(&pending_write).await {
+                    Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
+                    Err(_) => {
+                        let _ = send_action(&sender2, SocketAction::Close(handle)).await;
+                        return;
+                    }
+                    Ok(written) => {
+                        let _ = pending_write.drain(..written);
+                    }
+                }
+            } else if close_connection {
+                return;
+            } else {
+                // Receiver is empty and there's no pending data,
+                // we may block here and wait for new data.
+                match receiver.recv().await {
+                    Ok(val) => {
+                        pending_write.extend(val);
+                    }
+                    Err(_) => {
+                        // Ignore the error here, it will be
+                        // reported again in try_recv.
+                    }
+                }
+            }
+        }
+    };
+
+    //NOTE: If one future exits, this will take the other one down too.
+    tokio::select! {
+       _ = read => {},
+       _ = write => {},
+    };
+
+    if let Err(e) = writer.shutdown().await {
+        tracing::warn!("Failed to shutdown write half of socket: {e}");
+    }
 }
 
 impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
@@ -305,165 +536,23 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     ) {
-        /// Tries to send the given action properly handling failures.
-        ///
-        /// Returns `true` when the action has been sent properly,
-        /// `false` when the channel is closed.
-        async fn send_action(sender: &Sender<SocketAction>, action: SocketAction) -> bool {
-            sender
-                .send(action)
-                .await
-                .inspect_err(|err| tracing::warn!("Failed to send SocketAction: {}", err))
-                .is_ok()
-        }
-
-        let addr = format!("{}:{}", host, port);
-        let is_allowed = self.socket_allowed.contains(&addr);
-        let socket_mode = self.socket_mode;
-        let interface = self.interface.clone();
-
-        let future = Box::pin(async move {
-            match (is_allowed, socket_mode) {
-                (false, SocketMode::Allow) | (true, _) => {} // the process is allowed to continue. just dont do anything.
-                (false, SocketMode::Deny) => {
-                    // Just fail the connection.
-                    let action = SocketAction::Connect(handle, ConnectionState::Failed);
-                    let _ = send_action(&sender, action).await;
-
-                    tracing::warn!(
-                        "SWF tried to open a socket, but opening a socket is not allowed"
-                    );
-
-                    return;
-                }
-                (false, SocketMode::Ask) => {
-                    let attempt_sandbox_connect = interface.confirm_socket(&host, port).await;
-
-                    if !attempt_sandbox_connect {
-                        // fail the connection.
-                        let action = SocketAction::Connect(handle, ConnectionState::Failed);
-                        let _ = send_action(&sender, action).await;
-                        return;
-                    }
-                }
-            }
-
-            let host2 = host.clone();
-
-            let timeout = async {
-                Timer::after(timeout).await;
-                Result::<TcpStream, io::Error>::Err(io::Error::new(ErrorKind::TimedOut, ""))
-            };
-
-            let mut stream = match TcpStream::connect((host, port)).or(timeout).await {
-                Err(e) if e.kind() == ErrorKind::TimedOut => {
-                    warn!("Connection to {}:{} timed out", host2, port);
-                    let action = SocketAction::Connect(handle, ConnectionState::TimedOut);
-                    let _ = send_action(&sender, action).await;
-                    return;
-                }
-                Ok(stream) => {
-                    let action = SocketAction::Connect(handle, ConnectionState::Connected);
-                    if !send_action(&sender, action).await {
-                        return;
-                    }
-                    stream
-                }
-                Err(err) => {
-                    warn!("Failed to connect to {}:{}, error: {}", host2, port, err);
-                    let action = SocketAction::Connect(handle, ConnectionState::Failed);
-                    let _ = send_action(&sender, action).await;
-                    return;
-                }
-            };
-
-            let sender = sender;
-            //NOTE: We clone the sender here as we cant share it between async tasks.
-            let sender2 = sender.clone();
-            let (mut read, mut write) = stream.split();
-
-            let read = async move {
-                loop {
-                    let mut buffer = [0; 4096];
-
-                    match read.read(&mut buffer).await {
-                        Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
-                        Err(_) | Ok(0) => {
-                            let _ = send_action(&sender, SocketAction::Close(handle)).await;
-                            break;
-                        }
-                        Ok(read) => {
-                            let buffer = buffer.into_iter().take(read).collect::<Vec<_>>();
-
-                            let action = SocketAction::Data(handle, buffer);
-                            if !send_action(&sender, action).await {
-                                return;
-                            }
-                        }
-                    };
-                }
-            };
-
-            let write = async move {
-                let mut pending_write = vec![];
-
-                loop {
-                    let close_connection = loop {
-                        match receiver.try_recv() {
-                            Ok(val) => {
-                                pending_write.extend(val);
-                            }
-                            Err(TryRecvError::Empty) => break false,
-                            Err(TryRecvError::Closed) => {
-                                //NOTE: Channel sender has been dropped.
-                                //      This means we have to close the connection,
-                                //      but not here, as we might have a pending write.
-                                break true;
-                            }
-                        }
-                    };
-
-                    if !pending_write.is_empty() {
-                        match write.write(&pending_write).await {
-                            Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
-                            Err(_) => {
-                                let _ = send_action(&sender2, SocketAction::Close(handle)).await;
-                                return;
-                            }
-                            Ok(written) => {
-                                let _ = pending_write.drain(..written);
-                            }
-                        }
-                    } else if close_connection {
-                        return;
-                    } else {
-                        // Receiver is empty and there's no pending data,
-                        // we may block here and wait for new data.
-                        match receiver.recv().await {
-                            Ok(val) => {
-                                pending_write.extend(val);
-                            }
-                            Err(_) => {
-                                // Ignore the error here, it will be
-                                // reported again in try_recv.
-                            }
-                        }
-                    }
-                }
-            };
-
-            //NOTE: If one future exits, this will take the other one down too.
-            tokio::select! {
-               _ = read => {},
-               _ = write => {},
-            };
+        self.open_socket(host, port, timeout, false, handle, receiver, sender);
+    }
 
-            if let Err(e) = stream.shutdown().await {
-                tracing::warn!("Failed to shutdown write half of TcpStream: {e}");
-            }
-        });
+    fn supports_secure_sockets(&self) -> bool {
+        true
+    }
 
-        tokio::spawn(future);
+    fn connect_secure_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.open_socket(host, port, timeout, true, handle, receiver, sender);
     }
 }
 
@@ -7,12 +7,15 @@ use ruffle_render::backend::{
     RenderBackend, ShapeHandle, ShapeHandleImpl, ViewportDimensions,
 };
 use ruffle_render::bitmap::{
-    Bitmap, BitmapHandle, BitmapHandleImpl, BitmapSource, PixelRegion, PixelSnapping, RgbaBufRead,
-    SyncHandle,
+    Bitmap, BitmapFormat, BitmapHandle, BitmapHandleImpl, BitmapSource, PixelRegion, PixelSnapping,
+    RgbaBufRead, SyncHandle,
 };
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::pixel_bender::software::{self, SoftwareImage};
+use ruffle_render::pixel_bender::PixelBenderShaderImpl;
+use ruffle_render::projective::ProjectiveMatrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
@@ -38,7 +41,10 @@ pub struct WebCanvasRenderBackend {
     line: Path2d,
     line_rect: Path2d,
     mask_state: MaskState,
-    blend_modes: Vec<RenderBlendMode>,
+
+    /// The context of the innermost `BlendMode::Layer` that is being drawn, which
+    /// `BlendMode::Alpha` and `BlendMode::Erase` apply to.
+    nearest_layer: Option<CanvasRenderingContext2d>,
 
     // This is currently unused - we just store it to report
     // in `get_viewport_dimensions`
@@ -318,7 +324,7 @@ impl WebCanvasRenderBackend {
             line,
             line_rect,
             mask_state: MaskState::DrawContent,
-            blend_modes: vec![RenderBlendMode::Builtin(BlendMode::Normal)],
+            nearest_layer: None,
         };
         Ok(renderer)
     }
@@ -390,33 +396,6 @@ impl WebCanvasRenderBackend {
         ));
     }
 
-    fn apply_blend_mode(&mut self, blend: RenderBlendMode) {
-        // TODO: Objects with a blend mode need to be rendered to an intermediate buffer first,
-        // but for now we render each child directly to the canvas. This should look reasonable for most
-        // common cases.
-        // While canvas has built in support for most of the blend modes, a few aren't supported.
-        let mode = match blend {
-            RenderBlendMode::Builtin(BlendMode::Normal) => "source-over",
-            RenderBlendMode::Builtin(BlendMode::Layer) => "source-over", // Requires intermediate buffer.
-            RenderBlendMode::Builtin(BlendMode::Multiply) => "multiply",
-            RenderBlendMode::Builtin(BlendMode::Screen) => "screen",
-            RenderBlendMode::Builtin(BlendMode::Lighten) => "lighten",
-            RenderBlendMode::Builtin(BlendMode::Darken) => "darken",
-            RenderBlendMode::Builtin(BlendMode::Difference) => "difference",
-            RenderBlendMode::Builtin(BlendMode::Add) => "lighter",
-            RenderBlendMode::Builtin(BlendMode::Subtract) => "difference", // Not exposed by canvas, rendered as difference.
-            RenderBlendMode::Builtin(BlendMode::Invert) => "source-over",  // Not exposed by canvas.
-            RenderBlendMode::Builtin(BlendMode::Alpha) => "source-over", // Requires intermediate buffer.
-            RenderBlendMode::Builtin(BlendMode::Erase) => "source-over", // Requires intermediate buffer.
-            RenderBlendMode::Builtin(BlendMode::Overlay) => "overlay",
-            RenderBlendMode::Builtin(BlendMode::HardLight) => "hard-light",
-            RenderBlendMode::Shader(_) => "source-over", // Canvas does not support shaders
-        };
-        self.context
-            .set_global_composite_operation(mode)
-            .expect("Failed to update BlendMode");
-    }
-
     fn begin_frame(&mut self, clear: Color) {
         // Reset canvas transform in case it was left in a dirty state.
         self.context.reset_transform().warn_on_error();
@@ -437,25 +416,117 @@ impl WebCanvasRenderBackend {
         }
 
         self.mask_state = MaskState::DrawContent;
+        self.nearest_layer = None;
     }
 
-    fn push_blend_mode(&mut self, blend: RenderBlendMode) {
-        if !same_blend_mode(self.blend_modes.last(), &blend) {
-            self.apply_blend_mode(blend.clone());
+    /// Draws the given commands onto a canvas of their own, then blends the result onto
+    /// the current one.
+    fn draw_blend(&mut self, commands: CommandList, blend: RenderBlendMode) -> Result<(), JsValue> {
+        let applies_to_layer = matches!(
+            blend,
+            RenderBlendMode::Builtin(BlendMode::Alpha | BlendMode::Erase)
+        );
+        if applies_to_layer && self.nearest_layer.is_none() {
+            // An Alpha or Erase with no Layer above it is ignored.
+            return Ok(());
         }
-        self.blend_modes.push(blend);
+
+        let layer = BitmapData::empty(self.canvas.width(), self.canvas.height())?;
+        let canvas = std::mem::replace(&mut self.canvas, layer.canvas.clone());
+        let context = std::mem::replace(&mut self.context, layer.context.clone());
+        let mask_state = std::mem::replace(&mut self.mask_state, MaskState::DrawContent);
+        let nearest_layer = if let RenderBlendMode::Builtin(BlendMode::Layer) = blend {
+            self.nearest_layer.replace(layer.context.clone())
+        } else {
+            self.nearest_layer.clone()
+        };
+
+        commands.execute(self);
+
+        self.canvas = canvas;
+        self.context = context;
+        self.mask_state = mask_state;
+        self.nearest_layer = nearest_layer;
+
+        self.context.save();
+        self.context.reset_transform()?;
+        self.clear_color_filter();
+        let result = match composite_operation(&blend) {
+            Some(operation) => self
+                .context
+                .set_global_composite_operation(operation)
+                .and_then(|_| {
+                    self.context
+                        .draw_image_with_html_canvas_element(&layer.canvas, 0.0, 0.0)
+                }),
+            None => self.blend_pixels(&layer, &blend),
+        };
+        self.context.restore();
+        result
     }
 
-    fn pop_blend_mode(&mut self) {
-        let old = self.blend_modes.pop();
-        // We should never pop our base 'BlendMode::Normal'
-        let current = self
-            .blend_modes
-            .last()
-            .unwrap_or(&RenderBlendMode::Builtin(BlendMode::Normal));
-        if !same_blend_mode(old.as_ref(), current) {
-            self.apply_blend_mode(current.clone());
+    /// Blends a layer onto the current canvas pixel by pixel, for the blend modes
+    /// that canvas can't do on its own.
+    fn blend_pixels(&self, layer: &BitmapData, blend: &RenderBlendMode) -> Result<(), JsValue> {
+        let (width, height) = (self.canvas.width(), self.canvas.height());
+        let read = |context: &CanvasRenderingContext2d| {
+            context
+                .get_image_data(0.0, 0.0, width.into(), height.into())
+                .map(|image_data| image_data.data().0)
+        };
+        let mut pixels = read(&self.context)?;
+        let foreground = read(&layer.context)?;
+
+        match blend {
+            RenderBlendMode::Builtin(mode) => {
+                // Alpha and Erase blend with the nearest layer rather than the current canvas.
+                let background = match (mode, &self.nearest_layer) {
+                    (BlendMode::Alpha | BlendMode::Erase, Some(nearest_layer)) => {
+                        read(nearest_layer)?
+                    }
+                    _ => pixels.clone(),
+                };
+                for ((pixel, src), dst) in pixels
+                    .chunks_exact_mut(4)
+                    .zip(foreground.chunks_exact(4))
+                    .zip(background.chunks_exact(4))
+                {
+                    if src[3] > 0 {
+                        let blended = blend_pixel(*mode, premultiply(src), premultiply(dst));
+                        pixel.copy_from_slice(&unpremultiply(blended));
+                    }
+                }
+            }
+            RenderBlendMode::Shader(shader) => {
+                // Shaders work with premultiplied colors.
+                let to_image = |pixels: &[u8]| {
+                    let premultiplied: Vec<u8> = pixels
+                        .chunks_exact(4)
+                        .flat_map(|pixel| premultiply(pixel).map(|c| (c * 255.0).round() as u8))
+                        .collect();
+                    SoftwareImage::from_rgba8(width, height, &premultiplied)
+                };
+                let output = software::run_blend_shader(
+                    shader.0.parsed_shader(),
+                    to_image(&pixels),
+                    to_image(&foreground),
+                );
+                pixels = output
+                    .to_rgba8()
+                    .chunks_exact(4)
+                    .flat_map(|pixel| {
+                        unpremultiply(std::array::from_fn(|i| f32::from(pixel[i]) / 255.0))
+                    })
+                    .collect();
+            }
         }
+
+        // Drawing the result, rather than putting it, keeps it within the current mask.
+        let image_data = ImageData::new_with_u8_clamped_array(Clamped(pixels.as_slice()), width)?;
+        let result = BitmapData::with_image_data(image_data)?;
+        self.context.set_global_composite_operation("copy")?;
+        self.context
+            .draw_image_with_html_canvas_element(&result.canvas, 0.0, 0.0)
     }
 
     fn draw_lines(&mut self, color: Color, mut matrix: Matrix, rect: bool) {
@@ -569,9 +640,10 @@ impl RenderBackend for WebCanvasRenderBackend {
 
     fn compile_pixelbender_shader(
         &mut self,
-        _shader: ruffle_render::pixel_bender::PixelBenderShader,
+        shader: ruffle_render::pixel_bender::PixelBenderShader,
     ) -> Result<ruffle_render::pixel_bender::PixelBenderShaderHandle, Error> {
-        Err(Error::Unimplemented("compile_pixelbender_shader".into()))
+        // Shaders are run on the CPU by the caller.
+        Ok(ruffle_render::pixel_bender::software::SoftwareShader::new_handle(shader))
     }
 
     fn run_pixelbender_shader(
@@ -595,6 +667,40 @@ impl RenderBackend for WebCanvasRenderBackend {
         let bitmap_data = BitmapData::empty(width, height).map_err(Error::JavascriptError)?;
         Ok(BitmapHandle(Arc::new(bitmap_data)))
     }
+
+    fn capture_frame(
+        &mut self,
+        commands: CommandList,
+        _quality: StageQuality,
+        width: u32,
+        height: u32,
+    ) -> Result<Bitmap, Error> {
+        // The frame is drawn onto a canvas of its own, so that the visible one is left alone.
+        let target = BitmapData::empty(width, height).map_err(Error::JavascriptError)?;
+        let canvas = std::mem::replace(&mut self.canvas, target.canvas.clone());
+        let context = std::mem::replace(&mut self.context, target.context.clone());
+        let mask_state = std::mem::replace(&mut self.mask_state, MaskState::DrawContent);
+        let nearest_layer = self.nearest_layer.take();
+
+        self.begin_frame(Color::from_rgba(0));
+        commands.execute(self);
+
+        self.canvas = canvas;
+        self.context = context;
+        self.mask_state = mask_state;
+        self.nearest_layer = nearest_layer;
+
+        let image_data = target
+            .context
+            .get_image_data(0.0, 0.0, width.into(), height.into())
+            .map_err(Error::JavascriptError)?;
+        Ok(Bitmap::new(
+            width,
+            height,
+            BitmapFormat::Rgba,
+            image_data.data().0,
+        ))
+    }
 }
 
 impl CommandHandler for WebCanvasRenderBackend {
@@ -620,6 +726,31 @@ impl CommandHandler for WebCanvasRenderBackend {
         self.clear_color_filter();
     }
 
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: ProjectiveMatrix,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        // Canvas can only draw with an affine transform, so this uses the closest one around
+        // the middle of the bitmap.
+        let canvas = &as_bitmap_data(&bitmap).canvas;
+        let center = Point::new(
+            Twips::from_pixels_i32(canvas.width() as i32 / 2),
+            Twips::from_pixels_i32(canvas.height() as i32 / 2),
+        );
+        self.render_bitmap(
+            bitmap,
+            Transform {
+                matrix: matrix.linearize_at(center),
+                color_transform,
+            },
+            smoothing,
+            PixelSnapping::Never,
+        );
+    }
+
     fn render_stage3d(&mut self, _bitmap: BitmapHandle, _transform: Transform) {
         panic!("Stage3D should not have been created on canvas backend")
     }
@@ -877,9 +1008,9 @@ impl CommandHandler for WebCanvasRenderBackend {
     }
 
     fn blend(&mut self, commands: CommandList, blend: RenderBlendMode) {
-        self.push_blend_mode(blend);
-        commands.execute(self);
-        self.pop_blend_mode();
+        if let Err(e) = self.draw_blend(commands, blend) {
+            log::error!("Couldn't draw blend: {:?}", e);
+        }
     }
 }
 
@@ -1374,9 +1505,72 @@ impl MatrixExt for swf::Matrix {
     }
 }
 
-fn same_blend_mode(first: Option<&RenderBlendMode>, second: &RenderBlendMode) -> bool {
-    match (first, second) {
-        (Some(RenderBlendMode::Builtin(old)), RenderBlendMode::Builtin(new)) => old == new,
-        _ => false,
+/// The canvas composite operation that draws a blend, or `None` if canvas doesn't
+/// have one that matches Flash.
+fn composite_operation(blend: &RenderBlendMode) -> Option<&'static str> {
+    let operation = match blend {
+        RenderBlendMode::Builtin(BlendMode::Normal | BlendMode::Layer) => "source-over",
+        RenderBlendMode::Builtin(BlendMode::Multiply) => "multiply",
+        RenderBlendMode::Builtin(BlendMode::Screen) => "screen",
+        RenderBlendMode::Builtin(BlendMode::Lighten) => "lighten",
+        RenderBlendMode::Builtin(BlendMode::Darken) => "darken",
+        RenderBlendMode::Builtin(BlendMode::Difference) => "difference",
+        RenderBlendMode::Builtin(BlendMode::Add) => "lighter",
+        RenderBlendMode::Builtin(BlendMode::Overlay) => "overlay",
+        RenderBlendMode::Builtin(BlendMode::HardLight) => "hard-light",
+        RenderBlendMode::Builtin(
+            BlendMode::Subtract | BlendMode::Invert | BlendMode::Alpha | BlendMode::Erase,
+        )
+        | RenderBlendMode::Shader(_) => return None,
+    };
+    Some(operation)
+}
+
+/// Blends a premultiplied pixel onto another one, for the blend modes that canvas
+/// doesn't have a composite operation for.
+fn blend_pixel(mode: BlendMode, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+    let over = src[3] + dst[3] * (1.0 - src[3]);
+    match mode {
+        BlendMode::Subtract => [
+            (dst[0] - src[0]).max(0.0),
+            (dst[1] - src[1]).max(0.0),
+            (dst[2] - src[2]).max(0.0),
+            over,
+        ],
+        BlendMode::Invert => {
+            let invert = |i: usize| {
+                src[i] * (1.0 - dst[3]) + dst[i] * (1.0 - src[3]) + src[3] * (dst[3] - dst[i])
+            };
+            [invert(0), invert(1), invert(2), over]
+        }
+        BlendMode::Alpha => dst.map(|c| c * src[3]),
+        BlendMode::Erase => dst.map(|c| c * (1.0 - src[3])),
+        _ => src,
+    }
+}
+
+/// Reads an unpremultiplied canvas pixel as premultiplied floats.
+fn premultiply(pixel: &[u8]) -> [f32; 4] {
+    let alpha = f32::from(pixel[3]) / 255.0;
+    [
+        f32::from(pixel[0]) / 255.0 * alpha,
+        f32::from(pixel[1]) / 255.0 * alpha,
+        f32::from(pixel[2]) / 255.0 * alpha,
+        alpha,
+    ]
+}
+
+/// Turns premultiplied floats into an unpremultiplied canvas pixel.
+fn unpremultiply(pixel: [f32; 4]) -> [u8; 4] {
+    let alpha = pixel[3].clamp(0.0, 1.0);
+    if alpha <= 0.0 {
+        return [0; 4];
     }
+    let channel = |c: f32| ((c / alpha).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [
+        channel(pixel[0]),
+        channel(pixel[1]),
+        channel(pixel[2]),
+        (alpha * 255.0).round() as u8,
+    ]
 }
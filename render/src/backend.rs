@@ -1,6 +1,8 @@
 pub mod null;
 
-use crate::bitmap::{Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle};
+use crate::bitmap::{
+    Bitmap, BitmapFormat, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
 use crate::commands::CommandList;
 use crate::error::Error;
 use crate::filters::Filter;
@@ -13,6 +15,7 @@ use std::borrow::Cow;
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use swf::{Color, Rectangle, Twips};
 
@@ -94,6 +97,20 @@ pub trait RenderBackend: Downcast {
 
     fn set_quality(&mut self, quality: StageQuality);
 
+    /// Identifies the device that handles registered with this renderer live on.
+    ///
+    /// Handles registered under another id can't be used with this renderer, and must be
+    /// registered again; this happens once a renderer that lost its device is replaced.
+    fn device_id(&self) -> DeviceId {
+        DeviceId::default()
+    }
+
+    /// Whether the device behind this renderer was lost, such as when the browser drops the
+    /// WebGL context or the GPU is reset. Nothing can be drawn until the renderer is replaced.
+    fn is_device_lost(&self) -> bool {
+        false
+    }
+
     fn compile_pixelbender_shader(
         &mut self,
         shader: PixelBenderShader,
@@ -111,6 +128,56 @@ pub trait RenderBackend: Downcast {
         handle: Box<dyn SyncHandle>,
         with_rgba: RgbaBufRead,
     ) -> Result<(), Error>;
+
+    /// Resolves several sync handles at once.
+    ///
+    /// Backends that can share a single GPU submission and wait between
+    /// multiple readbacks should override this; by default, each handle
+    /// is resolved individually.
+    fn resolve_sync_handles(
+        &mut self,
+        handles: Vec<(Box<dyn SyncHandle>, RgbaBufRead)>,
+    ) -> Result<(), Error> {
+        for (handle, with_rgba) in handles {
+            self.resolve_sync_handle(handle, with_rgba)?;
+        }
+        Ok(())
+    }
+
+    /// Renders `commands` into a new `width` by `height` image, and reads it back as RGBA.
+    ///
+    /// Nothing is presented. Colors are premultiplied by alpha, as with any other readback,
+    /// so callers wanting straight colors should draw over an opaque background.
+    ///
+    /// By default, this renders offscreen; backends without offscreen support must override it.
+    fn capture_frame(
+        &mut self,
+        commands: CommandList,
+        quality: StageQuality,
+        width: u32,
+        height: u32,
+    ) -> Result<Bitmap, Error> {
+        let target = self.create_empty_texture(width, height)?;
+        let sync_handle = self
+            .render_offscreen(
+                target,
+                commands,
+                quality,
+                PixelRegion::for_whole_size(width, height),
+            )
+            .ok_or_else(|| Error::Unimplemented("Frame capture".into()))?;
+
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        self.resolve_sync_handle(
+            sync_handle,
+            Box::new(|rgba, row_stride| {
+                for row in rgba.chunks(row_stride as usize).take(height as usize) {
+                    data.extend_from_slice(&row[..width as usize * 4]);
+                }
+            }),
+        )?;
+        Ok(Bitmap::new(width, height, BitmapFormat::Rgba, data))
+    }
 }
 impl_downcast!(RenderBackend);
 
@@ -303,6 +370,22 @@ pub enum Context3DTriangleFace {
     FrontAndBack,
 }
 
+impl Context3DTriangleFace {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"none" {
+            Some(Context3DTriangleFace::None)
+        } else if s == b"back" {
+            Some(Context3DTriangleFace::Back)
+        } else if s == b"front" {
+            Some(Context3DTriangleFace::Front)
+        } else if s == b"frontAndBack" {
+            Some(Context3DTriangleFace::FrontAndBack)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Context3DProfile {
     Baseline,
@@ -369,6 +452,42 @@ impl Context3DCompareMode {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub enum Context3DStencilAction {
+    DecrementSaturate,
+    DecrementWrap,
+    IncrementSaturate,
+    IncrementWrap,
+    Invert,
+    Keep,
+    Set,
+    Zero,
+}
+
+impl Context3DStencilAction {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"decrementSaturate" {
+            Some(Context3DStencilAction::DecrementSaturate)
+        } else if s == b"decrementWrap" {
+            Some(Context3DStencilAction::DecrementWrap)
+        } else if s == b"incrementSaturate" {
+            Some(Context3DStencilAction::IncrementSaturate)
+        } else if s == b"incrementWrap" {
+            Some(Context3DStencilAction::IncrementWrap)
+        } else if s == b"invert" {
+            Some(Context3DStencilAction::Invert)
+        } else if s == b"keep" {
+            Some(Context3DStencilAction::Keep)
+        } else if s == b"set" {
+            Some(Context3DStencilAction::Set)
+        } else if s == b"zero" {
+            Some(Context3DStencilAction::Zero)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Context3DWrapMode {
     Clamp,
@@ -512,6 +631,18 @@ pub enum Context3DCommand<'a> {
         depth_mask: bool,
         pass_compare_mode: Context3DCompareMode,
     },
+    SetStencilActions {
+        triangle_face: Context3DTriangleFace,
+        compare_mode: Context3DCompareMode,
+        action_on_both_pass: Context3DStencilAction,
+        action_on_depth_fail: Context3DStencilAction,
+        action_on_depth_pass_stencil_fail: Context3DStencilAction,
+    },
+    SetStencilReferenceValue {
+        reference_value: u32,
+        read_mask: u32,
+        write_mask: u32,
+    },
     SetBlendFactors {
         source_factor: Context3DBlendFactor,
         destination_factor: Context3DBlendFactor,
@@ -529,6 +660,20 @@ pub enum Context3DCommand<'a> {
 #[derive(Clone, Debug)]
 pub struct ShapeHandle(pub Arc<dyn ShapeHandleImpl>);
 
+/// Identifies a device that handles live on. See `RenderBackend::device_id`.
+///
+/// The default id is shared by renderers that never lose their device.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DeviceId(u32);
+
+impl DeviceId {
+    /// Allocates an id that no other device has.
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 pub trait ShapeHandleImpl: Downcast + Debug {}
 impl_downcast!(ShapeHandleImpl);
 
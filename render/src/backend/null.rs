@@ -10,6 +10,7 @@ use crate::bitmap::{
 };
 use crate::commands::CommandList;
 use crate::error::Error;
+use crate::pixel_bender::software::SoftwareShader;
 use crate::pixel_bender::{PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle};
 use crate::quality::StageQuality;
 use crate::shape_utils::DistilledShape;
@@ -131,11 +132,9 @@ impl RenderBackend for NullRenderer {
 
     fn compile_pixelbender_shader(
         &mut self,
-        _shader: PixelBenderShader,
+        shader: PixelBenderShader,
     ) -> Result<PixelBenderShaderHandle, Error> {
-        Err(Error::Unimplemented(
-            "Pixel bender shader compilation".into(),
-        ))
+        Ok(SoftwareShader::new_handle(shader))
     }
 
     fn create_empty_texture(&mut self, _width: u32, _height: u32) -> Result<BitmapHandle, Error> {
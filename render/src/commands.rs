@@ -2,8 +2,9 @@ use crate::backend::ShapeHandle;
 use crate::bitmap::{BitmapHandle, PixelSnapping};
 use crate::matrix::Matrix;
 use crate::pixel_bender::PixelBenderShaderHandle;
+use crate::projective::ProjectiveMatrix;
 use crate::transform::Transform;
-use swf::{BlendMode, Color};
+use swf::{BlendMode, Color, ColorTransform};
 
 pub trait CommandHandler {
     fn render_bitmap(
@@ -13,6 +14,14 @@ pub trait CommandHandler {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     );
+    /// Draws a bitmap seen in perspective, such as an object rotated around the x or y axis.
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: ProjectiveMatrix,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    );
     fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform);
     fn render_shape(&mut self, shape: ShapeHandle, transform: Transform);
     fn draw_rect(&mut self, color: Color, matrix: Matrix);
@@ -66,6 +75,12 @@ impl CommandList {
                     smoothing,
                     pixel_snapping,
                 } => handler.render_bitmap(bitmap, transform, smoothing, pixel_snapping),
+                Command::RenderBitmapProjected {
+                    bitmap,
+                    matrix,
+                    color_transform,
+                    smoothing,
+                } => handler.render_bitmap_projected(bitmap, matrix, color_transform, smoothing),
                 Command::RenderShape { shape, transform } => handler.render_shape(shape, transform),
                 Command::RenderStage3D { bitmap, transform } => {
                     handler.render_stage3d(bitmap, transform)
@@ -106,6 +121,24 @@ impl CommandHandler for CommandList {
         }
     }
 
+    #[inline]
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: ProjectiveMatrix,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        if self.maskers_in_progress <= 1 {
+            self.commands.push(Command::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            });
+        }
+    }
+
     #[inline]
     fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
         if self.maskers_in_progress <= 1 {
@@ -191,6 +224,12 @@ pub enum Command {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     },
+    RenderBitmapProjected {
+        bitmap: BitmapHandle,
+        matrix: ProjectiveMatrix,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    },
     RenderStage3D {
         bitmap: BitmapHandle,
         transform: Transform,
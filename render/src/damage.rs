@@ -0,0 +1,425 @@
+//! Finding the parts of the stage that have changed since the last frame.
+//!
+//! Backends that keep their last frame around only need to redraw the areas where the
+//! draws of a frame differ from the ones before it. Draws are compared by what they
+//! draw and where, so anything that moves, changes or disappears damages both the
+//! area that it used to cover and the one it covers now.
+
+use crate::backend::ShapeHandle;
+use crate::bitmap::BitmapHandle;
+use crate::commands::{Command, CommandList, RenderBlendMode};
+use crate::matrix::Matrix;
+use crate::projective::ProjectiveMatrix;
+use crate::transform::Transform;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use swf::{Color, ColorTransform, Rectangle, Twips};
+
+/// What a backend knows about the handles used by a frame.
+pub trait DamageSource {
+    /// The bounds of a registered shape, in its own coordinates, if they're known.
+    fn shape_bounds(&self, shape: &ShapeHandle) -> Option<Rectangle<Twips>>;
+
+    /// Whether a shape is filled with a bitmap whose contents changed since the last frame.
+    fn shape_changed(&self, shape: &ShapeHandle) -> bool;
+
+    /// The size of a bitmap in pixels, if it's known.
+    fn bitmap_size(&self, bitmap: &BitmapHandle) -> Option<(u32, u32)>;
+
+    /// Whether the contents of a bitmap changed since the last frame.
+    fn bitmap_changed(&self, bitmap: &BitmapHandle) -> bool;
+}
+
+/// The part of a frame that needs to be redrawn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Damage {
+    /// Everything has to be redrawn.
+    Full,
+
+    /// Only the given area has changed, in twips of the stage. It's invalid if nothing has.
+    Partial(Rectangle<Twips>),
+}
+
+/// A draw of a frame, identified by everything that affects what it draws.
+#[derive(Clone, Debug)]
+struct DrawRecord {
+    fingerprint: u64,
+    bounds: Rectangle<Twips>,
+    changed: bool,
+}
+
+/// Compares the draws of each frame to the ones of the frame before it.
+#[derive(Debug, Default)]
+pub struct DamageTracker {
+    /// The draws of the last frame, or `None` if it has to be redrawn entirely.
+    previous: Option<Vec<DrawRecord>>,
+}
+
+impl DamageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets the last frame, such as when the viewport has been resized, so that
+    /// the next one is redrawn entirely.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    /// Finds the damage of a new frame, and remembers its draws for the next one.
+    pub fn track(&mut self, commands: &CommandList, source: &dyn DamageSource) -> Damage {
+        let mut current = Vec::with_capacity(commands.commands.len());
+        if !record_draws(&commands.commands, source, 0, &mut current) {
+            self.previous = None;
+            return Damage::Full;
+        }
+
+        let Some(previous) = self.previous.replace(current) else {
+            return Damage::Full;
+        };
+        let current = self.previous.as_ref().expect("Just set");
+        compare(&previous, current)
+    }
+}
+
+/// Records the draws of a list of commands. Returns `false` if any of them has unknown bounds.
+fn record_draws(
+    commands: &[Command],
+    source: &dyn DamageSource,
+    nesting: u64,
+    records: &mut Vec<DrawRecord>,
+) -> bool {
+    // Draws of masks are told apart from the draws that they mask.
+    let mut mask_state = 0u8;
+    for command in commands {
+        let mut hasher = DefaultHasher::new();
+        nesting.hash(&mut hasher);
+        mask_state.hash(&mut hasher);
+        std::mem::discriminant(command).hash(&mut hasher);
+
+        let (bounds, changed) = match command {
+            Command::RenderBitmap {
+                bitmap,
+                transform,
+                smoothing,
+                pixel_snapping,
+            } => {
+                hash_arc(&bitmap.0, &mut hasher);
+                hash_transform(transform, &mut hasher);
+                smoothing.hash(&mut hasher);
+                std::mem::discriminant(pixel_snapping).hash(&mut hasher);
+                (
+                    source
+                        .bitmap_size(bitmap)
+                        .map(|size| transform.matrix * size_bounds(size)),
+                    source.bitmap_changed(bitmap),
+                )
+            }
+            Command::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => {
+                hash_arc(&bitmap.0, &mut hasher);
+                hash_projective_matrix(matrix, &mut hasher);
+                hash_color_transform(color_transform, &mut hasher);
+                smoothing.hash(&mut hasher);
+                (
+                    // Bitmaps reaching behind the viewer have no bounds.
+                    source
+                        .bitmap_size(bitmap)
+                        .and_then(|size| matrix.transform_rectangle(size_bounds(size))),
+                    source.bitmap_changed(bitmap),
+                )
+            }
+            // Stage3D draws into its bitmap without the backend seeing it.
+            Command::RenderStage3D { .. } => return false,
+            Command::RenderShape { shape, transform } => {
+                hash_arc(&shape.0, &mut hasher);
+                hash_transform(transform, &mut hasher);
+                (
+                    source
+                        .shape_bounds(shape)
+                        .map(|bounds| transform.matrix * bounds),
+                    source.shape_changed(shape),
+                )
+            }
+            Command::DrawRect { color, matrix }
+            | Command::DrawLine { color, matrix }
+            | Command::DrawLineRect { color, matrix } => {
+                hash_color(color, &mut hasher);
+                hash_matrix(matrix, &mut hasher);
+                (Some(*matrix * unit_square()), false)
+            }
+            Command::PushMask => {
+                mask_state = 1;
+                continue;
+            }
+            Command::ActivateMask => {
+                mask_state = 2;
+                continue;
+            }
+            Command::DeactivateMask => {
+                mask_state = 3;
+                continue;
+            }
+            Command::PopMask => {
+                mask_state = 0;
+                continue;
+            }
+            Command::Blend(commands, blend_mode) => {
+                match blend_mode {
+                    RenderBlendMode::Builtin(mode) => {
+                        std::mem::discriminant(mode).hash(&mut hasher)
+                    }
+                    RenderBlendMode::Shader(shader) => hash_arc(&shader.0, &mut hasher),
+                }
+                let mut inner = Vec::with_capacity(commands.commands.len());
+                if !record_draws(&commands.commands, source, nesting + 1, &mut inner) {
+                    return false;
+                }
+                let mut bounds = Rectangle::default();
+                let mut changed = false;
+                for record in &inner {
+                    record.fingerprint.hash(&mut hasher);
+                    bounds = bounds.union(&record.bounds);
+                    changed |= record.changed;
+                }
+                (Some(bounds), changed)
+            }
+        };
+
+        let Some(bounds) = bounds else {
+            return false;
+        };
+        records.push(DrawRecord {
+            fingerprint: hasher.finish(),
+            bounds,
+            changed,
+        });
+    }
+    true
+}
+
+/// Finds the area covered by draws that are only in one of the frames, or that changed.
+fn compare(previous: &[DrawRecord], current: &[DrawRecord]) -> Damage {
+    let mut counts: HashMap<u64, (usize, usize)> = HashMap::new();
+    for record in previous {
+        counts.entry(record.fingerprint).or_default().0 += 1;
+    }
+    for record in current {
+        counts.entry(record.fingerprint).or_default().1 += 1;
+    }
+    // Draws whose contents changed in place count as changed in both frames.
+    let changed: HashSet<u64> = current
+        .iter()
+        .filter(|record| record.changed)
+        .map(|record| record.fingerprint)
+        .collect();
+    let is_unchanged = |record: &DrawRecord| {
+        !changed.contains(&record.fingerprint)
+            && counts.get(&record.fingerprint).is_some_and(|(a, b)| a == b)
+    };
+
+    // Draws that stayed the same may still have been reordered, which can change
+    // what ends up on top.
+    let kept_previous = previous
+        .iter()
+        .filter(|record| is_unchanged(record))
+        .map(|record| record.fingerprint);
+    let kept_current = current
+        .iter()
+        .filter(|record| is_unchanged(record))
+        .map(|record| record.fingerprint);
+    if !kept_previous.eq(kept_current) {
+        return Damage::Full;
+    }
+
+    let damage = previous
+        .iter()
+        .chain(current)
+        .filter(|record| !is_unchanged(record))
+        .fold(Rectangle::default(), |damage, record| {
+            damage.union(&record.bounds)
+        });
+    Damage::Partial(damage)
+}
+
+/// The square that rectangles and lines are drawn in, before their matrix.
+fn unit_square() -> Rectangle<Twips> {
+    Rectangle {
+        x_min: Twips::ZERO,
+        y_min: Twips::ZERO,
+        x_max: Twips::ONE,
+        y_max: Twips::ONE,
+    }
+}
+
+fn size_bounds((width, height): (u32, u32)) -> Rectangle<Twips> {
+    Rectangle {
+        x_min: Twips::ZERO,
+        y_min: Twips::ZERO,
+        x_max: Twips::from_pixels_i32(width as i32),
+        y_max: Twips::from_pixels_i32(height as i32),
+    }
+}
+
+fn hash_arc<T: ?Sized>(arc: &Arc<T>, hasher: &mut impl Hasher) {
+    (Arc::as_ptr(arc) as *const () as usize).hash(hasher);
+}
+
+fn hash_color(color: &Color, hasher: &mut impl Hasher) {
+    [color.r, color.g, color.b, color.a].hash(hasher);
+}
+
+fn hash_matrix(matrix: &Matrix, hasher: &mut impl Hasher) {
+    [matrix.a, matrix.b, matrix.c, matrix.d]
+        .map(f32::to_bits)
+        .hash(hasher);
+    [matrix.tx, matrix.ty].map(Twips::get).hash(hasher);
+}
+
+fn hash_projective_matrix(matrix: &ProjectiveMatrix, hasher: &mut impl Hasher) {
+    [
+        matrix.a, matrix.b, matrix.c, matrix.d, matrix.tx, matrix.ty, matrix.p, matrix.q, matrix.r,
+    ]
+    .map(f64::to_bits)
+    .hash(hasher);
+}
+
+fn hash_transform(transform: &Transform, hasher: &mut impl Hasher) {
+    hash_matrix(&transform.matrix, hasher);
+    hash_color_transform(&transform.color_transform, hasher);
+}
+
+fn hash_color_transform(color_transform: &ColorTransform, hasher: &mut impl Hasher) {
+    [
+        color_transform.r_multiply,
+        color_transform.g_multiply,
+        color_transform.b_multiply,
+        color_transform.a_multiply,
+    ]
+    .hash(hasher);
+    [
+        color_transform.r_add,
+        color_transform.g_add,
+        color_transform.b_add,
+        color_transform.a_add,
+    ]
+    .hash(hasher);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ShapeHandleImpl;
+    use crate::commands::CommandHandler;
+
+    #[derive(Debug)]
+    struct TestShape;
+
+    impl ShapeHandleImpl for TestShape {}
+
+    struct TestSource;
+
+    impl DamageSource for TestSource {
+        fn shape_bounds(&self, _shape: &ShapeHandle) -> Option<Rectangle<Twips>> {
+            Some(size_bounds((10, 10)))
+        }
+
+        fn shape_changed(&self, _shape: &ShapeHandle) -> bool {
+            false
+        }
+
+        fn bitmap_changed(&self, _bitmap: &BitmapHandle) -> bool {
+            false
+        }
+
+        fn bitmap_size(&self, _bitmap: &BitmapHandle) -> Option<(u32, u32)> {
+            None
+        }
+    }
+
+    fn frame(shapes: &[(&ShapeHandle, i32)]) -> CommandList {
+        let mut commands = CommandList::new();
+        for (shape, x) in shapes {
+            commands.render_shape(
+                (*shape).clone(),
+                Transform {
+                    matrix: Matrix::translate(Twips::from_pixels_i32(*x), Twips::ZERO),
+                    color_transform: Default::default(),
+                },
+            );
+        }
+        commands
+    }
+
+    fn pixels(x_min: i32, y_min: i32, x_max: i32, y_max: i32) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::from_pixels_i32(x_min),
+            y_min: Twips::from_pixels_i32(y_min),
+            x_max: Twips::from_pixels_i32(x_max),
+            y_max: Twips::from_pixels_i32(y_max),
+        }
+    }
+
+    #[test]
+    fn first_frame_is_full() {
+        let shape = ShapeHandle(Arc::new(TestShape));
+        let mut tracker = DamageTracker::new();
+        assert_eq!(
+            tracker.track(&frame(&[(&shape, 0)]), &TestSource),
+            Damage::Full
+        );
+    }
+
+    #[test]
+    fn unchanged_frame_has_no_damage() {
+        let shape = ShapeHandle(Arc::new(TestShape));
+        let mut tracker = DamageTracker::new();
+        tracker.track(&frame(&[(&shape, 0)]), &TestSource);
+        assert_eq!(
+            tracker.track(&frame(&[(&shape, 0)]), &TestSource),
+            Damage::Partial(Rectangle::default())
+        );
+    }
+
+    #[test]
+    fn moved_shape_damages_both_positions() {
+        let still = ShapeHandle(Arc::new(TestShape));
+        let moving = ShapeHandle(Arc::new(TestShape));
+        let mut tracker = DamageTracker::new();
+        tracker.track(&frame(&[(&still, 100), (&moving, 0)]), &TestSource);
+        assert_eq!(
+            tracker.track(&frame(&[(&still, 100), (&moving, 20)]), &TestSource),
+            Damage::Partial(pixels(0, 0, 30, 10))
+        );
+    }
+
+    #[test]
+    fn reordered_shapes_are_full() {
+        let first = ShapeHandle(Arc::new(TestShape));
+        let second = ShapeHandle(Arc::new(TestShape));
+        let mut tracker = DamageTracker::new();
+        tracker.track(&frame(&[(&first, 0), (&second, 5)]), &TestSource);
+        assert_eq!(
+            tracker.track(&frame(&[(&second, 5), (&first, 0)]), &TestSource),
+            Damage::Full
+        );
+    }
+
+    #[test]
+    fn reset_forces_full() {
+        let shape = ShapeHandle(Arc::new(TestShape));
+        let mut tracker = DamageTracker::new();
+        tracker.track(&frame(&[(&shape, 0)]), &TestSource);
+        tracker.reset();
+        assert_eq!(
+            tracker.track(&frame(&[(&shape, 0)]), &TestSource),
+            Damage::Full
+        );
+    }
+}
@@ -81,6 +81,8 @@ impl Filter {
             Filter::DropShadowFilter(filter) => filter.calculate_dest_rect(source_rect),
             Filter::BevelFilter(filter) => filter.calculate_dest_rect(source_rect),
             Filter::DisplacementMapFilter(filter) => filter.calculate_dest_rect(source_rect),
+            Filter::GradientBevelFilter(filter) => filter.calculate_dest_rect(source_rect),
+            Filter::GradientGlowFilter(filter) => filter.calculate_dest_rect(source_rect),
             _ => source_rect,
         }
     }
@@ -97,6 +99,43 @@ impl Filter {
     }
 }
 
+/// The colors of the gradient of a `GradientGlowFilter` or `GradientBevelFilter`,
+/// one for each possible ratio.
+///
+/// Glows are colored by the strength of the glow at each pixel, and bevels by how much
+/// of the highlight (ratio 0) or the shadow (ratio 255) is at each pixel.
+pub fn gradient_filter_colors(records: &[swf::GradientRecord]) -> [Color; 256] {
+    let mut colors = [Color::from_rgba(0); 256];
+    let (Some(first), Some(last)) = (records.first(), records.last()) else {
+        return colors;
+    };
+    for (ratio, color) in colors.iter_mut().enumerate() {
+        let ratio = ratio as u8;
+        let next = records
+            .iter()
+            .position(|record| record.ratio >= ratio)
+            .unwrap_or(records.len());
+        *color = if next == 0 {
+            first.color
+        } else if next == records.len() {
+            last.color
+        } else {
+            let (a, b) = (&records[next - 1], &records[next]);
+            let t = (ratio.saturating_sub(a.ratio) as f32
+                / b.ratio.saturating_sub(a.ratio).max(1) as f32)
+                .min(1.0);
+            let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            Color {
+                r: lerp(a.color.r, b.color.r),
+                g: lerp(a.color.g, b.color.g),
+                b: lerp(a.color.b, b.color.b),
+                a: lerp(a.color.a, b.color.a),
+            }
+        };
+    }
+    colors
+}
+
 impl From<&swf::Filter> for Filter {
     fn from(value: &swf::Filter) -> Self {
         match value {
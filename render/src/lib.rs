@@ -4,11 +4,13 @@ pub mod atf;
 pub mod backend;
 pub mod bitmap;
 pub mod blend;
+pub mod damage;
 pub mod error;
 pub mod filters;
 pub mod lines;
 pub mod matrix;
 pub mod pixel_bender;
+pub mod projective;
 // The `renderdoc` crate doesn't compile on apple platforms
 #[cfg(all(feature = "renderdoc", not(target_vendor = "apple")))]
 pub mod renderdoc;
@@ -0,0 +1,785 @@
+//! A software evaluator for Pixel Bender shaders, for renderers that can't compile them
+//! into GPU shaders. It follows the semantics of our naga-pixelbender backend, so that
+//! the same movie looks the same on every renderer.
+
+use std::sync::Arc;
+
+use super::{
+    ImageInputTexture, Opcode, Operation, PixelBenderParam, PixelBenderParamQualifier,
+    PixelBenderReg, PixelBenderRegChannel, PixelBenderRegKind, PixelBenderShader,
+    PixelBenderShaderArgument, PixelBenderShaderHandle, PixelBenderShaderImpl, PixelBenderType,
+    OUT_COORD_NAME,
+};
+
+/// A shader that is run on the CPU with [`run_shader`].
+#[derive(Debug)]
+pub struct SoftwareShader(PixelBenderShader);
+
+impl SoftwareShader {
+    pub fn new_handle(shader: PixelBenderShader) -> PixelBenderShaderHandle {
+        PixelBenderShaderHandle(Arc::new(Self(shader)))
+    }
+}
+
+impl PixelBenderShaderImpl for SoftwareShader {
+    fn parsed_shader(&self) -> &PixelBenderShader {
+        &self.0
+    }
+}
+
+/// How samples outside of an input image are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfRangeMode {
+    /// The edge of the image is repeated, as when a shader is run by a `ShaderJob`.
+    Clamp,
+    /// Transparent black is read, as when a shader is run by a `ShaderFilter`.
+    Zero,
+}
+
+/// The pixels of an image input, with each channel as a float.
+#[derive(Debug, Clone)]
+pub struct SoftwareImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl SoftwareImage {
+    /// Reads premultiplied RGBA pixels, as stored in a `BitmapData`.
+    pub fn from_rgba8(width: u32, height: u32, rgba: &[u8]) -> Self {
+        let pixels = rgba
+            .chunks_exact(4)
+            .map(|p| [p[0], p[1], p[2], p[3]].map(|c| f32::from(c) / 255.0))
+            .collect();
+        Self::new(width, height, pixels)
+    }
+
+    /// Reads little-endian floats with `channels` channels per pixel, as stored in a
+    /// `ByteArray` or `Vector.<Number>`. Missing channels are read like the GPU reads them.
+    pub fn from_floats(width: u32, height: u32, channels: u32, bytes: &[u8]) -> Self {
+        let channels = channels.clamp(1, 4) as usize;
+        let pixels = bytes
+            .chunks_exact(channels * 4)
+            .map(|pixel| {
+                let mut value = match channels {
+                    // Textures without a green or blue channel read them as 0 and alpha as 1,
+                    // but our 3-channel textures are padded with an alpha of 0.
+                    1 | 2 => [0.0, 0.0, 0.0, 1.0],
+                    _ => [0.0; 4],
+                };
+                for (value, bytes) in value.iter_mut().zip(pixel.chunks_exact(4)) {
+                    *value = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                }
+                value
+            })
+            .collect();
+        Self::new(width, height, pixels)
+    }
+
+    /// Reads an image input that carries its own pixels, which is anything but a texture.
+    pub fn from_input(input: &ImageInputTexture) -> Option<Self> {
+        match input {
+            ImageInputTexture::Bytes {
+                width,
+                height,
+                channels,
+                bytes,
+            } => Some(Self::from_floats(*width, *height, *channels, bytes)),
+            ImageInputTexture::Bitmap(_) | ImageInputTexture::TextureRef(_) => None,
+        }
+    }
+
+    fn new(width: u32, height: u32, mut pixels: Vec<[f32; 4]>) -> Self {
+        pixels.resize((width * height) as usize, [0.0; 4]);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn pixel(&self, x: i64, y: i64) -> [f32; 4] {
+        let x = x.clamp(0, i64::from(self.width) - 1) as usize;
+        let y = y.clamp(0, i64::from(self.height) - 1) as usize;
+        self.pixels[y * self.width as usize + x]
+    }
+
+    fn sample(&self, x: f32, y: f32, linear: bool, mode: OutOfRangeMode) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0.0; 4];
+        }
+        let (width, height) = (self.width as f32, self.height as f32);
+        if mode == OutOfRangeMode::Zero && (x < 0.0 || x > width || y < 0.0 || y > height) {
+            return [0.0; 4];
+        }
+        if !linear {
+            return self.pixel(x.floor() as i64, y.floor() as i64);
+        }
+
+        // Texels are centered on half-pixel coordinates.
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top = lerp(self.pixel(x0, y0), self.pixel(x0 + 1, y0), fx);
+        let bottom = lerp(self.pixel(x0, y0 + 1), self.pixel(x0 + 1, y0 + 1), fx);
+        lerp(top, bottom, fy)
+    }
+}
+
+fn lerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+/// The pixels that a shader produced.
+#[derive(Debug, Clone)]
+pub struct SoftwareOutput {
+    /// How many channels the shader writes, either 3 or 4.
+    pub channels: usize,
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl SoftwareOutput {
+    /// Returns the pixels as premultiplied RGBA, for a `BitmapData`.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| {
+                let alpha = if self.channels == 3 { 1.0 } else { pixel[3] };
+                // Unlike `clamp`, this turns NaN into 0.
+                let alpha = alpha.max(0.0).min(1.0);
+                // Keep the pixel premultiplied, even if the shader produced a channel above its alpha.
+                let rgb = pixel.map(|c| c.clamp(0.0, alpha));
+                [rgb[0], rgb[1], rgb[2], alpha].map(|c| (c * 255.0).round() as u8)
+            })
+            .collect()
+    }
+
+    /// Returns the pixels as little-endian floats, for a `ByteArray` or `Vector.<Number>`.
+    pub fn to_float_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| &pixel[..self.channels])
+            .flat_map(|c| c.to_le_bytes())
+            .collect()
+    }
+}
+
+/// Runs `shader` for every pixel of a `width` by `height` output.
+///
+/// `images` are the image inputs by their texture index, while the values are taken from
+/// `arguments`. Inputs without an image read as transparent black.
+pub fn run_shader(
+    shader: &PixelBenderShader,
+    arguments: &[PixelBenderShaderArgument],
+    images: &[Option<SoftwareImage>],
+    width: u32,
+    height: u32,
+    mode: OutOfRangeMode,
+) -> SoftwareOutput {
+    let mut out_coord = None;
+    let mut output = None;
+    for param in &shader.params {
+        if let PixelBenderParam::Normal {
+            qualifier,
+            reg,
+            name,
+            ..
+        } = param
+        {
+            match qualifier {
+                PixelBenderParamQualifier::Input if name == OUT_COORD_NAME => out_coord = Some(reg),
+                PixelBenderParamQualifier::Output => output = Some(reg),
+                PixelBenderParamQualifier::Input => {}
+            }
+        }
+    }
+
+    let mut machine = Machine {
+        shader,
+        images,
+        mode,
+        ends: block_ends(&shader.operations),
+        float_registers: Vec::new(),
+        int_registers: Vec::new(),
+    };
+
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            machine.float_registers.fill([0.0; 4]);
+            machine.int_registers.fill([0.0; 4]);
+            machine.load_arguments(arguments);
+            // The coordinates of the pixel center, as the GPU passes them.
+            if let Some(reg) = out_coord {
+                machine.store(reg, [x as f32 + 0.5, y as f32 + 0.5, 0.0, 0.0]);
+            }
+            machine.execute();
+            pixels.push(output.map_or([0.0; 4], |reg| machine.register(reg)));
+        }
+    }
+
+    SoftwareOutput {
+        channels: output.map_or(4, |reg| reg.channels.len()),
+        pixels,
+    }
+}
+
+/// Runs the shader of `BlendMode.SHADER`, which blends `foreground` onto `background`.
+///
+/// Its first image input is what's below the display object, and its second one is the
+/// display object itself. The output has the size of `background`.
+pub fn run_blend_shader(
+    shader: &PixelBenderShader,
+    background: SoftwareImage,
+    foreground: SoftwareImage,
+) -> SoftwareOutput {
+    let (width, height) = (background.width, background.height);
+    run_shader(
+        shader,
+        &[],
+        &[Some(background), Some(foreground)],
+        width,
+        height,
+        OutOfRangeMode::Zero,
+    )
+}
+
+/// Finds the `Else` and `EndIf` that belong to every `If`.
+fn block_ends(operations: &[Operation]) -> Vec<(usize, usize)> {
+    let mut ends = vec![(0, 0); operations.len()];
+    let mut open: Vec<(usize, Option<usize>)> = Vec::new();
+    for (index, operation) in operations.iter().enumerate() {
+        match operation {
+            Operation::If { .. } => open.push((index, None)),
+            Operation::Else => {
+                if let Some((_, else_index)) = open.last_mut() {
+                    *else_index = Some(index);
+                }
+            }
+            Operation::EndIf => {
+                if let Some((if_index, else_index)) = open.pop() {
+                    ends[if_index] = (else_index.unwrap_or(index), index);
+                    if let Some(else_index) = else_index {
+                        ends[else_index] = (index, index);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    ends
+}
+
+/// A column-major matrix of 2x2, 3x3 or 4x4.
+#[derive(Debug, Clone, Copy)]
+struct Matrix {
+    size: usize,
+    columns: [[f32; 4]; 4],
+}
+
+impl Matrix {
+    fn mul(&self, other: &Matrix) -> Matrix {
+        let mut columns = [[0.0; 4]; 4];
+        for (column, other_column) in columns.iter_mut().zip(&other.columns).take(self.size) {
+            *column = self.mul_vec(other_column);
+        }
+        Matrix {
+            size: self.size,
+            columns,
+        }
+    }
+
+    fn mul_vec(&self, vector: &[f32; 4]) -> [f32; 4] {
+        let mut result = [0.0; 4];
+        for (column, scale) in self.columns.iter().zip(vector).take(self.size) {
+            for (result, value) in result.iter_mut().zip(column).take(self.size) {
+                *result += value * scale;
+            }
+        }
+        result
+    }
+
+    fn vec_mul(&self, vector: &[f32; 4]) -> [f32; 4] {
+        let mut result = [0.0; 4];
+        for (result, column) in result.iter_mut().zip(&self.columns).take(self.size) {
+            *result = dot(column, vector, self.size);
+        }
+        result
+    }
+}
+
+fn dot(a: &[f32; 4], b: &[f32; 4], size: usize) -> f32 {
+    a.iter().zip(b).take(size).map(|(a, b)| a * b).sum()
+}
+
+fn matrix_size(channel: PixelBenderRegChannel) -> Option<usize> {
+    match channel {
+        PixelBenderRegChannel::M2x2 => Some(2),
+        PixelBenderRegChannel::M3x3 => Some(3),
+        PixelBenderRegChannel::M4x4 => Some(4),
+        _ => None,
+    }
+}
+
+fn bool_to_float(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+struct Machine<'a> {
+    shader: &'a PixelBenderShader,
+    images: &'a [Option<SoftwareImage>],
+    mode: OutOfRangeMode,
+    /// For an `If`, its `Else` (or `EndIf`) and `EndIf`. For an `Else`, its `EndIf`.
+    ends: Vec<(usize, usize)>,
+    /// Int registers are kept as floats too, and truncated when they are written.
+    float_registers: Vec<[f32; 4]>,
+    int_registers: Vec<[f32; 4]>,
+}
+
+impl Machine<'_> {
+    fn registers(&mut self, kind: PixelBenderRegKind, index: usize) -> &mut [f32; 4] {
+        let registers = match kind {
+            PixelBenderRegKind::Float => &mut self.float_registers,
+            PixelBenderRegKind::Int => &mut self.int_registers,
+        };
+        if index >= registers.len() {
+            registers.resize(index + 1, [0.0; 4]);
+        }
+        &mut registers[index]
+    }
+
+    /// Returns the whole register, without any swizzle.
+    fn register(&mut self, reg: &PixelBenderReg) -> [f32; 4] {
+        *self.registers(reg.kind, reg.index as usize)
+    }
+
+    /// Loads a register through its swizzle. Components beyond the swizzle are meaningless.
+    fn load(&mut self, reg: &PixelBenderReg) -> [f32; 4] {
+        let value = self.register(reg);
+        let mut result = [value[3]; 4];
+        for (result, channel) in result.iter_mut().zip(&reg.channels) {
+            if matrix_size(*channel).is_none() {
+                *result = value[*channel as usize];
+            }
+        }
+        result
+    }
+
+    fn load_matrix(&mut self, reg: &PixelBenderReg) -> Option<Matrix> {
+        let size = matrix_size(*reg.channels.first()?)?;
+        let mut columns = [[0.0; 4]; 4];
+        if size == 2 {
+            // A 2x2 matrix is kept in a single register.
+            let value = self.registers(PixelBenderRegKind::Float, reg.index as usize);
+            columns[0] = [value[0], value[1], 0.0, 0.0];
+            columns[1] = [value[2], value[3], 0.0, 0.0];
+        } else {
+            for (i, column) in columns.iter_mut().enumerate().take(size) {
+                *column = *self.registers(PixelBenderRegKind::Float, reg.index as usize + i);
+            }
+        }
+        Some(Matrix { size, columns })
+    }
+
+    /// Stores the components of `value` in order to the channels of `reg`.
+    fn store(&mut self, reg: &PixelBenderReg, value: [f32; 4]) {
+        let kind = reg.kind;
+        let register = self.registers(kind, reg.index as usize);
+        for (channel, value) in reg.channels.iter().zip(value) {
+            if matrix_size(*channel).is_none() {
+                register[*channel as usize] = match kind {
+                    PixelBenderRegKind::Float => value,
+                    PixelBenderRegKind::Int => value as i32 as f32,
+                };
+            }
+        }
+    }
+
+    fn store_matrix(&mut self, reg: &PixelBenderReg, matrix: Matrix) {
+        if matrix.size == 2 {
+            let [c0, c1, ..] = matrix.columns;
+            *self.registers(PixelBenderRegKind::Float, reg.index as usize) =
+                [c0[0], c0[1], c1[0], c1[1]];
+        } else {
+            for (i, column) in matrix.columns.iter().enumerate().take(matrix.size) {
+                let register = self.registers(PixelBenderRegKind::Float, reg.index as usize + i);
+                register[..matrix.size].copy_from_slice(&column[..matrix.size]);
+            }
+        }
+    }
+
+    fn load_arguments(&mut self, arguments: &[PixelBenderShaderArgument]) {
+        for argument in arguments {
+            let PixelBenderShaderArgument::ValueInput { index, value } = argument else {
+                continue;
+            };
+            let Some(PixelBenderParam::Normal { reg, name, .. }) =
+                self.shader.params.get(*index as usize)
+            else {
+                continue;
+            };
+            if name == OUT_COORD_NAME {
+                continue;
+            }
+            let vector = match value {
+                PixelBenderType::TFloat(a) => [*a, 0.0, 0.0, 0.0],
+                PixelBenderType::TFloat2(a, b) => [*a, *b, 0.0, 0.0],
+                PixelBenderType::TFloat3(a, b, c) => [*a, *b, *c, 0.0],
+                PixelBenderType::TFloat4(a, b, c, d) => [*a, *b, *c, *d],
+                PixelBenderType::TInt(a) => [*a, 0, 0, 0].map(f32::from),
+                PixelBenderType::TInt2(a, b) => [*a, *b, 0, 0].map(f32::from),
+                PixelBenderType::TInt3(a, b, c) => [*a, *b, *c, 0].map(f32::from),
+                PixelBenderType::TInt4(a, b, c, d) => [*a, *b, *c, *d].map(f32::from),
+                // Matrices are given in column-major order, like Flash Player reads them.
+                PixelBenderType::TFloat2x2(m) => {
+                    let columns = [
+                        [m[0], m[1], 0.0, 0.0],
+                        [m[2], m[3], 0.0, 0.0],
+                        [0.0; 4],
+                        [0.0; 4],
+                    ];
+                    self.store_matrix(reg, Matrix { size: 2, columns });
+                    continue;
+                }
+                PixelBenderType::TFloat3x3(m) => {
+                    let column = |i: usize| [m[i * 3], m[i * 3 + 1], m[i * 3 + 2], 0.0];
+                    let columns = [column(0), column(1), column(2), [0.0; 4]];
+                    self.store_matrix(reg, Matrix { size: 3, columns });
+                    continue;
+                }
+                PixelBenderType::TFloat4x4(m) => {
+                    let columns = std::array::from_fn(|i| std::array::from_fn(|j| m[i * 4 + j]));
+                    self.store_matrix(reg, Matrix { size: 4, columns });
+                    continue;
+                }
+                PixelBenderType::TString(_) => continue,
+            };
+            self.store(reg, vector);
+        }
+    }
+
+    fn execute(&mut self) {
+        let mut pc = 0;
+        while let Some(operation) = self.shader.operations.get(pc) {
+            pc += 1;
+            match operation {
+                Operation::Nop | Operation::EndIf => {}
+                Operation::Normal { opcode, dst, src } => self.normal(*opcode, dst, src),
+                Operation::LoadInt { dst, val } => self.store(dst, [*val as f32; 4]),
+                Operation::LoadFloat { dst, val } => self.store(dst, [*val; 4]),
+                Operation::If { src } => {
+                    if self.load(src)[0] == 0.0 {
+                        // Continue after the `Else`, or after the `EndIf` without one.
+                        pc = self.ends[pc - 1].0 + 1;
+                    }
+                }
+                // Reaching an `Else` means the `If` branch ran, so skip to the `EndIf`.
+                Operation::Else => pc = self.ends[pc - 1].1 + 1,
+                Operation::SampleNearest { dst, src, tf } => self.sample(dst, src, *tf, false),
+                Operation::SampleLinear { dst, src, tf } => self.sample(dst, src, *tf, true),
+                Operation::Select {
+                    src1,
+                    src2,
+                    condition,
+                    dst,
+                } => {
+                    let value = if self.load(condition)[0] != 0.0 {
+                        self.load(src1)
+                    } else {
+                        self.load(src2)
+                    };
+                    self.store(dst, value);
+                }
+            }
+        }
+    }
+
+    fn sample(&mut self, dst: &PixelBenderReg, src: &PixelBenderReg, tf: u8, linear: bool) {
+        let coord = self.load(src);
+        let value = match self.images.get(tf as usize) {
+            Some(Some(image)) => image.sample(coord[0], coord[1], linear, self.mode),
+            _ => [0.0; 4],
+        };
+        self.store(dst, value);
+    }
+
+    fn normal(&mut self, opcode: Opcode, dst: &PixelBenderReg, src_reg: &PixelBenderReg) {
+        if let Some(src) = self.load_matrix(src_reg) {
+            match opcode {
+                Opcode::MatVecMul => {
+                    let vector = self.load(dst);
+                    self.store(dst, src.mul_vec(&vector));
+                }
+                Opcode::VecMatMul => {
+                    let vector = self.load(dst);
+                    self.store(dst, src.vec_mul(&vector));
+                }
+                Opcode::Mul | Opcode::MatMatMul => {
+                    if let Some(left) = self.load_matrix(dst) {
+                        self.store_matrix(dst, left.mul(&src));
+                    }
+                }
+                Opcode::Mov => self.store_matrix(dst, src),
+                _ => tracing::warn!("Unimplemented matrix opcode {opcode:?}"),
+            }
+            return;
+        }
+
+        let src = self.load(src_reg);
+        let left = self.load(dst);
+        let size = src_reg.channels.len();
+        let unary = |f: fn(f32) -> f32| src.map(f);
+        let binary = |f: fn(f32, f32) -> f32| std::array::from_fn(|i| f(left[i], src[i]));
+        let mut dst = dst.clone();
+
+        let result = match opcode {
+            Opcode::Nop => return,
+            Opcode::Mov => src,
+            Opcode::Add => binary(|a, b| a + b),
+            Opcode::Sub => binary(|a, b| a - b),
+            Opcode::Mul => binary(|a, b| a * b),
+            Opcode::Div => binary(|a, b| a / b),
+            Opcode::Rcp => unary(|a| 1.0 / a),
+            Opcode::Atan2 => binary(f32::atan2),
+            Opcode::Pow => binary(f32::powf),
+            // Like WGSL, the result takes the sign of the dividend.
+            Opcode::Mod => binary(|a, b| a % b),
+            Opcode::Min => binary(f32::min),
+            Opcode::Max => binary(f32::max),
+            Opcode::Step => binary(|edge, x| bool_to_float(x >= edge)),
+            Opcode::Sin => unary(f32::sin),
+            Opcode::Cos => unary(f32::cos),
+            Opcode::Tan => unary(f32::tan),
+            Opcode::Asin => unary(f32::asin),
+            Opcode::Acos => unary(f32::acos),
+            Opcode::Atan => unary(f32::atan),
+            Opcode::Exp => unary(f32::exp),
+            Opcode::Exp2 => unary(f32::exp2),
+            Opcode::Log => unary(f32::ln),
+            Opcode::Log2 => unary(f32::log2),
+            Opcode::Sqrt => unary(f32::sqrt),
+            Opcode::RSqrt => unary(|a| 1.0 / a.sqrt()),
+            Opcode::Abs => unary(f32::abs),
+            Opcode::Sign => unary(|a| if a == 0.0 { 0.0 } else { a.signum() }),
+            Opcode::Floor => unary(f32::floor),
+            Opcode::Ceil => unary(f32::ceil),
+            Opcode::Fract => unary(|a| a - a.floor()),
+            Opcode::FloatToInt => unary(f32::trunc),
+            Opcode::IntToFloat => src,
+            Opcode::Normalize => {
+                let length = dot(&src, &src, size).sqrt();
+                src.map(|a| a / length)
+            }
+            Opcode::Length => [dot(&src, &src, size).sqrt(); 4],
+            Opcode::Distance => {
+                let difference: [f32; 4] = std::array::from_fn(|i| left[i] - src[i]);
+                [dot(&difference, &difference, size).sqrt(); 4]
+            }
+            Opcode::DotProduct => [dot(&left, &src, size); 4],
+            Opcode::CrossProduct => [
+                left[1] * src[2] - left[2] * src[1],
+                left[2] * src[0] - left[0] * src[2],
+                left[0] * src[1] - left[1] * src[0],
+                0.0,
+            ],
+            Opcode::Equal
+            | Opcode::NotEqual
+            | Opcode::LessThan
+            | Opcode::LessThanEqual
+            | Opcode::VectorEqual
+            | Opcode::VectorNotEqual => {
+                let compare = |a: f32, b: f32| match opcode {
+                    Opcode::Equal | Opcode::VectorEqual => a == b,
+                    Opcode::NotEqual | Opcode::VectorNotEqual => a != b,
+                    Opcode::LessThan => a < b,
+                    _ => a <= b,
+                };
+                let result = match opcode {
+                    Opcode::VectorEqual => (0..size).all(|i| compare(left[i], src[i])),
+                    Opcode::VectorNotEqual => (0..size).any(|i| compare(left[i], src[i])),
+                    _ => compare(left[0], src[0]),
+                };
+                // Comparisons write their result to the first component of int register 0.
+                dst = PixelBenderReg {
+                    index: 0,
+                    channels: vec![PixelBenderRegChannel::R],
+                    kind: PixelBenderRegKind::Int,
+                };
+                [bool_to_float(result); 4]
+            }
+            Opcode::LogicalNot => unary(|a| bool_to_float(a == 0.0)),
+            Opcode::LogicalAnd => binary(|a, b| bool_to_float(a != 0.0 && b != 0.0)),
+            Opcode::LogicalOr => binary(|a, b| bool_to_float(a != 0.0 || b != 0.0)),
+            Opcode::LogicalXor => binary(|a, b| bool_to_float((a != 0.0) != (b != 0.0))),
+            Opcode::FloatToBool | Opcode::IntToBool => unary(|a| bool_to_float(a != 0.0)),
+            Opcode::BoolToFloat | Opcode::BoolToInt => src,
+            Opcode::BoolAny => [bool_to_float(src[..size].iter().any(|a| *a != 0.0)); 4],
+            Opcode::BoolAll => [bool_to_float(src[..size].iter().all(|a| *a != 0.0)); 4],
+            _ => {
+                tracing::warn!("Unimplemented opcode {opcode:?}");
+                return;
+            }
+        };
+        self.store(&dst, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixel_bender::PixelBenderTypeOpcode;
+    use PixelBenderRegChannel::{A, B, G, R};
+
+    fn reg(index: u32, channels: &[PixelBenderRegChannel]) -> PixelBenderReg {
+        PixelBenderReg {
+            index,
+            channels: channels.to_vec(),
+            kind: PixelBenderRegKind::Float,
+        }
+    }
+
+    fn param(
+        qualifier: PixelBenderParamQualifier,
+        param_type: PixelBenderTypeOpcode,
+        reg: PixelBenderReg,
+        name: &str,
+    ) -> PixelBenderParam {
+        PixelBenderParam::Normal {
+            qualifier,
+            param_type,
+            reg,
+            name: name.to_string(),
+            metadata: vec![],
+        }
+    }
+
+    /// `dst = sampleNearest(src, outCoord()); if (dst.r > threshold) dst.rgb = 1 - dst.rgb;`
+    fn invert_bright_pixels() -> PixelBenderShader {
+        PixelBenderShader {
+            name: "InvertBright".to_string(),
+            version: 1,
+            params: vec![
+                param(
+                    PixelBenderParamQualifier::Input,
+                    PixelBenderTypeOpcode::TFloat2,
+                    reg(0, &[R, G]),
+                    OUT_COORD_NAME,
+                ),
+                PixelBenderParam::Texture {
+                    index: 0,
+                    channels: 4,
+                    name: "src".to_string(),
+                },
+                param(
+                    PixelBenderParamQualifier::Output,
+                    PixelBenderTypeOpcode::TFloat4,
+                    reg(1, &[R, G, B, A]),
+                    "dst",
+                ),
+                param(
+                    PixelBenderParamQualifier::Input,
+                    PixelBenderTypeOpcode::TFloat,
+                    reg(0, &[B]),
+                    "threshold",
+                ),
+            ],
+            metadata: vec![],
+            operations: vec![
+                Operation::SampleNearest {
+                    dst: reg(1, &[R, G, B, A]),
+                    src: reg(0, &[R, G]),
+                    tf: 0,
+                },
+                Operation::Normal {
+                    opcode: Opcode::Mov,
+                    dst: reg(2, &[R]),
+                    src: reg(0, &[B]),
+                },
+                Operation::Normal {
+                    opcode: Opcode::LessThan,
+                    dst: reg(2, &[R]),
+                    src: reg(1, &[R]),
+                },
+                Operation::If {
+                    src: PixelBenderReg {
+                        index: 0,
+                        channels: vec![R],
+                        kind: PixelBenderRegKind::Int,
+                    },
+                },
+                Operation::LoadFloat {
+                    dst: reg(3, &[R, G, B]),
+                    val: 1.0,
+                },
+                Operation::Normal {
+                    opcode: Opcode::Sub,
+                    dst: reg(3, &[R, G, B]),
+                    src: reg(1, &[R, G, B]),
+                },
+                Operation::Normal {
+                    opcode: Opcode::Mov,
+                    dst: reg(1, &[R, G, B]),
+                    src: reg(3, &[R, G, B]),
+                },
+                Operation::EndIf,
+            ],
+        }
+    }
+
+    #[test]
+    fn runs_per_pixel_with_branches() {
+        let shader = invert_bright_pixels();
+        let image = SoftwareImage::from_rgba8(2, 1, &[255, 0, 51, 255, 51, 102, 0, 255]);
+        let arguments = [PixelBenderShaderArgument::ValueInput {
+            index: 3,
+            value: PixelBenderType::TFloat(0.5),
+        }];
+        let output = run_shader(
+            &shader,
+            &arguments,
+            &[Some(image)],
+            2,
+            1,
+            OutOfRangeMode::Clamp,
+        );
+        assert_eq!(output.channels, 4);
+        assert_eq!(output.to_rgba8(), vec![0, 255, 204, 255, 51, 102, 0, 255]);
+    }
+
+    #[test]
+    fn samples_out_of_range() {
+        let image = SoftwareImage::from_floats(1, 1, 1, &0.5f32.to_le_bytes());
+        assert_eq!(
+            image.sample(3.0, 0.5, true, OutOfRangeMode::Clamp),
+            [0.5, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(image.sample(3.0, 0.5, true, OutOfRangeMode::Zero), [0.0; 4]);
+    }
+
+    #[test]
+    fn multiplies_matrices() {
+        let mut machine = Machine {
+            shader: &invert_bright_pixels(),
+            images: &[],
+            mode: OutOfRangeMode::Clamp,
+            ends: vec![],
+            float_registers: vec![],
+            int_registers: vec![],
+        };
+        let matrix = reg(4, &[PixelBenderRegChannel::M2x2]);
+        // Column-major [[1, 2], [3, 4]], as in `float2x2(1, 2, 3, 4)`.
+        machine.store(&reg(4, &[R, G, B, A]), [1.0, 2.0, 3.0, 4.0]);
+        machine.store(&reg(0, &[R, G]), [1.0, 1.0, 0.0, 0.0]);
+        machine.normal(Opcode::MatVecMul, &reg(0, &[R, G]), &matrix);
+        assert_eq!(machine.load(&reg(0, &[R, G]))[..2], [4.0, 6.0]);
+
+        machine.store(&reg(0, &[R, G]), [1.0, 1.0, 0.0, 0.0]);
+        machine.normal(Opcode::VecMatMul, &reg(0, &[R, G]), &matrix);
+        assert_eq!(machine.load(&reg(0, &[R, G]))[..2], [3.0, 7.0]);
+    }
+}
@@ -0,0 +1,269 @@
+//! Projective transforms, which draw flat content in perspective.
+
+use crate::matrix::Matrix;
+use swf::{Point, Rectangle, Twips};
+
+/// A 2D projective transform, which can map a rectangle onto any quadrilateral.
+///
+/// A point `(x, y)` in twips is mapped to `((a * x + c * y + tx) / w, (b * x + d * y + ty) / w)`,
+/// where `w = p * x + q * y + r`. This is how a flat object placed in 3D space looks once seen
+/// in perspective. With `p = q = 0` and `r = 1`, this is the same as a `Matrix`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProjectiveMatrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub tx: f64,
+    pub ty: f64,
+    pub p: f64,
+    pub q: f64,
+    pub r: f64,
+}
+
+impl ProjectiveMatrix {
+    pub const IDENTITY: Self = Self {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+        p: 0.0,
+        q: 0.0,
+        r: 1.0,
+    };
+
+    /// Maps a point, or returns `None` if it ends up behind the viewer.
+    pub fn transform_point(&self, point: Point<Twips>) -> Option<Point<Twips>> {
+        let (x, y) = (point.x.get() as f64, point.y.get() as f64);
+        let w = self.p * x + self.q * y + self.r;
+        if w <= f64::EPSILON {
+            return None;
+        }
+        Some(Point::new(
+            Twips::new(((self.a * x + self.c * y + self.tx) / w).round() as i32),
+            Twips::new(((self.b * x + self.d * y + self.ty) / w).round() as i32),
+        ))
+    }
+
+    /// The bounding box of a rectangle once it's been mapped, or `None` if any part of it ends
+    /// up behind the viewer.
+    pub fn transform_rectangle(&self, rect: Rectangle<Twips>) -> Option<Rectangle<Twips>> {
+        if !rect.is_valid() {
+            return Some(Default::default());
+        }
+        let corners = [
+            Point::new(rect.x_min, rect.y_min),
+            Point::new(rect.x_max, rect.y_min),
+            Point::new(rect.x_min, rect.y_max),
+            Point::new(rect.x_max, rect.y_max),
+        ];
+        let mut bounds = Rectangle::default();
+        for corner in corners {
+            bounds = bounds.encompass(self.transform_point(corner)?);
+        }
+        Some(bounds)
+    }
+
+    /// The affine matrix that matches this transform most closely around the given point.
+    ///
+    /// Anything that can only handle a `Matrix`, such as hit tests, uses this.
+    pub fn linearize_at(&self, point: Point<Twips>) -> Matrix {
+        let (x, y) = (point.x.get() as f64, point.y.get() as f64);
+        // Anything behind the viewer is squashed onto them.
+        let w = (self.p * x + self.q * y + self.r).max(f64::EPSILON);
+        let mapped_x = (self.a * x + self.c * y + self.tx) / w;
+        let mapped_y = (self.b * x + self.d * y + self.ty) / w;
+
+        // The derivatives of the mapping at the point.
+        let a = (self.a - mapped_x * self.p) / w;
+        let b = (self.b - mapped_y * self.p) / w;
+        let c = (self.c - mapped_x * self.q) / w;
+        let d = (self.d - mapped_y * self.q) / w;
+        Matrix {
+            a: a as f32,
+            b: b as f32,
+            c: c as f32,
+            d: d as f32,
+            tx: Twips::new((mapped_x - a * x - c * y).round() as i32),
+            ty: Twips::new((mapped_y - b * x - d * y).round() as i32),
+        }
+    }
+
+    /// This transform as a column-major 4x4 matrix working in pixels, as shaders use it.
+    ///
+    /// The perspective ends up in the `w` coordinate, so that the GPU divides by it and
+    /// interpolates texture coordinates correctly.
+    pub fn to_pixel_matrix4(&self) -> [[f32; 4]; 4] {
+        let twips_per_pixel = Twips::TWIPS_PER_PIXEL as f64;
+        [
+            [
+                self.a as f32,
+                self.b as f32,
+                0.0,
+                (self.p * twips_per_pixel) as f32,
+            ],
+            [
+                self.c as f32,
+                self.d as f32,
+                0.0,
+                (self.q * twips_per_pixel) as f32,
+            ],
+            [0.0, 0.0, 1.0, 0.0],
+            [
+                (self.tx / twips_per_pixel) as f32,
+                (self.ty / twips_per_pixel) as f32,
+                0.0,
+                self.r as f32,
+            ],
+        ]
+    }
+}
+
+impl Default for ProjectiveMatrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Matrix> for ProjectiveMatrix {
+    fn from(matrix: Matrix) -> Self {
+        Self {
+            a: matrix.a as f64,
+            b: matrix.b as f64,
+            c: matrix.c as f64,
+            d: matrix.d as f64,
+            tx: matrix.tx.get() as f64,
+            ty: matrix.ty.get() as f64,
+            p: 0.0,
+            q: 0.0,
+            r: 1.0,
+        }
+    }
+}
+
+impl std::ops::Mul for ProjectiveMatrix {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            a: self.a * rhs.a + self.c * rhs.b + self.tx * rhs.p,
+            b: self.b * rhs.a + self.d * rhs.b + self.ty * rhs.p,
+            c: self.a * rhs.c + self.c * rhs.d + self.tx * rhs.q,
+            d: self.b * rhs.c + self.d * rhs.d + self.ty * rhs.q,
+            tx: self.a * rhs.tx + self.c * rhs.ty + self.tx * rhs.r,
+            ty: self.b * rhs.tx + self.d * rhs.ty + self.ty * rhs.r,
+            p: self.p * rhs.a + self.q * rhs.b + self.r * rhs.p,
+            q: self.p * rhs.c + self.q * rhs.d + self.r * rhs.q,
+            r: self.p * rhs.tx + self.q * rhs.ty + self.r * rhs.r,
+        }
+    }
+}
+
+impl std::ops::Mul<Matrix> for ProjectiveMatrix {
+    type Output = Self;
+
+    fn mul(self, rhs: Matrix) -> Self {
+        self * Self::from(rhs)
+    }
+}
+
+impl std::ops::Mul<ProjectiveMatrix> for Matrix {
+    type Output = ProjectiveMatrix;
+
+    fn mul(self, rhs: ProjectiveMatrix) -> ProjectiveMatrix {
+        ProjectiveMatrix::from(self) * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Twice as far away at y = 100 pixels as at y = 0.
+    fn tilted() -> ProjectiveMatrix {
+        ProjectiveMatrix {
+            p: 0.0,
+            q: 1.0 / 2000.0,
+            ..ProjectiveMatrix::IDENTITY
+        }
+    }
+
+    fn pixels(x: f64, y: f64) -> Point<Twips> {
+        Point::new(Twips::from_pixels(x), Twips::from_pixels(y))
+    }
+
+    #[test]
+    fn affine_matrices_are_unchanged() {
+        let matrix = Matrix {
+            a: 2.0,
+            b: 0.5,
+            c: -0.5,
+            d: 3.0,
+            tx: Twips::from_pixels(10.0),
+            ty: Twips::from_pixels(-20.0),
+        };
+        let projective = ProjectiveMatrix::from(matrix);
+        for point in [pixels(0.0, 0.0), pixels(15.0, -7.0), pixels(100.0, 40.0)] {
+            assert_eq!(projective.transform_point(point), Some(matrix * point));
+        }
+        assert_eq!(projective.linearize_at(pixels(5.0, 5.0)), matrix);
+    }
+
+    #[test]
+    fn far_side_is_smaller() {
+        let projective = tilted();
+        assert_eq!(
+            projective.transform_point(pixels(100.0, 0.0)),
+            Some(pixels(100.0, 0.0))
+        );
+        // Twice as far away at y = 100, so half as large.
+        assert_eq!(
+            projective.transform_point(pixels(100.0, 100.0)),
+            Some(pixels(50.0, 50.0))
+        );
+    }
+
+    #[test]
+    fn behind_viewer_is_rejected() {
+        let projective = ProjectiveMatrix {
+            q: -1.0 / 2000.0,
+            ..ProjectiveMatrix::IDENTITY
+        };
+        assert!(projective.transform_point(pixels(0.0, 100.0)).is_none());
+        assert!(projective
+            .transform_rectangle(Rectangle {
+                x_min: Twips::ZERO,
+                y_min: Twips::ZERO,
+                x_max: Twips::from_pixels(10.0),
+                y_max: Twips::from_pixels(200.0),
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn multiplication_composes_mappings() {
+        let first = tilted();
+        let second = Matrix::translate(Twips::from_pixels(30.0), Twips::from_pixels(40.0));
+        let point = pixels(60.0, 20.0);
+        let composed = second * first;
+        let expected = first.transform_point(point).map(|point| second * point);
+        assert_eq!(composed.transform_point(point), expected);
+    }
+
+    #[test]
+    fn linearization_matches_nearby_points() {
+        let projective = tilted();
+        let center = pixels(50.0, 50.0);
+        let matrix = projective.linearize_at(center);
+        for point in [center, pixels(51.0, 51.0), pixels(49.0, 50.0)] {
+            let exact = projective
+                .transform_point(point)
+                .expect("In front of the viewer");
+            let approximate = matrix * point;
+            assert!((exact.x - approximate.x).get().abs() <= 1);
+            assert!((exact.y - approximate.y).get().abs() <= 1);
+        }
+    }
+}
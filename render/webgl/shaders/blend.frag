@@ -0,0 +1,74 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform mat4 view_matrix;
+uniform mat4 world_matrix;
+uniform vec4 mult_color;
+uniform vec4 add_color;
+uniform mat3 u_matrix;
+
+// The contents of the blend.
+uniform sampler2D u_texture;
+// What the contents are blended onto.
+uniform sampler2D u_parent_texture;
+uniform int u_blend_mode;
+
+varying vec2 frag_uv;
+
+vec3 blend_func(vec3 src, vec3 dst) {
+    if (u_blend_mode == 0) {
+        // Multiply
+        return src * dst;
+    } else if (u_blend_mode == 1) {
+        // Lighten
+        return max(src, dst);
+    } else if (u_blend_mode == 2) {
+        // Darken
+        return min(src, dst);
+    } else if (u_blend_mode == 3) {
+        // Difference
+        return abs(dst - src);
+    } else if (u_blend_mode == 4) {
+        // Invert
+        return 1.0 - dst;
+    } else if (u_blend_mode == 7) {
+        // Overlay
+        return mix(2.0 * src * dst, 1.0 - 2.0 * (1.0 - dst) * (1.0 - src), step(0.5, dst));
+    } else {
+        // HardLight
+        return mix(2.0 * src * dst, 1.0 - 2.0 * (1.0 - dst) * (1.0 - src), step(0.5, src));
+    }
+}
+
+void main() {
+    // dst is the parent pixel we're blending onto
+    vec4 dst = texture2D(u_parent_texture, frag_uv);
+    // src is the pixel that we want to apply
+    vec4 src = texture2D(u_texture, frag_uv);
+
+    if (src.a <= 0.0) {
+        discard;
+    }
+
+    if (u_blend_mode == 5) {
+        // Alpha
+        gl_FragColor = vec4(dst.rgb * src.a, src.a * dst.a);
+    } else if (u_blend_mode == 6) {
+        // Erase
+        gl_FragColor = vec4(dst.rgb * (1.0 - src.a), (1.0 - src.a) * dst.a);
+    } else if (dst.a <= 0.0) {
+        // Nothing to blend with, which Flash also does when multiplying.
+        gl_FragColor = src;
+    } else {
+        vec3 blended = blend_func(src.rgb / src.a, dst.rgb / dst.a);
+        gl_FragColor = vec4(
+            src.rgb * (1.0 - dst.a) + dst.rgb * (1.0 - src.a) + src.a * dst.a * blended,
+            src.a + dst.a * (1.0 - src.a)
+        );
+    }
+}
@@ -4,7 +4,7 @@
 
 use bytemuck::{Pod, Zeroable};
 use ruffle_render::backend::{
-    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    BitmapCacheEntry, Context3D, Context3DProfile, DeviceId, PixelBenderOutput, PixelBenderTarget,
     RenderBackend, ShapeHandle, ShapeHandleImpl, ViewportDimensions,
 };
 use ruffle_render::bitmap::{
@@ -12,8 +12,12 @@ use ruffle_render::bitmap::{
     RgbaBufRead, SyncHandle,
 };
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::damage::{Damage, DamageSource, DamageTracker};
 use ruffle_render::error::Error as BitmapError;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::pixel_bender::software::{self, SoftwareImage};
+use ruffle_render::pixel_bender::PixelBenderShaderImpl;
+use ruffle_render::projective::ProjectiveMatrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
@@ -22,8 +26,9 @@ use ruffle_render::tessellator::{
 use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::sync::Arc;
-use swf::{BlendMode, Color, Twips};
+use swf::{BlendMode, Color, ColorTransform, Rectangle, Twips};
 use thiserror::Error;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -79,6 +84,7 @@ const COLOR_FRAGMENT_GLSL: &str = include_str!("../shaders/color.frag");
 const TEXTURE_VERTEX_GLSL: &str = include_str!("../shaders/texture.vert");
 const GRADIENT_FRAGMENT_GLSL: &str = include_str!("../shaders/gradient.frag");
 const BITMAP_FRAGMENT_GLSL: &str = include_str!("../shaders/bitmap.frag");
+const BLEND_FRAGMENT_GLSL: &str = include_str!("../shaders/blend.frag");
 const NUM_VERTEX_ATTRIBUTES: u32 = 2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -124,14 +130,19 @@ pub struct WebGlRenderBackend {
     msaa_buffers: Option<MsaaBuffers>,
     msaa_sample_count: u32,
 
+    // The most MSAA samples this device supports, which is 1 without WebGL2.
+    max_msaa_sample_count: u32,
+
     color_program: ShaderProgram,
     bitmap_program: ShaderProgram,
     gradient_program: ShaderProgram,
+    blend_program: ShaderProgram,
 
     shape_tessellator: ShapeTessellator,
 
     color_quad_draws: Vec<Draw>,
     bitmap_quad_draws: Vec<Draw>,
+    blend_quad_draws: Vec<Draw>,
 
     mask_state: MaskState,
     num_masks: u32,
@@ -139,7 +150,6 @@ pub struct WebGlRenderBackend {
     is_transparent: bool,
 
     active_program: *const ShaderProgram,
-    blend_modes: Vec<RenderBlendMode>,
     mult_color: Option<[f32; 4]>,
     add_color: Option<[f32; 4]>,
 
@@ -147,9 +157,31 @@ pub struct WebGlRenderBackend {
     renderbuffer_height: i32,
     view_matrix: [[f32; 4]; 4],
 
+    /// Where draws currently end up.
+    target: RenderTarget,
+
+    /// The framebuffer of the innermost `BlendMode::Layer` that is being drawn, which
+    /// `BlendMode::Alpha` and `BlendMode::Erase` apply to.
+    nearest_layer: Option<WebGlFramebuffer>,
+
     // This is currently unused - we just hold on to it
     // to expose via `get_viewport_dimensions`
     viewport_scale_factor: f64,
+
+    /// Finds the parts of each frame that changed, so that only those are redrawn.
+    /// This needs the MSAA render buffer, which keeps the last frame around.
+    damage_tracker: DamageTracker,
+
+    /// The bitmaps whose contents changed since the last frame, by address.
+    changed_bitmaps: HashSet<usize>,
+
+    /// The clear color and transparency of the last frame. The stage is redrawn
+    /// entirely when they change, as they affect every pixel that nothing is drawn over.
+    last_clear: Option<(Color, bool)>,
+
+    /// Identifies this context. A context that was lost and restored needs a new renderer,
+    /// so this never changes.
+    device_id: DeviceId,
 }
 
 #[derive(Debug)]
@@ -196,7 +228,7 @@ impl WebGlRenderBackend {
         }
 
         // Attempt to create a WebGL2 context, but fall back to WebGL1 if unavailable.
-        let (gl, gl2, vao_ext, msaa_sample_count) = if let Ok(Some(gl)) =
+        let (gl, gl2, vao_ext, max_msaa_sample_count) = if let Ok(Some(gl)) =
             canvas.get_context_with_context_options("webgl2", &context_options)
         {
             log::info!("Creating WebGL2 context.");
@@ -204,15 +236,14 @@ impl WebGlRenderBackend {
                 .dyn_into::<Gl2>()
                 .map_err(|_| Error::CantCreateGLContext)?;
 
-            // Determine MSAA sample count.
-            let mut msaa_sample_count = quality.sample_count().min(4);
-
-            // Ensure that we don't exceed the max MSAA of this device.
+            // Determine the MSAA sample count, ensuring that we don't exceed the max MSAA
+            // of this device.
+            let mut max_msaa_sample_count = 4;
             if let Ok(max_samples) = gl2.get_parameter(Gl2::MAX_SAMPLES) {
                 let max_samples = max_samples.as_f64().unwrap_or(0.0) as u32;
-                if max_samples > 0 && max_samples < msaa_sample_count {
+                if max_samples > 0 && max_samples < max_msaa_sample_count {
                     log::info!("Device only supports {}xMSAA", max_samples);
-                    msaa_sample_count = max_samples;
+                    max_msaa_sample_count = max_samples;
                 }
             }
 
@@ -221,7 +252,7 @@ impl WebGlRenderBackend {
                 gl2.clone().unchecked_into::<Gl>(),
                 Some(gl2),
                 JsValue::UNDEFINED.unchecked_into(),
-                msaa_sample_count,
+                max_msaa_sample_count,
             )
         } else {
             // Fall back to WebGL1.
@@ -275,10 +306,12 @@ impl WebGlRenderBackend {
         let bitmap_fragment = Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, BITMAP_FRAGMENT_GLSL)?;
         let gradient_fragment =
             Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, GRADIENT_FRAGMENT_GLSL)?;
+        let blend_fragment = Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, BLEND_FRAGMENT_GLSL)?;
 
         let color_program = ShaderProgram::new(&gl, &color_vertex, &color_fragment)?;
         let bitmap_program = ShaderProgram::new(&gl, &texture_vertex, &bitmap_fragment)?;
         let gradient_program = ShaderProgram::new(&gl, &texture_vertex, &gradient_fragment)?;
+        let blend_program = ShaderProgram::new(&gl, &texture_vertex, &blend_fragment)?;
 
         gl.enable(Gl::BLEND);
 
@@ -291,39 +324,56 @@ impl WebGlRenderBackend {
             vao_ext,
 
             msaa_buffers: None,
-            msaa_sample_count,
+            msaa_sample_count: quality.sample_count().min(max_msaa_sample_count),
+            max_msaa_sample_count,
 
             color_program,
             gradient_program,
             bitmap_program,
+            blend_program,
 
             shape_tessellator: ShapeTessellator::new(),
 
             color_quad_draws: vec![],
             bitmap_quad_draws: vec![],
+            blend_quad_draws: vec![],
             renderbuffer_width: 1,
             renderbuffer_height: 1,
             view_matrix: [[0.0; 4]; 4],
 
+            target: RenderTarget {
+                framebuffer: None,
+                width: 1,
+                height: 1,
+            },
+            nearest_layer: None,
+
             mask_state: MaskState::NoMask,
             num_masks: 0,
             mask_state_dirty: true,
             is_transparent,
 
             active_program: std::ptr::null(),
-            blend_modes: vec![],
             mult_color: None,
             add_color: None,
 
             viewport_scale_factor: 1.0,
+
+            damage_tracker: DamageTracker::new(),
+            changed_bitmaps: HashSet::new(),
+            last_clear: None,
+
+            device_id: DeviceId::new(),
         };
 
-        renderer.push_blend_mode(RenderBlendMode::Builtin(BlendMode::Normal));
+        renderer.apply_blend_mode(BlendMode::Normal);
 
         let mut color_quad_mesh = renderer.build_quad_mesh(&renderer.color_program)?;
         let mut bitmap_quad_mesh = renderer.build_quad_mesh(&renderer.bitmap_program)?;
+        let mut blend_quad_mesh = renderer.build_quad_mesh(&renderer.blend_program)?;
         renderer.color_quad_draws.append(&mut color_quad_mesh);
         renderer.bitmap_quad_draws.append(&mut bitmap_quad_mesh);
+        renderer.blend_quad_draws.append(&mut blend_quad_mesh);
 
         renderer.set_viewport_dimensions(ViewportDimensions {
             width: 1,
@@ -444,6 +494,9 @@ impl WebGlRenderBackend {
     }
 
     fn build_msaa_buffers(&mut self) -> Result<(), Error> {
+        // The last frame is gone along with the old buffers.
+        self.damage_tracker.reset();
+
         if self.gl2.is_none() || self.msaa_sample_count <= 1 {
             self.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
             self.gl.bind_renderbuffer(Gl::RENDERBUFFER, None);
@@ -757,22 +810,23 @@ impl WebGlRenderBackend {
         }
     }
 
-    fn apply_blend_mode(&mut self, mode: RenderBlendMode) {
+    /// Sets up the blending of draws for the blend modes that don't need a shader.
+    fn apply_blend_mode(&mut self, mode: BlendMode) {
         let (blend_op, src_rgb, dst_rgb) = match mode {
-            RenderBlendMode::Builtin(BlendMode::Normal) => {
-                // src + (1-a)
-                (Gl::FUNC_ADD, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA)
-            }
-            RenderBlendMode::Builtin(BlendMode::Add) => {
+            BlendMode::Add => {
                 // src + dst
                 (Gl::FUNC_ADD, Gl::ONE, Gl::ONE)
             }
-            RenderBlendMode::Builtin(BlendMode::Subtract) => {
+            BlendMode::Subtract => {
                 // dst - src
                 (Gl::FUNC_REVERSE_SUBTRACT, Gl::ONE, Gl::ONE)
             }
+            BlendMode::Screen => {
+                // src + (1-src) * dst
+                (Gl::FUNC_ADD, Gl::ONE, Gl::ONE_MINUS_SRC_COLOR)
+            }
             _ => {
-                // TODO: Unsupported blend mode. Default to normal for now.
+                // src + (1-a)
                 (Gl::FUNC_ADD, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA)
             }
         };
@@ -781,7 +835,7 @@ impl WebGlRenderBackend {
             .blend_func_separate(src_rgb, dst_rgb, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
     }
 
-    fn begin_frame(&mut self, clear: Color) {
+    fn begin_frame(&mut self, clear: Color, damage: &Damage) {
         self.active_program = std::ptr::null();
         self.mask_state = MaskState::NoMask;
         self.num_masks = 0;
@@ -791,14 +845,29 @@ impl WebGlRenderBackend {
         self.add_color = None;
 
         // Bind to MSAA render buffer if using MSAA.
-        if let Some(msaa_buffers) = &self.msaa_buffers {
-            let gl = &self.gl;
-            gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(&msaa_buffers.render_framebuffer));
+        self.target = RenderTarget {
+            framebuffer: self
+                .msaa_buffers
+                .as_ref()
+                .map(|msaa_buffers| msaa_buffers.render_framebuffer.clone()),
+            width: self.renderbuffer_width,
+            height: self.renderbuffer_height,
+        };
+        self.nearest_layer = None;
+        if let Some(framebuffer) = &self.target.framebuffer {
+            self.gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(framebuffer));
         }
 
         self.gl
             .viewport(0, 0, self.renderbuffer_width, self.renderbuffer_height);
 
+        // Only the damaged part of the last frame is cleared and drawn again.
+        if let Damage::Partial(region) = damage {
+            let (x, y, width, height) = self.scissor_box(region);
+            self.gl.enable(Gl::SCISSOR_TEST);
+            self.gl.scissor(x, y, width, height);
+        }
+
         self.set_stencil_state();
         if self.is_transparent {
             self.gl.clear_color(0.0, 0.0, 0.0, 0.0);
@@ -815,6 +884,8 @@ impl WebGlRenderBackend {
     }
 
     fn end_frame(&mut self) {
+        self.gl.disable(Gl::SCISSOR_TEST);
+
         // Resolve MSAA, if we're using it (WebGL2).
         if let (Some(ref gl), Some(ref msaa_buffers)) = (&self.gl2, &self.msaa_buffers) {
             // Disable any remaining masking state.
@@ -901,22 +972,469 @@ impl WebGlRenderBackend {
         }
     }
 
-    fn push_blend_mode(&mut self, blend: RenderBlendMode) {
-        if !same_blend_mode(self.blend_modes.last(), &blend) {
-            self.apply_blend_mode(blend.clone());
+    /// Draws a texture over the whole of the current target, with the current blending.
+    fn draw_fullscreen_texture(&mut self, texture: &WebGlTexture) {
+        let program = &self.bitmap_program;
+        self.gl.use_program(Some(&program.program));
+        // The next draw has to set its uniforms again.
+        self.active_program = std::ptr::null();
+        self.mult_color = None;
+        self.add_color = None;
+
+        // Scale to fill screen.
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &FULLSCREEN_MATRIX);
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &IDENTITY_MATRIX);
+        program.uniform4fv(&self.gl, ShaderUniform::MultColor, &[1.0, 1.0, 1.0, 1.0]);
+        program.uniform4fv(&self.gl, ShaderUniform::AddColor, &[0.0, 0.0, 0.0, 0.0]);
+
+        program.uniform_matrix3fv(
+            &self.gl,
+            ShaderUniform::TextureMatrix,
+            &[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+
+        // Bind the texture.
+        self.gl.active_texture(Gl::TEXTURE0);
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(texture));
+        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+
+        // Render the quad.
+        let quad = &self.bitmap_quad_draws;
+        self.bind_vertex_array(Some(&quad[0].vao));
+        self.gl
+            .draw_elements_with_i32(Gl::TRIANGLE_FAN, quad[0].num_indices, Gl::UNSIGNED_INT, 0);
+    }
+
+    /// The area of the render buffer that covers the given region of the stage,
+    /// as `(x, y, width, height)` from its bottom left corner.
+    fn scissor_box(&self, region: &Rectangle<Twips>) -> (i32, i32, i32, i32) {
+        if !region.is_valid() {
+            return (0, 0, 0, 0);
+        }
+        // Leave room for antialiasing around the edges.
+        let x_min = (region.x_min.to_pixels().floor() as i32 - 1).max(0);
+        let y_min = (region.y_min.to_pixels().floor() as i32 - 1).max(0);
+        let x_max = (region.x_max.to_pixels().ceil() as i32 + 1).min(self.renderbuffer_width);
+        let y_max = (region.y_max.to_pixels().ceil() as i32 + 1).min(self.renderbuffer_height);
+        if x_max <= x_min || y_max <= y_min {
+            return (0, 0, 0, 0);
+        }
+        (
+            x_min,
+            self.renderbuffer_height - y_max,
+            x_max - x_min,
+            y_max - y_min,
+        )
+    }
+
+    /// Renders the commands of a bitmap cache into its texture, at the quality of the stage.
+    fn render_cache_entry(&mut self, entry: BitmapCacheEntry) -> Result<(), Error> {
+        self.draw_to_bitmap(
+            &entry.handle,
+            entry.commands,
+            Some(entry.clear),
+            self.msaa_sample_count,
+        )
+    }
+
+    /// Draws the given commands into the texture of a bitmap, clearing it first if a color is given.
+    /// With more than one sample, the commands are drawn with MSAA and resolved into the texture.
+    fn draw_to_bitmap(
+        &mut self,
+        handle: &BitmapHandle,
+        commands: CommandList,
+        clear: Option<Color>,
+        sample_count: u32,
+    ) -> Result<(), Error> {
+        let target = as_registry_data(handle);
+        let (width, height) = (target.width as i32, target.height as i32);
+        let texture = target.texture.clone();
+
+        let framebuffer = self
+            .gl
+            .create_framebuffer()
+            .ok_or(Error::UnableToCreateFrameBuffer)?;
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        self.gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+
+        // Masks need a stencil buffer of their own. With MSAA, the color is also drawn into
+        // a renderbuffer of its own, which is only resolved into the texture at the end.
+        let mut renderbuffers = Vec::with_capacity(2);
+        let msaa_framebuffer = match &self.gl2 {
+            Some(gl) if sample_count > 1 => {
+                let msaa_framebuffer = gl
+                    .create_framebuffer()
+                    .ok_or(Error::UnableToCreateFrameBuffer)?;
+                gl.bind_framebuffer(Gl2::FRAMEBUFFER, Some(&msaa_framebuffer));
+                for (format, attachment) in [
+                    (Gl2::RGBA8, Gl2::COLOR_ATTACHMENT0),
+                    (Gl2::STENCIL_INDEX8, Gl2::STENCIL_ATTACHMENT),
+                ] {
+                    let renderbuffer = gl
+                        .create_renderbuffer()
+                        .ok_or(Error::UnableToCreateRenderBuffer)?;
+                    gl.bind_renderbuffer(Gl2::RENDERBUFFER, Some(&renderbuffer));
+                    gl.renderbuffer_storage_multisample(
+                        Gl2::RENDERBUFFER,
+                        sample_count as i32,
+                        format,
+                        width,
+                        height,
+                    );
+                    gl.framebuffer_renderbuffer(
+                        Gl2::FRAMEBUFFER,
+                        attachment,
+                        Gl2::RENDERBUFFER,
+                        Some(&renderbuffer),
+                    );
+                    renderbuffers.push(renderbuffer);
+                }
+                Some(msaa_framebuffer)
+            }
+            _ => {
+                let stencil_renderbuffer = self
+                    .gl
+                    .create_renderbuffer()
+                    .ok_or(Error::UnableToCreateRenderBuffer)?;
+                self.gl
+                    .bind_renderbuffer(Gl::RENDERBUFFER, Some(&stencil_renderbuffer));
+                self.gl
+                    .renderbuffer_storage(Gl::RENDERBUFFER, Gl::STENCIL_INDEX8, width, height);
+                self.gl.framebuffer_renderbuffer(
+                    Gl::FRAMEBUFFER,
+                    Gl::STENCIL_ATTACHMENT,
+                    Gl::RENDERBUFFER,
+                    Some(&stencil_renderbuffer),
+                );
+                renderbuffers.push(stencil_renderbuffer);
+                None
+            }
+        };
+
+        // The first row of a texture is at the bottom, so nothing is flipped here.
+        let view_matrix = std::mem::replace(
+            &mut self.view_matrix,
+            [
+                [1.0 / (width as f32 / 2.0), 0.0, 0.0, 0.0],
+                [0.0, 1.0 / (height as f32 / 2.0), 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [-1.0, -1.0, 0.0, 1.0],
+            ],
+        );
+        let target = std::mem::replace(
+            &mut self.target,
+            RenderTarget {
+                framebuffer: Some(msaa_framebuffer.as_ref().unwrap_or(&framebuffer).clone()),
+                width,
+                height,
+            },
+        );
+        let nearest_layer = self.nearest_layer.take();
+        self.active_program = std::ptr::null();
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.mask_state_dirty = true;
+        self.mult_color = None;
+        self.add_color = None;
+
+        self.gl.viewport(0, 0, width, height);
+        self.set_stencil_state();
+        self.gl.stencil_mask(0xff);
+        if let Some(clear) = clear {
+            self.gl.clear_color(
+                clear.r as f32 / 255.0,
+                clear.g as f32 / 255.0,
+                clear.b as f32 / 255.0,
+                clear.a as f32 / 255.0,
+            );
+            self.gl.clear(Gl::COLOR_BUFFER_BIT | Gl::STENCIL_BUFFER_BIT);
+        } else {
+            self.gl.clear(Gl::STENCIL_BUFFER_BIT);
+            if msaa_framebuffer.is_some() {
+                // The multisampled buffer starts out empty, so the current pixels are copied in.
+                self.gl.blend_equation(Gl::FUNC_ADD);
+                self.gl.blend_func(Gl::ONE, Gl::ZERO);
+                self.draw_fullscreen_texture(&texture);
+                self.apply_blend_mode(BlendMode::Normal);
+            }
+        }
+
+        commands.execute(self);
+        self.changed_bitmaps
+            .insert(Arc::as_ptr(&handle.0) as *const () as usize);
+
+        if let (Some(gl), Some(msaa_framebuffer)) = (&self.gl2, &msaa_framebuffer) {
+            gl.bind_framebuffer(Gl2::READ_FRAMEBUFFER, Some(msaa_framebuffer));
+            gl.bind_framebuffer(Gl2::DRAW_FRAMEBUFFER, Some(&framebuffer));
+            gl.blit_framebuffer(
+                0,
+                0,
+                width,
+                height,
+                0,
+                0,
+                width,
+                height,
+                Gl2::COLOR_BUFFER_BIT,
+                Gl2::NEAREST,
+            );
+            gl.delete_framebuffer(Some(msaa_framebuffer));
         }
-        self.blend_modes.push(blend);
-    }
-    fn pop_blend_mode(&mut self) {
-        let old = self.blend_modes.pop();
-        // We never pop our base 'BlendMode::Normal'
-        let current = self
-            .blend_modes
-            .last()
-            .unwrap_or(&RenderBlendMode::Builtin(BlendMode::Normal));
-        if !same_blend_mode(old.as_ref(), current) {
-            self.apply_blend_mode(current.clone());
+
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        self.gl.bind_renderbuffer(Gl::RENDERBUFFER, None);
+        self.gl.delete_framebuffer(Some(&framebuffer));
+        for renderbuffer in &renderbuffers {
+            self.gl.delete_renderbuffer(Some(renderbuffer));
         }
+        self.view_matrix = view_matrix;
+        self.target = target;
+        self.nearest_layer = nearest_layer;
+        self.active_program = std::ptr::null();
+        Ok(())
+    }
+
+    /// Draws the given commands on their own, then blends the result onto the current target.
+    fn draw_blend(&mut self, commands: CommandList, blend: RenderBlendMode) -> Result<(), Error> {
+        let applies_to_layer = matches!(
+            blend,
+            RenderBlendMode::Builtin(BlendMode::Alpha | BlendMode::Erase)
+        );
+        if applies_to_layer && self.nearest_layer.is_none() {
+            // An Alpha or Erase with no Layer above it is ignored.
+            return Ok(());
+        }
+
+        // The contents are drawn into a transparent layer of their own first.
+        let (width, height) = (self.target.width, self.target.height);
+        let layer = RenderTexture::new(&self.gl, width, height, true)?;
+        let target = std::mem::replace(
+            &mut self.target,
+            RenderTarget {
+                framebuffer: Some(layer.framebuffer.clone()),
+                width,
+                height,
+            },
+        );
+        let nearest_layer = if let RenderBlendMode::Builtin(BlendMode::Layer) = blend {
+            self.nearest_layer.replace(layer.framebuffer.clone())
+        } else {
+            self.nearest_layer.clone()
+        };
+        let (mask_state, num_masks) = (self.mask_state, self.num_masks);
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.mask_state_dirty = true;
+        self.set_stencil_state();
+        self.gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        self.gl.stencil_mask(0xff);
+        self.gl.clear(Gl::COLOR_BUFFER_BIT | Gl::STENCIL_BUFFER_BIT);
+
+        commands.execute(self);
+
+        self.target = target;
+        self.nearest_layer = nearest_layer;
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+        self.mask_state_dirty = true;
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, self.target.framebuffer.as_ref());
+        self.set_stencil_state();
+
+        match blend {
+            RenderBlendMode::Builtin(mode) => match complex_blend_index(mode) {
+                None => {
+                    self.apply_blend_mode(mode);
+                    self.draw_fullscreen_texture(&layer.texture);
+                }
+                Some(index) => {
+                    // Alpha and Erase blend with the nearest layer rather than the current target.
+                    let background = if applies_to_layer {
+                        self.nearest_layer.clone()
+                    } else {
+                        self.target.framebuffer.clone()
+                    };
+                    let background = self.copy_framebuffer(background.as_ref())?;
+                    self.set_stencil_state();
+                    self.gl.blend_equation(Gl::FUNC_ADD);
+                    self.gl.blend_func(Gl::ONE, Gl::ZERO);
+                    self.draw_complex_blend(&layer.texture, &background.texture, index);
+                }
+            },
+            RenderBlendMode::Shader(shader) => {
+                // Shaders are run on the CPU, with the pixels of both sides read back.
+                let background = self.copy_framebuffer(self.target.framebuffer.clone().as_ref())?;
+                let background = self.read_framebuffer(&background.framebuffer)?;
+                let foreground = self.read_framebuffer(&layer.framebuffer)?;
+                let output = software::run_blend_shader(
+                    shader.0.parsed_shader(),
+                    SoftwareImage::from_rgba8(width as u32, height as u32, &background),
+                    SoftwareImage::from_rgba8(width as u32, height as u32, &foreground),
+                );
+                let output = flip_rows(&output.to_rgba8(), width as usize);
+
+                let result = RenderTexture::new(&self.gl, width, height, false)?;
+                self.gl.bind_texture(Gl::TEXTURE_2D, Some(&result.texture));
+                self.gl
+                    .tex_sub_image_2d_with_i32_and_i32_and_u32_and_type_and_opt_u8_array(
+                        Gl::TEXTURE_2D,
+                        0,
+                        0,
+                        0,
+                        width,
+                        height,
+                        Gl::RGBA,
+                        Gl::UNSIGNED_BYTE,
+                        Some(&output),
+                    )
+                    .into_js_result()?;
+                self.gl
+                    .bind_framebuffer(Gl::FRAMEBUFFER, self.target.framebuffer.as_ref());
+                self.set_stencil_state();
+                self.gl.blend_equation(Gl::FUNC_ADD);
+                self.gl.blend_func(Gl::ONE, Gl::ZERO);
+                self.draw_fullscreen_texture(&result.texture);
+            }
+        }
+        self.apply_blend_mode(BlendMode::Normal);
+        Ok(())
+    }
+
+    /// Draws the blend of a layer onto a copy of what's below it, with the given
+    /// mode of the blend shader.
+    fn draw_complex_blend(&mut self, texture: &WebGlTexture, background: &WebGlTexture, mode: i32) {
+        let program = &self.blend_program;
+        self.gl.use_program(Some(&program.program));
+        // The next draw has to set its uniforms again.
+        self.active_program = std::ptr::null();
+        self.mult_color = None;
+        self.add_color = None;
+
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &FULLSCREEN_MATRIX);
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &IDENTITY_MATRIX);
+        program.uniform_matrix3fv(
+            &self.gl,
+            ShaderUniform::TextureMatrix,
+            &[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+        program.uniform1i(&self.gl, ShaderUniform::BlendMode, mode);
+
+        self.gl.active_texture(Gl::TEXTURE1);
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(background));
+        program.uniform1i(&self.gl, ShaderUniform::ParentTexture, 1);
+        self.gl.active_texture(Gl::TEXTURE0);
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(texture));
+        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+
+        let quad = &self.blend_quad_draws;
+        self.bind_vertex_array(Some(&quad[0].vao));
+        self.gl
+            .draw_elements_with_i32(Gl::TRIANGLE_FAN, quad[0].num_indices, Gl::UNSIGNED_INT, 0);
+    }
+
+    /// Copies the contents of a framebuffer of the size of the current target into a texture,
+    /// so that it can be read while drawing.
+    fn copy_framebuffer(
+        &mut self,
+        framebuffer: Option<&WebGlFramebuffer>,
+    ) -> Result<RenderTexture, Error> {
+        let (width, height) = (self.target.width, self.target.height);
+        let copy = RenderTexture::new(&self.gl, width, height, false)?;
+        if let Some(gl) = &self.gl2 {
+            // Multisampled framebuffers can only be copied by blitting.
+            gl.bind_framebuffer(Gl2::READ_FRAMEBUFFER, framebuffer);
+            gl.bind_framebuffer(Gl2::DRAW_FRAMEBUFFER, Some(&copy.framebuffer));
+            gl.blit_framebuffer(
+                0,
+                0,
+                width,
+                height,
+                0,
+                0,
+                width,
+                height,
+                Gl2::COLOR_BUFFER_BIT,
+                Gl2::NEAREST,
+            );
+        } else {
+            self.gl.bind_framebuffer(Gl::FRAMEBUFFER, framebuffer);
+            self.gl.bind_texture(Gl::TEXTURE_2D, Some(&copy.texture));
+            self.gl
+                .copy_tex_sub_image_2d(Gl::TEXTURE_2D, 0, 0, 0, 0, 0, width, height);
+        }
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, self.target.framebuffer.as_ref());
+        Ok(copy)
+    }
+
+    /// Reads the given region of a bitmap's texture back from the GPU.
+    fn read_bitmap(
+        &mut self,
+        handle: &BitmapHandle,
+        region: PixelRegion,
+    ) -> Result<Vec<u8>, Error> {
+        let texture = &as_registry_data(handle).texture;
+        let mut pixels = vec![0; region.width() as usize * region.height() as usize * 4];
+        let framebuffer = self
+            .gl
+            .create_framebuffer()
+            .ok_or(Error::UnableToCreateFrameBuffer)?;
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        self.gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(texture),
+            0,
+        );
+        // The first row of a texture is at the bottom, as is the first row read back,
+        // so the rows come out in the same order as the bitmap.
+        let result = self
+            .gl
+            .read_pixels_with_opt_u8_array(
+                region.x_min as i32,
+                region.y_min as i32,
+                region.width() as i32,
+                region.height() as i32,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                Some(&mut pixels),
+            )
+            .into_js_result();
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        self.gl.delete_framebuffer(Some(&framebuffer));
+        result?;
+        Ok(pixels)
+    }
+
+    /// Reads the pixels of a framebuffer of the size of the current target, from the top row down.
+    fn read_framebuffer(&mut self, framebuffer: &WebGlFramebuffer) -> Result<Vec<u8>, Error> {
+        let (width, height) = (self.target.width, self.target.height);
+        let mut pixels = vec![0; width as usize * height as usize * 4];
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(framebuffer));
+        self.gl
+            .read_pixels_with_opt_u8_array(
+                0,
+                0,
+                width,
+                height,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                Some(&mut pixels),
+            )
+            .into_js_result()?;
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, self.target.framebuffer.as_ref());
+        // The first row of a framebuffer is at the bottom.
+        Ok(flip_rows(&pixels, width as usize))
     }
 
     fn draw_quad<const MODE: u32, const COUNT: i32>(&mut self, color: Color, matrix: Matrix) {
@@ -981,24 +1499,153 @@ impl WebGlRenderBackend {
         self.gl
             .draw_elements_with_i32(MODE, count, Gl::UNSIGNED_INT, 0);
     }
+
+    /// Draws a bitmap onto the quad placed by `world_matrix`.
+    fn draw_bitmap_quad(
+        &mut self,
+        bitmap: &BitmapHandle,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        self.set_stencil_state();
+        let entry = as_registry_data(bitmap);
+        // Adjust the quad draw to use the target bitmap.
+        let quad = &self.bitmap_quad_draws;
+        let draw = &quad[0];
+        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
+            matrix
+        } else {
+            unreachable!()
+        };
+
+        let mult_color = color_transform.mult_rgba_normalized();
+        let add_color = color_transform.add_rgba_normalized();
+
+        self.bind_vertex_array(Some(&draw.vao));
+
+        let program = &self.bitmap_program;
+
+        // Set common render state, while minimizing unnecessary state changes.
+        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
+        // are in the same location between shaders, and avoid changing them unless necessary.
+        if program as *const ShaderProgram != self.active_program {
+            self.gl.use_program(Some(&program.program));
+            self.active_program = program as *const ShaderProgram;
+
+            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
+
+            self.mult_color = None;
+            self.add_color = None;
+        }
+
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
+        if Some(mult_color) != self.mult_color {
+            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
+            self.mult_color = Some(mult_color);
+        }
+        if Some(add_color) != self.add_color {
+            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
+            self.add_color = Some(add_color);
+        }
+
+        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
+
+        // Bind texture.
+        self.gl.active_texture(Gl::TEXTURE0);
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
+        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+
+        // Set texture parameters.
+        let filter = if smoothing {
+            Gl::LINEAR as i32
+        } else {
+            Gl::NEAREST as i32
+        };
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
+
+        let wrap = Gl::CLAMP_TO_EDGE as i32;
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
+
+        // Draw the triangles.
+        self.gl
+            .draw_elements_with_i32(Gl::TRIANGLE_FAN, draw.num_indices, Gl::UNSIGNED_INT, 0);
+    }
 }
 
-fn same_blend_mode(first: Option<&RenderBlendMode>, second: &RenderBlendMode) -> bool {
-    match (first, second) {
-        (Some(RenderBlendMode::Builtin(old)), RenderBlendMode::Builtin(new)) => old == new,
-        _ => false,
+/// The mode of the blend shader for blend modes that need it, or `None` if the GPU can
+/// blend them on its own.
+fn complex_blend_index(mode: BlendMode) -> Option<i32> {
+    match mode {
+        BlendMode::Normal
+        | BlendMode::Layer
+        | BlendMode::Add
+        | BlendMode::Subtract
+        | BlendMode::Screen => None,
+        BlendMode::Multiply => Some(0),
+        BlendMode::Lighten => Some(1),
+        BlendMode::Darken => Some(2),
+        BlendMode::Difference => Some(3),
+        BlendMode::Invert => Some(4),
+        BlendMode::Alpha => Some(5),
+        BlendMode::Erase => Some(6),
+        BlendMode::Overlay => Some(7),
+        BlendMode::HardLight => Some(8),
     }
 }
 
+/// Flips RGBA pixels with the given width upside down.
+fn flip_rows(pixels: &[u8], width: usize) -> Vec<u8> {
+    pixels
+        .chunks_exact(width * 4)
+        .rev()
+        .flatten()
+        .copied()
+        .collect()
+}
+
+/// Maps the unit square onto the whole viewport.
+const FULLSCREEN_MATRIX: [[f32; 4]; 4] = [
+    [2.0, 0.0, 0.0, 0.0],
+    [0.0, 2.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [-1.0, -1.0, 0.0, 1.0],
+];
+
+const IDENTITY_MATRIX: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
 impl RenderBackend for WebGlRenderBackend {
     fn render_offscreen(
         &mut self,
-        _handle: BitmapHandle,
-        _commands: CommandList,
-        _quality: StageQuality,
-        _bounds: PixelRegion,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
     ) -> Option<Box<dyn SyncHandle>> {
-        None
+        // The pixels stay in the texture until they're needed on the CPU.
+        let sample_count = quality.sample_count().min(self.max_msaa_sample_count);
+        if let Err(e) = self.draw_to_bitmap(&handle, commands, None, sample_count) {
+            log::error!("Couldn't render offscreen: {:?}", e);
+            return None;
+        }
+        self.gl
+            .viewport(0, 0, self.renderbuffer_width, self.renderbuffer_height);
+        Some(Box::new(WebGlSyncHandle { handle, bounds }))
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
     }
 
     fn viewport_dimensions(&self) -> ViewportDimensions {
@@ -1039,9 +1686,11 @@ impl RenderBackend for WebGlRenderBackend {
         shape: DistilledShape,
         bitmap_source: &dyn BitmapSource,
     ) -> ShapeHandle {
+        let bounds = shape.shape_bounds.clone();
         let mesh = match self.register_shape_internal(shape, bitmap_source) {
             Ok(draws) => Mesh {
                 draws,
+                bounds,
                 gl2: self.gl2.clone(),
                 vao_ext: self.vao_ext.clone(),
             },
@@ -1049,6 +1698,7 @@ impl RenderBackend for WebGlRenderBackend {
                 log::error!("Couldn't register shape: {:?}", e);
                 Mesh {
                     draws: vec![],
+                    bounds,
                     gl2: self.gl2.clone(),
                     vao_ext: self.vao_ext.clone(),
                 }
@@ -1063,11 +1713,31 @@ impl RenderBackend for WebGlRenderBackend {
         commands: CommandList,
         cache_entries: Vec<BitmapCacheEntry>,
     ) {
-        if !cache_entries.is_empty() {
-            panic!("Bitmap caching is unavailable on the webgl backend");
+        for entry in cache_entries {
+            // Filters aren't supported here, so filtered objects are cached without them.
+            if let Err(e) = self.render_cache_entry(entry) {
+                log::error!("Couldn't render bitmap cache: {:?}", e);
+            }
+        }
+
+        if self.last_clear != Some((clear, self.is_transparent)) {
+            self.last_clear = Some((clear, self.is_transparent));
+            self.damage_tracker.reset();
+        }
+        let damage = if self.msaa_buffers.is_some() {
+            let source = WebGlDamageSource {
+                changed_bitmaps: &self.changed_bitmaps,
+            };
+            self.damage_tracker.track(&commands, &source)
+        } else {
+            Damage::Full
+        };
+        self.changed_bitmaps.clear();
+
+        self.begin_frame(clear, &damage);
+        if damage != Damage::Partial(Rectangle::default()) {
+            commands.execute(self);
         }
-        self.begin_frame(clear);
-        commands.execute(self);
         self.end_frame();
     }
 
@@ -1121,6 +1791,8 @@ impl RenderBackend for WebGlRenderBackend {
         bitmap: Bitmap,
         _region: PixelRegion,
     ) -> Result<(), BitmapError> {
+        self.changed_bitmaps
+            .insert(Arc::as_ptr(&handle.0) as *const () as usize);
         let texture = &as_registry_data(handle).texture;
 
         self.gl.bind_texture(Gl::TEXTURE_2D, Some(texture));
@@ -1195,23 +1867,35 @@ impl RenderBackend for WebGlRenderBackend {
 
     fn set_quality(&mut self, _quality: StageQuality) {}
 
+    fn device_id(&self) -> DeviceId {
+        self.device_id
+    }
+
+    fn is_device_lost(&self) -> bool {
+        self.gl.is_context_lost()
+    }
+
     fn compile_pixelbender_shader(
         &mut self,
-        _shader: ruffle_render::pixel_bender::PixelBenderShader,
+        shader: ruffle_render::pixel_bender::PixelBenderShader,
     ) -> Result<ruffle_render::pixel_bender::PixelBenderShaderHandle, BitmapError> {
-        Err(BitmapError::Unimplemented(
-            "compile_pixelbender_shader".into(),
-        ))
+        // Shaders are run on the CPU by the caller.
+        Ok(ruffle_render::pixel_bender::software::SoftwareShader::new_handle(shader))
     }
 
     fn resolve_sync_handle(
         &mut self,
-        _handle: Box<dyn SyncHandle>,
-        _with_rgba: RgbaBufRead,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
     ) -> Result<(), ruffle_render::error::Error> {
-        Err(ruffle_render::error::Error::Unimplemented(
-            "Sync handle resolution".into(),
-        ))
+        let handle = handle
+            .downcast::<WebGlSyncHandle>()
+            .map_err(|_| BitmapError::JavascriptError("Unexpected sync handle".into()))?;
+        let pixels = self
+            .read_bitmap(&handle.handle, handle.bounds)
+            .map_err(|e| BitmapError::JavascriptError(e.to_string().into()))?;
+        with_rgba(&pixels, handle.bounds.width() * 4);
+        Ok(())
     }
 
     fn run_pixelbender_shader(
@@ -1244,6 +1928,22 @@ impl RenderBackend for WebGlRenderBackend {
         self.gl
             .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::LINEAR as i32);
 
+        // Allocate the texture, so that it can be rendered to.
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                Gl::TEXTURE_2D,
+                0,
+                Gl::RGBA as i32,
+                width as i32,
+                height as i32,
+                0,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                None,
+            )
+            .into_js_result()
+            .map_err(|e| BitmapError::JavascriptError(e.into()))?;
+
         Ok(BitmapHandle(Arc::new(RegistryData {
             gl: self.gl.clone(),
             width,
@@ -1261,16 +1961,7 @@ impl CommandHandler for WebGlRenderBackend {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     ) {
-        self.set_stencil_state();
         let entry = as_registry_data(&bitmap);
-        // Adjust the quad draw to use the target bitmap.
-        let quad = &self.bitmap_quad_draws;
-        let draw = &quad[0];
-        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
-            matrix
-        } else {
-            unreachable!()
-        };
 
         // Scale the quad to the bitmap's dimensions.
         let mut matrix = transform.matrix;
@@ -1288,64 +1979,26 @@ impl CommandHandler for WebGlRenderBackend {
                 1.0,
             ],
         ];
+        self.draw_bitmap_quad(&bitmap, world_matrix, transform.color_transform, smoothing);
+    }
 
-        let mult_color = transform.color_transform.mult_rgba_normalized();
-        let add_color = transform.color_transform.add_rgba_normalized();
-
-        self.bind_vertex_array(Some(&draw.vao));
-
-        let program = &self.bitmap_program;
-
-        // Set common render state, while minimizing unnecessary state changes.
-        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
-        // are in the same location between shaders, and avoid changing them unless necessary.
-        if program as *const ShaderProgram != self.active_program {
-            self.gl.use_program(Some(&program.program));
-            self.active_program = program as *const ShaderProgram;
-
-            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
-
-            self.mult_color = None;
-            self.add_color = None;
-        }
-
-        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
-        if Some(mult_color) != self.mult_color {
-            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
-            self.mult_color = Some(mult_color);
-        }
-        if Some(add_color) != self.add_color {
-            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
-            self.add_color = Some(add_color);
-        }
-
-        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
-
-        // Bind texture.
-        self.gl.active_texture(Gl::TEXTURE0);
-        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
-        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
-
-        // Set texture parameters.
-        let filter = if smoothing {
-            Gl::LINEAR as i32
-        } else {
-            Gl::NEAREST as i32
-        };
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
-
-        let wrap = Gl::CLAMP_TO_EDGE as i32;
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
-
-        // Draw the triangles.
-        self.gl
-            .draw_elements_with_i32(Gl::TRIANGLE_FAN, draw.num_indices, Gl::UNSIGNED_INT, 0);
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: ProjectiveMatrix,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        let entry = as_registry_data(&bitmap);
+        let matrix = matrix * Matrix::scale(entry.width as f32, entry.height as f32);
+        // The perspective divide is left to the GPU, so that texture coordinates are
+        // interpolated across the bitmap correctly.
+        self.draw_bitmap_quad(
+            &bitmap,
+            matrix.to_pixel_matrix4(),
+            color_transform,
+            smoothing,
+        );
     }
 
     fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
@@ -1549,9 +2202,12 @@ impl CommandHandler for WebGlRenderBackend {
     }
 
     fn blend(&mut self, commands: CommandList, blend: RenderBlendMode) {
-        self.push_blend_mode(blend);
-        commands.execute(self);
-        self.pop_blend_mode();
+        if let Err(e) = self.draw_blend(commands, blend) {
+            log::error!("Couldn't draw blend: {:?}", e);
+            self.gl
+                .bind_framebuffer(Gl::FRAMEBUFFER, self.target.framebuffer.as_ref());
+            self.apply_blend_mode(BlendMode::Normal);
+        }
     }
 }
 
@@ -1628,6 +2284,7 @@ struct Mesh {
     gl2: Option<Gl2>,
     vao_ext: OesVertexArrayObject,
     draws: Vec<Draw>,
+    bounds: Rectangle<Twips>,
 }
 
 impl Drop for Mesh {
@@ -1646,6 +2303,40 @@ impl Drop for Mesh {
 
 impl ShapeHandleImpl for Mesh {}
 
+/// What the damage tracker needs to know about the handles of this backend.
+struct WebGlDamageSource<'a> {
+    changed_bitmaps: &'a HashSet<usize>,
+}
+
+impl DamageSource for WebGlDamageSource<'_> {
+    fn shape_bounds(&self, shape: &ShapeHandle) -> Option<Rectangle<Twips>> {
+        Some(as_mesh(shape).bounds.clone())
+    }
+
+    fn shape_changed(&self, shape: &ShapeHandle) -> bool {
+        as_mesh(shape)
+            .draws
+            .iter()
+            .any(|draw| match &draw.draw_type {
+                DrawType::Bitmap(BitmapDraw {
+                    handle: Some(handle),
+                    ..
+                }) => self.bitmap_changed(handle),
+                _ => false,
+            })
+    }
+
+    fn bitmap_size(&self, bitmap: &BitmapHandle) -> Option<(u32, u32)> {
+        let data = as_registry_data(bitmap);
+        Some((data.width, data.height))
+    }
+
+    fn bitmap_changed(&self, bitmap: &BitmapHandle) -> bool {
+        self.changed_bitmaps
+            .contains(&(Arc::as_ptr(&bitmap.0) as *const () as usize))
+    }
+}
+
 fn as_mesh(handle: &ShapeHandle) -> &Mesh {
     <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be a WebGL ShapeData")
 }
@@ -1680,6 +2371,105 @@ enum DrawType {
     Bitmap(BitmapDraw),
 }
 
+/// Where draws end up: the canvas, a bitmap cache or a blend layer.
+#[derive(Clone, Debug)]
+struct RenderTarget {
+    /// The framebuffer to draw to, or `None` for the canvas.
+    framebuffer: Option<WebGlFramebuffer>,
+    width: i32,
+    height: i32,
+}
+
+/// The result of drawing into a bitmap, which is read back from its texture when needed.
+#[derive(Debug)]
+struct WebGlSyncHandle {
+    handle: BitmapHandle,
+    bounds: PixelRegion,
+}
+
+impl SyncHandle for WebGlSyncHandle {}
+
+/// A texture that can be drawn to, such as the contents of a blend.
+#[derive(Debug)]
+struct RenderTexture {
+    gl: Gl,
+    texture: WebGlTexture,
+    framebuffer: WebGlFramebuffer,
+    stencil_renderbuffer: Option<WebGlRenderbuffer>,
+}
+
+impl RenderTexture {
+    /// Creates a texture with a framebuffer that is bound afterwards. The stencil buffer
+    /// is only needed to draw masks.
+    fn new(gl: &Gl, width: i32, height: i32, with_stencil: bool) -> Result<Self, Error> {
+        let texture = gl.create_texture().ok_or(Error::UnableToCreateTexture)?;
+        gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::NEAREST as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::NEAREST as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE as i32);
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            Gl::TEXTURE_2D,
+            0,
+            Gl::RGBA as i32,
+            width,
+            height,
+            0,
+            Gl::RGBA,
+            Gl::UNSIGNED_BYTE,
+            None,
+        )
+        .into_js_result()?;
+
+        let framebuffer = gl
+            .create_framebuffer()
+            .ok_or(Error::UnableToCreateFrameBuffer)?;
+        gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+
+        let stencil_renderbuffer = if with_stencil {
+            let renderbuffer = gl
+                .create_renderbuffer()
+                .ok_or(Error::UnableToCreateRenderBuffer)?;
+            gl.bind_renderbuffer(Gl::RENDERBUFFER, Some(&renderbuffer));
+            gl.renderbuffer_storage(Gl::RENDERBUFFER, Gl::STENCIL_INDEX8, width, height);
+            gl.framebuffer_renderbuffer(
+                Gl::FRAMEBUFFER,
+                Gl::STENCIL_ATTACHMENT,
+                Gl::RENDERBUFFER,
+                Some(&renderbuffer),
+            );
+            gl.bind_renderbuffer(Gl::RENDERBUFFER, None);
+            Some(renderbuffer)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            gl: gl.clone(),
+            texture,
+            framebuffer,
+            stencil_renderbuffer,
+        })
+    }
+}
+
+impl Drop for RenderTexture {
+    fn drop(&mut self) {
+        self.gl.delete_framebuffer(Some(&self.framebuffer));
+        self.gl.delete_texture(Some(&self.texture));
+        if let Some(renderbuffer) = &self.stencil_renderbuffer {
+            self.gl.delete_renderbuffer(Some(renderbuffer));
+        }
+    }
+}
+
 struct MsaaBuffers {
     color_renderbuffer: WebGlRenderbuffer,
     stencil_renderbuffer: WebGlRenderbuffer,
@@ -1700,7 +2490,7 @@ struct ShaderProgram {
 }
 
 // These should match the uniform names in the shaders.
-const NUM_UNIFORMS: usize = 12;
+const NUM_UNIFORMS: usize = 14;
 const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "world_matrix",
     "view_matrix",
@@ -1714,6 +2504,8 @@ const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "u_focal_point",
     "u_interpolation",
     "u_texture",
+    "u_parent_texture",
+    "u_blend_mode",
 ];
 
 enum ShaderUniform {
@@ -1729,6 +2521,8 @@ enum ShaderUniform {
     GradientFocalPoint,
     GradientInterpolation,
     BitmapTexture,
+    ParentTexture,
+    BlendMode,
 }
 
 impl ShaderProgram {
@@ -0,0 +1,62 @@
+// NOTE: The `shader_filter_common.wgsl` source is prepended to this before compilation.
+
+struct Filter {
+    // The matrix in row order, packed four values to an element.
+    matrix: array<vec4<f32>, 57>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    default_color: vec4<f32>,
+    clamp: u32,
+    preserve_alpha: u32,
+    source_width: f32,
+    source_height: f32,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<uniform> filter_args: Filter;
+
+fn matrix_value(index: u32) -> f32 {
+    return filter_args.matrix[index / 4u][index % 4u];
+}
+
+fn unpremultiply(color: vec4<f32>) -> vec4<f32> {
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+@vertex
+fn main_vertex(in: filter__VertexInput) -> filter__VertexOutput {
+    return filter__main_vertex(in);
+}
+
+@fragment
+fn main_fragment(in: filter__VertexOutput) -> @location(0) vec4<f32> {
+    let texel = vec2<f32>(1.0 / filter_args.source_width, 1.0 / filter_args.source_height);
+    let center = vec2<i32>(i32(filter_args.matrix_x / 2u), i32(filter_args.matrix_y / 2u));
+
+    var total = vec4<f32>(0.0);
+    for (var y = 0u; y < filter_args.matrix_y; y++) {
+        for (var x = 0u; x < filter_args.matrix_x; x++) {
+            let offset = vec2<f32>(vec2<i32>(i32(x), i32(y)) - center);
+            let uv = in.uv + offset * texel;
+            var color = unpremultiply(textureSampleLevel(texture, texture_sampler, uv, 0.0));
+            // Without clamping, pixels outside of the source are the default color.
+            if (filter_args.clamp == 0u && (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0)) {
+                color = filter_args.default_color;
+            }
+            total += matrix_value(y * filter_args.matrix_x + x) * color;
+        }
+    }
+
+    // The bias is given in the same 0-255 range as the colors.
+    var result = saturate(total / filter_args.divisor + filter_args.bias / 255.0);
+    if (filter_args.preserve_alpha != 0u) {
+        result.a = textureSampleLevel(texture, texture_sampler, in.uv, 0.0).a;
+    }
+    return vec4<f32>(result.rgb * result.a, result.a);
+}
@@ -3,7 +3,7 @@ use crate::buffer_pool::{BufferPool, TexturePool};
 use crate::context3d::WgpuContext3D;
 use crate::dynamic_transforms::DynamicTransforms;
 use crate::filters::FilterSource;
-use crate::mesh::{CommonGradient, Mesh, PendingDraw};
+use crate::mesh::{as_mesh, CommonGradient, Mesh, PendingDraw, PendingDrawType};
 use crate::pixel_bender::{run_pixelbender_shader_impl, ShaderMode};
 use crate::surface::{LayerRef, Surface};
 use crate::target::{MaybeOwnedBuffer, TextureTarget};
@@ -17,11 +17,12 @@ use image::imageops::FilterType;
 use ruffle_render::backend::{
     BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
 };
-use ruffle_render::backend::{RenderBackend, ShapeHandle, ViewportDimensions};
+use ruffle_render::backend::{DeviceId, RenderBackend, ShapeHandle, ViewportDimensions};
 use ruffle_render::bitmap::{
     Bitmap, BitmapFormat, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
 };
 use ruffle_render::commands::CommandList;
+use ruffle_render::damage::{DamageSource, DamageTracker};
 use ruffle_render::error::Error as BitmapError;
 use ruffle_render::filters::Filter;
 use ruffle_render::pixel_bender::{
@@ -33,9 +34,10 @@ use ruffle_render::shape_utils::DistilledShape;
 use ruffle_render::tessellator::ShapeTessellator;
 use std::borrow::Cow;
 use std::cell::Cell;
+use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
-use swf::Color;
+use swf::{Color, Rectangle, Twips};
 use tracing::instrument;
 use wgpu::SubmissionIndex;
 
@@ -53,6 +55,19 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     pub(crate) offscreen_buffer_pool: Arc<BufferPool<wgpu::Buffer, BufferDimensions>>,
     dynamic_transforms: DynamicTransforms,
     active_frame: ActiveFrame,
+
+    /// Whether frames are cleared to transparent, so that what's behind the surface shows through.
+    is_transparent: bool,
+
+    /// Finds the parts of each frame that changed, so that only those are redrawn.
+    damage_tracker: DamageTracker,
+
+    /// The bitmaps whose contents changed since the last frame, by address.
+    changed_bitmaps: HashSet<usize>,
+
+    /// The clear color and transparency of the last frame. The stage is redrawn
+    /// entirely when they change, as they affect every pixel that nothing is drawn over.
+    last_clear: Option<(Color, bool)>,
 }
 
 impl WgpuRenderBackend<SwapChainTarget> {
@@ -60,6 +75,7 @@ impl WgpuRenderBackend<SwapChainTarget> {
     pub async fn for_canvas(
         canvas: web_sys::HtmlCanvasElement,
         webgpu: bool,
+        is_transparent_requested: bool,
     ) -> Result<Self, Error> {
         let backends = if webgpu {
             wgpu::Backends::BROWSER_WEBGPU
@@ -80,9 +96,16 @@ impl WgpuRenderBackend<SwapChainTarget> {
         )
         .await?;
         let descriptors = Descriptors::new(instance, adapter, device, queue);
-        let target =
+        let mut target =
             SwapChainTarget::new(surface, &descriptors.adapter, (1, 1), &descriptors.device);
-        Self::new(Arc::new(descriptors), target)
+        let is_transparent = is_transparent_requested
+            && target.set_transparent(&descriptors.adapter, &descriptors.device);
+        if !is_transparent && is_transparent_requested {
+            tracing::warn!("Transparent surfaces are not supported, rendering opaque instead");
+        }
+        let mut backend = Self::new(Arc::new(descriptors), target)?;
+        backend.is_transparent = is_transparent;
+        Ok(backend)
     }
 
     /// # Safety
@@ -229,6 +252,10 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             offscreen_buffer_pool: Arc::new(offscreen_buffer_pool),
             dynamic_transforms: transforms,
             active_frame,
+            is_transparent: false,
+            damage_tracker: DamageTracker::new(),
+            changed_bitmaps: HashSet::new(),
+            last_clear: None,
         })
     }
 
@@ -238,6 +265,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         bitmap_source: &dyn BitmapSource,
     ) -> Mesh {
         let shape_id = shape.id;
+        let bounds = shape.shape_bounds.clone();
         let lyon_mesh = self
             .shape_tessellator
             .tessellate_shape(shape, bitmap_source);
@@ -288,6 +316,13 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             wgpu::BufferUsages::INDEX,
         );
 
+        let bitmaps = draws
+            .iter()
+            .filter_map(|draw| match &draw.draw_type {
+                PendingDrawType::Bitmap { handle, .. } => Some(bitmap_address(handle)),
+                _ => None,
+            })
+            .collect();
         let draws = draws
             .into_iter()
             .map(|d| d.finish(&self.descriptors, &uniform_buffer, &gradients))
@@ -297,6 +332,8 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             draws,
             vertex_buffer,
             index_buffer,
+            bounds,
+            bitmaps,
         }
     }
 
@@ -469,6 +506,14 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         );
     }
 
+    fn device_id(&self) -> DeviceId {
+        self.descriptors.device_id
+    }
+
+    fn is_device_lost(&self) -> bool {
+        self.descriptors.is_device_lost()
+    }
+
     fn viewport_dimensions(&self) -> ViewportDimensions {
         ViewportDimensions {
             width: self.target.width(),
@@ -509,6 +554,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         };
 
         for entry in cache_entries {
+            self.changed_bitmaps.insert(bitmap_address(&entry.handle));
             let texture = as_texture(&entry.handle);
             let mut surface = Surface::new(
                 &self.descriptors,
@@ -585,21 +631,36 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             }
         }
 
-        self.surface.draw_commands_and_copy_to(
-            frame_output.view(),
-            RenderTargetMode::FreshWithColor(wgpu::Color {
+        if self.last_clear != Some((clear, self.is_transparent)) {
+            self.last_clear = Some((clear, self.is_transparent));
+            self.damage_tracker.reset();
+        }
+        let source = WgpuDamageSource {
+            changed_bitmaps: &self.changed_bitmaps,
+        };
+        let damage = self.damage_tracker.track(&commands, &source);
+        self.changed_bitmaps.clear();
+
+        let clear = if self.is_transparent {
+            wgpu::Color::TRANSPARENT
+        } else {
+            wgpu::Color {
                 r: f64::from(clear.r) / 255.0,
                 g: f64::from(clear.g) / 255.0,
                 b: f64::from(clear.b) / 255.0,
                 a: f64::from(clear.a) / 255.0,
-            }),
+            }
+        };
+        self.surface.draw_damage_and_copy_to(
+            frame_output.view(),
+            &damage,
+            RenderTargetMode::FreshWithColor(clear),
             &self.descriptors,
             &mut self.active_frame.staging_belt,
             &self.dynamic_transforms,
             &mut self.active_frame.command_encoder,
             &self.meshes,
             commands,
-            LayerRef::None,
             &mut self.texture_pool,
         );
         self.active_frame.staging_belt.finish();
@@ -672,6 +733,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         bitmap: Bitmap,
         mut region: PixelRegion,
     ) -> Result<(), BitmapError> {
+        self.changed_bitmaps.insert(bitmap_address(handle));
         let texture = as_texture(handle);
 
         let mut bitmap = bitmap.to_rgba();
@@ -719,6 +781,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         quality: StageQuality,
         bounds: PixelRegion,
     ) -> Option<Box<dyn SyncHandle>> {
+        self.changed_bitmaps.insert(bitmap_address(&handle));
         let texture = as_texture(&handle);
 
         let extent = wgpu::Extent3d {
@@ -772,6 +835,9 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 | Filter::ShaderFilter(_)
                 | Filter::BevelFilter(_)
                 | Filter::DisplacementMapFilter(_)
+                | Filter::ConvolutionFilter(_)
+                | Filter::GradientGlowFilter(_)
+                | Filter::GradientBevelFilter(_)
         )
     }
 
@@ -788,6 +854,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         dest_point: (u32, u32),
         filter: Filter,
     ) -> Option<Box<dyn SyncHandle>> {
+        self.changed_bitmaps.insert(bitmap_address(&destination));
         let source_texture = as_texture(&source);
         let dest_texture = as_texture(&destination);
 
@@ -884,7 +951,10 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             crate::pixel_bender::temporary_texture_format_for_channels(output_channels as u32);
 
         let target_handle = match target {
-            PixelBenderTarget::Bitmap(handle) => handle.clone(),
+            PixelBenderTarget::Bitmap(handle) => {
+                self.changed_bitmaps.insert(bitmap_address(handle));
+                handle.clone()
+            }
             PixelBenderTarget::Bytes { width, height } => {
                 let extent = wgpu::Extent3d {
                     width: *width,
@@ -1087,6 +1157,18 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         handle.capture(with_rgba, &mut self.active_frame);
         Ok(())
     }
+
+    fn resolve_sync_handles(
+        &mut self,
+        handles: Vec<(Box<dyn SyncHandle>, RgbaBufRead)>,
+    ) -> Result<(), ruffle_render::error::Error> {
+        let handles = handles
+            .into_iter()
+            .map(|(handle, with_rgba)| (*handle.downcast::<QueueSyncHandle>().unwrap(), with_rgba))
+            .collect();
+        QueueSyncHandle::capture_all(handles, &mut self.active_frame);
+        Ok(())
+    }
 }
 
 pub async fn request_adapter_and_device(
@@ -1253,3 +1335,35 @@ impl ActiveFrame {
         }
     }
 }
+
+/// Identifies a bitmap by the address of its handle.
+fn bitmap_address(handle: &BitmapHandle) -> usize {
+    Arc::as_ptr(&handle.0) as *const () as usize
+}
+
+/// What the damage tracker needs to know about the handles of this backend.
+struct WgpuDamageSource<'a> {
+    changed_bitmaps: &'a HashSet<usize>,
+}
+
+impl DamageSource for WgpuDamageSource<'_> {
+    fn shape_bounds(&self, shape: &ShapeHandle) -> Option<Rectangle<Twips>> {
+        Some(as_mesh(shape).bounds.clone())
+    }
+
+    fn shape_changed(&self, shape: &ShapeHandle) -> bool {
+        as_mesh(shape)
+            .bitmaps
+            .iter()
+            .any(|bitmap| self.changed_bitmaps.contains(bitmap))
+    }
+
+    fn bitmap_size(&self, bitmap: &BitmapHandle) -> Option<(u32, u32)> {
+        let texture = as_texture(bitmap);
+        Some((texture.texture.width(), texture.texture.height()))
+    }
+
+    fn bitmap_changed(&self, bitmap: &BitmapHandle) -> bool {
+        self.changed_bitmaps.contains(&bitmap_address(bitmap))
+    }
+}
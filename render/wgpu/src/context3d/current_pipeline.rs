@@ -64,6 +64,11 @@ pub struct CurrentPipeline {
     depth_mask: bool,
     pass_compare_mode: wgpu::CompareFunction,
 
+    stencil_front: StencilFaceState,
+    stencil_back: StencilFaceState,
+    stencil_read_mask: u32,
+    stencil_write_mask: u32,
+
     color_component: wgpu::BlendComponent,
     alpha_component: wgpu::BlendComponent,
 
@@ -122,6 +127,12 @@ impl CurrentPipeline {
 
             depth_mask: true,
             pass_compare_mode: wgpu::CompareFunction::LessEqual,
+            // These match the defaults of `Context3D.setStencilActions`
+            // and `Context3D.setStencilReferenceValue`
+            stencil_front: StencilFaceState::IGNORE,
+            stencil_back: StencilFaceState::IGNORE,
+            stencil_read_mask: 0xFF,
+            stencil_write_mask: 0xFF,
             color_component: wgpu::BlendComponent::REPLACE,
             alpha_component: wgpu::BlendComponent::REPLACE,
             sample_count: 1,
@@ -184,6 +195,35 @@ impl CurrentPipeline {
         self.pass_compare_mode = pass_compare_mode;
     }
 
+    pub fn update_stencil_actions(
+        &mut self,
+        triangle_face: Context3DTriangleFace,
+        face_state: StencilFaceState,
+    ) {
+        let (front, back) = match triangle_face {
+            Context3DTriangleFace::Front => (true, false),
+            Context3DTriangleFace::Back => (false, true),
+            Context3DTriangleFace::FrontAndBack => (true, true),
+            Context3DTriangleFace::None => (false, false),
+        };
+        if front && self.stencil_front != face_state {
+            self.dirty.set(true);
+            self.stencil_front = face_state;
+        }
+        if back && self.stencil_back != face_state {
+            self.dirty.set(true);
+            self.stencil_back = face_state;
+        }
+    }
+
+    pub fn update_stencil_masks(&mut self, read_mask: u32, write_mask: u32) {
+        if self.stencil_read_mask != read_mask || self.stencil_write_mask != write_mask {
+            self.dirty.set(true);
+        }
+        self.stencil_read_mask = read_mask;
+        self.stencil_write_mask = write_mask;
+    }
+
     pub fn update_has_depth_texture(&mut self, has_depth_texture: bool) {
         if self.has_depth_texture != has_depth_texture {
             self.dirty.set(true);
@@ -446,12 +486,11 @@ impl CurrentPipeline {
                 format: TextureFormat::Depth24PlusStencil8,
                 depth_write_enabled: self.depth_mask,
                 depth_compare: self.pass_compare_mode,
-                // FIXME - implement this
                 stencil: wgpu::StencilState {
-                    front: StencilFaceState::IGNORE,
-                    back: StencilFaceState::IGNORE,
-                    read_mask: !0,
-                    write_mask: !0,
+                    front: self.stencil_front,
+                    back: self.stencil_back,
+                    read_mask: self.stencil_read_mask,
+                    write_mask: self.stencil_write_mask,
                 },
                 bias: Default::default(),
             })
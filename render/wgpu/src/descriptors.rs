@@ -7,7 +7,9 @@ use crate::{
     TextureTransforms,
 };
 use fnv::FnvHashMap;
+use ruffle_render::backend::DeviceId;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use wgpu::Backend;
 
@@ -26,6 +28,8 @@ pub struct Descriptors {
     pub shaders: Shaders,
     pipelines: Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<Pipelines>>>,
     pub filters: Filters,
+    pub device_id: DeviceId,
+    device_lost: Arc<AtomicBool>,
 }
 
 impl Debug for Descriptors {
@@ -49,6 +53,23 @@ impl Descriptors {
         let filters = Filters::new(&device);
         let backend = adapter.get_info().backend;
 
+        let device_lost = Arc::new(AtomicBool::new(false));
+        let lost = device_lost.clone();
+        device.set_device_lost_callback(move |reason, message| {
+            // The callback is also called when we drop or destroy the device ourselves,
+            // or replace the callback, none of which means that the device was lost.
+            if matches!(
+                reason,
+                wgpu::DeviceLostReason::Dropped
+                    | wgpu::DeviceLostReason::Destroyed
+                    | wgpu::DeviceLostReason::ReplacedCallback
+            ) {
+                return;
+            }
+            tracing::error!("Graphics device was lost ({reason:?}): {message}");
+            lost.store(true, Ordering::Relaxed);
+        });
+
         Self {
             wgpu_instance: instance,
             adapter,
@@ -64,9 +85,17 @@ impl Descriptors {
             shaders,
             pipelines: Default::default(),
             filters,
+            device_id: DeviceId::new(),
+            device_lost,
         }
     }
 
+    /// Whether the device was lost, such as when the GPU was reset.
+    /// Nothing created with it can be used anymore.
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::Relaxed)
+    }
+
     pub fn copy_srgb_pipeline(
         &self,
         format: wgpu::TextureFormat,
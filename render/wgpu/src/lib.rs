@@ -12,13 +12,14 @@ use crate::mesh::BitmapBinds;
 use crate::pipelines::Pipelines;
 use crate::target::{RenderTarget, SwapChainTarget};
 use crate::utils::{
-    capture_image, create_buffer_with_data, format_list, get_backend_names, BufferDimensions,
+    capture_image, capture_images, create_buffer_with_data, format_list, get_backend_names,
+    BufferDimensions,
 };
 use bytemuck::{Pod, Zeroable};
 use descriptors::Descriptors;
 use enum_map::Enum;
 use ruffle_render::backend::RawTexture;
-use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, PixelRegion, SyncHandle};
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, PixelRegion, RgbaBufRead, SyncHandle};
 use ruffle_render::shape_utils::GradientType;
 use ruffle_render::tessellator::{Gradient as TessGradient, Vertex as TessVertex};
 use std::cell::{Cell, OnceCell};
@@ -190,59 +191,118 @@ impl QueueSyncHandle {
                 descriptors,
                 pool,
             } => {
-                let texture = as_texture(&handle);
-
-                let buffer_dimensions = BufferDimensions::new(
-                    copy_area.width() as usize,
-                    copy_area.height() as usize,
-                    texture.texture.format(),
-                );
-
-                let buffer = pool.take(&descriptors, buffer_dimensions.clone());
-                frame.command_encoder.copy_texture_to_buffer(
-                    wgpu::ImageCopyTexture {
-                        texture: &texture.texture,
-                        mip_level: 0,
-                        origin: wgpu::Origin3d {
-                            x: copy_area.x_min,
-                            y: copy_area.y_min,
-                            z: 0,
-                        },
-                        aspect: wgpu::TextureAspect::All,
-                    },
-                    wgpu::ImageCopyBuffer {
-                        buffer: &buffer,
-                        layout: wgpu::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: Some(buffer_dimensions.padded_bytes_per_row),
-                            rows_per_image: None,
-                        },
-                    },
-                    wgpu::Extent3d {
-                        width: copy_area.width(),
-                        height: copy_area.height(),
-                        depth_or_array_layers: 1,
-                    },
-                );
+                let (buffer, buffer_dimensions) =
+                    Self::record_copy(&handle, copy_area, &descriptors, &pool, frame);
                 let index = frame.submit_direct(&descriptors);
 
-                let image = capture_image(
+                capture_image(
                     &descriptors.device,
                     &buffer,
                     &buffer_dimensions,
                     Some(index),
                     with_rgba,
-                );
+                )
+            }
+        }
+    }
 
-                // After we've read pixels from a texture enough times, we'll store this buffer so that
-                // future reads will be faster (it'll copy as part of the draw process instead)
-                texture
-                    .copy_count
-                    .set(texture.copy_count.get().saturating_add(1));
+    /// Captures several handles at once.
+    ///
+    /// Every copy that still needs to be made is recorded into a single submission,
+    /// and all resulting buffers are read back after a single wait on the device,
+    /// instead of stalling once per handle.
+    pub fn capture_all(handles: Vec<(Self, RgbaBufRead<'_>)>, frame: &mut ActiveFrame) {
+        let mut captures = Vec::with_capacity(handles.len());
+        let mut device_descriptors = None;
+        let mut needs_submit = false;
 
-                image
+        for (handle, with_rgba) in handles {
+            match handle {
+                QueueSyncHandle::AlreadyCopied {
+                    buffer,
+                    copy_dimensions,
+                    descriptors,
+                    ..
+                } => {
+                    captures.push((buffer, copy_dimensions.padded_bytes_per_row, with_rgba));
+                    device_descriptors = Some(descriptors);
+                }
+                QueueSyncHandle::NotCopied {
+                    handle,
+                    copy_area,
+                    descriptors,
+                    pool,
+                } => {
+                    let (buffer, buffer_dimensions) =
+                        Self::record_copy(&handle, copy_area, &descriptors, &pool, frame);
+                    captures.push((buffer, buffer_dimensions.padded_bytes_per_row, with_rgba));
+                    device_descriptors = Some(descriptors);
+                    needs_submit = true;
+                }
             }
         }
+
+        let Some(descriptors) = device_descriptors else {
+            return;
+        };
+
+        // A single submission covers every copy recorded above. If nothing had to be
+        // copied, we instead wait for all previous submissions to complete.
+        let index = needs_submit.then(|| frame.submit_direct(&descriptors));
+        capture_images(&descriptors.device, captures, index);
+    }
+
+    /// Records a copy of `copy_area` from the given texture into a pooled buffer.
+    /// The copy is executed with the next submission of `frame`.
+    fn record_copy(
+        handle: &BitmapHandle,
+        copy_area: PixelRegion,
+        descriptors: &Descriptors,
+        pool: &BufferPool<wgpu::Buffer, BufferDimensions>,
+        frame: &mut ActiveFrame,
+    ) -> (PoolEntry<wgpu::Buffer, BufferDimensions>, BufferDimensions) {
+        let texture = as_texture(handle);
+
+        let buffer_dimensions = BufferDimensions::new(
+            copy_area.width() as usize,
+            copy_area.height() as usize,
+            texture.texture.format(),
+        );
+
+        let buffer = pool.take(descriptors, buffer_dimensions.clone());
+        frame.command_encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: copy_area.x_min,
+                    y: copy_area.y_min,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(buffer_dimensions.padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: copy_area.width(),
+                height: copy_area.height(),
+                depth_or_array_layers: 1,
+            },
+        );
+
+        // After we've read pixels from a texture enough times, we'll store this buffer so that
+        // future reads will be faster (it'll copy as part of the draw process instead)
+        texture
+            .copy_count
+            .set(texture.copy_count.get().saturating_add(1));
+
+        (buffer, buffer_dimensions)
     }
 }
 
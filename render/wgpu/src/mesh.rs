@@ -8,9 +8,9 @@ use wgpu::util::DeviceExt;
 
 use crate::buffer_builder::BufferBuilder;
 use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
-use ruffle_render::bitmap::BitmapSource;
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
 use ruffle_render::tessellator::{Bitmap, Draw as LyonDraw, DrawType as TessDrawType, Gradient};
-use swf::{CharacterId, GradientInterpolation};
+use swf::{CharacterId, GradientInterpolation, Rectangle, Twips};
 
 /// How big to make gradient textures. Larger will keep more detail, but be slower and use more memory.
 const GRADIENT_SIZE: usize = 256;
@@ -20,6 +20,12 @@ pub struct Mesh {
     pub draws: Vec<Draw>,
     pub vertex_buffer: wgpu::Buffer,
     pub index_buffer: wgpu::Buffer,
+
+    /// The bounds of the shape, in its own coordinates.
+    pub bounds: Rectangle<Twips>,
+
+    /// The bitmaps that the shape is filled with, by address.
+    pub bitmaps: Vec<usize>,
 }
 
 impl ShapeHandleImpl for Mesh {}
@@ -127,6 +133,7 @@ pub enum PendingDrawType {
         bind_group_label: Option<String>,
     },
     Bitmap {
+        handle: BitmapHandle,
         texture_transforms_index: wgpu::BufferAddress,
         texture_view: wgpu::TextureView,
         is_repeating: bool,
@@ -187,6 +194,7 @@ impl PendingDrawType {
             create_debug_label!("Shape {} (bitmap) draw {} bindgroup", shape_id, draw_id);
 
         Some(PendingDrawType::Bitmap {
+            handle,
             texture_transforms_index,
             texture_view,
             is_repeating: bitmap.is_repeating,
@@ -255,6 +263,7 @@ impl PendingDrawType {
                 is_repeating,
                 is_smoothed,
                 bind_group_label,
+                ..
             } => {
                 let binds = BitmapBinds::new(
                     &descriptors.device,
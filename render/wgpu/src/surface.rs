@@ -6,16 +6,21 @@ use crate::blend::ComplexBlend;
 use crate::buffer_pool::TexturePool;
 use crate::dynamic_transforms::DynamicTransforms;
 use crate::filters::FilterSource;
+use crate::globals::Globals;
 use crate::mesh::Mesh;
 use crate::pixel_bender::{run_pixelbender_shader_impl, ShaderMode};
 use crate::surface::commands::{chunk_blends, Chunk, CommandRenderer};
 use crate::utils::{remove_srgb, supported_sample_count};
 use crate::{Descriptors, MaskState, Pipelines};
+use ruffle_render::bitmap::PixelRegion;
 use ruffle_render::commands::CommandList;
+use ruffle_render::damage::Damage;
 use ruffle_render::pixel_bender::{ImageInputTexture, PixelBenderShaderArgument};
 use ruffle_render::quality::StageQuality;
+use std::cell::OnceCell;
 use std::sync::Arc;
-use target::CommandTarget;
+use swf::{Rectangle, Twips};
+use target::{get_whole_frame_bind_group, CommandTarget};
 use tracing::instrument;
 
 use crate::utils::run_copy_pipeline;
@@ -32,6 +37,19 @@ pub struct Surface {
     pipelines: Arc<Pipelines>,
     format: wgpu::TextureFormat,
     actual_surface_format: wgpu::TextureFormat,
+
+    /// A copy of the last frame drawn by `draw_damage_and_copy_to`, which the
+    /// damaged parts of the next one are drawn over.
+    last_frame: Option<LastFrame>,
+}
+
+/// A copy of a frame that is kept around for the next one.
+#[derive(Debug)]
+struct LastFrame {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    globals: Arc<Globals>,
+    whole_frame_bind_group: OnceCell<(wgpu::Buffer, wgpu::BindGroup)>,
 }
 
 impl Surface {
@@ -62,9 +80,129 @@ impl Surface {
             pipelines,
             format: frame_buffer_format,
             actual_surface_format: surface_format,
+            last_frame: None,
         }
     }
 
+    /// Draws the damaged part of a frame over the last one, and copies the result to `frame_view`.
+    ///
+    /// Draws outside of the damaged part are clipped away, so they cost little.
+    /// The whole frame is drawn when there's no last frame yet.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "debug", skip_all)]
+    pub fn draw_damage_and_copy_to<'frame, 'global: 'frame>(
+        &mut self,
+        frame_view: &wgpu::TextureView,
+        damage: &Damage,
+        render_target_mode: RenderTargetMode,
+        descriptors: &'global Descriptors,
+        staging_belt: &'frame mut wgpu::util::StagingBelt,
+        dynamic_transforms: &'global DynamicTransforms,
+        draw_encoder: &'frame mut wgpu::CommandEncoder,
+        meshes: &'global Vec<Mesh>,
+        commands: CommandList,
+        texture_pool: &mut TexturePool,
+    ) {
+        let region = match (damage, &self.last_frame) {
+            (Damage::Partial(region), Some(_)) => self.damaged_pixels(region),
+            _ => Some(PixelRegion::for_whole_size(
+                self.size.width,
+                self.size.height,
+            )),
+        };
+
+        if let Some(region) = region {
+            let target = self.draw_commands_within(
+                render_target_mode,
+                descriptors,
+                meshes,
+                commands,
+                staging_belt,
+                dynamic_transforms,
+                draw_encoder,
+                LayerRef::None,
+                texture_pool,
+                Some(&region),
+            );
+
+            let (size, format) = (self.size, self.format);
+            let last_frame = self.last_frame.get_or_insert_with(|| {
+                let texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+                    label: create_debug_label!("Last frame").as_deref(),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    view_formats: &[format],
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                });
+                LastFrame {
+                    view: texture.create_view(&Default::default()),
+                    texture,
+                    globals: texture_pool.get_globals(descriptors, size.width, size.height),
+                    whole_frame_bind_group: OnceCell::new(),
+                }
+            });
+            let origin = wgpu::Origin3d {
+                x: region.x_min,
+                y: region.y_min,
+                z: 0,
+            };
+            draw_encoder.copy_texture_to_texture(
+                wgpu::ImageCopyTexture {
+                    texture: target.color_texture(),
+                    mip_level: 0,
+                    origin,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::ImageCopyTexture {
+                    texture: &last_frame.texture,
+                    mip_level: 0,
+                    origin,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::Extent3d {
+                    width: region.width(),
+                    height: region.height(),
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        let last_frame = self
+            .last_frame
+            .as_ref()
+            .expect("Last frame is drawn when there is none");
+        run_copy_pipeline(
+            descriptors,
+            self.format,
+            self.actual_surface_format,
+            frame_view,
+            &last_frame.view,
+            get_whole_frame_bind_group(&last_frame.whole_frame_bind_group, descriptors, self.size),
+            &last_frame.globals,
+            1,
+            draw_encoder,
+        );
+    }
+
+    /// The pixels covering a damaged region of the stage, with room for antialiasing
+    /// around the edges, or `None` if they're empty.
+    fn damaged_pixels(&self, region: &Rectangle<Twips>) -> Option<PixelRegion> {
+        if !region.is_valid() {
+            return None;
+        }
+        let mut pixels = PixelRegion::encompassing_twips(
+            (region.x_min, region.y_min),
+            (region.x_max, region.y_max),
+        );
+        pixels.x_min = pixels.x_min.saturating_sub(1);
+        pixels.y_min = pixels.y_min.saturating_sub(1);
+        pixels.clamp(self.size.width, self.size.height);
+        (pixels.width() > 0 && pixels.height() > 0).then_some(pixels)
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[instrument(level = "debug", skip_all)]
     pub fn draw_commands_and_copy_to<'frame, 'global: 'frame>(
@@ -118,6 +256,35 @@ impl Surface {
         draw_encoder: &'frame mut wgpu::CommandEncoder,
         nearest_layer: LayerRef<'frame>,
         texture_pool: &mut TexturePool,
+    ) -> CommandTarget {
+        self.draw_commands_within(
+            render_target_mode,
+            descriptors,
+            meshes,
+            commands,
+            staging_belt,
+            dynamic_transforms,
+            draw_encoder,
+            nearest_layer,
+            texture_pool,
+            None,
+        )
+    }
+
+    /// Draws the commands, only drawing within `scissor` if it's given.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_commands_within<'frame, 'global: 'frame>(
+        &mut self,
+        render_target_mode: RenderTargetMode,
+        descriptors: &'global Descriptors,
+        meshes: &'global Vec<Mesh>,
+        commands: CommandList,
+        staging_belt: &'global mut wgpu::util::StagingBelt,
+        dynamic_transforms: &'global DynamicTransforms,
+        draw_encoder: &'frame mut wgpu::CommandEncoder,
+        nearest_layer: LayerRef<'frame>,
+        texture_pool: &mut TexturePool,
+        scissor: Option<&PixelRegion>,
     ) -> CommandTarget {
         let target = CommandTarget::new(
             descriptors,
@@ -176,6 +343,14 @@ impl Surface {
                             },
                             ..Default::default()
                         });
+                    if let Some(scissor) = scissor {
+                        render_pass.set_scissor_rect(
+                            scissor.x_min,
+                            scissor.y_min,
+                            scissor.width(),
+                            scissor.height(),
+                        );
+                    }
                     render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
                     let mut renderer = CommandRenderer::new(
                         &self.pipelines,
@@ -304,6 +479,14 @@ impl Surface {
                             },
                             ..Default::default()
                         });
+                    if let Some(scissor) = scissor {
+                        render_pass.set_scissor_rect(
+                            scissor.x_min,
+                            scissor.y_min,
+                            scissor.width(),
+                            scissor.height(),
+                        );
+                    }
                     render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
 
                     if needs_stencil {
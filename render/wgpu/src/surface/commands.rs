@@ -14,6 +14,7 @@ use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::lines::{emulate_line, emulate_line_rect};
 use ruffle_render::matrix::Matrix;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
+use ruffle_render::projective::ProjectiveMatrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
 use std::mem;
@@ -567,18 +568,28 @@ impl<'a> WgpuCommandHandler<'a> {
         color_transform: ColorTransform,
         command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
     ) {
-        let transform = Transforms {
-            world_matrix: [
-                [matrix.a, matrix.b, 0.0, 0.0],
-                [matrix.c, matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    matrix.tx.to_pixels() as f32,
-                    matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
+        let world_matrix = [
+            [matrix.a, matrix.b, 0.0, 0.0],
+            [matrix.c, matrix.d, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [
+                matrix.tx.to_pixels() as f32,
+                matrix.ty.to_pixels() as f32,
+                0.0,
+                1.0,
             ],
+        ];
+        self.add_world_matrix_to_current(world_matrix, color_transform, command_builder);
+    }
+
+    fn add_world_matrix_to_current(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: ColorTransform,
+        command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
+    ) {
+        let transform = Transforms {
+            world_matrix,
             mult_color: color_transform.mult_rgba_normalized(),
             add_color: color_transform.add_rgba_normalized(),
         };
@@ -736,6 +747,37 @@ impl CommandHandler for WgpuCommandHandler<'_> {
             }
         });
     }
+
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: ProjectiveMatrix,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        let matrix = {
+            let texture = as_texture(&bitmap);
+            matrix
+                * Matrix::scale(
+                    texture.texture.width() as f32,
+                    texture.texture.height() as f32,
+                )
+        };
+        // The perspective divide is left to the GPU, so that texture coordinates are
+        // interpolated across the bitmap correctly.
+        self.add_world_matrix_to_current(
+            matrix.to_pixel_matrix4(),
+            color_transform,
+            |transform_buffer| DrawCommand::RenderBitmap {
+                bitmap,
+                transform_buffer,
+                smoothing,
+                blend_mode: TrivialBlend::Normal,
+                render_stage3d: false,
+            },
+        );
+    }
+
     fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
         let mut matrix = transform.matrix;
         {
@@ -455,7 +455,7 @@ impl CommandTarget {
     }
 }
 
-fn get_whole_frame_bind_group<'a>(
+pub(crate) fn get_whole_frame_bind_group<'a>(
     once_cell: &'a OnceCell<(wgpu::Buffer, wgpu::BindGroup)>,
     descriptors: &Descriptors,
     size: wgpu::Extent3d,
@@ -99,6 +99,23 @@ impl SwapChainTarget {
             window_surface: surface,
         }
     }
+
+    /// Composites the surface with premultiplied alpha, so that what's behind it shows through
+    /// its transparent parts.
+    ///
+    /// Returns `false` and leaves the surface opaque if the platform doesn't support that.
+    pub fn set_transparent(&mut self, adapter: &wgpu::Adapter, device: &wgpu::Device) -> bool {
+        let capabilities = self.window_surface.get_capabilities(adapter);
+        if !capabilities
+            .alpha_modes
+            .contains(&wgpu::CompositeAlphaMode::PreMultiplied)
+        {
+            return false;
+        }
+        self.surface_config.alpha_mode = wgpu::CompositeAlphaMode::PreMultiplied;
+        self.window_surface.configure(device, &self.surface_config);
+        true
+    }
 }
 
 impl RenderTarget for SwapChainTarget {
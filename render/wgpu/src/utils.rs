@@ -1,6 +1,7 @@
 use crate::buffer_pool::BufferDescription;
 use crate::descriptors::Descriptors;
 use crate::globals::Globals;
+use ruffle_render::bitmap::RgbaBufRead;
 use std::borrow::Cow;
 use wgpu::util::DeviceExt;
 use wgpu::{CommandEncoder, TextureFormat};
@@ -154,6 +155,40 @@ pub fn capture_image<R, F: FnOnce(&[u8], u32) -> R>(
     result
 }
 
+/// Like `capture_image`, but for several buffers at once.
+/// All buffers are mapped before waiting on the device, so that they
+/// can all be read back with a single poll.
+pub fn capture_images<B: std::ops::Deref<Target = wgpu::Buffer>>(
+    device: &wgpu::Device,
+    captures: Vec<(B, u32, RgbaBufRead<'_>)>,
+    index: Option<wgpu::SubmissionIndex>,
+) {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    for (buffer, _, _) in &captures {
+        let sender = sender.clone();
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                sender.send(result).unwrap();
+            });
+    }
+    device.poll(
+        index
+            .map(wgpu::Maintain::WaitForSubmissionIndex)
+            .unwrap_or(wgpu::Maintain::Wait),
+    );
+    for _ in 0..captures.len() {
+        let _ = receiver.recv().expect("MPSC channel must not fail");
+    }
+
+    for (buffer, padded_bytes_per_row, with_rgba) in captures {
+        let map = buffer.slice(..).get_mapped_range();
+        with_rgba(&map, padded_bytes_per_row);
+        drop(map);
+        buffer.unmap();
+    }
+}
+
 #[cfg(not(target_family = "wasm"))]
 pub fn buffer_to_image(
     device: &wgpu::Device,
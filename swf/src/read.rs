@@ -1,7 +1,7 @@
 use crate::extensions::ReadSwfExt;
 use crate::{
     error::{Error, Result},
-    string::{Encoding, SwfStr},
+    string::{Encoding, SwfStr, UTF_8},
     tag_code::TagCode,
     types::*,
 };
@@ -152,13 +152,19 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
 
     // In most SWFs, SetBackgroundColor will be the second or third tag after FileAttributes + Metadata.
     // It's possible for the SetBackgroundColor tag to be missing or appear later in wacky SWFs, so let's
-    // return `None` in this case.
+    // return `None` in this case. The Metadata tag is kept too, as it's written in the same place.
     let mut background_color = None;
+    let mut metadata = None;
     for _ in 0..2 {
-        if let Ok(Tag::SetBackgroundColor(color)) = tag {
-            background_color = Some(color);
-            break;
-        };
+        match tag {
+            Ok(Tag::SetBackgroundColor(color)) => {
+                background_color = Some(color);
+                break;
+            }
+            // Metadata is always UTF-8, as it's only written by SWF 8+ tools.
+            Ok(Tag::Metadata(xml)) => metadata = Some(xml.to_string_lossy(UTF_8)),
+            _ => {}
+        }
         tag = reader.read_tag();
     }
 
@@ -167,6 +173,7 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
             header,
             file_attributes,
             background_color,
+            metadata,
             uncompressed_len: uncompressed_len as i32,
         },
         data,
@@ -1182,7 +1189,7 @@ impl<'a> Reader<'a> {
         Ok(zone)
     }
 
-    fn read_define_font_info(&mut self, version: u8) -> Result<FontInfo<'a>> {
+    pub fn read_define_font_info(&mut self, version: u8) -> Result<FontInfo<'a>> {
         let id = self.read_u16()?;
         let name = self.read_str_with_len()?;
         let flags = FontInfoFlag::from_bits_truncate(self.read_u8()?);
@@ -2653,6 +2660,52 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn read_metadata() {
+        let header = read_from_file("tests/swfs/Avm2DefaultValue.swf").header;
+        assert!(header.has_metadata());
+        let metadata = header.metadata().unwrap();
+        assert!(metadata.starts_with("<rdf:RDF"));
+        assert!(metadata.contains("<dc:title>Apache Flex Application</dc:title>"));
+
+        let header = read_from_file("tests/swfs/uncompressed.swf").header;
+        assert!(!header.has_metadata());
+        assert_eq!(header.metadata(), None);
+    }
+
+    #[test]
+    fn read_metadata_before_background_color() {
+        let header = Header {
+            compression: Compression::None,
+            version: 13,
+            stage_size: Rectangle {
+                x_min: Twips::ZERO,
+                x_max: Twips::from_pixels(640.0),
+                y_min: Twips::ZERO,
+                y_max: Twips::from_pixels(480.0),
+            },
+            frame_rate: Fixed8::from_f32(60.0),
+            num_frames: 1,
+        };
+        let background_color = Color {
+            r: 0x11,
+            g: 0x22,
+            b: 0x33,
+            a: 0xFF,
+        };
+        let tags = [
+            Tag::FileAttributes(FileAttributes::HAS_METADATA),
+            Tag::Metadata(SwfStr::from_utf8_str("<rdf:RDF>\u{e9}</rdf:RDF>")),
+            Tag::SetBackgroundColor(background_color),
+        ];
+        let mut data = vec![];
+        crate::write::write_swf(&header, &tags, &mut data).unwrap();
+
+        let header = decompress_swf(&data[..]).unwrap().header;
+        assert_eq!(header.metadata(), Some("<rdf:RDF>\u{e9}</rdf:RDF>"));
+        assert_eq!(header.background_color(), Some(background_color));
+    }
+
     #[test]
     fn read_invalid_swf() {
         let junk = [0u8; 128];
@@ -99,6 +99,7 @@ pub struct HeaderExt {
     pub(crate) header: Header,
     pub(crate) file_attributes: FileAttributes,
     pub(crate) background_color: Option<SetBackgroundColor>,
+    pub(crate) metadata: Option<String>,
     pub(crate) uncompressed_len: i32,
 }
 
@@ -110,6 +111,7 @@ impl HeaderExt {
             header: Header::default_with_swf_version(version),
             file_attributes: Default::default(),
             background_color: None,
+            metadata: None,
             uncompressed_len: 0,
         }
     }
@@ -121,6 +123,7 @@ impl HeaderExt {
             header: Header::default_with_swf_version(0),
             file_attributes: Default::default(),
             background_color: None,
+            metadata: None,
             uncompressed_len: -1,
         }
     }
@@ -138,6 +141,7 @@ impl HeaderExt {
             header,
             file_attributes: Default::default(),
             background_color: None,
+            metadata: None,
             uncompressed_len: length,
         }
     }
@@ -168,6 +172,14 @@ impl HeaderExt {
         self.file_attributes.contains(FileAttributes::HAS_METADATA)
     }
 
+    /// The XMP metadata of the SWF from the Metadata tag, as RDF/XML.
+    ///
+    /// `None` will be returned if the Metadata tag was not found near the start of the SWF.
+    #[inline]
+    pub fn metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+
     /// Returns the basic SWF header.
     #[inline]
     pub fn swf_header(&self) -> &Header {
@@ -0,0 +1,69 @@
+//! Measures how long a frame takes for content that reads `BitmapData` pixels every frame,
+//! with each `BitmapReadbackMode`.
+//!
+//! Run with `cargo bench -p tests --features imgtests --bench bitmap_readback`.
+
+use anyhow::{anyhow, Context, Result};
+use environment::NativeEnvironment;
+use ruffle_core::bitmap::readback::BitmapReadbackMode;
+use ruffle_test_framework::options::TestOptions;
+use ruffle_test_framework::runner::TestStatus;
+use ruffle_test_framework::test::Test;
+use ruffle_test_framework::vfs::{PhysicalFS, VfsPath};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[path = "../tests/environment.rs"]
+mod environment;
+
+/// The test whose movie is measured, relative to `tests/swfs`.
+const FIXTURE: &str = "avm2/bitmapdata_readback_color_picker";
+
+/// How many times the movie is run for each mode.
+const RUNS: u32 = 20;
+
+fn run(options: TestOptions, root: &VfsPath) -> Result<(Duration, u32)> {
+    let test = Test::from_options(options, root.clone(), FIXTURE.to_string())
+        .context("Couldn't create test")?;
+    if !test.should_run(true, &NativeEnvironment) {
+        return Err(anyhow!("A renderer is required to measure readbacks"));
+    }
+
+    let mut runner = test.create_test_runner(&NativeEnvironment)?;
+    let mut elapsed = Duration::ZERO;
+    let mut frames = 0;
+    loop {
+        let start = Instant::now();
+        runner.tick();
+        elapsed += start.elapsed();
+        frames += 1;
+        match runner.test()? {
+            TestStatus::Continue | TestStatus::Sleep(_) => {}
+            TestStatus::Finished => break,
+        }
+    }
+    Ok((elapsed, frames))
+}
+
+fn main() -> Result<()> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/swfs")
+        .join(FIXTURE);
+    let root = VfsPath::new(PhysicalFS::new(directory));
+    let options = TestOptions::read(&root.join("test.toml")?)?;
+
+    for mode in [BitmapReadbackMode::Strict, BitmapReadbackMode::Throttled] {
+        let mut elapsed = Duration::ZERO;
+        let mut frames = 0;
+        for _ in 0..RUNS {
+            let (run_elapsed, run_frames) = run(options.with_bitmap_readback_mode(mode), &root)?;
+            elapsed += run_elapsed;
+            frames += run_frames;
+        }
+        println!(
+            "{mode:?}: {:.3} ms per frame over {frames} frames",
+            elapsed.as_secs_f64() * 1000.0 / frames as f64
+        );
+    }
+    Ok(())
+}
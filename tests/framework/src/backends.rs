@@ -1,9 +1,11 @@
 mod audio;
+mod camera;
 mod log;
 mod navigator;
 mod ui;
 
 pub use audio::TestAudioBackend;
+pub use camera::TestCameraBackend;
 pub use log::TestLogBackend;
 pub use navigator::TestNavigatorBackend;
 pub use ui::TestUiBackend;
@@ -0,0 +1,71 @@
+use ruffle_core::backend::camera::{CameraBackend, CameraFrame, CaptureStatus};
+
+/// A camera backend with two cameras: one that shows a solid color once it's opened, and one
+/// that the user always refuses access to.
+pub struct TestCameraBackend {
+    status: [CaptureStatus; 2],
+    frame: CameraFrame,
+    frame_number: u64,
+}
+
+impl Default for TestCameraBackend {
+    fn default() -> Self {
+        Self {
+            status: [CaptureStatus::Closed; 2],
+            frame: CameraFrame {
+                width: 0,
+                height: 0,
+                rgba: vec![],
+            },
+            frame_number: 0,
+        }
+    }
+}
+
+impl TestCameraBackend {
+    const ALLOWED: usize = 0;
+    const DENIED: usize = 1;
+
+    /// The color of every pixel captured by the allowed camera.
+    const COLOR: [u8; 4] = [0x33, 0x99, 0xFF, 0xFF];
+}
+
+impl CameraBackend for TestCameraBackend {
+    fn names(&self) -> Vec<String> {
+        vec!["Test Camera".to_string(), "Denied Camera".to_string()]
+    }
+
+    fn open(&mut self, index: usize, width: u32, height: u32, _fps: f64) {
+        match index {
+            Self::ALLOWED => {
+                self.status[index] = CaptureStatus::Active;
+                self.frame = CameraFrame {
+                    width,
+                    height,
+                    rgba: Self::COLOR.repeat((width * height) as usize),
+                };
+                self.frame_number += 1;
+            }
+            Self::DENIED => self.status[index] = CaptureStatus::Denied,
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, index: usize) {
+        if let Some(status) = self.status.get_mut(index) {
+            *status = CaptureStatus::Closed;
+        }
+    }
+
+    fn status(&self, index: usize) -> CaptureStatus {
+        self.status
+            .get(index)
+            .copied()
+            .unwrap_or(CaptureStatus::Closed)
+    }
+
+    fn latest_frame(&mut self, index: usize) -> Option<(u64, &CameraFrame)> {
+        (index == Self::ALLOWED && self.status[index] == CaptureStatus::Active)
+            .then_some((self.frame_number, &self.frame))
+    }
+}
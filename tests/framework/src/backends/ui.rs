@@ -1,8 +1,11 @@
+use crate::backends::TestLogBackend;
 use crate::test::Font;
 use chrono::{DateTime, Utc};
+use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    AccessibilityNode, ClipboardImage, DialogLoaderError, DialogResultFuture, FileDialogResult,
+    FileFilter, FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, PrintPage,
+    UiBackend, US_ENGLISH,
 };
 use url::Url;
 
@@ -76,20 +79,52 @@ impl FileDialogResult for TestFileDialogResult {
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
 /// * Simulated in-memory clipboard
+/// * Simulated printer, which logs every page printed
+/// * Optionally, a simulated screen reader, which logs every description of the stage
 pub struct TestUiBackend {
     fonts: Vec<Font>,
     clipboard: String,
+    clipboard_image: Option<ClipboardImage>,
+    log: TestLogBackend,
+    log_accessibility: bool,
 }
 
 impl TestUiBackend {
-    pub fn new(fonts: Vec<Font>) -> Self {
+    pub fn new(fonts: Vec<Font>, log: TestLogBackend, log_accessibility: bool) -> Self {
         Self {
             fonts,
             clipboard: "".to_string(),
+            clipboard_image: None,
+            log,
+            log_accessibility,
         }
     }
 }
 
+fn log_accessibility_nodes(log: &TestLogBackend, nodes: &[AccessibilityNode], indent: usize) {
+    for node in nodes {
+        let bounds = &node.bounds;
+        let mut line = format!(
+            "{:indent$}{:?} {:?} at ({}, {}) size ({}, {})",
+            "",
+            node.role,
+            node.name,
+            bounds.x_min.to_pixels(),
+            bounds.y_min.to_pixels(),
+            bounds.width().to_pixels(),
+            bounds.height().to_pixels(),
+        );
+        if !node.description.is_empty() {
+            line.push_str(&format!(" description {:?}", node.description));
+        }
+        if !node.shortcut.is_empty() {
+            line.push_str(&format!(" shortcut {:?}", node.shortcut));
+        }
+        log.avm_trace(&line);
+        log_accessibility_nodes(log, &node.children, indent + 2);
+    }
+}
+
 impl UiBackend for TestUiBackend {
     fn mouse_visible(&self) -> bool {
         true
@@ -105,6 +140,24 @@ impl UiBackend for TestUiBackend {
 
     fn set_clipboard_content(&mut self, content: String) {
         self.clipboard = content;
+        self.clipboard_image = None;
+    }
+
+    fn clipboard_image(&mut self) -> Option<ClipboardImage> {
+        self.clipboard_image.clone()
+    }
+
+    fn set_clipboard_image(&mut self, image: ClipboardImage) {
+        self.clipboard = "".to_string();
+        self.clipboard_image = Some(image);
+    }
+
+    fn clear_clipboard_content(&mut self) {
+        self.clipboard = "".to_string();
+    }
+
+    fn clear_clipboard_image(&mut self) {
+        self.clipboard_image = None;
     }
 
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
@@ -117,6 +170,38 @@ impl UiBackend for TestUiBackend {
 
     fn open_virtual_keyboard(&self) {}
 
+    fn is_accessibility_active(&self) -> bool {
+        self.log_accessibility
+    }
+
+    fn update_accessibility_tree(&mut self, nodes: &[AccessibilityNode]) {
+        self.log.avm_trace("UiBackend::update_accessibility_tree:");
+        log_accessibility_nodes(&self.log, nodes, 2);
+    }
+
+    fn can_print(&self) -> bool {
+        true
+    }
+
+    fn print(&mut self, pages: Vec<PrintPage>) {
+        self.log.avm_trace("UiBackend::print:");
+        for (i, page) in pages.iter().enumerate() {
+            let drawn = page
+                .rgba
+                .chunks_exact(4)
+                .filter(|pixel| *pixel != [255, 255, 255, 255])
+                .count();
+            self.log.avm_trace(&format!(
+                "  Page {}: {}x{} pixels at {} per point, {} not white",
+                i + 1,
+                page.width,
+                page.height,
+                page.scale,
+                drawn
+            ));
+        }
+    }
+
     fn close_virtual_keyboard(&self) {}
 
     fn language(&self) -> LanguageIdentifier {
@@ -1,4 +1,4 @@
-use crate::backends::TestAudioBackend;
+use crate::backends::{TestAudioBackend, TestCameraBackend};
 use crate::environment::{Environment, RenderInterface};
 use crate::image_trigger::ImageTrigger;
 use crate::util::write_image;
@@ -6,6 +6,7 @@ use anyhow::{anyhow, Result};
 use approx::relative_eq;
 use image::ImageFormat;
 use regex::Regex;
+use ruffle_core::bitmap::readback::BitmapReadbackMode;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{PlayerBuilder, PlayerRuntime, ViewportDimensions};
 use ruffle_render::backend::RenderBackend;
@@ -29,8 +30,10 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub log_accessibility: bool,
     pub required_features: RequiredFeatures,
     pub fonts: HashMap<String, FontOptions>,
+    pub compare_without_avm2_optimizer: bool,
 }
 
 impl Default for TestOptions {
@@ -47,8 +50,10 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            log_accessibility: false,
             required_features: RequiredFeatures::default(),
             fonts: Default::default(),
+            compare_without_avm2_optimizer: false,
         }
     }
 }
@@ -78,6 +83,22 @@ impl TestOptions {
         Ok(())
     }
 
+    /// The options for running this test again with the AVM2 optimizer
+    /// disabled, expecting the same results from the interpreter.
+    pub fn without_avm2_optimizer(&self) -> Self {
+        let mut options = self.clone();
+        options.player_options.avm2_optimizer_disabled = true;
+        options.compare_without_avm2_optimizer = false;
+        options
+    }
+
+    /// The options for running this test with the given `BitmapData` readback mode.
+    pub fn with_bitmap_readback_mode(&self, mode: BitmapReadbackMode) -> Self {
+        let mut options = self.clone();
+        options.player_options.bitmap_readback_mode = mode;
+        options
+    }
+
     pub fn output_path(&self, test_directory: &VfsPath) -> Result<VfsPath> {
         Ok(test_directory.join(&self.output_path)?)
     }
@@ -149,7 +170,10 @@ pub struct PlayerOptions {
     with_renderer: Option<RenderOptions>,
     with_audio: bool,
     with_video: bool,
+    with_camera: bool,
     runtime: PlayerRuntime,
+    avm2_optimizer_disabled: bool,
+    bitmap_readback_mode: BitmapReadbackMode,
 }
 
 impl PlayerOptions {
@@ -172,8 +196,18 @@ impl PlayerOptions {
             player_builder = player_builder.with_audio(TestAudioBackend::default());
         }
 
+        if self.with_camera {
+            player_builder = player_builder.with_camera(TestCameraBackend::default());
+        }
+
         player_builder = player_builder.with_player_runtime(self.runtime);
 
+        if self.avm2_optimizer_disabled {
+            player_builder = player_builder.with_avm2_optimizer_enabled(false);
+        }
+
+        player_builder = player_builder.with_bitmap_readback_mode(self.bitmap_readback_mode);
+
         if self.with_video {
             #[cfg(feature = "ruffle_video_external")]
             {
@@ -23,6 +23,9 @@ mod shared_object;
 
 const TEST_TOML_NAME: &str = "test.toml";
 
+/// Suffix of the names of tests that run again with the AVM2 optimizer disabled.
+const WITHOUT_AVM2_OPTIMIZER_SUFFIX: &str = "/without_avm2_optimizer";
+
 /// Convert the filter (e.g. from the CLI) to a test name.
 ///
 /// These two values may differ due to how
@@ -85,25 +88,26 @@ fn main() {
 
     let root = Path::new("tests/swfs");
     let mut tests: Vec<Trial> = if filter_exact {
-        look_up_test(root, &args).map_or_else(Vec::new, |trial| vec![trial])
+        look_up_test(root, &args).unwrap_or_default()
     } else {
         walkdir::WalkDir::new(root)
             .into_iter()
             .map(Result::unwrap)
             .filter(|entry| entry.file_type().is_file() && entry.file_name() == TEST_TOML_NAME)
-            .filter_map(|file| {
+            .flat_map(|file| {
                 let name = file
                     .path()
-                    .parent()?
+                    .parent()
+                    .unwrap()
                     .strip_prefix(root)
                     .context("Couldn't strip root prefix from test dir")
                     .unwrap()
                     .to_string_lossy()
                     .replace('\\', "/");
                 if is_candidate(&args, &name) {
-                    Some(run_test(&args, file.path(), &name))
+                    run_tests(&args, file.path(), &name)
                 } else {
-                    None
+                    vec![]
                 }
             })
             .collect()
@@ -131,11 +135,15 @@ fn main() {
     libtest_mimic::run(&args, tests).exit()
 }
 
-fn look_up_test(root: &Path, args: &Arguments) -> Option<Trial> {
-    let name = filter_to_test_name(args.filter.as_ref().unwrap());
+fn look_up_test(root: &Path, args: &Arguments) -> Option<Vec<Trial>> {
+    let filter_name = filter_to_test_name(args.filter.as_ref().unwrap());
+    // Tests run without the AVM2 optimizer share the directory of the original test.
+    let name = filter_name
+        .strip_suffix(WITHOUT_AVM2_OPTIMIZER_SUFFIX)
+        .unwrap_or(&filter_name);
     let absolute_root = std::fs::canonicalize(root).unwrap();
     let path = absolute_root
-        .join(&name)
+        .join(name)
         .join(TEST_TOML_NAME)
         .canonicalize()
         .ok()?;
@@ -143,27 +151,43 @@ fn look_up_test(root: &Path, args: &Arguments) -> Option<Trial> {
     // Make sure that:
     //   1. There's no path traversal (e.g. `cargo test ../../test`)
     //   2. The path is still exact (e.g. `cargo test avm1/../avm1/test`)
-    if path.strip_prefix(absolute_root).ok()? != Path::new(&name).join(TEST_TOML_NAME) {
+    if path.strip_prefix(absolute_root).ok()? != Path::new(name).join(TEST_TOML_NAME) {
         return None;
     }
     if path.is_file() {
-        Some(run_test(args, &path, &name))
+        let trials = run_tests(args, &path, name)
+            .into_iter()
+            .filter(|trial| trial.name() == filter_name)
+            .collect();
+        Some(trials)
     } else {
         None
     }
 }
 
-fn run_test(args: &Arguments, file: &Path, name: &str) -> Trial {
+fn run_tests(args: &Arguments, file: &Path, name: &str) -> Vec<Trial> {
     let root = VfsPath::new(PhysicalFS::new(file.parent().unwrap()));
-    let test = Test::from_options(
-        TestOptions::read(&root.join("test.toml").unwrap())
-            .context("Couldn't load test options")
-            .unwrap(),
-        root,
-        name.to_string(),
-    )
-    .with_context(|| format!("Couldn't create test {name}"))
-    .unwrap();
+    let options = TestOptions::read(&root.join("test.toml").unwrap())
+        .context("Couldn't load test options")
+        .unwrap();
+
+    let mut trials = vec![];
+    if options.compare_without_avm2_optimizer {
+        trials.push(run_test(
+            args,
+            options.without_avm2_optimizer(),
+            root.clone(),
+            format!("{name}{WITHOUT_AVM2_OPTIMIZER_SUFFIX}"),
+        ));
+    }
+    trials.push(run_test(args, options, root, name.to_string()));
+    trials
+}
+
+fn run_test(args: &Arguments, options: TestOptions, root: VfsPath, name: String) -> Trial {
+    let test = Test::from_options(options, root, name.clone())
+        .with_context(|| format!("Couldn't create test {name}"))
+        .unwrap();
 
     let ignore = !test.should_run(!args.list, &NativeEnvironment);
 
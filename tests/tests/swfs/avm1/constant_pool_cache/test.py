@@ -0,0 +1,129 @@
+# Assembles test.swf. The constant pools inside the function bodies can't be
+# written in ActionScript, so the actions are put together by hand.
+import struct
+
+
+def string(s):
+    return s.encode("utf-8") + b"\0"
+
+
+def action(code, payload=b""):
+    if code < 0x80:
+        return bytes([code])
+    return bytes([code]) + struct.pack("<H", len(payload)) + payload
+
+
+def constant_pool(*strings):
+    return action(0x88, struct.pack("<H", len(strings)) + b"".join(map(string, strings)))
+
+
+def push_constant(index):
+    return action(0x96, bytes([8, index]))
+
+
+def push_string(s):
+    return action(0x96, b"\0" + string(s))
+
+
+def push_int(i):
+    return action(0x96, b"\7" + struct.pack("<i", i))
+
+
+def push_null():
+    return action(0x96, b"\2")
+
+
+def define_function(name, body):
+    return action(0x9B, string(name) + struct.pack("<HH", 0, len(body))) + body
+
+
+def call(name):
+    return push_int(0) + push_string(name) + action(0x3D) + action(0x17)
+
+
+TRACE = action(0x26)
+GET_VARIABLE = action(0x1C)
+SET_VARIABLE = action(0x1D)
+GET_MEMBER = action(0x4E)
+SET_MEMBER = action(0x4F)
+INIT_OBJECT = action(0x43)
+
+
+def new_object(name):
+    return push_string(name) + push_int(0) + INIT_OBJECT + SET_VARIABLE
+
+
+def set_member(obj, name, value):
+    return push_string(obj) + GET_VARIABLE + push_string(name) + value + SET_MEMBER
+
+
+def trace_member(obj, name):
+    return push_string(obj) + GET_VARIABLE + push_string(name) + GET_MEMBER + TRACE
+
+
+def own_pool_function(name):
+    return define_function(
+        name,
+        constant_pool(name + ": one", name + ": two")
+        + push_constant(0)
+        + TRACE
+        + push_constant(1)
+        + TRACE,
+    )
+
+
+actions = (
+    # Two functions that share the constant pool of the frame.
+    constant_pool("shared: one", "shared: two")
+    + define_function("sharedOne", push_constant(0) + TRACE)
+    + define_function("sharedTwo", push_constant(1) + TRACE)
+    + call("sharedOne")
+    + call("sharedTwo")
+    # Two functions that define constant pools of their own every time they run.
+    + own_pool_function("first")
+    + own_pool_function("second")
+    + call("first")
+    + call("second")
+    + call("first")
+    + call("second")
+    # Replacing __proto__ has to be seen by lookups through it.
+    + new_object("o")
+    + new_object("p")
+    + set_member("p", "inherited", push_string("from p"))
+    + new_object("q")
+    + set_member("q", "inherited", push_string("from q"))
+    + new_object("r")
+    + trace_member("o", "inherited")
+    + set_member("o", "__proto__", push_string("p") + GET_VARIABLE)
+    + trace_member("o", "inherited")
+    + set_member("r", "__proto__", push_string("o") + GET_VARIABLE)
+    + trace_member("r", "inherited")
+    + set_member("o", "__proto__", push_string("q") + GET_VARIABLE)
+    + trace_member("o", "inherited")
+    + trace_member("r", "inherited")
+    + set_member("o", "__proto__", push_null())
+    + trace_member("o", "inherited")
+    + trace_member("r", "inherited")
+    + action(0x00)
+)
+
+
+def tag(code, data):
+    if len(data) < 0x3F:
+        return struct.pack("<H", code << 6 | len(data)) + data
+    return struct.pack("<HI", code << 6 | 0x3F, len(data)) + data
+
+
+# A 550x400 stage, as a RECT of 15-bit fields.
+rect = bytes([0x78, 0x00, 0x05, 0x5F, 0x00, 0x00, 0x0F, 0xA0, 0x00])
+body = (
+    rect
+    + struct.pack("<HH", 24 << 8, 1)
+    + tag(69, struct.pack("<I", 0))
+    + tag(9, bytes([0xFF, 0xFF, 0xFF]))
+    + tag(12, actions)
+    + tag(1, b"")
+    + tag(0, b"")
+)
+with open("test.swf", "wb") as f:
+    f.write(b"FWS" + bytes([8]) + struct.pack("<I", 8 + len(body)) + body)
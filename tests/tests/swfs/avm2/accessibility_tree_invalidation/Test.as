@@ -0,0 +1,83 @@
+package {
+	import flash.accessibility.Accessibility;
+	import flash.accessibility.AccessibilityProperties;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.text.TextField;
+
+	public class Test extends Sprite {
+		private var tick:int = 1;
+		private var text:TextField;
+		private var logo:Logo;
+
+		public function Test() {
+			text = new TextField();
+			text.x = 10;
+			text.y = 10;
+			text.width = 100;
+			text.height = 20;
+			text.text = "Hello";
+			addChild(text);
+
+			logo = new Logo();
+			logo.graphics.beginFill(0xFF0000);
+			logo.graphics.drawRect(0, 0, 40, 30);
+			logo.x = 200;
+			logo.y = 50;
+			var properties:AccessibilityProperties = new AccessibilityProperties();
+			properties.name = "Logo";
+			properties.description = "Company logo";
+			logo.accessibilityProperties = properties;
+			addChild(logo);
+
+			trace("/// tick 1: added a text field and a named sprite");
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(e:Event):void {
+			tick++;
+			switch (tick) {
+				case 2:
+					trace("/// tick 2: nothing changed");
+					break;
+				case 3:
+					trace("/// tick 3: renamed without calling updateProperties");
+					logo.accessibilityProperties.name = "Renamed";
+					break;
+				case 4:
+					trace("/// tick 4: updateProperties");
+					Accessibility.updateProperties();
+					break;
+				case 5:
+					trace("/// tick 5: changed the text");
+					text.text = "World";
+					break;
+				case 6:
+					trace("/// tick 6: moved the sprite");
+					logo.x += 10;
+					break;
+				case 7:
+					trace("/// tick 7: hid the sprite");
+					logo.visible = false;
+					break;
+				case 8:
+					trace("/// tick 8: nothing changed");
+					break;
+			}
+		}
+	}
+}
+
+import flash.accessibility.AccessibilityProperties;
+import flash.display.Sprite;
+
+class Logo extends Sprite {
+	override public function get accessibilityProperties():AccessibilityProperties {
+		trace("Logo.accessibilityProperties read");
+		return super.accessibilityProperties;
+	}
+
+	override public function set accessibilityProperties(value:AccessibilityProperties):void {
+		super.accessibilityProperties = value;
+	}
+}
@@ -0,0 +1,47 @@
+package {
+    import flash.display.MovieClip;
+    import flash.display.NativeWindow;
+    import flash.display.NativeWindowInitOptions;
+    import flash.events.Event;
+
+    public class Test extends MovieClip {
+        private var window:NativeWindow;
+        private var frame:int = 1;
+
+        public function Test() {
+            window = new NativeWindow(new NativeWindowInitOptions());
+            var child:MovieClip = new MovieClip();
+            window.stage.addChild(child);
+
+            for each (var type:String in [Event.ENTER_FRAME, Event.FRAME_CONSTRUCTED, Event.EXIT_FRAME]) {
+                addEventListener(type, logger("main"));
+                child.addEventListener(type, logger("window"));
+            }
+            addEventListener(Event.ENTER_FRAME, function(e:Event):void {
+                trace("mouse: main stage " + stage.mouseX + "," + stage.mouseY +
+                    " window stage " + window.stage.mouseX + "," + window.stage.mouseY);
+            });
+            child.addEventListener(Event.EXIT_FRAME, function(e:Event):void {
+                frame++;
+            });
+
+            var extra:Array = [];
+            try {
+                while (extra.length < 100) {
+                    extra.push(new NativeWindow(new NativeWindowInitOptions()));
+                }
+            } catch (e:Error) {
+                trace("opened " + extra.length + " more windows, then: " + e.errorID + " " + e.message);
+            }
+            extra.pop().close();
+            extra.push(new NativeWindow(new NativeWindowInitOptions()));
+            trace("reopened after closing one: " + extra.length);
+        }
+
+        private function logger(name:String):Function {
+            return function(e:Event):void {
+                trace("frame " + frame + ": " + name + " " + e.type);
+            };
+        }
+    }
+}
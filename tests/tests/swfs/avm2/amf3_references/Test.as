@@ -0,0 +1,42 @@
+package {
+	import flash.display.MovieClip;
+	import flash.net.ObjectEncoding;
+	import flash.utils.ByteArray;
+	import flash.utils.Dictionary;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var repeated: Object = {a: 1};
+			var array: Array = roundtrip("Repeated object", [repeated, repeated]);
+			trace("Same object: " + (array[0] === array[1]));
+
+			var cyclic: Object = {};
+			cyclic.self = cyclic;
+			var readCyclic: Object = roundtrip("Cyclic object", cyclic);
+			trace("Refers to itself: " + (readCyclic.self === readCyclic));
+
+			var dictionary: Dictionary = new Dictionary();
+			var key: Object = {};
+			dictionary[key] = key;
+			var readDictionary: Object = roundtrip("Dictionary", dictionary);
+			for (var readKey: Object in readDictionary) {
+				trace("Key is its own value: " + (readDictionary[readKey] === readKey));
+			}
+		}
+
+		private function roundtrip(name: String, value: Object): Object {
+			var out: ByteArray = new ByteArray();
+			out.objectEncoding = ObjectEncoding.AMF3;
+			out.writeObject(value);
+			out.position = 0;
+
+			var bytes: Array = [];
+			for (var i: int = 0; i < out.length; i++) {
+				bytes.push(out.readUnsignedByte());
+			}
+			trace(name + ": " + bytes);
+			out.position = 0;
+			return out.readObject();
+		}
+	}
+}
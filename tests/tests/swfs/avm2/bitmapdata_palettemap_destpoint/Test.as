@@ -0,0 +1,47 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		private function dump(bitmap:BitmapData):void {
+			for (var y:int = 0; y < bitmap.height; y++) {
+				for (var x:int = 0; x < bitmap.width; x++) {
+					trace("getPixel32(" + x + ", " + y + ") = 0x" + bitmap.getPixel32(x, y).toString(16));
+				}
+			}
+		}
+
+		public function Test() {
+			var invertRed:Array = [];
+			for (var i:int = 0; i < 256; i++) {
+				invertRed.push((255 - i) << 16);
+			}
+
+			var source:BitmapData = new BitmapData(2, 2, false, 0);
+			source.setPixel32(0, 0, 0xFF102030);
+			source.setPixel32(1, 0, 0xFF405060);
+			source.setPixel32(0, 1, 0xFF708090);
+			source.setPixel32(1, 1, 0xFFA0B0C0);
+
+			trace("// opaque target, destPoint = (1, 2)");
+			var target:BitmapData = new BitmapData(3, 4, false, 0xFF000000);
+			target.paletteMap(source, source.rect, new Point(1, 2), invertRed);
+			dump(target);
+
+			trace("// opaque target, destPoint = (2, 3)");
+			target = new BitmapData(3, 4, false, 0xFF000000);
+			target.paletteMap(source, source.rect, new Point(2, 3), invertRed);
+			dump(target);
+
+			var transparentSource:BitmapData = new BitmapData(2, 2, true, 0xFFFFFFFF);
+			transparentSource.setPixel32(1, 1, 0x80402010);
+
+			trace("// transparent target, sourceRect = (1, 1, 1, 1), destPoint = (0, 1)");
+			target = new BitmapData(2, 3, true, 0);
+			target.paletteMap(transparentSource, new Rectangle(1, 1, 1, 1), new Point(0, 1), invertRed);
+			dump(target);
+		}
+	}
+}
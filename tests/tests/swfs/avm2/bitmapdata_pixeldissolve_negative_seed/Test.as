@@ -0,0 +1,41 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+
+	public class Test extends MovieClip {
+		private static const FILL:uint = 0xFFFF0000;
+
+		private function countFilled(bitmap:BitmapData):int {
+			var count:int = 0;
+			for (var x:int = 0; x < bitmap.width; x++) {
+				for (var y:int = 0; y < bitmap.height; y++) {
+					if (bitmap.getPixel32(x, y) == FILL) {
+						count++;
+					}
+				}
+			}
+			return count;
+		}
+
+		// Every round should dissolve `numPixels` new pixels (plus the one at (0, 0)),
+		// until the whole bitmap is filled.
+		private function dissolveRounds(rounds:int, width:int, height:int, seed:int, numPixels:int):void {
+			trace("Dissolving for " + rounds + " rounds, with data: width: " + width + ", height: " + height + ", seed: " + seed + ", numPixels: " + numPixels);
+			var bitmap:BitmapData = new BitmapData(width, height, false, 0xFFCCCCCC);
+			for (var round:int = 1; round <= rounds; round++) {
+				seed = bitmap.pixelDissolve(bitmap, bitmap.rect, new Point(0, 0), seed, numPixels, FILL);
+				trace("(" + round + ") Overwritten pixel count: " + countFilled(bitmap));
+			}
+			trace("");
+		}
+
+		public function Test() {
+			dissolveRounds(20, 4, 4, -1, 1);
+			dissolveRounds(10, 5, 3, -123456789, 2);
+			dissolveRounds(10, 3, 3, int.MIN_VALUE, 1);
+			dissolveRounds(16, 10, 10, -7, 7);
+			dissolveRounds(1, 6, 6, -99, 35);
+		}
+	}
+}
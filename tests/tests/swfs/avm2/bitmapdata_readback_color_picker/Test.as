@@ -0,0 +1,53 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	// Picks colors from a bitmap that's redrawn every frame, as a color picker or a
+	// minimap would. Each frame reads what the previous one drew, then draws again.
+	// With throttled readbacks, reads may see the frame before that instead.
+	public class Test extends Sprite {
+		private var bitmap:BitmapData = new BitmapData(256, 256, false, 0);
+		private var shape:Shape = new Shape();
+		private var frame:int = 0;
+		private var reads:int = 0;
+		private var unknown:int = 0;
+
+		public function Test() {
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function colorOf(frame:int):uint {
+			return (frame * 0x050301) & 0xFFFFFF;
+		}
+
+		private function onEnterFrame(event:Event):void {
+			if (frame > 0) {
+				var last:uint = colorOf(frame - 1);
+				var beforeLast:uint = frame > 1 ? colorOf(frame - 2) : 0;
+				for (var y:int = 8; y < 256; y += 64) {
+					for (var x:int = 8; x < 256; x += 64) {
+						reads++;
+						var color:uint = bitmap.getPixel(x, y);
+						if (color != last && color != beforeLast) {
+							unknown++;
+						}
+					}
+				}
+			}
+
+			shape.graphics.clear();
+			shape.graphics.beginFill(colorOf(frame));
+			shape.graphics.drawRect(0, 0, 256, 256);
+			shape.graphics.endFill();
+			bitmap.draw(shape);
+
+			frame++;
+			if (frame == 50) {
+				trace("read " + reads + " pixels, " + unknown + " not drawn by the last two frames");
+				removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+			}
+		}
+	}
+}
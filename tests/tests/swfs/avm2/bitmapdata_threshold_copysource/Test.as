@@ -0,0 +1,45 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		private function dump(bitmap:BitmapData):void {
+			for (var y:int = 0; y < bitmap.height; y++) {
+				for (var x:int = 0; x < bitmap.width; x++) {
+					trace("getPixel32(" + x + ", " + y + ") = 0x" + bitmap.getPixel32(x, y).toString(16));
+				}
+			}
+		}
+
+		public function Test() {
+			// Semi-transparent pixels are stored premultiplied, but tested and copied un-multiplied.
+			var source:BitmapData = new BitmapData(3, 1, true, 0);
+			source.setPixel32(0, 0, 0x80402010);
+			source.setPixel32(1, 0, 0xFF00FF00);
+			source.setPixel32(2, 0, 0x40FFFFFF);
+
+			var target:BitmapData = new BitmapData(3, 1, true, 0xFF000000);
+			trace("// transparent target, == 0x80402010, copySource = false");
+			trace(target.threshold(source, source.rect, new Point(0, 0), "==", 0x80402010, 0x800000FF, 0xFFFFFFFF, false));
+			dump(target);
+
+			target = new BitmapData(3, 1, true, 0xFF000000);
+			trace("// transparent target, == 0xFF00FF00, copySource = true");
+			trace(target.threshold(source, source.rect, new Point(0, 0), "==", 0xFF00FF00, 0x800000FF, 0xFFFFFFFF, true));
+			dump(target);
+
+			target = new BitmapData(3, 1, false, 0xFF000000);
+			trace("// opaque target, == 0xFF00FF00, copySource = true");
+			trace(target.threshold(source, source.rect, new Point(0, 0), "==", 0xFF00FF00, 0x800000FF, 0xFFFFFFFF, true));
+			dump(target);
+
+			// Copied pixels come from the source rect, not from the destination coordinates.
+			target = new BitmapData(2, 1, true, 0);
+			trace("// offset source rect, < 0x80000000 on alpha, copySource = true");
+			trace(target.threshold(source, new Rectangle(1, 0, 2, 1), new Point(0, 0), "<", 0x80000000, 0xFFFF0000, 0xFF000000, true));
+			dump(target);
+		}
+	}
+}
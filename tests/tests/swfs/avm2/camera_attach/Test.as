@@ -0,0 +1,66 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.StatusEvent;
+    import flash.media.Camera;
+    import flash.media.Video;
+
+    public class Test extends Sprite {
+        private var frame:int = 0;
+        private var video:Video;
+        private var camera:Camera;
+        private var denied:Camera;
+
+        public function Test() {
+            trace("Camera.names: " + Camera.names);
+
+            camera = Camera.getCamera();
+            camera.addEventListener(StatusEvent.STATUS, onStatus);
+            trace("muted before attachCamera: " + camera.muted);
+
+            video = new Video();
+            video.attachCamera(camera);
+            trace("muted after attachCamera: " + camera.muted);
+            addChild(video);
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onStatus(event:StatusEvent):void {
+            trace("status: " + event.code + " (" + event.level + ")");
+        }
+
+        private function onEnterFrame(event:Event):void {
+            frame++;
+            switch (frame) {
+                case 2:
+                    removeChild(video);
+                    trace("muted after removing the video: " + camera.muted);
+                    break;
+                case 3:
+                    addChild(video);
+                    trace("muted after adding the video again: " + camera.muted);
+                    break;
+                case 4:
+                    video.attachCamera(null);
+                    trace("muted after attachCamera(null): " + camera.muted);
+                    break;
+                case 5:
+                    denied = Camera.getCamera("1");
+                    denied.addEventListener(StatusEvent.STATUS, onStatus);
+                    video.attachCamera(denied);
+                    trace("denied camera muted: " + denied.muted);
+                    break;
+                case 7:
+                    video.attachCamera(camera);
+                    trace("muted after switching back: " + camera.muted + ", denied camera muted: " + denied.muted);
+                    break;
+                case 9:
+                    removeChild(video);
+                    trace("muted after removing the video: " + camera.muted);
+                    removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+                    break;
+            }
+        }
+    }
+}
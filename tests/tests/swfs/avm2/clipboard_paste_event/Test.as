@@ -0,0 +1,75 @@
+package {
+	import flash.desktop.Clipboard;
+	import flash.desktop.ClipboardFormats;
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends Sprite {
+		private var frame:int = 0;
+
+		public function Test() {
+			trace("/// getData outside of a paste event");
+			tryGetData();
+
+			stage.addEventListener(Event.PASTE, onPaste);
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function tryGetData():void {
+			try {
+				trace(Clipboard.generalClipboard.getData(ClipboardFormats.TEXT_FORMAT));
+			} catch (e:SecurityError) {
+				trace("SecurityError " + e.errorID);
+			}
+		}
+
+		private function traceFormats():void {
+			var clipboard:Clipboard = Clipboard.generalClipboard;
+			trace("hasFormat(TEXT): " + clipboard.hasFormat(ClipboardFormats.TEXT_FORMAT));
+			trace("hasFormat(BITMAP): " + clipboard.hasFormat(ClipboardFormats.BITMAP_FORMAT));
+		}
+
+		private function onPaste(e:Event):void {
+			var clipboard:Clipboard = Clipboard.generalClipboard;
+			trace("/// paste event, target is stage: " + (e.target == stage));
+			trace("getData(TEXT): " + clipboard.getData(ClipboardFormats.TEXT_FORMAT));
+			traceFormats();
+
+			trace("/// clearData(BITMAP) keeps the text");
+			clipboard.clearData(ClipboardFormats.BITMAP_FORMAT);
+			traceFormats();
+
+			trace("/// clearData(TEXT)");
+			clipboard.clearData(ClipboardFormats.TEXT_FORMAT);
+			traceFormats();
+			trace("getData(TEXT): " + clipboard.getData(ClipboardFormats.TEXT_FORMAT));
+
+			trace("/// setData(BITMAP)");
+			var bitmap:BitmapData = new BitmapData(3, 2, true, 0xFF336699);
+			bitmap.setPixel32(2, 1, 0x80FF0000);
+			trace("setData: " + clipboard.setData(ClipboardFormats.BITMAP_FORMAT, bitmap));
+			traceFormats();
+			var pasted:BitmapData = clipboard.getData(ClipboardFormats.BITMAP_FORMAT) as BitmapData;
+			trace("getData(BITMAP): " + pasted.width + "x" + pasted.height);
+			trace("pixel (0, 0): " + pasted.getPixel32(0, 0).toString(16));
+			trace("pixel (2, 1): " + pasted.getPixel32(2, 1).toString(16));
+
+			trace("/// clearData(TEXT) keeps the bitmap");
+			clipboard.clearData(ClipboardFormats.TEXT_FORMAT);
+			traceFormats();
+
+			trace("/// clearData(BITMAP)");
+			clipboard.clearData(ClipboardFormats.BITMAP_FORMAT);
+			traceFormats();
+		}
+
+		private function onEnterFrame(e:Event):void {
+			frame++;
+			if (frame == 2) {
+				trace("/// getData after the paste event");
+				tryGetData();
+			}
+		}
+	}
+}
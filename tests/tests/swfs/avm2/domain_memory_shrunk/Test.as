@@ -0,0 +1,58 @@
+package {
+    import avm2.intrinsics.memory.si8;
+    import avm2.intrinsics.memory.si16;
+    import avm2.intrinsics.memory.si32;
+    import avm2.intrinsics.memory.sf32;
+    import avm2.intrinsics.memory.sf64;
+    import avm2.intrinsics.memory.li8;
+    import avm2.intrinsics.memory.li16;
+    import avm2.intrinsics.memory.li32;
+    import avm2.intrinsics.memory.lf32;
+    import avm2.intrinsics.memory.lf64;
+    import flash.system.ApplicationDomain;
+    import flash.utils.ByteArray;
+    import flash.display.Sprite;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var memory:ByteArray = new ByteArray();
+            memory.length = 1024;
+            ApplicationDomain.currentDomain.domainMemory = memory;
+
+            // The domain keeps using the ByteArray after it shrinks below the size of a value.
+            memory.length = 3;
+            trace("// length = 3");
+            test();
+
+            memory.length = 1;
+            trace("// length = 1");
+            test();
+
+            memory.length = 0;
+            trace("// length = 0");
+            test();
+        }
+
+        private function test():void {
+            attempt("si8(1, 0)", function():* { si8(1, 0); return li8(0); });
+            attempt("si16(0x0202, 0)", function():* { si16(0x0202, 0); return li16(0); });
+            attempt("si16(0x0303, 1)", function():* { si16(0x0303, 1); return li16(1); });
+            attempt("si32(4, 0)", function():* { si32(4, 0); return li32(0); });
+            attempt("sf32(5, 0)", function():* { sf32(5, 0); return lf32(0); });
+            attempt("sf64(6, 0)", function():* { sf64(6, 0); return lf64(0); });
+            attempt("li8(2)", function():* { return li8(2); });
+            attempt("li16(2)", function():* { return li16(2); });
+            attempt("li32(0)", function():* { return li32(0); });
+            attempt("lf32(0)", function():* { return lf32(0); });
+            attempt("lf64(0)", function():* { return lf64(0); });
+        }
+
+        private function attempt(name:String, f:Function):void {
+            try {
+                trace(name + ": " + f());
+            } catch (e:Error) {
+                trace(name + ": " + e);
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+# Replaces the calls to `avm2.intrinsics.memory` functions in test.swf with the domain
+# memory opcodes, for compilers that don't emit them. Run this after compiling Test.as.
+#
+# Only calls with constant arguments are replaced, such as `si8(1, 0)`.
+import re
+import struct
+
+OPCODES = {
+    "li8": 0x35, "li16": 0x36, "li32": 0x37, "lf32": 0x38, "lf64": 0x39,
+    "si8": 0x3A, "si16": 0x3B, "si32": 0x3C, "sf32": 0x3D, "sf64": 0x3E,
+}
+NOP = 0x02
+POP = 0x29
+
+
+def read_u30(data, pos):
+    value = 0
+    for i in range(5):
+        byte = data[pos]
+        pos += 1
+        value |= (byte & 0x7F) << (7 * i)
+        if not byte & 0x80:
+            break
+    return value, pos
+
+
+def write_u30(value):
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def intrinsic_multinames(abc):
+    """Finds the index of the multiname of each intrinsic function."""
+    pos = 4  # minor and major version
+    count, pos = read_u30(abc, pos)
+    for _ in range(max(count - 1, 0)):  # ints
+        _, pos = read_u30(abc, pos)
+    count, pos = read_u30(abc, pos)
+    for _ in range(max(count - 1, 0)):  # uints
+        _, pos = read_u30(abc, pos)
+    count, pos = read_u30(abc, pos)
+    pos += 8 * max(count - 1, 0)  # doubles
+    strings = [None]
+    count, pos = read_u30(abc, pos)
+    for _ in range(max(count - 1, 0)):
+        length, pos = read_u30(abc, pos)
+        strings.append(abc[pos:pos + length].decode("utf-8"))
+        pos += length
+    count, pos = read_u30(abc, pos)
+    for _ in range(max(count - 1, 0)):  # namespaces
+        pos += 1
+        _, pos = read_u30(abc, pos)
+    count, pos = read_u30(abc, pos)
+    for _ in range(max(count - 1, 0)):  # namespace sets
+        size, pos = read_u30(abc, pos)
+        for _ in range(size):
+            _, pos = read_u30(abc, pos)
+
+    found = {}
+    count, pos = read_u30(abc, pos)
+    for index in range(1, count):
+        kind = abc[pos]
+        pos += 1
+        if kind in (0x07, 0x0D, 0x09, 0x0E):  # QName(A), Multiname(A)
+            if kind in (0x07, 0x0D):
+                _, pos = read_u30(abc, pos)  # namespace
+                name, pos = read_u30(abc, pos)
+            else:
+                name, pos = read_u30(abc, pos)
+                _, pos = read_u30(abc, pos)  # namespace set
+            if strings[name] in OPCODES:
+                found[index] = strings[name]
+        elif kind in (0x0F, 0x10):  # RTQName(A)
+            _, pos = read_u30(abc, pos)
+        elif kind in (0x11, 0x12):  # RTQNameL(A)
+            pass
+        elif kind in (0x1B, 0x1C):  # MultinameL(A)
+            _, pos = read_u30(abc, pos)
+        elif kind == 0x1D:  # TypeName
+            _, pos = read_u30(abc, pos)
+            size, pos = read_u30(abc, pos)
+            for _ in range(size):
+                _, pos = read_u30(abc, pos)
+        else:
+            raise ValueError(f"Unknown multiname kind {kind:#x}")
+    return found
+
+
+# pushbyte, pushshort, pushint, pushuint, pushdouble
+PUSH = rb"(?:\x24.|[\x25\x2D\x2E\x2F][\x80-\xFF]{0,4}[\x00-\x7F])"
+
+
+def patch_abc(abc):
+    for index, name in intrinsic_multinames(abc).items():
+        index = write_u30(index)
+        call = re.compile(
+            rb"\x5D" + re.escape(index) + rb"(" + PUSH + rb"*)\x46" + re.escape(index) + rb"[\x01\x02](\x29?)",
+            re.DOTALL,
+        )
+
+        def replace(match, name=name, index=index):
+            arguments, pop = match.group(1), match.group(2)
+            # Loads leave their result on the stack, but stores don't.
+            keep_pop = pop and not name.startswith("s")
+            out = bytes([NOP] * (1 + len(index))) + arguments + bytes([OPCODES[name]])
+            # Keep the length of the code the same, so that jumps still land in the same place.
+            padding = len(match.group(0)) - len(out) - len(pop if keep_pop else b"")
+            return out + bytes([NOP] * padding) + (pop if keep_pop else b"")
+
+        abc = call.sub(replace, abc)
+    return abc
+
+
+def main():
+    with open("test.swf", "rb") as file:
+        swf = bytearray(file.read())
+    assert swf[:3] == b"FWS", "Expected an uncompressed SWF"
+
+    rect_bits = swf[8] >> 3
+    pos = 8 + (5 + 4 * rect_bits + 7) // 8 + 4
+    while pos < len(swf):
+        header, = struct.unpack("<H", swf[pos:pos + 2])
+        code, length = header >> 6, header & 0x3F
+        start = pos + 2
+        if length == 0x3F:
+            length, = struct.unpack("<I", swf[start:start + 4])
+            start += 4
+        if code == 72:  # DoABC
+            swf[start:start + length] = patch_abc(bytes(swf[start:start + length]))
+        elif code == 82:  # DoABC2
+            abc_start = swf.index(b"\0", start + 4) + 1
+            swf[abc_start:start + length] = patch_abc(bytes(swf[abc_start:start + length]))
+        pos = start + length
+
+    with open("test.swf", "wb") as file:
+        file.write(swf)
+
+
+if __name__ == "__main__":
+    main()
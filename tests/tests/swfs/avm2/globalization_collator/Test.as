@@ -0,0 +1,36 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.Collator;
+	import flash.globalization.CollatorMode;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var sorting:Collator = new Collator("en-US");
+			trace("// Sorting");
+			trace(sorting.actualLocaleIDName, sorting.lastOperationStatus);
+			trace(sorting.ignoreCase, sorting.ignoreDiacritics, sorting.numericComparison);
+			trace(sorting.compare("apple", "Banana"));
+			trace(sorting.compare("résumé", "resume"));
+			trace(sorting.compare("apple", "Apple"));
+			trace(sorting.equals("apple", "apple"));
+
+			var words:Array = ["banana", "Apple", "cherry", "apple", "Ápple"];
+			words.sort(sorting.compare);
+			trace(words);
+
+			trace("// numericComparison = true");
+			trace(sorting.compare("file10", "file9"));
+			sorting.numericComparison = true;
+			trace(sorting.compare("file10", "file9"));
+
+			trace("// Matching");
+			var matching:Collator = new Collator("en-US", CollatorMode.MATCHING);
+			trace(matching.ignoreCase, matching.ignoreDiacritics, matching.ignoreCharacterWidth, matching.ignoreKanaType);
+			trace(matching.compare("RÉSUMÉ", "resume"));
+			trace(matching.equals("ＡＢＣ", "abc"));
+
+			trace("// String.localeCompare only compares code units");
+			trace("apple".localeCompare("Banana") > 0);
+		}
+	}
+}
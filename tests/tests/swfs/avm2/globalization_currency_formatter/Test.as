@@ -0,0 +1,38 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.CurrencyFormatter;
+	import flash.globalization.CurrencyParseResult;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var cf:CurrencyFormatter = new CurrencyFormatter("en-US");
+			trace("// en-US");
+			trace(cf.actualLocaleIDName, cf.lastOperationStatus);
+			trace(cf.currencyISOCode, cf.currencySymbol, cf.fractionalDigits, cf.positiveCurrencyFormat, cf.negativeCurrencyFormat);
+			trace(cf.format(1234.5));
+			trace(cf.format(1234.5, true));
+			trace(cf.format(-0.5, true));
+			trace(cf.formattingWithCurrencySymbolIsSafe("USD"), cf.formattingWithCurrencySymbolIsSafe("EUR"));
+
+			trace("// Parsing");
+			var result:CurrencyParseResult = cf.parse("$12.50");
+			trace(result.value, result.currencyString);
+			result = cf.parse("USD 3");
+			trace(result.value, result.currencyString);
+			result = cf.parse("abc");
+			trace(result.value, cf.lastOperationStatus);
+
+			trace("// setCurrency");
+			cf.setCurrency("EUR", "€");
+			cf.positiveCurrencyFormat = 2;
+			trace(cf.format(10, true));
+			trace(cf.format(10));
+
+			trace("// de-DE");
+			var de:CurrencyFormatter = new CurrencyFormatter("de-DE");
+			trace(de.currencyISOCode, de.currencySymbol, de.positiveCurrencyFormat, de.negativeCurrencyFormat);
+			trace(de.format(1234.5, true));
+			trace(de.format(-1234.5, true));
+		}
+	}
+}
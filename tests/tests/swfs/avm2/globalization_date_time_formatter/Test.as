@@ -0,0 +1,53 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.DateTimeFormatter;
+	import flash.globalization.DateTimeNameStyle;
+	import flash.globalization.DateTimeStyle;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var date:Date = new Date(Date.UTC(2001, 1, 3, 16, 5, 6));
+
+			var dtf:DateTimeFormatter = new DateTimeFormatter("en-US");
+			trace("// en-US");
+			trace(dtf.actualLocaleIDName, dtf.lastOperationStatus);
+			trace(dtf.getDateStyle(), dtf.getTimeStyle(), dtf.getFirstWeekday());
+			trace(dtf.getDateTimePattern());
+			trace(dtf.formatUTC(date));
+
+			dtf.setDateTimeStyles(DateTimeStyle.MEDIUM, DateTimeStyle.MEDIUM);
+			trace(dtf.getDateTimePattern());
+			trace(dtf.formatUTC(date));
+
+			dtf.setDateTimeStyles(DateTimeStyle.SHORT, DateTimeStyle.NONE);
+			trace(dtf.getDateTimePattern());
+			trace(dtf.formatUTC(date));
+
+			dtf.setDateTimeStyles(DateTimeStyle.NONE, DateTimeStyle.SHORT);
+			trace(dtf.getDateTimePattern());
+			trace(dtf.formatUTC(date));
+
+			trace("// Custom pattern");
+			dtf.setDateTimePattern("yyyy-MM-dd'T'HH:mm:ss");
+			trace(dtf.getDateStyle(), dtf.getTimeStyle());
+			trace(dtf.formatUTC(date));
+			trace(dtf.format(date));
+
+			trace("// Names");
+			trace(dtf.getMonthNames(DateTimeNameStyle.LONG_ABBREVIATION).join(","));
+			trace(dtf.getWeekdayNames(DateTimeNameStyle.SHORT_ABBREVIATION).join(","));
+
+			trace("// de-DE");
+			var de:DateTimeFormatter = new DateTimeFormatter("de-DE");
+			trace(de.getFirstWeekday());
+			trace(de.formatUTC(date));
+			de.setDateTimeStyles(DateTimeStyle.SHORT, DateTimeStyle.SHORT);
+			trace(de.formatUTC(date));
+
+			trace("// ru-RU");
+			var ru:DateTimeFormatter = new DateTimeFormatter("ru-RU");
+			ru.setDateTimePattern("d MMMM");
+			trace(ru.formatUTC(date));
+		}
+	}
+}
@@ -0,0 +1,37 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.LocaleID;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace(LocaleID.DEFAULT);
+
+			describe(new LocaleID("zh-Hant-TW"));
+			describe(new LocaleID("de_DE_POSIX@collation=phonebook;currency=EUR"));
+			describe(new LocaleID("ar-EG"));
+
+			var preferred:Vector.<String> = LocaleID.determinePreferredLocales(
+				new <String>["fr-CA", "de"],
+				new <String>["en-US", "fr-FR", "de-DE"]);
+			trace(preferred.join(","));
+		}
+
+		private function describe(id:LocaleID):void {
+			trace("// " + id.name);
+			trace("language: \"" + id.getLanguage() + "\"");
+			trace("script: \"" + id.getScript() + "\"");
+			trace("region: \"" + id.getRegion() + "\"");
+			trace("variant: \"" + id.getVariant() + "\"");
+
+			var keywords:Object = id.getKeysAndValues();
+			var keys:Array = [];
+			for (var key:String in keywords) {
+				keys.push(key + "=" + keywords[key]);
+			}
+			keys.sort();
+			trace("keywords: " + keys.join(";"));
+			trace("right to left: " + id.isRightToLeft());
+			trace("status: " + id.lastOperationStatus);
+		}
+	}
+}
@@ -0,0 +1,50 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.NumberFormatter;
+	import flash.globalization.NumberParseResult;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var nf:NumberFormatter = new NumberFormatter("en-US");
+			trace("// en-US");
+			trace(nf.lastOperationStatus);
+			trace(nf.actualLocaleIDName);
+			trace(nf.decimalSeparator, nf.groupingSeparator, nf.groupingPattern, nf.negativeNumberFormat, nf.fractionalDigits);
+			trace(nf.formatNumber(1234567.891));
+			trace(nf.formatInt(-42));
+			trace(nf.formatUint(4000000000));
+
+			trace("// trailingZeros = false");
+			nf.trailingZeros = false;
+			trace(nf.formatNumber(2.5));
+			trace(nf.formatNumber(3));
+
+			trace("// leadingZero = false");
+			nf.leadingZero = false;
+			trace(nf.formatNumber(0.25));
+
+			trace("// negativeNumberFormat = 0");
+			nf.negativeNumberFormat = 0;
+			trace(nf.formatNumber(-1.5));
+
+			trace("// useGrouping = false");
+			nf.useGrouping = false;
+			trace(nf.formatNumber(12345.5));
+
+			trace("// de-DE");
+			var de:NumberFormatter = new NumberFormatter("de-DE");
+			trace(de.actualLocaleIDName, de.lastOperationStatus);
+			trace(de.formatNumber(1234.5));
+			trace(de.parseNumber("1.234,5"));
+
+			trace("// fr-FR");
+			var fr:NumberFormatter = new NumberFormatter("fr-FR");
+			var result:NumberParseResult = fr.parse("123,56 euros");
+			trace(result.value, result.startIndex, result.endIndex);
+
+			trace("// Parse errors");
+			var en:NumberFormatter = new NumberFormatter("en-US");
+			trace(en.parseNumber("12 apples"), en.lastOperationStatus);
+		}
+	}
+}
@@ -0,0 +1,37 @@
+package {
+	import flash.display.Sprite;
+	import flash.system.IME;
+	import flash.text.TextField;
+	import flash.text.TextFieldType;
+	import flash.text.TextFormat;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var text:TextField = new TextField();
+			text.type = TextFieldType.INPUT;
+			text.text = "hello world";
+			text.setTextFormat(new TextFormat(null, null, null, true), 6, 11);
+			addChild(text);
+			stage.focus = text;
+			text.setSelection(6, 11);
+
+			IME.setCompositionString("abc");
+			trace("// composing abc");
+			dump(text);
+
+			IME.setCompositionString("xy");
+			trace("// composing xy");
+			dump(text);
+
+			IME.compositionAbandoned();
+			trace("// abandoned");
+			dump(text);
+			trace("bold at 8: " + text.getTextFormat(8, 9).bold);
+		}
+
+		private function dump(text:TextField):void {
+			trace("text: " + text.text);
+			trace("selection: " + text.selectionBeginIndex + "-" + text.selectionEndIndex);
+		}
+	}
+}
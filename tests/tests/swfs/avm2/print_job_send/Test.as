@@ -0,0 +1,29 @@
+package {
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+	import flash.printing.PrintJob;
+	import flash.printing.PrintJobOptions;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var sprite:Sprite = new Sprite();
+			sprite.graphics.beginFill(0xFF0000);
+			sprite.graphics.drawRect(0, 0, 40, 30);
+
+			var job:PrintJob = new PrintJob();
+			trace("start: " + job.start());
+			trace("paper: " + job.paperWidth + "x" + job.paperHeight + ", page: " + job.pageWidth + "x" + job.pageHeight);
+			job.addPage(sprite);
+			job.addPage(sprite, new Rectangle(10, 10, 20, 10), new PrintJobOptions(true));
+			trace("sending");
+			job.send();
+			trace("sent");
+
+			var terminated:PrintJob = new PrintJob();
+			trace("start: " + terminated.start());
+			terminated.addPage(sprite);
+			terminated.terminate();
+			trace("terminated");
+		}
+	}
+}
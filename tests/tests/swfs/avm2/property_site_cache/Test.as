@@ -0,0 +1,84 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// One site that sees more classes than it can remember.
+			var objects: Array = [new A(), new B(), new C(), new D(), new E(), new F()];
+			for (var round: int = 0; round < 2; round++) {
+				var names: Array = [];
+				for each (var object: Object in objects) {
+					names.push(readName(object));
+				}
+				trace("Names: " + names);
+			}
+
+			// Dynamic properties added after the site has seen the class.
+			var dyn: Dyn = new Dyn();
+			trace("Before adding: " + readExtra(dyn));
+			trace("Before adding, again: " + readExtra(dyn));
+			dyn.extra = "added";
+			trace("After adding: " + readExtra(dyn));
+			delete dyn.extra;
+			trace("After deleting: " + readExtra(dyn));
+
+			try {
+				callExtra(dyn);
+			} catch (e: TypeError) {
+				trace("Calling before adding: " + e.errorID);
+			}
+			dyn.method = function(): String {
+				return "called";
+			};
+			trace("Calling after adding: " + callExtra(dyn));
+			trace("Sealed property: " + readFixed(dyn));
+		}
+
+		private function readName(object: Object): String {
+			return object.name;
+		}
+
+		private function readExtra(object: Object): * {
+			return object.extra;
+		}
+
+		private function callExtra(object: Object): * {
+			return object.method();
+		}
+
+		private function readFixed(object: Object): * {
+			return object.fixed;
+		}
+	}
+}
+
+class A {
+	public var name: String = "A";
+}
+
+class B {
+	public var name: String = "B";
+}
+
+class C {
+	public function get name(): String {
+		return "C";
+	}
+}
+
+class D {
+	public var name: String = "D";
+}
+
+class E {
+	public var first: int = 0;
+	public var name: String = "E";
+}
+
+class F {
+	public const name: String = "F";
+}
+
+dynamic class Dyn {
+	public var fixed: String = "fixed";
+}
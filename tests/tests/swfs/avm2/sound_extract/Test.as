@@ -0,0 +1,45 @@
+package {
+	import flash.display.Sprite;
+	import flash.media.Sound;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		public function Test() {
+			// 10000 sample frames, where frame `i` is `(i % 256) / 256` on the left
+			// and the negation of that on the right.
+			var sound:Sound = new Tone();
+
+			trace("// Consecutive chunks");
+			var bytes:ByteArray = new ByteArray();
+			trace(sound.extract(bytes, 4096, 0));
+			trace(sound.extract(bytes, 4096));
+			trace(bytes.length);
+			traceFrame(bytes, 0);
+			traceFrame(bytes, 300);
+			traceFrame(bytes, 5000);
+
+			trace("// Going back");
+			var back:ByteArray = new ByteArray();
+			trace(sound.extract(back, 2, 1000));
+			traceFrame(back, 0);
+			traceFrame(back, 1);
+
+			trace("// Skipping ahead");
+			var ahead:ByteArray = new ByteArray();
+			trace(sound.extract(ahead, 1, 7777));
+			traceFrame(ahead, 0);
+
+			trace("// End of the sound");
+			var tail:ByteArray = new ByteArray();
+			trace(sound.extract(tail, 4096, 9000));
+			traceFrame(tail, 999);
+			trace(sound.extract(tail, 4096));
+			trace(tail.length);
+		}
+
+		private function traceFrame(bytes:ByteArray, frame:int):void {
+			bytes.position = frame * 8;
+			trace("frame " + frame + ": " + bytes.readFloat() + " " + bytes.readFloat());
+		}
+	}
+}
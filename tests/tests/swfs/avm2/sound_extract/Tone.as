@@ -0,0 +1,7 @@
+package {
+	import flash.media.Sound;
+
+	// Linked to an uncompressed sound by `add_sound.py`.
+	public class Tone extends Sound {
+	}
+}
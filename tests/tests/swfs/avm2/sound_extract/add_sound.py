@@ -0,0 +1,55 @@
+#!/usr/bin/env python3
+# Adds the sound that `Tone` is linked to into test.swf, as the compiler can't embed it.
+# Run this after compiling Test.as.
+
+import struct
+import zlib
+
+NUM_SAMPLE_FRAMES = 10000
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def read_tags(data):
+    tags = []
+    while data:
+        header = struct.unpack_from("<H", data)[0]
+        code, length, offset = header >> 6, header & 0x3F, 2
+        if length == 0x3F:
+            length, offset = struct.unpack_from("<I", data, 2)[0], 6
+        tags.append((code, data[: offset + length]))
+        data = data[offset + length :]
+    return tags
+
+
+with open("test.swf", "rb") as f:
+    swf = f.read()
+signature, version = swf[:3], swf[3]
+body = zlib.decompress(swf[8:]) if signature == b"CWS" else swf[8:]
+rect_bits = body[0] >> 3
+header_length = (5 + 4 * rect_bits + 7) // 8 + 4
+header, tags = body[:header_length], read_tags(body[header_length:])
+
+samples = bytearray()
+for i in range(NUM_SAMPLE_FRAMES):
+    value = (i % 256) * 128
+    samples += struct.pack("<hh", value, -value)
+# Uncompressed little-endian, 44.1KHz, 16-bit, stereo.
+define_sound = tag(14, struct.pack("<HBI", 1, 0x3F, NUM_SAMPLE_FRAMES) + samples)
+symbol_class = tag(76, struct.pack("<HH", 1, 1) + b"Tone\0")
+
+out = bytearray()
+for code, data in tags:
+    # DefineSound goes before the code, and the class is linked alongside `Test`.
+    if code in (72, 82):
+        out += define_sound
+    if code == 76:
+        out += symbol_class
+    out += data
+body = header + out
+with open("test.swf", "wb") as f:
+    f.write(b"FWS" + bytes([version]) + struct.pack("<I", len(body) + 8) + body)
@@ -0,0 +1,103 @@
+#!/usr/bin/env python3
+"""Generates test.swf: a 2 minute cutscene of embedded Screen Video with a stream sound.
+
+The timeline runs at 24 FPS and goes through the 12 FPS video by placing it with a ratio
+of half its frame number. Each video frame is two 16x16 blocks: the left one is colored
+after the frame number, and the right one after the last keyframe, which comes every second.
+The last frame traces "end" and stops.
+"""
+
+import struct
+import zlib
+
+WIDTH, HEIGHT = 32, 16
+FRAME_RATE = 24
+NUM_FRAMES = FRAME_RATE * 120
+VIDEO_FRAMES = NUM_FRAMES // 2
+KEYFRAME_INTERVAL = 12
+SAMPLE_RATE = 5512
+VIDEO_ID = 1
+
+
+def frame_color(frame):
+    """The (r, g, b) color of a video frame, where neighbouring frames differ by 4."""
+    return (frame * 4) % 256, (frame // 64) * 10, 0x80
+
+
+def tag(code, data=b""):
+    if len(data) < 0x3F:
+        return struct.pack("<H", code << 6 | len(data)) + data
+    return struct.pack("<HI", code << 6 | 0x3F, len(data)) + data
+
+
+def rect(width, height):
+    # 15 bits per field is enough for the stage size in twips.
+    bits = "01111" + "".join(
+        format(value, "015b") for value in (0, width * 20, 0, height * 20)
+    )
+    bits += "0" * (-len(bits) % 8)
+    return bytes(int(bits[i : i + 8], 2) for i in range(0, len(bits), 8))
+
+
+def block(color):
+    r, g, b = color
+    # Screen Video blocks are BGR.
+    pixels = bytes((b, g, r)) * (16 * 16)
+    data = zlib.compress(pixels)
+    return struct.pack(">H", len(data)) + data
+
+
+def video_frame(frame):
+    keyframe = frame - frame % KEYFRAME_INTERVAL
+    is_keyframe = frame == keyframe
+    # Frame type (1 = keyframe, 2 = interframe) and codec (3 = Screen Video).
+    data = bytes(((1 if is_keyframe else 2) << 4 | 3,))
+    # Block width and height of 16, then the image size.
+    data += struct.pack(">HH", WIDTH, HEIGHT)
+    data += block(frame_color(frame))
+    # Interframes leave the keyframe block unchanged.
+    data += block(frame_color(keyframe)) if is_keyframe else struct.pack(">H", 0)
+    return tag(61, struct.pack("<HH", VIDEO_ID, frame) + data)
+
+
+def samples_before(frame):
+    return frame * SAMPLE_RATE // FRAME_RATE
+
+
+def main():
+    tags = tag(9, bytes((0, 0, 0)))
+    # SoundStreamHead2: uncompressed 8-bit mono at 5.5kHz, played back the same way.
+    tags += tag(
+        45,
+        bytes((0, 3 << 4)) + struct.pack("<H", samples_before(1)),
+    )
+    # DefineVideoStream: no deblocking or smoothing, Screen Video.
+    tags += tag(
+        60, struct.pack("<HHHHBB", VIDEO_ID, VIDEO_FRAMES, WIDTH, HEIGHT, 0, 3)
+    )
+    for frame in range(NUM_FRAMES):
+        ratio = frame // 2
+        if frame % 2 == 0:
+            tags += video_frame(ratio)
+        if frame == 0:
+            # PlaceObject2 with a character and a ratio.
+            tags += tag(26, struct.pack("<BHHH", 0x12, 1, VIDEO_ID, ratio))
+        else:
+            # PlaceObject2 moving the video to a new ratio.
+            tags += tag(26, struct.pack("<BHH", 0x11, 1, ratio))
+        silence = samples_before(frame + 1) - samples_before(frame)
+        tags += tag(19, b"\x80" * silence)
+        if frame == NUM_FRAMES - 1:
+            # DoAction: trace("end"); stop();
+            tags += tag(12, bytes((0x96, 5, 0, 0, 0)) + b"end\x00" + bytes((0x26, 0x07, 0)))
+        tags += tag(1)
+    tags += tag(0)
+
+    body = rect(WIDTH, HEIGHT) + struct.pack("<HH", FRAME_RATE << 8, NUM_FRAMES) + tags
+    header = b"CWS" + bytes((8,)) + struct.pack("<I", len(body) + 8)
+    with open("test.swf", "wb") as file:
+        file.write(header + zlib.compress(body))
+
+
+if __name__ == "__main__":
+    main()
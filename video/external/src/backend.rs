@@ -2,11 +2,11 @@
 use crate::decoder::openh264::OpenH264Codec;
 use crate::decoder::VideoDecoder;
 
-use ruffle_render::backend::RenderBackend;
+use ruffle_render::backend::{DeviceId, RenderBackend};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelRegion};
 use ruffle_video::backend::VideoBackend;
 use ruffle_video::error::Error;
-use ruffle_video::frame::{EncodedFrame, FrameDependency};
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
 use ruffle_video::VideoStreamHandle;
 use ruffle_video_software::backend::SoftwareVideoBackend;
 use slotmap::SlotMap;
@@ -21,6 +21,20 @@ enum ProxyOrStream {
 
     /// These streams are handled by this backend directly.
     Owned(VideoStream),
+
+    /// These streams failed in the external decoder, and the software
+    /// backend can't decode them either.
+    Unsupported(VideoCodec),
+}
+
+/// The parameters a stream was registered with, to hand it over to the
+/// software backend if the external decoder fails.
+#[derive(Clone, Copy)]
+struct Registration {
+    num_frames: u32,
+    size: (u16, u16),
+    codec: VideoCodec,
+    filter: VideoDeblocking,
 }
 
 /// A video backend that falls back to the software backend for most codecs,
@@ -29,6 +43,8 @@ pub struct ExternalVideoBackend {
     streams: SlotMap<VideoStreamHandle, ProxyOrStream>,
     #[cfg(feature = "openh264")]
     openh264_codec: Option<OpenH264Codec>,
+    #[cfg(feature = "webcodecs")]
+    webcodecs: Option<crate::decoder::webcodecs::Support>,
     software: SoftwareVideoBackend,
 }
 
@@ -46,6 +62,14 @@ impl ExternalVideoBackend {
             return Ok(decoder);
         }
 
+        #[cfg(feature = "webcodecs")]
+        if let Some(support) = &self.webcodecs {
+            let decoder = Box::new(crate::decoder::webcodecs::H264Decoder::new(
+                support.clone(),
+            )?);
+            return Ok(decoder);
+        }
+
         Err(Error::DecoderError("No OpenH264".into()))
     }
 
@@ -54,6 +78,8 @@ impl ExternalVideoBackend {
             streams: SlotMap::with_key(),
             #[cfg(feature = "openh264")]
             openh264_codec: None,
+            #[cfg(feature = "webcodecs")]
+            webcodecs: None,
             software: SoftwareVideoBackend::new(),
         }
     }
@@ -63,9 +89,51 @@ impl ExternalVideoBackend {
         Self {
             streams: SlotMap::with_key(),
             openh264_codec: Some(openh264_codec),
+            #[cfg(feature = "webcodecs")]
+            webcodecs: None,
             software: SoftwareVideoBackend::new(),
         }
     }
+
+    /// Creates a backend that decodes H.264 with WebCodecs, if the browser supports it.
+    ///
+    /// Without WebCodecs, H.264 streams can't be registered, as with the plain software backend.
+    #[cfg(feature = "webcodecs")]
+    pub fn new_with_webcodecs() -> Self {
+        Self {
+            webcodecs: crate::decoder::webcodecs::Support::probe(),
+            ..Self::new()
+        }
+    }
+
+    /// Hands a stream that its external decoder failed on over to the
+    /// software backend, which is what decodes it without an external decoder.
+    ///
+    /// If the software backend can't decode the stream either, it fails
+    /// from now on without trying the external decoder again.
+    fn fall_back(&mut self, handle: VideoStreamHandle, error: &Error) {
+        let Some(ProxyOrStream::Owned(stream)) = self.streams.get(handle) else {
+            return;
+        };
+        let Registration {
+            num_frames,
+            size,
+            codec,
+            filter,
+        } = stream.registration;
+
+        tracing::warn!("External {codec:?} decoder failed, falling back to software: {error}");
+        self.streams[handle] = match self
+            .software
+            .register_video_stream(num_frames, size, codec, filter)
+        {
+            Ok(inner) => ProxyOrStream::Proxied(inner),
+            Err(e) => {
+                tracing::error!("Software backend can't decode {codec:?} either: {e}");
+                ProxyOrStream::Unsupported(codec)
+            }
+        };
+    }
 }
 
 // NOTE: The stream handles coming in through this API must not be
@@ -80,7 +148,13 @@ impl VideoBackend for ExternalVideoBackend {
     ) -> Result<VideoStreamHandle, Error> {
         let proxy_or_stream = if codec == VideoCodec::H264 {
             let decoder = self.make_decoder()?;
-            let stream = VideoStream::new(decoder);
+            let registration = Registration {
+                num_frames,
+                size,
+                codec,
+                filter,
+            };
+            let stream = VideoStream::new(decoder, registration);
             ProxyOrStream::Owned(stream)
         } else {
             ProxyOrStream::Proxied(
@@ -94,20 +168,28 @@ impl VideoBackend for ExternalVideoBackend {
 
     fn configure_video_stream_decoder(
         &mut self,
-        stream: VideoStreamHandle,
+        handle: VideoStreamHandle,
         configuration_data: &[u8],
     ) -> Result<(), Error> {
         let stream = self
             .streams
-            .get_mut(stream)
+            .get_mut(handle)
             .ok_or(Error::VideoStreamIsNotRegistered)?;
 
-        match stream {
-            ProxyOrStream::Proxied(handle) => self
-                .software
-                .configure_video_stream_decoder(*handle, configuration_data),
+        let result = match stream {
+            ProxyOrStream::Proxied(handle) => {
+                return self
+                    .software
+                    .configure_video_stream_decoder(*handle, configuration_data)
+            }
             ProxyOrStream::Owned(stream) => stream.decoder.configure_decoder(configuration_data),
+            ProxyOrStream::Unsupported(codec) => return Err(Error::UnsupportedCodec(*codec)),
+        };
+        if let Err(e) = &result {
+            self.fall_back(handle, e);
+            return self.configure_video_stream_decoder(handle, configuration_data);
         }
+        result
     }
 
     fn preload_video_stream_frame(
@@ -125,60 +207,110 @@ impl VideoBackend for ExternalVideoBackend {
                 .software
                 .preload_video_stream_frame(*handle, encoded_frame),
             ProxyOrStream::Owned(stream) => stream.decoder.preload_frame(encoded_frame),
+            ProxyOrStream::Unsupported(codec) => Err(Error::UnsupportedCodec(*codec)),
         }
     }
 
     fn decode_video_stream_frame(
         &mut self,
-        stream: VideoStreamHandle,
+        handle: VideoStreamHandle,
         encoded_frame: EncodedFrame<'_>,
         renderer: &mut dyn RenderBackend,
     ) -> Result<BitmapInfo, Error> {
         let stream = self
             .streams
-            .get_mut(stream)
+            .get_mut(handle)
             .ok_or(Error::VideoStreamIsNotRegistered)?;
 
-        match stream {
+        let error = match stream {
             ProxyOrStream::Proxied(handle) => {
-                self.software
+                return self
+                    .software
                     .decode_video_stream_frame(*handle, encoded_frame, renderer)
             }
-            ProxyOrStream::Owned(stream) => {
-                let frame = stream.decoder.decode_frame(encoded_frame)?;
-
-                let w = frame.width();
-                let h = frame.height();
-
-                let handle = if let Some(bitmap) = stream.bitmap.clone() {
-                    renderer.update_texture(&bitmap, frame, PixelRegion::for_whole_size(w, h))?;
-                    bitmap
-                } else {
-                    renderer.register_bitmap(frame)?
-                };
-                stream.bitmap = Some(handle.clone());
-
-                Ok(BitmapInfo {
-                    handle,
-                    width: w as u16,
-                    height: h as u16,
-                })
+            ProxyOrStream::Owned(stream) => match stream.decoder.decode_frame(encoded_frame) {
+                Ok(frame) => return stream.upload_frame(frame, renderer),
+                // Decoders may fail on single frames, such as ones missing a reference frame.
+                Err(e) if !stream.decoder.has_failed() => return Err(e),
+                Err(e) => e,
+            },
+            ProxyOrStream::Unsupported(codec) => return Err(Error::UnsupportedCodec(*codec)),
+        };
+        self.fall_back(handle, &error);
+        self.decode_video_stream_frame(handle, encoded_frame, renderer)
+    }
+
+    fn poll_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<Option<BitmapInfo>, Error> {
+        let stream = self
+            .streams
+            .get_mut(stream)
+            .ok_or(Error::VideoStreamIsNotRegistered)?;
+
+        match stream {
+            ProxyOrStream::Proxied(handle) => {
+                self.software.poll_video_stream_frame(*handle, renderer)
             }
+            ProxyOrStream::Owned(stream) => match stream.decoder.take_decoded_frame() {
+                Some(frame) => stream.upload_frame(frame, renderer).map(Some),
+                None => Ok(None),
+            },
+            ProxyOrStream::Unsupported(_) => Ok(None),
+        }
+    }
+
+    fn is_video_stream_decoding(&self, stream: VideoStreamHandle) -> bool {
+        match self.streams.get(stream) {
+            Some(ProxyOrStream::Proxied(handle)) => self.software.is_video_stream_decoding(*handle),
+            Some(ProxyOrStream::Owned(stream)) => stream.decoder.is_decoding(),
+            Some(ProxyOrStream::Unsupported(_)) | None => false,
         }
     }
 }
 
 /// A single preloaded video stream.
 pub struct VideoStream {
-    bitmap: Option<BitmapHandle>,
+    /// The texture of the last decoded frame, along with the device it lives on.
+    bitmap: Option<(DeviceId, BitmapHandle)>,
     decoder: Box<dyn VideoDecoder>,
+    registration: Registration,
 }
 
 impl VideoStream {
-    fn new(decoder: Box<dyn VideoDecoder>) -> Self {
+    fn new(decoder: Box<dyn VideoDecoder>, registration: Registration) -> Self {
         Self {
             decoder,
             bitmap: None,
+            registration,
         }
     }
+
+    /// Copies a decoded frame into the texture of this stream, creating it if needed.
+    fn upload_frame(
+        &mut self,
+        frame: DecodedFrame,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error> {
+        let w = frame.width();
+        let h = frame.height();
+
+        let device_id = renderer.device_id();
+        let handle = match self.bitmap.clone() {
+            Some((registered_on, bitmap)) if registered_on == device_id => {
+                renderer.update_texture(&bitmap, frame, PixelRegion::for_whole_size(w, h))?;
+                bitmap
+            }
+            _ => renderer.register_bitmap(frame)?,
+        };
+        self.bitmap = Some((device_id, handle.clone()));
+
+        Ok(BitmapInfo {
+            handle,
+            width: w as u16,
+            height: h as u16,
+        })
+    }
 }
@@ -0,0 +1,354 @@
+//! H.264 decoding with the browser's WebCodecs `VideoDecoder`.
+//!
+//! WebCodecs decodes asynchronously, so every call to `decode_frame` returns
+//! the most recent frame that finished decoding. The frame that was just
+//! submitted is handed out by `take_decoded_frame` once it's read back.
+
+use crate::decoder::VideoDecoder;
+use js_sys::{Reflect, Uint8Array};
+use ruffle_render::bitmap::BitmapFormat;
+use ruffle_video::error::Error;
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    EncodedVideoChunk, EncodedVideoChunkInit, EncodedVideoChunkType, VideoDecoderConfig,
+    VideoDecoderInit, VideoFrame, VideoPixelFormat,
+};
+
+/// H.264 NAL unit type of an instantaneous decoding refresh (IDR) slice.
+const NAL_SLICE_IDR: u8 = 5;
+
+/// The H.264 profiles that are probed up front: Baseline, Main, Extended and High.
+const PROBED_PROFILES: [u8; 4] = [0x42, 0x4D, 0x58, 0x64];
+
+/// The level that profiles are probed at, 4.0, which covers 1080p.
+const PROBED_LEVEL: u8 = 0x28;
+
+/// The H.264 profiles that the browser's `VideoDecoder` said it decodes.
+///
+/// Browsers answer asynchronously, so until they do, streams are tried with WebCodecs anyway.
+#[derive(Clone, Default)]
+pub struct Support(Rc<RefCell<HashMap<u8, bool>>>);
+
+impl Support {
+    /// Asks the browser which H.264 profiles it decodes, or returns `None` if it doesn't have a
+    /// WebCodecs `VideoDecoder` at all.
+    pub fn probe() -> Option<Self> {
+        if !Reflect::has(&js_sys::global(), &"VideoDecoder".into()).unwrap_or(false) {
+            return None;
+        }
+
+        let support = Self::default();
+        for profile in PROBED_PROFILES {
+            let answers = support.0.clone();
+            is_config_supported(&avc_codec(profile, 0, PROBED_LEVEL), move |supported| {
+                answers.borrow_mut().insert(profile, supported);
+            });
+        }
+        Some(support)
+    }
+
+    fn allows(&self, profile: u8) -> bool {
+        self.0.borrow().get(&profile).copied().unwrap_or(true)
+    }
+}
+
+/// The WebCodecs codec string of an H.264 stream.
+fn avc_codec(profile: u8, compatibility: u8, level: u8) -> String {
+    format!("avc1.{profile:02x}{compatibility:02x}{level:02x}")
+}
+
+/// Asks the browser whether it decodes the given codec, calling `answer` once it knows.
+fn is_config_supported(codec: &str, answer: impl FnOnce(bool) + 'static) {
+    let promise = web_sys::VideoDecoder::is_config_supported(&VideoDecoderConfig::new(codec));
+    spawn_local(async move {
+        let supported = match JsFuture::from(promise).await {
+            Ok(support) => Reflect::get(&support, &"supported".into())
+                .ok()
+                .and_then(|supported| supported.as_bool())
+                .unwrap_or(false),
+            // The configuration isn't even valid.
+            Err(_) => false,
+        };
+        answer(supported);
+    });
+}
+
+/// State shared with the decoder's callbacks.
+#[derive(Default)]
+struct Output {
+    /// The last frame that was decoded and read back, if it wasn't returned yet.
+    new_frame: Option<DecodedFrame>,
+
+    /// How many submitted frames haven't been decoded and read back yet.
+    pending: u32,
+
+    /// Why the decoder stopped working, if it did.
+    error: Option<String>,
+}
+
+pub struct H264Decoder {
+    decoder: web_sys::VideoDecoder,
+    output: Rc<RefCell<Output>>,
+    support: Support,
+
+    /// The last frame returned by `decode_frame`, returned again until a new one is ready.
+    last_frame: Option<DecodedFrame>,
+
+    /// The size of the NAL unit length prefixes, from the AVC configuration.
+    length_size: u8,
+
+    // Kept alive for as long as the decoder can call them.
+    _on_output: Closure<dyn FnMut(VideoFrame)>,
+    _on_error: Closure<dyn FnMut(JsValue)>,
+}
+
+impl H264Decoder {
+    pub fn new(support: Support) -> Result<Self, Error> {
+        let output = Rc::new(RefCell::new(Output::default()));
+
+        let on_output = {
+            let output = Rc::downgrade(&output);
+            Closure::<dyn FnMut(_)>::new(move |frame: VideoFrame| {
+                let output = output.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let result = read_frame(&frame).await;
+                    frame.close();
+                    let Some(output) = output.upgrade() else {
+                        return;
+                    };
+                    let mut output = output.borrow_mut();
+                    output.pending = output.pending.saturating_sub(1);
+                    match result {
+                        Ok(decoded) => output.new_frame = Some(decoded),
+                        Err(e) => tracing::error!("Couldn't read back decoded frame: {e}"),
+                    }
+                });
+            })
+        };
+        let on_error = {
+            let output = Rc::downgrade(&output);
+            Closure::<dyn FnMut(_)>::new(move |error: JsValue| {
+                if let Some(output) = output.upgrade() {
+                    // A closed decoder won't output the frames it still had.
+                    let mut output = output.borrow_mut();
+                    output.error = Some(format!("{error:?}"));
+                    output.pending = 0;
+                }
+            })
+        };
+
+        let init = VideoDecoderInit::new(
+            on_error.as_ref().unchecked_ref(),
+            on_output.as_ref().unchecked_ref(),
+        );
+        let decoder = web_sys::VideoDecoder::new(&init).map_err(js_error)?;
+
+        Ok(Self {
+            decoder,
+            output,
+            support,
+            last_frame: None,
+            length_size: 0,
+            _on_output: on_output,
+            _on_error: on_error,
+        })
+    }
+
+    /// Whether any NAL unit in the frame is an IDR slice.
+    fn is_keyframe(&self, data: &[u8]) -> bool {
+        let length_size = usize::from(self.length_size);
+        let mut i = 0;
+        while i + length_size < data.len() {
+            let length = data[i..i + length_size]
+                .iter()
+                .fold(0, |length, byte| (length << 8) | usize::from(*byte));
+            i += length_size;
+            if data[i] & 0b0001_1111 == NAL_SLICE_IDR {
+                return true;
+            }
+            // A length past the end of the data means the frame is truncated.
+            match i.checked_add(length) {
+                Some(next) if next <= data.len() => i = next,
+                _ => break,
+            }
+        }
+        false
+    }
+}
+
+impl Drop for H264Decoder {
+    fn drop(&mut self) {
+        let _ = self.decoder.close();
+    }
+}
+
+impl VideoDecoder for H264Decoder {
+    fn configure_decoder(&mut self, configuration_data: &[u8]) -> Result<(), Error> {
+        // This is an AVCDecoderConfigurationRecord:
+        // [0]: configuration version, always 1
+        // [1]: profile
+        // [2]: compatibility
+        // [3]: level
+        // [4]: 6 reserved bits | NALU length size - 1
+        if configuration_data.len() < 7 || configuration_data[0] != 1 {
+            return Err(Error::DecoderError("Invalid AVC configuration".into()));
+        }
+        let profile = configuration_data[1];
+        let codec = avc_codec(profile, configuration_data[2], configuration_data[3]);
+        if !self.support.allows(profile) {
+            return Err(Error::DecoderError(
+                format!("The browser can't decode {codec}").into(),
+            ));
+        }
+        self.length_size = (configuration_data[4] & 0b0000_0011) + 1;
+
+        // The probe only covered common profiles at one level, so this exact stream is asked
+        // about too. Until the browser answers, it's decoded anyway.
+        let output = Rc::downgrade(&self.output);
+        let unsupported_codec = codec.clone();
+        is_config_supported(&codec, move |supported| {
+            if let Some(output) = output.upgrade().filter(|_| !supported) {
+                output.borrow_mut().error =
+                    Some(format!("The browser can't decode {unsupported_codec}"));
+            }
+        });
+
+        let config = VideoDecoderConfig::new(&codec);
+        // With a description, WebCodecs takes frames in the same length-prefixed format as FLV.
+        config.set_description(&Uint8Array::from(configuration_data));
+        config.set_optimize_for_latency(true);
+        self.decoder.configure(&config).map_err(js_error)
+    }
+
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        if self.length_size == 0 {
+            return Err(Error::DecoderError("Decoder not configured".into()));
+        }
+
+        if self.is_keyframe(encoded_frame.data) {
+            Ok(FrameDependency::None)
+        } else {
+            Ok(FrameDependency::Past)
+        }
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        if self.length_size == 0 {
+            return Err(Error::DecoderError("Decoder not configured".into()));
+        }
+        if let Some(error) = &self.output.borrow().error {
+            return Err(Error::DecoderError(error.clone().into()));
+        }
+
+        let chunk_type = if self.is_keyframe(encoded_frame.data) {
+            EncodedVideoChunkType::Key
+        } else {
+            EncodedVideoChunkType::Delta
+        };
+        let init = EncodedVideoChunkInit::new(
+            &Uint8Array::from(encoded_frame.data),
+            encoded_frame.frame_id.into(),
+            chunk_type,
+        );
+        let chunk = EncodedVideoChunk::new(&init).map_err(js_error)?;
+        self.decoder.decode(&chunk).map_err(js_error)?;
+
+        // The frame just submitted can't be ready yet, so this is the previous one,
+        // which stands in until `take_decoded_frame` returns the new one.
+        let mut output = self.output.borrow_mut();
+        output.pending += 1;
+        if let Some(frame) = output.new_frame.take() {
+            self.last_frame = Some(frame);
+        }
+        self.last_frame.clone().ok_or(Error::FramePending)
+    }
+
+    fn take_decoded_frame(&mut self) -> Option<DecodedFrame> {
+        let frame = self.output.borrow_mut().new_frame.take()?;
+        self.last_frame = Some(frame.clone());
+        Some(frame)
+    }
+
+    fn is_decoding(&self) -> bool {
+        let output = self.output.borrow();
+        output.pending > 0 || output.new_frame.is_some()
+    }
+
+    fn has_failed(&self) -> bool {
+        // WebCodecs closes a decoder that hits an error, or the browser can't decode the stream.
+        self.output.borrow().error.is_some()
+    }
+}
+
+fn js_error(error: JsValue) -> Error {
+    Error::DecoderError(format!("{error:?}").into())
+}
+
+/// Copies the visible pixels of the frame into a bitmap.
+async fn read_frame(frame: &VideoFrame) -> Result<DecodedFrame, String> {
+    let (width, height) = match frame.visible_rect() {
+        Some(rect) => (rect.width() as u32, rect.height() as u32),
+        None => (frame.display_width(), frame.display_height()),
+    };
+    let format = frame.format().ok_or("Frame has no readable format")?;
+
+    let size = frame.allocation_size().map_err(|e| format!("{e:?}"))?;
+    let buffer = Uint8Array::new_with_length(size);
+    JsFuture::from(frame.copy_to_with_buffer_source(&buffer))
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    let data = buffer.to_vec();
+
+    let (width_usize, height_usize) = (width as usize, height as usize);
+    let (data, format) = match format {
+        VideoPixelFormat::I420 => (data, BitmapFormat::Yuv420p),
+        VideoPixelFormat::Nv12 => (
+            nv12_to_i420(data, width_usize, height_usize),
+            BitmapFormat::Yuv420p,
+        ),
+        VideoPixelFormat::Rgba | VideoPixelFormat::Rgbx => {
+            (opaque_rgba(data, false), BitmapFormat::Rgba)
+        }
+        VideoPixelFormat::Bgra | VideoPixelFormat::Bgrx => {
+            (opaque_rgba(data, true), BitmapFormat::Rgba)
+        }
+        format => return Err(format!("Unsupported pixel format {format:?}")),
+    };
+
+    if data.len() < format.length_for_size(width_usize, height_usize) {
+        return Err("Frame data is too short".to_string());
+    }
+    Ok(DecodedFrame::new(width, height, format, data))
+}
+
+/// Splits the interleaved chroma plane of an NV12 frame into the two planes of I420.
+fn nv12_to_i420(data: Vec<u8>, width: usize, height: usize) -> Vec<u8> {
+    let luma_size = width * height;
+    let chroma_size = width.div_ceil(2) * height.div_ceil(2);
+    if data.len() < luma_size + chroma_size * 2 {
+        return data;
+    }
+
+    let mut out = Vec::with_capacity(luma_size + chroma_size * 2);
+    out.extend_from_slice(&data[..luma_size]);
+    let chroma = &data[luma_size..luma_size + chroma_size * 2];
+    out.extend(chroma.iter().step_by(2));
+    out.extend(chroma.iter().skip(1).step_by(2));
+    out
+}
+
+/// Makes every pixel opaque, swapping red and blue if the data is in BGRA order.
+fn opaque_rgba(mut data: Vec<u8>, is_bgra: bool) -> Vec<u8> {
+    for pixel in data.chunks_exact_mut(4) {
+        if is_bgra {
+            pixel.swap(0, 2);
+        }
+        pixel[3] = 0xFF;
+    }
+    data
+}
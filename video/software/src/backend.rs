@@ -1,5 +1,5 @@
 use crate::decoder::VideoDecoder;
-use ruffle_render::backend::RenderBackend;
+use ruffle_render::backend::{DeviceId, RenderBackend};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelRegion};
 use ruffle_video::backend::VideoBackend;
 use ruffle_video::error::Error;
@@ -91,13 +91,15 @@ impl VideoBackend for SoftwareVideoBackend {
         let w = frame.width();
         let h = frame.height();
 
-        let handle = if let Some(bitmap) = stream.bitmap.clone() {
-            renderer.update_texture(&bitmap, frame, PixelRegion::for_whole_size(w, h))?;
-            bitmap
-        } else {
-            renderer.register_bitmap(frame)?
+        let device_id = renderer.device_id();
+        let handle = match stream.bitmap.clone() {
+            Some((registered_on, bitmap)) if registered_on == device_id => {
+                renderer.update_texture(&bitmap, frame, PixelRegion::for_whole_size(w, h))?;
+                bitmap
+            }
+            _ => renderer.register_bitmap(frame)?,
         };
-        stream.bitmap = Some(handle.clone());
+        stream.bitmap = Some((device_id, handle.clone()));
 
         Ok(BitmapInfo {
             handle,
@@ -109,7 +111,8 @@ impl VideoBackend for SoftwareVideoBackend {
 
 /// A single preloaded video stream.
 pub struct VideoStream {
-    bitmap: Option<BitmapHandle>,
+    /// The texture of the last decoded frame, along with the device it lives on.
+    bitmap: Option<(DeviceId, BitmapHandle)>,
     decoder: Box<dyn VideoDecoder>,
 }
 
@@ -43,4 +43,24 @@ pub trait VideoDecoder {
     /// The decoded frame should be returned. An `Error` can be returned if
     /// a drawable bitmap can not be produced.
     fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error>;
+
+    /// Take the latest frame that finished decoding after `decode_frame`
+    /// returned, if any.
+    ///
+    /// Only decoders that work asynchronously have any such frames; their
+    /// `decode_frame` returns the last frame that was ready instead.
+    fn take_decoded_frame(&mut self) -> Option<DecodedFrame> {
+        None
+    }
+
+    /// Whether any frame passed to `decode_frame` is still being decoded.
+    fn is_decoding(&self) -> bool {
+        false
+    }
+
+    /// Whether the decoder stopped working for good, rather than only
+    /// failing to decode some frames.
+    fn has_failed(&self) -> bool {
+        false
+    }
 }
@@ -74,4 +74,24 @@ pub trait VideoBackend {
         encoded_frame: EncodedFrame<'_>,
         renderer: &mut dyn RenderBackend,
     ) -> Result<BitmapInfo, Error>;
+
+    /// Finish decoding a frame of a given video stream.
+    ///
+    /// Decoders that work asynchronously can only return the last frame that
+    /// finished decoding from `decode_video_stream_frame`. Once the frame that
+    /// was asked for is ready, it is returned from here instead, in the same
+    /// bitmap. `None` is returned if no frame finished decoding since.
+    fn poll_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _renderer: &mut dyn RenderBackend,
+    ) -> Result<Option<BitmapInfo>, Error> {
+        Ok(None)
+    }
+
+    /// Whether any frame passed to `decode_video_stream_frame` for a given
+    /// video stream has yet to be returned from `poll_video_stream_frame`.
+    fn is_video_stream_decoding(&self, _stream: VideoStreamHandle) -> bool {
+        false
+    }
 }
@@ -18,6 +18,12 @@ pub enum Error {
     #[error("Video decoding isn't supported")]
     DecodingNotSupported,
 
+    /// The decoder works asynchronously and hasn't finished any frame yet.
+    ///
+    /// The frame is returned from `VideoBackend::poll_video_stream_frame` once it's ready.
+    #[error("Video frame is still being decoded")]
+    FramePending,
+
     #[error(transparent)]
     DecoderError(Box<dyn std::error::Error + Send + Sync>),
 }
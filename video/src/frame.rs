@@ -2,6 +2,7 @@ use ruffle_render::bitmap::Bitmap;
 use swf::VideoCodec;
 
 /// An encoded video frame of some video codec.
+#[derive(Clone, Copy)]
 pub struct EncodedFrame<'a> {
     /// The codec used to encode the frame.
     pub codec: VideoCodec,
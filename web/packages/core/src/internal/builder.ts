@@ -41,6 +41,12 @@ export function configureBuilder(
     if (isExplicit(config.compatibilityRules)) {
         builder.setCompatibilityRules(config.compatibilityRules);
     }
+    if (isExplicit(config.compatibilityRulesUrl)) {
+        builder.setCompatibilityRulesUrl(config.compatibilityRulesUrl);
+    }
+    if (isExplicit(config.maxActivePlayers)) {
+        builder.setMaxActivePlayers(config.maxActivePlayers);
+    }
     if (isExplicit(config.letterbox)) {
         builder.setLetterbox(config.letterbox.toLowerCase());
     }
@@ -80,6 +86,9 @@ export function configureBuilder(
     if (isExplicit(config.logLevel)) {
         builder.setLogLevel(config.logLevel);
     }
+    if (isExplicit(config.logCallback)) {
+        builder.setLogCallback(config.logCallback);
+    }
     if (isExplicit(config.maxExecutionDuration)) {
         builder.setMaxExecutionDuration(
             parseDuration(config.maxExecutionDuration),
@@ -103,10 +112,105 @@ export function configureBuilder(
     if (isExplicit(config.playerRuntime)) {
         builder.setPlayerRuntime(config.playerRuntime);
     }
+    if (isExplicit(config.bitmapReadbackMode)) {
+        builder.setBitmapReadbackMode(config.bitmapReadbackMode);
+    }
+    if (isExplicit(config.maxNativeWindows)) {
+        builder.setMaxNativeWindows(config.maxNativeWindows);
+    }
+    if (isExplicit(config.lowLatencyInput)) {
+        builder.setLowLatencyInput(config.lowLatencyInput);
+    }
+    if (isExplicit(config.forceLowLatencyInput)) {
+        builder.setForceLowLatencyInput(config.forceLowLatencyInput);
+    }
+    if (isExplicit(config.storageBackend)) {
+        builder.setStorageBackend(config.storageBackend);
+    }
+    if (isExplicit(config.gamepadButtonMapping)) {
+        for (const [button, keyCode] of Object.entries(
+            config.gamepadButtonMapping,
+        )) {
+            if (isExplicit(keyCode)) {
+                builder.setGamepadButtonMapping(button, keyCode);
+            }
+        }
+    }
+    if (isExplicit(config.keyRemap)) {
+        for (const [fromKey, toKey] of Object.entries(config.keyRemap)) {
+            if (isExplicit(toKey)) {
+                builder.setKeyRemap(Number(fromKey), toKey);
+            }
+        }
+    }
+    if (isExplicit(config.gamepadMouseSpeed)) {
+        builder.setGamepadMouseSpeed(config.gamepadMouseSpeed);
+    }
+    if (isExplicit(config.allowCamera)) {
+        builder.setAllowCamera(config.allowCamera);
+    }
+    if (isExplicit(config.allowMicrophone)) {
+        builder.setAllowMicrophone(config.allowMicrophone);
+    }
+
+    if (isExplicit(config.adaptiveQuality)) {
+        builder.setAdaptiveQuality(config.adaptiveQuality);
+    }
+    if (isExplicit(config.accessibility)) {
+        builder.setAccessibility(config.accessibility);
+    }
+    if (isExplicit(config.backgroundBehavior)) {
+        builder.setBackgroundBehavior(config.backgroundBehavior);
+    }
+    if (isExplicit(config.autoplay)) {
+        builder.setAutoplay(config.autoplay);
+    }
+    if (isExplicit(config.unmuteOverlay)) {
+        builder.setUnmuteOverlay(config.unmuteOverlay);
+    }
+    if (isExplicit(config.allowLocalFontAccess)) {
+        builder.setAllowLocalFontAccess(config.allowLocalFontAccess);
+    }
+    if (isExplicit(config.waitForFullLoad)) {
+        builder.setWaitForFullLoad(config.waitForFullLoad);
+    }
+    if (isExplicit(config.loadAllowList)) {
+        builder.setLoadAllowList(config.loadAllowList);
+    }
+    if (isExplicit(config.policyFileMode)) {
+        builder.setPolicyFileMode(config.policyFileMode);
+    }
+    if (isExplicit(config.rslMirror)) {
+        builder.setRslMirror(config.rslMirror);
+    }
+    if (isExplicit(config.capabilities)) {
+        for (const [name, value] of Object.entries(config.capabilities)) {
+            if (isExplicit(value)) {
+                builder.setCapability(name, value);
+            }
+        }
+    }
+    if (isExplicit(config.parametersFromUrl)) {
+        builder.setParametersFromUrl(config.parametersFromUrl);
+    }
+    if (isExplicit(config.fetchHandler)) {
+        builder.setFetchHandler(config.fetchHandler);
+    }
+    if (isExplicit(config.contextMenuItems)) {
+        for (const item of config.contextMenuItems) {
+            builder.addContextMenuItem(item.label, item.callbackId);
+        }
+    }
 
     if (isExplicit(config.socketProxy)) {
         for (const proxy of config.socketProxy) {
-            builder.addSocketProxy(proxy.host, proxy.port, proxy.proxyUrl);
+            builder.addSocketProxy(
+                proxy.host,
+                proxy.port,
+                proxy.proxyUrl,
+                proxy.proxyProtocol,
+                proxy.tls,
+            );
         }
     }
 }
@@ -1,7 +1,15 @@
 import { PlayerV1, ReadyState } from "../../public/player";
 import { InnerPlayer } from "./inner";
-import type { DataLoadOptions, URLLoadOptions } from "../../public/config";
-import type { MovieMetadata } from "../../public/player";
+import type {
+    BundleLoadOptions,
+    DataLoadOptions,
+    URLLoadOptions,
+} from "../../public/config";
+import type {
+    HashReport,
+    HashSessionOptions,
+    MovieMetadata,
+} from "../../public/player";
 
 export class PlayerV1Impl implements PlayerV1 {
     #inner: InnerPlayer;
@@ -22,7 +30,11 @@ export class PlayerV1Impl implements PlayerV1 {
         return this.#inner.metadata;
     }
 
-    get loadedConfig(): URLLoadOptions | DataLoadOptions | null {
+    get loadedConfig():
+        | URLLoadOptions
+        | DataLoadOptions
+        | BundleLoadOptions
+        | null {
         return this.#inner.loadedConfig ?? null;
     }
 
@@ -31,7 +43,7 @@ export class PlayerV1Impl implements PlayerV1 {
     }
 
     async load(
-        options: string | URLLoadOptions | DataLoadOptions,
+        options: string | URLLoadOptions | DataLoadOptions | BundleLoadOptions,
         isPolyfillElement: boolean = false,
     ): Promise<void> {
         await this.#inner.load(options, isPolyfillElement);
@@ -41,6 +53,14 @@ export class PlayerV1Impl implements PlayerV1 {
         this.#inner.play();
     }
 
+    stepFrame(frames: number = 1): void {
+        this.#inner.stepFrame(frames);
+    }
+
+    gotoFrame(frame: number, scene?: string): void {
+        this.#inner.gotoFrame(frame, scene);
+    }
+
     get isPlaying(): boolean {
         return this.#inner.isPlaying;
     }
@@ -93,15 +113,73 @@ export class PlayerV1Impl implements PlayerV1 {
         this.#inner.traceObserver = observer;
     }
 
-    get config(): URLLoadOptions | DataLoadOptions | object {
+    get config():
+        | URLLoadOptions
+        | DataLoadOptions
+        | BundleLoadOptions
+        | object {
         return this.#inner.config;
     }
 
-    set config(value: URLLoadOptions | DataLoadOptions | object) {
+    set config(
+        value: URLLoadOptions | DataLoadOptions | BundleLoadOptions | object,
+    ) {
         this.#inner.config = value;
     }
 
     callExternalInterface(name: string, ...args: unknown[]): unknown {
         return this.#inner.callExternalInterface(name, args);
     }
+
+    setAuxWindowContainer(container: HTMLElement | null): void {
+        this.#inner.setAuxWindowContainer(container);
+    }
+
+    captureFrame(): Blob {
+        return this.#inner.captureFrame();
+    }
+
+    startProfiling(): void {
+        this.#inner.startProfiling();
+    }
+
+    stopProfiling(): string | null {
+        return this.#inner.stopProfiling();
+    }
+
+    startReplayRecording(seed?: number): void {
+        this.#inner.startReplayRecording(seed);
+    }
+
+    startReplay(replay: string): void {
+        this.#inner.startReplay(replay);
+    }
+
+    stopReplay(): string | null {
+        return this.#inner.stopReplay();
+    }
+
+    saveState(): string | null {
+        return this.#inner.saveState();
+    }
+
+    loadState(state: string): void {
+        this.#inner.loadState(state);
+    }
+
+    setLowLatencyInput(enabled: boolean, force = false): void {
+        this.#inner.setLowLatencyInput(enabled, force);
+    }
+
+    lowMemory(): void {
+        this.#inner.lowMemory();
+    }
+
+    runHashSession(options: HashSessionOptions): Promise<HashReport> {
+        return this.#inner.runHashSession(options);
+    }
+
+    cancelHashSession(): void {
+        this.#inner.cancelHashSession();
+    }
 }
@@ -1,15 +1,20 @@
 import type { RuffleHandle, ZipWriter } from "../../../dist/ruffle_web";
 import {
-    AutoPlay,
+    BundleLoadOptions,
     ContextMenu,
     DataLoadOptions,
     DEFAULT_CONFIG,
     NetworkingAccessMode,
-    UnmuteOverlay,
     URLLoadOptions,
     WindowMode,
 } from "../../public/config";
-import { MovieMetadata, ReadyState } from "../../public/player";
+import {
+    HashReport,
+    HashSessionOptions,
+    MovieMetadata,
+    ReadyState,
+    RendererReport,
+} from "../../public/player";
 import { ruffleShadowTemplate } from "../ui/shadow-template";
 import { text, textAsParagraphs } from "../i18n";
 import { swfFileName } from "../../swf-utils";
@@ -77,6 +82,41 @@ interface ContextMenuItem {
     enabled?: boolean;
 }
 
+/**
+ * A visible object on the stage, as described to screen readers.
+ */
+interface AccessibilityNode {
+    /**
+     * What kind of object this is.
+     */
+    role: "text" | "textField" | "button" | "graphic";
+
+    /**
+     * The text read out for this object.
+     */
+    name: string;
+
+    /**
+     * A longer description of this object, or an empty string.
+     */
+    description: string;
+
+    /**
+     * The keyboard shortcut for this object, or an empty string.
+     */
+    shortcut: string;
+
+    /**
+     * The area covered by this object, in CSS pixels relative to the player.
+     */
+    x: number;
+    y: number;
+    width: number;
+    height: number;
+
+    children: AccessibilityNode[];
+}
+
 /**
  * Converts arbitrary input to an easy to use record object.
  *
@@ -129,6 +169,15 @@ export class InnerPlayer {
      */
     static LOADED_METADATA = "loadedmetadata";
 
+    /**
+     * Triggered along with `loadedmetadata`.
+     *
+     * The event's `detail` is the {@link MovieMetadata} of the movie.
+     *
+     * @event RufflePlayer#metadataloaded
+     */
+    static METADATA_LOADED = "metadataloaded";
+
     /**
      * Triggered when a movie is fully loaded.
      *
@@ -136,6 +185,80 @@ export class InnerPlayer {
      */
     static LOADED_DATA = "loadeddata";
 
+    /**
+     * Triggered as the movie downloads and preloads.
+     *
+     * The event's `detail` has the `phase` of loading (`"download"` or `"preload"`),
+     * `bytesLoaded`, and `bytesTotal`, which is `undefined` if the server didn't say how
+     * long the download is. While preloading, the byte counts are the same as the movie
+     * sees with `MovieClip.getBytesLoaded()` and `MovieClip.getBytesTotal()`.
+     *
+     * @event RufflePlayer#loadprogress
+     */
+    static LOAD_PROGRESS = "loadprogress";
+
+    /**
+     * Triggered when the movie couldn't be loaded.
+     *
+     * The event's `detail` has the `message` of the error.
+     *
+     * @event RufflePlayer#loaderror
+     */
+    static LOAD_ERROR = "loaderror";
+
+    /**
+     * Triggered once the player has created its renderer.
+     *
+     * The event's `detail` is a {@link RendererReport}, with the renderer that was
+     * chosen and why any renderers preferred over it couldn't be used.
+     *
+     * @event RufflePlayer#renderercreated
+     */
+    static RENDERER_CREATED = "renderercreated";
+
+    /**
+     * Triggered when rendering resumes on a new renderer, after the browser lost the
+     * previous WebGL or WebGPU context.
+     *
+     * @event RufflePlayer#rendererrestored
+     */
+    static RENDERER_RESTORED = "rendererrestored";
+
+    /**
+     * Triggered for each call to `trace()` in the movie.
+     *
+     * The event's `detail` has the traced `message`.
+     *
+     * @event RufflePlayer#traceoutput
+     */
+    static TRACE_OUTPUT = "traceoutput";
+
+    /**
+     * Triggered when the movie has completely loaded, after its last `loadprogress` event.
+     *
+     * @event RufflePlayer#loaded
+     */
+    static LOADED = "loaded";
+
+    /**
+     * Triggered when an item from the `contextMenuItems` option is clicked.
+     *
+     * The event's `detail` has the `callbackId` of the item.
+     *
+     * @event RufflePlayer#contextmenuitem
+     */
+    static CONTEXT_MENU_ITEM = "contextmenuitem";
+
+    /**
+     * Triggered when the browser lets the movie's audio play, such as after the
+     * user first interacted with the player.
+     *
+     * Custom unmute overlays can be hidden on this event.
+     *
+     * @event RufflePlayer#audioavailable
+     */
+    static AUDIO_AVAILABLE = "audioavailable";
+
     // The element that contains this player
     public element: HTMLElement;
 
@@ -151,6 +274,7 @@ export class InnerPlayer {
     private readonly videoModal: HTMLDivElement;
     private readonly hardwareAccelerationModal: HTMLDivElement;
     private readonly clipboardModal: HTMLDivElement;
+    private readonly accessibilityTree: HTMLElement;
 
     private readonly contextMenuOverlay: HTMLElement;
     // Firefox has a read-only "contextMenu" property,
@@ -171,18 +295,23 @@ export class InnerPlayer {
     private _suppressContextMenu = false;
 
     // The effective config loaded upon `.load()`.
-    public loadedConfig?: URLLoadOptions | DataLoadOptions;
+    public loadedConfig?: URLLoadOptions | DataLoadOptions | BundleLoadOptions;
 
     private swfUrl?: URL;
     private instance: RuffleHandle | null;
+    private auxWindowContainer: HTMLElement | null = null;
+    // A snapshot of the builder options of the current instance, without any sensitive values.
+    // This is taken before the instance is created, so that it is available even if creating it panics.
+    private builderConfig: unknown = null;
     private newZipWriter: (() => ZipWriter) | null;
-    private lastActivePlayingState: boolean;
 
     metadata: MovieMetadata | null;
     _readyState: ReadyState;
 
     private panicked = false;
     private rendererDebugInfo = "";
+    // Tells the instance whether it's on screen, for `maxActivePlayers`.
+    private onScreenObserver: IntersectionObserver | null = null;
 
     private longPressTimer: ReturnType<typeof setTimeout> | null = null;
     private pointerDownPosition: Point | null = null;
@@ -225,6 +354,17 @@ export class InnerPlayer {
             "input",
             this.virtualKeyboardInput.bind(this),
         );
+        this.virtualKeyboard.addEventListener(
+            "compositionupdate",
+            (event: CompositionEvent) => this.instance?.ime_preedit(event.data),
+        );
+        this.virtualKeyboard.addEventListener(
+            "compositionend",
+            (event: CompositionEvent) => {
+                this.instance?.ime_commit(event.data);
+                this.virtualKeyboard.value = "";
+            },
+        );
         this.saveManager = this.shadow.getElementById(
             "save-manager",
         )! as HTMLDivElement;
@@ -240,6 +380,8 @@ export class InnerPlayer {
         this.clipboardModal = this.shadow.getElementById(
             "clipboard-modal",
         ) as HTMLDivElement;
+        this.accessibilityTree =
+            this.shadow.getElementById("accessibility-tree")!;
         this.addModalJavaScript(this.saveManager);
         this.addModalJavaScript(this.volumeControls);
         this.addModalJavaScript(this.videoModal);
@@ -317,9 +459,6 @@ export class InnerPlayer {
 
         this._readyState = ReadyState.HaveNothing;
         this.metadata = null;
-
-        this.lastActivePlayingState = false;
-        this.setupPauseOnTabHidden();
     }
 
     addFSCommandHandler(handler: (command: string, args: string) => void) {
@@ -327,20 +466,45 @@ export class InnerPlayer {
     }
 
     public callFSCommand(command: string, args: string): boolean {
-        if (this.onFSCommand.length == 0) {
+        const handlers = [...this.onFSCommand];
+        // Like the Flash Player plugin, also call the `<name>_DoFSCommand`
+        // global function of pages written for it.
+        const legacyHandler = this.getLegacyFSCommandHandler();
+        if (legacyHandler) {
+            handlers.push(legacyHandler);
+        }
+        if (handlers.length == 0) {
             return false;
         }
-        for (const handler of this.onFSCommand) {
+        for (const handler of handlers) {
             handler(command, args);
         }
         return true;
     }
 
+    private getLegacyFSCommandHandler():
+        | ((command: string, args: string) => void)
+        | null {
+        const names = [this.getObjectId(), this.element.id];
+        for (const name of names) {
+            if (!name) {
+                continue;
+            }
+            const handler = (window as unknown as Record<string, unknown>)[
+                `${name}_DoFSCommand`
+            ];
+            if (typeof handler === "function") {
+                return handler as (command: string, args: string) => void;
+            }
+        }
+        return null;
+    }
+
     /**
      * Any configuration that should apply to this specific player.
      * This will be defaulted with any global configuration.
      */
-    config: URLLoadOptions | DataLoadOptions | object = {};
+    config: URLLoadOptions | DataLoadOptions | BundleLoadOptions | object = {};
 
     /**
      * Add functions to open and close a modal.
@@ -439,37 +603,6 @@ export class InnerPlayer {
         });
     }
 
-    /**
-     * Setup event listener to detect when tab is not active to pause instance playback.
-     * this.instance.play() is called when the tab becomes visible only if the
-     * the instance was not paused before tab became hidden.
-     *
-     * See: https://developer.mozilla.org/en-US/docs/Web/API/Page_Visibility_API
-     * @ignore
-     * @internal
-     */
-    private setupPauseOnTabHidden(): void {
-        document.addEventListener(
-            "visibilitychange",
-            () => {
-                if (!this.instance) {
-                    return;
-                }
-
-                // Tab just changed to be inactive. Record whether instance was playing.
-                if (document.hidden) {
-                    this.lastActivePlayingState = this.instance.is_playing();
-                    this.instance.pause();
-                }
-                // Play only if instance was playing originally.
-                if (!document.hidden && this.lastActivePlayingState === true) {
-                    this.instance.play();
-                }
-            },
-            false,
-        );
-    }
-
     /**
      * Updates the internal shadow DOM to reflect any set attributes from
      * this element.
@@ -539,14 +672,48 @@ export class InnerPlayer {
     }
 
     /**
-     * Ensure a fresh Ruffle instance is ready on this player before continuing.
+     * Downloads a zipped game, unless its data was given directly.
+     *
+     * @param bundle The URL or data of the zipped game.
+     * @returns The data of the zipped game.
+     * @throws A LoadSwfError if the download failed.
+     *
+     * @private
+     */
+    private async fetchBundle(
+        bundle: string | ArrayLike<number> | ArrayBufferLike,
+    ): Promise<ArrayLike<number> | ArrayBufferLike> {
+        if (typeof bundle !== "string") {
+            return bundle;
+        }
+        const url = new URL(bundle, document.baseURI);
+        console.log(`Loading bundle ${url}`);
+        try {
+            const response = await fetch(url.href);
+            if (!response.ok) {
+                throw new Error(`HTTP status ${response.status}`);
+            }
+            return await response.arrayBuffer();
+        } catch (e) {
+            console.error(`Couldn't download bundle from ${url}`, e);
+            const error = new LoadSwfError(url);
+            this.panic(error);
+            throw error;
+        }
+    }
+
+    /**
+     * Ensure a fresh Ruffle instance, playing the given movie, is ready on this player before continuing.
      *
-     * @throws Any exceptions generated by loading Ruffle Core will be logged
+     * @param options The movie to load.
+     * @throws Any exceptions generated by loading Ruffle Core or the movie will be logged
      * and passed on.
      *
      * @private
      */
-    private async ensureFreshInstance(): Promise<void> {
+    private async ensureFreshInstance(
+        options: URLLoadOptions | DataLoadOptions | BundleLoadOptions,
+    ): Promise<void> {
         this.destroy();
 
         if (
@@ -582,7 +749,7 @@ export class InnerPlayer {
         }
 
         const [builder, zipWriterClass] = await createRuffleBuilder(
-            this.onRuffleDownloadProgress.bind(this),
+            this.updateLoadBar.bind(this),
         ).catch((e) => {
             console.error(`Serious error loading Ruffle: ${e}`);
             const error = new LoadRuffleWasmError(e);
@@ -621,12 +788,59 @@ export class InnerPlayer {
             }
         }
 
-        this.instance = await builder.build(this.container, this).catch((e) => {
-            console.error(`Serious error loading Ruffle: ${e}`);
-            this.panic(e);
+        this.builderConfig = builder.getConfig(false);
+        let instance: Promise<RuffleHandle>;
+        if ("url" in options) {
+            console.log(`Loading SWF file ${options.url}`);
+            this.swfUrl = new URL(options.url, document.baseURI);
+            instance = builder.buildWithUrl(
+                this.container,
+                this,
+                this.swfUrl.href,
+                sanitizeParameters(options.parameters),
+            );
+        } else if ("bundle" in options) {
+            console.log("Loading bundle");
+            delete this.swfUrl;
+            instance = builder.buildWithBundle(
+                this.container,
+                this,
+                new Uint8Array(await this.fetchBundle(options.bundle)),
+                sanitizeParameters(options.parameters),
+            );
+        } else {
+            console.log("Loading SWF data");
+            delete this.swfUrl;
+            instance = builder.buildWithData(
+                this.container,
+                this,
+                new Uint8Array(options.data),
+                sanitizeParameters(options.parameters),
+                options.swfFileName || "movie.swf",
+            );
+        }
+        this.instance = await instance.catch((e) => {
+            // The player was already destroyed when its movie failed to load.
+            this.instance = null;
+            if (e instanceof Error && e.name === "MovieLoadError") {
+                // The user has already been told why the movie couldn't be loaded.
+                console.error(`Error loading SWF file: ${e.message}`);
+            } else {
+                console.error(`Serious error loading Ruffle: ${e}`);
+                this.panic(e);
+            }
             throw e;
         });
 
+        this.instance!.set_aux_window_container(this.auxWindowContainer);
+        if ("IntersectionObserver" in window) {
+            this.onScreenObserver = new IntersectionObserver((entries) => {
+                for (const entry of entries) {
+                    this.instance?.set_on_screen(entry.isIntersecting);
+                }
+            });
+            this.onScreenObserver.observe(this.element);
+        }
         this.rendererDebugInfo = this.instance!.renderer_debug_info();
 
         if (this.rendererDebugInfo.includes("Adapter Device Type: Cpu")) {
@@ -669,41 +883,13 @@ export class InnerPlayer {
 
         this.unmuteAudioContext();
 
-        // Treat invalid values as `AutoPlay.Auto`.
-        if (
-            !this.loadedConfig ||
-            this.loadedConfig.autoplay === AutoPlay.On ||
-            (this.loadedConfig.autoplay !== AutoPlay.Off &&
-                this.audioState() === "running")
-        ) {
+        // The audio is resumed by the player itself on the first interaction,
+        // which then calls `onAudioAvailable`.
+        if (this.instance?.should_autoplay()) {
             this.play();
 
-            if (this.audioState() !== "running") {
-                // Treat invalid values as `UnmuteOverlay.Visible`.
-                if (
-                    !this.loadedConfig ||
-                    this.loadedConfig.unmuteOverlay !== UnmuteOverlay.Hidden
-                ) {
-                    this.unmuteOverlay.style.display = "block";
-                }
-
-                this.container.addEventListener(
-                    "click",
-                    this.unmuteOverlayClicked.bind(this),
-                    {
-                        once: true,
-                    },
-                );
-
-                const audioContext = this.instance?.audio_context();
-                if (audioContext) {
-                    audioContext.onstatechange = () => {
-                        if (audioContext.state === "running") {
-                            this.unmuteOverlayClicked();
-                        }
-                        audioContext.onstatechange = null;
-                    };
-                }
+            if (this.instance?.should_show_unmute_overlay()) {
+                this.unmuteOverlay.style.display = "block";
             }
         } else {
             this.playButton.style.display = "block";
@@ -711,12 +897,13 @@ export class InnerPlayer {
     }
 
     /**
-     * Uploads the splash screen progress bar.
+     * Updates the splash screen progress bar.
      *
-     * @param bytesLoaded The size of the Ruffle WebAssembly file downloaded so far.
-     * @param bytesTotal The total size of the Ruffle WebAssembly file.
+     * @param bytesLoaded The size of the Ruffle WebAssembly file downloaded so far,
+     * or of the movie loaded so far when waiting for its full load.
+     * @param bytesTotal The total size of the Ruffle WebAssembly file or of the movie.
      */
-    private onRuffleDownloadProgress(bytesLoaded: number, bytesTotal: number) {
+    private updateLoadBar(bytesLoaded: number, bytesTotal: number) {
         const loadBar = this.splashScreen.querySelector(
             ".loadbar-inner",
         ) as HTMLElement;
@@ -736,6 +923,8 @@ export class InnerPlayer {
      * Destroys the currently running instance of Ruffle.
      */
     destroy(): void {
+        this.onScreenObserver?.disconnect();
+        this.onScreenObserver = null;
         if (this.instance) {
             this.instance.destroy();
             this.instance = null;
@@ -746,8 +935,8 @@ export class InnerPlayer {
     }
 
     private checkOptions(
-        options: string | URLLoadOptions | DataLoadOptions,
-    ): URLLoadOptions | DataLoadOptions {
+        options: string | URLLoadOptions | DataLoadOptions | BundleLoadOptions,
+    ): URLLoadOptions | DataLoadOptions | BundleLoadOptions {
         if (typeof options === "string") {
             return { url: options };
         }
@@ -767,8 +956,8 @@ export class InnerPlayer {
             "Argument 0 must be a string or object",
         );
         check(
-            "url" in options || "data" in options,
-            "Argument 0 must contain a `url` or `data` key",
+            "url" in options || "data" in options || "bundle" in options,
+            "Argument 0 must contain a `url`, `data` or `bundle` key",
         );
         check(
             !("url" in options) || typeof options.url === "string",
@@ -799,6 +988,7 @@ export class InnerPlayer {
      * - A URL, passed as a string, which will load a URL with default options.
      * - A [[URLLoadOptions]] object, to load a URL with options.
      * - A [[DataLoadOptions]] object, to load data with options.
+     * - A [[BundleLoadOptions]] object, to load a zipped game with options.
      * The options, if provided, must only contain values provided for this specific movie.
      * They must not contain any default values, since those would overwrite other configuration
      * settings with a lower priority (e.g. the general RufflePlayer config).
@@ -809,7 +999,7 @@ export class InnerPlayer {
      * is defaulted by a global `window.RufflePlayer.config`.
      */
     async load(
-        options: string | URLLoadOptions | DataLoadOptions,
+        options: string | URLLoadOptions | DataLoadOptions | BundleLoadOptions,
         isPolyfillElement: boolean = false,
     ): Promise<void> {
         options = this.checkOptions(options);
@@ -852,26 +1042,11 @@ export class InnerPlayer {
                     this.loadedConfig.backgroundColor;
             }
 
-            await this.ensureFreshInstance();
-
-            if ("url" in options) {
-                console.log(`Loading SWF file ${options.url}`);
-                this.swfUrl = new URL(options.url, document.baseURI);
-
-                this.instance!.stream_from(
-                    this.swfUrl.href,
-                    sanitizeParameters(options.parameters),
-                );
-            } else if ("data" in options) {
-                console.log("Loading SWF data");
-                delete this.swfUrl;
-                this.instance!.load_data(
-                    new Uint8Array(options.data),
-                    sanitizeParameters(options.parameters),
-                    options.swfFileName || "movie.swf",
-                );
-            }
+            await this.ensureFreshInstance(options);
         } catch (e) {
+            if (e instanceof Error && e.name === "MovieLoadError") {
+                throw e;
+            }
             console.error(`Serious error occurred loading SWF file: ${e}`);
             const err = new Error(e as string);
             this.panic(err);
@@ -889,6 +1064,31 @@ export class InnerPlayer {
         }
     }
 
+    /**
+     * Plays the given number of frames, then pauses.
+     *
+     * @param frames The number of frames to play.
+     */
+    stepFrame(frames: number): void {
+        if (this.instance) {
+            this.instance.step_frame(frames);
+            this.playButton.style.display = "none";
+        }
+    }
+
+    /**
+     * Moves the movie to the given frame.
+     *
+     * @param frame The 1-based frame number.
+     * @param scene The scene that `frame` is counted from.
+     */
+    gotoFrame(frame: number, scene?: string): void {
+        if (!this.instance) {
+            throw new Error("No movie is loaded");
+        }
+        this.instance.goto_frame(frame, scene);
+    }
+
     /**
      * Whether this player is currently playing.
      *
@@ -1228,7 +1428,11 @@ export class InnerPlayer {
         }
     }
 
-    private virtualKeyboardInput() {
+    private virtualKeyboardInput(event: Event) {
+        // Text being composed with an IME is sent once the composition ends.
+        if ((event as InputEvent).isComposing) {
+            return;
+        }
         const input = this.virtualKeyboard;
         const string = input.value;
         for (const char of string) {
@@ -1279,6 +1483,98 @@ export class InnerPlayer {
         }
     }
 
+    /**
+     * Puts a PNG image on the system clipboard.
+     *
+     * This needs the async Clipboard API, which is only available on secure pages.
+     */
+    protected writeClipboardImage(png: Uint8Array): void {
+        if (!navigator.clipboard || !window.isSecureContext) {
+            console.warn("Couldn't copy image: the Clipboard API is unavailable");
+            return;
+        }
+        const blob = new Blob([png], { type: "image/png" });
+        navigator.clipboard
+            .write([new ClipboardItem({ "image/png": blob })])
+            .catch((err) => console.warn("Couldn't copy image:", err));
+    }
+
+    /**
+     * Moves the virtual keyboard input to the text cursor,
+     * so that the browser shows IME candidates next to it.
+     *
+     * The area is in CSS pixels, relative to the player.
+     */
+    protected setImeCursorArea(
+        x: number,
+        y: number,
+        width: number,
+        height: number,
+    ): void {
+        const style = this.virtualKeyboard.style;
+        style.left = `${x}px`;
+        style.top = `${y}px`;
+        style.width = `${Math.max(width, 1)}px`;
+        style.height = `${Math.max(height, 1)}px`;
+    }
+
+    /**
+     * Replaces the hidden mirror of the stage that screen readers follow.
+     */
+    protected updateAccessibilityTree(nodes: AccessibilityNode[]): void {
+        this.accessibilityTree.replaceChildren(
+            ...nodes.map((node) => this.accessibilityElement(node, 0, 0)),
+        );
+    }
+
+    /**
+     * Creates the element mirroring a node, positioned relative to its parent.
+     */
+    private accessibilityElement(
+        node: AccessibilityNode,
+        parentX: number,
+        parentY: number,
+    ): HTMLElement {
+        let element: HTMLElement;
+        switch (node.role) {
+            case "button":
+                element = document.createElement("button");
+                element.textContent = node.name;
+                element.addEventListener("click", () =>
+                    this.instance?.click(
+                        node.x + node.width / 2,
+                        node.y + node.height / 2,
+                    ),
+                );
+                break;
+            case "graphic":
+                element = document.createElement("div");
+                element.setAttribute("role", "group");
+                element.setAttribute("aria-label", node.name);
+                element.append(
+                    ...node.children.map((child) =>
+                        this.accessibilityElement(child, node.x, node.y),
+                    ),
+                );
+                break;
+            default:
+                element = document.createElement("p");
+                element.textContent = node.name;
+                break;
+        }
+        if (node.description) {
+            element.setAttribute("aria-description", node.description);
+        }
+        if (node.shortcut) {
+            element.setAttribute("aria-keyshortcuts", node.shortcut);
+        }
+        element.style.left = `${node.x - parentX}px`;
+        element.style.top = `${node.y - parentY}px`;
+        element.style.width = `${node.width}px`;
+        element.style.height = `${node.height}px`;
+        return element;
+    }
+
     protected isVirtualKeyboardFocused(): boolean {
         return this.shadow.activeElement === this.virtualKeyboard;
     }
@@ -1317,6 +1613,20 @@ export class InnerPlayer {
             addSeparator();
         }
 
+        if (this.instance) {
+            const embedderItems: string[] =
+                this.instance.embedder_context_menu_items();
+            embedderItems.forEach((label, index) => {
+                items.push({
+                    text: label,
+                    onClick: async () =>
+                        this.instance?.run_embedder_context_menu_item(index),
+                });
+            });
+
+            addSeparator();
+        }
+
         if (this.fullscreenEnabled) {
             if (this.isFullscreen) {
                 items.push({
@@ -1601,16 +1911,9 @@ export class InnerPlayer {
         return "suspended";
     }
 
-    private unmuteOverlayClicked(): void {
-        if (this.instance) {
-            if (this.audioState() !== "running") {
-                const audioContext = this.instance.audio_context();
-                if (audioContext) {
-                    audioContext.resume();
-                }
-            }
-            this.unmuteOverlay.style.display = "none";
-        }
+    protected onAudioAvailable(): void {
+        this.unmuteOverlay.style.display = "none";
+        this.element.dispatchEvent(new CustomEvent(InnerPlayer.AUDIO_AVAILABLE));
     }
 
     /**
@@ -1705,6 +2008,10 @@ export class InnerPlayer {
         return this.instance?.call_exposed_callback(name, args);
     }
 
+    public callFunction(request: string): string {
+        return this.instance?.call_function(request) ?? "<undefined/>";
+    }
+
     protected getObjectId(): string | null {
         return this.element.getAttribute("name");
     }
@@ -1720,6 +2027,137 @@ export class InnerPlayer {
         this.instance?.set_trace_observer(observer);
     }
 
+    /**
+     * Sets the element that additional windows opened by the movie are shown in.
+     *
+     * @param container The element to place windows in, or `null` to hide them.
+     */
+    setAuxWindowContainer(container: HTMLElement | null): void {
+        this.auxWindowContainer = container;
+        this.instance?.set_aux_window_container(container);
+    }
+
+    /**
+     * Captures the movie as it currently looks, as a PNG image.
+     *
+     * @returns The PNG image.
+     */
+    captureFrame(): Blob {
+        if (!this.instance) {
+            throw new Error("No movie is loaded");
+        }
+        return new Blob([this.instance.capture_frame()], {
+            type: "image/png",
+        });
+    }
+
+    /**
+     * Starts recording the time spent in ActionScript functions.
+     */
+    startProfiling(): void {
+        this.instance?.start_profiling();
+    }
+
+    /**
+     * Stops recording, and returns the profile as speedscope JSON.
+     *
+     * @returns The profile, or `null` if none was being recorded.
+     */
+    stopProfiling(): string | null {
+        return this.instance?.stop_profiling() ?? null;
+    }
+
+    /**
+     * Starts recording input into a replay.
+     *
+     * @param seed The seed for random numbers, or the current time if not given.
+     */
+    startReplayRecording(seed?: number): void {
+        this.instance?.start_replay_recording(seed);
+    }
+
+    /**
+     * Plays back a replay, ignoring input until it ends.
+     *
+     * @param replay The replay as JSON.
+     */
+    startReplay(replay: string): void {
+        if (!this.instance) {
+            throw new Error("No movie is loaded");
+        }
+        this.instance.start_replay(replay);
+    }
+
+    /**
+     * Stops recording or playing back a replay.
+     *
+     * @returns The replay as JSON, or `null` if there was none.
+     */
+    stopReplay(): string | null {
+        return this.instance?.stop_replay() ?? null;
+    }
+
+    /**
+     * Saves the state of the movie, as the replay up to now.
+     *
+     * @returns The save state as JSON, or `null` if no replay is active.
+     */
+    saveState(): string | null {
+        return this.instance?.save_state() ?? null;
+    }
+
+    /**
+     * Loads a save state, and records input again once it has played back.
+     *
+     * @param state The save state as JSON.
+     */
+    loadState(state: string): void {
+        if (!this.instance) {
+            throw new Error("No movie is loaded");
+        }
+        this.instance.load_state(state);
+    }
+
+    /**
+     * Enables or disables low-latency input while the movie is running.
+     *
+     * @param enabled Whether to enable low-latency input.
+     * @param force Whether to keep it enabled for ActionScript 3 movies.
+     */
+    setLowLatencyInput(enabled: boolean, force: boolean): void {
+        if (!this.instance) {
+            throw new Error("No movie is loaded");
+        }
+        this.instance.set_low_latency_input(enabled, force);
+    }
+
+    /**
+     * Frees memory that can be recreated later, such as decoded images.
+     */
+    lowMemory(): void {
+        this.instance?.low_memory();
+    }
+
+    /**
+     * Plays the movie deterministically and hashes every rendered frame.
+     *
+     * @param options The options of the session.
+     * @returns The versioned report with the hashes of each frame.
+     */
+    async runHashSession(options: HashSessionOptions): Promise<HashReport> {
+        if (!this.instance) {
+            throw new Error("No movie is loaded");
+        }
+        return await this.instance.run_hash_session(options);
+    }
+
+    /**
+     * Cancels the running hash session, if any.
+     */
+    cancelHashSession(): void {
+        this.instance?.cancel_hash_session();
+    }
+
     /**
      * Get data included in any panic of this ruffle-player
      *
@@ -1759,6 +2197,11 @@ export class InnerPlayer {
             }
         }
 
+        result += "\n# Player Config\n";
+        if (this.builderConfig) {
+            result += `${JSON.stringify(this.builderConfig, null, 2)}\n`;
+        }
+
         return result;
     }
 
@@ -1944,6 +2387,14 @@ export class InnerPlayer {
         }
     }
 
+    protected onContextMenuItem(callbackId: string): void {
+        this.element.dispatchEvent(
+            new CustomEvent(InnerPlayer.CONTEXT_MENU_ITEM, {
+                detail: { callbackId },
+            }),
+        );
+    }
+
     protected displayClipboardModal(accessDenied: boolean): void {
         const description = this.clipboardModal.querySelector(
             "#clipboard-modal-description",
@@ -1966,16 +2417,79 @@ export class InnerPlayer {
         this.container.classList.add("hidden");
     }
 
+    /**
+     * Called by the builder as soon as the Ruffle instance exists,
+     * before its movie loads and the metadata events fire.
+     *
+     * @param instance The new instance.
+     */
+    protected onInstanceCreated(instance: RuffleHandle): void {
+        this.instance = instance;
+    }
+
     protected setMetadata(metadata: MovieMetadata) {
         this.metadata = metadata;
-        // TODO: Switch this to ReadyState.Loading when we have streaming support.
-        this._readyState = ReadyState.Loaded;
-        this.hideSplashScreen();
+        this._readyState = ReadyState.Loading;
+        // A movie that waits for its full load keeps showing the load bar until then.
+        if (!this.loadedConfig?.waitForFullLoad) {
+            this.hideSplashScreen();
+        }
         this.element.dispatchEvent(
             new CustomEvent(InnerPlayer.LOADED_METADATA),
         );
-        // TODO: Move this to whatever function changes the ReadyState to Loaded when we have streaming support.
+        this.element.dispatchEvent(
+            new CustomEvent(InnerPlayer.METADATA_LOADED, { detail: metadata }),
+        );
+    }
+
+    protected onLoadProgress(
+        phase: "download" | "preload",
+        bytesLoaded: number,
+        bytesTotal?: number,
+    ): void {
+        if (
+            this.loadedConfig?.waitForFullLoad &&
+            phase === "preload" &&
+            bytesTotal
+        ) {
+            this.updateLoadBar(bytesLoaded, bytesTotal);
+        }
+        this.element.dispatchEvent(
+            new CustomEvent(InnerPlayer.LOAD_PROGRESS, {
+                detail: { phase, bytesLoaded, bytesTotal },
+            }),
+        );
+    }
+
+    protected onLoaded(): void {
+        this._readyState = ReadyState.Loaded;
+        this.hideSplashScreen();
         this.element.dispatchEvent(new CustomEvent(InnerPlayer.LOADED_DATA));
+        this.element.dispatchEvent(new CustomEvent(InnerPlayer.LOADED));
+    }
+
+    protected onTraceOutput(message: string): void {
+        this.element.dispatchEvent(
+            new CustomEvent(InnerPlayer.TRACE_OUTPUT, { detail: { message } }),
+        );
+    }
+
+    protected onRendererCreated(report: RendererReport): void {
+        this.element.dispatchEvent(
+            new CustomEvent(InnerPlayer.RENDERER_CREATED, { detail: report }),
+        );
+    }
+
+    protected onRendererRestored(): void {
+        this.element.dispatchEvent(
+            new CustomEvent(InnerPlayer.RENDERER_RESTORED),
+        );
+    }
+
+    protected onLoadError(message: string): void {
+        this.element.dispatchEvent(
+            new CustomEvent(InnerPlayer.LOAD_ERROR, { detail: { message } }),
+        );
     }
 }
 
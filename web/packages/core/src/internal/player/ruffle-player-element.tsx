@@ -1,4 +1,8 @@
-import type { DataLoadOptions, URLLoadOptions } from "../../public/config";
+import type {
+    BundleLoadOptions,
+    DataLoadOptions,
+    URLLoadOptions,
+} from "../../public/config";
 import { MovieMetadata, PlayerElement, ReadyState } from "../../public/player";
 import { InnerPlayer } from "./inner";
 import { APIVersions } from "../../public/player";
@@ -67,7 +71,11 @@ export class RufflePlayerElement extends HTMLElement implements PlayerElement {
         throw new Error(`Version ${version} not supported.`);
     }
 
-    get loadedConfig(): URLLoadOptions | DataLoadOptions | null {
+    get loadedConfig():
+        | URLLoadOptions
+        | DataLoadOptions
+        | BundleLoadOptions
+        | null {
         return this.#inner.loadedConfig ?? null;
     }
 
@@ -98,7 +106,7 @@ export class RufflePlayerElement extends HTMLElement implements PlayerElement {
     }
 
     async load(
-        options: string | URLLoadOptions | DataLoadOptions,
+        options: string | URLLoadOptions | DataLoadOptions | BundleLoadOptions,
         isPolyfillElement: boolean = false,
     ): Promise<void> {
         await this.#inner.load(options, isPolyfillElement);
@@ -156,6 +164,10 @@ export class RufflePlayerElement extends HTMLElement implements PlayerElement {
         return "";
     }
 
+    public CallFunction(request: string): string {
+        return this.#inner.callFunction(request);
+    }
+
     public PercentLoaded(): number {
         // [NA] This is a stub - we need to research how this is actually implemented (is it just base swf loadedBytes?)
         if (this.#inner._readyState === ReadyState.Loaded) {
@@ -165,11 +177,17 @@ export class RufflePlayerElement extends HTMLElement implements PlayerElement {
         }
     }
 
-    get config(): URLLoadOptions | DataLoadOptions | object {
+    get config():
+        | URLLoadOptions
+        | DataLoadOptions
+        | BundleLoadOptions
+        | object {
         return this.#inner.config;
     }
 
-    set config(value: URLLoadOptions | DataLoadOptions | object) {
+    set config(
+        value: URLLoadOptions | DataLoadOptions | BundleLoadOptions | object,
+    ) {
         this.#inner.config = value;
     }
 
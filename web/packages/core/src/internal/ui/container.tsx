@@ -31,6 +31,7 @@ export function MainContainer() {
                     </svg>
                 </div>
             </div>
+            <div id="accessibility-tree"></div>
             <input aria-hidden="true" id="virtual-keyboard" type="text" autocomplete="off" autocorrect="off" autocapitalize="none" />
         </div>
     );
@@ -2,11 +2,18 @@ import { RuffleObjectElement } from "./internal/player/ruffle-object-element";
 import { RuffleEmbedElement } from "./internal/player/ruffle-embed-element";
 import { installPlugin, FLASH_PLUGIN } from "./plugin-polyfill";
 import { publicPath } from "./public-path";
-import type { DataLoadOptions, URLLoadOptions } from "./public/config";
+import type {
+    BundleLoadOptions,
+    DataLoadOptions,
+    URLLoadOptions,
+} from "./public/config";
 import { isExtension } from "./current-script";
 
-const globalConfig: DataLoadOptions | URLLoadOptions | object =
-    window.RufflePlayer?.config ?? {};
+const globalConfig:
+    | DataLoadOptions
+    | URLLoadOptions
+    | BundleLoadOptions
+    | object = window.RufflePlayer?.config ?? {};
 const jsScriptUrl = publicPath(globalConfig) + "ruffle.js";
 
 /**
@@ -1,12 +1,15 @@
 import type { BaseLoadOptions } from "./load-options";
 import {
     AutoPlay,
+    BackgroundBehavior,
+    BitmapReadbackMode,
     ContextMenu,
     Letterbox,
     LogLevel,
     OpenURLMode,
     NetworkingAccessMode,
     PlayerRuntime,
+    PolicyFileMode,
     UnmuteOverlay,
     WindowMode,
 } from "./load-options";
@@ -20,9 +23,12 @@ export const DEFAULT_CONFIG: Required<BaseLoadOptions> = {
     unmuteOverlay: UnmuteOverlay.Visible,
     upgradeToHttps: true,
     compatibilityRules: true,
+    compatibilityRulesUrl: null,
+    maxActivePlayers: null,
     favorFlash: true,
     warnOnUnsupportedContent: true,
     logLevel: LogLevel.Error,
+    logCallback: null,
     showSwfDownload: false,
     contextMenu: ContextMenu.On,
     // Backwards-compatibility option
@@ -51,4 +57,26 @@ export const DEFAULT_CONFIG: Required<BaseLoadOptions> = {
     defaultFonts: {},
     credentialAllowList: [],
     playerRuntime: PlayerRuntime.FlashPlayer,
+    bitmapReadbackMode: BitmapReadbackMode.Strict,
+    maxNativeWindows: 8,
+    lowLatencyInput: false,
+    forceLowLatencyInput: false,
+    storageBackend: "local",
+    gamepadButtonMapping: {},
+    gamepadMouseSpeed: 0,
+    keyRemap: {},
+    allowCamera: true,
+    allowMicrophone: true,
+    contextMenuItems: [],
+    adaptiveQuality: false,
+    accessibility: false,
+    backgroundBehavior: BackgroundBehavior.Pause,
+    allowLocalFontAccess: false,
+    waitForFullLoad: false,
+    loadAllowList: null,
+    policyFileMode: PolicyFileMode.Ignore,
+    rslMirror: null,
+    capabilities: {},
+    parametersFromUrl: null,
+    fetchHandler: null,
 };
@@ -79,6 +79,31 @@ export enum LogLevel {
     Trace = "trace",
 }
 
+/**
+ * A log record, as given to the `logCallback` option.
+ */
+export interface LogRecord {
+    /**
+     * The level of the record.
+     */
+    level: LogLevel;
+
+    /**
+     * The module that logged the record, or `"avm_trace"` for `trace()` output.
+     */
+    target: string;
+
+    /**
+     * The logged message, followed by any other fields as `name=value`.
+     */
+    message: string;
+
+    /**
+     * Whether this is output of `trace()` in the movie.
+     */
+    isAvmTrace: boolean;
+}
+
 /**
  * The window mode of a Ruffle player.
  */
@@ -175,6 +200,25 @@ export enum ContextMenu {
     Off = "off",
 }
 
+/**
+ * Controls how reading pixels from a BitmapData waits for pending GPU work.
+ */
+export enum BitmapReadbackMode {
+    /**
+     * Every read waits for the GPU, exactly like Flash Player.
+     */
+    Strict = "strict",
+
+    /**
+     * Bitmaps that are read very frequently may return pixels that are up to
+     * one frame old, and other reads are batched together.
+     *
+     * This can greatly improve performance of content that reads pixels every frame,
+     * but changes the observable timing of those reads.
+     */
+    Throttled = "throttled",
+}
+
 /**
  * Represents the player runtime to emulate.
  */
@@ -230,6 +274,27 @@ export enum OpenURLMode {
     Deny = "deny",
 }
 
+/**
+ * What the player does while the page is hidden, such as in a background tab.
+ */
+export enum BackgroundBehavior {
+    /**
+     * Pause the movie and its audio until the page is visible again.
+     */
+    Pause = "pause",
+
+    /**
+     * Keep running, but only wake up twice a second.
+     * Timers still see all the time that passed, but frames in between are skipped.
+     */
+    Throttle = "throttle",
+
+    /**
+     * Keep running every frame, as often as the browser lets a hidden page wake up.
+     */
+    Run = "run",
+}
+
 /**
  * The networking API access mode of the Ruffle player.
  */
@@ -263,6 +328,46 @@ export enum NetworkingAccessMode {
     None = "none",
 }
 
+/**
+ * How cross-domain policy files (`crossdomain.xml`) are handled.
+ */
+export enum PolicyFileMode {
+    /**
+     * Reading data from other domains and connecting sockets to them fails
+     * unless a policy file allows it, as in Flash Player.
+     */
+    Enforce = "enforce",
+
+    /**
+     * Policy files are checked, but violations are only logged.
+     */
+    Warn = "warn",
+
+    /**
+     * Policy files aren't checked at all.
+     */
+    Ignore = "ignore",
+}
+
+/**
+ * The protocol used to reach a socket proxy.
+ */
+export enum SocketProxyProtocol {
+    /**
+     * The socket is relayed through a WebSocket connection.
+     */
+    WebSocket = "websocket",
+
+    /**
+     * The socket is relayed through a bidirectional WebTransport stream, for lower latency.
+     *
+     * If the browser doesn't support WebTransport or the session can't be established,
+     * Ruffle falls back to a WebSocket connection to the same proxy URL,
+     * with `https:` replaced by `wss:`.
+     */
+    WebTransport = "webtransport",
+}
+
 /**
  * Represents a host, port and proxyUrl. Used when a SWF file tries to use a Socket.
  */
@@ -280,6 +385,40 @@ export interface SocketProxy {
      * The proxy URL to use when SWF file tries to connect to the specified host and port.
      */
     proxyUrl: string;
+
+    /**
+     * The protocol used to reach the proxy.
+     *
+     * @default SocketProxyProtocol.WebSocket
+     */
+    proxyProtocol?: SocketProxyProtocol;
+
+    /**
+     * Whether the proxy connects to the server with TLS.
+     *
+     * Only such proxies are used for `SecureSocket` connections, and only other
+     * proxies are used for plain `Socket` connections. The proxy is responsible
+     * for checking the certificate of the server.
+     *
+     * @default false
+     */
+    tls?: boolean;
+}
+
+/**
+ * An item that the page adds to Ruffle's right-click menu.
+ */
+export interface CustomContextMenuItem {
+    /**
+     * The text shown to the user.
+     */
+    label: string;
+
+    /**
+     * Identifies the item in the `contextmenuitem` event that is dispatched
+     * on the player element when the item is clicked.
+     */
+    callbackId: string;
 }
 
 /**
@@ -319,6 +458,95 @@ export interface DefaultFonts {
      * `_明朝`, a Japanese Mincho font
      */
     japaneseMincho?: Array<string>;
+
+    /**
+     * Fonts used for characters that the requested device font has no glyphs for,
+     * such as CJK characters or emoji
+     */
+    fallback?: Array<string>;
+}
+
+/**
+ * Strings reported to the movie in place of the real ones, through `Capabilities`
+ * in ActionScript 3 and `System.capabilities` in ActionScript 1 and 2.
+ *
+ * Each is named after the property of `Capabilities` that it overrides.
+ */
+export interface Capabilities {
+    /**
+     * The version of Flash Player, such as `"WIN 32,0,0,0"`.
+     */
+    version?: string;
+
+    /**
+     * The operating system, such as `"Windows 8"`.
+     */
+    os?: string;
+
+    /**
+     * The type of player, such as `"PlugIn"` or `"StandAlone"`.
+     */
+    playerType?: string;
+
+    /**
+     * The language of the player, such as `"en"`.
+     */
+    language?: string;
+
+    /**
+     * The URL-encoded string of every capability, which movies send to servers.
+     */
+    serverString?: string;
+}
+
+/**
+ * The buttons of a gamepad, named after their position on the controller.
+ */
+export type GamepadButtonName =
+    | "south"
+    | "east"
+    | "north"
+    | "west"
+    | "left-trigger"
+    | "left-trigger2"
+    | "right-trigger"
+    | "right-trigger2"
+    | "select"
+    | "start"
+    | "d-pad-up"
+    | "d-pad-down"
+    | "d-pad-left"
+    | "d-pad-right";
+
+/**
+ * Stores the SharedObjects of movies, for pages that want to keep them elsewhere.
+ *
+ * Every method is called synchronously, while the movie waits.
+ */
+export interface StorageBackend {
+    /**
+     * Returns the data saved under the given name.
+     *
+     * @param name The name of the SharedObject.
+     * @returns The saved data, or `null` if nothing is saved under this name.
+     */
+    get(name: string): Uint8Array | null;
+
+    /**
+     * Saves data under the given name, replacing any existing data.
+     *
+     * @param name The name of the SharedObject.
+     * @param data The data to save.
+     * @returns Whether the data was saved.
+     */
+    put(name: string, data: Uint8Array): boolean;
+
+    /**
+     * Removes the data saved under the given name.
+     *
+     * @param name The name of the SharedObject.
+     */
+    remove(name: string): void;
 }
 
 /**
@@ -403,6 +631,36 @@ export interface BaseLoadOptions {
      */
     compatibilityRules?: boolean;
 
+    /**
+     * A URL to download more compatibility rules from, so that fixes for
+     * specific games don't have to wait for a new release of Ruffle.
+     *
+     * The JSON document has a `ruleSets` array. Each rule set has a `name`,
+     * and may have `swfDomainRewriteRules` (a list of `host` and `replacement`)
+     * as well as `playerVersion`, `frameRate` and `spoofedDomain`, which apply
+     * to movies loaded from one of its `swfHosts` (such as `*.example.com`).
+     *
+     * The rules are cached for a day, and replace built-in rule sets of the
+     * same name.
+     *
+     * @default null
+     */
+    compatibilityRulesUrl?: string | null;
+
+    /**
+     * The most players on the page that may run at once, or null for no limit.
+     *
+     * This helps pages with many movies, such as galleries or forum
+     * signatures. Players on screen run first, then the ones most recently
+     * clicked, and the others are paused until it's their turn.
+     *
+     * The limit applies to the whole page, as set by the last player created
+     * with one.
+     *
+     * @default null
+     */
+    maxActivePlayers?: number | null;
+
     /**
      * Favor using the real Adobe Flash Player over Ruffle if the browser supports it.
      *
@@ -430,6 +688,17 @@ export interface BaseLoadOptions {
      */
     logLevel?: LogLevel;
 
+    /**
+     * A function that receives log records, along with the console.
+     *
+     * Records are passed up to the {@link logLevel}, as well as all `trace()`
+     * output of the movie, so that pages can collect errors and traces
+     * without reading the console.
+     *
+     * @default null
+     */
+    logCallback?: ((record: LogRecord) => void) | null;
+
     /**
      * If set to true, the context menu has an option to download
      * the SWF.
@@ -613,7 +882,8 @@ export interface BaseLoadOptions {
      * An array of SocketProxy objects.
      *
      * When a SWF tries to establish a Socket connection, Ruffle will search for
-     * a matching SocketProxy object in this array and use it to establish a WebSocket connection,
+     * a matching SocketProxy object in this array and use it to establish a WebSocket connection
+     * (or a WebTransport stream, depending on its proxyProtocol),
      * through which all communication is tunneled through.
      *
      * When none are found, Ruffle will fail the connection gracefully.
@@ -667,6 +937,264 @@ export interface BaseLoadOptions {
      * This allows you to emulate Adobe AIR or Adobe Flash Player.
      */
     playerRuntime?: PlayerRuntime;
+
+    /**
+     * How reading pixels from a BitmapData waits for pending GPU work.
+     *
+     * @default BitmapReadbackMode.Strict
+     */
+    bitmapReadbackMode?: BitmapReadbackMode;
+
+    /**
+     * The maximum number of additional windows that AIR content may have open at once.
+     *
+     * Constructing a `NativeWindow` beyond this limit throws `Error #2037`.
+     * Windows are only displayed once a container has been provided with `setAuxWindowContainer`.
+     *
+     * @default 8
+     */
+    maxNativeWindows?: number;
+
+    /**
+     * Whether to handle input with the lowest possible latency,
+     * for content such as instruments and rhythm games.
+     *
+     * Sounds started by key and mouse handlers are output with smaller audio buffers,
+     * so that they are heard sooner, at the cost of some efficiency.
+     *
+     * This is disabled for ActionScript 3 movies, as they may rely on input events
+     * being dispatched in frame order, unless `forceLowLatencyInput` is also set.
+     *
+     * @default false
+     */
+    lowLatencyInput?: boolean;
+
+    /**
+     * Whether to keep `lowLatencyInput` enabled for ActionScript 3 movies.
+     *
+     * @default false
+     */
+    forceLowLatencyInput?: boolean;
+
+    /**
+     * Where movies store their SharedObjects.
+     *
+     * - `"local"` uses `localStorage`, which only holds a few megabytes per site.
+     * - `"indexeddb"` uses IndexedDB, which holds much more, for games with large saves.
+     *   Saves made with `"local"` before are still found, and are copied over when they're read.
+     * - Any other object is used as a {@link StorageBackend}.
+     *
+     * If the chosen storage is unavailable, `localStorage` is used instead.
+     *
+     * @default "local"
+     */
+    storageBackend?: "local" | "indexeddb" | StorageBackend;
+
+    /**
+     * Keys that gamepad buttons act as, so that games made for the keyboard can be played with a gamepad.
+     *
+     * Each button is mapped to a Flash key code, such as `32` for the space bar or `38` for the up arrow.
+     * The left stick acts as the D-pad. Only gamepads with the standard layout are supported,
+     * and only the focused player reacts to them.
+     *
+     * @example { "south": 32, "d-pad-up": 38, "d-pad-down": 40 }
+     * @default {}
+     */
+    gamepadButtonMapping?: Partial<Record<GamepadButtonName, number>>;
+
+    /**
+     * Keys that act as other keys, for games whose controls clash with browser shortcuts
+     * or are hard to reach on some keyboards.
+     *
+     * Both sides are Flash key codes: each key that is pressed is seen by the movie as the key it maps to.
+     * Typing into text fields is unaffected.
+     *
+     * @example { 87: 38, 65: 37, 83: 40, 68: 39 }
+     * @default {}
+     */
+    keyRemap?: Record<number, number>;
+
+    /**
+     * How fast the right stick of a gamepad moves the mouse, in pixels per second.
+     *
+     * `0` leaves the mouse alone.
+     *
+     * @default 0
+     */
+    gamepadMouseSpeed?: number;
+
+    /**
+     * Whether movies may ask to use the user's cameras, through `flash.media.Camera`.
+     *
+     * The browser still asks the user for permission before a movie can see anything.
+     *
+     * @default true
+     */
+    allowCamera?: boolean;
+
+    /**
+     * Whether movies may ask to use the user's microphones, through `flash.media.Microphone`.
+     *
+     * The browser still asks the user for permission before a movie can hear anything.
+     *
+     * @default true
+     */
+    allowMicrophone?: boolean;
+
+    /**
+     * Items to add to the right-click menu, after the items defined by the movie.
+     *
+     * When one is clicked, the player element dispatches a `contextmenuitem` event,
+     * with the item's `callbackId` in its `detail`.
+     *
+     * @default []
+     */
+    contextMenuItems?: Array<CustomContextMenuItem>;
+
+    /**
+     * Whether to lower the rendering quality while the movie can't keep up with its frame rate.
+     *
+     * The quality is lowered a step at a time while frames take too long to run and render,
+     * and restored once there's time to spare again. Movies still see the quality they set.
+     *
+     * @default false
+     */
+    adaptiveQuality?: boolean;
+
+    /**
+     * Whether to mirror the text and buttons on the stage into the page, for screen readers.
+     *
+     * The mirror is hidden, and follows the movie as it plays. Buttons in it can be
+     * activated, which clicks them in the movie. Movies see this as `Accessibility.active`,
+     * and can describe their objects with `accessibilityProperties` or `_accProps`.
+     *
+     * @default false
+     */
+    accessibility?: boolean;
+
+    /**
+     * What the player does while the page is hidden, such as in a background tab.
+     *
+     * Browsers stop animation frames in hidden pages, so the player can only keep
+     * running on timers, which they may slow down even further.
+     *
+     * @default BackgroundBehavior.Pause
+     */
+    backgroundBehavior?: BackgroundBehavior;
+
+    /**
+     * Whether device fonts may be read from the system, with the Local Font Access API.
+     *
+     * Text that asks for a font such as "Verdana" is then shown with that font when
+     * it's installed, instead of the bundled fonts or `fontSources`.
+     * Browsers only allow this once the user granted the "local-fonts" permission,
+     * and may ask for it when the movie is loaded.
+     *
+     * @default false
+     */
+    allowLocalFontAccess?: boolean;
+
+    /**
+     * Whether movies only start once they're completely loaded, rather than as they
+     * stream in.
+     *
+     * This is for movies that assume they're fully loaded on their first frame.
+     * The player is paused until every downloaded movie has been fully parsed, so
+     * preload animations are skipped. Movies loaded by the root movie are downloaded
+     * in full either way; only their parsing holds the player.
+     *
+     * @default false
+     */
+    waitForFullLoad?: boolean;
+
+    /**
+     * Restricts where the movie may load SWFs and data from.
+     *
+     * Entries are hosts (`example.com`), hosts along with their subdomains
+     * (`*.example.com`), or origins (`https://example.com:8080`).
+     * The origin of the movie itself is always allowed.
+     *
+     * Blocked loads fail as they would in Flash Player, with a `securityError` event
+     * for ActionScript 3 movies. This is enforced by Ruffle itself, so it also applies
+     * to loads that the browser would allow.
+     *
+     * When `null`, the movie may load from anywhere that the browser allows.
+     *
+     * @default null
+     */
+    loadAllowList?: Array<string> | null;
+
+    /**
+     * Whether cross-domain policy files are checked before the movie reads data from
+     * other domains, or connects sockets to them.
+     *
+     * The policy files are fetched from the other domain, at `/crossdomain.xml` or
+     * any location given to `Security.loadPolicyFile()`. Socket policies are
+     * requested on port 843, unless another `xmlsocket://` location was given.
+     *
+     * Loading SWFs and images for display isn't affected.
+     *
+     * @default PolicyFileMode.Ignore
+     */
+    policyFileMode?: PolicyFileMode;
+
+    /**
+     * A URL that Adobe's signed runtime shared libraries are loaded from, instead of
+     * Adobe's servers, laid out like `https://fpdownload.adobe.com/pub/swz/`.
+     *
+     * Movies with TLF text, made with Flash CS5 and later, load the Text Layout
+     * Framework from there before showing their content. Browsers don't allow
+     * reading from Adobe's servers, so without a mirror these movies only work
+     * when a copy of the library is next to them.
+     *
+     * @example "https://example.com/swz/"
+     * @default null
+     */
+    rslMirror?: string | null;
+
+    /**
+     * Overrides what the movie is told about the player and the system it runs on.
+     *
+     * Some content only runs on specific versions of Flash Player or on specific
+     * platforms, and checks `Capabilities` to refuse to run elsewhere.
+     * Anything not given is reported as usual.
+     *
+     * @example { "version": "WIN 10,0,0,0", "os": "Windows XP" }
+     * @default {}
+     */
+    capabilities?: Capabilities;
+
+    /**
+     * A URL whose query string is read into parameters for the movie,
+     * such as `window.location.href` to pass the page's own query string on.
+     *
+     * The query string is parsed the way Flash Player parsed it, including its
+     * handling of `+`, escapes and `&amp;`. These parameters come before
+     * {@link parameters}, which take precedence.
+     *
+     * @default null
+     */
+    parametersFromUrl?: string | null;
+
+    /**
+     * A function that may answer the requests made by the movie, instead of the network.
+     *
+     * It's called with the `Request` that Ruffle would fetch, and returns a `Response`,
+     * or a promise of one, for example to serve assets from an archive or to sign URLs.
+     * Returning `null` or `undefined` lets the request go to the network as usual,
+     * so the handler shouldn't read the request's body unless it answers it.
+     *
+     * @default null
+     */
+    fetchHandler?:
+        | ((
+              request: Request,
+          ) =>
+              | Response
+              | null
+              | undefined
+              | Promise<Response | null | undefined>)
+        | null;
 }
 
 /**
@@ -698,3 +1226,19 @@ export interface DataLoadOptions extends BaseLoadOptions {
      */
     swfFileName?: string;
 }
+
+/**
+ * Options to load a zipped game, such as a Ruffle bundle (`.ruf`)
+ * or a `.zip` of a folder holding a single SWF and the files it loads.
+ *
+ * The files of the zip are available to the movie under `file:///`,
+ * and relative URLs are resolved against the movie.
+ * A Ruffle bundle exposes its `content/` folder, and names the movie to play
+ * in its `ruffle-bundle.toml`.
+ */
+export interface BundleLoadOptions extends BaseLoadOptions {
+    /**
+     * The URL to download the zip from, or its data.
+     */
+    bundle: string | ArrayLike<number> | ArrayBufferLike;
+}
@@ -14,4 +14,16 @@ export interface FlashAPI {
      * @returns a value from 0 to 100, inclusive.
      */
     PercentLoaded(): number;
+
+    /**
+     * Calls a callback that the movie registered with `ExternalInterface.addCallback()`.
+     *
+     * Both the request and the result use Flash's XML encoding of values, such as
+     * `<invoke name="callback" returntype="xml"><arguments><string>hi</string></arguments></invoke>`.
+     * Prefer calling the callback directly, as a method of this element.
+     *
+     * @param request The `<invoke>` element naming the callback and its arguments.
+     * @returns The value returned by the callback, encoded as XML.
+     */
+    CallFunction(request: string): string;
 }
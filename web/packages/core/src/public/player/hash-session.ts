@@ -0,0 +1,125 @@
+/**
+ * An input event replayed during a hash session.
+ */
+export interface RecordedInput {
+    /**
+     * The frame that this event is delivered before.
+     */
+    frame: number;
+
+    /**
+     * The kind of event.
+     */
+    type:
+        | "keyDown"
+        | "keyUp"
+        | "textInput"
+        | "mouseMove"
+        | "mouseDown"
+        | "mouseUp"
+        | "mouseLeave"
+        | "mouseWheel";
+
+    /**
+     * The key code of a key event.
+     */
+    keyCode?: number;
+
+    /**
+     * The character of a key or text input event.
+     */
+    keyChar?: string;
+
+    /**
+     * The position of a mouse event, in pixels at the resolution of the session.
+     */
+    x?: number;
+    y?: number;
+
+    /**
+     * The button of a mouse event. Defaults to `"left"`.
+     */
+    button?: "left" | "middle" | "right";
+
+    /**
+     * The number of lines scrolled by a `"mouseWheel"` event.
+     */
+    delta?: number;
+}
+
+/**
+ * Options for {@link PlayerV1.runHashSession}.
+ */
+export interface HashSessionOptions {
+    /**
+     * The number of frames to run and hash.
+     */
+    frames: number;
+
+    /**
+     * The resolution that frames are rendered at.
+     */
+    resolution: { width: number; height: number };
+
+    /**
+     * The seed for the movie's random number generator. Defaults to `0`.
+     */
+    seed?: number;
+
+    /**
+     * Whether to also compute perceptual hashes, if the renderer supports it. Defaults to `true`.
+     */
+    perceptual?: boolean;
+
+    /**
+     * Input events to replay, in the order they should be delivered.
+     */
+    inputRecording?: RecordedInput[];
+}
+
+/**
+ * The hashes of a single frame.
+ */
+export interface FrameHash {
+    frame: number;
+
+    /**
+     * A hash of what was drawn, independent of the renderer.
+     * Identical across machines and renderers for the same Ruffle version.
+     */
+    exact: string;
+
+    /**
+     * A hash of how the frame looks, which tolerates small rendering differences.
+     * Only comparable between sessions that used the same renderer.
+     */
+    perceptual: string | null;
+
+    /**
+     * How long it took to run this frame, in milliseconds.
+     */
+    runMs: number;
+
+    /**
+     * How long it took to render this frame, in milliseconds.
+     */
+    renderMs: number;
+}
+
+/**
+ * The result of a hash session.
+ */
+export interface HashReport {
+    /**
+     * The version of this report's format.
+     */
+    version: number;
+    ruffleVersion: string;
+    renderer: string;
+    width: number;
+    height: number;
+    seed: number;
+    frameRate: number;
+    frames: FrameHash[];
+    totalMs: number;
+}
@@ -7,8 +7,10 @@
 export * from "./flash";
 export * from "./player-element";
 export * from "./movie-metadata";
+export * from "./renderer-report";
 export * from "./legacy";
 export * from "./v1";
+export * from "./hash-session";
 
 /**
  * Describes the loading state of an SWF movie.
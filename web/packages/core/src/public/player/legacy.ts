@@ -1,4 +1,8 @@
-import { DataLoadOptions, URLLoadOptions } from "../config";
+import {
+    BundleLoadOptions,
+    DataLoadOptions,
+    URLLoadOptions,
+} from "../config";
 import { MovieMetadata } from "./movie-metadata";
 
 import { ReadyState } from "./index";
@@ -39,7 +43,7 @@ export interface LegacyRuffleAPI {
      * This method may be replaced by Flash and is not guaranteed to exist.
      * A direct replacement is {@link PlayerV1.config}
      */
-    config: URLLoadOptions | DataLoadOptions | object;
+    config: URLLoadOptions | DataLoadOptions | BundleLoadOptions | object;
 
     /**
      * The effective config loaded with the last call to `load()`.
@@ -49,7 +53,11 @@ export interface LegacyRuffleAPI {
      * This method may be replaced by Flash and is not guaranteed to exist.
      * A direct replacement is {@link PlayerV1.loadedConfig}
      */
-    readonly loadedConfig: URLLoadOptions | DataLoadOptions | null;
+    readonly loadedConfig:
+        | URLLoadOptions
+        | DataLoadOptions
+        | BundleLoadOptions
+        | null;
 
     /**
      * Indicates the readiness of the playing movie.
@@ -93,6 +101,7 @@ export interface LegacyRuffleAPI {
      * - A URL, passed as a string, which will load a URL with default options.
      * - A {@link URLLoadOptions} object, to load a URL with options.
      * - A {@link DataLoadOptions} object, to load data with options.
+     * - A {@link BundleLoadOptions} object, to load a zipped game with options.
      * The options, if provided, must only contain values provided for this specific movie.
      * They must not contain any default values, since those would overwrite other configuration
      * settings with a lower priority (e.g. the general RufflePlayer config).
@@ -103,7 +112,9 @@ export interface LegacyRuffleAPI {
      * This method may be replaced by Flash and is not guaranteed to exist.
      * A direct replacement is {@link PlayerV1.load}
      */
-    load(options: string | URLLoadOptions | DataLoadOptions): Promise<void>;
+    load(
+        options: string | URLLoadOptions | DataLoadOptions | BundleLoadOptions,
+    ): Promise<void>;
 
     /**
      * Resumes the movie from suspension.
@@ -38,8 +38,25 @@ export interface MovieMetadata {
      */
     readonly isActionScript3: boolean;
 
+    /**
+     * The version of the ActionScript virtual machine that runs this movie:
+     * 1 for ActionScript 1.0 and 2.0, or 2 for ActionScript 3.0.
+     */
+    readonly avmVersion: 1 | 2;
+
+    /**
+     * How the movie file is compressed.
+     */
+    readonly compression: "none" | "zlib" | "lzma";
+
     /**
      * Uncompressed length in bytes.
      */
     readonly uncompressedLength: number;
+
+    /**
+     * The XMP metadata of the movie as RDF/XML, such as its title and author.
+     * May be `null` if the movie has no metadata.
+     */
+    readonly xmpMetadata: string | null;
 }
@@ -0,0 +1,34 @@
+/**
+ * Which renderer a player ended up with, as given by the `renderercreated` event.
+ */
+export interface RendererReport {
+    /**
+     * The name of the renderer, such as `"webgl"` or `"wgpu-webgl"`.
+     */
+    readonly renderer: string;
+
+    /**
+     * A description of the renderer, including the adapter and driver it runs on.
+     */
+    readonly debugInfo: string;
+
+    /**
+     * The renderers that were tried before this one, in order, and why they weren't used.
+     */
+    readonly failures: RendererFailure[];
+}
+
+/**
+ * A renderer that couldn't be used.
+ */
+export interface RendererFailure {
+    /**
+     * The name of the renderer.
+     */
+    readonly renderer: string;
+
+    /**
+     * Why the renderer couldn't be used.
+     */
+    readonly error: string;
+}
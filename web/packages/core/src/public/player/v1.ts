@@ -1,7 +1,12 @@
 import { MovieMetadata } from "./movie-metadata";
-import { DataLoadOptions, URLLoadOptions } from "../config";
+import {
+    BundleLoadOptions,
+    DataLoadOptions,
+    URLLoadOptions,
+} from "../config";
 
 import { ReadyState } from "./index";
+import type { HashReport, HashSessionOptions } from "./hash-session";
 
 export interface PlayerV1 {
     /**
@@ -28,13 +33,17 @@ export interface PlayerV1 {
      * Any configuration that should apply to this specific player.
      * This will be defaulted with any global configuration.
      */
-    config: URLLoadOptions | DataLoadOptions | object;
+    config: URLLoadOptions | DataLoadOptions | BundleLoadOptions | object;
 
     /**
      * The effective config loaded with the last call to `load()`.
      * If no such call has been made, this will be `null`.
      */
-    readonly loadedConfig: URLLoadOptions | DataLoadOptions | null;
+    readonly loadedConfig:
+        | URLLoadOptions
+        | DataLoadOptions
+        | BundleLoadOptions
+        | null;
 
     /**
      * Indicates the readiness of the playing movie.
@@ -68,6 +77,7 @@ export interface PlayerV1 {
      * - A URL, passed as a string, which will load a URL with default options.
      * - A {@link URLLoadOptions} object, to load a URL with options.
      * - A {@link DataLoadOptions} object, to load data with options.
+     * - A {@link BundleLoadOptions} object, to load a zipped game with options.
      * The options, if provided, must only contain values provided for this specific movie.
      * They must not contain any default values, since those would overwrite other configuration
      * settings with a lower priority (e.g. the general RufflePlayer config).
@@ -75,7 +85,9 @@ export interface PlayerV1 {
      * The options will be defaulted by the {@link config} field, which itself
      * is defaulted by a global `window.RufflePlayer.config`.
      */
-    load(options: string | URLLoadOptions | DataLoadOptions): Promise<void>;
+    load(
+        options: string | URLLoadOptions | DataLoadOptions | BundleLoadOptions,
+    ): Promise<void>;
 
     /**
      * Returns the master volume of the player.
@@ -157,6 +169,26 @@ export interface PlayerV1 {
      */
     resume(): void;
 
+    /**
+     * Plays the given number of frames, then suspends the movie.
+     *
+     * Frames run at the movie's frame rate, along with its timers and videos. Sound stays paused.
+     *
+     * @param frames The number of frames to play. Defaults to `1`.
+     * @see {@link suspended} to check if the stepping has finished
+     */
+    stepFrame(frames?: number): void;
+
+    /**
+     * Moves the movie to the given frame, as `gotoAndPlay` would if the movie is playing,
+     * or `gotoAndStop` if it isn't.
+     *
+     * @param frame The 1-based frame number.
+     * @param scene The scene that `frame` is counted from. Defaults to the start of the movie.
+     * @throws If no movie is loaded, or the scene or frame doesn't exist.
+     */
+    gotoFrame(frame: number, scene?: string): void;
+
     /**
      * Sets a trace observer on this flash player.
      *
@@ -189,4 +221,131 @@ export interface PlayerV1 {
      * @returns Any value returned by the callback.
      */
     callExternalInterface(name: string, ...args: unknown[]): unknown;
+
+    /**
+     * Sets the element that additional windows opened by the movie (AIR `NativeWindow`s) are shown in.
+     *
+     * Each window is placed in this element as an absolutely positioned frame with its own canvas.
+     * Until a container is set, such windows are created but not shown.
+     *
+     * @param container The element to place windows in, or `null` to hide them.
+     */
+    setAuxWindowContainer(container: HTMLElement | null): void;
+
+    /**
+     * Captures the movie as it currently looks, at the size of the player, as a PNG image.
+     *
+     * The capture is always opaque, even if the movie is transparent.
+     *
+     * @returns The PNG image.
+     * @throws If no movie is loaded, or the frame couldn't be rendered.
+     */
+    captureFrame(): Blob;
+
+    /**
+     * Starts recording how much time is spent in each ActionScript function.
+     *
+     * Any profile already being recorded is discarded.
+     */
+    startProfiling(): void;
+
+    /**
+     * Stops recording the profile started with {@link startProfiling}.
+     *
+     * The profile is in the speedscope format, and can be viewed as a flame graph at
+     * https://www.speedscope.app.
+     *
+     * @returns The profile as JSON, or `null` if no profile was being recorded.
+     */
+    stopProfiling(): string | null;
+
+    /**
+     * Starts recording the movie's input into a replay.
+     *
+     * While recording, random numbers and the time seen by the movie are made reproducible,
+     * so that the replay plays back exactly. This should be called before the movie starts
+     * playing, as a replay always plays back from the first frame.
+     *
+     * @param seed The seed for the movie's random numbers. Defaults to the current time.
+     */
+    startReplayRecording(seed?: number): void;
+
+    /**
+     * Plays back a replay recorded with {@link startReplayRecording}.
+     *
+     * Input from the user is ignored until the replay ends. Like recording, this should be
+     * called before the movie starts playing.
+     *
+     * @param replay The replay, as returned by {@link stopReplay}.
+     * Throws if the replay is invalid or was made by an incompatible version of Ruffle.
+     */
+    startReplay(replay: string): void;
+
+    /**
+     * Stops recording or playing back a replay.
+     *
+     * @returns The replay as JSON, or `null` if none was being recorded or played back.
+     */
+    stopReplay(): string | null;
+
+    /**
+     * Saves the state of the movie, to be loaded again with {@link loadState}.
+     *
+     * A save state is not a snapshot of the player, but the replay of the movie up to now,
+     * so one can only be saved while a replay is being recorded or played back,
+     * see {@link startReplayRecording}.
+     *
+     * @returns The save state as JSON, or `null` if no replay is being recorded or played back.
+     */
+    saveState(): string | null;
+
+    /**
+     * Loads a save state made with {@link saveState}.
+     *
+     * The movie is replayed from the start up to the save state, as fast as possible, so
+     * loading takes longer the longer the movie had been running. Input is recorded again
+     * once it has caught up, so that the movie can be saved again. Like replays, this should be called
+     * before the movie starts playing.
+     *
+     * @param state The save state, as returned by {@link saveState}.
+     * Throws if the save state is invalid or was made by an incompatible version of Ruffle.
+     */
+    loadState(state: string): void;
+
+    /**
+     * Enables or disables low-latency input while the movie is running,
+     * like the `lowLatencyInput` option does when the movie is loaded.
+     *
+     * @param enabled Whether to enable low-latency input.
+     * @param force Whether to keep it enabled for ActionScript 3 movies,
+     * like the `forceLowLatencyInput` option.
+     * Throws if it is enabled for an ActionScript 3 movie without being forced.
+     */
+    setLowLatencyInput(enabled: boolean, force?: boolean): void;
+
+    /**
+     * Frees memory that Ruffle can recreate later, such as the decoded images of the movie.
+     *
+     * Browsers don't tell pages when memory is running low, so a page that knows it is
+     * (for example from a failed allocation, or a hint from a native app embedding it)
+     * can call this to make room. Images are decoded again the next time they're drawn.
+     */
+    lowMemory(): void;
+
+    /**
+     * Plays the movie deterministically and hashes every rendered frame.
+     *
+     * This is meant for archives, to find movies whose rendering changed between versions of Ruffle.
+     * Normal playback is paused while the session runs, and continues afterwards.
+     *
+     * @param options The number of frames, resolution, random seed and inputs to use.
+     * @returns The versioned report with the hashes of each frame.
+     * Rejects if the options are invalid, another session is running, or the session is cancelled.
+     */
+    runHashSession(options: HashSessionOptions): Promise<HashReport>;
+
+    /**
+     * Cancels the running hash session, if any.
+     */
+    cancelHashSession(): void;
 }
@@ -1,7 +1,11 @@
 import { Version } from "../../version";
 import { VersionRange } from "../../version-range";
 import { SourceAPI } from "./source-api";
-import type { DataLoadOptions, URLLoadOptions } from "../config";
+import type {
+    BundleLoadOptions,
+    DataLoadOptions,
+    URLLoadOptions,
+} from "../config";
 
 declare global {
     interface Window {
@@ -22,7 +26,7 @@ declare global {
  * It needs to be forwards compatible and convertible into a modern day {@link PublicAPI}.
  */
 export interface PublicAPILike {
-    config?: DataLoadOptions | URLLoadOptions | object;
+    config?: DataLoadOptions | URLLoadOptions | BundleLoadOptions | object;
     sources?: Record<string, SourceAPI>;
     invoked?: boolean;
     newestName?: string | null;
@@ -45,7 +49,7 @@ export class PublicAPI implements PublicAPILike {
     /**
      * The configuration object used when Ruffle is instantiated.
      */
-    config: DataLoadOptions | URLLoadOptions | object;
+    config: DataLoadOptions | URLLoadOptions | BundleLoadOptions | object;
     sources: Record<string, SourceAPI>;
     invoked: boolean;
     newestName: string | null;
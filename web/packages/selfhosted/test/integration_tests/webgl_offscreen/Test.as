@@ -0,0 +1,67 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.StageQuality;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		private var cached: Shape;
+		private var frames: int = 0;
+
+		public function Test() {
+			cached = new Shape();
+			cached.graphics.beginFill(0xFF0000);
+			cached.graphics.drawRect(10, 10, 40, 40);
+			cached.graphics.endFill();
+			cached.cacheAsBitmap = true;
+			addChild(cached);
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+			// Magic phrase is expected by "playAndMonitor", tells the test that it's ready
+			trace("Hello from Flash!");
+		}
+
+		function onEnterFrame(event: Event) {
+			// Give the bitmap cache a couple of frames to be rendered.
+			frames++;
+			if (frames < 3) {
+				return;
+			}
+			removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+			var stageCopy: BitmapData = new BitmapData(60, 60, true, 0);
+			stageCopy.draw(this);
+			trace("cached inside: " + stageCopy.getPixel32(30, 30).toString(16));
+			trace("cached outside: " + stageCopy.getPixel32(55, 55).toString(16));
+
+			trace("low quality partial pixels: " + partialPixels(StageQuality.LOW));
+			trace("high quality has partial pixels: " + (partialPixels(StageQuality.HIGH) > 0));
+		}
+
+		// Counts the pixels on the edge of a triangle that are neither transparent nor opaque.
+		function partialPixels(quality: String): int {
+			var triangle: Shape = new Shape();
+			triangle.graphics.beginFill(0x000000);
+			triangle.graphics.moveTo(0, 0);
+			triangle.graphics.lineTo(40, 0);
+			triangle.graphics.lineTo(0, 40);
+			triangle.graphics.endFill();
+
+			var bitmap: BitmapData = new BitmapData(40, 40, true, 0);
+			bitmap.drawWithQuality(triangle, null, null, null, null, false, quality);
+
+			var count: int = 0;
+			for (var y: int = 0; y < 40; y++) {
+				for (var x: int = 0; x < 40; x++) {
+					var alpha: uint = bitmap.getPixel32(x, y) >>> 24;
+					if (alpha != 0 && alpha != 0xFF) {
+						count++;
+					}
+				}
+			}
+			return count;
+		}
+	}
+}
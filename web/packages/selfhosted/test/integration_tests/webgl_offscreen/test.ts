@@ -0,0 +1,24 @@
+import { loadJsAPI, getTraceOutput } from "../../utils.js";
+import { Config } from "ruffle-core";
+import { expect, use } from "chai";
+import chaiHtml from "chai-html";
+
+use(chaiHtml);
+
+describe("Offscreen rendering works with WebGL", () => {
+    loadJsAPI("/test/integration_tests/webgl_offscreen/test.swf", {
+        preferredRenderer: "webgl" as Config.RenderBackend,
+    });
+
+    it("renders bitmap caches and draws at the requested quality", async () => {
+        const player = await browser.$("<ruffle-player>");
+        const actualOutput = await getTraceOutput(browser, player);
+        expect(actualOutput).to.eql(
+            `cached inside: ffff0000
+cached outside: 0
+low quality partial pixels: 0
+high quality has partial pixels: true
+`,
+        );
+    });
+});
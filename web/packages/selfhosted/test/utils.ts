@@ -1,5 +1,5 @@
 import { expect } from "chai";
-import { Player, Setup } from "ruffle-core";
+import { Config, Player, Setup } from "ruffle-core";
 
 declare global {
     interface Window {
@@ -175,8 +175,16 @@ export async function openTest(
     await browser.url(`http://localhost:4567/test/${directory}/${filename}`);
 }
 
-/** Test set-up for JS API testing. */
-export function loadJsAPI(swf?: string) {
+/**
+ * Test set-up for JS API testing.
+ *
+ * @param swf The movie to load, if any.
+ * @param options Any options to load the movie with.
+ */
+export function loadJsAPI(
+    swf?: string,
+    options: Config.BaseLoadOptions = {},
+) {
     let player = null;
 
     before("Loads the test", async () => {
@@ -195,14 +203,15 @@ export function loadJsAPI(swf?: string) {
 
         if (swf) {
             await browser.execute(
-                async (player, swf) => {
+                async (player, swf, options) => {
                     // https://github.com/webdriverio/webdriverio/issues/6486
                     await (player as unknown as Player.PlayerElement)
                         .ruffle()
-                        .load(swf);
+                        .load({ ...options, url: swf });
                 },
                 player,
                 swf,
+                options,
             );
             await playAndMonitor(browser, player);
         }
@@ -0,0 +1,521 @@
+//! AAC decoding with the browser's WebCodecs `AudioDecoder`.
+//!
+//! Symphonia, which decodes AAC otherwise, only supports AAC-LC, while browsers also decode
+//! HE-AAC. The browser's decoder lives on the main thread, and the mixer's decoders must be
+//! `Send`, so the two only share queues: `AacDecoder` queues the packets it reads from the
+//! stream, and `AacDecoders::pump` passes them to the browser and queues the decoded samples.
+
+use js_sys::{Float32Array, Reflect, Uint8Array};
+use ruffle_core::backend::audio::decoders::{Decoder, ExternalSubstreamDecoder};
+use ruffle_core::backend::audio::swf::AudioCompression;
+use ruffle_core::backend::audio::SoundStreamInfo;
+use ruffle_core::buffer::{Substream, SubstreamChunksIter};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    AudioData, AudioDataCopyToOptions, AudioDecoder, AudioDecoderConfig, AudioDecoderInit,
+    AudioSampleFormat, EncodedAudioChunk, EncodedAudioChunkInit, EncodedAudioChunkType,
+};
+
+/// The FLV `AACPacketType` of a sequence header, which holds the `AudioSpecificConfig`.
+const AAC_SEQUENCE_HEADER: u8 = 0;
+
+/// The FLV `AACPacketType` of raw AAC frame data.
+const AAC_RAW: u8 = 1;
+
+/// The object types that the browser is asked about: AAC-LC, HE-AAC and HE-AAC v2.
+const OBJECT_TYPES: [u8; 3] = [2, 5, 29];
+
+/// The number of sample frames in an AAC frame.
+const FRAME_LENGTH: f64 = 1024.0;
+
+/// The sample rates that an `AudioSpecificConfig` indexes.
+const SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+/// Whether this browser has a WebCodecs `AudioDecoder` at all.
+fn is_available() -> bool {
+    Reflect::has(&js_sys::global(), &"AudioDecoder".into()).unwrap_or(false)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().expect("AAC decoder lock poisoned")
+}
+
+/// The parts of an `AudioSpecificConfig` that a decoder is configured with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct AudioSpecificConfig {
+    object_type: u8,
+
+    /// The output sample rate, which HE-AAC doubles from the rate of the core AAC-LC stream.
+    sample_rate: u32,
+
+    num_channels: u8,
+}
+
+impl AudioSpecificConfig {
+    /// Parses the start of an `AudioSpecificConfig`, or returns `None` if it isn't mono or
+    /// stereo audio.
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = BitReader { data, position: 0 };
+        let object_type = reader.object_type()?;
+        let mut sample_rate = reader.sample_rate()?;
+        let num_channels = reader.read(4)? as u8;
+        if object_type == 5 || object_type == 29 {
+            // With explicit signalling, the rate of the SBR output follows.
+            sample_rate = reader.sample_rate()?;
+        }
+        if !(1..=2).contains(&num_channels) {
+            return None;
+        }
+
+        Some(Self {
+            object_type,
+            sample_rate,
+            num_channels,
+        })
+    }
+
+    /// The codec string that WebCodecs identifies the stream by.
+    fn codec(&self) -> String {
+        format!("mp4a.40.{}", self.object_type)
+    }
+
+    fn to_decoder_config(self, description: &[u8]) -> AudioDecoderConfig {
+        let config =
+            AudioDecoderConfig::new(&self.codec(), self.num_channels.into(), self.sample_rate);
+        config.set_description(&Uint8Array::from(description));
+        config
+    }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, bits: usize) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..bits {
+            let byte = self.data.get(self.position / 8)?;
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.position += 1;
+        }
+        Some(value)
+    }
+
+    fn object_type(&mut self) -> Option<u8> {
+        match self.read(5)? {
+            31 => Some(32 + self.read(6)? as u8),
+            object_type => Some(object_type as u8),
+        }
+    }
+
+    fn sample_rate(&mut self) -> Option<u32> {
+        match self.read(4)? {
+            15 => self.read(24),
+            index => SAMPLE_RATES.get(index as usize).copied(),
+        }
+    }
+}
+
+enum Packet {
+    Configure(Vec<u8>),
+    Decode(Vec<u8>),
+}
+
+/// The queues shared by an `AacDecoder` and its `BrowserDecoder`.
+#[derive(Default)]
+struct Shared {
+    /// Packets read from the stream that weren't passed to the browser yet.
+    packets: VecDeque<Packet>,
+
+    /// Sample frames decoded by the browser that weren't mixed yet.
+    samples: VecDeque<[i16; 2]>,
+
+    /// How many packets the browser is still decoding.
+    pending: u32,
+
+    /// Whether the browser failed to decode the stream.
+    failed: bool,
+}
+
+/// The mixer's side of a stream that the browser decodes.
+struct AacDecoder {
+    chunks: SubstreamChunksIter,
+    shared: Arc<Mutex<Shared>>,
+    config: AudioSpecificConfig,
+
+    /// Decoded sample frames taken from `shared`, which is only locked once these run out.
+    samples: VecDeque<[i16; 2]>,
+
+    /// The object types that the browser decodes, which this stream's is removed from if the
+    /// browser fails to decode it.
+    supported: Arc<Mutex<Vec<u8>>>,
+}
+
+impl AacDecoder {
+    /// Queues the packets that were appended to the stream since the last call.
+    fn read_packets(&mut self, shared: &mut Shared) {
+        for chunk in &mut self.chunks {
+            let data = chunk.data();
+            match data.split_first() {
+                Some((&AAC_SEQUENCE_HEADER, config)) => {
+                    shared.packets.push_back(Packet::Configure(config.to_vec()))
+                }
+                Some((&AAC_RAW, frame)) => shared.packets.push_back(Packet::Decode(frame.to_vec())),
+                Some((packet_type, _)) => {
+                    tracing::error!("AacDecoder: unknown chunk type {}", packet_type)
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+impl Decoder for AacDecoder {
+    fn num_channels(&self) -> u8 {
+        self.config.num_channels
+    }
+
+    fn sample_rate(&self) -> u16 {
+        self.config.sample_rate as u16
+    }
+}
+
+impl Iterator for AacDecoder {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(frame) = self.samples.pop_front() {
+            return Some(frame);
+        }
+
+        let shared = self.shared.clone();
+        let mut shared = lock(&shared);
+        self.read_packets(&mut shared);
+
+        std::mem::swap(&mut self.samples, &mut shared.samples);
+        if let Some(frame) = self.samples.pop_front() {
+            return Some(frame);
+        }
+        if shared.failed {
+            // Later streams with this object type are decoded by Symphonia instead.
+            lock(&self.supported).retain(|t| *t != self.config.object_type);
+            return None;
+        }
+        if shared.pending > 0 || !shared.packets.is_empty() {
+            // The browser is still decoding, so the stream isn't over yet.
+            return Some([0, 0]);
+        }
+        None
+    }
+}
+
+/// The browser's side of a stream.
+struct BrowserDecoder {
+    shared: Weak<Mutex<Shared>>,
+    decoder: AudioDecoder,
+    config: AudioSpecificConfig,
+
+    /// The timestamp of the next packet, in microseconds.
+    timestamp: f64,
+
+    // Kept alive for as long as the decoder can call them.
+    _on_output: Closure<dyn FnMut(AudioData)>,
+    _on_error: Closure<dyn FnMut(JsValue)>,
+}
+
+impl BrowserDecoder {
+    fn new(shared: Weak<Mutex<Shared>>, config: AudioSpecificConfig) -> Result<Self, JsValue> {
+        let on_output = {
+            let shared = shared.clone();
+            Closure::<dyn FnMut(_)>::new(move |data: AudioData| {
+                let frames = read_audio_data(&data, config.sample_rate);
+                data.close();
+                let Some(shared) = shared.upgrade() else {
+                    return;
+                };
+                let mut shared = lock(&shared);
+                shared.pending = shared.pending.saturating_sub(1);
+                match frames {
+                    Ok(frames) => shared.samples.extend(frames),
+                    Err(e) => {
+                        tracing::error!("Couldn't read back decoded AAC: {:?}", e);
+                        shared.failed = true;
+                    }
+                }
+            })
+        };
+        let on_error = {
+            let shared = shared.clone();
+            Closure::<dyn FnMut(_)>::new(move |error: JsValue| {
+                tracing::error!("Browser failed to decode AAC: {:?}", error);
+                if let Some(shared) = shared.upgrade() {
+                    // A closed decoder won't output the frames it still had.
+                    let mut shared = lock(&shared);
+                    shared.failed = true;
+                    shared.pending = 0;
+                }
+            })
+        };
+
+        let init = AudioDecoderInit::new(
+            on_error.as_ref().unchecked_ref(),
+            on_output.as_ref().unchecked_ref(),
+        );
+        let decoder = AudioDecoder::new(&init)?;
+
+        Ok(Self {
+            shared,
+            decoder,
+            config,
+            timestamp: 0.0,
+            _on_output: on_output,
+            _on_error: on_error,
+        })
+    }
+
+    /// Passes the queued packets to the browser. Returns whether the stream is still alive.
+    fn pump(&mut self) -> bool {
+        let Some(shared) = self.shared.upgrade() else {
+            return false;
+        };
+        let mut shared = lock(&shared);
+        if shared.failed {
+            return false;
+        }
+
+        while let Some(packet) = shared.packets.pop_front() {
+            let result = match packet {
+                Packet::Configure(description) => match AudioSpecificConfig::parse(&description) {
+                    Some(config) => self
+                        .decoder
+                        .configure(&config.to_decoder_config(&description)),
+                    None => Err("Unsupported AudioSpecificConfig".into()),
+                },
+                Packet::Decode(frame) => self.decode(&frame).map(|()| shared.pending += 1),
+            };
+            if let Err(e) = result {
+                tracing::error!("Browser failed to decode AAC: {:?}", e);
+                shared.failed = true;
+                return false;
+            }
+        }
+        true
+    }
+
+    fn decode(&mut self, frame: &[u8]) -> Result<(), JsValue> {
+        let init = EncodedAudioChunkInit::new(
+            &Uint8Array::from(frame),
+            self.timestamp,
+            EncodedAudioChunkType::Key,
+        );
+        self.decoder.decode(&EncodedAudioChunk::new(&init)?)?;
+        self.timestamp += FRAME_LENGTH * 1_000_000.0 / f64::from(self.config.sample_rate);
+        Ok(())
+    }
+}
+
+impl Drop for BrowserDecoder {
+    fn drop(&mut self) {
+        let _ = self.decoder.close();
+    }
+}
+
+/// Copies decoded audio into stereo sample frames at the given sample rate.
+///
+/// Browsers may output HE-AAC without explicit signalling at twice the rate of the stream's
+/// configuration, which the mixer can't change once a sound plays.
+fn read_audio_data(data: &AudioData, sample_rate: u32) -> Result<Vec<[i16; 2]>, JsValue> {
+    let num_frames = data.number_of_frames();
+    let num_channels = data.number_of_channels().clamp(1, 2);
+    let mut planes = Vec::with_capacity(2);
+    for plane in 0..num_channels {
+        let options = AudioDataCopyToOptions::new(plane);
+        options.set_format(AudioSampleFormat::F32Planar);
+        let buffer = Float32Array::new_with_length(num_frames);
+        data.copy_to_with_buffer_source(&buffer, &options)?;
+        planes.push(buffer.to_vec());
+    }
+    let frames: Vec<[f32; 2]> = (0..num_frames as usize)
+        .map(|i| [planes[0][i], planes[planes.len() - 1][i]])
+        .collect();
+
+    let source_rate = data.sample_rate() as f64;
+    let frames = if frames.is_empty() || source_rate == f64::from(sample_rate) {
+        frames
+    } else {
+        resample(&frames, source_rate / f64::from(sample_rate))
+    };
+    Ok(frames
+        .into_iter()
+        .map(|frame| frame.map(|sample| (sample.clamp(-1.0, 1.0) * 32767.0) as i16))
+        .collect())
+}
+
+/// Linearly resamples frames, reading `step` input frames for every output frame.
+fn resample(frames: &[[f32; 2]], step: f64) -> Vec<[f32; 2]> {
+    let last = frames.len() - 1;
+    let len = (frames.len() as f64 / step).round() as usize;
+    (0..len)
+        .map(|i| {
+            let position = i as f64 * step;
+            let index = (position as usize).min(last);
+            let t = (position - index as f64) as f32;
+            let (a, b) = (frames[index], frames[(index + 1).min(last)]);
+            [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+        })
+        .collect()
+}
+
+/// A stream made by the mixer, waiting for its `BrowserDecoder`.
+type NewStream = (Weak<Mutex<Shared>>, AudioSpecificConfig);
+
+/// Decodes the AAC streams of a player with the browser, where it can.
+pub struct AacDecoders {
+    /// The object types that the browser said it decodes.
+    supported: Arc<Mutex<Vec<u8>>>,
+
+    /// Streams made by the mixer that don't have a `BrowserDecoder` yet.
+    new_streams: Arc<Mutex<Vec<NewStream>>>,
+
+    decoders: Vec<BrowserDecoder>,
+}
+
+impl AacDecoders {
+    pub fn new() -> Self {
+        let supported = Arc::new(Mutex::new(vec![]));
+        if is_available() {
+            for object_type in OBJECT_TYPES {
+                let supported = supported.clone();
+                let config = AudioSpecificConfig {
+                    object_type,
+                    sample_rate: 44100,
+                    num_channels: 2,
+                };
+                let config = AudioDecoderConfig::new(&config.codec(), 2, 44100);
+                let promise = AudioDecoder::is_config_supported(&config);
+                spawn_local(async move {
+                    let Ok(support) = JsFuture::from(promise).await else {
+                        return;
+                    };
+                    let is_supported = Reflect::get(&support, &"supported".into())
+                        .ok()
+                        .and_then(|supported| supported.as_bool())
+                        .unwrap_or(false);
+                    if is_supported {
+                        lock(&supported).push(object_type);
+                    }
+                });
+            }
+        }
+
+        Self {
+            supported,
+            new_streams: Arc::new(Mutex::new(vec![])),
+            decoders: vec![],
+        }
+    }
+
+    /// The decoder to give to `AudioMixer::set_external_substream_decoder`.
+    ///
+    /// Streams are left to Symphonia until the browser answers whether it supports them.
+    pub fn substream_decoder(&self) -> ExternalSubstreamDecoder {
+        let supported = self.supported.clone();
+        let new_streams = self.new_streams.clone();
+        Box::new(
+            move |stream_info: &SoundStreamInfo,
+                  substream: &Substream|
+                  -> Option<Box<dyn Decoder + Send>> {
+                if stream_info.stream_format.compression != AudioCompression::Aac {
+                    return None;
+                }
+                let header = substream.first_chunk()?;
+                let config = match header.data().split_first() {
+                    Some((&AAC_SEQUENCE_HEADER, config)) => AudioSpecificConfig::parse(config)?,
+                    _ => return None,
+                };
+                if !lock(&supported).contains(&config.object_type) {
+                    return None;
+                }
+
+                let shared = Arc::new(Mutex::new(Shared::default()));
+                lock(&new_streams).push((Arc::downgrade(&shared), config));
+                Some(Box::new(AacDecoder {
+                    chunks: substream.iter_chunks(),
+                    shared,
+                    config,
+                    samples: VecDeque::new(),
+                    supported: supported.clone(),
+                }))
+            },
+        )
+    }
+
+    /// Passes the packets queued by the mixer to the browser.
+    pub fn pump(&mut self) {
+        for (shared, config) in lock(&self.new_streams).drain(..) {
+            match BrowserDecoder::new(shared.clone(), config) {
+                Ok(decoder) => self.decoders.push(decoder),
+                Err(e) => {
+                    tracing::error!("Couldn't create AAC decoder: {:?}", e);
+                    if let Some(shared) = shared.upgrade() {
+                        lock(&shared).failed = true;
+                    }
+                }
+            }
+        }
+        self.decoders.retain_mut(BrowserDecoder::pump);
+    }
+}
+
+impl Default for AacDecoders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_audio_specific_configs() {
+        // AAC-LC, 44.1 kHz, stereo.
+        assert_eq!(
+            AudioSpecificConfig::parse(&[0x12, 0x10]),
+            Some(AudioSpecificConfig {
+                object_type: 2,
+                sample_rate: 44100,
+                num_channels: 2,
+            })
+        );
+        // HE-AAC, 22.05 kHz core with explicit 44.1 kHz output, stereo.
+        assert_eq!(
+            AudioSpecificConfig::parse(&[0x2B, 0x92, 0x08, 0x00]),
+            Some(AudioSpecificConfig {
+                object_type: 5,
+                sample_rate: 44100,
+                num_channels: 2,
+            })
+        );
+        // Six channels aren't mixed.
+        assert_eq!(AudioSpecificConfig::parse(&[0x12, 0x30]), None);
+        assert_eq!(AudioSpecificConfig::parse(&[0x12]), None);
+    }
+
+    #[test]
+    fn resamples_to_half_rate() {
+        let frames: Vec<[f32; 2]> = (0..8).map(|i| [i as f32, -(i as f32)]).collect();
+        let resampled = resample(&frames, 2.0);
+        assert_eq!(resampled.len(), 4);
+        assert_eq!(resampled[1], [2.0, -2.0]);
+        assert_eq!(resampled[3], [6.0, -6.0]);
+    }
+}
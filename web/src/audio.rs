@@ -1,3 +1,5 @@
+use crate::aac::AacDecoders;
+use crate::log_adapter::LogSubscriber;
 use ruffle_core::backend::audio::{
     swf, AudioBackend, AudioMixer, AudioMixerProxy, DecodeError, RegisterError, SoundHandle,
     SoundInstanceHandle, SoundStreamInfo, SoundTransform,
@@ -8,32 +10,95 @@ use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing_subscriber::layer::Layered;
-use tracing_subscriber::Registry;
-use tracing_wasm::WASMLayer;
 use wasm_bindgen::prelude::*;
-use web_sys::{AudioContext, AudioScheduledSourceNode};
+use web_sys::{
+    AudioContext, AudioContextOptions, AudioContextState, AudioScheduledSourceNode, GainNode,
+};
+
+thread_local! {
+    /// The audio contexts shared by every player on the page, normal and low-latency.
+    ///
+    /// Browsers only allow a handful of contexts per page, which pages with many
+    /// players would run out of. They are kept open for the lifetime of the page.
+    static SHARED_CONTEXTS: RefCell<[Option<AudioContext>; 2]> = const { RefCell::new([None, None]) };
+}
+
+/// Returns the shared audio context for the given latency, creating it if needed.
+fn shared_context(low_latency: bool) -> Result<AudioContext, JsError> {
+    SHARED_CONTEXTS.with_borrow_mut(|contexts| {
+        let slot = &mut contexts[usize::from(low_latency)];
+        if let Some(context) = slot.as_ref() {
+            if context.state() != AudioContextState::Closed {
+                return Ok(context.clone());
+            }
+        }
+        let context = if low_latency {
+            let options = AudioContextOptions::new();
+            options.set_latency_hint(&JsValue::from_str("interactive"));
+            AudioContext::new_with_context_options(&options).into_js_result()?
+        } else {
+            AudioContext::new().into_js_result()?
+        };
+        *slot = Some(context.clone());
+        Ok(context)
+    })
+}
 
 #[allow(dead_code)]
 pub struct WebAudioBackend {
     mixer: AudioMixer,
     context: AudioContext,
+    /// The node this player's audio is played through, as the context is shared.
+    output: GainNode,
+    /// Whether this player is paused. The shared context keeps running for the others.
+    paused: Rc<Cell<bool>>,
     /// The current length of both buffers, in frames (pairs of left/right samples).
     buffer_size: Rc<Cell<u32>>,
+    /// The bounds that the buffer size is adapted within.
+    buffer_sizes: Rc<Cell<BufferSizes>>,
     buffers: Vec<Rc<RefCell<Buffer>>>,
     /// When the last submitted buffer is expected to play out completely, in seconds.
     time: Rc<Cell<f64>>,
     /// For how many seconds were we able to continuously fill the next buffer "at a sufficiently early time".
     probation_elapsed: Rc<Cell<f32>>,
-    log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+    log_subscriber: Arc<LogSubscriber>,
+    /// Decodes AAC with WebCodecs, which unlike Symphonia supports HE-AAC.
+    aac_decoders: AacDecoders,
+}
+
+/// These govern the adaptive buffer size algorithm, all are in number of frames (pairs of samples).
+/// They must all be integer powers of 2 (due to how the algorithm works).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BufferSizes {
+    initial: u32,
+    min: u32,
+    max: u32,
+}
+
+impl BufferSizes {
+    const NORMAL: Self = Self {
+        initial: 2048, // 46.44 ms at 44.1 kHz
+        min: 1024,     // 23.22 ms at 44.1 kHz
+        max: 16384,    // 371.52 ms at 44.1 kHz
+    };
+
+    /// Used in low-latency mode. A newly started sound is heard after at most two buffers.
+    const LOW_LATENCY: Self = Self {
+        initial: 512, // 11.61 ms at 44.1 kHz
+        min: 256,     // 5.80 ms at 44.1 kHz
+        max: 2048,    // 46.44 ms at 44.1 kHz
+    };
+
+    fn new(low_latency: bool) -> Self {
+        if low_latency {
+            Self::LOW_LATENCY
+        } else {
+            Self::NORMAL
+        }
+    }
 }
 
 impl WebAudioBackend {
-    /// These govern the adaptive buffer size algorithm, all are in number of frames (pairs of samples).
-    /// They must all be integer powers of 2 (due to how the algorithm works).
-    const INITIAL_BUFFER_SIZE: u32 = 2048; // 46.44 ms at 44.1 kHz
-    const MIN_BUFFER_SIZE: u32 = 1024; // 23.22 ms at 44.1 kHz
-    const MAX_BUFFER_SIZE: u32 = 16384; // 371.52 ms at 44.1 kHz
     /// Buffer size will not be increased until this many seconds have elapsed after startup,
     /// to account for any initialization (shape tessellation, WASM JIT, etc.) hitches.
     const WARMUP_PERIOD: f32 = 2.0;
@@ -48,17 +113,29 @@ impl WebAudioBackend {
     /// is increased immediately. Must be in 0..1, and greater than `2 * NORMAL_PROGRESS_RANGE_MIN`.
     const NORMAL_PROGRESS_RANGE_MAX: f64 = 0.75;
 
-    pub fn new(log_subscriber: Arc<Layered<WASMLayer, Registry>>) -> Result<Self, JsError> {
-        let context = AudioContext::new().into_js_result()?;
+    pub fn new(log_subscriber: Arc<LogSubscriber>, low_latency: bool) -> Result<Self, JsError> {
+        let context = shared_context(low_latency)?;
+        let output = context.create_gain().into_js_result()?;
+        output
+            .connect_with_audio_node(&context.destination())
+            .into_js_result()?;
         let sample_rate = context.sample_rate();
+        let buffer_sizes = BufferSizes::new(low_latency);
+        let mut mixer = AudioMixer::new(2, sample_rate as u32);
+        let aac_decoders = AacDecoders::new();
+        mixer.set_external_substream_decoder(aac_decoders.substream_decoder());
         let mut audio = Self {
             context,
-            mixer: AudioMixer::new(2, sample_rate as u32),
-            buffer_size: Rc::new(Cell::new(Self::INITIAL_BUFFER_SIZE)),
+            output,
+            paused: Rc::new(Cell::new(false)),
+            mixer,
+            buffer_size: Rc::new(Cell::new(buffer_sizes.initial)),
+            buffer_sizes: Rc::new(Cell::new(buffer_sizes)),
             buffers: Vec::with_capacity(2),
             time: Rc::new(Cell::new(0.0)),
             probation_elapsed: Rc::new(Cell::new(0.0)),
             log_subscriber,
+            aac_decoders,
         };
 
         // Create and start the audio buffers.
@@ -76,17 +153,30 @@ impl WebAudioBackend {
     pub fn audio_context(&self) -> &AudioContext {
         &self.context
     }
+
+    /// Returns whether the AudioContext is running.
+    ///
+    /// Browsers keep it suspended until the user interacts with the page,
+    /// unless they allow the page to autoplay audio.
+    pub fn is_running(&self) -> bool {
+        self.context.state() == AudioContextState::Running
+    }
 }
 
 impl AudioBackend for WebAudioBackend {
     impl_audio_mixer_backend!(mixer);
 
     fn play(&mut self) {
+        self.paused.set(false);
         let _ = self.context.resume();
     }
 
     fn pause(&mut self) {
-        let _ = self.context.suspend();
+        self.paused.set(true);
+    }
+
+    fn tick(&mut self) {
+        self.aac_decoders.pump();
     }
 
     fn position_resolution(&self) -> Option<Duration> {
@@ -94,38 +184,55 @@ impl AudioBackend for WebAudioBackend {
             f64::from(self.buffer_size.get()) / f64::from(self.context.sample_rate()),
         ))
     }
+
+    fn set_low_latency(&mut self, low_latency: bool) {
+        let sizes = BufferSizes::new(low_latency);
+        self.buffer_sizes.set(sizes);
+        // The buffers are resized the next time they are filled.
+        self.buffer_size
+            .set(self.buffer_size.get().clamp(sizes.min, sizes.max));
+        self.probation_elapsed.set(0.0);
+    }
 }
 
 impl Drop for WebAudioBackend {
     fn drop(&mut self) {
-        let _ = self.context.close();
+        // The context is shared, so only this player's output is disconnected.
+        let _ = self.output.disconnect();
     }
 }
 
 struct Buffer {
     context: AudioContext,
+    output: GainNode,
+    paused: Rc<Cell<bool>>,
     mixer_proxy: AudioMixerProxy,
     buffer_size: Rc<Cell<u32>>,
+    buffer_sizes: Rc<Cell<BufferSizes>>,
     audio_buffer: Vec<f32>,
     js_buffer: web_sys::AudioBuffer,
     audio_node: Option<web_sys::AudioBufferSourceNode>,
     on_ended_handler: Closure<dyn FnMut()>,
     time: Rc<Cell<f64>>,
     probation_elapsed: Rc<Cell<f32>>,
-    log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+    log_subscriber: Arc<LogSubscriber>,
 }
 
 impl Buffer {
     fn new(audio: &WebAudioBackend) -> Result<Rc<RefCell<Self>>, JsError> {
         let sample_rate = audio.context.sample_rate();
+        let buffer_size = audio.buffer_size.get();
         let buffer = Rc::new(RefCell::new(Self {
             context: audio.context.clone(),
+            output: audio.output.clone(),
+            paused: audio.paused.clone(),
             mixer_proxy: audio.mixer.proxy(),
             buffer_size: audio.buffer_size.clone(),
-            audio_buffer: vec![0.0; 2 * WebAudioBackend::INITIAL_BUFFER_SIZE as usize],
+            buffer_sizes: audio.buffer_sizes.clone(),
+            audio_buffer: vec![0.0; 2 * buffer_size as usize],
             js_buffer: audio
                 .context
-                .create_buffer(2, WebAudioBackend::INITIAL_BUFFER_SIZE, sample_rate)
+                .create_buffer(2, buffer_size, sample_rate)
                 .into_js_result()?,
             audio_node: None,
             on_ended_handler: Closure::new(|| {}),
@@ -146,6 +253,7 @@ impl Buffer {
 
     fn play(&mut self) -> Result<(), JsError> {
         let _subscriber = tracing::subscriber::set_default(self.log_subscriber.clone());
+        let sizes = self.buffer_sizes.get();
 
         let time_left = self.time.get() - self.context.current_time();
         let mut buffer_timestep =
@@ -176,7 +284,7 @@ impl Buffer {
                 tracing::debug!("Audio underrun detected!");
             }
             if self.time.get() as f32 > WebAudioBackend::WARMUP_PERIOD {
-                if self.buffer_size.get() < WebAudioBackend::MAX_BUFFER_SIZE {
+                if self.buffer_size.get() < sizes.max {
                     self.buffer_size.set(self.buffer_size.get() * 2);
                     tracing::debug!(
                         "Increased audio buffer size to {} frames",
@@ -196,7 +304,7 @@ impl Buffer {
 
         // If enough quick fills happened, we decrease the buffer size.
         if self.probation_elapsed.get() > WebAudioBackend::PROBATION_LENGTH
-            && self.buffer_size.get() > WebAudioBackend::MIN_BUFFER_SIZE
+            && self.buffer_size.get() > sizes.min
         {
             self.buffer_size.set(self.buffer_size.get() / 2);
             tracing::debug!(
@@ -221,14 +329,19 @@ impl Buffer {
         }
 
         // Mix new audio into the output buffer and copy to JS.
-        self.mixer_proxy.mix(&mut self.audio_buffer);
+        // While paused, silence is played so that sounds don't advance.
+        if self.paused.get() {
+            self.audio_buffer.fill(0.0);
+        } else {
+            self.mixer_proxy.mix(&mut self.audio_buffer);
+        }
         copy_to_audio_buffer_interleaved(&self.js_buffer, &self.audio_buffer);
 
         // Create the audio node to play back the audio buffer.
         let audio_node = self.context.create_buffer_source().into_js_result()?;
         audio_node.set_buffer(Some(&self.js_buffer));
         audio_node
-            .connect_with_audio_node(&self.context.destination())
+            .connect_with_audio_node(&self.output)
             .into_js_result()?;
         let scheduled: &AudioScheduledSourceNode = &audio_node;
         scheduled.set_onended(Some(self.on_ended_handler.as_ref().unchecked_ref()));
@@ -266,3 +379,115 @@ extern "C" {
         interleaved_data: &[f32],
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::backend::audio::swf::{
+        AudioCompression, Sound, SoundEvent, SoundFormat, SoundInfo,
+    };
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    /// Presses a key that starts a sound at `press_time`, and returns how many seconds pass
+    /// before the sound is first heard in the output of the mixer.
+    ///
+    /// The mixer is fed like `Buffer::play` does at a fixed buffer size: buffer `n` plays from
+    /// `n * timestep`. The first two buffers are filled right away, and every other one when
+    /// buffer `n - 2` ends, which is when a sound started before then is first mixed in.
+    fn input_to_sound_latency(buffer_size: u32, press_time: f64) -> f64 {
+        let mut mixer = AudioMixer::new(2, SAMPLE_RATE);
+        let data: Vec<u8> = std::iter::repeat_n(i16::MAX / 2, 2 * SAMPLE_RATE as usize)
+            .flat_map(i16::to_le_bytes)
+            .collect();
+        let sound = mixer
+            .register_sound(&Sound {
+                id: 1,
+                format: SoundFormat {
+                    compression: AudioCompression::Uncompressed,
+                    sample_rate: SAMPLE_RATE as u16,
+                    is_stereo: true,
+                    is_16_bit: true,
+                },
+                num_samples: SAMPLE_RATE,
+                data: &data,
+            })
+            .expect("Sound should be registered");
+
+        let timestep = f64::from(buffer_size) / f64::from(SAMPLE_RATE);
+        let mut output = vec![0.0f32; 2 * buffer_size as usize];
+        let mut pressed = false;
+        for n in 0..1000 {
+            let fill_time = f64::from(n.max(1) - 1) * timestep;
+            if !pressed && press_time <= fill_time {
+                mixer
+                    .start_sound(
+                        sound,
+                        &SoundInfo {
+                            event: SoundEvent::Event,
+                            in_sample: None,
+                            out_sample: None,
+                            num_loops: 1,
+                            envelope: None,
+                        },
+                    )
+                    .expect("Sound should start");
+                pressed = true;
+            }
+
+            output.fill(0.0);
+            mixer.mix(&mut output);
+            if let Some(frame) = output.chunks_exact(2).position(|frame| frame[0] != 0.0) {
+                assert!(pressed, "Nothing should be heard before the key press");
+                let heard_time = f64::from(n) * timestep + frame as f64 / f64::from(SAMPLE_RATE);
+                return heard_time - press_time;
+            }
+        }
+        panic!("The sound was never heard");
+    }
+
+    /// The longest latency between a key press and its sound being heard,
+    /// for key presses injected at pseudo-random times over a second.
+    fn worst_case_latency(buffer_size: u32) -> f64 {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut worst = 0.0f64;
+        for _ in 0..100 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let press_time = (state % 1_000_000) as f64 / 1_000_000.0;
+            let latency = input_to_sound_latency(buffer_size, press_time);
+            assert!(latency > 0.0);
+            worst = worst.max(latency);
+        }
+        worst
+    }
+
+    #[test]
+    fn buffer_sizes_are_valid() {
+        for sizes in [BufferSizes::NORMAL, BufferSizes::LOW_LATENCY] {
+            assert!(sizes.min.is_power_of_two());
+            assert!(sizes.initial.is_power_of_two());
+            assert!(sizes.max.is_power_of_two());
+            assert!(sizes.min <= sizes.initial && sizes.initial <= sizes.max);
+        }
+    }
+
+    #[test]
+    fn low_latency_reduces_input_to_sound_latency() {
+        let normal = worst_case_latency(BufferSizes::NORMAL.initial);
+        let low_latency = worst_case_latency(BufferSizes::LOW_LATENCY.initial);
+
+        // A sound is heard after at most two buffers.
+        let buffer_time = |buffer_size| f64::from(buffer_size) / f64::from(SAMPLE_RATE);
+        assert!(normal <= 2.0 * buffer_time(BufferSizes::NORMAL.initial));
+        assert!(low_latency <= 2.0 * buffer_time(BufferSizes::LOW_LATENCY.initial));
+
+        assert!(low_latency < 0.025, "worst case latency was {low_latency}s");
+        assert!(low_latency * 3.0 < normal);
+
+        // Even after growing to its largest size, low-latency mode is no worse
+        // than the normal mode when it starts out.
+        assert!(worst_case_latency(BufferSizes::LOW_LATENCY.max) <= normal);
+    }
+}
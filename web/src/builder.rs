@@ -1,23 +1,33 @@
 use crate::external_interface::JavascriptInterface;
+use crate::local_fonts::LocalFonts;
+use crate::log_adapter::{JsLogLayer, LogSubscriber};
 use crate::navigator::{OpenUrlMode, WebNavigatorBackend};
 use crate::{
-    audio, log_adapter, storage, ui, JavascriptPlayer, RuffleHandle, SocketProxy,
-    RUFFLE_GLOBAL_PANIC,
+    audio, camera, compatibility_rules, gamepad, local_connection, log_adapter, microphone,
+    parse_movie_parameters, storage, ui, Autoplay, BackgroundBehavior, JavascriptPlayer,
+    RuffleHandle, SocketProxy, SocketProxyProtocol, UnmuteOverlay, RUFFLE_GLOBAL_PANIC,
 };
-use js_sys::Promise;
+use js_sys::{Function, Promise, Uint8Array};
 use ruffle_core::backend::audio::{AudioBackend, NullAudioBackend};
 use ruffle_core::backend::storage::{MemoryStorageBackend, StorageBackend};
 use ruffle_core::backend::ui::FontDefinition;
+use ruffle_core::bitmap::readback::BitmapReadbackMode;
 use ruffle_core::compatibility_rules::CompatibilityRules;
-use ruffle_core::config::{Letterbox, NetworkingAccessMode};
+use ruffle_core::config::{CapabilitiesConfig, Letterbox, NetworkingAccessMode, PolicyFileMode};
+use ruffle_core::events::{GamepadButton, KeyCode};
+use ruffle_core::native_window::DEFAULT_MAX_NATIVE_WINDOWS;
+use ruffle_core::rsl::RslMirror;
+use ruffle_core::sandbox::LoadAllowList;
 use ruffle_core::ttf_parser;
 use ruffle_core::{
-    swf, Color, DefaultFont, Player, PlayerBuilder, PlayerRuntime, StageAlign, StageScaleMode,
+    swf, Color, DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerRuntime, StageAlign,
+    StageScaleMode,
 };
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
-use ruffle_video_software::backend::SoftwareVideoBackend;
+use ruffle_video_external::backend::ExternalVideoBackend;
 use ruffle_web_common::JsResult;
+use serde::Serialize;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::error::Error;
@@ -25,12 +35,16 @@ use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tracing_subscriber::layer::{Layered, SubscriberExt};
-use tracing_subscriber::Registry;
+use tracing_subscriber::layer::SubscriberExt;
 use tracing_wasm::{WASMLayer, WASMLayerConfigBuilder};
 use wasm_bindgen::prelude::*;
 use web_sys::{HtmlCanvasElement, HtmlElement};
 
+mod config;
+mod parameters;
+
+use config::BuilderConfig;
+
 #[wasm_bindgen(inspectable)]
 #[derive(Debug, Clone)]
 pub struct RuffleInstanceBuilder {
@@ -61,6 +75,36 @@ pub struct RuffleInstanceBuilder {
     pub(crate) volume: f32,
     pub(crate) default_fonts: HashMap<DefaultFont, Vec<String>>,
     pub(crate) custom_fonts: Vec<(String, Vec<u8>)>,
+    pub(crate) bitmap_readback_mode: BitmapReadbackMode,
+    pub(crate) max_native_windows: usize,
+    pub(crate) low_latency_input: bool,
+    pub(crate) force_low_latency_input: bool,
+    pub(crate) storage: storage::StorageKind,
+    pub(crate) gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+    pub(crate) key_remap: HashMap<KeyCode, KeyCode>,
+    pub(crate) gamepad_mouse_speed: f64,
+    pub(crate) allow_camera: bool,
+    pub(crate) allow_microphone: bool,
+    pub(crate) context_menu_items: Vec<(String, String)>,
+    pub(crate) fetch_handler: Option<Function>,
+    pub(crate) adaptive_quality: bool,
+    pub(crate) accessibility: bool,
+    pub(crate) log_callback: Option<Function>,
+    pub(crate) background_behavior: BackgroundBehavior,
+    pub(crate) allow_local_font_access: bool,
+    pub(crate) wait_for_full_load: bool,
+    pub(crate) load_allow_list: Option<Vec<String>>,
+    pub(crate) policy_file_mode: PolicyFileMode,
+    pub(crate) rsl_mirror: Option<String>,
+    pub(crate) capabilities: CapabilitiesConfig,
+    pub(crate) url_parameters: Vec<(String, String)>,
+    pub(crate) autoplay: Autoplay,
+    pub(crate) unmute_overlay: UnmuteOverlay,
+    pub(crate) compatibility_rules_url: Option<String>,
+    pub(crate) max_active_players: Option<u32>,
+
+    /// The URL the movie is told it was loaded from, as decided by the compatibility rules.
+    pub(crate) spoofed_url: Option<String>,
 }
 
 impl Default for RuffleInstanceBuilder {
@@ -97,6 +141,34 @@ impl Default for RuffleInstanceBuilder {
             volume: 1.0,
             default_fonts: HashMap::new(),
             custom_fonts: vec![],
+            bitmap_readback_mode: BitmapReadbackMode::Strict,
+            max_native_windows: DEFAULT_MAX_NATIVE_WINDOWS,
+            low_latency_input: false,
+            force_low_latency_input: false,
+            storage: storage::StorageKind::Local,
+            gamepad_button_mapping: HashMap::new(),
+            key_remap: HashMap::new(),
+            gamepad_mouse_speed: 0.0,
+            allow_camera: true,
+            allow_microphone: true,
+            context_menu_items: vec![],
+            fetch_handler: None,
+            adaptive_quality: false,
+            accessibility: false,
+            log_callback: None,
+            background_behavior: BackgroundBehavior::Pause,
+            allow_local_font_access: false,
+            wait_for_full_load: false,
+            load_allow_list: None,
+            policy_file_mode: PolicyFileMode::Ignore,
+            rsl_mirror: None,
+            capabilities: CapabilitiesConfig::default(),
+            url_parameters: vec![],
+            autoplay: Autoplay::Auto,
+            unmute_overlay: UnmuteOverlay::Visible,
+            compatibility_rules_url: None,
+            max_active_players: None,
+            spoofed_url: None,
         }
     }
 }
@@ -132,6 +204,23 @@ impl RuffleInstanceBuilder {
         };
     }
 
+    /// Sets a URL to download more compatibility rule sets from, or stops downloading them if `null`.
+    ///
+    /// The rule sets are cached for a day, and replace the built-in ones of the same name.
+    #[wasm_bindgen(js_name = "setCompatibilityRulesUrl")]
+    pub fn set_compatibility_rules_url(&mut self, value: Option<String>) {
+        self.compatibility_rules_url = value;
+    }
+
+    /// Sets how many players on the page may run at once, or lifts the limit if `null`.
+    ///
+    /// The limit applies to the whole page. Players on screen are run first,
+    /// then the ones most recently interacted with, and the others are paused.
+    #[wasm_bindgen(js_name = "setMaxActivePlayers")]
+    pub fn set_max_active_players(&mut self, value: Option<u32>) {
+        self.max_active_players = value;
+    }
+
     #[wasm_bindgen(js_name = "setLetterbox")]
     pub fn set_letterbox(&mut self, value: &str) {
         self.letterbox = match value {
@@ -229,6 +318,15 @@ impl RuffleInstanceBuilder {
         }
     }
 
+    /// Sets a function that receives log records, along with the browser console.
+    ///
+    /// It's called with an object holding the `level`, `target` and `message` of each record
+    /// up to the log level, and of all `trace()` output, which has `isAvmTrace` set.
+    #[wasm_bindgen(js_name = "setLogCallback")]
+    pub fn set_log_callback(&mut self, value: Option<Function>) {
+        self.log_callback = value;
+    }
+
     #[wasm_bindgen(js_name = "setMaxExecutionDuration")]
     pub fn set_max_execution_duration(&mut self, value: f64) {
         self.max_execution_duration = Duration::from_secs_f64(value);
@@ -265,11 +363,24 @@ impl RuffleInstanceBuilder {
     }
 
     #[wasm_bindgen(js_name = "addSocketProxy")]
-    pub fn add_socket_proxy(&mut self, host: String, port: u16, proxy_url: String) {
+    pub fn add_socket_proxy(
+        &mut self,
+        host: String,
+        port: u16,
+        proxy_url: String,
+        proxy_protocol: Option<String>,
+        tls: Option<bool>,
+    ) {
+        let proxy_protocol = match proxy_protocol.as_deref() {
+            Some("webtransport") => SocketProxyProtocol::WebTransport,
+            _ => SocketProxyProtocol::WebSocket,
+        };
         self.socket_proxy.push(SocketProxy {
             host,
             port,
             proxy_url,
+            proxy_protocol,
+            tls: tls.unwrap_or_default(),
         })
     }
 
@@ -292,6 +403,214 @@ impl RuffleInstanceBuilder {
         self.volume = value;
     }
 
+    #[wasm_bindgen(js_name = "setBitmapReadbackMode")]
+    pub fn set_bitmap_readback_mode(&mut self, value: &str) {
+        self.bitmap_readback_mode = match value {
+            "strict" => BitmapReadbackMode::Strict,
+            "throttled" => BitmapReadbackMode::Throttled,
+            _ => return,
+        };
+    }
+
+    #[wasm_bindgen(js_name = "setMaxNativeWindows")]
+    pub fn set_max_native_windows(&mut self, value: u32) {
+        self.max_native_windows = value as usize;
+    }
+
+    #[wasm_bindgen(js_name = "setLowLatencyInput")]
+    pub fn set_low_latency_input(&mut self, value: bool) {
+        self.low_latency_input = value;
+    }
+
+    #[wasm_bindgen(js_name = "setForceLowLatencyInput")]
+    pub fn set_force_low_latency_input(&mut self, value: bool) {
+        self.force_low_latency_input = value;
+    }
+
+    /// Sets where SharedObjects are stored: `"local"`, `"indexeddb"`, or an object
+    /// with `get(name)`, `put(name, data)` and `remove(name)` methods.
+    #[wasm_bindgen(js_name = "setStorageBackend")]
+    pub fn set_storage_backend(&mut self, value: JsValue) {
+        self.storage = match value.as_string().as_deref() {
+            Some("local") => storage::StorageKind::Local,
+            Some("indexeddb") => storage::StorageKind::IndexedDb,
+            Some(_) => return,
+            None if value.is_object() => storage::StorageKind::Custom(value.unchecked_into()),
+            None => return,
+        };
+    }
+
+    /// Makes a gamepad button act as the key with the given Flash key code.
+    #[wasm_bindgen(js_name = "setGamepadButtonMapping")]
+    pub fn set_gamepad_button_mapping(&mut self, button: &str, key_code: u32) {
+        if let Some(button) = gamepad::parse_gamepad_button(button) {
+            self.gamepad_button_mapping
+                .insert(button, KeyCode::from_code(key_code));
+        }
+    }
+
+    /// Makes the key with the Flash key code `from_key` act as the key `to_key`.
+    #[wasm_bindgen(js_name = "setKeyRemap")]
+    pub fn set_key_remap(&mut self, from_key: u32, to_key: u32) {
+        self.key_remap
+            .insert(KeyCode::from_code(from_key), KeyCode::from_code(to_key));
+    }
+
+    /// Sets how fast the right stick of a gamepad moves the mouse, in pixels per second.
+    #[wasm_bindgen(js_name = "setGamepadMouseSpeed")]
+    pub fn set_gamepad_mouse_speed(&mut self, value: f64) {
+        self.gamepad_mouse_speed = value.max(0.0);
+    }
+
+    /// Sets whether movies may ask to use the user's cameras.
+    #[wasm_bindgen(js_name = "setAllowCamera")]
+    pub fn set_allow_camera(&mut self, value: bool) {
+        self.allow_camera = value;
+    }
+
+    /// Sets whether movies may ask to use the user's microphones.
+    #[wasm_bindgen(js_name = "setAllowMicrophone")]
+    pub fn set_allow_microphone(&mut self, value: bool) {
+        self.allow_microphone = value;
+    }
+
+    /// Sets whether the rendering quality is lowered while the movie can't keep up
+    /// with its frame rate, and restored once it can.
+    #[wasm_bindgen(js_name = "setAdaptiveQuality")]
+    pub fn set_adaptive_quality(&mut self, value: bool) {
+        self.adaptive_quality = value;
+    }
+
+    /// Sets whether the text and buttons on the stage are mirrored to the page,
+    /// so that screen readers can follow the movie.
+    #[wasm_bindgen(js_name = "setAccessibility")]
+    pub fn set_accessibility(&mut self, value: bool) {
+        self.accessibility = value;
+    }
+
+    /// Sets what the player does while the page is hidden, such as in a background tab.
+    #[wasm_bindgen(js_name = "setBackgroundBehavior")]
+    pub fn set_background_behavior(&mut self, value: &str) {
+        self.background_behavior = match value {
+            "pause" => BackgroundBehavior::Pause,
+            "throttle" => BackgroundBehavior::Throttle,
+            "run" => BackgroundBehavior::Run,
+            _ => return,
+        };
+    }
+
+    /// Sets whether the movie starts playing once it's loaded.
+    ///
+    /// With `auto`, it does if the browser lets the audio play right away.
+    #[wasm_bindgen(js_name = "setAutoplay")]
+    pub fn set_autoplay(&mut self, value: &str) {
+        self.autoplay = match value {
+            "on" => Autoplay::On,
+            "off" => Autoplay::Off,
+            "auto" => Autoplay::Auto,
+            _ => return,
+        };
+    }
+
+    /// Sets whether the user is asked to click to unmute, while the browser keeps the audio suspended.
+    #[wasm_bindgen(js_name = "setUnmuteOverlay")]
+    pub fn set_unmute_overlay(&mut self, value: &str) {
+        self.unmute_overlay = match value {
+            "visible" => UnmuteOverlay::Visible,
+            "hidden" => UnmuteOverlay::Hidden,
+            _ => return,
+        };
+    }
+
+    /// Sets whether device fonts may be read from the system, with the Local Font Access API.
+    ///
+    /// Browsers only allow this after the user granted the `local-fonts` permission,
+    /// which they may ask for when the player is created.
+    #[wasm_bindgen(js_name = "setAllowLocalFontAccess")]
+    pub fn set_allow_local_font_access(&mut self, value: bool) {
+        self.allow_local_font_access = value;
+    }
+
+    /// Sets whether movies only start once they're completely loaded, rather than as they stream in.
+    ///
+    /// The player is paused until every downloaded movie has been fully parsed, so movies see
+    /// themselves as loaded on their first frame and preload animations are skipped.
+    /// Movies loaded by the root movie are downloaded in full either way; only their parsing
+    /// holds the player.
+    #[wasm_bindgen(js_name = "setWaitForFullLoad")]
+    pub fn set_wait_for_full_load(&mut self, value: bool) {
+        self.wait_for_full_load = value;
+    }
+
+    /// Restricts where the movie may load SWFs and data from, or lifts the restriction if `null`.
+    ///
+    /// Entries are hosts (`example.com`), hosts with their subdomains (`*.example.com`)
+    /// or origins (`https://example.com`). The origin of the movie itself is always allowed.
+    #[wasm_bindgen(js_name = "setLoadAllowList")]
+    pub fn set_load_allow_list(&mut self, value: Option<Vec<String>>) {
+        self.load_allow_list = value;
+    }
+
+    /// Sets whether cross-domain policy files are checked before the movie reads data
+    /// from other domains or connects sockets to them.
+    ///
+    /// `enforce` blocks what isn't allowed, `warn` only logs it, and `ignore` skips the checks.
+    #[wasm_bindgen(js_name = "setPolicyFileMode")]
+    pub fn set_policy_file_mode(&mut self, value: &str) {
+        self.policy_file_mode = match value {
+            "enforce" => PolicyFileMode::Enforce,
+            "warn" => PolicyFileMode::Warn,
+            "ignore" => PolicyFileMode::Ignore,
+            _ => return,
+        };
+    }
+
+    /// Sets where Adobe's signed runtime shared libraries, such as the Text Layout
+    /// Framework used by TLF text, are loaded from instead of Adobe's servers.
+    ///
+    /// The mirror is laid out like `https://fpdownload.adobe.com/pub/swz/`.
+    #[wasm_bindgen(js_name = "setRslMirror")]
+    pub fn set_rsl_mirror(&mut self, value: Option<String>) {
+        self.rsl_mirror = value;
+    }
+
+    /// Overrides what the movie is told about the player, or stops overriding it if `null`.
+    ///
+    /// `name` is one of `version`, `os`, `playerType`, `language` or `serverString`,
+    /// the names of the properties of `Capabilities` in ActionScript.
+    #[wasm_bindgen(js_name = "setCapability")]
+    pub fn set_capability(&mut self, name: &str, value: Option<String>) {
+        if let Some(capability) = capability_mut(&mut self.capabilities, name) {
+            *capability = value;
+        }
+    }
+
+    /// Sets parameters for the movie from the query string of `url`, read as Flash Player did.
+    ///
+    /// They come before the parameters given when loading the movie, which take precedence.
+    #[wasm_bindgen(js_name = "setParametersFromUrl")]
+    pub fn set_parameters_from_url(&mut self, url: &str) {
+        self.url_parameters = parameters::parse_url_parameters(url);
+    }
+
+    /// Sets a function that may answer the requests made by the movie, instead of the network.
+    ///
+    /// It's called with a `Request`, and returns a `Response` (or a promise of one),
+    /// or `null` to let the request go to the network.
+    #[wasm_bindgen(js_name = "setFetchHandler")]
+    pub fn set_fetch_handler(&mut self, value: Option<Function>) {
+        self.fetch_handler = value;
+    }
+
+    /// Adds an item to the right-click menu, after the items defined by the movie.
+    ///
+    /// When the item is clicked, the player element dispatches a `contextmenuitem` event
+    /// with the given `callback_id`.
+    #[wasm_bindgen(js_name = "addContextMenuItem")]
+    pub fn add_context_menu_item(&mut self, label: String, callback_id: String) {
+        self.context_menu_items.push((label, callback_id));
+    }
+
     #[wasm_bindgen(js_name = "addFont")]
     pub fn add_font(&mut self, font_name: String, data: Vec<u8>) {
         self.custom_fonts.push((font_name, data))
@@ -306,6 +625,7 @@ impl RuffleInstanceBuilder {
             "japaneseGothic" => DefaultFont::JapaneseGothic,
             "japaneseGothicMono" => DefaultFont::JapaneseGothicMono,
             "japaneseMincho" => DefaultFont::JapaneseMincho,
+            "fallback" => DefaultFont::Fallback,
             _ => return,
         };
         self.default_fonts.insert(
@@ -317,10 +637,115 @@ impl RuffleInstanceBuilder {
         );
     }
 
-    // TODO: This should be split into two methods that either load url or load data
-    // Right now, that's done immediately afterwards in TS
-    pub async fn build(&self, parent: HtmlElement, js_player: JavascriptPlayer) -> Promise {
-        let copy = self.clone();
+    /// Returns a snapshot of every option of this builder, see `BuilderConfig`.
+    ///
+    /// Unless `include_sensitive` is set, private values such as the credential allow list
+    /// and the contents of custom fonts are left out.
+    #[wasm_bindgen(js_name = "getConfig")]
+    pub fn get_config(&self, include_sensitive: bool) -> JsValue {
+        self.to_config(include_sensitive).to_js()
+    }
+
+    /// Creates a builder with the exact options of a snapshot returned by `getConfig`.
+    #[wasm_bindgen(js_name = "fromConfig")]
+    pub fn from_config(config: JsValue) -> Result<RuffleInstanceBuilder, JsValue> {
+        let config: BuilderConfig = serde_wasm_bindgen::from_value(config)?;
+        Self::from_builder_config(config).map_err(|e| JsValue::from(e.to_string()))
+    }
+
+    /// Creates a player in `parent`, and streams the movie at `url` into it.
+    ///
+    /// `parameters` are *extra* parameters to set on the LoaderInfo -
+    /// parameters from `url` query parameters will be automatically added.
+    ///
+    /// The player is passed to `jsPlayer.onInstanceCreated` before the movie starts loading.
+    /// Resolves to the player once the movie header has been parsed. Rejects if the player
+    /// couldn't be created, or with a `MovieLoadError` if the movie couldn't be loaded.
+    #[wasm_bindgen(js_name = "buildWithUrl")]
+    pub fn build_with_url(
+        &self,
+        parent: HtmlElement,
+        js_player: JavascriptPlayer,
+        url: String,
+        parameters: JsValue,
+    ) -> Promise {
+        self.build(parent, js_player, MovieSource::Url { url, parameters })
+    }
+
+    /// Creates a player in `parent`, and plays the movie in `bytes` in it.
+    ///
+    /// `swf_name` is the file name that the movie appears to be loaded from,
+    /// next to the current page.
+    ///
+    /// The player is passed to `jsPlayer.onInstanceCreated` before the movie starts loading.
+    /// Resolves to the player once the movie header has been parsed. Rejects if the player
+    /// couldn't be created, or with a `MovieLoadError` if the movie couldn't be loaded.
+    #[wasm_bindgen(js_name = "buildWithData")]
+    pub fn build_with_data(
+        &self,
+        parent: HtmlElement,
+        js_player: JavascriptPlayer,
+        bytes: Uint8Array,
+        parameters: JsValue,
+        swf_name: String,
+    ) -> Promise {
+        self.build(
+            parent,
+            js_player,
+            MovieSource::Data {
+                bytes,
+                parameters,
+                swf_name,
+            },
+        )
+    }
+
+    /// Creates a player in `parent`, and plays the zipped game in `bytes` in it.
+    ///
+    /// The zip is either a Ruffle bundle, or holds a single SWF next to the files it loads.
+    /// Its files are available to the movie under `file:///`, and relative URLs are
+    /// resolved against the movie.
+    ///
+    /// The player is passed to `jsPlayer.onInstanceCreated` before the movie starts loading.
+    /// Resolves to the player once the movie header has been parsed. Rejects if the player
+    /// couldn't be created, or with a `MovieLoadError` if the movie couldn't be loaded.
+    #[wasm_bindgen(js_name = "buildWithBundle")]
+    pub fn build_with_bundle(
+        &self,
+        parent: HtmlElement,
+        js_player: JavascriptPlayer,
+        bytes: Uint8Array,
+        parameters: JsValue,
+    ) -> Promise {
+        self.build(parent, js_player, MovieSource::Bundle { bytes, parameters })
+    }
+}
+
+/// Where the root movie of a new player comes from.
+enum MovieSource {
+    Url {
+        url: String,
+        parameters: JsValue,
+    },
+    Data {
+        bytes: Uint8Array,
+        parameters: JsValue,
+        swf_name: String,
+    },
+    Bundle {
+        bytes: Uint8Array,
+        parameters: JsValue,
+    },
+}
+
+impl RuffleInstanceBuilder {
+    fn build(
+        &self,
+        parent: HtmlElement,
+        js_player: JavascriptPlayer,
+        movie: MovieSource,
+    ) -> Promise {
+        let mut copy = self.clone();
         wasm_bindgen_futures::future_to_promise(async move {
             if RUFFLE_GLOBAL_PANIC.is_completed() {
                 // If an actual panic happened, then we can't trust the state it left us in.
@@ -328,15 +753,66 @@ impl RuffleInstanceBuilder {
                 return Err("Ruffle is panicking!".into());
             }
 
-            let ruffle = RuffleHandle::new_internal(parent, js_player, copy)
+            if let Some(url) = &copy.compatibility_rules_url {
+                if let Some(document) = compatibility_rules::load(url).await {
+                    copy.compatibility_rules.add_rule_sets(document);
+                }
+            }
+            if let MovieSource::Url { url, .. } = &movie {
+                copy.apply_movie_overrides(url);
+            }
+
+            let mut parameters = copy.url_parameters.clone();
+            let ruffle = RuffleHandle::new_internal(parent, js_player.clone(), copy)
                 .await
                 .map_err(|err| JsValue::from(format!("Error creating player: {}", err)))?;
+
+            // The page hears about the movie's metadata while it loads, before this resolves,
+            // so it needs the player already.
+            js_player.on_instance_created(ruffle);
+
+            let loaded = match movie {
+                MovieSource::Url {
+                    url,
+                    parameters: extra,
+                } => {
+                    parameters.extend(parse_movie_parameters(&extra));
+                    ruffle.stream_from(url, parameters).await
+                }
+                MovieSource::Data {
+                    bytes,
+                    parameters: extra,
+                    swf_name,
+                } => {
+                    parameters.extend(parse_movie_parameters(&extra));
+                    ruffle.load_data(&bytes, parameters, &swf_name)
+                }
+                MovieSource::Bundle {
+                    bytes,
+                    parameters: extra,
+                } => {
+                    parameters.extend(parse_movie_parameters(&extra));
+                    ruffle.load_bundle(&bytes, parameters).await
+                }
+            };
+            if let Err(e) = loaded {
+                // Nobody will get a handle to this player, so nobody else could destroy it.
+                ruffle.destroy();
+                return Err(e);
+            }
+
             Ok(JsValue::from(ruffle))
         })
     }
-}
 
-impl RuffleInstanceBuilder {
+    /// Sets up the player the way the compatibility rules want for the movie at `swf_url`.
+    fn apply_movie_overrides(&mut self, swf_url: &str) {
+        let overrides = self.compatibility_rules.movie_overrides(swf_url);
+        self.player_version = overrides.player_version.or(self.player_version);
+        self.frame_rate = overrides.frame_rate.or(self.frame_rate);
+        self.spoofed_url = overrides.spoofed_url;
+    }
+
     pub fn setup_fonts(&self, player: &mut Player) {
         for (font_name, bytes) in &self.custom_fonts {
             let bytes_slice = &bytes[..];
@@ -363,10 +839,23 @@ impl RuffleInstanceBuilder {
                 if let Ok(swf_stream) = swf::decompress_swf(&bytes[..]) {
                     if let Ok(swf) = swf::parse_swf(&swf_stream) {
                         let encoding = swf::SwfStr::encoding_for_version(swf.header.version());
+                        // DefineFont tags are only named by the DefineFontInfo tags after them.
+                        let mut unnamed_fonts = HashMap::new();
                         for tag in swf.tags {
                             match tag {
-                                swf::Tag::DefineFont(_font) => {
-                                    tracing::warn!("DefineFont1 tag is not yet supported by Ruffle, inside font swf {font_name}");
+                                swf::Tag::DefineFont(font) => {
+                                    unnamed_fonts.insert(font.id, *font);
+                                }
+                                swf::Tag::DefineFontInfo(font_info) => {
+                                    if let Some(font) = unnamed_fonts.remove(&font_info.id) {
+                                        tracing::debug!(
+                                            "Loaded font {} from font swf {font_name}",
+                                            font_info.name.to_str_lossy(encoding)
+                                        );
+                                        player.register_device_font(FontDefinition::SwfFont1Tag(
+                                            font, *font_info, encoding,
+                                        ));
+                                    }
                                 }
                                 swf::Tag::DefineFont2(font) => {
                                     tracing::debug!(
@@ -399,6 +888,9 @@ impl RuffleInstanceBuilder {
                                 _ => {}
                             }
                         }
+                        for id in unnamed_fonts.keys() {
+                            tracing::warn!("Font {id} from font swf {font_name} has no DefineFontInfo tag naming it");
+                        }
                         continue;
                     }
                 }
@@ -441,19 +933,23 @@ impl RuffleInstanceBuilder {
         });
     }
 
-    pub fn create_log_subscriber(&self) -> Arc<Layered<WASMLayer, Registry>> {
+    pub fn create_log_subscriber(&self) -> Arc<LogSubscriber> {
         let layer = WASMLayer::new(
             WASMLayerConfigBuilder::new()
                 .set_report_logs_in_timings(cfg!(feature = "profiling"))
                 .set_max_level(self.log_level)
                 .build(),
         );
-        Arc::new(tracing_subscriber::registry().with(layer))
+        let js_layer = self
+            .log_callback
+            .clone()
+            .map(|callback| JsLogLayer::new(callback, self.log_level));
+        Arc::new(tracing_subscriber::registry().with(layer).with(js_layer))
     }
 
     pub async fn create_renderer(
         &self,
-    ) -> Result<(Box<dyn RenderBackend>, HtmlCanvasElement), Box<dyn Error>> {
+    ) -> Result<(Box<dyn RenderBackend>, HtmlCanvasElement, RendererReport), Box<dyn Error>> {
         let window = web_sys::window().ok_or("Expected window")?;
         let document = window.document().ok_or("Expected document")?;
         #[cfg(not(any(
@@ -464,128 +960,168 @@ impl RuffleInstanceBuilder {
         )))]
         std::compile_error!("You must enable one of the render backend features (e.g., webgl).");
 
-        let _is_transparent = self.wmode.as_deref() == Some("transparent");
+        // Try to create a backend, falling through to the next backend on failure.
+        // We must recreate the canvas each attempt, as only a single context may be created per canvas
+        // with `getContext`.
+        let mut failures = vec![];
+        for renderer in self.renderer_list() {
+            let canvas: HtmlCanvasElement = document
+                .create_element("canvas")
+                .into_js_result()?
+                .dyn_into()
+                .map_err(|_| "Expected HtmlCanvasElement")?;
+            match self.create_renderer_for_canvas(renderer, &canvas).await {
+                Ok(renderer) => {
+                    let report = RendererReport {
+                        renderer: renderer.name(),
+                        debug_info: renderer.debug_info().into_owned(),
+                        failures,
+                    };
+                    return Ok((renderer, canvas, report));
+                }
+                Err(error) => failures.push(RendererFailure {
+                    renderer: renderer.to_string(),
+                    error,
+                }),
+            }
+        }
+        Err("Unable to create renderer".into())
+    }
 
-        let mut renderer_list = vec!["wgpu-webgl", "webgpu", "webgl", "canvas"];
+    /// The names of the renderers in this build to try, in order of preference.
+    fn renderer_list(&self) -> Vec<&str> {
+        let mut renderer_list: Vec<&str> = [
+            (
+                cfg!(all(feature = "wgpu-webgl", target_family = "wasm")),
+                "wgpu-webgl",
+            ),
+            (
+                cfg!(all(feature = "webgpu", target_family = "wasm")),
+                "webgpu",
+            ),
+            (cfg!(feature = "webgl"), "webgl"),
+            (cfg!(feature = "canvas"), "canvas"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, renderer)| enabled.then_some(renderer))
+        .collect();
         if let Some(preferred_renderer) = &self.preferred_renderer {
             if let Some(pos) = renderer_list.iter().position(|&r| r == preferred_renderer) {
                 renderer_list.remove(pos);
                 renderer_list.insert(0, preferred_renderer.as_str());
+            } else if ["wgpu-webgl", "webgpu", "webgl", "canvas"]
+                .contains(&preferred_renderer.as_str())
+            {
+                tracing::warn!(
+                    "Renderer {} is not supported by this build",
+                    preferred_renderer
+                );
             } else {
                 tracing::error!("Unrecognized renderer name: {}", preferred_renderer);
             }
         }
+        renderer_list
+    }
 
-        // Try to create a backend, falling through to the next backend on failure.
-        // We must recreate the canvas each attempt, as only a single context may be created per canvas
-        // with `getContext`.
-        for renderer in renderer_list {
-            match renderer {
-                #[cfg(all(feature = "webgpu", target_family = "wasm"))]
-                "webgpu" => {
-                    // Check that we have access to WebGPU (navigator.gpu should exist).
-                    if web_sys::window()
-                        .ok_or(JsValue::FALSE)
-                        .and_then(|window| {
-                            js_sys::Reflect::has(&window.navigator(), &JsValue::from_str("gpu"))
-                        })
-                        .unwrap_or_default()
-                    {
-                        tracing::info!("Creating wgpu webgpu renderer...");
-                        let canvas: HtmlCanvasElement = document
-                            .create_element("canvas")
-                            .into_js_result()?
-                            .dyn_into()
-                            .map_err(|_| "Expected HtmlCanvasElement")?;
-
-                        match ruffle_render_wgpu::backend::WgpuRenderBackend::for_canvas(
-                            canvas.clone(),
-                            true,
-                        )
-                        .await
-                        {
-                            Ok(renderer) => {
-                                return Ok((Box::new(renderer), canvas));
-                            }
-                            Err(error) => {
-                                tracing::error!("Error creating wgpu webgpu renderer: {}", error)
-                            }
-                        }
-                    }
-                }
-                #[cfg(all(feature = "wgpu-webgl", target_family = "wasm"))]
-                "wgpu-webgl" => {
-                    tracing::info!("Creating wgpu webgl renderer...");
-                    let canvas: HtmlCanvasElement = document
-                        .create_element("canvas")
-                        .into_js_result()?
-                        .dyn_into()
-                        .map_err(|_| "Expected HtmlCanvasElement")?;
+    /// Creates the named renderer, drawing to `canvas`.
+    ///
+    /// Returns the reason if the renderer is unavailable or failed to be created.
+    pub(crate) async fn create_renderer_for_canvas(
+        &self,
+        renderer: &str,
+        canvas: &HtmlCanvasElement,
+    ) -> Result<Box<dyn RenderBackend>, String> {
+        #[allow(unused_variables)] // Builds without any renderer don't use it.
+        let is_transparent = self.wmode.as_deref() == Some("transparent");
 
+        match renderer {
+            #[cfg(all(feature = "webgpu", target_family = "wasm"))]
+            "webgpu" => {
+                // Check that we have access to WebGPU (navigator.gpu should exist).
+                if web_sys::window()
+                    .ok_or(JsValue::FALSE)
+                    .and_then(|window| {
+                        js_sys::Reflect::has(&window.navigator(), &JsValue::from_str("gpu"))
+                    })
+                    .unwrap_or_default()
+                {
+                    tracing::info!("Creating wgpu webgpu renderer...");
                     match ruffle_render_wgpu::backend::WgpuRenderBackend::for_canvas(
                         canvas.clone(),
-                        false,
+                        true,
+                        is_transparent,
                     )
                     .await
                     {
-                        Ok(renderer) => {
-                            return Ok((Box::new(renderer), canvas));
-                        }
+                        Ok(renderer) => Ok(Box::new(renderer)),
                         Err(error) => {
-                            tracing::error!("Error creating wgpu webgl renderer: {}", error)
+                            tracing::error!("Error creating wgpu webgpu renderer: {}", error);
+                            Err(error.to_string())
                         }
                     }
+                } else {
+                    Err("WebGPU is not supported by this browser".to_string())
                 }
-                #[cfg(feature = "webgl")]
-                "webgl" => {
-                    tracing::info!("Creating WebGL renderer...");
-                    let canvas: HtmlCanvasElement = document
-                        .create_element("canvas")
-                        .into_js_result()?
-                        .dyn_into()
-                        .map_err(|_| "Expected HtmlCanvasElement")?;
-                    match ruffle_render_webgl::WebGlRenderBackend::new(
-                        &canvas,
-                        _is_transparent,
-                        self.quality,
-                    ) {
-                        Ok(renderer) => {
-                            return Ok((Box::new(renderer), canvas));
-                        }
-                        Err(error) => {
-                            tracing::error!("Error creating WebGL renderer: {}", error)
-                        }
+            }
+            #[cfg(all(feature = "wgpu-webgl", target_family = "wasm"))]
+            "wgpu-webgl" => {
+                tracing::info!("Creating wgpu webgl renderer...");
+                match ruffle_render_wgpu::backend::WgpuRenderBackend::for_canvas(
+                    canvas.clone(),
+                    false,
+                    is_transparent,
+                )
+                .await
+                {
+                    Ok(renderer) => Ok(Box::new(renderer)),
+                    Err(error) => {
+                        tracing::error!("Error creating wgpu webgl renderer: {}", error);
+                        Err(error.to_string())
                     }
                 }
-                #[cfg(feature = "canvas")]
-                "canvas" => {
-                    tracing::info!("Creating Canvas renderer...");
-                    let canvas: HtmlCanvasElement = document
-                        .create_element("canvas")
-                        .into_js_result()?
-                        .dyn_into()
-                        .map_err(|_| "Expected HtmlCanvasElement")?;
-                    match ruffle_render_canvas::WebCanvasRenderBackend::new(
-                        &canvas,
-                        _is_transparent,
-                    ) {
-                        Ok(renderer) => {
-                            return Ok((Box::new(renderer), canvas));
-                        }
-                        Err(error) => tracing::error!("Error creating canvas renderer: {}", error),
+            }
+            #[cfg(feature = "webgl")]
+            "webgl" => {
+                tracing::info!("Creating WebGL renderer...");
+                match ruffle_render_webgl::WebGlRenderBackend::new(
+                    canvas,
+                    is_transparent,
+                    self.quality,
+                ) {
+                    Ok(renderer) => Ok(Box::new(renderer)),
+                    Err(error) => {
+                        tracing::error!("Error creating WebGL renderer: {}", error);
+                        Err(error.to_string())
                     }
                 }
-                _ => {}
             }
+            #[cfg(feature = "canvas")]
+            "canvas" => {
+                tracing::info!("Creating Canvas renderer...");
+                match ruffle_render_canvas::WebCanvasRenderBackend::new(canvas, is_transparent) {
+                    Ok(renderer) => Ok(Box::new(renderer)),
+                    Err(error) => {
+                        tracing::error!("Error creating canvas renderer: {}", error);
+                        Err(error.to_string())
+                    }
+                }
+            }
+            _ => Err("Not supported by this build".to_string()),
         }
-        Err("Unable to create renderer".into())
     }
 
     pub fn create_audio_backend(
         &self,
-        log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+        log_subscriber: Arc<LogSubscriber>,
     ) -> Box<dyn AudioBackend> {
-        if let Ok(audio) = audio::WebAudioBackend::new(log_subscriber.clone()) {
+        if let Ok(mut audio) =
+            audio::WebAudioBackend::new(log_subscriber.clone(), self.low_latency_input)
+        {
+            // Without autoplay, nothing is heard until the movie is played.
+            // Otherwise, the browser already starts the audio suspended if it doesn't allow autoplay.
+            if self.autoplay == Autoplay::Off {
+                audio.pause();
+            }
             Box::new(audio)
         } else {
             tracing::error!("Unable to create audio backend. No audio will be played.");
@@ -593,10 +1129,7 @@ impl RuffleInstanceBuilder {
         }
     }
 
-    pub fn create_navigator(
-        &self,
-        log_subscriber: Arc<Layered<WASMLayer, Registry>>,
-    ) -> WebNavigatorBackend {
+    pub fn create_navigator(&self, log_subscriber: Arc<LogSubscriber>) -> WebNavigatorBackend {
         WebNavigatorBackend::new(
             self.allow_script_access,
             self.allow_networking,
@@ -606,11 +1139,38 @@ impl RuffleInstanceBuilder {
             self.open_url_mode,
             self.socket_proxy.clone(),
             self.credential_allow_list.clone(),
+            self.fetch_handler.clone(),
         )
     }
 
-    pub fn create_storage_backend(&self) -> Box<dyn StorageBackend> {
-        match web_sys::window().expect("window()").local_storage() {
+    pub async fn create_storage_backend(&self) -> Box<dyn StorageBackend> {
+        let window = web_sys::window().expect("window()");
+        match &self.storage {
+            storage::StorageKind::Local => {}
+            storage::StorageKind::IndexedDb => match window.indexed_db() {
+                Ok(Some(factory)) => match storage::IndexedDbStorageBackend::open(
+                    factory,
+                    window.local_storage().ok().flatten(),
+                )
+                .await
+                {
+                    Ok(backend) => return Box::new(backend),
+                    Err(e) => tracing::warn!(
+                        "Unable to open IndexedDB: {:?}\nFalling back to localStorage.",
+                        e
+                    ),
+                },
+                err => tracing::warn!(
+                    "Unable to use IndexedDB: {:?}\nFalling back to localStorage.",
+                    err
+                ),
+            },
+            storage::StorageKind::Custom(custom) => {
+                return Box::new(storage::CustomStorageBackend::new(custom.clone()));
+            }
+        }
+
+        match window.local_storage() {
             Ok(Some(s)) => Box::new(storage::LocalStorageBackend::new(s)),
             err => {
                 tracing::warn!("Unable to use localStorage: {:?}\nData will not save.", err);
@@ -622,17 +1182,24 @@ impl RuffleInstanceBuilder {
     pub async fn create_player(
         &self,
         js_player: JavascriptPlayer,
-        log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+        log_subscriber: Arc<LogSubscriber>,
     ) -> Result<BuiltPlayer, Box<dyn Error>> {
         let window = web_sys::window().ok_or("Expected window")?;
 
-        let (renderer, canvas) = self.create_renderer().await?;
+        let (renderer, canvas, renderer_report) = self.create_renderer().await?;
 
         let mut builder = PlayerBuilder::new()
             .with_boxed_renderer(renderer)
             .with_boxed_audio(self.create_audio_backend(log_subscriber.clone()))
             .with_navigator(self.create_navigator(log_subscriber.clone()))
-            .with_storage(self.create_storage_backend());
+            .with_storage(self.create_storage_backend().await);
+
+        if self.allow_camera {
+            builder = builder.with_camera(camera::WebCameraBackend::new());
+        }
+        if self.allow_microphone {
+            builder = builder.with_microphone(microphone::WebMicrophoneBackend::new());
+        }
 
         // Create the external interface.
         if self.allow_script_access && self.allow_networking == NetworkingAccessMode::All {
@@ -642,11 +1209,34 @@ impl RuffleInstanceBuilder {
                 .with_fs_commands(interface);
         }
 
+        builder = builder.with_local_connection_backend(Box::new(
+            local_connection::WebLocalConnectionBackend::new(),
+        ));
+
+        #[cfg(feature = "jit")]
+        {
+            builder = builder.with_jit(crate::jit::WasmJitBackend::new());
+        }
+
+        let local_fonts = if self.allow_local_font_access {
+            LocalFonts::query(&window, log_subscriber.clone())
+                .await
+                .map(Rc::new)
+        } else {
+            None
+        };
+
         let trace_observer = Rc::new(RefCell::new(JsValue::UNDEFINED));
         let core = builder
             .with_log(log_adapter::WebLogBackend::new(trace_observer.clone()))
-            .with_ui(ui::WebUiBackend::new(js_player.clone(), &canvas))
-            .with_video(SoftwareVideoBackend::new())
+            .with_trace_output(log_adapter::WebTraceOutput::new(js_player.clone()))
+            .with_ui(ui::WebUiBackend::new(
+                js_player.clone(),
+                &canvas,
+                self.accessibility,
+                local_fonts.clone(),
+            ))
+            .with_video(ExternalVideoBackend::new_with_webcodecs())
             .with_letterbox(self.letterbox)
             .with_max_execution_duration(self.max_execution_duration)
             .with_player_version(self.player_version)
@@ -656,7 +1246,23 @@ impl RuffleInstanceBuilder {
             .with_align(self.stage_align, self.force_align)
             .with_scale_mode(self.scale, self.force_scale)
             .with_frame_rate(self.frame_rate)
+            .with_bitmap_readback_mode(self.bitmap_readback_mode)
+            .with_max_native_windows(self.max_native_windows)
+            .with_low_latency_input(self.low_latency_input, self.force_low_latency_input)
+            .with_adaptive_quality(self.adaptive_quality)
+            .with_load_behavior(if self.wait_for_full_load {
+                LoadBehavior::Delayed
+            } else {
+                LoadBehavior::Streaming
+            })
+            .with_load_allow_list(self.load_allow_list.as_deref().map(LoadAllowList::new))
+            .with_policy_file_mode(self.policy_file_mode)
+            .with_rsl_mirror(self.rsl_mirror.as_deref().and_then(RslMirror::new))
+            .with_capabilities(self.capabilities.clone())
+            .with_gamepad_button_mapping(self.gamepad_button_mapping.clone())
+            .with_key_remap(self.key_remap.clone())
             .with_page_url(window.location().href().ok())
+            .with_spoofed_url(self.spoofed_url.clone())
             .build();
 
         let player_weak = Arc::downgrade(&core);
@@ -668,7 +1274,10 @@ impl RuffleInstanceBuilder {
             core.navigator_mut()
                 .downcast_mut::<WebNavigatorBackend>()
                 .expect("Expected WebNavigatorBackend")
-                .set_player(player_weak);
+                .set_player(player_weak.clone());
+            if let Some(local_fonts) = &local_fonts {
+                local_fonts.set_player(player_weak);
+            }
             // Set config parameters.
             core.set_volume(self.volume);
             core.set_background_color(self.background_color);
@@ -682,6 +1291,7 @@ impl RuffleInstanceBuilder {
             core,
             canvas,
             trace_observer,
+            renderer_report,
         })
     }
 }
@@ -690,4 +1300,39 @@ pub struct BuiltPlayer {
     pub core: Arc<Mutex<Player>>,
     pub canvas: HtmlCanvasElement,
     pub trace_observer: Rc<RefCell<JsValue>>,
+    pub renderer_report: RendererReport,
+}
+
+/// Which renderer a player ended up with, and why the ones preferred over it weren't used.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RendererReport {
+    renderer: &'static str,
+    debug_info: String,
+    failures: Vec<RendererFailure>,
+}
+
+#[derive(Serialize)]
+pub struct RendererFailure {
+    renderer: String,
+    error: String,
+}
+
+/// The names of the strings in `CapabilitiesConfig`, as they're called in ActionScript.
+pub(crate) const CAPABILITY_NAMES: &[&str] =
+    &["version", "os", "playerType", "language", "serverString"];
+
+/// Returns the override of the capability with the given name, if it can be overridden.
+pub(crate) fn capability_mut<'a>(
+    capabilities: &'a mut CapabilitiesConfig,
+    name: &str,
+) -> Option<&'a mut Option<String>> {
+    match name {
+        "version" => Some(&mut capabilities.version),
+        "os" => Some(&mut capabilities.os),
+        "playerType" => Some(&mut capabilities.player_type),
+        "language" => Some(&mut capabilities.language),
+        "serverString" => Some(&mut capabilities.server_string),
+        _ => None,
+    }
 }
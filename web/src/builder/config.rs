@@ -0,0 +1,955 @@
+//! Snapshots of a `RuffleInstanceBuilder`, used to reproduce the exact setup of a player.
+//!
+//! Every option uses the same values as the corresponding `set*` method of the builder,
+//! which are also the values used by the JS config.
+
+use super::{capability_mut, RuffleInstanceBuilder, CAPABILITY_NAMES};
+use crate::gamepad::{gamepad_button_name, parse_gamepad_button};
+use crate::navigator::OpenUrlMode;
+use crate::storage::StorageKind;
+use crate::{Autoplay, BackgroundBehavior, SocketProxy, SocketProxyProtocol, UnmuteOverlay};
+use base64::prelude::*;
+use ruffle_core::bitmap::readback::BitmapReadbackMode;
+use ruffle_core::compatibility_rules::CompatibilityRules;
+use ruffle_core::config::{Letterbox, NetworkingAccessMode, PolicyFileMode};
+use ruffle_core::events::KeyCode;
+use ruffle_core::{Color, DefaultFont, PlayerRuntime, StageAlign, StageScaleMode};
+use ruffle_render::quality::StageQuality;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+
+/// The version of the snapshot format produced by this build.
+///
+/// This must be increased whenever an option is removed or changes meaning.
+/// Adding an option does not require a new version, as long as it has a default.
+pub const CONFIG_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error(
+        "Config version {0} is newer than the supported version {}",
+        CONFIG_VERSION
+    )]
+    UnsupportedVersion(u32),
+
+    #[error("Invalid value {value:?} for option {option}")]
+    InvalidValue { option: &'static str, value: String },
+}
+
+/// A value that may be private to the user, and so is only included when asked for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Sensitive<T> {
+    Included(T),
+
+    /// The value was left out, and only the number of entries it had is known.
+    Redacted {
+        redacted: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SocketProxyConfig {
+    pub host: String,
+    pub port: u16,
+    pub proxy_url: String,
+    #[serde(default = "default_proxy_protocol")]
+    pub proxy_protocol: String,
+    #[serde(default)]
+    pub tls: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextMenuItemConfig {
+    pub label: String,
+    pub callback_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FontConfig {
+    pub name: String,
+    pub byte_length: usize,
+
+    /// The base64-encoded font file, if sensitive values were included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+/// The full state of a `RuffleInstanceBuilder`, including options that were never set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuilderConfig {
+    pub version: u32,
+    pub allow_script_access: bool,
+    pub background_color: Option<u32>,
+    pub letterbox: String,
+    pub upgrade_to_https: bool,
+
+    /// Whether each of the built-in compatibility rule sets is enabled.
+    pub compatibility_rules: BTreeMap<String, bool>,
+    pub base_url: Option<String>,
+    pub show_menu: bool,
+    pub allow_fullscreen: bool,
+    pub stage_align: String,
+    pub force_align: bool,
+    pub quality: String,
+    pub scale: String,
+    pub force_scale: bool,
+    pub frame_rate: Option<f64>,
+    pub wmode: Option<String>,
+    pub log_level: String,
+
+    /// In seconds.
+    pub max_execution_duration: f64,
+    pub player_version: Option<u8>,
+    pub preferred_renderer: Option<String>,
+    pub open_url_mode: String,
+    pub allow_networking: String,
+    pub socket_proxy: Vec<SocketProxyConfig>,
+    pub credential_allow_list: Sensitive<Vec<String>>,
+    pub player_runtime: String,
+    pub volume: f32,
+    pub default_fonts: BTreeMap<String, Vec<String>>,
+    pub custom_fonts: Vec<FontConfig>,
+    pub bitmap_readback_mode: String,
+    pub max_native_windows: u32,
+
+    /// Added after version 1 was introduced, so may be missing from older snapshots.
+    #[serde(default)]
+    pub low_latency_input: bool,
+    #[serde(default)]
+    pub force_low_latency_input: bool,
+
+    /// Either "local", "indexeddb" or "custom". A custom backend can't be restored,
+    /// so it's replaced by "local".
+    #[serde(default = "default_storage_backend")]
+    pub storage_backend: String,
+
+    /// Flash key codes, by gamepad button name.
+    #[serde(default)]
+    pub gamepad_button_mapping: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub gamepad_mouse_speed: f64,
+
+    /// Flash key codes, by the key code that acts as them.
+    /// The keys are strings so that this is a plain object in JS.
+    #[serde(default)]
+    pub key_remap: BTreeMap<String, u32>,
+    #[serde(default = "default_allow_capture")]
+    pub allow_camera: bool,
+    #[serde(default = "default_allow_capture")]
+    pub allow_microphone: bool,
+    #[serde(default)]
+    pub context_menu_items: Vec<ContextMenuItemConfig>,
+
+    /// Whether a fetch handler was set. It's a function, so it can't be restored.
+    #[serde(default)]
+    pub fetch_handler: bool,
+    #[serde(default)]
+    pub adaptive_quality: bool,
+    #[serde(default)]
+    pub accessibility: bool,
+
+    /// Whether a log callback was set. It's a function, so it can't be restored.
+    #[serde(default)]
+    pub log_callback: bool,
+    #[serde(default = "default_background_behavior")]
+    pub background_behavior: String,
+    #[serde(default)]
+    pub allow_local_font_access: bool,
+    #[serde(default)]
+    pub wait_for_full_load: bool,
+    #[serde(default)]
+    pub load_allow_list: Option<Vec<String>>,
+    #[serde(default = "default_policy_file_mode")]
+    pub policy_file_mode: String,
+    #[serde(default)]
+    pub rsl_mirror: Option<String>,
+
+    /// The overridden `Capabilities` strings, by their name in ActionScript.
+    #[serde(default)]
+    pub capabilities: BTreeMap<String, String>,
+
+    /// The parameters read from `setParametersFromUrl`, in order.
+    #[serde(default)]
+    pub url_parameters: Vec<(String, String)>,
+    #[serde(default = "default_autoplay")]
+    pub autoplay: String,
+    #[serde(default = "default_unmute_overlay")]
+    pub unmute_overlay: String,
+    #[serde(default)]
+    pub compatibility_rules_url: Option<String>,
+    #[serde(default)]
+    pub max_active_players: Option<u32>,
+}
+
+fn default_storage_backend() -> String {
+    StorageKind::Local.name().to_string()
+}
+
+fn default_proxy_protocol() -> String {
+    proxy_protocol_name(SocketProxyProtocol::WebSocket).to_string()
+}
+
+fn default_background_behavior() -> String {
+    background_behavior_name(BackgroundBehavior::Pause).to_string()
+}
+
+fn default_policy_file_mode() -> String {
+    policy_file_mode_name(PolicyFileMode::Ignore).to_string()
+}
+
+fn default_autoplay() -> String {
+    autoplay_name(Autoplay::Auto).to_string()
+}
+
+fn default_unmute_overlay() -> String {
+    unmute_overlay_name(UnmuteOverlay::Visible).to_string()
+}
+
+fn default_allow_capture() -> bool {
+    true
+}
+
+impl BuilderConfig {
+    pub fn to_js(&self) -> JsValue {
+        // Serialize maps as plain objects, so that this can be passed to `JSON.stringify`.
+        self.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap_or(JsValue::NULL)
+    }
+}
+
+impl RuffleInstanceBuilder {
+    /// Takes a snapshot of every option of this builder.
+    ///
+    /// Unless `include_sensitive` is set, the credential allow list is left out
+    /// and custom fonts are only listed by name and size.
+    pub fn to_config(&self, include_sensitive: bool) -> BuilderConfig {
+        let builtin_rules = CompatibilityRules::builtin_rules();
+        let compatibility_rules = builtin_rules
+            .rule_set_names()
+            .map(|name| {
+                let enabled = self.compatibility_rules.rule_set_names().any(|n| n == name);
+                (name.to_string(), enabled)
+            })
+            .collect();
+
+        let credential_allow_list = if include_sensitive {
+            Sensitive::Included(self.credential_allow_list.clone())
+        } else {
+            Sensitive::Redacted {
+                redacted: self.credential_allow_list.len(),
+            }
+        };
+
+        let mut overrides = self.capabilities.clone();
+        let capabilities = CAPABILITY_NAMES
+            .iter()
+            .filter_map(|name| {
+                let value = capability_mut(&mut overrides, name)?.take()?;
+                Some((name.to_string(), value))
+            })
+            .collect();
+
+        BuilderConfig {
+            version: CONFIG_VERSION,
+            allow_script_access: self.allow_script_access,
+            background_color: self.background_color.map(|color| color.to_rgb()),
+            letterbox: letterbox_name(self.letterbox).to_string(),
+            upgrade_to_https: self.upgrade_to_https,
+            compatibility_rules,
+            base_url: self.base_url.clone(),
+            show_menu: self.show_menu,
+            allow_fullscreen: self.allow_fullscreen,
+            stage_align: stage_align_name(self.stage_align),
+            force_align: self.force_align,
+            quality: quality_name(self.quality).to_string(),
+            scale: scale_name(self.scale).to_string(),
+            force_scale: self.force_scale,
+            frame_rate: self.frame_rate,
+            wmode: self.wmode.clone(),
+            log_level: self.log_level.as_str().to_ascii_lowercase(),
+            max_execution_duration: self.max_execution_duration.as_secs_f64(),
+            player_version: self.player_version,
+            preferred_renderer: self.preferred_renderer.clone(),
+            open_url_mode: open_url_mode_name(self.open_url_mode).to_string(),
+            allow_networking: networking_name(self.allow_networking).to_string(),
+            socket_proxy: self
+                .socket_proxy
+                .iter()
+                .map(|proxy| SocketProxyConfig {
+                    host: proxy.host.clone(),
+                    port: proxy.port,
+                    proxy_url: proxy.proxy_url.clone(),
+                    proxy_protocol: proxy_protocol_name(proxy.proxy_protocol).to_string(),
+                    tls: proxy.tls,
+                })
+                .collect(),
+            credential_allow_list,
+            player_runtime: player_runtime_name(self.player_runtime).to_string(),
+            volume: self.volume,
+            default_fonts: self
+                .default_fonts
+                .iter()
+                .map(|(font, names)| (default_font_name(*font).to_string(), names.clone()))
+                .collect(),
+            custom_fonts: self
+                .custom_fonts
+                .iter()
+                .map(|(name, data)| FontConfig {
+                    name: name.clone(),
+                    byte_length: data.len(),
+                    data: include_sensitive.then(|| BASE64_STANDARD.encode(data)),
+                })
+                .collect(),
+            bitmap_readback_mode: readback_mode_name(self.bitmap_readback_mode).to_string(),
+            max_native_windows: self.max_native_windows as u32,
+            low_latency_input: self.low_latency_input,
+            force_low_latency_input: self.force_low_latency_input,
+            storage_backend: self.storage.name().to_string(),
+            gamepad_button_mapping: self
+                .gamepad_button_mapping
+                .iter()
+                .map(|(button, key_code)| {
+                    (gamepad_button_name(*button).to_string(), key_code.value())
+                })
+                .collect(),
+            gamepad_mouse_speed: self.gamepad_mouse_speed,
+            key_remap: self
+                .key_remap
+                .iter()
+                .map(|(from, to)| (from.value().to_string(), to.value()))
+                .collect(),
+            allow_camera: self.allow_camera,
+            allow_microphone: self.allow_microphone,
+            context_menu_items: self
+                .context_menu_items
+                .iter()
+                .map(|(label, callback_id)| ContextMenuItemConfig {
+                    label: label.clone(),
+                    callback_id: callback_id.clone(),
+                })
+                .collect(),
+            fetch_handler: self.fetch_handler.is_some(),
+            adaptive_quality: self.adaptive_quality,
+            accessibility: self.accessibility,
+            log_callback: self.log_callback.is_some(),
+            background_behavior: background_behavior_name(self.background_behavior).to_string(),
+            allow_local_font_access: self.allow_local_font_access,
+            wait_for_full_load: self.wait_for_full_load,
+            load_allow_list: self.load_allow_list.clone(),
+            policy_file_mode: policy_file_mode_name(self.policy_file_mode).to_string(),
+            rsl_mirror: self.rsl_mirror.clone(),
+            capabilities,
+            url_parameters: self.url_parameters.clone(),
+            autoplay: autoplay_name(self.autoplay).to_string(),
+            unmute_overlay: unmute_overlay_name(self.unmute_overlay).to_string(),
+            compatibility_rules_url: self.compatibility_rules_url.clone(),
+            max_active_players: self.max_active_players,
+        }
+    }
+
+    /// Reconstructs a builder from a snapshot made by `to_config`.
+    ///
+    /// Sensitive values that were redacted from the snapshot are left at their defaults.
+    pub fn from_builder_config(config: BuilderConfig) -> Result<Self, ConfigError> {
+        if config.version > CONFIG_VERSION {
+            return Err(ConfigError::UnsupportedVersion(config.version));
+        }
+
+        let mut builder = Self::default();
+        builder.allow_script_access = config.allow_script_access;
+        builder.background_color = config.background_color.map(|rgb| Color::from_rgb(rgb, 255));
+        builder.letterbox = parse("letterbox", &config.letterbox, letterbox_name)?;
+        builder.upgrade_to_https = config.upgrade_to_https;
+        builder.compatibility_rules = CompatibilityRules::builtin_rules();
+        builder
+            .compatibility_rules
+            .retain_rule_sets(|name| config.compatibility_rules.get(name) != Some(&false));
+        builder.base_url = config.base_url;
+        builder.show_menu = config.show_menu;
+        builder.allow_fullscreen = config.allow_fullscreen;
+        builder.set_stage_align(&config.stage_align);
+        builder.force_align = config.force_align;
+        builder.quality = parse("quality", &config.quality, quality_name)?;
+        builder.scale = parse("scale", &config.scale, scale_name)?;
+        builder.force_scale = config.force_scale;
+        builder.frame_rate = config.frame_rate;
+        builder.wmode = config.wmode;
+        builder.log_level = config
+            .log_level
+            .parse()
+            .map_err(|_| ConfigError::InvalidValue {
+                option: "logLevel",
+                value: config.log_level.clone(),
+            })?;
+        builder.max_execution_duration = Duration::try_from_secs_f64(config.max_execution_duration)
+            .map_err(|_| ConfigError::InvalidValue {
+                option: "maxExecutionDuration",
+                value: config.max_execution_duration.to_string(),
+            })?;
+        builder.player_version = config.player_version;
+        builder.preferred_renderer = config.preferred_renderer;
+        builder.open_url_mode = parse("openUrlMode", &config.open_url_mode, open_url_mode_name)?;
+        builder.allow_networking =
+            parse("allowNetworking", &config.allow_networking, networking_name)?;
+        builder.socket_proxy = config
+            .socket_proxy
+            .into_iter()
+            .map(|proxy| {
+                Ok(SocketProxy {
+                    host: proxy.host,
+                    port: proxy.port,
+                    proxy_url: proxy.proxy_url,
+                    proxy_protocol: parse(
+                        "socketProxy",
+                        &proxy.proxy_protocol,
+                        proxy_protocol_name,
+                    )?,
+                    tls: proxy.tls,
+                })
+            })
+            .collect::<Result<_, ConfigError>>()?;
+        match config.credential_allow_list {
+            Sensitive::Included(list) => builder.credential_allow_list = list,
+            Sensitive::Redacted { redacted: 0 } => {}
+            Sensitive::Redacted { redacted } => {
+                tracing::warn!(
+                    "Config is missing {redacted} redacted credential allow list entries"
+                )
+            }
+        }
+        builder.player_runtime =
+            parse("playerRuntime", &config.player_runtime, player_runtime_name)?;
+        builder.volume = config.volume;
+        for (name, fonts) in config.default_fonts {
+            let font = parse("defaultFonts", &name, default_font_name)?;
+            builder.default_fonts.insert(font, fonts);
+        }
+        for font in config.custom_fonts {
+            let Some(data) = font.data else {
+                tracing::warn!(
+                    "Config is missing the data of font {} ({} bytes)",
+                    font.name,
+                    font.byte_length
+                );
+                continue;
+            };
+            let data = BASE64_STANDARD
+                .decode(&data)
+                .map_err(|_| ConfigError::InvalidValue {
+                    option: "customFonts",
+                    value: font.name.clone(),
+                })?;
+            builder.custom_fonts.push((font.name, data));
+        }
+        builder.bitmap_readback_mode = parse(
+            "bitmapReadbackMode",
+            &config.bitmap_readback_mode,
+            readback_mode_name,
+        )?;
+        builder.max_native_windows = config.max_native_windows as usize;
+        builder.low_latency_input = config.low_latency_input;
+        builder.force_low_latency_input = config.force_low_latency_input;
+        builder.storage = match config.storage_backend.as_str() {
+            "local" => StorageKind::Local,
+            "indexeddb" => StorageKind::IndexedDb,
+            "custom" => {
+                tracing::warn!("Config used a custom storage backend, using localStorage instead");
+                StorageKind::Local
+            }
+            _ => {
+                return Err(ConfigError::InvalidValue {
+                    option: "storageBackend",
+                    value: config.storage_backend,
+                })
+            }
+        };
+        for (button, key_code) in config.gamepad_button_mapping {
+            let button =
+                parse_gamepad_button(&button).ok_or_else(|| ConfigError::InvalidValue {
+                    option: "gamepadButtonMapping",
+                    value: button.clone(),
+                })?;
+            builder
+                .gamepad_button_mapping
+                .insert(button, KeyCode::from_code(key_code));
+        }
+        builder.gamepad_mouse_speed = config.gamepad_mouse_speed;
+        for (from, to) in config.key_remap {
+            let from = from.parse().map_err(|_| ConfigError::InvalidValue {
+                option: "keyRemap",
+                value: from.clone(),
+            })?;
+            builder
+                .key_remap
+                .insert(KeyCode::from_code(from), KeyCode::from_code(to));
+        }
+        builder.allow_camera = config.allow_camera;
+        builder.allow_microphone = config.allow_microphone;
+        builder.adaptive_quality = config.adaptive_quality;
+        builder.accessibility = config.accessibility;
+        builder.background_behavior = parse(
+            "backgroundBehavior",
+            &config.background_behavior,
+            background_behavior_name,
+        )?;
+        builder.allow_local_font_access = config.allow_local_font_access;
+        builder.wait_for_full_load = config.wait_for_full_load;
+        builder.load_allow_list = config.load_allow_list;
+        builder.policy_file_mode = parse(
+            "policyFileMode",
+            &config.policy_file_mode,
+            policy_file_mode_name,
+        )?;
+        builder.rsl_mirror = config.rsl_mirror;
+        for (name, value) in config.capabilities {
+            let Some(capability) = capability_mut(&mut builder.capabilities, &name) else {
+                return Err(ConfigError::InvalidValue {
+                    option: "capabilities",
+                    value: name,
+                });
+            };
+            *capability = Some(value);
+        }
+        builder.url_parameters = config.url_parameters;
+        builder.autoplay = parse("autoplay", &config.autoplay, autoplay_name)?;
+        builder.unmute_overlay =
+            parse("unmuteOverlay", &config.unmute_overlay, unmute_overlay_name)?;
+        builder.compatibility_rules_url = config.compatibility_rules_url;
+        builder.max_active_players = config.max_active_players;
+        if config.fetch_handler {
+            tracing::warn!("Config used a fetch handler, which can't be restored");
+        }
+        if config.log_callback {
+            tracing::warn!("Config used a log callback, which can't be restored");
+        }
+        builder.context_menu_items = config
+            .context_menu_items
+            .into_iter()
+            .map(|item| (item.label, item.callback_id))
+            .collect();
+
+        Ok(builder)
+    }
+}
+
+/// Finds the value of an option whose name is `value`, by trying every possible value.
+fn parse<T: Copy + AllValues>(
+    option: &'static str,
+    value: &str,
+    name: fn(T) -> &'static str,
+) -> Result<T, ConfigError> {
+    T::ALL
+        .iter()
+        .copied()
+        .find(|candidate| name(*candidate) == value)
+        .ok_or_else(|| ConfigError::InvalidValue {
+            option,
+            value: value.to_string(),
+        })
+}
+
+trait AllValues: Sized + 'static {
+    const ALL: &'static [Self];
+}
+
+impl AllValues for Letterbox {
+    const ALL: &'static [Self] = &[Letterbox::Off, Letterbox::Fullscreen, Letterbox::On];
+}
+
+impl AllValues for StageQuality {
+    const ALL: &'static [Self] = &[
+        StageQuality::Low,
+        StageQuality::Medium,
+        StageQuality::High,
+        StageQuality::Best,
+        StageQuality::High8x8,
+        StageQuality::High8x8Linear,
+        StageQuality::High16x16,
+        StageQuality::High16x16Linear,
+    ];
+}
+
+impl AllValues for StageScaleMode {
+    const ALL: &'static [Self] = &[
+        StageScaleMode::ExactFit,
+        StageScaleMode::NoBorder,
+        StageScaleMode::NoScale,
+        StageScaleMode::ShowAll,
+    ];
+}
+
+impl AllValues for OpenUrlMode {
+    const ALL: &'static [Self] = &[OpenUrlMode::Allow, OpenUrlMode::Confirm, OpenUrlMode::Deny];
+}
+
+impl AllValues for NetworkingAccessMode {
+    const ALL: &'static [Self] = &[
+        NetworkingAccessMode::All,
+        NetworkingAccessMode::Internal,
+        NetworkingAccessMode::None,
+    ];
+}
+
+impl AllValues for SocketProxyProtocol {
+    const ALL: &'static [Self] = &[
+        SocketProxyProtocol::WebSocket,
+        SocketProxyProtocol::WebTransport,
+    ];
+}
+
+impl AllValues for PlayerRuntime {
+    const ALL: &'static [Self] = &[PlayerRuntime::FlashPlayer, PlayerRuntime::AIR];
+}
+
+impl AllValues for DefaultFont {
+    const ALL: &'static [Self] = &[
+        DefaultFont::Sans,
+        DefaultFont::Serif,
+        DefaultFont::Typewriter,
+        DefaultFont::JapaneseGothic,
+        DefaultFont::JapaneseGothicMono,
+        DefaultFont::JapaneseMincho,
+        DefaultFont::Fallback,
+    ];
+}
+
+impl AllValues for BitmapReadbackMode {
+    const ALL: &'static [Self] = &[BitmapReadbackMode::Strict, BitmapReadbackMode::Throttled];
+}
+
+impl AllValues for BackgroundBehavior {
+    const ALL: &'static [Self] = &[
+        BackgroundBehavior::Pause,
+        BackgroundBehavior::Throttle,
+        BackgroundBehavior::Run,
+    ];
+}
+
+impl AllValues for Autoplay {
+    const ALL: &'static [Self] = &[Autoplay::On, Autoplay::Off, Autoplay::Auto];
+}
+
+impl AllValues for PolicyFileMode {
+    const ALL: &'static [Self] = &[
+        PolicyFileMode::Enforce,
+        PolicyFileMode::Warn,
+        PolicyFileMode::Ignore,
+    ];
+}
+
+impl AllValues for UnmuteOverlay {
+    const ALL: &'static [Self] = &[UnmuteOverlay::Visible, UnmuteOverlay::Hidden];
+}
+
+fn letterbox_name(value: Letterbox) -> &'static str {
+    match value {
+        Letterbox::Off => "off",
+        Letterbox::Fullscreen => "fullscreen",
+        Letterbox::On => "on",
+    }
+}
+
+fn stage_align_name(value: StageAlign) -> String {
+    [
+        (StageAlign::TOP, 'T'),
+        (StageAlign::BOTTOM, 'B'),
+        (StageAlign::LEFT, 'L'),
+        (StageAlign::RIGHT, 'R'),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| value.contains(*flag))
+    .map(|(_, c)| c)
+    .collect()
+}
+
+fn quality_name(value: StageQuality) -> &'static str {
+    match value {
+        StageQuality::Low => "low",
+        StageQuality::Medium => "medium",
+        StageQuality::High => "high",
+        StageQuality::Best => "best",
+        StageQuality::High8x8 => "8x8",
+        StageQuality::High8x8Linear => "8x8linear",
+        StageQuality::High16x16 => "16x16",
+        StageQuality::High16x16Linear => "16x16linear",
+    }
+}
+
+fn scale_name(value: StageScaleMode) -> &'static str {
+    match value {
+        StageScaleMode::ExactFit => "exactfit",
+        StageScaleMode::NoBorder => "noborder",
+        StageScaleMode::NoScale => "noscale",
+        StageScaleMode::ShowAll => "showall",
+    }
+}
+
+fn open_url_mode_name(value: OpenUrlMode) -> &'static str {
+    match value {
+        OpenUrlMode::Allow => "allow",
+        OpenUrlMode::Confirm => "confirm",
+        OpenUrlMode::Deny => "deny",
+    }
+}
+
+fn networking_name(value: NetworkingAccessMode) -> &'static str {
+    match value {
+        NetworkingAccessMode::All => "all",
+        NetworkingAccessMode::Internal => "internal",
+        NetworkingAccessMode::None => "none",
+    }
+}
+
+fn proxy_protocol_name(value: SocketProxyProtocol) -> &'static str {
+    match value {
+        SocketProxyProtocol::WebSocket => "websocket",
+        SocketProxyProtocol::WebTransport => "webtransport",
+    }
+}
+
+fn policy_file_mode_name(value: PolicyFileMode) -> &'static str {
+    match value {
+        PolicyFileMode::Enforce => "enforce",
+        PolicyFileMode::Warn => "warn",
+        PolicyFileMode::Ignore => "ignore",
+    }
+}
+
+fn background_behavior_name(value: BackgroundBehavior) -> &'static str {
+    match value {
+        BackgroundBehavior::Pause => "pause",
+        BackgroundBehavior::Throttle => "throttle",
+        BackgroundBehavior::Run => "run",
+    }
+}
+
+fn autoplay_name(value: Autoplay) -> &'static str {
+    match value {
+        Autoplay::On => "on",
+        Autoplay::Off => "off",
+        Autoplay::Auto => "auto",
+    }
+}
+
+fn unmute_overlay_name(value: UnmuteOverlay) -> &'static str {
+    match value {
+        UnmuteOverlay::Visible => "visible",
+        UnmuteOverlay::Hidden => "hidden",
+    }
+}
+
+fn player_runtime_name(value: PlayerRuntime) -> &'static str {
+    match value {
+        PlayerRuntime::FlashPlayer => "flashPlayer",
+        PlayerRuntime::AIR => "air",
+    }
+}
+
+fn default_font_name(value: DefaultFont) -> &'static str {
+    match value {
+        DefaultFont::Sans => "sans",
+        DefaultFont::Serif => "serif",
+        DefaultFont::Typewriter => "typewriter",
+        DefaultFont::JapaneseGothic => "japaneseGothic",
+        DefaultFont::JapaneseGothicMono => "japaneseGothicMono",
+        DefaultFont::JapaneseMincho => "japaneseMincho",
+        DefaultFont::Fallback => "fallback",
+    }
+}
+
+fn readback_mode_name(value: BitmapReadbackMode) -> &'static str {
+    match value {
+        BitmapReadbackMode::Strict => "strict",
+        BitmapReadbackMode::Throttled => "throttled",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small xorshift generator, so that failures are reproducible from the seed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+
+        fn bool(&mut self) -> bool {
+            self.below(2) == 1
+        }
+
+        fn pick<T: Copy>(&mut self, values: &[T]) -> T {
+            values[self.below(values.len() as u64) as usize]
+        }
+
+        fn string(&mut self) -> String {
+            let len = self.below(12);
+            (0..len)
+                .map(|_| self.pick(&['a', 'Z', '0', '.', '/', ':', ' ', 'é', '"']))
+                .collect()
+        }
+
+        fn option<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> Option<T> {
+            self.bool().then(|| f(self))
+        }
+    }
+
+    fn random_builder(rng: &mut Rng) -> RuffleInstanceBuilder {
+        let mut builder = RuffleInstanceBuilder::default();
+        builder.set_allow_script_access(rng.bool());
+        builder.set_background_color(rng.option(|rng| rng.below(0x1000000) as u32));
+        builder.set_letterbox(letterbox_name(rng.pick(Letterbox::ALL)));
+        builder.set_upgrade_to_https(rng.bool());
+        builder.set_compatibility_rules(rng.bool());
+        builder.set_base_url(rng.option(Rng::string));
+        builder.set_show_menu(rng.bool());
+        builder.set_allow_fullscreen(rng.bool());
+        builder.set_stage_align(rng.pick(&["", "t", "BR", "tl", "LRTB", "x"]));
+        builder.set_force_align(rng.bool());
+        builder.set_quality(quality_name(rng.pick(StageQuality::ALL)));
+        builder.set_scale(scale_name(rng.pick(StageScaleMode::ALL)));
+        builder.set_force_scale(rng.bool());
+        builder.set_frame_rate(rng.option(|rng| rng.below(12000) as f64 / 100.0));
+        builder.set_wmode(rng.option(Rng::string));
+        builder.set_log_level(rng.pick(&["error", "warn", "info", "debug", "trace"]));
+        builder.set_max_execution_duration(rng.below(100_000) as f64 / 1000.0);
+        builder.set_player_version(rng.option(|rng| rng.below(33) as u8));
+        builder.set_preferred_renderer(rng.option(Rng::string));
+        builder.set_open_url_mode(open_url_mode_name(rng.pick(OpenUrlMode::ALL)));
+        builder.set_allow_networking(networking_name(rng.pick(NetworkingAccessMode::ALL)));
+        for _ in 0..rng.below(3) {
+            let (host, port, url) = (rng.string(), rng.below(65536) as u16, rng.string());
+            let protocol = proxy_protocol_name(rng.pick(SocketProxyProtocol::ALL));
+            builder.add_socket_proxy(
+                host,
+                port,
+                url,
+                Some(protocol.to_string()),
+                Some(rng.bool()),
+            );
+        }
+        let allow_list = (0..rng.below(3)).map(|_| rng.string()).collect();
+        builder.set_credential_allow_list(allow_list);
+        builder.set_player_runtime(player_runtime_name(rng.pick(PlayerRuntime::ALL)));
+        builder.set_volume(rng.below(101) as f32 / 100.0);
+        for _ in 0..rng.below(3) {
+            let names = (0..rng.below(3)).map(|_| rng.string()).collect();
+            builder
+                .default_fonts
+                .insert(rng.pick(DefaultFont::ALL), names);
+        }
+        for _ in 0..rng.below(3) {
+            let data = (0..rng.below(64)).map(|_| rng.next() as u8).collect();
+            builder.add_font(rng.string(), data);
+        }
+        builder.set_bitmap_readback_mode(readback_mode_name(rng.pick(BitmapReadbackMode::ALL)));
+        builder.set_max_native_windows(rng.below(16) as u32);
+        builder.set_low_latency_input(rng.bool());
+        builder.set_force_low_latency_input(rng.bool());
+        for _ in 0..rng.below(4) {
+            let button = rng.pick(&["south", "start", "d-pad-up", "right-trigger2"]);
+            builder.set_gamepad_button_mapping(button, rng.below(256) as u32);
+        }
+        builder.set_gamepad_mouse_speed(rng.below(2000) as f64);
+        for _ in 0..rng.below(4) {
+            builder.set_key_remap(rng.below(256) as u32, rng.below(256) as u32);
+        }
+        builder.set_allow_camera(rng.bool());
+        builder.set_allow_microphone(rng.bool());
+        for _ in 0..rng.below(3) {
+            builder.add_context_menu_item(rng.string(), rng.string());
+        }
+        builder.set_adaptive_quality(rng.bool());
+        builder.set_accessibility(rng.bool());
+        builder
+            .set_background_behavior(background_behavior_name(rng.pick(BackgroundBehavior::ALL)));
+        builder.set_allow_local_font_access(rng.bool());
+        builder.set_wait_for_full_load(rng.bool());
+        builder.set_load_allow_list(
+            rng.option(|rng| (0..rng.below(3)).map(|_| rng.string()).collect()),
+        );
+        builder.set_policy_file_mode(policy_file_mode_name(rng.pick(PolicyFileMode::ALL)));
+        builder.set_rsl_mirror(rng.option(Rng::string));
+        for name in CAPABILITY_NAMES {
+            builder.set_capability(name, rng.option(|rng| rng.string()));
+        }
+        builder.url_parameters = (0..rng.below(3))
+            .map(|_| (rng.string(), rng.string()))
+            .collect();
+        builder.set_autoplay(autoplay_name(rng.pick(Autoplay::ALL)));
+        builder.set_unmute_overlay(unmute_overlay_name(rng.pick(UnmuteOverlay::ALL)));
+        builder.set_compatibility_rules_url(rng.option(Rng::string));
+        builder.set_max_active_players(rng.option(|rng| rng.below(8) as u32));
+        builder.storage = if rng.bool() {
+            StorageKind::IndexedDb
+        } else {
+            StorageKind::Local
+        };
+        builder
+    }
+
+    #[test]
+    fn round_trips_randomized_builders() {
+        for seed in 1..=500 {
+            let mut rng = Rng(seed);
+            let builder = random_builder(&mut rng);
+            let config = builder.to_config(true);
+
+            let json = serde_json::to_string(&config).unwrap();
+            let parsed: BuilderConfig = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, config, "seed {seed}");
+
+            let rebuilt = RuffleInstanceBuilder::from_builder_config(parsed).unwrap();
+            assert_eq!(rebuilt.to_config(true), config, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn redacts_sensitive_values() {
+        let mut builder = RuffleInstanceBuilder::default();
+        builder.set_credential_allow_list(vec!["https://example.com".to_string()]);
+        builder.add_font("Secret".to_string(), vec![1, 2, 3]);
+
+        let config = builder.to_config(false);
+        assert_eq!(
+            config.credential_allow_list,
+            Sensitive::Redacted { redacted: 1 }
+        );
+        assert_eq!(
+            config.custom_fonts,
+            vec![FontConfig {
+                name: "Secret".to_string(),
+                byte_length: 3,
+                data: None,
+            }]
+        );
+
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: BuilderConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.credential_allow_list, config.credential_allow_list);
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let mut config = RuffleInstanceBuilder::default().to_config(false);
+        config.version = CONFIG_VERSION + 1;
+        assert!(RuffleInstanceBuilder::from_builder_config(config).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! Reading movie parameters out of a URL, the way Flash Player read them.
+
+/// Parses the query string of `url` into movie parameters.
+///
+/// This follows Flash Player rather than the URL standard:
+/// - Pairs are separated by `&`, and by `&amp;` as found in URLs copied out of HTML.
+/// - `+` is a space, and escapes are decoded exactly once, so `%2520` stays `%20`.
+/// - A `%` that isn't followed by two hex digits is kept as is.
+/// - Decoded bytes that aren't valid UTF-8 are read as Latin-1, as old pages relied on.
+/// - A key without `=` has an empty value, and pairs without a key are dropped.
+pub(crate) fn parse_url_parameters(url: &str) -> Vec<(String, String)> {
+    let Some((_, query)) = url.split_once('?') else {
+        return vec![];
+    };
+    let query = query.split_once('#').map_or(query, |(query, _)| query);
+    query
+        .replace("&amp;", "&")
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let key = decode(key);
+            (!key.is_empty()).then(|| (key, decode(value)))
+        })
+        .collect()
+}
+
+fn decode(input: &str) -> String {
+    let input = input.as_bytes();
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        let escaped = input
+            .get(i + 1..i + 3)
+            .filter(|hex| input[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(byte) = escaped {
+            bytes.push(byte);
+            i += 3;
+        } else {
+            bytes.push(if input[i] == b'+' { b' ' } else { input[i] });
+            i += 1;
+        }
+    }
+    String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().into_iter().map(char::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reads_the_query_string() {
+        assert_eq!(
+            parse_url_parameters("https://example.com/game.swf?level=3&name=a+b#frame"),
+            pairs(&[("level", "3"), ("name", "a b")])
+        );
+        assert_eq!(parse_url_parameters("?a=1"), pairs(&[("a", "1")]));
+        assert_eq!(
+            parse_url_parameters("https://example.com/game.swf"),
+            pairs(&[])
+        );
+    }
+
+    #[test]
+    fn follows_flash_quirks() {
+        assert_eq!(
+            parse_url_parameters("?a=1&amp;b=2&&=3&flag&c=x=y"),
+            pairs(&[("a", "1"), ("b", "2"), ("flag", ""), ("c", "x=y")])
+        );
+        assert_eq!(
+            parse_url_parameters("?plus=%2B&twice=%2520&bad=%zz%4&utf8=%C3%A9&latin1=%E9"),
+            pairs(&[
+                ("plus", "+"),
+                ("twice", "%20"),
+                ("bad", "%zz%4"),
+                ("utf8", "é"),
+                ("latin1", "é"),
+            ])
+        );
+    }
+}
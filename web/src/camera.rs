@@ -0,0 +1,356 @@
+use js_sys::{Array, Object, Reflect};
+use ruffle_core::backend::camera::{CameraBackend, CameraFrame, CaptureStatus};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, HtmlVideoElement, MediaDeviceInfo,
+    MediaDeviceKind, MediaDevices, MediaStream, MediaStreamConstraints, MediaStreamTrack,
+};
+
+/// `HTMLMediaElement.HAVE_CURRENT_DATA`
+const HAVE_CURRENT_DATA: u16 = 2;
+
+/// A camera or microphone.
+pub(crate) struct MediaDevice {
+    /// `None` for the browser's default device.
+    pub(crate) device_id: Option<String>,
+    pub(crate) label: String,
+}
+
+/// A camera that a movie asked to capture from.
+struct CaptureState {
+    status: CaptureStatus,
+    stream: Option<MediaStream>,
+    video: Option<HtmlVideoElement>,
+}
+
+impl CaptureState {
+    fn stop(&mut self) {
+        self.status = CaptureStatus::Closed;
+        if let Some(video) = self.video.take() {
+            video.set_src_object(None);
+        }
+        if let Some(stream) = self.stream.take() {
+            stop_tracks(&stream);
+        }
+    }
+}
+
+struct Capture {
+    /// Shared with the pending `getUserMedia` call.
+    state: Rc<RefCell<CaptureState>>,
+
+    /// The media time of the video frame that was last read back.
+    last_time: f64,
+    frame: Option<(u64, CameraFrame)>,
+}
+
+/// Captures cameras with `getUserMedia`, reading their frames back through a 2D canvas.
+pub struct WebCameraBackend {
+    media_devices: Option<MediaDevices>,
+    devices: Rc<RefCell<Vec<MediaDevice>>>,
+    captures: HashMap<usize, Capture>,
+    canvas: Option<(HtmlCanvasElement, CanvasRenderingContext2d)>,
+}
+
+impl WebCameraBackend {
+    pub fn new() -> Self {
+        let media_devices = web_sys::window().and_then(|w| w.navigator().media_devices().ok());
+
+        // Until the cameras are enumerated, offer the browser's default one.
+        let devices = Rc::new(RefCell::new(vec![]));
+        if let Some(media_devices) = &media_devices {
+            devices.borrow_mut().push(MediaDevice {
+                device_id: None,
+                label: "Default".to_string(),
+            });
+
+            let media_devices = media_devices.clone();
+            let devices = devices.clone();
+            spawn_local(async move {
+                match enumerate_devices(&media_devices, MediaDeviceKind::Videoinput, "Camera").await
+                {
+                    Ok(cameras) if !cameras.is_empty() => *devices.borrow_mut() = cameras,
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Couldn't enumerate cameras: {:?}", e),
+                }
+            });
+        }
+
+        Self {
+            media_devices,
+            devices,
+            captures: HashMap::new(),
+            canvas: None,
+        }
+    }
+
+    fn canvas(&mut self) -> Option<&(HtmlCanvasElement, CanvasRenderingContext2d)> {
+        if self.canvas.is_none() {
+            let document = web_sys::window()?.document()?;
+            let canvas: HtmlCanvasElement =
+                document.create_element("canvas").ok()?.dyn_into().ok()?;
+            let options = Object::new();
+            let _ = Reflect::set(&options, &"willReadFrequently".into(), &true.into());
+            let context = canvas
+                .get_context_with_context_options("2d", &options)
+                .ok()??
+                .dyn_into()
+                .ok()?;
+            self.canvas = Some((canvas, context));
+        }
+
+        self.canvas.as_ref()
+    }
+}
+
+impl Default for WebCameraBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for WebCameraBackend {
+    fn drop(&mut self) {
+        // Turn the cameras off when the player is destroyed.
+        for capture in self.captures.values() {
+            capture.state.borrow_mut().stop();
+        }
+    }
+}
+
+impl CameraBackend for WebCameraBackend {
+    fn names(&self) -> Vec<String> {
+        self.devices
+            .borrow()
+            .iter()
+            .map(|device| device.label.clone())
+            .collect()
+    }
+
+    fn open(&mut self, index: usize, width: u32, height: u32, fps: f64) {
+        self.close(index);
+
+        let Some(media_devices) = self.media_devices.clone() else {
+            return;
+        };
+        let Some(device_id) = self
+            .devices
+            .borrow()
+            .get(index)
+            .map(|device| device.device_id.clone())
+        else {
+            return;
+        };
+
+        let state = Rc::new(RefCell::new(CaptureState {
+            status: CaptureStatus::Pending,
+            stream: None,
+            video: None,
+        }));
+        self.captures.insert(
+            index,
+            Capture {
+                state: state.clone(),
+                last_time: f64::NAN,
+                frame: None,
+            },
+        );
+
+        let constraints = video_constraints(device_id.as_deref(), width, height, fps);
+        spawn_local(async move {
+            let stream = match get_user_media(&media_devices, &constraints).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::warn!("Couldn't open camera {index}: {:?}", e);
+                    let mut state = state.borrow_mut();
+                    if state.status == CaptureStatus::Pending {
+                        state.status = CaptureStatus::Denied;
+                    }
+                    return;
+                }
+            };
+
+            let mut state = state.borrow_mut();
+            state.stream = Some(stream.clone());
+            if state.status != CaptureStatus::Pending {
+                // The movie stopped capturing while waiting for the user.
+                state.stop();
+                return;
+            }
+
+            match create_video(&stream) {
+                Ok(video) => {
+                    state.video = Some(video);
+                    state.status = CaptureStatus::Active;
+                }
+                Err(e) => {
+                    tracing::error!("Couldn't play camera {index}: {:?}", e);
+                    state.stop();
+                    state.status = CaptureStatus::Denied;
+                }
+            }
+        });
+    }
+
+    fn close(&mut self, index: usize) {
+        if let Some(capture) = self.captures.remove(&index) {
+            capture.state.borrow_mut().stop();
+        }
+    }
+
+    fn status(&self, index: usize) -> CaptureStatus {
+        self.captures
+            .get(&index)
+            .map(|capture| capture.state.borrow().status)
+            .unwrap_or(CaptureStatus::Closed)
+    }
+
+    fn latest_frame(&mut self, index: usize) -> Option<(u64, &CameraFrame)> {
+        let video = self.captures.get(&index)?.state.borrow().video.clone();
+        if let Some(video) = video {
+            let time = video.current_time();
+            let is_new = video.ready_state() >= HAVE_CURRENT_DATA
+                && self.captures.get(&index)?.last_time != time;
+            if is_new {
+                let captured = self
+                    .canvas()
+                    .and_then(|(canvas, context)| read_frame(canvas, context, &video));
+                let capture = self.captures.get_mut(&index)?;
+                capture.last_time = time;
+                if let Some(frame) = captured {
+                    let number = capture.frame.as_ref().map_or(0, |(n, _)| n + 1);
+                    capture.frame = Some((number, frame));
+                }
+            }
+        }
+
+        let (number, frame) = self.captures.get(&index)?.frame.as_ref()?;
+        Some((*number, frame))
+    }
+}
+
+/// Lists the devices of the given kind. Devices without a label are named after `kind_name`.
+pub(crate) async fn enumerate_devices(
+    media_devices: &MediaDevices,
+    kind: MediaDeviceKind,
+    kind_name: &str,
+) -> Result<Vec<MediaDevice>, JsValue> {
+    let devices: Array = JsFuture::from(media_devices.enumerate_devices()?)
+        .await?
+        .dyn_into()?;
+
+    let mut found = vec![];
+    for device in devices.iter() {
+        let Ok(device) = device.dyn_into::<MediaDeviceInfo>() else {
+            continue;
+        };
+        if device.kind() != kind {
+            continue;
+        }
+
+        // Browsers hide the IDs and labels of devices until the user allows using one.
+        let device_id = device.device_id();
+        let label = device.label();
+        found.push(MediaDevice {
+            device_id: (!device_id.is_empty()).then_some(device_id),
+            label: if label.is_empty() {
+                format!("{kind_name} {}", found.len() + 1)
+            } else {
+                label
+            },
+        });
+    }
+
+    Ok(found)
+}
+
+fn video_constraints(
+    device_id: Option<&str>,
+    width: u32,
+    height: u32,
+    fps: f64,
+) -> MediaStreamConstraints {
+    fn ideal(value: JsValue) -> Object {
+        let constraint = Object::new();
+        let _ = Reflect::set(&constraint, &"ideal".into(), &value);
+        constraint
+    }
+
+    let video = Object::new();
+    let _ = Reflect::set(&video, &"width".into(), &ideal(width.into()));
+    let _ = Reflect::set(&video, &"height".into(), &ideal(height.into()));
+    let _ = Reflect::set(&video, &"frameRate".into(), &ideal(fps.into()));
+    if let Some(device_id) = device_id {
+        let exact = Object::new();
+        let _ = Reflect::set(&exact, &"exact".into(), &device_id.into());
+        let _ = Reflect::set(&video, &"deviceId".into(), &exact);
+    }
+
+    let constraints = MediaStreamConstraints::new();
+    constraints.set_audio(&false.into());
+    constraints.set_video(&video);
+    constraints
+}
+
+pub(crate) async fn get_user_media(
+    media_devices: &MediaDevices,
+    constraints: &MediaStreamConstraints,
+) -> Result<MediaStream, JsValue> {
+    JsFuture::from(media_devices.get_user_media_with_constraints(constraints)?)
+        .await?
+        .dyn_into()
+}
+
+pub(crate) fn stop_tracks(stream: &MediaStream) {
+    for track in stream.get_tracks().iter() {
+        if let Ok(track) = track.dyn_into::<MediaStreamTrack>() {
+            track.stop();
+        }
+    }
+}
+
+/// Creates a detached video element that plays the stream, so that its frames can be drawn.
+fn create_video(stream: &MediaStream) -> Result<HtmlVideoElement, JsValue> {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or("Expected document")?;
+    let video: HtmlVideoElement = document.create_element("video")?.dyn_into()?;
+    video.set_muted(true);
+    video.set_attribute("playsinline", "")?;
+    video.set_src_object(Some(stream));
+    let _ = video.play()?;
+    Ok(video)
+}
+
+fn read_frame(
+    canvas: &HtmlCanvasElement,
+    context: &CanvasRenderingContext2d,
+    video: &HtmlVideoElement,
+) -> Option<CameraFrame> {
+    let width = video.video_width();
+    let height = video.video_height();
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    if canvas.width() != width || canvas.height() != height {
+        canvas.set_width(width);
+        canvas.set_height(height);
+    }
+    context
+        .draw_image_with_html_video_element(video, 0.0, 0.0)
+        .ok()?;
+    let image_data = context
+        .get_image_data(0.0, 0.0, width.into(), height.into())
+        .ok()?;
+
+    Some(CameraFrame {
+        width,
+        height,
+        rgba: image_data.data().0,
+    })
+}
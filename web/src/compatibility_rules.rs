@@ -0,0 +1,88 @@
+//! Downloading compatibility rule sets, so that fixes for specific games can ship
+//! without a new release of Ruffle.
+
+use ruffle_core::compatibility_rules::RuleSetDocument;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Response, Storage};
+
+/// How long a downloaded ruleset is used before it's downloaded again, in milliseconds.
+const CACHE_DURATION: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+const CACHE_KEY_PREFIX: &str = "ruffle-compatibility-rules:";
+
+/// A downloaded ruleset, as kept in `localStorage`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedRuleSets {
+    /// When it was downloaded, in milliseconds since the Unix epoch.
+    fetched_at: f64,
+    document: String,
+}
+
+/// Returns the JSON ruleset at `url`, from the cache while it's fresh.
+///
+/// If it can't be downloaded, an expired copy from the cache is used instead.
+pub async fn load(url: &str) -> Option<RuleSetDocument> {
+    let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+    let key = format!("{CACHE_KEY_PREFIX}{url}");
+    let cached: Option<CachedRuleSets> = storage
+        .as_ref()
+        .and_then(|storage| storage.get_item(&key).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok());
+    let now = js_sys::Date::now();
+
+    if let Some(cached) = &cached {
+        if now - cached.fetched_at < CACHE_DURATION {
+            if let Some(document) = parse(&cached.document) {
+                return Some(document);
+            }
+        }
+    }
+
+    match fetch(url).await {
+        Ok(text) => {
+            if let Some(document) = parse(&text) {
+                if let Some(storage) = &storage {
+                    store(storage, &key, now, text);
+                }
+                return Some(document);
+            }
+        }
+        Err(e) => tracing::warn!("Unable to download compatibility rules from {url}: {e:?}"),
+    }
+    cached.and_then(|cached| parse(&cached.document))
+}
+
+fn parse(text: &str) -> Option<RuleSetDocument> {
+    serde_json::from_str(text)
+        .inspect_err(|e| tracing::warn!("Invalid compatibility rules: {e}"))
+        .ok()
+}
+
+fn store(storage: &Storage, key: &str, fetched_at: f64, document: String) {
+    let cached = CachedRuleSets {
+        fetched_at,
+        document,
+    };
+    if let Ok(json) = serde_json::to_string(&cached) {
+        if let Err(e) = storage.set_item(key, &json) {
+            tracing::warn!("Unable to cache compatibility rules: {e:?}");
+        }
+    }
+}
+
+async fn fetch(url: &str) -> Result<String, JsValue> {
+    let window = web_sys::window().ok_or("Expected window")?;
+    let response: Response = JsFuture::from(window.fetch_with_str(url))
+        .await?
+        .dyn_into()?;
+    if !response.ok() {
+        return Err(format!("HTTP status {}", response.status()).into());
+    }
+    JsFuture::from(response.text()?)
+        .await?
+        .as_string()
+        .ok_or_else(|| "Expected text".into())
+}
@@ -1,5 +1,5 @@
 use crate::{JavascriptPlayer, CURRENT_CONTEXT};
-use js_sys::{Array, Object};
+use js_sys::{Array, ArrayBuffer, Date, Object, Uint8Array};
 use ruffle_core::context::UpdateContext;
 use ruffle_core::external::{
     ExternalInterfaceProvider, FsCommandProvider, Value as ExternalValue, Value,
@@ -86,6 +86,10 @@ pub fn js_to_external_value(js: &JsValue) -> ExternalValue {
         ExternalValue::Null
     } else if js.is_undefined() {
         ExternalValue::Undefined
+    } else if let Some(date) = js.dyn_ref::<Date>() {
+        ExternalValue::Date(date.get_time())
+    } else if js.is_instance_of::<ArrayBuffer>() || js.is_instance_of::<Uint8Array>() {
+        ExternalValue::ByteArray(Uint8Array::new(js).to_vec())
     } else {
         let mut values = BTreeMap::new();
         for entry in Object::entries(&Object::from(js.to_owned())).values() {
@@ -127,5 +131,7 @@ pub fn external_to_js_value(external: ExternalValue) -> JsValue {
             }
             array.into()
         }
+        Value::Date(time) => Date::new(&JsValue::from_f64(time)).into(),
+        Value::ByteArray(bytes) => Uint8Array::from(&bytes[..]).buffer().into(),
     }
 }
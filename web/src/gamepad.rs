@@ -0,0 +1,259 @@
+//! Gamepad input, polled from the Gamepad API.
+
+use ruffle_core::events::GamepadButton;
+use ruffle_core::PlayerEvent;
+use std::collections::HashSet;
+use wasm_bindgen::JsCast;
+use web_sys::Navigator;
+
+/// The buttons of the "standard" gamepad mapping, by index.
+///
+/// See <https://w3c.github.io/gamepad/#remapping>.
+const STANDARD_BUTTONS: [(u32, GamepadButton); 14] = [
+    (0, GamepadButton::South),
+    (1, GamepadButton::East),
+    (2, GamepadButton::West),
+    (3, GamepadButton::North),
+    (4, GamepadButton::LeftTrigger),
+    (5, GamepadButton::RightTrigger),
+    (6, GamepadButton::LeftTrigger2),
+    (7, GamepadButton::RightTrigger2),
+    (8, GamepadButton::Select),
+    (9, GamepadButton::Start),
+    (12, GamepadButton::DPadUp),
+    (13, GamepadButton::DPadDown),
+    (14, GamepadButton::DPadLeft),
+    (15, GamepadButton::DPadRight),
+];
+
+/// How far the left stick must be pushed to press a D-pad direction.
+const STICK_THRESHOLD: f64 = 0.5;
+
+/// How far the right stick must be pushed before it moves the mouse.
+const STICK_DEAD_ZONE: f64 = 0.15;
+
+/// The name of a gamepad button in the `gamepadButtonMapping` option.
+///
+/// These are the same names as the desktop `--gamepad-button` option.
+pub fn gamepad_button_name(button: GamepadButton) -> &'static str {
+    match button {
+        GamepadButton::South => "south",
+        GamepadButton::East => "east",
+        GamepadButton::North => "north",
+        GamepadButton::West => "west",
+        GamepadButton::LeftTrigger => "left-trigger",
+        GamepadButton::LeftTrigger2 => "left-trigger2",
+        GamepadButton::RightTrigger => "right-trigger",
+        GamepadButton::RightTrigger2 => "right-trigger2",
+        GamepadButton::Select => "select",
+        GamepadButton::Start => "start",
+        GamepadButton::DPadUp => "d-pad-up",
+        GamepadButton::DPadDown => "d-pad-down",
+        GamepadButton::DPadLeft => "d-pad-left",
+        GamepadButton::DPadRight => "d-pad-right",
+    }
+}
+
+pub fn parse_gamepad_button(name: &str) -> Option<GamepadButton> {
+    STANDARD_BUTTONS
+        .iter()
+        .map(|(_, button)| *button)
+        .find(|button| gamepad_button_name(*button) == name)
+}
+
+/// The D-pad directions that the left stick acts as, at the given position.
+fn stick_directions(x: f64, y: f64) -> impl Iterator<Item = GamepadButton> {
+    [
+        (y <= -STICK_THRESHOLD, GamepadButton::DPadUp),
+        (y >= STICK_THRESHOLD, GamepadButton::DPadDown),
+        (x <= -STICK_THRESHOLD, GamepadButton::DPadLeft),
+        (x >= STICK_THRESHOLD, GamepadButton::DPadRight),
+    ]
+    .into_iter()
+    .filter_map(|(pushed, button)| pushed.then_some(button))
+}
+
+/// Turns the state of every connected gamepad into player events.
+///
+/// All gamepads act as one: a button is down while it's held on any of them.
+/// The left stick doubles as the D-pad, and the right stick may move the mouse.
+pub struct GamepadPoller {
+    pressed: HashSet<GamepadButton>,
+
+    /// How fast the right stick moves the mouse, in pixels per second. Zero disables it.
+    mouse_speed: f64,
+
+    /// Where the right stick has moved the mouse to.
+    pointer: Option<(f64, f64)>,
+}
+
+impl GamepadPoller {
+    pub fn new(mouse_speed: f64) -> Self {
+        Self {
+            pressed: HashSet::new(),
+            mouse_speed,
+            pointer: None,
+        }
+    }
+
+    /// Polls every gamepad, and returns the events for what changed since the last poll.
+    ///
+    /// `dt` is the time since the last poll in milliseconds, and `viewport` the size of the
+    /// player in device pixels.
+    pub fn poll(
+        &mut self,
+        navigator: &Navigator,
+        dt: f64,
+        viewport: (f64, f64),
+    ) -> Vec<PlayerEvent> {
+        let Ok(gamepads) = navigator.get_gamepads() else {
+            return vec![];
+        };
+
+        let mut pressed = HashSet::new();
+        let mut pointer_velocity = (0.0, 0.0);
+        for gamepad in gamepads.iter() {
+            let Ok(gamepad) = gamepad.dyn_into::<web_sys::Gamepad>() else {
+                continue;
+            };
+            if !gamepad.connected() || gamepad.mapping() != web_sys::GamepadMappingType::Standard {
+                continue;
+            }
+
+            let buttons = gamepad.buttons();
+            for (index, button) in STANDARD_BUTTONS {
+                let is_pressed = buttons
+                    .get(index)
+                    .dyn_into::<web_sys::GamepadButton>()
+                    .is_ok_and(|button| button.pressed());
+                if is_pressed {
+                    pressed.insert(button);
+                }
+            }
+
+            let axes: Vec<f64> = gamepad
+                .axes()
+                .iter()
+                .map(|axis| axis.as_f64().unwrap_or_default())
+                .collect();
+            if let [left_x, left_y, right_x, right_y, ..] = axes[..] {
+                pressed.extend(stick_directions(left_x, left_y));
+                if right_x.hypot(right_y) >= STICK_DEAD_ZONE {
+                    pointer_velocity.0 += right_x;
+                    pointer_velocity.1 += right_y;
+                }
+            }
+        }
+
+        let mut events = self.update_buttons(pressed);
+        if let Some(event) = self.move_pointer(pointer_velocity, dt, viewport) {
+            events.push(event);
+        }
+        events
+    }
+
+    /// Returns the events for the buttons that changed, and remembers the new state.
+    fn update_buttons(&mut self, pressed: HashSet<GamepadButton>) -> Vec<PlayerEvent> {
+        let released = self
+            .pressed
+            .difference(&pressed)
+            .map(|&button| PlayerEvent::GamepadButtonUp { button });
+        let newly_pressed = pressed
+            .difference(&self.pressed)
+            .map(|&button| PlayerEvent::GamepadButtonDown { button });
+        let events = released.chain(newly_pressed).collect();
+        self.pressed = pressed;
+        events
+    }
+
+    fn move_pointer(
+        &mut self,
+        (velocity_x, velocity_y): (f64, f64),
+        dt: f64,
+        (width, height): (f64, f64),
+    ) -> Option<PlayerEvent> {
+        if self.mouse_speed <= 0.0 || (velocity_x == 0.0 && velocity_y == 0.0) {
+            return None;
+        }
+
+        let (x, y) = self.pointer.unwrap_or((width / 2.0, height / 2.0));
+        let distance = self.mouse_speed * dt / 1000.0;
+        let x = (x + velocity_x * distance).clamp(0.0, width);
+        let y = (y + velocity_y * distance).clamp(0.0, height);
+        self.pointer = Some((x, y));
+        Some(PlayerEvent::MouseMove { x, y })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn button_names_round_trip() {
+        for (_, button) in STANDARD_BUTTONS {
+            assert_eq!(
+                parse_gamepad_button(gamepad_button_name(button)),
+                Some(button)
+            );
+        }
+        assert_eq!(parse_gamepad_button("DPadUp"), None);
+    }
+
+    #[test]
+    fn left_stick_acts_as_d_pad() {
+        let directions = |x, y| stick_directions(x, y).collect::<Vec<_>>();
+        assert_eq!(directions(0.2, -0.3), vec![]);
+        assert_eq!(directions(0.0, -1.0), vec![GamepadButton::DPadUp]);
+        assert_eq!(
+            directions(0.7, 0.7),
+            vec![GamepadButton::DPadDown, GamepadButton::DPadRight]
+        );
+    }
+
+    #[test]
+    fn reports_changed_buttons_only() {
+        let mut poller = GamepadPoller::new(0.0);
+        let events = poller.update_buttons(HashSet::from([GamepadButton::South]));
+        assert!(matches!(
+            events[..],
+            [PlayerEvent::GamepadButtonDown {
+                button: GamepadButton::South
+            }]
+        ));
+
+        let events =
+            poller.update_buttons(HashSet::from([GamepadButton::South, GamepadButton::Start]));
+        assert!(matches!(
+            events[..],
+            [PlayerEvent::GamepadButtonDown {
+                button: GamepadButton::Start
+            }]
+        ));
+
+        let events = poller.update_buttons(HashSet::new());
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|event| matches!(event, PlayerEvent::GamepadButtonUp { .. })));
+    }
+
+    #[test]
+    fn right_stick_moves_pointer_within_viewport() {
+        let mut poller = GamepadPoller::new(1000.0);
+        assert!(matches!(
+            poller.move_pointer((1.0, 0.0), 100.0, (400.0, 300.0)),
+            Some(PlayerEvent::MouseMove { x, y }) if x == 300.0 && y == 150.0
+        ));
+        assert!(matches!(
+            poller.move_pointer((1.0, -1.0), 1000.0, (400.0, 300.0)),
+            Some(PlayerEvent::MouseMove { x, y }) if x == 400.0 && y == 0.0
+        ));
+        assert!(poller
+            .move_pointer((0.0, 0.0), 100.0, (400.0, 300.0))
+            .is_none());
+        assert!(GamepadPoller::new(0.0)
+            .move_pointer((1.0, 0.0), 100.0, (400.0, 300.0))
+            .is_none());
+    }
+}
@@ -0,0 +1,218 @@
+//! Options for `RuffleHandle::run_hash_session`, as given by JavaScript.
+
+use ruffle_core::events::{KeyCode, MouseButton, MouseWheelDelta};
+use ruffle_core::hash_session::HashSessionOptions;
+use ruffle_core::PlayerEvent;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The largest resolution that frames may be hashed at, in either dimension.
+const MAX_RESOLUTION: u32 = 4096;
+
+#[derive(Debug, Error)]
+pub enum HashSessionError {
+    #[error("Invalid hash session options: {0}")]
+    InvalidOptions(String),
+
+    #[error(
+        "Resolution must be between 1x1 and {}x{}, got {}x{}",
+        MAX_RESOLUTION,
+        MAX_RESOLUTION,
+        .0,
+        .1
+    )]
+    InvalidResolution(u32, u32),
+
+    #[error("Unknown input event type \"{0}\" in frame {1}")]
+    UnknownInput(String, u32),
+
+    #[error("A hash session is already running")]
+    AlreadyRunning,
+
+    #[error("The hash session was cancelled")]
+    Cancelled,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A recorded input event. Coordinates are in pixels, at the resolution of the session.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedInput {
+    /// The frame that this event is delivered before.
+    pub frame: u32,
+
+    /// One of "keyDown", "keyUp", "textInput", "mouseMove", "mouseDown", "mouseUp",
+    /// "mouseLeave" or "mouseWheel".
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub key_code: u32,
+    #[serde(default)]
+    pub key_char: Option<char>,
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+
+    /// One of "left", "middle" or "right". Defaults to "left".
+    #[serde(default)]
+    pub button: Option<String>,
+
+    /// The number of lines scrolled by a "mouseWheel" event.
+    #[serde(default)]
+    pub delta: f64,
+}
+
+impl RecordedInput {
+    fn to_event(&self) -> Result<PlayerEvent, HashSessionError> {
+        let key_code = KeyCode::from_code(self.key_code);
+        let (x, y) = (self.x, self.y);
+        let button = match self.button.as_deref() {
+            None | Some("left") => MouseButton::Left,
+            Some("middle") => MouseButton::Middle,
+            Some("right") => MouseButton::Right,
+            Some(_) => MouseButton::Unknown,
+        };
+        let unknown = || HashSessionError::UnknownInput(self.kind.clone(), self.frame);
+
+        Ok(match self.kind.as_str() {
+            "keyDown" => PlayerEvent::KeyDown {
+                key_code,
+                key_char: self.key_char,
+            },
+            "keyUp" => PlayerEvent::KeyUp {
+                key_code,
+                key_char: self.key_char,
+            },
+            "textInput" => PlayerEvent::TextInput {
+                codepoint: self.key_char.ok_or_else(unknown)?,
+            },
+            "mouseMove" => PlayerEvent::MouseMove { x, y },
+            "mouseDown" => PlayerEvent::MouseDown {
+                x,
+                y,
+                button,
+                index: None,
+            },
+            "mouseUp" => PlayerEvent::MouseUp { x, y, button },
+            "mouseLeave" => PlayerEvent::MouseLeave,
+            "mouseWheel" => PlayerEvent::MouseWheel {
+                delta: MouseWheelDelta::Lines(self.delta),
+            },
+            _ => return Err(unknown()),
+        })
+    }
+}
+
+/// The options object passed to `runHashSession`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashSessionConfig {
+    pub frames: u32,
+    pub resolution: Resolution,
+    #[serde(default)]
+    pub seed: u32,
+
+    /// Defaults to `true`.
+    #[serde(default)]
+    pub perceptual: Option<bool>,
+    #[serde(default)]
+    pub input_recording: Vec<RecordedInput>,
+}
+
+impl HashSessionConfig {
+    pub fn into_options(self) -> Result<HashSessionOptions, HashSessionError> {
+        let Resolution { width, height } = self.resolution;
+        if !(1..=MAX_RESOLUTION).contains(&width) || !(1..=MAX_RESOLUTION).contains(&height) {
+            return Err(HashSessionError::InvalidResolution(width, height));
+        }
+
+        let inputs = self
+            .input_recording
+            .iter()
+            .map(|input| Ok((input.frame, input.to_event()?)))
+            .collect::<Result<_, HashSessionError>>()?;
+
+        Ok(HashSessionOptions {
+            frames: self.frames,
+            width,
+            height,
+            seed: self.seed.into(),
+            perceptual: self.perceptual.unwrap_or(true),
+            inputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Result<HashSessionOptions, HashSessionError> {
+        serde_json::from_str::<HashSessionConfig>(json)
+            .map_err(|e| HashSessionError::InvalidOptions(e.to_string()))?
+            .into_options()
+    }
+
+    #[test]
+    fn parses_options() {
+        let options = parse(
+            r#"{
+                "frames": 120,
+                "resolution": { "width": 320, "height": 240 },
+                "seed": 7,
+                "inputRecording": [
+                    { "frame": 3, "type": "keyDown", "keyCode": 65, "keyChar": "a" },
+                    { "frame": 4, "type": "mouseDown", "x": 10, "y": 20, "button": "right" },
+                    { "frame": 5, "type": "mouseWheel", "delta": -3 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(options.frames, 120);
+        assert_eq!((options.width, options.height), (320, 240));
+        assert_eq!(options.seed, 7);
+        assert!(options.perceptual);
+        assert!(matches!(
+            options.inputs[..],
+            [
+                (
+                    3,
+                    PlayerEvent::KeyDown {
+                        key_char: Some('a'),
+                        ..
+                    }
+                ),
+                (
+                    4,
+                    PlayerEvent::MouseDown {
+                        button: MouseButton::Right,
+                        ..
+                    }
+                ),
+                (5, PlayerEvent::MouseWheel { .. }),
+            ]
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        assert!(matches!(
+            parse(r#"{ "frames": 1, "resolution": { "width": 0, "height": 240 } }"#),
+            Err(HashSessionError::InvalidResolution(0, 240))
+        ));
+        assert!(matches!(
+            parse(
+                r#"{ "frames": 1, "resolution": { "width": 1, "height": 1 },
+                     "inputRecording": [{ "frame": 2, "type": "gamepad" }] }"#
+            ),
+            Err(HashSessionError::UnknownInput(_, 2))
+        ));
+    }
+}
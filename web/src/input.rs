@@ -1,4 +1,5 @@
 use ruffle_core::events::{KeyCode, TextControlCode};
+use web_sys::KeyboardEvent;
 
 /// Convert a web `KeyboardEvent.code` value into a Ruffle `KeyCode`.
 /// Return `KeyCode::Unknown` if there is no matching Flash key code.
@@ -117,6 +118,12 @@ pub fn web_to_ruffle_key_code(key_code: &str) -> KeyCode {
     }
 }
 
+/// Whether a key event is part of composing text with an input method editor.
+/// Such keys are handled by the IME, and shouldn't reach the movie.
+pub fn is_composing(event: &KeyboardEvent) -> bool {
+    event.is_composing() || event.key() == "Process"
+}
+
 /// Convert a web `KeyboardEvent.key` value into a character codepoint.
 /// Return `None` if they input was not a printable character.
 pub fn web_key_to_codepoint(key: &str) -> Option<char> {
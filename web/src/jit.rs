@@ -0,0 +1,755 @@
+//! Compiles AVM2 methods to WebAssembly modules, which the browser then compiles to native
+//! code.
+
+use js_sys::{Array, Function as JsFunction, Object, Reflect, Uint8Array, WebAssembly};
+use ruffle_core::backend::jit::{
+    number_to_int, BinaryOp, CompiledFunction, Exit, Function, Instruction, Interrupted,
+    JitBackend, Slot, Type, UnaryOp,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_time::Instant;
+
+/// How many loop iterations compiled code runs between checks of the deadline.
+const ITERATIONS_PER_POLL: i32 = 10000;
+
+/// The largest module that browsers let us compile synchronously on the main thread.
+/// Bigger methods keep running in the interpreter.
+const MAX_MODULE_SIZE: usize = 4096;
+
+/// The functions that compiled modules import, in the order of their function indices.
+const IMPORTS: [(&str, &[ValType], Option<ValType>); 3] = [
+    ("poll", &[], Some(ValType::I32)),
+    ("fmod", &[ValType::F64, ValType::F64], Some(ValType::F64)),
+    ("to_int", &[ValType::F64], Some(ValType::I32)),
+];
+const POLL: u32 = 0;
+const FMOD: u32 = 1;
+const TO_INT: u32 = 2;
+
+/// The state shared by the imports of every compiled module.
+struct Host {
+    deadline: Cell<Option<Instant>>,
+    interrupted: Cell<bool>,
+}
+
+/// Compiles AVM2 methods to WebAssembly.
+pub struct WasmJitBackend {
+    host: Rc<Host>,
+    imports: Object,
+
+    // The closures have to outlive every module that imports them.
+    _closures: Rc<Closures>,
+}
+
+struct Closures {
+    _poll: Closure<dyn Fn() -> i32>,
+    _fmod: Closure<dyn Fn(f64, f64) -> f64>,
+    _to_int: Closure<dyn Fn(f64) -> i32>,
+}
+
+impl WasmJitBackend {
+    pub fn new() -> Self {
+        let host = Rc::new(Host {
+            deadline: Cell::new(None),
+            interrupted: Cell::new(false),
+        });
+
+        let poll_host = host.clone();
+        let poll = Closure::<dyn Fn() -> i32>::new(move || {
+            let expired = poll_host
+                .deadline
+                .get()
+                .is_some_and(|deadline| Instant::now() >= deadline);
+            poll_host.interrupted.set(expired);
+            expired.into()
+        });
+        let fmod = Closure::<dyn Fn(f64, f64) -> f64>::new(|a: f64, b: f64| a % b);
+        let to_int = Closure::<dyn Fn(f64) -> i32>::new(number_to_int);
+
+        let env = Object::new();
+        let _ = Reflect::set(&env, &"poll".into(), poll.as_ref());
+        let _ = Reflect::set(&env, &"fmod".into(), fmod.as_ref());
+        let _ = Reflect::set(&env, &"to_int".into(), to_int.as_ref());
+        let imports = Object::new();
+        let _ = Reflect::set(&imports, &"env".into(), &env);
+
+        Self {
+            host,
+            imports,
+            _closures: Rc::new(Closures {
+                _poll: poll,
+                _fmod: fmod,
+                _to_int: to_int,
+            }),
+        }
+    }
+
+    fn instantiate(&self, bytes: &[u8]) -> Result<JsFunction, JsValue> {
+        let module = WebAssembly::Module::new(&Uint8Array::from(bytes).into())?;
+        let instance = WebAssembly::Instance::new(&module, &self.imports)?;
+        Reflect::get(&instance.exports(), &"run".into())?.dyn_into()
+    }
+}
+
+impl JitBackend for WasmJitBackend {
+    fn compile(&mut self, function: &Function) -> Option<Box<dyn CompiledFunction>> {
+        let bytes = encode(function);
+        if bytes.len() > MAX_MODULE_SIZE {
+            tracing::debug!(
+                "Not compiling AVM2 method, its module is {} bytes",
+                bytes.len()
+            );
+            return None;
+        }
+
+        match self.instantiate(&bytes) {
+            Ok(run) => Some(Box::new(CompiledModule {
+                run,
+                return_type: function.return_type,
+                host: self.host.clone(),
+                _closures: self._closures.clone(),
+            })),
+            Err(e) => {
+                tracing::warn!("Couldn't compile AVM2 method: {e:?}");
+                None
+            }
+        }
+    }
+}
+
+struct CompiledModule {
+    run: JsFunction,
+    return_type: Option<Type>,
+    host: Rc<Host>,
+    _closures: Rc<Closures>,
+}
+
+impl CompiledFunction for CompiledModule {
+    fn call(&self, args: &[Slot], deadline: Instant) -> Result<Option<Slot>, Interrupted> {
+        let args: Array = args
+            .iter()
+            .map(|arg| match *arg {
+                Slot::Int(int) => JsValue::from(int),
+                Slot::Number(number) => JsValue::from(number),
+                Slot::Boolean(boolean) => JsValue::from(i32::from(boolean)),
+            })
+            .collect();
+
+        // Calls can't nest, as compiled code doesn't call back into AVM2.
+        self.host.deadline.set(Some(deadline));
+        self.host.interrupted.set(false);
+        let result = self.run.apply(&JsValue::NULL, &args);
+        self.host.deadline.set(None);
+
+        let value = match result {
+            Ok(value) => value,
+            Err(e) => {
+                // Compiled code only traps when `poll` reports that the deadline has passed.
+                if !self.host.interrupted.get() {
+                    tracing::error!("Compiled AVM2 method failed: {e:?}");
+                }
+                return Err(Interrupted);
+            }
+        };
+
+        let number = value.as_f64().unwrap_or_default();
+        Ok(self.return_type.map(|ty| match ty {
+            Type::Int => Slot::Int(number as i32),
+            Type::Number => Slot::Number(number),
+            Type::Boolean => Slot::Boolean(number != 0.0),
+        }))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ValType {
+    I32,
+    F64,
+}
+
+impl ValType {
+    fn of(ty: Type) -> Self {
+        match ty {
+            Type::Int | Type::Boolean => ValType::I32,
+            Type::Number => ValType::F64,
+        }
+    }
+
+    fn code(self) -> u8 {
+        match self {
+            ValType::I32 => 0x7F,
+            ValType::F64 => 0x7C,
+        }
+    }
+}
+
+mod opcode {
+    pub const UNREACHABLE: u8 = 0x00;
+    pub const BLOCK: u8 = 0x02;
+    pub const LOOP: u8 = 0x03;
+    pub const IF: u8 = 0x04;
+    pub const ELSE: u8 = 0x05;
+    pub const END: u8 = 0x0B;
+    pub const BR: u8 = 0x0C;
+    pub const BR_TABLE: u8 = 0x0E;
+    pub const RETURN: u8 = 0x0F;
+    pub const CALL: u8 = 0x10;
+    pub const LOCAL_GET: u8 = 0x20;
+    pub const LOCAL_SET: u8 = 0x21;
+    pub const LOCAL_TEE: u8 = 0x22;
+    pub const I32_CONST: u8 = 0x41;
+    pub const F64_CONST: u8 = 0x44;
+    pub const I32_EQZ: u8 = 0x45;
+    pub const I32_EQ: u8 = 0x46;
+    pub const I32_NE: u8 = 0x47;
+    pub const I32_LT_S: u8 = 0x48;
+    pub const I32_LE_S: u8 = 0x4C;
+    pub const F64_EQ: u8 = 0x61;
+    pub const F64_LT: u8 = 0x63;
+    pub const F64_GT: u8 = 0x64;
+    pub const F64_LE: u8 = 0x65;
+    pub const I32_ADD: u8 = 0x6A;
+    pub const I32_SUB: u8 = 0x6B;
+    pub const I32_MUL: u8 = 0x6C;
+    pub const I32_AND: u8 = 0x71;
+    pub const I32_OR: u8 = 0x72;
+    pub const I32_XOR: u8 = 0x73;
+    pub const I32_SHL: u8 = 0x74;
+    pub const I32_SHR_S: u8 = 0x75;
+    pub const I32_SHR_U: u8 = 0x76;
+    pub const F64_ABS: u8 = 0x99;
+    pub const F64_NEG: u8 = 0x9A;
+    pub const F64_ADD: u8 = 0xA0;
+    pub const F64_SUB: u8 = 0xA1;
+    pub const F64_MUL: u8 = 0xA2;
+    pub const F64_DIV: u8 = 0xA3;
+    pub const I32_TRUNC_F64_S: u8 = 0xAA;
+    pub const F64_CONVERT_I32_S: u8 = 0xB7;
+    pub const F64_CONVERT_I32_U: u8 = 0xB8;
+    pub const EMPTY: u8 = 0x40;
+}
+
+/// Encodes a function as a module that exports it as `run`, taking the parameters as
+/// arguments and returning the result.
+fn encode(function: &Function) -> Vec<u8> {
+    let mut types = vec![];
+    for (_, params, result) in IMPORTS {
+        types.push(func_type(params, result));
+    }
+    let params: Vec<_> = function
+        .params()
+        .iter()
+        .map(|ty| ValType::of(*ty))
+        .collect();
+    types.push(func_type(&params, function.return_type.map(ValType::of)));
+
+    let mut imports = vec![];
+    for (index, (name, _, _)) in IMPORTS.iter().enumerate() {
+        write_name(&mut imports, "env");
+        write_name(&mut imports, name);
+        imports.push(0x00);
+        write_u32(&mut imports, index as u32);
+    }
+
+    let mut exports = vec![];
+    write_name(&mut exports, "run");
+    exports.push(0x00);
+    write_u32(&mut exports, IMPORTS.len() as u32);
+
+    let body = FunctionEncoder::new(function).encode();
+
+    let mut module = b"\0asm\x01\0\0\0".to_vec();
+    write_section(&mut module, 1, types.len(), &types.concat());
+    write_section(&mut module, 2, IMPORTS.len(), &imports);
+    let mut functions = vec![];
+    write_u32(&mut functions, IMPORTS.len() as u32);
+    write_section(&mut module, 3, 1, &functions);
+    write_section(&mut module, 7, 1, &exports);
+    let mut code = vec![];
+    write_u32(&mut code, body.len() as u32);
+    code.extend(body);
+    write_section(&mut module, 10, 1, &code);
+    module
+}
+
+fn func_type(params: &[ValType], result: Option<ValType>) -> Vec<u8> {
+    let mut bytes = vec![0x60];
+    write_u32(&mut bytes, params.len() as u32);
+    bytes.extend(params.iter().map(|ty| ty.code()));
+    write_u32(&mut bytes, result.is_some().into());
+    bytes.extend(result.map(ValType::code));
+    bytes
+}
+
+fn write_section(module: &mut Vec<u8>, id: u8, count: usize, contents: &[u8]) {
+    let mut section = vec![];
+    write_u32(&mut section, count as u32);
+    section.extend_from_slice(contents);
+    module.push(id);
+    write_u32(module, section.len() as u32);
+    module.extend(section);
+}
+
+fn write_name(bytes: &mut Vec<u8>, name: &str) {
+    write_u32(bytes, name.len() as u32);
+    bytes.extend_from_slice(name.as_bytes());
+}
+
+fn write_u32(bytes: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn write_i32(bytes: &mut Vec<u8>, mut value: i32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Encodes the body of a function.
+///
+/// The blocks are laid out as a loop around a `br_table` on a block index local: every block
+/// ends by setting the index of the next block and branching back to the top of the loop.
+struct FunctionEncoder<'a> {
+    function: &'a Function,
+    code: Vec<u8>,
+
+    /// How many `if`s the code being encoded is nested in.
+    depth: u32,
+
+    /// The local holding the index of the next block.
+    next_block: u32,
+
+    /// The local counting down to the next check of the deadline.
+    fuel: u32,
+}
+
+impl<'a> FunctionEncoder<'a> {
+    fn new(function: &'a Function) -> Self {
+        let next_block = function.vars.len() as u32;
+        Self {
+            function,
+            code: vec![],
+            depth: 0,
+            next_block,
+            fuel: next_block + 1,
+        }
+    }
+
+    fn encode(mut self) -> Vec<u8> {
+        // Locals after the parameters, grouped by runs of the same type.
+        let mut locals: Vec<(u32, ValType)> = vec![];
+        let types = self.function.vars[self.function.num_params..]
+            .iter()
+            .map(|ty| ValType::of(*ty))
+            .chain([ValType::I32, ValType::I32]);
+        for ty in types {
+            match locals.last_mut() {
+                Some((count, last)) if *last == ty => *count += 1,
+                _ => locals.push((1, ty)),
+            }
+        }
+        write_u32(&mut self.code, locals.len() as u32);
+        for (count, ty) in locals {
+            write_u32(&mut self.code, count);
+            self.code.push(ty.code());
+        }
+
+        self.i32_const(ITERATIONS_PER_POLL);
+        self.local(opcode::LOCAL_SET, self.fuel);
+
+        let num_blocks = self.function.blocks.len() as u32;
+        self.code.extend([opcode::LOOP, opcode::EMPTY]);
+        for _ in 0..num_blocks {
+            self.code.extend([opcode::BLOCK, opcode::EMPTY]);
+        }
+        self.local(opcode::LOCAL_GET, self.next_block);
+        self.code.push(opcode::BR_TABLE);
+        write_u32(&mut self.code, num_blocks);
+        for block in 0..num_blocks {
+            write_u32(&mut self.code, block);
+        }
+        write_u32(&mut self.code, num_blocks - 1);
+
+        for (id, block) in self.function.blocks.iter().enumerate() {
+            self.code.push(opcode::END);
+            // The loop is outside the blocks that come after this one.
+            let to_loop = num_blocks - 1 - id as u32;
+            for instruction in &block.instructions {
+                self.instruction(instruction);
+            }
+            match block.exit {
+                Exit::Jump(target) => self.jump(id as u32, target, to_loop),
+                Exit::Branch {
+                    cond,
+                    if_true,
+                    if_false,
+                } => {
+                    self.local(opcode::LOCAL_GET, cond);
+                    self.code.extend([opcode::IF, opcode::EMPTY]);
+                    self.depth += 1;
+                    self.jump(id as u32, if_true, to_loop);
+                    self.code.push(opcode::ELSE);
+                    self.jump(id as u32, if_false, to_loop);
+                    self.depth -= 1;
+                    self.code.push(opcode::END);
+                }
+                Exit::Return(value) => {
+                    if let Some(value) = value {
+                        self.local(opcode::LOCAL_GET, value);
+                    }
+                    self.code.push(opcode::RETURN);
+                }
+            }
+        }
+        self.code
+            .extend([opcode::END, opcode::UNREACHABLE, opcode::END]);
+        self.code
+    }
+
+    /// Continues at block `to`, polling the deadline first if this is a back-edge.
+    fn jump(&mut self, from: u32, to: u32, to_loop: u32) {
+        if to <= from {
+            self.local(opcode::LOCAL_GET, self.fuel);
+            self.i32_const(1);
+            self.code.push(opcode::I32_SUB);
+            self.local(opcode::LOCAL_TEE, self.fuel);
+            self.code
+                .extend([opcode::I32_EQZ, opcode::IF, opcode::EMPTY]);
+            self.i32_const(ITERATIONS_PER_POLL);
+            self.local(opcode::LOCAL_SET, self.fuel);
+            self.call(POLL);
+            self.code
+                .extend([opcode::IF, opcode::EMPTY, opcode::UNREACHABLE, opcode::END]);
+            self.code.push(opcode::END);
+        }
+        self.i32_const(to as i32);
+        self.local(opcode::LOCAL_SET, self.next_block);
+        self.code.push(opcode::BR);
+        write_u32(&mut self.code, to_loop + self.depth);
+    }
+
+    fn local(&mut self, op: u8, var: u32) {
+        self.code.push(op);
+        write_u32(&mut self.code, var);
+    }
+
+    fn i32_const(&mut self, value: i32) {
+        self.code.push(opcode::I32_CONST);
+        write_i32(&mut self.code, value);
+    }
+
+    fn f64_const(&mut self, value: f64) {
+        self.code.push(opcode::F64_CONST);
+        self.code.extend(value.to_le_bytes());
+    }
+
+    fn call(&mut self, function: u32) {
+        self.code.push(opcode::CALL);
+        write_u32(&mut self.code, function);
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) {
+        match *instruction {
+            Instruction::Const { dst, value } => {
+                match value {
+                    Slot::Int(int) => self.i32_const(int),
+                    Slot::Number(number) => self.f64_const(number),
+                    Slot::Boolean(boolean) => self.i32_const(boolean.into()),
+                }
+                self.local(opcode::LOCAL_SET, dst);
+            }
+            Instruction::Move { dst, src } => {
+                self.local(opcode::LOCAL_GET, src);
+                self.local(opcode::LOCAL_SET, dst);
+            }
+            Instruction::Convert { dst, src } => {
+                let from = self.function.vars[src as usize];
+                let to = self.function.vars[dst as usize];
+                self.local(opcode::LOCAL_GET, src);
+                match (from, to) {
+                    (Type::Int, Type::Number) => self.code.push(opcode::F64_CONVERT_I32_S),
+                    (Type::Boolean, Type::Number) => self.code.push(opcode::F64_CONVERT_I32_U),
+                    (Type::Int, Type::Boolean) => {
+                        self.i32_const(0);
+                        self.code.push(opcode::I32_NE);
+                    }
+                    (Type::Number, Type::Boolean) => {
+                        // `NaN` isn't greater than zero.
+                        self.code.push(opcode::F64_ABS);
+                        self.f64_const(0.0);
+                        self.code.push(opcode::F64_GT);
+                    }
+                    (Type::Number, Type::Int) => {
+                        // Truncate inline when in range, and call out for `ToInt32` otherwise.
+                        self.code.push(opcode::F64_ABS);
+                        self.f64_const(2147483648.0);
+                        self.code
+                            .extend([opcode::F64_LT, opcode::IF, ValType::I32.code()]);
+                        self.local(opcode::LOCAL_GET, src);
+                        self.code.extend([opcode::I32_TRUNC_F64_S, opcode::ELSE]);
+                        self.local(opcode::LOCAL_GET, src);
+                        self.call(TO_INT);
+                        self.code.push(opcode::END);
+                    }
+                    _ => {}
+                }
+                self.local(opcode::LOCAL_SET, dst);
+            }
+            Instruction::Unary { op, dst, src } => {
+                match op {
+                    UnaryOp::Negate => {
+                        self.local(opcode::LOCAL_GET, src);
+                        self.code.push(opcode::F64_NEG);
+                    }
+                    UnaryOp::NegateI => {
+                        self.i32_const(0);
+                        self.local(opcode::LOCAL_GET, src);
+                        self.code.push(opcode::I32_SUB);
+                    }
+                    UnaryOp::BitNot => {
+                        self.local(opcode::LOCAL_GET, src);
+                        self.i32_const(-1);
+                        self.code.push(opcode::I32_XOR);
+                    }
+                    UnaryOp::Not => {
+                        self.local(opcode::LOCAL_GET, src);
+                        self.code.push(opcode::I32_EQZ);
+                    }
+                    UnaryOp::ToUint => {
+                        self.local(opcode::LOCAL_GET, src);
+                        self.code.push(opcode::F64_CONVERT_I32_U);
+                    }
+                }
+                self.local(opcode::LOCAL_SET, dst);
+            }
+            Instruction::Binary { op, dst, lhs, rhs } => {
+                let operands = self.function.vars[lhs as usize];
+                self.local(opcode::LOCAL_GET, lhs);
+                self.local(opcode::LOCAL_GET, rhs);
+                let number = operands == Type::Number;
+                match op {
+                    BinaryOp::Add => self.code.push(opcode::F64_ADD),
+                    BinaryOp::Subtract => self.code.push(opcode::F64_SUB),
+                    BinaryOp::Multiply => self.code.push(opcode::F64_MUL),
+                    BinaryOp::Divide => self.code.push(opcode::F64_DIV),
+                    BinaryOp::Modulo => self.call(FMOD),
+                    BinaryOp::AddI => self.code.push(opcode::I32_ADD),
+                    BinaryOp::SubtractI => self.code.push(opcode::I32_SUB),
+                    BinaryOp::MultiplyI => self.code.push(opcode::I32_MUL),
+                    BinaryOp::BitAnd => self.code.push(opcode::I32_AND),
+                    BinaryOp::BitOr => self.code.push(opcode::I32_OR),
+                    BinaryOp::BitXor => self.code.push(opcode::I32_XOR),
+                    // WebAssembly shifts use the low five bits of the amount, like AVM2.
+                    BinaryOp::LShift => self.code.push(opcode::I32_SHL),
+                    BinaryOp::RShift => self.code.push(opcode::I32_SHR_S),
+                    BinaryOp::URShift => self
+                        .code
+                        .extend([opcode::I32_SHR_U, opcode::F64_CONVERT_I32_U]),
+                    BinaryOp::Equal if number => self.code.push(opcode::F64_EQ),
+                    BinaryOp::Equal => self.code.push(opcode::I32_EQ),
+                    BinaryOp::LessThan if number => self.code.push(opcode::F64_LT),
+                    BinaryOp::LessThan => self.code.push(opcode::I32_LT_S),
+                    BinaryOp::LessEqual if number => self.code.push(opcode::F64_LE),
+                    BinaryOp::LessEqual => self.code.push(opcode::I32_LE_S),
+                }
+                self.local(opcode::LOCAL_SET, dst);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::backend::jit::Block;
+    use std::time::Duration;
+    use wasmi::{Caller, Engine, Linker, Module, Store, Val};
+
+    /// Runs an encoded module the way the browser would.
+    fn call(function: &Function, args: &[Val], deadline: Instant) -> Result<Option<Val>, String> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, &encode(function)[..]).map_err(|e| e.to_string())?;
+        let mut store = Store::new(&engine, deadline);
+        let mut linker = <Linker<Instant>>::new(&engine);
+        linker
+            .func_wrap("env", "poll", |caller: Caller<'_, Instant>| {
+                i32::from(Instant::now() >= *caller.data())
+            })
+            .and_then(|linker| linker.func_wrap("env", "fmod", |a: f64, b: f64| a % b))
+            .and_then(|linker| linker.func_wrap("env", "to_int", number_to_int))
+            .map_err(|e| e.to_string())?;
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .and_then(|instance| instance.start(&mut store))
+            .map_err(|e| e.to_string())?;
+        let run = instance.get_func(&store, "run").ok_or("Missing export")?;
+
+        let mut results = match function.return_type {
+            Some(Type::Number) => vec![Val::F64(0.0.into())],
+            Some(_) => vec![Val::I32(0)],
+            None => vec![],
+        };
+        run.call(&mut store, args, &mut results)
+            .map_err(|e| e.to_string())?;
+        Ok(results.pop())
+    }
+
+    fn far_deadline() -> Instant {
+        Instant::now() + Duration::from_secs(60)
+    }
+
+    /// `var total:Number = 0; for (var i:int = 0; i < n; i++) { total += i % 7; } return total;`
+    fn sum_loop() -> Function {
+        Function {
+            // n, total, i, 1, 7, i as Number, i < n
+            vars: vec![
+                Type::Int,
+                Type::Number,
+                Type::Int,
+                Type::Int,
+                Type::Number,
+                Type::Number,
+                Type::Boolean,
+            ],
+            num_params: 1,
+            blocks: vec![
+                Block {
+                    instructions: vec![
+                        Instruction::Const {
+                            dst: 3,
+                            value: Slot::Int(1),
+                        },
+                        Instruction::Const {
+                            dst: 4,
+                            value: Slot::Number(7.0),
+                        },
+                    ],
+                    exit: Exit::Jump(2),
+                },
+                Block {
+                    instructions: vec![
+                        Instruction::Convert { dst: 5, src: 2 },
+                        Instruction::Binary {
+                            op: BinaryOp::Modulo,
+                            dst: 5,
+                            lhs: 5,
+                            rhs: 4,
+                        },
+                        Instruction::Binary {
+                            op: BinaryOp::Add,
+                            dst: 1,
+                            lhs: 1,
+                            rhs: 5,
+                        },
+                        Instruction::Binary {
+                            op: BinaryOp::AddI,
+                            dst: 2,
+                            lhs: 2,
+                            rhs: 3,
+                        },
+                    ],
+                    exit: Exit::Jump(2),
+                },
+                Block {
+                    instructions: vec![Instruction::Binary {
+                        op: BinaryOp::LessThan,
+                        dst: 6,
+                        lhs: 2,
+                        rhs: 0,
+                    }],
+                    exit: Exit::Branch {
+                        cond: 6,
+                        if_true: 1,
+                        if_false: 3,
+                    },
+                },
+                Block {
+                    instructions: vec![],
+                    exit: Exit::Return(Some(1)),
+                },
+            ],
+            return_type: Some(Type::Number),
+        }
+    }
+
+    #[test]
+    fn runs_loops() {
+        let function = sum_loop();
+        assert!(encode(&function).len() <= MAX_MODULE_SIZE);
+        let sum = |n| match call(&function, &[Val::I32(n)], far_deadline()) {
+            Ok(Some(Val::F64(sum))) => f64::from(sum),
+            result => panic!("Unexpected result {result:?}"),
+        };
+        assert_eq!(sum(0), 0.0);
+        assert_eq!(sum(-5), 0.0);
+        assert_eq!(sum(10), 24.0);
+        assert_eq!(sum(70000), 210000.0);
+    }
+
+    #[test]
+    fn converts_like_flash() {
+        // Number to int, int to Boolean, and the unsigned value of the int.
+        let function = Function {
+            vars: vec![Type::Number, Type::Int, Type::Number],
+            num_params: 1,
+            blocks: vec![Block {
+                instructions: vec![
+                    Instruction::Convert { dst: 1, src: 0 },
+                    Instruction::Unary {
+                        op: UnaryOp::ToUint,
+                        dst: 2,
+                        src: 1,
+                    },
+                ],
+                exit: Exit::Return(Some(2)),
+            }],
+            return_type: Some(Type::Number),
+        };
+        let to_uint = |n: f64| match call(&function, &[Val::F64(n.into())], far_deadline()) {
+            Ok(Some(Val::F64(n))) => f64::from(n),
+            result => panic!("Unexpected result {result:?}"),
+        };
+        assert_eq!(to_uint(1.9), 1.0);
+        assert_eq!(to_uint(-1.9), 4294967295.0);
+        assert_eq!(to_uint(4294967298.5), 2.0);
+        assert_eq!(to_uint(-2147483649.0), 2147483647.0);
+        assert_eq!(to_uint(1e20), 1661992960.0);
+        assert_eq!(to_uint(f64::NAN), 0.0);
+        assert_eq!(to_uint(f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn interrupts_infinite_loops() {
+        let function = Function {
+            vars: vec![],
+            num_params: 0,
+            blocks: vec![Block {
+                instructions: vec![],
+                exit: Exit::Jump(0),
+            }],
+            return_type: None,
+        };
+        let start = Instant::now();
+        let result = call(&function, &[], start + Duration::from_millis(50));
+        assert!(result.is_err(), "Loop should trap, got {result:?}");
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
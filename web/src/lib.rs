@@ -2,34 +2,61 @@
 #![allow(clippy::empty_docs)] //False positive in rustc 1.78 beta
 
 //! Ruffle web frontend.
+mod aac;
 mod audio;
 mod builder;
+mod camera;
+mod compatibility_rules;
 mod external_interface;
+mod gamepad;
+mod hash_session;
 mod input;
+#[cfg(feature = "jit")]
+mod jit;
+mod local_connection;
+mod local_fonts;
 mod log_adapter;
+mod microphone;
+mod native_windows;
 mod navigator;
+mod scheduler;
 mod storage;
 mod ui;
+mod web_views;
 mod zip;
 
 use crate::builder::RuffleInstanceBuilder;
+use crate::log_adapter::LogSubscriber;
 use external_interface::{external_to_js_value, js_to_external_value};
-use input::{web_key_to_codepoint, web_to_ruffle_key_code, web_to_ruffle_text_control};
+use hash_session::{HashSessionConfig, HashSessionError};
+use input::{
+    is_composing, web_key_to_codepoint, web_to_ruffle_key_code, web_to_ruffle_text_control,
+};
 use js_sys::{Error as JsError, Uint8Array};
 use ruffle_core::context::UpdateContext;
 use ruffle_core::context_menu::ContextMenuCallback;
-use ruffle_core::events::{MouseButton, MouseWheelDelta, TextControlCode};
+use ruffle_core::events::{
+    ImeEvent, MouseButton, MouseWheelDelta, TextControlCode, TouchEvent, TouchPhase,
+};
+use ruffle_core::external::{Invoke as ExternalInvoke, Value as ExternalValue};
+use ruffle_core::hash_session::HashSession;
+use ruffle_core::replay::Replay;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerEvent, StaticCallstack, ViewportDimensions};
 use ruffle_web_common::JsResult;
 use serde::Serialize;
 use slotmap::{new_key_type, SlotMap};
+use std::collections::VecDeque;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Once;
 use std::sync::{Arc, Mutex};
-use std::{cell::RefCell, error::Error, num::NonZeroI32};
-use tracing_subscriber::layer::{Layered, SubscriberExt};
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    num::NonZeroI32,
+};
+use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::registry::Registry;
 use tracing_wasm::{WASMLayer, WASMLayerConfigBuilder};
 use ui::WebUiBackend;
@@ -44,6 +71,20 @@ use web_sys::{
 
 static RUFFLE_GLOBAL_PANIC: Once = Once::new();
 
+/// How many input events are kept waiting for the next input micro-tick, in low-latency
+/// input mode.
+const MAX_DEFERRED_INPUT_EVENTS: usize = 64;
+
+/// For how long a hash session runs frames before yielding to the browser, in milliseconds.
+const HASH_SESSION_SLICE_MS: f64 = 50.0;
+
+/// How often a throttled player ticks while the page is hidden, in milliseconds.
+const THROTTLED_TICK_INTERVAL_MS: f64 = 500.0;
+
+/// For how long a player running in a hidden page may catch up on missed frames per tick,
+/// in milliseconds.
+const BACKGROUND_CATCH_UP_BUDGET_MS: f64 = 200.0;
+
 new_key_type! {
     /// An opaque handle to a `RuffleInstance` inside the pool.
     ///
@@ -129,6 +170,7 @@ struct RuffleInstance {
     mouse_leave_callback: Option<JsCallback<PointerEvent>>,
     mouse_down_callback: Option<JsCallback<PointerEvent>>,
     mouse_up_callback: Option<JsCallback<PointerEvent>>,
+    touch_cancel_callback: Option<JsCallback<PointerEvent>>,
     mouse_wheel_callback: Option<JsCallback<WheelEvent>>,
     key_down_callback: Option<JsCallback<KeyboardEvent>>,
     key_up_callback: Option<JsCallback<KeyboardEvent>>,
@@ -137,9 +179,46 @@ struct RuffleInstance {
     focusin_callback: Option<JsCallback<FocusEvent>>,
     focusout_callback: Option<JsCallback<FocusEvent>>,
     focus_on_press_callback: Option<JsCallback<PointerEvent>>,
+    context_lost_callback: Option<JsCallback<Event>>,
+    context_restored_callback: Option<JsCallback<Event>>,
+    visibility_callback: Option<JsCallback<Event>>,
+    audio_unlock_callbacks: Vec<JsCallback<Event>>,
+    audio_state_callback: Option<JsCallback<Event>>,
     has_focus: bool,
+
+    /// Whether a new renderer is being created, after the current one lost its device.
+    restoring_renderer: bool,
+
+    /// Ticks the player while the page is hidden, as animation frames don't run then.
+    background_handler: Option<Closure<dyn FnMut()>>,
+    background_handler_id: Option<i32>,
+
+    /// Whether the movie was playing before the page was hidden and the movie paused.
+    was_playing_before_hidden: bool,
+
+    /// Whether the movie was playing before the scheduler suspended it for other players.
+    was_playing_before_suspended: bool,
+
     trace_observer: Rc<RefCell<JsValue>>,
-    log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+    log_subscriber: Arc<LogSubscriber>,
+
+    /// The options this instance was created with.
+    config: RuffleInstanceBuilder,
+
+    /// Input events that are waiting for the next input micro-tick, in low-latency input mode.
+    deferred_input: RefCell<VecDeque<PlayerEvent>>,
+
+    /// Whether the player handled input in low-latency mode when it was last locked,
+    /// for events that arrive while it is busy.
+    low_latency_input: Cell<bool>,
+
+    /// Whether the running hash session should be cancelled, if one is running.
+    hash_session: Option<Rc<Cell<bool>>>,
+
+    /// Metadata about the root movie, once it's known.
+    metadata: Option<MovieMetadata>,
+
+    gamepads: gamepad::GamepadPoller,
 }
 
 #[wasm_bindgen(raw_module = "./internal/player/inner")]
@@ -163,6 +242,20 @@ extern "C" {
     #[wasm_bindgen(method, js_name = "displayRootMovieDownloadFailedMessage")]
     fn display_root_movie_download_failed_message(this: &JavascriptPlayer, invalid_swf: bool);
 
+    #[wasm_bindgen(method, js_name = "onLoadProgress")]
+    fn on_load_progress(
+        this: &JavascriptPlayer,
+        phase: &str,
+        bytes_loaded: f64,
+        bytes_total: Option<f64>,
+    );
+
+    #[wasm_bindgen(method, js_name = "onLoaded")]
+    fn on_loaded(this: &JavascriptPlayer);
+
+    #[wasm_bindgen(method, js_name = "onLoadError")]
+    fn on_load_error(this: &JavascriptPlayer, message: &str);
+
     #[wasm_bindgen(method, js_name = "displayMessage")]
     fn display_message(this: &JavascriptPlayer, message: &str);
 
@@ -175,6 +268,9 @@ extern "C" {
     #[wasm_bindgen(method, js_name = "setMetadata")]
     fn set_metadata(this: &JavascriptPlayer, metadata: JsValue);
 
+    #[wasm_bindgen(method, js_name = "onInstanceCreated")]
+    fn on_instance_created(this: &JavascriptPlayer, instance: RuffleHandle);
+
     #[wasm_bindgen(method, js_name = "openVirtualKeyboard")]
     fn open_virtual_keyboard(this: &JavascriptPlayer);
 
@@ -184,14 +280,38 @@ extern "C" {
     #[wasm_bindgen(method, js_name = "isVirtualKeyboardFocused")]
     fn is_virtual_keyboard_focused(this: &JavascriptPlayer) -> bool;
 
+    #[wasm_bindgen(method, js_name = "writeClipboardImage")]
+    fn write_clipboard_image(this: &JavascriptPlayer, png: Uint8Array);
+
+    #[wasm_bindgen(method, js_name = "setImeCursorArea")]
+    fn set_ime_cursor_area(this: &JavascriptPlayer, x: f64, y: f64, width: f64, height: f64);
+
     #[wasm_bindgen(method, js_name = "displayUnsupportedVideo")]
     fn display_unsupported_video(this: &JavascriptPlayer, url: &str);
 
+    #[wasm_bindgen(method, js_name = "onContextMenuItem")]
+    fn on_context_menu_item(this: &JavascriptPlayer, callback_id: &str);
+
     #[wasm_bindgen(method, js_name = "displayClipboardModal")]
     fn display_clipboard_modal(this: &JavascriptPlayer, access_denied: bool);
 
     #[wasm_bindgen(method, js_name = "suppressContextMenu")]
     fn suppress_context_menu(this: &JavascriptPlayer);
+
+    #[wasm_bindgen(method, js_name = "onRendererCreated")]
+    fn on_renderer_created(this: &JavascriptPlayer, report: JsValue);
+
+    #[wasm_bindgen(method, js_name = "onRendererRestored")]
+    fn on_renderer_restored(this: &JavascriptPlayer);
+
+    #[wasm_bindgen(method, js_name = "onTraceOutput")]
+    fn on_trace_output(this: &JavascriptPlayer, message: &str);
+
+    #[wasm_bindgen(method, js_name = "updateAccessibilityTree")]
+    fn update_accessibility_tree(this: &JavascriptPlayer, nodes: JsValue);
+
+    #[wasm_bindgen(method, js_name = "onAudioAvailable")]
+    fn on_audio_available(this: &JavascriptPlayer);
 }
 
 #[derive(Debug, Clone)]
@@ -200,6 +320,55 @@ pub struct SocketProxy {
     port: u16,
 
     proxy_url: String,
+    proxy_protocol: SocketProxyProtocol,
+
+    /// Whether the proxy connects to the server with TLS, for `SecureSocket`s.
+    tls: bool,
+}
+
+/// The protocol used to reach a socket proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocketProxyProtocol {
+    /// Relay the socket through a WebSocket connection.
+    WebSocket,
+
+    /// Relay the socket through a WebTransport stream.
+    /// Falls back to a WebSocket connection to the same proxy if WebTransport can't be used.
+    WebTransport,
+}
+
+/// What the player does while the page is hidden, such as in a background tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundBehavior {
+    /// Pause the movie and its audio until the page is visible again.
+    Pause,
+
+    /// Keep running, but only wake up twice a second.
+    /// Frames that can't be caught up on are skipped, while timers still see all the time that passed.
+    Throttle,
+
+    /// Keep running all frames, as often as the browser lets a background page wake up.
+    Run,
+}
+
+/// Whether the movie starts playing once it's loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Autoplay {
+    /// Always start playing, even if the browser keeps the audio suspended for now.
+    On,
+
+    /// Never start playing by itself, the play button is shown instead.
+    Off,
+
+    /// Start playing if the browser lets the audio play right away.
+    Auto,
+}
+
+/// Whether the user is asked to click to unmute, while the browser keeps the audio suspended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnmuteOverlay {
+    Visible,
+    Hidden,
 }
 
 /// Metadata about the playing SWF file to be passed back to JavaScript.
@@ -213,82 +382,39 @@ struct MovieMetadata {
     swf_version: u8,
     background_color: Option<String>,
     is_action_script_3: bool,
+    avm_version: u8,
+    compression: &'static str,
     #[serde(rename = "uncompressedLength")]
     uncompressed_len: i32,
+    xmp_metadata: Option<String>,
 }
 
 #[wasm_bindgen]
 impl RuffleHandle {
-    /// Stream an arbitrary movie file from (presumably) the Internet.
-    ///
-    /// This method should only be called once per player.
-    ///
-    /// `parameters` are *extra* parameters to set on the LoaderInfo -
-    /// parameters from `movie_url` query parameters will be automatically added.
-    pub fn stream_from(&self, movie_url: String, parameters: JsValue) -> Result<(), JsValue> {
+    pub fn play(&self) {
         let _ = self.with_core_mut(|core| {
-            let parameters_to_load = parse_movie_parameters(&parameters);
-
-            let ruffle = *self;
-            let on_metadata = move |swf_header: &ruffle_core::swf::HeaderExt| {
-                ruffle.on_metadata(swf_header);
-            };
-
-            core.fetch_root_movie(movie_url, parameters_to_load, Box::new(on_metadata));
+            core.set_is_playing(true);
         });
-        Ok(())
     }
 
-    /// Play an arbitrary movie on this instance.
-    ///
-    /// This method should only be called once per player.
-    pub fn load_data(
-        &self,
-        swf_data: Uint8Array,
-        parameters: JsValue,
-        swf_name: String,
-    ) -> Result<(), JsValue> {
-        let window = web_sys::window().ok_or("Expected window")?;
-        let mut url = Url::from_str(&window.location().href()?)
-            .map_err(|e| format!("Error creating url: {e}"))?;
-        url.set_query(None);
-        url.set_fragment(None);
-        if let Ok(mut segments) = url.path_segments_mut() {
-            segments.pop();
-            segments.push(&swf_name);
-        }
-
-        let mut movie =
-            SwfMovie::from_data(&swf_data.to_vec(), url.to_string(), None).map_err(|e| {
-                let _ = self.with_core_mut(|core| {
-                    core.ui_mut()
-                        .display_root_movie_download_failed_message(true);
-                });
-                format!("Error loading movie: {e}")
-            })?;
-        movie.append_parameters(parse_movie_parameters(&parameters));
-
-        self.on_metadata(movie.header());
-
-        let _ = self.with_core_mut(move |core| {
-            core.update(|uc| {
-                uc.set_root_movie(movie);
-            });
+    pub fn pause(&self) {
+        let _ = self.with_core_mut(|core| {
+            core.set_is_playing(false);
         });
-
-        Ok(())
     }
 
-    pub fn play(&self) {
+    /// Plays the given number of frames at the movie's frame rate, then pauses.
+    pub fn step_frame(&self, frames: u32) {
         let _ = self.with_core_mut(|core| {
-            core.set_is_playing(true);
+            core.suspend_after_frames(frames);
         });
     }
 
-    pub fn pause(&self) {
-        let _ = self.with_core_mut(|core| {
-            core.set_is_playing(false);
-        });
+    /// Moves the root movie to `frame`, counted from the start of `scene` if one is given.
+    pub fn goto_frame(&self, frame: u16, scene: Option<String>) -> Result<(), JsValue> {
+        self.with_core_mut(|core| core.goto_frame(frame, scene.as_deref()))
+            .map_err(|e| JsValue::from(JsError::new(&e.to_string())))?
+            .map_err(|e| JsValue::from(JsError::new(&e.to_string())))
     }
 
     pub fn is_playing(&self) -> bool {
@@ -300,6 +426,29 @@ impl RuffleHandle {
             .unwrap_or_default()
     }
 
+    /// Returns metadata about the root movie, or `null` if it isn't known yet.
+    pub fn metadata(&self) -> JsValue {
+        self.with_instance(|instance| {
+            instance
+                .metadata
+                .as_ref()
+                .and_then(|metadata| serde_wasm_bindgen::to_value(metadata).ok())
+        })
+        .ok()
+        .flatten()
+        .unwrap_or(JsValue::NULL)
+    }
+
+    /// Shows text being composed with an input method editor in the focused text field.
+    pub fn ime_preedit(&self, text: String) {
+        let _ = self.with_core_mut(|core| core.handle_ime_event(ImeEvent::Preedit(text, None)));
+    }
+
+    /// Inserts text composed with an input method editor into the focused text field.
+    pub fn ime_commit(&self, text: String) {
+        let _ = self.with_core_mut(|core| core.handle_ime_event(ImeEvent::Commit(text)));
+    }
+
     pub fn volume(&self) -> f32 {
         self.with_core(|core| core.volume()).unwrap_or_default()
     }
@@ -308,6 +457,34 @@ impl RuffleHandle {
         let _ = self.with_core_mut(|core| core.set_volume(value));
     }
 
+    /// Clicks the stage at a point given in CSS pixels, such as when a screen reader
+    /// activates a button mirrored by `updateAccessibilityTree`.
+    pub fn click(&self, x: f64, y: f64) {
+        let _ = self.with_instance(|instance| {
+            let x = x * instance.device_pixel_ratio;
+            let y = y * instance.device_pixel_ratio;
+            instance.handle_input_events(&[
+                PlayerEvent::MouseMove { x, y },
+                PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                    index: None,
+                },
+                PlayerEvent::MouseUp {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                },
+            ]);
+        });
+    }
+
+    /// Frees memory that can be recreated later, such as decoded bitmaps.
+    pub fn low_memory(&self) {
+        let _ = self.with_core_mut(|core| core.low_memory());
+    }
+
     pub fn renderer_debug_info(&self) -> JsValue {
         self.with_core(|core| JsValue::from_str(&core.renderer().debug_info()))
             .unwrap_or(JsValue::NULL)
@@ -318,6 +495,101 @@ impl RuffleHandle {
             .unwrap_or(JsValue::NULL)
     }
 
+    /// Starts recording the time spent in ActionScript functions.
+    pub fn start_profiling(&self) {
+        let _ = self.with_core_mut(|core| core.start_profiling());
+    }
+
+    /// Stops recording, and returns the profile as speedscope JSON, or `null` if no profile
+    /// was being taken.
+    pub fn stop_profiling(&self) -> JsValue {
+        self.with_core_mut(|core| core.stop_profiling())
+            .ok()
+            .flatten()
+            .map(|profile| JsValue::from_str(&profile.to_speedscope_json()))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    /// Starts recording input into a replay. See `ruffle_core::replay`.
+    ///
+    /// The seed defaults to the current time.
+    pub fn start_replay_recording(&self, seed: Option<u32>) {
+        let seed = seed.map_or_else(|| js_sys::Date::now() as u64, u64::from);
+        let _ = self.with_core_mut(|core| core.start_replay_recording(seed));
+    }
+
+    /// Plays back a replay made with `start_replay_recording`, ignoring input until it ends.
+    pub fn start_replay(&self, replay: &str) -> Result<(), JsValue> {
+        let replay =
+            Replay::from_json(replay).map_err(|e| JsValue::from(JsError::new(&e.to_string())))?;
+        self.with_core_mut(|core| core.start_replay(replay))
+            .map_err(|e| JsValue::from(JsError::new(&e.to_string())))
+    }
+
+    /// Stops recording or playing back a replay, and returns it as JSON, or `null` if there
+    /// was none.
+    pub fn stop_replay(&self) -> JsValue {
+        self.with_core_mut(|core| core.stop_replay())
+            .ok()
+            .flatten()
+            .map(|replay| JsValue::from_str(&replay.to_json()))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    /// Returns a save state of the movie as JSON, or `null` if no replay is being recorded or
+    /// played back.
+    pub fn save_state(&self) -> JsValue {
+        self.with_core(|core| core.save_state())
+            .ok()
+            .flatten()
+            .map(|state| JsValue::from_str(&state.to_json()))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    /// Loads a save state made with `save_state`, by replaying the movie up to it, and goes on
+    /// recording from there.
+    pub fn load_state(&self, state: &str) -> Result<(), JsValue> {
+        let state =
+            Replay::from_json(state).map_err(|e| JsValue::from(JsError::new(&e.to_string())))?;
+        self.with_core_mut(|core| core.load_state(state))
+            .map_err(|e| JsValue::from(JsError::new(&e.to_string())))
+    }
+
+    /// Enables or disables low-latency input while the movie is running.
+    /// See `Player::set_low_latency_input`.
+    pub fn set_low_latency_input(&self, enabled: bool, force: bool) -> Result<(), JsValue> {
+        self.with_instance(|instance| {
+            instance.with_core_mut(|core| {
+                let result = core.set_low_latency_input(enabled, force);
+                instance.low_latency_input.set(core.low_latency_input());
+                result
+            })
+        })
+        .and_then(std::convert::identity)
+        .map_err(|e| JsValue::from(JsError::new(&e.to_string())))?
+        .map_err(|e| JsValue::from(JsError::new(&e.to_string())))
+    }
+
+    /// Renders the stage as it currently looks, at the size of the canvas, and returns it
+    /// as a PNG image.
+    pub fn capture_frame(&self) -> Result<Uint8Array, JsValue> {
+        let to_js = |e: &dyn std::fmt::Display| JsValue::from(JsError::new(&e.to_string()));
+        let bitmap = self
+            .with_core_mut(|core| core.capture_frame())
+            .map_err(|e| to_js(&e))?
+            .map_err(|e| to_js(&e))?;
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, bitmap.width(), bitmap.height());
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(bitmap.data()))
+            .map_err(|e| to_js(&e))?;
+        Ok(Uint8Array::from(&png[..]))
+    }
+
     // after the context menu is closed, remember to call `clear_custom_menu_items`!
     pub fn prepare_context_menu(&self) -> JsValue {
         self.with_core_mut(|core| {
@@ -399,20 +671,151 @@ impl RuffleHandle {
         let _ = self.with_core_mut(|core| core.set_fullscreen(is_fullscreen));
     }
 
+    /// The labels of the items the embedder added to the context menu.
+    pub fn embedder_context_menu_items(&self) -> Vec<String> {
+        self.with_instance(|instance| {
+            instance
+                .config
+                .context_menu_items
+                .iter()
+                .map(|(label, _)| label.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+
+    pub fn run_embedder_context_menu_item(&self, index: usize) {
+        // The page's event listeners may call back into this instance, so it can't be borrowed.
+        let item = self.with_instance(|instance| {
+            let (_, callback_id) = instance.config.context_menu_items.get(index)?;
+            Some((instance.js_player.clone(), callback_id.clone()))
+        });
+        if let Ok(Some((js_player, callback_id))) = item {
+            js_player.on_context_menu_item(&callback_id);
+        }
+    }
+
     pub fn clear_custom_menu_items(&self) {
         let _ = self.with_core_mut(Player::clear_custom_menu_items);
     }
 
+    /// Sets the element that canvases for additional AIR `NativeWindow`s are placed in.
+    /// Each window is absolutely positioned, so the container should be positioned too.
+    pub fn set_aux_window_container(&self, container: Option<HtmlElement>) {
+        let _ = self.with_core_mut(|core| {
+            core.ui_mut()
+                .downcast_mut::<WebUiBackend>()
+                .expect("Web UI backend")
+                .native_windows_mut()
+                .set_container(container);
+        });
+    }
+
+    /// Returns a snapshot of the options this instance was created with.
+    ///
+    /// See `RuffleInstanceBuilder.getConfig` for the meaning of `include_sensitive`.
+    pub fn get_config(&self, include_sensitive: bool) -> JsValue {
+        self.with_instance(|instance| instance.config.to_config(include_sensitive).to_js())
+            .unwrap_or(JsValue::NULL)
+    }
+
+    /// Plays the movie deterministically, and hashes every rendered frame.
+    ///
+    /// This is meant for archives, to find movies whose rendering changed between versions of
+    /// Ruffle. See `ruffle_core::hash_session` for what the hashes do and do not guarantee.
+    /// Normal playback is paused while the session runs, and continues from where it left off.
+    ///
+    /// `options` is `{ frames, resolution: { width, height }, seed?, perceptual?, inputRecording? }`.
+    /// Resolves to the versioned report, or rejects if the options are invalid or the session
+    /// is cancelled with `cancel_hash_session`.
+    pub async fn run_hash_session(&self, options: JsValue) -> Result<JsValue, JsValue> {
+        let to_js = |e: HashSessionError| JsValue::from(JsError::new(&e.to_string()));
+        let options = serde_wasm_bindgen::from_value::<HashSessionConfig>(options)
+            .map_err(|e| HashSessionError::InvalidOptions(e.to_string()))
+            .and_then(HashSessionConfig::into_options)
+            .map_err(to_js)?;
+
+        let cancelled = Rc::new(Cell::new(false));
+        let already_running = self
+            .with_instance_mut(|instance| instance.hash_session.replace(cancelled.clone()))
+            .map_err(|e| JsValue::from(JsError::new(&e.to_string())))?;
+        if let Some(running) = already_running {
+            let _ = self.with_instance_mut(|instance| instance.hash_session = Some(running));
+            return Err(to_js(HashSessionError::AlreadyRunning));
+        }
+
+        let mut session = self
+            .with_core_mut(|core| HashSession::new(core, options))
+            .ok();
+        while let Some(running) = session.as_mut().filter(|session| !session.is_finished()) {
+            if cancelled.get() {
+                break;
+            }
+
+            let started = js_sys::Date::now();
+            let result = self.with_core_mut(|core| {
+                while !running.is_finished()
+                    && js_sys::Date::now() - started < HASH_SESSION_SLICE_MS
+                {
+                    running.step(core);
+                }
+            });
+            match result {
+                // The core is busy elsewhere; try again after yielding.
+                Ok(()) | Err(RuffleInstanceError::TryLockError) => {}
+                Err(_) => break,
+            }
+
+            // Let the page stay responsive, and give it a chance to cancel the session.
+            yield_to_event_loop().await;
+        }
+
+        let report =
+            session.and_then(|session| self.with_core_mut(|core| session.finish(core)).ok());
+        let _ = self.with_instance_mut(|instance| instance.hash_session = None);
+
+        if cancelled.get() {
+            return Err(to_js(HashSessionError::Cancelled));
+        }
+        let report = report.ok_or_else(|| JsValue::from(JsError::new("Player is unavailable")))?;
+        report
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(|e| JsValue::from(JsError::new(&e.to_string())))
+    }
+
+    /// Cancels the running hash session, if any. Its promise rejects once it has stopped.
+    pub fn cancel_hash_session(&self) {
+        let _ = self.with_instance(|instance| {
+            if let Some(cancelled) = &instance.hash_session {
+                cancelled.set(true);
+            }
+        });
+    }
+
     pub fn destroy(&self) {
         // Remove instance from the active list.
         let _ = self.remove_instance();
         // Instance is dropped at this point.
+
+        // Let a suspended player take its place.
+        Self::apply_schedule(scheduler::unregister(*self));
     }
 
     #[allow(clippy::boxed_local)] // for js_bind
     pub fn call_exposed_callback(&self, name: &str, args: Box<[JsValue]>) -> JsValue {
         let args: Vec<_> = args.iter().map(js_to_external_value).collect();
+        external_to_js_value(self.call_callback(name, args))
+    }
+
+    /// Calls a callback with an `<invoke>` request, as Flash's `CallFunction` did,
+    /// and returns the result in the same XML encoding.
+    pub fn call_function(&self, request: &str) -> Result<String, JsValue> {
+        let invoke = ExternalInvoke::from_xml(request)
+            .map_err(|e| JsValue::from_str(&format!("Invalid CallFunction request: {e}")))?;
+        Ok(self.call_callback(&invoke.name, invoke.arguments).to_xml())
+    }
 
+    fn call_callback(&self, name: &str, args: Vec<ExternalValue>) -> ExternalValue {
         // Re-entrant callbacks need to return through the hole that was punched through for them
         // We record the context of external functions, and then if we get an internal callback
         // during the same call we'll reuse that.
@@ -420,13 +823,40 @@ impl RuffleHandle {
         if let Some(context) = CURRENT_CONTEXT.with(|v| *v.borrow()) {
             unsafe {
                 if let Some(callback) = (*context).external_interface.get_callback(name) {
-                    return external_to_js_value(callback.call(&mut *context, name, args));
+                    return callback.call(&mut *context, name, args);
                 }
             }
         }
 
-        self.with_core_mut(|core| external_to_js_value(core.call_internal_interface(name, args)))
-            .unwrap_or(JsValue::UNDEFINED)
+        self.with_core_mut(|core| core.call_internal_interface(name, args))
+            .unwrap_or(ExternalValue::Undefined)
+    }
+
+    /// Returns whether the movie should start playing once it's loaded,
+    /// following the `autoplay` option.
+    pub fn should_autoplay(&self) -> bool {
+        self.with_instance(|instance| match instance.config.autoplay {
+            Autoplay::On => true,
+            Autoplay::Off => false,
+            Autoplay::Auto => instance.is_audio_running(),
+        })
+        .unwrap_or_default()
+    }
+
+    /// Returns whether the user should be asked to click to unmute,
+    /// as the browser keeps the audio suspended.
+    pub fn should_show_unmute_overlay(&self) -> bool {
+        self.with_instance(|instance| {
+            instance.config.unmute_overlay == UnmuteOverlay::Visible && !instance.is_audio_running()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Tells whether the player is on screen, as seen by an `IntersectionObserver`.
+    ///
+    /// When the page limits how many players run at once, those off screen give way.
+    pub fn set_on_screen(&self, on_screen: bool) {
+        Self::apply_schedule(scheduler::set_on_screen(*self, on_screen));
     }
 
     pub fn set_trace_observer(&self, observer: JsValue) {
@@ -457,6 +887,111 @@ impl RuffleHandle {
 }
 
 impl RuffleHandle {
+    /// Stream an arbitrary movie file from (presumably) the Internet.
+    ///
+    /// This method should only be called once per player.
+    ///
+    /// `parameters` are *extra* parameters to set on the LoaderInfo -
+    /// parameters from `movie_url` query parameters will be automatically added.
+    ///
+    /// Completes once the movie header has been parsed.
+    pub(crate) async fn stream_from(
+        &self,
+        movie_url: String,
+        parameters: Vec<(String, String)>,
+    ) -> Result<(), JsValue> {
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        let error_message = format!("Error loading movie from {movie_url}");
+        self.with_core_mut(|core| {
+            let ruffle = *self;
+            let on_metadata = move |swf_header: &ruffle_core::swf::HeaderExt| {
+                ruffle.on_metadata(swf_header);
+                let _ = sender.send(());
+            };
+
+            core.fetch_root_movie(movie_url, parameters, Box::new(on_metadata));
+        })
+        .map_err(|e| JsValue::from(JsError::new(&e.to_string())))?;
+
+        // The callback is dropped without being called if the movie fails to load.
+        receiver.await.map_err(|_| movie_load_error(&error_message))
+    }
+
+    /// Play an arbitrary movie on this instance.
+    ///
+    /// This method should only be called once per player.
+    pub(crate) fn load_data(
+        &self,
+        swf_data: &Uint8Array,
+        parameters: Vec<(String, String)>,
+        swf_name: &str,
+    ) -> Result<(), JsValue> {
+        let window = web_sys::window().ok_or("Expected window")?;
+        let mut url = Url::from_str(&window.location().href()?)
+            .map_err(|e| format!("Error creating url: {e}"))?;
+        url.set_query(None);
+        url.set_fragment(None);
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop();
+            segments.push(swf_name);
+        }
+
+        let mut movie =
+            SwfMovie::from_data(&swf_data.to_vec(), url.to_string(), None).map_err(|e| {
+                let _ = self.with_core_mut(|core| {
+                    core.ui_mut()
+                        .display_root_movie_download_failed_message(true);
+                    core.ui().root_movie_load_failed(&e.to_string());
+                });
+                movie_load_error(&format!("Error loading movie: {e}"))
+            })?;
+        movie.append_parameters(parameters);
+
+        self.on_metadata(movie.header());
+
+        let _ = self.with_core_mut(move |core| {
+            core.update(|uc| {
+                uc.set_root_movie(movie);
+            });
+        });
+
+        Ok(())
+    }
+
+    /// Play the movie of a zipped game on this instance, with the rest of the zip
+    /// available to it as a virtual filesystem.
+    ///
+    /// This method should only be called once per player.
+    ///
+    /// Completes once the movie header has been parsed.
+    pub(crate) async fn load_bundle(
+        &self,
+        bundle_data: &Uint8Array,
+        parameters: Vec<(String, String)>,
+    ) -> Result<(), JsValue> {
+        let bundle = zip::ZipBundle::open(bundle_data.to_vec()).map_err(|e| {
+            let _ = self.with_core_mut(|core| {
+                core.ui_mut()
+                    .display_root_movie_download_failed_message(true);
+                core.ui().root_movie_load_failed(&e);
+            });
+            movie_load_error(&format!("Error loading bundle: {e}"))
+        })?;
+        let movie_url = bundle.movie_url().to_string();
+
+        self.with_core_mut(|core| {
+            if let Some(navigator) = core
+                .navigator_mut()
+                .downcast_mut::<navigator::WebNavigatorBackend>()
+            {
+                navigator.set_bundle(bundle);
+            }
+        })
+        .map_err(|e| JsValue::from(JsError::new(&e.to_string())))?;
+
+        self.stream_from(movie_url, parameters).await
+    }
+
     async fn new_internal(
         parent: HtmlElement,
         js_player: JavascriptPlayer,
@@ -475,8 +1010,10 @@ impl RuffleHandle {
             .into_js_result()?;
 
         let mut callstack = None;
+        let mut low_latency_input = false;
         if let Ok(core) = player.core.try_lock() {
             callstack = Some(core.callstack());
+            low_latency_input = core.low_latency_input();
         }
 
         // Create instance.
@@ -496,6 +1033,7 @@ impl RuffleHandle {
             mouse_leave_callback: None,
             mouse_down_callback: None,
             mouse_up_callback: None,
+            touch_cancel_callback: None,
             mouse_wheel_callback: None,
             key_down_callback: None,
             key_up_callback: None,
@@ -504,10 +1042,26 @@ impl RuffleHandle {
             focusin_callback: None,
             focusout_callback: None,
             focus_on_press_callback: None,
+            context_lost_callback: None,
+            context_restored_callback: None,
+            visibility_callback: None,
+            audio_unlock_callbacks: vec![],
+            audio_state_callback: None,
             timestamp: None,
             has_focus: false,
+            restoring_renderer: false,
+            background_handler: None,
+            background_handler_id: None,
+            was_playing_before_hidden: false,
+            was_playing_before_suspended: false,
             trace_observer: player.trace_observer,
             log_subscriber,
+            gamepads: gamepad::GamepadPoller::new(config.gamepad_mouse_speed),
+            config,
+            deferred_input: RefCell::new(VecDeque::new()),
+            low_latency_input: Cell::new(low_latency_input),
+            hash_session: None,
+            metadata: None,
         };
 
         // Prevent touch-scrolling on canvas.
@@ -518,18 +1072,46 @@ impl RuffleHandle {
             .warn_on_error();
 
         // Register the instance and create the animation frame closure.
+        let max_active_players = instance.config.max_active_players;
         let mut ruffle = Self::add_instance(instance)?;
+        Self::apply_schedule(scheduler::register(
+            ruffle,
+            max_active_players.map(|max| max as usize),
+        ));
+
+        if let Ok(report) = serde_wasm_bindgen::to_value(&player.renderer_report) {
+            js_player.on_renderer_created(report);
+        }
+
+        // Input on the canvases of additional windows, and page loads in web views,
+        // are delivered to this instance.
+        let _ = ruffle.with_core_mut(|core| {
+            let ui = core
+                .ui_mut()
+                .downcast_mut::<WebUiBackend>()
+                .expect("Web UI backend");
+            ui.native_windows_mut().set_ruffle_handle(ruffle);
+            ui.web_views_mut().set_ruffle_handle(ruffle);
+        });
 
         // For backward compatibility.
         parent.set_tab_index(-1);
 
         let shadow_host = Self::get_shadow_host(&parent);
-        Self::set_up_focus_management(ruffle, shadow_host.unwrap_or(parent))?;
+        Self::set_up_focus_management(ruffle, shadow_host.unwrap_or(parent.clone()))?;
 
         // Create the animation frame closure.
         ruffle.with_instance_mut(|instance| {
             instance.animation_handler = Some(Closure::new(move |timestamp| {
-                ruffle.tick(timestamp);
+                ruffle.tick(timestamp, false);
+            }));
+
+            // Create the closure that takes over while the page is hidden.
+            instance.background_handler = Some(Closure::new(move || {
+                let timestamp = web_sys::window()
+                    .and_then(|window| window.performance())
+                    .map_or(0.0, |performance| performance.now());
+                ruffle.tick(timestamp, true);
             }));
 
             // Create mouse move handler.
@@ -539,9 +1121,28 @@ impl RuffleHandle {
                 false,
                 move |js_event: PointerEvent| {
                     let _ = ruffle.with_instance(move |instance| {
-                        let event = PlayerEvent::MouseMove {
-                            x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
-                            y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
+                        if instance.handle_touch_event(&js_event, TouchPhase::Move) {
+                            if instance.has_focus {
+                                js_event.prevent_default();
+                            }
+                            return;
+                        }
+                        let is_mouse_locked = instance
+                            .window
+                            .document()
+                            .and_then(|document| document.pointer_lock_element())
+                            .is_some_and(|element| js_sys::Object::is(&element, &instance.canvas));
+                        let event = if is_mouse_locked {
+                            // The position is stuck where the mouse was locked.
+                            PlayerEvent::MouseMotion {
+                                dx: f64::from(js_event.movement_x()) * instance.device_pixel_ratio,
+                                dy: f64::from(js_event.movement_y()) * instance.device_pixel_ratio,
+                            }
+                        } else {
+                            PlayerEvent::MouseMove {
+                                x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
+                                y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
+                            }
                         };
                         let _ = instance.with_core_mut(|core| {
                             core.handle_event(event);
@@ -596,6 +1197,10 @@ impl RuffleHandle {
                                 .unchecked_ref::<Element>()
                                 .set_pointer_capture(js_event.pointer_id());
                         }
+                        if instance.handle_touch_event(&js_event, TouchPhase::Begin) {
+                            js_event.prevent_default();
+                            return;
+                        }
                         let device_pixel_ratio = instance.device_pixel_ratio;
                         let event = PlayerEvent::MouseDown {
                             x: f64::from(js_event.offset_x()) * device_pixel_ratio,
@@ -609,9 +1214,7 @@ impl RuffleHandle {
                             // TODO The index should be provided by the browser, not calculated.
                             index: None,
                         };
-                        let handled = instance
-                            .with_core_mut(|core| core.handle_event(event))
-                            .unwrap_or_default();
+                        let handled = instance.handle_input_events(&[event]);
 
                         if handled
                             && matches!(
@@ -642,6 +1245,12 @@ impl RuffleHandle {
                                 .unchecked_ref::<Element>()
                                 .release_pointer_capture(js_event.pointer_id());
                         }
+                        if instance.handle_touch_event(&js_event, TouchPhase::End) {
+                            if instance.has_focus {
+                                js_event.prevent_default();
+                            }
+                            return;
+                        }
                         let event = PlayerEvent::MouseUp {
                             x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
                             y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
@@ -652,9 +1261,7 @@ impl RuffleHandle {
                                 _ => MouseButton::Unknown,
                             },
                         };
-                        let _ = instance.with_core_mut(|core| {
-                            core.handle_event(event);
-                        });
+                        instance.handle_input_events(&[event]);
 
                         if instance.has_focus {
                             js_event.prevent_default();
@@ -663,6 +1270,18 @@ impl RuffleHandle {
                 },
             ));
 
+            // Create touch cancel handler.
+            instance.touch_cancel_callback = Some(JsCallback::register(
+                &player.canvas,
+                "pointercancel",
+                false,
+                move |js_event: PointerEvent| {
+                    let _ = ruffle.with_instance(|instance| {
+                        instance.handle_touch_event(&js_event, TouchPhase::Cancel);
+                    });
+                },
+            ));
+
             // Create mouse wheel handler.
             instance.mouse_wheel_callback = Some(JsCallback::register(
                 &player.canvas,
@@ -696,31 +1315,29 @@ impl RuffleHandle {
                 false,
                 move |js_event: KeyboardEvent| {
                     let _ = ruffle.with_instance(|instance| {
-                        if instance.has_focus {
+                        // Keys pressed while composing text belong to the input method editor.
+                        if instance.has_focus && !is_composing(&js_event) {
                             let mut paste_event = false;
-                            let _ = instance.with_core_mut(|core| {
-                                let key_code = web_to_ruffle_key_code(&js_event.code());
-                                let key_char = web_key_to_codepoint(&js_event.key());
-                                let is_ctrl_cmd = js_event.ctrl_key() || js_event.meta_key();
-                                core.handle_event(PlayerEvent::KeyDown { key_code, key_char });
-
-                                if let Some(control_code) = web_to_ruffle_text_control(
-                                    &js_event.key(),
-                                    is_ctrl_cmd,
-                                    js_event.shift_key(),
-                                ) {
-                                    paste_event = control_code == TextControlCode::Paste;
-                                    // The JS paste event fires separately and the clipboard text is not available until then,
-                                    // so we need to wait before handling it
-                                    if !paste_event {
-                                        core.handle_event(PlayerEvent::TextControl {
-                                            code: control_code,
-                                        });
-                                    }
-                                } else if let Some(codepoint) = key_char {
-                                    core.handle_event(PlayerEvent::TextInput { codepoint });
+                            let key_code = web_to_ruffle_key_code(&js_event.code());
+                            let key_char = web_key_to_codepoint(&js_event.key());
+                            let is_ctrl_cmd = js_event.ctrl_key() || js_event.meta_key();
+                            let mut events = vec![PlayerEvent::KeyDown { key_code, key_char }];
+
+                            if let Some(control_code) = web_to_ruffle_text_control(
+                                &js_event.key(),
+                                is_ctrl_cmd,
+                                js_event.shift_key(),
+                            ) {
+                                paste_event = control_code == TextControlCode::Paste;
+                                // The JS paste event fires separately and the clipboard text is not available until then,
+                                // so we need to wait before handling it
+                                if !paste_event {
+                                    events.push(PlayerEvent::TextControl { code: control_code });
                                 }
-                            });
+                            } else if let Some(codepoint) = key_char {
+                                events.push(PlayerEvent::TextInput { codepoint });
+                            }
+                            instance.handle_input_events(&events);
 
                             // Don't prevent the JS paste event from firing
                             if !paste_event {
@@ -766,12 +1383,11 @@ impl RuffleHandle {
                 false,
                 move |js_event: KeyboardEvent| {
                     let _ = ruffle.with_instance(|instance| {
-                        if instance.has_focus {
-                            let _ = instance.with_core_mut(|core| {
-                                let key_code = web_to_ruffle_key_code(&js_event.code());
-                                let key_char = web_key_to_codepoint(&js_event.key());
-                                core.handle_event(PlayerEvent::KeyUp { key_code, key_char });
-                            });
+                        if instance.has_focus && !is_composing(&js_event) {
+                            let key_code = web_to_ruffle_key_code(&js_event.code());
+                            let key_char = web_key_to_codepoint(&js_event.key());
+                            instance
+                                .handle_input_events(&[PlayerEvent::KeyUp { key_code, key_char }]);
                             js_event.prevent_default();
                         }
                     });
@@ -784,14 +1400,97 @@ impl RuffleHandle {
                         core.flush_shared_objects();
                     });
                 }));
+
+            // The browser only restores a lost WebGL context if we ask it to,
+            // by cancelling the loss event.
+            instance.context_lost_callback = Some(JsCallback::register(
+                &player.canvas,
+                "webglcontextlost",
+                false,
+                |js_event: Event| {
+                    tracing::warn!("WebGL context lost, waiting for it to be restored");
+                    js_event.prevent_default();
+                },
+            ));
+
+            instance.context_restored_callback = Some(JsCallback::register(
+                &player.canvas,
+                "webglcontextrestored",
+                false,
+                move |_| {
+                    ruffle.restore_renderer();
+                },
+            ));
+
+            if let Some(document) = window.document() {
+                instance.visibility_callback = Some(JsCallback::register(
+                    &document,
+                    "visibilitychange",
+                    false,
+                    move |_| {
+                        ruffle.on_visibility_change();
+                    },
+                ));
+            }
+
+            let audio_context = instance
+                .with_core_mut(|core| {
+                    core.audio()
+                        .downcast_ref::<audio::WebAudioBackend>()
+                        .map(|audio| audio.audio_context().clone())
+                })
+                .ok()
+                .flatten();
+            if let Some(audio_context) = audio_context {
+                // Browsers only let audio start with a user interaction, which are these events.
+                // Mouse presses count on press, while touches and pens count on release.
+                for name in ["pointerdown", "pointerup", "keydown"] {
+                    instance.audio_unlock_callbacks.push(JsCallback::register(
+                        &parent,
+                        name,
+                        true,
+                        move |_| {
+                            ruffle.resume_audio();
+                        },
+                    ));
+                }
+
+                let context = audio_context.clone();
+                let js_player = js_player.clone();
+                instance.audio_state_callback = Some(JsCallback::register(
+                    &audio_context,
+                    "statechange",
+                    false,
+                    move |_| {
+                        if context.state() == web_sys::AudioContextState::Running {
+                            js_player.on_audio_available();
+                        }
+                    },
+                ));
+            }
         })?;
 
         // Set initial timestamp and do initial tick to start animation loop.
-        ruffle.tick(0.0);
+        ruffle.tick(0.0, false);
 
         Ok(ruffle)
     }
 
+    /// Updates the clipboard seen by the movie, when the browser lets us read it.
+    async fn refresh_clipboard(&self) {
+        let Some((text, image)) = ui::read_clipboard().await else {
+            return;
+        };
+        let _ = self.with_core_mut(|core| {
+            let ui = core
+                .ui_mut()
+                .downcast_mut::<WebUiBackend>()
+                .expect("Web UI backend");
+            ui.set_clipboard_content_buffer(text);
+            ui.set_clipboard_image_buffer(image);
+        });
+    }
+
     fn get_shadow_host(element: &HtmlElement) -> Option<HtmlElement> {
         element
             .get_root_node()
@@ -827,6 +1526,11 @@ impl RuffleHandle {
                             });
                         }
                     });
+
+                    // The clipboard may have changed while the player was in the background.
+                    wasm_bindgen_futures::spawn_local(async move {
+                        ruffle.refresh_clipboard().await;
+                    });
                 },
             ));
 
@@ -862,6 +1566,8 @@ impl RuffleHandle {
                 // We want to set the focus as early as we can.
                 true,
                 move |_js_event| {
+                    Self::apply_schedule(scheduler::touch(ruffle));
+
                     let has_focus = ruffle
                         .with_instance(|instance| instance.has_focus)
                         .unwrap_or_default();
@@ -1012,17 +1718,26 @@ impl RuffleHandle {
         ret
     }
 
-    fn tick(&mut self, timestamp: f64) {
+    /// Ticks the player, either from an animation frame or from the timer
+    /// that runs while the page is hidden.
+    fn tick(&mut self, timestamp: f64, in_background: bool) {
         let mut dt = 0.0;
+        let mut catch_up = false;
         let mut new_dimensions = None;
+        let mut hash_session_running = false;
+        let mut gamepad_events = vec![];
         let _ = self.with_instance_mut(|instance| {
+            // A running hash session drives the player itself, and any resize is handled after it.
+            hash_session_running = instance.hash_session.is_some();
+
             // Check for canvas resize.
             let canvas_width = instance.canvas.client_width();
             let canvas_height = instance.canvas.client_height();
             let device_pixel_ratio = instance.window.device_pixel_ratio(); // Changes via user zooming.
-            if instance.canvas_width != canvas_width
-                || instance.canvas_height != canvas_height
-                || (instance.device_pixel_ratio - device_pixel_ratio).abs() >= f64::EPSILON
+            if !hash_session_running
+                && (instance.canvas_width != canvas_width
+                    || instance.canvas_height != canvas_height
+                    || (instance.device_pixel_ratio - device_pixel_ratio).abs() >= f64::EPSILON)
             {
                 // If a canvas resizes, its drawing context will get scaled. You must reset
                 // the width and height attributes of the canvas element to recreate the context.
@@ -1043,8 +1758,11 @@ impl RuffleHandle {
                 ));
             }
 
-            // Request next animation frame.
-            if let Some(handler) = &instance.animation_handler {
+            // Request next animation frame. The background timer repeats by itself,
+            // and animation frames resume once the page is visible again.
+            if in_background {
+                catch_up = instance.config.background_behavior == BackgroundBehavior::Run;
+            } else if let Some(handler) = &instance.animation_handler {
                 let id = instance
                     .window
                     .request_animation_frame(handler.as_ref().unchecked_ref())
@@ -1061,9 +1779,26 @@ impl RuffleHandle {
 
             // Store the timestamp of the last tick.
             instance.timestamp = Some(timestamp);
+
+            // Gamepads aren't tied to an element, so only the focused player listens to them.
+            if instance.has_focus && !hash_session_running {
+                let viewport = (
+                    f64::from(instance.canvas_width) * instance.device_pixel_ratio,
+                    f64::from(instance.canvas_height) * instance.device_pixel_ratio,
+                );
+                gamepad_events = instance
+                    .gamepads
+                    .poll(&instance.window.navigator(), dt, viewport);
+            }
         });
 
+        // Suspended players keep their animation frames, but skip ticking and rendering.
+        if hash_session_running || !scheduler::is_active(*self) {
+            return;
+        }
+
         // Tick the Ruffle core.
+        let mut replace_renderer = false;
         let _ = self.with_core_mut(|core| {
             if let Some((ref canvas, viewport_width, viewport_height, device_pixel_ratio)) =
                 new_dimensions
@@ -1078,33 +1813,191 @@ impl RuffleHandle {
                 });
             }
 
+            for event in gamepad_events {
+                core.handle_event(event);
+            }
+
+            if catch_up {
+                // The browser wakes up background pages rarely, so run every frame
+                // that passed since then, as far as the time budget allows.
+                let frame_time = 1000.0 / core.frame_rate();
+                let started = js_sys::Date::now();
+                while dt > frame_time
+                    && frame_time > 0.0
+                    && js_sys::Date::now() - started < BACKGROUND_CATCH_UP_BUDGET_MS
+                {
+                    core.tick(frame_time);
+                    dt -= frame_time;
+                }
+            }
             core.tick(dt);
 
             // Render if the core signals a new frame, or if we resized.
             if core.needs_render() || new_dimensions.is_some() {
                 core.render();
             }
+
+            // A lost WebGL context has to be restored by the browser first,
+            // but a lost WebGPU device can be replaced right away.
+            replace_renderer =
+                core.renderer().is_device_lost() && core.renderer().name() == "webgpu";
+        });
+
+        if replace_renderer {
+            self.restore_renderer();
+        }
+
+        // Deliver any input that is still waiting for an input micro-tick.
+        let _ = self.with_instance(|instance| {
+            instance.handle_input_events(&[]);
+        });
+    }
+
+    /// Creates a new renderer for the canvas, to replace one that lost its device.
+    ///
+    /// The movie keeps running in the meantime, but nothing is drawn.
+    fn restore_renderer(&self) {
+        let ruffle = *self;
+        let Ok(Some((config, canvas, name))) = self.with_instance_mut(|instance| {
+            if instance.restoring_renderer {
+                return None;
+            }
+            let name = instance.with_core(|core| core.renderer().name()).ok()?;
+            instance.restoring_renderer = true;
+            Some((instance.config.clone(), instance.canvas.clone(), name))
+        }) else {
+            return;
+        };
+
+        tracing::info!("Recreating the {name} renderer");
+        wasm_bindgen_futures::spawn_local(async move {
+            let renderer = config.create_renderer_for_canvas(name, &canvas).await;
+            let _ = ruffle.with_instance_mut(|instance| {
+                let Ok(renderer) = renderer else {
+                    // Stay in the restoring state, rather than retrying every frame.
+                    tracing::error!("Unable to recreate the {name} renderer");
+                    return;
+                };
+                instance.restoring_renderer = false;
+                let _ = instance.with_core_mut(|core| core.replace_renderer(renderer));
+                instance.js_player.on_renderer_restored();
+            });
+        });
+    }
+
+    /// Resumes the audio of a playing movie on a user interaction,
+    /// if the browser kept it suspended until then.
+    fn resume_audio(&self) {
+        let _ = self.with_core_mut(|core| {
+            let suspended = core
+                .audio()
+                .downcast_ref::<audio::WebAudioBackend>()
+                .is_some_and(|audio| !audio.is_running());
+            if suspended && core.is_playing() {
+                core.audio_mut().play();
+            }
+        });
+    }
+
+    /// Applies the background behavior when the page is hidden or shown again.
+    /// Suspends or resumes the players whose turn changed in the scheduler.
+    fn apply_schedule(changes: Vec<(RuffleHandle, bool)>) {
+        for (ruffle, active) in changes {
+            let _ = ruffle.with_instance_mut(|instance| {
+                if !active {
+                    instance.was_playing_before_suspended = instance
+                        .with_core_mut(|core| {
+                            let was_playing = core.is_playing();
+                            core.set_is_playing(false);
+                            was_playing
+                        })
+                        .unwrap_or(false);
+                } else if instance.was_playing_before_suspended {
+                    instance.was_playing_before_suspended = false;
+                    let _ = instance.with_core_mut(|core| core.set_is_playing(true));
+                    // Don't make up for the time spent suspended.
+                    instance.timestamp = None;
+                }
+            });
+        }
+    }
+
+    fn on_visibility_change(&self) {
+        let _ = self.with_instance_mut(|instance| {
+            let hidden = instance
+                .window
+                .document()
+                .is_some_and(|document| document.hidden());
+
+            match instance.config.background_behavior {
+                BackgroundBehavior::Pause => {
+                    if hidden {
+                        instance.was_playing_before_hidden = instance
+                            .with_core_mut(|core| {
+                                let was_playing = core.is_playing();
+                                core.set_is_playing(false);
+                                was_playing
+                            })
+                            .unwrap_or(false);
+                    } else if instance.was_playing_before_hidden {
+                        let _ = instance.with_core_mut(|core| core.set_is_playing(true));
+                        // Don't make up for the time spent paused.
+                        instance.timestamp = None;
+                    }
+                }
+                BackgroundBehavior::Throttle | BackgroundBehavior::Run => {
+                    if hidden {
+                        let interval = if instance.config.background_behavior
+                            == BackgroundBehavior::Throttle
+                        {
+                            THROTTLED_TICK_INTERVAL_MS
+                        } else {
+                            instance
+                                .with_core(|core| 1000.0 / core.frame_rate())
+                                .unwrap_or(THROTTLED_TICK_INTERVAL_MS)
+                        };
+                        instance.start_background_ticks(interval);
+                    } else {
+                        instance.stop_background_ticks();
+                    }
+                }
+            }
         });
     }
 
     fn on_metadata(&self, swf_header: &ruffle_core::swf::HeaderExt) {
-        let _ = self.with_instance(|instance| {
-            // Convert the background color to an HTML hex color ("#FFFFFF").
-            let background_color = swf_header
-                .background_color()
-                .map(|color| format!("#{:06X}", color.to_rgb()));
-            let metadata = MovieMetadata {
-                width: swf_header.stage_size().width().to_pixels(),
-                height: swf_header.stage_size().height().to_pixels(),
-                frame_rate: swf_header.frame_rate().to_f32(),
-                num_frames: swf_header.num_frames(),
-                uncompressed_len: swf_header.uncompressed_len(),
-                swf_version: swf_header.version(),
-                background_color,
-                is_action_script_3: swf_header.is_action_script_3(),
-            };
+        // Convert the background color to an HTML hex color ("#FFFFFF").
+        let background_color = swf_header
+            .background_color()
+            .map(|color| format!("#{:06X}", color.to_rgb()));
+        let compression = match swf_header.compression() {
+            ruffle_core::swf::Compression::None => "none",
+            ruffle_core::swf::Compression::Zlib => "zlib",
+            ruffle_core::swf::Compression::Lzma => "lzma",
+        };
+        let metadata = MovieMetadata {
+            width: swf_header.stage_size().width().to_pixels(),
+            height: swf_header.stage_size().height().to_pixels(),
+            frame_rate: swf_header.frame_rate().to_f32(),
+            num_frames: swf_header.num_frames(),
+            uncompressed_len: swf_header.uncompressed_len(),
+            swf_version: swf_header.version(),
+            background_color,
+            is_action_script_3: swf_header.is_action_script_3(),
+            avm_version: if swf_header.is_action_script_3() {
+                2
+            } else {
+                1
+            },
+            compression,
+            xmp_metadata: swf_header.metadata().map(str::to_owned),
+        };
+        let value = serde_wasm_bindgen::to_value(&metadata);
 
-            if let Ok(value) = serde_wasm_bindgen::to_value(&metadata) {
+        // Store the metadata before telling the page, which may ask for it right away.
+        let _ = self.with_instance_mut(|instance| instance.metadata = Some(metadata));
+        let _ = self.with_instance(|instance| {
+            if let Ok(value) = value {
                 instance.js_player.set_metadata(value);
             }
         });
@@ -1112,7 +2005,26 @@ impl RuffleHandle {
 }
 
 impl RuffleInstance {
-    #[allow(dead_code)]
+    /// Starts ticking the player every `interval` milliseconds, for while the page is hidden.
+    fn start_background_ticks(&mut self, interval: f64) {
+        self.stop_background_ticks();
+        if let Some(handler) = &self.background_handler {
+            self.background_handler_id = self
+                .window
+                .set_interval_with_callback_and_timeout_and_arguments_0(
+                    handler.as_ref().unchecked_ref(),
+                    interval.clamp(1.0, THROTTLED_TICK_INTERVAL_MS) as i32,
+                )
+                .ok();
+        }
+    }
+
+    fn stop_background_ticks(&mut self) {
+        if let Some(id) = self.background_handler_id.take() {
+            self.window.clear_interval_with_handle(id);
+        }
+    }
+
     fn with_core<F, O>(&self, f: F) -> Result<O, RuffleInstanceError>
     where
         F: FnOnce(&ruffle_core::Player) -> O,
@@ -1142,6 +2054,87 @@ impl RuffleInstance {
         }
         ret
     }
+
+    /// Returns whether the audio is playing, or there's no audio to wait for.
+    fn is_audio_running(&self) -> bool {
+        self.with_core_mut(|core| {
+            core.audio()
+                .downcast_ref::<audio::WebAudioBackend>()
+                .map_or(true, |audio| audio.is_running())
+        })
+        .unwrap_or(true)
+    }
+
+    /// Delivers input events to the player, returning whether any of them were handled.
+    ///
+    /// The player can be busy when an event arrives, such as when page script called by
+    /// the movie dispatches one. Such events are normally dropped, but in low-latency input
+    /// mode they are delivered on the next tick instead, so that no key press goes unheard.
+    /// Passes a pointer event from a touch screen to the player as a touch point.
+    ///
+    /// Returns `true` if the event shouldn't also be handled as a mouse event,
+    /// which is the case for every touch point but the primary one.
+    fn handle_touch_event(&self, js_event: &PointerEvent, phase: TouchPhase) -> bool {
+        if js_event.pointer_type() != "touch" {
+            return false;
+        }
+        let event = TouchEvent {
+            id: js_event.pointer_id() as u32 as u64,
+            phase,
+            x: f64::from(js_event.offset_x()) * self.device_pixel_ratio,
+            y: f64::from(js_event.offset_y()) * self.device_pixel_ratio,
+        };
+        let _ = self.with_core_mut(|core| {
+            core.handle_touch_event(event);
+        });
+        !js_event.is_primary()
+    }
+
+    /// Delivers input events to the player, returning whether any of them were handled.
+    ///
+    /// In low-latency input mode, events are delivered in an input micro-tick, so that the
+    /// sounds they start are heard right away. Events that don't fit in the micro-tick, or
+    /// that arrive while the player is busy (such as when page script called by the movie
+    /// dispatches one), wait for the next micro-tick or frame. Otherwise, such events are
+    /// dropped.
+    fn handle_input_events(&self, events: &[PlayerEvent]) -> bool {
+        let low_latency_input = self.low_latency_input.get();
+        if low_latency_input {
+            let mut deferred = self.deferred_input.borrow_mut();
+            for &event in events {
+                if deferred.len() >= MAX_DEFERRED_INPUT_EVENTS {
+                    tracing::warn!("Too many input events are waiting for the player");
+                    deferred.pop_front();
+                }
+                deferred.push_back(event);
+            }
+        }
+
+        let Ok(mut core) = self.core.try_lock() else {
+            if !low_latency_input {
+                tracing::error!("{}", RuffleInstanceError::TryLockError);
+            }
+            return false;
+        };
+
+        let handled = if low_latency_input {
+            // Event handlers may dispatch more events, so the queue isn't borrowed meanwhile.
+            let mut pending = std::mem::take(&mut *self.deferred_input.borrow_mut());
+            let handled = core.run_input_micro_tick(&mut pending);
+            let mut deferred = self.deferred_input.borrow_mut();
+            pending.extend(deferred.drain(..));
+            *deferred = pending;
+            handled
+        } else {
+            let mut handled = false;
+            for &event in events {
+                handled |= core.handle_event(event);
+            }
+            handled
+        };
+        self.low_latency_input.set(core.low_latency_input());
+        handled
+    }
 }
 
 impl Drop for RuffleInstance {
@@ -1160,6 +2153,7 @@ impl Drop for RuffleInstance {
                 .cancel_animation_frame(id.into())
                 .warn_on_error();
         }
+        self.stop_background_ticks();
     }
 }
 
@@ -1177,6 +2171,29 @@ pub enum RuffleInstanceError {
     InstanceNotFound,
 }
 
+/// Creates the error that a player rejects with when its root movie fails to load.
+///
+/// The user has already been told about the failure by then, so it's named differently from
+/// other errors to let the page avoid reporting it twice.
+fn movie_load_error(message: &str) -> JsValue {
+    let error = js_sys::Error::new(message);
+    error.set_name("MovieLoadError");
+    error.into()
+}
+
+/// Resolves once the browser has had a chance to run other tasks, such as rendering the page
+/// and handling input.
+async fn yield_to_event_loop() {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback(&resolve);
+        } else {
+            let _ = resolve.call0(&JsValue::UNDEFINED);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
 fn parse_movie_parameters(input: &JsValue) -> Vec<(String, String)> {
     let mut params = Vec::new();
     if let Ok(keys) = js_sys::Reflect::own_keys(input) {
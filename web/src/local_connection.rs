@@ -0,0 +1,202 @@
+//! `LocalConnection`s between the players on a page, and other pages of the same origin.
+//!
+//! Every player of this page joins the same `LocalConnectionBus`. The bus reaches other
+//! pages (and frames) through a `BroadcastChannel`, where each page announces the names
+//! its players listen on, so that connecting and sending can be decided synchronously.
+
+use crate::JsCallback;
+use ruffle_core::backend::local_connection::{
+    InProcessLocalConnectionBackend, LocalConnectionBackend, LocalConnectionBus,
+    LocalConnectionMessage,
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::{Rc, Weak};
+use web_sys::{BroadcastChannel, MessageEvent};
+
+const CHANNEL_NAME: &str = "ruffle-local-connection";
+
+thread_local! {
+    static BRIDGE: RefCell<Option<Rc<Bridge>>> = const { RefCell::new(None) };
+}
+
+/// What the pages tell each other over the channel.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum BridgeMessage {
+    /// A player of the sending page listens on `name`.
+    Connect { name: String },
+
+    /// No player of the sending page listens on `name` anymore.
+    Close { name: String },
+
+    /// Asks every page to announce the names it listens on, sent when a page joins.
+    Query,
+
+    /// A method call for the player listening on `name`.
+    Message { name: String, data: Vec<u8> },
+}
+
+/// The bus of this page, and its link to the other pages.
+struct Bridge {
+    bus: LocalConnectionBus,
+
+    /// The channel to other pages, if the browser supports it.
+    channel: Option<BroadcastChannel>,
+
+    /// The connection names that players of other pages listen on.
+    remote_names: RefCell<HashSet<String>>,
+
+    _on_message: Option<JsCallback<MessageEvent>>,
+}
+
+impl Bridge {
+    fn get() -> Rc<Self> {
+        BRIDGE.with(|bridge| {
+            bridge
+                .borrow_mut()
+                .get_or_insert_with(|| Rc::new_cyclic(Self::new))
+                .clone()
+        })
+    }
+
+    fn new(this: &Weak<Self>) -> Self {
+        let channel = match BroadcastChannel::new(CHANNEL_NAME) {
+            Ok(channel) => Some(channel),
+            Err(e) => {
+                tracing::warn!("LocalConnection is limited to this page: {e:?}");
+                None
+            }
+        };
+        let on_message = channel.as_ref().map(|channel| {
+            let this = this.clone();
+            JsCallback::register(channel, "message", false, move |event: MessageEvent| {
+                if let Some(this) = this.upgrade() {
+                    match serde_wasm_bindgen::from_value(event.data()) {
+                        Ok(message) => this.handle(message),
+                        Err(e) => tracing::warn!("Invalid LocalConnection message: {e}"),
+                    }
+                }
+            })
+        });
+
+        let bridge = Self {
+            bus: LocalConnectionBus::new(),
+            channel,
+            remote_names: RefCell::new(HashSet::new()),
+            _on_message: on_message,
+        };
+        bridge.post(&BridgeMessage::Query);
+        bridge
+    }
+
+    fn post(&self, message: &BridgeMessage) {
+        let Some(channel) = &self.channel else {
+            return;
+        };
+        let result = serde_wasm_bindgen::to_value(message)
+            .map_err(Into::into)
+            .and_then(|value| channel.post_message(&value));
+        if let Err(e) = result {
+            tracing::warn!("Unable to post LocalConnection message: {e:?}");
+        }
+    }
+
+    fn handle(&self, message: BridgeMessage) {
+        match message {
+            BridgeMessage::Connect { name } => {
+                self.remote_names.borrow_mut().insert(name);
+            }
+            BridgeMessage::Close { name } => {
+                self.remote_names.borrow_mut().remove(&name);
+            }
+            BridgeMessage::Query => {
+                for name in self.bus.connection_names() {
+                    self.post(&BridgeMessage::Connect { name });
+                }
+            }
+            BridgeMessage::Message { name, data } => {
+                self.bus.deliver(LocalConnectionMessage {
+                    connection_name: name,
+                    data,
+                });
+            }
+        }
+    }
+}
+
+/// A player on the bus of this page.
+pub struct WebLocalConnectionBackend {
+    inner: InProcessLocalConnectionBackend,
+    bridge: Rc<Bridge>,
+
+    /// The names this player listens on, to be closed on other pages when it's dropped.
+    names: HashSet<String>,
+}
+
+impl WebLocalConnectionBackend {
+    pub fn new() -> Self {
+        let bridge = Bridge::get();
+        Self {
+            inner: bridge.bus.join(),
+            bridge,
+            names: HashSet::new(),
+        }
+    }
+}
+
+impl Default for WebLocalConnectionBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalConnectionBackend for WebLocalConnectionBackend {
+    fn connect(&mut self, name: &str) -> bool {
+        if self.bridge.remote_names.borrow().contains(name) || !self.inner.connect(name) {
+            return false;
+        }
+        self.names.insert(name.to_owned());
+        self.bridge.post(&BridgeMessage::Connect {
+            name: name.to_owned(),
+        });
+        true
+    }
+
+    fn close(&mut self, name: &str) {
+        self.inner.close(name);
+        if self.names.remove(name) {
+            self.bridge.post(&BridgeMessage::Close {
+                name: name.to_owned(),
+            });
+        }
+    }
+
+    fn is_connected(&self, name: &str) -> bool {
+        self.inner.is_connected(name) || self.bridge.remote_names.borrow().contains(name)
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) {
+        if self.inner.is_connected(&message.connection_name) {
+            self.inner.send(message);
+        } else {
+            self.bridge.post(&BridgeMessage::Message {
+                name: message.connection_name,
+                data: message.data,
+            });
+        }
+    }
+
+    fn receive(&mut self) -> Vec<LocalConnectionMessage> {
+        self.inner.receive()
+    }
+}
+
+impl Drop for WebLocalConnectionBackend {
+    fn drop(&mut self) {
+        for name in std::mem::take(&mut self.names) {
+            self.bridge.post(&BridgeMessage::Close { name });
+        }
+    }
+}
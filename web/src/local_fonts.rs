@@ -0,0 +1,171 @@
+//! Device fonts from the Local Font Access API, where the browser supports and permits it.
+//!
+//! The list of fonts is queried when the player is created, but a font is only read
+//! once a movie asks for it. Fonts have to be provided synchronously, so a font that
+//! isn't read yet is registered with the player later, and text fields are laid out again.
+
+use crate::log_adapter::LogSubscriber;
+use js_sys::{Array, ArrayBuffer, Function, Promise, Reflect, Uint8Array};
+use ruffle_core::backend::ui::FontDefinition;
+use ruffle_core::Player;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{Blob, Window};
+
+/// A font face on the system, as listed by `queryLocalFonts`.
+struct LocalFontFace {
+    postscript_name: String,
+    is_bold: bool,
+    is_italic: bool,
+
+    /// The `FontData` object, which can read the font file.
+    data: JsValue,
+}
+
+pub struct LocalFonts {
+    /// Every font face on the system, by lowercase family name.
+    faces: HashMap<String, Vec<LocalFontFace>>,
+
+    /// The requested fonts that were already looked up, so that each is only read once.
+    requested: RefCell<HashSet<(String, bool, bool)>>,
+
+    player: RefCell<Weak<Mutex<Player>>>,
+    log_subscriber: Arc<LogSubscriber>,
+}
+
+impl LocalFonts {
+    /// Lists the fonts on the system, or returns `None` if the page may not access them.
+    pub async fn query(window: &Window, log_subscriber: Arc<LogSubscriber>) -> Option<Self> {
+        let query = Reflect::get(window, &JsValue::from_str("queryLocalFonts"))
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?;
+        let promise = match query.call0(window) {
+            Ok(promise) => Promise::from(promise),
+            Err(e) => {
+                tracing::warn!("Unable to query local fonts: {e:?}");
+                return None;
+            }
+        };
+        let fonts = match JsFuture::from(promise).await {
+            Ok(fonts) => Array::from(&fonts),
+            Err(e) => {
+                tracing::warn!("Unable to query local fonts: {e:?}");
+                return None;
+            }
+        };
+
+        let get = |font: &JsValue, key: &str| {
+            Reflect::get(font, &JsValue::from_str(key))
+                .ok()
+                .and_then(|value| value.as_string())
+                .unwrap_or_default()
+        };
+        let mut faces: HashMap<String, Vec<LocalFontFace>> = HashMap::new();
+        for font in fonts.iter() {
+            let style = get(&font, "style").to_ascii_lowercase();
+            faces
+                .entry(get(&font, "family").to_lowercase())
+                .or_default()
+                .push(LocalFontFace {
+                    postscript_name: get(&font, "postscriptName"),
+                    is_bold: style.contains("bold"),
+                    is_italic: style.contains("italic") || style.contains("oblique"),
+                    data: font,
+                });
+        }
+        tracing::info!("Found {} local font families", faces.len());
+
+        Some(Self {
+            faces,
+            requested: RefCell::new(HashSet::new()),
+            player: RefCell::new(Weak::new()),
+            log_subscriber,
+        })
+    }
+
+    /// We need to set the player after construction because the player is created after the font list.
+    pub fn set_player(&self, player: Weak<Mutex<Player>>) {
+        *self.player.borrow_mut() = player;
+    }
+
+    /// Starts reading the system font matching the given name and style, if there is one.
+    ///
+    /// Once it's read, it's registered with the player as `name`.
+    pub fn load(&self, name: &str, is_bold: bool, is_italic: bool) {
+        if !self
+            .requested
+            .borrow_mut()
+            .insert((name.to_lowercase(), is_bold, is_italic))
+        {
+            return;
+        }
+        let Some(face) = self.faces.get(&name.to_lowercase()).and_then(|faces| {
+            faces
+                .iter()
+                .find(|face| face.is_bold == is_bold && face.is_italic == is_italic)
+        }) else {
+            return;
+        };
+
+        tracing::info!(
+            "Loading local font \"{}\" for \"{name}\" (italic: {is_italic}, bold: {is_bold})",
+            face.postscript_name
+        );
+        let name = name.to_owned();
+        let data = face.data.clone();
+        let player = self.player.borrow().clone();
+        let subscriber = self.log_subscriber.clone();
+        spawn_local(async move {
+            let _subscriber = tracing::subscriber::set_default(subscriber);
+            let bytes = match read_font(&data).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::warn!("Unable to read local font \"{name}\": {e:?}");
+                    return;
+                }
+            };
+
+            // This may run while the player is ticking, in which case we wait for a new task.
+            while player
+                .upgrade()
+                .is_some_and(|player| player.try_lock().is_err())
+            {
+                let promise = Promise::new(&mut |resolve, _reject| {
+                    web_sys::window()
+                        .expect("window")
+                        .set_timeout_with_callback(&resolve)
+                        .expect("Failed to call setTimeout with dummy promise");
+                });
+                let _ = JsFuture::from(promise).await;
+            }
+            let Some(player) = player.upgrade() else {
+                return;
+            };
+            let Ok(mut player) = player.try_lock() else {
+                return;
+            };
+            player.register_device_font(FontDefinition::FontFile {
+                name,
+                is_bold,
+                is_italic,
+                data: bytes,
+                index: 0,
+            });
+            player.relayout_text_fields();
+        });
+    }
+}
+
+/// Reads the font file of a `FontData` object.
+async fn read_font(data: &JsValue) -> Result<Vec<u8>, JsValue> {
+    let blob = Reflect::get(data, &JsValue::from_str("blob"))?.dyn_into::<Function>()?;
+    let blob: Blob = JsFuture::from(Promise::from(blob.call0(data)?))
+        .await?
+        .dyn_into()?;
+    let buffer: ArrayBuffer = JsFuture::from(blob.array_buffer()).await?.dyn_into()?;
+    Ok(Uint8Array::new(&buffer).to_vec())
+}
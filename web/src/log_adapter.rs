@@ -1,8 +1,21 @@
+use crate::JavascriptPlayer;
 use js_sys::Function;
-use ruffle_core::backend::log::LogBackend;
+use ruffle_core::backend::log::{LogBackend, TraceOutput};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::{cell::RefCell, rc::Rc};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layered};
+use tracing_subscriber::{Layer, Registry};
+use tracing_wasm::WASMLayer;
 use wasm_bindgen::{JsCast, JsValue};
 
+/// The log subscriber of a Ruffle instance.
+pub type LogSubscriber = Layered<Option<JsLogLayer>, Layered<WASMLayer, Registry>>;
+
 pub struct WebLogBackend {
     trace_observer: Rc<RefCell<JsValue>>,
 }
@@ -21,3 +34,107 @@ impl LogBackend for WebLogBackend {
         }
     }
 }
+
+/// Dispatches `trace()` output as `traceoutput` events on the player element.
+pub struct WebTraceOutput {
+    js_player: JavascriptPlayer,
+}
+
+impl WebTraceOutput {
+    pub fn new(js_player: JavascriptPlayer) -> Self {
+        Self { js_player }
+    }
+}
+
+impl TraceOutput for WebTraceOutput {
+    fn trace(&self, message: &str) {
+        self.js_player.on_trace_output(message);
+    }
+}
+
+thread_local! {
+    /// The functions that `JsLogLayer`s forward to, by layer id.
+    /// Subscribers must be `Send + Sync`, which JS functions aren't, so the layers only hold an id.
+    static LOG_CALLBACKS: RefCell<HashMap<u32, Function>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_LOG_CALLBACK_ID: AtomicU32 = AtomicU32::new(0);
+
+/// A tracing layer that forwards log records to a JavaScript function.
+///
+/// Records are forwarded up to the given level, and `trace()` output always is.
+pub struct JsLogLayer {
+    id: u32,
+    max_level: Level,
+}
+
+impl JsLogLayer {
+    pub fn new(callback: Function, max_level: Level) -> Self {
+        let id = NEXT_LOG_CALLBACK_ID.fetch_add(1, Ordering::Relaxed);
+        LOG_CALLBACKS.with(|callbacks| callbacks.borrow_mut().insert(id, callback));
+        Self { id, max_level }
+    }
+}
+
+impl Drop for JsLogLayer {
+    fn drop(&mut self) {
+        let _ = LOG_CALLBACKS.try_with(|callbacks| callbacks.borrow_mut().remove(&self.id));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for JsLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let is_avm_trace = metadata.target() == "avm_trace";
+        if *metadata.level() > self.max_level && !is_avm_trace {
+            return;
+        }
+
+        let Some(callback) =
+            LOG_CALLBACKS.with(|callbacks| callbacks.borrow().get(&self.id).cloned())
+        else {
+            return;
+        };
+
+        let mut message = MessageVisitor::default();
+        event.record(&mut message);
+        let record = JsLogRecord {
+            level: metadata.level().as_str().to_ascii_lowercase(),
+            target: metadata.target(),
+            message: message.0,
+            is_avm_trace,
+        };
+
+        // Don't log failures here, as that would call back into this layer.
+        if let Ok(record) = serde_wasm_bindgen::to_value(&record) {
+            let _ = callback.call1(&JsValue::NULL, &record);
+        }
+    }
+}
+
+/// A log record as given to the page.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsLogRecord<'a> {
+    level: String,
+    target: &'a str,
+    message: String,
+    is_avm_trace: bool,
+}
+
+/// Formats the message of an event, followed by its other fields as `name=value`.
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        if field.name() == "message" {
+            let _ = write!(self.0, "{value:?}");
+        } else {
+            let _ = write!(self.0, "{}={value:?}", field.name());
+        }
+    }
+}
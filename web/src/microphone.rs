@@ -0,0 +1,281 @@
+use crate::camera::{enumerate_devices, get_user_media, stop_tracks, MediaDevice};
+use js_sys::{Object, Reflect};
+use ruffle_core::backend::microphone::{CaptureStatus, MicrophoneBackend, Resampler};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{
+    AudioContext, AudioProcessingEvent, MediaDeviceKind, MediaDevices, MediaStream,
+    MediaStreamAudioSourceNode, MediaStreamConstraints, ScriptProcessorNode,
+};
+
+/// The number of samples in each buffer the script processor hands over.
+const BUFFER_SIZE: u32 = 2048;
+
+/// The most samples kept for a microphone that the movie isn't reading from.
+const MAX_BUFFERED_SAMPLES: usize = 96000;
+
+/// A microphone that a movie asked to capture from.
+struct CaptureState {
+    status: CaptureStatus,
+    stream: Option<MediaStream>,
+    source: Option<MediaStreamAudioSourceNode>,
+    processor: Option<ScriptProcessorNode>,
+    on_audio_process: Option<Closure<dyn FnMut(AudioProcessingEvent)>>,
+
+    /// The samples captured since the movie last took them, at the audio context's rate.
+    samples: Vec<f32>,
+}
+
+impl CaptureState {
+    fn stop(&mut self) {
+        self.status = CaptureStatus::Closed;
+        if let Some(processor) = self.processor.take() {
+            processor.set_onaudioprocess(None);
+            let _ = processor.disconnect();
+        }
+        if let Some(source) = self.source.take() {
+            let _ = source.disconnect();
+        }
+        if let Some(stream) = self.stream.take() {
+            stop_tracks(&stream);
+        }
+        self.on_audio_process = None;
+    }
+}
+
+struct Capture {
+    state: Rc<RefCell<CaptureState>>,
+    resampler: Resampler,
+}
+
+/// Captures microphones with `getUserMedia`, taking their samples from a Web Audio graph.
+pub struct WebMicrophoneBackend {
+    media_devices: Option<MediaDevices>,
+    devices: Rc<RefCell<Vec<MediaDevice>>>,
+    captures: HashMap<usize, Capture>,
+    context: Option<AudioContext>,
+}
+
+impl WebMicrophoneBackend {
+    pub fn new() -> Self {
+        let media_devices = web_sys::window().and_then(|w| w.navigator().media_devices().ok());
+
+        // Until the microphones are enumerated, offer the browser's default one.
+        let devices = Rc::new(RefCell::new(vec![]));
+        if let Some(media_devices) = &media_devices {
+            devices.borrow_mut().push(MediaDevice {
+                device_id: None,
+                label: "Default".to_string(),
+            });
+
+            let media_devices = media_devices.clone();
+            let devices = devices.clone();
+            spawn_local(async move {
+                match enumerate_devices(&media_devices, MediaDeviceKind::Audioinput, "Microphone")
+                    .await
+                {
+                    Ok(microphones) if !microphones.is_empty() => {
+                        *devices.borrow_mut() = microphones
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Couldn't enumerate microphones: {:?}", e),
+                }
+            });
+        }
+
+        Self {
+            media_devices,
+            devices,
+            captures: HashMap::new(),
+            context: None,
+        }
+    }
+
+    fn context(&mut self) -> Option<AudioContext> {
+        if self.context.is_none() {
+            self.context = AudioContext::new().ok();
+        }
+        self.context.clone()
+    }
+}
+
+impl Default for WebMicrophoneBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for WebMicrophoneBackend {
+    fn drop(&mut self) {
+        // Turn the microphones off when the player is destroyed.
+        for capture in self.captures.values() {
+            capture.state.borrow_mut().stop();
+        }
+        if let Some(context) = &self.context {
+            let _ = context.close();
+        }
+    }
+}
+
+impl MicrophoneBackend for WebMicrophoneBackend {
+    fn names(&self) -> Vec<String> {
+        self.devices
+            .borrow()
+            .iter()
+            .map(|device| device.label.clone())
+            .collect()
+    }
+
+    fn open(&mut self, index: usize, sample_rate: u32) {
+        self.close(index);
+
+        let Some(media_devices) = self.media_devices.clone() else {
+            return;
+        };
+        let Some(context) = self.context() else {
+            return;
+        };
+        let Some(device_id) = self
+            .devices
+            .borrow()
+            .get(index)
+            .map(|device| device.device_id.clone())
+        else {
+            return;
+        };
+
+        let state = Rc::new(RefCell::new(CaptureState {
+            status: CaptureStatus::Pending,
+            stream: None,
+            source: None,
+            processor: None,
+            on_audio_process: None,
+            samples: vec![],
+        }));
+        self.captures.insert(
+            index,
+            Capture {
+                state: state.clone(),
+                resampler: Resampler::new(context.sample_rate() as u32, sample_rate),
+            },
+        );
+
+        let constraints = audio_constraints(device_id.as_deref());
+        spawn_local(async move {
+            let stream = match get_user_media(&media_devices, &constraints).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::warn!("Couldn't open microphone {index}: {:?}", e);
+                    let mut state = state.borrow_mut();
+                    if state.status == CaptureStatus::Pending {
+                        state.status = CaptureStatus::Denied;
+                    }
+                    return;
+                }
+            };
+
+            let mut state_ref = state.borrow_mut();
+            state_ref.stream = Some(stream.clone());
+            if state_ref.status != CaptureStatus::Pending {
+                // The movie stopped capturing while waiting for the user.
+                state_ref.stop();
+                return;
+            }
+            drop(state_ref);
+
+            if let Err(e) = connect(&context, &stream, &state) {
+                tracing::error!("Couldn't capture microphone {index}: {:?}", e);
+                let mut state = state.borrow_mut();
+                state.stop();
+                state.status = CaptureStatus::Denied;
+            }
+        });
+    }
+
+    fn close(&mut self, index: usize) {
+        if let Some(capture) = self.captures.remove(&index) {
+            capture.state.borrow_mut().stop();
+        }
+    }
+
+    fn status(&self, index: usize) -> CaptureStatus {
+        self.captures
+            .get(&index)
+            .map(|capture| capture.state.borrow().status)
+            .unwrap_or(CaptureStatus::Closed)
+    }
+
+    fn take_samples(&mut self, index: usize) -> Vec<f32> {
+        let Some(capture) = self.captures.get_mut(&index) else {
+            return vec![];
+        };
+        let samples = std::mem::take(&mut capture.state.borrow_mut().samples);
+        capture.resampler.process(&samples)
+    }
+}
+
+fn audio_constraints(device_id: Option<&str>) -> MediaStreamConstraints {
+    let audio = Object::new();
+    if let Some(device_id) = device_id {
+        let exact = Object::new();
+        let _ = Reflect::set(&exact, &"exact".into(), &device_id.into());
+        let _ = Reflect::set(&audio, &"deviceId".into(), &exact);
+    }
+
+    let constraints = MediaStreamConstraints::new();
+    constraints.set_audio(&audio);
+    constraints.set_video(&false.into());
+    constraints
+}
+
+/// Feeds the stream through a script processor, which collects its samples.
+fn connect(
+    context: &AudioContext,
+    stream: &MediaStream,
+    state: &Rc<RefCell<CaptureState>>,
+) -> Result<(), JsValue> {
+    let source = context.create_media_stream_source(stream)?;
+    let processor = context
+        .create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(
+            BUFFER_SIZE,
+            1,
+            1,
+        )?;
+
+    let weak_state = Rc::downgrade(state);
+    let on_audio_process = Closure::<dyn FnMut(_)>::new(move |event: AudioProcessingEvent| {
+        let Some(state) = weak_state.upgrade() else {
+            return;
+        };
+        let Ok(buffer) = event.input_buffer() else {
+            return;
+        };
+        let Ok(data) = buffer.get_channel_data(0) else {
+            return;
+        };
+
+        let mut state = state.borrow_mut();
+        state.samples.extend(data);
+        if state.samples.len() > MAX_BUFFERED_SAMPLES {
+            let excess = state.samples.len() - MAX_BUFFERED_SAMPLES;
+            state.samples.drain(..excess);
+        }
+    });
+    processor.set_onaudioprocess(Some(on_audio_process.as_ref().unchecked_ref()));
+
+    source.connect_with_audio_node(&processor)?;
+    // Script processors only run while connected to the output. Nothing is written to
+    // the output buffer, so this plays silence.
+    processor.connect_with_audio_node(&context.destination())?;
+    let _ = context.resume();
+
+    let mut state = state.borrow_mut();
+    state.source = Some(source);
+    state.processor = Some(processor);
+    state.on_audio_process = Some(on_audio_process);
+    state.status = CaptureStatus::Active;
+    Ok(())
+}
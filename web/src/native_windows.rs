@@ -0,0 +1,339 @@
+//! Additional canvases for `NativeWindow`s opened by AIR content.
+//!
+//! Each window is an absolutely positioned frame (a title bar with a close button,
+//! and a canvas) inside a container element provided by the page.
+
+use crate::{JsCallback, RuffleHandle};
+use ruffle_core::backend::ui::{NativeWindowDescriptor, NativeWindowId};
+use ruffle_core::events::{MouseButton, MouseWheelDelta};
+use ruffle_core::PlayerEvent;
+use ruffle_web_common::JsResult;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::{Clamped, JsCast, JsValue};
+use web_sys::{
+    CanvasRenderingContext2d, Document, Element, FocusEvent, HtmlCanvasElement, HtmlElement,
+    ImageData, MouseEvent, PointerEvent, WheelEvent,
+};
+
+struct WebNativeWindow {
+    frame: HtmlElement,
+    title: HtmlElement,
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    pointer_callbacks: Vec<JsCallback<PointerEvent>>,
+    wheel_callback: Option<JsCallback<WheelEvent>>,
+    focus_callbacks: Vec<JsCallback<FocusEvent>>,
+    close_callback: Option<JsCallback<MouseEvent>>,
+}
+
+impl WebNativeWindow {
+    fn new(
+        document: &Document,
+        ruffle: Option<RuffleHandle>,
+        id: NativeWindowId,
+        focusing: Rc<Cell<bool>>,
+    ) -> Result<Self, JsValue> {
+        let frame: HtmlElement = document.create_element("div")?.unchecked_into();
+        frame.set_class_name("ruffle-native-window");
+        let style = frame.style();
+        style.set_property("position", "absolute")?;
+        style.set_property("display", "none")?;
+        style.set_property("flex-direction", "column")?;
+
+        let title_bar: HtmlElement = document.create_element("div")?.unchecked_into();
+        title_bar.set_class_name("ruffle-native-window-title-bar");
+        title_bar.style().set_property("display", "flex")?;
+
+        let title: HtmlElement = document.create_element("span")?.unchecked_into();
+        title.set_class_name("ruffle-native-window-title");
+        title.style().set_property("flex", "1")?;
+
+        let close: HtmlElement = document.create_element("button")?.unchecked_into();
+        close.set_class_name("ruffle-native-window-close");
+        close.set_text_content(Some("\u{00D7}"));
+        close.set_attribute("aria-label", "Close")?;
+
+        let canvas: HtmlCanvasElement = document.create_element("canvas")?.unchecked_into();
+        canvas.set_tab_index(-1);
+        canvas.style().set_property("touch-action", "none")?;
+        let context: CanvasRenderingContext2d = canvas
+            .get_context("2d")?
+            .ok_or("Unable to create a 2D canvas context")?
+            .unchecked_into();
+
+        title_bar.append_child(&title)?;
+        title_bar.append_child(&close)?;
+        frame.append_child(&title_bar)?;
+        frame.append_child(&canvas)?;
+
+        let mut window = Self {
+            frame,
+            title,
+            canvas,
+            context,
+            pointer_callbacks: Vec::new(),
+            wheel_callback: None,
+            focus_callbacks: Vec::new(),
+            close_callback: None,
+        };
+        if let Some(ruffle) = ruffle {
+            window.register_callbacks(ruffle, id, &close, focusing);
+        }
+        Ok(window)
+    }
+
+    fn register_callbacks(
+        &mut self,
+        ruffle: RuffleHandle,
+        id: NativeWindowId,
+        close: &Element,
+        focusing: Rc<Cell<bool>>,
+    ) {
+        let canvas = &self.canvas;
+        let pointer_event = move |name: &'static str| {
+            JsCallback::register(canvas, name, false, move |js_event: PointerEvent| {
+                let x = f64::from(js_event.offset_x());
+                let y = f64::from(js_event.offset_y());
+                let button = match js_event.button() {
+                    0 => MouseButton::Left,
+                    1 => MouseButton::Middle,
+                    2 => MouseButton::Right,
+                    _ => MouseButton::Unknown,
+                };
+                let event = match name {
+                    "pointermove" => PlayerEvent::MouseMove { x, y },
+                    "pointerdown" => {
+                        if let Some(target) = js_event.current_target() {
+                            let _ = target
+                                .unchecked_ref::<Element>()
+                                .set_pointer_capture(js_event.pointer_id());
+                        }
+                        PlayerEvent::MouseDown {
+                            x,
+                            y,
+                            button,
+                            index: None,
+                        }
+                    }
+                    "pointerup" => {
+                        if let Some(target) = js_event.current_target() {
+                            let _ = target
+                                .unchecked_ref::<Element>()
+                                .release_pointer_capture(js_event.pointer_id());
+                        }
+                        PlayerEvent::MouseUp { x, y, button }
+                    }
+                    _ => PlayerEvent::MouseLeave,
+                };
+                let _ = ruffle.with_core_mut(|core| {
+                    match event {
+                        PlayerEvent::MouseMove { .. } => core.set_mouse_in_stage(true),
+                        PlayerEvent::MouseLeave => core.set_mouse_in_stage(false),
+                        _ => {}
+                    }
+                    core.handle_native_window_event(id, event);
+                });
+                js_event.prevent_default();
+            })
+        };
+        self.pointer_callbacks = vec![
+            pointer_event("pointermove"),
+            pointer_event("pointerdown"),
+            pointer_event("pointerup"),
+            pointer_event("pointerleave"),
+        ];
+
+        self.wheel_callback = Some(JsCallback::register(
+            &self.canvas,
+            "wheel",
+            false,
+            move |js_event: WheelEvent| {
+                let delta = match js_event.delta_mode() {
+                    WheelEvent::DOM_DELTA_LINE => MouseWheelDelta::Lines(-js_event.delta_y()),
+                    WheelEvent::DOM_DELTA_PIXEL => MouseWheelDelta::Pixels(-js_event.delta_y()),
+                    _ => return,
+                };
+                let _ = ruffle.with_core_mut(|core| {
+                    core.handle_native_window_event(id, PlayerEvent::MouseWheel { delta });
+                    if core.should_prevent_scrolling() {
+                        js_event.prevent_default();
+                    }
+                });
+            },
+        ));
+
+        // Focus changes made on behalf of the movie are already known to the player,
+        // which is busy running the movie at that point.
+        let frame = &self.frame;
+        let focus_event = move |name: &'static str, active: bool| {
+            let focusing = focusing.clone();
+            JsCallback::register(frame, name, false, move |_: FocusEvent| {
+                if !focusing.get() {
+                    let _ = ruffle.with_core_mut(|core| core.set_native_window_active(id, active));
+                }
+            })
+        };
+        self.focus_callbacks = vec![focus_event("focusin", true), focus_event("focusout", false)];
+
+        self.close_callback = Some(JsCallback::register(
+            close,
+            "click",
+            false,
+            move |_: MouseEvent| {
+                let _ = ruffle.with_core_mut(|core| core.request_native_window_close(id));
+            },
+        ));
+    }
+
+    fn update(&self, descriptor: &NativeWindowDescriptor) -> Result<(), JsValue> {
+        let style = self.frame.style();
+        style.set_property("left", &format!("{}px", descriptor.x))?;
+        style.set_property("top", &format!("{}px", descriptor.y))?;
+        style.set_property("display", if descriptor.visible { "flex" } else { "none" })?;
+        self.frame.set_attribute("aria-label", &descriptor.title)?;
+        self.title.set_text_content(Some(&descriptor.title));
+
+        self.canvas.set_width(descriptor.width);
+        self.canvas.set_height(descriptor.height);
+        let canvas_style = self.canvas.style();
+        canvas_style.set_property("width", &format!("{}px", descriptor.width))?;
+        canvas_style.set_property("height", &format!("{}px", descriptor.height))?;
+        Ok(())
+    }
+
+    fn present(
+        &self,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        row_stride: u32,
+    ) -> Result<(), JsValue> {
+        // `ImageData` expects tightly packed, non-premultiplied pixels.
+        let row_len = width as usize * 4;
+        let mut pixels = Vec::with_capacity(row_len * height as usize);
+        for row in rgba.chunks(row_stride as usize).take(height as usize) {
+            for pixel in row[..row_len].chunks_exact(4) {
+                let a = pixel[3];
+                if a == 0 || a == 255 {
+                    pixels.extend_from_slice(pixel);
+                } else {
+                    let unmultiply = |c: u8| (u16::from(c) * 255 / u16::from(a)).min(255) as u8;
+                    pixels.extend_from_slice(&[
+                        unmultiply(pixel[0]),
+                        unmultiply(pixel[1]),
+                        unmultiply(pixel[2]),
+                        a,
+                    ]);
+                }
+            }
+        }
+
+        let image =
+            ImageData::new_with_u8_clamped_array_and_sh(Clamped(pixels.as_slice()), width, height)?;
+        self.context.put_image_data(&image, 0.0, 0.0)
+    }
+}
+
+/// The DOM side of every additional window opened by the movie.
+#[derive(Default)]
+pub struct NativeWindowHost {
+    /// The page-provided element that window frames are placed in.
+    /// Windows opened before a container is provided are attached once it is.
+    container: Option<HtmlElement>,
+
+    /// The instance that input on window canvases is delivered to.
+    ruffle: Option<RuffleHandle>,
+
+    windows: HashMap<NativeWindowId, WebNativeWindow>,
+
+    /// Windows that were closed, but whose event listeners may still be running
+    /// (such as the close button that triggered it). These are dropped later.
+    closed: Vec<WebNativeWindow>,
+
+    /// Set while focus is being moved to a window on behalf of the movie.
+    focusing: Rc<Cell<bool>>,
+}
+
+impl NativeWindowHost {
+    pub fn set_ruffle_handle(&mut self, ruffle: RuffleHandle) {
+        self.ruffle = Some(ruffle);
+    }
+
+    pub fn set_container(&mut self, container: Option<HtmlElement>) {
+        for window in self.windows.values() {
+            match &container {
+                Some(container) => container.append_child(&window.frame).warn_on_error(),
+                None => window.frame.remove(),
+            }
+        }
+        self.container = container;
+    }
+
+    pub fn open(&mut self, id: NativeWindowId, descriptor: &NativeWindowDescriptor) -> bool {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return false;
+        };
+
+        self.closed.clear();
+        let window = match WebNativeWindow::new(&document, self.ruffle, id, self.focusing.clone()) {
+            Ok(window) => window,
+            Err(e) => {
+                tracing::error!("Unable to create NativeWindow canvas: {:?}", e);
+                return false;
+            }
+        };
+        window.update(descriptor).warn_on_error();
+        if let Some(container) = &self.container {
+            container.append_child(&window.frame).warn_on_error();
+        }
+        self.windows.insert(id, window);
+        true
+    }
+
+    pub fn update(&mut self, id: NativeWindowId, descriptor: &NativeWindowDescriptor) {
+        if let Some(window) = self.windows.get(&id) {
+            window.update(descriptor).warn_on_error();
+        }
+    }
+
+    pub fn focus(&mut self, id: NativeWindowId) {
+        if let Some(window) = self.windows.get(&id) {
+            self.focusing.set(true);
+            window.canvas.focus().warn_on_error();
+            self.focusing.set(false);
+        }
+    }
+
+    pub fn present(
+        &mut self,
+        id: NativeWindowId,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        row_stride: u32,
+    ) {
+        self.closed.clear();
+        if let Some(window) = self.windows.get(&id) {
+            window
+                .present(width, height, rgba, row_stride)
+                .warn_on_error();
+        }
+    }
+
+    pub fn close(&mut self, id: NativeWindowId) {
+        if let Some(window) = self.windows.remove(&id) {
+            window.frame.remove();
+            self.closed.push(window);
+        }
+    }
+}
+
+impl Drop for NativeWindowHost {
+    fn drop(&mut self) {
+        for window in self.windows.values() {
+            window.frame.remove();
+        }
+    }
+}
@@ -1,10 +1,12 @@
 //! Navigator backend for web
-use crate::SocketProxy;
+use crate::log_adapter::LogSubscriber;
+use crate::zip::ZipBundle;
+use crate::{SocketProxy, SocketProxyProtocol};
 use async_channel::{Receiver, Sender};
 use futures_util::future::Either;
 use futures_util::{future, SinkExt, StreamExt};
 use gloo_net::websocket::{futures::WebSocket, Message};
-use js_sys::{Array, Promise, Uint8Array};
+use js_sys::{Array, Function, Promise, Uint8Array};
 use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, create_specific_fetch_error, get_encoding, ErrorResponse,
     NavigationMethod, NavigatorBackend, OwnedFuture, Request, SuccessResponse,
@@ -20,16 +22,15 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
-use tracing_subscriber::layer::Layered;
-use tracing_subscriber::Registry;
-use tracing_wasm::WASMLayer;
 use url::{ParseError, Url};
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use wasm_streams::readable::ReadableStream;
+use wasm_streams::writable::WritableStream;
 use web_sys::{
     window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, Request as WebRequest,
-    RequestCredentials, RequestInit, Response as WebResponse,
+    RequestCredentials, RequestInit, Response as WebResponse, WebTransport,
+    WebTransportBidirectionalStream,
 };
 
 /// The handling mode of links opening a new website.
@@ -49,7 +50,7 @@ pub enum OpenUrlMode {
 }
 
 pub struct WebNavigatorBackend {
-    log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+    log_subscriber: Arc<LogSubscriber>,
     allow_script_access: bool,
     allow_networking: NetworkingAccessMode,
     upgrade_to_https: bool,
@@ -57,6 +58,12 @@ pub struct WebNavigatorBackend {
     open_url_mode: OpenUrlMode,
     socket_proxies: Vec<SocketProxy>,
     credential_allow_list: Vec<String>,
+
+    /// A function of the page that may answer requests instead of the network.
+    fetch_handler: Option<Function>,
+
+    /// The zipped game that `file:///` URLs are read from, if one was loaded.
+    bundle: Option<ZipBundle>,
     player: Weak<Mutex<Player>>,
 }
 
@@ -67,10 +74,11 @@ impl WebNavigatorBackend {
         allow_networking: NetworkingAccessMode,
         upgrade_to_https: bool,
         base_url: Option<String>,
-        log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+        log_subscriber: Arc<LogSubscriber>,
         open_url_mode: OpenUrlMode,
         socket_proxies: Vec<SocketProxy>,
         credential_allow_list: Vec<String>,
+        fetch_handler: Option<Function>,
     ) -> Self {
         let window = web_sys::window().expect("window()");
 
@@ -114,14 +122,97 @@ impl WebNavigatorBackend {
             open_url_mode,
             socket_proxies,
             credential_allow_list,
+            fetch_handler,
+            bundle: None,
             player: Weak::new(),
         }
     }
 
+    /// Serves `file:///` URLs from `bundle`, and resolves relative URLs against its movie.
+    pub fn set_bundle(&mut self, bundle: ZipBundle) {
+        self.base_url = Some(bundle.movie_url().clone());
+        self.bundle = Some(bundle);
+    }
+
+    fn fetch_from_bundle(&self, url: Url) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        let Some(bundle) = &self.bundle else {
+            return async_return(create_specific_fetch_error(
+                "WASM target can't fetch local URL",
+                url.as_str(),
+                "",
+            ));
+        };
+        let Some(body) = bundle.read(&url) else {
+            return async_return(create_specific_fetch_error(
+                "Bundle doesn't contain",
+                url.as_str(),
+                "",
+            ));
+        };
+
+        let response: Box<dyn SuccessResponse> = Box::new(BundleResponse {
+            url: url.to_string(),
+            body: body.to_vec(),
+        });
+        async_return(Ok(response))
+    }
+
     /// We need to set the player after construction because the player is created after the navigator.
     pub fn set_player(&mut self, player: Weak<Mutex<Player>>) {
         self.player = player;
     }
+
+    /// Opens a socket for the movie through its proxy, which has to use TLS if `secure` is set.
+    fn open_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        secure: bool,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let Some(proxy) = self
+            .socket_proxies
+            .iter()
+            .find(|x| x.host == host && x.port == port && x.tls == secure)
+        else {
+            if secure {
+                tracing::warn!("Missing TLS socket proxy for host {}, port {}", host, port);
+            } else {
+                tracing::warn!("Missing WebSocket proxy for host {}, port {}", host, port);
+            }
+            sender
+                .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
+                .expect("working channel send");
+            return;
+        };
+
+        tracing::info!("Connecting to {}", proxy.proxy_url);
+
+        let proxy = proxy.clone();
+        self.spawn_future(Box::pin(async move {
+            let mut websocket_url = Cow::Borrowed(proxy.proxy_url.as_str());
+            if proxy.proxy_protocol == SocketProxyProtocol::WebTransport {
+                match open_web_transport(&proxy.proxy_url).await {
+                    Ok((transport, stream)) => {
+                        relay_web_transport(transport, stream, handle, receiver, sender).await;
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to open WebTransport stream, falling back to WebSocket, reason {:?}",
+                            e
+                        );
+                        websocket_url = Cow::Owned(web_socket_url(&proxy.proxy_url));
+                    }
+                }
+            }
+
+            relay_web_socket(&websocket_url, handle, receiver, sender).await;
+            Ok(())
+        }));
+    }
 }
 
 impl NavigatorBackend for WebNavigatorBackend {
@@ -256,11 +347,7 @@ impl NavigatorBackend for WebNavigatorBackend {
         let url = match self.resolve_url(request.url()) {
             Ok(url) => {
                 if url.scheme() == "file" {
-                    return async_return(create_specific_fetch_error(
-                        "WASM target can't fetch local URL",
-                        url.as_str(),
-                        "",
-                    ));
+                    return self.fetch_from_bundle(url);
                 } else {
                     url
                 }
@@ -284,6 +371,8 @@ impl NavigatorBackend for WebNavigatorBackend {
             RequestCredentials::SameOrigin
         };
 
+        let fetch_handler = self.fetch_handler.clone();
+
         Box::pin(async move {
             let init = RequestInit::new();
 
@@ -332,19 +421,43 @@ impl NavigatorBackend for WebNavigatorBackend {
                     })?;
             }
 
-            let window = web_sys::window().expect("window()");
-            let fetchval = JsFuture::from(window.fetch_with_request(&web_request))
-                .await
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?;
+            // The page may answer the request itself, or leave it to the network
+            // by returning nothing.
+            let mut fetchval = JsValue::UNDEFINED;
+            if let Some(fetch_handler) = fetch_handler {
+                let result = fetch_handler
+                    .call1(&JsValue::NULL, &web_request)
+                    .map_err(|e| ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError(format!("Fetch handler failed: {e:?}")),
+                    })?;
+                fetchval = JsFuture::from(Promise::resolve(&result))
+                    .await
+                    .map_err(|e| ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError(format!("Fetch handler failed: {e:?}")),
+                    })?;
+            }
+
+            if fetchval.is_null() || fetchval.is_undefined() {
+                let window = web_sys::window().expect("window()");
+                fetchval = JsFuture::from(window.fetch_with_request(&web_request))
+                    .await
+                    .map_err(|_| ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError("Got JS error".to_string()),
+                    })?;
+            }
 
             let response: WebResponse = fetchval.dyn_into().map_err(|_| ErrorResponse {
                 url: url.to_string(),
                 error: Error::FetchError("Fetch result wasn't a WebResponse".to_string()),
             })?;
-            let url = response.url();
+            // Responses made by the page with `new Response()` have no URL.
+            let url = match response.url() {
+                response_url if response_url.is_empty() => url.to_string(),
+                response_url => response_url,
+            };
             let status = response.status();
             let redirected = response.redirected();
             if !response.ok() {
@@ -358,6 +471,7 @@ impl NavigatorBackend for WebNavigatorBackend {
             }
 
             let wrapper: Box<dyn SuccessResponse> = Box::new(WebResponseWrapper {
+                url,
                 response,
                 body_stream: None,
             });
@@ -433,84 +547,205 @@ impl NavigatorBackend for WebNavigatorBackend {
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     ) {
-        let Some(proxy) = self
-            .socket_proxies
-            .iter()
-            .find(|x| x.host == host && x.port == port)
-        else {
-            tracing::warn!("Missing WebSocket proxy for host {}, port {}", host, port);
+        self.open_socket(host, port, false, handle, receiver, sender);
+    }
+
+    fn supports_secure_sockets(&self) -> bool {
+        self.socket_proxies.iter().any(|proxy| proxy.tls)
+    }
+
+    fn connect_secure_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        _timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.open_socket(host, port, true, handle, receiver, sender);
+    }
+}
+
+/// Opens a WebTransport session to `url`, and a bidirectional stream within it.
+async fn open_web_transport(
+    url: &str,
+) -> Result<(WebTransport, WebTransportBidirectionalStream), JsValue> {
+    // This fails if the browser doesn't support WebTransport.
+    let transport = WebTransport::new(url)?;
+    JsFuture::from(transport.ready()).await?;
+    let stream = JsFuture::from(transport.create_bidirectional_stream())
+        .await?
+        .unchecked_into();
+    Ok((transport, stream))
+}
+
+/// The URL to use for a WebSocket connection to a proxy that was meant to be reached with WebTransport.
+fn web_socket_url(url: &str) -> String {
+    let Ok(mut url) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let scheme = match url.scheme() {
+        "https" => "wss",
+        "http" => "ws",
+        _ => return url.into(),
+    };
+    if url.set_scheme(scheme).is_err() {
+        tracing::error!("Url::set_scheme failed on: {}", url);
+    }
+    url.into()
+}
+
+async fn relay_web_socket(
+    url: &str,
+    handle: SocketHandle,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+) {
+    let ws = match WebSocket::open(url) {
+        Ok(x) => x,
+        Err(e) => {
+            tracing::error!("Failed to create WebSocket, reason {:?}", e);
             sender
                 .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
                 .expect("working channel send");
             return;
+        }
+    };
+
+    let (mut ws_write, mut ws_read) = ws.split();
+    sender
+        .try_send(SocketAction::Connect(handle, ConnectionState::Connected))
+        .expect("working channel send");
+
+    loop {
+        match future::select(ws_read.next(), std::pin::pin!(receiver.recv())).await {
+            // Handle incoming messages.
+            Either::Left((Some(msg), _)) => match msg {
+                Ok(Message::Bytes(buf)) => sender
+                    .try_send(SocketAction::Data(handle, buf))
+                    .expect("working channel send"),
+                Ok(_) => tracing::warn!("Server sent an unexpected text message"),
+                Err(_) => {
+                    sender
+                        .try_send(SocketAction::Close(handle))
+                        .expect("working channel send");
+                    break;
+                }
+            },
+            // Handle outgoing messages.
+            Either::Right((Ok(msg), _)) => {
+                if let Err(e) = ws_write.send(Message::Bytes(msg)).await {
+                    tracing::warn!("Failed to send message to WebSocket {}", e);
+                    sender
+                        .try_send(SocketAction::Close(handle))
+                        .expect("working channel send");
+                }
+            }
+            // The connection was closed.
+            _ => break,
         };
+    }
 
-        tracing::info!("Connecting to {}", proxy.proxy_url);
+    let ws = ws_write
+        .reunite(ws_read)
+        .expect("both originate from the same websocket");
+    let _ = ws.close(None, None);
+}
 
-        let ws = match WebSocket::open(&proxy.proxy_url) {
-            Ok(x) => x,
-            Err(e) => {
-                tracing::error!("Failed to create WebSocket, reason {:?}", e);
+async fn relay_web_transport(
+    transport: WebTransport,
+    stream: WebTransportBidirectionalStream,
+    handle: SocketHandle,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+) {
+    let mut incoming = ReadableStream::from_raw(stream.readable().unchecked_into()).into_stream();
+    let mut writable = WritableStream::from_raw(stream.writable().unchecked_into());
+    let mut writer = writable.get_writer();
+    sender
+        .try_send(SocketAction::Connect(handle, ConnectionState::Connected))
+        .expect("working channel send");
+
+    loop {
+        match future::select(incoming.next(), std::pin::pin!(receiver.recv())).await {
+            // Handle incoming data.
+            Either::Left((Some(Ok(chunk)), _)) => sender
+                .try_send(SocketAction::Data(handle, Uint8Array::new(&chunk).to_vec()))
+                .expect("working channel send"),
+            Either::Left((Some(Err(_)), _)) => {
                 sender
-                    .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
+                    .try_send(SocketAction::Close(handle))
                     .expect("working channel send");
-                return;
+                break;
+            }
+            // Handle outgoing data.
+            Either::Right((Ok(msg), _)) => {
+                let chunk = Uint8Array::from(msg.as_slice());
+                if let Err(e) = writer.write(chunk.into()).await {
+                    tracing::warn!("Failed to send data to WebTransport stream {:?}", e);
+                    sender
+                        .try_send(SocketAction::Close(handle))
+                        .expect("working channel send");
+                }
             }
+            // The connection was closed.
+            _ => break,
         };
+    }
 
-        let (mut ws_write, mut ws_read) = ws.split();
-        sender
-            .try_send(SocketAction::Connect(handle, ConnectionState::Connected))
-            .expect("working channel send");
+    drop(incoming);
+    drop(writer);
+    transport.close();
+}
 
-        self.spawn_future(Box::pin(async move {
-            loop {
-                match future::select(ws_read.next(), std::pin::pin!(receiver.recv())).await {
-                    // Handle incoming messages.
-                    Either::Left((Some(msg), _)) => match msg {
-                        Ok(Message::Bytes(buf)) => sender
-                            .try_send(SocketAction::Data(handle, buf))
-                            .expect("working channel send"),
-                        Ok(_) => tracing::warn!("Server sent an unexpected text message"),
-                        Err(_) => {
-                            sender
-                                .try_send(SocketAction::Close(handle))
-                                .expect("working channel send");
-                            break;
-                        }
-                    },
-                    // Handle outgoing messages.
-                    Either::Right((Ok(msg), _)) => {
-                        if let Err(e) = ws_write.send(Message::Bytes(msg)).await {
-                            tracing::warn!("Failed to send message to WebSocket {}", e);
-                            sender
-                                .try_send(SocketAction::Close(handle))
-                                .expect("working channel send");
-                        }
-                    }
-                    // The connection was closed.
-                    _ => break,
-                };
-            }
+/// A response read from the files of a [`ZipBundle`].
+struct BundleResponse {
+    url: String,
+    body: Vec<u8>,
+}
 
-            let ws = ws_write
-                .reunite(ws_read)
-                .expect("both originate from the same websocket");
-            let _ = ws.close(None, None);
+impl SuccessResponse for BundleResponse {
+    fn url(&self) -> Cow<str> {
+        Cow::Borrowed(&self.url)
+    }
 
-            Ok(())
-        }));
+    fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+        async_return(Ok(self.body))
+    }
+
+    fn text_encoding(&self) -> Option<&'static Encoding> {
+        None
+    }
+
+    fn status(&self) -> u16 {
+        0
+    }
+
+    fn redirected(&self) -> bool {
+        false
+    }
+
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let body = std::mem::take(&mut self.body);
+        async_return(Ok((!body.is_empty()).then_some(body)))
+    }
+
+    fn expected_length(&self) -> Result<Option<u64>, Error> {
+        Ok(Some(self.body.len() as u64))
     }
 }
 
 struct WebResponseWrapper {
+    /// The final URL of the response.
+    url: String,
     response: WebResponse,
     body_stream: Option<Rc<RefCell<ReadableStream>>>,
 }
 
 impl SuccessResponse for WebResponseWrapper {
     fn url(&self) -> Cow<str> {
-        Cow::Owned(self.response.url())
+        Cow::Borrowed(&self.url)
     }
 
     fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
@@ -0,0 +1,190 @@
+//! Sharing the page between many players, such as the ones in forum signatures or galleries.
+//!
+//! When a limit is set, only that many players tick at once. Players that are on screen
+//! come first, then the ones that were interacted with most recently. The others are
+//! suspended until a slot frees up.
+
+use crate::RuffleHandle;
+use std::cell::RefCell;
+
+thread_local! {
+    static SCHEDULER: RefCell<Scheduler<RuffleHandle>> = RefCell::new(Scheduler::default());
+}
+
+/// Adds a player, limiting the page to `max_active` actively ticking players.
+///
+/// The limit is page-wide, so the last player to set one decides it.
+/// Returns the players whose state changed, and whether they are now active.
+pub fn register(handle: RuffleHandle, max_active: Option<usize>) -> Vec<(RuffleHandle, bool)> {
+    SCHEDULER.with_borrow_mut(|scheduler| {
+        if max_active.is_some() {
+            scheduler.max_active = max_active;
+        }
+        scheduler.register(handle)
+    })
+}
+
+/// Removes a player, letting a suspended one take its place.
+pub fn unregister(handle: RuffleHandle) -> Vec<(RuffleHandle, bool)> {
+    SCHEDULER.with_borrow_mut(|scheduler| scheduler.unregister(handle))
+}
+
+/// Records whether a player is on screen, as observed by the page.
+pub fn set_on_screen(handle: RuffleHandle, on_screen: bool) -> Vec<(RuffleHandle, bool)> {
+    SCHEDULER.with_borrow_mut(|scheduler| scheduler.set_on_screen(handle, on_screen))
+}
+
+/// Records that the user interacted with a player.
+pub fn touch(handle: RuffleHandle) -> Vec<(RuffleHandle, bool)> {
+    SCHEDULER.with_borrow_mut(|scheduler| scheduler.touch(handle))
+}
+
+/// Returns whether a player may tick.
+pub fn is_active(handle: RuffleHandle) -> bool {
+    SCHEDULER.with_borrow(|scheduler| scheduler.is_active(handle))
+}
+
+struct Entry<H> {
+    handle: H,
+    on_screen: bool,
+    /// When the player was last interacted with, as a counter. Higher is more recent.
+    last_activity: u64,
+    active: bool,
+}
+
+struct Scheduler<H> {
+    max_active: Option<usize>,
+    entries: Vec<Entry<H>>,
+    activity_counter: u64,
+}
+
+impl<H> Default for Scheduler<H> {
+    fn default() -> Self {
+        Self {
+            max_active: None,
+            entries: Vec::new(),
+            activity_counter: 0,
+        }
+    }
+}
+
+impl<H: Copy + PartialEq> Scheduler<H> {
+    fn register(&mut self, handle: H) -> Vec<(H, bool)> {
+        self.activity_counter += 1;
+        self.entries.push(Entry {
+            handle,
+            // Until the page tells otherwise, players are assumed to be visible.
+            on_screen: true,
+            last_activity: self.activity_counter,
+            active: false,
+        });
+        self.reschedule()
+    }
+
+    fn unregister(&mut self, handle: H) -> Vec<(H, bool)> {
+        self.entries.retain(|entry| entry.handle != handle);
+        self.reschedule()
+    }
+
+    fn set_on_screen(&mut self, handle: H, on_screen: bool) -> Vec<(H, bool)> {
+        if let Some(entry) = self.entry_mut(handle) {
+            entry.on_screen = on_screen;
+        }
+        self.reschedule()
+    }
+
+    fn touch(&mut self, handle: H) -> Vec<(H, bool)> {
+        self.activity_counter += 1;
+        let counter = self.activity_counter;
+        if let Some(entry) = self.entry_mut(handle) {
+            entry.last_activity = counter;
+        }
+        self.reschedule()
+    }
+
+    fn is_active(&self, handle: H) -> bool {
+        self.entries
+            .iter()
+            .find(|entry| entry.handle == handle)
+            .map_or(true, |entry| entry.active)
+    }
+
+    fn entry_mut(&mut self, handle: H) -> Option<&mut Entry<H>> {
+        self.entries.iter_mut().find(|entry| entry.handle == handle)
+    }
+
+    /// Picks the active players, returning the ones that changed.
+    fn reschedule(&mut self) -> Vec<(H, bool)> {
+        let mut order: Vec<usize> = (0..self.entries.len()).collect();
+        order.sort_by_key(|&i| {
+            let entry = &self.entries[i];
+            (!entry.on_screen, std::cmp::Reverse(entry.last_activity))
+        });
+        let max_active = self.max_active.unwrap_or(usize::MAX);
+
+        let mut changes = Vec::new();
+        for (rank, i) in order.into_iter().enumerate() {
+            let entry = &mut self.entries[i];
+            let active = rank < max_active;
+            if entry.active != active {
+                entry.active = active;
+                changes.push((entry.handle, active));
+            }
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_active_players() {
+        let mut scheduler = Scheduler {
+            max_active: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(scheduler.register(1), vec![(1, true)]);
+        assert_eq!(scheduler.register(2), vec![(2, true)]);
+        // The newest player takes the place of the one that was least recently active.
+        assert_eq!(scheduler.register(3), vec![(3, true), (1, false)]);
+        assert!(!scheduler.is_active(1));
+
+        assert_eq!(scheduler.touch(1), vec![(1, true), (2, false)]);
+        assert_eq!(scheduler.unregister(3), vec![(2, true)]);
+        assert!(scheduler.is_active(1) && scheduler.is_active(2));
+    }
+
+    #[test]
+    fn prefers_players_on_screen() {
+        let mut scheduler = Scheduler {
+            max_active: Some(1),
+            ..Default::default()
+        };
+        scheduler.register(1);
+        scheduler.register(2);
+        assert!(scheduler.is_active(2));
+
+        assert_eq!(
+            scheduler.set_on_screen(2, false),
+            vec![(1, true), (2, false)]
+        );
+        // Interacting doesn't beat being on screen.
+        assert_eq!(scheduler.touch(2), vec![]);
+        assert_eq!(
+            scheduler.set_on_screen(1, false),
+            vec![(2, true), (1, false)]
+        );
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let mut scheduler = Scheduler::default();
+        for handle in 0..10 {
+            scheduler.register(handle);
+            scheduler.set_on_screen(handle, false);
+        }
+        assert!((0..10).all(|handle| scheduler.is_active(handle)));
+    }
+}
@@ -1,7 +1,45 @@
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use futures::future::{LocalBoxFuture, Shared};
+use futures::FutureExt;
+use js_sys::Uint8Array;
 use ruffle_core::backend::storage::StorageBackend;
-use web_sys::Storage;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbFactory, IdbObjectStore, IdbRequest, IdbTransactionMode, Storage};
+
+/// The IndexedDB database that SharedObjects are stored in.
+const DATABASE_NAME: &str = "ruffle";
+const DATABASE_VERSION: u32 = 1;
+const STORE_NAME: &str = "sharedObjects";
+
+/// Where a player stores its SharedObjects.
+#[derive(Clone, Debug, Default)]
+pub enum StorageKind {
+    /// `localStorage`, which is limited to a few megabytes per origin.
+    #[default]
+    Local,
+
+    /// IndexedDB, which may store much more.
+    IndexedDb,
+
+    /// An object provided by the page, with `get`, `put` and `remove` methods.
+    Custom(JsStorage),
+}
+
+impl StorageKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StorageKind::Local => "local",
+            StorageKind::IndexedDb => "indexeddb",
+            StorageKind::Custom(_) => "custom",
+        }
+    }
+}
 
 pub struct LocalStorageBackend {
     storage: Storage,
@@ -34,3 +72,259 @@ impl StorageBackend for LocalStorageBackend {
         let _ = self.storage.delete(name);
     }
 }
+
+thread_local! {
+    /// The database shared by every player on the page, which is only loaded once.
+    static DATABASE: RefCell<Option<OpenDatabase>> = const { RefCell::new(None) };
+}
+
+type OpenDatabase = Shared<LocalBoxFuture<'static, Result<Rc<Database>, JsValue>>>;
+
+/// The IndexedDB database, along with a copy of every entry in it.
+///
+/// IndexedDB is asynchronous, but SharedObjects are read synchronously, so every entry is
+/// loaded up front and kept in memory. Writes update the copy in memory immediately,
+/// and are saved to their key in the background.
+struct Database {
+    database: IdbDatabase,
+    entries: RefCell<HashMap<String, Vec<u8>>>,
+}
+
+impl Database {
+    async fn open(factory: IdbFactory) -> Result<Rc<Self>, JsValue> {
+        let request = factory.open_with_u32(DATABASE_NAME, DATABASE_VERSION)?;
+        let upgrade_request = request.clone();
+        let on_upgrade_needed = Closure::<dyn FnMut()>::new(move || {
+            let result = upgrade_request
+                .result()
+                .and_then(|database| database.dyn_into::<IdbDatabase>())
+                .and_then(|database| database.create_object_store(STORE_NAME));
+            if let Err(e) = result {
+                tracing::error!("Unable to create IndexedDB store: {e:?}");
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+        let database = request_result(&request).await;
+        request.set_onupgradeneeded(None);
+        let database: IdbDatabase = database?.dyn_into()?;
+
+        // Both requests must be made before waiting, or the transaction would end in between.
+        let store = database
+            .transaction_with_str(STORE_NAME)?
+            .object_store(STORE_NAME)?;
+        let keys = request_result(&store.get_all_keys()?);
+        let values = request_result(&store.get_all()?);
+        let keys = js_sys::Array::from(&keys.await?);
+        let values = js_sys::Array::from(&values.await?);
+
+        let entries = keys
+            .iter()
+            .zip(values.iter())
+            .filter_map(|(key, value)| {
+                let value = value.dyn_into::<Uint8Array>().ok()?;
+                Some((key.as_string()?, value.to_vec()))
+            })
+            .collect();
+        Ok(Rc::new(Self {
+            database,
+            entries: RefCell::new(entries),
+        }))
+    }
+
+    fn put(&self, name: &str, value: &[u8]) -> bool {
+        let saved = self.write(name, |store| {
+            store.put_with_key(&Uint8Array::from(value), &JsValue::from_str(name))
+        });
+        if saved {
+            self.entries
+                .borrow_mut()
+                .insert(name.to_string(), value.to_vec());
+        }
+        saved
+    }
+
+    fn remove(&self, name: &str) {
+        self.entries.borrow_mut().remove(name);
+        self.write(name, |store| store.delete(&JsValue::from_str(name)));
+    }
+
+    /// Starts a write to the store, and logs it if it fails.
+    fn write(
+        &self,
+        name: &str,
+        write: impl FnOnce(&IdbObjectStore) -> Result<IdbRequest, JsValue>,
+    ) -> bool {
+        let request = self
+            .database
+            .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)
+            .and_then(|transaction| transaction.object_store(STORE_NAME))
+            .and_then(|store| write(&store));
+        match request {
+            Ok(request) => {
+                let name = name.to_string();
+                let result = request_result(&request);
+                wasm_bindgen_futures::spawn_local(async move {
+                    if let Err(e) = result.await {
+                        tracing::error!("Unable to save SharedObject {name} to IndexedDB: {e:?}");
+                    }
+                });
+                true
+            }
+            Err(e) => {
+                tracing::error!("Unable to save SharedObject {name} to IndexedDB: {e:?}");
+                false
+            }
+        }
+    }
+}
+
+/// Stores SharedObjects in IndexedDB.
+///
+/// SharedObjects that aren't in IndexedDB yet are read from `localStorage`, where
+/// players saved them before, and are copied over the first time they're read.
+pub struct IndexedDbStorageBackend {
+    database: Rc<Database>,
+    local_storage: Option<LocalStorageBackend>,
+}
+
+impl IndexedDbStorageBackend {
+    pub(crate) async fn open(
+        factory: IdbFactory,
+        local_storage: Option<Storage>,
+    ) -> Result<Self, JsValue> {
+        let database = DATABASE.with_borrow_mut(|database| {
+            database
+                .get_or_insert_with(|| Database::open(factory).boxed_local().shared())
+                .clone()
+        });
+        let database = database.await.inspect_err(|_| {
+            // Let the next player try again.
+            DATABASE.set(None);
+        })?;
+        Ok(Self {
+            database,
+            local_storage: local_storage.map(LocalStorageBackend::new),
+        })
+    }
+
+    /// Copies a SharedObject saved in `localStorage` into IndexedDB.
+    fn migrate(&self, name: &str) -> Option<Vec<u8>> {
+        let data = self.local_storage.as_ref()?.get(name)?;
+        self.database.put(name, &data);
+        Some(data)
+    }
+}
+
+impl StorageBackend for IndexedDbStorageBackend {
+    fn get(&self, name: &str) -> Option<Vec<u8>> {
+        let data = self.database.entries.borrow().get(name).cloned();
+        data.or_else(|| self.migrate(name))
+    }
+
+    fn put(&mut self, name: &str, value: &[u8]) -> bool {
+        self.database.put(name, value)
+    }
+
+    fn get_size(&self, name: &str) -> Option<usize> {
+        let size = self.database.entries.borrow().get(name).map(Vec::len);
+        size.or_else(|| self.migrate(name).as_deref().map(<[u8]>::len))
+    }
+
+    fn remove_key(&mut self, name: &str) {
+        self.database.remove(name);
+        // Otherwise the old copy would be migrated again.
+        if let Some(local_storage) = &mut self.local_storage {
+            local_storage.remove_key(name);
+        }
+    }
+}
+
+/// Waits for an IndexedDB request to complete, and returns its result.
+///
+/// The callbacks are set right away, so that no event is missed before the future is polled.
+fn request_result(request: &IdbRequest) -> impl Future<Output = Result<JsValue, JsValue>> {
+    let mut callbacks = None;
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let success_request = request.clone();
+        let on_success: Closure<dyn FnMut()> = Closure::once(move || {
+            let result = success_request.result().unwrap_or_default();
+            let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+        });
+        let error_request = request.clone();
+        let on_error: Closure<dyn FnMut()> = Closure::once(move || {
+            let error = error_request
+                .error()
+                .ok()
+                .flatten()
+                .map(JsValue::from)
+                .unwrap_or_default();
+            let _ = reject.call1(&JsValue::UNDEFINED, &error);
+        });
+        request.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+        request.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        callbacks = Some((on_success, on_error));
+    });
+    let request = request.clone();
+    async move {
+        let result = JsFuture::from(promise).await;
+        // Only one of the callbacks was called; both are freed here.
+        request.set_onsuccess(None);
+        request.set_onerror(None);
+        drop(callbacks);
+        result
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// A storage backend implemented by the page.
+    #[derive(Clone, Debug)]
+    pub type JsStorage;
+
+    #[wasm_bindgen(method, catch)]
+    fn get(this: &JsStorage, name: &str) -> Result<Option<Uint8Array>, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn put(this: &JsStorage, name: &str, value: Uint8Array) -> Result<bool, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn remove(this: &JsStorage, name: &str) -> Result<(), JsValue>;
+}
+
+/// Stores SharedObjects with an object provided by the page.
+pub struct CustomStorageBackend {
+    storage: JsStorage,
+}
+
+impl CustomStorageBackend {
+    pub(crate) fn new(storage: JsStorage) -> Self {
+        CustomStorageBackend { storage }
+    }
+}
+
+impl StorageBackend for CustomStorageBackend {
+    fn get(&self, name: &str) -> Option<Vec<u8>> {
+        match self.storage.get(name) {
+            Ok(data) => data.map(|data| data.to_vec()),
+            Err(e) => {
+                tracing::error!("Custom storage failed to get {name}: {e:?}");
+                None
+            }
+        }
+    }
+
+    fn put(&mut self, name: &str, value: &[u8]) -> bool {
+        self.storage
+            .put(name, Uint8Array::from(value))
+            .unwrap_or_else(|e| {
+                tracing::error!("Custom storage failed to put {name}: {e:?}");
+                false
+            })
+    }
+
+    fn remove_key(&mut self, name: &str) {
+        if let Err(e) = self.storage.remove(name) {
+            tracing::error!("Custom storage failed to remove {name}: {e:?}");
+        }
+    }
+}
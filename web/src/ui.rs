@@ -1,21 +1,38 @@
 use super::JavascriptPlayer;
+use crate::local_fonts::LocalFonts;
+use crate::native_windows::NativeWindowHost;
+use crate::web_views::WebViewHost;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use rfd::{AsyncFileDialog, FileHandle};
+use ruffle_core::backend::ui::{AccessibilityNode, AccessibilityRole};
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
+    ClipboardImage, DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
 };
+use ruffle_core::backend::ui::{CursorImage, CustomCursorId};
 use ruffle_core::backend::ui::{
-    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, NativeWindowDescriptor,
+    NativeWindowId, PrintPage, RootMovieLoadPhase, UiBackend, WebViewId, WebViewNavigation,
+    WebViewViewport, US_ENGLISH,
 };
+use ruffle_core::printing;
+use ruffle_core::swf::{Rectangle, Twips};
 use ruffle_web_common::JsResult;
+use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::rc::Rc;
 use url::Url;
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
 use web_sys::{
-    Blob, HtmlCanvasElement, HtmlDocument, HtmlElement, HtmlTextAreaElement, Url as JsUrl,
+    Blob, ClipboardItem, HtmlCanvasElement, HtmlDocument, HtmlElement, HtmlIFrameElement,
+    HtmlTextAreaElement, Navigator, PermissionState, PermissionStatus, Url as JsUrl,
 };
 
 use chrono::{DateTime, Utc};
-use js_sys::{Array, Uint8Array};
+use js_sys::{Array, Object, Reflect, Uint8Array};
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -108,6 +125,70 @@ fn download_as_file(filename: Option<&str>, data: &[u8]) -> Result<(), JsValue>
     Ok(())
 }
 
+/// Prints pages through the print dialog of the browser.
+///
+/// The pages are put in a document of their own, in an invisible frame, so that the rest
+/// of the page isn't printed with them. The frame is removed once it's done printing.
+fn print_pages(pages: &[PrintPage]) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or(JsValue::from("no window"))?;
+    let document = window.document().ok_or(JsValue::from("no document"))?;
+    let body = document.body().ok_or(JsValue::from("no body"))?;
+
+    let mut html = format!(
+        "<!DOCTYPE html><html><head><style>\
+         @page {{ size: letter portrait; margin: {margin}pt; }}\
+         body {{ margin: 0; }}\
+         img {{ display: block; break-after: page; }}\
+         img:last-child {{ break-after: auto; }}\
+         </style></head><body>",
+        margin = (printing::PAPER_WIDTH - printing::PAGE_WIDTH) / 2
+    );
+    let mut urls = Vec::with_capacity(pages.len());
+    for page in pages {
+        let png = encode_png(page.width, page.height, &page.rgba)
+            .map_err(|e| JsValue::from(e.to_string()))?;
+        let blob = Blob::new_with_u8_array_sequence(&Array::of1(&Uint8Array::from(&png[..])))?;
+        let url = JsUrl::create_object_url_with_blob(&blob)?;
+        html.push_str(&format!(
+            "<img src=\"{url}\" style=\"width: {}pt; height: {}pt;\">",
+            page.width / page.scale,
+            page.height / page.scale
+        ));
+        urls.push(url);
+    }
+    html.push_str("</body></html>");
+
+    // Browsers don't print frames that aren't displayed, so this one is just made invisible.
+    let frame: HtmlIFrameElement = document.create_element("iframe")?.dyn_into()?;
+    let style = frame.style();
+    style.set_property("position", "fixed")?;
+    style.set_property("width", "0")?;
+    style.set_property("height", "0")?;
+    style.set_property("border", "0")?;
+    style.set_property("visibility", "hidden")?;
+    frame.set_srcdoc(&html);
+
+    let loaded_frame = frame.clone();
+    let on_load = Closure::once_into_js(move || {
+        let Some(frame_window) = loaded_frame.content_window() else {
+            return;
+        };
+        let cleanup = Closure::once_into_js(move || {
+            loaded_frame.remove();
+            for url in urls {
+                JsUrl::revoke_object_url(&url).warn_on_error();
+            }
+        });
+        frame_window
+            .add_event_listener_with_callback("afterprint", cleanup.unchecked_ref())
+            .warn_on_error();
+        frame_window.print().warn_on_error();
+    });
+    frame.set_onload(Some(on_load.unchecked_ref()));
+    body.append_child(&frame)?;
+    Ok(())
+}
+
 impl FileDialogResult for WebFileDialogResult {
     fn is_cancelled(&self) -> bool {
         self.canceled
@@ -158,15 +239,36 @@ pub struct WebUiBackend {
     canvas: HtmlCanvasElement,
     cursor_visible: bool,
     cursor: MouseCursor,
+
+    /// The CSS `cursor` values of each frame of the cursors registered by the movie.
+    custom_cursors: HashMap<CustomCursorId, Vec<String>>,
     language: LanguageIdentifier,
     clipboard_content: String,
+    clipboard_image: Option<ClipboardImage>,
 
     /// Is a dialog currently open
     dialog_open: bool,
+
+    /// Canvases for additional windows opened by AIR content.
+    native_windows: NativeWindowHost,
+
+    /// Iframes for `StageWebView`s created by AIR content.
+    web_views: WebViewHost,
+
+    /// Whether the stage is mirrored to the page for screen readers.
+    accessibility: bool,
+
+    /// The fonts on the system, if the page may access them.
+    local_fonts: Option<Rc<LocalFonts>>,
 }
 
 impl WebUiBackend {
-    pub fn new(js_player: JavascriptPlayer, canvas: &HtmlCanvasElement) -> Self {
+    pub fn new(
+        js_player: JavascriptPlayer,
+        canvas: &HtmlCanvasElement,
+        accessibility: bool,
+        local_fonts: Option<Rc<LocalFonts>>,
+    ) -> Self {
         let window = web_sys::window().expect("window()");
         let preferred_language = window.navigator().language();
         let language = preferred_language
@@ -177,9 +279,15 @@ impl WebUiBackend {
             canvas: canvas.clone(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
+            custom_cursors: HashMap::new(),
             language,
             clipboard_content: "".into(),
+            clipboard_image: None,
             dialog_open: false,
+            native_windows: NativeWindowHost::default(),
+            web_views: WebViewHost::default(),
+            accessibility,
+            local_fonts,
         }
     }
 
@@ -190,6 +298,12 @@ impl WebUiBackend {
                 MouseCursor::Hand => "pointer",
                 MouseCursor::IBeam => "text",
                 MouseCursor::Grab => "grab",
+                MouseCursor::Custom { id, frame } => self
+                    .custom_cursors
+                    .get(&id)
+                    .and_then(|frames| frames.get(frame as usize))
+                    .map(String::as_str)
+                    .unwrap_or("auto"),
             }
         } else {
             "none"
@@ -202,6 +316,64 @@ impl WebUiBackend {
 
     pub fn set_clipboard_content_buffer(&mut self, content: String) {
         self.clipboard_content = content;
+        self.clipboard_image = None;
+    }
+
+    pub fn set_clipboard_image_buffer(&mut self, image: Option<ClipboardImage>) {
+        self.clipboard_image = image;
+    }
+
+    /// Copies text with `document.execCommand("copy")`, which works on insecure pages too.
+    fn copy_with_exec_command(&self, content: &str) {
+        let Some(element) = self.canvas.parent_element() else {
+            return;
+        };
+        let window = web_sys::window().expect("window()");
+        let document: HtmlDocument = window
+            .document()
+            .expect("document()")
+            .dyn_into()
+            .expect("document() didn't give us a document");
+        let textarea: HtmlTextAreaElement = document
+            .create_element("textarea")
+            .expect("create_element() must succeed")
+            .dyn_into()
+            .expect("create_element(\"textarea\") didn't give us a textarea");
+
+        let editing_text = self.js_player.is_virtual_keyboard_focused();
+        textarea.set_value(content);
+        let _ = element.append_child(&textarea);
+        let _ = textarea.focus();
+        textarea.select();
+
+        match document.exec_command("copy") {
+            Ok(success) => {
+                if !success {
+                    tracing::warn!(
+                        "Couldn't set clipboard contents: The browser rejected the call"
+                    );
+                }
+            }
+            Err(e) => tracing::error!("Couldn't set clipboard contents: {:?}", e),
+        }
+
+        if let Ok(element) = element.clone().dyn_into::<HtmlElement>() {
+            // Ensure we don't lose our focus.
+            let _ = element.focus();
+        }
+        let _ = element.remove_child(&textarea);
+        if editing_text {
+            // Return focus to the text area
+            self.js_player.open_virtual_keyboard();
+        }
+    }
+
+    pub fn native_windows_mut(&mut self) -> &mut NativeWindowHost {
+        &mut self.native_windows
+    }
+
+    pub fn web_views_mut(&mut self) -> &mut WebViewHost {
+        &mut self.web_views
     }
 }
 
@@ -220,6 +392,45 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
+    fn register_custom_cursor(&mut self, id: CustomCursorId, frames: Vec<CursorImage>) {
+        let frames = frames
+            .iter()
+            .map(
+                |image| match encode_png(image.width, image.height, &image.rgba) {
+                    Ok(png) => format!(
+                        "url(data:image/png;base64,{}) {} {}, auto",
+                        BASE64_STANDARD.encode(png),
+                        image.hotspot_x,
+                        image.hotspot_y
+                    ),
+                    Err(e) => {
+                        tracing::error!("Couldn't encode cursor image: {e}");
+                        "auto".to_string()
+                    }
+                },
+            )
+            .collect();
+        self.custom_cursors.insert(id, frames);
+    }
+
+    fn unregister_custom_cursor(&mut self, id: CustomCursorId) {
+        self.custom_cursors.remove(&id);
+    }
+
+    fn set_mouse_lock(&mut self, locked: bool) {
+        if locked {
+            self.canvas.request_pointer_lock();
+        } else if let Some(document) = self.canvas.owner_document() {
+            document.exit_pointer_lock();
+        }
+    }
+
+    fn max_touch_points(&self) -> u32 {
+        web_sys::window().map_or(0, |window| {
+            window.navigator().max_touch_points().max(0) as u32
+        })
+    }
+
     fn clipboard_content(&mut self) -> String {
         // On web, clipboard content is not directly accessible due to security restrictions,
         // but pasting from the clipboard is supported via the JS `paste` event
@@ -235,48 +446,47 @@ impl UiBackend for WebUiBackend {
     fn set_clipboard_content(&mut self, content: String) {
         self.set_clipboard_content_buffer(content.to_owned());
 
-        // We use `document.execCommand("copy")` as `navigator.clipboard.writeText("string")`
-        // is available only in secure contexts (HTTPS).
-        if let Some(element) = self.canvas.parent_element() {
-            let window = web_sys::window().expect("window()");
-            let document: HtmlDocument = window
-                .document()
-                .expect("document()")
-                .dyn_into()
-                .expect("document() didn't give us a document");
-            let textarea: HtmlTextAreaElement = document
-                .create_element("textarea")
-                .expect("create_element() must succeed")
-                .dyn_into()
-                .expect("create_element(\"textarea\") didn't give us a textarea");
-
-            let editing_text = self.js_player.is_virtual_keyboard_focused();
-            textarea.set_value(&content);
-            let _ = element.append_child(&textarea);
-            let _ = textarea.focus();
-            textarea.select();
-
-            match document.exec_command("copy") {
-                Ok(success) => {
-                    if !success {
-                        tracing::warn!(
-                            "Couldn't set clipboard contents: The browser rejected the call"
-                        );
-                    }
-                }
-                Err(e) => tracing::error!("Couldn't set clipboard contents: {:?}", e),
-            }
+        // The Clipboard API is available only in secure contexts (HTTPS),
+        // so fall back to `document.execCommand("copy")` elsewhere.
+        let window = web_sys::window().expect("window()");
+        let navigator = window.navigator();
+        if !window.is_secure_context() || !JsValue::from_str("clipboard").js_in(&navigator) {
+            self.copy_with_exec_command(&content);
+            return;
+        }
 
-            if let Ok(element) = element.clone().dyn_into::<HtmlElement>() {
-                // Ensure we don't lose our focus.
-                let _ = element.focus();
-            }
-            let _ = element.remove_child(&textarea);
-            if editing_text {
-                // Return focus to the text area
-                self.js_player.open_virtual_keyboard();
+        let promise = navigator.clipboard().write_text(&content);
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = JsFuture::from(promise).await {
+                tracing::warn!("Couldn't set clipboard contents: {e:?}");
             }
+        });
+    }
+
+    fn clipboard_image(&mut self) -> Option<ClipboardImage> {
+        self.clipboard_image.clone()
+    }
+
+    fn set_clipboard_image(&mut self, image: ClipboardImage) {
+        // Browsers only accept PNG images on the clipboard.
+        match encode_png(image.width, image.height, &image.rgba) {
+            Ok(png) => self
+                .js_player
+                .write_clipboard_image(Uint8Array::from(&png[..])),
+            Err(e) => tracing::error!("Couldn't encode clipboard image: {e}"),
         }
+        self.clipboard_content.clear();
+        self.clipboard_image = Some(image);
+    }
+
+    fn clear_clipboard_content(&mut self) {
+        // Browsers can only replace everything on the system clipboard,
+        // so this only clears what the movie can see.
+        self.clipboard_content.clear();
+    }
+
+    fn clear_clipboard_image(&mut self) {
+        self.clipboard_image = None;
     }
 
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
@@ -294,6 +504,31 @@ impl UiBackend for WebUiBackend {
             .display_root_movie_download_failed_message(invalid_swf)
     }
 
+    fn root_movie_load_progress(
+        &self,
+        phase: RootMovieLoadPhase,
+        bytes_loaded: u64,
+        bytes_total: Option<u64>,
+    ) {
+        let phase = match phase {
+            RootMovieLoadPhase::Download => "download",
+            RootMovieLoadPhase::Preload => "preload",
+        };
+        self.js_player.on_load_progress(
+            phase,
+            bytes_loaded as f64,
+            bytes_total.map(|total| total as f64),
+        );
+    }
+
+    fn root_movie_loaded(&self) {
+        self.js_player.on_loaded();
+    }
+
+    fn root_movie_load_failed(&self, message: &str) {
+        self.js_player.on_load_error(message);
+    }
+
     fn message(&self, message: &str) {
         self.js_player.display_message(message);
     }
@@ -306,6 +541,17 @@ impl UiBackend for WebUiBackend {
         self.js_player.close_virtual_keyboard()
     }
 
+    fn set_ime_cursor_area(&mut self, area: Rectangle<Twips>) {
+        // The area is in canvas pixels, but the page positions things in CSS pixels.
+        let scale = f64::from(self.canvas.client_width()) / f64::from(self.canvas.width().max(1));
+        self.js_player.set_ime_cursor_area(
+            area.x_min.to_pixels() * scale,
+            area.y_min.to_pixels() * scale,
+            area.width().to_pixels() * scale,
+            area.height().to_pixels() * scale,
+        );
+    }
+
     fn language(&self) -> LanguageIdentifier {
         self.language.clone()
     }
@@ -316,13 +562,17 @@ impl UiBackend for WebUiBackend {
 
     fn load_device_font(
         &self,
-        _name: &str,
-        _is_bold: bool,
-        _is_italic: bool,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
         _register: &mut dyn FnMut(FontDefinition),
     ) {
-        // Because fonts must be loaded instantly (no async),
-        // we actually just provide them all upfront at time of Player creation.
+        // Because fonts must be loaded instantly (no async), custom fonts are
+        // provided upfront at time of Player creation, and local fonts register themselves
+        // once they're read.
+        if let Some(local_fonts) = &self.local_fonts {
+            local_fonts.load(name, is_bold, is_italic);
+        }
     }
 
     fn display_file_open_dialog(&mut self, filters: Vec<FileFilter>) -> Option<DialogResultFuture> {
@@ -383,4 +633,221 @@ impl UiBackend for WebUiBackend {
             result
         }))
     }
+
+    fn open_native_window(
+        &mut self,
+        id: NativeWindowId,
+        descriptor: &NativeWindowDescriptor,
+    ) -> bool {
+        self.native_windows.open(id, descriptor)
+    }
+
+    fn update_native_window(&mut self, id: NativeWindowId, descriptor: &NativeWindowDescriptor) {
+        self.native_windows.update(id, descriptor);
+    }
+
+    fn focus_native_window(&mut self, id: NativeWindowId) {
+        self.native_windows.focus(id);
+    }
+
+    fn present_native_window(
+        &mut self,
+        id: NativeWindowId,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        row_stride: u32,
+    ) {
+        self.native_windows
+            .present(id, width, height, rgba, row_stride);
+    }
+
+    fn close_native_window(&mut self, id: NativeWindowId) {
+        self.native_windows.close(id);
+    }
+
+    fn supports_web_views(&self) -> bool {
+        true
+    }
+
+    fn create_web_view(&mut self, id: WebViewId) {
+        self.web_views.create(&self.canvas, id);
+    }
+
+    fn set_web_view_viewport(&mut self, id: WebViewId, viewport: Option<WebViewViewport>) {
+        self.web_views.set_viewport(&self.canvas, id, viewport);
+    }
+
+    fn load_web_view_url(&mut self, id: WebViewId, url: &str) {
+        self.web_views.load_url(id, url);
+    }
+
+    fn load_web_view_string(&mut self, id: WebViewId, text: &str, mime_type: &str) {
+        self.web_views.load_string(id, text, mime_type);
+    }
+
+    fn navigate_web_view(&mut self, id: WebViewId, navigation: WebViewNavigation) {
+        self.web_views.navigate(id, navigation);
+    }
+
+    fn dispose_web_view(&mut self, id: WebViewId) {
+        self.web_views.dispose(id);
+    }
+
+    fn is_accessibility_active(&self) -> bool {
+        self.accessibility
+    }
+
+    fn can_print(&self) -> bool {
+        true
+    }
+
+    fn print(&mut self, pages: Vec<PrintPage>) {
+        if let Err(e) = print_pages(&pages) {
+            tracing::error!("Couldn't print pages: {e:?}");
+        }
+    }
+
+    fn update_accessibility_tree(&mut self, nodes: &[AccessibilityNode]) {
+        // The bounds are in canvas pixels, but the page positions things in CSS pixels.
+        let scale = f64::from(self.canvas.client_width()) / f64::from(self.canvas.width().max(1));
+        let nodes: Vec<_> = nodes
+            .iter()
+            .map(|node| JsAccessibilityNode::new(node, scale))
+            .collect();
+        match serde_wasm_bindgen::to_value(&nodes) {
+            Ok(nodes) => self.js_player.update_accessibility_tree(nodes),
+            Err(e) => tracing::error!("Couldn't describe the stage to the page: {:?}", e),
+        }
+    }
+}
+
+/// An `AccessibilityNode` as given to the page, positioned in CSS pixels.
+#[derive(Serialize)]
+struct JsAccessibilityNode {
+    role: &'static str,
+    name: String,
+    description: String,
+    shortcut: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    children: Vec<JsAccessibilityNode>,
+}
+
+impl JsAccessibilityNode {
+    fn new(node: &AccessibilityNode, scale: f64) -> Self {
+        Self {
+            role: match node.role {
+                AccessibilityRole::Text => "text",
+                AccessibilityRole::TextField => "textField",
+                AccessibilityRole::Button => "button",
+                AccessibilityRole::Graphic => "graphic",
+            },
+            name: node.name.clone(),
+            description: node.description.clone(),
+            shortcut: node.shortcut.clone(),
+            x: node.bounds.x_min.to_pixels() * scale,
+            y: node.bounds.y_min.to_pixels() * scale,
+            width: node.bounds.width().to_pixels() * scale,
+            height: node.bounds.height().to_pixels() * scale,
+            children: node
+                .children
+                .iter()
+                .map(|child| Self::new(child, scale))
+                .collect(),
+        }
+    }
+}
+
+/// Reads the system clipboard, if the user has already allowed the page to do so.
+///
+/// This never asks for permission, as the browser would prompt the user at a surprising time.
+pub async fn read_clipboard() -> Option<(String, Option<ClipboardImage>)> {
+    let navigator = web_sys::window()?.navigator();
+    if !JsValue::from_str("clipboard").js_in(&navigator) {
+        return None;
+    }
+    if clipboard_read_permission(&navigator).await? != PermissionState::Granted {
+        return None;
+    }
+
+    let items: Array = JsFuture::from(navigator.clipboard().read())
+        .await
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    let mut text = String::new();
+    let mut image = None;
+    for item in items.iter() {
+        let item: ClipboardItem = item.dyn_into().ok()?;
+        for item_type in item.types().iter().filter_map(|t| t.as_string()) {
+            match item_type.as_str() {
+                "text/plain" if text.is_empty() => {
+                    let blob = clipboard_blob(&item, &item_type).await?;
+                    text = JsFuture::from(blob.text()).await.ok()?.as_string()?;
+                }
+                "image/png" if image.is_none() => {
+                    let blob = clipboard_blob(&item, &item_type).await?;
+                    let buffer = JsFuture::from(blob.array_buffer()).await.ok()?;
+                    image = decode_png(&Uint8Array::new(&buffer).to_vec());
+                }
+                _ => {}
+            }
+        }
+    }
+    Some((text, image))
+}
+
+async fn clipboard_blob(item: &ClipboardItem, item_type: &str) -> Option<Blob> {
+    JsFuture::from(item.get_type(item_type))
+        .await
+        .ok()?
+        .dyn_into()
+        .ok()
+}
+
+/// Whether the page may read the clipboard. `None` if the browser doesn't say.
+async fn clipboard_read_permission(navigator: &Navigator) -> Option<PermissionState> {
+    let descriptor = Object::new();
+    Reflect::set(&descriptor, &"name".into(), &"clipboard-read".into()).ok()?;
+    // Browsers that don't know this permission reject the query.
+    let query = navigator.permissions().ok()?.query(&descriptor).ok()?;
+    let status: PermissionStatus = JsFuture::from(query).await.ok()?.dyn_into().ok()?;
+    Some(status.state())
+}
+
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>, png::EncodingError> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(rgba)?;
+    Ok(png)
+}
+
+fn decode_png(data: &[u8]) -> Option<ClipboardImage> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(
+        png::Transformations::normalize_to_color8() | png::Transformations::ALPHA,
+    );
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    buffer.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        _ => return None,
+    };
+    Some(ClipboardImage {
+        width: info.width,
+        height: info.height,
+        rgba,
+    })
 }
@@ -0,0 +1,252 @@
+//! Iframes for `StageWebView`s created by AIR content.
+//!
+//! Each web view is an absolutely positioned iframe placed next to the canvas, so the
+//! canvas' parent should be positioned. Pages from other origins don't expose their
+//! location or history, so the history seen by the movie is kept here instead.
+
+use crate::{JsCallback, RuffleHandle};
+use ruffle_core::backend::ui::{WebViewEvent, WebViewId, WebViewNavigation, WebViewViewport};
+use ruffle_web_common::JsResult;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Event, HtmlCanvasElement, HtmlIFrameElement};
+
+#[derive(Default)]
+struct WebViewHistory {
+    entries: Vec<String>,
+    index: usize,
+
+    /// Set while the iframe is going to an entry that is already in the history.
+    traversing: bool,
+}
+
+impl WebViewHistory {
+    fn current(&self) -> Option<&str> {
+        self.entries.get(self.index).map(String::as_str)
+    }
+
+    fn push(&mut self, location: String) {
+        if self.current() == Some(location.as_str()) {
+            return;
+        }
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.index + 1);
+            self.index += 1;
+        }
+        self.entries.push(location);
+    }
+}
+
+struct WebView {
+    iframe: HtmlIFrameElement,
+    history: Rc<RefCell<WebViewHistory>>,
+    _load_callback: Option<JsCallback<Event>>,
+}
+
+impl WebView {
+    fn new(ruffle: Option<RuffleHandle>, id: WebViewId) -> Result<Self, JsValue> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or("No document")?;
+        let iframe: HtmlIFrameElement = document.create_element("iframe")?.unchecked_into();
+        iframe.set_class_name("ruffle-web-view");
+        let style = iframe.style();
+        style.set_property("position", "absolute")?;
+        style.set_property("display", "none")?;
+        style.set_property("border", "none")?;
+
+        let history = Rc::new(RefCell::new(WebViewHistory::default()));
+        let load_callback = ruffle.map(|ruffle| {
+            let iframe_ref = iframe.clone();
+            let history = history.clone();
+            JsCallback::register(&iframe, "load", false, move |_: Event| {
+                // Same-origin pages tell us where they went; other pages are
+                // assumed to be where we sent them.
+                let document = iframe_ref.content_document();
+                let location = document
+                    .as_ref()
+                    .and_then(|document| document.location())
+                    .and_then(|location| location.href().ok());
+                let title = document.map(|document| document.title());
+
+                let mut history = history.borrow_mut();
+                let traversing = std::mem::take(&mut history.traversing);
+                if let Some(location) = location.filter(|location| location != "about:blank") {
+                    if !traversing {
+                        history.push(location);
+                    }
+                }
+                let Some(location) = history.current().map(str::to_string) else {
+                    // This is the initial blank page.
+                    return;
+                };
+                let can_go_back = history.index > 0;
+                let can_go_forward = history.index + 1 < history.entries.len();
+                drop(history);
+
+                let _ = ruffle.with_core_mut(|core| {
+                    core.handle_web_view_event(
+                        id,
+                        WebViewEvent::LocationChanged {
+                            location,
+                            can_go_back,
+                            can_go_forward,
+                        },
+                    );
+                    core.handle_web_view_event(id, WebViewEvent::Complete { title });
+                });
+            })
+        });
+
+        Ok(Self {
+            iframe,
+            history,
+            _load_callback: load_callback,
+        })
+    }
+
+    fn set_viewport(
+        &self,
+        canvas: &HtmlCanvasElement,
+        viewport: Option<WebViewViewport>,
+    ) -> Result<(), JsValue> {
+        let style = self.iframe.style();
+        let Some(viewport) = viewport else {
+            return style.set_property("display", "none");
+        };
+
+        // The viewport is in stage pixels, while the canvas may be scaled by the page.
+        let scale = if canvas.width() > 0 {
+            f64::from(canvas.client_width()) / f64::from(canvas.width())
+        } else {
+            1.0
+        };
+        let left = f64::from(canvas.offset_left()) + f64::from(viewport.x) * scale;
+        let top = f64::from(canvas.offset_top()) + f64::from(viewport.y) * scale;
+        style.set_property("left", &format!("{left}px"))?;
+        style.set_property("top", &format!("{top}px"))?;
+        style.set_property("width", &format!("{}px", f64::from(viewport.width) * scale))?;
+        style.set_property(
+            "height",
+            &format!("{}px", f64::from(viewport.height) * scale),
+        )?;
+        style.set_property("display", "block")
+    }
+
+    fn load_url(&self, url: &str) {
+        let mut history = self.history.borrow_mut();
+        history.push(url.to_string());
+        history.traversing = true;
+        drop(history);
+        self.iframe.set_src(url);
+    }
+
+    fn load_string(&self, text: &str, mime_type: &str) {
+        if mime_type == "text/html" {
+            self.iframe.set_srcdoc(text);
+        } else {
+            let text = js_sys::encode_uri_component(text);
+            self.iframe
+                .set_src(&format!("data:{mime_type};charset=utf-8,{text}"));
+        }
+    }
+
+    fn navigate(&self, navigation: WebViewNavigation) {
+        let mut history = self.history.borrow_mut();
+        let index = match navigation {
+            WebViewNavigation::Back if history.index > 0 => history.index - 1,
+            WebViewNavigation::Forward if history.index + 1 < history.entries.len() => {
+                history.index + 1
+            }
+            WebViewNavigation::Reload => history.index,
+            WebViewNavigation::Stop => {
+                if let Some(window) = self.iframe.content_window() {
+                    window.stop().warn_on_error();
+                }
+                return;
+            }
+            _ => return,
+        };
+        let Some(location) = history.entries.get(index).cloned() else {
+            return;
+        };
+        history.index = index;
+        history.traversing = true;
+        drop(history);
+        self.iframe.set_src(&location);
+    }
+}
+
+/// The DOM side of every `StageWebView` created by the movie.
+#[derive(Default)]
+pub struct WebViewHost {
+    /// The instance that page loads are reported to.
+    ruffle: Option<RuffleHandle>,
+
+    views: HashMap<WebViewId, WebView>,
+}
+
+impl WebViewHost {
+    pub fn set_ruffle_handle(&mut self, ruffle: RuffleHandle) {
+        self.ruffle = Some(ruffle);
+    }
+
+    pub fn create(&mut self, canvas: &HtmlCanvasElement, id: WebViewId) {
+        let view = match WebView::new(self.ruffle, id) {
+            Ok(view) => view,
+            Err(e) => {
+                tracing::error!("Unable to create StageWebView iframe: {:?}", e);
+                return;
+            }
+        };
+        if let Some(parent) = canvas.parent_element() {
+            parent.append_child(&view.iframe).warn_on_error();
+        }
+        self.views.insert(id, view);
+    }
+
+    pub fn set_viewport(
+        &mut self,
+        canvas: &HtmlCanvasElement,
+        id: WebViewId,
+        viewport: Option<WebViewViewport>,
+    ) {
+        if let Some(view) = self.views.get(&id) {
+            view.set_viewport(canvas, viewport).warn_on_error();
+        }
+    }
+
+    pub fn load_url(&mut self, id: WebViewId, url: &str) {
+        if let Some(view) = self.views.get(&id) {
+            view.load_url(url);
+        }
+    }
+
+    pub fn load_string(&mut self, id: WebViewId, text: &str, mime_type: &str) {
+        if let Some(view) = self.views.get(&id) {
+            view.load_string(text, mime_type);
+        }
+    }
+
+    pub fn navigate(&mut self, id: WebViewId, navigation: WebViewNavigation) {
+        if let Some(view) = self.views.get(&id) {
+            view.navigate(navigation);
+        }
+    }
+
+    pub fn dispose(&mut self, id: WebViewId) {
+        if let Some(view) = self.views.remove(&id) {
+            view.iframe.remove();
+        }
+    }
+}
+
+impl Drop for WebViewHost {
+    fn drop(&mut self) {
+        for view in self.views.values() {
+            view.iframe.remove();
+        }
+    }
+}
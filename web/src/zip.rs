@@ -1,8 +1,13 @@
 use std::collections::HashMap;
-use std::io::Write;
+use std::io::{Cursor, Read, Write};
+use url::Url;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsValue;
 use zip::write::SimpleFileOptions;
+use zip::ZipArchive;
+
+/// The file that marks a zip as a Ruffle bundle, as described in `frontend-utils/src/bundle/README.md`.
+const BUNDLE_INFORMATION_FILENAME: &str = "ruffle-bundle.toml";
 
 #[wasm_bindgen]
 #[derive(Default)]
@@ -35,3 +40,185 @@ impl ZipWriter {
         Ok(buffer)
     }
 }
+
+/// The files of a zipped game, exposed to the movie as a virtual filesystem under `file:///`.
+///
+/// This is either a Ruffle bundle, whose `content/` directory is exposed and whose
+/// `ruffle-bundle.toml` names the movie to play, or any other zip with a single SWF at its root.
+/// A zip holding nothing but one folder is looked into, as that's what zipping a folder gives.
+pub struct ZipBundle {
+    files: HashMap<String, Vec<u8>>,
+    movie_url: Url,
+}
+
+impl ZipBundle {
+    pub fn open(data: Vec<u8>) -> Result<Self, String> {
+        let mut archive = ZipArchive::new(Cursor::new(data)).map_err(|e| e.to_string())?;
+        let mut files = HashMap::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).map_err(|e| e.to_string())?;
+            if !file.is_file() {
+                continue;
+            }
+            let Some(path) = file.enclosed_name() else {
+                continue;
+            };
+            let Some(path) = path.to_str().map(|path| path.replace('\\', "/")) else {
+                continue;
+            };
+            let mut content = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut content).map_err(|e| e.to_string())?;
+            files.insert(path, content);
+        }
+
+        if let Some(information) = files.get(BUNDLE_INFORMATION_FILENAME) {
+            let movie_url = read_bundle_url(information)?;
+            let files = files
+                .into_iter()
+                .filter_map(|(path, content)| {
+                    Some((path.strip_prefix("content/")?.to_owned(), content))
+                })
+                .collect();
+            return Ok(Self { files, movie_url });
+        }
+
+        let files = strip_common_folder(files);
+        let mut movies = files
+            .keys()
+            .filter(|path| !path.contains('/') && path.to_ascii_lowercase().ends_with(".swf"));
+        let movie = match (movies.next(), movies.next()) {
+            (Some(movie), None) => movie,
+            (None, _) => return Err("The zip doesn't contain a SWF file".to_string()),
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "The zip contains several SWF files, add a {BUNDLE_INFORMATION_FILENAME} to choose one"
+                ))
+            }
+        };
+        let movie_url = Url::parse("file:///")
+            .and_then(|root| root.join(movie))
+            .map_err(|e| e.to_string())?;
+        Ok(Self { files, movie_url })
+    }
+
+    /// The URL of the movie to play.
+    pub fn movie_url(&self) -> &Url {
+        &self.movie_url
+    }
+
+    /// Reads the file at a `file:///` URL, ignoring its query.
+    pub fn read(&self, url: &Url) -> Option<&[u8]> {
+        if url.scheme() != "file" {
+            return None;
+        }
+        let path = url.path().strip_prefix('/').unwrap_or(url.path());
+        let path = percent_decode(path)?;
+        self.files.get(&path).map(Vec::as_slice)
+    }
+}
+
+/// Reads `bundle.url` out of a `ruffle-bundle.toml` file.
+fn read_bundle_url(information: &[u8]) -> Result<Url, String> {
+    let information = std::str::from_utf8(information).map_err(|e| e.to_string())?;
+    let document = information
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| format!("Invalid {BUNDLE_INFORMATION_FILENAME}: {e}"))?;
+    let url = document
+        .get("bundle")
+        .and_then(|bundle| bundle.get("url"))
+        .and_then(|url| url.as_str())
+        .ok_or_else(|| format!("{BUNDLE_INFORMATION_FILENAME} is missing bundle.url"))?;
+    Url::parse(url).map_err(|e| format!("Invalid bundle.url {url}: {e}"))
+}
+
+/// Removes the folder that every file is in, if there's one.
+fn strip_common_folder(files: HashMap<String, Vec<u8>>) -> HashMap<String, Vec<u8>> {
+    let mut folders = files
+        .keys()
+        .map(|path| path.split_once('/').map(|(folder, _)| folder));
+    let Some(Some(folder)) = folders.next() else {
+        return files;
+    };
+    if !folders.all(|other| other == Some(folder)) {
+        return files;
+    }
+
+    let prefix = format!("{folder}/");
+    files
+        .into_iter()
+        .map(|(path, content)| (path[prefix.len()..].to_owned(), content))
+        .collect()
+}
+
+/// Decodes the `%XX` escapes of a URL path.
+fn percent_decode(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(index + 1..index + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new();
+        for (name, content) in files {
+            writer.add_file(name.to_string(), content.as_bytes().to_vec());
+        }
+        writer.save().expect("zip should be written")
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).expect("url should be valid")
+    }
+
+    #[test]
+    fn plain_zip_in_folder() {
+        let bundle = ZipBundle::open(zip(&[
+            ("game/main.swf", "swf"),
+            ("game/data/level 1.xml", "xml"),
+        ]))
+        .expect("bundle should open");
+        assert_eq!(bundle.movie_url(), &url("file:///main.swf"));
+        assert_eq!(
+            bundle.read(&url("file:///data/level%201.xml?v=2")),
+            Some(&b"xml"[..])
+        );
+        assert_eq!(bundle.read(&url("file:///game/main.swf")), None);
+    }
+
+    #[test]
+    fn plain_zip_needs_one_movie() {
+        assert!(ZipBundle::open(zip(&[("readme.txt", "")])).is_err());
+        assert!(ZipBundle::open(zip(&[("a.swf", ""), ("b.swf", "")])).is_err());
+    }
+
+    #[test]
+    fn ruffle_bundle() {
+        let bundle = ZipBundle::open(zip(&[
+            (
+                "ruffle-bundle.toml",
+                "[bundle]\nname = \"Game\"\nurl = \"file:///levels/start.swf\"",
+            ),
+            ("content/levels/start.swf", "swf"),
+            ("content/music.mp3", "mp3"),
+            ("other.swf", "swf"),
+        ]))
+        .expect("bundle should open");
+        assert_eq!(bundle.movie_url(), &url("file:///levels/start.swf"));
+        assert_eq!(bundle.read(&url("file:///music.mp3")), Some(&b"mp3"[..]));
+        assert_eq!(bundle.read(&url("file:///other.swf")), None);
+    }
+}